@@ -0,0 +1,265 @@
+//! Dead-wallet detection — BRN accrual stops for wallets that go quiet.
+//!
+//! A wallet with no block and no liveness proof for
+//! `inactivity_suspension_secs` stops accruing BRN, so the birthright of an
+//! abandoned key doesn't pile up forever. It is warned when it enters the
+//! grace period before that point. Any later block — typically a Liveness
+//! block — resumes accrual and keeps what was accrued before the suspension;
+//! re-verification starts a fresh wallet state.
+//!
+//! The suspension always takes effect at `last seen + inactivity_suspension_secs`,
+//! however late it is noticed, so every node computes the same balance.
+
+use burst_types::{Timestamp, WalletAddress};
+
+use crate::engine::BrnEngine;
+use crate::state::{BrnWalletState, InactivityStatus};
+
+/// A change in a wallet's inactivity standing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InactivityEvent {
+    /// The wallet entered its grace period and will stop accruing at
+    /// `suspends_at` unless it shows activity.
+    GracePeriodStarted {
+        wallet: WalletAddress,
+        suspends_at: Timestamp,
+    },
+    /// Accrual stopped at `at`.
+    AccrualSuspended {
+        wallet: WalletAddress,
+        at: Timestamp,
+    },
+    /// Activity resumed accrual at `at`.
+    AccrualResumed {
+        wallet: WalletAddress,
+        at: Timestamp,
+    },
+}
+
+impl InactivityEvent {
+    /// The wallet the event is about.
+    pub fn wallet(&self) -> &WalletAddress {
+        match self {
+            Self::GracePeriodStarted { wallet, .. }
+            | Self::AccrualSuspended { wallet, .. }
+            | Self::AccrualResumed { wallet, .. } => wallet,
+        }
+    }
+}
+
+/// Advance one wallet's inactivity standing to `now`.
+fn check_wallet(
+    wallet: &WalletAddress,
+    state: &mut BrnWalletState,
+    now: Timestamp,
+    suspension_secs: u64,
+    grace_secs: u64,
+) -> Option<InactivityEvent> {
+    // De-verified wallets and already suspended ones have nothing to lose.
+    if suspension_secs == 0 || !state.accrual_active {
+        return None;
+    }
+    let suspends_at = Timestamp::new(state.last_seen().as_secs().saturating_add(suspension_secs));
+    if now.as_secs() >= suspends_at.as_secs() {
+        state.stop_accrual(suspends_at);
+        state.inactivity = InactivityStatus::Suspended;
+        return Some(InactivityEvent::AccrualSuspended {
+            wallet: wallet.clone(),
+            at: suspends_at,
+        });
+    }
+    let grace_starts = suspends_at.as_secs().saturating_sub(grace_secs);
+    if now.as_secs() >= grace_starts && state.inactivity == InactivityStatus::Active {
+        state.inactivity = InactivityStatus::GracePeriod;
+        return Some(InactivityEvent::GracePeriodStarted {
+            wallet: wallet.clone(),
+            suspends_at,
+        });
+    }
+    None
+}
+
+impl BrnEngine {
+    /// Warn wallets entering their grace period and suspend accrual for
+    /// those past it. Events are ordered by wallet address.
+    pub fn check_inactivity(
+        &mut self,
+        now: Timestamp,
+        suspension_secs: u64,
+        grace_secs: u64,
+    ) -> Vec<InactivityEvent> {
+        let mut events: Vec<InactivityEvent> = self
+            .wallets
+            .iter_mut()
+            .filter_map(|(wallet, state)| {
+                check_wallet(wallet, state, now, suspension_secs, grace_secs)
+            })
+            .collect();
+        events.sort_by(|a, b| a.wallet().as_str().cmp(b.wallet().as_str()));
+        events
+    }
+
+    /// Record a block or liveness proof from `wallet` at `at`.
+    ///
+    /// A wallet that went quiet past its deadline is suspended as of that
+    /// deadline first, then resumed from `at` with its earlier accrual
+    /// banked. Activity is never dated before the wallet was last seen or
+    /// suspended, so a backdated block cannot rewind its clock. Returns the
+    /// resumption, if there was one.
+    pub fn record_activity(
+        &mut self,
+        wallet: &WalletAddress,
+        at: Timestamp,
+        suspension_secs: u64,
+    ) -> Option<InactivityEvent> {
+        let rates = &self.rate_history;
+        let state = self.wallets.get_mut(wallet)?;
+        let at = at.max(state.last_seen());
+        check_wallet(wallet, state, at, suspension_secs, 0);
+
+        if state.inactivity != InactivityStatus::Suspended {
+            state.last_active = Some(at);
+            state.inactivity = InactivityStatus::Active;
+            return None;
+        }
+        let stopped_at = state.accrual_stopped_at.unwrap_or(at);
+        let at = at.max(stopped_at);
+        state.banked_accrual = state
+            .banked_accrual
+            .saturating_add(rates.total_accrued(state.verified_at, stopped_at));
+        state.resume_accrual(at);
+        Some(InactivityEvent::AccrualResumed {
+            wallet: wallet.clone(),
+            at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUSPEND: u64 = 1_000;
+    const GRACE: u64 = 100;
+
+    fn wallet(n: u8) -> WalletAddress {
+        WalletAddress::new(format!("brst_{:0>60}", n))
+    }
+
+    fn engine_with(wallets: &[WalletAddress]) -> BrnEngine {
+        let mut engine = BrnEngine::with_rate(10, Timestamp::new(0));
+        for w in wallets {
+            engine.track_wallet(w.clone(), BrnWalletState::new(Timestamp::new(0)));
+        }
+        engine
+    }
+
+    #[test]
+    fn warns_then_suspends_at_the_deadline() {
+        let w = wallet(1);
+        let mut engine = engine_with(std::slice::from_ref(&w));
+
+        assert!(engine
+            .check_inactivity(Timestamp::new(899), SUSPEND, GRACE)
+            .is_empty());
+        assert_eq!(
+            engine.check_inactivity(Timestamp::new(900), SUSPEND, GRACE),
+            vec![InactivityEvent::GracePeriodStarted {
+                wallet: w.clone(),
+                suspends_at: Timestamp::new(1_000),
+            }]
+        );
+        // Warned only once.
+        assert!(engine
+            .check_inactivity(Timestamp::new(950), SUSPEND, GRACE)
+            .is_empty());
+
+        // Noticed late, but the suspension still dates from the deadline.
+        assert_eq!(
+            engine.check_inactivity(Timestamp::new(5_000), SUSPEND, GRACE),
+            vec![InactivityEvent::AccrualSuspended {
+                wallet: w.clone(),
+                at: Timestamp::new(1_000),
+            }]
+        );
+        let state = engine.get_wallet(&w).unwrap();
+        assert_eq!(engine.compute_balance(state, Timestamp::new(9_000)), 10_000);
+    }
+
+    #[test]
+    fn activity_resets_the_clock() {
+        let w = wallet(1);
+        let mut engine = engine_with(std::slice::from_ref(&w));
+        engine.check_inactivity(Timestamp::new(950), SUSPEND, GRACE);
+
+        assert_eq!(
+            engine.record_activity(&w, Timestamp::new(960), SUSPEND),
+            None
+        );
+        assert_eq!(
+            engine.get_wallet(&w).unwrap().inactivity,
+            InactivityStatus::Active
+        );
+        assert!(engine
+            .check_inactivity(Timestamp::new(1_500), SUSPEND, GRACE)
+            .is_empty());
+    }
+
+    #[test]
+    fn liveness_resumes_and_keeps_earlier_accrual() {
+        let w = wallet(1);
+        let mut engine = engine_with(std::slice::from_ref(&w));
+
+        // The checker never ran; the late block still suspends from t=1000.
+        assert_eq!(
+            engine.record_activity(&w, Timestamp::new(3_000), SUSPEND),
+            Some(InactivityEvent::AccrualResumed {
+                wallet: w.clone(),
+                at: Timestamp::new(3_000),
+            })
+        );
+        let state = engine.get_wallet(&w).unwrap();
+        assert!(state.accrual_active);
+        // 1000s banked before the suspension, 500s since resuming.
+        assert_eq!(engine.compute_balance(state, Timestamp::new(3_500)), 15_000);
+    }
+
+    #[test]
+    fn backdated_activity_does_not_rewind_the_clock() {
+        let w = wallet(1);
+        let mut engine = engine_with(std::slice::from_ref(&w));
+        engine.record_activity(&w, Timestamp::new(800), SUSPEND);
+        engine.record_activity(&w, Timestamp::new(100), SUSPEND);
+        assert_eq!(
+            engine.get_wallet(&w).unwrap().last_seen(),
+            Timestamp::new(800)
+        );
+
+        // Once suspended at 1800, a block stamped earlier resumes from the
+        // suspension, without accruing the suspended stretch twice.
+        engine.check_inactivity(Timestamp::new(5_000), SUSPEND, GRACE);
+        assert_eq!(
+            engine.record_activity(&w, Timestamp::new(900), SUSPEND),
+            Some(InactivityEvent::AccrualResumed {
+                wallet: w.clone(),
+                at: Timestamp::new(1_800),
+            })
+        );
+        let state = engine.get_wallet(&w).unwrap();
+        assert_eq!(engine.compute_balance(state, Timestamp::new(1_800)), 18_000);
+    }
+
+    #[test]
+    fn disabled_rule_and_deverified_wallets_are_ignored() {
+        let (a, b) = (wallet(1), wallet(2));
+        let mut engine = engine_with(&[a.clone(), b.clone()]);
+        assert!(engine
+            .check_inactivity(Timestamp::new(10_000), 0, GRACE)
+            .is_empty());
+
+        engine.deactivate_wallet(&b, Timestamp::new(10)).unwrap();
+        let events = engine.check_inactivity(Timestamp::new(10_000), SUSPEND, GRACE);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].wallet(), &a);
+    }
+}
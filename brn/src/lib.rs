@@ -8,13 +8,16 @@
 //! - Recording burn operations (BRN → TRST minting)
 //! - Staking/unstaking for verification and challenges
 //! - Rate change splitting (preserving pre-change accrual)
+//! - Suspending accrual for inactive wallets
 
 pub mod engine;
 pub mod error;
+pub mod inactivity;
 pub mod stake;
 pub mod state;
 
 pub use engine::BrnEngine;
 pub use error::BrnError;
+pub use inactivity::InactivityEvent;
 pub use stake::{Stake, StakeId, StakeKind};
-pub use state::{BrnWalletState, RateHistory, RateSegment};
+pub use state::{BrnWalletState, InactivityStatus, RateHistory, RateSegment};
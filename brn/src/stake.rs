@@ -1,4 +1,4 @@
-//! BRN staking for verification voting and challenges.
+//! BRN staking for verification voting, challenges and the verifier pool.
 
 use burst_types::{Timestamp, WalletAddress};
 use serde::{Deserialize, Serialize};
@@ -13,6 +13,8 @@ pub enum StakeKind {
     Verification { target_wallet: WalletAddress },
     /// Staked as a challenger contesting another wallet.
     Challenge { target_wallet: WalletAddress },
+    /// Locked for as long as the staker stays in the verifier pool.
+    VerifierPool,
 }
 
 /// An active BRN stake.
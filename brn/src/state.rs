@@ -132,12 +132,38 @@ pub struct BrnWalletState {
     /// Used to cap accrual computation.
     #[serde(default)]
     pub accrual_stopped_at: Option<Timestamp>,
+
+    /// Last block or liveness proof from this wallet; `None` means nothing
+    /// since `verified_at`.
+    #[serde(default)]
+    pub last_active: Option<Timestamp>,
+
+    /// Where the wallet stands under the inactivity rule.
+    #[serde(default)]
+    pub inactivity: InactivityStatus,
+
+    /// BRN accrued before an inactivity suspension, kept when a liveness
+    /// proof resumes accrual.
+    #[serde(default)]
+    pub banked_accrual: u128,
 }
 
 fn default_true() -> bool {
     true
 }
 
+/// Inactivity standing of a wallet — see `BrnEngine::check_inactivity`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InactivityStatus {
+    /// Recently active.
+    #[default]
+    Active,
+    /// Within the grace period before suspension; the wallet was warned.
+    GracePeriod,
+    /// Accrual suspended until a liveness proof or re-verification.
+    Suspended,
+}
+
 impl BrnWalletState {
     /// Create a new BRN state for a freshly verified wallet.
     /// No longer takes `initial_rate` — rate is global.
@@ -148,6 +174,9 @@ impl BrnWalletState {
             total_staked: 0,
             accrual_active: true,
             accrual_stopped_at: None,
+            last_active: None,
+            inactivity: InactivityStatus::Active,
+            banked_accrual: 0,
         }
     }
 
@@ -164,7 +193,9 @@ impl BrnWalletState {
         } else {
             self.accrual_stopped_at.unwrap_or(now)
         };
-        let accrued = rates.total_accrued_checked(self.verified_at, effective_now)?;
+        let accrued = rates
+            .total_accrued_checked(self.verified_at, effective_now)?
+            .checked_add(self.banked_accrual)?;
         let after_burned = accrued.checked_sub(self.total_burned)?;
         after_burned.checked_sub(self.total_staked)
     }
@@ -185,6 +216,13 @@ impl BrnWalletState {
         self.accrual_active = true;
         self.accrual_stopped_at = None;
         self.verified_at = at;
+        self.last_active = Some(at);
+        self.inactivity = InactivityStatus::Active;
+    }
+
+    /// When the wallet last showed signs of life.
+    pub fn last_seen(&self) -> Timestamp {
+        self.last_active.unwrap_or(self.verified_at)
     }
 }
 
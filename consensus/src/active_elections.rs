@@ -6,9 +6,12 @@
 
 use crate::election::{Election, ElectionState, ElectionStatus};
 use crate::error::ConsensusError;
-use crate::vote_info::VoteResult;
-use burst_types::{BlockHash, Timestamp, WalletAddress};
+use crate::vote_info::{VoteInfo, VoteResult};
+use burst_types::memory::entry_bytes;
+use burst_types::{BlockHash, MemoryAccounting, Timestamp, WalletAddress};
+use burst_utils::{DetailType, Direction, SampleType, StatType, Stats};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Container for all active consensus elections.
 ///
@@ -24,6 +27,8 @@ pub struct ActiveElections {
     /// Should be `max(current_online, trended_ema, min_floor)` to prevent
     /// quorum collapse on temporary online weight dips.
     effective_weight: u128,
+    /// Election and vote counters.
+    stats: Arc<Stats>,
 }
 
 impl ActiveElections {
@@ -32,9 +37,15 @@ impl ActiveElections {
             elections: HashMap::new(),
             max_elections,
             effective_weight: online_weight,
+            stats: Arc::default(),
         }
     }
 
+    /// Report into a shared stats registry.
+    pub fn set_stats(&mut self, stats: Arc<Stats>) {
+        self.stats = stats;
+    }
+
     /// Start a new election for the given root block.
     ///
     /// Returns an error if the container is at capacity or if an election
@@ -45,6 +56,7 @@ impl ActiveElections {
         now: Timestamp,
     ) -> Result<(), ConsensusError> {
         if self.elections.len() >= self.max_elections {
+            self.stats.inc(StatType::Election, DetailType::Overflow);
             return Err(ConsensusError::ElectionCapacityReached(self.max_elections));
         }
         if self.elections.contains_key(&root) {
@@ -53,6 +65,7 @@ impl ActiveElections {
 
         let election = Election::new(root, self.effective_weight, now);
         self.elections.insert(root, election);
+        self.stats.inc(StatType::Election, DetailType::Started);
         Ok(())
     }
 
@@ -80,9 +93,18 @@ impl ActiveElections {
         let vote_result = election.vote(voter, block, weight, is_final, now);
 
         match vote_result {
-            VoteResult::Error(msg) => Err(ConsensusError::FinalVoteAlreadyCast(msg)),
+            VoteResult::Error(msg) => {
+                self.stats.inc(StatType::Vote, DetailType::Invalid);
+                Err(ConsensusError::FinalVoteAlreadyCast(msg))
+            }
             _ => {
+                self.stats.inc(StatType::Vote, DetailType::Processed);
                 let status = election.try_confirm(now);
+                if let Some(status) = &status {
+                    self.stats.inc(StatType::Election, DetailType::Confirmed);
+                    self.stats
+                        .sample(SampleType::ElectionDuration, status.election_duration_ms);
+                }
                 Ok(status)
             }
         }
@@ -103,6 +125,14 @@ impl ActiveElections {
         for root in &expired {
             self.elections.remove(root);
         }
+        if !expired.is_empty() {
+            self.stats.add_dir(
+                StatType::Election,
+                DetailType::Expired,
+                Direction::In,
+                expired.len() as u64,
+            );
+        }
 
         expired
     }
@@ -172,6 +202,27 @@ impl ActiveElections {
         self.effective_weight = weight;
     }
 
+    /// The effective online weight new elections are created with.
+    pub fn effective_weight(&self) -> u128 {
+        self.effective_weight
+    }
+
+    /// Iterate over all elections, keyed by root.
+    pub fn elections(&self) -> impl Iterator<Item = (&BlockHash, &Election)> {
+        self.elections.iter()
+    }
+
+    /// Maximum number of concurrent elections.
+    pub fn max_elections(&self) -> usize {
+        self.max_elections
+    }
+
+    /// Change the election limit. Running elections are never evicted; if
+    /// the new limit is lower, no new elections start until enough finish.
+    pub fn set_max_elections(&mut self, max_elections: usize) {
+        self.max_elections = max_elections;
+    }
+
     /// Whether the container has reached its capacity limit.
     pub fn is_at_capacity(&self) -> bool {
         self.elections.len() >= self.max_elections
@@ -216,6 +267,23 @@ impl ActiveElections {
     }
 }
 
+impl MemoryAccounting for ActiveElections {
+    fn element_count(&self) -> usize {
+        self.elections.len()
+    }
+
+    fn approximate_bytes(&self) -> usize {
+        self.elections
+            .values()
+            .map(|election| {
+                entry_bytes::<(BlockHash, Election)>(1)
+                    + entry_bytes::<(WalletAddress, VoteInfo)>(election.last_votes.len())
+                    + entry_bytes::<(BlockHash, u128)>(election.tally.len())
+            })
+            .sum()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
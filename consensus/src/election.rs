@@ -297,7 +297,7 @@ mod tests {
 
         assert_eq!(result, VoteResult::Updated);
         // Block 2 should have been removed (tally dropped to 0)
-        assert!(e.tally.get(&make_hash(2)).is_none());
+        assert!(!e.tally.contains_key(&make_hash(2)));
         assert_eq!(e.tally.get(&make_hash(3)), Some(&300));
         // Sequence should have incremented
         assert_eq!(e.last_votes.get(&make_voter("alice")).unwrap().sequence, 2);
@@ -432,7 +432,7 @@ mod tests {
         assert_eq!(result, VoteResult::Updated);
 
         assert_eq!(e.tally.get(&make_hash(2)), Some(&700));
-        assert!(e.tally.get(&make_hash(3)).is_none());
+        assert!(!e.tally.contains_key(&make_hash(3)));
     }
 
     #[test]
@@ -0,0 +1,176 @@
+//! Adaptive sizing of the active elections container.
+//!
+//! A fixed election limit is either too small for a quiet, fast node or too
+//! large for a loaded one: every active election costs vote routing and
+//! solicitation work, and once the node falls behind, confirmations slow
+//! down for everyone. The [`ElectionLimitTuner`] periodically looks at how
+//! long recent confirmations took, how many votes are waiting to be
+//! processed and how late the tuning tick itself fired (a cheap proxy for
+//! CPU saturation), then shrinks or grows the limit within configured bounds.
+
+use serde::{Deserialize, Serialize};
+
+/// Bounds and targets for the election limit tuner.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ElectionLimitConfig {
+    /// Whether to tune the limit. When disabled the limit stays at `ceiling`.
+    pub auto_tune: bool,
+    /// The limit never drops below this.
+    pub floor: usize,
+    /// The limit never exceeds this; also the starting limit.
+    pub ceiling: usize,
+    /// Average confirmation time above which the limit shrinks. The limit
+    /// grows while confirmations take less than half of this.
+    pub target_confirmation_ms: u64,
+    /// Votes awaiting processing above which the limit shrinks.
+    pub max_vote_backlog: usize,
+    /// Scheduling delay of the tuning tick above which the node is
+    /// considered CPU-bound and the limit shrinks.
+    pub max_scheduling_lag_ms: u64,
+}
+
+impl Default for ElectionLimitConfig {
+    fn default() -> Self {
+        Self {
+            auto_tune: true,
+            floor: 500,
+            ceiling: 5_000,
+            target_confirmation_ms: 2_000,
+            max_vote_backlog: 256,
+            max_scheduling_lag_ms: 100,
+        }
+    }
+}
+
+/// Grows and shrinks the active election limit from observed load.
+pub struct ElectionLimitTuner {
+    config: ElectionLimitConfig,
+    limit: usize,
+    /// Confirmation durations observed since the last adjustment.
+    durations_ms: Vec<u64>,
+}
+
+impl ElectionLimitTuner {
+    pub fn new(config: ElectionLimitConfig) -> Self {
+        let floor = config.floor.min(config.ceiling);
+        let config = ElectionLimitConfig { floor, ..config };
+        Self {
+            limit: config.ceiling,
+            config,
+            durations_ms: Vec::new(),
+        }
+    }
+
+    /// The current election limit.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    pub fn config(&self) -> &ElectionLimitConfig {
+        &self.config
+    }
+
+    /// Record how long an election took to confirm.
+    pub fn observe_confirmation(&mut self, duration_ms: u64) {
+        self.durations_ms.push(duration_ms);
+    }
+
+    /// Average confirmation time since the last adjustment.
+    fn average_duration_ms(&self) -> Option<u64> {
+        if self.durations_ms.is_empty() {
+            return None;
+        }
+        let sum: u128 = self.durations_ms.iter().map(|&d| d as u128).sum();
+        Some((sum / self.durations_ms.len() as u128) as u64)
+    }
+
+    /// Recompute the limit from the confirmations observed since the last
+    /// call, the current vote backlog and the tick's scheduling lag.
+    /// Returns the new limit.
+    ///
+    /// Any sign of overload shrinks the limit by a quarter. It grows by a
+    /// quarter only when confirmations are fast and nothing is backed up;
+    /// without recent confirmations the limit is left alone.
+    pub fn adjust(&mut self, vote_backlog: usize, scheduling_lag_ms: u64) -> usize {
+        let average = self.average_duration_ms();
+        self.durations_ms.clear();
+        if !self.config.auto_tune {
+            return self.limit;
+        }
+
+        let overloaded = vote_backlog > self.config.max_vote_backlog
+            || scheduling_lag_ms > self.config.max_scheduling_lag_ms
+            || average.is_some_and(|avg| avg > self.config.target_confirmation_ms);
+        let healthy = vote_backlog <= self.config.max_vote_backlog / 2
+            && scheduling_lag_ms <= self.config.max_scheduling_lag_ms / 2
+            && average.is_some_and(|avg| avg < self.config.target_confirmation_ms / 2);
+
+        let next = if overloaded {
+            self.limit - self.limit / 4
+        } else if healthy {
+            self.limit + (self.limit / 4).max(1)
+        } else {
+            self.limit
+        };
+        self.limit = next.clamp(self.config.floor, self.config.ceiling);
+        self.limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tuner() -> ElectionLimitTuner {
+        ElectionLimitTuner::new(ElectionLimitConfig {
+            floor: 100,
+            ceiling: 1_000,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn shrinks_on_slow_confirmations_backlog_or_lag_down_to_floor() {
+        let mut tuner = tuner();
+        assert_eq!(tuner.limit(), 1_000);
+
+        tuner.observe_confirmation(5_000);
+        tuner.observe_confirmation(3_000);
+        assert_eq!(tuner.adjust(0, 0), 750);
+        assert_eq!(tuner.adjust(1_000, 0), 563);
+        assert_eq!(tuner.adjust(0, 500), 423);
+        for _ in 0..20 {
+            tuner.adjust(1_000, 0);
+        }
+        assert_eq!(tuner.limit(), 100);
+    }
+
+    #[test]
+    fn grows_only_while_fast_and_idle() {
+        let mut tuner = tuner();
+        tuner.adjust(1_000, 0);
+        assert_eq!(tuner.limit(), 750);
+
+        // No confirmations: hold.
+        assert_eq!(tuner.adjust(0, 0), 750);
+        // Fast but with a moderate backlog: hold.
+        tuner.observe_confirmation(200);
+        assert_eq!(tuner.adjust(200, 0), 750);
+        // Fast and idle: grow, capped at the ceiling.
+        tuner.observe_confirmation(200);
+        assert_eq!(tuner.adjust(0, 0), 937);
+        tuner.observe_confirmation(200);
+        assert_eq!(tuner.adjust(0, 0), 1_000);
+    }
+
+    #[test]
+    fn disabled_keeps_the_ceiling() {
+        let mut tuner = ElectionLimitTuner::new(ElectionLimitConfig {
+            auto_tune: false,
+            ..Default::default()
+        });
+        tuner.observe_confirmation(60_000);
+        assert_eq!(tuner.adjust(10_000, 10_000), 5_000);
+    }
+}
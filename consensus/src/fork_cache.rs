@@ -2,7 +2,8 @@
 //! When a fork is detected, both competing blocks are cached so the
 //! election can consider all candidates.
 
-use burst_types::BlockHash;
+use burst_types::memory::entry_bytes;
+use burst_types::{BlockHash, MemoryAccounting};
 use std::collections::{HashMap, VecDeque};
 
 /// Maximum entries in the fork cache.
@@ -82,6 +83,17 @@ impl ForkCache {
     }
 }
 
+impl MemoryAccounting for ForkCache {
+    fn element_count(&self) -> usize {
+        self.total_count
+    }
+
+    fn approximate_bytes(&self) -> usize {
+        entry_bytes::<(BlockHash, Vec<BlockHash>)>(self.entries.len())
+            + entry_bytes::<BlockHash>(self.total_count + self.insertion_order.len())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
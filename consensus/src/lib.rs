@@ -10,6 +10,7 @@
 //!
 //! - [`election`] — Election state machine (Passive → Active → Confirmed/Expired).
 //! - [`active_elections`] — Container managing all ongoing elections.
+//! - [`election_tuner`] — Load-based tuning of the active election limit.
 //! - [`vote_info`] — Per-voter vote data with final/non-final distinction.
 //! - [`vote_cache`] — Pre-election vote storage for out-of-order vote arrival.
 //! - [`voting`] — Representative voting with per-voter tracking and tallying.
@@ -21,6 +22,7 @@ pub mod active_elections;
 pub mod backlog_scanner;
 pub mod conflict;
 pub mod election;
+pub mod election_tuner;
 pub mod equivocation;
 pub mod error;
 pub mod fork_cache;
@@ -43,17 +45,20 @@ pub use active_elections::ActiveElections;
 pub use backlog_scanner::BacklogScanner;
 pub use conflict::ConflictDetector;
 pub use election::{Election, ElectionState, ElectionStatus};
+pub use election_tuner::{ElectionLimitConfig, ElectionLimitTuner};
 pub use equivocation::{EquivocationDetector, EquivocationProof};
 pub use error::ConsensusError;
 pub use fork_cache::ForkCache;
 pub use online_weight::OnlineWeightSampler;
-pub use rep_crawler::{DiscoveredRep, RepCrawler};
-pub use rep_weights::RepWeightCache;
+pub use rep_crawler::{DiscoveredRep, RepCrawler, RepPerformance};
+pub use rep_weights::{RepWeightCache, WeightDrift};
 pub use representative::Representative;
 pub use request_aggregator::RequestAggregator;
-pub use scheduler::{ElectionBehavior, HintedScheduler, PriorityScheduler};
+pub use scheduler::{
+    default_bucket_thresholds, ElectionBehavior, HintedScheduler, PriorityScheduler,
+};
 pub use vote_by_hash::VoteByHash;
-pub use vote_cache::VoteCache;
+pub use vote_cache::{VoteCache, DEFAULT_VOTE_CACHE_CAPACITY};
 pub use vote_generator::{GeneratedVote, VoteGenerator};
 pub use vote_info::{VoteInfo, VoteResult};
 pub use vote_rebroadcast::VoteRebroadcaster;
@@ -4,12 +4,21 @@
 //! 1. Periodically send `confirm_req` for a random confirmed block to connected peers
 //! 2. When a vote response arrives, learn the peer's representative account
 //! 3. Track which peers are representatives and their weight
+//! 4. Track each representative's responsiveness: round-trip latency of
+//!    crawl queries, the share of queries it answered, and its last vote
 //!
 //! This is essential for the consensus layer to know how much voting weight
 //! is reachable and to build the quorum picture.
+//!
+//! All timestamps are Unix milliseconds.
 
 use burst_types::{BlockHash, WalletAddress};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+
+/// Latency samples kept per representative.
+const MAX_LATENCY_SAMPLES: usize = 128;
+/// Query outcomes (answered / timed out) kept per representative.
+const MAX_PROBE_OUTCOMES: usize = 100;
 
 /// Discovers representatives by probing peers with confirm_req messages.
 ///
@@ -22,10 +31,12 @@ pub struct RepCrawler {
     discovered_reps: HashMap<String, DiscoveredRep>,
     /// Pending queries: query_hash -> (peer_id, sent_at)
     pending_queries: HashMap<BlockHash, Vec<(String, u64)>>,
-    /// Query timeout in seconds
-    query_timeout_secs: u64,
-    /// Interval between crawl rounds in seconds
-    crawl_interval_secs: u64,
+    /// Representative -> responsiveness statistics
+    performance: HashMap<WalletAddress, RepPerformance>,
+    /// Query timeout in milliseconds
+    query_timeout_ms: u64,
+    /// Interval between crawl rounds in milliseconds
+    crawl_interval_ms: u64,
     /// Last crawl timestamp
     last_crawl: u64,
     /// Whether we've reached sufficient quorum coverage
@@ -37,22 +48,76 @@ pub struct RepCrawler {
 pub struct DiscoveredRep {
     pub peer_id: String,
     pub representative: WalletAddress,
-    pub weight: u64,
+    pub weight: u128,
     pub last_seen: u64,
 }
 
+/// How responsive a representative has been to crawl queries and votes.
+#[derive(Clone, Debug, Default)]
+pub struct RepPerformance {
+    /// Recent crawl query round-trip times, oldest first.
+    latencies_ms: VecDeque<u64>,
+    /// Recent crawl query outcomes: `true` if answered before the timeout.
+    outcomes: VecDeque<bool>,
+    /// When the representative's last vote of any kind arrived.
+    pub last_vote: Option<u64>,
+}
+
+impl RepPerformance {
+    /// Nearest-rank percentile of recent query latencies, `p` in `0..=100`.
+    pub fn latency_percentile(&self, p: u8) -> Option<u64> {
+        if self.latencies_ms.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<u64> = self.latencies_ms.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (p.min(100) as usize * sorted.len()).div_ceil(100);
+        Some(sorted[rank.saturating_sub(1)])
+    }
+
+    /// Share of recent crawl queries the representative answered, in
+    /// `0.0..=1.0`. `None` until it has been queried.
+    pub fn uptime_score(&self) -> Option<f64> {
+        if self.outcomes.is_empty() {
+            return None;
+        }
+        let answered = self.outcomes.iter().filter(|&&ok| ok).count();
+        Some(answered as f64 / self.outcomes.len() as f64)
+    }
+
+    /// Number of crawl queries the statistics cover.
+    pub fn probe_count(&self) -> usize {
+        self.outcomes.len()
+    }
+
+    fn record_outcome(&mut self, answered: bool) {
+        if self.outcomes.len() >= MAX_PROBE_OUTCOMES {
+            self.outcomes.pop_front();
+        }
+        self.outcomes.push_back(answered);
+    }
+
+    fn record_latency(&mut self, latency_ms: u64) {
+        if self.latencies_ms.len() >= MAX_LATENCY_SAMPLES {
+            self.latencies_ms.pop_front();
+        }
+        self.latencies_ms.push_back(latency_ms);
+    }
+}
+
 impl RepCrawler {
     /// Create a new rep crawler.
     ///
     /// # Arguments
-    /// - `query_timeout_secs` — how long to wait for a vote response before considering a query stale
-    /// - `crawl_interval_secs` — minimum interval between crawl rounds
-    pub fn new(query_timeout_secs: u64, crawl_interval_secs: u64) -> Self {
+    /// - `query_timeout_ms` — how long to wait for a vote response before considering a query stale
+    /// - `crawl_interval_ms` — minimum interval between crawl rounds
+    pub fn new(query_timeout_ms: u64, crawl_interval_ms: u64) -> Self {
         Self {
             discovered_reps: HashMap::new(),
             pending_queries: HashMap::new(),
-            query_timeout_secs,
-            crawl_interval_secs,
+            performance: HashMap::new(),
+            query_timeout_ms,
+            crawl_interval_ms,
             last_crawl: 0,
             sufficient_weight: false,
         }
@@ -60,7 +125,7 @@ impl RepCrawler {
 
     /// Check if it's time for another crawl round.
     pub fn should_crawl(&self, now: u64) -> bool {
-        now - self.last_crawl >= self.crawl_interval_secs
+        now.saturating_sub(self.last_crawl) >= self.crawl_interval_ms
     }
 
     /// Start a crawl: register a confirmed block hash and the peers to query.
@@ -83,11 +148,14 @@ impl RepCrawler {
 
     /// Process a vote response from a peer during crawling.
     /// Returns the discovered representative info if successfully recorded.
+    ///
+    /// If the peer has an outstanding crawl query, the oldest one is
+    /// resolved and its round-trip time recorded as a latency sample.
     pub fn process_response(
         &mut self,
         peer_id: &str,
         voter: &WalletAddress,
-        weight: u64,
+        weight: u128,
         now: u64,
     ) -> Option<DiscoveredRep> {
         let rep = DiscoveredRep {
@@ -98,15 +166,77 @@ impl RepCrawler {
         };
         self.discovered_reps
             .insert(peer_id.to_string(), rep.clone());
+
+        let sent_at = self.take_pending_query(peer_id);
+        let perf = self.performance.entry(voter.clone()).or_default();
+        perf.last_vote = Some(now);
+        if let Some(sent_at) = sent_at {
+            perf.record_outcome(true);
+            perf.record_latency(now.saturating_sub(sent_at));
+        }
         Some(rep)
     }
 
-    /// Remove expired pending queries (those older than `query_timeout_secs`).
+    /// Note a vote from `voter` outside of crawling, for last-vote tracking.
+    pub fn record_vote(&mut self, voter: &WalletAddress, now: u64) {
+        self.performance.entry(voter.clone()).or_default().last_vote = Some(now);
+    }
+
+    /// Remove expired pending queries (those older than `query_timeout_ms`).
+    ///
+    /// An expired query to a peer with a known representative counts as a
+    /// missed response against that representative's uptime.
     pub fn cleanup_expired(&mut self, now: u64) {
+        let mut missed = Vec::new();
         self.pending_queries.retain(|_, peers| {
-            peers.retain(|(_, sent_at)| now - *sent_at <= self.query_timeout_secs);
+            peers.retain(|(peer_id, sent_at)| {
+                let fresh = now.saturating_sub(*sent_at) <= self.query_timeout_ms;
+                if !fresh {
+                    missed.push(peer_id.clone());
+                }
+                fresh
+            });
             !peers.is_empty()
         });
+        for peer_id in missed {
+            if let Some(rep) = self.discovered_reps.get(&peer_id) {
+                self.performance
+                    .entry(rep.representative.clone())
+                    .or_default()
+                    .record_outcome(false);
+            }
+        }
+    }
+
+    /// Responsiveness statistics for a representative.
+    pub fn performance(&self, representative: &WalletAddress) -> Option<&RepPerformance> {
+        self.performance.get(representative)
+    }
+
+    /// Responsiveness statistics for every representative seen so far.
+    pub fn performances(&self) -> impl Iterator<Item = (&WalletAddress, &RepPerformance)> {
+        self.performance.iter()
+    }
+
+    /// Remove and return the send time of the peer's oldest pending query.
+    fn take_pending_query(&mut self, peer_id: &str) -> Option<u64> {
+        let (hash, index, sent_at) = self
+            .pending_queries
+            .iter()
+            .flat_map(|(hash, peers)| {
+                peers
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (peer, _))| peer == peer_id)
+                    .map(move |(i, (_, sent_at))| (*hash, i, *sent_at))
+            })
+            .min_by_key(|(_, _, sent_at)| *sent_at)?;
+        let peers = self.pending_queries.get_mut(&hash)?;
+        peers.remove(index);
+        if peers.is_empty() {
+            self.pending_queries.remove(&hash);
+        }
+        Some(sent_at)
     }
 
     /// Get all discovered representatives.
@@ -115,7 +245,7 @@ impl RepCrawler {
     }
 
     /// Total discovered representative weight.
-    pub fn total_discovered_weight(&self) -> u64 {
+    pub fn total_discovered_weight(&self) -> u128 {
         self.discovered_reps.values().map(|r| r.weight).sum()
     }
 
@@ -173,7 +303,7 @@ mod tests {
         let mut crawler = RepCrawler::new(10, 60);
         let peers = vec!["peer1".to_string()];
         crawler.start_crawl(test_hash(1), &peers, 100);
-        assert!(!crawler.should_crawl(110)); // only 10s later
+        assert!(!crawler.should_crawl(110)); // only 10ms later
     }
 
     #[test]
@@ -181,7 +311,7 @@ mod tests {
         let mut crawler = RepCrawler::new(10, 60);
         let peers = vec!["peer1".to_string()];
         crawler.start_crawl(test_hash(1), &peers, 100);
-        assert!(crawler.should_crawl(161)); // 61s later
+        assert!(crawler.should_crawl(161)); // 61ms later
     }
 
     #[test]
@@ -230,7 +360,7 @@ mod tests {
         crawler.start_crawl(test_hash(1), &peers, 100);
         assert_eq!(crawler.pending_query_count(), 1);
 
-        // Cleanup at now=111 (11s after send, timeout=10s)
+        // Cleanup at now=111 (11ms after send, timeout=10ms)
         crawler.cleanup_expired(111);
         assert_eq!(crawler.pending_query_count(), 0);
     }
@@ -241,7 +371,7 @@ mod tests {
         let peers = vec!["peer1".to_string()];
         crawler.start_crawl(test_hash(1), &peers, 100);
 
-        // Cleanup at now=105 (5s after send, timeout=10s)
+        // Cleanup at now=105 (5ms after send, timeout=10ms)
         crawler.cleanup_expired(105);
         assert_eq!(crawler.pending_query_count(), 1);
     }
@@ -279,4 +409,41 @@ mod tests {
         // All 3 peers registered under 1 block hash
         assert_eq!(crawler.pending_query_count(), 1);
     }
+
+    #[test]
+    fn responses_record_latency_and_uptime() {
+        let mut crawler = RepCrawler::new(1_000, 5_000);
+        let rep = test_address("rep1");
+        let peers = vec!["peer1".to_string()];
+
+        // Round 1: answered after 40ms. Round 2: answered after 120ms.
+        crawler.start_crawl(test_hash(1), &peers, 10_000);
+        crawler.process_response("peer1", &rep, 500, 10_040);
+        crawler.start_crawl(test_hash(2), &peers, 20_000);
+        crawler.process_response("peer1", &rep, 500, 20_120);
+        // Round 3: never answered.
+        crawler.start_crawl(test_hash(3), &peers, 30_000);
+        crawler.cleanup_expired(31_500);
+
+        let perf = crawler.performance(&rep).unwrap();
+        assert_eq!(perf.latency_percentile(50), Some(40));
+        assert_eq!(perf.latency_percentile(99), Some(120));
+        assert_eq!(perf.probe_count(), 3);
+        assert!((perf.uptime_score().unwrap() - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(perf.last_vote, Some(20_120));
+        assert_eq!(crawler.pending_query_count(), 0);
+
+        crawler.record_vote(&rep, 40_000);
+        assert_eq!(crawler.performance(&rep).unwrap().last_vote, Some(40_000));
+    }
+
+    #[test]
+    fn unsolicited_response_has_no_latency_sample() {
+        let mut crawler = RepCrawler::new(1_000, 5_000);
+        let rep = test_address("rep1");
+        crawler.process_response("peer1", &rep, 500, 100);
+        let perf = crawler.performance(&rep).unwrap();
+        assert_eq!(perf.latency_percentile(50), None);
+        assert_eq!(perf.uptime_score(), None);
+    }
 }
@@ -6,10 +6,25 @@
 //!
 //! Weight is denominated in raw TRST (u128) — each account's delegated weight
 //! equals its TRST balance, matching the whitepaper's balance-weighted ORV.
+//!
+//! Because the cache is only ever adjusted, a missed or doubled update
+//! persists until something recomputes it. [`RepWeightCache::reconcile`]
+//! recomputes the weights from the account set and reports every
+//! representative whose cached weight had drifted.
 
 use burst_types::WalletAddress;
 use std::collections::HashMap;
 
+/// A representative whose cached weight disagreed with the account set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WeightDrift {
+    pub representative: WalletAddress,
+    /// Weight the cache held.
+    pub cached: u128,
+    /// Weight delegated to the representative by the account set.
+    pub expected: u128,
+}
+
 /// Cached representative weights, updated incrementally on ChangeRep blocks.
 pub struct RepWeightCache {
     /// representative_address → total delegated TRST balance.
@@ -88,6 +103,36 @@ impl RepWeightCache {
             self.total_weight = self.total_weight.saturating_add(balance);
         }
     }
+
+    /// Rebuild the cache from the account set, as [`rebuild_from_accounts`]
+    /// does, and return the representatives whose weight changed, ordered
+    /// by address.
+    ///
+    /// [`rebuild_from_accounts`]: Self::rebuild_from_accounts
+    pub fn reconcile(
+        &mut self,
+        accounts: impl Iterator<Item = (WalletAddress, WalletAddress, u128)>,
+    ) -> Vec<WeightDrift> {
+        let cached = std::mem::take(&mut self.weights);
+        self.rebuild_from_accounts(accounts);
+        let mut drift: Vec<WeightDrift> = cached
+            .keys()
+            .chain(self.weights.keys())
+            .collect::<std::collections::HashSet<_>>()
+            .into_iter()
+            .filter_map(|rep| {
+                let cached = cached.get(rep).copied().unwrap_or(0);
+                let expected = self.weight(rep);
+                (cached != expected).then(|| WeightDrift {
+                    representative: rep.clone(),
+                    cached,
+                    expected,
+                })
+            })
+            .collect();
+        drift.sort_by(|a, b| a.representative.as_str().cmp(b.representative.as_str()));
+        drift
+    }
 }
 
 impl Default for RepWeightCache {
@@ -109,6 +154,47 @@ mod tests {
         WalletAddress::new(format!("brst_acct_{name}"))
     }
 
+    #[test]
+    fn reconcile_corrects_and_reports_drift() {
+        let mut cache = RepWeightCache::new();
+        cache.add_weight(&rep("alice"), 500);
+        cache.add_weight(&rep("bob"), 300);
+        cache.add_weight(&rep("carol"), 50);
+
+        let drift = cache.reconcile(
+            vec![
+                (account("a"), rep("alice"), 500),
+                (account("b"), rep("bob"), 200),
+                (account("d"), rep("dave"), 70),
+            ]
+            .into_iter(),
+        );
+        assert_eq!(
+            drift
+                .iter()
+                .map(|d| (d.representative.as_str(), d.cached, d.expected))
+                .collect::<Vec<_>>(),
+            vec![
+                ("brst_bob", 300, 200),
+                ("brst_carol", 50, 0),
+                ("brst_dave", 0, 70),
+            ]
+        );
+        assert_eq!(cache.weight(&rep("bob")), 200);
+        assert_eq!(cache.weight(&rep("carol")), 0);
+        assert_eq!(cache.total_weight(), 770);
+        assert!(cache
+            .reconcile(
+                vec![
+                    (account("a"), rep("alice"), 500),
+                    (account("b"), rep("bob"), 200),
+                    (account("d"), rep("dave"), 70),
+                ]
+                .into_iter()
+            )
+            .is_empty());
+    }
+
     #[test]
     fn new_cache_is_empty() {
         let cache = RepWeightCache::new();
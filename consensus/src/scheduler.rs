@@ -6,9 +6,11 @@
 //!   significant vote weight in the vote cache (i.e. representatives voted
 //!   before the node saw the fork).
 //!
-//! - **Priority**: Schedules elections based on account importance (balance),
-//!   ensuring that high-value accounts get elections resolved first during
-//!   contention.
+//! - **Priority**: Schedules elections by account balance bucket. Buckets
+//!   take turns and each has a reserved share of election slots, so
+//!   high-value accounts are served first without starving small ones.
+
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use burst_types::{BlockHash, WalletAddress};
 
@@ -63,82 +65,199 @@ impl HintedScheduler {
 
 // ── Priority scheduler ──────────────────────────────────────────────────
 
-/// Schedules elections based on account importance (balance).
+/// Default lower balance bounds of the priority buckets: zero, then every
+/// power of ten up to 10^19.
+pub fn default_bucket_thresholds() -> Vec<u64> {
+    std::iter::once(0)
+        .chain((0..=19).map(|exp| 10u64.pow(exp)))
+        .collect()
+}
+
+/// Schedules elections by account balance with per-bucket fairness.
 ///
-/// Higher-balance accounts get their elections resolved first. This is a
-/// simple max-heap (sorted Vec) of pending election requests.
+/// Accounts are divided into balance buckets. Each bucket has its own
+/// bounded queue, served round-robin across accounts so one account cannot
+/// monopolise its bucket, and a reserved share of election slots. Buckets
+/// are themselves served round-robin, highest balances first, and buckets
+/// below their share are served before buckets above it. A flood of
+/// high-balance blocks therefore fills only its own bucket and cannot starve
+/// small accounts indefinitely.
 pub struct PriorityScheduler {
-    /// Pending accounts needing election, maintained in sorted order.
-    queue: Vec<PriorityEntry>,
-    /// Maximum queue size.
-    max_queue: usize,
+    buckets: Vec<Bucket>,
+    /// Every queued root, for deduplication.
+    queued: HashSet<BlockHash>,
+    /// Bucket index to try first on the next pop.
+    cursor: usize,
+    /// Elections started by this scheduler, with the bucket they came from.
+    active: HashMap<BlockHash, usize>,
+    /// Maximum queued roots per bucket.
+    max_per_bucket: usize,
+    /// Election slots reserved for each bucket.
+    reserved_per_bucket: usize,
 }
 
-struct PriorityEntry {
-    root: BlockHash,
-    account: WalletAddress,
-    priority: u64,
+struct Bucket {
+    /// Lowest balance (inclusive) that falls into this bucket.
+    min_balance: u64,
+    /// Accounts with pending roots, in round-robin order.
+    accounts: VecDeque<WalletAddress>,
+    /// Pending roots per account, oldest first.
+    pending: HashMap<WalletAddress, VecDeque<BlockHash>>,
+    /// Total pending roots in this bucket.
+    len: usize,
+    /// Elections currently active from this bucket.
+    active: usize,
+}
+
+impl Bucket {
+    fn new(min_balance: u64) -> Self {
+        Self {
+            min_balance,
+            accounts: VecDeque::new(),
+            pending: HashMap::new(),
+            len: 0,
+            active: 0,
+        }
+    }
+
+    fn push(&mut self, root: BlockHash, account: WalletAddress) {
+        let roots = self.pending.entry(account.clone()).or_default();
+        if roots.is_empty() {
+            self.accounts.push_back(account);
+        }
+        roots.push_back(root);
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<(BlockHash, WalletAddress)> {
+        let account = self.accounts.pop_front()?;
+        let roots = self.pending.get_mut(&account)?;
+        let root = roots.pop_front()?;
+        if roots.is_empty() {
+            self.pending.remove(&account);
+        } else {
+            self.accounts.push_back(account.clone());
+        }
+        self.len -= 1;
+        Some((root, account))
+    }
 }
 
 impl PriorityScheduler {
+    /// Scheduler with the default buckets, holding at most `max_queue`
+    /// pending roots and sharing `max_queue` election slots between buckets.
     pub fn new(max_queue: usize) -> Self {
+        Self::with_buckets(default_bucket_thresholds(), max_queue, max_queue)
+    }
+
+    /// Scheduler with custom bucket lower bounds. `max_queue` and
+    /// `max_elections` are split evenly across buckets (at least one each).
+    pub fn with_buckets(mut thresholds: Vec<u64>, max_queue: usize, max_elections: usize) -> Self {
+        thresholds.sort_unstable();
+        thresholds.dedup();
+        if thresholds.first() != Some(&0) {
+            thresholds.insert(0, 0);
+        }
+        let count = thresholds.len();
         Self {
-            queue: Vec::new(),
-            max_queue,
+            buckets: thresholds.into_iter().map(Bucket::new).collect(),
+            queued: HashSet::new(),
+            cursor: count - 1,
+            active: HashMap::new(),
+            max_per_bucket: (max_queue / count).max(1),
+            reserved_per_bucket: (max_elections / count).max(1),
         }
     }
 
-    /// Add a block that needs an election, with its account balance as priority.
+    /// Index of the bucket `balance` falls into.
+    fn bucket_index(&self, balance: u64) -> usize {
+        self.buckets
+            .partition_point(|bucket| bucket.min_balance <= balance)
+            .saturating_sub(1)
+    }
+
+    /// Add a block that needs an election, bucketed by its account balance.
     ///
-    /// If the queue is full, the entry is dropped (lower priority than
-    /// everything already queued). Duplicate roots are silently ignored.
+    /// If the block's bucket is full the entry is dropped; other buckets are
+    /// unaffected. Duplicate roots are silently ignored.
     pub fn push(&mut self, root: BlockHash, account: WalletAddress, balance: u64) {
-        // Deduplicate by root
-        if self.queue.iter().any(|e| e.root == root) {
+        if self.active.contains_key(&root) || self.queued.contains(&root) {
+            return;
+        }
+        let max_per_bucket = self.max_per_bucket;
+        let index = self.bucket_index(balance);
+        let bucket = &mut self.buckets[index];
+        if bucket.len >= max_per_bucket {
             return;
         }
+        bucket.push(root, account);
+        self.queued.insert(root);
+    }
+
+    /// Pop the next root to start an election for.
+    ///
+    /// Buckets still below their reserved share of election slots are served
+    /// first; otherwise any bucket with pending work is served. Within each
+    /// pass buckets take turns, starting from the highest balances.
+    pub fn pop(&mut self) -> Option<(BlockHash, WalletAddress)> {
+        let reserved = self.reserved_per_bucket;
+        let index = self
+            .next_bucket(|bucket| bucket.len > 0 && bucket.active < reserved)
+            .or_else(|| self.next_bucket(|bucket| bucket.len > 0))?;
+        let (root, account) = self.buckets[index].pop()?;
+        self.queued.remove(&root);
+        self.buckets[index].active += 1;
+        self.active.insert(root, index);
+        self.cursor = index.checked_sub(1).unwrap_or(self.buckets.len() - 1);
+        Some((root, account))
+    }
+
+    /// First bucket matching `eligible`, walking down from the cursor and
+    /// wrapping around to the top.
+    fn next_bucket(&self, eligible: impl Fn(&Bucket) -> bool) -> Option<usize> {
+        let count = self.buckets.len();
+        (0..count)
+            .map(|step| (self.cursor + count - step) % count)
+            .find(|&index| eligible(&self.buckets[index]))
+    }
 
-        if self.queue.len() >= self.max_queue {
-            // Only insert if higher priority than the lowest entry
-            if let Some(min) = self.queue.last() {
-                if balance <= min.priority {
-                    return;
-                }
-                self.queue.pop();
+    /// Release the slots of elections that are no longer active, so their
+    /// buckets can start new ones.
+    pub fn release_finished(&mut self, is_active: impl Fn(&BlockHash) -> bool) {
+        let buckets = &mut self.buckets;
+        self.active.retain(|root, index| {
+            let keep = is_active(root);
+            if !keep {
+                buckets[*index].active = buckets[*index].active.saturating_sub(1);
             }
-        }
+            keep
+        });
+    }
 
-        let entry = PriorityEntry {
-            root,
-            account,
-            priority: balance,
-        };
-        // Insert in sorted order (highest priority first)
-        let pos = self
-            .queue
-            .binary_search_by(|e| balance.cmp(&e.priority))
-            .unwrap_or_else(|pos| pos);
-        self.queue.insert(pos, entry);
+    /// Re-split `max_elections` slots across buckets, e.g. after the active
+    /// election limit changed.
+    pub fn set_max_elections(&mut self, max_elections: usize) {
+        self.reserved_per_bucket = (max_elections / self.buckets.len()).max(1);
     }
 
-    /// Pop the highest-priority entry.
-    pub fn pop(&mut self) -> Option<(BlockHash, WalletAddress)> {
-        if self.queue.is_empty() {
-            None
-        } else {
-            let entry = self.queue.remove(0);
-            Some((entry.root, entry.account))
-        }
+    /// Number of buckets.
+    pub fn bucket_count(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Pending roots and active elections for the bucket at `index`.
+    pub fn bucket_stats(&self, index: usize) -> Option<(usize, usize)> {
+        self.buckets.get(index).map(|b| (b.len, b.active))
     }
 
     /// Current queue length.
     pub fn len(&self) -> usize {
-        self.queue.len()
+        self.buckets.iter().map(|b| b.len).sum()
     }
 
     /// Whether the queue is empty.
     pub fn is_empty(&self) -> bool {
-        self.queue.is_empty()
+        self.len() == 0
     }
 }
 
@@ -207,19 +326,19 @@ mod tests {
 
     #[test]
     fn priority_push_and_pop() {
-        let mut sched = PriorityScheduler::new(10);
+        let mut sched = PriorityScheduler::new(100);
         sched.push(make_hash(1), make_addr("alice"), 100);
-        sched.push(make_hash(2), make_addr("bob"), 500);
-        sched.push(make_hash(3), make_addr("carol"), 200);
+        sched.push(make_hash(2), make_addr("bob"), 50_000);
+        sched.push(make_hash(3), make_addr("carol"), 2_000);
 
         assert_eq!(sched.len(), 3);
 
-        // Highest priority (500) should come first
+        // Highest bucket (50_000) should come first
         let (root, account) = sched.pop().unwrap();
         assert_eq!(root, make_hash(2));
         assert_eq!(account, make_addr("bob"));
 
-        // Next: 200
+        // Next: 2_000
         let (root, _) = sched.pop().unwrap();
         assert_eq!(root, make_hash(3));
 
@@ -232,7 +351,7 @@ mod tests {
 
     #[test]
     fn priority_duplicate_root_ignored() {
-        let mut sched = PriorityScheduler::new(10);
+        let mut sched = PriorityScheduler::new(100);
         sched.push(make_hash(1), make_addr("alice"), 100);
         sched.push(make_hash(1), make_addr("bob"), 500);
 
@@ -240,43 +359,75 @@ mod tests {
         let (_, account) = sched.pop().unwrap();
         // Original entry should remain
         assert_eq!(account, make_addr("alice"));
-    }
 
-    #[test]
-    fn priority_respects_max_queue() {
-        let mut sched = PriorityScheduler::new(2);
+        // A root with a running election is not queued again either.
         sched.push(make_hash(1), make_addr("alice"), 100);
-        sched.push(make_hash(2), make_addr("bob"), 200);
+        assert!(sched.is_empty());
+    }
 
-        // Queue is full. This has higher priority than the lowest (100),
-        // so it should replace it.
-        sched.push(make_hash(3), make_addr("carol"), 300);
+    #[test]
+    fn priority_full_bucket_drops_only_its_own_entries() {
+        // Two buckets, one queued root each.
+        let mut sched = PriorityScheduler::with_buckets(vec![0, 1_000], 2, 2);
+        sched.push(make_hash(1), make_addr("whale"), 5_000);
+        sched.push(make_hash(2), make_addr("whale"), 6_000);
+        sched.push(make_hash(3), make_addr("minnow"), 10);
 
         assert_eq!(sched.len(), 2);
+        assert_eq!(sched.bucket_stats(0), Some((1, 0)));
+        assert_eq!(sched.bucket_stats(1), Some((1, 0)));
+    }
 
-        let (root1, _) = sched.pop().unwrap();
-        let (root2, _) = sched.pop().unwrap();
+    #[test]
+    fn priority_flood_cannot_starve_small_accounts() {
+        let mut sched = PriorityScheduler::with_buckets(vec![0, 1_000], 1_000, 4);
+        for i in 0..100u8 {
+            sched.push(make_hash(i), make_addr(&format!("whale{i}")), 1_000_000);
+        }
+        sched.push(make_hash(200), make_addr("minnow"), 10);
 
-        // Should have carol (300) and bob (200), alice (100) was evicted
-        assert_eq!(root1, make_hash(3));
-        assert_eq!(root2, make_hash(2));
+        let popped: Vec<_> = (0..2).map(|_| sched.pop().unwrap().0).collect();
+        assert!(popped.contains(&make_hash(200)));
     }
 
     #[test]
-    fn priority_low_entry_dropped_when_full() {
-        let mut sched = PriorityScheduler::new(2);
-        sched.push(make_hash(1), make_addr("alice"), 200);
-        sched.push(make_hash(2), make_addr("bob"), 300);
-
-        // This has lower priority than everything in the queue
-        sched.push(make_hash(3), make_addr("carol"), 100);
+    fn priority_round_robins_accounts_within_a_bucket() {
+        let mut sched = PriorityScheduler::with_buckets(vec![0], 100, 100);
+        sched.push(make_hash(1), make_addr("spammer"), 10);
+        sched.push(make_hash(2), make_addr("spammer"), 10);
+        sched.push(make_hash(3), make_addr("spammer"), 10);
+        sched.push(make_hash(4), make_addr("alice"), 10);
+
+        let order: Vec<_> = std::iter::from_fn(|| sched.pop().map(|(r, _)| r)).collect();
+        assert_eq!(
+            order,
+            vec![make_hash(1), make_hash(4), make_hash(2), make_hash(3)]
+        );
+    }
 
-        assert_eq!(sched.len(), 2);
-        // Carol should NOT be in the queue
-        let (r1, _) = sched.pop().unwrap();
-        let (r2, _) = sched.pop().unwrap();
-        assert_eq!(r1, make_hash(2));
-        assert_eq!(r2, make_hash(1));
+    #[test]
+    fn priority_prefers_buckets_below_their_share() {
+        // One reserved slot per bucket.
+        let mut sched = PriorityScheduler::with_buckets(vec![0, 1_000], 100, 2);
+        sched.push(make_hash(1), make_addr("whale"), 5_000);
+        sched.push(make_hash(2), make_addr("whale"), 5_000);
+        sched.push(make_hash(3), make_addr("whale"), 5_000);
+
+        assert_eq!(sched.pop().unwrap().0, make_hash(1));
+        sched.push(make_hash(4), make_addr("minnow"), 10);
+        sched.push(make_hash(5), make_addr("minnow"), 10);
+
+        // The high bucket used its share, so the low bucket goes next, and
+        // once both are over their share they take turns again.
+        assert_eq!(sched.pop().unwrap().0, make_hash(4));
+        assert_eq!(sched.pop().unwrap().0, make_hash(2));
+        assert_eq!(sched.bucket_stats(1), Some((1, 2)));
+
+        // Finished elections free their bucket's slots.
+        sched.release_finished(|root| *root == make_hash(4));
+        assert_eq!(sched.bucket_stats(1), Some((1, 0)));
+        assert_eq!(sched.bucket_stats(0), Some((1, 1)));
+        assert_eq!(sched.pop().unwrap().0, make_hash(3));
     }
 
     #[test]
@@ -8,12 +8,21 @@
 //! Unlike the simple list-based approach, this cache tracks per-voter
 //! deduplication (replacing votes with higher timestamps), maintains running
 //! tallies, enforces a per-hash voter limit, and expires stale entries via TTL.
-
-use burst_types::{BlockHash, WalletAddress};
-use std::collections::{BTreeMap, HashMap};
+//!
+//! The number of hashes is bounded. When full, the hash with the least cached
+//! weight — the oldest among equals — makes room, unless the incoming vote
+//! carries less weight than that.
+
+use crate::active_elections::ActiveElections;
+use burst_types::memory::entry_bytes;
+use burst_types::{BlockHash, MemoryAccounting, Timestamp, WalletAddress};
+use burst_utils::{DetailType, StatType, Stats};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-const MAX_CACHE_SIZE: usize = 65536;
+/// Default number of distinct block hashes the cache holds votes for.
+pub const DEFAULT_VOTE_CACHE_CAPACITY: usize = 65536;
 const MAX_VOTERS_PER_HASH: usize = 64;
 const VOTE_CACHE_TTL: Duration = Duration::from_secs(900);
 
@@ -29,6 +38,7 @@ struct CacheEntry {
     votes: Vec<CachedVote>,
     tally: u128,
     final_tally: u128,
+    created: Instant,
 }
 
 /// Pre-election vote storage with per-voter deduplication and running tallies.
@@ -43,21 +53,40 @@ pub struct VoteCache {
     /// votes at that time. Enables O(log n) eviction by iterating only
     /// entries older than the TTL cutoff.
     time_index: BTreeMap<Instant, Vec<BlockHash>>,
+    /// Eviction order: `(tally, created, hash)`, weakest and oldest first.
+    by_weight: BTreeSet<(u128, Instant, BlockHash)>,
+    capacity: usize,
+    /// Hit, miss and eviction counters.
+    stats: Arc<Stats>,
 }
 
 impl VoteCache {
     pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_VOTE_CACHE_CAPACITY)
+    }
+
+    /// A cache holding votes for at most `capacity` block hashes.
+    pub fn with_capacity(capacity: usize) -> Self {
         Self {
             entries: HashMap::new(),
             time_index: BTreeMap::new(),
+            by_weight: BTreeSet::new(),
+            capacity,
+            stats: Arc::default(),
         }
     }
 
+    /// Report into a shared stats registry.
+    pub fn set_stats(&mut self, stats: Arc<Stats>) {
+        self.stats = stats;
+    }
+
     /// Cache a vote for a block hash.
     ///
     /// Handles duplicate voters by replacing if the new timestamp is higher.
     /// Enforces a per-hash voter limit by evicting the lowest-weight voter.
-    /// Triggers TTL cleanup when the cache exceeds capacity.
+    /// A vote for a new hash when the cache is full first triggers TTL
+    /// cleanup, then evicts the weakest hash if the vote outweighs it.
     pub fn insert(
         &mut self,
         hash: BlockHash,
@@ -66,15 +95,20 @@ impl VoteCache {
         timestamp: u64,
         is_final: bool,
     ) {
-        if self.entries.len() >= MAX_CACHE_SIZE {
-            self.cleanup();
+        let is_new = !self.entries.contains_key(&hash);
+        if is_new && !self.make_room(weight) {
+            return;
         }
 
+        let now = Instant::now();
         let entry = self.entries.entry(hash).or_insert_with(|| CacheEntry {
             votes: Vec::new(),
             tally: 0,
             final_tally: 0,
+            created: now,
         });
+        let previous_tally = entry.tally;
+        let created = entry.created;
 
         if let Some(existing) = entry.votes.iter_mut().find(|v| v.voter == voter) {
             if timestamp > existing.timestamp {
@@ -85,11 +119,14 @@ impl VoteCache {
                 existing.weight = weight;
                 existing.timestamp = timestamp;
                 existing.is_final = is_final;
-                existing.arrived = Instant::now();
+                existing.arrived = now;
                 entry.tally += weight;
                 if is_final {
                     entry.final_tally += weight;
                 }
+                let tally = entry.tally;
+                self.reindex(hash, created, previous_tally, tally);
+                self.time_index.entry(now).or_default().push(hash);
             }
             return;
         }
@@ -114,7 +151,6 @@ impl VoteCache {
             }
         }
 
-        let now = Instant::now();
         entry.tally += weight;
         if is_final {
             entry.final_tally += weight;
@@ -126,22 +162,48 @@ impl VoteCache {
             is_final,
             arrived: now,
         });
+        let tally = entry.tally;
+        if is_new {
+            self.by_weight.insert((tally, created, hash));
+        } else {
+            self.reindex(hash, created, previous_tally, tally);
+        }
         self.time_index.entry(now).or_default().push(hash);
     }
 
     /// Get and remove all cached votes for a block hash (called when election starts).
     pub fn drain(&mut self, hash: &BlockHash) -> Vec<(WalletAddress, u128, u64, bool)> {
-        if let Some(entry) = self.entries.remove(hash) {
+        if let Some(entry) = self.remove_entry(hash) {
+            self.stats.inc(StatType::VoteCache, DetailType::Hit);
             entry
                 .votes
                 .into_iter()
                 .map(|v| (v.voter, v.weight, v.timestamp, v.is_final))
                 .collect()
         } else {
+            self.stats.inc(StatType::VoteCache, DetailType::Miss);
             vec![]
         }
     }
 
+    /// Replay the votes cached for `root` into its election, which has just
+    /// started, and release them. Returns how many votes were applied.
+    pub fn replay_into(
+        &mut self,
+        root: &BlockHash,
+        elections: &mut ActiveElections,
+        now: Timestamp,
+    ) -> usize {
+        self.drain(root)
+            .into_iter()
+            .filter(|(voter, weight, _, is_final)| {
+                elections
+                    .process_vote(root, voter, *root, *weight, *is_final, now)
+                    .is_ok()
+            })
+            .count()
+    }
+
     /// Get the tally for a block hash without removing.
     /// Returns `(total_tally, final_tally)`.
     pub fn tally(&self, hash: &BlockHash) -> (u128, u128) {
@@ -190,26 +252,69 @@ impl VoteCache {
 
         // Prune expired votes only in candidate entries.
         for hash in candidate_hashes {
-            let remove_entry = if let Some(entry) = self.entries.get_mut(&hash) {
-                entry.votes.retain(|v| v.arrived > cutoff);
-                entry.tally = entry.votes.iter().map(|v| v.weight).sum();
-                entry.final_tally = entry
-                    .votes
-                    .iter()
-                    .filter(|v| v.is_final)
-                    .map(|v| v.weight)
-                    .sum();
-                entry.votes.is_empty()
-            } else {
-                false
+            let Some(entry) = self.entries.get_mut(&hash) else {
+                continue;
             };
+            let previous_tally = entry.tally;
+            entry.votes.retain(|v| v.arrived > cutoff);
+            entry.tally = entry.votes.iter().map(|v| v.weight).sum();
+            entry.final_tally = entry
+                .votes
+                .iter()
+                .filter(|v| v.is_final)
+                .map(|v| v.weight)
+                .sum();
+            let (created, tally) = (entry.created, entry.tally);
 
-            if remove_entry {
-                self.entries.remove(&hash);
+            if entry.votes.is_empty() {
+                self.remove_entry(&hash);
+            } else {
+                self.reindex(hash, created, previous_tally, tally);
+            }
+        }
+    }
+
+    /// Make room for a vote of `weight` for a new hash. Returns `false`
+    /// when the cache is full of hashes that all carry more weight.
+    fn make_room(&mut self, weight: u128) -> bool {
+        if self.entries.len() < self.capacity {
+            return true;
+        }
+        self.cleanup();
+        if self.entries.len() < self.capacity {
+            return true;
+        }
+        match self.by_weight.first().copied() {
+            Some((lowest, _, victim)) if weight > lowest => {
+                self.remove_entry(&victim);
+                self.stats.inc(StatType::VoteCache, DetailType::Evicted);
+                true
+            }
+            _ => {
+                self.stats.inc(StatType::VoteCache, DetailType::Overflow);
+                false
             }
         }
     }
 
+    fn remove_entry(&mut self, hash: &BlockHash) -> Option<CacheEntry> {
+        let entry = self.entries.remove(hash)?;
+        self.by_weight.remove(&(entry.tally, entry.created, *hash));
+        Some(entry)
+    }
+
+    fn reindex(&mut self, hash: BlockHash, created: Instant, from: u128, to: u128) {
+        if from != to {
+            self.by_weight.remove(&(from, created, hash));
+            self.by_weight.insert((to, created, hash));
+        }
+    }
+
+    /// Maximum number of distinct block hashes held.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
     /// Number of distinct block hashes with cached votes.
     pub fn len(&self) -> usize {
         self.entries.len()
@@ -226,6 +331,20 @@ impl VoteCache {
     }
 }
 
+impl MemoryAccounting for VoteCache {
+    fn element_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn approximate_bytes(&self) -> usize {
+        entry_bytes::<(BlockHash, CacheEntry)>(self.entries.len())
+            + entry_bytes::<CachedVote>(self.total_votes())
+            + entry_bytes::<(Instant, Vec<BlockHash>)>(self.time_index.len())
+            + entry_bytes::<BlockHash>(self.time_index.values().map(Vec::len).sum())
+            + entry_bytes::<(u128, Instant, BlockHash)>(self.by_weight.len())
+    }
+}
+
 impl Default for VoteCache {
     fn default() -> Self {
         Self::new()
@@ -235,6 +354,7 @@ impl Default for VoteCache {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use burst_utils::Direction;
 
     fn make_hash(byte: u8) -> BlockHash {
         BlockHash::new([byte; 32])
@@ -441,4 +561,70 @@ mod tests {
         let cache = VoteCache::default();
         assert!(cache.is_empty());
     }
+
+    #[test]
+    fn full_cache_evicts_lowest_weight_then_oldest() {
+        let mut cache = VoteCache::with_capacity(3);
+        cache.insert(make_hash(1), make_voter("a"), 100, 1, false);
+        cache.insert(make_hash(2), make_voter("b"), 50, 1, false);
+        cache.insert(make_hash(3), make_voter("c"), 50, 1, false);
+
+        cache.insert(make_hash(4), make_voter("d"), 60, 1, false);
+        assert_eq!(cache.len(), 3);
+        // Hash 2 and 3 tie on weight; the older one goes.
+        assert_eq!(cache.tally(&make_hash(2)), (0, 0));
+        assert_eq!(cache.tally(&make_hash(3)), (50, 0));
+        assert_eq!(cache.tally(&make_hash(4)), (60, 0));
+    }
+
+    #[test]
+    fn full_cache_rejects_weaker_newcomer() {
+        let stats = Arc::new(Stats::default());
+        let mut cache = VoteCache::with_capacity(2);
+        cache.set_stats(Arc::clone(&stats));
+        cache.insert(make_hash(1), make_voter("a"), 100, 1, false);
+        cache.insert(make_hash(2), make_voter("b"), 100, 1, false);
+
+        cache.insert(make_hash(3), make_voter("c"), 10, 1, false);
+        assert_eq!(cache.tally(&make_hash(3)), (0, 0));
+        assert_eq!(
+            stats.count(StatType::VoteCache, DetailType::Overflow, Direction::In),
+            1
+        );
+
+        // Votes for hashes already cached still land, and reorder eviction.
+        cache.insert(make_hash(1), make_voter("c"), 10, 1, false);
+        cache.insert(make_hash(3), make_voter("d"), 105, 1, false);
+        assert_eq!(cache.tally(&make_hash(1)), (110, 0));
+        assert_eq!(cache.tally(&make_hash(2)), (0, 0));
+        assert_eq!(
+            stats.count(StatType::VoteCache, DetailType::Evicted, Direction::In),
+            1
+        );
+    }
+
+    #[test]
+    fn replay_applies_cached_votes_once() {
+        let stats = Arc::new(Stats::default());
+        let mut cache = VoteCache::new();
+        cache.set_stats(Arc::clone(&stats));
+        let mut elections = ActiveElections::new(10, 1_000);
+        cache.insert(make_hash(1), make_voter("alice"), 100, 1000, false);
+        cache.insert(make_hash(1), make_voter("bob"), 200, 1001, false);
+
+        let now = Timestamp::new(1);
+        elections.start_election(make_hash(1), now).unwrap();
+        assert_eq!(cache.replay_into(&make_hash(1), &mut elections, now), 2);
+        assert!(cache.is_empty());
+        assert_eq!(cache.replay_into(&make_hash(1), &mut elections, now), 0);
+
+        assert_eq!(
+            stats.count(StatType::VoteCache, DetailType::Hit, Direction::In),
+            1
+        );
+        assert_eq!(
+            stats.count(StatType::VoteCache, DetailType::Miss, Direction::In),
+            1
+        );
+    }
 }
@@ -31,6 +31,15 @@ impl VoteGenerator {
         }
     }
 
+    /// Switch to a new representative account and signing key.
+    ///
+    /// The sequence counter restarts, since it orders votes per representative.
+    pub fn rotate_key(&mut self, representative: WalletAddress, signing_key: [u8; 32]) {
+        self.representative = representative;
+        self.signing_key = PrivateKey(signing_key);
+        self.sequence = 0;
+    }
+
     /// Generate a non-final vote for a block.
     ///
     /// Non-final votes can be changed later (e.g. if the node sees a
@@ -77,6 +86,12 @@ impl VoteGenerator {
         self.sequence
     }
 
+    /// Sign an arbitrary payload with the representative key, e.g. a
+    /// checkpoint digest.
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        sign_message(message, &self.signing_key)
+    }
+
     /// Sign a vote payload: block_hash ‖ is_final ‖ timestamp ‖ sequence.
     fn sign_vote(
         &self,
@@ -221,6 +236,27 @@ mod tests {
         assert!(verify_signature(&payload, &vote.signature, &pubkey));
     }
 
+    #[test]
+    fn rotate_key_switches_voter_and_restarts_sequence() {
+        let (mut gen, old_pubkey) = make_generator();
+        gen.generate_vote(make_hash(1));
+
+        let kp = generate_keypair();
+        let new_rep = burst_crypto::derive_address(&kp.public);
+        gen.rotate_key(new_rep.clone(), kp.private.0);
+        let vote = gen.generate_vote(make_hash(2));
+        assert_eq!(vote.voter, new_rep);
+        assert_eq!(vote.sequence, 1);
+
+        let mut payload = Vec::new();
+        payload.extend_from_slice(vote.block_hash.as_bytes());
+        payload.push(u8::from(vote.is_final));
+        payload.extend_from_slice(&vote.timestamp.to_le_bytes());
+        payload.extend_from_slice(&vote.sequence.to_le_bytes());
+        assert!(verify_signature(&payload, &vote.signature, &kp.public));
+        assert!(!verify_signature(&payload, &vote.signature, &old_pubkey));
+    }
+
     #[test]
     fn to_bytes_produces_valid_encoding() {
         let (mut gen, _) = make_generator();
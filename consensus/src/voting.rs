@@ -151,7 +151,7 @@ mod tests {
         assert_eq!(result, VoteResult::Updated);
 
         let tally = voting.tally();
-        assert!(tally.get(&make_hash(1)).is_none() || *tally.get(&make_hash(1)).unwrap() == 0);
+        assert!(!tally.contains_key(&make_hash(1)) || *tally.get(&make_hash(1)).unwrap() == 0);
         assert_eq!(tally.get(&make_hash(2)), Some(&300));
     }
 
@@ -0,0 +1,187 @@
+//! Encrypted direct messages between wallets.
+//!
+//! A [`DirectMessage`] is [sealed](crate::seal) to the recipient's key and
+//! signed by the sender, so any node can check who sent it and hold it for
+//! the recipient without being able to read it. Messages carry an expiry
+//! and live only in node memory until then; they never touch the ledger.
+//!
+//! The sender and recipient addresses are mixed into the encryption
+//! context, so a ciphertext re-signed by someone else no longer opens.
+
+use burst_types::{KeyPair, PrivateKey, PublicKey, Signature, WalletAddress};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    blake2b_256, blake2b_256_multi, decode_address, derive_address, ed25519_private_to_x25519,
+    ed25519_public_to_x25519, sign_message, verify_signature,
+};
+
+/// Domain separator for the signature and the encryption context.
+const DIRECT_MESSAGE_DOMAIN: &[u8] = b"burst-direct-message";
+
+/// Errors from sealing, checking or opening a direct message.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DirectMessageError {
+    #[error("invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("invalid direct message signature")]
+    BadSignature,
+
+    #[error("direct message is not addressed to this key")]
+    WrongRecipient,
+
+    #[error("direct message could not be decrypted")]
+    Decryption,
+}
+
+/// An encrypted, signed message from one wallet to another.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectMessage {
+    pub sender: WalletAddress,
+    pub recipient: WalletAddress,
+    /// The sender's one-time X25519 public key.
+    pub ephemeral_public: [u8; 32],
+    pub ciphertext: Vec<u8>,
+    /// Unix seconds.
+    pub created_at: u64,
+    /// Unix seconds after which nodes drop the message.
+    pub expires_at: u64,
+    pub signature: Signature,
+}
+
+impl DirectMessage {
+    /// Encrypt `plaintext` for `recipient` and sign it with `sender`.
+    pub fn seal(
+        sender: &KeyPair,
+        recipient: &WalletAddress,
+        plaintext: &[u8],
+        created_at: u64,
+        expires_at: u64,
+    ) -> Result<Self, DirectMessageError> {
+        let recipient_key = decode_address(recipient.as_str())
+            .and_then(|key| ed25519_public_to_x25519(&key))
+            .ok_or_else(|| DirectMessageError::InvalidAddress(recipient.to_string()))?;
+        let sender_address = derive_address(&sender.public);
+        let context = encryption_context(&sender_address, recipient);
+        let (ephemeral_public, ciphertext) = crate::seal(plaintext, &recipient_key, &context);
+        let mut message = Self {
+            sender: sender_address,
+            recipient: recipient.clone(),
+            ephemeral_public,
+            ciphertext,
+            created_at,
+            expires_at,
+            signature: Signature([0u8; 64]),
+        };
+        message.signature = sign_message(&message.signing_digest(), &sender.private);
+        Ok(message)
+    }
+
+    /// Identifies the message, for deduplication and acknowledgement.
+    pub fn id(&self) -> [u8; 32] {
+        blake2b_256_multi(&[&self.signing_digest(), &self.signature.0])
+    }
+
+    /// Whether the sender's signature covers the message as it is.
+    pub fn verify_signature(&self) -> bool {
+        let Some(key) = decode_address(self.sender.as_str()) else {
+            return false;
+        };
+        verify_signature(&self.signing_digest(), &self.signature, &PublicKey(key))
+    }
+
+    /// Whether the message has expired at unix second `now`.
+    pub fn is_expired(&self, now: u64) -> bool {
+        now > self.expires_at
+    }
+
+    /// Check the signature and decrypt the message with the recipient's
+    /// private key.
+    pub fn open(&self, recipient_private: &PrivateKey) -> Result<Vec<u8>, DirectMessageError> {
+        if !self.verify_signature() {
+            return Err(DirectMessageError::BadSignature);
+        }
+        let public = crate::public_from_private(recipient_private);
+        if derive_address(&public) != self.recipient {
+            return Err(DirectMessageError::WrongRecipient);
+        }
+        let secret = ed25519_private_to_x25519(&recipient_private.0);
+        let context = encryption_context(&self.sender, &self.recipient);
+        crate::open(&self.ephemeral_public, &self.ciphertext, &secret, &context)
+            .map_err(|_| DirectMessageError::Decryption)
+    }
+
+    /// The digest the sender signs: everything but the signature.
+    fn signing_digest(&self) -> [u8; 32] {
+        blake2b_256_multi(&[
+            DIRECT_MESSAGE_DOMAIN,
+            self.sender.as_str().as_bytes(),
+            self.recipient.as_str().as_bytes(),
+            &self.ephemeral_public,
+            &blake2b_256(&self.ciphertext),
+            &self.created_at.to_be_bytes(),
+            &self.expires_at.to_be_bytes(),
+        ])
+    }
+}
+
+fn encryption_context(sender: &WalletAddress, recipient: &WalletAddress) -> Vec<u8> {
+    [
+        DIRECT_MESSAGE_DOMAIN,
+        sender.as_str().as_bytes(),
+        recipient.as_str().as_bytes(),
+    ]
+    .join(&b'|')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_keypair;
+
+    #[test]
+    fn only_the_recipient_opens_a_message() {
+        let alice = generate_keypair();
+        let bob = generate_keypair();
+        let mallory = generate_keypair();
+        let bob_address = derive_address(&bob.public);
+
+        let message = DirectMessage::seal(&alice, &bob_address, b"meet at 10", 100, 200).unwrap();
+        assert_eq!(message.sender, derive_address(&alice.public));
+        assert!(message.verify_signature());
+        assert_eq!(message.open(&bob.private).unwrap(), b"meet at 10");
+        assert_eq!(
+            message.open(&mallory.private),
+            Err(DirectMessageError::WrongRecipient)
+        );
+        assert!(!message.is_expired(200));
+        assert!(message.is_expired(201));
+    }
+
+    #[test]
+    fn tampering_or_resigning_is_detected() {
+        let alice = generate_keypair();
+        let bob = generate_keypair();
+        let mallory = generate_keypair();
+        let bob_address = derive_address(&bob.public);
+        let message = DirectMessage::seal(&alice, &bob_address, b"hello", 100, 200).unwrap();
+
+        let mut extended = message.clone();
+        extended.expires_at = 10_000;
+        assert!(!extended.verify_signature());
+        assert_ne!(extended.id(), message.id());
+
+        // Mallory re-signs Alice's ciphertext as her own: the signature
+        // holds, but the ciphertext was sealed for Alice as sender.
+        let mut stolen = message.clone();
+        stolen.sender = derive_address(&mallory.public);
+        stolen.signature = sign_message(&stolen.signing_digest(), &mallory.private);
+        assert!(stolen.verify_signature());
+        assert_eq!(
+            stolen.open(&bob.private),
+            Err(DirectMessageError::Decryption)
+        );
+    }
+}
@@ -1,12 +1,16 @@
-//! Encryption helpers for delegation key sharing.
+//! Encryption helpers for delegation key sharing and sealed messages.
 //!
 //! Uses X25519 Diffie-Hellman for key agreement, then ChaCha20-Poly1305
 //! AEAD for authenticated encryption of the delegation private key.
+//! [`seal`] / [`open`] do the same for arbitrary payloads with a fresh
+//! ephemeral sender key per message.
 
 use chacha20poly1305::{
     aead::{Aead, KeyInit},
     ChaCha20Poly1305, Nonce,
 };
+use rand::rngs::OsRng;
+use rand::RngCore;
 use x25519_dalek::{PublicKey as X25519Public, StaticSecret};
 
 /// Encrypt a delegation private key for secure transmission.
@@ -70,6 +74,61 @@ pub fn decrypt_delegation_key(
     Ok(key)
 }
 
+/// Derive the AEAD cipher and nonce for a sealed message.
+///
+/// The nonce comes from the ephemeral public key, which is fresh for every
+/// message, so a (key, nonce) pair is never reused.
+fn sealed_cipher(
+    shared: &[u8; 32],
+    ephemeral_public: &[u8; 32],
+    context: &[u8],
+) -> (ChaCha20Poly1305, Nonce) {
+    let sym_key = crate::hash::blake2b_256_multi(&[shared, b"burst-sealed", context]);
+    let cipher = ChaCha20Poly1305::new_from_slice(&sym_key).expect("valid key length");
+    let mut nonce_bytes = [0u8; 12];
+    nonce_bytes.copy_from_slice(&ephemeral_public[..12]);
+    (cipher, Nonce::from(nonce_bytes))
+}
+
+/// Encrypt `plaintext` for the holder of `recipient_x25519_public`.
+///
+/// A fresh ephemeral X25519 key is generated per message; its public half is
+/// returned alongside the ciphertext and is needed to [`open`] it. `context`
+/// is mixed into the key so a message sealed for one purpose cannot be
+/// replayed as another.
+pub fn seal(
+    plaintext: &[u8],
+    recipient_x25519_public: &[u8; 32],
+    context: &[u8],
+) -> ([u8; 32], Vec<u8>) {
+    let mut secret_bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut secret_bytes);
+    let secret = StaticSecret::from(secret_bytes);
+    let ephemeral_public = *X25519Public::from(&secret).as_bytes();
+    let shared = secret.diffie_hellman(&X25519Public::from(*recipient_x25519_public));
+
+    let (cipher, nonce) = sealed_cipher(shared.as_bytes(), &ephemeral_public, context);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .expect("encryption should not fail");
+    (ephemeral_public, ciphertext)
+}
+
+/// Decrypt a message produced by [`seal`].
+pub fn open(
+    ephemeral_public: &[u8; 32],
+    ciphertext: &[u8],
+    recipient_x25519_secret: &[u8; 32],
+    context: &[u8],
+) -> Result<Vec<u8>, &'static str> {
+    let secret = StaticSecret::from(*recipient_x25519_secret);
+    let shared = secret.diffie_hellman(&X25519Public::from(*ephemeral_public));
+    let (cipher, nonce) = sealed_cipher(shared.as_bytes(), ephemeral_public, context);
+    cipher
+        .decrypt(&nonce, ciphertext)
+        .map_err(|_| "decryption failed: authentication check failed")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,4 +194,19 @@ mod tests {
 
         assert!(result.is_err(), "AEAD should detect tampered ciphertext");
     }
+
+    #[test]
+    fn sealed_message_roundtrip_and_context_binding() {
+        let recipient_secret = [7u8; 32];
+        let recipient_pub = X25519Public::from(&StaticSecret::from(recipient_secret));
+
+        let (ephemeral, ciphertext) = seal(b"hello custodian", recipient_pub.as_bytes(), b"ctx");
+        let (ephemeral2, _) = seal(b"hello custodian", recipient_pub.as_bytes(), b"ctx");
+        assert_ne!(ephemeral, ephemeral2, "each message uses a fresh key");
+
+        let plain = open(&ephemeral, &ciphertext, &recipient_secret, b"ctx").unwrap();
+        assert_eq!(plain, b"hello custodian");
+        assert!(open(&ephemeral, &ciphertext, &recipient_secret, b"other").is_err());
+        assert!(open(&ephemeral, &ciphertext, &[8u8; 32], b"ctx").is_err());
+    }
 }
@@ -2,7 +2,7 @@
 
 use blake2::digest::consts::U32;
 use blake2::{Blake2b, Digest};
-use burst_types::{BlockHash, TxHash};
+use burst_types::{BlockHash, NetworkId, TxHash};
 
 type Blake2b256 = Blake2b<U32>;
 
@@ -33,6 +33,19 @@ pub fn hash_block(block_bytes: &[u8]) -> BlockHash {
     BlockHash::new(blake2b_256(block_bytes))
 }
 
+/// Domain prefix for blocks bound to one network.
+pub const NETWORK_BLOCK_DOMAIN: &[u8] = b"burst-network-block";
+
+/// Hash a serialized block under `network`'s domain, so the hash (and any
+/// signature over it) is meaningless on every other network.
+pub fn hash_block_in_network(network: NetworkId, block_bytes: &[u8]) -> BlockHash {
+    BlockHash::new(blake2b_256_multi(&[
+        NETWORK_BLOCK_DOMAIN,
+        &[network.code()],
+        block_bytes,
+    ]))
+}
+
 /// Hash a serialized transaction to produce its `TxHash`.
 pub fn hash_transaction(tx_bytes: &[u8]) -> TxHash {
     TxHash::new(blake2b_256(tx_bytes))
@@ -69,6 +82,16 @@ mod tests {
         assert_eq!(single, multi);
     }
 
+    #[test]
+    fn network_hashes_are_domain_separated() {
+        let bytes = b"test block data";
+        let live = hash_block_in_network(NetworkId::Live, bytes);
+        assert_eq!(live, hash_block_in_network(NetworkId::Live, bytes));
+        assert_ne!(live, hash_block_in_network(NetworkId::Test, bytes));
+        assert_ne!(live, hash_block_in_network(NetworkId::Dev, bytes));
+        assert_ne!(live, hash_block(bytes));
+    }
+
     #[test]
     fn hash_block_returns_blockhash() {
         let h = hash_block(b"test block data");
@@ -5,6 +5,14 @@
 //!
 //! The derivation uses HMAC-SHA512 to produce a 64-byte seed from the mnemonic,
 //! then takes the first 32 bytes as the Ed25519 secret key.
+//!
+//! Phrases may be in any of the BIP39 wordlists; the language is detected
+//! when parsing, trying English first. An optional passphrase (the "25th
+//! word") is mixed into the seed, so the same phrase with a different
+//! passphrase opens a different wallet. The empty passphrase gives the same
+//! keys as before passphrases were supported.
+
+pub use bip39::Language;
 
 use bip39::Mnemonic;
 use burst_types::{KeyPair, PrivateKey, PublicKey};
@@ -26,30 +34,74 @@ pub enum MnemonicError {
 
     #[error("key derivation failed: {0}")]
     DerivationFailed(String),
+
+    #[error("a mnemonic has 12, 15, 18, 21 or 24 words, not {0}")]
+    InvalidWordCount(usize),
+
+    #[error("word {index} is not in the {language} wordlist")]
+    UnknownWord { index: usize, language: Language },
 }
 
+/// Word counts a BIP39 mnemonic can have.
+pub const MNEMONIC_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
 /// Generate a new 24-word BIP39 mnemonic from 256-bit entropy.
 pub fn generate_mnemonic() -> Result<String, MnemonicError> {
+    generate_mnemonic_in(Language::English)
+}
+
+/// Generate a new 24-word BIP39 mnemonic in `language`.
+pub fn generate_mnemonic_in(language: Language) -> Result<String, MnemonicError> {
     let mut entropy = [0u8; 32];
     rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, &mut entropy);
-    let mnemonic = Mnemonic::from_entropy(&entropy)
+    let mnemonic = Mnemonic::from_entropy_in(language, &entropy)
         .map_err(|e| MnemonicError::DerivationFailed(e.to_string()))?;
     Ok(mnemonic.to_string())
 }
 
+/// Parse a phrase in the first wordlist, English first, in which it is a
+/// valid mnemonic. Words shared between lists don't make a phrase
+/// ambiguous, since the checksum rarely holds in more than one.
+fn parse(phrase: &str) -> Result<Mnemonic, MnemonicError> {
+    Language::ALL
+        .iter()
+        .find_map(|&language| Mnemonic::parse_in(language, phrase).ok())
+        .ok_or_else(|| {
+            // Detecting the language names the problem best: the unknown
+            // word, the word count or the checksum.
+            let detail = Mnemonic::parse(phrase).err().map(|e| e.to_string());
+            MnemonicError::InvalidMnemonic(detail.unwrap_or_default())
+        })
+}
+
+/// The wordlist a valid mnemonic phrase is written in.
+pub fn mnemonic_language(mnemonic: &str) -> Result<Language, MnemonicError> {
+    parse(mnemonic).map(|m| m.language())
+}
+
 /// Derive an Ed25519 keypair from a BIP39 mnemonic phrase.
+pub fn keypair_from_mnemonic(mnemonic: &str) -> Result<KeyPair, MnemonicError> {
+    keypair_from_mnemonic_with_passphrase(mnemonic, "")
+}
+
+/// Derive an Ed25519 keypair from a BIP39 mnemonic phrase hardened with
+/// `passphrase`. A wrong passphrase is not an error: it derives the keys of
+/// another, empty wallet.
 ///
 /// Process:
-/// 1. Validate the mnemonic and derive the BIP39 seed (with empty passphrase)
+/// 1. Validate the mnemonic and derive the BIP39 seed with the passphrase
 /// 2. Apply HMAC-SHA512 with the BIP44 path as key to derive a child key
 /// 3. Take the first 32 bytes as the Ed25519 secret key
 /// 4. Derive the corresponding public key
-pub fn keypair_from_mnemonic(mnemonic: &str) -> Result<KeyPair, MnemonicError> {
-    let mnemonic = Mnemonic::parse_normalized(mnemonic)
-        .map_err(|e| MnemonicError::InvalidMnemonic(e.to_string()))?;
+pub fn keypair_from_mnemonic_with_passphrase(
+    mnemonic: &str,
+    passphrase: &str,
+) -> Result<KeyPair, MnemonicError> {
+    let mnemonic = parse(mnemonic)?;
 
-    // BIP39 seed derivation (PBKDF2-HMAC-SHA512 with "mnemonic" as salt, 2048 rounds)
-    let seed = mnemonic.to_seed_normalized("");
+    // BIP39 seed derivation (PBKDF2-HMAC-SHA512 with "mnemonic" + passphrase
+    // as salt, 2048 rounds). Both are NFKD-normalized first.
+    let seed = mnemonic.to_seed(passphrase);
 
     // Derive child key using HMAC-SHA512 with the BIP44 path.
     // We use the path string as the HMAC key and the seed as the message,
@@ -73,7 +125,56 @@ pub fn keypair_from_mnemonic(mnemonic: &str) -> Result<KeyPair, MnemonicError> {
 
 /// Validate that a mnemonic phrase is a valid BIP39 mnemonic.
 pub fn validate_mnemonic(mnemonic: &str) -> bool {
-    Mnemonic::parse_normalized(mnemonic).is_ok()
+    parse(mnemonic).is_ok()
+}
+
+/// Words of `language` starting with `prefix`, for completing a word as it
+/// is typed. English words are unique in their first four letters, so
+/// there a four-letter prefix matches at most one.
+pub fn words_with_prefix(language: Language, prefix: &str) -> Vec<&'static str> {
+    if prefix.is_empty() {
+        return Vec::new();
+    }
+    language
+        .word_list()
+        .iter()
+        .copied()
+        .filter(|word| word.starts_with(prefix))
+        .collect()
+}
+
+/// The words that can end a mnemonic beginning with `preceding`: those
+/// that make its checksum valid. `preceding` holds one word less than a
+/// valid mnemonic, e.g. 23 preceding words leave 8 candidates and 11 leave 128.
+pub fn final_word_candidates(
+    language: Language,
+    preceding: &[&str],
+) -> Result<Vec<&'static str>, MnemonicError> {
+    let count = preceding.len() + 1;
+    if !MNEMONIC_WORD_COUNTS.contains(&count) {
+        return Err(MnemonicError::InvalidWordCount(count));
+    }
+    let words = language.word_list();
+    let mut phrase = String::new();
+    for (index, word) in preceding.iter().enumerate() {
+        let found = language
+            .find_word(word)
+            .ok_or(MnemonicError::UnknownWord { index, language })?;
+        phrase.push_str(words[usize::from(found)]);
+        phrase.push(' ');
+    }
+    let stem = phrase.len();
+
+    // Only the checksum is checked, so trying every word is cheap.
+    let mut candidates = Vec::new();
+    for &word in words {
+        phrase.truncate(stem);
+        phrase.push_str(word);
+        if Mnemonic::parse_in_normalized(language, &phrase).is_ok() {
+            candidates.push(word);
+        }
+    }
+    Ok(candidates)
 }
 
 #[cfg(test)]
@@ -143,4 +244,60 @@ mod tests {
         let kp2 = keypair_from_mnemonic(mnemonic).unwrap();
         assert_eq!(kp.public.0, kp2.public.0);
     }
+
+    #[test]
+    fn passphrase_hardens_the_seed() {
+        let mnemonic = generate_mnemonic().unwrap();
+        let plain = keypair_from_mnemonic(&mnemonic).unwrap();
+        let empty = keypair_from_mnemonic_with_passphrase(&mnemonic, "").unwrap();
+        let hardened = keypair_from_mnemonic_with_passphrase(&mnemonic, "correct horse").unwrap();
+        assert_eq!(plain.public.0, empty.public.0);
+        assert_ne!(plain.public.0, hardened.public.0);
+        let again = keypair_from_mnemonic_with_passphrase(&mnemonic, "correct horse").unwrap();
+        assert_eq!(hardened.public.0, again.public.0);
+    }
+
+    #[test]
+    fn other_wordlists_round_trip() {
+        for language in [Language::Spanish, Language::Japanese, Language::French] {
+            let mnemonic = generate_mnemonic_in(language).unwrap();
+            assert!(validate_mnemonic(&mnemonic), "{language}");
+            assert_eq!(mnemonic_language(&mnemonic).unwrap(), language);
+            keypair_from_mnemonic(&mnemonic).unwrap();
+        }
+        let english = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert_eq!(mnemonic_language(english).unwrap(), Language::English);
+    }
+
+    #[test]
+    fn completion_respects_prefix_and_checksum() {
+        assert_eq!(words_with_prefix(Language::English, "aban"), ["abandon"]);
+        assert!(words_with_prefix(Language::English, "zzz").is_empty());
+
+        let preceding = ["abandon"; 11];
+        let finals = final_word_candidates(Language::English, &preceding).unwrap();
+        assert_eq!(finals.len(), 128);
+        assert!(finals.contains(&"about"));
+        assert!(!finals.contains(&"abandon"));
+        for word in &finals {
+            let phrase = format!("{} {word}", preceding.join(" "));
+            assert!(validate_mnemonic(&phrase));
+        }
+
+        let preceding = ["abandon"; 23];
+        let finals = final_word_candidates(Language::English, &preceding).unwrap();
+        assert_eq!(finals.len(), 8);
+        assert!(finals.contains(&"art"));
+
+        assert!(matches!(
+            final_word_candidates(Language::English, &["abandon"; 12]),
+            Err(MnemonicError::InvalidWordCount(13))
+        ));
+        let mut unknown = ["abandon"; 11];
+        unknown[4] = "nope";
+        assert!(matches!(
+            final_word_candidates(Language::English, &unknown),
+            Err(MnemonicError::UnknownWord { index: 4, .. })
+        ));
+    }
 }
@@ -1,9 +1,15 @@
 //! BURST daemon — entry point for running a BURST node.
 
-use burst_node::NodeConfig;
+mod scenario;
+
+use burst_node::{init_logging, LogFormat, NodeConfig};
 use burst_types::NetworkId;
 use clap::Parser;
 use std::path::PathBuf;
+use std::time::Duration;
+
+/// How often the config file is checked for log filter changes.
+const LOG_CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(5);
 
 #[derive(Parser)]
 #[command(name = "burst-daemon", about = "BURST protocol node daemon")]
@@ -57,6 +63,11 @@ struct Cli {
     #[arg(long, env = "BURST_DISABLE_UPNP")]
     disable_upnp: bool,
 
+    /// Encrypted representative key file (passphrase from
+    /// BURST_REPRESENTATIVE_KEY_PASSWORD). Created on first start.
+    #[arg(long, env = "BURST_REPRESENTATIVE_KEY_FILE")]
+    representative_key_file: Option<PathBuf>,
+
     /// Log level: "trace", "debug", "info", "warn", "error".
     #[arg(long, default_value = "info", env = "BURST_LOG_LEVEL")]
     log_level: String,
@@ -79,18 +90,51 @@ enum Command {
         #[command(subcommand)]
         action: NodeAction,
     },
+    /// Run scripted scenarios against a dev node.
+    #[command(name = "scenario")]
+    Scenario {
+        #[command(subcommand)]
+        action: ScenarioAction,
+    },
 }
 
 #[derive(clap::Subcommand)]
 enum NodeAction {
     /// Run the node.
     Run,
+    /// Migrate the database schema without starting the node.
+    Migrate {
+        /// Schema version to migrate to, up or down (defaults to the newest).
+        #[arg(long)]
+        to: Option<u32>,
+
+        /// Report the steps and the records each would touch, without
+        /// writing anything.
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum ScenarioAction {
+    /// Execute a YAML scenario file, failing on the first unmet expectation.
+    Run {
+        /// Path to the scenario file.
+        file: PathBuf,
+
+        /// RPC endpoint of the dev node (defaults to localhost on --rpc-port).
+        #[arg(long)]
+        rpc_url: Option<String>,
+
+        /// Admin RPC endpoint of the dev node (defaults to localhost on the
+        /// configured admin port).
+        #[arg(long)]
+        admin_url: Option<String>,
+    },
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    burst_utils::init_tracing();
-
     let cli = Cli::parse();
 
     fn parse_network(s: &str) -> NetworkId {
@@ -103,29 +147,32 @@ async fn main() -> anyhow::Result<()> {
 
     let cli_network = cli.network.as_deref().map(parse_network);
 
+    // Logging starts once the config is known, so problems reading it are
+    // reported afterwards.
+    let mut config_warning = None;
     let file_config: Option<NodeConfig> = if let Some(ref config_path) = cli.config {
         match std::fs::read_to_string(config_path) {
             Ok(contents) => match toml::from_str::<NodeConfig>(&contents) {
-                Ok(cfg) => {
-                    tracing::info!("Loaded config from {}", config_path.display());
-                    Some(cfg)
-                }
+                Ok(cfg) => Some(cfg),
                 Err(e) => {
-                    tracing::warn!("Failed to parse config file: {e}, using CLI defaults");
+                    config_warning = Some(format!(
+                        "Failed to parse config file: {e}, using CLI defaults"
+                    ));
                     None
                 }
             },
             Err(e) => {
-                tracing::warn!(
+                config_warning = Some(format!(
                     "Failed to read config file {}: {e}, using CLI defaults",
                     config_path.display()
-                );
+                ));
                 None
             }
         }
     } else {
         None
     };
+    let config_loaded = file_config.is_some();
 
     let enable_upnp = !cli.disable_upnp;
 
@@ -149,6 +196,9 @@ async fn main() -> anyhow::Result<()> {
             enable_faucet: cli.faucet || file_cfg.enable_faucet,
             enable_upnp: enable_upnp && file_cfg.enable_upnp,
             log_level: cli.log_level,
+            representative_key_file: cli
+                .representative_key_file
+                .or(file_cfg.representative_key_file),
             ..file_cfg
         }
     } else {
@@ -167,10 +217,28 @@ async fn main() -> anyhow::Result<()> {
             enable_faucet: cli.faucet,
             enable_upnp,
             log_level: cli.log_level,
+            representative_key_file: cli.representative_key_file,
             ..Default::default()
         }
     };
 
+    let log_format = LogFormat::parse(&config.log_format).unwrap_or_default();
+    let log_reload = init_logging(log_format, &config.log_level, &config.logging)?;
+    match (&cli.config, config_warning) {
+        (Some(path), None) if config_loaded => {
+            tracing::info!("Loaded config from {}", path.display());
+            log_reload.watch_config_file(path.clone(), LOG_CONFIG_POLL_INTERVAL);
+        }
+        (_, Some(warning)) => tracing::warn!("{warning}"),
+        _ => {}
+    }
+    if LogFormat::parse(&config.log_format).is_none() {
+        tracing::warn!(
+            "unknown log_format {:?}, using human-readable logs",
+            config.log_format
+        );
+    }
+
     match cli.command {
         Command::Node { action } => match action {
             NodeAction::Run => {
@@ -206,7 +274,52 @@ async fn main() -> anyhow::Result<()> {
 
                 tracing::info!("BURST daemon exited cleanly");
             }
+            NodeAction::Migrate { to, dry_run } => {
+                let store = burst_node::BurstNode::open_store(&config)?;
+                let migrator = burst_store_lmdb::Migrator::new();
+                let target = to.unwrap_or(migrator.latest());
+                let plan = if dry_run {
+                    migrator.plan(&store, target)?
+                } else {
+                    migrator.migrate_to(&store, target)?
+                };
+                for step in &plan.steps {
+                    tracing::info!(
+                        from = step.from,
+                        to = step.to,
+                        records = step.affected,
+                        "{}{}",
+                        if dry_run { "would run: " } else { "ran: " },
+                        step.description
+                    );
+                }
+                tracing::info!(
+                    from = plan.from,
+                    to = plan.to,
+                    steps = plan.steps.len(),
+                    records = plan.affected(),
+                    dry_run,
+                    "schema migration {}",
+                    if dry_run { "planned" } else { "finished" }
+                );
+            }
         },
+        Command::Scenario {
+            action:
+                ScenarioAction::Run {
+                    file,
+                    rpc_url,
+                    admin_url,
+                },
+        } => {
+            let scenario = scenario::Scenario::load(&file)?;
+            let rpc_url = rpc_url.unwrap_or_else(|| format!("http://127.0.0.1:{}", cli.rpc_port));
+            let admin_url =
+                admin_url.unwrap_or_else(|| format!("http://127.0.0.1:{}", config.admin_rpc.port));
+            scenario::ScenarioRunner::new(rpc_url, admin_url, config.admin_rpc.auth_token)?
+                .run(&scenario)
+                .await?;
+        }
     }
 
     Ok(())
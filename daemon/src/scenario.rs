@@ -0,0 +1,474 @@
+//! Declarative end-to-end scenarios run against a dev node.
+//!
+//! A scenario is a YAML file naming a sequence of steps — create wallets,
+//! burn, send, endorse, challenge, propose, vote, advance time — followed by
+//! expectations on the resulting account and proposal state:
+//!
+//! ```yaml
+//! name: endorse a newcomer
+//! steps:
+//!   - create_wallet: { name: alice, verified: true }
+//!   - create_wallet: { name: bob }
+//!   - burn: { wallet: alice, amount: 100 }
+//!   - endorse: { from: alice, target: bob, amount: 10 }
+//!   - advance_time: { seconds: 86400 }
+//!   - expect: { wallet: alice, total_brn_burned: 110 }
+//! ```
+//!
+//! The runner drives the node over JSON-RPC: it holds each wallet's key and
+//! signs through the faucet-gated `*_simple` actions, and moves time with
+//! the `dev_*` actions on the admin listener. The node must run on the dev
+//! network with RPC, the admin listener and the faucet enabled.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context};
+use serde::Deserialize;
+use serde_json::json;
+
+fn default_true() -> bool {
+    true
+}
+
+/// A parsed scenario file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub name: String,
+    /// Confirm blocks as soon as they are accepted, so each step sees the
+    /// previous one's effects without waiting for elections.
+    #[serde(default = "default_true")]
+    pub instant_confirmation: bool,
+    #[serde(deserialize_with = "serde_yaml::with::singleton_map_recursive::deserialize")]
+    pub steps: Vec<Step>,
+}
+
+/// One scenario step. Wallets and proposals are referred to by the names
+/// the scenario gives them.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+    /// Create a wallet; `verified` wallets are verified and funded by the
+    /// faucet.
+    CreateWallet {
+        name: String,
+        #[serde(default)]
+        verified: bool,
+    },
+    /// Burn BRN into TRST.
+    Burn { wallet: String, amount: u128 },
+    /// Send TRST; the receiver pockets it unless `receive` is false.
+    Send {
+        from: String,
+        to: String,
+        amount: u128,
+        #[serde(default = "default_true")]
+        receive: bool,
+    },
+    /// Pocket every pending transfer of a wallet.
+    Receive { wallet: String },
+    /// Endorse a wallet by burning BRN.
+    Endorse {
+        from: String,
+        target: String,
+        amount: u128,
+    },
+    /// Challenge a wallet by staking BRN.
+    Challenge {
+        from: String,
+        target: String,
+        stake: u128,
+    },
+    /// Propose a parameter change, naming the proposal `name`.
+    Propose {
+        wallet: String,
+        name: String,
+        param: String,
+        value: u128,
+    },
+    /// Vote `yea`, `nay` or `abstain` on a proposal.
+    Vote {
+        wallet: String,
+        proposal: String,
+        vote: String,
+    },
+    /// Move a proposal into its next governance phase.
+    AdvancePhase { proposal: String },
+    /// Move the node's logical clock forward.
+    AdvanceTime { seconds: u64 },
+    /// Assert fields of `account_info` for a wallet.
+    Expect {
+        wallet: String,
+        #[serde(flatten)]
+        fields: BTreeMap<String, serde_json::Value>,
+    },
+    /// Assert fields of `governance_proposal_info` for a proposal.
+    ExpectProposal {
+        proposal: String,
+        #[serde(flatten)]
+        fields: BTreeMap<String, serde_json::Value>,
+    },
+}
+
+impl Scenario {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Self::parse(&contents).with_context(|| format!("invalid scenario {}", path.display()))
+    }
+
+    pub fn parse(yaml: &str) -> anyhow::Result<Self> {
+        Ok(serde_yaml::from_str(yaml)?)
+    }
+}
+
+struct Wallet {
+    address: String,
+    private_key: String,
+}
+
+/// Runs scenarios against the node at `rpc_url`, and `admin_url` for the
+/// `dev_*` actions.
+pub struct ScenarioRunner {
+    http: reqwest::Client,
+    rpc_url: String,
+    admin_url: String,
+    /// Bearer token for the admin listener, if it requires one.
+    admin_token: Option<String>,
+    wallets: HashMap<String, Wallet>,
+    proposals: HashMap<String, String>,
+}
+
+impl ScenarioRunner {
+    pub fn new(
+        rpc_url: impl Into<String>,
+        admin_url: impl Into<String>,
+        admin_token: Option<String>,
+    ) -> anyhow::Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(120))
+            .build()?;
+        Ok(Self {
+            http,
+            rpc_url: rpc_url.into(),
+            admin_url: admin_url.into(),
+            admin_token,
+            wallets: HashMap::new(),
+            proposals: HashMap::new(),
+        })
+    }
+
+    /// Run every step in order, stopping at the first failure.
+    pub async fn run(&mut self, scenario: &Scenario) -> anyhow::Result<()> {
+        tracing::info!(scenario = %scenario.name, steps = scenario.steps.len(), "running scenario");
+        self.call_admin(
+            "dev_instant_confirmation",
+            json!({ "enabled": scenario.instant_confirmation }),
+        )
+        .await?;
+        for (index, step) in scenario.steps.iter().enumerate() {
+            self.step(step)
+                .await
+                .with_context(|| format!("step {} ({step:?}) failed", index + 1))?;
+            tracing::info!(step = index + 1, "scenario step passed");
+        }
+        tracing::info!(scenario = %scenario.name, "scenario passed");
+        Ok(())
+    }
+
+    async fn step(&mut self, step: &Step) -> anyhow::Result<()> {
+        match step {
+            Step::CreateWallet { name, verified } => {
+                if self.wallets.contains_key(name) {
+                    bail!("wallet {name} already exists");
+                }
+                let created = self.call("wallet_create_full", json!({})).await?;
+                let wallet = Wallet {
+                    address: string_field(&created, "address")?,
+                    private_key: string_field(&created, "private_key")?,
+                };
+                if *verified {
+                    self.call("faucet", json!({ "account": wallet.address }))
+                        .await?;
+                }
+                self.wallets.insert(name.clone(), wallet);
+            }
+            Step::Burn { wallet, amount } => {
+                let key = &self.wallet(wallet)?.private_key;
+                self.call(
+                    "burn_simple",
+                    json!({ "private_key": key, "amount": amount.to_string() }),
+                )
+                .await?;
+            }
+            Step::Send {
+                from,
+                to,
+                amount,
+                receive,
+            } => {
+                let params = json!({
+                    "private_key": self.wallet(from)?.private_key,
+                    "destination": self.wallet(to)?.address,
+                    "amount": amount.to_string(),
+                });
+                self.call("send_simple", params).await?;
+                if *receive {
+                    self.receive_all(to).await?;
+                }
+            }
+            Step::Receive { wallet } => self.receive_all(wallet).await?,
+            Step::Endorse {
+                from,
+                target,
+                amount,
+            } => {
+                self.spend_brn("endorse_simple", from, target, *amount)
+                    .await?
+            }
+            Step::Challenge {
+                from,
+                target,
+                stake,
+            } => {
+                self.spend_brn("challenge_simple", from, target, *stake)
+                    .await?
+            }
+            Step::Propose {
+                wallet,
+                name,
+                param,
+                value,
+            } => {
+                let params = json!({
+                    "private_key": self.wallet(wallet)?.private_key,
+                    "param": param,
+                    "new_value": value.to_string(),
+                });
+                let proposed = self.call("governance_propose_simple", params).await?;
+                self.proposals
+                    .insert(name.clone(), string_field(&proposed, "proposal_hash")?);
+            }
+            Step::Vote {
+                wallet,
+                proposal,
+                vote,
+            } => {
+                let params = json!({
+                    "private_key": self.wallet(wallet)?.private_key,
+                    "proposal_hash": self.proposal(proposal)?,
+                    "vote": vote,
+                });
+                self.call("governance_vote_simple", params).await?;
+            }
+            Step::AdvancePhase { proposal } => {
+                let params = json!({ "proposal": self.proposal(proposal)? });
+                self.call_admin("dev_governance_advance", params).await?;
+            }
+            Step::AdvanceTime { seconds } => {
+                self.call_admin("dev_clock_advance", json!({ "seconds": seconds }))
+                    .await?;
+            }
+            Step::Expect { wallet, fields } => {
+                let params = json!({ "account": self.wallet(wallet)?.address });
+                let info = self.call("account_info", params).await?;
+                check_fields(&info, fields)?;
+            }
+            Step::ExpectProposal { proposal, fields } => {
+                let params = json!({ "hash": self.proposal(proposal)? });
+                let info = self.call("governance_proposal_info", params).await?;
+                check_fields(&info, fields)?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn spend_brn(
+        &self,
+        action: &str,
+        from: &str,
+        target: &str,
+        amount: u128,
+    ) -> anyhow::Result<()> {
+        let params = json!({
+            "private_key": self.wallet(from)?.private_key,
+            "target": self.wallet(target)?.address,
+            "amount": amount.to_string(),
+        });
+        self.call(action, params).await?;
+        Ok(())
+    }
+
+    async fn receive_all(&self, name: &str) -> anyhow::Result<()> {
+        let wallet = self.wallet(name)?;
+        let pending = self
+            .call("account_pending", json!({ "account": wallet.address }))
+            .await?;
+        let entries = pending
+            .get("pending")
+            .and_then(|p| p.as_array())
+            .ok_or_else(|| anyhow!("malformed account_pending response"))?;
+        for entry in entries {
+            let params = json!({
+                "private_key": wallet.private_key,
+                "send_block_hash": string_field(entry, "hash")?,
+            });
+            self.call("receive_simple", params).await?;
+        }
+        Ok(())
+    }
+
+    fn wallet(&self, name: &str) -> anyhow::Result<&Wallet> {
+        self.wallets
+            .get(name)
+            .ok_or_else(|| anyhow!("unknown wallet {name}"))
+    }
+
+    fn proposal(&self, name: &str) -> anyhow::Result<&str> {
+        self.proposals
+            .get(name)
+            .map(String::as_str)
+            .ok_or_else(|| anyhow!("unknown proposal {name}"))
+    }
+
+    /// Send a JSON-RPC request to the public listener and return its
+    /// `result`.
+    async fn call(
+        &self,
+        action: &str,
+        params: serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        self.post(self.http.post(&self.rpc_url), action, params)
+            .await
+    }
+
+    /// Send a JSON-RPC request to the admin listener and return its
+    /// `result`.
+    async fn call_admin(
+        &self,
+        action: &str,
+        params: serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        let mut request = self.http.post(&self.admin_url);
+        if let Some(token) = &self.admin_token {
+            request = request.bearer_auth(token);
+        }
+        self.post(request, action, params).await
+    }
+
+    async fn post(
+        &self,
+        request: reqwest::RequestBuilder,
+        action: &str,
+        mut params: serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        params
+            .as_object_mut()
+            .ok_or_else(|| anyhow!("params must be a JSON object"))?
+            .insert("action".into(), json!(action));
+        let response: serde_json::Value = request
+            .json(&params)
+            .send()
+            .await
+            .with_context(|| format!("{action} request failed"))?
+            .error_for_status()?
+            .json()
+            .await?;
+        if let Some(error) = response.get("error") {
+            let message = error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .or(error.as_str())
+                .unwrap_or("unknown error");
+            bail!("{action}: {message}");
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow!("{action}: response has no result"))
+    }
+}
+
+fn string_field(value: &serde_json::Value, field: &str) -> anyhow::Result<String> {
+    value
+        .get(field)
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("response has no {field}"))
+}
+
+/// Compare `expected` fields with `actual`, treating numbers and their
+/// decimal strings as equal so balances can be written unquoted.
+fn check_fields(
+    actual: &serde_json::Value,
+    expected: &BTreeMap<String, serde_json::Value>,
+) -> anyhow::Result<()> {
+    let render = |value: &serde_json::Value| match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    let mismatches: Vec<String> = expected
+        .iter()
+        .filter_map(|(field, want)| match actual.get(field) {
+            Some(got) if render(got) == render(want) => None,
+            Some(got) => Some(format!(
+                "{field}: expected {}, got {}",
+                render(want),
+                render(got)
+            )),
+            None => Some(format!("{field}: not in response")),
+        })
+        .collect();
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        bail!("{}", mismatches.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_steps_and_checks_expectations() {
+        let scenario = Scenario::parse(
+            r#"
+name: endorse a newcomer
+steps:
+  - create_wallet: { name: alice, verified: true }
+  - burn: { wallet: alice, amount: 340282366920938463463374607431768211455 }
+  - send: { from: alice, to: bob, amount: 5, receive: false }
+  - advance_time: { seconds: 86400 }
+  - expect: { wallet: alice, trst_balance: 40, verification_state: verified }
+"#,
+        )
+        .unwrap();
+        assert!(scenario.instant_confirmation);
+        assert_eq!(scenario.steps.len(), 5);
+        assert!(matches!(
+            scenario.steps[1],
+            Step::Burn {
+                amount: u128::MAX,
+                ..
+            }
+        ));
+        assert!(matches!(
+            scenario.steps[2],
+            Step::Send { receive: false, .. }
+        ));
+        let Step::Expect { fields, .. } = &scenario.steps[4] else {
+            panic!("expected an expect step");
+        };
+
+        let info = json!({ "trst_balance": "40", "verification_state": "verified" });
+        assert!(check_fields(&info, fields).is_ok());
+        let info = json!({ "trst_balance": "39", "verification_state": "verified" });
+        let err = check_fields(&info, fields).unwrap_err().to_string();
+        assert_eq!(err, "trst_balance: expected 40, got 39");
+
+        assert!(Scenario::parse("name: x\nsteps:\n  - teleport: {}\n").is_err());
+    }
+}
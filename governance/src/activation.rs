@@ -0,0 +1,254 @@
+//! Activation records for parameter changes.
+//!
+//! A proposal that passes promotion activates at a timestamp derived from
+//! its promotion deadline, not from any node's clock, so every node agrees
+//! on when the new parameters take effect. The GovernanceActivation block on
+//! the genesis chain commits to a [`GovernanceQuorumCertificate`] — the
+//! tallies that carried the proposal, the thresholds they met, the
+//! activation timestamp and the resulting params hash — through its
+//! `origin` field. Each node rebuilds the certificate from its own copy of
+//! the votes and only applies the change when the hashes agree; from the
+//! activation timestamp on, blocks stamped with the old params hash are
+//! rejected.
+
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use burst_types::{BlockHash, ProtocolParams, Timestamp, TxHash};
+use serde::{Deserialize, Serialize};
+
+use crate::engine::GovernanceEngine;
+use crate::error::GovernanceError;
+use crate::proposal::{GovernancePhase, Proposal, ProposalContent};
+
+/// Votes cast in one voting phase.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoteTally {
+    pub yea: u32,
+    pub nay: u32,
+    pub abstain: u32,
+}
+
+impl VoteTally {
+    /// Turnout in basis points of `eligible` voters.
+    pub fn participation_bps(&self, eligible: u32) -> u32 {
+        let total = u64::from(self.yea) + u64::from(self.nay) + u64::from(self.abstain);
+        (total * 10_000)
+            .checked_div(u64::from(eligible))
+            .map_or(0, |bps| bps.min(u64::from(u32::MAX)) as u32)
+    }
+
+    /// Yea share of the yea and nay votes in basis points.
+    pub fn approval_bps(&self) -> u32 {
+        let decided = u64::from(self.yea) + u64::from(self.nay);
+        (u64::from(self.yea) * 10_000)
+            .checked_div(decided)
+            .unwrap_or(0) as u32
+    }
+}
+
+/// Evidence that a proposal passed, committed in its activation block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GovernanceQuorumCertificate {
+    pub proposal: TxHash,
+    pub exploration: VoteTally,
+    pub promotion: VoteTally,
+    pub total_eligible_voters: u32,
+    /// Base quorum the promotion vote was held to. The adaptive quorum can
+    /// only be higher, so a passing vote always meets it.
+    pub quorum_bps: u32,
+    pub supermajority_bps: u32,
+    /// Promotion approval under a conviction tally, which the raw counts
+    /// cannot show. `None`, and left out of the hash, when votes count once
+    /// each.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_bps: Option<u32>,
+    /// When the new parameters take effect.
+    pub activation_at: Timestamp,
+    /// Hash of the parameters after the change.
+    pub new_params_hash: BlockHash,
+}
+
+impl GovernanceQuorumCertificate {
+    /// Certificate for `proposal` once it has passed promotion, with the
+    /// thresholds taken from `params`, the parameters in force before the
+    /// change. `None` while the proposal has not passed.
+    pub fn for_proposal(
+        proposal: &Proposal,
+        params: &ProtocolParams,
+        new_params_hash: BlockHash,
+    ) -> Option<Self> {
+        if !matches!(
+            proposal.phase,
+            GovernancePhase::Activation | GovernancePhase::Activated
+        ) {
+            return None;
+        }
+        Some(Self {
+            proposal: proposal.hash,
+            exploration: VoteTally {
+                yea: proposal.exploration_votes_yea,
+                nay: proposal.exploration_votes_nay,
+                abstain: proposal.exploration_votes_abstain,
+            },
+            promotion: VoteTally {
+                yea: proposal.promotion_votes_yea,
+                nay: proposal.promotion_votes_nay,
+                abstain: proposal.promotion_votes_abstain,
+            },
+            total_eligible_voters: proposal.total_eligible_voters,
+            quorum_bps: params.governance_quorum_bps,
+            supermajority_bps: GovernanceEngine::required_supermajority(proposal, params),
+            approval_bps: None,
+            activation_at: proposal.activation_at?,
+            new_params_hash,
+        })
+    }
+
+    /// Hash committed in the activation block's `origin` field.
+    pub fn hash(&self) -> TxHash {
+        let bytes = bincode::serialize(self).expect("certificate serialization is infallible");
+        let digest = Blake2b::<U32>::digest(&bytes);
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&digest);
+        TxHash::new(hash)
+    }
+
+    /// Check that the promotion tally meets the recorded thresholds.
+    pub fn verify(&self) -> Result<(), GovernanceError> {
+        let participation = self.promotion.participation_bps(self.total_eligible_voters);
+        if participation < self.quorum_bps {
+            return Err(GovernanceError::QuorumNotMet {
+                have_bps: participation,
+                need_bps: self.quorum_bps,
+            });
+        }
+        let approval = self
+            .approval_bps
+            .unwrap_or_else(|| self.promotion.approval_bps());
+        if approval < self.supermajority_bps {
+            return Err(GovernanceError::SupermajorityNotMet {
+                have_bps: approval,
+                need_bps: self.supermajority_bps,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl GovernanceEngine {
+    /// [`GovernanceQuorumCertificate::for_proposal`], with the promotion
+    /// approval this engine counted under a conviction tally.
+    pub fn quorum_certificate(
+        &self,
+        proposal: &Proposal,
+        params: &ProtocolParams,
+        new_params_hash: BlockHash,
+    ) -> Option<GovernanceQuorumCertificate> {
+        let mut certificate =
+            GovernanceQuorumCertificate::for_proposal(proposal, params, new_params_hash)?;
+        let opened = proposal.promotion_started_at?;
+        certificate.approval_bps = self.conviction_support_bps(
+            proposal,
+            opened,
+            Self::promotion_closes_at(proposal, params)?,
+            params,
+        );
+        Some(certificate)
+    }
+
+    /// Latest activation timestamp among activated parameter changes.
+    pub fn last_activation_at(&self) -> Option<Timestamp> {
+        self.all_proposals()
+            .filter(|p| p.phase == GovernancePhase::Activated)
+            .filter(|p| {
+                !matches!(
+                    p.content,
+                    ProposalContent::ConstitutionalAmendment { .. }
+                        | ProposalContent::TreasuryGrant { .. }
+                )
+            })
+            .filter_map(|p| p.activation_at)
+            .max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::GovernableParam;
+    use burst_types::WalletAddress;
+
+    #[test]
+    fn certificate_commits_to_tally_and_deterministic_activation_time() {
+        let params = ProtocolParams::burst_defaults();
+        let mut proposal = Proposal {
+            hash: TxHash::new([7; 32]),
+            proposer: WalletAddress::new("brst_proposer"),
+            phase: GovernancePhase::Promotion,
+            content: ProposalContent::ParameterChange {
+                param: GovernableParam::BrnRate,
+                new_value: 1,
+            },
+            endorsement_count: 10,
+            total_eligible_voters: 100,
+            exploration_started_at: Some(Timestamp::new(1_000)),
+            exploration_votes_yea: 90,
+            exploration_votes_nay: 5,
+            exploration_votes_abstain: 0,
+            cooldown_started_at: Some(Timestamp::new(2_000)),
+            promotion_started_at: Some(Timestamp::new(3_000)),
+            promotion_votes_yea: 85,
+            promotion_votes_nay: 10,
+            promotion_votes_abstain: 5,
+            round: 0,
+            created_at: Timestamp::new(0),
+            activation_at: None,
+        };
+        assert!(
+            GovernanceQuorumCertificate::for_proposal(&proposal, &params, BlockHash::ZERO)
+                .is_none()
+        );
+
+        // Whenever a node gets round to counting, activation lands on the
+        // same timestamp.
+        let counted_at = 3_000
+            + params.governance_promotion_duration_secs
+            + params.governance_propagation_buffer_secs;
+        let engine = GovernanceEngine::new();
+        let mut late = proposal.clone();
+        engine
+            .try_advance(&mut proposal, Timestamp::new(counted_at), &params)
+            .unwrap();
+        engine
+            .try_advance(&mut late, Timestamp::new(counted_at + 500), &params)
+            .unwrap();
+        assert_eq!(proposal.activation_at, late.activation_at);
+        assert_eq!(
+            proposal.activation_at,
+            Some(Timestamp::new(
+                counted_at + params.governance_propagation_buffer_secs
+            ))
+        );
+
+        let new_hash = BlockHash::new([3; 32]);
+        let cert = GovernanceQuorumCertificate::for_proposal(&proposal, &params, new_hash).unwrap();
+        assert_eq!(cert.activation_at, proposal.activation_at.unwrap());
+        assert_eq!(cert.supermajority_bps, params.governance_supermajority_bps);
+        assert!(cert.verify().is_ok());
+        assert_eq!(
+            cert.hash(),
+            GovernanceQuorumCertificate::for_proposal(&late, &params, new_hash)
+                .unwrap()
+                .hash()
+        );
+
+        let mut forged = cert.clone();
+        forged.promotion.yea = 40;
+        forged.promotion.nay = 55;
+        assert_ne!(forged.hash(), cert.hash());
+        assert!(matches!(
+            forged.verify(),
+            Err(GovernanceError::SupermajorityNotMet { .. })
+        ));
+    }
+}
@@ -0,0 +1,376 @@
+//! Sealed ballots — commit-reveal voting for proposals that opt in.
+//!
+//! While a sealed proposal's voting window is open, voters publish only a
+//! commitment to their ballot. Once it closes they reveal the ballot, and
+//! only revealed ballots enter the tally, so no running count exists while
+//! votes can still be cast. Reveals are accepted during the first half of
+//! the propagation buffer that precedes counting; the second half lets the
+//! last reveals reach every node before the votes are counted.
+//!
+//! Commitments live in a [`BallotBox`] kept beside the
+//! [`GovernanceEngine`], which does the tallying.
+
+use std::collections::{HashMap, HashSet};
+
+use burst_transactions::governance::{BallotMode, GovernanceVote};
+use burst_types::{ProtocolParams, Timestamp, TxHash, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+use crate::engine::GovernanceEngine;
+use crate::error::GovernanceError;
+use crate::proposal::{GovernancePhase, Proposal};
+
+/// Domain separator for ballot commitments.
+const COMMITMENT_DOMAIN: &[u8] = b"burst-sealed-ballot-v1";
+
+/// Commitment to `voter`'s sealed ballot on `proposal`. `sealed` is the
+/// vote byte followed by a 31-byte random salt.
+pub fn ballot_commitment(proposal: &TxHash, voter: &WalletAddress, sealed: &[u8; 32]) -> [u8; 32] {
+    burst_crypto::blake2b_256_multi(&[
+        COMMITMENT_DOMAIN,
+        proposal.as_bytes(),
+        voter.as_str().as_bytes(),
+        sealed,
+    ])
+}
+
+/// Seconds (start, end) during which ballots of a vote that opened at
+/// `started` and ran `duration_secs` may be revealed.
+pub fn reveal_window(
+    started: Timestamp,
+    duration_secs: u64,
+    params: &ProtocolParams,
+) -> (u64, u64) {
+    let open = started.as_secs().saturating_add(duration_secs);
+    (
+        open,
+        open.saturating_add(params.governance_propagation_buffer_secs / 2),
+    )
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct SealedBallot {
+    commitment: [u8; 32],
+    revealed: bool,
+}
+
+/// Ballot modes and commitments for sealed proposals.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BallotBox {
+    /// Proposals voted on with sealed ballots.
+    sealed: HashSet<TxHash>,
+    /// Commitments per vote, keyed by proposal and the time its voting
+    /// window opened; a new phase or round starts a new vote.
+    commitments: HashMap<(TxHash, u64), HashMap<WalletAddress, SealedBallot>>,
+}
+
+impl BallotBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record how `proposal` is voted on.
+    pub fn set_mode(&mut self, proposal: TxHash, mode: BallotMode) {
+        match mode {
+            BallotMode::Sealed => self.sealed.insert(proposal),
+            BallotMode::Open => self.sealed.remove(&proposal),
+        };
+    }
+
+    pub fn mode(&self, proposal: &TxHash) -> BallotMode {
+        if self.sealed.contains(proposal) {
+            BallotMode::Sealed
+        } else {
+            BallotMode::Open
+        }
+    }
+
+    /// Commitments and reveals in the vote `proposal` is holding, or held
+    /// last.
+    pub fn sealed_counts(&self, proposal: &Proposal) -> (u32, u32) {
+        let Some(started) = proposal
+            .promotion_started_at
+            .or(proposal.exploration_started_at)
+        else {
+            return (0, 0);
+        };
+        self.commitments
+            .get(&(proposal.hash, started.as_secs()))
+            .map_or((0, 0), |ballots| {
+                (
+                    ballots.len() as u32,
+                    ballots.values().filter(|b| b.revealed).count() as u32,
+                )
+            })
+    }
+
+    /// Drop the commitments of proposals that are no longer voting.
+    pub fn prune(&mut self, engine: &GovernanceEngine) {
+        self.commitments.retain(|(hash, _), _| {
+            engine.get_proposal(hash).is_some_and(|p| {
+                matches!(
+                    p.phase,
+                    GovernancePhase::Exploration
+                        | GovernancePhase::Cooldown
+                        | GovernancePhase::Promotion
+                )
+            })
+        });
+    }
+}
+
+impl GovernanceEngine {
+    /// Cast an open vote in whichever vote the proposal is holding.
+    pub fn cast_vote(
+        &mut self,
+        ballots: &BallotBox,
+        proposal_hash: &TxHash,
+        voter: &WalletAddress,
+        vote: GovernanceVote,
+        now: Timestamp,
+        params: &ProtocolParams,
+    ) -> Result<(), GovernanceError> {
+        if ballots.mode(proposal_hash) == BallotMode::Sealed {
+            return Err(GovernanceError::SealedBallotRequired);
+        }
+        match self.cast_exploration_vote(proposal_hash, voter, vote, now, params) {
+            Err(GovernanceError::WrongPhase) => {
+                self.cast_promotion_vote(proposal_hash, voter, vote, now, params)
+            }
+            result => result,
+        }
+    }
+
+    /// Record `voter`'s commitment while a sealed proposal's voting window
+    /// is open. Each voter commits once per vote.
+    pub fn commit_ballot(
+        &self,
+        ballots: &mut BallotBox,
+        proposal_hash: &TxHash,
+        voter: &WalletAddress,
+        commitment: [u8; 32],
+        now: Timestamp,
+        params: &ProtocolParams,
+    ) -> Result<(), GovernanceError> {
+        let proposal = self
+            .get_proposal(proposal_hash)
+            .ok_or_else(|| GovernanceError::ProposalNotFound(proposal_hash.to_string()))?;
+        if ballots.mode(proposal_hash) != BallotMode::Sealed {
+            return Err(GovernanceError::NotSealed);
+        }
+        let (started, duration) =
+            Self::voting_window(proposal, params).ok_or(GovernanceError::WrongPhase)?;
+        if started.has_expired(duration, now) {
+            return Err(GovernanceError::VotingClosed);
+        }
+
+        let vote = ballots
+            .commitments
+            .entry((*proposal_hash, started.as_secs()))
+            .or_default();
+        if vote.contains_key(voter) {
+            return Err(GovernanceError::AlreadyVoted(voter.to_string()));
+        }
+        vote.insert(
+            voter.clone(),
+            SealedBallot {
+                commitment,
+                revealed: false,
+            },
+        );
+        Ok(())
+    }
+
+    /// Open `voter`'s committed ballot during the reveal window and add it
+    /// to the tally.
+    pub fn reveal_ballot(
+        &mut self,
+        ballots: &mut BallotBox,
+        proposal_hash: &TxHash,
+        voter: &WalletAddress,
+        sealed: &[u8; 32],
+        now: Timestamp,
+        params: &ProtocolParams,
+    ) -> Result<GovernanceVote, GovernanceError> {
+        let proposal = self
+            .get_proposal(proposal_hash)
+            .ok_or_else(|| GovernanceError::ProposalNotFound(proposal_hash.to_string()))?;
+        if ballots.mode(proposal_hash) != BallotMode::Sealed {
+            return Err(GovernanceError::NotSealed);
+        }
+        let (started, duration) =
+            Self::voting_window(proposal, params).ok_or(GovernanceError::WrongPhase)?;
+        let (open, close) = reveal_window(started, duration, params);
+        if now.as_secs() < open {
+            return Err(GovernanceError::PhaseNotExpired);
+        }
+        if now.as_secs() >= close {
+            return Err(GovernanceError::RevealWindowClosed);
+        }
+        let phase = proposal.phase.clone();
+
+        let ballot = ballots
+            .commitments
+            .get_mut(&(*proposal_hash, started.as_secs()))
+            .and_then(|vote| vote.get_mut(voter))
+            .ok_or_else(|| GovernanceError::NoCommitment(voter.to_string()))?;
+        if ballot.revealed {
+            return Err(GovernanceError::AlreadyVoted(voter.to_string()));
+        }
+        if ballot_commitment(proposal_hash, voter, sealed) != ballot.commitment {
+            return Err(GovernanceError::CommitmentMismatch);
+        }
+        let vote = GovernanceVote::from_byte(sealed[0]).ok_or_else(|| {
+            GovernanceError::Other(format!("invalid vote byte {} in ballot", sealed[0]))
+        })?;
+
+        self.tally_vote(proposal_hash, voter, vote, phase, now)?;
+        ballot.revealed = true;
+        Ok(vote)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::GovernableParam;
+    use crate::proposal::ProposalContent;
+
+    fn sealed(vote: GovernanceVote, salt: u8) -> [u8; 32] {
+        let mut ballot = [salt; 32];
+        ballot[0] = vote.as_byte();
+        ballot
+    }
+
+    #[test]
+    fn sealed_ballots_count_only_once_revealed() {
+        let params = ProtocolParams::burst_defaults();
+        let hash = TxHash::new([9; 32]);
+        let mut engine = GovernanceEngine::new();
+        engine
+            .submit_emergency_proposal(
+                &mut Proposal {
+                    hash,
+                    proposer: WalletAddress::new("brst_proposer"),
+                    phase: GovernancePhase::Proposal,
+                    content: ProposalContent::Emergency {
+                        description: "halt".into(),
+                        param: GovernableParam::BrnRate,
+                        new_value: 1,
+                    },
+                    endorsement_count: 0,
+                    total_eligible_voters: 10,
+                    exploration_started_at: None,
+                    exploration_votes_yea: 0,
+                    exploration_votes_nay: 0,
+                    exploration_votes_abstain: 0,
+                    cooldown_started_at: None,
+                    promotion_started_at: None,
+                    promotion_votes_yea: 0,
+                    promotion_votes_nay: 0,
+                    promotion_votes_abstain: 0,
+                    round: 0,
+                    created_at: Timestamp::new(1_000),
+                    activation_at: None,
+                },
+                Timestamp::new(1_000),
+            )
+            .unwrap();
+        let started = engine
+            .get_proposal(&hash)
+            .unwrap()
+            .exploration_started_at
+            .unwrap();
+        let mut ballots = BallotBox::new();
+        ballots.set_mode(hash, BallotMode::Sealed);
+
+        let alice = WalletAddress::new("brst_alice");
+        let bob = WalletAddress::new("brst_bob");
+        let during = Timestamp::new(started.as_secs() + 10);
+        assert!(matches!(
+            engine.cast_vote(
+                &ballots,
+                &hash,
+                &alice,
+                GovernanceVote::Yea,
+                during,
+                &params
+            ),
+            Err(GovernanceError::SealedBallotRequired)
+        ));
+
+        let alice_ballot = sealed(GovernanceVote::Yea, 1);
+        let bob_ballot = sealed(GovernanceVote::Nay, 2);
+        for (voter, ballot) in [(&alice, &alice_ballot), (&bob, &bob_ballot)] {
+            engine
+                .commit_ballot(
+                    &mut ballots,
+                    &hash,
+                    voter,
+                    ballot_commitment(&hash, voter, ballot),
+                    during,
+                    &params,
+                )
+                .unwrap();
+        }
+        let proposal = engine.get_proposal(&hash).unwrap();
+        assert_eq!(ballots.sealed_counts(proposal), (2, 0));
+        assert_eq!(proposal.exploration_votes_yea, 0);
+        assert!(matches!(
+            engine.reveal_ballot(&mut ballots, &hash, &alice, &alice_ballot, during, &params),
+            Err(GovernanceError::PhaseNotExpired)
+        ));
+
+        let (open, close) = reveal_window(started, 86_400, &params);
+        let revealing = Timestamp::new(open);
+        assert!(matches!(
+            engine.commit_ballot(&mut ballots, &hash, &alice, [0; 32], revealing, &params),
+            Err(GovernanceError::VotingClosed)
+        ));
+        assert!(matches!(
+            engine.reveal_ballot(
+                &mut ballots,
+                &hash,
+                &alice,
+                &sealed(GovernanceVote::Nay, 1),
+                revealing,
+                &params
+            ),
+            Err(GovernanceError::CommitmentMismatch)
+        ));
+        assert_eq!(
+            engine
+                .reveal_ballot(
+                    &mut ballots,
+                    &hash,
+                    &alice,
+                    &alice_ballot,
+                    revealing,
+                    &params
+                )
+                .unwrap(),
+            GovernanceVote::Yea
+        );
+        assert!(matches!(
+            engine.reveal_ballot(
+                &mut ballots,
+                &hash,
+                &bob,
+                &bob_ballot,
+                Timestamp::new(close),
+                &params
+            ),
+            Err(GovernanceError::RevealWindowClosed)
+        ));
+
+        let proposal = engine.get_proposal(&hash).unwrap();
+        assert_eq!(ballots.sealed_counts(proposal), (2, 1));
+        assert_eq!(
+            (
+                proposal.exploration_votes_yea,
+                proposal.exploration_votes_nay
+            ),
+            (1, 0)
+        );
+    }
+}
@@ -0,0 +1,221 @@
+//! Conviction voting — an experimental tally for test networks.
+//!
+//! Under [`GovernanceTallyMode::Conviction`] a vote weighs more the longer
+//! it has stood when its voting window closes: a vote cast at the last
+//! moment counts once, and one that stood for the whole
+//! `governance_conviction_period_secs` counts four times, growing linearly
+//! in between. Conviction only shifts the supermajority; quorum still
+//! counts wallets.
+//!
+//! [`VoteTimes`] records when each vote was cast in every build. The
+//! weighted tally is compiled only with the `conviction-voting` feature;
+//! without it governance cannot select the mode.
+
+use std::collections::HashMap;
+
+use burst_types::{ProtocolParams, Timestamp, TxHash, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+use crate::engine::GovernanceEngine;
+use crate::proposal::Proposal;
+
+/// Weight of a vote cast as its window closes, in basis points.
+pub const BASE_CONVICTION_BPS: u64 = 10_000;
+
+/// Weight of a vote that stood for the full conviction period.
+pub const MAX_CONVICTION_BPS: u64 = 40_000;
+
+/// When each governance vote was cast.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct VoteTimes {
+    /// Cast times per vote, keyed by proposal and the time its voting
+    /// window opened.
+    cast: HashMap<(TxHash, u64), HashMap<WalletAddress, u64>>,
+}
+
+impl VoteTimes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn record(
+        &mut self,
+        proposal: TxHash,
+        window_opened: Timestamp,
+        voter: &WalletAddress,
+        at: Timestamp,
+    ) {
+        self.cast
+            .entry((proposal, window_opened.as_secs()))
+            .or_default()
+            .insert(voter.clone(), at.as_secs());
+    }
+
+    /// Drop the votes of proposals `keep` rejects.
+    pub(crate) fn retain(&mut self, keep: impl Fn(&TxHash) -> bool) {
+        self.cast.retain(|(hash, _), _| keep(hash));
+    }
+}
+
+/// Weight of a vote that stood `standing_secs` before its window closed.
+#[cfg(feature = "conviction-voting")]
+pub fn conviction_bps(standing_secs: u64, period_secs: u64) -> u64 {
+    if period_secs == 0 {
+        return MAX_CONVICTION_BPS;
+    }
+    let standing = u128::from(standing_secs.min(period_secs));
+    let growth =
+        u128::from(MAX_CONVICTION_BPS - BASE_CONVICTION_BPS) * standing / u128::from(period_secs);
+    BASE_CONVICTION_BPS + growth as u64
+}
+
+impl GovernanceEngine {
+    /// Yea share of the vote that opened at `opened` and closes at
+    /// `closes_at`, with each vote weighted by its conviction. `None` when
+    /// votes count once each.
+    #[cfg(feature = "conviction-voting")]
+    pub(crate) fn conviction_support_bps(
+        &self,
+        proposal: &Proposal,
+        opened: Timestamp,
+        closes_at: u64,
+        params: &ProtocolParams,
+    ) -> Option<u32> {
+        use burst_transactions::governance::GovernanceVote;
+        use burst_types::GovernanceTallyMode;
+
+        if params.governance_tally_mode != GovernanceTallyMode::Conviction {
+            return None;
+        }
+        let votes = if proposal.promotion_started_at == Some(opened) {
+            self.get_promotion_votes(&proposal.hash)
+        } else {
+            self.get_exploration_votes(&proposal.hash)
+        };
+        let (mut yea, mut nay) = (0u128, 0u128);
+        if let (Some(votes), Some(cast)) = (
+            votes,
+            self.vote_times()
+                .cast
+                .get(&(proposal.hash, opened.as_secs())),
+        ) {
+            for (voter, at) in cast {
+                let weight = u128::from(conviction_bps(
+                    closes_at.saturating_sub(*at),
+                    params.governance_conviction_period_secs,
+                ));
+                match votes.get(voter) {
+                    Some(GovernanceVote::Yea) => yea += weight,
+                    Some(GovernanceVote::Nay) => nay += weight,
+                    _ => {}
+                }
+            }
+        }
+        Some(
+            (yea * 10_000)
+                .checked_div(yea + nay)
+                .map_or(0, |bps| bps as u32),
+        )
+    }
+
+    #[cfg(not(feature = "conviction-voting"))]
+    pub(crate) fn conviction_support_bps(
+        &self,
+        _proposal: &Proposal,
+        _opened: Timestamp,
+        _closes_at: u64,
+        _params: &ProtocolParams,
+    ) -> Option<u32> {
+        None
+    }
+}
+
+#[cfg(all(test, feature = "conviction-voting"))]
+mod tests {
+    use super::*;
+    use crate::params::GovernableParam;
+    use crate::proposal::{GovernancePhase, ProposalContent};
+    use burst_transactions::governance::GovernanceVote;
+    use burst_types::GovernanceTallyMode;
+
+    #[test]
+    fn conviction_outweighs_a_late_majority() {
+        assert_eq!(conviction_bps(0, 100), BASE_CONVICTION_BPS);
+        assert_eq!(conviction_bps(50, 100), 25_000);
+        assert_eq!(conviction_bps(500, 100), MAX_CONVICTION_BPS);
+        assert!(GovernableParam::GovernanceTallyMode
+            .validate(GovernanceTallyMode::Conviction.code())
+            .is_ok());
+
+        let mut params = ProtocolParams::testnet_defaults();
+        let hash = TxHash::new([7; 32]);
+        let mut engine = GovernanceEngine::new();
+        engine
+            .submit_emergency_proposal(
+                &mut Proposal {
+                    hash,
+                    proposer: WalletAddress::new("brst_proposer"),
+                    phase: GovernancePhase::Proposal,
+                    content: ProposalContent::Emergency {
+                        description: "halt".into(),
+                        param: GovernableParam::BrnRate,
+                        new_value: 1,
+                    },
+                    endorsement_count: 0,
+                    total_eligible_voters: 5,
+                    exploration_started_at: None,
+                    exploration_votes_yea: 0,
+                    exploration_votes_nay: 0,
+                    exploration_votes_abstain: 0,
+                    cooldown_started_at: None,
+                    promotion_started_at: None,
+                    promotion_votes_yea: 0,
+                    promotion_votes_nay: 0,
+                    promotion_votes_abstain: 0,
+                    round: 0,
+                    created_at: Timestamp::new(1_000),
+                    activation_at: None,
+                },
+                Timestamp::new(1_000),
+            )
+            .unwrap();
+
+        // Two nays stand the whole day; three yeas arrive at the end.
+        let closes_at = 1_000 + 86_400;
+        let cast = |engine: &mut GovernanceEngine, id: u8, vote, at: u64| {
+            let voter = WalletAddress::new(format!("brst_voter{id}"));
+            engine
+                .cast_exploration_vote(&hash, &voter, vote, Timestamp::new(at), &params)
+                .unwrap();
+        };
+        cast(&mut engine, 1, GovernanceVote::Nay, 1_000);
+        cast(&mut engine, 2, GovernanceVote::Nay, 1_000);
+        for id in 3..6 {
+            cast(&mut engine, id, GovernanceVote::Yea, closes_at - 1);
+        }
+
+        let proposal = engine.get_proposal(&hash).unwrap().clone();
+        let opened = Timestamp::new(1_000);
+        assert_eq!(
+            engine.conviction_support_bps(&proposal, opened, closes_at, &params),
+            None
+        );
+        params.governance_tally_mode = GovernanceTallyMode::Conviction;
+        params.governance_conviction_period_secs = 86_400;
+        // 3 × ~1x yea against 2 × 4x nay.
+        assert_eq!(
+            engine.conviction_support_bps(&proposal, opened, closes_at, &params),
+            Some(2_727)
+        );
+
+        let mut advancing = proposal.clone();
+        let counted = Timestamp::new(closes_at + params.governance_propagation_buffer_secs);
+        assert!(matches!(
+            engine.try_advance(&mut advancing, counted, &params),
+            Err(crate::GovernanceError::SupermajorityNotMet {
+                have_bps: 2_727,
+                ..
+            })
+        ));
+    }
+}
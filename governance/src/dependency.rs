@@ -0,0 +1,274 @@
+//! Proposal dependencies and conflicts.
+//!
+//! A proposal may require another to activate first. It votes as usual,
+//! but activates no earlier than its dependency and is rejected if the
+//! dependency is rejected or withdrawn.
+//!
+//! Two proposals conflict when they change the same [`GovernableParam`] or
+//! amend the same constitutional article. Both may pass: changes apply in
+//! activation order, so the later activation supersedes the earlier one.
+//! Activations due at the same moment are sequenced dependencies first,
+//! then by proposal hash, so every node applies them in the same order.
+//!
+//! [`GovernableParam`]: crate::params::GovernableParam
+
+use std::collections::HashMap;
+
+use burst_types::TxHash;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::GovernanceEngine;
+use crate::error::GovernanceError;
+use crate::proposal::{GovernancePhase, ProposalContent};
+
+/// Which proposal each proposal requires to activate first.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ProposalDependencies {
+    requires: HashMap<TxHash, TxHash>,
+}
+
+impl ProposalDependencies {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Whether two proposals change the same thing.
+pub fn conflicts(a: &ProposalContent, b: &ProposalContent) -> bool {
+    use ProposalContent::{ConstitutionalAmendment, Emergency, ParameterChange};
+    match (a, b) {
+        (
+            ParameterChange { param: x, .. } | Emergency { param: x, .. },
+            ParameterChange { param: y, .. } | Emergency { param: y, .. },
+        ) => x == y,
+        // Governance amendments add or replace the article they are titled
+        // after.
+        (ConstitutionalAmendment { title: x, .. }, ConstitutionalAmendment { title: y, .. }) => {
+            x.trim().eq_ignore_ascii_case(y.trim())
+        }
+        _ => false,
+    }
+}
+
+fn settled(phase: &GovernancePhase) -> bool {
+    matches!(
+        phase,
+        GovernancePhase::Rejected | GovernancePhase::Withdrawn | GovernancePhase::Activated
+    )
+}
+
+impl GovernanceEngine {
+    /// Record that `proposal` requires `dependency` to activate first.
+    pub fn require(&mut self, proposal: TxHash, dependency: TxHash) -> Result<(), GovernanceError> {
+        if proposal == dependency {
+            return Err(GovernanceError::InvalidDependency(
+                "a proposal cannot require itself".into(),
+            ));
+        }
+        let phase = &self
+            .get_proposal(&dependency)
+            .ok_or_else(|| {
+                GovernanceError::InvalidDependency(format!("unknown proposal {dependency}"))
+            })?
+            .phase;
+        if matches!(
+            phase,
+            GovernancePhase::Rejected | GovernancePhase::Withdrawn
+        ) {
+            return Err(GovernanceError::InvalidDependency(format!(
+                "proposal {dependency} is {phase:?}"
+            )));
+        }
+        self.dependencies_mut()
+            .requires
+            .insert(proposal, dependency);
+        Ok(())
+    }
+
+    /// The proposal `proposal` requires to activate first.
+    pub fn dependency_of(&self, proposal: &TxHash) -> Option<TxHash> {
+        self.dependencies().requires.get(proposal).copied()
+    }
+
+    /// Proposals still in progress whose change conflicts with `proposal`'s.
+    pub fn conflicting_proposals(&self, proposal: &TxHash) -> Vec<TxHash> {
+        let Some(target) = self.get_proposal(proposal) else {
+            return Vec::new();
+        };
+        let mut hashes: Vec<TxHash> = self
+            .all_proposals()
+            .filter(|other| other.hash != *proposal && !settled(&other.phase))
+            .filter(|other| conflicts(&target.content, &other.content))
+            .map(|other| other.hash)
+            .collect();
+        hashes.sort_by_key(|hash| *hash.as_bytes());
+        hashes
+    }
+
+    /// Whether `proposal` may activate as far as its dependency goes.
+    pub(crate) fn dependency_activated(&self, proposal: &TxHash) -> bool {
+        self.dependency_of(proposal).is_none_or(|dependency| {
+            self.get_proposal(&dependency)
+                .is_some_and(|p| p.phase == GovernancePhase::Activated)
+        })
+    }
+
+    /// Hold scheduled dependents until their dependencies activate and
+    /// reject those whose dependency failed. Repeats so chains settle in
+    /// one pass.
+    pub(crate) fn sequence_dependents(&mut self) {
+        loop {
+            let mut rejected = Vec::new();
+            let mut deferred = Vec::new();
+            for (proposal, dependency) in &self.dependencies().requires {
+                let Some(dependent) = self.get_proposal(proposal) else {
+                    continue;
+                };
+                if settled(&dependent.phase) {
+                    continue;
+                }
+                match self.get_proposal(dependency) {
+                    None => rejected.push(*proposal),
+                    Some(required) => match required.phase {
+                        GovernancePhase::Rejected | GovernancePhase::Withdrawn => {
+                            rejected.push(*proposal)
+                        }
+                        GovernancePhase::Activation
+                            if dependent.phase == GovernancePhase::Activation
+                                && dependent.activation_at < required.activation_at =>
+                        {
+                            deferred.push((*proposal, required.activation_at))
+                        }
+                        _ => {}
+                    },
+                }
+            }
+            if rejected.is_empty() && deferred.is_empty() {
+                return;
+            }
+            for hash in rejected {
+                if let Some(proposal) = self.get_proposal_mut(&hash) {
+                    proposal.phase = GovernancePhase::Rejected;
+                    tracing::info!(proposal = ?hash, "proposal rejected: its dependency failed");
+                }
+            }
+            for (hash, activation_at) in deferred {
+                if let Some(proposal) = self.get_proposal_mut(&hash) {
+                    proposal.activation_at = activation_at;
+                }
+            }
+        }
+    }
+
+    /// Forget the dependencies of settled proposals.
+    pub(crate) fn prune_dependencies(&mut self) {
+        let live: Vec<TxHash> = self
+            .dependencies()
+            .requires
+            .keys()
+            .filter(|hash| self.get_proposal(hash).is_some_and(|p| !settled(&p.phase)))
+            .copied()
+            .collect();
+        self.dependencies_mut()
+            .requires
+            .retain(|hash, _| live.contains(hash));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::GovernableParam;
+    use crate::proposal::Proposal;
+    use burst_types::{ProtocolParams, Timestamp, WalletAddress};
+
+    fn scheduled(seed: u8, param: GovernableParam, activation_at: u64) -> Proposal {
+        Proposal {
+            hash: TxHash::new([seed; 32]),
+            proposer: WalletAddress::new("brst_proposer"),
+            phase: GovernancePhase::Activation,
+            content: ProposalContent::ParameterChange {
+                param,
+                new_value: u128::from(seed),
+            },
+            endorsement_count: 0,
+            total_eligible_voters: 1,
+            exploration_started_at: None,
+            exploration_votes_yea: 0,
+            exploration_votes_nay: 0,
+            exploration_votes_abstain: 0,
+            cooldown_started_at: None,
+            promotion_started_at: None,
+            promotion_votes_yea: 0,
+            promotion_votes_nay: 0,
+            promotion_votes_abstain: 0,
+            round: 0,
+            created_at: Timestamp::new(0),
+            activation_at: Some(Timestamp::new(activation_at)),
+        }
+    }
+
+    #[test]
+    fn dependents_wait_and_conflicting_changes_apply_in_order() {
+        let mut params = ProtocolParams::burst_defaults();
+        let mut engine = GovernanceEngine::new();
+        // Two changes to the same parameter due at the same moment, and a
+        // third change that requires the higher-hashed of them.
+        for proposal in [
+            scheduled(2, GovernableParam::MaxRevotes, 100),
+            scheduled(1, GovernableParam::MaxRevotes, 100),
+            scheduled(3, GovernableParam::NumVerifiers, 50),
+        ] {
+            let hash = engine
+                .submit_proposal(proposal.clone(), u128::MAX, true, &params)
+                .unwrap();
+            *engine.get_proposal_mut(&hash).unwrap() = proposal;
+        }
+        let (first, second, dependent) = (
+            TxHash::new([1; 32]),
+            TxHash::new([2; 32]),
+            TxHash::new([3; 32]),
+        );
+        assert_eq!(engine.conflicting_proposals(&first), vec![second]);
+        assert!(engine.conflicting_proposals(&dependent).is_empty());
+        assert!(matches!(
+            engine.require(dependent, dependent),
+            Err(GovernanceError::InvalidDependency(_))
+        ));
+        engine.require(dependent, second).unwrap();
+
+        // Not yet due: only the dependent's own time has come, and it waits.
+        assert!(engine.tick(Timestamp::new(60), &mut params).is_empty());
+        assert_eq!(
+            engine.get_proposal(&dependent).unwrap().activation_at,
+            Some(Timestamp::new(100))
+        );
+
+        let activated = engine.tick(Timestamp::new(100), &mut params);
+        assert_eq!(activated, vec![first, second, dependent]);
+        assert_eq!(params.max_revotes, 2, "the later activation supersedes");
+        assert_eq!(params.num_verifiers, 3);
+        assert_eq!(engine.dependency_of(&dependent), None, "settled, so pruned");
+
+        // A dependent of a withdrawn proposal is rejected.
+        let mut orphan = scheduled(4, GovernableParam::MaxRevotes, 200);
+        orphan.phase = GovernancePhase::Promotion;
+        let mut withdrawn = scheduled(5, GovernableParam::EndorsementThreshold, 200);
+        withdrawn.phase = GovernancePhase::Proposal;
+        for proposal in [orphan.clone(), withdrawn.clone()] {
+            let hash = engine
+                .submit_proposal(proposal.clone(), u128::MAX, true, &params)
+                .unwrap();
+            *engine.get_proposal_mut(&hash).unwrap() = proposal;
+        }
+        engine.require(orphan.hash, withdrawn.hash).unwrap();
+        engine
+            .withdraw(&withdrawn.hash, &withdrawn.proposer)
+            .unwrap();
+        engine.sequence_dependents();
+        assert_eq!(
+            engine.get_proposal(&orphan.hash).unwrap().phase,
+            GovernancePhase::Rejected
+        );
+    }
+}
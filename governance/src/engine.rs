@@ -4,11 +4,15 @@
 //! Emergency flow: Exploration → Promotion → Activation (skip Proposal and Cooldown)
 //! With adaptive quorum biasing (EMA-based).
 
+use crate::conviction::VoteTimes;
 use crate::delegation::DelegationEngine;
+use crate::dependency::ProposalDependencies;
 use crate::error::GovernanceError;
 use crate::proposal::{GovernancePhase, Proposal, ProposalContent};
 use burst_transactions::governance::GovernanceVote;
-use burst_types::{ProtocolParams, Timestamp, TxHash, WalletAddress};
+use burst_types::{
+    GovernanceTallyMode, ProtocolParams, Timestamp, TrstDecayMode, TxHash, WalletAddress,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -41,6 +45,14 @@ pub struct GovernanceEngine {
     pending_changes: Vec<(crate::params::GovernableParam, u128)>,
     /// Constitutional amendments that have been activated but not yet applied by the node.
     pending_amendments: Vec<ProposalContent>,
+    /// When each vote was cast, for conviction tallies. Persisted by the
+    /// node under its own key so the engine's encoding is unchanged.
+    #[serde(skip)]
+    vote_times: VoteTimes,
+    /// Which proposals must activate before others. Persisted by the node
+    /// like `vote_times`.
+    #[serde(skip)]
+    dependencies: ProposalDependencies,
 }
 
 impl GovernanceEngine {
@@ -53,6 +65,8 @@ impl GovernanceEngine {
             endorsement_counts: HashMap::new(),
             pending_changes: Vec::new(),
             pending_amendments: Vec::new(),
+            vote_times: VoteTimes::new(),
+            dependencies: ProposalDependencies::new(),
         }
     }
 
@@ -78,6 +92,8 @@ impl GovernanceEngine {
             ));
         }
 
+        Self::validate_content(&proposal.content)?;
+
         // Validate proposer can afford the governance proposal cost
         if proposer_brn_balance < params.governance_proposal_cost {
             return Err(GovernanceError::InsufficientBrn {
@@ -111,6 +127,7 @@ impl GovernanceEngine {
         if !matches!(proposal.content, ProposalContent::Emergency { .. }) {
             return Err(GovernanceError::WrongPhase);
         }
+        Self::validate_content(&proposal.content)?;
         proposal.phase = GovernancePhase::Exploration;
         proposal.exploration_started_at = Some(now);
 
@@ -206,24 +223,13 @@ impl GovernanceEngine {
             }
         }
 
-        let votes = self.exploration_votes.entry(*proposal_hash).or_default();
-
-        if votes.contains_key(voter) {
-            return Err(GovernanceError::AlreadyVoted(voter.to_string()));
-        }
-
-        votes.insert(voter.clone(), vote);
-
-        // Update aggregate counts on the proposal
-        if let Some(proposal) = self.proposals.get_mut(proposal_hash) {
-            match vote {
-                GovernanceVote::Yea => proposal.exploration_votes_yea += 1,
-                GovernanceVote::Nay => proposal.exploration_votes_nay += 1,
-                GovernanceVote::Abstain => proposal.exploration_votes_abstain += 1,
-            }
-        }
-
-        Ok(())
+        self.tally_vote(
+            proposal_hash,
+            voter,
+            vote,
+            GovernancePhase::Exploration,
+            now,
+        )
     }
 
     /// Cast a vote during the Promotion phase.
@@ -260,7 +266,26 @@ impl GovernanceEngine {
             }
         }
 
-        let votes = self.promotion_votes.entry(*proposal_hash).or_default();
+        self.tally_vote(proposal_hash, voter, vote, GovernancePhase::Promotion, now)
+    }
+
+    /// Record `voter`'s vote in the Exploration or Promotion tally of a
+    /// proposal, once per voter. Phase and window checks are the caller's.
+    pub(crate) fn tally_vote(
+        &mut self,
+        proposal_hash: &TxHash,
+        voter: &WalletAddress,
+        vote: GovernanceVote,
+        phase: GovernancePhase,
+        now: Timestamp,
+    ) -> Result<(), GovernanceError> {
+        let votes = match phase {
+            GovernancePhase::Exploration => &mut self.exploration_votes,
+            GovernancePhase::Promotion => &mut self.promotion_votes,
+            _ => return Err(GovernanceError::WrongPhase),
+        }
+        .entry(*proposal_hash)
+        .or_default();
 
         if votes.contains_key(voter) {
             return Err(GovernanceError::AlreadyVoted(voter.to_string()));
@@ -270,16 +295,61 @@ impl GovernanceEngine {
 
         // Update aggregate counts on the proposal
         if let Some(proposal) = self.proposals.get_mut(proposal_hash) {
+            let opened = if phase == GovernancePhase::Exploration {
+                proposal.exploration_started_at
+            } else {
+                proposal.promotion_started_at
+            };
+            if let Some(opened) = opened {
+                self.vote_times.record(*proposal_hash, opened, voter, now);
+            }
+            let (yea, nay, abstain) = if phase == GovernancePhase::Exploration {
+                (
+                    &mut proposal.exploration_votes_yea,
+                    &mut proposal.exploration_votes_nay,
+                    &mut proposal.exploration_votes_abstain,
+                )
+            } else {
+                (
+                    &mut proposal.promotion_votes_yea,
+                    &mut proposal.promotion_votes_nay,
+                    &mut proposal.promotion_votes_abstain,
+                )
+            };
             match vote {
-                GovernanceVote::Yea => proposal.promotion_votes_yea += 1,
-                GovernanceVote::Nay => proposal.promotion_votes_nay += 1,
-                GovernanceVote::Abstain => proposal.promotion_votes_abstain += 1,
+                GovernanceVote::Yea => *yea += 1,
+                GovernanceVote::Nay => *nay += 1,
+                GovernanceVote::Abstain => *abstain += 1,
             }
         }
 
         Ok(())
     }
 
+    /// When each vote was cast, for the node to persist.
+    pub fn vote_times(&self) -> &VoteTimes {
+        &self.vote_times
+    }
+
+    /// Restore vote times persisted by the node.
+    pub fn restore_vote_times(&mut self, times: VoteTimes) {
+        self.vote_times = times;
+    }
+
+    /// Proposal dependencies, for the node to persist.
+    pub fn dependencies(&self) -> &ProposalDependencies {
+        &self.dependencies
+    }
+
+    /// Restore dependencies persisted by the node.
+    pub fn restore_dependencies(&mut self, dependencies: ProposalDependencies) {
+        self.dependencies = dependencies;
+    }
+
+    pub(crate) fn dependencies_mut(&mut self) -> &mut ProposalDependencies {
+        &mut self.dependencies
+    }
+
     /// Get a reference to a stored proposal by hash.
     pub fn get_proposal(&self, hash: &TxHash) -> Option<&Proposal> {
         self.proposals.get(hash)
@@ -316,28 +386,38 @@ impl GovernanceEngine {
     /// Returns a list of proposals that were activated.
     ///
     /// Two-pass approach:
-    /// 1. Activate proposals already in the Activation phase whose `activation_at` <= `now`.
+    /// 1. Activate proposals already in the Activation phase whose `activation_at` <= `now`,
+    ///    earliest first and each after its dependency.
     /// 2. Try advancing other proposals; newly promoted ones get deferred activation.
     pub fn tick(&mut self, now: Timestamp, params: &mut ProtocolParams) -> Vec<TxHash> {
         let mut activated = Vec::new();
+        self.sequence_dependents();
 
         // Pass 1: activate proposals whose deferred activation timestamp has arrived
-        let pending_activation: Vec<TxHash> = self
+        let mut pending_activation: Vec<(Timestamp, TxHash)> = self
             .proposals
             .iter()
-            .filter(|(_, p)| {
-                p.phase == GovernancePhase::Activation
-                    && p.activation_at
-                        .map(|at| now.as_secs() >= at.as_secs())
-                        .unwrap_or(false)
+            .filter_map(|(h, p)| match p.activation_at {
+                Some(at) if p.phase == GovernancePhase::Activation && now >= at => Some((at, *h)),
+                _ => None,
             })
-            .map(|(h, _)| *h)
             .collect();
+        // Conflicting changes apply in this order, so the later one wins.
+        pending_activation.sort_by_key(|(at, hash)| (*at, *hash.as_bytes()));
+        let mut pending_activation: Vec<TxHash> =
+            pending_activation.into_iter().map(|(_, h)| h).collect();
 
-        for hash in pending_activation {
+        while let Some(next) = pending_activation
+            .iter()
+            .position(|hash| self.dependency_activated(hash))
+        {
+            let hash = pending_activation.remove(next);
             if let Some(proposal) = self.proposals.get(&hash) {
                 let p = proposal.clone();
                 if self.activate(&p, params).is_ok() {
+                    if let Some(slot) = self.proposals.get_mut(&hash) {
+                        slot.phase = GovernancePhase::Activated;
+                    }
                     activated.push(hash);
                 }
             }
@@ -354,12 +434,9 @@ impl GovernanceEngine {
                 let mut p = proposal.clone();
                 match self.try_advance(&mut p, now, params) {
                     Ok(GovernancePhase::Activation) => {
-                        p.activation_at = Some(Timestamp::new(
-                            now.as_secs() + activation_delay_secs(params),
-                        ));
                         tracing::debug!(
                             proposal = ?hash,
-                            phase = ?GovernancePhase::Activation,
+                            activation_at = ?p.activation_at,
                             "governance proposal scheduled for activation"
                         );
                     }
@@ -383,6 +460,22 @@ impl GovernanceEngine {
                 }
             }
         }
+
+        let proposals = &self.proposals;
+        self.vote_times.retain(|hash| {
+            proposals.get(hash).is_some_and(|p| {
+                // Certificates for activated proposals still need them.
+                matches!(
+                    p.phase,
+                    GovernancePhase::Exploration
+                        | GovernancePhase::Cooldown
+                        | GovernancePhase::Promotion
+                        | GovernancePhase::Activation
+                        | GovernancePhase::Activated
+                )
+            })
+        });
+        self.prune_dependencies();
         activated
     }
 
@@ -409,32 +502,76 @@ impl GovernanceEngine {
 
     /// Determine the required supermajority threshold for a proposal.
     ///
-    /// Returns the supermajority required to pass a given proposal.
-    ///
-    /// Meta-threshold rules (whitepaper §6):
-    /// - Changing `ConstiSupermajorityBps` requires the CURRENT consti threshold.
-    ///   This is the self-referential property: "The Consti threshold is changed
-    ///   by hitting that same Consti threshold (not the parameter threshold)."
-    /// - Changing `GovernanceSupermajorityBps` requires the *current* governance
-    ///   threshold OR 85%, whichever is higher. This prevents a majority from
-    ///   first lowering the threshold and then pushing through destructive changes.
-    /// - Constitutional amendments require the consti threshold.
-    /// - All other parameter changes use the normal governance threshold.
+    /// Parameter changes use the threshold from the parameter registry
+    /// ([`ParamThreshold`](crate::params::ParamThreshold)); constitutional
+    /// amendments require the consti threshold and treasury grants the
+    /// governance threshold.
     fn get_required_supermajority(proposal: &Proposal, params: &ProtocolParams) -> u32 {
         match &proposal.content {
             ProposalContent::ParameterChange { param, .. }
-            | ProposalContent::Emergency { param, .. } => match param {
-                crate::params::GovernableParam::ConstiSupermajorityBps => {
-                    params.consti_supermajority_bps
-                }
-                crate::params::GovernableParam::GovernanceSupermajorityBps => {
-                    // Meta-threshold: changing the threshold requires at least
-                    // the current threshold or 85%, whichever is higher.
-                    params.governance_supermajority_bps.max(8500)
-                }
-                _ => params.governance_supermajority_bps,
-            },
+            | ProposalContent::Emergency { param, .. } => {
+                param.spec().threshold.supermajority_bps(params)
+            }
             ProposalContent::ConstitutionalAmendment { .. } => params.consti_supermajority_bps,
+            ProposalContent::TreasuryGrant { .. } => params.governance_supermajority_bps,
+        }
+    }
+
+    /// Start and length of the vote a proposal is currently holding, if it
+    /// is in Exploration or Promotion.
+    pub(crate) fn voting_window(
+        proposal: &Proposal,
+        params: &ProtocolParams,
+    ) -> Option<(Timestamp, u64)> {
+        let is_emergency = matches!(proposal.content, ProposalContent::Emergency { .. });
+        match proposal.phase {
+            GovernancePhase::Exploration => Some((
+                proposal.exploration_started_at?,
+                if is_emergency {
+                    EMERGENCY_PHASE_DURATION_SECS
+                } else {
+                    params.governance_exploration_duration_secs
+                },
+            )),
+            GovernancePhase::Promotion => Some((
+                proposal.promotion_started_at?,
+                if is_emergency {
+                    EMERGENCY_PHASE_DURATION_SECS
+                } else {
+                    params.governance_promotion_duration_secs
+                },
+            )),
+            _ => None,
+        }
+    }
+
+    /// Supermajority a proposal's promotion vote must reach, including the
+    /// fixed emergency threshold.
+    pub(crate) fn required_supermajority(proposal: &Proposal, params: &ProtocolParams) -> u32 {
+        if matches!(proposal.content, ProposalContent::Emergency { .. }) {
+            EMERGENCY_SUPERMAJORITY_BPS
+        } else {
+            Self::get_required_supermajority(proposal, params)
+        }
+    }
+
+    /// Reject parameter changes outside the parameter's registered bounds
+    /// and grants of nothing.
+    fn validate_content(content: &ProposalContent) -> Result<(), GovernanceError> {
+        match content {
+            ProposalContent::ParameterChange { param, new_value }
+            | ProposalContent::Emergency {
+                param, new_value, ..
+            } => param.validate(*new_value),
+            ProposalContent::ConstitutionalAmendment { .. } => Ok(()),
+            ProposalContent::TreasuryGrant { recipient, amount } => {
+                if recipient.as_str().is_empty() || *amount == 0 {
+                    return Err(GovernanceError::Other(
+                        "treasury grant needs a recipient and a non-zero amount".to_string(),
+                    ));
+                }
+                Ok(())
+            }
         }
     }
 
@@ -526,16 +663,26 @@ impl GovernanceEngine {
             params.governance_ema_participation_bps,
         );
 
-        let supermajority_bps = if is_emergency {
-            EMERGENCY_SUPERMAJORITY_BPS
-        } else {
-            Self::get_required_supermajority(proposal, params)
-        };
+        let supermajority_bps = Self::required_supermajority(proposal, params);
+        let support_bps = self
+            .conviction_support_bps(
+                proposal,
+                exploration_started,
+                exploration_started.as_secs().saturating_add(duration_secs),
+                params,
+            )
+            .unwrap_or_else(|| {
+                Self::majority_support_bps(
+                    proposal.exploration_votes_yea,
+                    proposal.exploration_votes_nay,
+                )
+            });
 
         self.check_vote_result(
-            proposal.exploration_votes_yea,
-            proposal.exploration_votes_nay,
-            proposal.exploration_votes_abstain,
+            proposal.exploration_votes_yea
+                + proposal.exploration_votes_nay
+                + proposal.exploration_votes_abstain,
+            support_bps,
             proposal.total_eligible_voters,
             effective_quorum,
             supermajority_bps,
@@ -616,16 +763,26 @@ impl GovernanceEngine {
             params.governance_ema_participation_bps,
         );
 
-        let supermajority_bps = if is_emergency {
-            EMERGENCY_SUPERMAJORITY_BPS
-        } else {
-            Self::get_required_supermajority(proposal, params)
-        };
+        let supermajority_bps = Self::required_supermajority(proposal, params);
+        let support_bps = self
+            .conviction_support_bps(
+                proposal,
+                promotion_started,
+                promotion_started.as_secs().saturating_add(duration_secs),
+                params,
+            )
+            .unwrap_or_else(|| {
+                Self::majority_support_bps(
+                    proposal.promotion_votes_yea,
+                    proposal.promotion_votes_nay,
+                )
+            });
 
         self.check_vote_result(
-            proposal.promotion_votes_yea,
-            proposal.promotion_votes_nay,
-            proposal.promotion_votes_abstain,
+            proposal.promotion_votes_yea
+                + proposal.promotion_votes_nay
+                + proposal.promotion_votes_abstain,
+            support_bps,
             proposal.total_eligible_voters,
             effective_quorum,
             supermajority_bps,
@@ -635,19 +792,56 @@ impl GovernanceEngine {
         )?;
 
         proposal.phase = GovernancePhase::Activation;
-        proposal.activation_at = Some(now);
+        proposal.activation_at = Self::scheduled_activation_at(proposal, params);
         Ok(GovernancePhase::Activation)
     }
 
-    /// Check quorum and supermajority for a vote phase.
+    /// When a proposal that passes promotion takes effect: the end of its
+    /// promotion vote, plus the propagation buffer before counting, plus
+    /// the activation delay. It depends only on the proposal and the
+    /// parameters, never on when a node counted the votes.
+    pub fn scheduled_activation_at(
+        proposal: &Proposal,
+        params: &ProtocolParams,
+    ) -> Option<Timestamp> {
+        Some(Timestamp::new(
+            Self::promotion_closes_at(proposal, params)?
+                .saturating_add(params.governance_propagation_buffer_secs)
+                .saturating_add(activation_delay_secs(params)),
+        ))
+    }
+
+    /// When a proposal's promotion vote closes, once it has started.
+    pub(crate) fn promotion_closes_at(proposal: &Proposal, params: &ProtocolParams) -> Option<u64> {
+        let duration_secs = if matches!(proposal.content, ProposalContent::Emergency { .. }) {
+            EMERGENCY_PHASE_DURATION_SECS
+        } else {
+            params.governance_promotion_duration_secs
+        };
+        Some(
+            proposal
+                .promotion_started_at?
+                .as_secs()
+                .saturating_add(duration_secs),
+        )
+    }
+
+    /// Yea share of the yea and nay votes, in basis points, one per wallet.
+    fn majority_support_bps(votes_yea: u32, votes_nay: u32) -> u32 {
+        (votes_yea * 10000)
+            .checked_div(votes_yea + votes_nay)
+            .unwrap_or(0)
+    }
+
+    /// Check quorum and supermajority for a vote phase. `support_bps` is
+    /// the yea share under the configured tally mode.
     /// On failure, resets the proposal to Proposal phase with an incremented round
     /// counter (if rounds remain), or terminally rejects it.
     #[allow(clippy::too_many_arguments)]
     fn check_vote_result(
         &self,
-        votes_yea: u32,
-        votes_nay: u32,
-        votes_abstain: u32,
+        total_votes: u32,
+        support_bps: u32,
         total_eligible_voters: u32,
         quorum_bps: u32,
         supermajority_bps: u32,
@@ -655,7 +849,6 @@ impl GovernanceEngine {
         now: Timestamp,
         params: &ProtocolParams,
     ) -> Result<(), GovernanceError> {
-        let total_votes = votes_yea + votes_nay + votes_abstain;
         let participation_bps = (total_votes * 10000)
             .checked_div(total_eligible_voters)
             .unwrap_or(0);
@@ -668,13 +861,10 @@ impl GovernanceEngine {
             });
         }
 
-        let total_yea_nay = votes_yea + votes_nay;
-        let supermajority_actual_bps = (votes_yea * 10000).checked_div(total_yea_nay).unwrap_or(0);
-
-        if supermajority_actual_bps < supermajority_bps {
+        if support_bps < supermajority_bps {
             self.fail_proposal(proposal, now, params);
             return Err(GovernanceError::SupermajorityNotMet {
-                have_bps: supermajority_actual_bps,
+                have_bps: support_bps,
                 need_bps: supermajority_bps,
             });
         }
@@ -738,6 +928,24 @@ impl GovernanceEngine {
             crate::params::GovernableParam::TrstExpirySecs => {
                 params.trst_expiry_secs = Self::saturating_u64(new_value);
             }
+            crate::params::GovernableParam::TrstDecayMode => {
+                // Codes are checked against the registry before this runs.
+                if let Some(mode) = TrstDecayMode::from_code(new_value) {
+                    params.trst_decay_mode = mode;
+                }
+            }
+            crate::params::GovernableParam::TrstDecayHalfLifeSecs => {
+                params.trst_decay_half_life_secs = Self::saturating_u64(new_value);
+            }
+            crate::params::GovernableParam::InactivitySuspensionSecs => {
+                params.inactivity_suspension_secs = Self::saturating_u64(new_value);
+            }
+            crate::params::GovernableParam::InactivityGraceSecs => {
+                params.inactivity_grace_secs = Self::saturating_u64(new_value);
+            }
+            crate::params::GovernableParam::PendingTtlSecs => {
+                params.pending_ttl_secs = Self::saturating_u64(new_value);
+            }
             crate::params::GovernableParam::EndorsementThreshold => {
                 params.endorsement_threshold = Self::saturating_u32(new_value);
             }
@@ -759,6 +967,15 @@ impl GovernanceEngine {
             crate::params::GovernableParam::ChallengeStakeAmount => {
                 params.challenge_stake_amount = new_value;
             }
+            crate::params::GovernableParam::ChallengeTargetCooldownSecs => {
+                params.challenge_target_cooldown_secs = Self::saturating_u64(new_value);
+            }
+            crate::params::GovernableParam::ChallengeLossAllowance => {
+                params.challenge_loss_allowance = Self::saturating_u32(new_value);
+            }
+            crate::params::GovernableParam::ChallengeStakeEscalationBps => {
+                params.challenge_stake_escalation_bps = Self::saturating_u32(new_value);
+            }
             crate::params::GovernableParam::GovernanceProposalDurationSecs => {
                 params.governance_proposal_duration_secs = Self::saturating_u64(new_value);
             }
@@ -792,6 +1009,12 @@ impl GovernanceEngine {
             crate::params::GovernableParam::VerificationTimeoutSecs => {
                 params.verification_timeout_secs = Self::saturating_u64(new_value);
             }
+            crate::params::GovernableParam::VerificationRoundTimeoutSecs => {
+                params.verification_round_timeout_secs = Self::saturating_u64(new_value);
+            }
+            crate::params::GovernableParam::MaxVerificationRounds => {
+                params.max_verification_rounds = Self::saturating_u32(new_value);
+            }
             crate::params::GovernableParam::ChallengeDurationSecs => {
                 params.challenge_duration_secs = Self::saturating_u64(new_value);
             }
@@ -828,6 +1051,53 @@ impl GovernanceEngine {
             crate::params::GovernableParam::MinWorkDifficulty => {
                 params.min_work_difficulty = Self::saturating_u64(new_value);
             }
+            crate::params::GovernableParam::WorkBaseMultiplierBps => {
+                params.work_base_multiplier_bps = Self::saturating_u32(new_value);
+            }
+            crate::params::GovernableParam::WorkReceiveMultiplierBps => {
+                params.work_receive_multiplier_bps = Self::saturating_u32(new_value);
+            }
+            crate::params::GovernableParam::WorkEpochMultiplierBps => {
+                params.work_epoch_multiplier_bps = Self::saturating_u32(new_value);
+            }
+            crate::params::GovernableParam::WorkGovernanceMultiplierBps => {
+                params.work_governance_multiplier_bps = Self::saturating_u32(new_value);
+            }
+            crate::params::GovernableParam::LedgerEpoch => {
+                params.ledger_epoch = u8::try_from(new_value).unwrap_or(u8::MAX);
+            }
+            crate::params::GovernableParam::GovernanceTallyMode => {
+                if let Some(mode) = GovernanceTallyMode::from_code(new_value) {
+                    params.governance_tally_mode = mode;
+                }
+            }
+            crate::params::GovernableParam::GovernanceConvictionPeriodSecs => {
+                params.governance_conviction_period_secs = Self::saturating_u64(new_value);
+            }
+            crate::params::GovernableParam::BootstrapSpendingLimit => {
+                params.bootstrap_spending_limit = new_value;
+            }
+            crate::params::GovernableParam::BootstrapBurnLimit => {
+                params.bootstrap_burn_limit = new_value;
+            }
+            crate::params::GovernableParam::ProbationPeriodSecs => {
+                params.probation_period_secs = Self::saturating_u64(new_value);
+            }
+            crate::params::GovernableParam::ProbationDailySendCap => {
+                params.probation_daily_send_cap = new_value;
+            }
+            crate::params::GovernableParam::ProvisionalDailySendCap => {
+                params.provisional_daily_send_cap = new_value;
+            }
+            crate::params::GovernableParam::AliasFee => {
+                params.alias_fee = new_value;
+            }
+            crate::params::GovernableParam::AliasTermSecs => {
+                params.alias_term_secs = Self::saturating_u64(new_value);
+            }
+            crate::params::GovernableParam::AliasGraceSecs => {
+                params.alias_grace_secs = Self::saturating_u64(new_value);
+            }
         }
     }
 
@@ -835,11 +1105,15 @@ impl GovernanceEngine {
     ///
     /// Records the change in `pending_changes` so the node can propagate it
     /// to subsystem engines (e.g. BRN rate changes to wallet states).
+    /// Treasury grants change no parameters; the node pays them from its
+    /// [`Treasury`](crate::treasury::Treasury).
     pub fn activate(
         &mut self,
         proposal: &Proposal,
         params: &mut ProtocolParams,
     ) -> Result<(), GovernanceError> {
+        // An upgrade may have tightened the bounds since submission.
+        Self::validate_content(&proposal.content)?;
         match &proposal.content {
             ProposalContent::ParameterChange { param, new_value } => {
                 Self::apply_param_change(param, *new_value, params);
@@ -857,6 +1131,7 @@ impl GovernanceEngine {
                 self.pending_changes.push((param.clone(), *new_value));
                 Ok(())
             }
+            ProposalContent::TreasuryGrant { .. } => Ok(()),
         }
     }
 
@@ -1892,6 +2167,37 @@ mod tests {
         assert_eq!(params.trst_expiry_secs, new_expiry as u64);
     }
 
+    #[test]
+    fn test_activate_trst_decay_mode() {
+        let mut engine = GovernanceEngine::new();
+        let mut params = default_params();
+
+        let mut proposal = make_proposal(0, 10);
+        proposal.phase = GovernancePhase::Activation;
+        proposal.content = ProposalContent::ParameterChange {
+            param: crate::params::GovernableParam::TrstDecayMode,
+            new_value: TrstDecayMode::Exponential.code(),
+        };
+        proposal.exploration_started_at = Some(Timestamp::new(0));
+        proposal.cooldown_started_at = Some(Timestamp::new(0));
+        proposal.promotion_started_at = Some(Timestamp::new(0));
+        proposal.activation_at = Some(Timestamp::new(0));
+
+        assert!(engine.activate(&proposal, &mut params).is_ok());
+        assert_eq!(params.trst_decay_mode, TrstDecayMode::Exponential);
+
+        // Unknown codes are refused and don't change the mode.
+        proposal.content = ProposalContent::ParameterChange {
+            param: crate::params::GovernableParam::TrstDecayMode,
+            new_value: 99,
+        };
+        assert!(matches!(
+            engine.activate(&proposal, &mut params),
+            Err(GovernanceError::ParamOutOfRange { .. })
+        ));
+        assert_eq!(params.trst_decay_mode, TrstDecayMode::Exponential);
+    }
+
     // ── Proposal Withdrawal (6.4) ─────────────────────────────────────
 
     #[test]
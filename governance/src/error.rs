@@ -50,6 +50,41 @@ pub enum GovernanceError {
     #[error("voting window has closed for the current phase")]
     VotingClosed,
 
+    #[error("{param} must be between {min} and {max}, got {value}")]
+    ParamOutOfRange {
+        param: &'static str,
+        value: u128,
+        min: u128,
+        max: u128,
+    },
+
+    #[error("proposal uses sealed ballots: commit and reveal instead of voting openly")]
+    SealedBallotRequired,
+
+    #[error("proposal does not use sealed ballots")]
+    NotSealed,
+
+    #[error("wallet {0} has not committed a ballot on this proposal")]
+    NoCommitment(String),
+
+    #[error("revealed ballot does not match the commitment")]
+    CommitmentMismatch,
+
+    #[error("reveal window has closed")]
+    RevealWindowClosed,
+
+    #[error("invalid delegate profile: {0}")]
+    InvalidDelegateProfile(String),
+
+    #[error("invalid proposal dependency: {0}")]
+    InvalidDependency(String),
+
+    #[error("insufficient treasury balance: have {have}, need {need}")]
+    InsufficientTreasury { have: u128, need: u128 },
+
+    #[error("grant approved by proposal {0} was already paid")]
+    GrantAlreadyPaid(String),
+
     #[error("{0}")]
     Other(String),
 }
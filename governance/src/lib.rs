@@ -7,14 +7,26 @@
 //! Key principle: one wallet = one vote (not stake-weighted).
 //! All protocol parameters are governable, including the governance parameters themselves.
 
+pub mod activation;
+pub mod ballot;
+pub mod conviction;
 pub mod delegation;
+pub mod dependency;
 pub mod engine;
 pub mod error;
 pub mod params;
+pub mod profile;
 pub mod proposal;
+pub mod treasury;
 
+pub use activation::{GovernanceQuorumCertificate, VoteTally};
+pub use ballot::{ballot_commitment, BallotBox};
+pub use conviction::VoteTimes;
 pub use delegation::{DelegationEngine, DelegationScope, DelegationSnapshot, ScopedDelegation};
+pub use dependency::{conflicts, ProposalDependencies};
 pub use engine::GovernanceEngine;
 pub use error::GovernanceError;
-pub use params::GovernableParam;
+pub use params::{GovernableParam, ParamSpec, ParamThreshold, ParamType};
+pub use profile::{DelegateProfile, SignedDelegateProfile};
 pub use proposal::{GovernancePhase, Proposal, ProposalContent};
+pub use treasury::{Treasury, TreasuryGrant, TreasuryInflow, TreasurySource};
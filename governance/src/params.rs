@@ -2,6 +2,9 @@
 //!
 //! Every parameter can be changed via the 5-phase governance process,
 //! including the governance parameters themselves (self-governing thresholds).
+//! [`GovernableParam::spec`] is the registry of each parameter's type, unit,
+//! bounds and required vote threshold; proposals outside the bounds are
+//! refused when submitted and again when they activate.
 
 use crate::error::GovernanceError;
 use burst_types::ProtocolParams;
@@ -13,6 +16,15 @@ pub enum GovernableParam {
     // The General Equation
     BrnRate,
     TrstExpirySecs,
+    TrstDecayMode,
+    TrstDecayHalfLifeSecs,
+
+    // Inactivity
+    InactivitySuspensionSecs,
+    InactivityGraceSecs,
+
+    // Pending transfers
+    PendingTtlSecs,
 
     // Verification
     EndorsementThreshold,
@@ -24,6 +36,9 @@ pub enum GovernableParam {
 
     // Challenges
     ChallengeStakeAmount,
+    ChallengeTargetCooldownSecs,
+    ChallengeLossAllowance,
+    ChallengeStakeEscalationBps,
 
     // Governance (self-governing!)
     GovernanceProposalDurationSecs,
@@ -41,6 +56,8 @@ pub enum GovernableParam {
 
     // Verification
     VerificationTimeoutSecs,
+    VerificationRoundTimeoutSecs,
+    MaxVerificationRounds,
     ChallengeDurationSecs,
     EndorserRewardBps,
 
@@ -63,6 +80,31 @@ pub enum GovernableParam {
 
     // Anti-Spam
     MinWorkDifficulty,
+    WorkBaseMultiplierBps,
+    WorkReceiveMultiplierBps,
+    WorkEpochMultiplierBps,
+    WorkGovernanceMultiplierBps,
+
+    // Ledger format
+    LedgerEpoch,
+
+    // Governance (tally experiments)
+    GovernanceTallyMode,
+    GovernanceConvictionPeriodSecs,
+
+    // Economic (bootstrap phase)
+    BootstrapSpendingLimit,
+    BootstrapBurnLimit,
+
+    // Economic (probation tiers)
+    ProbationPeriodSecs,
+    ProbationDailySendCap,
+    ProvisionalDailySendCap,
+
+    // Aliases
+    AliasFee,
+    AliasTermSecs,
+    AliasGraceSecs,
 }
 
 impl GovernableParam {
@@ -71,6 +113,11 @@ impl GovernableParam {
         match self {
             Self::BrnRate => "brn_rate",
             Self::TrstExpirySecs => "trst_expiry_secs",
+            Self::TrstDecayMode => "trst_decay_mode",
+            Self::TrstDecayHalfLifeSecs => "trst_decay_half_life_secs",
+            Self::InactivitySuspensionSecs => "inactivity_suspension_secs",
+            Self::InactivityGraceSecs => "inactivity_grace_secs",
+            Self::PendingTtlSecs => "pending_ttl_secs",
             Self::EndorsementThreshold => "endorsement_threshold",
             Self::EndorsementBurnAmount => "endorsement_burn_amount",
             Self::NumVerifiers => "num_verifiers",
@@ -78,6 +125,9 @@ impl GovernableParam {
             Self::VerifierStakeAmount => "verifier_stake_amount",
             Self::MaxRevotes => "max_revotes",
             Self::ChallengeStakeAmount => "challenge_stake_amount",
+            Self::ChallengeTargetCooldownSecs => "challenge_target_cooldown_secs",
+            Self::ChallengeLossAllowance => "challenge_loss_allowance",
+            Self::ChallengeStakeEscalationBps => "challenge_stake_escalation_bps",
             Self::GovernanceProposalDurationSecs => "governance_proposal_duration_secs",
             Self::GovernanceExplorationDurationSecs => "governance_exploration_duration_secs",
             Self::GovernanceCooldownDurationSecs => "governance_cooldown_duration_secs",
@@ -89,6 +139,8 @@ impl GovernableParam {
             Self::ConstiSupermajorityBps => "consti_supermajority_bps",
             Self::ConstiQuorumBps => "consti_quorum_bps",
             Self::VerificationTimeoutSecs => "verification_timeout_secs",
+            Self::VerificationRoundTimeoutSecs => "verification_round_timeout_secs",
+            Self::MaxVerificationRounds => "max_verification_rounds",
             Self::ChallengeDurationSecs => "challenge_duration_secs",
             Self::EndorserRewardBps => "endorser_reward_bps",
             Self::NewWalletSpendingLimit => "new_wallet_spending_limit",
@@ -101,10 +153,341 @@ impl GovernableParam {
             Self::GovernanceProposalWindowSecs => "governance_proposal_window_secs",
             Self::GovernancePropagationBufferSecs => "governance_propagation_buffer_secs",
             Self::MinWorkDifficulty => "min_work_difficulty",
+            Self::WorkBaseMultiplierBps => "work_base_multiplier_bps",
+            Self::WorkReceiveMultiplierBps => "work_receive_multiplier_bps",
+            Self::WorkEpochMultiplierBps => "work_epoch_multiplier_bps",
+            Self::WorkGovernanceMultiplierBps => "work_governance_multiplier_bps",
+            Self::LedgerEpoch => "ledger_epoch",
+            Self::GovernanceTallyMode => "governance_tally_mode",
+            Self::GovernanceConvictionPeriodSecs => "governance_conviction_period_secs",
+            Self::BootstrapSpendingLimit => "bootstrap_spending_limit",
+            Self::BootstrapBurnLimit => "bootstrap_burn_limit",
+            Self::ProbationPeriodSecs => "probation_period_secs",
+            Self::ProbationDailySendCap => "probation_daily_send_cap",
+            Self::ProvisionalDailySendCap => "provisional_daily_send_cap",
+            Self::AliasFee => "alias_fee",
+            Self::AliasTermSecs => "alias_term_secs",
+            Self::AliasGraceSecs => "alias_grace_secs",
+        }
+    }
+
+    /// Every governable parameter, in declaration order.
+    pub const ALL: [GovernableParam; 57] = [
+        Self::BrnRate,
+        Self::TrstExpirySecs,
+        Self::TrstDecayMode,
+        Self::TrstDecayHalfLifeSecs,
+        Self::InactivitySuspensionSecs,
+        Self::InactivityGraceSecs,
+        Self::PendingTtlSecs,
+        Self::EndorsementThreshold,
+        Self::EndorsementBurnAmount,
+        Self::NumVerifiers,
+        Self::VerificationThresholdBps,
+        Self::VerifierStakeAmount,
+        Self::MaxRevotes,
+        Self::ChallengeStakeAmount,
+        Self::ChallengeTargetCooldownSecs,
+        Self::ChallengeLossAllowance,
+        Self::ChallengeStakeEscalationBps,
+        Self::GovernanceProposalDurationSecs,
+        Self::GovernanceExplorationDurationSecs,
+        Self::GovernanceCooldownDurationSecs,
+        Self::GovernancePromotionDurationSecs,
+        Self::GovernanceSupermajorityBps,
+        Self::GovernanceQuorumBps,
+        Self::GovernanceProposalEndorsements,
+        Self::GovernanceEmaParticipationBps,
+        Self::ConstiSupermajorityBps,
+        Self::ConstiQuorumBps,
+        Self::VerificationTimeoutSecs,
+        Self::VerificationRoundTimeoutSecs,
+        Self::MaxVerificationRounds,
+        Self::ChallengeDurationSecs,
+        Self::EndorserRewardBps,
+        Self::NewWalletSpendingLimit,
+        Self::NewWalletLimitDurationSecs,
+        Self::BootstrapExitThreshold,
+        Self::NewWalletTxLimitPerDay,
+        Self::NewWalletRateLimitDurationSecs,
+        Self::GovernanceProposalCost,
+        Self::GovernanceMaxRounds,
+        Self::GovernanceProposalWindowSecs,
+        Self::GovernancePropagationBufferSecs,
+        Self::MinWorkDifficulty,
+        Self::WorkBaseMultiplierBps,
+        Self::WorkReceiveMultiplierBps,
+        Self::WorkEpochMultiplierBps,
+        Self::WorkGovernanceMultiplierBps,
+        Self::LedgerEpoch,
+        Self::GovernanceTallyMode,
+        Self::GovernanceConvictionPeriodSecs,
+        Self::BootstrapSpendingLimit,
+        Self::BootstrapBurnLimit,
+        Self::ProbationPeriodSecs,
+        Self::ProbationDailySendCap,
+        Self::ProvisionalDailySendCap,
+        Self::AliasFee,
+        Self::AliasTermSecs,
+        Self::AliasGraceSecs,
+    ];
+
+    /// Look a parameter up by its [`name`](Self::name).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|param| param.name() == name)
+    }
+
+    /// Registry entry describing this parameter's type, unit, bounds and
+    /// the vote threshold a change to it must reach.
+    pub fn spec(&self) -> ParamSpec {
+        use ParamThreshold::{Constitutional, Governance, GovernanceMeta};
+        use ParamType::{Amount, Bps, Code, Count, Difficulty, Epoch, Multiplier, Seconds};
+        let (ty, min, max, threshold) = match self {
+            Self::BrnRate => (Amount, 0, None, Governance),
+            Self::TrstExpirySecs => (Seconds, 1, None, Governance),
+            Self::TrstDecayMode => (Code, 0, Some(2), Governance),
+            Self::TrstDecayHalfLifeSecs => (Seconds, 1, None, Governance),
+            Self::InactivitySuspensionSecs => (Seconds, 0, None, Governance),
+            Self::InactivityGraceSecs => (Seconds, 0, None, Governance),
+            Self::PendingTtlSecs => (Seconds, 0, None, Governance),
+            Self::EndorsementThreshold => (Count, 1, None, Governance),
+            Self::EndorsementBurnAmount => (Amount, 0, None, Governance),
+            Self::NumVerifiers => (Count, 1, None, Governance),
+            Self::VerificationThresholdBps => (Bps, 1, None, Governance),
+            Self::VerifierStakeAmount => (Amount, 0, None, Governance),
+            Self::MaxRevotes => (Count, 0, None, Governance),
+            Self::ChallengeStakeAmount => (Amount, 0, None, Governance),
+            Self::ChallengeTargetCooldownSecs => (Seconds, 0, None, Governance),
+            Self::ChallengeLossAllowance => (Count, 0, None, Governance),
+            Self::ChallengeStakeEscalationBps => (Multiplier, 0, None, Governance),
+            Self::GovernanceProposalDurationSecs => (Seconds, 1, None, Governance),
+            Self::GovernanceExplorationDurationSecs => (Seconds, 1, None, Governance),
+            Self::GovernanceCooldownDurationSecs => (Seconds, 1, None, Governance),
+            Self::GovernancePromotionDurationSecs => (Seconds, 1, None, Governance),
+            // A supermajority has to be more than half.
+            Self::GovernanceSupermajorityBps => (Bps, 5001, None, GovernanceMeta),
+            Self::GovernanceQuorumBps => (Bps, 1, None, Governance),
+            Self::GovernanceProposalEndorsements => (Count, 0, None, Governance),
+            Self::GovernanceEmaParticipationBps => (Bps, 0, None, Governance),
+            Self::ConstiSupermajorityBps => (Bps, 5001, None, Constitutional),
+            Self::ConstiQuorumBps => (Bps, 1, None, Constitutional),
+            Self::VerificationTimeoutSecs => (Seconds, 1, None, Governance),
+            Self::VerificationRoundTimeoutSecs => (Seconds, 1, None, Governance),
+            Self::MaxVerificationRounds => (Count, 1, None, Governance),
+            Self::ChallengeDurationSecs => (Seconds, 1, None, Governance),
+            Self::EndorserRewardBps => (Bps, 0, None, Governance),
+            Self::NewWalletSpendingLimit => (Amount, 0, None, Governance),
+            Self::NewWalletLimitDurationSecs => (Seconds, 0, None, Governance),
+            Self::BootstrapExitThreshold => (Count, 0, None, Governance),
+            Self::NewWalletTxLimitPerDay => (Count, 0, None, Governance),
+            Self::NewWalletRateLimitDurationSecs => (Seconds, 0, None, Governance),
+            Self::GovernanceProposalCost => (Amount, 0, None, Governance),
+            Self::GovernanceMaxRounds => (Count, 1, None, Governance),
+            Self::GovernanceProposalWindowSecs => (Seconds, 1, None, Governance),
+            Self::GovernancePropagationBufferSecs => (Seconds, 0, None, Governance),
+            Self::MinWorkDifficulty => (Difficulty, 0, None, Governance),
+            Self::WorkBaseMultiplierBps => (Multiplier, 1, None, Governance),
+            Self::WorkReceiveMultiplierBps => (Multiplier, 1, None, Governance),
+            Self::WorkEpochMultiplierBps => (Multiplier, 1, None, Governance),
+            Self::WorkGovernanceMultiplierBps => (Multiplier, 1, None, Governance),
+            Self::LedgerEpoch => (Epoch, 0, None, Governance),
+            // Conviction tallies exist only in builds with the feature.
+            Self::GovernanceTallyMode => (
+                Code,
+                0,
+                Some(u128::from(cfg!(feature = "conviction-voting"))),
+                GovernanceMeta,
+            ),
+            Self::GovernanceConvictionPeriodSecs => (Seconds, 1, None, Governance),
+            Self::BootstrapSpendingLimit => (Amount, 0, None, Governance),
+            Self::BootstrapBurnLimit => (Amount, 0, None, Governance),
+            Self::ProbationPeriodSecs => (Seconds, 0, None, Governance),
+            Self::ProbationDailySendCap => (Amount, 0, None, Governance),
+            Self::ProvisionalDailySendCap => (Amount, 0, None, Governance),
+            Self::AliasFee => (Amount, 0, None, Governance),
+            Self::AliasTermSecs => (Seconds, 0, None, Governance),
+            Self::AliasGraceSecs => (Seconds, 0, None, Governance),
+        };
+        ParamSpec {
+            ty,
+            min,
+            max: max.unwrap_or(ty.max()),
+            threshold,
+        }
+    }
+
+    /// Check `value` against this parameter's bounds.
+    pub fn validate(&self, value: u128) -> Result<(), GovernanceError> {
+        let spec = self.spec();
+        if (spec.min..=spec.max).contains(&value) {
+            Ok(())
+        } else {
+            Err(GovernanceError::ParamOutOfRange {
+                param: self.name(),
+                value,
+                min: spec.min,
+                max: spec.max,
+            })
+        }
+    }
+
+    /// This parameter's value in `params`, in the form a proposal carries.
+    pub fn current_value(&self, params: &ProtocolParams) -> u128 {
+        match self {
+            Self::BrnRate => params.brn_rate,
+            Self::TrstExpirySecs => params.trst_expiry_secs.into(),
+            Self::TrstDecayMode => params.trst_decay_mode.code(),
+            Self::TrstDecayHalfLifeSecs => params.trst_decay_half_life_secs.into(),
+            Self::InactivitySuspensionSecs => params.inactivity_suspension_secs.into(),
+            Self::InactivityGraceSecs => params.inactivity_grace_secs.into(),
+            Self::PendingTtlSecs => params.pending_ttl_secs.into(),
+            Self::EndorsementThreshold => params.endorsement_threshold.into(),
+            Self::EndorsementBurnAmount => params.endorsement_burn_amount,
+            Self::NumVerifiers => params.num_verifiers.into(),
+            Self::VerificationThresholdBps => params.verification_threshold_bps.into(),
+            Self::VerifierStakeAmount => params.verifier_stake_amount,
+            Self::MaxRevotes => params.max_revotes.into(),
+            Self::ChallengeStakeAmount => params.challenge_stake_amount,
+            Self::ChallengeTargetCooldownSecs => params.challenge_target_cooldown_secs.into(),
+            Self::ChallengeLossAllowance => params.challenge_loss_allowance.into(),
+            Self::ChallengeStakeEscalationBps => params.challenge_stake_escalation_bps.into(),
+            Self::GovernanceProposalDurationSecs => params.governance_proposal_duration_secs.into(),
+            Self::GovernanceExplorationDurationSecs => {
+                params.governance_exploration_duration_secs.into()
+            }
+            Self::GovernanceCooldownDurationSecs => params.governance_cooldown_duration_secs.into(),
+            Self::GovernancePromotionDurationSecs => {
+                params.governance_promotion_duration_secs.into()
+            }
+            Self::GovernanceSupermajorityBps => params.governance_supermajority_bps.into(),
+            Self::GovernanceQuorumBps => params.governance_quorum_bps.into(),
+            Self::GovernanceProposalEndorsements => params.governance_proposal_endorsements.into(),
+            Self::GovernanceEmaParticipationBps => params.governance_ema_participation_bps.into(),
+            Self::ConstiSupermajorityBps => params.consti_supermajority_bps.into(),
+            Self::ConstiQuorumBps => params.consti_quorum_bps.into(),
+            Self::VerificationTimeoutSecs => params.verification_timeout_secs.into(),
+            Self::VerificationRoundTimeoutSecs => params.verification_round_timeout_secs.into(),
+            Self::MaxVerificationRounds => params.max_verification_rounds.into(),
+            Self::ChallengeDurationSecs => params.challenge_duration_secs.into(),
+            Self::EndorserRewardBps => params.endorser_reward_bps.into(),
+            Self::NewWalletSpendingLimit => params.new_wallet_spending_limit,
+            Self::NewWalletLimitDurationSecs => params.new_wallet_limit_duration_secs.into(),
+            Self::BootstrapExitThreshold => params.bootstrap_exit_threshold.into(),
+            Self::NewWalletTxLimitPerDay => params.new_wallet_tx_limit_per_day.into(),
+            Self::NewWalletRateLimitDurationSecs => {
+                params.new_wallet_rate_limit_duration_secs.into()
+            }
+            Self::GovernanceProposalCost => params.governance_proposal_cost,
+            Self::GovernanceMaxRounds => params.governance_max_rounds.into(),
+            Self::GovernanceProposalWindowSecs => params.governance_proposal_window_secs.into(),
+            Self::GovernancePropagationBufferSecs => {
+                params.governance_propagation_buffer_secs.into()
+            }
+            Self::MinWorkDifficulty => params.min_work_difficulty.into(),
+            Self::WorkBaseMultiplierBps => params.work_base_multiplier_bps.into(),
+            Self::WorkReceiveMultiplierBps => params.work_receive_multiplier_bps.into(),
+            Self::WorkEpochMultiplierBps => params.work_epoch_multiplier_bps.into(),
+            Self::WorkGovernanceMultiplierBps => params.work_governance_multiplier_bps.into(),
+            Self::LedgerEpoch => params.ledger_epoch.into(),
+            Self::GovernanceTallyMode => params.governance_tally_mode.code(),
+            Self::GovernanceConvictionPeriodSecs => params.governance_conviction_period_secs.into(),
+            Self::BootstrapSpendingLimit => params.bootstrap_spending_limit,
+            Self::BootstrapBurnLimit => params.bootstrap_burn_limit,
+            Self::ProbationPeriodSecs => params.probation_period_secs.into(),
+            Self::ProbationDailySendCap => params.probation_daily_send_cap,
+            Self::ProvisionalDailySendCap => params.provisional_daily_send_cap,
+            Self::AliasFee => params.alias_fee,
+            Self::AliasTermSecs => params.alias_term_secs.into(),
+            Self::AliasGraceSecs => params.alias_grace_secs.into(),
+        }
+    }
+}
+
+/// How a parameter's raw `u128` value is read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamType {
+    /// Raw token units (or raw units per second for `brn_rate`).
+    Amount,
+    /// A whole number of things: verifiers, rounds, endorsements.
+    Count,
+    /// A duration in seconds.
+    Seconds,
+    /// A fraction in basis points, at most 10 000.
+    Bps,
+    /// A scale factor in basis points, where 10 000 is 1×.
+    Multiplier,
+    /// An enumeration code, such as a `TrstDecayMode`.
+    Code,
+    /// A proof-of-work difficulty threshold.
+    Difficulty,
+    /// A ledger format epoch.
+    Epoch,
+}
+
+impl ParamType {
+    /// Unit reported alongside values of this type.
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Self::Amount => "raw",
+            Self::Count => "count",
+            Self::Seconds => "seconds",
+            Self::Bps | Self::Multiplier => "bps",
+            Self::Code => "code",
+            Self::Difficulty => "difficulty",
+            Self::Epoch => "epoch",
+        }
+    }
+
+    /// Largest value the `ProtocolParams` field can hold.
+    pub fn max(&self) -> u128 {
+        match self {
+            Self::Amount => u128::MAX,
+            Self::Count | Self::Multiplier => u32::MAX.into(),
+            Self::Bps => 10_000,
+            Self::Seconds | Self::Difficulty => u64::MAX.into(),
+            Self::Code | Self::Epoch => u8::MAX.into(),
         }
     }
 }
 
+/// Which supermajority a change to a parameter must reach in the
+/// exploration and promotion votes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamThreshold {
+    /// `governance_supermajority_bps`.
+    Governance,
+    /// The current governance threshold or 85%, whichever is higher, so a
+    /// majority cannot first lower the bar and then use it.
+    GovernanceMeta,
+    /// `consti_supermajority_bps`: the constitutional threshold is changed
+    /// by reaching that same threshold.
+    Constitutional,
+}
+
+impl ParamThreshold {
+    /// Required supermajority in basis points under `params`.
+    pub fn supermajority_bps(&self, params: &ProtocolParams) -> u32 {
+        match self {
+            Self::Governance => params.governance_supermajority_bps,
+            Self::GovernanceMeta => params.governance_supermajority_bps.max(8500),
+            Self::Constitutional => params.consti_supermajority_bps,
+        }
+    }
+}
+
+/// Registry entry for one governable parameter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParamSpec {
+    pub ty: ParamType,
+    /// Smallest value a proposal may set.
+    pub min: u128,
+    /// Largest value a proposal may set.
+    pub max: u128,
+    pub threshold: ParamThreshold,
+}
+
 /// Trait for governable parameters that can be applied to ProtocolParams.
 pub trait GovernableParamTrait {
     /// Apply a new value to the protocol parameters.
@@ -130,3 +513,35 @@ impl GovernableParamTrait for TrstExpiryParam {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_covers_every_parameter_and_admits_the_defaults() {
+        for defaults in [
+            ProtocolParams::burst_defaults(),
+            ProtocolParams::testnet_defaults(),
+        ] {
+            for param in GovernableParam::ALL {
+                assert_eq!(
+                    GovernableParam::from_name(param.name()),
+                    Some(param.clone())
+                );
+                let value = param.current_value(&defaults);
+                assert!(param.validate(value).is_ok(), "{} = {value}", param.name());
+            }
+        }
+        assert_eq!(GovernableParam::from_name("no_such_param"), None);
+
+        let spec = GovernableParam::GovernanceSupermajorityBps.spec();
+        assert_eq!((spec.min, spec.max), (5001, 10_000));
+        assert_eq!(spec.threshold, ParamThreshold::GovernanceMeta);
+        assert!(matches!(
+            GovernableParam::GovernanceSupermajorityBps.validate(5000),
+            Err(GovernanceError::ParamOutOfRange { min: 5001, .. })
+        ));
+        assert!(GovernableParam::LedgerEpoch.validate(256).is_err());
+    }
+}
@@ -0,0 +1,158 @@
+//! Delegate profiles — opt-in discovery for governance delegates.
+//!
+//! A wallet that wants to be found as a delegate signs a [`DelegateProfile`]
+//! and publishes its hash on-chain in a `DelegateProfile` block. The profile
+//! itself stays off-chain: nodes accept it only when the signature is the
+//! delegate's and its [`anchor`](DelegateProfile::anchor) matches the latest
+//! anchor on the delegate's chain, so a profile cannot be edited or revived
+//! without a new block.
+
+use burst_types::{BlockHash, PrivateKey, PublicKey, Signature, Timestamp, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+use crate::delegation::DelegationScope;
+use crate::error::GovernanceError;
+
+/// Domain separator for profile anchors.
+const ANCHOR_DOMAIN: &[u8] = b"burst-delegate-profile-v1";
+
+/// Longest display name a profile may carry, in bytes.
+pub const MAX_PROFILE_NAME_LEN: usize = 64;
+/// Longest statement a profile may carry, in bytes.
+pub const MAX_PROFILE_STATEMENT_LEN: usize = 4096;
+/// Longest URL a profile may carry, in bytes.
+pub const MAX_PROFILE_URL_LEN: usize = 256;
+/// Most scopes a profile may list.
+pub const MAX_PROFILE_SCOPES: usize = 16;
+
+/// What a delegate tells prospective delegators about themselves.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DelegateProfile {
+    pub delegate: WalletAddress,
+    pub name: String,
+    pub statement: String,
+    /// Scopes the delegate is willing to take delegations for.
+    pub scopes: Vec<DelegationScope>,
+    #[serde(default)]
+    pub url: Option<String>,
+    pub published_at: Timestamp,
+}
+
+impl DelegateProfile {
+    /// Commitment to the whole profile, used as the link of a
+    /// `DelegateProfile` block and as the signed message.
+    pub fn anchor(&self) -> [u8; 32] {
+        let bytes = bincode::serialize(self).expect("profile serialization is infallible");
+        burst_crypto::blake2b_256_multi(&[ANCHOR_DOMAIN, &bytes])
+    }
+
+    /// Whether an on-chain anchor link commits to this profile.
+    pub fn matches_anchor(&self, link: &BlockHash) -> bool {
+        link.as_bytes() == &self.anchor()
+    }
+
+    /// Check the size limits.
+    pub fn check(&self) -> Result<(), GovernanceError> {
+        let invalid = |msg: String| Err(GovernanceError::InvalidDelegateProfile(msg));
+        if self.name.trim().is_empty() || self.name.len() > MAX_PROFILE_NAME_LEN {
+            return invalid(format!("name must be 1 to {MAX_PROFILE_NAME_LEN} bytes"));
+        }
+        if self.statement.len() > MAX_PROFILE_STATEMENT_LEN {
+            return invalid(format!(
+                "statement exceeds {MAX_PROFILE_STATEMENT_LEN} bytes"
+            ));
+        }
+        if self.scopes.is_empty() || self.scopes.len() > MAX_PROFILE_SCOPES {
+            return invalid(format!("must list 1 to {MAX_PROFILE_SCOPES} scopes"));
+        }
+        if self
+            .url
+            .as_ref()
+            .is_some_and(|url| url.len() > MAX_PROFILE_URL_LEN)
+        {
+            return invalid(format!("url exceeds {MAX_PROFILE_URL_LEN} bytes"));
+        }
+        Ok(())
+    }
+}
+
+/// A profile with the delegate's signature over its anchor.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedDelegateProfile {
+    pub profile: DelegateProfile,
+    pub signature: Signature,
+}
+
+impl SignedDelegateProfile {
+    /// Sign `profile` with the delegate's account key.
+    pub fn sign(profile: DelegateProfile, private_key: &PrivateKey) -> Self {
+        let signature = burst_crypto::sign_message(&profile.anchor(), private_key);
+        Self { profile, signature }
+    }
+
+    /// Check the size limits and that the delegate's key made the signature.
+    pub fn verify(&self) -> Result<(), GovernanceError> {
+        self.profile.check()?;
+        let key =
+            burst_crypto::decode_address(self.profile.delegate.as_str()).ok_or_else(|| {
+                GovernanceError::InvalidDelegateProfile(format!(
+                    "invalid delegate address {}",
+                    self.profile.delegate
+                ))
+            })?;
+        if !burst_crypto::verify_signature(&self.profile.anchor(), &self.signature, &PublicKey(key))
+        {
+            return Err(GovernanceError::InvalidDelegateProfile(
+                "signature does not match the delegate's key".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_types::TxHash;
+
+    #[test]
+    fn signed_profile_verifies_and_anchor_commits_to_content() {
+        let kp = burst_crypto::keypair_from_seed(&[5u8; 32]);
+        let profile = DelegateProfile {
+            delegate: burst_crypto::derive_address(&kp.public),
+            name: "Ada".into(),
+            statement: "Conservative on monetary parameters.".into(),
+            scopes: vec![
+                DelegationScope::Category("economic".into()),
+                DelegationScope::Proposal(TxHash::new([1; 32])),
+            ],
+            url: Some("https://ada.example".into()),
+            published_at: Timestamp::new(1_000),
+        };
+        let link = BlockHash::new(profile.anchor());
+        let signed = SignedDelegateProfile::sign(profile.clone(), &kp.private);
+        assert!(signed.verify().is_ok());
+        assert!(signed.profile.matches_anchor(&link));
+
+        let mut edited = signed.clone();
+        edited.profile.statement = "Liberal on monetary parameters.".into();
+        assert!(!edited.profile.matches_anchor(&link));
+        assert!(edited.verify().is_err());
+
+        let other = burst_crypto::keypair_from_seed(&[6u8; 32]);
+        let forged = SignedDelegateProfile::sign(profile.clone(), &other.private);
+        assert!(forged.verify().is_err());
+
+        let unscoped = SignedDelegateProfile::sign(
+            DelegateProfile {
+                scopes: Vec::new(),
+                ..profile
+            },
+            &kp.private,
+        );
+        assert!(matches!(
+            unscoped.verify(),
+            Err(GovernanceError::InvalidDelegateProfile(_))
+        ));
+    }
+}
@@ -96,4 +96,9 @@ pub enum ProposalContent {
         param: super::params::GovernableParam,
         new_value: u128,
     },
+    /// Pay `amount` of TRST from the protocol treasury to `recipient`.
+    TreasuryGrant {
+        recipient: WalletAddress,
+        amount: u128,
+    },
 }
@@ -0,0 +1,229 @@
+//! Protocol treasury — value the protocol takes back, held for grants.
+//!
+//! Verifier stakes forfeited for voting against the outcome, the stakes of
+//! rejected challenges and TRST that expires unspent are credited to the
+//! treasury instead of simply disappearing. Every inflow names the block
+//! or token it came from and is counted once, so nodes replaying the same
+//! chain arrive at the same balance. Governance disburses it with
+//! [`ProposalContent::TreasuryGrant`] proposals, paid out as newly minted
+//! TRST when the proposal's activation block is processed.
+//!
+//! [`ProposalContent::TreasuryGrant`]: crate::proposal::ProposalContent::TreasuryGrant
+
+use std::collections::HashSet;
+
+use burst_types::{PublicKey, Timestamp, TxHash, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+use crate::error::GovernanceError;
+
+/// Domain separator for the treasury account's key. Nobody holds the
+/// matching private key, so the account can never sign a block.
+const TREASURY_KEY_DOMAIN: &[u8] = b"burst-protocol-treasury-v1";
+
+/// Where a treasury inflow came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TreasurySource {
+    /// A verifier's stake, forfeited for voting against the outcome.
+    VerifierStake,
+    /// A challenger's stake, forfeited when the challenge was rejected.
+    ChallengeStake,
+    /// TRST that expired before it was spent.
+    ExpiredTrst,
+}
+
+impl TreasurySource {
+    pub const ALL: [TreasurySource; 3] = [
+        TreasurySource::VerifierStake,
+        TreasurySource::ChallengeStake,
+        TreasurySource::ExpiredTrst,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TreasurySource::VerifierStake => "verifier_stake",
+            TreasurySource::ChallengeStake => "challenge_stake",
+            TreasurySource::ExpiredTrst => "expired_trst",
+        }
+    }
+}
+
+/// Value credited to the treasury.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TreasuryInflow {
+    pub source: TreasurySource,
+    /// Block that forfeited the stake, or the token that expired.
+    pub reference: TxHash,
+    /// Wallet the value was taken from.
+    pub from: WalletAddress,
+    pub amount: u128,
+    pub at: Timestamp,
+}
+
+/// A grant paid out of the treasury.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TreasuryGrant {
+    /// The proposal that approved it.
+    pub proposal: TxHash,
+    pub recipient: WalletAddress,
+    pub amount: u128,
+    pub at: Timestamp,
+}
+
+/// Balance and full history of the protocol treasury.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Treasury {
+    balance: u128,
+    inflows: Vec<TreasuryInflow>,
+    grants: Vec<TreasuryGrant>,
+    /// Inflows already credited, by reference and source wallet.
+    credited: HashSet<(TxHash, WalletAddress)>,
+}
+
+impl Treasury {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The treasury's account address.
+    pub fn address() -> WalletAddress {
+        burst_crypto::derive_address(&PublicKey(burst_crypto::blake2b_256(TREASURY_KEY_DOMAIN)))
+    }
+
+    pub fn balance(&self) -> u128 {
+        self.balance
+    }
+
+    /// Every inflow, oldest first.
+    pub fn inflows(&self) -> &[TreasuryInflow] {
+        &self.inflows
+    }
+
+    /// Every grant paid, oldest first.
+    pub fn grants(&self) -> &[TreasuryGrant] {
+        &self.grants
+    }
+
+    /// Total credited from `source`.
+    pub fn total_from(&self, source: TreasurySource) -> u128 {
+        self.inflows
+            .iter()
+            .filter(|inflow| inflow.source == source)
+            .fold(0u128, |total, inflow| total.saturating_add(inflow.amount))
+    }
+
+    pub fn total_granted(&self) -> u128 {
+        self.grants
+            .iter()
+            .fold(0u128, |total, grant| total.saturating_add(grant.amount))
+    }
+
+    /// Credit `amount` taken from `from`. Returns `false` if this inflow was
+    /// already credited or is empty.
+    pub fn credit(
+        &mut self,
+        source: TreasurySource,
+        reference: TxHash,
+        from: &WalletAddress,
+        amount: u128,
+        at: Timestamp,
+    ) -> bool {
+        if amount == 0 || !self.credited.insert((reference, from.clone())) {
+            return false;
+        }
+        self.balance = self.balance.saturating_add(amount);
+        self.inflows.push(TreasuryInflow {
+            source,
+            reference,
+            from: from.clone(),
+            amount,
+            at,
+        });
+        true
+    }
+
+    /// Pay the grant approved by `proposal`, once.
+    pub fn disburse(
+        &mut self,
+        proposal: TxHash,
+        recipient: &WalletAddress,
+        amount: u128,
+        at: Timestamp,
+    ) -> Result<(), GovernanceError> {
+        if self.grants.iter().any(|grant| grant.proposal == proposal) {
+            return Err(GovernanceError::GrantAlreadyPaid(proposal.to_string()));
+        }
+        if amount > self.balance {
+            return Err(GovernanceError::InsufficientTreasury {
+                have: self.balance,
+                need: amount,
+            });
+        }
+        self.balance -= amount;
+        self.grants.push(TreasuryGrant {
+            proposal,
+            recipient: recipient.clone(),
+            amount,
+            at,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inflows_count_once_and_grants_draw_the_balance() {
+        let verifier = WalletAddress::new("brst_verifier");
+        let challenger = WalletAddress::new("brst_challenger");
+        let block = TxHash::new([1; 32]);
+        let mut treasury = Treasury::new();
+
+        assert!(treasury.credit(
+            TreasurySource::VerifierStake,
+            block,
+            &verifier,
+            300,
+            Timestamp::new(10)
+        ));
+        assert!(!treasury.credit(
+            TreasurySource::VerifierStake,
+            block,
+            &verifier,
+            300,
+            Timestamp::new(11)
+        ));
+        assert!(treasury.credit(
+            TreasurySource::ChallengeStake,
+            block,
+            &challenger,
+            200,
+            Timestamp::new(10)
+        ));
+        assert_eq!(treasury.balance(), 500);
+        assert_eq!(treasury.total_from(TreasurySource::VerifierStake), 300);
+        assert_eq!(treasury.total_from(TreasurySource::ExpiredTrst), 0);
+
+        let grant = TxHash::new([2; 32]);
+        let recipient = WalletAddress::new("brst_recipient");
+        assert!(matches!(
+            treasury.disburse(grant, &recipient, 501, Timestamp::new(20)),
+            Err(GovernanceError::InsufficientTreasury {
+                have: 500,
+                need: 501
+            })
+        ));
+        treasury
+            .disburse(grant, &recipient, 450, Timestamp::new(20))
+            .unwrap();
+        assert!(matches!(
+            treasury.disburse(grant, &recipient, 10, Timestamp::new(21)),
+            Err(GovernanceError::GrantAlreadyPaid(_))
+        ));
+        assert_eq!(treasury.balance(), 50);
+        assert_eq!(treasury.total_granted(), 450);
+        assert!(Treasury::address().as_str().starts_with("brst_"));
+    }
+}
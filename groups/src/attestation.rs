@@ -0,0 +1,170 @@
+//! Signed membership attestations.
+//!
+//! Instead of answering every `/verify` query live, a group can hand out an
+//! attestation: its signature over "wallet W is (not) a member with score S,
+//! valid until T". Clients cache attestations and verify them offline against
+//! the group's public key, so a spoofed endpoint cannot vouch for anyone.
+
+use burst_types::{KeyPair, PublicKey, Signature, Timestamp, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+use crate::error::GroupError;
+use crate::types::MemberStatus;
+
+/// Domain separator for attestation signatures.
+const ATTESTATION_DOMAIN: &[u8] = b"burst-group-attestation";
+
+/// A group's signed statement about one wallet's membership.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MembershipAttestation {
+    pub group_id: String,
+    pub member: WalletAddress,
+    pub valid: bool,
+    pub score: f64,
+    pub issued_at: Timestamp,
+    /// The attestation must not be relied on at or after this time.
+    pub expires_at: Timestamp,
+    pub signature: Signature,
+}
+
+impl MembershipAttestation {
+    /// Create and sign an attestation with the group's keypair.
+    pub fn issue(
+        group_id: impl Into<String>,
+        member: WalletAddress,
+        status: &MemberStatus,
+        issued_at: Timestamp,
+        expires_at: Timestamp,
+        group_keys: &KeyPair,
+    ) -> Self {
+        let mut attestation = Self {
+            group_id: group_id.into(),
+            member,
+            valid: status.valid,
+            score: status.score,
+            issued_at,
+            expires_at,
+            signature: Signature([0u8; 64]),
+        };
+        attestation.signature =
+            burst_crypto::sign_message(&attestation.signing_digest(), &group_keys.private);
+        attestation
+    }
+
+    /// Digest covered by the signature: every field except the signature.
+    pub fn signing_digest(&self) -> [u8; 32] {
+        let id_len = (self.group_id.len() as u32).to_be_bytes();
+        burst_crypto::blake2b_256_multi(&[
+            ATTESTATION_DOMAIN,
+            &id_len,
+            self.group_id.as_bytes(),
+            self.member.as_str().as_bytes(),
+            &[self.valid as u8],
+            &self.score.to_bits().to_be_bytes(),
+            &self.issued_at.as_secs().to_be_bytes(),
+            &self.expires_at.as_secs().to_be_bytes(),
+        ])
+    }
+
+    /// Whether the attestation has expired at `now`.
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Verify the signature against the group's key and check expiry.
+    pub fn verify(&self, group_key: &PublicKey, now: Timestamp) -> Result<(), GroupError> {
+        if self.expires_at <= self.issued_at {
+            return Err(GroupError::InvalidAttestation(
+                "expiry is not after issue time".into(),
+            ));
+        }
+        if !burst_crypto::verify_signature(&self.signing_digest(), &self.signature, group_key) {
+            return Err(GroupError::InvalidAttestation(format!(
+                "bad signature for group {}",
+                self.group_id
+            )));
+        }
+        if self.is_expired(now) {
+            return Err(GroupError::AttestationExpired {
+                expired_at: self.expires_at.as_secs(),
+            });
+        }
+        Ok(())
+    }
+
+    /// The attested status, in the shape returned by live verification.
+    pub fn member_status(&self) -> MemberStatus {
+        MemberStatus {
+            valid: self.valid,
+            score: self.score,
+            metadata: Some(serde_json::json!({
+                "attested_at": self.issued_at.as_secs(),
+                "expires_at": self.expires_at.as_secs(),
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(group_keys: &KeyPair) -> MembershipAttestation {
+        let member =
+            burst_crypto::derive_address(&burst_crypto::keypair_from_seed(&[9u8; 32]).public);
+        let status = MemberStatus {
+            valid: true,
+            score: 0.8,
+            metadata: None,
+        };
+        MembershipAttestation::issue(
+            "coop",
+            member,
+            &status,
+            Timestamp::new(1_000),
+            Timestamp::new(2_000),
+            group_keys,
+        )
+    }
+
+    #[test]
+    fn verifies_until_expiry() {
+        let keys = burst_crypto::keypair_from_seed(&[1u8; 32]);
+        let att = issue(&keys);
+        assert!(att.verify(&keys.public, Timestamp::new(1_500)).is_ok());
+        assert!(matches!(
+            att.verify(&keys.public, Timestamp::new(2_000)),
+            Err(GroupError::AttestationExpired { expired_at: 2_000 })
+        ));
+    }
+
+    #[test]
+    fn rejects_wrong_key_and_tampering() {
+        let keys = burst_crypto::keypair_from_seed(&[1u8; 32]);
+        let other = burst_crypto::keypair_from_seed(&[2u8; 32]);
+        let att = issue(&keys);
+        assert!(matches!(
+            att.verify(&other.public, Timestamp::new(1_500)),
+            Err(GroupError::InvalidAttestation(_))
+        ));
+
+        let mut boosted = att.clone();
+        boosted.score = 1.0;
+        assert!(boosted.verify(&keys.public, Timestamp::new(1_500)).is_err());
+
+        let mut extended = att;
+        extended.expires_at = Timestamp::new(9_000);
+        assert!(extended
+            .verify(&keys.public, Timestamp::new(1_500))
+            .is_err());
+    }
+
+    #[test]
+    fn serde_roundtrip_preserves_signature() {
+        let keys = burst_crypto::keypair_from_seed(&[1u8; 32]);
+        let att = issue(&keys);
+        let json = serde_json::to_string(&att).unwrap();
+        let back: MembershipAttestation = serde_json::from_str(&json).unwrap();
+        assert!(back.verify(&keys.public, Timestamp::new(1_500)).is_ok());
+    }
+}
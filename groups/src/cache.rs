@@ -0,0 +1,716 @@
+//! Response caching, request coalescing and circuit breaking for group lookups.
+//!
+//! [`CachedGroupClient`] sits in front of a [`GroupClient`] so that receive
+//! checks don't hit a group's endpoint for every pending transfer:
+//!
+//! - answers are kept in a bounded LRU keyed by (group, wallet), with a TTL
+//!   that can be set per group;
+//! - an expired answer is still served for a grace window while a background
+//!   task refreshes it (stale-while-revalidate);
+//! - concurrent misses for the same key share a single request;
+//! - repeated failures open a per-group circuit. While it is open, and
+//!   whenever a request fails, lookups degrade to the configured
+//!   [`FallbackPolicy`] instead of waiting on a dead endpoint.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use tokio::sync::OnceCell;
+
+use crate::client::GroupClient;
+use crate::error::GroupError;
+use crate::types::MemberStatus;
+
+/// Boxed future returned by a [`MembershipSource`].
+pub type SourceFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<MemberStatus, GroupError>> + Send + 'a>>;
+
+/// Where a [`CachedGroupClient`] gets fresh answers from.
+///
+/// Implemented by [`GroupClient`]; tests plug in an in-memory source.
+pub trait MembershipSource: Send + Sync + 'static {
+    fn verify_member<'a>(&'a self, endpoint_url: &'a str, wallet_id: &'a str) -> SourceFuture<'a>;
+}
+
+impl MembershipSource for GroupClient {
+    fn verify_member<'a>(&'a self, endpoint_url: &'a str, wallet_id: &'a str) -> SourceFuture<'a> {
+        Box::pin(GroupClient::verify_member(self, endpoint_url, wallet_id))
+    }
+}
+
+/// What a lookup returns when the group cannot be reached.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FallbackPolicy {
+    /// Return the error to the caller.
+    #[default]
+    Fail,
+    /// Serve the last cached answer however old it is, or fail if there is none.
+    LastKnown,
+    /// Treat the wallet as not a member.
+    Deny,
+    /// Treat the wallet as a member with the given score.
+    Allow { score: f64 },
+}
+
+/// Tuning for [`CachedGroupClient`].
+#[derive(Clone, Debug)]
+pub struct CacheConfig {
+    /// Maximum number of (group, wallet) answers kept.
+    pub capacity: usize,
+    /// How long an answer is fresh, unless overridden per group.
+    pub default_ttl: Duration,
+    /// Per-group TTL overrides.
+    pub group_ttls: HashMap<String, Duration>,
+    /// How long past its TTL an answer may still be served while it is
+    /// refreshed in the background.
+    pub stale_grace: Duration,
+    /// Consecutive failures that open a group's circuit.
+    pub failure_threshold: u32,
+    /// How long an open circuit short-circuits lookups before a trial request.
+    pub open_duration: Duration,
+    /// Behaviour when a group is unreachable.
+    pub fallback: FallbackPolicy,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            default_ttl: Duration::from_secs(300),
+            group_ttls: HashMap::new(),
+            stale_grace: Duration::from_secs(60),
+            failure_threshold: 3,
+            open_duration: Duration::from_secs(30),
+            fallback: FallbackPolicy::Fail,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Override the TTL for one group.
+    pub fn with_group_ttl(mut self, group_id: impl Into<String>, ttl: Duration) -> Self {
+        self.group_ttls.insert(group_id.into(), ttl);
+        self
+    }
+
+    /// The TTL that applies to `group_id`.
+    pub fn ttl_for(&self, group_id: &str) -> Duration {
+        self.group_ttls
+            .get(group_id)
+            .copied()
+            .unwrap_or(self.default_ttl)
+    }
+}
+
+/// State of a group's circuit breaker.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests go through normally.
+    Closed,
+    /// The endpoint is considered down; lookups use the fallback policy.
+    Open,
+    /// The cooldown has passed; the next request is a trial.
+    HalfOpen,
+}
+
+type Key = (String, String);
+
+struct Entry {
+    status: MemberStatus,
+    fetched_at: Instant,
+    /// Position in `State::order`.
+    tick: u64,
+}
+
+#[derive(Default)]
+struct Breaker {
+    failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl Breaker {
+    fn state(&self, now: Instant) -> CircuitState {
+        match self.open_until {
+            Some(until) if now < until => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+            None => CircuitState::Closed,
+        }
+    }
+
+    fn record_failure(&mut self, now: Instant, threshold: u32, open_for: Duration) {
+        self.failures = self.failures.saturating_add(1);
+        // A failed trial in the half-open state re-opens immediately.
+        if self.failures >= threshold || self.open_until.is_some() {
+            self.open_until = Some(now + open_for);
+        }
+    }
+}
+
+type InflightCell = Arc<OnceCell<Result<MemberStatus, GroupError>>>;
+
+#[derive(Default)]
+struct State {
+    entries: HashMap<Key, Entry>,
+    /// Access order: least recently used first.
+    order: BTreeMap<u64, Key>,
+    next_tick: u64,
+    breakers: HashMap<String, Breaker>,
+    inflight: HashMap<Key, InflightCell>,
+    refreshing: HashSet<Key>,
+}
+
+impl State {
+    /// Mark `key` as most recently used and return its entry.
+    fn touch(&mut self, key: &Key) -> Option<&Entry> {
+        let tick = self.next_tick;
+        let entry = self.entries.get_mut(key)?;
+        self.order.remove(&entry.tick);
+        entry.tick = tick;
+        self.order.insert(tick, key.clone());
+        self.next_tick += 1;
+        Some(entry)
+    }
+
+    fn insert(&mut self, key: Key, status: MemberStatus, now: Instant, capacity: usize) {
+        if let Some(old) = self.entries.remove(&key) {
+            self.order.remove(&old.tick);
+        }
+        while self.entries.len() >= capacity.max(1) {
+            let Some((_, lru)) = self.order.pop_first() else {
+                break;
+            };
+            self.entries.remove(&lru);
+        }
+        let tick = self.next_tick;
+        self.next_tick += 1;
+        self.order.insert(tick, key.clone());
+        self.entries.insert(
+            key,
+            Entry {
+                status,
+                fetched_at: now,
+                tick,
+            },
+        );
+    }
+
+    fn remove(&mut self, key: &Key) {
+        if let Some(old) = self.entries.remove(key) {
+            self.order.remove(&old.tick);
+        }
+    }
+
+    fn circuit(&self, group_id: &str, now: Instant) -> CircuitState {
+        self.breakers
+            .get(group_id)
+            .map_or(CircuitState::Closed, |b| b.state(now))
+    }
+}
+
+type Clock = Box<dyn Fn() -> Instant + Send + Sync>;
+
+struct Inner<S> {
+    source: S,
+    config: CacheConfig,
+    state: Mutex<State>,
+    clock: Clock,
+}
+
+/// A [`GroupClient`] with an LRU answer cache, request coalescing and
+/// per-group circuit breakers. Cheap to clone; clones share the cache.
+pub struct CachedGroupClient<S: MembershipSource = GroupClient> {
+    inner: Arc<Inner<S>>,
+}
+
+impl<S: MembershipSource> Clone for CachedGroupClient<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl CachedGroupClient<GroupClient> {
+    /// Cache in front of a default [`GroupClient`].
+    pub fn new(config: CacheConfig) -> Self {
+        Self::with_source(GroupClient::new(), config)
+    }
+}
+
+impl Default for CachedGroupClient<GroupClient> {
+    fn default() -> Self {
+        Self::new(CacheConfig::default())
+    }
+}
+
+impl<S: MembershipSource> CachedGroupClient<S> {
+    /// Cache in front of an arbitrary membership source.
+    pub fn with_source(source: S, config: CacheConfig) -> Self {
+        Self::with_clock(source, config, Box::new(Instant::now))
+    }
+
+    fn with_clock(source: S, config: CacheConfig, clock: Clock) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                source,
+                config,
+                state: Mutex::new(State::default()),
+                clock,
+            }),
+        }
+    }
+
+    pub fn config(&self) -> &CacheConfig {
+        &self.inner.config
+    }
+
+    /// Number of cached answers.
+    pub fn len(&self) -> usize {
+        self.state().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Current circuit state for a group.
+    pub fn circuit_state(&self, group_id: &str) -> CircuitState {
+        let now = self.now();
+        self.state().circuit(group_id, now)
+    }
+
+    /// Drop the cached answer for one wallet in one group.
+    pub fn invalidate(&self, group_id: &str, wallet_id: &str) {
+        self.state()
+            .remove(&(group_id.to_string(), wallet_id.to_string()));
+    }
+
+    /// Drop every cached answer from one group.
+    pub fn invalidate_group(&self, group_id: &str) {
+        let mut state = self.state();
+        let keys: Vec<Key> = state
+            .entries
+            .keys()
+            .filter(|(g, _)| g == group_id)
+            .cloned()
+            .collect();
+        for key in &keys {
+            state.remove(key);
+        }
+    }
+
+    /// Check a wallet's membership, served from cache when possible.
+    pub async fn verify_member(
+        &self,
+        group_id: &str,
+        endpoint_url: &str,
+        wallet_id: &str,
+    ) -> Result<MemberStatus, GroupError> {
+        let key = (group_id.to_string(), wallet_id.to_string());
+        let now = self.now();
+        let ttl = self.inner.config.ttl_for(group_id);
+        let circuit = {
+            let mut state = self.state();
+            let circuit = state.circuit(group_id, now);
+            if let Some(entry) = state.touch(&key) {
+                let age = now.saturating_duration_since(entry.fetched_at);
+                let status = entry.status.clone();
+                if age < ttl {
+                    return Ok(status);
+                }
+                if age < ttl + self.inner.config.stale_grace {
+                    if circuit != CircuitState::Open && state.refreshing.insert(key.clone()) {
+                        self.spawn_refresh(key, endpoint_url.to_string());
+                    }
+                    return Ok(status);
+                }
+            }
+            circuit
+        };
+
+        if circuit == CircuitState::Open {
+            let err = GroupError::Unreachable(format!("circuit open for group {group_id}"));
+            return self.degrade(&key, err);
+        }
+        match self.fetch(&key, endpoint_url).await {
+            Ok(status) => Ok(status),
+            Err(e) => self.degrade(&key, e),
+        }
+    }
+
+    /// Query several groups concurrently; results are in input order.
+    pub async fn verify_member_multi(
+        &self,
+        endpoints: &[(&str, &str)], // (group_id, endpoint_url)
+        wallet_id: &str,
+    ) -> Vec<(String, Result<MemberStatus, GroupError>)> {
+        let mut handles = Vec::with_capacity(endpoints.len());
+        for (group_id, endpoint_url) in endpoints {
+            let this = self.clone();
+            let gid = group_id.to_string();
+            let url = endpoint_url.to_string();
+            let wallet = wallet_id.to_string();
+            handles.push(tokio::spawn(async move {
+                let result = this.verify_member(&gid, &url, &wallet).await;
+                (gid, result)
+            }));
+        }
+
+        let mut results = Vec::with_capacity(handles.len());
+        for (handle, (group_id, _)) in handles.into_iter().zip(endpoints) {
+            results.push(handle.await.unwrap_or_else(|e| {
+                (
+                    group_id.to_string(),
+                    Err(GroupError::Other(format!("task join error: {e}"))),
+                )
+            }));
+        }
+        results
+    }
+
+    fn now(&self) -> Instant {
+        (self.inner.clock)()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.inner
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Fetch from the source, sharing the request with concurrent callers for
+    /// the same key. Updates the cache and the group's breaker once per request.
+    async fn fetch(&self, key: &Key, endpoint_url: &str) -> Result<MemberStatus, GroupError> {
+        let cell = self
+            .state()
+            .inflight
+            .entry(key.clone())
+            .or_default()
+            .clone();
+        let result = cell
+            .get_or_init(|| async {
+                let result = self.inner.source.verify_member(endpoint_url, &key.1).await;
+                self.record(key, &result);
+                result
+            })
+            .await
+            .clone();
+
+        let mut state = self.state();
+        if state
+            .inflight
+            .get(key)
+            .is_some_and(|current| Arc::ptr_eq(current, &cell))
+        {
+            state.inflight.remove(key);
+        }
+        result
+    }
+
+    fn record(&self, key: &Key, result: &Result<MemberStatus, GroupError>) {
+        let now = self.now();
+        let config = &self.inner.config;
+        let mut state = self.state();
+        match result {
+            Ok(status) => {
+                state.breakers.remove(&key.0);
+                state.insert(key.clone(), status.clone(), now, config.capacity);
+            }
+            Err(_) => {
+                state
+                    .breakers
+                    .entry(key.0.clone())
+                    .or_default()
+                    .record_failure(now, config.failure_threshold, config.open_duration);
+            }
+        }
+    }
+
+    fn spawn_refresh(&self, key: Key, endpoint_url: String) {
+        let this = self.clone();
+        tokio::spawn(async move {
+            // Errors are recorded against the breaker; the stale answer stays.
+            let _ = this.fetch(&key, &endpoint_url).await;
+            this.state().refreshing.remove(&key);
+        });
+    }
+
+    fn degrade(&self, key: &Key, err: GroupError) -> Result<MemberStatus, GroupError> {
+        let fallback = |label: &str, valid: bool, score: f64| MemberStatus {
+            valid,
+            score,
+            metadata: Some(serde_json::json!({ "fallback": label, "error": err.to_string() })),
+        };
+        match self.inner.config.fallback {
+            FallbackPolicy::Fail => Err(err),
+            FallbackPolicy::LastKnown => match self.state().touch(key) {
+                Some(entry) => Ok(entry.status.clone()),
+                None => Err(err),
+            },
+            FallbackPolicy::Deny => Ok(fallback("deny", false, 0.0)),
+            FallbackPolicy::Allow { score } => Ok(fallback("allow", true, score)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+    /// In-memory source that counts calls and can be switched off.
+    #[derive(Clone, Default)]
+    struct FakeSource {
+        calls: Arc<AtomicU32>,
+        down: Arc<AtomicBool>,
+        delay: Duration,
+    }
+
+    impl MembershipSource for FakeSource {
+        fn verify_member<'a>(&'a self, _endpoint: &'a str, wallet_id: &'a str) -> SourceFuture<'a> {
+            Box::pin(async move {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                if !self.delay.is_zero() {
+                    tokio::time::sleep(self.delay).await;
+                }
+                if self.down.load(Ordering::SeqCst) {
+                    return Err(GroupError::Unreachable("down".into()));
+                }
+                Ok(MemberStatus {
+                    valid: wallet_id.starts_with("brst_"),
+                    score: 0.9,
+                    metadata: None,
+                })
+            })
+        }
+    }
+
+    /// Manually advanced clock.
+    fn manual_clock() -> (Arc<Mutex<Duration>>, Clock) {
+        let start = Instant::now();
+        let offset = Arc::new(Mutex::new(Duration::ZERO));
+        let handle = Arc::clone(&offset);
+        (offset, Box::new(move || start + *handle.lock().unwrap()))
+    }
+
+    fn advance(offset: &Mutex<Duration>, by: Duration) {
+        *offset.lock().unwrap() += by;
+    }
+
+    fn client(
+        source: &FakeSource,
+        config: CacheConfig,
+    ) -> (Arc<Mutex<Duration>>, CachedGroupClient<FakeSource>) {
+        let (offset, clock) = manual_clock();
+        (
+            offset,
+            CachedGroupClient::with_clock(source.clone(), config, clock),
+        )
+    }
+
+    #[tokio::test]
+    async fn caches_until_per_group_ttl_expires() {
+        let source = FakeSource::default();
+        let config = CacheConfig {
+            stale_grace: Duration::ZERO,
+            ..CacheConfig::default()
+        }
+        .with_group_ttl("fast", Duration::from_secs(10));
+        let (offset, cached) = client(&source, config);
+
+        for _ in 0..3 {
+            cached
+                .verify_member("fast", "http://g", "brst_a")
+                .await
+                .unwrap();
+            cached
+                .verify_member("slow", "http://g", "brst_a")
+                .await
+                .unwrap();
+        }
+        assert_eq!(source.calls.load(Ordering::SeqCst), 2);
+
+        advance(&offset, Duration::from_secs(11));
+        cached
+            .verify_member("fast", "http://g", "brst_a")
+            .await
+            .unwrap();
+        cached
+            .verify_member("slow", "http://g", "brst_a")
+            .await
+            .unwrap();
+        assert_eq!(source.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn evicts_least_recently_used() {
+        let source = FakeSource::default();
+        let config = CacheConfig {
+            capacity: 2,
+            ..CacheConfig::default()
+        };
+        let (_, cached) = client(&source, config);
+
+        cached
+            .verify_member("g", "http://g", "brst_a")
+            .await
+            .unwrap();
+        cached
+            .verify_member("g", "http://g", "brst_b")
+            .await
+            .unwrap();
+        // Touch `a` so `b` becomes the eviction candidate.
+        cached
+            .verify_member("g", "http://g", "brst_a")
+            .await
+            .unwrap();
+        cached
+            .verify_member("g", "http://g", "brst_c")
+            .await
+            .unwrap();
+        assert_eq!(cached.len(), 2);
+        assert_eq!(source.calls.load(Ordering::SeqCst), 3);
+
+        cached
+            .verify_member("g", "http://g", "brst_a")
+            .await
+            .unwrap();
+        assert_eq!(source.calls.load(Ordering::SeqCst), 3);
+        cached
+            .verify_member("g", "http://g", "brst_b")
+            .await
+            .unwrap();
+        assert_eq!(source.calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn serves_stale_while_refreshing() {
+        let source = FakeSource::default();
+        let config = CacheConfig {
+            default_ttl: Duration::from_secs(10),
+            stale_grace: Duration::from_secs(10),
+            ..CacheConfig::default()
+        };
+        let (offset, cached) = client(&source, config);
+
+        cached
+            .verify_member("g", "http://g", "brst_a")
+            .await
+            .unwrap();
+        advance(&offset, Duration::from_secs(15));
+        let stale = cached.verify_member("g", "http://g", "brst_a").await;
+        assert!(stale.unwrap().valid);
+
+        // The background refresh runs once and makes the entry fresh again.
+        for _ in 0..50 {
+            if cached.state().refreshing.is_empty() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(source.calls.load(Ordering::SeqCst), 2);
+        cached
+            .verify_member("g", "http://g", "brst_a")
+            .await
+            .unwrap();
+        assert_eq!(source.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn coalesces_concurrent_misses() {
+        let source = FakeSource {
+            delay: Duration::from_millis(20),
+            ..FakeSource::default()
+        };
+        let (_, cached) = client(&source, CacheConfig::default());
+
+        let lookups: Vec<_> = (0..8)
+            .map(|_| {
+                let cached = cached.clone();
+                tokio::spawn(async move { cached.verify_member("g", "http://g", "brst_a").await })
+            })
+            .collect();
+        for lookup in lookups {
+            assert!(lookup.await.unwrap().unwrap().valid);
+        }
+        assert_eq!(source.calls.load(Ordering::SeqCst), 1);
+        assert!(cached.state().inflight.is_empty());
+    }
+
+    #[tokio::test]
+    async fn circuit_opens_and_degrades_to_fallback() {
+        let source = FakeSource::default();
+        source.down.store(true, Ordering::SeqCst);
+        let config = CacheConfig {
+            failure_threshold: 2,
+            open_duration: Duration::from_secs(30),
+            fallback: FallbackPolicy::Deny,
+            ..CacheConfig::default()
+        };
+        let (offset, cached) = client(&source, config);
+
+        for _ in 0..2 {
+            let status = cached
+                .verify_member("g", "http://g", "brst_a")
+                .await
+                .unwrap();
+            assert!(!status.valid);
+        }
+        assert_eq!(cached.circuit_state("g"), CircuitState::Open);
+        assert_eq!(cached.circuit_state("other"), CircuitState::Closed);
+
+        // While open, the endpoint is not contacted at all.
+        cached
+            .verify_member("g", "http://g", "brst_b")
+            .await
+            .unwrap();
+        assert_eq!(source.calls.load(Ordering::SeqCst), 2);
+
+        // After the cooldown a trial request goes through and closes the circuit.
+        advance(&offset, Duration::from_secs(31));
+        assert_eq!(cached.circuit_state("g"), CircuitState::HalfOpen);
+        source.down.store(false, Ordering::SeqCst);
+        assert!(
+            cached
+                .verify_member("g", "http://g", "brst_a")
+                .await
+                .unwrap()
+                .valid
+        );
+        assert_eq!(cached.circuit_state("g"), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn last_known_fallback_serves_expired_answer() {
+        let source = FakeSource::default();
+        let config = CacheConfig {
+            default_ttl: Duration::from_secs(10),
+            stale_grace: Duration::ZERO,
+            fallback: FallbackPolicy::LastKnown,
+            ..CacheConfig::default()
+        };
+        let (offset, cached) = client(&source, config);
+
+        cached
+            .verify_member("g", "http://g", "brst_a")
+            .await
+            .unwrap();
+        source.down.store(true, Ordering::SeqCst);
+        advance(&offset, Duration::from_secs(3_600));
+        assert!(
+            cached
+                .verify_member("g", "http://g", "brst_a")
+                .await
+                .unwrap()
+                .valid
+        );
+        assert!(matches!(
+            cached.verify_member("g", "http://g", "brst_b").await,
+            Err(GroupError::Unreachable(_))
+        ));
+    }
+}
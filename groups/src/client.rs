@@ -1,5 +1,6 @@
 //! HTTP client for querying group verification endpoints.
 
+use crate::attestation::MembershipAttestation;
 use crate::error::GroupError;
 use crate::types::MemberStatus;
 
@@ -15,6 +16,7 @@ const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
 /// Client for querying group trust endpoints.
 ///
 /// Sends `GET /verify/{wallet_id}` to a group's endpoint and parses the response.
+#[derive(Clone)]
 pub struct GroupClient {
     /// HTTP client (reusable connection pool).
     http_client: reqwest::Client,
@@ -95,6 +97,45 @@ impl GroupClient {
         })
     }
 
+    /// Fetch a signed membership attestation for a wallet.
+    ///
+    /// `GET {endpoint_url}/attest/{wallet_id}` -> MembershipAttestation.
+    /// The result is not verified; check it with
+    /// [`MembershipAttestation::verify`] or `GroupRegistry::verify_attestation`.
+    pub async fn fetch_attestation(
+        &self,
+        endpoint_url: &str,
+        wallet_id: &str,
+    ) -> Result<MembershipAttestation, GroupError> {
+        let url = format!(
+            "{}/attest/{}",
+            endpoint_url.trim_end_matches('/'),
+            wallet_id
+        );
+
+        let response = self.http_client.get(&url).send().await.map_err(|e| {
+            if e.is_timeout() {
+                GroupError::Unreachable(format!("request timed out: {e}"))
+            } else if e.is_connect() {
+                GroupError::Unreachable(format!("connection failed: {e}"))
+            } else {
+                GroupError::RequestFailed(e.to_string())
+            }
+        })?;
+
+        if !response.status().is_success() {
+            return Err(GroupError::RequestFailed(format!(
+                "HTTP status {}",
+                response.status()
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| GroupError::InvalidResponse(format!("failed to parse attestation: {e}")))
+    }
+
     /// Query multiple groups in parallel for a wallet.
     ///
     /// Each endpoint is queried concurrently via `tokio::spawn`. Results are
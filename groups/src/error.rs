@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-#[derive(Debug, Error)]
+#[derive(Clone, Debug, Error)]
 pub enum GroupError {
     #[error("group {0} not found")]
     GroupNotFound(String),
@@ -14,6 +14,12 @@ pub enum GroupError {
     #[error("group endpoint unreachable: {0}")]
     Unreachable(String),
 
+    #[error("invalid membership attestation: {0}")]
+    InvalidAttestation(String),
+
+    #[error("membership attestation expired at {expired_at}")]
+    AttestationExpired { expired_at: u64 },
+
     #[error("{0}")]
     Other(String),
 }
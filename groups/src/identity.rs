@@ -0,0 +1,98 @@
+//! Signed group identities and their on-chain anchors.
+//!
+//! A group proves who it is with an Ed25519 keypair. Its identity — ID,
+//! public key, and endpoint — can be committed to on-chain with a
+//! `GroupAnchor` block whose link is [`GroupIdentity::anchor`], so clients can
+//! detect a swapped key or hijacked endpoint without trusting the endpoint.
+
+use burst_types::{BlockHash, PublicKey};
+use serde::{Deserialize, Serialize};
+
+use crate::types::GroupInfo;
+
+/// Domain separator for group anchor commitments.
+const ANCHOR_DOMAIN: &[u8] = b"burst-group-anchor";
+
+/// The verifiable identity of a trust group.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupIdentity {
+    pub group_id: String,
+    pub public_key: PublicKey,
+    pub endpoint_url: String,
+}
+
+impl GroupIdentity {
+    pub fn new(
+        group_id: impl Into<String>,
+        public_key: PublicKey,
+        endpoint_url: impl Into<String>,
+    ) -> Self {
+        Self {
+            group_id: group_id.into(),
+            public_key,
+            endpoint_url: endpoint_url.into(),
+        }
+    }
+
+    /// Identity of a registered group, if it has published a signing key.
+    pub fn from_info(info: &GroupInfo) -> Option<Self> {
+        Some(Self::new(
+            info.id.clone(),
+            info.public_key.clone()?,
+            info.endpoint_url.clone(),
+        ))
+    }
+
+    /// Blake2b-256 of the endpoint URL (trailing slashes ignored).
+    pub fn endpoint_hash(&self) -> [u8; 32] {
+        burst_crypto::blake2b_256(self.endpoint_url.trim_end_matches('/').as_bytes())
+    }
+
+    /// Commitment to the group ID, key, and endpoint hash, used as the link
+    /// of a `GroupAnchor` block.
+    pub fn anchor(&self) -> [u8; 32] {
+        let id_len = (self.group_id.len() as u32).to_be_bytes();
+        burst_crypto::blake2b_256_multi(&[
+            ANCHOR_DOMAIN,
+            &id_len,
+            self.group_id.as_bytes(),
+            &self.public_key.0,
+            &self.endpoint_hash(),
+        ])
+    }
+
+    /// Whether an on-chain anchor link commits to this identity.
+    pub fn matches_anchor(&self, link: &BlockHash) -> bool {
+        link.as_bytes() == &self.anchor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity() -> GroupIdentity {
+        let kp = burst_crypto::keypair_from_seed(&[3u8; 32]);
+        GroupIdentity::new("coop", kp.public, "https://coop.example/")
+    }
+
+    #[test]
+    fn anchor_commits_to_key_and_endpoint() {
+        let id = identity();
+        let link = BlockHash::new(id.anchor());
+        assert!(id.matches_anchor(&link));
+
+        let mut moved = id.clone();
+        moved.endpoint_url = "https://evil.example".into();
+        assert!(!moved.matches_anchor(&link));
+
+        let mut rekeyed = id.clone();
+        rekeyed.public_key = burst_crypto::keypair_from_seed(&[4u8; 32]).public;
+        assert!(!rekeyed.matches_anchor(&link));
+
+        // Trailing slash doesn't change the endpoint hash
+        let mut trimmed = id;
+        trimmed.endpoint_url = "https://coop.example".into();
+        assert!(trimmed.matches_anchor(&link));
+    }
+}
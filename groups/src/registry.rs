@@ -1,7 +1,9 @@
 //! Group registry — discover and manage known groups.
 
+use crate::attestation::MembershipAttestation;
 use crate::error::GroupError;
 use crate::types::GroupInfo;
+use burst_types::Timestamp;
 use std::collections::HashMap;
 
 /// Registry of known groups and their endpoints.
@@ -36,6 +38,24 @@ impl GroupRegistry {
     pub fn list(&self) -> Vec<&GroupInfo> {
         self.groups.values().collect()
     }
+
+    /// Verify an attestation against the registered key of its group.
+    pub fn verify_attestation(
+        &self,
+        attestation: &MembershipAttestation,
+        now: Timestamp,
+    ) -> Result<(), GroupError> {
+        let info = self
+            .get(&attestation.group_id)
+            .ok_or_else(|| GroupError::GroupNotFound(attestation.group_id.clone()))?;
+        let key = info.public_key.as_ref().ok_or_else(|| {
+            GroupError::InvalidAttestation(format!(
+                "group {} has no registered signing key",
+                info.id
+            ))
+        })?;
+        attestation.verify(key, now)
+    }
 }
 
 impl Default for GroupRegistry {
@@ -43,3 +63,56 @@ impl Default for GroupRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MemberStatus;
+
+    #[test]
+    fn verify_attestation_uses_registered_key() {
+        let keys = burst_crypto::keypair_from_seed(&[1u8; 32]);
+        let mut registry = GroupRegistry::new();
+        registry
+            .register(GroupInfo {
+                id: "coop".into(),
+                name: "Coop".into(),
+                description: String::new(),
+                endpoint_url: "https://coop.example".into(),
+                member_count: 1,
+                public_key: Some(keys.public.clone()),
+            })
+            .unwrap();
+
+        let member = burst_types::WalletAddress::new("brst_member");
+        let status = MemberStatus {
+            valid: true,
+            score: 1.0,
+            metadata: None,
+        };
+        let att = MembershipAttestation::issue(
+            "coop",
+            member.clone(),
+            &status,
+            Timestamp::new(10),
+            Timestamp::new(20),
+            &keys,
+        );
+        assert!(registry
+            .verify_attestation(&att, Timestamp::new(15))
+            .is_ok());
+
+        let stranger = MembershipAttestation::issue(
+            "other",
+            member,
+            &status,
+            Timestamp::new(10),
+            Timestamp::new(20),
+            &keys,
+        );
+        assert!(matches!(
+            registry.verify_attestation(&stranger, Timestamp::new(15)),
+            Err(GroupError::GroupNotFound(_))
+        ));
+    }
+}
@@ -1,5 +1,6 @@
 //! Group trust layer types.
 
+use burst_types::PublicKey;
 use serde::{Deserialize, Serialize};
 
 /// Information about a registered group.
@@ -15,6 +16,9 @@ pub struct GroupInfo {
     pub endpoint_url: String,
     /// Number of members (self-reported).
     pub member_count: u64,
+    /// The group's signing key, if it issues membership attestations.
+    #[serde(default)]
+    pub public_key: Option<PublicKey>,
 }
 
 /// Response from a group's member verification endpoint.
@@ -1,8 +1,11 @@
 //! Per-account chain management.
 
 use crate::error::LedgerError;
-use crate::state_block::StateBlock;
-use burst_types::{BlockHash, WalletAddress};
+use crate::state_block::{BlockType, StateBlock};
+use burst_store::account::AccountInfo;
+use burst_store::block::BlockStore;
+use burst_store::StoreError;
+use burst_types::{BlockHash, Timestamp, TxHash, WalletAddress};
 
 /// Represents a single account's chain in the block-lattice.
 pub struct AccountChain {
@@ -35,3 +38,69 @@ impl AccountChain {
         self.block_count += 1;
     }
 }
+
+/// Walk `account`'s chain back from the head, handing each block and its
+/// height to `visit` until it returns `false` or the chain runs out.
+fn walk_back<S: BlockStore + ?Sized>(
+    blocks: &S,
+    account: &AccountInfo,
+    mut visit: impl FnMut(u64, StateBlock) -> bool,
+) -> Result<(), StoreError> {
+    for height in (1..=account.block_count).rev() {
+        let Some(hash) = blocks.block_at_height(&account.address, height)? else {
+            break;
+        };
+        let Ok(block) = bincode::deserialize::<StateBlock>(&blocks.get_block(&hash)?) else {
+            break;
+        };
+        if !visit(height, block) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// TRST `account` sent (or locked in HTLCs) in blocks stamped at or after
+/// `since`, walking its chain back from the head. The block processor keeps
+/// timestamps from running backwards along a chain, so the walk stops at the
+/// first block stamped before `since`.
+pub fn trst_sent_since<S: BlockStore + ?Sized>(
+    blocks: &S,
+    account: &AccountInfo,
+    since: Timestamp,
+) -> Result<u128, StoreError> {
+    let mut sent = 0u128;
+    let mut newer: Option<StateBlock> = None;
+    walk_back(blocks, account, |_, block| {
+        if let Some(newer) = newer.take() {
+            if matches!(newer.block_type, BlockType::Send | BlockType::HtlcLock) {
+                sent = sent.saturating_add(block.trst_balance.saturating_sub(newer.trst_balance));
+            }
+        }
+        let in_window = block.timestamp >= since;
+        newer = Some(block);
+        in_window
+    })?;
+    Ok(sent)
+}
+
+/// Height of the block on `account`'s chain, stamped at or after `since`,
+/// that carries `transaction`. Transactions are only accepted within a
+/// timestamp window, so looking back over that window is enough to catch a
+/// resubmission.
+pub fn transaction_height_since<S: BlockStore + ?Sized>(
+    blocks: &S,
+    account: &AccountInfo,
+    transaction: &TxHash,
+    since: Timestamp,
+) -> Result<Option<u64>, StoreError> {
+    let mut found = None;
+    walk_back(blocks, account, |height, block| {
+        if block.transaction == *transaction {
+            found = Some(height);
+            return false;
+        }
+        block.timestamp >= since
+    })?;
+    Ok(found)
+}
@@ -0,0 +1,274 @@
+//! Representative-signed ledger checkpoints.
+//!
+//! Every so many cemented blocks a representative takes a [`CheckpointDigest`]
+//! of its cemented ledger: the cemented block count, a root over each
+//! account's cemented frontier and an account-state root over each
+//! account's [`CementedAccount`] state. It signs the digest and gossips the
+//! [`CheckpointVote`]. Votes for the same digest are collected into a
+//! [`Checkpoint`]; once the signers hold a quorum of weight it is a trust
+//! anchor much shallower than genesis: a bootstrapping node can check the
+//! cemented frontiers it is given against it, and a light client can start
+//! from it.
+//!
+//! A digest commits to cemented blocks only, and is taken exactly when the
+//! cemented count reaches a multiple of the interval, so representatives
+//! that cemented the same blocks sign the same digest however many blocks
+//! each has received beyond them. Several digests may still circulate for
+//! one cemented count when nodes cemented different blocks to reach it;
+//! each is kept apart and only a digest that gathers a quorum counts.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use burst_store::state_tree::{account_key, tree_root};
+use burst_types::{BlockHash, PrivateKey, PublicKey, Signature, WalletAddress};
+
+/// Domain separator for checkpoint signatures.
+const CHECKPOINT_DOMAIN: &[u8] = b"burst-checkpoint";
+
+/// The cemented ledger state a checkpoint commits to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CheckpointDigest {
+    /// Cemented blocks across all accounts.
+    pub cemented_count: u64,
+    /// Root over every account's cemented frontier, see [`frontiers_root`].
+    pub frontiers_root: BlockHash,
+    /// Root of a tree shaped like the account-state tree (see
+    /// [`burst_store::state_tree`]) whose leaves are
+    /// [`CementedAccount::state_hash`]es, against which single accounts can
+    /// be proven.
+    pub account_state_root: BlockHash,
+}
+
+impl CheckpointDigest {
+    /// Digest of the cemented accounts, given in ascending account order.
+    pub fn from_cemented(accounts: &[CementedAccount]) -> Self {
+        let cemented_count = accounts
+            .iter()
+            .fold(0u64, |total, account| total.saturating_add(account.height));
+        let mut leaves: Vec<_> = accounts
+            .iter()
+            .map(|account| (account_key(&account.address), account.state_hash()))
+            .collect();
+        Self {
+            cemented_count,
+            frontiers_root: frontiers_root(accounts.iter().map(|a| (&a.address, &a.frontier))),
+            account_state_root: tree_root(&mut leaves),
+        }
+    }
+
+    /// The bytes a representative signs: domain tag, cemented count
+    /// (big-endian) and both roots.
+    pub fn signed_message(&self) -> Vec<u8> {
+        let mut msg = Vec::with_capacity(CHECKPOINT_DOMAIN.len() + 8 + 64);
+        msg.extend_from_slice(CHECKPOINT_DOMAIN);
+        msg.extend_from_slice(&self.cemented_count.to_be_bytes());
+        msg.extend_from_slice(self.frontiers_root.as_bytes());
+        msg.extend_from_slice(self.account_state_root.as_bytes());
+        msg
+    }
+
+    /// Identifier of the digest, used to key stored checkpoints.
+    pub fn hash(&self) -> BlockHash {
+        BlockHash::new(burst_crypto::blake2b_256(&self.signed_message()))
+    }
+
+    /// Whether `accounts`, in ascending account order, are the cemented
+    /// accounts this digest commits to.
+    pub fn covers(&self, accounts: &[CementedAccount]) -> bool {
+        *self == Self::from_cemented(accounts)
+    }
+}
+
+/// An account's cemented state: its cemented height and the block at that
+/// height, whose hash commits to the account's balances and representative.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CementedAccount {
+    pub address: WalletAddress,
+    /// Cemented blocks in the account's chain.
+    pub height: u64,
+    /// Hash of the block at `height`.
+    pub frontier: BlockHash,
+}
+
+impl CementedAccount {
+    /// Hash of the account's cemented state, a leaf of the digest's
+    /// account-state root.
+    pub fn state_hash(&self) -> BlockHash {
+        BlockHash::new(burst_crypto::blake2b_256_multi(&[
+            self.address.as_str().as_bytes(),
+            &self.height.to_be_bytes(),
+            self.frontier.as_bytes(),
+        ]))
+    }
+}
+
+/// Root over cemented frontiers, given as `(account, cemented head)` in
+/// ascending account order. Accounts without cemented blocks are left out.
+pub fn frontiers_root<'a>(
+    frontiers: impl IntoIterator<Item = (&'a WalletAddress, &'a BlockHash)>,
+) -> BlockHash {
+    let mut root = [0u8; 32];
+    for (account, head) in frontiers {
+        root =
+            burst_crypto::blake2b_256_multi(&[&root, account.as_str().as_bytes(), head.as_bytes()]);
+    }
+    BlockHash::new(root)
+}
+
+/// One representative's signature over a checkpoint digest.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointVote {
+    pub digest: CheckpointDigest,
+    pub representative: WalletAddress,
+    pub signature: Signature,
+}
+
+impl CheckpointVote {
+    /// Sign `digest` as the holder of `key`.
+    pub fn sign(digest: CheckpointDigest, key: &PrivateKey) -> Self {
+        let representative = burst_crypto::derive_address(&burst_crypto::public_from_private(key));
+        let signature = burst_crypto::sign_message(&digest.signed_message(), key);
+        Self {
+            digest,
+            representative,
+            signature,
+        }
+    }
+
+    /// Whether the signature is valid for the representative's key.
+    pub fn verify_signature(&self) -> bool {
+        let Some(key) = burst_crypto::decode_address(self.representative.as_str()) else {
+            return false;
+        };
+        burst_crypto::verify_signature(
+            &self.digest.signed_message(),
+            &self.signature,
+            &PublicKey(key),
+        )
+    }
+}
+
+/// Why a checkpoint is not a trust anchor.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum CheckpointError {
+    #[error("vote from {0} is for a different digest")]
+    DigestMismatch(WalletAddress),
+    #[error("vote from {0} is not signed by its representative")]
+    BadSignature(WalletAddress),
+    #[error("checkpoint carries {weight} weight, {required} required")]
+    InsufficientWeight { weight: u128, required: u128 },
+}
+
+/// A digest with the representative votes collected for it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub digest: CheckpointDigest,
+    /// At most one vote per representative.
+    pub votes: Vec<CheckpointVote>,
+    /// Unix seconds when the digest was first seen.
+    pub first_seen: u64,
+}
+
+impl Checkpoint {
+    pub fn new(digest: CheckpointDigest, first_seen: u64) -> Self {
+        Self {
+            digest,
+            votes: Vec::new(),
+            first_seen,
+        }
+    }
+
+    /// Add a vote for this digest. Returns `false` for a vote on another
+    /// digest or from a representative that already voted.
+    pub fn add_vote(&mut self, vote: CheckpointVote) -> bool {
+        if vote.digest != self.digest
+            || self
+                .votes
+                .iter()
+                .any(|v| v.representative == vote.representative)
+        {
+            return false;
+        }
+        self.votes.push(vote);
+        true
+    }
+
+    /// Combined weight of the voters under `weight_of`.
+    pub fn weight(&self, weight_of: impl Fn(&WalletAddress) -> u128) -> u128 {
+        self.votes.iter().fold(0u128, |total, vote| {
+            total.saturating_add(weight_of(&vote.representative))
+        })
+    }
+
+    /// Check every vote and that the voters hold at least `required`
+    /// weight under `weight_of`. Returns the weight that signed.
+    pub fn verify(
+        &self,
+        weight_of: impl Fn(&WalletAddress) -> u128,
+        required: u128,
+    ) -> Result<u128, CheckpointError> {
+        for vote in &self.votes {
+            if vote.digest != self.digest {
+                return Err(CheckpointError::DigestMismatch(vote.representative.clone()));
+            }
+            if !vote.verify_signature() {
+                return Err(CheckpointError::BadSignature(vote.representative.clone()));
+            }
+        }
+        let weight = self.weight(weight_of);
+        if weight < required {
+            return Err(CheckpointError::InsufficientWeight { weight, required });
+        }
+        Ok(weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cemented() -> Vec<CementedAccount> {
+        vec![CementedAccount {
+            address: WalletAddress::new("brst_alice"),
+            height: 10_000,
+            frontier: BlockHash::new([1; 32]),
+        }]
+    }
+
+    fn digest() -> CheckpointDigest {
+        CheckpointDigest::from_cemented(&cemented())
+    }
+
+    #[test]
+    fn quorum_of_signed_votes_makes_an_anchor() {
+        let reps: Vec<_> = (0..3).map(|_| burst_crypto::generate_keypair()).collect();
+        let mut checkpoint = Checkpoint::new(digest(), 0);
+        for rep in &reps {
+            assert!(checkpoint.add_vote(CheckpointVote::sign(digest(), &rep.private)));
+        }
+        assert!(!checkpoint.add_vote(CheckpointVote::sign(digest(), &reps[0].private)));
+
+        let weight_of = |_: &WalletAddress| 40u128;
+        assert_eq!(checkpoint.verify(weight_of, 100), Ok(120));
+        assert_eq!(
+            checkpoint.verify(weight_of, 121),
+            Err(CheckpointError::InsufficientWeight {
+                weight: 120,
+                required: 121
+            })
+        );
+
+        checkpoint.votes[1].digest.cemented_count += 1;
+        assert!(matches!(
+            checkpoint.verify(weight_of, 100),
+            Err(CheckpointError::DigestMismatch(_))
+        ));
+
+        assert_eq!(digest().cemented_count, 10_000);
+        assert!(digest().covers(&cemented()));
+        let mut behind = cemented();
+        behind[0].height -= 1;
+        assert!(!digest().covers(&behind));
+    }
+}
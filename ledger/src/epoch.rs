@@ -0,0 +1,184 @@
+//! Ledger epochs — gating block versions for format migrations.
+//!
+//! Every account starts at epoch 0 and may publish blocks up to
+//! [`BASE_BLOCK_VERSION`]. A new block layout is rolled out by raising the
+//! epoch: either per account, with an epoch block signed by the genesis or
+//! upgrade key (whose `version` becomes the account's epoch), or for the
+//! whole ledger at once, by a governance vote on the `ledger_epoch`
+//! parameter. An account may then publish blocks up to the higher of the
+//! two. Nodes keep validating older versions, so the switch needs no
+//! ledger-wide restart.
+//!
+//! Version 3 binds blocks to one network. It is also a floor: once an
+//! account (or the whole ledger) reaches epoch 3, its new blocks must be
+//! network-bound, so a signature made on another network cannot be
+//! replayed there. Blocks already in the chain, genesis included, keep
+//! their old versions; chains migrate by an epoch block per account or a
+//! `ledger_epoch` vote.
+
+use crate::state_block::{BlockType, StateBlock, BLOCK_VERSION_V3};
+use burst_types::NetworkId;
+
+/// Block version every account may use without an upgrade.
+pub const BASE_BLOCK_VERSION: u8 = 1;
+
+/// Highest block version this node knows how to validate. Version 2 adds
+/// the tagged extension area and version 3 binds blocks to a network;
+/// accounts reach them through an epoch upgrade.
+pub const MAX_SUPPORTED_BLOCK_VERSION: u8 = BLOCK_VERSION_V3;
+
+/// Highest block version an account may publish, given its own epoch and
+/// the network-wide `ledger_epoch`.
+pub fn max_block_version(account_epoch: u8, ledger_epoch: u8) -> u8 {
+    BASE_BLOCK_VERSION.max(account_epoch).max(ledger_epoch)
+}
+
+/// How a producer lays out the blocks it builds for one account: the
+/// newest version the account may publish, and the network version 3
+/// blocks are bound to (see `StateBlock::apply_layout`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockLayout {
+    pub version: u8,
+    pub network: NetworkId,
+}
+
+impl BlockLayout {
+    /// The layout for an account at `account_epoch` under `ledger_epoch`.
+    pub fn for_account(account_epoch: u8, ledger_epoch: u8, network: NetworkId) -> Self {
+        Self {
+            version: max_block_version(account_epoch, ledger_epoch)
+                .min(MAX_SUPPORTED_BLOCK_VERSION),
+            network,
+        }
+    }
+}
+
+/// Lowest block version an account may publish: from epoch 3 on, blocks
+/// must be network-bound.
+pub fn min_block_version(account_epoch: u8, ledger_epoch: u8) -> u8 {
+    if account_epoch.max(ledger_epoch) >= BLOCK_VERSION_V3 {
+        BLOCK_VERSION_V3
+    } else {
+        BASE_BLOCK_VERSION
+    }
+}
+
+/// Check `block.version` against what the account is allowed to publish.
+///
+/// Epoch blocks must raise the account's epoch to a version this node
+/// supports. Other blocks must not use a version beyond what the account's
+/// epoch (or the ledger epoch) has unlocked.
+pub fn validate_block_version(
+    block: &StateBlock,
+    account_epoch: u8,
+    ledger_epoch: u8,
+) -> Result<(), String> {
+    if block.version > MAX_SUPPORTED_BLOCK_VERSION {
+        return Err(format!(
+            "block version {} is not supported (highest is {MAX_SUPPORTED_BLOCK_VERSION})",
+            block.version
+        ));
+    }
+    if block.block_type == BlockType::Epoch {
+        if block.version <= account_epoch {
+            return Err(format!(
+                "epoch block version {} does not raise account epoch {account_epoch}",
+                block.version
+            ));
+        }
+        return Ok(());
+    }
+    let allowed = max_block_version(account_epoch, ledger_epoch);
+    if block.version > allowed {
+        return Err(format!(
+            "block version {} is not active for this account (epoch allows {allowed})",
+            block.version
+        ));
+    }
+    let minimum = min_block_version(account_epoch, ledger_epoch);
+    if block.version < minimum {
+        return Err(format!(
+            "block version {} is retired for this account (epoch requires {minimum})",
+            block.version
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_block::BLOCK_VERSION_V2;
+    use burst_types::{BlockHash, Signature, Timestamp, TxHash, WalletAddress};
+
+    fn block(block_type: BlockType, version: u8) -> StateBlock {
+        StateBlock {
+            version,
+            block_type,
+            account: WalletAddress::new("brst_account"),
+            previous: BlockHash::new([1u8; 32]),
+            representative: WalletAddress::new("brst_rep"),
+            brn_balance: 0,
+            trst_balance: 0,
+            link: BlockHash::ZERO,
+            origin: TxHash::ZERO,
+            transaction: TxHash::ZERO,
+            timestamp: Timestamp::new(1_000),
+            params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
+            work: 0,
+            signature: Signature([1u8; 64]),
+            hash: BlockHash::ZERO,
+        }
+    }
+
+    #[test]
+    fn versions_are_gated_by_account_and_ledger_epoch() {
+        assert_eq!(max_block_version(0, 0), BASE_BLOCK_VERSION);
+        assert_eq!(max_block_version(3, 2), 3);
+        assert_eq!(max_block_version(1, 4), 4);
+
+        let send = block(BlockType::Send, BASE_BLOCK_VERSION);
+        assert!(validate_block_version(&send, 0, 0).is_ok());
+        let unsupported = block(BlockType::Send, MAX_SUPPORTED_BLOCK_VERSION + 1);
+        assert!(validate_block_version(&unsupported, u8::MAX, u8::MAX).is_err());
+
+        let v2 = block(BlockType::Send, BLOCK_VERSION_V2);
+        assert!(validate_block_version(&v2, 0, 0).is_err());
+        assert!(validate_block_version(&v2, BLOCK_VERSION_V2, 0).is_ok());
+        assert!(validate_block_version(&v2, 0, BLOCK_VERSION_V2).is_ok());
+    }
+
+    #[test]
+    fn epoch_blocks_must_raise_the_account_epoch() {
+        let epoch = block(BlockType::Epoch, BASE_BLOCK_VERSION);
+        assert!(validate_block_version(&epoch, 0, 0).is_ok());
+        assert!(validate_block_version(&epoch, BASE_BLOCK_VERSION, 0).is_err());
+    }
+
+    #[test]
+    fn epoch_3_retires_unbound_blocks() {
+        assert_eq!(min_block_version(BLOCK_VERSION_V2, 0), BASE_BLOCK_VERSION);
+        assert_eq!(min_block_version(BLOCK_VERSION_V3, 0), BLOCK_VERSION_V3);
+        assert_eq!(min_block_version(0, BLOCK_VERSION_V3), BLOCK_VERSION_V3);
+
+        let v2 = block(BlockType::Send, BLOCK_VERSION_V2);
+        let v3 = block(BlockType::Send, BLOCK_VERSION_V3);
+        assert!(validate_block_version(&v2, BLOCK_VERSION_V2, 0).is_ok());
+        assert!(validate_block_version(&v2, BLOCK_VERSION_V3, 0).is_err());
+        assert!(validate_block_version(&v2, 0, BLOCK_VERSION_V3).is_err());
+        assert!(validate_block_version(&v3, 0, BLOCK_VERSION_V3).is_ok());
+        // Network binding waits for its own epoch.
+        assert!(validate_block_version(&v3, 0, 0).is_err());
+        assert!(validate_block_version(&v3, BLOCK_VERSION_V2, 0).is_err());
+
+        // A dev chain migrates account by account with a v3 epoch block.
+        let upgrade = block(BlockType::Epoch, BLOCK_VERSION_V3);
+        assert!(validate_block_version(&upgrade, BLOCK_VERSION_V2, 0).is_ok());
+    }
+}
@@ -1,3 +1,4 @@
+use burst_types::{ErrorCode, HasErrorCode};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -17,3 +18,15 @@ pub enum LedgerError {
     #[error("storage error: {0}")]
     Storage(#[from] burst_store::StoreError),
 }
+
+impl HasErrorCode for LedgerError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::BlockGap { .. } => ErrorCode::BlockNotFound,
+            Self::Fork { .. } => ErrorCode::Fork,
+            Self::InvalidBlock { .. } => ErrorCode::InvalidBlock,
+            Self::AccountNotFound(_) => ErrorCode::AccountNotFound,
+            Self::Storage(e) => e.error_code(),
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! Block v2 extension area.
+//!
+//! Version 2 state blocks carry a list of tagged extensions after the fixed
+//! fields. Each extension is encoded for hashing as `tag (u16 BE) ‖ length
+//! (u16 BE) ‖ value`, so a node can step over tags it does not understand:
+//! unknown extensions are still hashed (the signature covers them) but are
+//! otherwise ignored. New kinds of data can then ride on blocks without
+//! another change to the block layout.
+
+use serde::{Deserialize, Serialize};
+
+use burst_transactions::send::MAX_MEMO_LEN;
+use burst_types::NetworkId;
+
+/// Payment memo/reference (Send blocks only, at most `MAX_MEMO_LEN` bytes).
+pub const EXT_MEMO: u16 = 1;
+/// Hash of an off-chain proof the block refers to (32 bytes).
+pub const EXT_PROOF_REFERENCE: u16 = 2;
+/// Attestation from a verification method, opaque to the ledger.
+pub const EXT_METHOD_ATTESTATION: u16 = 3;
+/// The block's position in its account chain (u64 BE; the open block is 1).
+/// Binds the signature to one slot, so the same intent cannot be replayed
+/// at a later height.
+pub const EXT_SEQUENCE: u16 = 4;
+/// Destination tag (u64 BE, Send blocks only). Lets one receiving account
+/// tell many depositors apart — an exchange hands each customer a tag
+/// instead of an account of their own.
+pub const EXT_DESTINATION_TAG: u16 = 5;
+/// Network the block belongs to (one [`NetworkId::code`] byte). Mandatory
+/// from block version 3, whose hash is also domain-separated by it.
+pub const EXT_NETWORK_ID: u16 = 6;
+
+/// Largest encoded extension area accepted on a block.
+pub const MAX_EXTENSIONS_LEN: usize = 1024;
+
+/// Largest method attestation value.
+pub const MAX_METHOD_ATTESTATION_LEN: usize = 256;
+
+/// One tagged extension.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockExtension {
+    pub tag: u16,
+    pub value: Vec<u8>,
+}
+
+impl BlockExtension {
+    pub fn new(tag: u16, value: Vec<u8>) -> Self {
+        Self { tag, value }
+    }
+
+    /// Whether this node knows the meaning of the tag.
+    pub fn is_known(&self) -> bool {
+        matches!(
+            self.tag,
+            EXT_MEMO
+                | EXT_PROOF_REFERENCE
+                | EXT_METHOD_ATTESTATION
+                | EXT_SEQUENCE
+                | EXT_DESTINATION_TAG
+                | EXT_NETWORK_ID
+        )
+    }
+
+    /// A sequence extension for the block at `height` in its chain.
+    pub fn sequence(height: u64) -> Self {
+        Self::new(EXT_SEQUENCE, height.to_be_bytes().to_vec())
+    }
+
+    /// A destination tag extension.
+    pub fn destination_tag(tag: u64) -> Self {
+        Self::new(EXT_DESTINATION_TAG, tag.to_be_bytes().to_vec())
+    }
+
+    /// A network-id extension binding the block to `network`.
+    pub fn network_id(network: NetworkId) -> Self {
+        Self::new(EXT_NETWORK_ID, vec![network.code()])
+    }
+}
+
+/// Encode extensions as the TLV bytes that are hashed into a v2 block.
+pub fn encode_extensions(extensions: &[BlockExtension]) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    for extension in extensions {
+        buffer.extend_from_slice(&extension.tag.to_be_bytes());
+        buffer.extend_from_slice(&(extension.value.len() as u16).to_be_bytes());
+        buffer.extend_from_slice(&extension.value);
+    }
+    buffer
+}
+
+/// Decode a TLV extension area, keeping unknown tags.
+pub fn decode_extensions(mut bytes: &[u8]) -> Result<Vec<BlockExtension>, String> {
+    let mut extensions = Vec::new();
+    while !bytes.is_empty() {
+        if bytes.len() < 4 {
+            return Err("truncated extension header".into());
+        }
+        let tag = u16::from_be_bytes([bytes[0], bytes[1]]);
+        let len = u16::from_be_bytes([bytes[2], bytes[3]]) as usize;
+        let Some(value) = bytes.get(4..4 + len) else {
+            return Err(format!("extension {tag} is truncated"));
+        };
+        extensions.push(BlockExtension::new(tag, value.to_vec()));
+        bytes = &bytes[4 + len..];
+    }
+    Ok(extensions)
+}
+
+/// Check an extension list: tags strictly increasing (one of each, in
+/// canonical order), the encoded area within `MAX_EXTENSIONS_LEN`, and the
+/// known tags well formed. `is_send` says whether a memo or destination tag
+/// is allowed.
+pub fn validate_extensions(extensions: &[BlockExtension], is_send: bool) -> Result<(), String> {
+    let encoded_len: usize = extensions.iter().map(|e| 4 + e.value.len()).sum();
+    if encoded_len > MAX_EXTENSIONS_LEN {
+        return Err(format!(
+            "extension area is {encoded_len} bytes, maximum is {MAX_EXTENSIONS_LEN}"
+        ));
+    }
+    for pair in extensions.windows(2) {
+        if pair[0].tag >= pair[1].tag {
+            return Err("extensions must be in strictly increasing tag order".into());
+        }
+    }
+    for extension in extensions {
+        match extension.tag {
+            EXT_MEMO if !is_send => {
+                return Err("memo extension is only allowed on send blocks".into())
+            }
+            EXT_MEMO if extension.value.len() > MAX_MEMO_LEN => {
+                return Err(format!(
+                    "memo extension is {} bytes, maximum is {MAX_MEMO_LEN}",
+                    extension.value.len()
+                ))
+            }
+            EXT_PROOF_REFERENCE if extension.value.len() != 32 => {
+                return Err("proof reference extension must be a 32-byte hash".into())
+            }
+            EXT_SEQUENCE if extension.value.len() != 8 => {
+                return Err("sequence extension must be an 8-byte height".into())
+            }
+            EXT_SEQUENCE if extension.value == [0u8; 8] => {
+                return Err("sequence extension must be at least 1".into())
+            }
+            EXT_DESTINATION_TAG if !is_send => {
+                return Err("destination tag is only allowed on send blocks".into())
+            }
+            EXT_DESTINATION_TAG if extension.value.len() != 8 => {
+                return Err("destination tag extension must be an 8-byte integer".into())
+            }
+            EXT_NETWORK_ID
+                if extension.value.len() != 1
+                    || NetworkId::from_code(extension.value[0]).is_none() =>
+            {
+                return Err("network id extension must be one known network code".into())
+            }
+            EXT_METHOD_ATTESTATION if extension.value.len() > MAX_METHOD_ATTESTATION_LEN => {
+                return Err(format!(
+                    "method attestation is {} bytes, maximum is {MAX_METHOD_ATTESTATION_LEN}",
+                    extension.value.len()
+                ))
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_tags_survive_a_round_trip() {
+        let extensions = vec![
+            BlockExtension::new(EXT_MEMO, b"invoice 42".to_vec()),
+            BlockExtension::new(EXT_PROOF_REFERENCE, vec![9u8; 32]),
+            BlockExtension::new(900, vec![1, 2, 3]),
+        ];
+        let decoded = decode_extensions(&encode_extensions(&extensions)).unwrap();
+        assert_eq!(decoded, extensions);
+        assert!(!decoded[2].is_known());
+        assert!(validate_extensions(&decoded, true).is_ok());
+
+        let mut truncated = encode_extensions(&extensions);
+        truncated.pop();
+        assert!(decode_extensions(&truncated).is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_known_extensions_and_bad_order() {
+        let memo = BlockExtension::new(EXT_MEMO, b"hi".to_vec());
+        assert!(validate_extensions(std::slice::from_ref(&memo), false).is_err());
+
+        let short_proof = BlockExtension::new(EXT_PROOF_REFERENCE, vec![0u8; 4]);
+        assert!(validate_extensions(&[short_proof], true).is_err());
+
+        let out_of_order = vec![
+            BlockExtension::new(EXT_METHOD_ATTESTATION, vec![1]),
+            memo.clone(),
+        ];
+        assert!(validate_extensions(&out_of_order, true).is_err());
+        assert!(validate_extensions(&[memo.clone(), memo], true).is_err());
+
+        let tag = BlockExtension::destination_tag(42);
+        assert!(validate_extensions(std::slice::from_ref(&tag), true).is_ok());
+        assert!(validate_extensions(std::slice::from_ref(&tag), false).is_err());
+        let short_tag = BlockExtension::new(EXT_DESTINATION_TAG, vec![0u8; 4]);
+        assert!(validate_extensions(&[short_tag], true).is_err());
+
+        let network = BlockExtension::network_id(NetworkId::Test);
+        assert!(validate_extensions(&[network], false).is_ok());
+        let unknown_network = BlockExtension::new(EXT_NETWORK_ID, vec![0]);
+        assert!(validate_extensions(&[unknown_network], false).is_err());
+
+        let oversized = BlockExtension::new(77, vec![0u8; MAX_EXTENSIONS_LEN]);
+        assert!(validate_extensions(&[oversized], true).is_err());
+    }
+}
@@ -48,6 +48,12 @@ pub fn create_genesis_block(config: &GenesisConfig) -> StateBlock {
         transaction: TxHash::ZERO,
         timestamp,
         params_hash: BlockHash::ZERO,
+        memo: None,
+        htlc: None,
+        reject_reason: None,
+        recovery: None,
+        delegation_key: None,
+        extensions: Vec::new(),
         work: 0,
         signature: Signature([0u8; 64]),
         hash: BlockHash::ZERO,
@@ -5,19 +5,37 @@
 //! Consensus is only needed for conflict resolution (double-spends).
 
 pub mod account_chain;
+pub mod checkpoint;
+pub mod epoch;
 pub mod error;
+pub mod extension;
 pub mod frontier;
 pub mod genesis;
 pub mod ledger;
+pub mod proof;
 pub mod pruning;
 pub mod snapshot;
 pub mod state_block;
 
-pub use account_chain::AccountChain;
+pub use account_chain::{transaction_height_since, trst_sent_since, AccountChain};
+pub use checkpoint::{
+    frontiers_root, CementedAccount, Checkpoint, CheckpointDigest, CheckpointError, CheckpointVote,
+};
+pub use epoch::{
+    min_block_version, validate_block_version, BlockLayout, BASE_BLOCK_VERSION,
+    MAX_SUPPORTED_BLOCK_VERSION,
+};
 pub use error::LedgerError;
+pub use extension::{
+    BlockExtension, EXT_DESTINATION_TAG, EXT_MEMO, EXT_METHOD_ATTESTATION, EXT_NETWORK_ID,
+    EXT_PROOF_REFERENCE, EXT_SEQUENCE,
+};
 pub use frontier::DagFrontier;
 pub use genesis::{create_genesis_block, genesis_hash, GenesisConfig};
 pub use ledger::{Ledger, LedgerSummary};
+pub use proof::{BlockProof, CertifiedVote, ProofVote, QuorumCertificate};
 pub use pruning::{LedgerPruner, PruneResult, PruningConfig};
 pub use snapshot::{AccountSnapshot, LedgerSnapshot};
-pub use state_block::{BlockType, StateBlock, CURRENT_BLOCK_VERSION};
+pub use state_block::{
+    work_root, BlockType, StateBlock, BLOCK_VERSION_V2, BLOCK_VERSION_V3, CURRENT_BLOCK_VERSION,
+};
@@ -0,0 +1,99 @@
+//! Light-client block proofs.
+//!
+//! A [`BlockProof`] shows that a block belongs to its account chain and was
+//! finalized by the representatives. It carries the account chain from the
+//! open block up to the proven block, so the hash links can be recomputed,
+//! and the final votes cast for the block, which a client checks against a
+//! representative set it already trusts. Nothing else from the ledger is
+//! needed to verify it.
+
+use serde::{Deserialize, Serialize};
+
+use burst_types::{BlockHash, PublicKey, Signature, WalletAddress};
+
+use crate::state_block::StateBlock;
+
+/// A representative's signed final vote, as carried in a proof.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofVote {
+    pub voter: WalletAddress,
+    pub block_hashes: Vec<BlockHash>,
+    pub timestamp: u64,
+    pub signature: Signature,
+}
+
+impl ProofVote {
+    /// The bytes a representative signs: timestamp (big-endian) followed
+    /// by each voted block hash.
+    pub fn signed_message(&self) -> Vec<u8> {
+        let mut msg = Vec::with_capacity(8 + self.block_hashes.len() * 32);
+        msg.extend_from_slice(&self.timestamp.to_be_bytes());
+        for hash in &self.block_hashes {
+            msg.extend_from_slice(hash.as_bytes());
+        }
+        msg
+    }
+
+    /// Whether the signature is valid for the voter's key.
+    pub fn verify_signature(&self) -> bool {
+        let Some(key) = burst_crypto::decode_address(self.voter.as_str()) else {
+            return false;
+        };
+        burst_crypto::verify_signature(&self.signed_message(), &self.signature, &PublicKey(key))
+    }
+}
+
+/// Proof that a block is in its account chain and was finalized.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BlockProof {
+    /// The account chain from the open block to the proven block, oldest
+    /// first.
+    pub chain: Vec<StateBlock>,
+    /// Final votes for the proven block.
+    pub votes: Vec<ProofVote>,
+}
+
+impl BlockProof {
+    /// The proven block (the last block of the chain).
+    pub fn block(&self) -> Option<&StateBlock> {
+        self.chain.last()
+    }
+
+    /// Height of the proven block in its account chain (the open block is 1).
+    pub fn height(&self) -> u64 {
+        self.chain.len() as u64
+    }
+}
+
+/// A final vote together with the weight it carried.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertifiedVote {
+    pub vote: ProofVote,
+    pub weight: u128,
+}
+
+/// The final votes that confirmed a block, stored alongside it when it is
+/// cemented so the confirmation stays auditable after the representatives
+/// that voted for it rotate out.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuorumCertificate {
+    pub block_hash: BlockHash,
+    /// One entry per representative, with its weight at cementation time.
+    pub votes: Vec<CertifiedVote>,
+    /// Unix seconds.
+    pub cemented_at: u64,
+}
+
+impl QuorumCertificate {
+    /// Combined weight of the certified votes.
+    pub fn total_weight(&self) -> u128 {
+        self.votes
+            .iter()
+            .fold(0u128, |total, entry| total.saturating_add(entry.weight))
+    }
+
+    /// The votes without their weights, as carried in a [`BlockProof`].
+    pub fn proof_votes(&self) -> Vec<ProofVote> {
+        self.votes.iter().map(|entry| entry.vote.clone()).collect()
+    }
+}
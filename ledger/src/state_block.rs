@@ -3,11 +3,23 @@
 //! Inspired by Nano's state blocks: every block contains the full account state,
 //! enabling efficient pruning without losing security.
 
-use burst_crypto::blake2b_256;
-use burst_types::{BlockHash, Signature, Timestamp, TxHash, WalletAddress};
-use burst_work::validate_work;
+use burst_crypto::{blake2b_256, hash_block, hash_block_in_network};
+use burst_transactions::alias::validate_alias_name;
+use burst_transactions::delegate::{DelegationKey, ENCRYPTED_DELEGATION_KEY_LEN};
+use burst_transactions::htlc::HtlcData;
+use burst_transactions::recovery::RecoveryData;
+use burst_transactions::reject_receive::RejectReason;
+use burst_transactions::send::MAX_MEMO_LEN;
+use burst_types::{BlockHash, NetworkId, Signature, Timestamp, TxHash, WalletAddress};
+use burst_work::{validate_work, WorkBlockKind};
 use serde::{Deserialize, Serialize};
 
+use crate::epoch::BlockLayout;
+use crate::extension::{
+    encode_extensions, validate_extensions, BlockExtension, EXT_DESTINATION_TAG, EXT_MEMO,
+    EXT_NETWORK_ID, EXT_SEQUENCE,
+};
+
 /// The type of operation this block represents.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BlockType {
@@ -46,11 +58,152 @@ pub enum BlockType {
     /// Governance activation block — records an on-chain parameter change
     /// (Tezos-style self-amendment). Placed on the genesis account's chain.
     GovernanceActivation,
+    /// Hash-time-locked TRST send (claimable with a preimage until a timeout).
+    HtlcLock,
+    /// Claim a hash-time-locked send by revealing the preimage.
+    HtlcClaim,
+    /// Refund an expired hash-time-locked send to its sender.
+    HtlcRefund,
+    /// Anchor a trust group's signing key and endpoint hash on-chain.
+    GroupAnchor,
+    /// Designate the custodians who can approve a key rotation.
+    RecoverySetup,
+    /// Rotate the account key with custodian approval (social recovery).
+    Recovery,
+    /// Hand the current delegate a new generation of the delegation key.
+    RotateDelegationKey,
+    /// Join the verifier pool, locking the verifier stake.
+    VerifierOptIn,
+    /// Leave the verifier pool and get the stake back.
+    VerifierOptOut,
+    /// Keepalive proof — resumes BRN accrual suspended for inactivity.
+    Liveness,
+    /// Anchor the hash of a signed off-chain governance delegate profile.
+    DelegateProfile,
+    /// Commit to a sealed governance ballot.
+    GovernanceCommit,
+    /// Reveal a sealed governance ballot after voting closes.
+    GovernanceReveal,
+    /// Register or renew a human-readable alias for the account.
+    Alias,
+}
+
+impl BlockType {
+    /// Explicit subtype code, as hashed into the block.
+    pub fn subtype_code(&self) -> u8 {
+        match self {
+            BlockType::Open => 0,
+            BlockType::Burn => 1,
+            BlockType::Send => 2,
+            BlockType::Receive => 3,
+            BlockType::Split => 4,
+            BlockType::Merge => 5,
+            BlockType::Endorse => 6,
+            BlockType::Challenge => 7,
+            BlockType::GovernanceProposal => 8,
+            BlockType::GovernanceVote => 9,
+            BlockType::Delegate => 10,
+            BlockType::RevokeDelegation => 11,
+            BlockType::ChangeRepresentative => 12,
+            BlockType::Epoch => 13,
+            BlockType::RejectReceive => 14,
+            BlockType::VerificationVote => 15,
+            BlockType::GovernanceActivation => 16,
+            BlockType::HtlcLock => 17,
+            BlockType::HtlcClaim => 18,
+            BlockType::HtlcRefund => 19,
+            BlockType::GroupAnchor => 20,
+            BlockType::RecoverySetup => 21,
+            BlockType::Recovery => 22,
+            BlockType::RotateDelegationKey => 23,
+            BlockType::VerifierOptIn => 24,
+            BlockType::VerifierOptOut => 25,
+            BlockType::Liveness => 26,
+            BlockType::DelegateProfile => 27,
+            BlockType::GovernanceCommit => 28,
+            BlockType::GovernanceReveal => 29,
+            BlockType::Alias => 30,
+        }
+    }
+
+    /// Which proof-of-work threshold applies to this block type.
+    pub fn work_kind(&self) -> WorkBlockKind {
+        match self {
+            BlockType::Receive | BlockType::Open => WorkBlockKind::ReceiveOrOpen,
+            BlockType::Epoch | BlockType::GovernanceActivation => WorkBlockKind::Epoch,
+            BlockType::GovernanceProposal
+            | BlockType::GovernanceVote
+            | BlockType::GovernanceCommit
+            | BlockType::GovernanceReveal => WorkBlockKind::Governance,
+            _ => WorkBlockKind::Base,
+        }
+    }
+
+    /// The block type for a subtype code, if it is one this node knows.
+    pub fn from_subtype_code(code: u8) -> Option<Self> {
+        Some(match code {
+            0 => BlockType::Open,
+            1 => BlockType::Burn,
+            2 => BlockType::Send,
+            3 => BlockType::Receive,
+            4 => BlockType::Split,
+            5 => BlockType::Merge,
+            6 => BlockType::Endorse,
+            7 => BlockType::Challenge,
+            8 => BlockType::GovernanceProposal,
+            9 => BlockType::GovernanceVote,
+            10 => BlockType::Delegate,
+            11 => BlockType::RevokeDelegation,
+            12 => BlockType::ChangeRepresentative,
+            13 => BlockType::Epoch,
+            14 => BlockType::RejectReceive,
+            15 => BlockType::VerificationVote,
+            16 => BlockType::GovernanceActivation,
+            17 => BlockType::HtlcLock,
+            18 => BlockType::HtlcClaim,
+            19 => BlockType::HtlcRefund,
+            20 => BlockType::GroupAnchor,
+            21 => BlockType::RecoverySetup,
+            22 => BlockType::Recovery,
+            23 => BlockType::RotateDelegationKey,
+            24 => BlockType::VerifierOptIn,
+            25 => BlockType::VerifierOptOut,
+            26 => BlockType::Liveness,
+            27 => BlockType::DelegateProfile,
+            28 => BlockType::GovernanceCommit,
+            29 => BlockType::GovernanceReveal,
+            30 => BlockType::Alias,
+            _ => return None,
+        })
+    }
 }
 
 /// Current state block version.
 pub const CURRENT_BLOCK_VERSION: u8 = 1;
 
+/// First block version with an extension area. Its use is gated by the
+/// epoch mechanism (see `epoch::validate_block_version`).
+pub const BLOCK_VERSION_V2: u8 = 2;
+
+/// First network-bound block version: the block must carry a network-id
+/// extension and is hashed under that network's domain, so a block or
+/// signature from one network can never be replayed on another.
+pub const BLOCK_VERSION_V3: u8 = 3;
+
+/// Proof-of-work root of a v2 block that extends `previous` in `account`'s
+/// chain: `previous` itself, or for the first block (`previous` zero) the
+/// hash of the account address.
+pub fn work_root(account: &WalletAddress, previous: BlockHash) -> BlockHash {
+    if previous.is_zero() {
+        BlockHash::new(blake2b_256(account.as_str().as_bytes()))
+    } else {
+        previous
+    }
+}
+
+/// Prefix hashed ahead of every v2 block.
+pub const BLOCK_V2_HASH_DOMAIN: &[u8] = b"burst-block-v2";
+
 /// A state block in BURST's block-lattice.
 ///
 /// Each block contains the full account state after the operation,
@@ -84,7 +237,13 @@ pub struct StateBlock {
     /// - For Send: destination account's block hash (pairing)
     /// - For Receive: the send block hash being received
     /// - For Endorse: the target wallet's pending verification
-    /// - For GovernanceVote: the proposal hash
+    /// - For GovernanceVote / GovernanceCommit / GovernanceReveal: the proposal hash
+    /// - For HtlcLock: the receiver (as for Send)
+    /// - For HtlcClaim / HtlcRefund: the lock block hash
+    /// - For GroupAnchor: the group identity commitment
+    /// - For DelegateProfile: the hash of the delegate's signed profile
+    /// - For Recovery: the new account public key
+    /// - For Delegate / RotateDelegationKey: the delegate's public key
     pub link: BlockHash,
 
     /// Origin burn transaction hash for TRST provenance tracking.
@@ -100,6 +259,31 @@ pub struct StateBlock {
     /// Equivalent to Tezos's protocol hash in block headers.
     pub params_hash: BlockHash,
 
+    /// Optional payment memo/reference (at most `MAX_MEMO_LEN` bytes).
+    /// Only Send blocks may carry one, except that Alias blocks carry the
+    /// alias name here; it is hashed into the block when present.
+    pub memo: Option<Vec<u8>>,
+
+    /// HTLC terms (HtlcLock) or revealed preimage (HtlcClaim). Hashed into
+    /// the block when present.
+    pub htlc: Option<HtlcData>,
+
+    /// Why the receiver declined the transfer (RejectReceive only). Hashed
+    /// into the block when present.
+    pub reject_reason: Option<RejectReason>,
+
+    /// Custodian designation (RecoverySetup) or approved key rotation
+    /// (Recovery). Hashed into the block when present.
+    pub recovery: Option<RecoveryData>,
+
+    /// The delegation key handed to the delegate (Delegate and
+    /// RotateDelegationKey). Hashed into the block when present.
+    pub delegation_key: Option<DelegationKey>,
+
+    /// Tagged extensions (v2 blocks only). Hashed into v2 blocks; unknown
+    /// tags are accepted and ignored. See [`crate::extension`].
+    pub extensions: Vec<BlockExtension>,
+
     /// Proof-of-work nonce (anti-spam).
     pub work: u64,
 
@@ -128,33 +312,32 @@ impl StateBlock {
         // 7b. origin (32 bytes)
         // 8. transaction (32 bytes)
         // 9. timestamp (8 bytes, big-endian u64)
+        // 10. params_hash (32 bytes)
+        // 11. memo (1-byte length prefix + bytes, only when present)
+        // 12. htlc (1-byte tag + payload, only when present)
+        // 13. reject_reason (1-byte code, only when present)
+        // 14. recovery (1-byte tag + payload, only when present)
+        // 15. delegation_key (public key, length-prefixed ciphertext and
+        //     sender key, only when present)
+        // 16. extensions (v2 only: 2-byte count + TLV area, always present)
+        //
+        // Version 2 blocks are additionally prefixed with
+        // `BLOCK_V2_HASH_DOMAIN`; version 1 hashes are unchanged. Version 3
+        // blocks are then hashed under their network's domain.
 
         let mut buffer = Vec::with_capacity(256);
 
+        // v2 blocks are domain-separated so no v2 encoding can collide with
+        // a v1 one.
+        if self.version >= BLOCK_VERSION_V2 {
+            buffer.extend_from_slice(BLOCK_V2_HASH_DOMAIN);
+        }
+
         // 0. version
         buffer.push(self.version);
 
-        // 1. block_type as u8 discriminant
-        let block_type_byte = match self.block_type {
-            BlockType::Open => 0,
-            BlockType::Burn => 1,
-            BlockType::Send => 2,
-            BlockType::Receive => 3,
-            BlockType::Split => 4,
-            BlockType::Merge => 5,
-            BlockType::Endorse => 6,
-            BlockType::Challenge => 7,
-            BlockType::GovernanceProposal => 8,
-            BlockType::GovernanceVote => 9,
-            BlockType::Delegate => 10,
-            BlockType::RevokeDelegation => 11,
-            BlockType::ChangeRepresentative => 12,
-            BlockType::Epoch => 13,
-            BlockType::RejectReceive => 14,
-            BlockType::VerificationVote => 15,
-            BlockType::GovernanceActivation => 16,
-        };
-        buffer.push(block_type_byte);
+        // 1. block_type as its explicit subtype code
+        buffer.push(self.block_type.subtype_code());
 
         // 2. account (string bytes)
         buffer.extend_from_slice(self.account.as_str().as_bytes());
@@ -186,14 +369,353 @@ impl StateBlock {
         // 10. params_hash (32 bytes)
         buffer.extend_from_slice(self.params_hash.as_bytes());
 
+        // 11. memo — omitted entirely when absent so memo-less blocks keep
+        // their original hashes.
+        if let Some(ref memo) = self.memo {
+            buffer.push(memo.len() as u8);
+            buffer.extend_from_slice(memo);
+        }
+
+        // 12. htlc — likewise omitted when absent.
+        match self.htlc {
+            Some(HtlcData::Lock {
+                ref hash_lock,
+                refund_after,
+            }) => {
+                buffer.push(1);
+                buffer.extend_from_slice(hash_lock);
+                buffer.extend_from_slice(&refund_after.as_secs().to_be_bytes());
+            }
+            Some(HtlcData::Claim { ref preimage }) => {
+                buffer.push(2);
+                buffer.extend_from_slice(preimage);
+            }
+            None => {}
+        }
+
+        // 13. reject_reason — likewise omitted when absent.
+        if let Some(reason) = self.reject_reason {
+            buffer.push(reason.code());
+        }
+
+        // 14. recovery — likewise omitted when absent. Addresses are
+        // length-prefixed since they are variable-length strings.
+        match self.recovery {
+            Some(RecoveryData::Setup {
+                ref custodians,
+                threshold,
+                delay_secs,
+            }) => {
+                buffer.push(1);
+                buffer.push(custodians.len() as u8);
+                for custodian in custodians {
+                    buffer.push(custodian.as_str().len() as u8);
+                    buffer.extend_from_slice(custodian.as_str().as_bytes());
+                }
+                buffer.push(threshold);
+                buffer.extend_from_slice(&delay_secs.to_be_bytes());
+            }
+            Some(RecoveryData::Rotate {
+                ref new_key,
+                requested_at,
+                ref approvals,
+            }) => {
+                buffer.push(2);
+                buffer.extend_from_slice(new_key.as_bytes());
+                buffer.extend_from_slice(&requested_at.as_secs().to_be_bytes());
+                buffer.push(approvals.len() as u8);
+                for approval in approvals {
+                    buffer.push(approval.custodian.as_str().len() as u8);
+                    buffer.extend_from_slice(approval.custodian.as_str().as_bytes());
+                    buffer.extend_from_slice(approval.signature.as_bytes());
+                }
+            }
+            None => {}
+        }
+
+        // 15. delegation_key — likewise omitted when absent.
+        if let Some(ref key) = self.delegation_key {
+            buffer.extend_from_slice(key.public_key.as_bytes());
+            buffer.push(key.encrypted_private_key.len() as u8);
+            buffer.extend_from_slice(&key.encrypted_private_key);
+            buffer.extend_from_slice(&key.sender_x25519_public);
+        }
+
+        // 16. extensions — v2 blocks always commit to the area, even when
+        // empty, so an extension cannot be stripped without changing the hash.
+        if self.version >= BLOCK_VERSION_V2 {
+            buffer.extend_from_slice(&(self.extensions.len() as u16).to_be_bytes());
+            buffer.extend_from_slice(&encode_extensions(&self.extensions));
+        }
+
         // Hash the concatenated bytes
-        let hash_bytes = blake2b_256(&buffer);
-        BlockHash::new(hash_bytes)
+        match self.network() {
+            Some(network) => hash_block_in_network(network, &buffer),
+            None => hash_block(&buffer),
+        }
+    }
+
+    /// The value this block's proof-of-work is computed over.
+    ///
+    /// Version 1 blocks work on their own hash. From version 2 the root is
+    /// the previous block, or for an open block the account root (see
+    /// [`work_root`]), so work for an account's next block can be computed
+    /// as soon as its frontier is known.
+    pub fn work_root(&self) -> BlockHash {
+        if self.version < BLOCK_VERSION_V2 {
+            return self.hash;
+        }
+        work_root(&self.account, self.previous)
     }
 
     /// Verify this block's proof-of-work meets the minimum difficulty.
     pub fn verify_work(&self, min_difficulty: u64) -> bool {
-        validate_work(&self.hash, self.work, min_difficulty)
+        validate_work(&self.work_root(), self.work, min_difficulty)
+    }
+
+    /// Validate the optional memo: Send blocks only, at most `MAX_MEMO_LEN`
+    /// bytes. Alias blocks must carry the alias name there instead.
+    pub fn validate_memo(&self) -> Result<(), String> {
+        if self.block_type == BlockType::Alias {
+            let name = self
+                .memo
+                .as_deref()
+                .and_then(|memo| std::str::from_utf8(memo).ok())
+                .ok_or("alias block must carry the alias name as its memo")?;
+            return validate_alias_name(name).map_err(|e| e.to_string());
+        }
+        let Some(ref memo) = self.memo else {
+            return Ok(());
+        };
+        if self.block_type != BlockType::Send {
+            return Err(format!(
+                "memo is only allowed on send blocks, not {:?}",
+                self.block_type
+            ));
+        }
+        if memo.len() > MAX_MEMO_LEN {
+            return Err(format!(
+                "memo is {} bytes, maximum is {}",
+                memo.len(),
+                MAX_MEMO_LEN
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validate that the HTLC payload matches the block type: locks carry
+    /// terms with a timeout after the block timestamp, claims carry a
+    /// preimage, and no other block type carries either.
+    pub fn validate_htlc(&self) -> Result<(), String> {
+        match (&self.block_type, &self.htlc) {
+            (BlockType::HtlcLock, Some(HtlcData::Lock { refund_after, .. })) => {
+                burst_transactions::htlc::validate_lock_terms(*refund_after, self.timestamp)
+                    .map_err(|e| e.to_string())
+            }
+            (BlockType::HtlcLock, _) => Err("HTLC lock block must carry lock terms".into()),
+            (BlockType::HtlcClaim, Some(HtlcData::Claim { .. })) => Ok(()),
+            (BlockType::HtlcClaim, _) => Err("HTLC claim block must carry a preimage".into()),
+            (_, None) => Ok(()),
+            (other, Some(_)) => Err(format!("{other:?} block cannot carry HTLC data")),
+        }
+    }
+
+    /// Validate that only RejectReceive blocks carry a reject reason.
+    /// Reject blocks without one (from older wallets) remain valid.
+    pub fn validate_reject_reason(&self) -> Result<(), String> {
+        if self.reject_reason.is_some() && self.block_type != BlockType::RejectReceive {
+            return Err(format!(
+                "{:?} block cannot carry a reject reason",
+                self.block_type
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validate that the recovery payload matches the block type: setups
+    /// carry a well-formed custodian set, recoveries carry a rotation whose
+    /// new key is also the link, and no other block type carries either.
+    ///
+    /// Approvals and the delay depend on the account's stored custodian set
+    /// and are checked by the block processor.
+    pub fn validate_recovery(&self) -> Result<(), String> {
+        match (&self.block_type, &self.recovery) {
+            (
+                BlockType::RecoverySetup,
+                Some(RecoveryData::Setup {
+                    custodians,
+                    threshold,
+                    ..
+                }),
+            ) => {
+                burst_transactions::recovery::validate_setup(&self.account, custodians, *threshold)
+                    .map_err(|e| e.to_string())
+            }
+            (BlockType::RecoverySetup, _) => {
+                Err("recovery setup block must carry a custodian set".into())
+            }
+            (BlockType::Recovery, Some(RecoveryData::Rotate { new_key, .. })) => {
+                if self.link.as_bytes() != new_key.as_bytes() {
+                    return Err("recovery block link must be the new account key".into());
+                }
+                Ok(())
+            }
+            (BlockType::Recovery, _) => Err("recovery block must carry a key rotation".into()),
+            (_, None) => Ok(()),
+            (other, Some(_)) => Err(format!("{other:?} block cannot carry recovery data")),
+        }
+    }
+
+    /// Validate that Delegate and RotateDelegationKey blocks, and only
+    /// those, carry a delegation key with a well-formed encrypted private key.
+    pub fn validate_delegation_key(&self) -> Result<(), String> {
+        let expects_key = matches!(
+            self.block_type,
+            BlockType::Delegate | BlockType::RotateDelegationKey
+        );
+        match &self.delegation_key {
+            None if expects_key => Err(format!(
+                "{:?} block must carry a delegation key",
+                self.block_type
+            )),
+            None => Ok(()),
+            Some(_) if !expects_key => Err(format!(
+                "{:?} block cannot carry a delegation key",
+                self.block_type
+            )),
+            Some(key) if key.encrypted_private_key.len() != ENCRYPTED_DELEGATION_KEY_LEN => {
+                Err(format!(
+                    "encrypted delegation key is {} bytes, expected {}",
+                    key.encrypted_private_key.len(),
+                    ENCRYPTED_DELEGATION_KEY_LEN
+                ))
+            }
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Validate the extension area: v1 blocks carry none; v2 extensions must
+    /// be canonical and well formed, and a memo extension cannot be combined
+    /// with the legacy memo field.
+    pub fn validate_extensions(&self) -> Result<(), String> {
+        if self.version < BLOCK_VERSION_V2 {
+            if !self.extensions.is_empty() {
+                return Err(format!(
+                    "version {} block cannot carry extensions",
+                    self.version
+                ));
+            }
+            return Ok(());
+        }
+        validate_extensions(&self.extensions, self.block_type == BlockType::Send)?;
+        if self.memo.is_some() && self.extensions.iter().any(|e| e.tag == EXT_MEMO) {
+            return Err("block carries both a memo and a memo extension".into());
+        }
+        let has_network = self.extensions.iter().any(|e| e.tag == EXT_NETWORK_ID);
+        if self.version >= BLOCK_VERSION_V3 && !has_network {
+            return Err(format!(
+                "version {} block must carry a network id",
+                self.version
+            ));
+        }
+        if self.version < BLOCK_VERSION_V3 && has_network {
+            return Err("network id extension needs a version 3 block".into());
+        }
+        Ok(())
+    }
+
+    /// The network a v3 block is bound to.
+    pub fn network(&self) -> Option<NetworkId> {
+        if self.version < BLOCK_VERSION_V3 {
+            return None;
+        }
+        self.extensions
+            .iter()
+            .find(|e| e.tag == EXT_NETWORK_ID)
+            .and_then(|e| match e.value.as_slice() {
+                [code] => NetworkId::from_code(*code),
+                _ => None,
+            })
+    }
+
+    /// Check that a network-bound block belongs to `network`. Older,
+    /// unbound versions pass; the epoch rules decide whether they are still
+    /// allowed (see `epoch::validate_block_version`).
+    pub fn validate_network(&self, network: NetworkId) -> Result<(), String> {
+        if self.version < BLOCK_VERSION_V3 {
+            return Ok(());
+        }
+        match self.network() {
+            Some(bound) if bound == network => Ok(()),
+            Some(bound) => Err(format!(
+                "block belongs to the {} network, not {}",
+                bound.as_str(),
+                network.as_str()
+            )),
+            None => Err("network-bound block carries no network id".into()),
+        }
+    }
+
+    /// Upgrade the block to version 3 bound to `network`, and recompute
+    /// its hash. Sign the block after binding it.
+    pub fn bind_to_network(&mut self, network: NetworkId) {
+        self.version = self.version.max(BLOCK_VERSION_V3);
+        self.extensions.retain(|e| e.tag != EXT_NETWORK_ID);
+        let at = self.extensions.partition_point(|e| e.tag < EXT_NETWORK_ID);
+        self.extensions
+            .insert(at, BlockExtension::network_id(network));
+        self.hash = self.compute_hash();
+    }
+
+    /// Lay a freshly built block out as `layout` describes and recompute
+    /// its hash: from version 2 it commits to its chain position
+    /// `sequence`, and from version 3 it is bound to the layout's network.
+    /// A block already carrying extensions is at least version 2. Sign the
+    /// block afterwards.
+    pub fn apply_layout(&mut self, layout: BlockLayout, sequence: u64) {
+        self.extensions
+            .retain(|e| e.tag != EXT_SEQUENCE && e.tag != EXT_NETWORK_ID);
+        let floor = if self.extensions.is_empty() {
+            CURRENT_BLOCK_VERSION
+        } else {
+            BLOCK_VERSION_V2
+        };
+        self.version = layout.version.max(floor);
+        if self.version >= BLOCK_VERSION_V2 {
+            let at = self.extensions.partition_point(|e| e.tag < EXT_SEQUENCE);
+            self.extensions
+                .insert(at, BlockExtension::sequence(sequence));
+        }
+        if self.version >= BLOCK_VERSION_V3 {
+            self.bind_to_network(layout.network);
+        } else {
+            self.hash = self.compute_hash();
+        }
+    }
+
+    /// The chain position this block commits to through its sequence
+    /// extension (v2 blocks only).
+    pub fn sequence(&self) -> Option<u64> {
+        if self.version < BLOCK_VERSION_V2 {
+            return None;
+        }
+        self.extensions
+            .iter()
+            .find(|e| e.tag == EXT_SEQUENCE)
+            .and_then(|e| <[u8; 8]>::try_from(e.value.as_slice()).ok())
+            .map(u64::from_be_bytes)
+    }
+
+    /// The destination tag a v2 send block carries, if any.
+    pub fn destination_tag(&self) -> Option<u64> {
+        if self.version < BLOCK_VERSION_V2 {
+            return None;
+        }
+        self.extensions
+            .iter()
+            .find(|e| e.tag == EXT_DESTINATION_TAG)
+            .and_then(|e| <[u8; 8]>::try_from(e.value.as_slice()).ok())
+            .map(u64::from_be_bytes)
     }
 
     /// Whether this is the first block in an account chain.
@@ -226,6 +748,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1234567890),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([0u8; 64]),
             hash: BlockHash::ZERO,
@@ -305,6 +833,250 @@ mod tests {
         assert_eq!(hash1, hash2);
     }
 
+    #[test]
+    fn test_compute_hash_includes_memo() {
+        let mut block1 = create_test_block();
+        block1.block_type = BlockType::Send;
+        let mut block2 = block1.clone();
+        block2.memo = Some(b"invoice-42".to_vec());
+        let mut block3 = block1.clone();
+        block3.memo = Some(b"invoice-43".to_vec());
+
+        assert_ne!(block1.compute_hash(), block2.compute_hash());
+        assert_ne!(block2.compute_hash(), block3.compute_hash());
+    }
+
+    #[test]
+    fn test_validate_memo() {
+        let mut block = create_test_block();
+        assert!(block.validate_memo().is_ok());
+
+        // Memo on a non-send block is rejected
+        block.memo = Some(vec![1u8; 4]);
+        assert!(block.validate_memo().is_err());
+
+        block.block_type = BlockType::Send;
+        assert!(block.validate_memo().is_ok());
+
+        block.memo = Some(vec![1u8; MAX_MEMO_LEN]);
+        assert!(block.validate_memo().is_ok());
+
+        block.memo = Some(vec![1u8; MAX_MEMO_LEN + 1]);
+        assert!(block.validate_memo().is_err());
+
+        // Alias blocks must carry a valid alias name
+        block.block_type = BlockType::Alias;
+        block.memo = None;
+        assert!(block.validate_memo().is_err());
+        block.memo = Some(b"Not An Alias".to_vec());
+        assert!(block.validate_memo().is_err());
+        block.memo = Some(b"alice".to_vec());
+        assert!(block.validate_memo().is_ok());
+    }
+
+    #[test]
+    fn test_validate_htlc() {
+        let mut block = create_test_block();
+        assert!(block.validate_htlc().is_ok());
+
+        let lock = HtlcData::Lock {
+            hash_lock: [3u8; 32],
+            refund_after: Timestamp::new(block.timestamp.as_secs() + 60),
+        };
+        // HTLC data on a non-HTLC block is rejected
+        block.htlc = Some(lock.clone());
+        assert!(block.validate_htlc().is_err());
+
+        block.block_type = BlockType::HtlcLock;
+        assert!(block.validate_htlc().is_ok());
+
+        block.htlc = None;
+        assert!(block.validate_htlc().is_err());
+
+        block.block_type = BlockType::HtlcClaim;
+        block.htlc = Some(lock);
+        assert!(block.validate_htlc().is_err());
+        block.htlc = Some(HtlcData::Claim {
+            preimage: [4u8; 32],
+        });
+        assert!(block.validate_htlc().is_ok());
+
+        // HTLC data is covered by the block hash
+        let hash = block.compute_hash();
+        block.htlc = Some(HtlcData::Claim {
+            preimage: [5u8; 32],
+        });
+        assert_ne!(block.compute_hash(), hash);
+    }
+
+    #[test]
+    fn test_validate_reject_reason() {
+        let mut block = create_test_block();
+        assert!(block.validate_reject_reason().is_ok());
+
+        block.reject_reason = Some(RejectReason::UnknownSender);
+        assert!(block.validate_reject_reason().is_err());
+
+        block.block_type = BlockType::RejectReceive;
+        assert!(block.validate_reject_reason().is_ok());
+
+        // The reason is covered by the block hash
+        let hash = block.compute_hash();
+        block.reject_reason = Some(RejectReason::TaintedProvenance);
+        assert_ne!(block.compute_hash(), hash);
+        block.reject_reason = None;
+        assert_ne!(block.compute_hash(), hash);
+    }
+
+    #[test]
+    fn test_validate_recovery() {
+        use burst_transactions::recovery::CustodianApproval;
+        use burst_types::PublicKey;
+
+        let custodian = WalletAddress::new(
+            "brst_3333333333333333333333333333333333333333333333333333333333333333333",
+        );
+        let mut block = create_test_block();
+        block.block_type = BlockType::RecoverySetup;
+        assert!(block.validate_recovery().is_err());
+        block.recovery = Some(RecoveryData::Setup {
+            custodians: vec![custodian.clone()],
+            threshold: 1,
+            delay_secs: 86_400,
+        });
+        assert!(block.validate_recovery().is_ok());
+        block.recovery = Some(RecoveryData::Setup {
+            custodians: vec![custodian.clone()],
+            threshold: 2,
+            delay_secs: 86_400,
+        });
+        assert!(block.validate_recovery().is_err());
+
+        let new_key = PublicKey([5u8; 32]);
+        block.block_type = BlockType::Recovery;
+        block.recovery = Some(RecoveryData::Rotate {
+            new_key: new_key.clone(),
+            requested_at: Timestamp::new(1),
+            approvals: vec![CustodianApproval {
+                custodian,
+                signature: Signature([1u8; 64]),
+            }],
+        });
+        assert!(
+            block.validate_recovery().is_err(),
+            "link must be the new key"
+        );
+        block.link = BlockHash::new(new_key.0);
+        assert!(block.validate_recovery().is_ok());
+
+        // Approvals are covered by the block hash
+        let hash = block.compute_hash();
+        if let Some(RecoveryData::Rotate { approvals, .. }) = &mut block.recovery {
+            approvals.clear();
+        }
+        assert_ne!(block.compute_hash(), hash);
+
+        block.block_type = BlockType::Send;
+        assert!(block.validate_recovery().is_err());
+    }
+
+    #[test]
+    fn test_validate_delegation_key() {
+        use burst_types::PublicKey;
+
+        let mut block = create_test_block();
+        block.block_type = BlockType::Delegate;
+        assert!(block.validate_delegation_key().is_err());
+
+        block.delegation_key = Some(DelegationKey {
+            public_key: PublicKey([3u8; 32]),
+            encrypted_private_key: vec![0u8; ENCRYPTED_DELEGATION_KEY_LEN],
+            sender_x25519_public: [4u8; 32],
+        });
+        assert!(block.validate_delegation_key().is_ok());
+        block.block_type = BlockType::RotateDelegationKey;
+        assert!(block.validate_delegation_key().is_ok());
+
+        // The key is covered by the block hash
+        let hash = block.compute_hash();
+        if let Some(key) = &mut block.delegation_key {
+            key.public_key = PublicKey([5u8; 32]);
+        }
+        assert_ne!(block.compute_hash(), hash);
+
+        if let Some(key) = &mut block.delegation_key {
+            key.encrypted_private_key.truncate(32);
+        }
+        assert!(block.validate_delegation_key().is_err());
+
+        block.block_type = BlockType::Send;
+        assert!(block.validate_delegation_key().is_err());
+    }
+
+    #[test]
+    fn test_v2_hashing_and_extensions() {
+        use crate::extension::EXT_PROOF_REFERENCE;
+
+        let v1 = create_test_block();
+        let v1_hash = v1.compute_hash();
+
+        // v1 blocks never hash an extension area and may not carry one.
+        let mut with_ext = v1.clone();
+        with_ext
+            .extensions
+            .push(BlockExtension::new(EXT_PROOF_REFERENCE, vec![1u8; 32]));
+        assert_eq!(with_ext.compute_hash(), v1_hash);
+        assert!(with_ext.validate_extensions().is_err());
+
+        // The same fields under v2 hash differently, and the extensions are
+        // covered by the hash.
+        let mut v2 = v1.clone();
+        v2.version = BLOCK_VERSION_V2;
+        let v2_hash = v2.compute_hash();
+        assert_ne!(v2_hash, v1_hash);
+        v2.extensions
+            .push(BlockExtension::new(EXT_PROOF_REFERENCE, vec![1u8; 32]));
+        v2.extensions.push(BlockExtension::new(4000, vec![7, 7]));
+        assert_ne!(v2.compute_hash(), v2_hash);
+        assert!(v2.validate_extensions().is_ok(), "unknown tags are skipped");
+
+        // A memo extension cannot duplicate the legacy memo field.
+        v2.block_type = BlockType::Send;
+        v2.extensions
+            .insert(0, BlockExtension::new(EXT_MEMO, b"ref".to_vec()));
+        assert!(v2.validate_extensions().is_ok());
+        v2.memo = Some(b"ref".to_vec());
+        assert!(v2.validate_extensions().is_err());
+        v2.memo = None;
+
+        // The sequence extension is read back only from v2 blocks.
+        assert_eq!(v2.sequence(), None);
+        v2.extensions.insert(2, BlockExtension::sequence(7));
+        assert!(v2.validate_extensions().is_ok());
+        assert_eq!(v2.sequence(), Some(7));
+        v2.extensions
+            .insert(3, BlockExtension::destination_tag(1001));
+        assert!(v2.validate_extensions().is_ok());
+        assert_eq!(v2.destination_tag(), Some(1001));
+        v2.version = 1;
+        assert_eq!(v2.sequence(), None);
+        assert_eq!(v2.destination_tag(), None);
+    }
+
+    #[test]
+    fn test_subtype_codes_round_trip() {
+        for code in 0..=u8::MAX {
+            if let Some(block_type) = BlockType::from_subtype_code(code) {
+                assert_eq!(block_type.subtype_code(), code);
+            }
+        }
+        assert_eq!(
+            BlockType::from_subtype_code(BlockType::Epoch.subtype_code()),
+            Some(BlockType::Epoch)
+        );
+        assert_eq!(BlockType::from_subtype_code(31), None);
+    }
+
     #[test]
     fn test_verify_work_with_valid_nonce() {
         let mut block = create_test_block();
@@ -324,6 +1096,99 @@ mod tests {
         assert!(block.verify_work(min_difficulty));
     }
 
+    #[test]
+    fn test_v2_work_is_rooted_at_the_frontier() {
+        let mut block = create_test_block();
+        block.version = BLOCK_VERSION_V2;
+        block.hash = block.compute_hash();
+        assert_eq!(block.previous, BlockHash::ZERO);
+        assert_eq!(
+            block.work_root(),
+            work_root(&block.account, BlockHash::ZERO)
+        );
+        assert_ne!(block.work_root(), block.hash);
+
+        // Work computed on the frontier before the block exists is valid.
+        block.block_type = BlockType::Send;
+        block.previous = BlockHash::new([5u8; 32]);
+        let nonce = WorkGenerator.generate(&block.previous, 1000).unwrap();
+        block.hash = block.compute_hash();
+        block.work = nonce.0;
+        assert_eq!(block.work_root(), block.previous);
+        assert!(block.verify_work(1000));
+    }
+
+    #[test]
+    fn test_layout_follows_the_account_epoch() {
+        let layout = |epoch| BlockLayout::for_account(epoch, 0, NetworkId::Live);
+
+        let mut v1 = create_test_block();
+        v1.apply_layout(layout(0), 4);
+        assert_eq!(v1.version, CURRENT_BLOCK_VERSION);
+        assert!(v1.extensions.is_empty());
+        assert_eq!(v1.hash, v1.compute_hash());
+
+        let mut v2 = create_test_block();
+        v2.block_type = BlockType::Send;
+        v2.extensions = vec![BlockExtension::destination_tag(9)];
+        v2.apply_layout(layout(BLOCK_VERSION_V2), 4);
+        assert_eq!(v2.version, BLOCK_VERSION_V2);
+        assert_eq!(v2.sequence(), Some(4));
+        assert_eq!(v2.destination_tag(), Some(9));
+        assert_eq!(v2.network(), None);
+        assert!(v2.validate_extensions().is_ok());
+
+        let mut v3 = create_test_block();
+        v3.apply_layout(layout(BLOCK_VERSION_V3), 4);
+        assert_eq!(v3.sequence(), Some(4));
+        assert_eq!(v3.network(), Some(NetworkId::Live));
+        assert!(v3.validate_extensions().is_ok());
+        assert_eq!(v3.hash, v3.compute_hash());
+    }
+
+    #[test]
+    fn test_v3_blocks_are_bound_to_their_network() {
+        let mut block = create_test_block();
+        block.version = BLOCK_VERSION_V2;
+        block.extensions = vec![BlockExtension::sequence(1)];
+        block.hash = block.compute_hash();
+        let unbound = block.hash;
+        assert_eq!(block.network(), None);
+        assert!(block.validate_network(NetworkId::Live).is_ok());
+
+        let mut live = block.clone();
+        live.bind_to_network(NetworkId::Live);
+        assert_eq!(live.version, BLOCK_VERSION_V3);
+        assert_eq!(live.network(), Some(NetworkId::Live));
+        assert!(live.validate_extensions().is_ok());
+        assert!(live.validate_network(NetworkId::Live).is_ok());
+        assert!(live.validate_network(NetworkId::Test).is_err());
+
+        // The same intent bound to another network hashes differently, so a
+        // test-network signature cannot be replayed on live.
+        let mut test = block.clone();
+        test.bind_to_network(NetworkId::Test);
+        assert_ne!(live.hash, test.hash);
+        assert_ne!(live.hash, unbound);
+        assert_eq!(live.hash, live.compute_hash());
+
+        // Rewriting the network id on a copy changes the hash it must carry.
+        let mut forged = test.clone();
+        forged.extensions = live.extensions.clone();
+        assert_ne!(forged.compute_hash(), test.hash);
+
+        // v3 blocks without a network id, and network ids on older
+        // versions, are malformed.
+        let mut missing = live.clone();
+        missing.extensions.retain(|e| e.tag != EXT_NETWORK_ID);
+        assert!(missing.validate_extensions().is_err());
+        let mut early = block.clone();
+        early
+            .extensions
+            .push(BlockExtension::network_id(NetworkId::Dev));
+        assert!(early.validate_extensions().is_err());
+    }
+
     #[test]
     fn test_verify_work_with_invalid_nonce() {
         let mut block = create_test_block();
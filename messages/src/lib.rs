@@ -75,7 +75,7 @@ pub struct KeepaliveMessage {
 }
 
 /// A peer's network address.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct PeerAddress {
     pub ip: String,
     pub port: u16,
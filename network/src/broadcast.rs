@@ -139,6 +139,8 @@ mod tests {
             ban_until_secs: None,
             telemetry: None,
             peering_addr: None,
+            quality: Default::default(),
+            connected_since_secs: None,
         }
     }
 
@@ -1,14 +1,26 @@
 //! Rolling hash set for network-layer message deduplication.
 //!
 //! Prevents the same message from being processed or relayed twice by
-//! maintaining a bounded set of recently-seen Blake2b-256 message hashes.
+//! maintaining a bounded set of recently-seen message hashes. Callers key
+//! messages by content (a block's hash, a vote's canonical encoding) rather
+//! than by frame, so a message re-framed on the way is still recognised.
+//!
+//! Alongside the global set, each peer has a smaller set of the hashes it
+//! is known to have — because it sent them to us or we sent them to it —
+//! so relays skip peers that already have a message, including its source.
 
-use std::collections::HashSet;
-use std::collections::VecDeque;
+use burst_types::memory::entry_bytes;
+use burst_types::MemoryAccounting;
+use burst_utils::{DetailType, StatType, Stats};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 
 /// Default dedup capacity: track the last 65 536 message hashes.
 pub const DEFAULT_DEDUP_CAPACITY: usize = 65_536;
 
+/// Default per-peer capacity: remember the last 4 096 hashes each peer has.
+pub const DEFAULT_PEER_KNOWN_CAPACITY: usize = 4_096;
+
 /// Rolling hash set for message deduplication.
 ///
 /// Keeps track of the last `capacity` message hashes in insertion order.
@@ -18,6 +30,32 @@ pub struct MessageDedup {
     capacity: usize,
     hashes: HashSet<[u8; 32]>,
     order: VecDeque<[u8; 32]>,
+    /// Hashes each peer already has, by peer ID.
+    known: HashMap<String, KnownSet>,
+    peer_capacity: usize,
+    /// Duplicate and unique message counters.
+    stats: Arc<Stats>,
+}
+
+/// Bounded, insertion-ordered set of the hashes one peer has.
+#[derive(Default)]
+struct KnownSet {
+    hashes: HashSet<[u8; 32]>,
+    order: VecDeque<[u8; 32]>,
+}
+
+impl KnownSet {
+    fn insert(&mut self, hash: [u8; 32], capacity: usize) {
+        if capacity == 0 || !self.hashes.insert(hash) {
+            return;
+        }
+        self.order.push_back(hash);
+        while self.order.len() > capacity {
+            if let Some(old) = self.order.pop_front() {
+                self.hashes.remove(&old);
+            }
+        }
+    }
 }
 
 impl MessageDedup {
@@ -27,17 +65,27 @@ impl MessageDedup {
             capacity,
             hashes: HashSet::with_capacity(capacity),
             order: VecDeque::with_capacity(capacity),
+            known: HashMap::new(),
+            peer_capacity: DEFAULT_PEER_KNOWN_CAPACITY,
+            stats: Arc::default(),
         }
     }
 
+    /// Report into a shared stats registry.
+    pub fn set_stats(&mut self, stats: Arc<Stats>) {
+        self.stats = stats;
+    }
+
     /// Check if a message hash has been seen before.
     ///
     /// Returns `true` if the hash is a duplicate (already seen).
     /// Returns `false` if the hash is new — it is recorded for future checks.
     pub fn is_duplicate(&mut self, hash: &[u8; 32]) -> bool {
         if self.hashes.contains(hash) {
+            self.stats.inc(StatType::Message, DetailType::Duplicate);
             return true;
         }
+        self.stats.inc(StatType::Message, DetailType::Unique);
         // Evict oldest if at capacity
         if self.hashes.len() >= self.capacity {
             if let Some(old) = self.order.pop_front() {
@@ -49,6 +97,35 @@ impl MessageDedup {
         false
     }
 
+    /// Record that `peer` sent us the message with `hash`, then check it
+    /// like [`is_duplicate`](Self::is_duplicate).
+    pub fn observe(&mut self, peer: &str, hash: &[u8; 32]) -> bool {
+        self.mark_known(peer, hash);
+        self.is_duplicate(hash)
+    }
+
+    /// Record that `peer` has the message with `hash`, e.g. because we
+    /// just sent it.
+    pub fn mark_known(&mut self, peer: &str, hash: &[u8; 32]) {
+        let capacity = self.peer_capacity;
+        self.known
+            .entry(peer.to_string())
+            .or_default()
+            .insert(*hash, capacity);
+    }
+
+    /// Whether `peer` is known to have the message with `hash`.
+    pub fn peer_knows(&self, peer: &str, hash: &[u8; 32]) -> bool {
+        self.known
+            .get(peer)
+            .is_some_and(|known| known.hashes.contains(hash))
+    }
+
+    /// Drop everything remembered about a disconnected peer.
+    pub fn forget_peer(&mut self, peer: &str) {
+        self.known.remove(peer);
+    }
+
     /// Compute the Blake2b-256 hash of raw message bytes.
     ///
     /// Delegates to `burst_crypto::blake2b_256` for consistency with the
@@ -68,6 +145,27 @@ impl MessageDedup {
     }
 }
 
+impl MemoryAccounting for MessageDedup {
+    fn element_count(&self) -> usize {
+        self.hashes.len()
+            + self
+                .known
+                .values()
+                .map(|known| known.hashes.len())
+                .sum::<usize>()
+    }
+
+    fn approximate_bytes(&self) -> usize {
+        // Each hash is held by both a set and its eviction queue.
+        let known: usize = self
+            .known
+            .values()
+            .map(|known| known.hashes.len() + known.order.len())
+            .sum();
+        entry_bytes::<[u8; 32]>(self.hashes.len() + self.order.len() + known)
+    }
+}
+
 impl Default for MessageDedup {
     fn default() -> Self {
         Self::new(DEFAULT_DEDUP_CAPACITY)
@@ -162,4 +260,34 @@ mod tests {
         dedup.is_duplicate(&hash);
         assert_eq!(dedup.len(), 1);
     }
+
+    #[test]
+    fn peers_remember_what_they_sent_and_were_sent() {
+        let mut dedup = MessageDedup::new(100);
+        let hash = MessageDedup::hash_message(b"block");
+        assert!(!dedup.observe("1.0.0.1:7075", &hash));
+        // A second peer relaying it is a duplicate, but now knows it too.
+        assert!(dedup.observe("1.0.0.2:7075", &hash));
+        assert!(dedup.peer_knows("1.0.0.1:7075", &hash));
+        assert!(dedup.peer_knows("1.0.0.2:7075", &hash));
+        assert!(!dedup.peer_knows("1.0.0.3:7075", &hash));
+
+        dedup.mark_known("1.0.0.3:7075", &hash);
+        assert!(dedup.peer_knows("1.0.0.3:7075", &hash));
+
+        dedup.forget_peer("1.0.0.1:7075");
+        assert!(!dedup.peer_knows("1.0.0.1:7075", &hash));
+    }
+
+    #[test]
+    fn per_peer_memory_is_bounded() {
+        let mut dedup = MessageDedup::new(100);
+        dedup.peer_capacity = 2;
+        let hashes: Vec<_> = (0u8..3).map(|i| [i; 32]).collect();
+        for hash in &hashes {
+            dedup.mark_known("peer", hash);
+        }
+        assert!(!dedup.peer_knows("peer", &hashes[0]));
+        assert!(dedup.peer_knows("peer", &hashes[2]));
+    }
 }
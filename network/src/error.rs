@@ -20,6 +20,9 @@ pub enum NetworkError {
     #[error("IO error: {0}")]
     Io(String),
 
+    #[error("invalid address or subnet: {0}")]
+    InvalidSubnet(String),
+
     #[error("channel closed")]
     ChannelClosed,
 }
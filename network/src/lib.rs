@@ -9,7 +9,9 @@ pub mod clock_sync;
 pub mod connection;
 pub mod dedup;
 pub mod error;
+pub mod peer_address;
 pub mod peer_manager;
+pub mod subnet;
 pub mod syn_cookies;
 pub mod sync;
 pub mod throttle;
@@ -19,9 +21,14 @@ pub use auth::PeerAuth;
 pub use broadcast::{BroadcastResult, Broadcaster};
 pub use clock_sync::ClockSync;
 pub use connection::{ConnectionPool, PeerConnection, DEFAULT_MAX_CONNECTIONS};
-pub use dedup::{MessageDedup, DEFAULT_DEDUP_CAPACITY};
+pub use dedup::{MessageDedup, DEFAULT_DEDUP_CAPACITY, DEFAULT_PEER_KNOWN_CAPACITY};
 pub use error::NetworkError;
-pub use peer_manager::{PeerManager, PeerState, PeerTelemetry, PenaltyReason};
+pub use peer_address::{parse_peer_address, validate_peer_address, AddressRejection};
+pub use peer_manager::{
+    ConnectionRefusal, PeerManager, PeerQuality, PeerState, PeerTelemetry, PenaltyReason,
+    BAN_INDEFINITE, DEFAULT_MAX_PEERS_PER_SUBNET,
+};
+pub use subnet::IpNet;
 pub use syn_cookies::SynCookies;
 pub use sync::{
     BootstrapResult, SyncAccountResult, SyncHandle, SyncProtocol, SyncRequest, SyncResponse,
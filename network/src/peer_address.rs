@@ -0,0 +1,148 @@
+//! Validation of peer addresses shared in keepalive messages.
+//!
+//! Keepalives let any connected peer hand us addresses to dial, so each one
+//! is parsed and checked before it can become a connection candidate:
+//! unspecified, multicast and broadcast addresses are never useful, and on
+//! the live network neither are private, loopback or link-local ranges or
+//! privileged ports.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use burst_messages::PeerAddress;
+
+/// Lowest port accepted for a shared address on the live network.
+pub const MIN_PUBLIC_PORT: u16 = 1024;
+
+/// Why a shared address was not accepted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressRejection {
+    /// Not an `ip:port` pair.
+    Malformed,
+    /// Unspecified, multicast or broadcast.
+    Unroutable,
+    /// Private, loopback, link-local or shared (CGNAT) range.
+    Private,
+    /// Port 0, or a privileged port on the live network.
+    BadPort,
+}
+
+impl AddressRejection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Malformed => "malformed",
+            Self::Unroutable => "unroutable",
+            Self::Private => "private",
+            Self::BadPort => "bad_port",
+        }
+    }
+}
+
+/// Parse `"ip:port"` or `"[ipv6]:port"` as shared in keepalives.
+pub fn parse_peer_address(s: &str) -> Option<PeerAddress> {
+    let (ip, port) = s.rsplit_once(':')?;
+    let ip = ip.trim_start_matches('[').trim_end_matches(']');
+    let port = port.parse::<u16>().ok()?;
+    ip.parse::<IpAddr>().ok()?;
+    Some(PeerAddress {
+        ip: ip.to_string(),
+        port,
+    })
+}
+
+/// Check that `addr` is worth dialing. `allow_private` admits private
+/// ranges and privileged ports, for test and dev networks run on a LAN.
+pub fn validate_peer_address(
+    addr: &PeerAddress,
+    allow_private: bool,
+) -> Result<IpAddr, AddressRejection> {
+    let ip = addr
+        .ip
+        .parse::<IpAddr>()
+        .map_err(|_| AddressRejection::Malformed)?
+        .to_canonical();
+    if addr.port == 0 || (!allow_private && addr.port < MIN_PUBLIC_PORT) {
+        return Err(AddressRejection::BadPort);
+    }
+    let (unroutable, private) = match ip {
+        IpAddr::V4(v4) => (
+            v4.is_unspecified() || v4.is_multicast() || v4.is_broadcast(),
+            is_private_v4(&v4),
+        ),
+        IpAddr::V6(v6) => (v6.is_unspecified() || v6.is_multicast(), is_private_v6(&v6)),
+    };
+    if unroutable {
+        return Err(AddressRejection::Unroutable);
+    }
+    if private && !allow_private {
+        return Err(AddressRejection::Private);
+    }
+    Ok(ip)
+}
+
+fn is_private_v4(ip: &Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || a == 0
+        // Shared address space (RFC 6598).
+        || (a == 100 && (64..128).contains(&b))
+}
+
+fn is_private_v6(ip: &Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    ip.is_loopback()
+        // Unique local fc00::/7.
+        || (first & 0xfe00) == 0xfc00
+        // Link-local fe80::/10.
+        || (first & 0xffc0) == 0xfe80
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(s: &str, allow_private: bool) -> Result<IpAddr, AddressRejection> {
+        let addr = parse_peer_address(s).ok_or(AddressRejection::Malformed)?;
+        validate_peer_address(&addr, allow_private)
+    }
+
+    #[test]
+    fn parses_v4_and_bracketed_v6() {
+        assert_eq!(parse_peer_address("1.2.3.4:7075").unwrap().ip, "1.2.3.4");
+        let v6 = parse_peer_address("[2001:db8::1]:7075").unwrap();
+        assert_eq!((v6.ip.as_str(), v6.port), ("2001:db8::1", 7075));
+        assert!(parse_peer_address("1.2.3.4").is_none());
+        assert!(parse_peer_address("example.com:7075").is_none());
+        assert!(parse_peer_address("1.2.3.4:70000").is_none());
+    }
+
+    #[test]
+    fn live_rules_reject_private_ranges_and_bad_ports() {
+        assert!(check("8.8.8.8:7075", false).is_ok());
+        assert!(check("[2a00:1450::1]:7075", false).is_ok());
+        for private in [
+            "10.1.1.1:7075",
+            "192.168.0.9:7075",
+            "127.0.0.1:7075",
+            "100.100.0.1:7075",
+            "[fd00::1]:7075",
+            "[fe80::1]:7075",
+            "[::ffff:10.0.0.1]:7075",
+        ] {
+            assert_eq!(check(private, false), Err(AddressRejection::Private));
+            assert!(check(private, true).is_ok());
+        }
+        assert_eq!(
+            check("0.0.0.0:7075", true),
+            Err(AddressRejection::Unroutable)
+        );
+        assert_eq!(
+            check("224.0.0.1:7075", true),
+            Err(AddressRejection::Unroutable)
+        );
+        assert_eq!(check("8.8.8.8:0", true), Err(AddressRejection::BadPort));
+        assert_eq!(check("8.8.8.8:80", false), Err(AddressRejection::BadPort));
+        assert!(check("8.8.8.8:80", true).is_ok());
+    }
+}
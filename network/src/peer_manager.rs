@@ -1,10 +1,15 @@
 //! Peer discovery, keepalive, connection tracking, and peer scoring/banning.
 
 use burst_messages::PeerAddress;
+use burst_types::BlockHash;
 use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::collections::VecDeque;
-use std::net::SocketAddrV4;
+use std::net::{IpAddr, SocketAddrV4};
+
+use crate::peer_address::validate_peer_address;
+use crate::subnet::IpNet;
 
 // ---------------------------------------------------------------------------
 // Penalty / scoring types
@@ -50,6 +55,45 @@ pub struct PeerTelemetry {
     pub minor_version: u8,
     pub patch_version: u8,
     pub timestamp: u64,
+    /// Root of the peer's account-state tree; zero if the peer does not
+    /// report it.
+    pub account_state_root: BlockHash,
+    /// Network load multiplier the peer applies to the work threshold; 1 if
+    /// the peer does not report it.
+    pub network_multiplier: u64,
+}
+
+/// Connection quality figures for a peer, kept across restarts in the peer
+/// cache so the node can reconnect to its best peers first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerQuality {
+    /// Total seconds the peer has been connected, over all sessions.
+    pub uptime_secs: u64,
+    /// Outbound connection attempts, successful or not.
+    pub handshake_attempts: u32,
+    /// Outbound connection attempts that completed the handshake.
+    pub handshake_successes: u32,
+    /// Votes from this peer that were applied to an active election.
+    pub useful_votes: u64,
+}
+
+/// Uptime beyond a week adds nothing to a peer's quality score.
+const QUALITY_MAX_UPTIME_HOURS: u64 = 168;
+/// Useful votes beyond this add nothing to a peer's quality score.
+const QUALITY_MAX_USEFUL_VOTES: u64 = 10_000;
+
+impl PeerQuality {
+    /// Ranking score, higher is better: the handshake success rate (with
+    /// one success and one failure assumed, so an untried peer rates 50%)
+    /// scaled by how long the peer stayed up and how many useful votes it
+    /// delivered.
+    pub fn score(&self) -> u64 {
+        let reliability = (self.handshake_successes as u64 + 1) * 1000
+            / (self.handshake_attempts.max(self.handshake_successes) as u64 + 2);
+        let uptime = (self.uptime_secs / 3600).min(QUALITY_MAX_UPTIME_HOURS);
+        let votes = self.useful_votes.min(QUALITY_MAX_USEFUL_VOTES) / 100;
+        reliability * (1 + uptime + votes)
+    }
 }
 
 /// Per-peer metadata tracked by the [`PeerManager`].
@@ -71,6 +115,28 @@ pub struct PeerState {
     /// Differs from `address` when the peer is behind NAT (the TCP
     /// `peer_addr` may use an ephemeral port).
     pub peering_addr: Option<PeerAddress>,
+    /// Quality figures, excluding the current session's uptime.
+    pub quality: PeerQuality,
+    /// When the current session started, while connected.
+    pub connected_since_secs: Option<u64>,
+}
+
+impl PeerState {
+    /// Quality figures including the uptime of the current session.
+    pub fn current_quality(&self) -> PeerQuality {
+        let mut quality = self.quality;
+        if let Some(since) = self.connected_since_secs {
+            quality.uptime_secs += self.last_seen_secs.saturating_sub(since);
+        }
+        quality
+    }
+
+    /// Close the current session, folding its uptime into `quality`. The
+    /// session is taken to end when the peer was last heard from.
+    fn end_session(&mut self) {
+        self.quality = self.current_quality();
+        self.connected_since_secs = None;
+    }
 }
 
 /// Score at or below which a peer is banned.
@@ -86,8 +152,38 @@ const SCORE_MAX: i32 = 100;
 // Peer manager
 // ---------------------------------------------------------------------------
 
+/// Default cap on connected peers sharing one /24 (IPv4) or /48 (IPv6).
+pub const DEFAULT_MAX_PEERS_PER_SUBNET: usize = 4;
+
+/// `ban_until_secs` value for an address ban that never expires.
+pub const BAN_INDEFINITE: u64 = u64::MAX;
+
+/// Why an address may not be connected to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionRefusal {
+    /// The address matches the denylist, or an allowlist is set and the
+    /// address is not on it.
+    Denied,
+    /// The address is covered by an operator ban.
+    Banned,
+    /// The address's subnet already holds the maximum number of peers.
+    SubnetFull,
+}
+
+impl ConnectionRefusal {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Denied => "denied",
+            Self::Banned => "banned",
+            Self::SubnetFull => "subnet_full",
+        }
+    }
+}
+
 /// Maximum number of recent keepalive payloads to buffer for the reachout loop.
 const LATEST_KEEPALIVES_CAPACITY: usize = 32;
+/// Maximum number of shared addresses waiting to be probed.
+const UNVERIFIED_PEERS_CAPACITY: usize = 256;
 
 /// Central registry for peer discovery, keepalive scheduling, scoring, and
 /// ban management.
@@ -112,6 +208,21 @@ pub struct PeerManager {
     /// Ring buffer of recently received keepalive peer lists. The reachout
     /// loop pops random entries and attempts connections to discovered peers.
     latest_keepalives: VecDeque<Vec<PeerAddress>>,
+    /// When non-empty, only addresses inside these ranges may connect.
+    allowlist: Vec<IpNet>,
+    /// Addresses inside these ranges are never connected to.
+    denylist: Vec<IpNet>,
+    /// Cap on connected peers per /24 or /48; 0 disables it.
+    max_peers_per_subnet: usize,
+    /// Operator bans by range, with the unix second they expire at
+    /// ([`BAN_INDEFINITE`] for never).
+    address_bans: HashMap<IpNet, u64>,
+    /// Whether private ranges and privileged ports are acceptable in shared
+    /// and advertised addresses (test and dev networks).
+    allow_private_addresses: bool,
+    /// Addresses learned from keepalives that passed validation but have
+    /// not yet answered a probe.
+    unverified: VecDeque<PeerAddress>,
 }
 
 impl PeerManager {
@@ -126,6 +237,12 @@ impl PeerManager {
             num_connected: 0,
             external_address: None,
             latest_keepalives: VecDeque::with_capacity(LATEST_KEEPALIVES_CAPACITY),
+            allowlist: Vec::new(),
+            denylist: Vec::new(),
+            max_peers_per_subnet: DEFAULT_MAX_PEERS_PER_SUBNET,
+            address_bans: HashMap::new(),
+            allow_private_addresses: false,
+            unverified: VecDeque::new(),
         }
     }
 
@@ -144,6 +261,12 @@ impl PeerManager {
             num_connected: 0,
             external_address: None,
             latest_keepalives: VecDeque::with_capacity(LATEST_KEEPALIVES_CAPACITY),
+            allowlist: Vec::new(),
+            denylist: Vec::new(),
+            max_peers_per_subnet: DEFAULT_MAX_PEERS_PER_SUBNET,
+            address_bans: HashMap::new(),
+            allow_private_addresses: false,
+            unverified: VecDeque::new(),
         }
     }
 
@@ -163,8 +286,11 @@ impl PeerManager {
 
     /// Add a discovered peer. If at capacity, evicts the lowest-scoring
     /// peer when the new peer would score higher (new peers start at 0).
-    /// Banned peers are never added.
+    /// Banned, denied and address-banned peers are never added.
     pub fn add_peer(&mut self, address: PeerAddress) {
+        if self.is_address_blocked(&address.ip) {
+            return;
+        }
         let key = Self::peer_key(&address);
 
         if let Some(existing) = self.peers.get(&key) {
@@ -206,10 +332,29 @@ impl PeerManager {
                 ban_until_secs: None,
                 telemetry: None,
                 peering_addr: None,
+                quality: PeerQuality::default(),
+                connected_since_secs: None,
             },
         );
     }
 
+    /// Re-add a peer from the persistent cache with its recorded quality.
+    pub fn restore_peer(
+        &mut self,
+        address: PeerAddress,
+        last_seen_secs: u64,
+        quality: PeerQuality,
+    ) {
+        let key = Self::peer_key(&address);
+        self.add_peer(address);
+        if let Some(peer) = self.peers.get_mut(&key) {
+            if !peer.connected {
+                peer.last_seen_secs = last_seen_secs;
+                peer.quality = quality;
+            }
+        }
+    }
+
     /// Find the peer with the lowest reputation score.
     fn find_worst_peer(&self) -> Option<(String, i32)> {
         self.peers
@@ -232,6 +377,7 @@ impl PeerManager {
         if let Some(peer) = self.peers.get_mut(peer_id) {
             if !peer.connected {
                 self.num_connected += 1;
+                peer.connected_since_secs = Some(now_secs);
             }
             peer.connected = true;
             peer.last_seen_secs = now_secs;
@@ -253,6 +399,25 @@ impl PeerManager {
                 self.num_connected = self.num_connected.saturating_sub(1);
             }
             peer.connected = false;
+            peer.end_session();
+        }
+    }
+
+    /// Record the outcome of an outbound connection attempt.
+    pub fn record_handshake(&mut self, peer_id: &str, succeeded: bool) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.quality.handshake_attempts = peer.quality.handshake_attempts.saturating_add(1);
+            if succeeded {
+                peer.quality.handshake_successes =
+                    peer.quality.handshake_successes.saturating_add(1);
+            }
+        }
+    }
+
+    /// Record that a vote relayed by the peer counted in an election.
+    pub fn record_useful_vote(&mut self, peer_id: &str) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.quality.useful_votes = peer.quality.useful_votes.saturating_add(1);
         }
     }
 
@@ -294,7 +459,7 @@ impl PeerManager {
     pub fn get_connectable_peers(&self) -> Vec<&PeerState> {
         self.peers
             .values()
-            .filter(|p| !p.connected && !p.banned)
+            .filter(|p| !p.connected && !p.banned && !self.is_address_blocked(&p.address.ip))
             .collect()
     }
 
@@ -365,11 +530,26 @@ impl PeerManager {
 
     /// Returns the address to advertise for this node in keepalive messages.
     /// Prefers the UPnP external address; falls back to `None` if unavailable.
+    /// Only addresses that pass the same validation applied to shared
+    /// addresses are advertised.
     pub fn self_peer_address(&self) -> Option<PeerAddress> {
-        self.external_address.map(|addr| PeerAddress {
-            ip: addr.ip().to_string(),
-            port: addr.port(),
-        })
+        self.external_address
+            .map(|addr| PeerAddress {
+                ip: addr.ip().to_string(),
+                port: addr.port(),
+            })
+            .filter(|addr| validate_peer_address(addr, self.allow_private_addresses).is_ok())
+    }
+
+    /// Accept private ranges and privileged ports in shared and advertised
+    /// addresses. Off by default; enable for test and dev networks.
+    pub fn set_allow_private_addresses(&mut self, allow: bool) {
+        self.allow_private_addresses = allow;
+    }
+
+    /// Whether private ranges are accepted in shared addresses.
+    pub fn allow_private_addresses(&self) -> bool {
+        self.allow_private_addresses
     }
 
     // -- Keepalive -------------------------------------------------------------
@@ -388,18 +568,63 @@ impl PeerManager {
         self.last_keepalive_secs = Some(now_secs);
     }
 
-    /// Process a received keepalive message: learn any new peer addresses
-    /// and buffer the full list for the reachout loop.
-    pub fn process_keepalive(&mut self, peers: Vec<PeerAddress>) {
-        if !peers.is_empty() {
-            if self.latest_keepalives.len() >= LATEST_KEEPALIVES_CAPACITY {
-                self.latest_keepalives.pop_front();
+    /// Process a received keepalive message. Shared addresses that are
+    /// valid, not blocked, not our own and not already known (as a peer,
+    /// by a connection from the same IP, or as a pending candidate) are
+    /// queued for probing. Returns how many were queued.
+    pub fn process_keepalive(&mut self, peers: Vec<PeerAddress>) -> usize {
+        let own = self.self_peer_address();
+        let mut queued = 0;
+        for addr in peers {
+            if validate_peer_address(&addr, self.allow_private_addresses).is_err()
+                || self.is_address_blocked(&addr.ip)
+                || own.as_ref() == Some(&addr)
+                || self.peers.contains_key(&Self::peer_key(&addr))
+                || !self.connected_peer_ids_from_ip(&addr.ip).is_empty()
+                || self.unverified.contains(&addr)
+            {
+                continue;
             }
-            self.latest_keepalives.push_back(peers.clone());
+            if self.unverified.len() >= UNVERIFIED_PEERS_CAPACITY {
+                self.unverified.pop_front();
+            }
+            self.unverified.push_back(addr);
+            queued += 1;
         }
-        for addr in peers {
-            self.add_peer(addr);
+        queued
+    }
+
+    /// Remove and return up to `count` random unverified addresses to probe.
+    pub fn take_probe_candidates(&mut self, count: usize) -> Vec<PeerAddress> {
+        let mut rng = rand::thread_rng();
+        let mut taken = Vec::with_capacity(count.min(self.unverified.len()));
+        while taken.len() < count && !self.unverified.is_empty() {
+            let idx = rand::Rng::gen_range(&mut rng, 0..self.unverified.len());
+            if let Some(addr) = self.unverified.remove(idx) {
+                taken.push(addr);
+            }
         }
+        taken
+    }
+
+    /// Number of shared addresses waiting to be probed.
+    pub fn unverified_count(&self) -> usize {
+        self.unverified.len()
+    }
+
+    /// Add addresses that answered a probe as peers, and buffer them for
+    /// the reachout loop.
+    pub fn add_probed_peers(&mut self, peers: Vec<PeerAddress>) {
+        if peers.is_empty() {
+            return;
+        }
+        for addr in &peers {
+            self.add_peer(addr.clone());
+        }
+        if self.latest_keepalives.len() >= LATEST_KEEPALIVES_CAPACITY {
+            self.latest_keepalives.pop_front();
+        }
+        self.latest_keepalives.push_back(peers);
     }
 
     /// Pop a random recently received keepalive peer list for the reachout
@@ -428,6 +653,16 @@ impl PeerManager {
             .collect()
     }
 
+    /// Last-seen time and quality of every peer that is not banned and has
+    /// been seen, for the persistent peer cache.
+    pub fn peer_quality_records(&self) -> Vec<(String, u64, PeerQuality)> {
+        self.peers
+            .iter()
+            .filter(|(_, p)| !p.banned && p.last_seen_secs > 0)
+            .map(|(key, p)| (key.clone(), p.last_seen_secs, p.current_quality()))
+            .collect()
+    }
+
     // -- Scoring / banning -----------------------------------------------------
 
     /// Penalize a peer for bad behaviour. Returns `true` if the peer was
@@ -467,8 +702,9 @@ impl PeerManager {
         self.peers.get(peer_id).is_some_and(|p| p.banned)
     }
 
-    /// Unban peers whose ban has expired.
+    /// Unban peers and address ranges whose ban has expired.
     pub fn check_bans(&mut self, now_secs: u64) {
+        self.address_bans.retain(|_, until| now_secs < *until);
         for peer in self.peers.values_mut() {
             if peer.banned {
                 if let Some(until) = peer.ban_until_secs {
@@ -492,6 +728,7 @@ impl PeerManager {
         for (key, peer) in self.peers.iter_mut() {
             if peer.connected && !peer.banned && peer.last_seen_secs < cutoff {
                 peer.connected = false;
+                peer.end_session();
                 self.num_connected = self.num_connected.saturating_sub(1);
                 idle_peers.push(key.clone());
             }
@@ -507,10 +744,132 @@ impl PeerManager {
         }
     }
 
+    // -- Access lists / subnet limits -------------------------------------------
+
+    /// Replace the allow and deny lists.
+    pub fn set_access_lists(&mut self, allowlist: Vec<IpNet>, denylist: Vec<IpNet>) {
+        self.allowlist = allowlist;
+        self.denylist = denylist;
+    }
+
+    /// Set the cap on connected peers per /24 (IPv4) or /48 (IPv6);
+    /// 0 disables it.
+    pub fn set_max_peers_per_subnet(&mut self, max: usize) {
+        self.max_peers_per_subnet = max;
+    }
+
+    fn is_allowlisted(&self, ip: &IpAddr) -> bool {
+        self.allowlist.iter().any(|net| net.contains(ip))
+    }
+
+    /// Whether the access lists or a ban rule out `ip` regardless of how
+    /// many peers are connected. Unparseable addresses are never blocked.
+    fn is_address_blocked(&self, ip: &str) -> bool {
+        let Ok(ip) = ip.parse::<IpAddr>() else {
+            return false;
+        };
+        if !self.allowlist.is_empty() {
+            return !self.is_allowlisted(&ip);
+        }
+        self.denylist.iter().any(|net| net.contains(&ip))
+            || self.address_bans.keys().any(|net| net.contains(&ip))
+    }
+
+    /// Decide whether a new connection to or from `ip` may be made.
+    ///
+    /// Allowlisted addresses bypass bans and subnet caps; with a non-empty
+    /// allowlist every other address is denied. Reconnects from an address
+    /// that is already connected do not count against its subnet.
+    pub fn check_connection(&self, ip: &str) -> Result<(), ConnectionRefusal> {
+        let Ok(addr) = ip.parse::<IpAddr>() else {
+            return Ok(());
+        };
+        if !self.allowlist.is_empty() {
+            return if self.is_allowlisted(&addr) {
+                Ok(())
+            } else {
+                Err(ConnectionRefusal::Denied)
+            };
+        }
+        if self.denylist.iter().any(|net| net.contains(&addr)) {
+            return Err(ConnectionRefusal::Denied);
+        }
+        if self.address_bans.keys().any(|net| net.contains(&addr)) {
+            return Err(ConnectionRefusal::Banned);
+        }
+        if self.max_peers_per_subnet > 0 {
+            let subnet = IpNet::subnet_of(addr);
+            let addr = addr.to_canonical();
+            let in_subnet = self
+                .iter_connected()
+                .filter_map(|(_, p)| p.address.ip.parse::<IpAddr>().ok())
+                .filter(|peer_ip| peer_ip.to_canonical() != addr && subnet.contains(peer_ip))
+                .count();
+            if in_subnet >= self.max_peers_per_subnet {
+                return Err(ConnectionRefusal::SubnetFull);
+            }
+        }
+        Ok(())
+    }
+
+    /// Ban every address in `target` until `until_secs` ([`BAN_INDEFINITE`]
+    /// for never). Known peers in the range are marked banned; the IDs of
+    /// those that were connected are returned so the caller can close them.
+    pub fn ban_address(&mut self, target: IpNet, until_secs: u64) -> Vec<String> {
+        self.address_bans.insert(target, until_secs);
+        let mut disconnected = Vec::new();
+        for (key, peer) in self.peers.iter_mut() {
+            let in_range = peer
+                .address
+                .ip
+                .parse::<IpAddr>()
+                .is_ok_and(|ip| target.contains(&ip));
+            if !in_range {
+                continue;
+            }
+            if peer.connected {
+                self.num_connected = self.num_connected.saturating_sub(1);
+                disconnected.push(key.clone());
+            }
+            peer.banned = true;
+            peer.connected = false;
+            peer.end_session();
+            peer.ban_until_secs = Some(until_secs);
+        }
+        disconnected
+    }
+
+    /// Lift an address ban. Peers it covered become connectable again.
+    /// Returns `false` when no ban for exactly `target` exists.
+    pub fn unban_address(&mut self, target: &IpNet) -> bool {
+        if self.address_bans.remove(target).is_none() {
+            return false;
+        }
+        for peer in self.peers.values_mut() {
+            let in_range = peer
+                .address
+                .ip
+                .parse::<IpAddr>()
+                .is_ok_and(|ip| target.contains(&ip));
+            if in_range && peer.banned {
+                peer.banned = false;
+                peer.ban_until_secs = None;
+                peer.score = 0;
+            }
+        }
+        true
+    }
+
+    /// Active address bans with their expiry.
+    pub fn address_bans(&self) -> impl Iterator<Item = (&IpNet, &u64)> {
+        self.address_bans.iter()
+    }
+
     /// Internal helper — mark a peer as banned.
     fn ban_peer(peer: &mut PeerState, now_secs: u64) {
         peer.banned = true;
         peer.connected = false;
+        peer.end_session();
         peer.ban_until_secs = Some(now_secs + BAN_DURATION_SECS);
     }
 }
@@ -602,10 +961,45 @@ mod tests {
     }
 
     #[test]
-    fn process_keepalive_learns_peers() {
+    fn process_keepalive_queues_valid_unknown_peers_for_probing() {
         let mut pm = PeerManager::new(10);
-        pm.process_keepalive(vec![addr("5.5.5.5", 7075), addr("6.6.6.6", 7075)]);
-        assert_eq!(pm.peers.len(), 2);
+        pm.add_peer(addr("7.7.7.7", 7075));
+        let queued = pm.process_keepalive(vec![
+            addr("5.5.5.5", 7075),
+            addr("6.6.6.6", 7075),
+            addr("5.5.5.5", 7075),
+            addr("7.7.7.7", 7075),
+            addr("10.0.0.1", 7075),
+            addr("8.8.8.8", 0),
+        ]);
+        assert_eq!(queued, 2);
+        assert_eq!(pm.unverified_count(), 2);
+        // Nothing is a peer until it has answered a probe.
+        assert_eq!(pm.peers.len(), 1);
+        assert!(pm.pop_random_keepalive().is_none());
+
+        let mut probed = pm.take_probe_candidates(8);
+        probed.sort_by(|a, b| a.ip.cmp(&b.ip));
+        assert_eq!(probed, vec![addr("5.5.5.5", 7075), addr("6.6.6.6", 7075)]);
+        assert_eq!(pm.unverified_count(), 0);
+        pm.add_probed_peers(probed);
+        assert_eq!(pm.peers.len(), 3);
+        assert_eq!(pm.pop_random_keepalive().map(|p| p.len()), Some(2));
+
+        pm.set_allow_private_addresses(true);
+        assert_eq!(pm.process_keepalive(vec![addr("10.0.0.1", 7075)]), 1);
+    }
+
+    #[test]
+    fn self_advertisement_requires_valid_external_address() {
+        let mut pm = PeerManager::new(10);
+        pm.set_external_address("192.168.1.10:7075".parse().unwrap());
+        assert!(pm.self_peer_address().is_none());
+        pm.set_external_address("203.0.113.5:7075".parse().unwrap());
+        assert_eq!(
+            pm.random_peers_with_self(8),
+            vec![addr("203.0.113.5", 7075)]
+        );
     }
 
     #[test]
@@ -711,4 +1105,124 @@ mod tests {
         assert_eq!(idle.len(), 2);
         assert_eq!(pm.connected_count(), 0);
     }
+
+    #[test]
+    fn quality_accumulates_across_sessions() {
+        let mut pm = PeerManager::new(10);
+        let id = key("1.0.0.1", 1);
+        pm.restore_peer(
+            addr("1.0.0.1", 1),
+            50,
+            PeerQuality {
+                uptime_secs: 3_600,
+                ..Default::default()
+            },
+        );
+        pm.record_handshake(&id, true);
+        pm.mark_connected(&id, 100);
+        pm.touch(&id, 7_300);
+        pm.record_useful_vote(&id);
+        assert_eq!(pm.peers[&id].current_quality().uptime_secs, 3_600 + 7_200);
+
+        pm.mark_disconnected(&id);
+        pm.record_handshake(&id, false);
+        let records = pm.peer_quality_records();
+        assert_eq!(records.len(), 1);
+        let (_, last_seen, quality) = records[0];
+        assert_eq!(last_seen, 7_300);
+        assert_eq!(
+            quality,
+            PeerQuality {
+                uptime_secs: 10_800,
+                handshake_attempts: 2,
+                handshake_successes: 1,
+                useful_votes: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn quality_score_prefers_reliable_long_lived_peers() {
+        let untried = PeerQuality::default();
+        let flaky = PeerQuality {
+            handshake_attempts: 10,
+            handshake_successes: 1,
+            uptime_secs: 3 * 3_600,
+            ..Default::default()
+        };
+        let steady = PeerQuality {
+            handshake_attempts: 10,
+            handshake_successes: 10,
+            uptime_secs: 3 * 3_600,
+            ..Default::default()
+        };
+        let voter = PeerQuality {
+            useful_votes: 5_000,
+            ..steady
+        };
+        assert_eq!(untried.score(), 500);
+        assert!(steady.score() > flaky.score());
+        assert!(voter.score() > steady.score());
+    }
+
+    #[test]
+    fn access_lists_and_subnet_cap() {
+        let mut pm = PeerManager::new(10);
+        pm.set_max_peers_per_subnet(2);
+        for (ip, port) in [("10.0.0.1", 1), ("10.0.0.2", 2)] {
+            pm.add_peer(addr(ip, port));
+            pm.mark_connected(&key(ip, port), 0);
+        }
+        assert_eq!(
+            pm.check_connection("10.0.0.3"),
+            Err(ConnectionRefusal::SubnetFull)
+        );
+        // A reconnect from an address already connected is not a new slot.
+        assert_eq!(pm.check_connection("10.0.0.1"), Ok(()));
+        assert_eq!(pm.check_connection("10.0.1.3"), Ok(()));
+
+        pm.set_access_lists(vec![], vec!["192.168.0.0/16".parse().unwrap()]);
+        assert_eq!(
+            pm.check_connection("192.168.4.4"),
+            Err(ConnectionRefusal::Denied)
+        );
+        pm.add_peer(addr("192.168.4.4", 1));
+        assert!(pm.get_connectable_peers().is_empty());
+
+        // An allowlist admits only its ranges, ignoring the subnet cap.
+        pm.set_access_lists(vec!["10.0.0.0/24".parse().unwrap()], vec![]);
+        assert_eq!(pm.check_connection("10.0.0.3"), Ok(()));
+        assert_eq!(
+            pm.check_connection("8.8.8.8"),
+            Err(ConnectionRefusal::Denied)
+        );
+    }
+
+    #[test]
+    fn address_bans_cover_ranges_and_expire() {
+        let mut pm = PeerManager::new(10);
+        for (ip, port) in [("10.0.0.1", 1), ("10.0.0.2", 2), ("10.0.1.1", 3)] {
+            pm.add_peer(addr(ip, port));
+            pm.mark_connected(&key(ip, port), 0);
+        }
+        let range: IpNet = "10.0.0.0/24".parse().unwrap();
+        let mut dropped = pm.ban_address(range, 1_000);
+        dropped.sort();
+        assert_eq!(dropped, vec![key("10.0.0.1", 1), key("10.0.0.2", 2)]);
+        assert_eq!(pm.connected_count(), 1);
+        assert_eq!(
+            pm.check_connection("10.0.0.9"),
+            Err(ConnectionRefusal::Banned)
+        );
+
+        pm.check_bans(1_000);
+        assert_eq!(pm.check_connection("10.0.0.9"), Ok(()));
+        assert!(!pm.is_banned(&key("10.0.0.1", 1)));
+
+        pm.ban_address(range, BAN_INDEFINITE);
+        assert!(pm.unban_address(&range));
+        assert!(!pm.unban_address(&range));
+        assert_eq!(pm.address_bans().count(), 0);
+        assert!(!pm.is_banned(&key("10.0.0.2", 2)));
+    }
 }
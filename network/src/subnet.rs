@@ -0,0 +1,160 @@
+//! IP address ranges for peer allow/deny lists, bans and subnet limits.
+
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use crate::error::NetworkError;
+
+/// Prefix length that groups IPv4 peers for connection caps.
+pub const IPV4_SUBNET_PREFIX: u8 = 24;
+/// Prefix length that groups IPv6 peers for connection caps.
+pub const IPV6_SUBNET_PREFIX: u8 = 48;
+
+/// A CIDR range (`"10.0.0.0/8"`, `"2001:db8::/32"`) or a single address.
+///
+/// The stored address is always the network address, so two spellings of
+/// the same range compare equal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IpNet {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl IpNet {
+    /// The range of `prefix` leading bits around `addr`.
+    pub fn new(addr: IpAddr, prefix: u8) -> Result<Self, NetworkError> {
+        let addr = addr.to_canonical();
+        let max = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        if prefix > max {
+            return Err(NetworkError::InvalidSubnet(format!("{addr}/{prefix}")));
+        }
+        Ok(Self {
+            network: mask(addr, prefix),
+            prefix,
+        })
+    }
+
+    /// A range holding exactly one address.
+    pub fn host(addr: IpAddr) -> Self {
+        let addr = addr.to_canonical();
+        let prefix = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        Self {
+            network: addr,
+            prefix,
+        }
+    }
+
+    /// The /24 (IPv4) or /48 (IPv6) an address belongs to.
+    pub fn subnet_of(addr: IpAddr) -> Self {
+        let addr = addr.to_canonical();
+        let prefix = match addr {
+            IpAddr::V4(_) => IPV4_SUBNET_PREFIX,
+            IpAddr::V6(_) => IPV6_SUBNET_PREFIX,
+        };
+        Self {
+            network: mask(addr, prefix),
+            prefix,
+        }
+    }
+
+    /// Whether `addr` falls inside the range.
+    pub fn contains(&self, addr: &IpAddr) -> bool {
+        let addr = addr.to_canonical();
+        match (self.network, addr) {
+            (IpAddr::V4(_), IpAddr::V4(_)) | (IpAddr::V6(_), IpAddr::V6(_)) => {
+                mask(addr, self.prefix) == self.network
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Zero all but the leading `prefix` bits of `addr`.
+fn mask(addr: IpAddr, prefix: u8) -> IpAddr {
+    match addr {
+        IpAddr::V4(v4) => {
+            let bits = u32::from(v4);
+            let masked = bits & u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            IpAddr::V4(Ipv4Addr::from(masked))
+        }
+        IpAddr::V6(v6) => {
+            let bits = u128::from(v6);
+            let masked = bits & u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            IpAddr::V6(Ipv6Addr::from(masked))
+        }
+    }
+}
+
+impl FromStr for IpNet {
+    type Err = NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || NetworkError::InvalidSubnet(s.to_string());
+        match s.split_once('/') {
+            Some((addr, prefix)) => {
+                let addr = addr.trim().parse().map_err(|_| invalid())?;
+                let prefix = prefix.trim().parse().map_err(|_| invalid())?;
+                Self::new(addr, prefix)
+            }
+            None => Ok(Self::host(s.trim().parse().map_err(|_| invalid())?)),
+        }
+    }
+}
+
+impl fmt::Display for IpNet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_ranges_and_hosts() {
+        let net: IpNet = "10.1.2.99/24".parse().unwrap();
+        assert_eq!(net.to_string(), "10.1.2.0/24");
+        assert!(net.contains(&ip("10.1.2.7")));
+        assert!(!net.contains(&ip("10.1.3.7")));
+        assert!(!net.contains(&ip("2001:db8::1")));
+
+        let host: IpNet = "192.168.0.5".parse().unwrap();
+        assert_eq!(host.to_string(), "192.168.0.5/32");
+        assert!(host.contains(&ip("::ffff:192.168.0.5")));
+
+        assert!("0.0.0.0/0"
+            .parse::<IpNet>()
+            .unwrap()
+            .contains(&ip("8.8.8.8")));
+        assert!("10.0.0.0/33".parse::<IpNet>().is_err());
+        assert!("not-an-ip".parse::<IpNet>().is_err());
+    }
+
+    #[test]
+    fn groups_peers_by_subnet() {
+        assert_eq!(
+            IpNet::subnet_of(ip("203.0.113.77")).to_string(),
+            "203.0.113.0/24"
+        );
+        assert_eq!(
+            IpNet::subnet_of(ip("2001:db8:aa:bb::1")),
+            IpNet::subnet_of(ip("2001:db8:aa:cc::2"))
+        );
+        assert_ne!(
+            IpNet::subnet_of(ip("2001:db8:aa::1")),
+            IpNet::subnet_of(ip("2001:db8:ab::1"))
+        );
+    }
+}
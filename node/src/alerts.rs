@@ -0,0 +1,657 @@
+//! Operator alerts.
+//!
+//! When an `[alerts]` section is configured, the node checks its own health
+//! every `check_interval_secs` and posts to the configured endpoints when a
+//! threshold is breached and again when it clears:
+//!
+//! - no blocks cemented for `no_confirmation_minutes`,
+//! - fewer than `min_peers` connected peers,
+//! - less than `min_disk_free_percent` free on the data directory's disk,
+//! - clock offset beyond `max_clock_drift_ms`,
+//! - a failed integrity check (ledger invariant violation, or a state root
+//!   or state digest that diverges from a peer's),
+//! - the node in read-only mode because storage ran low (see
+//!   [`crate::storage_guard`]).
+//!
+//! Each `[[alerts.endpoints]]` entry is either a plain `webhook`, which
+//! receives the [`Alert`] as JSON (signed like callbacks when a `secret` is
+//! set), or `pagerduty`, which receives a PagerDuty Events API v2 event
+//! that is triggered and resolved under one dedup key per alert kind.
+//! An alert still firing is re-sent every `repeat_secs`.
+//!
+//! Setting a threshold to `0` disables that check.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use prometheus::IntCounterVec;
+use serde::{Deserialize, Serialize};
+
+use crate::callback::{sign_payload, RetryPolicy, SIGNATURE_HEADER};
+use crate::error::NodeError;
+
+/// PagerDuty Events API v2 endpoint.
+pub const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// The `[alerts]` config section.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AlertsConfig {
+    /// Where alerts are sent.
+    pub endpoints: Vec<AlertEndpoint>,
+    /// Name the alerts report as their source.
+    #[serde(default = "default_source")]
+    pub source: String,
+    /// Seconds between health checks.
+    #[serde(default = "default_check_interval_secs")]
+    pub check_interval_secs: u64,
+    /// Alert when nothing has been cemented for this many minutes.
+    #[serde(default = "default_no_confirmation_minutes")]
+    pub no_confirmation_minutes: u64,
+    /// Alert when fewer peers than this are connected.
+    #[serde(default = "default_min_peers")]
+    pub min_peers: usize,
+    /// Alert when less than this share of the data disk is free.
+    #[serde(default = "default_min_disk_free_percent")]
+    pub min_disk_free_percent: f64,
+    /// Alert when the clock offset exceeds this many milliseconds.
+    #[serde(default = "default_max_clock_drift_ms")]
+    pub max_clock_drift_ms: u64,
+    /// Alert on failed integrity checks.
+    #[serde(default = "default_integrity")]
+    pub integrity: bool,
+    /// Re-send a still-firing alert after this many seconds. `0` sends it
+    /// once.
+    #[serde(default = "default_repeat_secs")]
+    pub repeat_secs: u64,
+    /// Per-request timeout in seconds.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default)]
+    pub retry: RetryPolicy,
+}
+
+/// One alert destination.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AlertEndpoint {
+    /// POST the alert as JSON.
+    Webhook {
+        url: String,
+        /// Shared secret for the HMAC signature header.
+        #[serde(default)]
+        secret: Option<String>,
+    },
+    /// Trigger and resolve PagerDuty incidents.
+    Pagerduty {
+        routing_key: String,
+        #[serde(default = "default_pagerduty_url")]
+        url: String,
+    },
+}
+
+fn default_source() -> String {
+    "burst-node".into()
+}
+
+fn default_check_interval_secs() -> u64 {
+    30
+}
+
+fn default_no_confirmation_minutes() -> u64 {
+    10
+}
+
+fn default_min_peers() -> usize {
+    3
+}
+
+fn default_min_disk_free_percent() -> f64 {
+    10.0
+}
+
+fn default_max_clock_drift_ms() -> u64 {
+    2_000
+}
+
+fn default_integrity() -> bool {
+    true
+}
+
+fn default_repeat_secs() -> u64 {
+    3_600
+}
+
+fn default_timeout_secs() -> u64 {
+    10
+}
+
+fn default_pagerduty_url() -> String {
+    PAGERDUTY_EVENTS_URL.into()
+}
+
+impl AlertsConfig {
+    /// Reject configurations the notifier cannot act on.
+    pub fn validate(&self) -> Result<(), NodeError> {
+        if self.endpoints.is_empty() {
+            return Err(NodeError::Config(
+                "alerts endpoints must not be empty".into(),
+            ));
+        }
+        for endpoint in &self.endpoints {
+            let url = match endpoint {
+                AlertEndpoint::Webhook { url, .. } | AlertEndpoint::Pagerduty { url, .. } => url,
+            };
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                return Err(NodeError::Config(format!(
+                    "alert endpoint url must be http(s): {url}"
+                )));
+            }
+        }
+        if self.check_interval_secs == 0 {
+            return Err(NodeError::Config(
+                "alerts check_interval_secs must be at least 1".into(),
+            ));
+        }
+        if self.retry.max_attempts == 0 {
+            return Err(NodeError::Config(
+                "alerts retry.max_attempts must be at least 1".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// What an alert is about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    NoConfirmations,
+    LowPeerCount,
+    DiskNearlyFull,
+    ClockDrift,
+    IntegrityFailure,
+    StorageReadOnly,
+}
+
+impl AlertKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NoConfirmations => "no_confirmations",
+            Self::LowPeerCount => "low_peer_count",
+            Self::DiskNearlyFull => "disk_nearly_full",
+            Self::ClockDrift => "clock_drift",
+            Self::IntegrityFailure => "integrity_failure",
+            Self::StorageReadOnly => "storage_read_only",
+        }
+    }
+
+    /// PagerDuty severity.
+    fn severity(&self) -> &'static str {
+        match self {
+            Self::IntegrityFailure | Self::NoConfirmations | Self::StorageReadOnly => "critical",
+            Self::DiskNearlyFull => "error",
+            Self::LowPeerCount | Self::ClockDrift => "warning",
+        }
+    }
+}
+
+/// Whether an alert started or cleared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertStatus {
+    Firing,
+    Resolved,
+}
+
+/// One alert notification, sent as the body of `webhook` endpoints.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Alert {
+    pub source: String,
+    pub alert: AlertKind,
+    pub status: AlertStatus,
+    pub summary: String,
+    /// The measured value that breached (or no longer breaches).
+    pub value: f64,
+    pub threshold: f64,
+    /// Unix seconds.
+    pub timestamp: u64,
+}
+
+/// Health figures taken at one check.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HealthSample {
+    pub now_secs: u64,
+    /// Blocks cemented since startup.
+    pub cemented_count: u64,
+    pub peer_count: usize,
+    /// Free share of the data directory's disk; `None` if unknown.
+    pub disk_free_percent: Option<f64>,
+    pub clock_offset_ms: i64,
+    /// Integrity check failures since startup.
+    pub integrity_failures: u64,
+    /// Whether the node stopped writing because storage ran low.
+    pub storage_read_only: bool,
+}
+
+/// Turns health samples into alert transitions.
+pub struct AlertMonitor {
+    config: AlertsConfig,
+    /// Firing alerts and when each was last sent.
+    firing: HashMap<AlertKind, u64>,
+    /// Cemented count last seen and when it last moved.
+    cemented: Option<(u64, u64)>,
+    integrity_failures: Option<u64>,
+}
+
+impl AlertMonitor {
+    pub fn new(config: AlertsConfig) -> Self {
+        Self {
+            config,
+            firing: HashMap::new(),
+            cemented: None,
+            integrity_failures: None,
+        }
+    }
+
+    /// Whether `kind` is currently firing.
+    pub fn is_firing(&self, kind: AlertKind) -> bool {
+        self.firing.contains_key(&kind)
+    }
+
+    /// Check `sample` against the thresholds. Returns the alerts to send:
+    /// newly breached thresholds, cleared ones, and firing ones due for a
+    /// repeat.
+    pub fn evaluate(&mut self, sample: &HealthSample) -> Vec<Alert> {
+        let now = sample.now_secs;
+        let config = &self.config;
+        // (kind, breached, value, threshold, summary)
+        let mut checks: Vec<(AlertKind, bool, f64, f64, String)> = Vec::new();
+
+        let since = match self.cemented {
+            Some((count, since)) if count == sample.cemented_count => since,
+            _ => now,
+        };
+        self.cemented = Some((sample.cemented_count, since));
+        if config.no_confirmation_minutes > 0 {
+            let stalled_mins = now.saturating_sub(since) / 60;
+            checks.push((
+                AlertKind::NoConfirmations,
+                stalled_mins >= config.no_confirmation_minutes,
+                stalled_mins as f64,
+                config.no_confirmation_minutes as f64,
+                format!("no blocks cemented for {stalled_mins} minutes"),
+            ));
+        }
+
+        if config.min_peers > 0 {
+            checks.push((
+                AlertKind::LowPeerCount,
+                sample.peer_count < config.min_peers,
+                sample.peer_count as f64,
+                config.min_peers as f64,
+                format!("{} peers connected", sample.peer_count),
+            ));
+        }
+
+        if let (true, Some(free)) = (config.min_disk_free_percent > 0.0, sample.disk_free_percent) {
+            checks.push((
+                AlertKind::DiskNearlyFull,
+                free < config.min_disk_free_percent,
+                free,
+                config.min_disk_free_percent,
+                format!("{free:.1}% free on the data disk"),
+            ));
+        }
+
+        if config.max_clock_drift_ms > 0 {
+            let drift = sample.clock_offset_ms.unsigned_abs();
+            checks.push((
+                AlertKind::ClockDrift,
+                drift > config.max_clock_drift_ms,
+                drift as f64,
+                config.max_clock_drift_ms as f64,
+                format!("clock is off by {drift} ms"),
+            ));
+        }
+
+        let previous = self
+            .integrity_failures
+            .replace(sample.integrity_failures)
+            .unwrap_or(sample.integrity_failures);
+        if config.integrity {
+            let new = sample.integrity_failures.saturating_sub(previous);
+            checks.push((
+                AlertKind::IntegrityFailure,
+                new > 0,
+                new as f64,
+                0.0,
+                format!("{new} integrity check failures since the last check"),
+            ));
+        }
+
+        checks.push((
+            AlertKind::StorageReadOnly,
+            sample.storage_read_only,
+            sample.storage_read_only as u8 as f64,
+            0.0,
+            if sample.storage_read_only {
+                "storage nearly exhausted: node is read-only".into()
+            } else {
+                "storage recovered: node is writing again".into()
+            },
+        ));
+
+        let mut alerts = Vec::new();
+        for (kind, breached, value, threshold, summary) in checks {
+            let status = match (breached, self.firing.get(&kind)) {
+                (true, None) => AlertStatus::Firing,
+                (true, Some(&sent))
+                    if self.config.repeat_secs > 0
+                        && now.saturating_sub(sent) >= self.config.repeat_secs =>
+                {
+                    AlertStatus::Firing
+                }
+                (false, Some(_)) => AlertStatus::Resolved,
+                _ => continue,
+            };
+            match status {
+                AlertStatus::Firing => self.firing.insert(kind, now),
+                AlertStatus::Resolved => self.firing.remove(&kind),
+            };
+            alerts.push(Alert {
+                source: self.config.source.clone(),
+                alert: kind,
+                status,
+                summary,
+                value,
+                threshold,
+                timestamp: now,
+            });
+        }
+        alerts
+    }
+}
+
+/// The PagerDuty Events API v2 body for `alert`.
+pub fn pagerduty_event(routing_key: &str, alert: &Alert) -> serde_json::Value {
+    let dedup_key = format!("{}:{}", alert.source, alert.alert.as_str());
+    match alert.status {
+        AlertStatus::Firing => serde_json::json!({
+            "routing_key": routing_key,
+            "event_action": "trigger",
+            "dedup_key": dedup_key,
+            "payload": {
+                "summary": format!("{}: {}", alert.source, alert.summary),
+                "source": alert.source,
+                "severity": alert.alert.severity(),
+                "component": "burst-node",
+                "class": alert.alert.as_str(),
+                "custom_details": {
+                    "value": alert.value,
+                    "threshold": alert.threshold,
+                },
+            },
+        }),
+        AlertStatus::Resolved => serde_json::json!({
+            "routing_key": routing_key,
+            "event_action": "resolve",
+            "dedup_key": dedup_key,
+        }),
+    }
+}
+
+/// Sends alerts to every configured endpoint.
+pub struct AlertNotifier {
+    endpoints: Vec<AlertEndpoint>,
+    retry: RetryPolicy,
+    http: reqwest::Client,
+}
+
+impl AlertNotifier {
+    pub fn new(config: &AlertsConfig) -> Result<Self, NodeError> {
+        config.validate()?;
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|e| NodeError::Config(format!("failed to create alerts client: {e}")))?;
+        Ok(Self {
+            endpoints: config.endpoints.clone(),
+            retry: config.retry.clone(),
+            http,
+        })
+    }
+
+    /// Send `alert` to every endpoint, each in its own task so a slow
+    /// endpoint delays neither the others nor the next health check.
+    pub fn notify(self: &Arc<Self>, alert: Alert) {
+        for index in 0..self.endpoints.len() {
+            let notifier = Arc::clone(self);
+            let alert = alert.clone();
+            tokio::spawn(async move {
+                if let Err(e) = notifier.deliver(index, &alert).await {
+                    tracing::warn!(alert = alert.alert.as_str(), "alert dropped: {e}");
+                }
+            });
+        }
+    }
+
+    /// POST `alert` to endpoint `index`, retrying connection errors, `429`
+    /// and `5xx` per the retry policy.
+    async fn deliver(&self, index: usize, alert: &Alert) -> Result<(), String> {
+        let (url, body, signature) = match &self.endpoints[index] {
+            AlertEndpoint::Webhook { url, secret } => {
+                let body = serde_json::to_string(alert).map_err(|e| e.to_string())?;
+                let signature = secret
+                    .as_deref()
+                    .map(|secret| format!("sha256={}", sign_payload(secret, body.as_bytes())));
+                (url, body, signature)
+            }
+            AlertEndpoint::Pagerduty { url, routing_key } => {
+                (url, pagerduty_event(routing_key, alert).to_string(), None)
+            }
+        };
+
+        let mut attempt = 1;
+        loop {
+            let mut request = self
+                .http
+                .post(url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone());
+            if let Some(signature) = &signature {
+                request = request.header(SIGNATURE_HEADER, signature);
+            }
+            let error = match request.send().await {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => {
+                    let status = response.status();
+                    if !(status.is_server_error() || status.as_u16() == 429) {
+                        return Err(format!("{url} returned HTTP {status}"));
+                    }
+                    format!("{url} returned HTTP {status}")
+                }
+                Err(e) => format!("request to {url} failed: {e}"),
+            };
+            if attempt >= self.retry.max_attempts {
+                return Err(format!("{error} (after {attempt} attempts)"));
+            }
+            tokio::time::sleep(self.retry.backoff(attempt)).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Sum of a counter over all its label values.
+pub fn counter_total(counter: &IntCounterVec) -> u64 {
+    use prometheus::core::Collector;
+    counter
+        .collect()
+        .iter()
+        .flat_map(|family| family.get_metric())
+        .map(|metric| metric.get_counter().get_value() as u64)
+        .sum()
+}
+
+/// Free share of the disk holding `path`, in percent.
+#[cfg(unix)]
+pub fn disk_free_percent(path: &Path) -> Option<f64> {
+    use std::os::unix::ffi::OsStrExt;
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stats = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `path` is NUL-terminated and `stats` is only read after
+    // statvfs reports that it filled it in.
+    let stats = unsafe {
+        if libc::statvfs(path.as_ptr(), stats.as_mut_ptr()) != 0 {
+            return None;
+        }
+        stats.assume_init()
+    };
+    if stats.f_blocks == 0 {
+        return None;
+    }
+    Some(stats.f_bavail as f64 / stats.f_blocks as f64 * 100.0)
+}
+
+/// Free share of the disk holding `path`; unknown on this platform.
+#[cfg(not(unix))]
+pub fn disk_free_percent(_path: &Path) -> Option<f64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> AlertsConfig {
+        toml::from_str(
+            r#"
+            [[endpoints]]
+            kind = "webhook"
+            url = "https://ops.example/hook"
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn healthy(now_secs: u64, cemented_count: u64) -> HealthSample {
+        HealthSample {
+            now_secs,
+            cemented_count,
+            peer_count: 8,
+            disk_free_percent: Some(50.0),
+            clock_offset_ms: 10,
+            integrity_failures: 0,
+            storage_read_only: false,
+        }
+    }
+
+    #[test]
+    fn thresholds_fire_once_repeat_and_resolve() {
+        let mut monitor = AlertMonitor::new(config());
+        assert!(monitor.evaluate(&healthy(0, 1)).is_empty());
+
+        // Peers drop and the disk fills: both fire, once.
+        let mut sample = healthy(30, 2);
+        sample.peer_count = 1;
+        sample.disk_free_percent = Some(4.0);
+        let fired: Vec<AlertKind> = monitor.evaluate(&sample).iter().map(|a| a.alert).collect();
+        assert_eq!(
+            fired,
+            vec![AlertKind::LowPeerCount, AlertKind::DiskNearlyFull]
+        );
+        sample.now_secs = 60;
+        assert!(monitor.evaluate(&sample).is_empty());
+        // Still firing an hour later: sent again.
+        sample.now_secs = 30 + 3_600;
+        sample.cemented_count = 3;
+        assert_eq!(monitor.evaluate(&sample).len(), 2);
+
+        // Recovery resolves both; nothing cemented for ten minutes fires.
+        let alerts = monitor.evaluate(&healthy(30 + 3_600 + 600, 3));
+        let statuses: Vec<(AlertKind, AlertStatus)> =
+            alerts.iter().map(|a| (a.alert, a.status)).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (AlertKind::NoConfirmations, AlertStatus::Firing),
+                (AlertKind::LowPeerCount, AlertStatus::Resolved),
+                (AlertKind::DiskNearlyFull, AlertStatus::Resolved),
+            ]
+        );
+        assert!(monitor.is_firing(AlertKind::NoConfirmations));
+
+        // An integrity failure fires until a check passes without new ones;
+        // clock drift past the limit fires too.
+        let mut sample = healthy(5_000, 4);
+        sample.integrity_failures = 2;
+        sample.clock_offset_ms = -5_000;
+        sample.storage_read_only = true;
+        let alerts = monitor.evaluate(&sample);
+        assert!(alerts
+            .iter()
+            .any(|a| a.alert == AlertKind::IntegrityFailure && a.value == 2.0));
+        assert!(alerts.iter().any(|a| a.alert == AlertKind::ClockDrift));
+        assert!(alerts.iter().any(|a| a.alert == AlertKind::StorageReadOnly));
+        sample.now_secs = 5_030;
+        let alerts = monitor.evaluate(&sample);
+        assert!(alerts
+            .iter()
+            .any(|a| a.alert == AlertKind::IntegrityFailure && a.status == AlertStatus::Resolved));
+    }
+
+    #[test]
+    fn config_validates_and_builds_pagerduty_events() {
+        let parsed: AlertsConfig = toml::from_str(
+            r#"
+            source = "node-eu-1"
+            min_peers = 0
+
+            [[endpoints]]
+            kind = "pagerduty"
+            routing_key = "R0UT1NG"
+
+            [[endpoints]]
+            kind = "webhook"
+            url = "ftp://nowhere"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(parsed.min_peers, 0);
+        assert_eq!(
+            parsed.endpoints[0],
+            AlertEndpoint::Pagerduty {
+                routing_key: "R0UT1NG".into(),
+                url: PAGERDUTY_EVENTS_URL.into(),
+            }
+        );
+        assert!(matches!(parsed.validate(), Err(NodeError::Config(_))));
+        assert!(config().validate().is_ok());
+
+        let mut alert = Alert {
+            source: "node-eu-1".into(),
+            alert: AlertKind::DiskNearlyFull,
+            status: AlertStatus::Firing,
+            summary: "4.0% free on the data disk".into(),
+            value: 4.0,
+            threshold: 10.0,
+            timestamp: 1,
+        };
+        let trigger = pagerduty_event("R0UT1NG", &alert);
+        assert_eq!(trigger["event_action"], "trigger");
+        assert_eq!(trigger["dedup_key"], "node-eu-1:disk_nearly_full");
+        assert_eq!(trigger["payload"]["severity"], "error");
+        alert.status = AlertStatus::Resolved;
+        let resolve = pagerduty_event("R0UT1NG", &alert);
+        assert_eq!(resolve["event_action"], "resolve");
+        assert_eq!(resolve["dedup_key"], trigger["dedup_key"]);
+    }
+
+    #[test]
+    fn data_disk_free_space_is_a_percentage() {
+        if let Some(free) = disk_free_percent(Path::new(".")) {
+            assert!((0.0..=100.0).contains(&free));
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Alias registration rules.
+//!
+//! An Alias block registers the name carried in its memo for the signing
+//! account, burning `alias_fee` BRN. Names are first come, first served:
+//!
+//! - a free name, or one whose term and grace period have both passed, goes
+//!   to whoever registers it first;
+//! - the owner renews by registering the name again, which extends the term
+//!   from the current expiry (or from the block, if already expired);
+//! - during the grace period after expiry only the previous owner may renew.
+//!
+//! Times come from the block timestamp so every node reaches the same
+//! registry. An `alias_term_secs` of zero disables registration.
+
+use burst_ledger::StateBlock;
+use burst_store::AliasRecord;
+use burst_types::{ProtocolParams, Timestamp};
+
+/// Check an Alias block against the current record for its name and
+/// return the record it registers.
+pub fn validate_registration(
+    block: &StateBlock,
+    prev_brn_balance: u128,
+    existing: Option<&AliasRecord>,
+    params: &ProtocolParams,
+) -> Result<AliasRecord, String> {
+    if params.alias_term_secs == 0 {
+        return Err("alias registration is disabled".into());
+    }
+    let name = block
+        .memo
+        .as_deref()
+        .and_then(|memo| std::str::from_utf8(memo).ok())
+        .ok_or("alias block must carry the alias name as its memo")?;
+    let fee = prev_brn_balance.saturating_sub(block.brn_balance);
+    if fee != params.alias_fee {
+        return Err(format!(
+            "alias block burns {fee} BRN, but the alias fee is {}",
+            params.alias_fee
+        ));
+    }
+
+    let now = block.timestamp.as_secs();
+    let extend = |from: u64| Timestamp::new(from.saturating_add(params.alias_term_secs));
+    match existing {
+        Some(record) if record.owner == block.account => Ok(AliasRecord {
+            name: name.to_string(),
+            owner: block.account.clone(),
+            registered_at: record.registered_at,
+            expires_at: extend(record.expires_at.as_secs().max(now)),
+        }),
+        Some(record)
+            if now
+                < record
+                    .expires_at
+                    .as_secs()
+                    .saturating_add(params.alias_grace_secs) =>
+        {
+            Err(format!("alias {name:?} is registered to {}", record.owner))
+        }
+        _ => Ok(AliasRecord {
+            name: name.to_string(),
+            owner: block.account.clone(),
+            registered_at: block.timestamp,
+            expires_at: extend(now),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_ledger::{BlockType, CURRENT_BLOCK_VERSION};
+    use burst_types::{BlockHash, Signature, TxHash, WalletAddress};
+
+    fn params() -> ProtocolParams {
+        ProtocolParams {
+            alias_fee: 100,
+            alias_term_secs: 1000,
+            alias_grace_secs: 100,
+            ..Default::default()
+        }
+    }
+
+    fn alias_block(account: &str, name: &str, brn_after: u128, at: u64) -> StateBlock {
+        StateBlock {
+            version: CURRENT_BLOCK_VERSION,
+            block_type: BlockType::Alias,
+            account: WalletAddress::new(account),
+            previous: BlockHash::new([1u8; 32]),
+            representative: WalletAddress::new(account),
+            brn_balance: brn_after,
+            trst_balance: 0,
+            link: BlockHash::ZERO,
+            origin: TxHash::ZERO,
+            transaction: TxHash::ZERO,
+            timestamp: Timestamp::new(at),
+            params_hash: BlockHash::ZERO,
+            memo: Some(name.as_bytes().to_vec()),
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
+            work: 0,
+            signature: Signature([0u8; 64]),
+            hash: BlockHash::ZERO,
+        }
+    }
+
+    #[test]
+    fn first_registration_needs_the_exact_fee() {
+        let params = params();
+        let block = alias_block("brst_alice", "alice", 900, 50);
+        let record = validate_registration(&block, 1000, None, &params).unwrap();
+        assert_eq!(record.owner, block.account);
+        assert_eq!(record.registered_at, Timestamp::new(50));
+        assert_eq!(record.expires_at, Timestamp::new(1050));
+
+        let cheap = alias_block("brst_alice", "alice", 950, 50);
+        assert!(validate_registration(&cheap, 1000, None, &params).is_err());
+
+        let disabled = ProtocolParams {
+            alias_term_secs: 0,
+            ..params
+        };
+        assert!(validate_registration(&block, 1000, None, &disabled).is_err());
+    }
+
+    #[test]
+    fn owner_renews_and_others_wait_out_the_grace_period() {
+        let params = params();
+        let record = validate_registration(
+            &alias_block("brst_alice", "alice", 900, 0),
+            1000,
+            None,
+            &params,
+        )
+        .unwrap();
+
+        // Renewing early extends from the current expiry.
+        let renewed = validate_registration(
+            &alias_block("brst_alice", "alice", 800, 500),
+            900,
+            Some(&record),
+            &params,
+        )
+        .unwrap();
+        assert_eq!(renewed.registered_at, Timestamp::new(0));
+        assert_eq!(renewed.expires_at, Timestamp::new(2000));
+
+        // Someone else is refused until term and grace have both passed.
+        let taken = alias_block("brst_bob", "alice", 900, 1050);
+        assert!(validate_registration(&taken, 1000, Some(&record), &params).is_err());
+        let lapsed = alias_block("brst_bob", "alice", 900, 1100);
+        let record = validate_registration(&lapsed, 1000, Some(&record), &params).unwrap();
+        assert_eq!(record.owner, WalletAddress::new("brst_bob"));
+        assert_eq!(record.expires_at, Timestamp::new(2100));
+    }
+}
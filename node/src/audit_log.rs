@@ -0,0 +1,121 @@
+//! Audit log of privileged actions.
+//!
+//! Every entry goes to the `audit_log` LMDB table, which numbers it and
+//! serves the `audit_log` RPC, and as one JSON line to [`AUDIT_LOG_FILE`] in
+//! the data directory, for operators who ship logs to external storage.
+//! Both are append-only. Which RPC actions are recorded is decided by the
+//! RPC server.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use burst_rpc::{AdminFuture, AuditEntry, AuditLog};
+use burst_store::audit::AuditStore;
+use burst_store_lmdb::LmdbStore;
+use tokio::sync::Mutex;
+
+/// File name of the audit log inside the data directory.
+pub const AUDIT_LOG_FILE: &str = "audit.log";
+
+pub struct NodeAuditLog {
+    store: Arc<LmdbStore>,
+    file: Mutex<File>,
+}
+
+impl NodeAuditLog {
+    /// Open the audit log, appending to the file at `path`.
+    pub fn open(store: Arc<LmdbStore>, path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            store,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Record `entry` in both logs and return its sequence number.
+    pub async fn append(&self, mut entry: AuditEntry) -> Result<u64, String> {
+        let bytes = serde_json::to_vec(&entry).map_err(|e| e.to_string())?;
+        // Hold the file across both writes so the file keeps LMDB order.
+        let mut file = self.file.lock().await;
+        entry.seq = self
+            .store
+            .audit_store()
+            .append_audit_entry(&bytes)
+            .map_err(|e| e.to_string())?;
+        let mut line = serde_json::to_vec(&entry).map_err(|e| e.to_string())?;
+        line.push(b'\n');
+        file.write_all(&line)
+            .and_then(|()| file.sync_data())
+            .map_err(|e| format!("failed to write {AUDIT_LOG_FILE}: {e}"))?;
+        Ok(entry.seq)
+    }
+
+    /// Up to `limit` entries numbered below `before`, newest first.
+    pub fn entries(&self, before: Option<u64>, limit: usize) -> Result<Vec<AuditEntry>, String> {
+        self.store
+            .audit_store()
+            .audit_entries(before, limit)
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .map(|(seq, bytes)| {
+                let entry: AuditEntry =
+                    serde_json::from_slice(&bytes).map_err(|e| e.to_string())?;
+                Ok(AuditEntry { seq, ..entry })
+            })
+            .collect()
+    }
+}
+
+impl AuditLog for NodeAuditLog {
+    fn record(&self, entry: AuditEntry) -> AdminFuture<'_, u64> {
+        Box::pin(self.append(entry))
+    }
+
+    fn entries(&self, before: Option<u64>, limit: usize) -> AdminFuture<'_, Vec<AuditEntry>> {
+        Box::pin(async move { NodeAuditLog::entries(self, before, limit) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(action: &str) -> AuditEntry {
+        AuditEntry {
+            seq: 0,
+            timestamp: 1_700_000_000,
+            caller: "127.0.0.1".into(),
+            action: action.into(),
+            params: serde_json::json!({ "address": "203.0.113.0/24" }),
+            error: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn entries_reach_the_table_and_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(LmdbStore::open(dir.path(), 46, 1 << 22).unwrap());
+        let path = dir.path().join(AUDIT_LOG_FILE);
+        let log = NodeAuditLog::open(store, &path).unwrap();
+
+        assert_eq!(log.append(entry("peer_ban")).await.unwrap(), 1);
+        assert_eq!(log.append(entry("peer_unban")).await.unwrap(), 2);
+
+        let entries = log.entries(None, 10).unwrap();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|e| (e.seq, e.action.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(2, "peer_unban"), (1, "peer_ban")]
+        );
+        let lines: Vec<AuditEntry> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines, entries.into_iter().rev().collect::<Vec<_>>());
+    }
+}
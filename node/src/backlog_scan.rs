@@ -0,0 +1,228 @@
+//! Backlog scan — re-schedules unconfirmed blocks whose elections lapsed.
+//!
+//! A block gets one shot at an election when it is processed. If that
+//! election expires without quorum (or never starts because the container
+//! was full), the block stays unconfirmed forever unless something schedules
+//! it again. The scan walks the account table a page at a time, picks the
+//! first uncemented block of every account whose confirmation height trails
+//! its chain, and hands it back to the priority scheduler. Blocks younger
+//! than the election lifetime are skipped; they may still be in their first
+//! election.
+
+use burst_ledger::StateBlock;
+use burst_store::account::AccountStore;
+use burst_store::block::BlockStore;
+use burst_store::StoreError;
+use burst_types::{BlockHash, WalletAddress};
+
+/// Accounts examined per pass.
+pub const DEFAULT_ACCOUNTS_PER_PASS: usize = 1_024;
+
+/// Blocks handed to the scheduler per pass.
+pub const DEFAULT_MAX_SCHEDULED_PER_PASS: usize = 128;
+
+/// An uncemented block that should get another election.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BacklogCandidate {
+    pub hash: BlockHash,
+    pub account: WalletAddress,
+    /// Account TRST balance, saturated to `u64`, for scheduler bucketing.
+    pub balance: u64,
+}
+
+/// Resumable, rate-bounded walk over accounts with uncemented blocks.
+pub struct BacklogScan {
+    /// Last account examined; the next pass starts after it.
+    cursor: Option<WalletAddress>,
+    accounts_per_pass: usize,
+    max_per_pass: usize,
+    /// Blocks younger than this are left to their first election.
+    min_age_secs: u64,
+}
+
+impl BacklogScan {
+    pub fn new(accounts_per_pass: usize, max_per_pass: usize, min_age_secs: u64) -> Self {
+        Self {
+            cursor: None,
+            accounts_per_pass: accounts_per_pass.max(1),
+            max_per_pass,
+            min_age_secs,
+        }
+    }
+
+    /// Examine the next page of accounts and return up to `max_per_pass`
+    /// blocks to re-schedule. `skip` filters out blocks that already have an
+    /// election or were just confirmed. Wraps around to the first account
+    /// after reaching the end of the table.
+    pub fn next_pass(
+        &mut self,
+        accounts: &dyn AccountStore,
+        blocks: &dyn BlockStore,
+        now_secs: u64,
+        skip: impl Fn(&BlockHash) -> bool,
+    ) -> Result<Vec<BacklogCandidate>, StoreError> {
+        let page = accounts.iter_accounts_paged(self.cursor.as_ref(), self.accounts_per_pass)?;
+
+        let mut candidates = Vec::new();
+        for info in &page {
+            if candidates.len() >= self.max_per_pass {
+                // Resume from here on the next pass.
+                return Ok(candidates);
+            }
+            self.cursor = Some(info.address.clone());
+            if info.confirmation_height >= info.block_count {
+                continue;
+            }
+            let Some(hash) = blocks.block_at_height(&info.address, info.confirmation_height + 1)?
+            else {
+                continue;
+            };
+            if skip(&hash) || !self.is_old_enough(blocks, &hash, now_secs) {
+                continue;
+            }
+            candidates.push(BacklogCandidate {
+                hash,
+                account: info.address.clone(),
+                balance: info.trst_balance.min(u64::MAX as u128) as u64,
+            });
+        }
+        // A short page means the end of the table: start over next time.
+        if page.len() < self.accounts_per_pass {
+            self.cursor = None;
+        }
+        Ok(candidates)
+    }
+
+    fn is_old_enough(&self, blocks: &dyn BlockStore, hash: &BlockHash, now_secs: u64) -> bool {
+        blocks
+            .get_block(hash)
+            .ok()
+            .and_then(|bytes| bincode::deserialize::<StateBlock>(&bytes).ok())
+            .is_some_and(|block| {
+                block.timestamp.as_secs().saturating_add(self.min_age_secs) <= now_secs
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_ledger::{BlockType, CURRENT_BLOCK_VERSION};
+    use burst_store::account::AccountInfo;
+    use burst_types::{Signature, Timestamp, TxHash, WalletState};
+
+    fn block(account: &WalletAddress, previous: BlockHash, timestamp: u64) -> StateBlock {
+        let mut block = StateBlock {
+            version: CURRENT_BLOCK_VERSION,
+            block_type: BlockType::Open,
+            account: account.clone(),
+            previous,
+            representative: account.clone(),
+            brn_balance: 0,
+            trst_balance: 0,
+            link: BlockHash::ZERO,
+            origin: TxHash::ZERO,
+            transaction: TxHash::ZERO,
+            timestamp: Timestamp::new(timestamp),
+            params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
+            work: 0,
+            signature: Signature([1u8; 64]),
+            hash: BlockHash::ZERO,
+        };
+        block.hash = block.compute_hash();
+        block
+    }
+
+    /// Store a chain of blocks with the given timestamps and cemented height.
+    fn put_chain(
+        env: &burst_store_lmdb::LmdbEnvironment,
+        name: &str,
+        timestamps: &[u64],
+        confirmation_height: u64,
+    ) -> Vec<BlockHash> {
+        let account = WalletAddress::new(format!("brst_{name}"));
+        let blocks = env.block_store();
+        let mut previous = BlockHash::ZERO;
+        let mut hashes = Vec::new();
+        for &ts in timestamps {
+            let b = block(&account, previous, ts);
+            blocks
+                .put_block_with_account(&b.hash, &bincode::serialize(&b).unwrap(), &account)
+                .unwrap();
+            previous = b.hash;
+            hashes.push(b.hash);
+        }
+        env.account_store()
+            .put_account(&AccountInfo {
+                address: account.clone(),
+                state: WalletState::Unverified,
+                verified_at: None,
+                head: previous,
+                block_count: timestamps.len() as u64,
+                confirmation_height,
+                representative: account,
+                total_brn_burned: 0,
+                total_brn_staked: 0,
+                trst_balance: 5,
+                expired_trst: 0,
+                revoked_trst: 0,
+                epoch: 0,
+            })
+            .unwrap();
+        hashes
+    }
+
+    #[test]
+    fn schedules_first_uncemented_block_once_old_enough() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = burst_store_lmdb::LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+        let stale = put_chain(&env, "a_stale", &[100, 200, 300], 1);
+        put_chain(&env, "b_cemented", &[100], 1);
+        put_chain(&env, "c_fresh", &[990], 0);
+        let skipped = put_chain(&env, "d_active", &[100], 0);
+
+        let mut scan = BacklogScan::new(10, 10, 30);
+        let candidates = scan
+            .next_pass(&env.account_store(), &env.block_store(), 1_000, |hash| {
+                *hash == skipped[0]
+            })
+            .unwrap();
+        assert_eq!(
+            candidates,
+            vec![BacklogCandidate {
+                hash: stale[1],
+                account: WalletAddress::new("brst_a_stale"),
+                balance: 5,
+            }]
+        );
+    }
+
+    #[test]
+    fn passes_are_bounded_and_resume_where_they_stopped() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = burst_store_lmdb::LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+        let heads: Vec<BlockHash> = ["a", "b", "c"]
+            .iter()
+            .map(|name| put_chain(&env, name, &[100], 0)[0])
+            .collect();
+
+        let mut scan = BacklogScan::new(10, 2, 0);
+        let mut pass = || {
+            scan.next_pass(&env.account_store(), &env.block_store(), 1_000, |_| false)
+                .unwrap()
+                .into_iter()
+                .map(|c| c.hash)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(pass(), vec![heads[0], heads[1]]);
+        assert_eq!(pass(), vec![heads[2]]);
+        // Wrapped around to the start.
+        assert_eq!(pass(), vec![heads[0], heads[1]]);
+    }
+}
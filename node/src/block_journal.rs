@@ -0,0 +1,244 @@
+//! Write-ahead journal of accepted blocks.
+//!
+//! Every block the processor accepts is appended here before its LMDB write
+//! batch is built, and settled once the batch commits. A node that dies
+//! between the two finds the block in the journal at the next start and
+//! queues it again. Replayed blocks stay journaled until they commit (or are
+//! turned away), so a second crash during replay loses nothing either; the
+//! file is truncated only when no journaled block is outstanding.
+//!
+//! Records are a 4-byte little-endian length followed by the bincode-encoded
+//! block; a record torn by the crash is cut off on open. Journaling is off
+//! unless enabled in the node configuration.
+
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use burst_ledger::StateBlock;
+use burst_types::{BlockHash, NetworkId};
+use serde::{Deserialize, Serialize};
+
+/// File name of the journal inside the data directory.
+pub const BLOCK_JOURNAL_FILE: &str = "block_journal";
+
+/// When journal appends are flushed to disk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalFsync {
+    /// `fsync` after every append; survives power loss.
+    Always,
+    /// Leave flushing to the OS; survives a process crash only.
+    Never,
+}
+
+impl JournalFsync {
+    /// Policy used when none is configured: dev networks trade durability
+    /// for speed, live and test networks do not.
+    pub fn default_for(network: NetworkId) -> Self {
+        match network {
+            NetworkId::Live | NetworkId::Test => Self::Always,
+            NetworkId::Dev => Self::Never,
+        }
+    }
+}
+
+/// Settings of the block journal.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JournalConfig {
+    /// Whether accepted blocks are journaled at all. Off by default.
+    pub enabled: bool,
+    /// Flush policy; the network's default when unset.
+    pub fsync: Option<JournalFsync>,
+}
+
+impl JournalConfig {
+    /// The flush policy in effect on `network`.
+    pub fn fsync_for(&self, network: NetworkId) -> JournalFsync {
+        self.fsync
+            .unwrap_or_else(|| JournalFsync::default_for(network))
+    }
+}
+
+/// Append-only journal of blocks accepted but not yet committed.
+pub struct BlockJournal {
+    file: File,
+    fsync: JournalFsync,
+    /// Journaled blocks neither committed nor turned away yet.
+    outstanding: HashSet<BlockHash>,
+}
+
+impl BlockJournal {
+    /// Open or create the journal at `path`. Returns it together with the
+    /// blocks left over from a previous run, oldest first; they stay
+    /// outstanding until [`settle`](Self::settle)d.
+    pub fn open(path: &Path, fsync: JournalFsync) -> io::Result<(Self, Vec<StateBlock>)> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)?;
+        let (recovered, valid_len) = parse_records(&contents);
+        if valid_len < contents.len() {
+            // Drop the torn tail so new records follow the last whole one.
+            file.set_len(valid_len as u64)?;
+        }
+        let outstanding = recovered.iter().map(|block| block.hash).collect();
+        Ok((
+            Self {
+                file,
+                fsync,
+                outstanding,
+            },
+            recovered,
+        ))
+    }
+
+    /// Record an accepted block ahead of its commit.
+    pub fn append(&mut self, block: &StateBlock) -> io::Result<()> {
+        let bytes = bincode::serialize(block).map_err(io::Error::other)?;
+        let mut record = Vec::with_capacity(4 + bytes.len());
+        record.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        record.extend_from_slice(&bytes);
+        self.file.write_all(&record)?;
+        if self.fsync == JournalFsync::Always {
+            self.file.sync_data()?;
+        }
+        self.outstanding.insert(block.hash);
+        Ok(())
+    }
+
+    /// Settle a journaled block, once committed or once processing turned it
+    /// away. The records are dropped when nothing is outstanding any more;
+    /// hashes that were never journaled are ignored.
+    pub fn settle(&mut self, hash: &BlockHash) -> io::Result<()> {
+        if self.outstanding.remove(hash) && self.outstanding.is_empty() {
+            self.file.set_len(0)?;
+        }
+        Ok(())
+    }
+
+    /// Number of journaled blocks not yet settled.
+    pub fn outstanding(&self) -> usize {
+        self.outstanding.len()
+    }
+}
+
+/// Decode records up to the first incomplete or undecodable one. Returns
+/// the blocks and the length of the well-formed prefix.
+fn parse_records(contents: &[u8]) -> (Vec<StateBlock>, usize) {
+    let mut blocks = Vec::new();
+    let mut offset = 0;
+    while let Some((len, rest)) = contents[offset..].split_first_chunk::<4>() {
+        let len = u32::from_le_bytes(*len) as usize;
+        let Some(record) = rest.get(..len) else {
+            break;
+        };
+        match bincode::deserialize::<StateBlock>(record) {
+            Ok(block) => blocks.push(block),
+            Err(_) => break,
+        }
+        offset += 4 + len;
+    }
+    (blocks, offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_ledger::{BlockType, CURRENT_BLOCK_VERSION};
+    use burst_types::{BlockHash, Signature, Timestamp, TxHash, WalletAddress};
+
+    fn block(seed: u8) -> StateBlock {
+        let mut block = StateBlock {
+            version: CURRENT_BLOCK_VERSION,
+            block_type: BlockType::Send,
+            account: WalletAddress::new("brst_alice"),
+            previous: BlockHash::new([seed; 32]),
+            representative: WalletAddress::new("brst_rep"),
+            brn_balance: 0,
+            trst_balance: 0,
+            link: BlockHash::ZERO,
+            origin: TxHash::ZERO,
+            transaction: TxHash::ZERO,
+            timestamp: Timestamp::new(1),
+            params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
+            work: 0,
+            signature: Signature([0; 64]),
+            hash: BlockHash::ZERO,
+        };
+        block.hash = block.compute_hash();
+        block
+    }
+
+    #[test]
+    fn uncommitted_blocks_survive_reopen_and_torn_tail_is_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(BLOCK_JOURNAL_FILE);
+
+        let (mut journal, recovered) = BlockJournal::open(&path, JournalFsync::Always).unwrap();
+        assert!(recovered.is_empty());
+        journal.append(&block(1)).unwrap();
+        journal.settle(&block(1).hash).unwrap();
+        journal.append(&block(2)).unwrap();
+        journal.append(&block(3)).unwrap();
+        drop(journal);
+
+        // A crash halfway through a third record.
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&[200, 0, 0, 0, 1, 2]).unwrap();
+        drop(file);
+
+        let (mut journal, recovered) = BlockJournal::open(&path, JournalFsync::Never).unwrap();
+        let hashes: Vec<_> = recovered.iter().map(|b| b.hash).collect();
+        assert_eq!(hashes, vec![block(2).hash, block(3).hash]);
+
+        // The torn tail was cut, so later records are readable.
+        journal.append(&block(4)).unwrap();
+        drop(journal);
+        let (_, recovered) = BlockJournal::open(&path, JournalFsync::Never).unwrap();
+        assert_eq!(recovered.len(), 3);
+    }
+
+    #[test]
+    fn replayed_blocks_stay_journaled_until_all_settle() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(BLOCK_JOURNAL_FILE);
+
+        let (mut journal, _) = BlockJournal::open(&path, JournalFsync::Never).unwrap();
+        journal.append(&block(1)).unwrap();
+        journal.append(&block(2)).unwrap();
+        drop(journal);
+
+        // Restart: both are replayed; a new block commits before either.
+        let (mut journal, recovered) = BlockJournal::open(&path, JournalFsync::Never).unwrap();
+        assert_eq!(recovered.len(), 2);
+        assert_eq!(journal.outstanding(), 2);
+        journal.append(&block(3)).unwrap();
+        journal.settle(&block(3).hash).unwrap();
+        journal.settle(&block(1).hash).unwrap();
+        // Unknown hashes settle nothing.
+        journal.settle(&block(9).hash).unwrap();
+        drop(journal);
+
+        // A crash now still finds the block that never committed.
+        let (mut journal, recovered) = BlockJournal::open(&path, JournalFsync::Never).unwrap();
+        assert!(recovered.iter().any(|b| b.hash == block(2).hash));
+        for b in &recovered {
+            journal.settle(&b.hash).unwrap();
+        }
+        drop(journal);
+        let (_, recovered) = BlockJournal::open(&path, JournalFsync::Never).unwrap();
+        assert!(recovered.is_empty());
+    }
+}
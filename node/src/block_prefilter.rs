@@ -0,0 +1,261 @@
+//! Cheap block checks applied at the network edge.
+//!
+//! Blocks gossiped by peers are checked here in the read loop, before they
+//! are queued for the block processor: the hash must match the contents,
+//! the block must be signed and carry enough proof-of-work, its timestamp
+//! must not be in the future (nor, for an HTLC claim, too far in the
+//! past), its optional payloads must be well formed, and it must not be a
+//! block we already queued recently. Failures are charged to the sending
+//! peer, so obvious spam costs the sender its reputation instead of costing
+//! us block-processor capacity.
+
+use std::collections::{HashSet, VecDeque};
+
+use burst_ledger::StateBlock;
+use burst_network::PenaltyReason;
+use burst_types::{BlockHash, Signature};
+use burst_work::WorkThresholds;
+
+use crate::block_processor::validate_live_timestamp;
+
+/// Recently queued block hashes remembered for duplicate suppression.
+pub const DEFAULT_PREFILTER_CAPACITY: usize = 65_536;
+
+/// Accepted clock skew for block timestamps (matches the block processor).
+const MAX_FUTURE_SECS: u64 = 60;
+
+/// Why a block was dropped at the edge.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PrefilterReject {
+    /// The block hash does not match its contents.
+    HashMismatch,
+    /// The signature is all zeroes.
+    Unsigned,
+    /// The proof-of-work is below the threshold for the block type.
+    InsufficientWork,
+    /// The timestamp is too far in the future.
+    FutureTimestamp,
+    /// An HTLC claim is stamped too far in the past.
+    StaleTimestamp,
+    /// An optional payload is malformed or on the wrong block type.
+    Malformed(String),
+    /// The block was already queued recently.
+    Duplicate,
+}
+
+impl PrefilterReject {
+    /// Short name, used as the metrics label.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrefilterReject::HashMismatch => "hash_mismatch",
+            PrefilterReject::Unsigned => "unsigned",
+            PrefilterReject::InsufficientWork => "insufficient_work",
+            PrefilterReject::FutureTimestamp => "future_timestamp",
+            PrefilterReject::StaleTimestamp => "stale_timestamp",
+            PrefilterReject::Malformed(_) => "malformed",
+            PrefilterReject::Duplicate => "duplicate",
+        }
+    }
+
+    /// Penalty charged to the peer that sent the block. Duplicates are
+    /// normal flooding behaviour and cost nothing, and a stale claim may
+    /// just have been relayed slowly.
+    pub fn penalty(&self) -> Option<PenaltyReason> {
+        match self {
+            PrefilterReject::HashMismatch
+            | PrefilterReject::Unsigned
+            | PrefilterReject::Malformed(_) => Some(PenaltyReason::InvalidBlock),
+            PrefilterReject::InsufficientWork | PrefilterReject::FutureTimestamp => {
+                Some(PenaltyReason::Spam)
+            }
+            PrefilterReject::StaleTimestamp | PrefilterReject::Duplicate => None,
+        }
+    }
+}
+
+/// Stateless block checks plus a bounded set of recently queued hashes.
+pub struct BlockPrefilter {
+    work_thresholds: WorkThresholds,
+    /// Extra work demanded under network load or while the node is
+    /// overloaded; 1.0 otherwise.
+    work_multiplier: f64,
+    capacity: usize,
+    seen: HashSet<BlockHash>,
+    seen_order: VecDeque<BlockHash>,
+}
+
+impl BlockPrefilter {
+    pub fn new(work_thresholds: WorkThresholds, capacity: usize) -> Self {
+        Self {
+            work_thresholds,
+            work_multiplier: 1.0,
+            capacity,
+            seen: HashSet::new(),
+            seen_order: VecDeque::new(),
+        }
+    }
+
+    /// Replace the work thresholds (after a governance change).
+    pub fn set_work_thresholds(&mut self, work_thresholds: WorkThresholds) {
+        self.work_thresholds = work_thresholds;
+    }
+
+    /// Scale the work thresholds by `multiplier` (network load times the
+    /// overload factor).
+    pub fn set_work_multiplier(&mut self, multiplier: f64) {
+        self.work_multiplier = multiplier;
+    }
+
+    /// Run the edge checks, cheapest first.
+    pub fn check(&self, block: &StateBlock, now_secs: u64) -> Result<(), PrefilterReject> {
+        if self.seen.contains(&block.hash) {
+            return Err(PrefilterReject::Duplicate);
+        }
+        if block.timestamp.as_secs() > now_secs + MAX_FUTURE_SECS {
+            return Err(PrefilterReject::FutureTimestamp);
+        }
+        if validate_live_timestamp(block, now_secs).is_err() {
+            return Err(PrefilterReject::StaleTimestamp);
+        }
+        if block.signature == Signature([0u8; 64]) {
+            return Err(PrefilterReject::Unsigned);
+        }
+        block
+            .validate_memo()
+            .and_then(|_| block.validate_htlc())
+            .and_then(|_| block.validate_reject_reason())
+            .and_then(|_| block.validate_recovery())
+            .and_then(|_| block.validate_delegation_key())
+            .and_then(|_| block.validate_extensions())
+            .map_err(PrefilterReject::Malformed)?;
+        if block.compute_hash() != block.hash {
+            return Err(PrefilterReject::HashMismatch);
+        }
+        let threshold = self
+            .work_thresholds
+            .scaled(self.work_multiplier)
+            .threshold_for(block.block_type.work_kind());
+        if !block.verify_work(threshold) {
+            return Err(PrefilterReject::InsufficientWork);
+        }
+        Ok(())
+    }
+
+    /// Remember a block that was queued, so copies from other peers are
+    /// dropped at the edge. Only call this once the block is actually
+    /// queued; a block dropped for lack of queue space may be re-sent.
+    pub fn mark_queued(&mut self, hash: BlockHash) {
+        if !self.seen.insert(hash) {
+            return;
+        }
+        self.seen_order.push_back(hash);
+        if self.seen_order.len() > self.capacity {
+            if let Some(oldest) = self.seen_order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_ledger::{BlockType, CURRENT_BLOCK_VERSION};
+    use burst_types::{Timestamp, TxHash, WalletAddress};
+
+    fn send_block() -> StateBlock {
+        let mut block = StateBlock {
+            version: CURRENT_BLOCK_VERSION,
+            block_type: BlockType::Send,
+            account: WalletAddress::new(
+                "brst_1111111111111111111111111111111111111111111111111111111111111111111",
+            ),
+            previous: BlockHash::new([7u8; 32]),
+            representative: WalletAddress::new(
+                "brst_2222222222222222222222222222222222222222222222222222222222222222222",
+            ),
+            brn_balance: 1000,
+            trst_balance: 0,
+            link: BlockHash::ZERO,
+            origin: TxHash::ZERO,
+            transaction: TxHash::ZERO,
+            timestamp: Timestamp::new(1_000_000),
+            params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
+            work: 0,
+            signature: Signature([1u8; 64]),
+            hash: BlockHash::ZERO,
+        };
+        block.hash = block.compute_hash();
+        block
+    }
+
+    #[test]
+    fn rejects_cheaply_detectable_spam() {
+        let filter = BlockPrefilter::new(WorkThresholds::with_base(0), 16);
+        let now = 1_000_000;
+        assert_eq!(filter.check(&send_block(), now), Ok(()));
+
+        let mut tampered = send_block();
+        tampered.brn_balance += 1;
+        assert_eq!(
+            filter.check(&tampered, now),
+            Err(PrefilterReject::HashMismatch)
+        );
+
+        let mut unsigned = send_block();
+        unsigned.signature = Signature([0u8; 64]);
+        assert_eq!(filter.check(&unsigned, now), Err(PrefilterReject::Unsigned));
+
+        let mut future = send_block();
+        future.timestamp = Timestamp::new(now + MAX_FUTURE_SECS + 1);
+        future.hash = future.compute_hash();
+        assert_eq!(
+            filter.check(&future, now),
+            Err(PrefilterReject::FutureTimestamp)
+        );
+
+        let mut stale_claim = send_block();
+        stale_claim.block_type = BlockType::HtlcClaim;
+        stale_claim.htlc = Some(burst_transactions::htlc::HtlcData::Claim {
+            preimage: [7u8; 32],
+        });
+        stale_claim.hash = stale_claim.compute_hash();
+        assert_eq!(filter.check(&stale_claim, now), Ok(()));
+        let rejected = filter.check(&stale_claim, now + 3600).unwrap_err();
+        assert_eq!(rejected, PrefilterReject::StaleTimestamp);
+        assert_eq!(rejected.penalty(), None);
+
+        let mut misplaced = send_block();
+        misplaced.reject_reason =
+            Some(burst_transactions::reject_receive::RejectReason::UnknownSender);
+        misplaced.hash = misplaced.compute_hash();
+        let rejected = filter.check(&misplaced, now).unwrap_err();
+        assert!(matches!(rejected, PrefilterReject::Malformed(_)));
+        assert_eq!(rejected.penalty(), Some(PenaltyReason::InvalidBlock));
+
+        let strict = BlockPrefilter::new(WorkThresholds::with_base(u64::MAX), 16);
+        assert_eq!(
+            strict.check(&send_block(), now),
+            Err(PrefilterReject::InsufficientWork)
+        );
+    }
+
+    #[test]
+    fn duplicates_are_dropped_without_penalty_and_forgotten_when_full() {
+        let mut filter = BlockPrefilter::new(WorkThresholds::with_base(0), 1);
+        let block = send_block();
+        filter.mark_queued(block.hash);
+        let rejected = filter.check(&block, 1_000_000).unwrap_err();
+        assert_eq!(rejected, PrefilterReject::Duplicate);
+        assert_eq!(rejected.penalty(), None);
+
+        filter.mark_queued(BlockHash::new([9u8; 32]));
+        assert_eq!(filter.check(&block, 1_000_000), Ok(()));
+    }
+}
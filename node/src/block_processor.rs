@@ -4,13 +4,24 @@
 //! gap detection, fork detection, and finally ledger application. Inspired by the
 //! rsnano-node block processor architecture.
 
+use crate::limits::{check_bootstrap_limits, BootstrapLimits, LimitedAction};
 use crate::unchecked::UncheckedMap;
-use burst_crypto::{decode_address, verify_signature};
-use burst_ledger::{BlockType, DagFrontier, StateBlock};
+use burst_crypto::{decode_address, derive_address, verify_signature};
+use burst_ledger::{BlockType, DagFrontier, StateBlock, MAX_SUPPORTED_BLOCK_VERSION};
+use burst_store::account::AccountStore;
 use burst_store::block::BlockStore;
 use burst_store::delegation::DelegationStore;
-use burst_types::{BlockHash, PublicKey, Signature, Timestamp, WalletAddress};
-use burst_work::{WorkBlockKind, WorkThresholds};
+use burst_store::pending::PendingInfo;
+use burst_store::recovery::RecoveryStore;
+use burst_store::StoreError;
+use burst_transactions::htlc::{self, HtlcData};
+use burst_transactions::recovery::{self, RecoveryData};
+use burst_types::{
+    BlockHash, ContainerUsage, MemoryAccounting, NetworkId, PublicKey, Signature, Timestamp,
+    TxHash, WalletAddress,
+};
+use burst_utils::{DetailType, StatType, Stats};
+use burst_work::WorkThresholds;
 use std::collections::{HashSet, VecDeque};
 use std::sync::Arc;
 
@@ -116,7 +127,10 @@ impl ProcessingQueue {
 fn is_delegation_allowed(block_type: &BlockType) -> bool {
     matches!(
         block_type,
-        BlockType::GovernanceVote | BlockType::ChangeRepresentative
+        BlockType::GovernanceVote
+            | BlockType::GovernanceCommit
+            | BlockType::GovernanceReveal
+            | BlockType::ChangeRepresentative
     )
 }
 
@@ -142,6 +156,51 @@ fn extract_signing_pubkey(signature: &Signature, _hash: &[u8; 32]) -> Option<[u8
 /// Maximum number of recently processed hashes to keep in the dedup cache.
 const MAX_RECENTLY_PROCESSED: usize = 65_536;
 
+/// How far a transaction's timestamp may be from the clock of the node it
+/// is submitted to.
+pub const TX_TIME_TOLERANCE_SECS: u64 = 300;
+
+/// Check a block submitted live — over RPC or gossip — against the local
+/// clock: an HTLC claim may not be stamped more than
+/// [`TX_TIME_TOLERANCE_SECS`] in the past, so it cannot be backdated under
+/// its lock's refund timeout. Blocks synced during bootstrap are not held
+/// to this.
+pub fn validate_live_timestamp(block: &StateBlock, now_secs: u64) -> Result<(), String> {
+    if block.block_type == BlockType::HtlcClaim
+        && block.timestamp.as_secs() + TX_TIME_TOLERANCE_SECS < now_secs
+    {
+        return Err(format!(
+            "HTLC claim stamped {} is too far behind the clock",
+            block.timestamp.as_secs()
+        ));
+    }
+    Ok(())
+}
+
+/// Height at which `transaction` was already applied on `account`'s chain,
+/// for a block carrying it stamped at `at`. Both blocks were stamped within
+/// the tolerance of the transaction's own timestamp, so only the blocks
+/// stamped within twice the tolerance before `at` are searched.
+pub fn applied_height<A, B>(
+    accounts: &A,
+    blocks: &B,
+    account: &WalletAddress,
+    transaction: &TxHash,
+    at: Timestamp,
+) -> Result<Option<u64>, StoreError>
+where
+    A: AccountStore + ?Sized,
+    B: BlockStore + ?Sized,
+{
+    let info = match accounts.get_account(account) {
+        Ok(info) => info,
+        Err(StoreError::NotFound(_)) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let since = Timestamp::new(at.as_secs().saturating_sub(2 * TX_TIME_TOLERANCE_SECS));
+    burst_ledger::transaction_height_since(blocks, &info, transaction, since)
+}
+
 /// Multi-stage block processing pipeline.
 ///
 /// Processes blocks synchronously through validation stages. The node calls this
@@ -153,12 +212,20 @@ pub struct BlockProcessor {
     min_work_difficulty: u64,
     /// Per-block-type PoW thresholds.
     work_thresholds: WorkThresholds,
+    /// Network load multiplier on `work_thresholds`; 1 when throughput is
+    /// under target.
+    work_multiplier: u64,
     /// Hashes of recently processed blocks (dedup cache).
     recently_processed: HashSet<BlockHash>,
     /// FIFO eviction order for bounded dedup cache.
     recently_processed_order: VecDeque<BlockHash>,
-    /// The genesis account — only this account may sign epoch blocks.
+    /// The genesis account — signs epoch and governance activation blocks.
     genesis_account: WalletAddress,
+    /// Additional account allowed to sign epoch blocks.
+    upgrade_key: Option<WalletAddress>,
+    /// The network this node runs on. Network-bound blocks for any other
+    /// network are rejected; unset accepts any.
+    network: Option<NetworkId>,
     /// Whether to verify Ed25519 signatures. Disabled in tests with synthetic addresses.
     verify_signatures: bool,
     /// Whether to validate block timestamps against wall-clock time.
@@ -166,21 +233,38 @@ pub struct BlockProcessor {
     validate_timestamps: bool,
     /// Optional delegation store for verifying delegation key signatures.
     pub delegation_store: Option<Arc<dyn DelegationStore + Send + Sync>>,
-    /// Optional persistent block store for dedup fallback after cache eviction.
+    /// Custodian sets and rotated signing keys. Recovery blocks are rejected
+    /// when unset.
+    pub recovery_store: Option<Arc<dyn RecoveryStore + Send + Sync>>,
+    /// Persistent block store for dedup fallback after cache eviction and
+    /// for block heights. Blocks carrying a sequence are rejected when unset.
     pub block_store: Option<Arc<dyn BlockStore + Send + Sync>>,
+    /// Verified-wallet count for the bootstrap-phase caps, and account
+    /// chains for the replay check; neither is enforced when unset.
+    pub account_store: Option<Arc<dyn AccountStore + Send + Sync>>,
+    /// Bootstrap-phase caps in force. Updated after GovernanceActivation blocks.
+    bootstrap_limits: BootstrapLimits,
     /// Current protocol params hash. Updated after GovernanceActivation blocks.
     /// When set, blocks with a non-zero params_hash that doesn't match are
     /// logged as warnings (soft validation during bootstrap grace period).
     current_params_hash: BlockHash,
+    /// When `current_params_hash` took effect, if it came from an activation.
+    /// Blocks timestamped at or after it must carry the current hash.
+    params_activated_at: Option<Timestamp>,
+    /// Per-outcome block counters.
+    stats: Arc<Stats>,
 }
 
-/// Map a ledger `BlockType` to the work-crate's `WorkBlockKind`.
-fn block_type_to_work_kind(bt: &BlockType) -> WorkBlockKind {
-    match bt {
-        BlockType::Receive | BlockType::Open => WorkBlockKind::ReceiveOrOpen,
-        BlockType::Epoch | BlockType::GovernanceActivation => WorkBlockKind::Epoch,
-        _ => WorkBlockKind::Base,
+/// Check that a settling block credits exactly the pending amount.
+fn check_settled_amount(block: &StateBlock, prev_trst: u128, amount: u128) -> Result<(), String> {
+    let credited = block.trst_balance.saturating_sub(prev_trst);
+    if credited != amount {
+        return Err(format!(
+            "{:?} credits {credited} TRST but {amount} is locked",
+            block.block_type
+        ));
     }
+    Ok(())
 }
 
 impl BlockProcessor {
@@ -202,27 +286,74 @@ impl BlockProcessor {
             unchecked: UncheckedMap::new(max_unchecked),
             min_work_difficulty,
             work_thresholds: WorkThresholds::with_base(min_work_difficulty),
+            work_multiplier: 1,
             recently_processed: HashSet::with_capacity(MAX_RECENTLY_PROCESSED),
             recently_processed_order: VecDeque::with_capacity(MAX_RECENTLY_PROCESSED),
             genesis_account,
+            upgrade_key: None,
+            network: None,
             verify_signatures: true,
             validate_timestamps: true,
             delegation_store: None,
+            recovery_store: None,
             block_store: None,
+            account_store: None,
+            bootstrap_limits: BootstrapLimits::default(),
             current_params_hash: BlockHash::ZERO,
+            params_activated_at: None,
+            stats: Arc::default(),
         }
     }
 
+    /// Report into a shared stats registry.
+    pub fn set_stats(&mut self, stats: Arc<Stats>) {
+        self.stats = stats;
+    }
+
     /// Set the current protocol params hash for validation.
     pub fn set_params_hash(&mut self, hash: BlockHash) {
         self.current_params_hash = hash;
     }
 
+    /// Switch to the parameters activated at `activated_at`. From then on,
+    /// blocks stamped with any other params hash are rejected.
+    pub fn apply_params_activation(&mut self, hash: BlockHash, activated_at: Timestamp) {
+        self.current_params_hash = hash;
+        self.params_activated_at = Some(activated_at);
+    }
+
     /// Get the current protocol params hash.
     pub fn params_hash(&self) -> BlockHash {
         self.current_params_hash
     }
 
+    /// Set the bootstrap-phase caps in force.
+    pub fn set_bootstrap_limits(&mut self, limits: BootstrapLimits) {
+        self.bootstrap_limits = limits;
+    }
+
+    /// Allow `account` to sign epoch blocks alongside the genesis account.
+    pub fn set_upgrade_key(&mut self, account: WalletAddress) {
+        self.upgrade_key = Some(account);
+    }
+
+    /// Only accept network-bound blocks for `network`.
+    pub fn set_network(&mut self, network: NetworkId) {
+        self.network = Some(network);
+    }
+
+    /// Whether an epoch block carries a valid signature from the upgrade key.
+    fn signed_by_upgrade_key(&self, block: &StateBlock) -> bool {
+        block.block_type == BlockType::Epoch
+            && self
+                .upgrade_key
+                .as_ref()
+                .and_then(|key| decode_address(key.as_str()))
+                .is_some_and(|key| {
+                    verify_signature(block.hash.as_bytes(), &block.signature, &PublicKey(key))
+                })
+    }
+
     /// Disable Ed25519 signature verification (for testing with synthetic addresses).
     pub fn set_verify_signatures(&mut self, verify: bool) {
         self.verify_signatures = verify;
@@ -233,6 +364,83 @@ impl BlockProcessor {
         self.validate_timestamps = validate;
     }
 
+    /// The key a block must be signed with: the genesis key for epoch and
+    /// activation blocks, the new key for a recovery, the rotated key of a
+    /// recovered account, and otherwise the key encoded in the address.
+    fn signing_key(&self, block: &StateBlock) -> Result<PublicKey, String> {
+        if let Some(RecoveryData::Rotate { new_key, .. }) = &block.recovery {
+            return Ok(new_key.clone());
+        }
+        let signer = if matches!(
+            block.block_type,
+            BlockType::Epoch | BlockType::GovernanceActivation
+        ) {
+            &self.genesis_account
+        } else {
+            if let Some(ref store) = self.recovery_store {
+                match store.get_signing_key(&block.account) {
+                    Ok(Some(key)) => return Ok(key),
+                    Ok(None) => {}
+                    Err(e) => return Err(format!("unable to look up signing key: {e}")),
+                }
+            }
+            &block.account
+        };
+        decode_address(signer.as_str())
+            .map(PublicKey)
+            .ok_or_else(|| "unable to decode account address for signature verification".into())
+    }
+
+    /// Check a Recovery block against the account's custodian set: enough
+    /// distinct custodians approved rotating to the new key from this
+    /// block's `previous`, and the delay since the request has passed by the
+    /// block's timestamp.
+    fn validate_recovery_rotation(&self, block: &StateBlock) -> Result<(), String> {
+        let Some(RecoveryData::Rotate {
+            new_key,
+            requested_at,
+            approvals,
+        }) = &block.recovery
+        else {
+            return Err("recovery block must carry a key rotation".into());
+        };
+        let Some(ref store) = self.recovery_store else {
+            return Err("recovery is not enabled on this node".into());
+        };
+        let config = store
+            .get_recovery_config(&block.account)
+            .map_err(|e| format!("unable to look up recovery custodians: {e}"))?
+            .ok_or("account has no recovery custodians")?;
+        let digest =
+            recovery::recovery_digest(&block.account, new_key, &block.previous, *requested_at);
+        recovery::verify_approvals(&config.custodians, config.threshold, &digest, approvals)
+            .map_err(|e| e.to_string())?;
+        recovery::validate_delay(*requested_at, config.delay_secs, block.timestamp)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Check a RotateDelegationKey block against the delegation store: the
+    /// account must have an active delegation to the delegate named in the
+    /// link. Without a delegation store there is nothing to check against.
+    fn validate_delegation_rotation(&self, block: &StateBlock) -> Result<(), String> {
+        let Some(ref store) = self.delegation_store else {
+            return Ok(());
+        };
+        let record = store
+            .get_delegation_by_delegator(&block.account)
+            .map_err(|e| format!("unable to look up delegation: {e}"))?
+            .filter(|record| !record.revoked)
+            .ok_or("account has no active delegation to rotate")?;
+        let delegate = derive_address(&PublicKey(*block.link.as_bytes()));
+        if delegate != record.delegate {
+            return Err(format!(
+                "delegation key rotation names {delegate}, but the active delegate is {}",
+                record.delegate
+            ));
+        }
+        Ok(())
+    }
+
     /// Insert a hash into the bounded dedup cache, evicting the oldest if full.
     fn mark_processed(&mut self, hash: BlockHash) {
         if self.recently_processed.contains(&hash) {
@@ -258,6 +466,21 @@ impl BlockProcessor {
     /// 6. **Open block** — validate first-block-in-chain semantics
     /// 7. **Chain append** — accept if block extends the frontier
     pub fn process(&mut self, block: &StateBlock, frontier: &mut DagFrontier) -> ProcessResult {
+        let result = self.run_stages(block, frontier);
+        let detail = match result {
+            ProcessResult::Accepted => DetailType::Accepted,
+            ProcessResult::Gap => DetailType::Gap,
+            ProcessResult::GapSource => DetailType::GapSource,
+            ProcessResult::Fork => DetailType::Fork,
+            ProcessResult::Rejected(_) => DetailType::Rejected,
+            ProcessResult::Duplicate => DetailType::Duplicate,
+            ProcessResult::Queued => DetailType::Queued,
+        };
+        self.stats.inc(StatType::BlockProcessor, detail);
+        result
+    }
+
+    fn run_stages(&mut self, block: &StateBlock, frontier: &mut DagFrontier) -> ProcessResult {
         // Stage 1: Dedup check (in-memory cache + persistent store fallback)
         if self.recently_processed.contains(&block.hash) {
             return ProcessResult::Duplicate;
@@ -269,10 +492,12 @@ impl BlockProcessor {
             }
         }
 
-        // Stage 2: PoW validation — threshold varies by block type
+        // Stage 2: PoW validation — threshold varies by block type and is
+        // scaled by the network load multiplier, as at the edge
         let work_threshold = self
             .work_thresholds
-            .threshold_for(block_type_to_work_kind(&block.block_type));
+            .scaled(self.work_multiplier as f64)
+            .threshold_for(block.block_type.work_kind());
         if !block.verify_work(work_threshold) {
             return ProcessResult::Rejected(
                 "proof-of-work does not meet minimum difficulty".into(),
@@ -293,31 +518,71 @@ impl BlockProcessor {
             }
         }
 
+        // Stage 2.55: Block versions beyond what this node can validate are
+        // rejected outright; per-account gating happens at the node layer.
+        if block.version > MAX_SUPPORTED_BLOCK_VERSION {
+            return ProcessResult::Rejected(format!(
+                "block version {} is not supported",
+                block.version
+            ));
+        }
+
+        // Stage 2.56: A network-bound block must belong to this network —
+        // blocks and signatures from other networks are never replayed here.
+        // Whether an unbound block is still allowed is up to the epoch rules.
+        if let Some(network) = self.network {
+            if let Err(reason) = block.validate_network(network) {
+                return ProcessResult::Rejected(reason);
+            }
+        }
+
+        // Stage 2.6: Memo validation — only Send blocks may carry a memo,
+        // and it must fit within the fixed reference size. Alias blocks
+        // carry a well-formed alias name there instead.
+        if let Err(reason) = block.validate_memo() {
+            return ProcessResult::Rejected(reason);
+        }
+
+        // Stage 2.7: HTLC payload must match the block type.
+        if let Err(reason) = block.validate_htlc() {
+            return ProcessResult::Rejected(reason);
+        }
+
+        // Stage 2.8: Reject reasons only belong on RejectReceive blocks.
+        if let Err(reason) = block.validate_reject_reason() {
+            return ProcessResult::Rejected(reason);
+        }
+
+        // Stage 2.9: Recovery payload must match the block type.
+        if let Err(reason) = block.validate_recovery() {
+            return ProcessResult::Rejected(reason);
+        }
+
+        // Stage 2.10: Delegation keys only ride on Delegate and
+        // RotateDelegationKey blocks.
+        if let Err(reason) = block.validate_delegation_key() {
+            return ProcessResult::Rejected(reason);
+        }
+
+        // Stage 2.11: v2 extension areas must be canonical and well formed;
+        // v1 blocks carry none.
+        if let Err(reason) = block.validate_extensions() {
+            return ProcessResult::Rejected(reason);
+        }
+
         // Stage 3: Signature verification
         if block.signature == Signature([0u8; 64]) {
             return ProcessResult::Rejected("signature is zero (unsigned block)".into());
         }
 
         if self.verify_signatures {
-            let signer = if matches!(
-                block.block_type,
-                BlockType::Epoch | BlockType::GovernanceActivation
-            ) {
-                &self.genesis_account
-            } else {
-                &block.account
-            };
-
-            let pubkey_bytes = match decode_address(signer.as_str()) {
-                Some(bytes) => bytes,
-                None => {
-                    return ProcessResult::Rejected(
-                        "unable to decode account address for signature verification".into(),
-                    )
-                }
+            let public_key = match self.signing_key(block) {
+                Ok(key) => key,
+                Err(reason) => return ProcessResult::Rejected(reason),
             };
-            let public_key = PublicKey(pubkey_bytes);
-            if !verify_signature(block.hash.as_bytes(), &block.signature, &public_key) {
+            if !verify_signature(block.hash.as_bytes(), &block.signature, &public_key)
+                && !self.signed_by_upgrade_key(block)
+            {
                 // Primary key verification failed — check delegation key fallback
                 if let Some(ref del_store) = self.delegation_store {
                     let signing_pubkey =
@@ -350,14 +615,21 @@ impl BlockProcessor {
         // Stage 3.5: params_hash validation
         // GovernanceActivation blocks carry the *new* params_hash in their
         // `transaction` field; their own `params_hash` stamp is the pre-activation
-        // hash, so we skip validation for them. Bootstrap blocks (old blocks
-        // replayed) may carry an earlier params_hash, so we only warn rather
-        // than hard-reject to allow gradual migration.
+        // hash, so we skip validation for them. Blocks timestamped after the
+        // current parameters activated were produced under the wrong regime
+        // and are rejected; earlier ones (old blocks replayed during
+        // bootstrap) may carry an earlier params_hash, so we only warn.
         if !block.params_hash.is_zero()
             && !self.current_params_hash.is_zero()
             && block.params_hash != self.current_params_hash
             && block.block_type != BlockType::GovernanceActivation
         {
+            if let Some(at) = self.params_activated_at.filter(|&at| block.timestamp >= at) {
+                return ProcessResult::Rejected(format!(
+                    "block params_hash {} does not match the parameters in force since {at}",
+                    block.params_hash
+                ));
+            }
             tracing::warn!(
                 block_hash = %block.hash,
                 block_params = %block.params_hash,
@@ -374,6 +646,54 @@ impl BlockProcessor {
             return self.process_governance_activation(block, frontier);
         }
 
+        // Stage 3.7: Key rotation needs enough custodian approvals for the
+        // current head and must wait out the configured delay.
+        if block.block_type == BlockType::Recovery {
+            if let Err(reason) = self.validate_recovery_rotation(block) {
+                return ProcessResult::Rejected(reason);
+            }
+        }
+
+        // Stage 3.8: A delegation key can only be rotated for the account's
+        // active delegate.
+        if block.block_type == BlockType::RotateDelegationKey {
+            if let Err(reason) = self.validate_delegation_rotation(block) {
+                return ProcessResult::Rejected(reason);
+            }
+        }
+
+        // Stage 3.9: A v2 block carrying a sequence must sit at that height
+        // in its chain, so a late resubmission of the same intent cannot
+        // land at a later position. A block whose previous block is not
+        // stored yet is checked once the gap is filled.
+        if let Some(sequence) = block.sequence() {
+            match self.chain_position(block) {
+                Ok(Some(position)) if sequence != position => {
+                    return ProcessResult::Rejected(format!(
+                        "block sequence {sequence} does not match chain position {position}"
+                    ));
+                }
+                Ok(Some(_)) => {}
+                Ok(None) => {
+                    self.queue_unchecked(block.previous, block.clone());
+                    return ProcessResult::Gap;
+                }
+                Err(reason) => return ProcessResult::Rejected(reason),
+            }
+        }
+
+        // Stage 3.10: While the network is bootstrapping, TRST sends and
+        // BRN burns are capped for every wallet.
+        if let Err(reason) = self.validate_bootstrap_limits(block) {
+            return ProcessResult::Rejected(reason);
+        }
+
+        // Stage 3.11: Timestamps never run backwards along a chain, so a
+        // block cannot be backdated past anything already on its account.
+        if let Err(reason) = self.validate_timestamp_order(block) {
+            return ProcessResult::Rejected(reason);
+        }
+
         // Stage 4–8: Account-state–dependent checks
         let account_head = frontier.get_head(&block.account).copied();
 
@@ -389,11 +709,21 @@ impl BlockProcessor {
                 }
 
                 if block.previous == frontier_head {
+                    // Stage 4.4: A transaction already on the chain is a
+                    // replay, however the block carrying it arrived.
+                    if let Err(reason) = self.validate_not_replayed(block) {
+                        return ProcessResult::Rejected(reason);
+                    }
+
                     // Stage 4.5: Gap-source — for Receive/RejectReceive/VerificationVote blocks,
                     // verify the linked source block has been seen. If not, queue as gap-source.
                     if matches!(
                         block.block_type,
-                        BlockType::Receive | BlockType::RejectReceive | BlockType::VerificationVote
+                        BlockType::Receive
+                            | BlockType::RejectReceive
+                            | BlockType::VerificationVote
+                            | BlockType::HtlcClaim
+                            | BlockType::HtlcRefund
                     ) && !block.link.is_zero()
                         && !self.source_known(&block.link)
                     {
@@ -436,7 +766,11 @@ impl BlockProcessor {
                     // Gap-source check for receive-type and verification-vote open blocks
                     if matches!(
                         block.block_type,
-                        BlockType::Receive | BlockType::RejectReceive | BlockType::VerificationVote
+                        BlockType::Receive
+                            | BlockType::RejectReceive
+                            | BlockType::VerificationVote
+                            | BlockType::HtlcClaim
+                            | BlockType::HtlcRefund
                     ) && !block.link.is_zero()
                         && !self.source_known(&block.link)
                     {
@@ -456,6 +790,116 @@ impl BlockProcessor {
         }
     }
 
+    /// Height `block` would occupy in its account chain: 1 for an open
+    /// block, one past its previous block otherwise. `None` when the
+    /// previous block is not stored yet.
+    fn chain_position(&self, block: &StateBlock) -> Result<Option<u64>, String> {
+        if block.previous.is_zero() {
+            return Ok(Some(1));
+        }
+        let Some(ref store) = self.block_store else {
+            return Err("block sequence cannot be checked without a block store".into());
+        };
+        let unreadable = |e: StoreError| format!("unable to read block heights: {e}");
+        match store.height_of_block(&block.previous).map_err(unreadable)? {
+            Some(height) => Ok(Some(height + 1)),
+            None if store.exists(&block.previous).map_err(unreadable)? => Err(format!(
+                "height of previous block {} is not stored",
+                block.previous
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Check that `block` is not stamped before its previous block. A
+    /// previous block that is not stored yet is left to the gap check.
+    fn validate_timestamp_order(&self, block: &StateBlock) -> Result<(), String> {
+        if block.previous.is_zero() {
+            return Ok(());
+        }
+        let Some(ref store) = self.block_store else {
+            return Ok(());
+        };
+        let bytes = match store.get_block(&block.previous) {
+            Ok(bytes) => bytes,
+            Err(StoreError::NotFound(_)) => return Ok(()),
+            Err(e) => return Err(format!("unable to read previous block: {e}")),
+        };
+        let previous: StateBlock = bincode::deserialize(&bytes)
+            .map_err(|e| format!("unable to decode previous block: {e}"))?;
+        if block.timestamp < previous.timestamp {
+            return Err(format!(
+                "block timestamp {} is before its previous block's {}",
+                block.timestamp.as_secs(),
+                previous.timestamp.as_secs()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check that the transaction `block` carries is not already on its
+    /// account chain.
+    fn validate_not_replayed(&self, block: &StateBlock) -> Result<(), String> {
+        if block.transaction.is_zero() {
+            return Ok(());
+        }
+        let (Some(accounts), Some(blocks)) = (&self.account_store, &self.block_store) else {
+            return Ok(());
+        };
+        match applied_height(
+            accounts.as_ref(),
+            blocks.as_ref(),
+            &block.account,
+            &block.transaction,
+            block.timestamp,
+        ) {
+            Ok(None) => Ok(()),
+            Ok(Some(height)) => Err(format!(
+                "transaction {} was already applied at sequence {height}",
+                block.transaction
+            )),
+            Err(e) => Err(format!("unable to check for replay: {e}")),
+        }
+    }
+
+    /// Check a send or burn against the bootstrap-phase caps. The amount is
+    /// the balance drop from the previous block; a block whose previous
+    /// block is not stored yet is checked once the gap is filled.
+    fn validate_bootstrap_limits(&self, block: &StateBlock) -> Result<(), String> {
+        if !self.bootstrap_limits.is_enabled()
+            || !matches!(
+                block.block_type,
+                BlockType::Send | BlockType::HtlcLock | BlockType::Burn
+            )
+        {
+            return Ok(());
+        }
+        let (Some(accounts), Some(blocks)) = (&self.account_store, &self.block_store) else {
+            return Ok(());
+        };
+        let Some(previous) = blocks
+            .get_block(&block.previous)
+            .ok()
+            .and_then(|bytes| bincode::deserialize::<StateBlock>(&bytes).ok())
+        else {
+            return Ok(());
+        };
+        let action = if block.block_type == BlockType::Burn {
+            LimitedAction::Burn {
+                amount: previous.brn_balance.saturating_sub(block.brn_balance),
+            }
+        } else {
+            LimitedAction::Send {
+                amount: previous.trst_balance.saturating_sub(block.trst_balance),
+                sent_today: 0,
+            }
+        };
+        let verified_count = accounts
+            .verified_account_count()
+            .map_err(|e| format!("unable to count verified wallets: {e}"))?;
+        check_bootstrap_limits(action, verified_count, &self.bootstrap_limits)
+    }
+
     /// Check if a source/link block is known, first in the in-memory dedup
     /// cache, then falling back to the persistent block store.
     fn source_known(&self, hash: &BlockHash) -> bool {
@@ -509,6 +953,11 @@ impl BlockProcessor {
         self.unchecked.len()
     }
 
+    /// Unchecked entry count and approximate memory.
+    pub fn unchecked_usage(&self) -> ContainerUsage {
+        self.unchecked.memory_usage()
+    }
+
     /// Remove unchecked entries older than `max_age_secs`. Returns how many were removed.
     pub fn cleanup_unchecked(&mut self, max_age_secs: u64, now: u64) -> usize {
         self.unchecked.clear_expired(max_age_secs, now)
@@ -632,7 +1081,10 @@ impl BlockProcessor {
     /// Process an epoch block.
     ///
     /// Epoch blocks are special protocol-upgrade markers. Rules:
-    /// - Only the genesis account may sign epoch blocks.
+    /// - Only the genesis account or the configured upgrade key may sign
+    ///   epoch blocks.
+    /// - The block's `version` must raise the account's epoch (checked at
+    ///   the node layer, which has the account state).
     /// - The target account must already exist in the frontier.
     /// - `previous` must reference the current head of the target account.
     /// - Epoch blocks don't transfer any value (balances must remain unchanged).
@@ -747,6 +1199,18 @@ impl BlockProcessor {
         &self.work_thresholds
     }
 
+    /// Replace the per-block-type work thresholds (after governance changes
+    /// the base difficulty or a multiplier).
+    pub fn set_work_thresholds(&mut self, thresholds: WorkThresholds) {
+        self.min_work_difficulty = thresholds.base;
+        self.work_thresholds = thresholds;
+    }
+
+    /// Scale the work thresholds by the network load multiplier.
+    pub fn set_work_multiplier(&mut self, multiplier: u64) {
+        self.work_multiplier = multiplier.max(1);
+    }
+
     /// The genesis account (authorized to sign epoch blocks).
     pub fn genesis_account(&self) -> &WalletAddress {
         &self.genesis_account
@@ -766,7 +1230,7 @@ impl BlockProcessor {
         prev_trst: u128,
     ) -> Result<(), String> {
         match block.block_type {
-            BlockType::Send => {
+            BlockType::Send | BlockType::HtlcLock => {
                 if block.trst_balance > prev_trst {
                     return Err("send block cannot increase TRST balance".into());
                 }
@@ -827,10 +1291,30 @@ impl BlockProcessor {
                     return Err("challenge block cannot change TRST balance".into());
                 }
             }
+            BlockType::VerifierOptIn => {
+                if block.brn_balance >= prev_brn {
+                    return Err("verifier opt-in block must lock a BRN stake".into());
+                }
+                if block.trst_balance != prev_trst {
+                    return Err("verifier opt-in block cannot change TRST balance".into());
+                }
+            }
+            BlockType::VerifierOptOut => {
+                if block.brn_balance <= prev_brn {
+                    return Err("verifier opt-out block must return the BRN stake".into());
+                }
+                if block.trst_balance != prev_trst {
+                    return Err("verifier opt-out block cannot change TRST balance".into());
+                }
+            }
             BlockType::GovernanceProposal
             | BlockType::GovernanceVote
+            | BlockType::GovernanceCommit
+            | BlockType::GovernanceReveal
             | BlockType::Delegate
-            | BlockType::RevokeDelegation => {
+            | BlockType::RevokeDelegation
+            | BlockType::RotateDelegationKey
+            | BlockType::Liveness => {
                 if block.brn_balance != prev_brn {
                     return Err(format!(
                         "{:?} block cannot change BRN balance",
@@ -872,12 +1356,129 @@ impl BlockProcessor {
                     return Err("verification-vote block cannot change balances".into());
                 }
             }
+            BlockType::RecoverySetup | BlockType::Recovery => {
+                if block.brn_balance != prev_brn || block.trst_balance != prev_trst {
+                    return Err(format!(
+                        "{:?} block cannot change balances",
+                        block.block_type
+                    ));
+                }
+            }
+            BlockType::GroupAnchor => {
+                if block.brn_balance != prev_brn || block.trst_balance != prev_trst {
+                    return Err("group anchor block cannot change balances".into());
+                }
+                if block.link.is_zero() {
+                    return Err("group anchor block must carry an anchor".into());
+                }
+            }
+            BlockType::DelegateProfile => {
+                if block.brn_balance != prev_brn || block.trst_balance != prev_trst {
+                    return Err("delegate profile block cannot change balances".into());
+                }
+                if block.link.is_zero() {
+                    return Err("delegate profile block must carry a profile anchor".into());
+                }
+            }
+            BlockType::Alias => {
+                if block.brn_balance > prev_brn {
+                    return Err("alias block cannot increase BRN balance".into());
+                }
+                if block.trst_balance != prev_trst {
+                    return Err("alias block cannot change TRST balance".into());
+                }
+            }
+            BlockType::HtlcClaim | BlockType::HtlcRefund => {
+                if block.trst_balance <= prev_trst {
+                    return Err(format!(
+                        "{:?} block must increase TRST balance",
+                        block.block_type
+                    ));
+                }
+                if block.brn_balance != prev_brn {
+                    return Err(format!(
+                        "{:?} block cannot change BRN balance",
+                        block.block_type
+                    ));
+                }
+            }
             BlockType::Open => {
                 // Open blocks have no previous — caller should not invoke this for them.
             }
         }
         Ok(())
     }
+
+    /// Validate a block that settles a pending entry against that entry.
+    ///
+    /// - `HtlcClaim`: the pending entry must be an HTLC, the preimage must
+    ///   match its hash lock, the claim must precede the refund timeout
+    ///   without being stamped before `lock_block`, and exactly the locked
+    ///   amount must be credited.
+    /// - `HtlcRefund`: `lock_block` must be an `HtlcLock` sent by this account,
+    ///   its pending entry must still exist, the timeout must have passed, and
+    ///   exactly the locked amount must be credited.
+    /// - `Receive` / `RejectReceive`: HTLC entries cannot be pocketed or
+    ///   rejected this way.
+    ///
+    /// Timeouts are compared against the settling block's timestamp, which
+    /// the processor keeps from running backwards along the chain. Other
+    /// block types pass unchecked.
+    pub fn validate_htlc_settlement(
+        block: &StateBlock,
+        prev_trst: u128,
+        lock_block: Option<&StateBlock>,
+        pending: Option<&PendingInfo>,
+    ) -> Result<(), String> {
+        match block.block_type {
+            BlockType::Receive | BlockType::RejectReceive => {
+                if pending.is_some_and(|p| p.htlc.is_some()) {
+                    return Err("HTLC transfers can only be claimed or refunded".into());
+                }
+                Ok(())
+            }
+            BlockType::HtlcClaim => {
+                let pending = pending.ok_or("no pending HTLC for this claim")?;
+                let terms = pending
+                    .htlc
+                    .as_ref()
+                    .ok_or("pending entry is not an HTLC")?;
+                let Some(HtlcData::Claim { ref preimage }) = block.htlc else {
+                    return Err("HTLC claim block must carry a preimage".into());
+                };
+                let lock = lock_block.ok_or("HTLC lock block not found")?;
+                if block.timestamp < lock.timestamp {
+                    return Err("HTLC claim cannot be stamped before its lock".into());
+                }
+                htlc::validate_claim(
+                    &terms.hash_lock,
+                    terms.refund_after,
+                    preimage,
+                    block.timestamp,
+                )
+                .map_err(|e| e.to_string())?;
+                check_settled_amount(block, prev_trst, pending.amount)
+            }
+            BlockType::HtlcRefund => {
+                let lock = lock_block.ok_or("HTLC lock block not found")?;
+                if lock.block_type != BlockType::HtlcLock {
+                    return Err("refund must reference an HTLC lock block".into());
+                }
+                if lock.account != block.account {
+                    return Err("only the HTLC sender can refund it".into());
+                }
+                let pending = pending.ok_or("HTLC already claimed or refunded")?;
+                let terms = pending
+                    .htlc
+                    .as_ref()
+                    .ok_or("pending entry is not an HTLC")?;
+                htlc::validate_refund(terms.refund_after, block.timestamp)
+                    .map_err(|e| e.to_string())?;
+                check_settled_amount(block, prev_trst, pending.amount)
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -885,7 +1486,9 @@ mod tests {
     use super::*;
     use burst_crypto::{derive_address, generate_keypair, keypair_from_seed, sign_message};
     use burst_ledger::{BlockType, DagFrontier, CURRENT_BLOCK_VERSION};
+    use burst_store::account::AccountInfo;
     use burst_store::block::BlockStore;
+    use burst_store_lmdb::LmdbEnvironment;
     use burst_types::{BlockHash, Signature, Timestamp, TxHash, WalletAddress};
     use burst_work::WorkGenerator;
 
@@ -916,6 +1519,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([1u8; 64]),
             hash: BlockHash::ZERO,
@@ -947,6 +1556,12 @@ mod tests {
             transaction: TxHash::new([0xBB; 32]),
             timestamp: Timestamp::new(1_000_001),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([2u8; 64]),
             hash: BlockHash::ZERO,
@@ -977,6 +1592,12 @@ mod tests {
             transaction: TxHash::new([0xDD; 32]),
             timestamp: Timestamp::new(1_000_002),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([3u8; 64]),
             hash: BlockHash::ZERO,
@@ -1029,6 +1650,365 @@ mod tests {
         assert_eq!(frontier.get_head(&test_account()), Some(&send.hash));
     }
 
+    #[test]
+    fn blocks_under_superseded_params_rejected_after_activation() {
+        let mut processor = test_processor(0);
+        let mut frontier = DagFrontier::new();
+        let old_params = BlockHash::new([0x01; 32]);
+        let new_params = BlockHash::new([0x02; 32]);
+
+        // The open block predates the activation: a stale stamp only warns.
+        processor.apply_params_activation(new_params, Timestamp::new(1_000_001));
+        let mut open = make_open_block(0);
+        open.params_hash = old_params;
+        open.hash = open.compute_hash();
+        assert_eq!(
+            processor.process(&open, &mut frontier),
+            ProcessResult::Accepted
+        );
+
+        let mut stale = make_send_block(open.hash, 0);
+        stale.params_hash = old_params;
+        stale.hash = stale.compute_hash();
+        assert!(matches!(
+            processor.process(&stale, &mut frontier),
+            ProcessResult::Rejected(reason) if reason.contains("params_hash")
+        ));
+
+        let mut current = make_send_block(open.hash, 0);
+        current.params_hash = new_params;
+        current.hash = current.compute_hash();
+        assert_eq!(
+            processor.process(&current, &mut frontier),
+            ProcessResult::Accepted
+        );
+    }
+
+    #[test]
+    fn scheduled_payment_passes_validation_under_current_params() {
+        use burst_wallet_core::scheduler::{ExecutionContext, RunOutcome};
+        use burst_wallet_core::transaction_builder::AccountState;
+        use burst_wallet_core::{PaymentTemplate, Recurrence, Scheduler};
+
+        let kp = keypair_from_seed(&[0x51; 32]);
+        let owner = derive_address(&kp.public);
+        let params = BlockHash::new([0x02; 32]);
+        let (_dir, env, mut processor) = stored_processor();
+        processor.set_verify_signatures(true);
+        processor.apply_params_activation(params, Timestamp::new(1_000_000));
+        let mut frontier = DagFrontier::new();
+
+        let mut open = make_open_block(0);
+        open.account = owner.clone();
+        open.trst_balance = 100;
+        open.origin = TxHash::new([0x33; 32]);
+        open.params_hash = params;
+        open.hash = open.compute_hash();
+        open.signature = sign_message(open.hash.as_bytes(), &kp.private);
+        assert_eq!(
+            processor.process(&open, &mut frontier),
+            ProcessResult::Accepted
+        );
+        persist(&env, &open);
+
+        let mut scheduler = Scheduler::new(owner.clone());
+        let id = scheduler
+            .schedule(
+                PaymentTemplate {
+                    receiver: derive_address(&keypair_from_seed(&[0x52; 32]).public),
+                    amount: 40,
+                    memo: None,
+                    recurrence: Recurrence::weekly(),
+                    first_due: Timestamp::new(1_000_100),
+                    expires_at: None,
+                },
+                &kp,
+            )
+            .unwrap();
+
+        // What `Scheduler::submit_due` resolves from the node.
+        let now = Timestamp::new(1_000_100);
+        let mut ctx = ExecutionContext {
+            account_state: AccountState {
+                head: open.hash,
+                block_count: 1,
+                representative: open.representative.clone(),
+                brn_balance: open.brn_balance,
+                trst_balance: open.trst_balance,
+                network: NetworkId::Dev,
+                block_version: burst_ledger::BLOCK_VERSION_V3,
+            },
+            transferable_trst: open.trst_balance,
+            previous_origin: open.origin,
+            params_hash: processor.params_hash(),
+            private_key: &kp.private,
+        };
+        let runs = scheduler.prepare_due(now, &mut ctx);
+        let RunOutcome::Built(ref send) = runs[0].outcome else {
+            panic!("expected a built block");
+        };
+        assert_eq!(send.origin, open.origin);
+        assert_eq!(send.params_hash, params);
+        assert_eq!(
+            processor.process(send, &mut frontier),
+            ProcessResult::Accepted
+        );
+        assert_eq!(frontier.get_head(&owner), Some(&send.hash));
+
+        scheduler.record_accepted(&id, now).unwrap();
+        assert_eq!(scheduler.get(&id).unwrap().runs, 1);
+    }
+
+    #[test]
+    fn accept_send_with_memo() {
+        let mut processor = test_processor(0);
+        let mut frontier = DagFrontier::new();
+
+        let open = make_open_block(0);
+        processor.process(&open, &mut frontier);
+
+        let mut send = make_send_block(open.hash, 0);
+        send.memo = Some(b"INV-2024-0001".to_vec());
+        send.hash = send.compute_hash();
+        assert_eq!(
+            processor.process(&send, &mut frontier),
+            ProcessResult::Accepted
+        );
+    }
+
+    #[test]
+    fn reject_oversized_memo() {
+        let mut processor = test_processor(0);
+        let mut frontier = DagFrontier::new();
+
+        let open = make_open_block(0);
+        processor.process(&open, &mut frontier);
+
+        let mut send = make_send_block(open.hash, 0);
+        send.memo = Some(vec![0u8; 33]);
+        send.hash = send.compute_hash();
+        assert!(matches!(
+            processor.process(&send, &mut frontier),
+            ProcessResult::Rejected(_)
+        ));
+        assert_eq!(frontier.get_head(&test_account()), Some(&open.hash));
+    }
+
+    #[test]
+    fn reject_memo_on_non_send_block() {
+        let mut processor = test_processor(0);
+        let mut frontier = DagFrontier::new();
+
+        let mut open = make_open_block(0);
+        open.memo = Some(b"ref".to_vec());
+        open.hash = open.compute_hash();
+        assert!(matches!(
+            processor.process(&open, &mut frontier),
+            ProcessResult::Rejected(_)
+        ));
+    }
+
+    fn htlc_pending(amount: u128, preimage: &[u8; 32], refund_after: u64) -> PendingInfo {
+        PendingInfo {
+            source: test_account(),
+            amount,
+            timestamp: Timestamp::new(1_000_001),
+            provenance: vec![],
+            memo: None,
+            destination_tag: None,
+            htlc: Some(burst_store::pending::PendingHtlc {
+                hash_lock: htlc::hash_lock_for(preimage),
+                refund_after: Timestamp::new(refund_after),
+            }),
+            returned: false,
+        }
+    }
+
+    #[test]
+    fn reject_htlc_lock_without_future_timeout() {
+        let mut processor = test_processor(0);
+        let mut frontier = DagFrontier::new();
+
+        let open = make_open_block(0);
+        processor.process(&open, &mut frontier);
+
+        let mut lock = make_send_block(open.hash, 0);
+        lock.block_type = BlockType::HtlcLock;
+        lock.htlc = Some(HtlcData::Lock {
+            hash_lock: [1u8; 32],
+            refund_after: lock.timestamp,
+        });
+        lock.hash = lock.compute_hash();
+        assert!(matches!(
+            processor.process(&lock, &mut frontier),
+            ProcessResult::Rejected(_)
+        ));
+
+        lock.htlc = Some(HtlcData::Lock {
+            hash_lock: [1u8; 32],
+            refund_after: Timestamp::new(lock.timestamp.as_secs() + 3600),
+        });
+        lock.hash = lock.compute_hash();
+        assert_eq!(
+            processor.process(&lock, &mut frontier),
+            ProcessResult::Accepted
+        );
+    }
+
+    #[test]
+    fn htlc_claim_settlement() {
+        let preimage = [7u8; 32];
+        let pending = htlc_pending(50, &preimage, 2_000_000);
+
+        let mut lock = make_send_block(BlockHash::new([4u8; 32]), 0);
+        lock.block_type = BlockType::HtlcLock;
+        let lock = Some(&lock);
+
+        let mut claim = make_send_block(BlockHash::new([3u8; 32]), 0);
+        claim.block_type = BlockType::HtlcClaim;
+        claim.trst_balance = 150;
+        claim.htlc = Some(HtlcData::Claim { preimage });
+        assert!(
+            BlockProcessor::validate_htlc_settlement(&claim, 100, lock, Some(&pending)).is_ok()
+        );
+
+        // Lock block unknown
+        assert!(
+            BlockProcessor::validate_htlc_settlement(&claim, 100, None, Some(&pending)).is_err()
+        );
+
+        // Wrong amount credited
+        assert!(
+            BlockProcessor::validate_htlc_settlement(&claim, 90, lock, Some(&pending)).is_err()
+        );
+
+        // Wrong preimage
+        claim.htlc = Some(HtlcData::Claim {
+            preimage: [8u8; 32],
+        });
+        assert!(
+            BlockProcessor::validate_htlc_settlement(&claim, 100, lock, Some(&pending)).is_err()
+        );
+
+        // Past the refund timeout
+        claim.htlc = Some(HtlcData::Claim { preimage });
+        claim.timestamp = Timestamp::new(2_000_000);
+        assert!(
+            BlockProcessor::validate_htlc_settlement(&claim, 100, lock, Some(&pending)).is_err()
+        );
+
+        // Plain receive of an HTLC entry is not allowed
+        let mut receive = claim.clone();
+        receive.block_type = BlockType::Receive;
+        receive.htlc = None;
+        assert!(
+            BlockProcessor::validate_htlc_settlement(&receive, 100, None, Some(&pending)).is_err()
+        );
+    }
+
+    #[test]
+    fn backdated_htlc_claim_is_rejected() {
+        let preimage = [7u8; 32];
+        let pending = htlc_pending(50, &preimage, 2_000_000);
+        let mut lock = make_send_block(BlockHash::new([4u8; 32]), 0);
+        lock.block_type = BlockType::HtlcLock;
+
+        // The receiver's chain has moved past the refund timeout.
+        let (_dir, env, mut processor) = stored_processor();
+        let mut frontier = DagFrontier::new();
+        let open = make_open_block(0);
+        assert_eq!(
+            processor.process(&open, &mut frontier),
+            ProcessResult::Accepted
+        );
+        persist(&env, &open);
+        let mut send = make_send_block(open.hash, 0);
+        send.timestamp = Timestamp::new(2_000_100);
+        send.hash = send.compute_hash();
+        assert_eq!(
+            processor.process(&send, &mut frontier),
+            ProcessResult::Accepted
+        );
+        persist(&env, &send);
+
+        // A claim stamped under the timeout would settle on its own terms,
+        // but cannot sit after a later block on the chain.
+        let mut claim = make_send_block(send.hash, 0);
+        claim.block_type = BlockType::HtlcClaim;
+        claim.link = lock.hash;
+        claim.trst_balance = 150;
+        claim.htlc = Some(HtlcData::Claim { preimage });
+        claim.timestamp = Timestamp::new(1_500_000);
+        claim.hash = claim.compute_hash();
+        assert!(
+            BlockProcessor::validate_htlc_settlement(&claim, 100, Some(&lock), Some(&pending))
+                .is_ok()
+        );
+        assert!(matches!(
+            processor.process(&claim, &mut frontier),
+            ProcessResult::Rejected(reason) if reason.contains("before its previous block")
+        ));
+
+        // Nor before the lock it claims.
+        claim.timestamp = Timestamp::new(lock.timestamp.as_secs() - 1);
+        assert!(
+            BlockProcessor::validate_htlc_settlement(&claim, 100, Some(&lock), Some(&pending))
+                .is_err()
+        );
+
+        // Submitted live, it must be stamped close to the local clock.
+        claim.timestamp = Timestamp::new(1_500_000);
+        assert!(validate_live_timestamp(&claim, 1_500_000 + TX_TIME_TOLERANCE_SECS).is_ok());
+        assert!(validate_live_timestamp(&claim, 2_000_100).is_err());
+    }
+
+    #[test]
+    fn htlc_refund_settlement() {
+        let preimage = [7u8; 32];
+        let pending = htlc_pending(50, &preimage, 2_000_000);
+
+        let mut lock = make_send_block(BlockHash::new([3u8; 32]), 0);
+        lock.block_type = BlockType::HtlcLock;
+
+        let mut refund = make_send_block(lock.hash, 0);
+        refund.block_type = BlockType::HtlcRefund;
+        refund.link = lock.hash;
+        refund.trst_balance = 150;
+        refund.timestamp = Timestamp::new(1_999_999);
+
+        // Too early
+        assert!(BlockProcessor::validate_htlc_settlement(
+            &refund,
+            100,
+            Some(&lock),
+            Some(&pending)
+        )
+        .is_err());
+
+        refund.timestamp = Timestamp::new(2_000_000);
+        assert!(BlockProcessor::validate_htlc_settlement(
+            &refund,
+            100,
+            Some(&lock),
+            Some(&pending)
+        )
+        .is_ok());
+
+        // Already claimed (pending gone)
+        assert!(BlockProcessor::validate_htlc_settlement(&refund, 100, Some(&lock), None).is_err());
+
+        // Only the lock's sender may refund
+        let mut foreign_lock = lock.clone();
+        foreign_lock.account = test_representative();
+        assert!(BlockProcessor::validate_htlc_settlement(
+            &refund,
+            100,
+            Some(&foreign_lock),
+            Some(&pending)
+        )
+        .is_err());
+    }
+
     #[test]
     fn accept_chain_of_three_blocks() {
         let mut processor = test_processor(0);
@@ -1122,6 +2102,32 @@ mod tests {
         assert_eq!(result, ProcessResult::Accepted);
     }
 
+    #[test]
+    fn network_multiplier_raises_the_work_threshold() {
+        let mut processor = test_processor(1000);
+        let mut frontier = DagFrontier::new();
+        let kind = burst_work::WorkBlockKind::ReceiveOrOpen;
+        let base = processor.work_thresholds().threshold_for(kind);
+        let raised = processor.work_thresholds().scaled(4.0).threshold_for(kind);
+
+        // Work that meets the base threshold but not the raised one.
+        let mut block = make_open_block(0);
+        while !block.verify_work(base) || block.verify_work(raised) {
+            block.work += 1;
+        }
+
+        processor.set_work_multiplier(4);
+        assert_eq!(
+            processor.process(&block, &mut frontier),
+            ProcessResult::Rejected("proof-of-work does not meet minimum difficulty".into())
+        );
+        processor.set_work_multiplier(1);
+        assert_eq!(
+            processor.process(&block, &mut frontier),
+            ProcessResult::Accepted
+        );
+    }
+
     // ── Signature validation ────────────────────────────────────────────
 
     #[test]
@@ -1301,6 +2307,12 @@ mod tests {
             transaction: TxHash::new([0xFF; 32]),
             timestamp: Timestamp::new(2_000_000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([5u8; 64]),
             hash: BlockHash::ZERO,
@@ -1332,6 +2344,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([1u8; 64]),
             hash: BlockHash::ZERO,
@@ -1371,6 +2389,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_001),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([4u8; 64]),
             hash: BlockHash::ZERO,
@@ -1463,6 +2487,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(2_000_000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([7u8; 64]),
             hash: BlockHash::ZERO,
@@ -1577,6 +2607,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([0u8; 64]),
             hash: BlockHash::ZERO,
@@ -1609,6 +2645,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([0u8; 64]),
             hash: BlockHash::ZERO,
@@ -1647,6 +2689,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([0u8; 64]),
             hash: BlockHash::ZERO,
@@ -1672,6 +2720,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(2_000_000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([0u8; 64]),
             hash: BlockHash::ZERO,
@@ -1709,6 +2763,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([0u8; 64]),
             hash: BlockHash::ZERO,
@@ -1734,6 +2794,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(2_000_000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([0u8; 64]),
             hash: BlockHash::ZERO,
@@ -1746,6 +2812,208 @@ mod tests {
         );
     }
 
+    #[test]
+    fn epoch_block_accepted_from_configured_upgrade_key() {
+        let genesis_kp = keypair_from_seed(&[42u8; 32]);
+        let upgrade_kp = keypair_from_seed(&[43u8; 32]);
+        let account_kp = generate_keypair();
+        let account_address = derive_address(&account_kp.public);
+
+        let mut processor =
+            BlockProcessor::with_genesis_account(0, derive_address(&genesis_kp.public));
+        let mut frontier = DagFrontier::new();
+
+        let mut open = make_open_block(0);
+        open.account = account_address.clone();
+        open.hash = open.compute_hash();
+        open.signature = sign_message(open.hash.as_bytes(), &account_kp.private);
+        assert_eq!(
+            processor.process(&open, &mut frontier),
+            ProcessResult::Accepted
+        );
+
+        let mut epoch = make_epoch_block(open.hash, account_address);
+        epoch.signature = sign_message(epoch.hash.as_bytes(), &upgrade_kp.private);
+        assert_eq!(
+            processor.process(&epoch, &mut frontier),
+            ProcessResult::Rejected("invalid signature".into())
+        );
+
+        processor.set_upgrade_key(derive_address(&upgrade_kp.public));
+        assert_eq!(
+            processor.process(&epoch, &mut frontier),
+            ProcessResult::Accepted
+        );
+
+        let mut future = make_send_block(epoch.hash, 0);
+        future.version = burst_ledger::MAX_SUPPORTED_BLOCK_VERSION + 1;
+        future.hash = future.compute_hash();
+        assert!(matches!(
+            processor.process(&future, &mut frontier),
+            ProcessResult::Rejected(reason) if reason.contains("not supported")
+        ));
+    }
+
+    #[test]
+    fn blocks_bound_to_another_network_are_rejected() {
+        let mut processor = test_processor(0);
+        processor.set_network(NetworkId::Live);
+        let mut frontier = DagFrontier::new();
+
+        let mut open = make_open_block(0);
+        open.bind_to_network(NetworkId::Test);
+        assert!(matches!(
+            processor.process(&open, &mut frontier),
+            ProcessResult::Rejected(reason) if reason.contains("test network")
+        ));
+
+        open.bind_to_network(NetworkId::Live);
+        assert_eq!(
+            processor.process(&open, &mut frontier),
+            ProcessResult::Accepted
+        );
+
+        // Unbound blocks from before the network epoch still sync; the
+        // epoch rules retire them once the account reaches version 3.
+        assert_eq!(
+            processor.process(&make_open_block(0), &mut DagFrontier::new()),
+            ProcessResult::Accepted
+        );
+    }
+
+    /// A processor reading a fresh LMDB ledger, so block heights and
+    /// account chains are known.
+    fn stored_processor() -> (tempfile::TempDir, LmdbEnvironment, BlockProcessor) {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+        let mut processor = test_processor(0);
+        processor.block_store = Some(Arc::new(env.block_store()));
+        processor.account_store = Some(Arc::new(env.account_store()));
+        (dir, env, processor)
+    }
+
+    /// Persist an accepted block at the head of its chain, as the node does.
+    fn persist(env: &LmdbEnvironment, block: &StateBlock) {
+        let bytes = bincode::serialize(block).unwrap();
+        env.block_store()
+            .put_block_with_account(&block.hash, &bytes, &block.account)
+            .unwrap();
+        let block_count = env
+            .account_store()
+            .get_account(&block.account)
+            .map_or(1, |info| info.block_count + 1);
+        env.account_store()
+            .put_account(&AccountInfo {
+                address: block.account.clone(),
+                state: burst_types::WalletState::Unverified,
+                verified_at: None,
+                head: block.hash,
+                block_count,
+                confirmation_height: 0,
+                representative: block.representative.clone(),
+                total_brn_burned: 0,
+                total_brn_staked: 0,
+                trst_balance: block.trst_balance,
+                expired_trst: 0,
+                revoked_trst: 0,
+                epoch: 0,
+            })
+            .unwrap();
+    }
+
+    fn with_sequence(mut block: StateBlock, sequence: u64) -> StateBlock {
+        block.version = burst_ledger::BLOCK_VERSION_V2;
+        block.extensions = vec![burst_ledger::BlockExtension::sequence(sequence)];
+        block.hash = block.compute_hash();
+        block
+    }
+
+    #[test]
+    fn sequence_must_match_chain_position() {
+        let (_dir, env, mut processor) = stored_processor();
+        let mut frontier = DagFrontier::new();
+
+        let open = with_sequence(make_open_block(0), 2);
+        assert!(matches!(
+            processor.process(&open, &mut frontier),
+            ProcessResult::Rejected(reason) if reason.contains("chain position 1")
+        ));
+        let open = with_sequence(make_open_block(0), 1);
+        assert_eq!(
+            processor.process(&open, &mut frontier),
+            ProcessResult::Accepted
+        );
+        persist(&env, &open);
+
+        let send = with_sequence(make_send_block(open.hash, 0), 3);
+        assert!(matches!(
+            processor.process(&send, &mut frontier),
+            ProcessResult::Rejected(reason) if reason.contains("chain position 2")
+        ));
+        let send = with_sequence(make_send_block(open.hash, 0), 2);
+        assert_eq!(
+            processor.process(&send, &mut frontier),
+            ProcessResult::Accepted
+        );
+
+        // Until its previous block is stored, a block waits in the gap queue.
+        let next = with_sequence(make_send_block(send.hash, 0), 3);
+        assert_eq!(processor.process(&next, &mut frontier), ProcessResult::Gap);
+    }
+
+    #[test]
+    fn sequence_is_not_skipped_without_a_block_store() {
+        let mut processor = test_processor(0);
+        let mut frontier = DagFrontier::new();
+        let open = make_open_block(0);
+        assert_eq!(
+            processor.process(&open, &mut frontier),
+            ProcessResult::Accepted
+        );
+
+        let send = with_sequence(make_send_block(open.hash, 0), 2);
+        assert!(matches!(
+            processor.process(&send, &mut frontier),
+            ProcessResult::Rejected(reason) if reason.contains("without a block store")
+        ));
+    }
+
+    #[test]
+    fn transaction_already_on_the_chain_is_a_replay() {
+        let (_dir, env, mut processor) = stored_processor();
+        let mut frontier = DagFrontier::new();
+
+        let open = make_open_block(0);
+        assert_eq!(
+            processor.process(&open, &mut frontier),
+            ProcessResult::Accepted
+        );
+        persist(&env, &open);
+        let send = make_send_block(open.hash, 0);
+        assert_eq!(
+            processor.process(&send, &mut frontier),
+            ProcessResult::Accepted
+        );
+        persist(&env, &send);
+
+        // The same transaction resubmitted on top of the chain, as a peer
+        // or bootstrap would hand it over.
+        let mut replay = make_send_block(send.hash, 0);
+        replay.trst_balance = 50;
+        replay.hash = replay.compute_hash();
+        assert!(matches!(
+            processor.process(&replay, &mut frontier),
+            ProcessResult::Rejected(reason) if reason.contains("already applied at sequence 2")
+        ));
+
+        replay.transaction = TxHash::new([0xCC; 32]);
+        replay.hash = replay.compute_hash();
+        assert_eq!(
+            processor.process(&replay, &mut frontier),
+            ProcessResult::Accepted
+        );
+    }
+
     // ── Balance validation ───────────────────────────────────────────────
 
     #[test]
@@ -1798,6 +3066,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_001),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([1u8; 64]),
             hash: BlockHash::ZERO,
@@ -1822,6 +3096,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_001),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([1u8; 64]),
             hash: BlockHash::ZERO,
@@ -1850,6 +3130,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_001),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([1u8; 64]),
             hash: BlockHash::ZERO,
@@ -1874,6 +3160,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_001),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([1u8; 64]),
             hash: BlockHash::ZERO,
@@ -1899,6 +3191,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_001),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([1u8; 64]),
             hash: BlockHash::ZERO,
@@ -2029,6 +3327,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_010),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([6u8; 64]),
             hash: BlockHash::ZERO,
@@ -2216,6 +3520,64 @@ mod tests {
         assert_eq!(first.source, BlockSource::Local);
     }
 
+    // ── Bootstrap-phase caps ─────────────────────────────────────────────
+
+    #[test]
+    fn bootstrap_caps_sends_in_trst_and_burns_in_brn() {
+        let store = Arc::new(burst_nullables::NullStore::new());
+        let mut processor = test_processor(0);
+        processor.block_store = Some(store.clone());
+        processor.account_store = Some(store.clone());
+        processor.set_bootstrap_limits(BootstrapLimits {
+            exit_threshold: 50,
+            trst_limit: 100,
+            brn_limit: 10,
+        });
+        let mut frontier = DagFrontier::new();
+
+        let mut open = make_open_block(0);
+        open.trst_balance = 1_000;
+        open.hash = open.compute_hash();
+        assert_eq!(
+            processor.process(&open, &mut frontier),
+            ProcessResult::Accepted
+        );
+        store_block(&store, &open);
+
+        let mut send = make_send_block(open.hash, 0);
+        send.brn_balance = open.brn_balance;
+        send.trst_balance = 899;
+        send.hash = send.compute_hash();
+        assert!(matches!(
+            processor.process(&send, &mut frontier),
+            ProcessResult::Rejected(reason) if reason.contains("TRST amount 101")
+        ));
+        send.trst_balance = 900;
+        send.hash = send.compute_hash();
+        assert_eq!(
+            processor.process(&send, &mut frontier),
+            ProcessResult::Accepted
+        );
+        store_block(&store, &send);
+
+        // A burn is held to the BRN cap, not the TRST one.
+        let mut burn = make_send_block(send.hash, 0);
+        burn.block_type = BlockType::Burn;
+        burn.trst_balance = send.trst_balance;
+        burn.brn_balance = 989;
+        burn.hash = burn.compute_hash();
+        assert!(matches!(
+            processor.process(&burn, &mut frontier),
+            ProcessResult::Rejected(reason) if reason.contains("BRN amount 11")
+        ));
+        burn.brn_balance = 990;
+        burn.hash = burn.compute_hash();
+        assert_eq!(
+            processor.process(&burn, &mut frontier),
+            ProcessResult::Accepted
+        );
+    }
+
     // ── Cascade rollback tests ──────────────────────────────────────────
 
     fn store_block(store: &burst_nullables::NullStore, block: &StateBlock) {
@@ -2393,4 +3755,280 @@ mod tests {
         assert_eq!(rolled[1], send1.hash);
         assert_eq!(frontier.get_head(&test_account()), Some(&open.hash));
     }
+
+    /// A signed block on `account`'s chain with unchanged balances.
+    fn signed_block(
+        block_type: BlockType,
+        account: &WalletAddress,
+        previous: BlockHash,
+        timestamp: u64,
+        recovery: Option<RecoveryData>,
+        signer: &burst_types::KeyPair,
+    ) -> StateBlock {
+        let link = match &recovery {
+            Some(RecoveryData::Rotate { new_key, .. }) => BlockHash::new(*new_key.as_bytes()),
+            _ => BlockHash::ZERO,
+        };
+        let mut block = StateBlock {
+            version: CURRENT_BLOCK_VERSION,
+            block_type,
+            account: account.clone(),
+            previous,
+            representative: test_representative(),
+            brn_balance: 1000,
+            trst_balance: 0,
+            link,
+            origin: TxHash::ZERO,
+            transaction: TxHash::ZERO,
+            timestamp: Timestamp::new(timestamp),
+            params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery,
+            delegation_key: None,
+            extensions: Vec::new(),
+            work: 0,
+            signature: Signature([0u8; 64]),
+            hash: BlockHash::ZERO,
+        };
+        block.hash = block.compute_hash();
+        block.signature = sign_message(block.hash.as_bytes(), &signer.private);
+        block
+    }
+
+    #[test]
+    fn recovery_rotates_signing_key_after_delay() {
+        use burst_store::recovery::{RecoveryConfig, RecoveryStore};
+        use burst_transactions::recovery::CustodianApproval;
+
+        let owner = keypair_from_seed(&[1u8; 32]);
+        let account = derive_address(&owner.public);
+        let custodians: Vec<_> = (2..5u8).map(|s| keypair_from_seed(&[s; 32])).collect();
+        let addresses: Vec<_> = custodians
+            .iter()
+            .map(|k| derive_address(&k.public))
+            .collect();
+        let new_keys = keypair_from_seed(&[9u8; 32]);
+
+        let store = Arc::new(burst_nullables::NullRecoveryStore::new());
+        let mut processor = BlockProcessor::new(0);
+        processor.recovery_store = Some(store.clone());
+        let mut frontier = DagFrontier::new();
+
+        let open = signed_block(
+            BlockType::Open,
+            &account,
+            BlockHash::ZERO,
+            1_000,
+            None,
+            &owner,
+        );
+        assert_eq!(
+            processor.process(&open, &mut frontier),
+            ProcessResult::Accepted
+        );
+        // What the node records once a RecoverySetup block is accepted.
+        store
+            .put_recovery_config(&RecoveryConfig {
+                account: account.clone(),
+                custodians: addresses.clone(),
+                threshold: 2,
+                delay_secs: 500,
+                configured_at: Timestamp::new(1_000),
+            })
+            .unwrap();
+
+        let requested_at = Timestamp::new(2_000);
+        let digest =
+            recovery::recovery_digest(&account, &new_keys.public, &open.hash, requested_at);
+        let approve = |i: usize| CustodianApproval {
+            custodian: addresses[i].clone(),
+            signature: sign_message(&digest, &custodians[i].private),
+        };
+        let rotate = |approvals: Vec<CustodianApproval>| {
+            Some(RecoveryData::Rotate {
+                new_key: new_keys.public.clone(),
+                requested_at,
+                approvals,
+            })
+        };
+
+        let one = signed_block(
+            BlockType::Recovery,
+            &account,
+            open.hash,
+            2_500,
+            rotate(vec![approve(0)]),
+            &new_keys,
+        );
+        assert!(matches!(
+            processor.process(&one, &mut frontier),
+            ProcessResult::Rejected(r) if r.contains("approvals")
+        ));
+        let early = signed_block(
+            BlockType::Recovery,
+            &account,
+            open.hash,
+            2_499,
+            rotate(vec![approve(0), approve(2)]),
+            &new_keys,
+        );
+        assert!(matches!(
+            processor.process(&early, &mut frontier),
+            ProcessResult::Rejected(r) if r.contains("before 2500")
+        ));
+        let rotation = signed_block(
+            BlockType::Recovery,
+            &account,
+            open.hash,
+            2_500,
+            rotate(vec![approve(0), approve(2)]),
+            &new_keys,
+        );
+        assert_eq!(
+            processor.process(&rotation, &mut frontier),
+            ProcessResult::Accepted
+        );
+        store.put_signing_key(&account, &new_keys.public).unwrap();
+
+        // From now on only the new key can extend the chain.
+        let by_old = signed_block(
+            BlockType::ChangeRepresentative,
+            &account,
+            rotation.hash,
+            2_600,
+            None,
+            &owner,
+        );
+        assert_eq!(
+            processor.process(&by_old, &mut frontier),
+            ProcessResult::Rejected("invalid signature".into())
+        );
+        let by_new = signed_block(
+            BlockType::ChangeRepresentative,
+            &account,
+            rotation.hash,
+            2_600,
+            None,
+            &new_keys,
+        );
+        assert_eq!(
+            processor.process(&by_new, &mut frontier),
+            ProcessResult::Accepted
+        );
+    }
+
+    #[test]
+    fn recovery_requires_designated_custodians() {
+        let owner = keypair_from_seed(&[1u8; 32]);
+        let account = derive_address(&owner.public);
+        let new_keys = keypair_from_seed(&[9u8; 32]);
+        let mut processor = BlockProcessor::new(0);
+        processor.recovery_store = Some(Arc::new(burst_nullables::NullRecoveryStore::new()));
+        let mut frontier = DagFrontier::new();
+
+        let open = signed_block(
+            BlockType::Open,
+            &account,
+            BlockHash::ZERO,
+            1_000,
+            None,
+            &owner,
+        );
+        assert_eq!(
+            processor.process(&open, &mut frontier),
+            ProcessResult::Accepted
+        );
+
+        let rotate = Some(RecoveryData::Rotate {
+            new_key: new_keys.public.clone(),
+            requested_at: Timestamp::new(1_000),
+            approvals: Vec::new(),
+        });
+        let block = signed_block(
+            BlockType::Recovery,
+            &account,
+            open.hash,
+            5_000,
+            rotate,
+            &new_keys,
+        );
+        assert_eq!(
+            processor.process(&block, &mut frontier),
+            ProcessResult::Rejected("account has no recovery custodians".into())
+        );
+    }
+
+    #[test]
+    fn delegation_key_rotation_requires_active_delegate() {
+        use burst_store::delegation::{DelegationRecord, DelegationStore};
+        use burst_transactions::delegate::DelegationKey;
+
+        let owner = keypair_from_seed(&[1u8; 32]);
+        let account = derive_address(&owner.public);
+        let delegate = keypair_from_seed(&[2u8; 32]);
+        let stranger = keypair_from_seed(&[3u8; 32]);
+        let store = Arc::new(burst_nullables::NullDelegationStore::new());
+        let mut processor = BlockProcessor::new(0);
+        processor.delegation_store = Some(store.clone());
+        let mut frontier = DagFrontier::new();
+
+        let open = signed_block(
+            BlockType::Open,
+            &account,
+            BlockHash::ZERO,
+            1_000,
+            None,
+            &owner,
+        );
+        assert_eq!(
+            processor.process(&open, &mut frontier),
+            ProcessResult::Accepted
+        );
+
+        let rotation = |to: &burst_types::KeyPair| {
+            let mut block = signed_block(
+                BlockType::RotateDelegationKey,
+                &account,
+                open.hash,
+                2_000,
+                None,
+                &owner,
+            );
+            block.link = BlockHash::new(*to.public.as_bytes());
+            block.delegation_key = Some(DelegationKey {
+                public_key: keypair_from_seed(&[4u8; 32]).public,
+                encrypted_private_key: vec![0u8; 48],
+                sender_x25519_public: [5u8; 32],
+            });
+            block.hash = block.compute_hash();
+            block.signature = sign_message(block.hash.as_bytes(), &owner.private);
+            block
+        };
+
+        assert_eq!(
+            processor.process(&rotation(&delegate), &mut frontier),
+            ProcessResult::Rejected("account has no active delegation to rotate".into())
+        );
+
+        store
+            .put_delegation(&DelegationRecord {
+                delegator: account.clone(),
+                delegate: derive_address(&delegate.public),
+                delegation_public_key: [6u8; 32],
+                generation: 1,
+                created_at: Timestamp::new(1_500),
+                revoked: false,
+            })
+            .unwrap();
+        assert!(matches!(
+            processor.process(&rotation(&stranger), &mut frontier),
+            ProcessResult::Rejected(_)
+        ));
+        assert_eq!(
+            processor.process(&rotation(&delegate), &mut frontier),
+            ProcessResult::Accepted
+        );
+    }
 }
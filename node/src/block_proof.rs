@@ -0,0 +1,167 @@
+//! Light-client block proof generation.
+//!
+//! Assembles a [`BlockProof`] for a block from the local ledger (the
+//! account chain back to the open block) and the signed final votes for the
+//! block, taken from the final vote archive or, once the archive has trimmed
+//! them, from the block's stored quorum certificate. Clients verify it with
+//! `burst_wallet_core::light`.
+
+use burst_ledger::{BlockProof, ProofVote, StateBlock};
+use burst_store::block::BlockStore;
+use burst_store::{FinalVoteStore, QuorumCertificateStore};
+use burst_types::BlockHash;
+
+use crate::error::NodeError;
+use crate::final_votes::FinalVoteArchive;
+use crate::quorum_certificate::load_quorum_certificate;
+
+/// Longest account chain a proof is built for.
+pub const MAX_PROOF_CHAIN_LENGTH: usize = 10_000;
+
+/// Build the proof for `hash`. Fails if the block or any ancestor is
+/// missing (e.g. pruned), the chain is too long, or no final votes for the
+/// block are stored.
+pub fn build_block_proof<S: FinalVoteStore, C: QuorumCertificateStore>(
+    block_store: &dyn BlockStore,
+    archive: &FinalVoteArchive<S>,
+    certificates: &C,
+    hash: &BlockHash,
+) -> Result<BlockProof, NodeError> {
+    let load = |hash: &BlockHash| -> Result<StateBlock, NodeError> {
+        let bytes = block_store
+            .get_block(hash)
+            .map_err(|_| NodeError::ProofUnavailable(format!("block {hash} not found")))?;
+        bincode::deserialize(&bytes).map_err(|e| NodeError::InvalidBlock(e.to_string()))
+    };
+
+    let mut chain = vec![load(hash)?];
+    while let Some(previous) = chain.last().map(|block| block.previous) {
+        if previous.is_zero() {
+            break;
+        }
+        if chain.len() >= MAX_PROOF_CHAIN_LENGTH {
+            return Err(NodeError::ProofUnavailable(format!(
+                "account chain is longer than {MAX_PROOF_CHAIN_LENGTH} blocks"
+            )));
+        }
+        chain.push(load(&previous)?);
+    }
+    chain.reverse();
+
+    let mut votes: Vec<ProofVote> = archive
+        .replay(std::slice::from_ref(hash))?
+        .into_iter()
+        .filter(|vote| vote.is_final)
+        .map(|vote| ProofVote {
+            voter: vote.voter,
+            block_hashes: vote.block_hashes,
+            timestamp: vote.timestamp,
+            signature: vote.signature,
+        })
+        .collect();
+    if votes.is_empty() {
+        if let Some(certificate) = load_quorum_certificate(certificates, hash)? {
+            votes = certificate.proof_votes();
+        }
+    }
+    if votes.is_empty() {
+        return Err(NodeError::ProofUnavailable(format!(
+            "no final votes stored for {hash}"
+        )));
+    }
+
+    Ok(BlockProof { chain, votes })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_ledger::{BlockType, CURRENT_BLOCK_VERSION};
+    use burst_store_lmdb::LmdbEnvironment;
+    use burst_types::{Signature, Timestamp, TxHash, WalletAddress};
+    use burst_wallet_core::{verify_block_proof, RepresentativeSet};
+
+    use crate::wire_message::WireVote;
+
+    fn block(account: &WalletAddress, previous: BlockHash, balance: u128) -> StateBlock {
+        let mut block = StateBlock {
+            version: CURRENT_BLOCK_VERSION,
+            block_type: if previous.is_zero() {
+                BlockType::Open
+            } else {
+                BlockType::Send
+            },
+            account: account.clone(),
+            previous,
+            representative: account.clone(),
+            brn_balance: 0,
+            trst_balance: balance,
+            link: BlockHash::ZERO,
+            origin: TxHash::ZERO,
+            transaction: TxHash::ZERO,
+            timestamp: Timestamp::new(1_000),
+            params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
+            work: 0,
+            signature: Signature([1u8; 64]),
+            hash: BlockHash::ZERO,
+        };
+        block.hash = block.compute_hash();
+        block
+    }
+
+    #[test]
+    fn builds_a_proof_light_clients_accept() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 10 * 1024 * 1024).unwrap();
+        let block_store = env.block_store();
+        let archive = FinalVoteArchive::new(env.final_vote_store(), 64);
+        let certificates = env.quorum_certificate_store();
+
+        let owner = WalletAddress::new("brst_owner");
+        let open = block(&owner, BlockHash::ZERO, 100);
+        let send = block(&owner, open.hash, 40);
+        for b in [&open, &send] {
+            block_store
+                .put_block(&b.hash, &bincode::serialize(b).unwrap())
+                .unwrap();
+        }
+        assert!(matches!(
+            build_block_proof(&block_store, &archive, &certificates, &send.hash),
+            Err(NodeError::ProofUnavailable(_))
+        ));
+
+        let rep = burst_crypto::keypair_from_seed(&[5u8; 32]);
+        let rep_address = burst_crypto::derive_address(&rep.public);
+        let mut vote = ProofVote {
+            voter: rep_address.clone(),
+            block_hashes: vec![send.hash],
+            timestamp: 2_000,
+            signature: Signature([0u8; 64]),
+        };
+        vote.signature = burst_crypto::sign_message(&vote.signed_message(), &rep.private);
+        archive
+            .record(&WireVote {
+                voter: vote.voter,
+                block_hashes: vote.block_hashes,
+                is_final: true,
+                timestamp: vote.timestamp,
+                sequence: 0,
+                signature: vote.signature,
+            })
+            .unwrap();
+
+        let proof = build_block_proof(&block_store, &archive, &certificates, &send.hash).unwrap();
+        assert_eq!(proof.height(), 2);
+        assert_eq!(proof.chain[0].hash, open.hash);
+
+        let reps = RepresentativeSet::new([(rep_address, 10)].into_iter().collect(), 10);
+        let verified = verify_block_proof(&proof, &send.hash, &reps).unwrap();
+        assert_eq!(verified.block.hash, send.hash);
+    }
+}
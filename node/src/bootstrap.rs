@@ -306,6 +306,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([1u8; 64]),
             hash: BlockHash::ZERO,
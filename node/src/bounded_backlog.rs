@@ -4,7 +4,8 @@
 //! exceeds its capacity, the lowest-priority blocks (by PoW difficulty) that
 //! are not in active elections are candidates for proactive rollback.
 
-use burst_types::{BlockHash, WalletAddress};
+use burst_types::memory::entry_bytes;
+use burst_types::{BlockHash, MemoryAccounting, WalletAddress};
 use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Maximum unconfirmed blocks before proactive rollback.
@@ -112,7 +113,7 @@ impl BoundedBacklog {
         }
         let overage = self.entries.len() - self.max_size;
         let mut to_evict = Vec::with_capacity(overage);
-        for ((_, hash), _) in self.by_priority.iter() {
+        for (_, hash) in self.by_priority.keys() {
             if to_evict.len() >= overage {
                 break;
             }
@@ -139,6 +140,19 @@ impl BoundedBacklog {
     }
 }
 
+impl MemoryAccounting for BoundedBacklog {
+    fn element_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn approximate_bytes(&self) -> usize {
+        entry_bytes::<(BlockHash, BacklogEntry)>(self.entries.len())
+            + entry_bytes::<((u64, BlockHash), WalletAddress)>(self.by_priority.len())
+            + entry_bytes::<(WalletAddress, usize)>(self.per_account.len())
+            + entry_bytes::<BlockHash>(self.protected.len())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,365 @@
+//! HTTP callback webhooks.
+//!
+//! When a `[callback]` section is configured, the node POSTs every event on
+//! the selected WebSocket topics to a URL, the way exchange backends already
+//! consume Nano node callbacks. The body is exactly the JSON event the
+//! WebSocket server sends. With a `secret`, each request carries an
+//! `X-Burst-Signature: sha256=<hex>` header holding the HMAC-SHA256 of the
+//! body, so the receiver can reject forged callbacks.
+//!
+//! Deliveries are retried with exponential backoff on connection errors,
+//! `429` and `5xx` responses; other responses are final.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use burst_websocket::{SubscriptionTopic, WsState};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+use crate::error::NodeError;
+use crate::shutdown::ShutdownController;
+
+/// Header carrying the HMAC of the request body.
+pub const SIGNATURE_HEADER: &str = "X-Burst-Signature";
+/// Header naming the event topic.
+pub const TOPIC_HEADER: &str = "X-Burst-Topic";
+
+/// The `[callback]` config section.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CallbackConfig {
+    /// Endpoint that receives the POSTs (`http://` or `https://`).
+    pub url: String,
+    /// Topics to forward.
+    #[serde(default = "default_topics")]
+    pub topics: Vec<SubscriptionTopic>,
+    /// Shared secret for the HMAC signature header. Unsigned when unset.
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Per-request timeout in seconds.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default)]
+    pub retry: RetryPolicy,
+}
+
+/// How failed deliveries are retried.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Total delivery attempts per event, including the first.
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on each further retry.
+    #[serde(default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    /// Upper bound on the delay between retries.
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+}
+
+fn default_topics() -> Vec<SubscriptionTopic> {
+    vec![
+        SubscriptionTopic::Confirmation,
+        SubscriptionTopic::Verification,
+    ]
+}
+
+fn default_timeout_secs() -> u64 {
+    10
+}
+
+fn default_max_attempts() -> u32 {
+    5
+}
+
+fn default_initial_backoff_ms() -> u64 {
+    500
+}
+
+fn default_max_backoff_ms() -> u64 {
+    30_000
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_max_attempts(),
+            initial_backoff_ms: default_initial_backoff_ms(),
+            max_backoff_ms: default_max_backoff_ms(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `retry` (1-based).
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1u64
+            .checked_shl(retry.saturating_sub(1))
+            .unwrap_or(u64::MAX);
+        Duration::from_millis(
+            self.initial_backoff_ms
+                .saturating_mul(factor)
+                .min(self.max_backoff_ms),
+        )
+    }
+}
+
+impl CallbackConfig {
+    /// Reject configurations the dispatcher cannot act on.
+    pub fn validate(&self) -> Result<(), NodeError> {
+        if !(self.url.starts_with("http://") || self.url.starts_with("https://")) {
+            return Err(NodeError::Config(format!(
+                "callback url must be http(s): {}",
+                self.url
+            )));
+        }
+        if self.topics.is_empty() {
+            return Err(NodeError::Config(
+                "callback topics must not be empty".into(),
+            ));
+        }
+        if self.retry.max_attempts == 0 {
+            return Err(NodeError::Config(
+                "callback retry.max_attempts must be at least 1".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Hex HMAC-SHA256 of `body` under `secret`, as sent in [`SIGNATURE_HEADER`]
+/// (after the `sha256=` prefix).
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Forwards WebSocket events to the configured callback URL.
+pub struct CallbackDispatcher {
+    config: CallbackConfig,
+    http: reqwest::Client,
+}
+
+impl CallbackDispatcher {
+    pub fn new(config: CallbackConfig) -> Result<Self, NodeError> {
+        config.validate()?;
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|e| NodeError::Config(format!("failed to create callback client: {e}")))?;
+        Ok(Self { config, http })
+    }
+
+    /// Spawn one delivery task per configured topic. Events on a topic are
+    /// delivered in order; a slow endpoint only delays its own topic.
+    pub fn spawn(self, ws_state: &WsState, shutdown: &ShutdownController) -> Vec<JoinHandle<()>> {
+        let dispatcher = Arc::new(self);
+        dispatcher
+            .config
+            .topics
+            .iter()
+            .map(|topic| {
+                let rx = ws_state.sender_for(topic).subscribe();
+                let mut shutdown_rx = shutdown.subscribe();
+                let dispatcher = Arc::clone(&dispatcher);
+                let topic = topic.clone();
+                tokio::spawn(async move {
+                    tokio::select! {
+                        biased;
+                        _ = shutdown_rx.recv() => {
+                            tracing::debug!(%topic, "callback task shutting down");
+                        }
+                        _ = dispatcher.forward(&topic, rx) => {}
+                    }
+                })
+            })
+            .collect()
+    }
+
+    async fn forward(&self, topic: &SubscriptionTopic, mut rx: broadcast::Receiver<String>) {
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    if let Err(e) = self.deliver(topic, event).await {
+                        tracing::warn!(%topic, url = %self.config.url, "callback dropped: {e}");
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    tracing::warn!(%topic, missed, "callback fell behind, events skipped");
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+
+    /// POST one event, retrying per the policy. Returns the last failure.
+    pub async fn deliver(&self, topic: &SubscriptionTopic, body: String) -> Result<(), String> {
+        let signature = self
+            .config
+            .secret
+            .as_deref()
+            .map(|secret| format!("sha256={}", sign_payload(secret, body.as_bytes())));
+
+        let mut attempt = 1;
+        loop {
+            let mut request = self
+                .http
+                .post(&self.config.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(TOPIC_HEADER, topic.to_string())
+                .body(body.clone());
+            if let Some(signature) = &signature {
+                request = request.header(SIGNATURE_HEADER, signature);
+            }
+
+            let error = match request.send().await {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => {
+                    let status = response.status();
+                    if !(status.is_server_error() || status.as_u16() == 429) {
+                        return Err(format!("endpoint returned HTTP {status}"));
+                    }
+                    format!("endpoint returned HTTP {status}")
+                }
+                Err(e) => format!("request failed: {e}"),
+            };
+
+            if attempt >= self.config.retry.max_attempts {
+                return Err(format!("{error} (after {attempt} attempts)"));
+            }
+            tracing::debug!(%topic, attempt, "callback delivery failed, retrying: {error}");
+            tokio::time::sleep(self.config.retry.backoff(attempt)).await;
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            initial_backoff_ms: 100,
+            max_backoff_ms: 1_000,
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(4), Duration::from_millis(800));
+        assert_eq!(policy.backoff(5), Duration::from_millis(1_000));
+        assert_eq!(policy.backoff(80), Duration::from_millis(1_000));
+    }
+
+    #[test]
+    fn config_parses_with_defaults_and_validates() {
+        let config: CallbackConfig = toml::from_str(r#"url = "https://example.com/hook""#).unwrap();
+        assert_eq!(config.topics, default_topics());
+        assert_eq!(config.retry, RetryPolicy::default());
+        assert!(config.validate().is_ok());
+
+        let bad: CallbackConfig = toml::from_str(r#"url = "ftp://example.com""#).unwrap();
+        assert!(matches!(bad.validate(), Err(NodeError::Config(_))));
+    }
+
+    #[test]
+    fn signature_is_hmac_sha256() {
+        // RFC 4231 test case 2.
+        assert_eq!(
+            sign_payload("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    /// Answer each connection with the next status, returning the raw requests.
+    async fn serve(statuses: Vec<u16>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for status in statuses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 8192];
+                let mut len = 0;
+                loop {
+                    let n = socket.read(&mut buf[len..]).await.unwrap();
+                    len += n;
+                    let text = String::from_utf8_lossy(&buf[..len]).to_string();
+                    if let Some(end) = text.find("\r\n\r\n") {
+                        let body_len = text
+                            .lines()
+                            .find_map(|l| {
+                                l.to_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if len >= end + 4 + body_len || n == 0 {
+                            requests.push(text);
+                            break;
+                        }
+                    }
+                }
+                let response = format!(
+                    "HTTP/1.1 {status} X\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    fn dispatcher(url: String, max_attempts: u32) -> CallbackDispatcher {
+        CallbackDispatcher::new(CallbackConfig {
+            url,
+            topics: default_topics(),
+            secret: Some("s3cret".into()),
+            timeout_secs: 5,
+            retry: RetryPolicy {
+                max_attempts,
+                initial_backoff_ms: 1,
+                max_backoff_ms: 1,
+            },
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors_and_signs_the_body() {
+        let (url, server) = serve(vec![503, 200]).await;
+        let body = r#"{"topic":"confirmation"}"#.to_string();
+        dispatcher(url, 3)
+            .deliver(&SubscriptionTopic::Confirmation, body.clone())
+            .await
+            .unwrap();
+
+        let requests = server.await.unwrap();
+        assert_eq!(requests.len(), 2);
+        let expected = format!("sha256={}", sign_payload("s3cret", body.as_bytes()));
+        for request in &requests {
+            let lower = request.to_lowercase();
+            assert!(lower.contains(&format!("x-burst-signature: {expected}")));
+            assert!(lower.contains("x-burst-topic: confirmation"));
+            assert!(request.ends_with(&body));
+        }
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        let (url, server) = serve(vec![400]).await;
+        let result = dispatcher(url, 3)
+            .deliver(&SubscriptionTopic::Verification, "{}".into())
+            .await;
+        assert!(result.unwrap_err().contains("400"));
+        assert_eq!(server.await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,363 @@
+//! Taking, signing and collecting ledger checkpoints.
+//!
+//! The cementation task counts the ledger's cemented blocks with a
+//! [`CheckpointTrigger`] and digests the cemented ledger each time the count
+//! reaches a multiple of [`NodeConfig::checkpoint_interval`](crate::NodeConfig).
+//! A representative node signs the digest and floods the [`CheckpointVote`];
+//! every node collects the votes it receives per digest in the checkpoint
+//! store. See [`burst_ledger::checkpoint`] for what a digest commits to.
+
+use burst_ledger::{CementedAccount, Checkpoint, CheckpointDigest, CheckpointVote, StateBlock};
+use burst_store::account::AccountStore;
+use burst_store::block::BlockStore;
+use burst_store::{CheckpointStore, StoreError};
+use burst_types::{BlockHash, WalletAddress};
+
+/// Cemented blocks between two checkpoints by default.
+pub const DEFAULT_CHECKPOINT_INTERVAL: u64 = 10_000;
+
+/// Digest the cemented state of the ledger.
+///
+/// The frontier of an account is the block at its confirmation height, so
+/// blocks still awaiting confirmation are left out. `cap` holds one
+/// account at a lower height than its confirmation height, for a digest
+/// taken part way through cementing that account's blocks.
+pub fn take_checkpoint_digest<A: AccountStore, B: BlockStore>(
+    accounts: &A,
+    blocks: &B,
+    cap: Option<(&WalletAddress, u64)>,
+) -> Result<CheckpointDigest, StoreError> {
+    let accounts = accounts.iter_accounts()?;
+    let mut cemented = Vec::with_capacity(accounts.len());
+    for info in &accounts {
+        let height = match cap {
+            Some((address, height)) if *address == info.address => height,
+            _ => info.confirmation_height,
+        };
+        if height == 0 {
+            continue;
+        }
+        let frontier = if height == info.block_count {
+            info.head
+        } else {
+            blocks
+                .block_at_height(&info.address, height)?
+                .ok_or_else(|| {
+                    StoreError::NotFound(format!("block {height} of {}", info.address))
+                })?
+        };
+        cemented.push(CementedAccount {
+            address: info.address.clone(),
+            height,
+            frontier,
+        });
+    }
+    Ok(CheckpointDigest::from_cemented(&cemented))
+}
+
+/// Blocks cemented across the ledger: the sum of every account's
+/// confirmation height.
+pub fn ledger_cemented_count<A: AccountStore>(accounts: &A) -> Result<u64, StoreError> {
+    Ok(accounts.iter_accounts()?.iter().fold(0u64, |total, info| {
+        total.saturating_add(info.confirmation_height)
+    }))
+}
+
+/// Follows the ledger's cemented count as blocks are cemented and digests
+/// the ledger at every multiple of the interval.
+pub struct CheckpointTrigger {
+    interval: u64,
+    cemented_count: u64,
+}
+
+impl CheckpointTrigger {
+    /// A trigger for a ledger with `cemented_count` blocks cemented so far.
+    pub fn new(interval: u64, cemented_count: u64) -> Self {
+        Self {
+            interval: interval.max(1),
+            cemented_count,
+        }
+    }
+
+    /// Record that `blocks_cemented` blocks of one account were just
+    /// cemented, up to `frontier` at `new_height`, and digest the ledger as
+    /// it stood at each multiple of the interval passed on the way.
+    ///
+    /// Must be called after each account is cemented and before the next
+    /// one is, so that only this account can be past the multiple.
+    pub fn cemented<A: AccountStore, B: BlockStore>(
+        &mut self,
+        accounts: &A,
+        blocks: &B,
+        frontier: &BlockHash,
+        blocks_cemented: u64,
+        new_height: u64,
+    ) -> Result<Vec<CheckpointDigest>, StoreError> {
+        let before = self.cemented_count;
+        let after = before.saturating_add(blocks_cemented);
+        self.cemented_count = after;
+
+        let mut at = (before / self.interval + 1).saturating_mul(self.interval);
+        if at > after {
+            return Ok(Vec::new());
+        }
+        let block: StateBlock = bincode::deserialize(&blocks.get_block(frontier)?)
+            .map_err(|e| StoreError::Serialization(e.to_string()))?;
+        let mut digests = Vec::new();
+        while at <= after {
+            let height = new_height - (after - at);
+            digests.push(take_checkpoint_digest(
+                accounts,
+                blocks,
+                Some((&block.account, height)),
+            )?);
+            match at.checked_add(self.interval) {
+                Some(next) => at = next,
+                None => break,
+            }
+        }
+        Ok(digests)
+    }
+}
+
+/// Add a vote to the checkpoint for its digest, creating the checkpoint on
+/// the first vote. Returns the updated checkpoint, or `None` if the vote was
+/// already recorded.
+///
+/// The vote's signature is not checked here.
+pub fn record_checkpoint_vote<C: CheckpointStore>(
+    store: &C,
+    vote: CheckpointVote,
+    now_secs: u64,
+) -> Result<Option<Checkpoint>, StoreError> {
+    let digest = vote.digest;
+    let key = digest.hash();
+    let mut checkpoint = match store.get_checkpoint(digest.cemented_count, &key) {
+        Ok(bytes) => bincode::deserialize::<Checkpoint>(&bytes)
+            .map_err(|e| StoreError::Serialization(e.to_string()))?,
+        Err(StoreError::NotFound(_)) => Checkpoint::new(digest, now_secs),
+        Err(e) => return Err(e),
+    };
+    if !checkpoint.add_vote(vote) {
+        return Ok(None);
+    }
+    let bytes =
+        bincode::serialize(&checkpoint).map_err(|e| StoreError::Serialization(e.to_string()))?;
+    store.put_checkpoint(digest.cemented_count, &key, &bytes)?;
+    Ok(Some(checkpoint))
+}
+
+/// Up to `limit` stored checkpoints, highest cemented count first.
+/// Entries that fail to decode are skipped.
+pub fn recent_checkpoints<C: CheckpointStore>(
+    store: &C,
+    limit: usize,
+) -> Result<Vec<Checkpoint>, StoreError> {
+    Ok(store
+        .recent_checkpoints(limit)?
+        .iter()
+        .filter_map(|bytes| bincode::deserialize::<Checkpoint>(bytes).ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::confirmation_processor::{CementResult, ConfirmationProcessor, LmdbChainWalker};
+    use burst_ledger::{BlockType, CURRENT_BLOCK_VERSION};
+    use burst_store::account::AccountInfo;
+    use burst_store_lmdb::LmdbEnvironment;
+    use burst_types::{Signature, Timestamp, TxHash, WalletState};
+    use std::sync::Arc;
+
+    fn block(account: &WalletAddress, previous: BlockHash, height: u64) -> StateBlock {
+        let mut block = StateBlock {
+            version: CURRENT_BLOCK_VERSION,
+            block_type: BlockType::Open,
+            account: account.clone(),
+            previous,
+            representative: account.clone(),
+            brn_balance: 0,
+            trst_balance: height as u128,
+            link: BlockHash::ZERO,
+            origin: TxHash::ZERO,
+            transaction: TxHash::ZERO,
+            timestamp: Timestamp::new(height),
+            params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
+            work: 0,
+            signature: Signature([1u8; 64]),
+            hash: BlockHash::ZERO,
+        };
+        block.hash = block.compute_hash();
+        block
+    }
+
+    /// The ledger of one node, cementing through the node's own processor.
+    struct TestNode {
+        _dir: tempfile::TempDir,
+        env: LmdbEnvironment,
+        trigger: CheckpointTrigger,
+    }
+
+    impl TestNode {
+        fn new(interval: u64) -> Self {
+            let dir = tempfile::tempdir().unwrap();
+            let env = LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+            Self {
+                _dir: dir,
+                env,
+                trigger: CheckpointTrigger::new(interval, 0),
+            }
+        }
+
+        /// Apply the next `count` blocks of `name`'s chain, uncemented.
+        fn receive(&self, name: &str, count: u64) {
+            let address = WalletAddress::new(format!("brst_{name}"));
+            let mut info = self
+                .env
+                .account_store()
+                .get_account(&address)
+                .unwrap_or(AccountInfo {
+                    address: address.clone(),
+                    state: WalletState::Unverified,
+                    verified_at: None,
+                    head: BlockHash::ZERO,
+                    block_count: 0,
+                    confirmation_height: 0,
+                    representative: address.clone(),
+                    total_brn_burned: 0,
+                    total_brn_staked: 0,
+                    trst_balance: 0,
+                    expired_trst: 0,
+                    revoked_trst: 0,
+                    epoch: 0,
+                });
+            for height in info.block_count + 1..=info.block_count + count {
+                let b = block(&address, info.head, height);
+                self.env
+                    .block_store()
+                    .put_block_with_account(&b.hash, &bincode::serialize(&b).unwrap(), &address)
+                    .unwrap();
+                info.head = b.hash;
+                info.block_count = height;
+                info.trst_balance = b.trst_balance;
+            }
+            self.env.account_store().put_account(&info).unwrap();
+        }
+
+        /// Cement `name`'s chain up to `height`, returning the digests
+        /// taken on the way.
+        fn cement(&mut self, name: &str, height: u64) -> Vec<CheckpointDigest> {
+            let address = WalletAddress::new(format!("brst_{name}"));
+            let hash = self
+                .env
+                .block_store()
+                .block_at_height(&address, height)
+                .unwrap()
+                .unwrap();
+            let mut walker = LmdbChainWalker::new(
+                Arc::new(self.env.account_store()),
+                Arc::new(self.env.block_store()),
+            );
+            let (result, _) = ConfirmationProcessor.process(&hash, &mut walker);
+            let CementResult::Cemented {
+                blocks_cemented,
+                new_height,
+            } = result
+            else {
+                panic!("nothing cemented: {result:?}");
+            };
+            self.trigger
+                .cemented(
+                    &self.env.account_store(),
+                    &self.env.block_store(),
+                    &hash,
+                    blocks_cemented,
+                    new_height,
+                )
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn two_nodes_take_the_same_digests_while_blocks_keep_arriving() {
+        let mut a = TestNode::new(4);
+        let mut b = TestNode::new(4);
+
+        // B has received more blocks than A, and the two cement in a
+        // different order. Each digest is taken at exactly four cemented
+        // blocks, with bob held back to where he stood at that count.
+        a.receive("alice", 3);
+        a.receive("bob", 2);
+        b.receive("alice", 3);
+        b.receive("bob", 3);
+        b.receive("carol", 1);
+        assert!(a.cement("alice", 3).is_empty());
+        let a_first = a.cement("bob", 2);
+        assert!(b.cement("bob", 1).is_empty());
+        let b_first = b.cement("alice", 3);
+        assert_eq!(a_first.len(), 1);
+        assert_eq!(a_first, b_first);
+        assert_eq!(a_first[0].cemented_count, 4);
+
+        // More blocks arrive; carol's only ever reaches B, uncemented.
+        a.receive("alice", 2);
+        a.receive("bob", 1);
+        b.receive("alice", 3);
+        b.receive("carol", 1);
+        assert!(a.cement("alice", 5).is_empty());
+        let a_second = a.cement("bob", 3);
+        assert!(b.cement("bob", 3).is_empty());
+        let b_second = b.cement("alice", 5);
+        assert_eq!(a_second.len(), 1);
+        assert_eq!(a_second, b_second);
+        assert_eq!(a_second[0].cemented_count, 8);
+
+        // The digest is the cemented ledger as it stood at the count.
+        let alice = WalletAddress::new("brst_alice");
+        let bob = WalletAddress::new("brst_bob");
+        let frontier = |address: &WalletAddress, height| {
+            a.env
+                .block_store()
+                .block_at_height(address, height)
+                .unwrap()
+                .unwrap()
+        };
+        assert!(a_second[0].covers(&[
+            CementedAccount {
+                address: alice.clone(),
+                height: 5,
+                frontier: frontier(&alice, 5),
+            },
+            CementedAccount {
+                address: bob.clone(),
+                height: 3,
+                frontier: frontier(&bob, 3),
+            },
+        ]));
+        assert_eq!(ledger_cemented_count(&b.env.account_store()).unwrap(), 8);
+    }
+
+    #[test]
+    fn votes_collect_per_digest() {
+        let mut node = TestNode::new(1);
+        node.receive("alice", 1);
+        let digest = node.cement("alice", 1)[0];
+
+        let store = node.env.checkpoint_store();
+        let rep = burst_crypto::generate_keypair();
+        let vote = CheckpointVote::sign(digest, &rep.private);
+        let checkpoint = record_checkpoint_vote(&store, vote.clone(), 5)
+            .unwrap()
+            .unwrap();
+        assert_eq!(checkpoint.votes.len(), 1);
+        assert_eq!(record_checkpoint_vote(&store, vote, 6).unwrap(), None);
+        assert_eq!(recent_checkpoints(&store, 10).unwrap(), vec![checkpoint]);
+    }
+}
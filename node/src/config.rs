@@ -3,8 +3,23 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-use burst_types::{NetworkId, ProtocolParams};
-
+use burst_consensus::{ElectionLimitConfig, DEFAULT_VOTE_CACHE_CAPACITY};
+use burst_rpc::{AdminRpcConfig, IpcConfig};
+use burst_types::{NetworkId, ProtocolParams, WalletAddress};
+use burst_utils::WatchdogConfig;
+
+use crate::alerts::AlertsConfig;
+use crate::block_journal::JournalConfig;
+use crate::callback::CallbackConfig;
+use crate::checkpoint::DEFAULT_CHECKPOINT_INTERVAL;
+use crate::confirmation_history::DEFAULT_CONFIRMATION_HISTORY_SIZE;
+use crate::dev_controls::DevOverrides;
+use crate::gossip::GossipConfig;
+use crate::invariants::InvariantConfig;
+use crate::logging::LoggingConfig;
+use crate::mailbox::MailboxConfig;
+use crate::overload::OverloadConfig;
+use crate::storage_guard::StorageConfig;
 use crate::NodeError;
 
 /// Configuration for a BURST node.
@@ -29,6 +44,20 @@ pub struct NodeConfig {
     #[serde(default = "default_max_peers")]
     pub max_peers: usize,
 
+    /// Addresses or CIDR ranges (`"10.0.0.0/8"`) allowed to connect. When
+    /// non-empty, all other peers are refused and subnet limits don't apply.
+    #[serde(default)]
+    pub peer_allowlist: Vec<String>,
+
+    /// Addresses or CIDR ranges never connected to.
+    #[serde(default)]
+    pub peer_denylist: Vec<String>,
+
+    /// Maximum connected peers sharing a /24 (IPv4) or /48 (IPv6) subnet.
+    /// `0` disables the limit.
+    #[serde(default = "default_max_peers_per_subnet")]
+    pub max_peers_per_subnet: usize,
+
     /// Port to listen on for P2P connections.
     #[serde(default = "default_p2p_port")]
     pub port: u16,
@@ -41,10 +70,21 @@ pub struct NodeConfig {
     #[serde(default = "default_true")]
     pub enable_rpc: bool,
 
-    /// RPC port (if enabled).
+    /// RPC port (if enabled). The public listener serves read and submit
+    /// actions only.
     #[serde(default = "default_rpc_port")]
     pub rpc_port: u16,
 
+    /// Loopback-only listener for peer bans, key rotation and the other
+    /// administrative actions.
+    #[serde(default)]
+    pub admin_rpc: AdminRpcConfig,
+
+    /// Unix socket serving the public RPC actions to local wallets and
+    /// scripts, guarded by file permissions. Works with `enable_rpc` off.
+    #[serde(default)]
+    pub ipc: IpcConfig,
+
     /// Whether to enable the WebSocket server.
     #[serde(default)]
     pub enable_websocket: bool,
@@ -65,6 +105,10 @@ pub struct NodeConfig {
     #[serde(default = "default_log_level")]
     pub log_level: String,
 
+    /// Per-module levels and file output with rotation.
+    #[serde(default)]
+    pub logging: LoggingConfig,
+
     /// Number of work threads for PoW validation.
     #[serde(default = "default_work_threads")]
     pub work_threads: usize,
@@ -88,6 +132,91 @@ pub struct NodeConfig {
     /// If only IP is given, the P2P port is used. UPnP overrides this when active.
     #[serde(default)]
     pub advertise_address: Option<String>,
+
+    /// Pending receives below this amount (raw TRST) are left out of
+    /// `pending_sweep` results so dust sends don't cost the wallet a block
+    /// each. `0` sweeps everything. Written as a decimal string in TOML,
+    /// which has no 128-bit integers.
+    #[serde(default, with = "u128_string")]
+    pub receive_minimum: u128,
+
+    /// Keystore file holding the representative (vote signing) key. Created
+    /// on first start if missing; the passphrase is read from the
+    /// `BURST_REPRESENTATIVE_KEY_PASSWORD` environment variable. When unset,
+    /// the node votes with a transient key generated at startup.
+    #[serde(default)]
+    pub representative_key_file: Option<PathBuf>,
+
+    /// HTTP webhook for confirmations and verification events. Disabled
+    /// when the `[callback]` section is absent.
+    #[serde(default)]
+    pub callback: Option<CallbackConfig>,
+
+    /// Operator alerts posted to webhooks or PagerDuty. Disabled when the
+    /// `[alerts]` section is absent.
+    #[serde(default)]
+    pub alerts: Option<AlertsConfig>,
+
+    /// Active election limit and load-based tuning of it.
+    #[serde(default)]
+    pub active_elections: ElectionLimitConfig,
+
+    /// Number of recent confirmations kept for the `confirmation_history`
+    /// RPC. `0` disables recording.
+    #[serde(default = "default_confirmation_history_size")]
+    pub confirmation_history_size: usize,
+
+    /// Number of block hashes whose early votes are held until their
+    /// election starts. When full, the least-weighted hash is evicted.
+    #[serde(default = "default_vote_cache_capacity")]
+    pub vote_cache_capacity: usize,
+
+    /// Account whose key may sign epoch blocks in addition to the genesis
+    /// account, so ledger upgrades don't require the genesis key online.
+    #[serde(default)]
+    pub epoch_upgrade_key: Option<WalletAddress>,
+
+    /// Thresholds above which slow operations are logged and reported by
+    /// `node_watchdog`.
+    #[serde(default)]
+    pub watchdog: WatchdogConfig,
+
+    /// When and how the node degrades under overload.
+    #[serde(default)]
+    pub overload: OverloadConfig,
+
+    /// LMDB map size and growth, and the disk and map thresholds at which
+    /// the node warns and then stops writing.
+    #[serde(default)]
+    pub storage: StorageConfig,
+
+    /// TTL, hop limit and fanout for relayed blocks, checkpoint votes,
+    /// revocation notices and direct messages.
+    #[serde(default)]
+    pub gossip: GossipConfig,
+
+    /// Limits on the encrypted direct messages held for wallets.
+    #[serde(default)]
+    pub direct_messages: MailboxConfig,
+
+    /// Write-ahead journaling of accepted blocks and its fsync policy.
+    #[serde(default)]
+    pub journal: JournalConfig,
+
+    /// Economic invariant checks after each block write batch, for
+    /// debugging. Off by default.
+    #[serde(default)]
+    pub invariants: InvariantConfig,
+
+    /// Cemented blocks between two ledger checkpoints. `0` disables taking
+    /// and signing checkpoints; votes from peers are still collected.
+    #[serde(default = "default_checkpoint_interval")]
+    pub checkpoint_interval: u64,
+
+    /// Instant confirmation and clock warp for testing. Refused at start-up
+    /// on any network but dev.
+    #[serde(default)]
+    pub dev_overrides: DevOverrides,
 }
 
 // ── Serde default helpers ──────────────────────────────────────────────
@@ -104,6 +233,10 @@ fn default_max_peers() -> usize {
     50
 }
 
+fn default_max_peers_per_subnet() -> usize {
+    burst_network::DEFAULT_MAX_PEERS_PER_SUBNET
+}
+
 fn default_p2p_port() -> u16 {
     NetworkId::Dev.default_port()
 }
@@ -132,6 +265,32 @@ fn default_work_threads() -> usize {
     1
 }
 
+fn default_confirmation_history_size() -> usize {
+    DEFAULT_CONFIRMATION_HISTORY_SIZE
+}
+
+fn default_vote_cache_capacity() -> usize {
+    DEFAULT_VOTE_CACHE_CAPACITY
+}
+
+fn default_checkpoint_interval() -> u64 {
+    DEFAULT_CHECKPOINT_INTERVAL
+}
+
+/// (De)serialize a `u128` as a decimal string.
+mod u128_string {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 // ── Impl ───────────────────────────────────────────────────────────────
 
 impl NodeConfig {
@@ -160,20 +319,43 @@ impl Default for NodeConfig {
             data_dir: default_data_dir(),
             params: ProtocolParams::default(),
             max_peers: default_max_peers(),
+            peer_allowlist: Vec::new(),
+            peer_denylist: Vec::new(),
+            max_peers_per_subnet: default_max_peers_per_subnet(),
             port: default_p2p_port(),
             enable_verification: false,
             enable_rpc: default_true(),
             rpc_port: default_rpc_port(),
+            admin_rpc: AdminRpcConfig::default(),
+            ipc: IpcConfig::default(),
             enable_websocket: false,
             websocket_port: default_ws_port(),
             bootstrap_peers: Vec::new(),
             log_format: default_log_format(),
             log_level: default_log_level(),
+            logging: LoggingConfig::default(),
             work_threads: default_work_threads(),
             enable_metrics: false,
             enable_faucet: false,
             enable_upnp: true,
             advertise_address: None,
+            receive_minimum: 0,
+            representative_key_file: None,
+            callback: None,
+            alerts: None,
+            active_elections: ElectionLimitConfig::default(),
+            confirmation_history_size: default_confirmation_history_size(),
+            vote_cache_capacity: default_vote_cache_capacity(),
+            epoch_upgrade_key: None,
+            watchdog: WatchdogConfig::default(),
+            overload: OverloadConfig::default(),
+            storage: StorageConfig::default(),
+            gossip: GossipConfig::default(),
+            direct_messages: MailboxConfig::default(),
+            journal: JournalConfig::default(),
+            invariants: InvariantConfig::default(),
+            checkpoint_interval: default_checkpoint_interval(),
+            dev_overrides: DevOverrides::default(),
         }
     }
 }
@@ -211,6 +393,135 @@ mod tests {
         assert_eq!(config.log_format, "human"); // default
     }
 
+    #[test]
+    fn receive_minimum_is_a_decimal_string() {
+        let config =
+            NodeConfig::from_toml_str(r#"receive_minimum = "1000000000000000000000""#).unwrap();
+        assert_eq!(config.receive_minimum, 1_000_000_000_000_000_000_000);
+        let parsed = NodeConfig::from_toml_str(&config.to_toml_string()).unwrap();
+        assert_eq!(parsed.receive_minimum, config.receive_minimum);
+    }
+
+    #[test]
+    fn representative_key_file_roundtrips() {
+        let config =
+            NodeConfig::from_toml_str(r#"representative_key_file = "/var/burst/rep.json""#)
+                .unwrap();
+        assert_eq!(
+            config.representative_key_file,
+            Some(PathBuf::from("/var/burst/rep.json"))
+        );
+        let parsed = NodeConfig::from_toml_str(&config.to_toml_string()).unwrap();
+        assert_eq!(
+            parsed.representative_key_file,
+            config.representative_key_file
+        );
+        assert!(NodeConfig::default().representative_key_file.is_none());
+    }
+
+    #[test]
+    fn callback_section_roundtrips() {
+        let toml = r#"
+            rpc_port = 9999
+
+            [callback]
+            url = "https://exchange.example/burst"
+            topics = ["confirmation"]
+            secret = "hunter2"
+
+            [callback.retry]
+            max_attempts = 3
+        "#;
+        let config = NodeConfig::from_toml_str(toml).unwrap();
+        let callback = config.callback.as_ref().unwrap();
+        assert_eq!(callback.url, "https://exchange.example/burst");
+        assert_eq!(
+            callback.topics,
+            vec![burst_websocket::SubscriptionTopic::Confirmation]
+        );
+        assert_eq!(callback.retry.max_attempts, 3);
+        assert_eq!(callback.retry.initial_backoff_ms, 500);
+
+        let parsed = NodeConfig::from_toml_str(&config.to_toml_string()).unwrap();
+        assert_eq!(parsed.callback.unwrap().secret.as_deref(), Some("hunter2"));
+        assert!(NodeConfig::default().callback.is_none());
+    }
+
+    #[test]
+    fn alerts_section_roundtrips() {
+        let toml = r#"
+            [alerts]
+            min_peers = 5
+
+            [[alerts.endpoints]]
+            kind = "pagerduty"
+            routing_key = "abc123"
+        "#;
+        let config = NodeConfig::from_toml_str(toml).unwrap();
+        let alerts = config.alerts.as_ref().unwrap();
+        assert_eq!(alerts.min_peers, 5);
+        assert_eq!(alerts.no_confirmation_minutes, 10);
+        assert_eq!(alerts.endpoints.len(), 1);
+
+        let parsed = NodeConfig::from_toml_str(&config.to_toml_string()).unwrap();
+        assert_eq!(parsed.alerts.unwrap().endpoints, alerts.endpoints);
+        assert!(NodeConfig::default().alerts.is_none());
+    }
+
+    #[test]
+    fn active_elections_section_overrides_defaults() {
+        let config = NodeConfig::from_toml_str(
+            r#"
+            [active_elections]
+            ceiling = 800
+            auto_tune = false
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.active_elections.ceiling, 800);
+        assert!(!config.active_elections.auto_tune);
+        assert_eq!(config.active_elections.floor, 500);
+        assert!(NodeConfig::default().active_elections.auto_tune);
+    }
+
+    #[test]
+    fn admin_rpc_is_configured_apart_from_public_rpc() {
+        let config = NodeConfig::from_toml_str(
+            r#"
+            rpc_port = 9999
+
+            [admin_rpc]
+            port = 9998
+            auth_token = "s3cret"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.rpc_port, 9999);
+        assert!(config.admin_rpc.enabled);
+        assert_eq!(config.admin_rpc.port, 9998);
+        let parsed = NodeConfig::from_toml_str(&config.to_toml_string()).unwrap();
+        assert_eq!(parsed.admin_rpc.auth_token.as_deref(), Some("s3cret"));
+        assert_eq!(NodeConfig::default().admin_rpc.port, 7079);
+    }
+
+    #[test]
+    fn ipc_section_takes_an_octal_mode() {
+        let config = NodeConfig::from_toml_str(
+            r#"
+            [ipc]
+            enabled = true
+            path = "/run/burst/node.ipc"
+            mode = 0o660
+        "#,
+        )
+        .unwrap();
+        assert!(config.ipc.enabled);
+        assert_eq!(config.ipc.path, Some(PathBuf::from("/run/burst/node.ipc")));
+        assert_eq!(config.ipc.mode, 0o660);
+        assert!(!NodeConfig::default().ipc.enabled);
+        assert_eq!(NodeConfig::default().ipc.mode, 0o600);
+    }
+
     #[test]
     fn missing_file_returns_config_error() {
         let result = NodeConfig::from_toml_file("/nonexistent/burst.toml");
@@ -218,4 +529,58 @@ mod tests {
         let err = result.unwrap_err();
         assert!(matches!(err, NodeError::Config(_)));
     }
+
+    #[test]
+    fn confirmation_history_size_defaults_and_overrides() {
+        assert_eq!(
+            NodeConfig::default().confirmation_history_size,
+            DEFAULT_CONFIRMATION_HISTORY_SIZE
+        );
+        let config = NodeConfig::from_toml_str("confirmation_history_size = 0").unwrap();
+        assert_eq!(config.confirmation_history_size, 0);
+    }
+
+    #[test]
+    fn vote_cache_capacity_defaults_and_overrides() {
+        assert_eq!(
+            NodeConfig::default().vote_cache_capacity,
+            DEFAULT_VOTE_CACHE_CAPACITY
+        );
+        let config = NodeConfig::from_toml_str("vote_cache_capacity = 1024").unwrap();
+        assert_eq!(config.vote_cache_capacity, 1024);
+    }
+
+    #[test]
+    fn gossip_section_overrides_only_given_fields() {
+        let config = NodeConfig::from_toml_str("[gossip]\nttl = 2\nmax_fanout = 6\n").unwrap();
+        assert_eq!(config.gossip.ttl, 2);
+        assert_eq!(config.gossip.max_fanout, 6);
+        assert_eq!(config.gossip.max_hops, GossipConfig::default().max_hops);
+    }
+
+    #[test]
+    fn invariant_checks_are_off_unless_enabled() {
+        assert!(!NodeConfig::default().invariants.enabled);
+        let config =
+            NodeConfig::from_toml_str("[invariants]\nenabled = true\nhalt = true\n").unwrap();
+        assert!(config.invariants.enabled);
+        assert!(config.invariants.halt);
+        assert_eq!(config.invariants.scan_interval, 1);
+    }
+
+    #[test]
+    fn direct_messages_section_overrides_only_given_fields() {
+        let config = NodeConfig::from_toml_str(
+            "[direct_messages]
+per_recipient = 8
+",
+        )
+        .unwrap();
+        assert!(config.direct_messages.enabled);
+        assert_eq!(config.direct_messages.per_recipient, 8);
+        assert_eq!(
+            config.direct_messages.capacity,
+            MailboxConfig::default().capacity
+        );
+    }
 }
@@ -0,0 +1,178 @@
+//! Bounded history of recent confirmations with duration statistics.
+//!
+//! The confirmation task records one entry per confirmed election. Average
+//! and p95 election durations over the history are the main figures
+//! operators compare node health with, so the ring is persisted to the meta
+//! store periodically and on shutdown instead of starting empty after every
+//! restart.
+
+use std::collections::VecDeque;
+
+use burst_ledger::StateBlock;
+use burst_types::{BlockHash, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+/// Meta store key the history is persisted under.
+pub const CONFIRMATION_HISTORY_META_KEY: &str = "confirmation_history";
+
+/// Default number of confirmations kept.
+pub const DEFAULT_CONFIRMATION_HISTORY_SIZE: usize = 2048;
+
+/// One confirmed election.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfirmationRecord {
+    pub hash: BlockHash,
+    pub account: WalletAddress,
+    /// TRST moved by the block (balance change against its predecessor).
+    pub amount: u128,
+    /// Weight behind the winner when the election confirmed.
+    pub tally: u128,
+    pub duration_ms: u64,
+    /// Unix seconds.
+    pub confirmed_at: u64,
+}
+
+/// Duration statistics over the recorded confirmations.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConfirmationStats {
+    pub count: usize,
+    pub average_ms: u64,
+    /// 95th percentile (nearest rank).
+    pub p95_ms: u64,
+}
+
+/// Ring of the most recent confirmations, oldest first.
+pub struct ConfirmationHistory {
+    capacity: usize,
+    entries: VecDeque<ConfirmationRecord>,
+}
+
+impl ConfirmationHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Append a confirmation, evicting the oldest when full. A zero
+    /// capacity disables recording.
+    pub fn record(&mut self, record: ConfirmationRecord) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(record);
+    }
+
+    /// Up to `count` confirmations, newest first.
+    pub fn recent(&self, count: usize) -> Vec<ConfirmationRecord> {
+        self.entries.iter().rev().take(count).cloned().collect()
+    }
+
+    /// Average and p95 duration over the whole history.
+    pub fn stats(&self) -> ConfirmationStats {
+        if self.entries.is_empty() {
+            return ConfirmationStats::default();
+        }
+        let mut durations: Vec<u64> = self.entries.iter().map(|e| e.duration_ms).collect();
+        durations.sort_unstable();
+        let count = durations.len();
+        let total: u128 = durations.iter().map(|&d| d as u128).sum();
+        let p95_rank = (count * 95).div_ceil(100).max(1);
+        ConfirmationStats {
+            count,
+            average_ms: (total / count as u128) as u64,
+            p95_ms: durations[p95_rank - 1],
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Serialize the recorded entries for the meta store.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bincode::serialize(&self.entries).unwrap_or_default()
+    }
+
+    /// Restore a persisted history. If the configured capacity shrank since
+    /// it was saved, only the newest entries are kept.
+    pub fn from_bytes(bytes: &[u8], capacity: usize) -> Result<Self, bincode::Error> {
+        let mut entries: VecDeque<ConfirmationRecord> = bincode::deserialize(bytes)?;
+        while entries.len() > capacity {
+            entries.pop_front();
+        }
+        Ok(Self { capacity, entries })
+    }
+}
+
+/// TRST moved by `block`: the change in balance against `previous`, or the
+/// whole balance for the first block of a chain.
+pub fn transferred_amount(block: &StateBlock, previous: Option<&StateBlock>) -> u128 {
+    match previous {
+        Some(previous) => block.trst_balance.abs_diff(previous.trst_balance),
+        None => block.trst_balance,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(byte: u8, duration_ms: u64) -> ConfirmationRecord {
+        ConfirmationRecord {
+            hash: BlockHash::new([byte; 32]),
+            account: WalletAddress::new("brst_account"),
+            amount: 10,
+            tally: 1_000,
+            duration_ms,
+            confirmed_at: 1_000 + byte as u64,
+        }
+    }
+
+    #[test]
+    fn keeps_newest_entries_and_computes_stats() {
+        let mut history = ConfirmationHistory::new(20);
+        for i in 0..25u8 {
+            history.record(record(i, (i as u64 + 1) * 10));
+        }
+        assert_eq!(history.len(), 20);
+        let recent = history.recent(2);
+        assert_eq!(recent[0].hash, BlockHash::new([24; 32]));
+        assert_eq!(recent[1].hash, BlockHash::new([23; 32]));
+
+        // Durations 60..=250 in steps of 10.
+        let stats = history.stats();
+        assert_eq!(stats.count, 20);
+        assert_eq!(stats.average_ms, 155);
+        assert_eq!(stats.p95_ms, 240);
+        assert_eq!(
+            ConfirmationHistory::new(4).stats(),
+            ConfirmationStats::default()
+        );
+    }
+
+    #[test]
+    fn round_trips_through_bytes_and_truncates_to_capacity() {
+        let mut history = ConfirmationHistory::new(8);
+        for i in 0..5u8 {
+            history.record(record(i, 100));
+        }
+        let restored = ConfirmationHistory::from_bytes(&history.to_bytes(), 3).unwrap();
+        assert_eq!(restored.len(), 3);
+        assert_eq!(restored.capacity(), 3);
+        assert_eq!(restored.recent(1)[0], record(4, 100));
+        assert_eq!(restored.recent(3)[2], record(2, 100));
+    }
+}
@@ -7,26 +7,49 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::AsyncWriteExt;
 use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::sync::{Mutex, RwLock};
+use tokio::task::JoinHandle;
 
-use burst_consensus::{ActiveElections, OnlineWeightSampler, RepWeightCache};
+use burst_consensus::{
+    ActiveElections, ConsensusError, OnlineWeightSampler, RepCrawler, RepWeightCache, VoteCache,
+};
 use burst_crypto::{decode_address, verify_signature};
-use burst_ledger::{DagFrontier, StateBlock};
-use burst_network::{BandwidthThrottle, MessageDedup, PeerManager, PeerTelemetry, SynCookies};
+use burst_ledger::{CheckpointVote, DagFrontier, StateBlock};
+use burst_network::{
+    BandwidthThrottle, Broadcaster, MessageDedup, PeerManager, PeerTelemetry, PenaltyReason,
+    SynCookies,
+};
+use burst_protocol::codec::{BufferPool, FrameReader};
 use burst_store::account::AccountStore;
 use burst_store::block::BlockStore;
+use burst_store::StateTreeStore;
 use burst_store_lmdb::LmdbStore;
-use burst_types::{PublicKey, Signature, Timestamp, WalletAddress};
+use burst_trst::TrstEngine;
+use burst_types::{BlockHash, PublicKey, Signature, Timestamp, WalletAddress};
 
+use crate::block_prefilter::BlockPrefilter;
 use crate::bootstrap::{BootstrapClient, BootstrapMessage, BootstrapServer};
+use crate::checkpoint::record_checkpoint_vote;
+use crate::final_votes::{FinalVoteArchive, DEFAULT_MAX_FINAL_VOTES};
+use crate::gossip::{GossipConfig, GossipHop};
+use crate::inbound_queue::{InboundLane, InboundMessage, InboundQueue, PushOutcome};
+use crate::mailbox::{Mailbox, MailboxOutcome};
 use crate::metrics::NodeMetrics;
+use crate::online_weight::OnlineWeightTracker;
+use crate::overload::OverloadController;
 use crate::priority_queue::BlockPriorityQueue;
-use crate::wire_message::{ConfirmAckMsg, TelemetryAckMessage, WireMessage, WireVote};
-
-/// Maximum message body size (matches protocol codec limit).
-const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024; // 16 MiB
+use crate::revocation_notice::{
+    load_revocation_notice, revoke_fraudulent_wallet, save_revocation_notice, RevocationNotice,
+};
+use crate::shutdown::ShutdownController;
+use crate::state_digest::{bucket_range, DigestCheck, StateDigest, StateDigestLog};
+use crate::vote_admission::{VoteAdmission, VoteAdmissionControl};
+use crate::wire_message::{
+    peek_voter, ConfirmAckMsg, TelemetryAckMessage, TelemetryAckV2Message, WireMessage, WireVote,
+    TELEMETRY_VERSION,
+};
 
 /// Read timeout for peer connections.
 const READ_TIMEOUT: Duration = Duration::from_secs(30);
@@ -110,8 +133,9 @@ pub async fn write_framed(writer: &Mutex<OwnedWriteHalf>, payload: &[u8]) -> std
 }
 
 /// Spawn a background task that reads framed messages from a peer's read
-/// half, deserializes them as [`WireMessage`]s, and dispatches to the
-/// appropriate subsystem.
+/// half into buffers taken from `read_buffers`, deserializes them as [`WireMessage`]s, and routes them: votes,
+/// blocks, bootstrap and telemetry are queued on their inbound lanes for
+/// the inbound workers, everything else is handled inline.
 ///
 /// On read error or disconnect the peer is cleaned up from both the
 /// connection registry and the peer manager.
@@ -119,36 +143,31 @@ pub async fn write_framed(writer: &Mutex<OwnedWriteHalf>, payload: &[u8]) -> std
 pub fn spawn_peer_read_loop(
     peer_id: String,
     reader: OwnedReadHalf,
-    block_queue: Arc<BlockPriorityQueue>,
     connection_registry: Arc<RwLock<ConnectionRegistry>>,
     peer_manager: Arc<RwLock<PeerManager>>,
     metrics: Arc<NodeMetrics>,
-    active_elections: Arc<RwLock<ActiveElections>>,
-    rep_weights: Arc<RwLock<RepWeightCache>>,
     message_dedup: Arc<Mutex<MessageDedup>>,
-    online_weight_sampler: Arc<Mutex<OnlineWeightSampler>>,
+    inbound: Arc<InboundQueue>,
+    prefilter: Arc<Mutex<BlockPrefilter>>,
+    rep_weights: Arc<RwLock<RepWeightCache>>,
+    vote_admission: Arc<Mutex<VoteAdmissionControl>>,
     syn_cookies: Option<Arc<Mutex<SynCookies>>>,
     peer_ip: String,
-    frontier: Arc<RwLock<DagFrontier>>,
-    store: Arc<LmdbStore>,
-    our_params_hash: burst_types::BlockHash,
+    read_buffers: Arc<BufferPool>,
 ) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
         let result = peer_read_loop(
             &peer_id,
-            reader,
-            &block_queue,
-            &active_elections,
-            &rep_weights,
+            FrameReader::new(reader, read_buffers),
             &peer_manager,
             &message_dedup,
-            &online_weight_sampler,
+            &inbound,
+            &prefilter,
+            &rep_weights,
+            &vote_admission,
             syn_cookies.as_deref(),
             &peer_ip,
-            &connection_registry,
-            &frontier,
-            &store,
-            our_params_hash,
+            &metrics,
         )
         .await;
         match &result {
@@ -165,6 +184,7 @@ pub fn spawn_peer_read_loop(
             let mut registry = connection_registry.write().await;
             registry.remove(&peer_id);
         }
+        message_dedup.lock().await.forget_peer(&peer_id);
 
         // Mark peer as disconnected in the peer manager
         {
@@ -177,32 +197,29 @@ pub fn spawn_peer_read_loop(
     })
 }
 
-/// Inner read loop: reads length-prefixed frames and dispatches them.
+/// Inner read loop: reads length-prefixed frames, drops duplicates, votes
+/// over their voter's weight-based rate and blocks that fail the edge
+/// checks, and hands each message to its inbound lane or handles it inline.
 ///
-/// Integrates message deduplication, peer reputation rewards, and online
-/// weight sampling for effective quorum computation.
+/// Vote admission runs on the voter read in place from the frame, so votes
+/// it drops are never fully deserialized.
 #[allow(clippy::too_many_arguments)]
 async fn peer_read_loop(
     peer_id: &str,
-    mut reader: OwnedReadHalf,
-    block_queue: &BlockPriorityQueue,
-    active_elections: &RwLock<ActiveElections>,
-    rep_weights: &RwLock<RepWeightCache>,
+    mut frames: FrameReader<OwnedReadHalf>,
     peer_manager: &RwLock<PeerManager>,
     message_dedup: &Mutex<MessageDedup>,
-    online_weight_sampler: &Mutex<OnlineWeightSampler>,
+    inbound: &InboundQueue,
+    prefilter: &Mutex<BlockPrefilter>,
+    rep_weights: &RwLock<RepWeightCache>,
+    vote_admission: &Mutex<VoteAdmissionControl>,
     syn_cookies: Option<&Mutex<SynCookies>>,
     peer_ip: &str,
-    connection_registry: &RwLock<ConnectionRegistry>,
-    frontier: &RwLock<DagFrontier>,
-    store: &LmdbStore,
-    our_params_hash: burst_types::BlockHash,
+    metrics: &NodeMetrics,
 ) -> Result<(), std::io::Error> {
-    // SYN cookie validation: inbound peers must respond with a signed cookie
     if let Some(cookies) = syn_cookies {
-        let mut len_buf = [0u8; 4];
-        match tokio::time::timeout(READ_TIMEOUT, reader.read_exact(&mut len_buf)).await {
-            Ok(Ok(_)) => {}
+        let body = match tokio::time::timeout(READ_TIMEOUT, frames.next_frame()).await {
+            Ok(Ok(frame)) => frame,
             Ok(Err(e)) => return Err(e),
             Err(_) => {
                 return Err(std::io::Error::new(
@@ -210,27 +227,7 @@ async fn peer_read_loop(
                     "handshake timeout",
                 ));
             }
-        }
-
-        let body_len = u32::from_be_bytes(len_buf) as usize;
-        if body_len > MAX_MESSAGE_SIZE {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "handshake message too large",
-            ));
-        }
-
-        let mut body = vec![0u8; body_len];
-        match tokio::time::timeout(READ_TIMEOUT, reader.read_exact(&mut body)).await {
-            Ok(Ok(_)) => {}
-            Ok(Err(e)) => return Err(e),
-            Err(_) => {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::TimedOut,
-                    "handshake body timeout",
-                ));
-            }
-        }
+        };
 
         match bincode::deserialize::<WireMessage>(&body) {
             Ok(WireMessage::Handshake(hs)) => {
@@ -267,57 +264,21 @@ async fn peer_read_loop(
     }
 
     loop {
-        // Read the 4-byte length prefix with a timeout
-        let mut len_buf = [0u8; 4];
-        match tokio::time::timeout(READ_TIMEOUT, reader.read_exact(&mut len_buf)).await {
-            Ok(Ok(_)) => {}
-            Ok(Err(e)) => return Err(e),
-            Err(_) => {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::TimedOut,
-                    "read timeout (30s idle)",
-                ));
+        let body = match tokio::time::timeout(READ_TIMEOUT, frames.next_frame()).await {
+            Ok(Ok(frame)) => frame,
+            Ok(Err(e)) => {
+                if e.kind() == std::io::ErrorKind::InvalidData {
+                    tracing::warn!(peer = %peer_id, error = %e, "peer sent oversized message, disconnecting");
+                }
+                return Err(e);
             }
-        }
-
-        let body_len = u32::from_be_bytes(len_buf) as usize;
-        if body_len > MAX_MESSAGE_SIZE {
-            tracing::warn!(
-                peer = %peer_id,
-                size = body_len,
-                "peer sent oversized message, disconnecting"
-            );
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("message too large: {body_len} > {MAX_MESSAGE_SIZE}"),
-            ));
-        }
-
-        // Read the message body
-        let mut body = vec![0u8; body_len];
-        match tokio::time::timeout(READ_TIMEOUT, reader.read_exact(&mut body)).await {
-            Ok(Ok(_)) => {}
-            Ok(Err(e)) => return Err(e),
             Err(_) => {
                 return Err(std::io::Error::new(
                     std::io::ErrorKind::TimedOut,
-                    "read timeout waiting for message body",
+                    "read timeout (30s idle)",
                 ));
             }
-        }
-
-        // Message-level deduplication: skip if we've already seen this payload.
-        {
-            let msg_hash = MessageDedup::hash_message(&body);
-            let mut dedup = message_dedup.lock().await;
-            if dedup.is_duplicate(&msg_hash) {
-                tracing::trace!(
-                    peer = %peer_id,
-                    "dropped duplicate message"
-                );
-                continue;
-            }
-        }
+        };
 
         // Update last-seen timestamp for idle detection.
         {
@@ -329,252 +290,164 @@ async fn peer_read_loop(
             pm.touch(peer_id, now);
         }
 
+        // Weight-proportional vote admission: a voter without weight cannot
+        // make us decode and signature-check a flood of votes. Votes are
+        // relayed, so the limit is per voter and the relaying peer is not
+        // penalized.
+        let voter = peek_voter(&body);
+        let is_vote = voter.is_some();
+        if let Some(voter) = voter {
+            if !voter.starts_with(WalletAddress::PREFIX) {
+                tracing::trace!(peer = %peer_id, "dropped vote with malformed voter");
+                continue;
+            }
+            // Votes are never enveloped, so the frame is the vote's canonical
+            // encoding and its hash the vote's content key. Duplicates are
+            // dropped before they use up the voter's admission budget.
+            let key = MessageDedup::hash_message(&body);
+            if message_dedup.lock().await.observe(peer_id, &key) {
+                tracing::trace!(peer = %peer_id, "dropped duplicate vote");
+                continue;
+            }
+            let voter = WalletAddress::new(voter);
+            let (weight, total_weight) = {
+                let weights = rep_weights.read().await;
+                (weights.weight(&voter), weights.total_weight())
+            };
+            let admission =
+                vote_admission
+                    .lock()
+                    .await
+                    .admit(&voter, weight, total_weight, unix_now_ms());
+            metrics
+                .votes_admission
+                .with_label_values(&[admission.as_str()])
+                .inc();
+            if admission != VoteAdmission::Admitted {
+                tracing::trace!(peer = %peer_id, %voter, ?admission, "vote dropped by admission control");
+                continue;
+            }
+        }
+
         // Try to deserialize as a WireMessage (the canonical P2P envelope).
-        match bincode::deserialize::<WireMessage>(&body) {
-            Ok(WireMessage::Block(block)) => {
-                tracing::debug!(
+        let message = match bincode::deserialize::<WireMessage>(&body) {
+            Ok(message) => message,
+            Err(_) => {
+                tracing::trace!(
                     peer = %peer_id,
-                    hash = %block.hash,
-                    "received block from peer"
+                    body_len = body.len(),
+                    "failed to deserialize wire message, dropping"
                 );
-                if block_queue.push(*block).await {
-                    // Reward peer for delivering a new block (+1 reputation).
-                    let mut pm = peer_manager.write().await;
-                    pm.reward(peer_id, 1);
-                } else {
-                    tracing::warn!(
-                        peer = %peer_id,
-                        "block priority queue full, dropping block"
-                    );
-                }
-            }
-            Ok(WireMessage::Vote(vote)) => {
-                if !is_vote_signature_valid(&vote) {
-                    continue;
-                }
-                {
-                    let rw = rep_weights.read().await;
-                    if rw.weight(&vote.voter) == 0 {
-                        tracing::trace!(voter = %vote.voter, "ignoring zero-weight vote");
-                        continue;
-                    }
-                }
-                {
-                    let now = unix_now_secs();
-                    let mut sampler = online_weight_sampler.lock().await;
-                    sampler.record_vote(&vote.voter, now);
-                }
-                dispatch_vote(peer_id, &vote, active_elections, rep_weights).await;
+                continue;
             }
-            Ok(WireMessage::ConfirmReq(req)) => {
-                tracing::debug!(
+        };
+        let Some((message, gossip)) = crate::gossip::open(message) else {
+            tracing::trace!(peer = %peer_id, "gossip envelope around a non-relayed message, dropping");
+            continue;
+        };
+
+        // Message-level deduplication on content, so the same block in a
+        // different frame is still a duplicate. The sender is remembered as
+        // having it either way, so it is not sent back.
+        if !is_vote {
+            let key = crate::gossip::content_key(&message);
+            if message_dedup.lock().await.observe(peer_id, &key) {
+                tracing::trace!(
                     peer = %peer_id,
-                    hashes = req.block_hashes.len(),
-                    "received confirm_req"
+                    "dropped duplicate message"
                 );
-                let ae = active_elections.read().await;
-                let mut confirmed_hashes = Vec::new();
-                for hash in &req.block_hashes {
-                    if let Some(election) = ae.get_election(hash) {
-                        if election.is_confirmed() {
-                            confirmed_hashes.push(*hash);
-                        }
-                    }
-                }
-                drop(ae);
-
-                if !confirmed_hashes.is_empty() {
-                    let vote = WireVote {
-                        voter: WalletAddress::new("brst_node"),
-                        block_hashes: confirmed_hashes,
-                        is_final: true,
-                        timestamp: unix_now_secs(),
-                        sequence: 0,
-                        signature: Signature([0u8; 64]),
-                    };
-                    let ack = WireMessage::ConfirmAck(ConfirmAckMsg { vote });
-                    if let Ok(bytes) = bincode::serialize(&ack) {
-                        let registry = connection_registry.read().await;
-                        if let Some(writer) = registry.get(peer_id) {
-                            if let Err(e) = write_framed(&writer, &bytes).await {
-                                tracing::warn!(peer = %peer_id, "failed to send confirm_ack: {e}");
-                            }
-                        }
-                    }
-                }
+                continue;
             }
-            Ok(WireMessage::ConfirmAck(ack)) => {
-                if !is_vote_signature_valid(&ack.vote) {
-                    continue;
-                }
-                {
-                    let rw = rep_weights.read().await;
-                    if rw.weight(&ack.vote.voter) == 0 {
-                        tracing::trace!(voter = %ack.vote.voter, "ignoring zero-weight confirm_ack");
-                        continue;
+        }
+
+        // Edge checks for gossiped blocks: drop obvious spam before it takes
+        // a slot in the block lane, and charge it to the sender.
+        let mut queued_block = None;
+        if let WireMessage::Block(block) = &message {
+            if let Err(reject) = prefilter.lock().await.check(block, unix_now_secs()) {
+                metrics
+                    .blocks_prefiltered
+                    .with_label_values(&[reject.as_str()])
+                    .inc();
+                tracing::debug!(peer = %peer_id, hash = %block.hash, ?reject, "block dropped at edge");
+                if let Some(reason) = reject.penalty() {
+                    let mut pm = peer_manager.write().await;
+                    if pm.penalize(peer_id, reason, unix_now_secs()) {
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::PermissionDenied,
+                            "peer banned for sending invalid blocks",
+                        ));
                     }
                 }
-                {
-                    let now = unix_now_secs();
-                    let mut sampler = online_weight_sampler.lock().await;
-                    sampler.record_vote(&ack.vote.voter, now);
-                }
-                dispatch_vote(peer_id, &ack.vote, active_elections, rep_weights).await;
+                continue;
             }
-            Ok(WireMessage::Keepalive(ka)) => {
+            queued_block = Some(block.hash);
+        }
+
+        if let Some(lane) = InboundLane::for_message(&message) {
+            let item = InboundMessage {
+                peer_id: peer_id.to_string(),
+                lane,
+                message,
+                gossip,
+            };
+            let (outcome, depth) = inbound.push(item).await;
+            metrics
+                .inbound_queue_depth
+                .with_label_values(&[lane.as_str()])
+                .set(depth as i64);
+            if outcome != PushOutcome::Queued {
+                metrics
+                    .inbound_dropped
+                    .with_label_values(&[lane.as_str()])
+                    .inc();
+                tracing::trace!(peer = %peer_id, lane = lane.as_str(), ?outcome, "inbound message dropped");
+            }
+            if let Some(hash) = queued_block
+                .filter(|_| matches!(outcome, PushOutcome::Queued | PushOutcome::EvictedOldest))
+            {
+                prefilter.lock().await.mark_queued(hash);
+            }
+            continue;
+        }
+
+        match message {
+            WireMessage::Keepalive(ka) => {
                 tracing::trace!(
                     peer = %peer_id,
                     peers = ka.peers.len(),
                     "received keepalive"
                 );
                 if !ka.peers.is_empty() {
-                    let mut parsed: Vec<burst_messages::PeerAddress> =
-                        Vec::with_capacity(ka.peers.len());
-                    for addr_str in &ka.peers {
-                        let parts: Vec<&str> = addr_str.rsplitn(2, ':').collect();
-                        if parts.len() == 2 {
-                            if let Ok(port) = parts[0].parse::<u16>() {
-                                let ip = parts[1].to_string();
-                                parsed.push(burst_messages::PeerAddress { ip, port });
-                            }
-                        }
-                    }
+                    let parsed: Vec<burst_messages::PeerAddress> = ka
+                        .peers
+                        .iter()
+                        .filter_map(|addr_str| burst_network::parse_peer_address(addr_str))
+                        .collect();
 
                     // Slot 0 is the sender's self-advertised address (peering_addr).
                     // Store it so keepalive messages use the NAT-traversed address.
                     let peering = parsed.first().cloned();
 
                     let mut pm = peer_manager.write().await;
-                    pm.process_keepalive(parsed);
-                    if let Some(pa) = peering {
-                        if !pa.ip.is_empty() && pa.ip != "0.0.0.0" && pa.ip != "::" {
-                            pm.set_peering_addr(peer_id, pa);
-                        }
+                    let allow_private = pm.allow_private_addresses();
+                    if let Some(pa) = peering.filter(|pa| {
+                        burst_network::validate_peer_address(pa, allow_private).is_ok()
+                    }) {
+                        pm.set_peering_addr(peer_id, pa);
                     }
+                    let queued = pm.process_keepalive(parsed);
+                    tracing::trace!(peer = %peer_id, queued, "keepalive peers queued for probing");
                 }
             }
-            Ok(WireMessage::Bootstrap(msg)) => match msg {
-                BootstrapMessage::FrontierReq {
-                    start_account,
-                    max_count,
-                } => {
-                    tracing::debug!(peer = %peer_id, "received frontier request");
-                    let frontier_entries: Vec<_> = {
-                        let f = frontier.read().await;
-                        f.iter().map(|(a, h)| (a.clone(), *h)).collect()
-                    };
-                    let resp = BootstrapServer::handle_frontier_req(
-                        &start_account,
-                        max_count,
-                        &frontier_entries,
-                    );
-                    let wire_resp = WireMessage::Bootstrap(resp);
-                    if let Ok(bytes) = bincode::serialize(&wire_resp) {
-                        let registry = connection_registry.read().await;
-                        if let Some(writer) = registry.get(peer_id) {
-                            if let Err(e) = write_framed(&writer, &bytes).await {
-                                tracing::warn!(peer = %peer_id, "failed to send frontier response: {e}");
-                            }
-                        }
-                    }
-                }
-                BootstrapMessage::FrontierResp {
-                    frontiers,
-                    has_more,
-                } => {
-                    tracing::info!(
-                        peer = %peer_id,
-                        count = frontiers.len(),
-                        has_more,
-                        "received frontier response"
-                    );
-                    let local_frontiers: Vec<_> = {
-                        let f = frontier.read().await;
-                        f.iter().map(|(a, h)| (a.clone(), *h)).collect()
-                    };
-                    let mut client = BootstrapClient::new(10_000);
-                    let requests =
-                        client.process_frontier_resp(&frontiers, has_more, &local_frontiers);
-                    for req in requests {
-                        let wire_req = WireMessage::Bootstrap(req);
-                        if let Ok(bytes) = bincode::serialize(&wire_req) {
-                            let registry = connection_registry.read().await;
-                            if let Some(writer) = registry.get(peer_id) {
-                                if let Err(e) = write_framed(&writer, &bytes).await {
-                                    tracing::warn!(peer = %peer_id, "failed to send bootstrap request: {e}");
-                                }
-                            }
-                        }
-                    }
-                }
-                BootstrapMessage::BulkPullReq { account, end } => {
-                    tracing::debug!(peer = %peer_id, %account, "received bulk pull request");
-                    let block_store = store.block_store();
-                    let resp = BootstrapServer::handle_bulk_pull_req(
-                        &account,
-                        &end,
-                        |hash| block_store.get_block(hash).ok(),
-                        |acct| block_store.get_account_blocks(acct).unwrap_or_default(),
-                    );
-                    let wire_resp = WireMessage::Bootstrap(resp);
-                    if let Ok(bytes) = bincode::serialize(&wire_resp) {
-                        let registry = connection_registry.read().await;
-                        if let Some(writer) = registry.get(peer_id) {
-                            if let Err(e) = write_framed(&writer, &bytes).await {
-                                tracing::warn!(peer = %peer_id, "failed to send bulk pull response: {e}");
-                            }
-                        }
-                    }
-                }
-                BootstrapMessage::BulkPullResp { blocks } => {
-                    let client = BootstrapClient::new(10_000);
-                    let deserialized = client.process_bulk_pull_resp(&blocks);
-                    tracing::info!(
-                        peer = %peer_id,
-                        count = deserialized.len(),
-                        "received bulk pull response"
-                    );
-                    for block in deserialized {
-                        if !block_queue.push(block).await {
-                            tracing::warn!(peer = %peer_id, "block queue full during bootstrap");
-                            break;
-                        }
-                    }
-                }
-                BootstrapMessage::BlockReq { hash } => {
-                    tracing::debug!(peer = %peer_id, %hash, "received block request");
-                    let block_store = store.block_store();
-                    let resp =
-                        BootstrapServer::handle_block_req(&hash, |h| block_store.get_block(h).ok());
-                    let wire_resp = WireMessage::Bootstrap(resp);
-                    if let Ok(bytes) = bincode::serialize(&wire_resp) {
-                        let registry = connection_registry.read().await;
-                        if let Some(writer) = registry.get(peer_id) {
-                            if let Err(e) = write_framed(&writer, &bytes).await {
-                                tracing::warn!(peer = %peer_id, "failed to send block response: {e}");
-                            }
-                        }
-                    }
-                }
-                BootstrapMessage::BlockResp { block } => {
-                    if let Some(bytes) = block {
-                        if let Ok(blk) = bincode::deserialize::<StateBlock>(&bytes) {
-                            tracing::debug!(peer = %peer_id, hash = %blk.hash, "received block response");
-                            if !block_queue.push(blk).await {
-                                tracing::warn!(peer = %peer_id, "block queue full during block fetch");
-                            }
-                        }
-                    }
-                }
-            },
-            Ok(WireMessage::Handshake(hs)) => {
+            WireMessage::Handshake(hs) => {
                 tracing::debug!(
                     peer = %peer_id,
                     node_id = %hs.node_id,
                     "received handshake"
                 );
             }
-            Ok(WireMessage::VerificationRequest(msg)) => {
+            WireMessage::VerificationRequest(msg) => {
                 tracing::debug!(
                     peer = %peer_id,
                     target = %msg.target,
@@ -582,7 +455,7 @@ async fn peer_read_loop(
                     "received verification request"
                 );
             }
-            Ok(WireMessage::VerificationVote(msg)) => {
+            WireMessage::VerificationVote(msg) => {
                 tracing::debug!(
                     peer = %peer_id,
                     target = %msg.target,
@@ -590,7 +463,7 @@ async fn peer_read_loop(
                     "received verification vote"
                 );
             }
-            Ok(WireMessage::GovernanceProposal(msg)) => {
+            WireMessage::GovernanceProposal(msg) => {
                 tracing::debug!(
                     peer = %peer_id,
                     proposal = %msg.proposal_hash,
@@ -598,7 +471,7 @@ async fn peer_read_loop(
                     "received governance proposal"
                 );
             }
-            Ok(WireMessage::GovernanceVote(msg)) => {
+            WireMessage::GovernanceVote(msg) => {
                 tracing::debug!(
                     peer = %peer_id,
                     proposal = %msg.proposal_hash,
@@ -606,77 +479,760 @@ async fn peer_read_loop(
                     "received governance vote"
                 );
             }
-            Ok(WireMessage::TelemetryReq) => {
-                tracing::trace!(
+            _ => {}
+        }
+    }
+}
+
+/// Shared state the inbound workers need to process queued messages.
+pub struct InboundContext {
+    pub block_queue: Arc<BlockPriorityQueue>,
+    pub active_elections: Arc<RwLock<ActiveElections>>,
+    pub vote_cache: Arc<RwLock<VoteCache>>,
+    pub rep_weights: Arc<RwLock<RepWeightCache>>,
+    pub peer_manager: Arc<RwLock<PeerManager>>,
+    pub online_weight_sampler: Arc<Mutex<OnlineWeightSampler>>,
+    pub rep_crawler: Arc<Mutex<RepCrawler>>,
+    pub connection_registry: Arc<RwLock<ConnectionRegistry>>,
+    pub frontier: Arc<RwLock<DagFrontier>>,
+    pub store: Arc<LmdbStore>,
+    pub metrics: Arc<NodeMetrics>,
+    pub our_params_hash: burst_types::BlockHash,
+    pub overload: Arc<OverloadController>,
+    pub trst_engine: Arc<Mutex<TrstEngine>>,
+    pub online_weight_tracker: Arc<Mutex<OnlineWeightTracker>>,
+    pub broadcaster: Broadcaster,
+    pub gossip: GossipConfig,
+    pub message_dedup: Arc<Mutex<MessageDedup>>,
+    pub mailbox: Arc<Mutex<Mailbox>>,
+    pub state_digests: Arc<Mutex<StateDigestLog>>,
+    pub difficulty_adjuster: Arc<Mutex<burst_work::DifficultyAdjuster>>,
+}
+
+/// Spawn `workers` tasks that take messages from the inbound lanes in fair
+/// order and process them until shutdown.
+pub fn spawn_inbound_workers(
+    ctx: InboundContext,
+    queue: Arc<InboundQueue>,
+    workers: usize,
+    shutdown: &ShutdownController,
+) -> Vec<JoinHandle<()>> {
+    let ctx = Arc::new(ctx);
+    (0..workers.max(1))
+        .map(|_| {
+            let ctx = Arc::clone(&ctx);
+            let queue = Arc::clone(&queue);
+            let mut shutdown_rx = shutdown.subscribe();
+            tokio::spawn(async move {
+                loop {
+                    let (item, depth) = tokio::select! {
+                        biased;
+                        _ = shutdown_rx.recv() => break,
+                        next = queue.pop() => next,
+                    };
+                    ctx.metrics
+                        .inbound_queue_depth
+                        .with_label_values(&[item.lane.as_str()])
+                        .set(depth as i64);
+                    process_inbound(&ctx, &item.peer_id, item.message, item.gossip).await;
+                }
+            })
+        })
+        .collect()
+}
+
+/// Process one message taken from an inbound lane.
+///
+/// Integrates peer reputation rewards, online weight sampling for
+/// effective quorum computation, and representative crawl responses.
+async fn process_inbound(
+    ctx: &InboundContext,
+    peer_id: &str,
+    message: WireMessage,
+    gossip: Option<GossipHop>,
+) {
+    let InboundContext {
+        block_queue,
+        active_elections,
+        vote_cache,
+        rep_weights,
+        peer_manager,
+        online_weight_sampler,
+        rep_crawler,
+        connection_registry,
+        frontier,
+        store,
+        metrics,
+        our_params_hash,
+        overload,
+        ..
+    } = ctx;
+    match message {
+        WireMessage::Block(block) => {
+            tracing::debug!(
+                peer = %peer_id,
+                hash = %block.hash,
+                "received block from peer"
+            );
+            let relayed = gossip.map(|hop| (WireMessage::Block(block.clone()), hop));
+            if block_queue.push(*block).await {
+                // Reward peer for delivering a new block (+1 reputation).
+                peer_manager.write().await.reward(peer_id, 1);
+                // Only blocks that arrived as gossip spread further; bare
+                // ones are answers to our own requests.
+                if let Some((message, hop)) = relayed {
+                    relay(ctx, peer_id, message, hop).await;
+                }
+            } else {
+                tracing::warn!(
                     peer = %peer_id,
-                    "received telemetry request, sending response"
+                    "block priority queue full, dropping block"
                 );
-                let block_count = store.block_store().block_count().unwrap_or(0);
-                let account_count = store.account_store().account_count().unwrap_or(0);
-                let peer_count = {
-                    let pm = peer_manager.read().await;
-                    pm.connected_count() as u32
-                };
+            }
+        }
+        WireMessage::Vote(vote) => {
+            if !is_vote_signature_valid(&vote) {
+                return;
+            }
+            {
+                let rw = rep_weights.read().await;
+                if rw.weight(&vote.voter) == 0 {
+                    tracing::trace!(voter = %vote.voter, "ignoring zero-weight vote");
+                    return;
+                }
+            }
+            {
+                let now = unix_now_secs();
+                let mut sampler = online_weight_sampler.lock().await;
+                sampler.record_vote(&vote.voter, now);
+            }
+            rep_crawler
+                .lock()
+                .await
+                .record_vote(&vote.voter, unix_now_ms());
+            archive_final_vote(store, metrics, &vote);
+            metrics.vote_backlog.inc();
+            let useful =
+                dispatch_vote(peer_id, &vote, active_elections, vote_cache, rep_weights).await;
+            metrics.vote_backlog.dec();
+            if useful {
+                peer_manager.write().await.record_useful_vote(peer_id);
+            }
+        }
+        WireMessage::ConfirmReq(req) => {
+            tracing::debug!(
+                peer = %peer_id,
+                hashes = req.block_hashes.len(),
+                "received confirm_req"
+            );
+            let ae = active_elections.read().await;
+            let mut confirmed_hashes = Vec::new();
+            let mut inactive_hashes = Vec::new();
+            for hash in &req.block_hashes {
+                match ae.get_election(hash) {
+                    Some(election) if election.is_confirmed() => confirmed_hashes.push(*hash),
+                    Some(_) => {}
+                    None => inactive_hashes.push(*hash),
+                }
+            }
+            drop(ae);
+
+            // Roots without an election may have been finalized before
+            // (possibly before a restart): re-ack with the stored votes.
+            if !inactive_hashes.is_empty() {
+                let archive =
+                    FinalVoteArchive::new(store.final_vote_store(), DEFAULT_MAX_FINAL_VOTES);
+                match archive.replay(&inactive_hashes) {
+                    Ok(votes) => {
+                        for vote in votes {
+                            let ack = WireMessage::ConfirmAck(ConfirmAckMsg { vote });
+                            let Ok(bytes) = bincode::serialize(&ack) else {
+                                continue;
+                            };
+                            let registry = connection_registry.read().await;
+                            if let Some(writer) = registry.get(peer_id) {
+                                if let Err(e) = write_framed(&writer, &bytes).await {
+                                    tracing::warn!(peer = %peer_id, "failed to replay final vote: {e}");
+                                    break;
+                                }
+                                metrics.final_votes_replayed.inc();
+                            }
+                        }
+                    }
+                    Err(e) => tracing::warn!("failed to load stored final votes: {e}"),
+                }
+            }
 
-                let ack = WireMessage::TelemetryAck(TelemetryAckMessage {
-                    block_count,
-                    cemented_count: 0,
-                    unchecked_count: 0,
-                    account_count,
-                    bandwidth_cap: 0,
-                    peer_count,
-                    protocol_version: 1,
-                    uptime: 0,
-                    genesis_hash: burst_types::BlockHash::ZERO,
-                    major_version: 0,
-                    minor_version: 1,
-                    patch_version: 0,
+            if !confirmed_hashes.is_empty() {
+                let vote = WireVote {
+                    voter: WalletAddress::new("brst_node"),
+                    block_hashes: confirmed_hashes,
+                    is_final: true,
                     timestamp: unix_now_secs(),
-                    params_hash: our_params_hash,
-                });
+                    sequence: 0,
+                    signature: Signature([0u8; 64]),
+                };
+                let ack = WireMessage::ConfirmAck(ConfirmAckMsg { vote });
                 if let Ok(bytes) = bincode::serialize(&ack) {
                     let registry = connection_registry.read().await;
                     if let Some(writer) = registry.get(peer_id) {
                         if let Err(e) = write_framed(&writer, &bytes).await {
-                            tracing::warn!(peer = %peer_id, "failed to send telemetry ack: {e}");
+                            tracing::warn!(peer = %peer_id, "failed to send confirm_ack: {e}");
                         }
                     }
                 }
             }
-            Ok(WireMessage::TelemetryAck(msg)) => {
-                tracing::trace!(
+        }
+        WireMessage::ConfirmAck(ack) => {
+            if !is_vote_signature_valid(&ack.vote) {
+                return;
+            }
+            let weight = rep_weights.read().await.weight(&ack.vote.voter);
+            if weight == 0 {
+                tracing::trace!(voter = %ack.vote.voter, "ignoring zero-weight confirm_ack");
+                return;
+            }
+            {
+                let now = unix_now_secs();
+                let mut sampler = online_weight_sampler.lock().await;
+                sampler.record_vote(&ack.vote.voter, now);
+            }
+            rep_crawler.lock().await.process_response(
+                peer_id,
+                &ack.vote.voter,
+                weight,
+                unix_now_ms(),
+            );
+            archive_final_vote(store, metrics, &ack.vote);
+            metrics.vote_backlog.inc();
+            let useful = dispatch_vote(
+                peer_id,
+                &ack.vote,
+                active_elections,
+                vote_cache,
+                rep_weights,
+            )
+            .await;
+            metrics.vote_backlog.dec();
+            if useful {
+                peer_manager.write().await.record_useful_vote(peer_id);
+            }
+        }
+        WireMessage::Bootstrap(msg) => match msg {
+            BootstrapMessage::FrontierReq {
+                start_account,
+                max_count,
+            } => {
+                tracing::debug!(peer = %peer_id, "received frontier request");
+                let frontier_entries: Vec<_> = {
+                    let f = frontier.read().await;
+                    f.iter().map(|(a, h)| (a.clone(), *h)).collect()
+                };
+                let resp = BootstrapServer::handle_frontier_req(
+                    &start_account,
+                    max_count,
+                    &frontier_entries,
+                );
+                let wire_resp = WireMessage::Bootstrap(resp);
+                if let Ok(bytes) = bincode::serialize(&wire_resp) {
+                    let registry = connection_registry.read().await;
+                    if let Some(writer) = registry.get(peer_id) {
+                        if let Err(e) = write_framed(&writer, &bytes).await {
+                            tracing::warn!(peer = %peer_id, "failed to send frontier response: {e}");
+                        }
+                    }
+                }
+            }
+            BootstrapMessage::FrontierResp {
+                frontiers,
+                has_more,
+            } => {
+                tracing::info!(
                     peer = %peer_id,
-                    peer_count = msg.peer_count,
-                    blocks = msg.block_count,
-                    version = format!("{}.{}.{}", msg.major_version, msg.minor_version, msg.patch_version),
-                    "received telemetry from peer"
+                    count = frontiers.len(),
+                    has_more,
+                    "received frontier response"
                 );
-                let mut pm = peer_manager.write().await;
-                pm.update_telemetry(
-                    peer_id,
-                    PeerTelemetry {
-                        block_count: msg.block_count,
-                        cemented_count: msg.cemented_count,
-                        account_count: msg.account_count,
-                        peer_count: msg.peer_count,
-                        protocol_version: msg.protocol_version,
-                        uptime: msg.uptime,
-                        major_version: msg.major_version,
-                        minor_version: msg.minor_version,
-                        patch_version: msg.patch_version,
-                        timestamp: msg.timestamp,
-                    },
+                let local_frontiers: Vec<_> = {
+                    let f = frontier.read().await;
+                    f.iter().map(|(a, h)| (a.clone(), *h)).collect()
+                };
+                let mut client = BootstrapClient::new(10_000);
+                let requests = client.process_frontier_resp(&frontiers, has_more, &local_frontiers);
+                for req in requests {
+                    let wire_req = WireMessage::Bootstrap(req);
+                    if let Ok(bytes) = bincode::serialize(&wire_req) {
+                        let registry = connection_registry.read().await;
+                        if let Some(writer) = registry.get(peer_id) {
+                            if let Err(e) = write_framed(&writer, &bytes).await {
+                                tracing::warn!(peer = %peer_id, "failed to send bootstrap request: {e}");
+                            }
+                        }
+                    }
+                }
+            }
+            BootstrapMessage::BulkPullReq { account, end } => {
+                tracing::debug!(peer = %peer_id, %account, "received bulk pull request");
+                let block_store = store.block_store();
+                let resp = BootstrapServer::handle_bulk_pull_req(
+                    &account,
+                    &end,
+                    |hash| block_store.get_block(hash).ok(),
+                    |acct| block_store.get_account_blocks(acct).unwrap_or_default(),
                 );
+                let wire_resp = WireMessage::Bootstrap(resp);
+                if let Ok(bytes) = bincode::serialize(&wire_resp) {
+                    let registry = connection_registry.read().await;
+                    if let Some(writer) = registry.get(peer_id) {
+                        if let Err(e) = write_framed(&writer, &bytes).await {
+                            tracing::warn!(peer = %peer_id, "failed to send bulk pull response: {e}");
+                        }
+                    }
+                }
             }
-            Err(_) => {
-                tracing::trace!(
+            BootstrapMessage::BulkPullResp { blocks } => {
+                let client = BootstrapClient::new(10_000);
+                let deserialized = client.process_bulk_pull_resp(&blocks);
+                tracing::info!(
                     peer = %peer_id,
-                    body_len = body.len(),
-                    "failed to deserialize wire message, dropping"
+                    count = deserialized.len(),
+                    "received bulk pull response"
                 );
+                for block in deserialized {
+                    if !block_queue.push(block).await {
+                        tracing::warn!(peer = %peer_id, "block queue full during bootstrap");
+                        break;
+                    }
+                }
+            }
+            BootstrapMessage::BlockReq { hash } => {
+                tracing::debug!(peer = %peer_id, %hash, "received block request");
+                let block_store = store.block_store();
+                let resp =
+                    BootstrapServer::handle_block_req(&hash, |h| block_store.get_block(h).ok());
+                let wire_resp = WireMessage::Bootstrap(resp);
+                if let Ok(bytes) = bincode::serialize(&wire_resp) {
+                    let registry = connection_registry.read().await;
+                    if let Some(writer) = registry.get(peer_id) {
+                        if let Err(e) = write_framed(&writer, &bytes).await {
+                            tracing::warn!(peer = %peer_id, "failed to send block response: {e}");
+                        }
+                    }
+                }
+            }
+            BootstrapMessage::BlockResp { block } => {
+                if let Some(bytes) = block {
+                    if let Ok(blk) = bincode::deserialize::<StateBlock>(&bytes) {
+                        tracing::debug!(peer = %peer_id, hash = %blk.hash, "received block response");
+                        if !block_queue.push(blk).await {
+                            tracing::warn!(peer = %peer_id, "block queue full during block fetch");
+                        }
+                    }
+                }
             }
+        },
+        WireMessage::TelemetryReq | WireMessage::TelemetryReqV2 => {
+            tracing::trace!(
+                peer = %peer_id,
+                "received telemetry request, sending response"
+            );
+            let block_count = store.block_store().block_count().unwrap_or(0);
+            let account_count = store.account_store().account_count().unwrap_or(0);
+            let peer_count = {
+                let pm = peer_manager.read().await;
+                pm.connected_count() as u32
+            };
+
+            let base = TelemetryAckMessage {
+                block_count,
+                cemented_count: 0,
+                unchecked_count: 0,
+                account_count,
+                bandwidth_cap: 0,
+                peer_count,
+                protocol_version: TELEMETRY_VERSION,
+                uptime: 0,
+                genesis_hash: burst_types::BlockHash::ZERO,
+                major_version: 0,
+                minor_version: 1,
+                patch_version: 0,
+                timestamp: unix_now_secs(),
+                params_hash: *our_params_hash,
+            };
+            // Answer in the layout the peer asked with.
+            let ack = if matches!(message, WireMessage::TelemetryReqV2) {
+                WireMessage::TelemetryAckV2(TelemetryAckV2Message {
+                    base,
+                    overloaded: overload.is_overloaded(),
+                    account_state_root: store
+                        .state_tree_store()
+                        .state_root()
+                        .unwrap_or(BlockHash::ZERO),
+                    state_digest: ctx.state_digests.lock().await.latest().cloned(),
+                    network_multiplier: ctx.difficulty_adjuster.lock().await.current_multiplier(),
+                })
+            } else {
+                WireMessage::TelemetryAck(base)
+            };
+            if let Ok(bytes) = bincode::serialize(&ack) {
+                let registry = connection_registry.read().await;
+                if let Some(writer) = registry.get(peer_id) {
+                    if let Err(e) = write_framed(&writer, &bytes).await {
+                        tracing::warn!(peer = %peer_id, "failed to send telemetry ack: {e}");
+                    }
+                }
+            }
+        }
+        WireMessage::TelemetryAck(msg) => {
+            // A version 1 peer reports no load or ledger-state figures.
+            record_telemetry(ctx, peer_id, &msg, false, &BlockHash::ZERO, 1).await;
+        }
+        WireMessage::TelemetryAckV2(msg) => {
+            if let Some(ref digest) = msg.state_digest {
+                check_state_digest(ctx, peer_id, digest).await;
+            }
+            record_telemetry(
+                ctx,
+                peer_id,
+                &msg.base,
+                msg.overloaded,
+                &msg.account_state_root,
+                msg.network_multiplier,
+            )
+            .await;
+        }
+        WireMessage::RevocationNotice(notice) => {
+            handle_revocation_notice(ctx, peer_id, *notice, gossip).await;
+        }
+        WireMessage::CheckpointVote(vote) => {
+            handle_checkpoint_vote(ctx, peer_id, vote, gossip).await;
+        }
+        WireMessage::DirectMessage(message) => {
+            handle_direct_message(ctx, peer_id, *message, gossip).await;
+        }
+        _ => {}
+    }
+}
+
+/// Record a gossiped checkpoint vote and relay it.
+///
+/// Votes from representatives without weight are dropped; a bad signature
+/// costs the sending peer reputation. Only votes not seen before are
+/// relayed.
+/// Log a peer's telemetry, check its account-state root against ours and
+/// keep it for the peer.
+async fn record_telemetry(
+    ctx: &InboundContext,
+    peer_id: &str,
+    msg: &TelemetryAckMessage,
+    overloaded: bool,
+    account_state_root: &BlockHash,
+    network_multiplier: u64,
+) {
+    tracing::trace!(
+        peer = %peer_id,
+        peer_count = msg.peer_count,
+        blocks = msg.block_count,
+        overloaded,
+        network_multiplier,
+        version = format!("{}.{}.{}", msg.major_version, msg.minor_version, msg.patch_version),
+        "received telemetry from peer"
+    );
+    check_state_root(ctx, peer_id, msg.block_count, account_state_root);
+    let mut pm = ctx.peer_manager.write().await;
+    pm.update_telemetry(
+        peer_id,
+        PeerTelemetry {
+            block_count: msg.block_count,
+            cemented_count: msg.cemented_count,
+            account_count: msg.account_count,
+            peer_count: msg.peer_count,
+            protocol_version: msg.protocol_version,
+            uptime: msg.uptime,
+            major_version: msg.major_version,
+            minor_version: msg.minor_version,
+            patch_version: msg.patch_version,
+            timestamp: msg.timestamp,
+            account_state_root: *account_state_root,
+            network_multiplier,
+        },
+    );
+}
+
+/// Compare a peer's account-state root with ours when both ledgers hold the
+/// same number of blocks; a mismatch means the ledgers have diverged.
+fn check_state_root(ctx: &InboundContext, peer_id: &str, block_count: u64, root: &BlockHash) {
+    if root.is_zero() || ctx.store.block_store().block_count().ok() != Some(block_count) {
+        return;
+    }
+    let Ok(ours) = ctx.store.state_tree_store().state_root() else {
+        return;
+    };
+    if ours == *root {
+        ctx.metrics
+            .state_root_checks
+            .with_label_values(&["matched"])
+            .inc();
+    } else {
+        ctx.metrics
+            .state_root_checks
+            .with_label_values(&["diverged"])
+            .inc();
+        tracing::warn!(
+            peer = %peer_id,
+            block_count,
+            ours = %ours,
+            theirs = %root,
+            "account state root differs from peer at the same block count"
+        );
+    }
+}
+
+/// Compare a peer's state digest with ours at the same cemented count, and
+/// alert on the first bucket of accounts where they differ.
+async fn check_state_digest(ctx: &InboundContext, peer_id: &str, theirs: &StateDigest) {
+    let check = ctx.state_digests.lock().await.check(theirs);
+    match check {
+        DigestCheck::Incomparable => {}
+        DigestCheck::Matched => {
+            ctx.metrics
+                .state_digest_checks
+                .with_label_values(&["matched"])
+                .inc();
+        }
+        DigestCheck::Diverged { bucket } => {
+            ctx.metrics
+                .state_digest_checks
+                .with_label_values(&["diverged"])
+                .inc();
+            let (first, last) = bucket_range(bucket);
+            tracing::error!(
+                peer = %peer_id,
+                cemented_count = theirs.cemented_count,
+                bucket,
+                account_range = format!("address hash {first:#04x}..={last:#04x}"),
+                "cemented state diverges from peer at the same cemented count"
+            );
+        }
+    }
+}
+
+async fn handle_checkpoint_vote(
+    ctx: &InboundContext,
+    peer_id: &str,
+    vote: CheckpointVote,
+    gossip: Option<GossipHop>,
+) {
+    if !vote.verify_signature() {
+        ctx.metrics
+            .checkpoint_votes
+            .with_label_values(&["rejected"])
+            .inc();
+        tracing::debug!(peer = %peer_id, representative = %vote.representative, "rejected checkpoint vote with invalid signature");
+        let mut pm = ctx.peer_manager.write().await;
+        pm.penalize(peer_id, PenaltyReason::InvalidVote, unix_now_secs());
+        return;
+    }
+    if ctx.rep_weights.read().await.weight(&vote.representative) == 0 {
+        ctx.metrics
+            .checkpoint_votes
+            .with_label_values(&["unweighted"])
+            .inc();
+        return;
+    }
+
+    let checkpoint = match record_checkpoint_vote(
+        &ctx.store.checkpoint_store(),
+        vote.clone(),
+        unix_now_secs(),
+    ) {
+        Ok(Some(checkpoint)) => checkpoint,
+        Ok(None) => {
+            ctx.metrics
+                .checkpoint_votes
+                .with_label_values(&["duplicate"])
+                .inc();
+            return;
+        }
+        Err(e) => {
+            tracing::warn!(representative = %vote.representative, "failed to record checkpoint vote: {e}");
+            return;
+        }
+    };
+    ctx.metrics
+        .checkpoint_votes
+        .with_label_values(&["recorded"])
+        .inc();
+
+    let quorum_delta = ctx.online_weight_tracker.lock().await.quorum_delta();
+    let (weight, previous) = {
+        let weights = ctx.rep_weights.read().await;
+        let weight = checkpoint.weight(|rep| weights.weight(rep));
+        (
+            weight,
+            weight.saturating_sub(weights.weight(&vote.representative)),
+        )
+    };
+    if weight >= quorum_delta && previous < quorum_delta {
+        tracing::info!(
+            cemented_count = checkpoint.digest.cemented_count,
+            digest = %checkpoint.digest.hash(),
+            weight,
+            representatives = checkpoint.votes.len(),
+            "checkpoint reached quorum"
+        );
+    }
+
+    let hop = gossip.unwrap_or_else(|| ctx.gossip.origin());
+    relay(ctx, peer_id, WireMessage::CheckpointVote(vote), hop).await;
+}
+
+/// Apply a gossiped revocation notice and relay it.
+///
+/// Notices for a wallet that already has one are replays and are dropped
+/// before the certificate is checked. A notice whose certificate does not
+/// hold up costs the sending peer reputation.
+async fn handle_revocation_notice(
+    ctx: &InboundContext,
+    peer_id: &str,
+    notice: RevocationNotice,
+    gossip: Option<GossipHop>,
+) {
+    let verification_store = ctx.store.verification_store();
+    match load_revocation_notice(&verification_store, &notice.wallet) {
+        Ok(None) => {}
+        Ok(Some(_)) => {
+            ctx.metrics
+                .revocation_notices
+                .with_label_values(&["replayed"])
+                .inc();
+            return;
+        }
+        Err(e) => {
+            tracing::warn!(wallet = %notice.wallet, "failed to look up revocation notice: {e}");
+            return;
         }
     }
+
+    let quorum_delta = ctx.online_weight_tracker.lock().await.quorum_delta();
+    let verified = {
+        let weights = ctx.rep_weights.read().await;
+        notice.verify(&weights, quorum_delta)
+    };
+    let weight = match verified {
+        Ok(weight) => weight,
+        Err(e) => {
+            ctx.metrics
+                .revocation_notices
+                .with_label_values(&["rejected"])
+                .inc();
+            tracing::debug!(peer = %peer_id, wallet = %notice.wallet, "rejected revocation notice: {e}");
+            let mut pm = ctx.peer_manager.write().await;
+            pm.penalize(peer_id, PenaltyReason::InvalidVote, unix_now_secs());
+            return;
+        }
+    };
+
+    let revoked = {
+        let mut trst = ctx.trst_engine.lock().await;
+        revoke_fraudulent_wallet(&ctx.store, &mut trst, &notice.wallet)
+    };
+    if let Err(e) = save_revocation_notice(&verification_store, &notice) {
+        tracing::warn!(wallet = %notice.wallet, "failed to persist revocation notice: {e}");
+    }
+    ctx.metrics
+        .revocation_notices
+        .with_label_values(&["applied"])
+        .inc();
+    tracing::warn!(
+        peer = %peer_id,
+        wallet = %notice.wallet,
+        issuer = %notice.issuer,
+        weight,
+        revoked,
+        "applied revocation notice"
+    );
+
+    let hop = gossip.unwrap_or_else(|| ctx.gossip.origin());
+    relay(
+        ctx,
+        peer_id,
+        WireMessage::RevocationNotice(Box::new(notice)),
+        hop,
+    )
+    .await;
+}
+
+/// Hold a gossiped direct message for its recipient and relay it.
+///
+/// Only messages newly stored are relayed. A forged signature costs the
+/// sending peer reputation; expired or oversized messages are just dropped.
+async fn handle_direct_message(
+    ctx: &InboundContext,
+    peer_id: &str,
+    message: burst_crypto::DirectMessage,
+    gossip: Option<GossipHop>,
+) {
+    let outcome = ctx
+        .mailbox
+        .lock()
+        .await
+        .insert(message.clone(), unix_now_secs());
+    ctx.metrics
+        .direct_messages
+        .with_label_values(&[outcome.as_str()])
+        .inc();
+    match outcome {
+        MailboxOutcome::Stored => {}
+        MailboxOutcome::BadSignature => {
+            tracing::debug!(peer = %peer_id, sender = %message.sender, "rejected direct message with invalid signature");
+            let mut pm = ctx.peer_manager.write().await;
+            pm.penalize(peer_id, PenaltyReason::Spam, unix_now_secs());
+            return;
+        }
+        _ => return,
+    }
+
+    let hop = gossip.unwrap_or_else(|| ctx.gossip.origin());
+    relay(
+        ctx,
+        peer_id,
+        WireMessage::DirectMessage(Box::new(message)),
+        hop,
+    )
+    .await;
+}
+
+/// Pass received gossip on to peers other than the one it came from, and
+/// not already known to have it, unless its TTL or hop budget is spent.
+async fn relay(ctx: &InboundContext, peer_id: &str, message: WireMessage, hop: GossipHop) {
+    let Some(next) = ctx.gossip.next_hop(hop) else {
+        ctx.metrics
+            .gossip_relays
+            .with_label_values(&["expired"])
+            .inc();
+        return;
+    };
+    let peers: Vec<burst_network::PeerState> = {
+        let pm = ctx.peer_manager.read().await;
+        pm.iter_connected()
+            .filter(|(id, _)| id.as_str() != peer_id)
+            .map(|(_, s)| s.clone())
+            .collect()
+    };
+    crate::gossip::flood(
+        &ctx.broadcaster,
+        &ctx.gossip,
+        &ctx.message_dedup,
+        message,
+        next,
+        &peers,
+    )
+    .await;
+    ctx.metrics
+        .gossip_relays
+        .with_label_values(&["relayed"])
+        .inc();
 }
 
 /// Verify the Ed25519 signature on a wire vote.
@@ -706,19 +1262,35 @@ fn is_vote_signature_valid(vote: &crate::wire_message::WireVote) -> bool {
     true
 }
 
-/// Route a received vote (from Vote or ConfirmAck) to active elections.
+/// Persist a validated final vote so it can be replayed after a restart.
+fn archive_final_vote(store: &LmdbStore, metrics: &NodeMetrics, vote: &WireVote) {
+    if !vote.is_final {
+        return;
+    }
+    let archive = FinalVoteArchive::new(store.final_vote_store(), DEFAULT_MAX_FINAL_VOTES);
+    match archive.record(vote) {
+        Ok(count) => metrics.final_votes_stored.set(count as i64),
+        Err(e) => tracing::warn!(voter = %vote.voter, "failed to store final vote: {e}"),
+    }
+}
+
+/// Route a received vote (from Vote or ConfirmAck) to active elections,
+/// caching it for blocks whose election has not started yet. Returns
+/// whether it counted towards any of them.
 async fn dispatch_vote(
     peer_id: &str,
     vote: &crate::wire_message::WireVote,
     active_elections: &RwLock<ActiveElections>,
+    vote_cache: &RwLock<VoteCache>,
     rep_weights: &RwLock<RepWeightCache>,
-) {
+) -> bool {
     let weight = {
         let rw = rep_weights.read().await;
         rw.weight(&vote.voter)
     };
     let now = Timestamp::new(unix_now_secs());
     let mut ae = active_elections.write().await;
+    let mut useful = false;
     for block_hash in &vote.block_hashes {
         match ae.process_vote(
             block_hash,
@@ -729,6 +1301,7 @@ async fn dispatch_vote(
             now,
         ) {
             Ok(Some(status)) => {
+                useful = true;
                 tracing::info!(
                     peer = %peer_id,
                     winner = %status.winner,
@@ -736,7 +1309,17 @@ async fn dispatch_vote(
                     "election confirmed by incoming vote"
                 );
             }
-            Ok(None) => {}
+            Ok(None) => useful = true,
+            // Early vote: hold it until the election starts.
+            Err(ConsensusError::ElectionNotFound(_)) => {
+                vote_cache.write().await.insert(
+                    *block_hash,
+                    vote.voter.clone(),
+                    weight,
+                    vote.timestamp,
+                    vote.is_final,
+                );
+            }
             Err(e) => {
                 tracing::trace!(
                     peer = %peer_id,
@@ -747,6 +1330,7 @@ async fn dispatch_vote(
             }
         }
     }
+    useful
 }
 
 /// Helper: current UNIX timestamp in seconds.
@@ -756,3 +1340,11 @@ fn unix_now_secs() -> u64 {
         .unwrap_or_default()
         .as_secs()
 }
+
+/// Helper: current UNIX timestamp in milliseconds.
+fn unix_now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
@@ -0,0 +1,229 @@
+//! Dev-network controls for exercising time-dependent behaviour.
+//!
+//! Governance phases and TRST expiry are measured in days, which makes them
+//! impractical to test against a running node. On a dev network the node can
+//! confirm accepted blocks straight away instead of holding elections,
+//! advance its logical clock, and push a governance proposal into its next
+//! phase. The settings come from [`NodeConfig::dev_overrides`](crate::NodeConfig)
+//! and the `dev_*` RPCs; on any other network the overrides are refused at
+//! start-up and the controls reject every change.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use burst_governance::{GovernanceEngine, GovernancePhase, ProposalContent};
+use burst_types::{NetworkId, Timestamp, TxHash};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Dev-network overrides, all off by default.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DevOverrides {
+    /// Confirm accepted blocks without holding an election.
+    pub instant_confirmation: bool,
+    /// Seconds the logical clock starts ahead of wall-clock time.
+    pub clock_offset_secs: u64,
+}
+
+impl DevOverrides {
+    /// Whether any override departs from normal behaviour.
+    pub fn is_set(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum DevControlError {
+    #[error("dev controls are only available on the dev network")]
+    NotDevNetwork,
+    #[error("unknown proposal {0}")]
+    UnknownProposal(TxHash),
+    #[error("proposal is in phase {0:?}, which has no next phase")]
+    FinalPhase(GovernancePhase),
+}
+
+/// The node's logical clock and instant-confirmation switch.
+///
+/// The clock is wall-clock time plus an offset that only moves forward, so
+/// time-based rules see a warp as time having passed.
+pub struct DevControls {
+    network: NetworkId,
+    clock_offset_secs: AtomicU64,
+    instant_confirmation: AtomicBool,
+}
+
+impl DevControls {
+    /// Controls for `network`, starting from `overrides`. Overrides on a
+    /// network other than dev are an error.
+    pub fn new(network: NetworkId, overrides: &DevOverrides) -> Result<Self, DevControlError> {
+        if overrides.is_set() && network != NetworkId::Dev {
+            return Err(DevControlError::NotDevNetwork);
+        }
+        Ok(Self {
+            network,
+            clock_offset_secs: AtomicU64::new(overrides.clock_offset_secs),
+            instant_confirmation: AtomicBool::new(overrides.instant_confirmation),
+        })
+    }
+
+    fn ensure_dev(&self) -> Result<(), DevControlError> {
+        if self.network == NetworkId::Dev {
+            Ok(())
+        } else {
+            Err(DevControlError::NotDevNetwork)
+        }
+    }
+
+    /// Current logical time in Unix seconds.
+    pub fn now_secs(&self) -> u64 {
+        Timestamp::now()
+            .as_secs()
+            .saturating_add(self.clock_offset_secs())
+    }
+
+    /// Current logical time.
+    pub fn now(&self) -> Timestamp {
+        Timestamp::new(self.now_secs())
+    }
+
+    /// How far the logical clock runs ahead of wall-clock time.
+    pub fn clock_offset_secs(&self) -> u64 {
+        self.clock_offset_secs.load(Ordering::Relaxed)
+    }
+
+    /// Move the logical clock `secs` forward. Returns the new offset.
+    pub fn advance_clock(&self, secs: u64) -> Result<u64, DevControlError> {
+        self.ensure_dev()?;
+        let previous = self
+            .clock_offset_secs
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |offset| {
+                Some(offset.saturating_add(secs))
+            })
+            .unwrap_or_default();
+        Ok(previous.saturating_add(secs))
+    }
+
+    /// Whether accepted blocks are confirmed without an election.
+    pub fn instant_confirmation(&self) -> bool {
+        self.instant_confirmation.load(Ordering::Relaxed)
+    }
+
+    pub fn set_instant_confirmation(&self, enabled: bool) -> Result<(), DevControlError> {
+        self.ensure_dev()?;
+        self.instant_confirmation.store(enabled, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Move a proposal into its next phase regardless of durations, votes
+    /// and endorsements. A proposal moved into activation is activated by
+    /// the next governance tick. Returns the new phase.
+    pub fn force_governance_phase(
+        &self,
+        governance: &mut GovernanceEngine,
+        proposal: &TxHash,
+    ) -> Result<GovernancePhase, DevControlError> {
+        self.ensure_dev()?;
+        let now = self.now();
+        let proposal = governance
+            .get_proposal_mut(proposal)
+            .ok_or(DevControlError::UnknownProposal(*proposal))?;
+        let is_emergency = matches!(proposal.content, ProposalContent::Emergency { .. });
+        match proposal.phase {
+            GovernancePhase::Proposal => {
+                proposal.phase = GovernancePhase::Exploration;
+                proposal.exploration_started_at = Some(now);
+            }
+            GovernancePhase::Exploration if is_emergency => {
+                proposal.phase = GovernancePhase::Promotion;
+                proposal.promotion_started_at = Some(now);
+            }
+            GovernancePhase::Exploration => {
+                proposal.phase = GovernancePhase::Cooldown;
+                proposal.cooldown_started_at = Some(now);
+            }
+            GovernancePhase::Cooldown => {
+                proposal.phase = GovernancePhase::Promotion;
+                proposal.promotion_started_at = Some(now);
+            }
+            GovernancePhase::Promotion | GovernancePhase::Activation => {
+                proposal.phase = GovernancePhase::Activation;
+                proposal.activation_at = Some(now);
+            }
+            ref phase => return Err(DevControlError::FinalPhase(phase.clone())),
+        }
+        Ok(proposal.phase.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_governance::{GovernableParam, Proposal};
+    use burst_types::{ProtocolParams, WalletAddress};
+
+    #[test]
+    fn controls_are_refused_off_the_dev_network() {
+        let overrides = DevOverrides {
+            instant_confirmation: true,
+            clock_offset_secs: 0,
+        };
+        assert_eq!(
+            DevControls::new(NetworkId::Live, &overrides).err(),
+            Some(DevControlError::NotDevNetwork)
+        );
+        let live = DevControls::new(NetworkId::Live, &DevOverrides::default()).unwrap();
+        assert_eq!(live.advance_clock(60), Err(DevControlError::NotDevNetwork));
+        assert_eq!(
+            live.set_instant_confirmation(true),
+            Err(DevControlError::NotDevNetwork)
+        );
+        assert_eq!(live.clock_offset_secs(), 0);
+
+        let dev = DevControls::new(NetworkId::Dev, &overrides).unwrap();
+        assert!(dev.instant_confirmation());
+        let before = dev.now_secs();
+        assert_eq!(dev.advance_clock(86_400), Ok(86_400));
+        assert!(dev.now_secs() >= before + 86_400);
+
+        let mut governance = GovernanceEngine::new();
+        let proposal = Proposal {
+            hash: TxHash::new([1; 32]),
+            proposer: WalletAddress::new("brst_proposer"),
+            phase: GovernancePhase::Proposal,
+            content: ProposalContent::ParameterChange {
+                param: GovernableParam::BrnRate,
+                new_value: 2,
+            },
+            endorsement_count: 0,
+            total_eligible_voters: 0,
+            exploration_started_at: None,
+            exploration_votes_yea: 0,
+            exploration_votes_nay: 0,
+            exploration_votes_abstain: 0,
+            cooldown_started_at: None,
+            promotion_started_at: None,
+            promotion_votes_yea: 0,
+            promotion_votes_nay: 0,
+            promotion_votes_abstain: 0,
+            round: 0,
+            created_at: dev.now(),
+            activation_at: None,
+        };
+        let hash = governance
+            .submit_proposal(proposal, u128::MAX, true, &ProtocolParams::default())
+            .unwrap();
+        let phases: Vec<_> = (0..5)
+            .map(|_| dev.force_governance_phase(&mut governance, &hash))
+            .collect();
+        assert_eq!(
+            phases,
+            vec![
+                Ok(GovernancePhase::Exploration),
+                Ok(GovernancePhase::Cooldown),
+                Ok(GovernancePhase::Promotion),
+                Ok(GovernancePhase::Activation),
+                Ok(GovernancePhase::Activation),
+            ]
+        );
+    }
+}
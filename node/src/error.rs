@@ -1,3 +1,4 @@
+use burst_types::{ErrorCode, HasErrorCode};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -20,18 +21,42 @@ pub enum NodeError {
     #[error("store error: {0}")]
     Store(#[from] burst_store::StoreError),
 
+    #[error(transparent)]
+    Protocol(#[from] burst_types::BurstError),
+
     #[error("invalid block: {0}")]
     InvalidBlock(String),
 
+    #[error("transaction validation failed: {0}")]
+    InvalidTransaction(String),
+
+    #[error("transaction {tx} was already applied at sequence {height}")]
+    Replayed { tx: String, height: u64 },
+
+    #[error("{0}")]
+    Busy(String),
+
+    #[error("{0}")]
+    QueueFull(String),
+
+    #[error("node is read-only: storage is nearly exhausted")]
+    ReadOnly,
+
     #[error("proof-of-work does not meet minimum difficulty")]
     WorkInvalid,
 
     #[error("block signature is invalid")]
     SignatureInvalid,
 
+    #[error("block proof unavailable: {0}")]
+    ProofUnavailable(String),
+
     #[error("config error: {0}")]
     Config(String),
 
+    #[error("keystore error: {0}")]
+    Keystore(#[from] burst_wallet_core::WalletError),
+
     #[error("node not initialized")]
     NotInitialized,
 
@@ -50,3 +75,97 @@ pub enum NodeError {
     #[error("{0}")]
     Other(String),
 }
+
+impl HasErrorCode for NodeError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Ledger(e) => e.error_code(),
+            Self::Network(_) => ErrorCode::Network,
+            Self::Verification(_) => ErrorCode::Verification,
+            Self::Governance(_) => ErrorCode::Governance,
+            Self::Consensus(_) => ErrorCode::Consensus,
+            Self::Store(e) => e.error_code(),
+            Self::Protocol(e) => e.error_code(),
+            Self::InvalidBlock(_) => ErrorCode::InvalidBlock,
+            Self::InvalidTransaction(_) => ErrorCode::InvalidTransaction,
+            Self::Replayed { .. } => ErrorCode::DuplicateTransaction,
+            Self::Busy(_) => ErrorCode::Busy,
+            Self::QueueFull(_) => ErrorCode::QueueFull,
+            Self::ReadOnly => ErrorCode::StorageReadOnly,
+            Self::WorkInvalid => ErrorCode::InvalidWork,
+            Self::SignatureInvalid => ErrorCode::InvalidSignature,
+            Self::ProofUnavailable(_) => ErrorCode::ProofUnavailable,
+            Self::Config(_) => ErrorCode::InvalidConfig,
+            Self::Keystore(_) => ErrorCode::Keystore,
+            Self::NotInitialized => ErrorCode::NodeUnavailable,
+            Self::Io(_)
+            | Self::Rpc(_)
+            | Self::WebSocket(_)
+            | Self::ShutdownTimeout
+            | Self::Other(_) => ErrorCode::Internal,
+        }
+    }
+}
+
+impl From<NodeError> for burst_rpc::error::RpcError {
+    fn from(e: NodeError) -> Self {
+        use burst_types::BurstError;
+
+        let details = match &e {
+            NodeError::Protocol(
+                BurstError::InsufficientBrn { needed, available }
+                | BurstError::InsufficientTrst { needed, available },
+            ) => Some(serde_json::json!({
+                "required": needed.to_string(),
+                "available": available.to_string(),
+            })),
+            NodeError::Protocol(BurstError::InsufficientTransferableTrst {
+                needed,
+                transferable,
+            }) => Some(serde_json::json!({
+                "required": needed.to_string(),
+                "available": transferable.to_string(),
+            })),
+            NodeError::Replayed { tx, height } => {
+                Some(serde_json::json!({ "transaction": tx, "height": height }))
+            }
+            _ => None,
+        };
+        let error = Self::coded(e.error_code(), e.to_string());
+        match details {
+            Some(details) => error.with_data(details),
+            None => error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_types::BurstError;
+
+    #[test]
+    fn codes_survive_into_rpc_errors() {
+        let err = NodeError::from(BurstError::InsufficientTrst {
+            needed: 10,
+            available: 4,
+        });
+        assert_eq!(err.error_code(), ErrorCode::InsufficientTrst);
+        let rpc = burst_rpc::error::RpcError::from(err);
+        assert_eq!(rpc.error_code(), ErrorCode::InsufficientTrst);
+        assert_eq!(
+            rpc.to_string(),
+            "insufficient TRST balance: need 10, have 4"
+        );
+        let data = rpc.to_object().data.unwrap();
+        assert_eq!(data["required"], "10");
+        assert_eq!(data["available"], "4");
+
+        let store = NodeError::Store(burst_store::StoreError::NotFound("k".into()));
+        assert_eq!(store.error_code(), ErrorCode::NotFound);
+        assert_eq!(
+            NodeError::Other("x".into()).error_code(),
+            ErrorCode::Internal
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! Final vote archive — persists final votes so they survive restarts.
+//!
+//! Final votes are otherwise ephemeral: once an election is cleaned up the
+//! node can no longer answer a `confirm_req` for its root, and a peer that
+//! was offline during the election ends up starting a fresh one. The archive
+//! keeps the original signed votes in a bounded table so the node can re-ack
+//! finalized roots immediately, even straight after a restart.
+
+use std::collections::HashSet;
+
+use burst_store::{FinalVoteStore, StoreError};
+use burst_types::BlockHash;
+
+use crate::wire_message::WireVote;
+
+/// Default upper bound on stored `(root, voter)` entries.
+pub const DEFAULT_MAX_FINAL_VOTES: u64 = 65_536;
+
+/// Bounded store of signed final votes, keyed by root.
+pub struct FinalVoteArchive<S> {
+    store: S,
+    max_entries: u64,
+}
+
+impl<S: FinalVoteStore> FinalVoteArchive<S> {
+    pub fn new(store: S, max_entries: u64) -> Self {
+        Self { store, max_entries }
+    }
+
+    /// Persist a final vote under every root it covers, trimming the oldest
+    /// entries if the table grows past its bound. Non-final votes are
+    /// ignored. Returns the number of stored entries afterwards.
+    pub fn record(&self, vote: &WireVote) -> Result<u64, StoreError> {
+        if !vote.is_final {
+            return self.count();
+        }
+        let bytes =
+            bincode::serialize(vote).map_err(|e| StoreError::Serialization(e.to_string()))?;
+        for root in &vote.block_hashes {
+            self.store.put_final_vote(root, &vote.voter, &bytes)?;
+        }
+        let count = self.store.final_vote_count()?;
+        if count > self.max_entries {
+            let removed = self.store.trim_final_votes(self.max_entries)?;
+            return Ok(count - removed);
+        }
+        Ok(count)
+    }
+
+    /// Stored final votes covering any of `roots`. A vote covering several
+    /// of the requested roots is returned once.
+    pub fn replay(&self, roots: &[BlockHash]) -> Result<Vec<WireVote>, StoreError> {
+        let mut seen = HashSet::new();
+        let mut votes = Vec::new();
+        for root in roots {
+            for bytes in self.store.get_final_votes(root)? {
+                if !seen.insert(bytes.clone()) {
+                    continue;
+                }
+                match bincode::deserialize::<WireVote>(&bytes) {
+                    Ok(vote) => votes.push(vote),
+                    Err(e) => tracing::warn!(root = %root, "skipping corrupt final vote: {e}"),
+                }
+            }
+        }
+        Ok(votes)
+    }
+
+    /// Number of stored `(root, voter)` entries.
+    pub fn count(&self) -> Result<u64, StoreError> {
+        self.store.final_vote_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_types::{Signature, WalletAddress};
+
+    fn vote(voter: &str, roots: &[u8], is_final: bool) -> WireVote {
+        WireVote {
+            voter: WalletAddress::new(voter),
+            block_hashes: roots.iter().map(|b| BlockHash::new([*b; 32])).collect(),
+            is_final,
+            timestamp: 1_000,
+            sequence: 1,
+            signature: Signature([7u8; 64]),
+        }
+    }
+
+    #[test]
+    fn records_final_votes_and_replays_them_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = burst_store_lmdb::LmdbEnvironment::open(dir.path(), 46, 1 << 20).unwrap();
+        let archive = FinalVoteArchive::new(env.final_vote_store(), 3);
+
+        assert_eq!(archive.record(&vote("brst_rep_a", &[1], false)).unwrap(), 0);
+        assert_eq!(
+            archive.record(&vote("brst_rep_a", &[1, 2], true)).unwrap(),
+            2
+        );
+        assert_eq!(archive.record(&vote("brst_rep_b", &[2], true)).unwrap(), 3);
+
+        let replayed = archive
+            .replay(&[BlockHash::new([1u8; 32]), BlockHash::new([2u8; 32])])
+            .unwrap();
+        assert_eq!(replayed.len(), 2);
+        assert!(replayed.iter().all(|v| v.is_final));
+
+        // Past the bound the oldest entry (rep_a on root 1) is dropped.
+        assert_eq!(archive.record(&vote("brst_rep_c", &[3], true)).unwrap(), 3);
+        let replayed = archive.replay(&[BlockHash::new([1u8; 32])]).unwrap();
+        assert!(replayed.is_empty());
+    }
+}
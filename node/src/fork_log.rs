@@ -0,0 +1,283 @@
+//! Persistent record of detected forks.
+//!
+//! A fork is two blocks from the same account competing for the same slot
+//! in its chain — the signature of a double-spend attempt. Each one goes to
+//! the `forks` LMDB table when the competing block arrives, and is updated
+//! with the outcome once the election for the slot confirms. The table
+//! serves the `forks_recent` RPC; each change is also counted in
+//! `burst_forks_total` and announced on the WebSocket `account_update`
+//! topic, so wallet providers can judge a counterparty's history.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use burst_ledger::StateBlock;
+use burst_rpc::{AdminFuture, ForkEntry, ForkHistory, ForkOutcome};
+use burst_store::block::BlockStore;
+use burst_store::fork::ForkStore;
+use burst_store_lmdb::LmdbStore;
+use burst_types::{BlockHash, Timestamp};
+use burst_websocket::WsState;
+
+use crate::metrics::NodeMetrics;
+
+/// Newest records scanned on open for forks still awaiting an outcome.
+const PENDING_SCAN: usize = 1_024;
+
+/// Records read per page when filtering the table by account.
+const SCAN_PAGE: usize = 256;
+
+pub struct NodeForkLog {
+    store: Arc<LmdbStore>,
+    /// Unresolved forks by the root of the contested slot.
+    pending: Mutex<HashMap<String, Vec<ForkEntry>>>,
+}
+
+/// The block already in the ledger at the slot `block` competes for.
+pub fn contested_block(store: &LmdbStore, block: &StateBlock) -> Option<BlockHash> {
+    let block_store = store.block_store();
+    let height = if block.previous.is_zero() {
+        1
+    } else {
+        block_store.height_of_block(&block.previous).ok()?? + 1
+    };
+    block_store
+        .block_at_height(&block.account, height)
+        .ok()
+        .flatten()
+        .filter(|existing| *existing != block.hash)
+}
+
+/// Count `fork` and announce it to WebSocket subscribers.
+pub fn report(fork: &ForkEntry, metrics: &NodeMetrics, ws_state: &WsState) {
+    let (outcome, change_type) = match fork.outcome {
+        ForkOutcome::Pending => ("detected", "fork_detected"),
+        outcome => (outcome.as_str(), "fork_resolved"),
+    };
+    metrics.forks.with_label_values(&[outcome]).inc();
+    ws_state.publish_fork(
+        &fork.account,
+        change_type,
+        serde_json::to_value(fork).unwrap_or_default(),
+    );
+}
+
+impl NodeForkLog {
+    /// Open the fork log, picking up forks still awaiting an outcome.
+    pub fn open(store: Arc<LmdbStore>) -> Result<Self, String> {
+        let log = Self {
+            store,
+            pending: Mutex::new(HashMap::new()),
+        };
+        let mut pending: HashMap<String, Vec<ForkEntry>> = HashMap::new();
+        for fork in log.page(None, PENDING_SCAN)?.into_iter().rev() {
+            if fork.outcome == ForkOutcome::Pending {
+                pending.entry(fork.root.clone()).or_default().push(fork);
+            }
+        }
+        *log.pending.lock().unwrap() = pending;
+        Ok(log)
+    }
+
+    /// Record that `fork` competes with `existing` for the slot after
+    /// `fork.previous`. Returns `None` if this fork is already recorded.
+    pub fn record(
+        &self,
+        fork: &StateBlock,
+        existing: &BlockHash,
+        now: Timestamp,
+    ) -> Result<Option<ForkEntry>, String> {
+        let root = fork.previous.to_string();
+        let fork_hash = fork.hash.to_string();
+        let mut pending = self.pending.lock().unwrap();
+        let slot = pending.entry(root.clone()).or_default();
+        if slot.iter().any(|entry| entry.fork == fork_hash) {
+            return Ok(None);
+        }
+        let mut entry = ForkEntry {
+            seq: 0,
+            account: fork.account.to_string(),
+            root,
+            existing: existing.to_string(),
+            fork: fork_hash,
+            detected_at: now.as_secs(),
+            outcome: ForkOutcome::Pending,
+            winner: None,
+            resolved_at: None,
+        };
+        let bytes = serde_json::to_vec(&entry).map_err(|e| e.to_string())?;
+        entry.seq = self
+            .store
+            .fork_store()
+            .append_fork(&bytes)
+            .map_err(|e| e.to_string())?;
+        slot.push(entry.clone());
+        Ok(Some(entry))
+    }
+
+    /// Settle every fork for the slot `winner` was confirmed in. Returns
+    /// the forks resolved, empty when `winner` was not contested.
+    pub fn resolve(&self, winner: &BlockHash, now: Timestamp) -> Result<Vec<ForkEntry>, String> {
+        let winner = winner.to_string();
+        let mut pending = self.pending.lock().unwrap();
+        let Some(root) = pending
+            .iter()
+            .find(|(_, forks)| {
+                forks
+                    .iter()
+                    .any(|fork| fork.existing == winner || fork.fork == winner)
+            })
+            .map(|(root, _)| root.clone())
+        else {
+            return Ok(Vec::new());
+        };
+        let mut resolved = pending.remove(&root).unwrap_or_default();
+        for fork in &mut resolved {
+            fork.outcome = if fork.existing == winner {
+                ForkOutcome::ExistingWon
+            } else if fork.fork == winner {
+                ForkOutcome::ForkWon
+            } else {
+                ForkOutcome::Superseded
+            };
+            fork.winner = Some(winner.clone());
+            fork.resolved_at = Some(now.as_secs());
+            let bytes = serde_json::to_vec(&*fork).map_err(|e| e.to_string())?;
+            self.store
+                .fork_store()
+                .update_fork(fork.seq, &bytes)
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(resolved)
+    }
+
+    /// Up to `limit` forks numbered below `before`, newest first, only
+    /// those by `account` if given.
+    pub fn forks(
+        &self,
+        mut before: Option<u64>,
+        limit: usize,
+        account: Option<&str>,
+    ) -> Result<Vec<ForkEntry>, String> {
+        let Some(account) = account else {
+            return self.page(before, limit);
+        };
+        let mut forks = Vec::new();
+        while forks.len() < limit {
+            let page = self.page(before, SCAN_PAGE)?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            before = Some(last.seq);
+            forks.extend(page.into_iter().filter(|fork| fork.account == account));
+        }
+        forks.truncate(limit);
+        Ok(forks)
+    }
+
+    fn page(&self, before: Option<u64>, limit: usize) -> Result<Vec<ForkEntry>, String> {
+        self.store
+            .fork_store()
+            .forks(before, limit)
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .map(|(seq, bytes)| {
+                let fork: ForkEntry = serde_json::from_slice(&bytes).map_err(|e| e.to_string())?;
+                Ok(ForkEntry { seq, ..fork })
+            })
+            .collect()
+    }
+}
+
+impl ForkHistory for NodeForkLog {
+    fn forks<'a>(
+        &'a self,
+        before: Option<u64>,
+        limit: usize,
+        account: Option<&'a str>,
+    ) -> AdminFuture<'a, Vec<ForkEntry>> {
+        Box::pin(async move { NodeForkLog::forks(self, before, limit, account) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_ledger::{BlockType, CURRENT_BLOCK_VERSION};
+    use burst_types::{Signature, TxHash, WalletAddress};
+
+    fn block(account: &str, previous: u8, hash: u8) -> StateBlock {
+        StateBlock {
+            version: CURRENT_BLOCK_VERSION,
+            block_type: BlockType::Send,
+            account: WalletAddress::new(account),
+            previous: BlockHash::new([previous; 32]),
+            representative: WalletAddress::new(account),
+            brn_balance: 0,
+            trst_balance: 0,
+            link: BlockHash::ZERO,
+            origin: TxHash::ZERO,
+            transaction: TxHash::ZERO,
+            timestamp: Timestamp::new(100),
+            params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
+            work: 0,
+            signature: Signature([0; 64]),
+            hash: BlockHash::new([hash; 32]),
+        }
+    }
+
+    #[test]
+    fn forks_are_recorded_once_and_settled_by_the_winner() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(LmdbStore::open(dir.path(), 46, 1 << 22).unwrap());
+        let log = NodeForkLog::open(Arc::clone(&store)).unwrap();
+        let existing = BlockHash::new([2; 32]);
+        let now = Timestamp::new(100);
+
+        let first = log
+            .record(&block("brst_mallory", 1, 3), &existing, now)
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.seq, 1);
+        assert!(log
+            .record(&block("brst_mallory", 1, 3), &existing, now)
+            .unwrap()
+            .is_none());
+        log.record(&block("brst_mallory", 1, 4), &existing, now)
+            .unwrap();
+        log.record(&block("brst_eve", 9, 8), &BlockHash::new([7; 32]), now)
+            .unwrap();
+        assert!(log
+            .resolve(&BlockHash::new([5; 32]), now)
+            .unwrap()
+            .is_empty());
+
+        // Pending forks survive a restart.
+        let log = NodeForkLog::open(store).unwrap();
+        let resolved = log
+            .resolve(&BlockHash::new([3; 32]), Timestamp::new(200))
+            .unwrap();
+        assert_eq!(
+            resolved.iter().map(|f| f.outcome).collect::<Vec<_>>(),
+            vec![ForkOutcome::ForkWon, ForkOutcome::Superseded]
+        );
+
+        let mallory = log.forks(None, 10, Some("brst_mallory")).unwrap();
+        assert_eq!(
+            mallory.iter().map(|f| f.seq).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert_eq!(mallory[1].resolved_at, Some(200));
+        assert_eq!(log.forks(None, 1, None).unwrap()[0].account, "brst_eve");
+        assert_eq!(
+            log.forks(None, 10, None).unwrap()[0].outcome,
+            ForkOutcome::Pending
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! Hop-limited flooding of relayed wire messages.
+//!
+//! Blocks, checkpoint votes, revocation notices and direct messages spread
+//! by relay: a node that receives one passes it on to some of its peers.
+//! They travel in a [`GossipEnvelope`] whose TTL is decremented and hop
+//! count incremented at every relay. A node stops relaying when the TTL runs out, or when the
+//! hop count reaches its own `max_hops` — which bounds peers that reset the
+//! TTL. Fanout grows with the square root of the peer count up to a cap, so
+//! dense topologies don't multiply duplicates.
+//!
+//! Messages are identified by [`content_key`], not by their frame, both for
+//! dropping duplicates and for remembering which peers already have a
+//! message; a flood only picks peers that don't.
+//!
+//! Votes are not relayed and stay bare, where vote admission can read the
+//! voter straight from the frame.
+
+use burst_network::{BroadcastResult, Broadcaster, MessageDedup, PeerState};
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::wire_message::{GossipEnvelope, WireMessage};
+
+/// Flooding limits, the `[gossip]` section of the node config.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GossipConfig {
+    /// TTL given to messages this node originates.
+    pub ttl: u8,
+    /// Messages that have already been relayed this many times are not
+    /// relayed again, whatever TTL they carry.
+    pub max_hops: u8,
+    /// Peers sent to, at least (or all of them, if fewer).
+    pub min_fanout: usize,
+    /// Peers sent to, at most.
+    pub max_fanout: usize,
+}
+
+impl Default for GossipConfig {
+    fn default() -> Self {
+        Self {
+            ttl: 4,
+            max_hops: 8,
+            min_fanout: 4,
+            max_fanout: 16,
+        }
+    }
+}
+
+/// Relay state of a received message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GossipHop {
+    pub ttl: u8,
+    pub hops: u8,
+}
+
+impl GossipConfig {
+    /// Number of peers to send a flooded message to, out of `peers`.
+    pub fn fanout(&self, peers: usize) -> usize {
+        let sqrt = (peers as f64).sqrt().ceil() as usize;
+        sqrt.clamp(self.min_fanout, self.max_fanout.max(self.min_fanout))
+            .min(peers)
+    }
+
+    /// Relay state for a message this node originates, or for a bare
+    /// message from a peer that predates envelopes.
+    pub fn origin(&self) -> GossipHop {
+        GossipHop {
+            ttl: self.ttl,
+            hops: 0,
+        }
+    }
+
+    /// Relay state to forward a message received with `hop`, or `None`
+    /// when it should spread no further.
+    pub fn next_hop(&self, hop: GossipHop) -> Option<GossipHop> {
+        let hops = hop.hops.saturating_add(1);
+        if hop.ttl <= 1 || hops >= self.max_hops {
+            return None;
+        }
+        Some(GossipHop {
+            ttl: hop.ttl - 1,
+            hops,
+        })
+    }
+}
+
+/// Whether `message` is spread by relay and may travel in an envelope.
+pub fn is_relayed(message: &WireMessage) -> bool {
+    matches!(
+        message,
+        WireMessage::Block(_)
+            | WireMessage::CheckpointVote(_)
+            | WireMessage::RevocationNotice(_)
+            | WireMessage::DirectMessage(_)
+    )
+}
+
+/// Dedup key of a message: the block hash for blocks, so the same block
+/// is recognised whatever its framing, and the hash of the canonical
+/// encoding for everything else. Call on messages taken out of their
+/// envelope.
+pub fn content_key(message: &WireMessage) -> [u8; 32] {
+    match message {
+        WireMessage::Block(block) => *block.hash.as_bytes(),
+        message => bincode::serialize(message)
+            .map(|bytes| MessageDedup::hash_message(&bytes))
+            .unwrap_or_default(),
+    }
+}
+
+/// Take a received message out of its envelope. Bare messages come back
+/// with no relay state. `None` for envelopes around anything that is not
+/// relayed, including other envelopes.
+pub fn open(message: WireMessage) -> Option<(WireMessage, Option<GossipHop>)> {
+    match message {
+        WireMessage::Gossip(envelope) => {
+            let GossipEnvelope { ttl, hops, message } = envelope;
+            is_relayed(&message).then_some((*message, Some(GossipHop { ttl, hops })))
+        }
+        message => Some((message, None)),
+    }
+}
+
+/// Send `message` with relay state `hop` to an adaptive subset of those
+/// `peers` not already known to have it, and remember that they do.
+pub async fn flood(
+    broadcaster: &Broadcaster,
+    config: &GossipConfig,
+    dedup: &Mutex<MessageDedup>,
+    message: WireMessage,
+    hop: GossipHop,
+    peers: &[PeerState],
+) -> BroadcastResult {
+    let key = content_key(&message);
+    let targets: Vec<PeerState> = {
+        let mut dedup = dedup.lock().await;
+        let mut fresh: Vec<PeerState> = peers
+            .iter()
+            .filter(|peer| peer.connected && !peer.banned)
+            .filter(|peer| !dedup.peer_knows(&peer_id(peer), &key))
+            .cloned()
+            .collect();
+        fresh.shuffle(&mut rand::thread_rng());
+        fresh.truncate(config.fanout(peers.len()));
+        for peer in &fresh {
+            dedup.mark_known(&peer_id(peer), &key);
+        }
+        fresh
+    };
+    if targets.is_empty() {
+        return BroadcastResult::default();
+    }
+    let envelope = WireMessage::Gossip(GossipEnvelope {
+        ttl: hop.ttl,
+        hops: hop.hops,
+        message: Box::new(message),
+    });
+    let Ok(bytes) = bincode::serialize(&envelope) else {
+        return BroadcastResult::default();
+    };
+    broadcaster.broadcast_to_all(&bytes, &targets).await
+}
+
+/// The ID a peer is registered and sent to under.
+fn peer_id(peer: &PeerState) -> String {
+    format!("{}:{}", peer.address.ip, peer.address.port)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fanout_grows_with_peers_within_bounds() {
+        let config = GossipConfig::default();
+        assert_eq!(config.fanout(0), 0);
+        assert_eq!(config.fanout(3), 3);
+        assert_eq!(config.fanout(9), 4);
+        assert_eq!(config.fanout(100), 10);
+        assert_eq!(config.fanout(10_000), 16);
+    }
+
+    #[test]
+    fn relaying_stops_at_ttl_or_max_hops() {
+        let config = GossipConfig {
+            ttl: 3,
+            max_hops: 3,
+            ..GossipConfig::default()
+        };
+        let first = config.next_hop(config.origin()).unwrap();
+        assert_eq!(first, GossipHop { ttl: 2, hops: 1 });
+        let second = config.next_hop(first).unwrap();
+        assert_eq!(config.next_hop(second), None);
+
+        // A peer that inflates the TTL is still cut off by the hop count.
+        let inflated = GossipHop { ttl: 200, hops: 2 };
+        assert_eq!(config.next_hop(inflated), None);
+    }
+
+    #[test]
+    fn open_unwraps_relayed_messages_only() {
+        let bare = open(WireMessage::TelemetryReq).unwrap();
+        assert!(matches!(bare, (WireMessage::TelemetryReq, None)));
+
+        let wrapped = WireMessage::Gossip(GossipEnvelope {
+            ttl: 1,
+            hops: 1,
+            message: Box::new(WireMessage::TelemetryReq),
+        });
+        assert!(open(wrapped).is_none());
+
+        let nested = WireMessage::Gossip(GossipEnvelope {
+            ttl: 2,
+            hops: 0,
+            message: Box::new(WireMessage::Gossip(GossipEnvelope {
+                ttl: 2,
+                hops: 0,
+                message: Box::new(WireMessage::TelemetryReq),
+            })),
+        });
+        assert!(open(nested).is_none());
+    }
+
+    #[test]
+    fn vote_key_is_the_hash_of_its_bare_frame() {
+        use crate::wire_message::WireVote;
+        use burst_types::{BlockHash, Signature, WalletAddress};
+
+        // The read loop dedups votes on the raw frame before decoding them.
+        let vote = WireMessage::Vote(WireVote {
+            voter: WalletAddress::new("brst_voter".to_string()),
+            block_hashes: vec![BlockHash::new([0xAA; 32])],
+            is_final: false,
+            timestamp: 1,
+            sequence: 1,
+            signature: Signature([0xBB; 64]),
+        });
+        let frame = bincode::serialize(&vote).unwrap();
+        assert_eq!(content_key(&vote), MessageDedup::hash_message(&frame));
+    }
+}
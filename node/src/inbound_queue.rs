@@ -0,0 +1,448 @@
+//! Typed inbound processing lanes with weighted fair scheduling.
+//!
+//! Peer read loops only frame and classify messages; the expensive work
+//! (vote verification, block queueing, bootstrap serving, telemetry) runs
+//! on worker tasks fed from one bounded queue per message type. Each lane
+//! has its own capacity and drop policy, and workers take messages from the
+//! lanes in weighted round-robin order, so a flood on one lane (say, blocks)
+//! only fills that lane and cannot delay vote processing beyond its share.
+
+use std::collections::VecDeque;
+
+use tokio::sync::{Mutex, Notify};
+
+use crate::gossip::GossipHop;
+use crate::wire_message::WireMessage;
+
+/// Inbound message category, each with its own queue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InboundLane {
+    /// Votes, confirm requests, confirm acks and certificate-backed
+    /// revocation notices.
+    Votes,
+    /// Blocks published by peers.
+    Blocks,
+    /// Bootstrap requests and responses.
+    Bootstrap,
+    /// Telemetry requests and responses.
+    Telemetry,
+    /// Encrypted direct messages between wallets.
+    Messages,
+}
+
+impl InboundLane {
+    /// All lanes, in scheduling order.
+    pub const ALL: [InboundLane; 5] = [
+        InboundLane::Votes,
+        InboundLane::Blocks,
+        InboundLane::Bootstrap,
+        InboundLane::Telemetry,
+        InboundLane::Messages,
+    ];
+
+    /// The lane a message is processed on, or `None` for messages cheap
+    /// enough to handle directly in the read loop (keepalives, handshakes,
+    /// gossip that is only logged).
+    pub fn for_message(message: &WireMessage) -> Option<Self> {
+        match message {
+            WireMessage::Vote(_)
+            | WireMessage::ConfirmReq(_)
+            | WireMessage::ConfirmAck(_)
+            | WireMessage::RevocationNotice(_)
+            | WireMessage::CheckpointVote(_) => Some(InboundLane::Votes),
+            WireMessage::Block(_) => Some(InboundLane::Blocks),
+            WireMessage::Bootstrap(_) => Some(InboundLane::Bootstrap),
+            WireMessage::TelemetryReq
+            | WireMessage::TelemetryAck(_)
+            | WireMessage::TelemetryReqV2
+            | WireMessage::TelemetryAckV2(_) => Some(InboundLane::Telemetry),
+            WireMessage::DirectMessage(_) => Some(InboundLane::Messages),
+            _ => None,
+        }
+    }
+
+    /// Lowercase name, used as the metrics label.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InboundLane::Votes => "votes",
+            InboundLane::Blocks => "blocks",
+            InboundLane::Bootstrap => "bootstrap",
+            InboundLane::Telemetry => "telemetry",
+            InboundLane::Messages => "messages",
+        }
+    }
+
+    /// Lowest-priority traffic, shed while the node is overloaded.
+    pub fn is_sheddable(&self) -> bool {
+        matches!(
+            self,
+            InboundLane::Bootstrap | InboundLane::Telemetry | InboundLane::Messages
+        )
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// What to discard when a lane is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DropPolicy {
+    /// Reject the incoming message; queued work keeps its place.
+    DropNewest,
+    /// Evict the oldest queued message to make room; fresh data wins.
+    DropOldest,
+}
+
+/// Capacity, scheduling weight and drop policy of one lane.
+#[derive(Clone, Copy, Debug)]
+pub struct LaneConfig {
+    pub capacity: usize,
+    /// Messages taken from this lane per scheduling round.
+    pub weight: usize,
+    pub drop_policy: DropPolicy,
+}
+
+impl LaneConfig {
+    /// Default configuration for `lane`. Votes get the largest share, and
+    /// stale votes are less useful than fresh ones; blocks keep arrival
+    /// order and reject overflow so PoW spent earlier is not wasted.
+    pub fn default_for(lane: InboundLane) -> Self {
+        let (capacity, weight, drop_policy) = match lane {
+            InboundLane::Votes => (16_384, 4, DropPolicy::DropOldest),
+            InboundLane::Blocks => (8_192, 2, DropPolicy::DropNewest),
+            InboundLane::Bootstrap => (2_048, 1, DropPolicy::DropNewest),
+            InboundLane::Telemetry => (256, 1, DropPolicy::DropOldest),
+            InboundLane::Messages => (1_024, 1, DropPolicy::DropOldest),
+        };
+        Self {
+            capacity,
+            weight,
+            drop_policy,
+        }
+    }
+}
+
+/// A message waiting to be processed.
+#[derive(Debug)]
+pub struct InboundMessage {
+    pub peer_id: String,
+    pub lane: InboundLane,
+    pub message: WireMessage,
+    /// Relay state, for messages that arrived in a gossip envelope.
+    pub gossip: Option<GossipHop>,
+}
+
+/// Outcome of [`InboundQueues::push`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PushOutcome {
+    Queued,
+    /// Queued after evicting the lane's oldest message.
+    EvictedOldest,
+    /// The lane was full and the message was discarded.
+    Rejected,
+    /// The lane is being shed under overload and the message was discarded.
+    Shed,
+}
+
+struct Lane {
+    config: LaneConfig,
+    queue: VecDeque<InboundMessage>,
+}
+
+/// The per-lane queues and the weighted round-robin state.
+pub struct InboundQueues {
+    lanes: Vec<Lane>,
+    /// Lane currently being served.
+    cursor: usize,
+    /// Messages the current lane may still take this round.
+    credits: usize,
+    /// Whether sheddable lanes refuse new messages.
+    shedding: bool,
+}
+
+impl InboundQueues {
+    /// Queues with the given configuration per lane.
+    pub fn new(config: impl Fn(InboundLane) -> LaneConfig) -> Self {
+        let lanes: Vec<Lane> = InboundLane::ALL
+            .iter()
+            .map(|&lane| Lane {
+                config: config(lane),
+                queue: VecDeque::new(),
+            })
+            .collect();
+        let credits = lanes[0].config.weight.max(1);
+        Self {
+            lanes,
+            cursor: 0,
+            credits,
+            shedding: false,
+        }
+    }
+
+    /// Start or stop shedding the lowest-priority lanes. Messages already
+    /// queued on them are dropped when shedding starts.
+    pub fn set_shedding(&mut self, shedding: bool) {
+        self.shedding = shedding;
+        if shedding {
+            for &lane in InboundLane::ALL.iter().filter(|lane| lane.is_sheddable()) {
+                self.lanes[lane.index()].queue.clear();
+            }
+        }
+    }
+
+    /// Queue a message on its lane, applying the lane's drop policy if full.
+    pub fn push(&mut self, item: InboundMessage) -> PushOutcome {
+        if self.shedding && item.lane.is_sheddable() {
+            return PushOutcome::Shed;
+        }
+        let lane = &mut self.lanes[item.lane.index()];
+        if lane.queue.len() < lane.config.capacity {
+            lane.queue.push_back(item);
+            return PushOutcome::Queued;
+        }
+        match lane.config.drop_policy {
+            DropPolicy::DropNewest => PushOutcome::Rejected,
+            DropPolicy::DropOldest => {
+                if lane.queue.pop_front().is_none() {
+                    return PushOutcome::Rejected;
+                }
+                lane.queue.push_back(item);
+                PushOutcome::EvictedOldest
+            }
+        }
+    }
+
+    /// Take the next message in weighted round-robin order: each lane
+    /// serves up to `weight` messages before the next non-empty lane gets
+    /// its turn.
+    pub fn pop(&mut self) -> Option<InboundMessage> {
+        for _ in 0..=self.lanes.len() {
+            if self.credits > 0 {
+                if let Some(item) = self.lanes[self.cursor].queue.pop_front() {
+                    self.credits -= 1;
+                    return Some(item);
+                }
+            }
+            self.cursor = (self.cursor + 1) % self.lanes.len();
+            self.credits = self.lanes[self.cursor].config.weight.max(1);
+        }
+        None
+    }
+
+    /// Messages queued on `lane`.
+    pub fn len(&self, lane: InboundLane) -> usize {
+        self.lanes[lane.index()].queue.len()
+    }
+
+    /// Whether every lane is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lanes.iter().all(|lane| lane.queue.is_empty())
+    }
+}
+
+impl Default for InboundQueues {
+    fn default() -> Self {
+        Self::new(LaneConfig::default_for)
+    }
+}
+
+/// Async handle shared by the read loops (producers) and the inbound
+/// workers (consumers).
+pub struct InboundQueue {
+    queues: Mutex<InboundQueues>,
+    notify: Notify,
+}
+
+impl InboundQueue {
+    pub fn new(queues: InboundQueues) -> Self {
+        Self {
+            queues: Mutex::new(queues),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Queue a message and wake a worker. Returns the push outcome and the
+    /// lane's depth afterwards.
+    pub async fn push(&self, item: InboundMessage) -> (PushOutcome, usize) {
+        let lane = item.lane;
+        let (outcome, depth) = {
+            let mut queues = self.queues.lock().await;
+            let outcome = queues.push(item);
+            (outcome, queues.len(lane))
+        };
+        if matches!(outcome, PushOutcome::Queued | PushOutcome::EvictedOldest) {
+            self.notify.notify_one();
+        }
+        (outcome, depth)
+    }
+
+    /// See [`InboundQueues::set_shedding`].
+    pub async fn set_shedding(&self, shedding: bool) {
+        self.queues.lock().await.set_shedding(shedding);
+    }
+
+    /// Wait for the next message in fair order. Also returns the depth of
+    /// the message's lane after removal.
+    pub async fn pop(&self) -> (InboundMessage, usize) {
+        loop {
+            {
+                let mut queues = self.queues.lock().await;
+                if let Some(item) = queues.pop() {
+                    let depth = queues.len(item.lane);
+                    if !queues.is_empty() {
+                        // Let another worker pick up the rest.
+                        self.notify.notify_one();
+                    }
+                    return (item, depth);
+                }
+            }
+            self.notify.notified().await;
+        }
+    }
+}
+
+impl Default for InboundQueue {
+    fn default() -> Self {
+        Self::new(InboundQueues::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(lane: InboundLane, tag: &str) -> InboundMessage {
+        InboundMessage {
+            peer_id: tag.to_string(),
+            lane,
+            message: WireMessage::TelemetryReq,
+            gossip: None,
+        }
+    }
+
+    fn config(capacity: usize, weight: usize, drop_policy: DropPolicy) -> LaneConfig {
+        LaneConfig {
+            capacity,
+            weight,
+            drop_policy,
+        }
+    }
+
+    #[test]
+    fn block_flood_cannot_starve_votes() {
+        let mut queues = InboundQueues::new(|lane| match lane {
+            InboundLane::Votes => config(100, 2, DropPolicy::DropOldest),
+            _ => config(100, 1, DropPolicy::DropNewest),
+        });
+        for i in 0..50 {
+            queues.push(item(InboundLane::Blocks, &format!("block{i}")));
+        }
+        for i in 0..3 {
+            queues.push(item(InboundLane::Votes, &format!("vote{i}")));
+        }
+        queues.push(item(InboundLane::Telemetry, "telemetry"));
+
+        let order: Vec<String> = (0..7).map(|_| queues.pop().unwrap().peer_id).collect();
+        assert_eq!(
+            order,
+            vec![
+                "vote0",
+                "vote1",
+                "block0",
+                "telemetry",
+                "vote2",
+                "block1",
+                "block2"
+            ]
+        );
+    }
+
+    #[test]
+    fn drop_policies_apply_per_lane() {
+        let mut queues = InboundQueues::new(|lane| match lane {
+            InboundLane::Votes => config(2, 1, DropPolicy::DropOldest),
+            _ => config(2, 1, DropPolicy::DropNewest),
+        });
+        assert_eq!(
+            queues.push(item(InboundLane::Votes, "v0")),
+            PushOutcome::Queued
+        );
+        queues.push(item(InboundLane::Votes, "v1"));
+        assert_eq!(
+            queues.push(item(InboundLane::Votes, "v2")),
+            PushOutcome::EvictedOldest
+        );
+        queues.push(item(InboundLane::Blocks, "b0"));
+        queues.push(item(InboundLane::Blocks, "b1"));
+        assert_eq!(
+            queues.push(item(InboundLane::Blocks, "b2")),
+            PushOutcome::Rejected
+        );
+
+        assert_eq!(queues.len(InboundLane::Votes), 2);
+        assert_eq!(queues.len(InboundLane::Blocks), 2);
+        let mut popped = Vec::new();
+        while let Some(next) = queues.pop() {
+            popped.push(next.peer_id);
+        }
+        assert_eq!(popped, vec!["v1", "b0", "v2", "b1"]);
+        assert!(queues.is_empty());
+    }
+
+    #[test]
+    fn shedding_drops_only_low_priority_lanes() {
+        let mut queues = InboundQueues::new(|_| config(4, 1, DropPolicy::DropNewest));
+        queues.push(item(InboundLane::Bootstrap, "boot0"));
+        queues.set_shedding(true);
+        assert_eq!(queues.len(InboundLane::Bootstrap), 0);
+        assert_eq!(
+            queues.push(item(InboundLane::Telemetry, "tel0")),
+            PushOutcome::Shed
+        );
+        assert_eq!(
+            queues.push(item(InboundLane::Blocks, "b0")),
+            PushOutcome::Queued
+        );
+
+        queues.set_shedding(false);
+        assert_eq!(
+            queues.push(item(InboundLane::Telemetry, "tel1")),
+            PushOutcome::Queued
+        );
+    }
+
+    #[test]
+    fn classifies_messages_by_lane() {
+        assert_eq!(
+            InboundLane::for_message(&WireMessage::TelemetryReq),
+            Some(InboundLane::Telemetry)
+        );
+        assert_eq!(
+            InboundLane::for_message(&WireMessage::ConfirmReq(
+                crate::wire_message::ConfirmReqMsg {
+                    block_hashes: vec![]
+                }
+            )),
+            Some(InboundLane::Votes)
+        );
+        assert_eq!(
+            InboundLane::for_message(&WireMessage::Keepalive(crate::wire_message::KeepaliveMsg {
+                peers: vec![]
+            })),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn async_pop_waits_for_push() {
+        let queue = std::sync::Arc::new(InboundQueue::default());
+        let consumer = {
+            let queue = std::sync::Arc::clone(&queue);
+            tokio::spawn(async move { queue.pop().await.0.peer_id })
+        };
+        tokio::task::yield_now().await;
+        queue.push(item(InboundLane::Telemetry, "peer")).await;
+        assert_eq!(consumer.await.unwrap(), "peer");
+    }
+}
@@ -0,0 +1,428 @@
+//! Economic invariant checks run after each block write batch.
+//!
+//! A debugging aid for catching ledger-corrupting bugs where they happen
+//! instead of blocks later. When enabled, the block processor calls
+//! [`InvariantChecker::after_batch`] once a block's batch has committed:
+//!
+//! - the block's own writes are read back — a send must leave a pending
+//!   entry holding exactly what it debited, a receive must consume its
+//!   entry, and no account's BRN burned total may go down;
+//! - every `scan_interval` batches the whole ledger is totalled: TRST held
+//!   in chains and in flight, plus TRST expired or revoked, may never
+//!   exceed TRST minted (BRN burned, 1:1), and total BRN burned may never
+//!   fall.
+//!
+//! Each violation is logged and counted in `burst_invariant_violations_total`.
+//! With `halt = true` the node shuts down on the first one, leaving the
+//! ledger as it was for inspection.
+
+use std::fmt;
+
+use burst_ledger::{BlockType, StateBlock};
+use burst_store::account::{AccountInfo, AccountStore};
+use burst_store::pending::PendingStore;
+use burst_store_lmdb::LmdbStore;
+use burst_types::TxHash;
+use serde::{Deserialize, Serialize};
+
+use crate::ledger_bridge::extract_receiver_from_link;
+
+/// The `[invariants]` section of the node config.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InvariantConfig {
+    /// Run the checks at all. Off by default: a ledger scan costs time
+    /// proportional to the number of accounts.
+    pub enabled: bool,
+    /// Shut the node down on a violation instead of only reporting it.
+    pub halt: bool,
+    /// Write batches between two full-ledger scans; `1` scans after every
+    /// batch, `0` never scans. Block-local checks run after every batch.
+    pub scan_interval: u64,
+}
+
+impl Default for InvariantConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            halt: false,
+            scan_interval: 1,
+        }
+    }
+}
+
+/// A property the ledger must always have.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Invariant {
+    /// TRST in the ledger, expired or revoked never exceeds TRST minted.
+    TrstSupply,
+    /// Sends leave a pending entry for exactly what they debit, and
+    /// receives consume theirs.
+    PendingMatchesSend,
+    /// BRN burned totals never decrease.
+    BurnMonotonic,
+}
+
+impl Invariant {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::TrstSupply => "trst_supply",
+            Self::PendingMatchesSend => "pending_matches_send",
+            Self::BurnMonotonic => "burn_monotonic",
+        }
+    }
+}
+
+impl fmt::Display for Invariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A failed check and what was found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    pub invariant: Invariant,
+    pub detail: String,
+}
+
+/// Ledger-wide sums of the balances the invariants relate.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LedgerTotals {
+    pub trst_held: u128,
+    pub trst_pending: u128,
+    pub trst_expired: u128,
+    pub trst_revoked: u128,
+    pub brn_burned: u128,
+}
+
+impl LedgerTotals {
+    /// Sum every account and pending entry in `store`.
+    pub fn scan(store: &LmdbStore) -> Result<Self, String> {
+        let mut totals = Self::default();
+        let accounts = store
+            .account_store()
+            .iter_accounts()
+            .map_err(|e| format!("failed to read accounts: {e}"))?;
+        for account in &accounts {
+            totals.trst_held = totals.trst_held.saturating_add(account.trst_balance);
+            totals.trst_expired = totals.trst_expired.saturating_add(account.expired_trst);
+            totals.trst_revoked = totals.trst_revoked.saturating_add(account.revoked_trst);
+            totals.brn_burned = totals.brn_burned.saturating_add(account.total_brn_burned);
+        }
+        let pending = store
+            .pending_store()
+            .iter_pending()
+            .map_err(|e| format!("failed to read pending entries: {e}"))?;
+        for (_, _, info) in &pending {
+            totals.trst_pending = totals.trst_pending.saturating_add(info.amount);
+        }
+        Ok(totals)
+    }
+
+    /// TRST ever minted, one per BRN burned.
+    pub fn trst_minted(&self) -> u128 {
+        self.brn_burned
+    }
+
+    /// TRST accounted for in the ledger: held, in flight, expired or
+    /// revoked. Minted TRST not yet received into a chain makes up the
+    /// difference to [`trst_minted`](Self::trst_minted).
+    pub fn trst_accounted(&self) -> u128 {
+        self.trst_held
+            .saturating_add(self.trst_pending)
+            .saturating_add(self.trst_expired)
+            .saturating_add(self.trst_revoked)
+    }
+}
+
+pub struct InvariantChecker {
+    config: InvariantConfig,
+    batches: u64,
+    /// Totals from the previous scan.
+    last: Option<LedgerTotals>,
+}
+
+impl InvariantChecker {
+    pub fn new(config: InvariantConfig) -> Self {
+        Self {
+            config,
+            batches: 0,
+            last: None,
+        }
+    }
+
+    pub fn config(&self) -> &InvariantConfig {
+        &self.config
+    }
+
+    /// Check the ledger after the batch writing `block` committed.
+    /// `prev_account` is the account as it was before the block.
+    pub fn after_batch(
+        &mut self,
+        store: &LmdbStore,
+        block: &StateBlock,
+        prev_account: Option<&AccountInfo>,
+    ) -> Vec<Violation> {
+        if !self.config.enabled {
+            return Vec::new();
+        }
+        let mut violations = check_block(store, block, prev_account);
+        self.batches += 1;
+        let interval = self.config.scan_interval;
+        if interval > 0 && self.batches.is_multiple_of(interval) {
+            violations.extend(self.check_totals(store));
+        }
+        violations
+    }
+
+    /// Total the ledger and compare with the previous scan.
+    pub fn check_totals(&mut self, store: &LmdbStore) -> Vec<Violation> {
+        let totals = match LedgerTotals::scan(store) {
+            Ok(totals) => totals,
+            Err(e) => {
+                tracing::warn!("skipping ledger invariant scan: {e}");
+                return Vec::new();
+            }
+        };
+        let mut violations = Vec::new();
+        if totals.trst_accounted() > totals.trst_minted() {
+            violations.push(Violation {
+                invariant: Invariant::TrstSupply,
+                detail: format!(
+                    "{} TRST held, {} pending, {} expired and {} revoked exceed {} minted",
+                    totals.trst_held,
+                    totals.trst_pending,
+                    totals.trst_expired,
+                    totals.trst_revoked,
+                    totals.trst_minted()
+                ),
+            });
+        }
+        if let Some(last) = self.last {
+            if totals.brn_burned < last.brn_burned {
+                violations.push(Violation {
+                    invariant: Invariant::BurnMonotonic,
+                    detail: format!(
+                        "total BRN burned fell from {} to {}",
+                        last.brn_burned, totals.brn_burned
+                    ),
+                });
+            }
+        }
+        self.last = Some(totals);
+        violations
+    }
+}
+
+/// Read back what the batch for `block` wrote.
+fn check_block(
+    store: &LmdbStore,
+    block: &StateBlock,
+    prev_account: Option<&AccountInfo>,
+) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let prev_trst = prev_account.map_or(0, |a| a.trst_balance);
+    let prev_burned = prev_account.map_or(0, |a| a.total_brn_burned);
+
+    if let Ok(account) = store.account_store().get_account(&block.account) {
+        if account.total_brn_burned < prev_burned {
+            violations.push(Violation {
+                invariant: Invariant::BurnMonotonic,
+                detail: format!(
+                    "{} burned total fell from {prev_burned} to {} at {}",
+                    block.account, account.total_brn_burned, block.hash
+                ),
+            });
+        }
+    }
+
+    let pending = store.pending_store();
+    match block.block_type {
+        BlockType::Send | BlockType::HtlcLock => {
+            let Some(destination) = extract_receiver_from_link(&block.link) else {
+                return violations;
+            };
+            let debited = prev_trst.saturating_sub(block.trst_balance);
+            let locked = pending
+                .get_pending(&destination, &block.hash.into_tx_hash())
+                .ok()
+                .map(|info| info.amount);
+            if locked != Some(debited) {
+                violations.push(Violation {
+                    invariant: Invariant::PendingMatchesSend,
+                    detail: format!(
+                        "{} debited {debited} TRST but left {} pending for {destination}",
+                        block.hash,
+                        locked.map_or("nothing".to_string(), |amount| amount.to_string())
+                    ),
+                });
+            }
+        }
+        BlockType::Receive | BlockType::HtlcClaim if !block.link.is_zero() => {
+            let source = TxHash::new(*block.link.as_bytes());
+            if pending.get_pending(&block.account, &source).is_ok() {
+                violations.push(Violation {
+                    invariant: Invariant::PendingMatchesSend,
+                    detail: format!(
+                        "{} received {} but its pending entry is still there",
+                        block.hash, block.link
+                    ),
+                });
+            }
+        }
+        _ => {}
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_store::pending::PendingInfo;
+    use burst_types::{BlockHash, Signature, Timestamp, WalletAddress, WalletState};
+
+    fn account(address: &WalletAddress, trst: u128, burned: u128) -> AccountInfo {
+        AccountInfo {
+            address: address.clone(),
+            state: WalletState::Unverified,
+            verified_at: None,
+            head: BlockHash::ZERO,
+            block_count: 1,
+            confirmation_height: 0,
+            representative: address.clone(),
+            total_brn_burned: burned,
+            total_brn_staked: 0,
+            trst_balance: trst,
+            expired_trst: 0,
+            revoked_trst: 0,
+            epoch: 0,
+        }
+    }
+
+    /// A wallet that can be named in a block link.
+    fn wallet(seed: u8) -> (WalletAddress, BlockHash) {
+        let public = burst_crypto::keypair_from_seed(&[seed; 32]).public;
+        (
+            burst_crypto::derive_address(&public),
+            BlockHash::new(public.0),
+        )
+    }
+
+    fn send(from: &WalletAddress, to: BlockHash, trst_after: u128) -> StateBlock {
+        let mut block = StateBlock {
+            version: 1,
+            block_type: BlockType::Send,
+            account: from.clone(),
+            previous: BlockHash::new([1; 32]),
+            representative: from.clone(),
+            brn_balance: 0,
+            trst_balance: trst_after,
+            link: to,
+            origin: TxHash::ZERO,
+            transaction: TxHash::ZERO,
+            timestamp: Timestamp::new(1),
+            params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
+            work: 0,
+            signature: Signature([0; 64]),
+            hash: BlockHash::ZERO,
+        };
+        block.hash = block.compute_hash();
+        block
+    }
+
+    fn pending(source: &WalletAddress, amount: u128) -> PendingInfo {
+        PendingInfo {
+            source: source.clone(),
+            amount,
+            timestamp: Timestamp::new(1),
+            provenance: Vec::new(),
+            memo: None,
+            destination_tag: None,
+            htlc: None,
+            returned: false,
+        }
+    }
+
+    fn checker() -> InvariantChecker {
+        InvariantChecker::new(InvariantConfig {
+            enabled: true,
+            ..InvariantConfig::default()
+        })
+    }
+
+    #[test]
+    fn send_must_lock_what_it_debits() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LmdbStore::open(dir.path(), 46, 1 << 22).unwrap();
+        let alice = WalletAddress::new("brst_alice".to_string());
+        let (bob, bob_link) = wallet(2);
+        let before = account(&alice, 100, 100);
+        store
+            .account_store()
+            .put_account(&account(&alice, 60, 100))
+            .unwrap();
+        let block = send(&alice, bob_link, 60);
+
+        let mut checker = checker();
+        let violations = checker.after_batch(&store, &block, Some(&before));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].invariant, Invariant::PendingMatchesSend);
+
+        store
+            .pending_store()
+            .put_pending(&bob, &block.hash.into_tx_hash(), &pending(&alice, 40))
+            .unwrap();
+        assert!(checker
+            .after_batch(&store, &block, Some(&before))
+            .is_empty());
+    }
+
+    #[test]
+    fn ledger_scan_catches_inflation_and_falling_burns() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LmdbStore::open(dir.path(), 46, 1 << 22).unwrap();
+        let alice = WalletAddress::new("brst_alice".to_string());
+        let bob = WalletAddress::new("brst_bob".to_string());
+        let accounts = store.account_store();
+        accounts.put_account(&account(&alice, 60, 100)).unwrap();
+        store
+            .pending_store()
+            .put_pending(&bob, &TxHash::new([7; 32]), &pending(&alice, 40))
+            .unwrap();
+
+        let mut checker = checker();
+        assert!(checker.check_totals(&store).is_empty());
+
+        accounts.put_account(&account(&bob, 1, 0)).unwrap();
+        let violations = checker.check_totals(&store);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].invariant, Invariant::TrstSupply);
+
+        accounts.put_account(&account(&bob, 0, 0)).unwrap();
+        accounts.put_account(&account(&alice, 0, 90)).unwrap();
+        let violations = checker.check_totals(&store);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].invariant, Invariant::BurnMonotonic);
+    }
+
+    #[test]
+    fn disabled_checker_does_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LmdbStore::open(dir.path(), 46, 1 << 22).unwrap();
+        let alice = WalletAddress::new("brst_alice".to_string());
+        let (_, bob_link) = wallet(2);
+        let mut checker = InvariantChecker::new(InvariantConfig::default());
+        let block = send(&alice, bob_link, 0);
+        let before = account(&alice, 10, 0);
+        assert!(checker
+            .after_batch(&store, &block, Some(&before))
+            .is_empty());
+    }
+}
@@ -4,7 +4,10 @@
 use burst_brn::{BrnEngine, Stake, StakeKind};
 use burst_governance::ProposalContent;
 use burst_ledger::{BlockType, StateBlock};
-use burst_transactions::governance::GovernanceVote;
+use burst_transactions::governance::{
+    proposal_dependency, treasury_grant, BallotMode, GovernanceVote,
+};
+use burst_transactions::reject_receive::RejectReason;
 use burst_trst::{TrstEngine, TrstToken};
 use burst_types::{BlockHash, Timestamp, WalletAddress};
 
@@ -68,7 +71,7 @@ pub fn process_block_economics(
                 }
             }
         }
-        BlockType::Send => {
+        BlockType::Send | BlockType::HtlcLock => {
             // TRST transfer — sender's balance decreases.
             //
             // Expiry correctness: The block processor validates that the send
@@ -89,11 +92,16 @@ pub fn process_block_economics(
                 trst_balance_after: block.trst_balance,
             }
         }
-        BlockType::Receive => EconomicResult::Receive {
+        BlockType::Receive | BlockType::HtlcClaim => EconomicResult::Receive {
             receiver: block.account.clone(),
             send_block_hash: block.link,
             trst_balance_after: block.trst_balance,
         },
+        BlockType::HtlcRefund => EconomicResult::HtlcRefund {
+            sender: block.account.clone(),
+            lock_block_hash: block.link,
+            trst_balance_after: block.trst_balance,
+        },
         BlockType::Split => {
             // TRST split — one token becomes multiple tokens.
             // Expiry: the child tokens inherit the parent's origin_timestamp,
@@ -173,11 +181,39 @@ pub fn process_block_economics(
                 target,
             }
         }
+        BlockType::VerifierOptIn => {
+            // Joining the verifier pool locks BRN for as long as the verifier
+            // stays in. An opt-in whose stake cannot be locked never joins.
+            let stake_amount = prev_brn_balance.saturating_sub(block.brn_balance);
+            match record_brn_stake(
+                brn_engine,
+                &block.account,
+                stake_amount,
+                StakeKind::VerifierPool,
+                now,
+            ) {
+                Ok(stake) => EconomicResult::VerifierOptIn {
+                    verifier: block.account.clone(),
+                    stake,
+                },
+                Err(e) => EconomicResult::Rejected {
+                    reason: format!("verifier stake could not be locked: {e}"),
+                },
+            }
+        }
+        // The stake itself is returned by the caller, which holds the
+        // persisted stake record.
+        BlockType::VerifierOptOut => EconomicResult::VerifierOptOut {
+            verifier: block.account.clone(),
+            stake_amount: block.brn_balance.saturating_sub(prev_brn_balance),
+        },
         BlockType::RejectReceive => EconomicResult::RejectReceive {
             rejecter: block.account.clone(),
             send_block_hash: block.link,
+            reason: block.reject_reason,
         },
-        BlockType::ChangeRepresentative => EconomicResult::RepChange {
+        // A recovery rotates the key but may also move the representative.
+        BlockType::ChangeRepresentative | BlockType::Recovery => EconomicResult::RepChange {
             account: block.account.clone(),
             old_rep: None,
             new_rep: block.representative.clone(),
@@ -185,16 +221,24 @@ pub fn process_block_economics(
         },
         BlockType::GovernanceProposal => {
             let proposal_hash = block.transaction;
-            let content = decode_proposal_content_from_link(&block.link);
+            let content = match treasury_grant(&block.link, &block.origin) {
+                Some((amount, recipient)) => Some(ProposalContent::TreasuryGrant {
+                    recipient: burst_crypto::derive_address(&burst_types::PublicKey(recipient)),
+                    amount,
+                }),
+                None => decode_proposal_content_from_link(&block.link),
+            };
             EconomicResult::GovernanceProposal {
                 proposer: block.account.clone(),
                 proposal_hash,
                 content,
+                ballot: BallotMode::from_proposal_link(&block.link),
+                requires: proposal_dependency(&block.link, &block.origin),
             }
         }
         BlockType::GovernanceVote => {
             let proposal_hash = block.link.into_tx_hash();
-            match decode_governance_vote(block.transaction.as_bytes()[0]) {
+            match GovernanceVote::from_byte(block.transaction.as_bytes()[0]) {
                 Some(vote) => EconomicResult::GovernanceVote {
                     voter: block.account.clone(),
                     proposal_hash,
@@ -210,6 +254,16 @@ pub fn process_block_economics(
                 }
             }
         }
+        BlockType::GovernanceCommit => EconomicResult::GovernanceCommit {
+            voter: block.account.clone(),
+            proposal_hash: block.link.into_tx_hash(),
+            commitment: *block.transaction.as_bytes(),
+        },
+        BlockType::GovernanceReveal => EconomicResult::GovernanceReveal {
+            voter: block.account.clone(),
+            proposal_hash: block.link.into_tx_hash(),
+            sealed: *block.transaction.as_bytes(),
+        },
         BlockType::VerificationVote => {
             let voter = block.account.clone();
             let target = extract_receiver_from_link(&block.link);
@@ -222,12 +276,22 @@ pub fn process_block_economics(
                 stake: stake_amount,
             }
         }
+        BlockType::Alias => {
+            // Alias registration — the fee is burned, not held.
+            let burn_amount = prev_brn_balance.saturating_sub(block.brn_balance);
+            let burn_result = record_brn_burn(brn_engine, &block.account, burn_amount, now);
+            EconomicResult::Alias {
+                burn_amount,
+                burn_result,
+            }
+        }
         BlockType::GovernanceActivation => {
             let proposal_hash = burst_types::TxHash::new(*block.link.as_bytes());
             let new_params_hash = BlockHash::new(*block.transaction.as_bytes());
             EconomicResult::GovernanceActivation {
                 proposal_hash,
                 new_params_hash,
+                certificate: block.origin,
             }
         }
         _ => EconomicResult::NoEconomicEffect,
@@ -273,15 +337,20 @@ fn record_brn_stake(
     }
 }
 
-/// Decode a `GovernanceVote` from the first byte of the transaction field.
+/// Release a BRN stake back to the staker's available balance.
 ///
-/// Encoding: 0 = Yea, 1 = Nay, 2 = Abstain. Returns `None` for unknown values.
-fn decode_governance_vote(byte: u8) -> Option<GovernanceVote> {
-    match byte {
-        0 => Some(GovernanceVote::Yea),
-        1 => Some(GovernanceVote::Nay),
-        2 => Some(GovernanceVote::Abstain),
-        _ => None,
+/// Same remove-reinsert pattern as [`record_brn_stake`].
+pub(crate) fn return_brn_stake(
+    brn_engine: &mut BrnEngine,
+    account: &WalletAddress,
+    stake: &mut Stake,
+) -> Result<(), String> {
+    if let Some(mut state) = brn_engine.wallets.remove(account) {
+        let result = brn_engine.return_stake(account, &mut state, stake);
+        brn_engine.wallets.insert(account.clone(), state);
+        result.map_err(|e| e.to_string())
+    } else {
+        Err("wallet not tracked in BRN engine".to_string())
     }
 }
 
@@ -427,6 +496,12 @@ pub enum EconomicResult {
         send_block_hash: BlockHash,
         trst_balance_after: u128,
     },
+    /// Expired HTLC lock returned to its sender.
+    HtlcRefund {
+        sender: WalletAddress,
+        lock_block_hash: BlockHash,
+        trst_balance_after: u128,
+    },
     /// TRST split into multiple tokens.
     Split { account: WalletAddress },
     /// TRST merge from multiple tokens.
@@ -443,6 +518,21 @@ pub enum EconomicResult {
         stake_result: Result<Stake, String>,
         target: Option<WalletAddress>,
     },
+    /// Verifier joined the pool, locking its stake.
+    VerifierOptIn {
+        verifier: WalletAddress,
+        stake: Stake,
+    },
+    /// Verifier left the pool; its stake is due back.
+    VerifierOptOut {
+        verifier: WalletAddress,
+        stake_amount: u128,
+    },
+    /// Alias registered or renewed, its fee burned.
+    Alias {
+        burn_amount: u128,
+        burn_result: Result<(), String>,
+    },
     /// Representative change.
     RepChange {
         account: WalletAddress,
@@ -455,6 +545,9 @@ pub enum EconomicResult {
         proposer: WalletAddress,
         proposal_hash: burst_types::TxHash,
         content: Option<burst_governance::ProposalContent>,
+        ballot: BallotMode,
+        /// Proposal that has to activate first.
+        requires: Option<burst_types::TxHash>,
     },
     /// Governance vote cast.
     GovernanceVote {
@@ -462,10 +555,23 @@ pub enum EconomicResult {
         proposal_hash: burst_types::TxHash,
         vote: burst_transactions::governance::GovernanceVote,
     },
+    /// Sealed ballot committed.
+    GovernanceCommit {
+        voter: WalletAddress,
+        proposal_hash: burst_types::TxHash,
+        commitment: [u8; 32],
+    },
+    /// Sealed ballot revealed: the vote byte followed by the salt.
+    GovernanceReveal {
+        voter: WalletAddress,
+        proposal_hash: burst_types::TxHash,
+        sealed: [u8; 32],
+    },
     /// TRST receive rejected — pending entry returned to sender.
     RejectReceive {
         rejecter: WalletAddress,
         send_block_hash: BlockHash,
+        reason: Option<RejectReason>,
     },
     /// Verification vote — verifier cast a vote on a wallet's humanity.
     VerificationVoteResult {
@@ -478,6 +584,8 @@ pub enum EconomicResult {
     GovernanceActivation {
         proposal_hash: burst_types::TxHash,
         new_params_hash: BlockHash,
+        /// Hash of the proposal's `GovernanceQuorumCertificate`.
+        certificate: burst_types::TxHash,
     },
     /// Block rejected due to economic invariant violation.
     Rejected { reason: String },
@@ -531,6 +639,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([1u8; 64]),
             hash: BlockHash::ZERO,
@@ -553,6 +667,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_001),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([2u8; 64]),
             hash: BlockHash::ZERO,
@@ -576,6 +696,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_002),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([3u8; 64]),
             hash: BlockHash::ZERO,
@@ -603,6 +729,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_003),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([4u8; 64]),
             hash: BlockHash::ZERO,
@@ -630,6 +762,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_004),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([5u8; 64]),
             hash: BlockHash::ZERO,
@@ -698,6 +836,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([1u8; 64]),
             hash: BlockHash::ZERO,
@@ -794,6 +938,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([1u8; 64]),
             hash: BlockHash::ZERO,
@@ -880,6 +1030,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn verifier_opt_in_locks_stake_until_opt_out() {
+        // 10 BRN/s since genesis: 10M accrued at t = 1_000_000.
+        let mut brn_engine = BrnEngine::with_rate(10, Timestamp::new(0));
+        let mut trst_engine = TrstEngine::new();
+        let now = Timestamp::new(1_000_000);
+        let account = test_account();
+        brn_engine.track_wallet(
+            account.clone(),
+            burst_brn::BrnWalletState::new(Timestamp::new(0)),
+        );
+
+        let mut block = make_challenge_block_for_target(&test_target());
+        block.block_type = BlockType::VerifierOptIn;
+        block.link = BlockHash::ZERO;
+        block.brn_balance = 9_999_500;
+        let result = process_block_economics(
+            &block,
+            &mut brn_engine,
+            &mut trst_engine,
+            now,
+            3600,
+            10_000_000,
+        );
+        let EconomicResult::VerifierOptIn {
+            verifier,
+            mut stake,
+        } = result
+        else {
+            panic!("Expected VerifierOptIn, got {:?}", result);
+        };
+        assert_eq!(verifier, account);
+        assert_eq!(
+            (stake.amount, stake.kind.clone()),
+            (500, StakeKind::VerifierPool)
+        );
+        assert_eq!(
+            brn_engine.compute_balance(&brn_engine.wallets[&account], now),
+            9_999_500
+        );
+
+        // A stake larger than the available balance cannot be locked.
+        block.brn_balance = 0;
+        let result = process_block_economics(
+            &block,
+            &mut brn_engine,
+            &mut trst_engine,
+            now,
+            3600,
+            20_000_000,
+        );
+        assert!(matches!(result, EconomicResult::Rejected { .. }));
+
+        return_brn_stake(&mut brn_engine, &account, &mut stake).unwrap();
+        assert!(stake.resolved);
+        assert_eq!(
+            brn_engine.compute_balance(&brn_engine.wallets[&account], now),
+            10_000_000
+        );
+    }
+
     #[test]
     fn extract_receiver_from_zero_link_returns_none() {
         let link = BlockHash::ZERO;
@@ -6,8 +6,10 @@
 use burst_consensus::RepWeightCache;
 use burst_ledger::{BlockType, StateBlock};
 use burst_store::account::AccountInfo;
-use burst_store_lmdb::WriteBatch;
-use burst_types::{WalletAddress, WalletState};
+use burst_store::pending::PendingStore;
+use burst_store_lmdb::{LmdbStore, WriteBatch};
+use burst_transactions::htlc::HtlcData;
+use burst_types::{Timestamp, TxHash, WalletAddress, WalletState};
 
 /// Update ledger state after a block is accepted.
 ///
@@ -109,9 +111,19 @@ pub fn create_pending_entry(
     destination: &WalletAddress,
     consumed: Vec<burst_trst::ConsumedProvenance>,
 ) -> Result<(), String> {
-    if block.block_type != BlockType::Send || block.link.is_zero() {
+    if !matches!(block.block_type, BlockType::Send | BlockType::HtlcLock) || block.link.is_zero() {
         return Ok(());
     }
+    let htlc = match block.htlc {
+        Some(HtlcData::Lock {
+            hash_lock,
+            refund_after,
+        }) => Some(PendingHtlc {
+            hash_lock,
+            refund_after,
+        }),
+        _ => None,
+    };
     let provenance: Vec<PendingProvenance> = consumed
         .into_iter()
         .map(|c| PendingProvenance {
@@ -136,6 +148,10 @@ pub fn create_pending_entry(
         amount,
         timestamp: block.timestamp,
         provenance,
+        memo: block.memo.clone(),
+        destination_tag: block.destination_tag(),
+        htlc,
+        returned: false,
     })
     .map_err(|e| format!("serialize pending: {e}"))?;
     batch
@@ -146,12 +162,14 @@ pub fn create_pending_entry(
 
 /// Delete a pending entry when a receive or reject-receive block is processed.
 ///
-/// Handles both `Receive` (claiming pending TRST) and `RejectReceive`
-/// (returning pending TRST to sender). Uses binary composite key
+/// Handles `Receive` (claiming pending TRST), `RejectReceive` (returning
+/// pending TRST to sender) and `HtlcClaim`. Uses binary composite key
 /// `account_bytes ++ link_bytes` matching `create_pending_entry`.
 pub fn delete_pending_entry(batch: &mut WriteBatch<'_>, block: &StateBlock) -> Result<(), String> {
-    if (block.block_type != BlockType::Receive && block.block_type != BlockType::RejectReceive)
-        || block.link.is_zero()
+    if !matches!(
+        block.block_type,
+        BlockType::Receive | BlockType::RejectReceive | BlockType::HtlcClaim
+    ) || block.link.is_zero()
     {
         return Ok(());
     }
@@ -161,8 +179,158 @@ pub fn delete_pending_entry(batch: &mut WriteBatch<'_>, block: &StateBlock) -> R
     Ok(())
 }
 
+/// Return a rejected transfer to its sender as a new pending entry.
+///
+/// The entry is keyed by the reject block's hash, so the sender pockets it
+/// with an ordinary receive linking the reject block. Provenance carries
+/// over unchanged so the returned TRST keeps its original expiry.
+pub fn create_returned_pending_entry(
+    batch: &mut WriteBatch<'_>,
+    block: &StateBlock,
+    rejected: &PendingInfo,
+) -> Result<(), String> {
+    if block.block_type != BlockType::RejectReceive || block.link.is_zero() {
+        return Ok(());
+    }
+    let pending_data = bincode::serialize(&PendingInfo {
+        source: block.account.clone(),
+        amount: rejected.amount,
+        timestamp: block.timestamp,
+        provenance: rejected.provenance.clone(),
+        memo: None,
+        destination_tag: None,
+        htlc: None,
+        returned: true,
+    })
+    .map_err(|e| format!("serialize pending: {e}"))?;
+    batch
+        .put_pending(&rejected.source, block.hash.as_bytes(), &pending_data)
+        .map_err(|e| format!("put returned pending: {e}"))?;
+    Ok(())
+}
+
+/// Send an unreceived transfer back to its sender.
+///
+/// The entry moves from `destination` to the sender under the same send
+/// hash, so the sender pockets it with an ordinary receive linking its own
+/// send block. It is stamped with the time it expired rather than the time
+/// it was noticed, and marked returned so it can't bounce back again.
+pub fn return_expired_pending_entry(
+    batch: &mut WriteBatch<'_>,
+    destination: &WalletAddress,
+    send_hash: &TxHash,
+    info: &PendingInfo,
+    expired_at: Timestamp,
+) -> Result<(), String> {
+    let pending_data = bincode::serialize(&PendingInfo {
+        source: destination.clone(),
+        amount: info.amount,
+        timestamp: expired_at,
+        provenance: info.provenance.clone(),
+        memo: None,
+        destination_tag: None,
+        htlc: None,
+        returned: true,
+    })
+    .map_err(|e| format!("serialize pending: {e}"))?;
+    batch
+        .delete_pending(destination, send_hash.as_bytes())
+        .map_err(|e| format!("delete expired pending: {e}"))?;
+    batch
+        .put_pending(&info.source, send_hash.as_bytes(), &pending_data)
+        .map_err(|e| format!("put returned pending: {e}"))?;
+    Ok(())
+}
+
+/// Pay a treasury grant as a pending entry from the treasury account.
+///
+/// The entry is keyed by the activation block that paid it, so the
+/// recipient pockets it with an ordinary receive linking that block. The
+/// TRST originates from the grant proposal at the block's timestamp.
+pub fn create_grant_pending_entry(
+    batch: &mut WriteBatch<'_>,
+    block: &StateBlock,
+    proposal: TxHash,
+    recipient: &WalletAddress,
+    amount: u128,
+) -> Result<(), String> {
+    let treasury = burst_governance::Treasury::address();
+    let pending_data = bincode::serialize(&PendingInfo {
+        source: treasury.clone(),
+        amount,
+        timestamp: block.timestamp,
+        provenance: vec![PendingProvenance {
+            amount,
+            origin: proposal,
+            origin_wallet: treasury,
+            origin_timestamp: block.timestamp,
+            effective_origin_timestamp: block.timestamp,
+            origin_proportions: Vec::new(),
+        }],
+        memo: None,
+        destination_tag: None,
+        htlc: None,
+        returned: false,
+    })
+    .map_err(|e| format!("serialize pending: {e}"))?;
+    batch
+        .put_pending(recipient, block.hash.as_bytes(), &pending_data)
+        .map_err(|e| format!("put grant pending: {e}"))?;
+    Ok(())
+}
+
+/// Return every pending entry whose TTL has passed by `now` to its sender,
+/// in one batch. Returns the entries that were returned, as they were
+/// before the move.
+pub fn return_expired_pending(
+    store: &LmdbStore,
+    ttl_secs: u64,
+    now: Timestamp,
+) -> Result<Vec<(WalletAddress, TxHash, PendingInfo)>, String> {
+    let expired: Vec<_> = store
+        .pending_store()
+        .iter_pending()
+        .map_err(|e| format!("scan pending: {e}"))?
+        .into_iter()
+        .filter(|(_, _, info)| {
+            info.expires_at(ttl_secs)
+                .is_some_and(|at| now.as_secs() >= at.as_secs())
+        })
+        .collect();
+    if expired.is_empty() {
+        return Ok(expired);
+    }
+    let mut batch = store
+        .write_batch()
+        .map_err(|e| format!("open write batch: {e}"))?;
+    for (destination, send_hash, info) in &expired {
+        let expired_at = info.expires_at(ttl_secs).unwrap_or(now);
+        return_expired_pending_entry(&mut batch, destination, send_hash, info, expired_at)?;
+    }
+    batch.commit().map_err(|e| format!("commit: {e}"))?;
+    Ok(expired)
+}
+
+/// Delete the pending entry of an HTLC lock being refunded.
+///
+/// The entry is keyed by the lock's receiver, not the refunding account, so
+/// the caller supplies `destination` (resolved from the lock block).
+pub fn delete_refunded_pending_entry(
+    batch: &mut WriteBatch<'_>,
+    block: &StateBlock,
+    destination: &WalletAddress,
+) -> Result<(), String> {
+    if block.block_type != BlockType::HtlcRefund || block.link.is_zero() {
+        return Ok(());
+    }
+    batch
+        .delete_pending(destination, block.link.as_bytes())
+        .map_err(|e| format!("delete refunded pending: {e}"))?;
+    Ok(())
+}
+
 /// Re-export the canonical PendingInfo from the store crate.
-pub use burst_store::pending::{PendingInfo, PendingProvenance};
+pub use burst_store::pending::{PendingHtlc, PendingInfo, PendingProvenance};
 
 #[cfg(test)]
 mod tests {
@@ -198,6 +366,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([0u8; 64]),
             hash: BlockHash::ZERO,
@@ -226,6 +400,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(2000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([0u8; 64]),
             hash: BlockHash::ZERO,
@@ -254,6 +434,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(3000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([0u8; 64]),
             hash: BlockHash::ZERO,
@@ -295,6 +481,10 @@ mod tests {
             amount: 500,
             timestamp: Timestamp::new(1234),
             provenance: Vec::new(),
+            memo: None,
+            destination_tag: None,
+            htlc: None,
+            returned: false,
         };
         let bytes = bincode::serialize(&info).unwrap();
         let deserialized: PendingInfo = bincode::deserialize(&bytes).unwrap();
@@ -9,14 +9,30 @@
 //! - Maintains clock synchronization
 //! - Participates in consensus (representative voting for conflict resolution)
 
+pub mod alerts;
+pub mod aliases;
+pub mod audit_log;
+pub mod backlog_scan;
+pub mod block_journal;
+pub mod block_prefilter;
 pub mod block_processor;
+pub mod block_proof;
 pub mod bootstrap;
 pub mod bounded_backlog;
+pub mod callback;
+pub mod checkpoint;
 pub mod config;
+pub mod confirmation_history;
 pub mod confirmation_processor;
 pub mod confirming_set;
 pub mod connection_registry;
+pub mod dev_controls;
 pub mod error;
+pub mod final_votes;
+pub mod fork_log;
+pub mod gossip;
+pub mod inbound_queue;
+pub mod invariants;
 pub mod ledger_bridge;
 pub mod ledger_cache;
 pub mod ledger_event;
@@ -24,49 +40,84 @@ pub mod ledger_updater;
 pub mod limits;
 pub mod local_broadcaster;
 pub mod logging;
+pub mod mailbox;
+pub mod merger_graph;
 pub mod metrics;
 pub mod node;
 pub mod online_weight;
+pub mod overload;
 pub mod parallel_processor;
 pub mod peer_connector;
 pub mod priority_queue;
+pub mod quorum_certificate;
 pub mod recently_confirmed;
+pub mod rep_key;
+pub mod revocation_notice;
 pub mod shutdown;
+pub mod state_digest;
+pub mod storage_guard;
 pub mod tracing_spans;
+pub mod trst_portfolios;
+pub mod tx_status;
 pub mod unchecked;
 pub mod verification_processor;
+pub mod vote_admission;
 pub mod wire_message;
 
+pub use alerts::{AlertMonitor, AlertNotifier, AlertsConfig};
+pub use backlog_scan::{BacklogCandidate, BacklogScan};
+pub use block_journal::{BlockJournal, JournalConfig, JournalFsync};
+pub use block_prefilter::{BlockPrefilter, PrefilterReject};
 pub use block_processor::{
     BlockContext, BlockProcessor, BlockSource, ProcessResult, ProcessingQueue, RollbackResult,
 };
+pub use block_proof::build_block_proof;
 pub use bootstrap::{BootstrapClient, BootstrapMessage, BootstrapServer};
 pub use bounded_backlog::BoundedBacklog;
+pub use callback::{CallbackConfig, CallbackDispatcher, RetryPolicy};
 pub use config::NodeConfig;
+pub use confirmation_history::{ConfirmationHistory, ConfirmationRecord, ConfirmationStats};
 pub use confirmation_processor::{
     CementResult, ChainWalker, ConfirmationProcessor, LmdbChainWalker,
 };
 pub use confirming_set::ConfirmingSet;
 pub use connection_registry::ConnectionRegistry;
+pub use dev_controls::{DevControlError, DevControls, DevOverrides};
 pub use error::NodeError;
+pub use final_votes::FinalVoteArchive;
+pub use gossip::{GossipConfig, GossipHop};
+pub use inbound_queue::{InboundLane, InboundQueue};
 pub use ledger_bridge::{process_block_economics, EconomicResult};
 pub use ledger_event::{EventBus, LedgerEvent};
 pub use ledger_updater::{
-    create_pending_entry, delete_pending_entry, update_account_on_block, PendingInfo,
+    create_pending_entry, create_returned_pending_entry, delete_pending_entry,
+    return_expired_pending, update_account_on_block, PendingInfo,
 };
 pub use limits::check_wallet_limits;
 pub use local_broadcaster::LocalBroadcaster;
-pub use logging::{init_logging, LogFormat};
+pub use logging::{
+    init_logging, LogFileConfig, LogFormat, LogReloadHandle, LogRotation, LoggingConfig,
+};
+pub use mailbox::{Mailbox, MailboxConfig, MailboxOutcome};
+pub use merger_graph::{attach_merger_graph_store, LmdbMergerGraphSource};
 pub use metrics::NodeMetrics;
 pub use node::BurstNode;
 pub use online_weight::OnlineWeightTracker;
+pub use overload::{OverloadConfig, OverloadController};
 pub use parallel_processor::ParallelBlockProcessor;
 pub use peer_connector::{connect_to_peer, is_peer_connected, PeerConnectorContext};
 pub use priority_queue::{work_difficulty, BlockPriorityQueue};
+pub use quorum_certificate::{build_quorum_certificate, record_quorum_certificate};
 pub use recently_confirmed::RecentlyConfirmed;
+pub use revocation_notice::{RevocationNotice, RevocationNoticeError};
 pub use shutdown::ShutdownController;
+pub use state_digest::{take_state_digest, DigestCheck, StateDigest, StateDigestLog};
+pub use storage_guard::{StorageConfig, StorageGuard, StorageLevel};
+pub use trst_portfolios::{attach_portfolio_store, LmdbPortfolioSource};
+pub use tx_status::TxStatusTracker;
 pub use unchecked::{GapType, UncheckedMap};
 pub use verification_processor::{VerificationOutcome, VerificationProcessor, VerifierPool};
+pub use vote_admission::{VoteAdmission, VoteAdmissionControl};
 pub use wire_message::{
     ConfirmAckMsg, ConfirmReqMsg, HandshakeMsg, KeepaliveMsg, WireMessage, WireVote,
 };
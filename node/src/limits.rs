@@ -1,23 +1,75 @@
-//! New wallet spending and rate limits.
+//! New wallet and bootstrap-phase spending and rate limits.
 //!
 //! Enforces `new_wallet_spending_limit`, `new_wallet_tx_limit_per_day`, and
 //! `new_wallet_rate_limit_duration_secs` from `ProtocolParams`. These limits
 //! only apply to wallets that have been verified for less than the configured
 //! duration; established wallets are exempt.
+//!
+//! Newly verified wallets also pass through probation tiers (see
+//! [`burst_verification::probation`]): daily send caps, and no endorsements
+//! while on probation.
+//!
+//! While the network is young — fewer verified wallets than
+//! `bootstrap_exit_threshold` — `bootstrap_spending_limit` additionally caps
+//! every TRST send and `bootstrap_burn_limit` every BRN burn, from every
+//! wallet, established or not. The block processor enforces these caps; they
+//! lift by themselves once the verified population crosses the threshold.
 
 use burst_store::account::AccountInfo;
 use burst_types::{ProtocolParams, Timestamp};
+use burst_verification::{probation_tier, ChallengeHistory};
+
+/// What a block asks of its wallet, as far as the limits are concerned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitedAction {
+    /// Send or lock `amount` TRST, `sent_today` having already been sent
+    /// within the last day.
+    Send { amount: u128, sent_today: u128 },
+    /// Burn `amount` BRN.
+    Burn { amount: u128 },
+    /// Issue an endorsement.
+    Endorse,
+}
 
-/// Check if a transaction from this account exceeds new-wallet limits.
+/// Check if a block from this account exceeds its new-wallet limits or the
+/// limits of its probation tier.
 ///
-/// Returns `Ok(())` if the transaction is allowed, or `Err(reason)` if it
-/// violates a spending or rate limit.
+/// Returns `Ok(())` if the block is allowed, or `Err(reason)` if it
+/// violates a spending, rate or endorsement limit.
 pub fn check_wallet_limits(
     account: &AccountInfo,
-    amount: u128,
+    history: &ChallengeHistory,
+    action: LimitedAction,
     now: Timestamp,
     params: &ProtocolParams,
 ) -> Result<(), String> {
+    let (tier, _) = probation_tier(account.verified_at, history, now, params);
+    let amount = match action {
+        LimitedAction::Endorse => {
+            if !tier.may_endorse() {
+                return Err(format!(
+                    "wallets on {} may not issue endorsements",
+                    tier.as_str()
+                ));
+            }
+            return Ok(());
+        }
+        LimitedAction::Send { amount, sent_today } => {
+            let cap = tier.daily_send_cap(params);
+            if cap > 0 && sent_today.saturating_add(amount) > cap {
+                return Err(format!(
+                    "sending {} would exceed the {} daily send cap {} ({} already sent today)",
+                    amount,
+                    tier.as_str(),
+                    cap,
+                    sent_today
+                ));
+            }
+            amount
+        }
+        LimitedAction::Burn { amount } => amount,
+    };
+
     // If no limits are configured, skip entirely
     if params.new_wallet_spending_limit == 0 && params.new_wallet_tx_limit_per_day == 0 {
         return Ok(());
@@ -47,6 +99,58 @@ pub fn check_wallet_limits(
     Ok(())
 }
 
+/// The network-wide bootstrap-phase caps in force.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BootstrapLimits {
+    /// Verified wallets needed before the caps lift.
+    pub exit_threshold: u32,
+    /// Largest TRST send or lock (raw units; 0 = no cap).
+    pub trst_limit: u128,
+    /// Largest BRN burn (raw units; 0 = no cap).
+    pub brn_limit: u128,
+}
+
+impl BootstrapLimits {
+    /// The caps governed by `params`.
+    pub fn from_params(params: &ProtocolParams) -> Self {
+        Self {
+            exit_threshold: params.bootstrap_exit_threshold,
+            trst_limit: params.bootstrap_spending_limit,
+            brn_limit: params.bootstrap_burn_limit,
+        }
+    }
+
+    /// Whether any cap is configured at all.
+    pub fn is_enabled(&self) -> bool {
+        self.trst_limit > 0 || self.brn_limit > 0
+    }
+}
+
+/// Check a block against the network-wide bootstrap-phase caps, given the
+/// number of verified wallets on the ledger: TRST sends against the TRST
+/// cap, BRN burns against the BRN cap.
+pub fn check_bootstrap_limits(
+    action: LimitedAction,
+    verified_count: u64,
+    limits: &BootstrapLimits,
+) -> Result<(), String> {
+    if verified_count >= u64::from(limits.exit_threshold) {
+        return Ok(());
+    }
+    let (amount, limit, unit) = match action {
+        LimitedAction::Send { amount, .. } => (amount, limits.trst_limit, "TRST"),
+        LimitedAction::Burn { amount } => (amount, limits.brn_limit, "BRN"),
+        LimitedAction::Endorse => return Ok(()),
+    };
+    if limit > 0 && amount > limit {
+        return Err(format!(
+            "{unit} amount {} exceeds bootstrap limit {} ({}/{} wallets verified)",
+            amount, limit, verified_count, limits.exit_threshold
+        ));
+    }
+    Ok(())
+}
+
 /// Check if a new wallet has exceeded its daily transaction limit.
 pub fn check_daily_tx_limit(block_count_today: u32, params: &ProtocolParams) -> Result<(), String> {
     if params.new_wallet_tx_limit_per_day == 0 {
@@ -66,6 +170,13 @@ mod tests {
     use super::*;
     use burst_types::{BlockHash, WalletAddress, WalletState};
 
+    fn send(amount: u128) -> LimitedAction {
+        LimitedAction::Send {
+            amount,
+            sent_today: 0,
+        }
+    }
+
     fn make_account(verified_at: Option<u64>) -> AccountInfo {
         AccountInfo {
             address: WalletAddress::new(
@@ -93,11 +204,12 @@ mod tests {
     }
 
     fn test_params() -> ProtocolParams {
-        let mut params = ProtocolParams::default();
-        params.new_wallet_spending_limit = 5000;
-        params.new_wallet_tx_limit_per_day = 10;
-        params.new_wallet_rate_limit_duration_secs = 30 * 24 * 3600; // 30 days
-        params
+        ProtocolParams {
+            new_wallet_spending_limit: 5000,
+            new_wallet_tx_limit_per_day: 10,
+            new_wallet_rate_limit_duration_secs: 30 * 24 * 3600, // 30 days
+            ..Default::default()
+        }
     }
 
     #[test]
@@ -106,7 +218,13 @@ mod tests {
         let params = test_params();
         let now = Timestamp::new(1000 + 86400); // 1 day after verification
 
-        let result = check_wallet_limits(&account, 4000, now, &params);
+        let result = check_wallet_limits(
+            &account,
+            &ChallengeHistory::default(),
+            send(4000),
+            now,
+            &params,
+        );
         assert!(result.is_ok());
     }
 
@@ -116,7 +234,13 @@ mod tests {
         let params = test_params();
         let now = Timestamp::new(1000 + 86400); // 1 day after verification
 
-        let result = check_wallet_limits(&account, 6000, now, &params);
+        let result = check_wallet_limits(
+            &account,
+            &ChallengeHistory::default(),
+            send(6000),
+            now,
+            &params,
+        );
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -130,7 +254,13 @@ mod tests {
         // Well past the rate limit duration (30 days + extra)
         let now = Timestamp::new(1000 + params.new_wallet_rate_limit_duration_secs + 1);
 
-        let result = check_wallet_limits(&account, 999999, now, &params);
+        let result = check_wallet_limits(
+            &account,
+            &ChallengeHistory::default(),
+            send(999999),
+            now,
+            &params,
+        );
         assert!(result.is_ok());
     }
 
@@ -140,7 +270,13 @@ mod tests {
         let params = test_params();
         let now = Timestamp::new(1000);
 
-        let result = check_wallet_limits(&account, 6000, now, &params);
+        let result = check_wallet_limits(
+            &account,
+            &ChallengeHistory::default(),
+            send(6000),
+            now,
+            &params,
+        );
         assert!(result.is_err());
     }
 
@@ -152,7 +288,13 @@ mod tests {
         params.new_wallet_tx_limit_per_day = 0;
         let now = Timestamp::new(1000 + 86400);
 
-        let result = check_wallet_limits(&account, 999999, now, &params);
+        let result = check_wallet_limits(
+            &account,
+            &ChallengeHistory::default(),
+            send(999999),
+            now,
+            &params,
+        );
         assert!(result.is_ok());
     }
 
@@ -162,7 +304,90 @@ mod tests {
         let params = test_params();
         let now = Timestamp::new(1000 + 86400);
 
-        let result = check_wallet_limits(&account, 5000, now, &params);
+        let result = check_wallet_limits(
+            &account,
+            &ChallengeHistory::default(),
+            send(5000),
+            now,
+            &params,
+        );
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn bootstrap_cap_applies_until_threshold() {
+        let mut params = test_params();
+        params.bootstrap_spending_limit = 1000;
+        params.bootstrap_exit_threshold = 50;
+        let limits = BootstrapLimits::from_params(&params);
+
+        assert!(check_bootstrap_limits(send(1000), 10, &limits).is_ok());
+        let err = check_bootstrap_limits(send(1001), 49, &limits).unwrap_err();
+        assert!(err.contains("TRST amount 1001 exceeds bootstrap limit 1000"));
+        assert!(check_bootstrap_limits(send(1001), 50, &limits).is_ok());
+
+        params.bootstrap_spending_limit = 0;
+        let limits = BootstrapLimits::from_params(&params);
+        assert!(!limits.is_enabled());
+        assert!(check_bootstrap_limits(send(u128::MAX), 0, &limits).is_ok());
+    }
+
+    #[test]
+    fn bootstrap_burns_are_capped_in_brn() {
+        let mut params = test_params();
+        params.bootstrap_spending_limit = 1000;
+        params.bootstrap_burn_limit = 50;
+        params.bootstrap_exit_threshold = 50;
+        let limits = BootstrapLimits::from_params(&params);
+
+        let burn = |amount| LimitedAction::Burn { amount };
+        assert!(check_bootstrap_limits(burn(50), 0, &limits).is_ok());
+        let err = check_bootstrap_limits(burn(51), 0, &limits).unwrap_err();
+        assert!(err.contains("BRN amount 51 exceeds bootstrap limit 50"));
+        // The TRST cap says nothing about burns, nor the BRN cap about sends.
+        assert!(check_bootstrap_limits(send(500), 0, &limits).is_ok());
+        params.bootstrap_burn_limit = 0;
+        let limits = BootstrapLimits::from_params(&params);
+        assert!(check_bootstrap_limits(burn(5000), 0, &limits).is_ok());
+        assert!(check_bootstrap_limits(LimitedAction::Endorse, 0, &limits).is_ok());
+    }
+
+    #[test]
+    fn probation_caps_daily_sends_and_endorsements() {
+        let account = make_account(Some(1000));
+        let mut params = test_params();
+        params.probation_period_secs = 7 * 86400;
+        params.probation_daily_send_cap = 3000;
+        params.provisional_daily_send_cap = 8000;
+        let history = ChallengeHistory::default();
+        let on_probation = Timestamp::new(1000 + 86400);
+        let provisional = Timestamp::new(1000 + 8 * 86400);
+
+        let spent = LimitedAction::Send {
+            amount: 1000,
+            sent_today: 2500,
+        };
+        let err =
+            check_wallet_limits(&account, &history, spent, on_probation, &params).unwrap_err();
+        assert!(err.contains("probation daily send cap"));
+        assert!(check_wallet_limits(&account, &history, spent, provisional, &params).is_ok());
+
+        let err = check_wallet_limits(
+            &account,
+            &history,
+            LimitedAction::Endorse,
+            on_probation,
+            &params,
+        )
+        .unwrap_err();
+        assert!(err.contains("may not issue endorsements"));
+        assert!(check_wallet_limits(
+            &account,
+            &history,
+            LimitedAction::Endorse,
+            provisional,
+            &params
+        )
+        .is_ok());
+    }
 }
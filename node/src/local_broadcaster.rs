@@ -211,8 +211,8 @@ mod tests {
         broadcaster.track(hash(4), vec![4], 400);
         assert_eq!(broadcaster.len(), 3);
         // hash(1) was evicted
-        assert!(broadcaster.blocks.get(&hash(1)).is_none());
-        assert!(broadcaster.blocks.get(&hash(4)).is_some());
+        assert!(!broadcaster.blocks.contains_key(&hash(1)));
+        assert!(broadcaster.blocks.contains_key(&hash(4)));
     }
 
     #[test]
@@ -4,42 +4,439 @@
 //! - [`LogFormat::Human`] — coloured, human-readable lines (development).
 //! - [`LogFormat::Json`] — newline-delimited JSON (production / log aggregation).
 //!
-//! The filter level can be overridden at runtime via the `RUST_LOG`
-//! environment variable.  When `RUST_LOG` is not set, the caller-supplied
-//! `level` string is used (e.g. `"info"`, `"debug,burst_node=trace"`).
+//! JSON lines always carry the same keys so log pipelines can rely on them:
+//! `ts_ms` (Unix milliseconds), `level`, `target`, `message`, `span` (the
+//! innermost span, or `null`) and `fields` (the event's other fields).
+//!
+//! Logs go to stdout and, when [`LoggingConfig::file`] is set, to a file
+//! rotated by size and/or time. The filter is the node's log level plus the
+//! per-module overrides in [`LoggingConfig::modules`]; [`LogReloadHandle`]
+//! swaps it while running. The `RUST_LOG` environment variable, when set,
+//! replaces the configured filter and disables reloading.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use serde::{Deserialize, Serialize};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::layer::Layered;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{
+    fmt as tracing_fmt, layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Layer,
+    Registry,
+};
 
 /// Selects the output format for structured logs.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum LogFormat {
     /// Pretty-printed, coloured output for local development.
+    #[default]
     Human,
     /// Newline-delimited JSON for production and log aggregation pipelines.
     Json,
 }
 
-/// Initialise the global tracing subscriber.
+impl LogFormat {
+    /// Parse `"human"` or `"json"`, as written in `NodeConfig::log_format`.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "human" => Some(Self::Human),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// When the log file is started afresh regardless of its size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogRotation {
+    #[default]
+    Never,
+    Hourly,
+    Daily,
+}
+
+impl LogRotation {
+    /// Index of the period containing `unix_secs`; rotation happens when it
+    /// changes.
+    fn period(self, unix_secs: u64) -> u64 {
+        match self {
+            Self::Never => 0,
+            Self::Hourly => unix_secs / 3_600,
+            Self::Daily => unix_secs / 86_400,
+        }
+    }
+}
+
+/// Log file output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogFileConfig {
+    /// File written to. Rotated files get `.1`, `.2`, … appended, `.1`
+    /// being the newest.
+    pub path: PathBuf,
+    /// Format of the file, independent of stdout.
+    pub format: LogFormat,
+    /// Rotate once the file would grow past this many bytes; `0` disables
+    /// size-based rotation.
+    pub max_bytes: u64,
+    /// Time-based rotation.
+    pub rotation: LogRotation,
+    /// Rotated files kept besides the current one.
+    pub max_files: usize,
+}
+
+impl Default for LogFileConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("burst-node.log"),
+            format: LogFormat::Json,
+            max_bytes: 100 * 1024 * 1024,
+            rotation: LogRotation::Never,
+            max_files: 5,
+        }
+    }
+}
+
+/// Logging settings beyond the base level and stdout format.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Level per module path, overriding the base level, e.g.
+    /// `"burst_node::connection_registry" = "debug"`. Reloadable.
+    pub modules: BTreeMap<String, String>,
+    /// File output; stdout only when unset.
+    pub file: Option<LogFileConfig>,
+}
+
+/// The filter directives for `level` with `modules` overriding it.
+pub fn filter_directives(level: &str, modules: &BTreeMap<String, String>) -> String {
+    std::iter::once(level.to_string())
+        .chain(
+            modules
+                .iter()
+                .map(|(module, level)| format!("{module}={level}")),
+        )
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Swaps the log filter of a running node.
+#[derive(Clone)]
+pub struct LogReloadHandle {
+    handle: Option<reload::Handle<EnvFilter, Registry>>,
+}
+
+impl LogReloadHandle {
+    /// Replace the filter with `level` and `modules`. Does nothing when the
+    /// filter comes from `RUST_LOG`.
+    pub fn reload(&self, level: &str, modules: &BTreeMap<String, String>) -> Result<(), String> {
+        let Some(handle) = &self.handle else {
+            return Ok(());
+        };
+        let filter = EnvFilter::try_new(filter_directives(level, modules))
+            .map_err(|e| format!("invalid log filter: {e}"))?;
+        handle.reload(filter).map_err(|e| e.to_string())
+    }
+
+    /// Poll the TOML config file at `path` every `interval` and apply its
+    /// `log_level` and `logging.modules` when the file changes. Other
+    /// logging settings need a restart.
+    pub fn watch_config_file(
+        self,
+        path: PathBuf,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+            let mut last = modified(&path);
+            loop {
+                tokio::time::sleep(interval).await;
+                let current = modified(&path);
+                if current == last {
+                    continue;
+                }
+                last = current;
+                let config = match fs::read_to_string(&path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|s| crate::NodeConfig::from_toml_str(&s).map_err(|e| e.to_string()))
+                {
+                    Ok(config) => config,
+                    Err(e) => {
+                        tracing::warn!(path = %path.display(), "not reloading log filter: {e}");
+                        continue;
+                    }
+                };
+                match self.reload(&config.log_level, &config.logging.modules) {
+                    Ok(()) => tracing::info!(
+                        filter = %filter_directives(&config.log_level, &config.logging.modules),
+                        "log filter reloaded"
+                    ),
+                    Err(e) => tracing::warn!("not reloading log filter: {e}"),
+                }
+            }
+        })
+    }
+}
+
+type FilteredRegistry = Layered<reload::Layer<EnvFilter, Registry>, Registry>;
+type BoxedLayer = Box<dyn Layer<FilteredRegistry> + Send + Sync>;
+
+fn output_layer<W>(format: LogFormat, writer: W, ansi: bool) -> BoxedLayer
+where
+    W: for<'w> tracing_fmt::MakeWriter<'w> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Human => tracing_fmt::layer()
+            .with_target(true)
+            .with_thread_ids(true)
+            .with_ansi(ansi)
+            .with_writer(writer)
+            .boxed(),
+        LogFormat::Json => tracing_fmt::layer()
+            .event_format(JsonFormat)
+            .with_writer(writer)
+            .boxed(),
+    }
+}
+
+/// Initialise the global tracing subscriber: stdout in `format`, plus the
+/// file in `config`, filtered at `level` with the module overrides.
 ///
 /// # Panics
 ///
 /// Panics if a global subscriber has already been set (i.e. this function
 /// was called twice in the same process).
-pub fn init_logging(format: LogFormat, level: &str) {
-    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level));
+pub fn init_logging(
+    format: LogFormat,
+    level: &str,
+    config: &LoggingConfig,
+) -> io::Result<LogReloadHandle> {
+    let from_env = EnvFilter::try_from_default_env().ok();
+    let reloadable = from_env.is_none();
+    let filter =
+        from_env.unwrap_or_else(|| EnvFilter::new(filter_directives(level, &config.modules)));
+    let (filter, handle) = reload::Layer::new(filter);
 
-    match format {
-        LogFormat::Human => {
-            tracing_subscriber::registry()
-                .with(filter)
-                .with(fmt::layer().with_target(true).with_thread_ids(true))
-                .init();
+    let mut layers = vec![output_layer(format, io::stdout, true)];
+    if let Some(file) = &config.file {
+        layers.push(output_layer(
+            file.format,
+            Mutex::new(RotatingFile::open(file)?),
+            false,
+        ));
+    }
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(layers)
+        .init();
+
+    Ok(LogReloadHandle {
+        handle: reloadable.then_some(handle),
+    })
+}
+
+// ── JSON format ─────────────────────────────────────────────────────────
+
+/// Formats events as one JSON object per line with fixed top-level keys.
+struct JsonFormat;
+
+#[derive(Default)]
+struct JsonFields(serde_json::Map<String, serde_json::Value>);
+
+impl Visit for JsonFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().into(), format!("{value:?}").into());
+    }
+}
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let mut fields = JsonFields::default();
+        event.record(&mut fields);
+        let message = fields.0.remove("message").unwrap_or_default();
+        let metadata = event.metadata();
+        let ts_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let line = serde_json::json!({
+            "ts_ms": ts_ms,
+            "level": metadata.level().as_str(),
+            "target": metadata.target(),
+            "message": message,
+            "span": ctx.lookup_current().map(|span| span.name()),
+            "fields": fields.0,
+        });
+        writeln!(writer, "{line}")
+    }
+}
+
+// ── Rotating file ───────────────────────────────────────────────────────
+
+/// A log file that starts afresh when it grows too large or its rotation
+/// period ends, keeping a bounded number of old files.
+struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    rotation: LogRotation,
+    max_files: usize,
+    file: File,
+    size: u64,
+    period: u64,
+}
+
+fn unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+impl RotatingFile {
+    fn open(config: &LogFileConfig) -> io::Result<Self> {
+        if let Some(dir) = config.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&config.path)?;
+        Ok(Self {
+            path: config.path.clone(),
+            max_bytes: config.max_bytes,
+            rotation: config.rotation,
+            max_files: config.max_files,
+            size: file.metadata()?.len(),
+            file,
+            period: config.rotation.period(unix_secs()),
+        })
+    }
+
+    fn rotated_path(&self, n: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{n}"));
+        name.into()
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if self.max_files == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            for n in (1..self.max_files).rev() {
+                let from = self.rotated_path(n);
+                if from.exists() {
+                    fs::rename(from, self.rotated_path(n + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1))?;
+        }
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let period = self.rotation.period(unix_secs());
+        let too_big = self.max_bytes > 0 && self.size + buf.len() as u64 > self.max_bytes;
+        if self.size > 0 && (too_big || period != self.period) {
+            self.rotate()?;
         }
-        LogFormat::Json => {
-            tracing_subscriber::registry()
-                .with(filter)
-                .with(fmt::layer().json().with_target(true).with_thread_ids(true))
-                .init();
+        self.period = period;
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module_overrides_follow_the_base_level() {
+        let modules = BTreeMap::from([
+            (
+                "burst_node::connection_registry".to_string(),
+                "debug".to_string(),
+            ),
+            ("heed".to_string(), "warn".to_string()),
+        ]);
+        assert_eq!(
+            filter_directives("info", &modules),
+            "info,burst_node::connection_registry=debug,heed=warn"
+        );
+        assert_eq!(filter_directives("info", &BTreeMap::new()), "info");
+    }
+
+    #[test]
+    fn file_rotates_by_size_and_keeps_max_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = LogFileConfig {
+            path: dir.path().join("logs").join("node.log"),
+            format: LogFormat::Json,
+            max_bytes: 10,
+            rotation: LogRotation::Never,
+            max_files: 2,
+        };
+        let mut file = RotatingFile::open(&config).unwrap();
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            file.write_all(line.as_bytes()).unwrap();
         }
+        let read = |name: &str| fs::read_to_string(dir.path().join("logs").join(name)).unwrap();
+        assert_eq!(read("node.log"), "fourth\n");
+        assert_eq!(read("node.log.1"), "third\n");
+        assert_eq!(read("node.log.2"), "second\n");
+        assert!(!dir.path().join("logs").join("node.log.3").exists());
     }
 }
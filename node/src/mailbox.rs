@@ -0,0 +1,279 @@
+//! Store-and-forward of encrypted direct messages.
+//!
+//! Verifiers and candidates coordinate a verification session over
+//! [`DirectMessage`]s. A node accepts one from a wallet over RPC or from a
+//! peer as gossip, holds it in memory until it expires, relays it, and
+//! hands it to the recipient's wallet when asked. Any node the message
+//! reaches can serve it, so the two wallets need not use the same node.
+//!
+//! Nodes cannot read the messages and never write them to the ledger or to
+//! disk: they are lost on restart and dropped at expiry. The mailbox is
+//! bounded overall and per recipient, and the oldest messages make room for
+//! new ones.
+
+use std::collections::{HashMap, VecDeque};
+
+use burst_crypto::DirectMessage;
+use burst_types::WalletAddress;
+use serde::{Deserialize, Serialize};
+
+/// Direct message limits, the `[direct_messages]` section of the node
+/// config.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MailboxConfig {
+    /// Whether to accept, relay and serve direct messages at all.
+    pub enabled: bool,
+    /// Furthest in the future a message may expire, in seconds.
+    pub max_ttl_secs: u64,
+    /// Largest ciphertext accepted, in bytes.
+    pub max_message_bytes: usize,
+    /// Messages held in total.
+    pub capacity: usize,
+    /// Messages held for any one recipient.
+    pub per_recipient: usize,
+}
+
+impl Default for MailboxConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_ttl_secs: 24 * 60 * 60,
+            max_message_bytes: 4096,
+            capacity: 4096,
+            per_recipient: 64,
+        }
+    }
+}
+
+/// What happened to a message offered to the mailbox.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MailboxOutcome {
+    Stored,
+    Duplicate,
+    Expired,
+    /// Expires further ahead than `max_ttl_secs`.
+    TtlTooLong,
+    TooLarge,
+    BadSignature,
+    Disabled,
+}
+
+impl MailboxOutcome {
+    /// Lowercase name, used as the metrics label.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MailboxOutcome::Stored => "stored",
+            MailboxOutcome::Duplicate => "duplicate",
+            MailboxOutcome::Expired => "expired",
+            MailboxOutcome::TtlTooLong => "ttl_too_long",
+            MailboxOutcome::TooLarge => "too_large",
+            MailboxOutcome::BadSignature => "bad_signature",
+            MailboxOutcome::Disabled => "disabled",
+        }
+    }
+}
+
+/// Direct messages held for their recipients.
+pub struct Mailbox {
+    config: MailboxConfig,
+    messages: HashMap<[u8; 32], DirectMessage>,
+    /// Message IDs, oldest arrival first.
+    order: VecDeque<[u8; 32]>,
+    per_recipient: HashMap<WalletAddress, usize>,
+}
+
+impl Mailbox {
+    pub fn new(config: MailboxConfig) -> Self {
+        Self {
+            config,
+            messages: HashMap::new(),
+            order: VecDeque::new(),
+            per_recipient: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &MailboxConfig {
+        &self.config
+    }
+
+    /// Number of messages held.
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Check `message` and hold it at unix second `now`. The signature is
+    /// checked last, after the cheap checks.
+    pub fn insert(&mut self, message: DirectMessage, now: u64) -> MailboxOutcome {
+        if !self.config.enabled {
+            return MailboxOutcome::Disabled;
+        }
+        if message.is_expired(now) {
+            return MailboxOutcome::Expired;
+        }
+        if message.expires_at > now.saturating_add(self.config.max_ttl_secs) {
+            return MailboxOutcome::TtlTooLong;
+        }
+        if message.ciphertext.len() > self.config.max_message_bytes {
+            return MailboxOutcome::TooLarge;
+        }
+        let id = message.id();
+        if self.messages.contains_key(&id) {
+            return MailboxOutcome::Duplicate;
+        }
+        if !message.verify_signature() {
+            return MailboxOutcome::BadSignature;
+        }
+
+        if self.count_for(&message.recipient) >= self.config.per_recipient {
+            let recipient = message.recipient.clone();
+            if let Some(position) = self
+                .order
+                .iter()
+                .position(|id| self.messages[id].recipient == recipient)
+            {
+                self.remove_at(position);
+            }
+        }
+        if self.messages.len() >= self.config.capacity {
+            self.prune(now);
+        }
+        while self.messages.len() >= self.config.capacity.max(1) {
+            self.remove_at(0);
+        }
+
+        *self
+            .per_recipient
+            .entry(message.recipient.clone())
+            .or_default() += 1;
+        self.order.push_back(id);
+        self.messages.insert(id, message);
+        MailboxOutcome::Stored
+    }
+
+    /// Unexpired messages for `recipient`, oldest first. They stay held
+    /// until they expire, so a wallet can fetch them again from here or
+    /// from another node.
+    pub fn fetch(&self, recipient: &WalletAddress, now: u64) -> Vec<DirectMessage> {
+        self.order
+            .iter()
+            .map(|id| &self.messages[id])
+            .filter(|message| message.recipient == *recipient && !message.is_expired(now))
+            .cloned()
+            .collect()
+    }
+
+    /// Drop expired messages. Returns how many were dropped.
+    pub fn prune(&mut self, now: u64) -> usize {
+        let before = self.messages.len();
+        let mut position = 0;
+        while position < self.order.len() {
+            if self.messages[&self.order[position]].is_expired(now) {
+                self.remove_at(position);
+            } else {
+                position += 1;
+            }
+        }
+        before - self.messages.len()
+    }
+
+    fn count_for(&self, recipient: &WalletAddress) -> usize {
+        self.per_recipient.get(recipient).copied().unwrap_or(0)
+    }
+
+    fn remove_at(&mut self, position: usize) {
+        let Some(id) = self.order.remove(position) else {
+            return;
+        };
+        let Some(message) = self.messages.remove(&id) else {
+            return;
+        };
+        if let Some(count) = self.per_recipient.get_mut(&message.recipient) {
+            *count -= 1;
+            if *count == 0 {
+                self.per_recipient.remove(&message.recipient);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_crypto::{derive_address, generate_keypair};
+    use burst_types::KeyPair;
+
+    fn message(
+        sender: &KeyPair,
+        to: &WalletAddress,
+        body: &[u8],
+        expires_at: u64,
+    ) -> DirectMessage {
+        DirectMessage::seal(sender, to, body, 100, expires_at).unwrap()
+    }
+
+    #[test]
+    fn holds_checked_messages_until_they_expire() {
+        let mut mailbox = Mailbox::new(MailboxConfig::default());
+        let alice = generate_keypair();
+        let bob = derive_address(&generate_keypair().public);
+
+        let hello = message(&alice, &bob, b"hello", 1_000);
+        assert_eq!(mailbox.insert(hello.clone(), 100), MailboxOutcome::Stored);
+        assert_eq!(
+            mailbox.insert(hello.clone(), 100),
+            MailboxOutcome::Duplicate
+        );
+        assert_eq!(
+            mailbox.insert(message(&alice, &bob, b"late", 50), 100),
+            MailboxOutcome::Expired
+        );
+        assert_eq!(
+            mailbox.insert(message(&alice, &bob, b"forever", u64::MAX), 100),
+            MailboxOutcome::TtlTooLong
+        );
+        let mut forged = message(&alice, &bob, b"forged", 1_000);
+        forged.ciphertext.push(0);
+        assert_eq!(mailbox.insert(forged, 100), MailboxOutcome::BadSignature);
+
+        assert_eq!(mailbox.fetch(&bob, 500), vec![hello]);
+        assert!(mailbox.fetch(&bob, 1_001).is_empty());
+        assert_eq!(mailbox.prune(1_001), 1);
+        assert!(mailbox.is_empty());
+    }
+
+    #[test]
+    fn oldest_messages_make_room() {
+        let mut mailbox = Mailbox::new(MailboxConfig {
+            capacity: 3,
+            per_recipient: 2,
+            ..MailboxConfig::default()
+        });
+        let alice = generate_keypair();
+        let bob = derive_address(&generate_keypair().public);
+        let carol = derive_address(&generate_keypair().public);
+
+        let first = message(&alice, &bob, b"1", 1_000);
+        let second = message(&alice, &bob, b"2", 1_000);
+        let third = message(&alice, &bob, b"3", 1_000);
+        for m in [&first, &second, &third] {
+            assert_eq!(mailbox.insert(m.clone(), 100), MailboxOutcome::Stored);
+        }
+        assert_eq!(
+            mailbox.fetch(&bob, 100),
+            vec![second.clone(), third.clone()]
+        );
+
+        let to_carol = message(&alice, &carol, b"c1", 1_000);
+        let again = message(&alice, &carol, b"c2", 1_000);
+        mailbox.insert(to_carol.clone(), 100);
+        mailbox.insert(again.clone(), 100);
+        assert_eq!(mailbox.len(), 3);
+        assert_eq!(mailbox.fetch(&bob, 100), vec![third]);
+        assert_eq!(mailbox.fetch(&carol, 100), vec![to_carol, again]);
+    }
+}
@@ -0,0 +1,189 @@
+//! Disk-backed merger graph.
+//!
+//! The TRST engine's merger graph journals every change it makes. The block
+//! processor writes those changes edge by edge into the block's unified
+//! write batch, so the graph on disk commits together with the block that
+//! changed it and survives a crash. [`LmdbMergerGraphSource`] lets the
+//! in-memory graph page subgraphs back in after its cache has been trimmed.
+
+use burst_store::merger_graph::MergerGraphStore;
+use burst_store::meta::MetaStore;
+use burst_store::StoreError;
+use burst_store_lmdb::{LmdbMergerGraphStore, LmdbStore, WriteBatch};
+use burst_trst::{MergeNode, MergerGraph, MergerGraphDelta, MergerGraphSource};
+use burst_types::TxHash;
+
+/// Merge nodes kept in memory before the cache is dropped.
+pub const MERGER_GRAPH_CACHE_NODES: usize = 100_000;
+
+/// Meta key of the whole-graph blob written by earlier versions at shutdown.
+pub const LEGACY_MERGER_GRAPH_META_KEY: &str = "merger_graph";
+
+/// Reads the persisted merger graph for [`MergerGraph::page_in`].
+pub struct LmdbMergerGraphSource(pub LmdbMergerGraphStore);
+
+impl MergerGraphSource for LmdbMergerGraphSource {
+    fn merges_for_origin(&self, origin: &TxHash) -> Vec<TxHash> {
+        self.0.get_merges_for_origin(origin).unwrap_or_else(|e| {
+            tracing::warn!(%origin, "failed to read merger graph edges: {e}");
+            Vec::new()
+        })
+    }
+
+    fn downstream(&self, merge_tx: &TxHash) -> Vec<TxHash> {
+        self.0.get_downstream(merge_tx).unwrap_or_else(|e| {
+            tracing::warn!(%merge_tx, "failed to read downstream merges: {e}");
+            Vec::new()
+        })
+    }
+
+    fn merge_node(&self, merge_tx: &TxHash) -> Option<MergeNode> {
+        match self.0.get_merge_node(merge_tx) {
+            Ok(bytes) => bincode::deserialize(&bytes)
+                .inspect_err(|e| tracing::warn!(%merge_tx, "corrupt merge node: {e}"))
+                .ok(),
+            Err(StoreError::NotFound(_)) => None,
+            Err(e) => {
+                tracing::warn!(%merge_tx, "failed to read merge node: {e}");
+                None
+            }
+        }
+    }
+}
+
+/// Add merger graph changes to a write batch.
+pub fn write_merger_graph_deltas(
+    batch: &mut WriteBatch<'_>,
+    deltas: &[MergerGraphDelta],
+) -> Result<(), StoreError> {
+    for delta in deltas {
+        match delta {
+            MergerGraphDelta::OriginEdge { origin, merge_tx } => {
+                batch.put_merge_origin(origin, merge_tx)?
+            }
+            MergerGraphDelta::DownstreamEdge { parent, child } => {
+                batch.put_merge_downstream(parent, child)?
+            }
+            MergerGraphDelta::Node(node) => {
+                let bytes = bincode::serialize(node)
+                    .map_err(|e| StoreError::Serialization(e.to_string()))?;
+                batch.put_merge_node(&node.merge_tx, &bytes)?
+            }
+            MergerGraphDelta::Revoked(origin) => batch.put_revoked_origin(origin)?,
+            MergerGraphDelta::Unrevoked(origin) => batch.delete_revoked_origin(origin)?,
+        }
+    }
+    Ok(())
+}
+
+/// Write the graph's pending changes in a batch of their own, for changes
+/// made outside block processing (revocations). Returns how many were written.
+pub fn persist_merger_graph_deltas(
+    store: &LmdbStore,
+    graph: &mut MergerGraph,
+) -> Result<usize, StoreError> {
+    let deltas = graph.take_deltas();
+    if deltas.is_empty() {
+        return Ok(0);
+    }
+    let mut batch = store.write_batch()?;
+    write_merger_graph_deltas(&mut batch, &deltas)?;
+    batch.commit()?;
+    Ok(deltas.len())
+}
+
+/// Back `graph` with the store. A whole-graph blob left by an earlier
+/// version is written out edge by edge first and then removed. Returns the
+/// number of revoked origins loaded.
+pub fn attach_merger_graph_store(
+    store: &LmdbStore,
+    graph: &mut MergerGraph,
+) -> Result<usize, StoreError> {
+    let meta = store.meta_store();
+    if let Ok(bytes) = meta.get_meta(LEGACY_MERGER_GRAPH_META_KEY) {
+        match MergerGraph::from_bytes(&bytes) {
+            Ok(legacy) => {
+                let deltas = legacy.snapshot_deltas();
+                let mut batch = store.write_batch()?;
+                write_merger_graph_deltas(&mut batch, &deltas)?;
+                batch.commit()?;
+                tracing::info!(
+                    entries = deltas.len(),
+                    "migrated merger graph blob to per-edge storage"
+                );
+            }
+            Err(e) => tracing::warn!("discarding unreadable merger graph blob: {e}"),
+        }
+        meta.delete_meta(LEGACY_MERGER_GRAPH_META_KEY)?;
+    }
+
+    let merger_store = store.merger_graph_store();
+    let revoked = merger_store.iter_revoked_origins()?;
+    let count = revoked.len();
+    graph.attach_source(Box::new(LmdbMergerGraphSource(merger_store)), revoked);
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_trst::merger_graph::MergeSource;
+    use burst_types::WalletAddress;
+
+    fn tx(id: u8) -> TxHash {
+        TxHash::new([id; 32])
+    }
+
+    fn merge(merge_tx: TxHash, origins: &[TxHash]) -> MergeNode {
+        MergeNode {
+            merge_tx,
+            source_origins: origins
+                .iter()
+                .map(|origin| MergeSource {
+                    origin: *origin,
+                    amount: 50,
+                })
+                .collect(),
+            total_amount: 50 * origins.len() as u128,
+            holder: WalletAddress::new("brst_holder"),
+        }
+    }
+
+    #[test]
+    fn legacy_blob_is_migrated_and_paged_back_in() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LmdbStore::open(dir.path(), 46, 1 << 22).unwrap();
+
+        let mut legacy = MergerGraph::new();
+        legacy.record_merge(merge(tx(10), &[tx(1), tx(2)]));
+        legacy.mark_origin_revoked(tx(2));
+        store
+            .meta_store()
+            .put_meta(LEGACY_MERGER_GRAPH_META_KEY, &legacy.to_bytes())
+            .unwrap();
+
+        let mut graph = MergerGraph::new();
+        assert_eq!(attach_merger_graph_store(&store, &mut graph).unwrap(), 1);
+        assert!(store
+            .meta_store()
+            .get_meta(LEGACY_MERGER_GRAPH_META_KEY)
+            .is_err());
+        assert!(graph.is_origin_revoked(&tx(2)));
+        assert_eq!(graph.cached_node_count(), 0);
+
+        // New merges go to disk through a batch and survive a cache trim.
+        graph.record_merge(merge(tx(20), &[tx(10), tx(3)]));
+        graph.mark_origin_revoked(tx(1));
+        assert_eq!(persist_merger_graph_deltas(&store, &mut graph).unwrap(), 5);
+        assert!(graph.trim_cache(0));
+
+        graph.page_in(&tx(1));
+        let events = graph.propagate_revocation(&tx(1));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].merge_tx, tx(20));
+
+        let mut reopened = MergerGraph::new();
+        attach_merger_graph_store(&store, &mut reopened).unwrap();
+        assert!(reopened.is_origin_revoked(&tx(1)));
+    }
+}
@@ -6,9 +6,10 @@
 //! can encode into the Prometheus text exposition format.
 
 use prometheus::{
-    register_histogram_with_registry, register_int_counter_with_registry,
-    register_int_gauge_with_registry, Histogram, HistogramOpts, IntCounter, IntGauge, Opts,
-    Registry,
+    register_histogram_with_registry, register_int_counter_vec_with_registry,
+    register_int_counter_with_registry, register_int_gauge_vec_with_registry,
+    register_int_gauge_with_registry, Histogram, HistogramOpts, IntCounter, IntCounterVec,
+    IntGauge, IntGaugeVec, Opts, Registry,
 };
 
 /// Central collection of all node-level Prometheus metrics.
@@ -27,6 +28,32 @@ pub struct NodeMetrics {
     pub transactions_received: IntCounter,
     /// Total number of consensus votes received from representatives.
     pub votes_received: IntCounter,
+    /// Total stored final votes re-sent in answer to `confirm_req`.
+    pub final_votes_replayed: IntCounter,
+    /// Inbound messages dropped because their lane was full, per lane.
+    pub inbound_dropped: IntCounterVec,
+    /// Gossiped blocks dropped by the edge checks, per reason.
+    pub blocks_prefiltered: IntCounterVec,
+    /// Inbound votes offered for admission, per outcome.
+    pub votes_admission: IntCounterVec,
+    /// Revocation notices issued, applied or dropped, per outcome.
+    pub revocation_notices: IntCounterVec,
+    /// Forks detected and resolved, per outcome.
+    pub forks: IntCounterVec,
+    /// Checkpoint votes signed, recorded or dropped, per outcome.
+    pub checkpoint_votes: IntCounterVec,
+    /// Account-state roots compared with peers at equal block counts, per
+    /// outcome.
+    pub state_root_checks: IntCounterVec,
+    /// State digests compared with peers at equal cemented counts, per
+    /// outcome.
+    pub state_digest_checks: IntCounterVec,
+    /// Received gossip relayed on or stopped at its hop limit, per outcome.
+    pub gossip_relays: IntCounterVec,
+    /// Ledger invariant violations found after write batches, per invariant.
+    pub invariant_violations: IntCounterVec,
+    /// Direct messages offered to the mailbox, per outcome.
+    pub direct_messages: IntCounterVec,
 
     // ── Gauges ──────────────────────────────────────────────────────────
     /// Current number of blocks in the ledger.
@@ -39,6 +66,24 @@ pub struct NodeMetrics {
     pub election_count: IntGauge,
     /// Current number of blocks in the unchecked map (awaiting dependencies).
     pub unchecked_count: IntGauge,
+    /// Current number of `(root, voter)` entries in the final vote archive.
+    pub final_votes_stored: IntGauge,
+    /// Quorum certificates stored alongside cemented blocks.
+    pub quorum_certificates_stored: IntGauge,
+    /// Current limit on concurrent elections, as set by the election tuner.
+    pub active_elections_limit: IntGauge,
+    /// Network load multiplier applied to the edge work threshold.
+    pub work_network_multiplier: IntGauge,
+    /// Size of the LMDB memory map, in bytes.
+    pub lmdb_map_size_bytes: IntGauge,
+    /// High-water mark of the LMDB memory map, in bytes.
+    pub lmdb_map_used_bytes: IntGauge,
+    /// 1 while the node is read-only because storage ran low.
+    pub storage_read_only: IntGauge,
+    /// Received votes waiting to be applied to elections.
+    pub vote_backlog: IntGauge,
+    /// Messages waiting on each inbound processing lane.
+    pub inbound_queue_depth: IntGaugeVec,
 
     // ── Histograms ──────────────────────────────────────────────────────
     /// Time from block reception to confirmation, in milliseconds.
@@ -99,6 +144,15 @@ impl NodeMetrics {
         )
         .expect("failed to register votes_received counter");
 
+        let final_votes_replayed = register_int_counter_with_registry!(
+            Opts::new(
+                "burst_final_votes_replayed_total",
+                "Total stored final votes replayed to peers"
+            ),
+            registry
+        )
+        .expect("failed to register final_votes_replayed counter");
+
         // Gauges
         let block_count = register_int_gauge_with_registry!(
             Opts::new(
@@ -136,6 +190,198 @@ impl NodeMetrics {
         )
         .expect("failed to register unchecked_count gauge");
 
+        let final_votes_stored = register_int_gauge_with_registry!(
+            Opts::new(
+                "burst_final_votes_stored",
+                "Current number of stored final votes"
+            ),
+            registry
+        )
+        .expect("failed to register final_votes_stored gauge");
+
+        let quorum_certificates_stored = register_int_gauge_with_registry!(
+            Opts::new(
+                "burst_quorum_certificates_stored",
+                "Number of quorum certificates stored for cemented blocks"
+            ),
+            registry
+        )
+        .expect("failed to register quorum_certificates_stored gauge");
+
+        let active_elections_limit = register_int_gauge_with_registry!(
+            Opts::new(
+                "burst_active_elections_limit",
+                "Current limit on concurrent elections"
+            ),
+            registry
+        )
+        .expect("failed to register active_elections_limit gauge");
+
+        let work_network_multiplier = register_int_gauge_with_registry!(
+            Opts::new(
+                "burst_work_network_multiplier",
+                "Network load multiplier applied to the edge work threshold"
+            ),
+            registry
+        )
+        .expect("failed to register work_network_multiplier gauge");
+
+        let lmdb_map_size_bytes = register_int_gauge_with_registry!(
+            Opts::new(
+                "burst_lmdb_map_size_bytes",
+                "Size of the LMDB memory map, in bytes"
+            ),
+            registry
+        )
+        .expect("failed to register lmdb_map_size_bytes gauge");
+
+        let lmdb_map_used_bytes = register_int_gauge_with_registry!(
+            Opts::new(
+                "burst_lmdb_map_used_bytes",
+                "High-water mark of the LMDB memory map, in bytes"
+            ),
+            registry
+        )
+        .expect("failed to register lmdb_map_used_bytes gauge");
+
+        let storage_read_only = register_int_gauge_with_registry!(
+            Opts::new(
+                "burst_storage_read_only",
+                "1 while the node is read-only because storage ran low"
+            ),
+            registry
+        )
+        .expect("failed to register storage_read_only gauge");
+
+        let vote_backlog = register_int_gauge_with_registry!(
+            Opts::new(
+                "burst_vote_backlog",
+                "Received votes waiting to be applied to elections"
+            ),
+            registry
+        )
+        .expect("failed to register vote_backlog gauge");
+
+        let inbound_queue_depth = register_int_gauge_vec_with_registry!(
+            Opts::new(
+                "burst_inbound_queue_depth",
+                "Messages waiting on each inbound processing lane"
+            ),
+            &["lane"],
+            registry
+        )
+        .expect("failed to register inbound_queue_depth gauge");
+
+        let inbound_dropped = register_int_counter_vec_with_registry!(
+            Opts::new(
+                "burst_inbound_dropped_total",
+                "Inbound messages dropped because their lane was full"
+            ),
+            &["lane"],
+            registry
+        )
+        .expect("failed to register inbound_dropped counter");
+
+        let blocks_prefiltered = register_int_counter_vec_with_registry!(
+            Opts::new(
+                "burst_blocks_prefiltered_total",
+                "Gossiped blocks dropped by the edge checks"
+            ),
+            &["reason"],
+            registry
+        )
+        .expect("failed to register blocks_prefiltered counter");
+
+        let votes_admission = register_int_counter_vec_with_registry!(
+            Opts::new(
+                "burst_votes_admission_total",
+                "Inbound votes admitted or dropped by weight-proportional rate limiting"
+            ),
+            &["outcome"],
+            registry
+        )
+        .expect("failed to register votes_admission counter");
+
+        let revocation_notices = register_int_counter_vec_with_registry!(
+            Opts::new(
+                "burst_revocation_notices_total",
+                "Fraud revocation notices issued, applied, replayed or rejected"
+            ),
+            &["outcome"],
+            registry
+        )
+        .expect("failed to register revocation_notices counter");
+
+        let forks = register_int_counter_vec_with_registry!(
+            Opts::new(
+                "burst_forks_total",
+                "Forks detected, and resolved for the existing block, the competing block or a third"
+            ),
+            &["outcome"],
+            registry
+        )
+        .expect("failed to register forks counter");
+
+        let checkpoint_votes = register_int_counter_vec_with_registry!(
+            Opts::new(
+                "burst_checkpoint_votes_total",
+                "Checkpoint votes signed, recorded, duplicated, unweighted or rejected"
+            ),
+            &["outcome"],
+            registry
+        )
+        .expect("failed to register checkpoint_votes counter");
+
+        let state_root_checks = register_int_counter_vec_with_registry!(
+            Opts::new(
+                "burst_state_root_checks_total",
+                "Account state roots compared with peers, matched or diverged"
+            ),
+            &["outcome"],
+            registry
+        )
+        .expect("failed to register state_root_checks counter");
+
+        let state_digest_checks = register_int_counter_vec_with_registry!(
+            Opts::new(
+                "burst_state_digest_checks_total",
+                "State digests compared with peers, matched or diverged"
+            ),
+            &["outcome"],
+            registry
+        )
+        .expect("failed to register state_digest_checks counter");
+
+        let gossip_relays = register_int_counter_vec_with_registry!(
+            Opts::new(
+                "burst_gossip_relays_total",
+                "Received gossip relayed on, or expired at its TTL or hop limit"
+            ),
+            &["outcome"],
+            registry
+        )
+        .expect("failed to register gossip_relays counter");
+
+        let invariant_violations = register_int_counter_vec_with_registry!(
+            Opts::new(
+                "burst_invariant_violations_total",
+                "Economic invariant violations found after block write batches"
+            ),
+            &["invariant"],
+            registry
+        )
+        .expect("failed to register invariant_violations counter");
+
+        let direct_messages = register_int_counter_vec_with_registry!(
+            Opts::new(
+                "burst_direct_messages_total",
+                "Direct messages stored for their recipients or dropped"
+            ),
+            &["outcome"],
+            registry
+        )
+        .expect("failed to register direct_messages counter");
+
         // Histograms – use exponential buckets covering 1 ms → ~16 s.
         let confirmation_latency_ms = register_histogram_with_registry!(
             HistogramOpts::new(
@@ -164,11 +410,32 @@ impl NodeMetrics {
             blocks_confirmed,
             transactions_received,
             votes_received,
+            final_votes_replayed,
+            inbound_dropped,
+            blocks_prefiltered,
+            votes_admission,
+            revocation_notices,
+            forks,
+            checkpoint_votes,
+            state_root_checks,
+            state_digest_checks,
+            gossip_relays,
+            invariant_violations,
+            direct_messages,
             block_count,
             account_count,
             peer_count,
             election_count,
             unchecked_count,
+            final_votes_stored,
+            quorum_certificates_stored,
+            active_elections_limit,
+            work_network_multiplier,
+            lmdb_map_size_bytes,
+            lmdb_map_used_bytes,
+            storage_read_only,
+            vote_backlog,
+            inbound_queue_depth,
             confirmation_latency_ms,
             block_process_time_ms,
         }
@@ -1,5 +1,7 @@
 //! The main BURST node struct — wires all protocol subsystems together.
 
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -8,62 +10,121 @@ use tokio::task::JoinHandle;
 
 use burst_brn::BrnEngine;
 use burst_consensus::{
-    ActiveElections, OnlineWeightSampler, PriorityScheduler, RepWeightCache, VoteCache,
-    VoteGenerator,
+    ActiveElections, ElectionLimitTuner, OnlineWeightSampler, PriorityScheduler, RepCrawler,
+    RepWeightCache, VoteCache, VoteGenerator,
 };
+use burst_crypto::DirectMessage;
 use burst_governance::delegation::DelegationEngine;
-use burst_governance::GovernanceEngine;
+use burst_governance::{
+    BallotBox, GovernanceEngine, ProposalDependencies, Treasury, TreasurySource, VoteTimes,
+};
 use burst_ledger::{
-    BlockType, DagFrontier, LedgerPruner, PruningConfig, StateBlock, CURRENT_BLOCK_VERSION,
+    BlockProof, BlockType, CheckpointVote, DagFrontier, LedgerPruner, PruningConfig, StateBlock,
+    CURRENT_BLOCK_VERSION,
 };
 use burst_messages::PeerAddress;
-use burst_network::{Broadcaster, ClockSync, PeerManager, PortMapper, UpnpState};
-use burst_rpc::{BlockProcessorCallback, ProcessResult as RpcProcessResult, RpcServer, RpcState};
+use burst_network::{
+    Broadcaster, ClockSync, IpNet, NetworkError, PeerManager, PeerQuality, PortMapper, UpnpState,
+    BAN_INDEFINITE,
+};
+use burst_protocol::codec::BufferPool;
+use burst_rpc::error::RpcError;
+use burst_rpc::{
+    AdminFuture, AuditLog, BlockProcessorCallback, BlockProofSource, CheckpointEntry,
+    CheckpointView, CheckpointsFuture, ConfirmationEntry, ConfirmationHistoryFuture,
+    ConfirmationHistorySnapshot, ConfirmationHistoryView, ContainerView, ContainersFuture,
+    DevAdmin, DevClock, DirectMessageFuture, DirectMessageRelay, PeerAdmin, PeerBan,
+    ProcessResult as RpcProcessResult, QuorumFuture, QuorumSnapshot, QuorumView,
+    RepresentativeKeyAdmin, RepresentativeRotation, RpcServer, RpcState, SyncAdmin,
+    TrstValueFuture, TrstValueView,
+};
 use burst_store::block::BlockStore;
 use burst_store::brn::BrnStore;
 use burst_store::frontier::FrontierStore;
-use burst_store_lmdb::LmdbStore;
+use burst_store_lmdb::{LmdbStore, Migrator, CURRENT_SCHEMA_VERSION};
 use burst_trst::TrstEngine;
-use burst_types::{BlockHash, ProtocolParams, Signature, Timestamp, TxHash, WalletAddress};
+use burst_types::{
+    BlockHash, BurstError, ErrorCode, MemoryAccounting, ProtocolParams, Signature, Timestamp,
+    TxHash, WalletAddress,
+};
+use burst_utils::{SampleType, Stats, Watchdog, WatchedOp};
+use burst_wallet_core::keystore::KdfParams;
 use burst_websocket::{WebSocketServer, WsState};
 use burst_work::WorkGenerator;
 
 use burst_store::account::AccountStore;
+use burst_store::alias::AliasStore;
 use burst_store::delegation::{DelegationRecord, DelegationStore};
+use burst_store::final_votes::FinalVoteStore;
 use burst_store::meta::MetaStore;
 use burst_store::pending::PendingStore;
+use burst_store::quorum_certificate::QuorumCertificateStore;
+use burst_store::recovery::{RecoveryConfig, RecoveryStore};
 use burst_store::rep_weights::RepWeightStore;
 use burst_store::trst_index::TrstIndexStore;
-
-use crate::block_processor::{BlockProcessor, ProcessResult};
+use burst_store::verification::VerificationStore;
+
+use crate::alerts::{counter_total, disk_free_percent, AlertMonitor, AlertNotifier, HealthSample};
+use crate::audit_log::{NodeAuditLog, AUDIT_LOG_FILE};
+use crate::backlog_scan::{BacklogScan, DEFAULT_ACCOUNTS_PER_PASS, DEFAULT_MAX_SCHEDULED_PER_PASS};
+use crate::block_journal::{BlockJournal, BLOCK_JOURNAL_FILE};
+use crate::block_prefilter::{BlockPrefilter, DEFAULT_PREFILTER_CAPACITY};
+use crate::block_processor::{
+    applied_height, validate_live_timestamp, BlockProcessor, ProcessResult, TX_TIME_TOLERANCE_SECS,
+};
+use crate::block_proof::build_block_proof;
 use crate::bounded_backlog::BoundedBacklog;
+use crate::callback::CallbackDispatcher;
+use crate::checkpoint::{
+    ledger_cemented_count, recent_checkpoints, record_checkpoint_vote, CheckpointTrigger,
+};
 use crate::config::NodeConfig;
+use crate::confirmation_history::{
+    transferred_amount, ConfirmationHistory, ConfirmationRecord, CONFIRMATION_HISTORY_META_KEY,
+};
 use crate::confirmation_processor::{CementResult, ConfirmationProcessor, LmdbChainWalker};
 use crate::confirming_set::ConfirmingSet;
-use crate::connection_registry::{spawn_peer_read_loop, write_framed, ConnectionRegistry};
+use crate::connection_registry::{
+    spawn_inbound_workers, spawn_peer_read_loop, write_framed, ConnectionRegistry, InboundContext,
+};
+use crate::dev_controls::DevControls;
 use crate::error::NodeError;
+use crate::final_votes::{FinalVoteArchive, DEFAULT_MAX_FINAL_VOTES};
+use crate::fork_log::NodeForkLog;
+use crate::gossip::{self, GossipConfig};
+use crate::inbound_queue::InboundQueue;
+use crate::invariants::InvariantChecker;
 use crate::ledger_cache::LedgerCache;
 use crate::local_broadcaster::LocalBroadcaster;
+use crate::mailbox::{Mailbox, MailboxOutcome};
 use crate::metrics::NodeMetrics;
 use crate::online_weight::OnlineWeightTracker;
+use crate::overload::{LoadSample, OverloadController};
 use crate::priority_queue::BlockPriorityQueue;
+use crate::quorum_certificate::{load_quorum_certificate, record_quorum_certificate};
 use crate::recently_confirmed::RecentlyConfirmed;
+use crate::rep_key;
+use crate::revocation_notice::{
+    load_revocation_notice, revoke_fraudulent_wallet, save_revocation_notice, RevocationNotice,
+};
 use crate::shutdown::ShutdownController;
+use crate::state_digest::{take_state_digest, StateDigestLog};
+use crate::storage_guard::{map_size_at_open, StorageGuard, StorageLevel, StorageSample};
+use crate::tx_status::{TxStatusTracker, DEFAULT_TX_STATUS_CAPACITY};
 use crate::verification_processor::{VerificationProcessor, VerifierPool};
+use crate::vote_admission::VoteAdmissionControl;
 use crate::wire_message::{WireMessage, WireVote};
 
-/// Default LMDB map size: 1 GiB.
-const DEFAULT_MAP_SIZE: usize = 1 << 30;
+/// How long growing the LMDB map waits for open transactions.
+const MAP_GROW_WAIT: Duration = Duration::from_millis(250);
 /// Number of named LMDB databases.
-const MAX_DBS: u32 = 29;
+const MAX_DBS: u32 = 47;
 /// Channel capacity for the block-processing pipeline.
 const BLOCK_CHANNEL_CAPACITY: usize = 4096;
 /// Channel capacity for outbound peer messages.
 const OUTBOUND_CHANNEL_CAPACITY: usize = 4096;
 /// Timeout for waiting on background tasks during shutdown.
 const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
-/// Meta-store key used to persist the serialized MergerGraph.
-const MERGER_GRAPH_META_KEY: &str = "merger_graph";
 /// Meta-store key used to persist the verification orchestrator snapshot.
 const VERIFICATION_ORCHESTRATOR_META_KEY: &str = "verification_orchestrator";
 
@@ -86,27 +147,548 @@ fn genesis_address() -> WalletAddress {
 /// the RPC crate, breaking the circular dependency.
 struct NodeBlockProcessor {
     block_queue: Arc<BlockPriorityQueue>,
+    live_params: Arc<std::sync::RwLock<ProtocolParams>>,
+    tx_status: Arc<TxStatusTracker>,
+    storage_guard: Arc<StorageGuard>,
 }
 
 impl BlockProcessorCallback for NodeBlockProcessor {
-    fn process_block(&self, block_bytes: &[u8]) -> Result<RpcProcessResult, String> {
+    fn process_block(&self, block_bytes: &[u8]) -> Result<RpcProcessResult, RpcError> {
         let block: StateBlock = bincode::deserialize(block_bytes)
-            .map_err(|e| format!("failed to deserialize block: {e}"))?;
+            .map_err(|e| NodeError::InvalidBlock(format!("failed to deserialize block: {e}")))?;
 
+        if self.storage_guard.is_read_only() {
+            return Err(NodeError::ReadOnly.into());
+        }
+        validate_live_timestamp(&block, unix_now_secs()).map_err(NodeError::InvalidBlock)?;
+        let hash = block.hash;
+        self.tx_status.submitted(hash, unix_now_secs());
         if self.block_queue.try_push(block) {
             Ok(RpcProcessResult::Queued)
         } else {
-            Err("block queue full — try again later".to_string())
+            self.tx_status
+                .dropped(&hash, "block queue full", unix_now_secs());
+            Err(NodeError::QueueFull("block queue full — try again later".into()).into())
         }
     }
+
+    fn params_hash(&self) -> Option<BlockHash> {
+        self.live_params
+            .read()
+            .ok()
+            .map(|params| params.params_hash())
+    }
+
+    fn params(&self) -> Option<ProtocolParams> {
+        self.live_params.read().ok().map(|params| params.clone())
+    }
+}
+
+// ── RepresentativeKeyAdmin bridge ───────────────────────────────────────
+
+/// Rotates the representative key file for the RPC crate and swaps the new
+/// key into the vote generator.
+struct NodeRepresentativeKeyAdmin {
+    key_file: PathBuf,
+    vote_generator: Arc<Mutex<VoteGenerator>>,
+    ws_state: Arc<WsState>,
+}
+
+impl RepresentativeKeyAdmin for NodeRepresentativeKeyAdmin {
+    fn rotate_representative_key<'a>(
+        &'a self,
+        password: &'a str,
+    ) -> AdminFuture<'a, RepresentativeRotation> {
+        Box::pin(async move {
+            // Hold the generator across the file rewrite so concurrent
+            // rotations cannot interleave.
+            let mut vg = self.vote_generator.lock().await;
+            let path = self.key_file.clone();
+            let password = password.to_string();
+            let (previous, keys) =
+                tokio::task::spawn_blocking(move || rep_key::rotate(&path, &password))
+                    .await
+                    .map_err(|e| format!("key rotation task failed: {e}"))?
+                    .map_err(|e| e.to_string())?;
+
+            let previous_rep = burst_crypto::derive_address(&previous.public);
+            let representative = burst_crypto::derive_address(&keys.public);
+            let rotated_at = Timestamp::now();
+            let digest = rep_key::rotation_digest(&previous_rep, &representative, rotated_at);
+            let rotation = RepresentativeRotation {
+                previous: previous_rep,
+                representative: representative.clone(),
+                rotated_at: rotated_at.as_secs(),
+                previous_signature: hex::encode(
+                    burst_crypto::sign_message(&digest, &previous.private).0,
+                ),
+                signature: hex::encode(burst_crypto::sign_message(&digest, &keys.private).0),
+            };
+            vg.rotate_key(representative, keys.private.0);
+            drop(vg);
+
+            tracing::info!(
+                previous = %rotation.previous,
+                representative = %rotation.representative,
+                "representative key rotated"
+            );
+            self.ws_state.publish_representative_rotation(
+                rotation.previous.as_str(),
+                rotation.representative.as_str(),
+                &rotation.previous_signature,
+            );
+            Ok(rotation)
+        })
+    }
+}
+
+// ── PeerAdmin bridge ────────────────────────────────────────────────────
+
+/// Applies operator bans to the peer manager, persists them, and closes
+/// the connections they cover.
+struct NodePeerAdmin {
+    peer_manager: Arc<RwLock<PeerManager>>,
+    connection_registry: Arc<RwLock<ConnectionRegistry>>,
+    store: Arc<LmdbStore>,
+}
+
+impl PeerAdmin for NodePeerAdmin {
+    fn ban_peer<'a>(
+        &'a self,
+        target: &'a str,
+        duration_secs: Option<u64>,
+    ) -> AdminFuture<'a, PeerBan> {
+        Box::pin(async move {
+            use burst_store::peer::PeerStore;
+            let net: IpNet = target.parse().map_err(|e: NetworkError| e.to_string())?;
+            let until = match duration_secs {
+                Some(secs) => unix_now_secs().saturating_add(secs),
+                None => BAN_INDEFINITE,
+            };
+            self.store
+                .peer_store()
+                .put_ban(&net.to_string(), until)
+                .map_err(|e| format!("failed to persist ban: {e}"))?;
+            let disconnected = self.peer_manager.write().await.ban_address(net, until);
+            if !disconnected.is_empty() {
+                let mut registry = self.connection_registry.write().await;
+                for peer_id in &disconnected {
+                    registry.remove(peer_id);
+                }
+            }
+            tracing::info!(
+                target = %net,
+                until,
+                disconnected = disconnected.len(),
+                "peer address banned"
+            );
+            Ok(PeerBan {
+                target: net.to_string(),
+                until: (until != BAN_INDEFINITE).then_some(until),
+                disconnected: disconnected.len(),
+            })
+        })
+    }
+
+    fn unban_peer<'a>(&'a self, target: &'a str) -> AdminFuture<'a, bool> {
+        Box::pin(async move {
+            use burst_store::peer::PeerStore;
+            let net: IpNet = target.parse().map_err(|e: NetworkError| e.to_string())?;
+            self.store
+                .peer_store()
+                .delete_ban(&net.to_string())
+                .map_err(|e| format!("failed to remove persisted ban: {e}"))?;
+            let removed = self.peer_manager.write().await.unban_address(&net);
+            if removed {
+                tracing::info!(target = %net, "peer address unbanned");
+            }
+            Ok(removed)
+        })
+    }
+}
+
+// ── SyncAdmin bridge ────────────────────────────────────────────────────
+
+/// Peers asked for an account chain by `bootstrap_account`, at least.
+const BOOTSTRAP_ACCOUNT_MIN_PEERS: usize = 3;
+
+/// Re-floods stored blocks and requests account chains from peers for the
+/// `republish` and `bootstrap_account` admin actions.
+struct NodeSyncAdmin {
+    peer_manager: Arc<RwLock<PeerManager>>,
+    broadcaster: Broadcaster,
+    store: Arc<LmdbStore>,
+}
+
+impl NodeSyncAdmin {
+    async fn connected_peers(&self) -> Vec<burst_network::PeerState> {
+        let pm = self.peer_manager.read().await;
+        pm.iter_connected().map(|(_, s)| s.clone()).collect()
+    }
+}
+
+impl SyncAdmin for NodeSyncAdmin {
+    fn republish<'a>(&'a self, hash: &'a BlockHash) -> AdminFuture<'a, usize> {
+        Box::pin(async move {
+            let bytes = self
+                .store
+                .block_store()
+                .get_block(hash)
+                .map_err(|e| format!("failed to load block {hash}: {e}"))?;
+            let block: StateBlock = bincode::deserialize(&bytes)
+                .map_err(|e| format!("failed to deserialize block {hash}: {e}"))?;
+            let message = bincode::serialize(&WireMessage::Block(Box::new(block)))
+                .map_err(|e| format!("failed to serialize block {hash}: {e}"))?;
+            let peers = self.connected_peers().await;
+            let result = self.broadcaster.broadcast_to_all(&message, &peers).await;
+            tracing::info!(%hash, sent = result.sent, failed = result.failed, "block republished");
+            Ok(result.sent)
+        })
+    }
+
+    fn bootstrap_account<'a>(&'a self, account: &'a WalletAddress) -> AdminFuture<'a, usize> {
+        Box::pin(async move {
+            let request = WireMessage::Bootstrap(crate::bootstrap::BootstrapMessage::BulkPullReq {
+                account: account.clone(),
+                end: BlockHash::ZERO,
+            });
+            let message = bincode::serialize(&request)
+                .map_err(|e| format!("failed to serialize bulk pull request: {e}"))?;
+            let peers = self.connected_peers().await;
+            if peers.is_empty() {
+                return Err("no connected peers to bootstrap from".into());
+            }
+            let result = self
+                .broadcaster
+                .broadcast_with_fanout(&message, &peers, BOOTSTRAP_ACCOUNT_MIN_PEERS)
+                .await;
+            tracing::info!(%account, peers = result.sent, "requested account chain from peers");
+            Ok(result.sent)
+        })
+    }
+}
+
+// ── DirectMessageRelay bridge ───────────────────────────────────────────
+
+/// Takes direct messages from wallets into the node's [`Mailbox`] and
+/// floods them to peers.
+struct NodeDirectMessages {
+    mailbox: Arc<Mutex<Mailbox>>,
+    peer_manager: Arc<RwLock<PeerManager>>,
+    broadcaster: Broadcaster,
+    gossip: GossipConfig,
+    message_dedup: Arc<Mutex<burst_network::MessageDedup>>,
+    metrics: Arc<NodeMetrics>,
+}
+
+impl DirectMessageRelay for NodeDirectMessages {
+    fn submit(&self, message: DirectMessage) -> DirectMessageFuture<'_, usize> {
+        Box::pin(async move {
+            let outcome = self
+                .mailbox
+                .lock()
+                .await
+                .insert(message.clone(), unix_now_secs());
+            self.metrics
+                .direct_messages
+                .with_label_values(&[outcome.as_str()])
+                .inc();
+            let refused = |code, reason: &str| Err(RpcError::coded(code, reason));
+            match outcome {
+                MailboxOutcome::Stored => {}
+                MailboxOutcome::Duplicate => return Ok(0),
+                MailboxOutcome::BadSignature => {
+                    return refused(
+                        ErrorCode::InvalidSignature,
+                        "invalid direct message signature",
+                    )
+                }
+                MailboxOutcome::Expired => {
+                    return refused(ErrorCode::InvalidRequest, "direct message has expired")
+                }
+                MailboxOutcome::TtlTooLong => {
+                    return refused(
+                        ErrorCode::InvalidRequest,
+                        "direct message expires too far ahead",
+                    )
+                }
+                MailboxOutcome::TooLarge => {
+                    return refused(ErrorCode::InvalidRequest, "direct message is too large")
+                }
+                MailboxOutcome::Disabled => {
+                    return refused(
+                        ErrorCode::Unsupported,
+                        "direct messages are not enabled on this node",
+                    )
+                }
+            }
+            let peers: Vec<burst_network::PeerState> = {
+                let pm = self.peer_manager.read().await;
+                pm.iter_connected().map(|(_, s)| s.clone()).collect()
+            };
+            let result = gossip::flood(
+                &self.broadcaster,
+                &self.gossip,
+                &self.message_dedup,
+                WireMessage::DirectMessage(Box::new(message)),
+                self.gossip.origin(),
+                &peers,
+            )
+            .await;
+            Ok(result.sent)
+        })
+    }
+
+    fn fetch<'a>(
+        &'a self,
+        recipient: &'a WalletAddress,
+    ) -> DirectMessageFuture<'a, Vec<DirectMessage>> {
+        Box::pin(async move { Ok(self.mailbox.lock().await.fetch(recipient, unix_now_secs())) })
+    }
+}
+
+// ── DevAdmin bridge ─────────────────────────────────────────────────────
+
+/// Exposes the node's [`DevControls`] to the RPC crate on dev networks.
+struct NodeDevAdmin {
+    controls: Arc<DevControls>,
+    governance: Arc<Mutex<GovernanceEngine>>,
+}
+
+impl DevAdmin for NodeDevAdmin {
+    fn now(&self) -> Timestamp {
+        self.controls.now()
+    }
+
+    fn advance_clock(&self, secs: u64) -> AdminFuture<'_, DevClock> {
+        Box::pin(async move {
+            let offset_secs = self
+                .controls
+                .advance_clock(secs)
+                .map_err(|e| e.to_string())?;
+            tracing::info!(secs, offset_secs, "dev clock advanced");
+            Ok(DevClock {
+                offset_secs,
+                now: self.controls.now_secs(),
+            })
+        })
+    }
+
+    fn set_instant_confirmation(&self, enabled: bool) -> AdminFuture<'_, bool> {
+        Box::pin(async move {
+            self.controls
+                .set_instant_confirmation(enabled)
+                .map_err(|e| e.to_string())?;
+            tracing::info!(enabled, "dev instant confirmation toggled");
+            Ok(enabled)
+        })
+    }
+
+    fn force_governance_phase(&self, proposal: TxHash) -> AdminFuture<'_, String> {
+        Box::pin(async move {
+            let mut governance = self.governance.lock().await;
+            let phase = self
+                .controls
+                .force_governance_phase(&mut governance, &proposal)
+                .map_err(|e| e.to_string())?;
+            tracing::info!(%proposal, ?phase, "dev governance phase forced");
+            Ok(format!("{phase:?}"))
+        })
+    }
+}
+
+// ── QuorumView bridge ───────────────────────────────────────────────────
+
+/// Exposes the node's [`OnlineWeightTracker`] to the RPC crate.
+struct NodeQuorumView {
+    online_weight_tracker: Arc<Mutex<OnlineWeightTracker>>,
+}
+
+impl QuorumView for NodeQuorumView {
+    fn quorum(&self) -> QuorumFuture<'_> {
+        Box::pin(async move {
+            let tracker = self.online_weight_tracker.lock().await;
+            QuorumSnapshot {
+                online_weight: tracker.current_weight(),
+                trended_weight: tracker.trended_weight(),
+                minimum_weight: tracker.minimum_weight(),
+                quorum_delta: tracker.quorum_delta(),
+                sample_count: tracker.sample_count(),
+            }
+        })
+    }
+}
+
+/// Exposes the node's [`ConfirmationHistory`] to the RPC crate.
+struct NodeConfirmationHistoryView {
+    history: Arc<Mutex<ConfirmationHistory>>,
+}
+
+impl ConfirmationHistoryView for NodeConfirmationHistoryView {
+    fn confirmation_history(&self, count: usize) -> ConfirmationHistoryFuture<'_> {
+        Box::pin(async move {
+            let history = self.history.lock().await;
+            let stats = history.stats();
+            ConfirmationHistorySnapshot {
+                confirmations: history
+                    .recent(count)
+                    .into_iter()
+                    .map(|record| ConfirmationEntry {
+                        hash: record.hash,
+                        account: record.account,
+                        amount: record.amount,
+                        tally: record.tally,
+                        duration_ms: record.duration_ms,
+                        confirmed_at: record.confirmed_at,
+                    })
+                    .collect(),
+                count: stats.count,
+                average_ms: stats.average_ms,
+                p95_ms: stats.p95_ms,
+            }
+        })
+    }
+}
+
+/// Serves stored checkpoints, weighed against the current quorum.
+struct NodeCheckpointView {
+    store: Arc<LmdbStore>,
+    rep_weights: Arc<RwLock<RepWeightCache>>,
+    online_weight_tracker: Arc<Mutex<OnlineWeightTracker>>,
+}
+
+impl CheckpointView for NodeCheckpointView {
+    fn checkpoints(&self, count: usize) -> CheckpointsFuture<'_> {
+        Box::pin(async move {
+            let checkpoints = recent_checkpoints(&self.store.checkpoint_store(), count)
+                .map_err(|e| e.to_string())?;
+            let quorum_delta = self.online_weight_tracker.lock().await.quorum_delta();
+            let weights = self.rep_weights.read().await;
+            Ok(checkpoints
+                .into_iter()
+                .map(|checkpoint| {
+                    let weight = checkpoint.weight(|rep| weights.weight(rep));
+                    CheckpointEntry {
+                        checkpoint,
+                        weight,
+                        quorum: weight >= quorum_delta,
+                    }
+                })
+                .collect())
+        })
+    }
+}
+
+/// Serves light-client block proofs from the ledger and final vote archive.
+struct NodeBlockProofSource {
+    store: Arc<LmdbStore>,
+}
+
+impl BlockProofSource for NodeBlockProofSource {
+    fn block_proof(&self, hash: &BlockHash) -> Result<BlockProof, String> {
+        let archive = FinalVoteArchive::new(self.store.final_vote_store(), DEFAULT_MAX_FINAL_VOTES);
+        build_block_proof(
+            &self.store.block_store(),
+            &archive,
+            &self.store.quorum_certificate_store(),
+            hash,
+        )
+        .map_err(|e| e.to_string())
+    }
+}
+
+/// Values TRST balances for the RPC crate under the decay mode currently
+/// in force.
+struct NodeTrstValueView {
+    trst_engine: Arc<Mutex<TrstEngine>>,
+    live_params: Arc<std::sync::RwLock<ProtocolParams>>,
+}
+
+impl TrstValueView for NodeTrstValueView {
+    fn trst_value<'a>(&'a self, account: &'a WalletAddress, now: Timestamp) -> TrstValueFuture<'a> {
+        Box::pin(async move {
+            let params = self.live_params.read().ok()?.clone();
+            self.trst_engine.lock().await.transferable_value(
+                account,
+                now,
+                params.trst_expiry_secs,
+                params.trst_decay_mode,
+                params.trst_decay_half_life_secs,
+            )
+        })
+    }
+}
+
+/// Reports the node's caches and queues for `node_containers`.
+struct NodeContainerView {
+    block_processor: Arc<Mutex<BlockProcessor>>,
+    vote_cache: Arc<RwLock<VoteCache>>,
+    active_elections: Arc<RwLock<ActiveElections>>,
+    fork_cache: Arc<Mutex<burst_consensus::ForkCache>>,
+    backlog: Arc<Mutex<BoundedBacklog>>,
+    message_dedup: Arc<Mutex<burst_network::MessageDedup>>,
+    block_queue: Arc<BlockPriorityQueue>,
+    trst_engine: Arc<Mutex<TrstEngine>>,
+}
+
+impl ContainerView for NodeContainerView {
+    fn containers(&self) -> ContainersFuture<'_> {
+        Box::pin(async move {
+            vec![
+                (
+                    "unchecked",
+                    self.block_processor.lock().await.unchecked_usage(),
+                ),
+                ("vote_cache", self.vote_cache.read().await.memory_usage()),
+                (
+                    "active_elections",
+                    self.active_elections.read().await.memory_usage(),
+                ),
+                ("fork_cache", self.fork_cache.lock().await.memory_usage()),
+                ("backlog", self.backlog.lock().await.memory_usage()),
+                (
+                    "message_dedup",
+                    self.message_dedup.lock().await.memory_usage(),
+                ),
+                ("block_queue", self.block_queue.memory_usage().await),
+                (
+                    "trst_portfolios",
+                    self.trst_engine.lock().await.memory_usage(),
+                ),
+            ]
+        })
+    }
 }
 
 /// Maximum number of recently confirmed hashes to remember.
 const RECENTLY_CONFIRMED_CAPACITY: usize = 65_536;
-/// Default maximum concurrent elections.
-const MAX_ACTIVE_ELECTIONS: usize = 5000;
+/// How long an election may run before it is cleaned up as expired.
+const ELECTION_LIFETIME_MS: u64 = 30_000;
+/// Interval between backlog scan passes.
+const BACKLOG_SCAN_INTERVAL: Duration = Duration::from_secs(5);
+/// Interval between recomputations of representative weights from the
+/// account store.
+const REP_WEIGHT_CHECK_INTERVAL: Duration = Duration::from_secs(3600);
+/// Interval between confirmation history snapshots to the meta store.
+const CONFIRMATION_HISTORY_PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often the state digest is retaken, if anything was cemented since.
+const STATE_DIGEST_POLL_INTERVAL: Duration = Duration::from_secs(30);
+/// Worker tasks draining the inbound processing lanes.
+const INBOUND_WORKERS: usize = 4;
 /// Default initial online weight estimate.
 const DEFAULT_ONLINE_WEIGHT: u128 = 1_000_000;
+/// Interval between probes of addresses learned from keepalives.
+const PEER_PROBE_INTERVAL: Duration = Duration::from_secs(5);
+/// Addresses probed per round.
+const PEER_PROBE_SAMPLE: usize = 4;
+/// Cached peers not seen for this long are dropped from the peer cache.
+const PEER_CACHE_RETENTION_SECS: u64 = 7 * 24 * 3600;
+/// Connections the peer cache loader aims for before it stops dialing.
+const PEER_CACHE_RECONNECT_TARGET: usize = 8;
+/// Below this many connected peers, bootstrap seeds are dialed even when
+/// the peer cache supplied some.
+const SEED_FALLBACK_MIN_PEERS: usize = 4;
 /// Default vote cache size.
 /// A running BURST node.
 pub struct BurstNode {
@@ -114,11 +696,23 @@ pub struct BurstNode {
     pub brn_engine: Arc<Mutex<BrnEngine>>,
     pub trst_engine: Arc<Mutex<TrstEngine>>,
     pub governance: Arc<Mutex<GovernanceEngine>>,
+    /// Sealed-ballot modes and commitments, persisted beside the engine.
+    pub governance_ballots: Arc<Mutex<BallotBox>>,
+    /// Protocol treasury: forfeited stakes and expired TRST, paid out as grants.
+    pub treasury: Arc<Mutex<Treasury>>,
     pub block_processor: Arc<Mutex<BlockProcessor>>,
     pub frontier: Arc<RwLock<DagFrontier>>,
     pub peer_manager: Arc<RwLock<PeerManager>>,
     pub store: Arc<LmdbStore>,
     pub metrics: Arc<NodeMetrics>,
+    /// Diagnostic counters and samples served by the `stats` RPC.
+    pub stats: Arc<Stats>,
+    /// Slow-operation watchdog served by the `node_watchdog` RPC.
+    pub watchdog: Arc<Watchdog>,
+    /// Overload detection and the degraded mode it switches on.
+    pub overload: Arc<OverloadController>,
+    /// Disk and LMDB map monitoring, and the read-only mode it switches on.
+    pub storage_guard: Arc<StorageGuard>,
     pub shutdown: Arc<ShutdownController>,
     pub ws_state: Arc<WsState>,
     /// Registry mapping peer IDs to their TCP write halves.
@@ -129,6 +723,10 @@ pub struct BurstNode {
     pub vote_cache: Arc<RwLock<VoteCache>>,
     /// Bounded cache of recently confirmed block hashes (prevents re-elections).
     pub recently_confirmed: Arc<RwLock<RecentlyConfirmed>>,
+    /// Recent confirmations with durations, for `confirmation_history`.
+    pub confirmation_history: Arc<Mutex<ConfirmationHistory>>,
+    /// Progress of locally submitted blocks, for `tx_status`.
+    pub tx_status: Arc<TxStatusTracker>,
     /// Vote generator for this node's representative key.
     pub vote_generator: Arc<Mutex<VoteGenerator>>,
     /// Cached representative weights for vote routing.
@@ -153,8 +751,14 @@ pub struct BurstNode {
     pub syn_cookies: Arc<Mutex<burst_network::SynCookies>>,
     /// Online weight sampler — tracks recently-active reps for quorum calculation.
     pub online_weight_sampler: Arc<Mutex<OnlineWeightSampler>>,
+    /// Representative crawler — probes peers and tracks rep responsiveness.
+    pub rep_crawler: Arc<Mutex<RepCrawler>>,
     /// Message deduplication filter — prevents processing duplicate P2P messages.
     pub message_dedup: Arc<Mutex<burst_network::MessageDedup>>,
+    /// Encrypted direct messages held in memory for their recipients.
+    pub mailbox: Arc<Mutex<Mailbox>>,
+    /// Recent digests of the cemented state, compared with peers' telemetry.
+    pub state_digests: Arc<Mutex<StateDigestLog>>,
     /// Clock synchronization service for BRN time-dependent computation.
     pub clock_sync: Arc<Mutex<ClockSync>>,
     /// Delegation engine for governance vote delegation.
@@ -163,6 +767,8 @@ pub struct BurstNode {
     pub vrf_client: Arc<Mutex<burst_vrf::DrandClient>>,
     /// Delegation store for scope-enforced delegation key verification.
     pub delegation_store: Arc<dyn DelegationStore + Send + Sync>,
+    /// Recovery custodian sets and rotated account signing keys.
+    pub recovery_store: Arc<dyn RecoveryStore + Send + Sync>,
     /// Verification orchestrator — end-to-end UHV workflow engine.
     pub verification_orchestrator: Arc<Mutex<burst_verification::VerificationOrchestrator>>,
     /// Adaptive PoW difficulty adjuster based on recent throughput.
@@ -171,12 +777,33 @@ pub struct BurstNode {
     pub consti_engine: Arc<Mutex<burst_consti::ConstiEngine>>,
     /// Election priority scheduler — higher-balance accounts get elections first.
     pub priority_scheduler: Arc<Mutex<PriorityScheduler>>,
+    /// Grows and shrinks the active election limit from observed load.
+    pub election_tuner: Arc<Mutex<ElectionLimitTuner>>,
     /// Aggregate online weight tracker with historical sampling for quorum stability.
     pub online_weight_tracker: Arc<Mutex<OnlineWeightTracker>>,
+    /// Logical clock and instant confirmation; only adjustable on dev networks.
+    pub dev_controls: Arc<DevControls>,
 
     /// Priority queue for submitting blocks into the processing pipeline.
     /// Blocks are ordered by PoW difficulty (higher = processed first).
     block_queue: Arc<BlockPriorityQueue>,
+    /// Write-ahead journal of accepted blocks awaiting their LMDB commit.
+    block_journal: Option<Arc<Mutex<BlockJournal>>>,
+    /// Append-only record of privileged RPC actions.
+    audit_log: Arc<NodeAuditLog>,
+    /// Detected forks and how they were resolved.
+    fork_log: Arc<NodeForkLog>,
+    /// Protocol parameters currently in force; locally built blocks are
+    /// stamped with their hash. Updated by activation blocks.
+    live_params: Arc<std::sync::RwLock<ProtocolParams>>,
+    /// Per-message-type lanes between the peer read loops and the inbound workers.
+    inbound_queue: Arc<InboundQueue>,
+    /// Edge checks applied to gossiped blocks before they are queued.
+    block_prefilter: Arc<Mutex<BlockPrefilter>>,
+    /// Weight-proportional rate limits for inbound votes.
+    vote_admission: Arc<Mutex<VoteAdmissionControl>>,
+    /// Read buffers recycled across peer read loops.
+    read_buffers: Arc<BufferPool>,
     /// Broadcaster for flooding messages to connected peers.
     broadcaster: Broadcaster,
     /// Node identity private key for P2P handshakes.
@@ -192,6 +819,23 @@ pub struct BurstNode {
 }
 
 impl BurstNode {
+    /// Open the LMDB environment at `config.data_dir`, growing the map if
+    /// the ledger has outgrown it. The schema is not migrated.
+    pub fn open_store(config: &NodeConfig) -> Result<LmdbStore, NodeError> {
+        config.storage.validate()?;
+        let map_size =
+            map_size_at_open(&config.storage, LmdbStore::data_file_len(&config.data_dir));
+        let store = LmdbStore::open(&config.data_dir, MAX_DBS, map_size)
+            .map_err(|e| NodeError::Other(format!("failed to open LMDB: {e}")))?;
+        if map_size as u64 > config.storage.map_size_mb << 20 {
+            tracing::info!(
+                map_size_mb = map_size >> 20,
+                "grew LMDB map to fit the ledger"
+            );
+        }
+        Ok(store)
+    }
+
     /// Create and initialize a new BURST node.
     ///
     /// Opens the LMDB environment at `config.data_dir` and prepares all
@@ -199,23 +843,97 @@ impl BurstNode {
     /// processing blocks.
     pub async fn new(mut config: NodeConfig) -> Result<Self, NodeError> {
         let min_work_difficulty = config.params.min_work_difficulty;
-
-        // Open LMDB storage
-        let store = LmdbStore::open(&config.data_dir, MAX_DBS, DEFAULT_MAP_SIZE)
-            .map_err(|e| NodeError::Other(format!("failed to open LMDB: {e}")))?;
+        let dev_controls = DevControls::new(config.network, &config.dev_overrides)
+            .map_err(|e| NodeError::Config(format!("dev_overrides: {e}")))?;
+
+        // Open LMDB storage and bring its schema up to date.
+        let store = Self::open_store(&config)?;
+        Migrator::new()
+            .run(&store)
+            .map_err(|e| NodeError::Other(format!("database migration failed: {e}")))?;
         let store = Arc::new(store);
 
+        let audit_log = Arc::new(NodeAuditLog::open(
+            Arc::clone(&store),
+            &config.data_dir.join(AUDIT_LOG_FILE),
+        )?);
+        let fork_log = Arc::new(NodeForkLog::open(Arc::clone(&store)).map_err(NodeError::Other)?);
+
         // Peer manager
-        let peer_manager = PeerManager::with_config(
+        let mut peer_manager = PeerManager::with_config(
             config.max_peers,
             config.bootstrap_peers.clone(),
             15, // keepalive interval (must be < READ_TIMEOUT of 30s)
         );
+        let parse_nets = |entries: &[String]| -> Result<Vec<IpNet>, NodeError> {
+            entries
+                .iter()
+                .map(|entry| {
+                    entry
+                        .parse::<IpNet>()
+                        .map_err(|e| NodeError::Config(e.to_string()))
+                })
+                .collect()
+        };
+        peer_manager.set_access_lists(
+            parse_nets(&config.peer_allowlist)?,
+            parse_nets(&config.peer_denylist)?,
+        );
+        peer_manager.set_max_peers_per_subnet(config.max_peers_per_subnet);
+        peer_manager
+            .set_allow_private_addresses(!matches!(config.network, burst_types::NetworkId::Live));
+        // Restore operator bans, dropping those that expired while offline.
+        use burst_store::peer::PeerStore;
+        let now_secs = unix_now_secs();
+        match store.peer_store().iter_bans() {
+            Ok(bans) => {
+                for (target, until) in bans {
+                    if until <= now_secs {
+                        let _ = store.peer_store().delete_ban(&target);
+                        continue;
+                    }
+                    match target.parse::<IpNet>() {
+                        Ok(net) => {
+                            peer_manager.ban_address(net, until);
+                        }
+                        Err(e) => tracing::warn!("ignoring persisted peer ban: {e}"),
+                    }
+                }
+            }
+            Err(e) => tracing::warn!("failed to load persisted peer bans: {e}"),
+        }
         let peer_manager = Arc::new(RwLock::new(peer_manager));
 
         // Block priority queue (replaces FIFO channel — higher PoW = higher priority)
         let block_queue = Arc::new(BlockPriorityQueue::new(BLOCK_CHANNEL_CAPACITY));
 
+        // Block journal: queue blocks accepted but never committed by the
+        // previous run ahead of anything else. They stay journaled until the
+        // block processor commits or rejects them.
+        let block_journal = if config.journal.enabled {
+            let path = config.data_dir.join(BLOCK_JOURNAL_FILE);
+            let fsync = config.journal.fsync_for(config.network);
+            let (mut journal, recovered) = BlockJournal::open(&path, fsync)?;
+            let mut replayed = 0usize;
+            for block in recovered {
+                let hash = block.hash;
+                if store.block_store().exists(&hash).unwrap_or(false) {
+                    journal.settle(&hash)?;
+                } else if block_queue.try_push(block) {
+                    replayed += 1;
+                } else {
+                    tracing::warn!(%hash, "block queue full, dropping journaled block");
+                    journal.settle(&hash)?;
+                }
+            }
+            if replayed > 0 {
+                tracing::info!(replayed, "requeued uncommitted blocks from the journal");
+            }
+            Some(Arc::new(Mutex::new(journal)))
+        } else {
+            None
+        };
+
         // Outbound message channel
         let (outbound_tx, outbound_rx) =
             mpsc::channel::<(String, Vec<u8>)>(OUTBOUND_CHANNEL_CAPACITY);
@@ -226,6 +944,10 @@ impl BurstNode {
 
         // Metrics
         let metrics = Arc::new(NodeMetrics::new());
+        let stats = Arc::new(Stats::default());
+        let watchdog = Arc::new(Watchdog::new(config.watchdog.clone()));
+        let overload = Arc::new(OverloadController::new(config.overload.clone()));
+        let storage_guard = Arc::new(StorageGuard::new(config.storage.clone()));
 
         // WebSocket shared state (always created; only served if enabled)
         let ws_state = Arc::new(WsState::new(256));
@@ -236,28 +958,65 @@ impl BurstNode {
         // Block processor + frontier (loaded from store)
         let frontier = Self::load_frontier_from_store(&store)?;
         let frontier = Arc::new(RwLock::new(frontier));
-        let block_processor = Arc::new(Mutex::new(BlockProcessor::with_genesis_account(
-            min_work_difficulty,
-            genesis_address(),
-        )));
+        let recovery_store: Arc<dyn RecoveryStore + Send + Sync> =
+            Arc::new(burst_nullables::NullRecoveryStore::new());
+        let block_processor = {
+            let mut bp =
+                BlockProcessor::with_genesis_account(min_work_difficulty, genesis_address());
+            bp.recovery_store = Some(Arc::clone(&recovery_store));
+            bp.block_store = Some(Arc::new(store.block_store()));
+            bp.account_store = Some(Arc::new(store.account_store()));
+            bp.set_stats(Arc::clone(&stats));
+            bp.set_network(config.network);
+            if let Some(ref upgrade_key) = config.epoch_upgrade_key {
+                bp.set_upgrade_key(upgrade_key.clone());
+            }
+            Arc::new(Mutex::new(bp))
+        };
 
         // Consensus subsystems
-        let active_elections = Arc::new(RwLock::new(ActiveElections::new(
-            MAX_ACTIVE_ELECTIONS,
-            DEFAULT_ONLINE_WEIGHT,
-        )));
-        let vote_cache = Arc::new(RwLock::new(VoteCache::new()));
+        let election_tuner = ElectionLimitTuner::new(config.active_elections.clone());
+        let active_elections = {
+            let mut elections = ActiveElections::new(election_tuner.limit(), DEFAULT_ONLINE_WEIGHT);
+            elections.set_stats(Arc::clone(&stats));
+            Arc::new(RwLock::new(elections))
+        };
+        let vote_cache = {
+            let mut cache = VoteCache::with_capacity(config.vote_cache_capacity);
+            cache.set_stats(Arc::clone(&stats));
+            Arc::new(RwLock::new(cache))
+        };
         let recently_confirmed = Arc::new(RwLock::new(RecentlyConfirmed::new(
             RECENTLY_CONFIRMED_CAPACITY,
         )));
+        let confirmation_history = Arc::new(Mutex::new(ConfirmationHistory::new(
+            config.confirmation_history_size,
+        )));
+        let tx_status = Arc::new(TxStatusTracker::new(DEFAULT_TX_STATUS_CAPACITY));
 
         // Vote generator — produce votes when acting as a representative.
-        // Generate a transient node key; in production the key would come
-        // from persistent configuration.
-        let vote_kp = burst_crypto::generate_keypair();
+        // The key comes from the encrypted representative key file when one
+        // is configured; otherwise a transient key is generated.
+        let vote_kp = match config.representative_key_file {
+            Some(ref path) => {
+                let password =
+                    std::env::var(rep_key::REPRESENTATIVE_KEY_PASSWORD_ENV).map_err(|_| {
+                        NodeError::Config(format!(
+                            "representative_key_file is set but {} is not",
+                            rep_key::REPRESENTATIVE_KEY_PASSWORD_ENV
+                        ))
+                    })?;
+                rep_key::load_or_create(path, &password, &KdfParams::default())?
+            }
+            None => burst_crypto::generate_keypair(),
+        };
         let vote_generator = {
             let rep_addr = burst_crypto::derive_address(&vote_kp.public);
-            tracing::info!(representative = %rep_addr, "generated node representative key");
+            if config.representative_key_file.is_some() {
+                tracing::info!(representative = %rep_addr, "loaded node representative key");
+            } else {
+                tracing::info!(representative = %rep_addr, "generated transient node representative key");
+            }
             Arc::new(Mutex::new(VoteGenerator::new(rep_addr, vote_kp.private.0)))
         };
         let node_kp = burst_crypto::generate_keypair();
@@ -279,9 +1038,12 @@ impl BurstNode {
             config.params.num_verifiers,
             0.67, // vote threshold — 67% of verifiers must participate
         ));
-        let verifier_pool = Arc::new(Mutex::new(VerifierPool::new(
+        let verifier_pool = VerifierPool::load(
             config.params.verifier_stake_amount,
-        )));
+            &store.verification_store(),
+        )
+        .map_err(|e| NodeError::Other(format!("failed to load verifier pool: {e}")))?;
+        let verifier_pool = Arc::new(Mutex::new(verifier_pool));
 
         // Consensus infrastructure — fork cache, vote spacing, request aggregator
         let fork_cache = Arc::new(Mutex::new(burst_consensus::ForkCache::new()));
@@ -296,8 +1058,17 @@ impl BurstNode {
         // Online weight sampler — 5-minute window for representative liveness
         let online_weight_sampler = Arc::new(Mutex::new(OnlineWeightSampler::new(300)));
 
+        // Representative crawler — 5s query timeout, crawl every 30s
+        let rep_crawler = Arc::new(Mutex::new(RepCrawler::new(5_000, 30_000)));
+
         // Message deduplication — bounded filter to prevent duplicate P2P message processing
-        let message_dedup = Arc::new(Mutex::new(burst_network::MessageDedup::new(65_536)));
+        let message_dedup = {
+            let mut dedup = burst_network::MessageDedup::new(65_536);
+            dedup.set_stats(Arc::clone(&stats));
+            Arc::new(Mutex::new(dedup))
+        };
+
+        let mailbox = Arc::new(Mutex::new(Mailbox::new(config.direct_messages)));
 
         // Clock synchronization (5-second max drift tolerance)
         let clock_sync = Arc::new(Mutex::new(ClockSync::new(5_000)));
@@ -328,6 +1099,13 @@ impl BurstNode {
                 }
             }
         }
+        let live_params = config.params.clone();
+        let work_thresholds = burst_work::WorkThresholds::from_params(&config.params);
+        {
+            let mut bp = block_processor.lock().await;
+            bp.set_work_thresholds(work_thresholds.clone());
+            bp.set_bootstrap_limits(crate::limits::BootstrapLimits::from_params(&config.params));
+        }
 
         let brn_engine = {
             let brn_store = store.brn_store();
@@ -383,7 +1161,25 @@ impl BurstNode {
                 let brn_store = store.brn_store();
                 match brn_store.get_meta(b"governance_engine") {
                     Ok(Some(ref bytes)) => match bincode::deserialize::<GovernanceEngine>(bytes) {
-                        Ok(engine) => {
+                        Ok(mut engine) => {
+                            if let Some(times) = brn_store
+                                .get_meta(b"governance_vote_times")
+                                .ok()
+                                .flatten()
+                                .and_then(|bytes| bincode::deserialize::<VoteTimes>(&bytes).ok())
+                            {
+                                engine.restore_vote_times(times);
+                            }
+                            if let Some(dependencies) = brn_store
+                                .get_meta(b"governance_dependencies")
+                                .ok()
+                                .flatten()
+                                .and_then(|bytes| {
+                                    bincode::deserialize::<ProposalDependencies>(&bytes).ok()
+                                })
+                            {
+                                engine.restore_dependencies(dependencies);
+                            }
                             let count = engine.all_proposals().count();
                             tracing::info!(
                                 proposals = count,
@@ -402,17 +1198,43 @@ impl BurstNode {
                     }
                 }
             },
+            governance_ballots: {
+                let ballots = store
+                    .brn_store()
+                    .get_meta(b"governance_ballots")
+                    .ok()
+                    .flatten()
+                    .and_then(|bytes| bincode::deserialize::<BallotBox>(&bytes).ok())
+                    .unwrap_or_default();
+                Arc::new(Mutex::new(ballots))
+            },
+            treasury: {
+                let treasury = store
+                    .brn_store()
+                    .get_meta(b"governance_treasury")
+                    .ok()
+                    .flatten()
+                    .and_then(|bytes| bincode::deserialize::<Treasury>(&bytes).ok())
+                    .unwrap_or_default();
+                Arc::new(Mutex::new(treasury))
+            },
             block_processor,
             frontier,
             peer_manager,
             store,
             metrics,
+            stats,
+            watchdog,
+            overload,
+            storage_guard,
             shutdown,
             ws_state,
             connection_registry,
             active_elections,
             vote_cache,
             recently_confirmed,
+            confirmation_history,
+            tx_status,
             vote_generator,
             rep_weights,
             confirming_set,
@@ -425,11 +1247,15 @@ impl BurstNode {
             request_aggregator,
             syn_cookies,
             online_weight_sampler,
+            rep_crawler,
             message_dedup,
+            mailbox,
+            state_digests: Arc::new(Mutex::new(StateDigestLog::default())),
             clock_sync,
             delegation_engine,
             vrf_client,
             delegation_store: Arc::new(burst_nullables::NullDelegationStore::new()),
+            recovery_store,
             verification_orchestrator,
             difficulty_adjuster: Arc::new(Mutex::new(burst_work::DifficultyAdjuster::new(
                 min_work_difficulty,
@@ -437,12 +1263,27 @@ impl BurstNode {
                 10000,
             ))),
             consti_engine: Arc::new(Mutex::new(burst_consti::ConstiEngine::new())),
-            priority_scheduler: Arc::new(Mutex::new(PriorityScheduler::new(MAX_ACTIVE_ELECTIONS))),
+            priority_scheduler: Arc::new(Mutex::new(PriorityScheduler::new(
+                election_tuner.config().ceiling,
+            ))),
+            election_tuner: Arc::new(Mutex::new(election_tuner)),
             online_weight_tracker: Arc::new(Mutex::new(OnlineWeightTracker::new(
                 DEFAULT_ONLINE_WEIGHT,
                 60_000_000, // minimum weight floor
             ))),
+            dev_controls: Arc::new(dev_controls),
             block_queue,
+            block_journal,
+            audit_log,
+            fork_log,
+            live_params: Arc::new(std::sync::RwLock::new(live_params)),
+            inbound_queue: Arc::new(InboundQueue::default()),
+            block_prefilter: Arc::new(Mutex::new(BlockPrefilter::new(
+                work_thresholds,
+                DEFAULT_PREFILTER_CAPACITY,
+            ))),
+            vote_admission: Arc::new(Mutex::new(VoteAdmissionControl::default())),
+            read_buffers: Arc::new(BufferPool::default()),
             broadcaster,
             node_private_key,
             node_address,
@@ -514,6 +1355,12 @@ impl BurstNode {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(0),
             params_hash: self.config.params.params_hash(),
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([0u8; 64]),
             hash: BlockHash::ZERO,
@@ -536,7 +1383,7 @@ impl BurstNode {
             .put_frontier(&genesis_account, &genesis_block.hash)
             .map_err(|e| NodeError::Other(format!("failed to batch genesis frontier: {e}")))?;
         batch
-            .put_meta("schema_version", b"1")
+            .put_meta("schema_version", &CURRENT_SCHEMA_VERSION.to_le_bytes())
             .map_err(|e| NodeError::Other(format!("failed to batch schema version: {e}")))?;
         batch
             .commit()
@@ -557,9 +1404,17 @@ impl BurstNode {
         let frontier = Arc::clone(&self.frontier);
         let store = Arc::clone(&self.store);
         let metrics = Arc::clone(&self.metrics);
+        let stats = Arc::clone(&self.stats);
+        let watchdog = Arc::clone(&self.watchdog);
+        let overload_bp = Arc::clone(&self.overload);
+        let storage_guard_bp = Arc::clone(&self.storage_guard);
         let mut shutdown_rx = self.shutdown.subscribe();
+        let shutdown_bp = Arc::clone(&self.shutdown);
+        let mut invariants_bp = InvariantChecker::new(self.config.invariants);
         let block_queue = Arc::clone(&self.block_queue);
+        let block_journal_bp = self.block_journal.clone();
         let active_elections_bp = Arc::clone(&self.active_elections);
+        let vote_cache_bp = Arc::clone(&self.vote_cache);
         let vote_generator_bp = Arc::clone(&self.vote_generator);
         let broadcaster_bp = self.broadcaster.clone();
         let peer_manager_bp = Arc::clone(&self.peer_manager);
@@ -569,36 +1424,64 @@ impl BurstNode {
         let brn_engine_bp = Arc::clone(&self.brn_engine);
         let trst_engine_bp = Arc::clone(&self.trst_engine);
         let ledger_cache_bp = Arc::clone(&self.ledger_cache);
-        let trst_expiry_secs = self.config.params.trst_expiry_secs;
         let mut config_params_bp = self.config.params.clone();
+        let live_params_bp = Arc::clone(&self.live_params);
+        // Blocks stamped with superseded parameters stay rejected across
+        // restarts.
+        if let Some(activated_at) = self.governance.lock().await.last_activation_at() {
+            self.block_processor
+                .lock()
+                .await
+                .apply_params_activation(config_params_bp.params_hash(), activated_at);
+        }
         let fork_cache_bp = Arc::clone(&self.fork_cache);
+        let fork_log_bp = Arc::clone(&self.fork_log);
         let vote_spacing_bp = Arc::clone(&self.vote_spacing);
         let ws_state_bp = Arc::clone(&self.ws_state);
         let governance_bp = Arc::clone(&self.governance);
+        let ballots_bp = Arc::clone(&self.governance_ballots);
+        let treasury_bp = Arc::clone(&self.treasury);
         let delegation_bp = Arc::clone(&self.delegation_engine);
         let delegation_store_bp = Arc::clone(&self.delegation_store);
+        let recovery_store_bp = Arc::clone(&self.recovery_store);
         let vrf_client_bp = Arc::clone(&self.vrf_client);
         let verifier_pool_bp = Arc::clone(&self.verifier_pool);
         let _verification_processor_bp = Arc::clone(&self.verification_processor);
         let verification_orch_bp = Arc::clone(&self.verification_orchestrator);
         let difficulty_adjuster_bp = Arc::clone(&self.difficulty_adjuster);
+        let prefilter_bp = Arc::clone(&self.block_prefilter);
         let priority_scheduler_bp = Arc::clone(&self.priority_scheduler);
+        let dev_controls_bp = Arc::clone(&self.dev_controls);
+        let recently_confirmed_bp = Arc::clone(&self.recently_confirmed);
+        let confirming_set_bp = Arc::clone(&self.confirming_set);
+        let tx_status_bp = Arc::clone(&self.tx_status);
         let consti_engine_bp = Arc::clone(&self.consti_engine);
+        // Verdict block → wallet revoked by it, until the block is cemented
+        // and a revocation notice can carry its certificate.
+        let fraud_verdicts: Arc<Mutex<HashMap<BlockHash, WalletAddress>>> =
+            Arc::new(Mutex::new(HashMap::new()));
+        let fraud_verdicts_bp = Arc::clone(&fraud_verdicts);
 
         let bp_handle = tokio::spawn(async move {
             loop {
                 // Pop the highest-priority block (by PoW difficulty).
                 // Use select! to remain responsive to shutdown signals.
+                // While storage is read-only, blocks stay queued rather
+                // than fail halfway through being written.
+                let read_only = storage_guard_bp.is_read_only();
                 let block = tokio::select! {
                     biased;
                     _ = shutdown_rx.recv() => {
                         tracing::info!("block processor task shutting down");
                         break;
                     }
-                    block = block_queue.pop() => block,
+                    _ = tokio::time::sleep(Duration::from_secs(1)), if read_only => continue,
+                    block = block_queue.pop(), if !read_only => block,
                 };
 
                 let start = std::time::Instant::now();
+                let _iteration =
+                    watchdog.start(WatchedOp::BlockProcessorIteration, "block_processor");
                 let _loop_now_secs = unix_now_secs();
 
                 // Load previous block (if any) for balance validation and
@@ -639,15 +1522,26 @@ impl BurstNode {
                     .err()
                 });
 
+                // Gate block versions by the account's epoch and the
+                // governance-set ledger epoch.
+                let version_rejected = burst_ledger::validate_block_version(
+                    &block,
+                    prev_account.as_ref().map_or(0, |acct| acct.epoch),
+                    config_params_bp.ledger_epoch,
+                )
+                .err();
+
                 // Enforce verification status for Send/Burn blocks
                 let verification_rejected = if matches!(
                     block.block_type,
                     BlockType::Send
+                        | BlockType::HtlcLock
                         | BlockType::Burn
                         | BlockType::Split
                         | BlockType::Merge
                         | BlockType::Endorse
                         | BlockType::Challenge
+                        | BlockType::VerifierOptIn
                 ) {
                     prev_account
                         .as_ref()
@@ -673,30 +1567,116 @@ impl BurstNode {
                     None
                 };
 
-                // Enforce new wallet spending limits
-                let spending_limit_rejected =
-                    if matches!(block.block_type, BlockType::Send | BlockType::Burn) {
-                        prev_account.as_ref().and_then(|acct| {
-                            let amount = if block.block_type == BlockType::Send {
-                                acct.trst_balance.saturating_sub(block.trst_balance)
-                            } else {
-                                prev_brn_balance.saturating_sub(block.brn_balance)
-                            };
-                            let now = Timestamp::new(unix_now_secs());
-                            crate::limits::check_wallet_limits(acct, amount, now, &config_params_bp)
-                                .err()
-                        })
-                    } else {
-                        None
-                    };
+                // Enforce new wallet and probation-tier limits (the
+                // bootstrap-phase caps are the block processor's)
+                let spending_limit_rejected = if matches!(
+                    block.block_type,
+                    BlockType::Send | BlockType::HtlcLock | BlockType::Burn | BlockType::Endorse
+                ) {
+                    prev_account.as_ref().and_then(|acct| {
+                        use crate::limits::LimitedAction;
+                        let now = dev_controls_bp.now();
+                        let history = match crate::verification_processor::load_challenge_history(
+                            &store.verification_store(),
+                            &block.account,
+                        ) {
+                            Ok(history) => history,
+                            Err(e) => {
+                                return Some(format!("unable to look up challenge history: {e}"))
+                            }
+                        };
+                        let action = match block.block_type {
+                            BlockType::Endorse => LimitedAction::Endorse,
+                            BlockType::Burn => LimitedAction::Burn {
+                                amount: prev_brn_balance.saturating_sub(block.brn_balance),
+                            },
+                            _ => {
+                                // The window ends at the block itself, so every
+                                // node totals the same blocks.
+                                let since = Timestamp::new(
+                                    block
+                                        .timestamp
+                                        .as_secs()
+                                        .saturating_sub(burst_verification::SEND_CAP_WINDOW_SECS),
+                                );
+                                let sent_today = match burst_ledger::trst_sent_since(
+                                    &store.block_store(),
+                                    acct,
+                                    since,
+                                ) {
+                                    Ok(sent) => sent,
+                                    Err(e) => {
+                                        return Some(format!("unable to total recent sends: {e}"))
+                                    }
+                                };
+                                LimitedAction::Send {
+                                    amount: acct.trst_balance.saturating_sub(block.trst_balance),
+                                    sent_today,
+                                }
+                            }
+                        };
+                        crate::limits::check_wallet_limits(
+                            acct,
+                            &history,
+                            action,
+                            now,
+                            &config_params_bp,
+                        )
+                        .err()
+                    })
+                } else {
+                    None
+                };
+
+                // Verifier pool changes are checked against the persisted
+                // membership: an opt-in locks at least the governed stake,
+                // an opt-out returns exactly what was locked.
+                let mut verifier_stake: Option<burst_brn::Stake> = None;
+                let verifier_pool_rejected = if matches!(
+                    block.block_type,
+                    BlockType::VerifierOptIn | BlockType::VerifierOptOut
+                ) {
+                    match store.verification_store().get_verifier(&block.account) {
+                        Ok(record) => {
+                            verifier_stake =
+                                record.and_then(|bytes| bincode::deserialize(&bytes).ok());
+                            crate::verification_processor::validate_pool_change(
+                                &block,
+                                prev_brn_balance,
+                                verifier_stake.as_ref(),
+                                config_params_bp.verifier_stake_amount,
+                            )
+                            .err()
+                        }
+                        Err(e) => Some(format!("unable to look up verifier pool: {e}")),
+                    }
+                } else {
+                    None
+                };
+
+                // Challenges must respect the anti-harassment rules recorded
+                // in the persisted challenge history.
+                let challenge_rejected = if block.block_type == BlockType::Challenge {
+                    crate::verification_processor::validate_challenge(
+                        &block,
+                        prev_brn_balance,
+                        &store.verification_store(),
+                        dev_controls_bp.now(),
+                        &config_params_bp,
+                    )
+                    .err()
+                } else {
+                    None
+                };
 
                 // Reject sends/splits of expired or revoked TRST.
                 // The TrstEngine tracks per-wallet token portfolios in memory;
                 // if the sender is tracked, verify the send amount doesn't
-                // exceed the non-expired, non-revoked (transferable) balance.
+                // exceed the non-expired, non-revoked (transferable) balance,
+                // valued under the network's TRST decay mode.
                 let trst_transferable_rejected = if matches!(
                     block.block_type,
-                    BlockType::Send | BlockType::Split
+                    BlockType::Send | BlockType::HtlcLock | BlockType::Split
                 ) {
                     let send_amount = prev_account
                         .as_ref()
@@ -704,8 +1684,14 @@ impl BurstNode {
                         .unwrap_or(0);
                     if send_amount > 0 {
                         let mut trst = trst_engine_bp.lock().await;
-                        let now = Timestamp::new(unix_now_secs());
-                        match trst.transferable_balance(&block.account, now, trst_expiry_secs) {
+                        let now = dev_controls_bp.now();
+                        match trst.transferable_value(
+                            &block.account,
+                            now,
+                            config_params_bp.trst_expiry_secs,
+                            config_params_bp.trst_decay_mode,
+                            config_params_bp.trst_decay_half_life_secs,
+                        ) {
                             Some(transferable) if send_amount > transferable => {
                                 tracing::warn!(
                                     account = %block.account,
@@ -727,10 +1713,100 @@ impl BurstNode {
                     None
                 };
 
+                // Validate HTLC claims/refunds (and plain receives) against the
+                // pending entry they settle. Transfers past their pending TTL
+                // are moved back to their sender by the expiry task, so the
+                // entry is simply gone. Refund entries are keyed by the
+                // lock's receiver, resolved from the lock block.
+                let mut htlc_refund_destination: Option<burst_types::WalletAddress> = None;
+                // Pending entry a RejectReceive declines; returned to its sender.
+                let mut rejected_pending: Option<burst_store::PendingInfo> = None;
+                let htlc_rejected = if matches!(
+                    block.block_type,
+                    BlockType::Receive
+                        | BlockType::RejectReceive
+                        | BlockType::HtlcClaim
+                        | BlockType::HtlcRefund
+                ) {
+                    let lock_block = store
+                        .block_store()
+                        .get_block(&block.link)
+                        .ok()
+                        .and_then(|bytes| bincode::deserialize::<StateBlock>(&bytes).ok());
+                    let destination = if block.block_type == BlockType::HtlcRefund {
+                        lock_block.as_ref().and_then(|lock| {
+                            crate::ledger_bridge::extract_receiver_from_link(&lock.link)
+                        })
+                    } else {
+                        Some(block.account.clone())
+                    };
+                    let pending = destination.as_ref().and_then(|dest| {
+                        store
+                            .pending_store()
+                            .get_pending(dest, &block.link.into_tx_hash())
+                            .ok()
+                    });
+                    if block.block_type == BlockType::HtlcRefund {
+                        htlc_refund_destination = destination;
+                    }
+                    let rejected = BlockProcessor::validate_htlc_settlement(
+                        &block,
+                        prev_block.as_ref().map_or(0, |b| b.trst_balance),
+                        lock_block.as_ref(),
+                        pending.as_ref(),
+                    )
+                    .err();
+                    if block.block_type == BlockType::RejectReceive {
+                        rejected_pending = pending;
+                    }
+                    rejected
+                } else {
+                    None
+                };
+
+                // Alias registrations are checked against the registry: the
+                // exact governed fee, and no taking a name still held by
+                // someone else (term plus grace period).
+                let mut alias_record: Option<burst_store::AliasRecord> = None;
+                let alias_rejected = if block.block_type == BlockType::Alias {
+                    let name = block
+                        .memo
+                        .as_deref()
+                        .map(String::from_utf8_lossy)
+                        .unwrap_or_default();
+                    match store.alias_store().get_alias(&name) {
+                        Ok(existing) => match crate::aliases::validate_registration(
+                            &block,
+                            prev_brn_balance,
+                            existing.as_ref(),
+                            &config_params_bp,
+                        ) {
+                            Ok(record) => {
+                                alias_record = Some(record);
+                                None
+                            }
+                            Err(reason) => Some(reason),
+                        },
+                        Err(e) => Some(format!("unable to look up alias: {e}")),
+                    }
+                } else {
+                    None
+                };
+
                 let result = if let Some(reason) = balance_rejected {
                     ProcessResult::Rejected(reason)
+                } else if let Some(reason) = version_rejected {
+                    ProcessResult::Rejected(reason)
+                } else if let Some(reason) = htlc_rejected {
+                    ProcessResult::Rejected(reason)
                 } else if let Some(reason) = verification_rejected {
                     ProcessResult::Rejected(reason)
+                } else if let Some(reason) = verifier_pool_rejected {
+                    ProcessResult::Rejected(reason)
+                } else if let Some(reason) = challenge_rejected {
+                    ProcessResult::Rejected(reason)
+                } else if let Some(reason) = alias_rejected {
+                    ProcessResult::Rejected(reason)
                 } else if let Some(reason) = spending_limit_rejected {
                     ProcessResult::Rejected(reason)
                 } else if let Some(reason) = trst_transferable_rejected {
@@ -743,6 +1819,38 @@ impl BurstNode {
 
                 metrics.blocks_processed.inc();
 
+                match &result {
+                    ProcessResult::Rejected(reason) => {
+                        tx_status_bp.rejected(&block.hash, reason, unix_now_secs())
+                    }
+                    ProcessResult::Gap => tx_status_bp.waiting(
+                        &block.hash,
+                        "waiting for the previous block",
+                        unix_now_secs(),
+                    ),
+                    ProcessResult::GapSource => tx_status_bp.waiting(
+                        &block.hash,
+                        "waiting for the source block",
+                        unix_now_secs(),
+                    ),
+                    ProcessResult::Fork => tx_status_bp.waiting(
+                        &block.hash,
+                        "competing with another block; election started",
+                        unix_now_secs(),
+                    ),
+                    _ => {}
+                }
+
+                // A journaled block replayed at startup that is not accepted
+                // again will never commit.
+                if result != ProcessResult::Accepted {
+                    if let Some(ref journal) = block_journal_bp {
+                        if let Err(e) = journal.lock().await.settle(&block.hash) {
+                            tracing::warn!("failed to truncate block journal: {e}");
+                        }
+                    }
+                }
+
                 match &result {
                     ProcessResult::Accepted => {
                         let bytes = match bincode::serialize(&block) {
@@ -763,7 +1871,7 @@ impl BurstNode {
                                 unix_now_secs(),
                             );
                         }
-                        {
+                        if !dev_controls_bp.instant_confirmation() {
                             let balance = block.trst_balance.min(u64::MAX as u128) as u64;
                             let mut sched = priority_scheduler_bp.lock().await;
                             sched.push(block.hash, block.account.clone(), balance);
@@ -775,15 +1883,33 @@ impl BurstNode {
                         let mut rw = rep_weights_bp.write().await;
                         let mut brn = brn_engine_bp.lock().await;
                         let mut trst = trst_engine_bp.lock().await;
+                        let trst_held = watchdog.start(WatchedOp::LockHold, "trst_engine");
+                        // Keep the merger graph's cache bounded; trimmed
+                        // subgraphs are paged back in from LMDB on demand.
+                        trst.merger_graph
+                            .trim_cache(crate::merger_graph::MERGER_GRAPH_CACHE_NODES);
 
                         // ── In-memory economics ──────────────────────────────
-                        let econ_now = Timestamp::new(unix_now_secs());
+                        let econ_now = dev_controls_bp.now();
+                        // Every accepted block is proof of life; a wallet
+                        // suspended for inactivity resumes accrual before the
+                        // block's economics apply. Activity is dated by the
+                        // block, so replay and bootstrap agree on it, and
+                        // never before the wallet was last seen.
+                        if let Some(event) = brn.record_activity(
+                            &block.account,
+                            block.timestamp,
+                            config_params_bp.inactivity_suspension_secs,
+                        ) {
+                            tracing::info!(account = %block.account, "BRN accrual resumed after inactivity");
+                            publish_inactivity_event(&ws_state_bp, &event);
+                        }
                         let econ_result = crate::ledger_bridge::process_block_economics(
                             &block,
                             &mut brn,
                             &mut trst,
                             econ_now,
-                            trst_expiry_secs,
+                            config_params_bp.trst_expiry_secs,
                             prev_brn_balance,
                         );
                         tracing::trace!(hash = %block.hash, ?econ_result, "block economics processed");
@@ -792,6 +1918,7 @@ impl BurstNode {
                             econ_result
                         {
                             tracing::error!(hash = %block.hash, %reason, "block rejected due to economic invariant violation");
+                            tx_status_bp.rejected(&block.hash, reason, unix_now_secs());
                             drop(trst);
                             drop(brn);
                             drop(rw);
@@ -821,7 +1948,7 @@ impl BurstNode {
                                     token
                                         .effective_origin_timestamp
                                         .as_secs()
-                                        .saturating_add(trst_expiry_secs),
+                                        .saturating_add(config_params_bp.trst_expiry_secs),
                                 );
                                 deferred_trst_indices = Some((token.origin, token.id, expiry_ts));
                             }
@@ -859,7 +1986,7 @@ impl BurstNode {
                                         crate::ledger_bridge::create_received_token(
                                             &block,
                                             &pend,
-                                            trst_expiry_secs,
+                                            config_params_bp.trst_expiry_secs,
                                         );
                                     trst.track_token(received_token);
                                     tracing::debug!(
@@ -876,6 +2003,32 @@ impl BurstNode {
                                     );
                                 }
                             }
+                            crate::ledger_bridge::EconomicResult::HtlcRefund {
+                                ref sender,
+                                lock_block_hash,
+                                ..
+                            } => {
+                                if let Some(ref destination) = htlc_refund_destination {
+                                    if let Ok(pend) = store
+                                        .pending_store()
+                                        .get_pending(destination, &lock_block_hash.into_tx_hash())
+                                    {
+                                        trst.track_token(
+                                            crate::ledger_bridge::create_received_token(
+                                                &block,
+                                                &pend,
+                                                config_params_bp.trst_expiry_secs,
+                                            ),
+                                        );
+                                        tracing::debug!(
+                                            %sender,
+                                            %lock_block_hash,
+                                            amount = pend.amount,
+                                            "HTLC refund: token returned to sender portfolio"
+                                        );
+                                    }
+                                }
+                            }
                             crate::ledger_bridge::EconomicResult::Merge { ref account } => {
                                 if let Some(portfolio) = trst.get_portfolio(account) {
                                     let active_tokens: Vec<burst_trst::TrstToken> = portfolio
@@ -892,7 +2045,7 @@ impl BurstNode {
                                             account.clone(),
                                             merge_tx,
                                             econ_now,
-                                            trst_expiry_secs,
+                                            config_params_bp.trst_expiry_secs,
                                         ) {
                                             Ok(merged) => {
                                                 let ids_to_remove: std::collections::HashSet<_> =
@@ -938,7 +2091,7 @@ impl BurstNode {
                                                     ],
                                                     &[hash_a, hash_b],
                                                     econ_now,
-                                                    trst_expiry_secs,
+                                                    config_params_bp.trst_expiry_secs,
                                                 ) {
                                                     Ok(children) => {
                                                         trst.untrack_token(account, &parent.id);
@@ -956,12 +2109,37 @@ impl BurstNode {
                                     }
                                 }
                             }
+                            crate::ledger_bridge::EconomicResult::VerifierOptOut {
+                                ref verifier,
+                                ..
+                            } => {
+                                if let Some(ref mut stake) = verifier_stake {
+                                    if let Err(e) = crate::ledger_bridge::return_brn_stake(
+                                        &mut brn, verifier, stake,
+                                    ) {
+                                        tracing::warn!(%verifier, error = %e, "failed to return verifier stake");
+                                    }
+                                }
+                            }
                             _ => {}
                         }
 
+                        // Merger graph changes commit with the block that made them.
+                        let merger_deltas = trst.merger_graph.take_deltas();
+                        let portfolio_updates = trst.take_dirty_portfolios();
+
                         // Drop TRST engine lock before verification orchestrator
+                        drop(trst_held);
                         drop(trst);
 
+                        // Journal the block first so a crash before the
+                        // commit below cannot lose it.
+                        if let Some(ref journal) = block_journal_bp {
+                            if let Err(e) = journal.lock().await.append(&block) {
+                                tracing::warn!(hash = %block.hash, "failed to journal block: {e}");
+                            }
+                        }
+
                         // ── Unified write batch — single fsync ───────────────
                         // All block, frontier, account, pending, and TRST index
                         // writes are batched into one LMDB transaction.
@@ -1001,6 +2179,22 @@ impl BurstNode {
                             {
                                 tracing::warn!(hash = %block.hash, "failed to delete pending: {e}");
                             }
+                            if let Some(ref rejected) = rejected_pending {
+                                if let Err(e) = crate::ledger_updater::create_returned_pending_entry(
+                                    &mut batch, &block, rejected,
+                                ) {
+                                    tracing::warn!(hash = %block.hash, "failed to return rejected pending: {e}");
+                                }
+                            }
+                            if let Some(ref destination) = htlc_refund_destination {
+                                if let Err(e) = crate::ledger_updater::delete_refunded_pending_entry(
+                                    &mut batch,
+                                    &block,
+                                    destination,
+                                ) {
+                                    tracing::warn!(hash = %block.hash, "failed to delete refunded pending: {e}");
+                                }
+                            }
                             if let Some((amount, ref dest, ref provenance)) = deferred_pending {
                                 if let Err(e) = crate::ledger_updater::create_pending_entry(
                                     &mut batch,
@@ -1020,8 +2214,62 @@ impl BurstNode {
                                     tracing::warn!(token_id = %token_id, "failed to batch TRST expiry index: {e}");
                                 }
                             }
+                            if let Err(e) = crate::merger_graph::write_merger_graph_deltas(
+                                &mut batch,
+                                &merger_deltas,
+                            ) {
+                                tracing::warn!(hash = %block.hash, "failed to batch merger graph: {e}");
+                            }
+                            if let Err(e) = crate::trst_portfolios::write_portfolios(
+                                &mut batch,
+                                &portfolio_updates,
+                            ) {
+                                tracing::warn!(hash = %block.hash, "failed to batch TRST portfolios: {e}");
+                            }
+
+                            match &econ_result {
+                                crate::ledger_bridge::EconomicResult::VerifierOptIn {
+                                    verifier,
+                                    stake,
+                                } => {
+                                    let record = match bincode::serialize(stake) {
+                                        Ok(record) => record,
+                                        Err(e) => {
+                                            tracing::error!(hash = %block.hash, "failed to encode verifier stake: {e}");
+                                            break 'persist false;
+                                        }
+                                    };
+                                    if let Err(e) = batch.put_verifier(verifier, &record) {
+                                        tracing::error!(hash = %block.hash, "failed to batch verifier pool entry: {e}");
+                                        break 'persist false;
+                                    }
+                                }
+                                crate::ledger_bridge::EconomicResult::VerifierOptOut {
+                                    verifier,
+                                    ..
+                                } => {
+                                    if let Err(e) = batch.delete_verifier(verifier) {
+                                        tracing::error!(hash = %block.hash, "failed to batch verifier pool removal: {e}");
+                                        break 'persist false;
+                                    }
+                                }
+                                _ => {}
+                            }
+                            if let Some(ref record) = alias_record {
+                                if let Err(e) = batch.put_alias(record) {
+                                    tracing::error!(hash = %block.hash, "failed to batch alias registration: {e}");
+                                    break 'persist false;
+                                }
+                            }
 
-                            if let Err(e) = batch.commit() {
+                            let committed = {
+                                let _commit = watchdog.start(WatchedOp::LmdbCommit, "block_batch");
+                                let commit_started = std::time::Instant::now();
+                                let committed = batch.commit();
+                                overload_bp.record_commit(commit_started.elapsed());
+                                committed
+                            };
+                            if let Err(e) = committed {
                                 tracing::error!(hash = %block.hash, "failed to commit unified batch: {e}");
                                 break 'persist false;
                             }
@@ -1042,6 +2290,32 @@ impl BurstNode {
                         };
                         drop(rw);
 
+                        if persisted {
+                            if let Some(ref journal) = block_journal_bp {
+                                if let Err(e) = journal.lock().await.settle(&block.hash) {
+                                    tracing::warn!("failed to truncate block journal: {e}");
+                                }
+                            }
+                            let violations =
+                                invariants_bp.after_batch(&store, &block, prev_account.as_ref());
+                            for violation in &violations {
+                                metrics
+                                    .invariant_violations
+                                    .with_label_values(&[violation.invariant.as_str()])
+                                    .inc();
+                                tracing::error!(
+                                    hash = %block.hash,
+                                    invariant = %violation.invariant,
+                                    "ledger invariant violated: {}",
+                                    violation.detail
+                                );
+                            }
+                            if !violations.is_empty() && invariants_bp.config().halt {
+                                tracing::error!("halting node on ledger invariant violation");
+                                shutdown_bp.shutdown();
+                            }
+                        }
+
                         if !persisted {
                             let mut f = frontier.write().await;
                             if block.previous.is_zero() {
@@ -1066,6 +2340,37 @@ impl BurstNode {
 
                         // Post-commit: verification, governance, etc. (can await)
 
+                        if persisted {
+                            match &econ_result {
+                                crate::ledger_bridge::EconomicResult::VerifierOptIn {
+                                    verifier,
+                                    stake,
+                                } => {
+                                    let mut pool = verifier_pool_bp.lock().await;
+                                    if let Err(e) = pool.opt_in(verifier.clone(), stake.amount) {
+                                        tracing::warn!(%verifier, error = %e, "verifier pool opt-in failed");
+                                    }
+                                    tracing::info!(
+                                        %verifier,
+                                        stake = stake.amount,
+                                        "verifier joined the pool"
+                                    );
+                                }
+                                crate::ledger_bridge::EconomicResult::VerifierOptOut {
+                                    verifier,
+                                    stake_amount,
+                                } => {
+                                    verifier_pool_bp.lock().await.opt_out(verifier);
+                                    tracing::info!(
+                                        %verifier,
+                                        stake_amount,
+                                        "verifier left the pool"
+                                    );
+                                }
+                                _ => {}
+                            }
+                        }
+
                         if let crate::ledger_bridge::EconomicResult::Endorse {
                             target: Some(ref target_addr),
                             burn_amount,
@@ -1124,57 +2429,20 @@ impl BurstNode {
 
                                 // Fetch VRF randomness and feed selected verifiers to the orchestrator
                                 let vrf = Arc::clone(&vrf_client_bp);
-                                let pool = Arc::clone(&verifier_pool_bp);
+                                let store_vrf = Arc::clone(&store);
                                 let orch_vrf = Arc::clone(&verification_orch_bp);
                                 let target_for_vrf = target_addr.clone();
                                 let params_vrf = config_params_bp.clone();
                                 tokio::spawn(async move {
-                                    let client = vrf.lock().await;
-                                    match client.fetch_latest().await {
-                                        Ok(beacon) => {
-                                            let randomness = hex::decode(&beacon.randomness)
-                                                .unwrap_or_else(|_| vec![0u8; 32]);
-                                            let mut rand_bytes = [0u8; 32];
-                                            let copy_len = randomness.len().min(32);
-                                            rand_bytes[..copy_len]
-                                                .copy_from_slice(&randomness[..copy_len]);
-
-                                            let verifier_addrs = {
-                                                let p = pool.lock().await;
-                                                p.pool()
-                                            };
-
-                                            let mut orch = orch_vrf.lock().await;
-                                            match orch.select_verifiers(
-                                                &target_for_vrf,
-                                                &verifier_addrs,
-                                                &rand_bytes,
-                                                &params_vrf,
-                                            ) {
-                                                Ok(selected) => {
-                                                    tracing::info!(
-                                                        target = %target_for_vrf,
-                                                        selected_count = selected.len(),
-                                                        drand_round = beacon.round,
-                                                        "verifiers selected via VRF for endorsement"
-                                                    );
-                                                }
-                                                Err(e) => {
-                                                    tracing::error!(
-                                                        error = %e,
-                                                        target = %target_for_vrf,
-                                                        "failed to assign verifiers via orchestrator"
-                                                    );
-                                                }
-                                            }
-                                        }
-                                        Err(e) => {
-                                            tracing::error!(
-                                                error = %e,
-                                                "failed to fetch VRF randomness for verification"
-                                            );
-                                        }
-                                    }
+                                    draw_verifiers_via_vrf(
+                                        &vrf,
+                                        &store_vrf,
+                                        &orch_vrf,
+                                        &target_for_vrf,
+                                        &params_vrf,
+                                        false,
+                                    )
+                                    .await;
                                 });
                             }
                         }
@@ -1200,26 +2468,32 @@ impl BurstNode {
                             );
 
                             // Register the challenge with the orchestrator for
-                            // re-verification. Do NOT revoke TRST or change
-                            // account state here — that only happens if the
+                            // re-verification and mark the target Challenged.
+                            // Do NOT revoke TRST here — that only happens if the
                             // orchestrator confirms fraud via WalletUnverified.
                             let challenger_verified = prev_account
                                 .as_ref()
                                 .is_some_and(|a| a.state == burst_types::WalletState::Verified);
                             let mut orch = verification_orch_bp.lock().await;
-                            if let Err(e) = orch.initiate_challenge(
+                            match orch.initiate_challenge(
                                 target_addr,
                                 &block.account,
                                 challenger_verified,
                                 stake_amount,
                                 &config_params_bp,
                             ) {
-                                tracing::warn!(
+                                Ok(()) => transition_stored_account(
+                                    &store,
+                                    target_addr,
+                                    burst_types::WalletState::Challenged,
+                                    burst_types::TransitionReason::ChallengeInitiated,
+                                ),
+                                Err(e) => tracing::warn!(
                                     target = %target_addr,
                                     challenger = %block.account,
                                     error = %e,
                                     "challenge initiation failed in orchestrator"
-                                );
+                                ),
                             }
                         }
 
@@ -1229,6 +2503,33 @@ impl BurstNode {
                         // BurnOnly: BRN was burned but no valid receiver was found,
                         // so no TRST was minted. The burn was already recorded by
                         // process_block_economics; log for visibility.
+                        if let crate::ledger_bridge::EconomicResult::Alias {
+                            burn_amount,
+                            ref burn_result,
+                        } = econ_result
+                        {
+                            match (burn_result, alias_record.as_ref()) {
+                                (Ok(()), Some(record)) => {
+                                    tracing::info!(
+                                        account = %block.account,
+                                        alias = %record.name,
+                                        expires_at = %record.expires_at,
+                                        burn_amount,
+                                        "alias registered"
+                                    );
+                                }
+                                (Err(e), _) => {
+                                    tracing::error!(
+                                        account = %block.account,
+                                        burn_amount,
+                                        error = %e,
+                                        "alias fee burn recording failed"
+                                    );
+                                }
+                                _ => {}
+                            }
+                        }
+
                         if let crate::ledger_bridge::EconomicResult::BurnOnly {
                             burn_amount,
                             ref burn_result,
@@ -1258,6 +2559,8 @@ impl BurstNode {
                             ref proposer,
                             proposal_hash,
                             ref content,
+                            ballot,
+                            requires,
                         } = econ_result
                         {
                             let mut gov = governance_bp.lock().await;
@@ -1284,7 +2587,7 @@ impl BurstNode {
                                 proposer: proposer.clone(),
                                 content: proposal_content,
                                 phase: burst_governance::proposal::GovernancePhase::Proposal,
-                                created_at: Timestamp::new(unix_now_secs()),
+                                created_at: dev_controls_bp.now(),
                                 endorsement_count: 0,
                                 exploration_votes_yea: 0,
                                 exploration_votes_nay: 0,
@@ -1303,20 +2606,24 @@ impl BurstNode {
                                 .wallets
                                 .get(&block.account)
                                 .map(|ws| {
-                                    ws.available_balance(
-                                        &brn.rate_history,
-                                        Timestamp::new(unix_now_secs()),
-                                    )
+                                    ws.available_balance(&brn.rate_history, dev_controls_bp.now())
                                 })
                                 .unwrap_or(0);
-                            match gov.submit_proposal(
-                                proposal,
-                                brn_balance,
-                                proposer_verified,
-                                &config_params_bp,
-                            ) {
+                            let required = match requires {
+                                Some(dependency) => gov.require(proposal_hash, dependency),
+                                None => Ok(()),
+                            };
+                            match required.and_then(|()| {
+                                gov.submit_proposal(
+                                    proposal,
+                                    brn_balance,
+                                    proposer_verified,
+                                    &config_params_bp,
+                                )
+                            }) {
                                 Ok(hash) => {
-                                    tracing::info!(%hash, proposer = %proposer, "governance proposal registered in engine")
+                                    ballots_bp.lock().await.set_mode(hash, ballot);
+                                    tracing::info!(%hash, proposer = %proposer, ?ballot, "governance proposal registered in engine")
                                 }
                                 Err(e) => {
                                     tracing::warn!(proposer = %proposer, "governance proposal rejected by engine: {e}")
@@ -1335,7 +2642,7 @@ impl BurstNode {
                         } = econ_result
                         {
                             let mut gov = governance_bp.lock().await;
-                            let now = Timestamp::new(unix_now_secs());
+                            let now = dev_controls_bp.now();
 
                             let voting_power = {
                                 let del = delegation_bp.lock().await;
@@ -1349,7 +2656,9 @@ impl BurstNode {
                                 "governance vote with delegated voting power"
                             );
 
-                            match gov.cast_exploration_vote(
+                            let ballots = ballots_bp.lock().await;
+                            match gov.cast_vote(
+                                &ballots,
                                 &proposal_hash,
                                 voter,
                                 vote,
@@ -1357,23 +2666,9 @@ impl BurstNode {
                                 &config_params_bp,
                             ) {
                                 Ok(()) => {
-                                    tracing::info!(%proposal_hash, voter = %voter, ?vote, "governance exploration vote recorded")
-                                }
-                                Err(burst_governance::GovernanceError::WrongPhase) => {
-                                    match gov.cast_promotion_vote(
-                                        &proposal_hash,
-                                        voter,
-                                        vote,
-                                        now,
-                                        &config_params_bp,
-                                    ) {
-                                        Ok(()) => {
-                                            tracing::info!(%proposal_hash, voter = %voter, ?vote, "governance promotion vote recorded")
-                                        }
-                                        Err(e) => {
-                                            tracing::warn!(%proposal_hash, voter = %voter, "governance vote rejected: {e}")
-                                        }
-                                    }
+                                    let phase =
+                                        gov.get_proposal(&proposal_hash).map(|p| p.phase.clone());
+                                    tracing::info!(%proposal_hash, voter = %voter, ?vote, ?phase, "governance vote recorded")
                                 }
                                 Err(e) => {
                                     tracing::warn!(%proposal_hash, voter = %voter, "governance vote rejected: {e}")
@@ -1381,6 +2676,56 @@ impl BurstNode {
                             }
                         }
 
+                        if let crate::ledger_bridge::EconomicResult::GovernanceCommit {
+                            ref voter,
+                            proposal_hash,
+                            commitment,
+                        } = econ_result
+                        {
+                            let gov = governance_bp.lock().await;
+                            let mut ballots = ballots_bp.lock().await;
+                            match gov.commit_ballot(
+                                &mut ballots,
+                                &proposal_hash,
+                                voter,
+                                commitment,
+                                dev_controls_bp.now(),
+                                &config_params_bp,
+                            ) {
+                                Ok(()) => {
+                                    tracing::info!(%proposal_hash, voter = %voter, "sealed governance ballot committed")
+                                }
+                                Err(e) => {
+                                    tracing::warn!(%proposal_hash, voter = %voter, "sealed ballot commitment rejected: {e}")
+                                }
+                            }
+                        }
+
+                        if let crate::ledger_bridge::EconomicResult::GovernanceReveal {
+                            ref voter,
+                            proposal_hash,
+                            ref sealed,
+                        } = econ_result
+                        {
+                            let mut gov = governance_bp.lock().await;
+                            let mut ballots = ballots_bp.lock().await;
+                            match gov.reveal_ballot(
+                                &mut ballots,
+                                &proposal_hash,
+                                voter,
+                                sealed,
+                                dev_controls_bp.now(),
+                                &config_params_bp,
+                            ) {
+                                Ok(vote) => {
+                                    tracing::info!(%proposal_hash, voter = %voter, ?vote, "sealed governance ballot revealed")
+                                }
+                                Err(e) => {
+                                    tracing::warn!(%proposal_hash, voter = %voter, "sealed ballot reveal rejected: {e}")
+                                }
+                            }
+                        }
+
                         // Process delegation blocks through the DelegationEngine
                         if block.block_type == BlockType::Delegate {
                             let target =
@@ -1401,20 +2746,27 @@ impl BurstNode {
                                 }
 
                                 // Store delegation record for scope-enforced signature verification.
-                                // The delegation public key is derived from the transaction hash field.
-                                let delegation_public_key: [u8; 32] = *block.transaction.as_bytes();
-                                let record = DelegationRecord {
-                                    delegator: block.account.clone(),
-                                    delegate: target_addr.clone(),
-                                    delegation_public_key,
-                                    created_at: block.timestamp,
-                                    revoked: false,
-                                };
-                                if let Err(e) = delegation_store_bp.put_delegation(&record) {
-                                    tracing::warn!(
-                                        delegator = %block.account,
-                                        "failed to store delegation record: {e}"
-                                    );
+                                // A re-delegation continues the previous key generation count.
+                                if let Some(ref key) = block.delegation_key {
+                                    let generation = delegation_store_bp
+                                        .get_delegation_by_delegator(&block.account)
+                                        .ok()
+                                        .flatten()
+                                        .map_or(1, |previous| previous.generation + 1);
+                                    let record = DelegationRecord {
+                                        delegator: block.account.clone(),
+                                        delegate: target_addr.clone(),
+                                        delegation_public_key: *key.public_key.as_bytes(),
+                                        generation,
+                                        created_at: block.timestamp,
+                                        revoked: false,
+                                    };
+                                    if let Err(e) = delegation_store_bp.put_delegation(&record) {
+                                        tracing::warn!(
+                                            delegator = %block.account,
+                                            "failed to store delegation record: {e}"
+                                        );
+                                    }
                                 }
                             } else {
                                 tracing::warn!(
@@ -1424,6 +2776,26 @@ impl BurstNode {
                             }
                         }
 
+                        if block.block_type == BlockType::RotateDelegationKey {
+                            if let Some(ref key) = block.delegation_key {
+                                match delegation_store_bp.rotate_delegation_key(
+                                    &block.account,
+                                    *key.public_key.as_bytes(),
+                                    block.timestamp,
+                                ) {
+                                    Ok(record) => tracing::info!(
+                                        delegator = %block.account,
+                                        generation = record.generation,
+                                        "delegation key rotated"
+                                    ),
+                                    Err(e) => tracing::warn!(
+                                        delegator = %block.account,
+                                        "failed to rotate delegation key: {e}"
+                                    ),
+                                }
+                            }
+                        }
+
                         if block.block_type == BlockType::RevokeDelegation {
                             let mut del = delegation_bp.lock().await;
                             del.undelegate(&block.account);
@@ -1441,21 +2813,100 @@ impl BurstNode {
                             }
                         }
 
+                        // Recovery: record the custodian set, or switch the
+                        // account to its recovered signing key.
+                        match &block.recovery {
+                            Some(burst_transactions::recovery::RecoveryData::Setup {
+                                custodians,
+                                threshold,
+                                delay_secs,
+                            }) => {
+                                let config = RecoveryConfig {
+                                    account: block.account.clone(),
+                                    custodians: custodians.clone(),
+                                    threshold: *threshold,
+                                    delay_secs: *delay_secs,
+                                    configured_at: block.timestamp,
+                                };
+                                if let Err(e) = recovery_store_bp.put_recovery_config(&config) {
+                                    tracing::warn!(
+                                        account = %block.account,
+                                        "failed to store recovery custodians: {e}"
+                                    );
+                                }
+                            }
+                            Some(burst_transactions::recovery::RecoveryData::Rotate {
+                                new_key,
+                                ..
+                            }) => {
+                                match recovery_store_bp.put_signing_key(&block.account, new_key) {
+                                    Ok(()) => tracing::info!(
+                                        account = %block.account,
+                                        "account key rotated by social recovery"
+                                    ),
+                                    Err(e) => tracing::warn!(
+                                        account = %block.account,
+                                        "failed to store recovered signing key: {e}"
+                                    ),
+                                }
+                            }
+                            None => {}
+                        }
+
                         // GovernanceActivation: apply the on-chain parameter change
                         // (Tezos-style self-amendment recorded on the genesis chain).
                         if let crate::ledger_bridge::EconomicResult::GovernanceActivation {
                             proposal_hash,
                             new_params_hash,
+                            certificate,
                         } = &econ_result
                         {
                             let mut gov = governance_bp.lock().await;
                             if let Some(proposal) = gov.get_proposal(proposal_hash) {
                                 let p = proposal.clone();
                                 let mut params = config_params_bp.clone();
-                                if gov.activate(&p, &mut params).is_ok() {
+                                // Rebuild the quorum certificate from this node's own
+                                // tally; a block committing to any other is not applied.
+                                let certified_at = gov
+                                    .quorum_certificate(&p, &config_params_bp, *new_params_hash)
+                                    .filter(|local| {
+                                        local.hash() == *certificate && local.verify().is_ok()
+                                    })
+                                    .map(|local| local.activation_at);
+                                if certified_at.is_none() {
+                                    tracing::warn!(
+                                        %proposal_hash,
+                                        %certificate,
+                                        "governance activation block does not match the local quorum certificate — skipping"
+                                    );
+                                } else if gov.activate(&p, &mut params).is_ok() {
                                     let computed = params.params_hash();
                                     if computed == *new_params_hash {
                                         config_params_bp = params.clone();
+                                        let thresholds =
+                                            burst_work::WorkThresholds::from_params(&params);
+                                        {
+                                            let mut processor = bp.lock().await;
+                                            processor.set_work_thresholds(thresholds.clone());
+                                            processor.set_bootstrap_limits(
+                                                crate::limits::BootstrapLimits::from_params(
+                                                    &params,
+                                                ),
+                                            );
+                                        }
+                                        if let Some(activation_at) = certified_at {
+                                            bp.lock()
+                                                .await
+                                                .apply_params_activation(computed, activation_at);
+                                        }
+                                        if let Ok(mut current) = live_params_bp.write() {
+                                            *current = params.clone();
+                                        }
+                                        trst_engine_bp
+                                            .lock()
+                                            .await
+                                            .set_expiry_secs(params.trst_expiry_secs);
+                                        prefilter_bp.lock().await.set_work_thresholds(thresholds);
                                         let changes = gov.drain_pending_changes();
                                         if !changes.is_empty() {
                                             for (param, value) in &changes {
@@ -1465,7 +2916,7 @@ impl BurstNode {
                                                             brn_engine_bp.lock().await;
                                                         if let Err(e) = brn_lock.apply_rate_change(
                                                             *value,
-                                                            Timestamp::new(unix_now_secs()),
+                                                            dev_controls_bp.now(),
                                                         ) {
                                                             tracing::warn!(error = %e, "failed to propagate BRN rate change from activation block");
                                                         }
@@ -1490,7 +2941,7 @@ impl BurstNode {
                                                         votes_yea: 0,
                                                         votes_nay: 0,
                                                         votes_abstain: 0,
-                                                        created_at: Timestamp::new(unix_now_secs()),
+                                                        created_at: dev_controls_bp.now(),
                                                         operations: Vec::new(),
                                                     };
                                                     match consti.activate_amendment_internal(&amendment) {
@@ -1500,6 +2951,21 @@ impl BurstNode {
                                                 }
                                             }
                                         }
+                                        if let burst_governance::ProposalContent::TreasuryGrant {
+                                            ref recipient,
+                                            amount,
+                                        } = p.content
+                                        {
+                                            pay_treasury_grant(
+                                                &store,
+                                                &treasury_bp,
+                                                &block,
+                                                *proposal_hash,
+                                                recipient,
+                                                amount,
+                                            )
+                                            .await;
+                                        }
                                         // Persist to LMDB
                                         if let Ok(bytes) = bincode::serialize(&config_params_bp) {
                                             let brn_store_meta = store.brn_store();
@@ -1575,16 +3041,19 @@ impl BurstNode {
                         }
 
                         // RejectReceive: pending entry deleted in the write batch above
-                        // (delete_pending_entry handles both Receive and RejectReceive).
+                        // (delete_pending_entry handles both Receive and RejectReceive)
+                        // and re-created for the sender via create_returned_pending_entry.
                         if let crate::ledger_bridge::EconomicResult::RejectReceive {
                             ref rejecter,
                             send_block_hash,
+                            reason,
                         } = econ_result
                         {
                             tracing::info!(
                                 %rejecter,
                                 %send_block_hash,
-                                "TRST receive rejected, pending entry deleted in write batch"
+                                reason = reason.map_or("unspecified", |r| r.as_str()),
+                                "TRST receive rejected, pending entry returned to sender"
                             );
                         }
 
@@ -1646,34 +3115,97 @@ impl BurstNode {
                                 }
                             }
 
+                            // A challenge re-vote resolves once every selected
+                            // verifier has voted.
+                            if orch.challenge_votes_complete(target_addr) {
+                                if let Err(e) =
+                                    orch.resolve_challenge(target_addr, &config_params_bp)
+                                {
+                                    tracing::warn!(
+                                        target = %target_addr,
+                                        error = %e,
+                                        "challenge resolution failed"
+                                    );
+                                }
+                            }
+
                             // Drain orchestrator events and act on them
                             let events = orch.drain_events();
                             for event in events {
+                                let (event_type, subject) = verification_event_kind(&event);
+                                ws_state_bp.publish_verification(
+                                    event_type,
+                                    subject.as_str(),
+                                    subject.as_str(),
+                                );
                                 match event {
                                         burst_verification::VerificationEvent::EndorsementComplete { ref wallet } => {
                                             tracing::info!(%wallet, "endorsement threshold reached");
+                                            transition_stored_account(
+                                                &store,
+                                                wallet,
+                                                burst_types::WalletState::Endorsed,
+                                                burst_types::TransitionReason::EndorsementThresholdReached,
+                                            );
                                         }
                                         burst_verification::VerificationEvent::VerifiersSelected { ref wallet, ref verifiers } => {
                                             tracing::info!(%wallet, count = verifiers.len(), "verifiers assigned by orchestrator");
+                                            // Challenge re-votes and replacement draws
+                                            // leave the account state alone.
+                                            if store.account_store().get_account(wallet).is_ok_and(|a| a.state == burst_types::WalletState::Endorsed) {
+                                                transition_stored_account(
+                                                    &store,
+                                                    wallet,
+                                                    burst_types::WalletState::Voting,
+                                                    burst_types::TransitionReason::VerifiersSelected,
+                                                );
+                                            }
                                         }
                                         burst_verification::VerificationEvent::VerificationComplete { ref wallet, ref result, ref outcomes } => {
                                             tracing::info!(%wallet, ?result, "verification complete");
-                                            if *result == burst_verification::VerificationResult::Verified {
-                                                if let Ok(mut acct) = store.account_store().get_account(wallet) {
-                                                    acct.state = burst_types::WalletState::Verified;
-                                                    acct.verified_at = Some(Timestamp::now());
+                                            if let Some(state) = orch.get_state(wallet) {
+                                                let outcome = match result {
+                                                    burst_verification::VerificationResult::Verified => burst_store::VerificationRecordOutcome::Verified,
+                                                    burst_verification::VerificationResult::Failed => burst_store::VerificationRecordOutcome::Failed,
+                                                };
+                                                persist_verification_record(&store, state, outcome);
+                                            }
+                                            if let Ok(mut acct) = store.account_store().get_account(wallet) {
+                                                // Accounts opened after their endorsement
+                                                // completed catch up through the steps they missed.
+                                                if acct.state == burst_types::WalletState::Unverified {
+                                                    transition_account_state(&store, &mut acct, burst_types::WalletState::Endorsed, burst_types::TransitionReason::EndorsementThresholdReached);
+                                                }
+                                                if acct.state == burst_types::WalletState::Endorsed {
+                                                    transition_account_state(&store, &mut acct, burst_types::WalletState::Voting, burst_types::TransitionReason::VerifiersSelected);
+                                                }
+                                                let changed = match result {
+                                                    burst_verification::VerificationResult::Verified => {
+                                                        let passed = transition_account_state(&store, &mut acct, burst_types::WalletState::Verified, burst_types::TransitionReason::VerificationPassed);
+                                                        if passed {
+                                                            acct.verified_at = Some(dev_controls_bp.now());
+                                                        }
+                                                        passed
+                                                    }
+                                                    burst_verification::VerificationResult::Failed => {
+                                                        transition_account_state(&store, &mut acct, burst_types::WalletState::Unverified, burst_types::TransitionReason::VerificationFailed)
+                                                    }
+                                                };
+                                                if changed {
                                                     if let Err(e) = store.account_store().put_account(&acct) {
-                                                        tracing::error!(%wallet, "failed to update account to Verified: {e}");
+                                                        tracing::error!(%wallet, "failed to update account after verification: {e}");
                                                     }
                                                 }
+                                            }
+                                            if *result == burst_verification::VerificationResult::Verified {
                                                 let mut brn_inner = brn_engine_bp.lock().await;
-                                                let ws = burst_brn::BrnWalletState::new(Timestamp::now());
+                                                let ws = burst_brn::BrnWalletState::new(dev_controls_bp.now());
                                                 brn_inner.track_wallet(wallet.clone(), ws);
                                                 tracing::info!(%wallet, "BRN accrual activated after verification");
 
                                                 // Mint TRST rewards for endorsers
                                                 let mut trst_inner = trst_engine_bp.lock().await;
-                                                let now_ts = Timestamp::now();
+                                                let now_ts = dev_controls_bp.now();
                                                 for eo in &outcomes.endorsers {
                                                     if eo.trst_reward > 0 {
                                                         let reward_hash = TxHash::new(
@@ -1698,7 +3230,7 @@ impl BurstNode {
                                                                 );
                                                                 let expiry_ts = Timestamp::new(
                                                                     token.effective_origin_timestamp.as_secs()
-                                                                        .saturating_add(trst_expiry_secs),
+                                                                        .saturating_add(config_params_bp.trst_expiry_secs),
                                                                 );
                                                                 if let Ok(mut idx_batch) = store.write_batch() {
                                                                     let _ = idx_batch.put_origin_index(&token.origin, &token.id);
@@ -1745,6 +3277,16 @@ impl BurstNode {
                                                             penalty = vo.penalty,
                                                             "dissenter verifier stake forfeited"
                                                         );
+                                                        let mut treasury = treasury_bp.lock().await;
+                                                        if treasury.credit(
+                                                            TreasurySource::VerifierStake,
+                                                            block.hash.into_tx_hash(),
+                                                            &vo.address,
+                                                            vo.staked,
+                                                            block.timestamp,
+                                                        ) {
+                                                            persist_treasury(&store, &treasury);
+                                                        }
                                                     }
                                                 } else {
                                                     tracing::warn!(
@@ -1757,28 +3299,42 @@ impl BurstNode {
                                         burst_verification::VerificationEvent::WalletUnverified { ref wallet } => {
                                             tracing::warn!(%wallet, "wallet unverified (fraud confirmed)");
                                             let mut trst_inner = trst_engine_bp.lock().await;
-                                            let revocations = trst_inner.revoke_by_origin(wallet);
+                                            revoke_fraudulent_wallet(&store, &mut trst_inner, wallet);
                                             drop(trst_inner);
-                                            let total_revoked: u128 = revocations.iter().map(|r| r.revoked_amount).sum();
-                                            if !revocations.is_empty() {
-                                                tracing::warn!(
-                                                    %wallet,
-                                                    revoked_count = revocations.len(),
-                                                    total_revoked,
-                                                    "TRST revoked via orchestrator fraud confirmation"
-                                                );
-                                            }
-                                            if let Ok(mut acct) = store.account_store().get_account(wallet) {
-                                                acct.state = burst_types::WalletState::Revoked;
-                                                acct.revoked_trst = acct.revoked_trst.saturating_add(total_revoked);
-                                                acct.trst_balance = acct.trst_balance.saturating_sub(total_revoked);
-                                                if let Err(e) = store.account_store().put_account(&acct) {
-                                                    tracing::error!(%wallet, "failed to persist account Revoked state: {e}");
-                                                }
-                                            }
+                                            // Announce the verdict once this block is cemented.
+                                            fraud_verdicts_bp.lock().await.insert(block.hash, wallet.clone());
                                         }
                                         burst_verification::VerificationEvent::ChallengeResolved { ref wallet, ref outcome } => {
                                             tracing::info!(%wallet, ?outcome.outcome, "challenge resolved via orchestrator");
+                                            persist_challenge_history(&store, &orch, outcome);
+                                            if outcome.outcome == burst_verification::ChallengeResult::ChallengeRejected {
+                                                let mut treasury = treasury_bp.lock().await;
+                                                if treasury.credit(
+                                                    TreasurySource::ChallengeStake,
+                                                    block.hash.into_tx_hash(),
+                                                    &outcome.challenger,
+                                                    outcome.challenger_stake,
+                                                    block.timestamp,
+                                                ) {
+                                                    persist_treasury(&store, &treasury);
+                                                }
+                                            }
+                                            let survived = match outcome.outcome {
+                                                burst_verification::ChallengeResult::FraudConfirmed => None,
+                                                burst_verification::ChallengeResult::ChallengeRejected => Some(burst_types::TransitionReason::ChallengeRejected),
+                                                burst_verification::ChallengeResult::Expired => Some(burst_types::TransitionReason::ChallengeExpired),
+                                            };
+                                            if let Some(reason) = survived {
+                                                transition_stored_account(&store, wallet, burst_types::WalletState::Verified, reason);
+                                            }
+                                            if let Some(state) = orch.get_state(wallet) {
+                                                let record_outcome = match outcome.outcome {
+                                                    burst_verification::ChallengeResult::FraudConfirmed => burst_store::VerificationRecordOutcome::FraudConfirmed,
+                                                    burst_verification::ChallengeResult::ChallengeRejected => burst_store::VerificationRecordOutcome::ChallengeRejected,
+                                                    burst_verification::ChallengeResult::Expired => burst_store::VerificationRecordOutcome::ChallengeExpired,
+                                                };
+                                                persist_verification_record(&store, state, record_outcome);
+                                            }
                                         }
                                         burst_verification::VerificationEvent::VerifierPenalized { ref verifier, ref reason, cooldown_until } => {
                                             tracing::warn!(
@@ -1788,16 +3344,43 @@ impl BurstNode {
                                                 "verifier penalized — excluded from future selection"
                                             );
                                         }
+                                        burst_verification::VerificationEvent::CollusionFlagged { ref wallet, score_bps, ref flags } => {
+                                            tracing::warn!(
+                                                %wallet,
+                                                score_bps,
+                                                patterns = flags.len(),
+                                                "endorsement graph flagged wallet as a challenge candidate"
+                                            );
+                                        }
+                                        // Only produced by the verification timeout
+                                        // task, which handles them itself.
+                                        burst_verification::VerificationEvent::VerifiersTimedOut { .. }
+                                        | burst_verification::VerificationEvent::VerificationExpired { .. } => {}
                                     }
                             }
                         }
 
                         // Track acceptance (NOT confirmation — that happens via consensus)
                         metrics.blocks_accepted.inc();
+                        tx_status_bp.accepted(&block.hash, unix_now_secs());
+
+                        // Dev networks may confirm straight away, without an election.
+                        if dev_controls_bp.instant_confirmation() {
+                            recently_confirmed_bp.write().await.insert(block.hash);
+                            tx_status_bp.confirmed(&block.hash, unix_now_secs());
+                            if confirming_set_bp.lock().await.add(block.hash) {
+                                metrics.blocks_confirmed.inc();
+                                tracing::debug!(hash = %block.hash, "block confirmed instantly (dev)");
+                            } else {
+                                tracing::warn!(hash = %block.hash, "confirming set full — backpressure");
+                            }
+                        }
+                        // Arrival time, not the block's own timestamp: the
+                        // creator picks that and could spread a burst out.
                         difficulty_adjuster_bp
                             .lock()
                             .await
-                            .record_block(block.timestamp.as_secs());
+                            .record_block(unix_now_secs());
                         tracing::debug!(hash = %block.hash, "block accepted and persisted");
 
                         // Publish block acceptance event to WebSocket subscribers
@@ -1805,12 +3388,34 @@ impl BurstNode {
                             &block.account.to_string(),
                             &format!("{}", block.hash),
                             &block.trst_balance.to_string(),
+                            block.memo.as_ref().map(hex::encode).as_deref(),
+                            block.destination_tag(),
                         );
                         ws_state_bp.publish_account_update(
                             &block.account.to_string(),
                             &block.trst_balance.to_string(),
                             &format!("{:?}", block.block_type),
                         );
+                        if let Some((amount, ref dest, _)) = deferred_pending {
+                            ws_state_bp.publish_pending(
+                                dest.as_str(),
+                                &block.account.to_string(),
+                                &format!("{}", block.hash),
+                                &amount.to_string(),
+                                block.memo.as_ref().map(hex::encode).as_deref(),
+                                block.destination_tag(),
+                            );
+                        }
+                        if let Some(ref rejected) = rejected_pending {
+                            ws_state_bp.publish_rejected(
+                                rejected.source.as_str(),
+                                &block.account.to_string(),
+                                &format!("{}", block.link),
+                                &format!("{}", block.hash),
+                                &rejected.amount.to_string(),
+                                block.reject_reason.map(|r| r.as_str()),
+                            );
+                        }
 
                         // TASK 2: Generate and broadcast a vote for the accepted block
                         {
@@ -1854,6 +3459,20 @@ impl BurstNode {
                             let mut fc = fork_cache_bp.lock().await;
                             fc.insert(block.previous, block.hash);
                         }
+                        // Record the double-spend attempt against the block it
+                        // competes with
+                        if let Some(existing) = crate::fork_log::contested_block(&store, &block) {
+                            let now = Timestamp::new(unix_now_secs());
+                            match fork_log_bp.record(&block, &existing, now) {
+                                Ok(Some(fork)) => {
+                                    crate::fork_log::report(&fork, &metrics, &ws_state_bp)
+                                }
+                                Ok(None) => {}
+                                Err(e) => {
+                                    tracing::warn!(hash = %block.hash, "failed to record fork: {e}")
+                                }
+                            }
+                        }
                         // Fork detected — start an election on the root (previous block)
                         let now = Timestamp::new(unix_now_secs());
                         let mut ae = active_elections_bp.write().await;
@@ -1864,9 +3483,15 @@ impl BurstNode {
                                 "could not start election for fork"
                             );
                         } else {
+                            let replayed = vote_cache_bp.write().await.replay_into(
+                                &block.previous,
+                                &mut ae,
+                                now,
+                            );
                             tracing::info!(
                                 root = %block.previous,
                                 fork_hash = %block.hash,
+                                replayed,
                                 "election started for fork"
                             );
                         }
@@ -1878,6 +3503,7 @@ impl BurstNode {
 
                 let elapsed = start.elapsed().as_secs_f64() * 1000.0;
                 metrics.block_process_time_ms.observe(elapsed);
+                stats.sample(SampleType::BlockProcessTime, elapsed as u64);
             }
         });
         self.task_handles.push(bp_handle);
@@ -1899,6 +3525,10 @@ impl BurstNode {
         let governance_ct = Arc::clone(&self.governance);
         let brn_engine_ct = Arc::clone(&self.brn_engine);
         let local_broadcaster_ct = Arc::clone(&self.local_broadcaster);
+        let election_tuner_ct = Arc::clone(&self.election_tuner);
+        let confirmation_history_ct = Arc::clone(&self.confirmation_history);
+        let tx_status_ct = Arc::clone(&self.tx_status);
+        let fork_log_ct = Arc::clone(&self.fork_log);
 
         let confirmation_handle = tokio::spawn(async move {
             let mut interval = tokio::time::interval(Duration::from_millis(500));
@@ -1924,6 +3554,7 @@ impl BurstNode {
                                 let mut rc = recently_confirmed_ct.write().await;
                                 rc.insert(winner);
                             }
+                            tx_status_ct.confirmed(&winner, unix_now_secs());
 
                             // Add to confirming set for batched cementation
                             {
@@ -1952,14 +3583,31 @@ impl BurstNode {
                             metrics_ct
                                 .confirmation_latency_ms
                                 .observe(status.election_duration_ms as f64);
+                            election_tuner_ct
+                                .lock()
+                                .await
+                                .observe_confirmation(status.election_duration_ms);
 
                             // Publish WebSocket notification
                             ws_state_ct.publish_confirmation(
                                 "",
                                 &format!("{}", winner),
                                 "0",
+                                None,
+                                None,
                             );
 
+                            // Settle any fork recorded for the winner's slot
+                            match fork_log_ct.resolve(&winner, Timestamp::new(unix_now_secs())) {
+                                Ok(resolved) => {
+                                    for fork in &resolved {
+                                        crate::fork_log::report(fork, &metrics_ct, &ws_state_ct);
+                                        tx_status_ct.fork_resolved(fork, unix_now_secs());
+                                    }
+                                }
+                                Err(e) => tracing::warn!(%winner, "failed to resolve fork: {e}"),
+                            }
+
                             tracing::info!(
                                 winner = %winner,
                                 tally = status.tally,
@@ -1967,19 +3615,55 @@ impl BurstNode {
                                 "block confirmed by consensus"
                             );
 
+                            // Record in the confirmation history
+                            {
+                                let block_store = store_ct.block_store();
+                                let load = |hash: &BlockHash| {
+                                    let bytes = block_store.get_block(hash).ok()?;
+                                    bincode::deserialize::<StateBlock>(&bytes).ok()
+                                };
+                                if let Some(block) = load(&winner) {
+                                    let previous = if block.previous.is_zero() {
+                                        None
+                                    } else {
+                                        load(&block.previous)
+                                    };
+                                    let record = ConfirmationRecord {
+                                        hash: winner,
+                                        amount: transferred_amount(&block, previous.as_ref()),
+                                        account: block.account,
+                                        tally: status.tally,
+                                        duration_ms: status.election_duration_ms,
+                                        confirmed_at: unix_now_secs(),
+                                    };
+                                    confirmation_history_ct.lock().await.record(record);
+                                }
+                            }
+
                             // TASK 3: Generate and broadcast a FINAL vote for the winner
                             {
                                 let mut vg = vote_generator_ct.lock().await;
                                 if vg.is_representative {
                                     let final_vote = vg.generate_final_vote(winner);
-                                    let wire_msg = WireMessage::Vote(WireVote {
+                                    let wire_vote = WireVote {
                                         voter: final_vote.voter,
                                         block_hashes: vec![final_vote.block_hash],
                                         is_final: true,
                                         timestamp: final_vote.timestamp,
                                         sequence: final_vote.sequence,
                                         signature: final_vote.signature,
-                                    });
+                                    };
+                                    // Keep our own final vote so the root can
+                                    // be re-acked after a restart.
+                                    let archive = FinalVoteArchive::new(
+                                        store_ct.final_vote_store(),
+                                        DEFAULT_MAX_FINAL_VOTES,
+                                    );
+                                    match archive.record(&wire_vote) {
+                                        Ok(count) => metrics_ct.final_votes_stored.set(count as i64),
+                                        Err(e) => tracing::warn!("failed to store own final vote: {e}"),
+                                    }
+                                    let wire_msg = WireMessage::Vote(wire_vote);
                                     if let Ok(bytes) = bincode::serialize(&wire_msg) {
                                         let peers: Vec<burst_network::PeerState> = {
                                             let pm = peer_manager_ct.read().await;
@@ -2027,6 +3711,11 @@ impl BurstNode {
                                                             if let Err(e) = block_store.delete_block(&frontier_hash) {
                                                                 tracing::warn!(hash = %frontier_hash, "failed to delete rolled-back block: {e}");
                                                             }
+                                                            tx_status_ct.dropped(
+                                                                &frontier_hash,
+                                                                "rolled back after losing fork election",
+                                                                unix_now_secs(),
+                                                            );
                                                             tracing::info!(
                                                                 account = %winner_block.account,
                                                                 rolled_back = %frontier_hash,
@@ -2074,15 +3763,6 @@ impl BurstNode {
                                                 );
                                             }
                                         }
-                                        burst_governance::GovernableParam::TrstExpirySecs => {
-                                            tracing::info!(
-                                                new_expiry = value,
-                                                "governance updated TRST expiry (applied at block processing)"
-                                            );
-                                            // TRST expiry is read from params at block processing time,
-                                            // so no engine update needed — the governance store is the
-                                            // source of truth.
-                                        }
                                         other => {
                                             tracing::info!(
                                                 param = ?other,
@@ -2099,7 +3779,7 @@ impl BurstNode {
                         let now = Timestamp::new(unix_now_secs());
                         {
                             let mut ae = active_elections_ct.write().await;
-                            let expired = ae.cleanup_expired(30_000, now);
+                            let expired = ae.cleanup_expired(ELECTION_LIFETIME_MS, now);
                             if !expired.is_empty() {
                                 tracing::debug!(
                                     count = expired.len(),
@@ -2115,13 +3795,66 @@ impl BurstNode {
         });
         self.task_handles.push(confirmation_handle);
 
+        // ── Confirmation history snapshots ────────────────────────────────
+        let confirmation_history_ch = Arc::clone(&self.confirmation_history);
+        let store_ch = Arc::clone(&self.store);
+        let mut shutdown_rx_ch = self.shutdown.subscribe();
+
+        let confirmation_history_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CONFIRMATION_HISTORY_PERSIST_INTERVAL);
+            interval.tick().await;
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = shutdown_rx_ch.recv() => break,
+                    _ = interval.tick() => {
+                        let bytes = confirmation_history_ch.lock().await.to_bytes();
+                        let meta = store_ch.meta_store();
+                        if let Err(e) = meta.put_meta(CONFIRMATION_HISTORY_META_KEY, &bytes) {
+                            tracing::warn!("failed to persist confirmation history: {e}");
+                        }
+                    }
+                }
+            }
+        });
+        self.task_handles.push(confirmation_history_handle);
+
+        // Checkpoint digests, taken by the cementation task at each multiple
+        // of `checkpoint_interval` cemented blocks and signed by the
+        // checkpoint task.
+        let checkpoint_interval = self.config.checkpoint_interval;
+        let (checkpoint_tx, mut checkpoint_rx) =
+            tokio::sync::mpsc::unbounded_channel::<burst_ledger::CheckpointDigest>();
+
         // ── Cementation task — durably cements confirmed blocks in batches ─
         let confirming_set_cement = Arc::clone(&self.confirming_set);
+        let tx_status_cement = Arc::clone(&self.tx_status);
         let store_cement = Arc::clone(&self.store);
+        let rep_weights_cement = Arc::clone(&self.rep_weights);
+        let metrics_cement = Arc::clone(&self.metrics);
+        let broadcaster_cement = self.broadcaster.clone();
+        let peer_manager_cement = Arc::clone(&self.peer_manager);
+        let gossip_cement = self.config.gossip;
+        let message_dedup_cement = Arc::clone(&self.message_dedup);
+        let node_key_cement = burst_types::PrivateKey(self.node_private_key.0);
+        let fraud_verdicts_cement = Arc::clone(&fraud_verdicts);
         let mut shutdown_rx_cement = self.shutdown.subscribe();
 
         let cementation_handle = tokio::spawn(async move {
             let processor = ConfirmationProcessor;
+            let mut checkpoint_trigger = if checkpoint_interval > 0 {
+                match ledger_cemented_count(&store_cement.account_store()) {
+                    Ok(count) => Some(CheckpointTrigger::new(checkpoint_interval, count)),
+                    Err(e) => {
+                        tracing::warn!(
+                            "failed to count cemented blocks, not taking checkpoints: {e}"
+                        );
+                        None
+                    }
+                }
+            } else {
+                None
+            };
             let mut interval = tokio::time::interval(Duration::from_millis(100));
             loop {
                 tokio::select! {
@@ -2146,7 +3879,7 @@ impl BurstNode {
                                     block_store.clone(),
                                 );
                                 match processor.process(block_hash, &mut walker) {
-                                    (CementResult::Cemented { blocks_cemented, new_height }, _cemented_hashes) => {
+                                    (CementResult::Cemented { blocks_cemented, new_height }, cemented_hashes) => {
                                         tracing::debug!(
                                             blocks = blocks_cemented,
                                             height = new_height,
@@ -2154,6 +3887,76 @@ impl BurstNode {
                                             "cemented blocks"
                                         );
                                         total_cemented += blocks_cemented;
+                                        let cemented_at = unix_now_secs();
+                                        for hash in &cemented_hashes {
+                                            tx_status_cement.cemented(hash, cemented_at);
+                                        }
+
+                                        // Digest the ledger before anything else is
+                                        // cemented if a multiple of the checkpoint
+                                        // interval was reached.
+                                        if let Some(trigger) = checkpoint_trigger.as_mut() {
+                                            match trigger.cemented(
+                                                account_store.as_ref(),
+                                                block_store.as_ref(),
+                                                block_hash,
+                                                blocks_cemented,
+                                                new_height,
+                                            ) {
+                                                Ok(digests) => {
+                                                    for digest in digests {
+                                                        let _ = checkpoint_tx.send(digest);
+                                                    }
+                                                }
+                                                Err(e) => tracing::warn!("failed to take checkpoint: {e}"),
+                                            }
+                                        }
+
+                                        // Keep the final votes that confirmed the
+                                        // block alongside it.
+                                        let archive = FinalVoteArchive::new(
+                                            store_cement.final_vote_store(),
+                                            DEFAULT_MAX_FINAL_VOTES,
+                                        );
+                                        let certificates = store_cement.quorum_certificate_store();
+                                        let weights = rep_weights_cement.read().await;
+                                        match record_quorum_certificate(
+                                            &archive,
+                                            &certificates,
+                                            &weights,
+                                            block_hash,
+                                            unix_now_secs(),
+                                        ) {
+                                            Ok(true) => {
+                                                if let Ok(count) = certificates.certificate_count() {
+                                                    metrics_cement
+                                                        .quorum_certificates_stored
+                                                        .set(count as i64);
+                                                }
+                                            }
+                                            Ok(false) => {}
+                                            Err(e) => tracing::warn!(
+                                                hash = %block_hash,
+                                                "failed to store quorum certificate: {e}"
+                                            ),
+                                        }
+                                        drop(weights);
+
+                                        let verdict = fraud_verdicts_cement.lock().await.remove(block_hash);
+                                        if let Some(wallet) = verdict {
+                                            issue_revocation_notice(
+                                                &store_cement,
+                                                &broadcaster_cement,
+                                                &peer_manager_cement,
+                                                &gossip_cement,
+                                                &message_dedup_cement,
+                                                &metrics_cement,
+                                                &node_key_cement,
+                                                wallet,
+                                                block_hash,
+                                            )
+                                            .await;
+                                        }
                                     }
                                     (CementResult::AlreadyCemented, _) => {}
                                     (CementResult::BlockNotFound, _) => {
@@ -2183,16 +3986,132 @@ impl BurstNode {
         });
         self.task_handles.push(cementation_handle);
 
+        // ── Checkpoint task — representatives sign the digests the
+        //    cementation task takes and flood their vote. ─────────────────
+        if checkpoint_interval > 0 {
+            let store_cp = Arc::clone(&self.store);
+            let vote_generator_cp = Arc::clone(&self.vote_generator);
+            let broadcaster_cp = self.broadcaster.clone();
+            let peer_manager_cp = Arc::clone(&self.peer_manager);
+            let gossip_cp = self.config.gossip;
+            let message_dedup_cp = Arc::clone(&self.message_dedup);
+            let metrics_cp = Arc::clone(&self.metrics);
+            let mut shutdown_rx_cp = self.shutdown.subscribe();
+
+            let checkpoint_handle = tokio::spawn(async move {
+                loop {
+                    tokio::select! {
+                        biased;
+                        _ = shutdown_rx_cp.recv() => break,
+                        digest = checkpoint_rx.recv() => {
+                            let Some(digest) = digest else { break };
+                            let vote = {
+                                let vg = vote_generator_cp.lock().await;
+                                if !vg.is_representative {
+                                    tracing::debug!(
+                                        cemented_count = digest.cemented_count,
+                                        "checkpoint taken; not a representative, not signing"
+                                    );
+                                    continue;
+                                }
+                                CheckpointVote {
+                                    digest,
+                                    representative: vg.representative.clone(),
+                                    signature: vg.sign(&digest.signed_message()),
+                                }
+                            };
+                            if let Err(e) = record_checkpoint_vote(
+                                &store_cp.checkpoint_store(),
+                                vote.clone(),
+                                unix_now_secs(),
+                            ) {
+                                tracing::warn!("failed to store checkpoint vote: {e}");
+                            }
+                            metrics_cp
+                                .checkpoint_votes
+                                .with_label_values(&["signed"])
+                                .inc();
+                            tracing::info!(
+                                cemented_count = digest.cemented_count,
+                                digest = %digest.hash(),
+                                "signed checkpoint"
+                            );
+                            let peers: Vec<burst_network::PeerState> = {
+                                let pm = peer_manager_cp.read().await;
+                                pm.iter_connected().map(|(_, s)| s.clone()).collect()
+                            };
+                            gossip::flood(
+                                &broadcaster_cp,
+                                &gossip_cp,
+                                &message_dedup_cp,
+                                WireMessage::CheckpointVote(vote),
+                                gossip_cp.origin(),
+                                &peers,
+                            )
+                            .await;
+                        }
+                    }
+                }
+            });
+            self.task_handles.push(checkpoint_handle);
+        }
+
+        // ── State digest task — digests the cemented state for comparison
+        //    with peers' telemetry whenever more blocks have been cemented. ──
+        let confirming_set_sd = Arc::clone(&self.confirming_set);
+        let store_sd = Arc::clone(&self.store);
+        let rep_weights_sd = Arc::clone(&self.rep_weights);
+        let state_digests_sd = Arc::clone(&self.state_digests);
+        let mut shutdown_rx_sd = self.shutdown.subscribe();
+
+        let state_digest_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(STATE_DIGEST_POLL_INTERVAL);
+            let mut last_cemented = None;
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = shutdown_rx_sd.recv() => break,
+                    _ = interval.tick() => {
+                        let cemented = confirming_set_sd.lock().await.cemented_count();
+                        if last_cemented == Some(cemented) {
+                            continue;
+                        }
+                        let weights = rep_weights_sd.read().await.all_weights().clone();
+                        match take_state_digest(
+                            &store_sd.account_store(),
+                            &store_sd.block_store(),
+                            &weights,
+                        ) {
+                            Ok(digest) => {
+                                last_cemented = Some(cemented);
+                                tracing::debug!(
+                                    cemented_count = digest.cemented_count,
+                                    root = %digest.root(),
+                                    "state digest taken"
+                                );
+                                state_digests_sd.lock().await.record(digest);
+                            }
+                            Err(e) => tracing::warn!("failed to take state digest: {e}"),
+                        }
+                    }
+                }
+            }
+        });
+        self.task_handles.push(state_digest_handle);
+
         // ── Governance tick task — periodically advances proposals through phases.
         //    When a proposal reaches activation, creates a GovernanceActivation
         //    block on the genesis chain (Tezos-style on-chain self-amendment).
         //    The actual param change is applied when that block is processed. ──
         let governance_tick = Arc::clone(&self.governance);
+        let ballots_tick = Arc::clone(&self.governance_ballots);
         let store_gov = Arc::clone(&self.store);
         let block_queue_gov = Arc::clone(&self.block_queue);
         let frontier_gov = Arc::clone(&self.frontier);
+        let network_gov = self.config.network;
         let mut shutdown_rx_gov = self.shutdown.subscribe();
         let mut gov_params = self.config.params.clone();
+        let dev_controls_gov = Arc::clone(&self.dev_controls);
 
         let gov_tick_handle = tokio::spawn(async move {
             let mut interval = tokio::time::interval(Duration::from_secs(10));
@@ -2204,19 +4123,32 @@ impl BurstNode {
                         break;
                     }
                     _ = interval.tick() => {
-                        let now = Timestamp::new(unix_now_secs());
+                        let now = dev_controls_gov.now();
                         let mut gov = governance_tick.lock().await;
+                        let params_before = gov_params.clone();
                         let activated = gov.tick(now, &mut gov_params);
                         if !activated.is_empty() {
                             // For each activated proposal, create a GovernanceActivation
                             // block on the genesis chain. The block processing loop
-                            // applies the param change when it processes the block.
+                            // applies the param change when it processes the block,
+                            // so each block builds on the parameters of the one before.
+                            let mut running_params = params_before;
                             for proposal_hash in &activated {
                                 let proposal_snapshot = gov.get_proposal(proposal_hash).cloned();
                                 if let Some(proposal) = proposal_snapshot {
-                                    let mut tentative_params = gov_params.clone();
+                                    let mut tentative_params = running_params.clone();
                                     if gov.activate(&proposal, &mut tentative_params).is_ok() {
                                         let new_params_hash = tentative_params.params_hash();
+                                        let Some(certificate) = gov.quorum_certificate(
+                                            &proposal,
+                                            &running_params,
+                                            new_params_hash,
+                                        ) else {
+                                            tracing::warn!(%proposal_hash, "no quorum certificate for activated proposal");
+                                            continue;
+                                        };
+                                        let stamped_params_hash = running_params.params_hash();
+                                        running_params = tentative_params;
 
                                         let kp = genesis_keypair();
                                         let genesis_addr = genesis_address();
@@ -2233,7 +4165,7 @@ impl BurstNode {
                                         };
 
                                         let genesis_acct = store_gov.account_store().get_account(&genesis_addr);
-                                        let (brn_bal, trst_bal) = match genesis_acct {
+                                        let (brn_bal, trst_bal) = match &genesis_acct {
                                             Ok(acct) => (0u128, acct.trst_balance),
                                             Err(_) => (0, 0),
                                         };
@@ -2247,28 +4179,41 @@ impl BurstNode {
                                             brn_balance: brn_bal,
                                             trst_balance: trst_bal,
                                             link: BlockHash::new(*proposal_hash.as_bytes()),
-                                            origin: TxHash::ZERO,
+                                            origin: certificate.hash(),
                                             transaction: TxHash::new(*new_params_hash.as_bytes()),
                                             timestamp: now,
-                                            params_hash: gov_params.params_hash(),
+                                            params_hash: stamped_params_hash,
+                                            memo: None,
+                                            htlc: None,
+                                            reject_reason: None,
+                                            recovery: None,
+                                            delegation_key: None,
+                                            extensions: Vec::new(),
                                             work: 0,
                                             signature: Signature([0u8; 64]),
                                             hash: BlockHash::ZERO,
                                         };
-                                        block.hash = block.compute_hash();
+                                        let layout = burst_ledger::BlockLayout::for_account(
+                                            genesis_acct.as_ref().map_or(0, |acct| acct.epoch),
+                                            gov_params.ledger_epoch,
+                                            network_gov,
+                                        );
+                                        let sequence = genesis_acct
+                                            .as_ref()
+                                            .map_or(1, |acct| acct.block_count + 1);
+                                        block.apply_layout(layout, sequence);
                                         block.signature = burst_crypto::sign_message(
                                             block.hash.as_bytes(),
                                             &kp.private,
                                         );
 
-                                        let work_thresholds = burst_work::WorkThresholds::with_base(
-                                            gov_params.min_work_difficulty,
-                                        );
+                                        let work_thresholds =
+                                            burst_work::WorkThresholds::from_params(&gov_params);
                                         let threshold = work_thresholds.threshold_for(
                                             burst_work::WorkBlockKind::Epoch,
                                         );
                                         let generator = WorkGenerator;
-                                        match generator.generate(&block.hash, threshold) {
+                                        match generator.generate(&block.work_root(), threshold) {
                                             Ok(nonce) => block.work = nonce.0,
                                             Err(e) => {
                                                 tracing::warn!(error = %e, "failed to generate PoW for activation block");
@@ -2279,6 +4224,7 @@ impl BurstNode {
                                         tracing::info!(
                                             %proposal_hash,
                                             params_hash = %new_params_hash,
+                                            activation_at = %certificate.activation_at,
                                             block_hash = %block.hash,
                                             "created GovernanceActivation block on genesis chain"
                                         );
@@ -2326,6 +4272,23 @@ impl BurstNode {
                                 tracing::trace!(error = %e, "failed to persist GovernanceEngine state");
                             }
                         }
+                        if let Ok(bytes) = bincode::serialize(gov.vote_times()) {
+                            if let Err(e) = store_gov.brn_store().put_meta(b"governance_vote_times", &bytes) {
+                                tracing::trace!(error = %e, "failed to persist governance vote times");
+                            }
+                        }
+                        if let Ok(bytes) = bincode::serialize(gov.dependencies()) {
+                            if let Err(e) = store_gov.brn_store().put_meta(b"governance_dependencies", &bytes) {
+                                tracing::trace!(error = %e, "failed to persist proposal dependencies");
+                            }
+                        }
+                        let mut ballots = ballots_tick.lock().await;
+                        ballots.prune(&gov);
+                        if let Ok(bytes) = bincode::serialize(&*ballots) {
+                            if let Err(e) = store_gov.brn_store().put_meta(b"governance_ballots", &bytes) {
+                                tracing::trace!(error = %e, "failed to persist sealed ballots");
+                            }
+                        }
                     }
                 }
             }
@@ -2383,6 +4346,7 @@ impl BurstNode {
         let conn_registry_drain = Arc::clone(&self.connection_registry);
         let peer_manager_drain = Arc::clone(&self.peer_manager);
         let metrics_drain = Arc::clone(&self.metrics);
+        let watchdog_drain = Arc::clone(&self.watchdog);
 
         let drain_handle = tokio::spawn(async move {
             loop {
@@ -2411,7 +4375,11 @@ impl BurstNode {
 
                         match writer {
                             Some(writer) => {
-                                if let Err(e) = write_framed(&writer, &msg_bytes).await {
+                                let written = {
+                                    let _write = watchdog_drain.start(WatchedOp::PeerWrite, "outbound_drain");
+                                    write_framed(&writer, &msg_bytes).await
+                                };
+                                if let Err(e) = written {
                                     tracing::warn!(
                                         peer = %peer_id,
                                         error = %e,
@@ -2439,57 +4407,291 @@ impl BurstNode {
                 }
             }
         });
-        self.task_handles.push(drain_handle);
+        self.task_handles.push(drain_handle);
+
+        // ── Expired TRST cleanup task — returns expired pending tokens ─────
+        let store_expiry = Arc::clone(&self.store);
+        let trst_engine_expiry = Arc::clone(&self.trst_engine);
+        let treasury_expiry = Arc::clone(&self.treasury);
+        let live_params_expiry = Arc::clone(&self.live_params);
+        let dev_controls_expiry = Arc::clone(&self.dev_controls);
+        let mut shutdown_rx_expiry = self.shutdown.subscribe();
+
+        let expiry_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(60));
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = shutdown_rx_expiry.recv() => {
+                        tracing::info!("expired TRST cleanup task shutting down");
+                        break;
+                    }
+                    _ = interval.tick() => {
+                        let now_secs = dev_controls_expiry.now_secs();
+                        let cutoff = Timestamp::new(now_secs);
+                        let expiry_secs = trst_engine_expiry.lock().await.expiry_secs;
+                        let trst_idx = store_expiry.trst_index_store();
+                        match trst_idx.get_expired_before(cutoff) {
+                            Ok(expired) if !expired.is_empty() => {
+                                tracing::info!(
+                                    count = expired.len(),
+                                    cutoff = now_secs,
+                                    expiry_secs,
+                                    "found expired TRST tokens for cleanup"
+                                );
+                                // Clean up expiry index entries. The TRST engine
+                                // uses lazy expiry checking via `is_transferable()`
+                                // at transfer time, so we don't update engine state
+                                // here. This cleanup only prevents index bloat.
+                                // Account-level expired_trst counters are updated
+                                // when transactions involving expired tokens are
+                                // rejected during block processing.
+                                for tx_hash in &expired {
+                                    if let Err(e) = trst_idx.delete_expiry_index(cutoff, tx_hash) {
+                                        tracing::warn!(token = %tx_hash, "failed to clean up expiry index: {e}");
+                                    }
+                                }
+                            }
+                            Ok(_) => {} // no expired tokens
+                            Err(e) => {
+                                tracing::warn!("failed to query expired TRST indices: {e}");
+                            }
+                        }
+                        // TRST that expired unspent goes to the treasury, once
+                        // per token.
+                        let expired = {
+                            let mut trst = trst_engine_expiry.lock().await;
+                            trst.flush_all_expired(cutoff, expiry_secs);
+                            trst.drain_expired()
+                        };
+                        if !expired.is_empty() {
+                            let mut treasury = treasury_expiry.lock().await;
+                            let credited = expired
+                                .iter()
+                                .filter(|token| {
+                                    treasury.credit(
+                                        TreasurySource::ExpiredTrst,
+                                        token.token_id,
+                                        &token.wallet,
+                                        token.amount,
+                                        cutoff,
+                                    )
+                                })
+                                .count();
+                            if credited > 0 {
+                                persist_treasury(&store_expiry, &treasury);
+                                tracing::info!(
+                                    tokens = credited,
+                                    balance = treasury.balance(),
+                                    "expired TRST credited to the treasury"
+                                );
+                            }
+                        }
+                        // Sends nobody received within the pending TTL go back
+                        // to their senders, who pocket them like any receive.
+                        // The TTL is the governed one in force now.
+                        let Some(pending_ttl_secs) =
+                            live_params_expiry.read().ok().map(|p| p.pending_ttl_secs)
+                        else {
+                            continue;
+                        };
+                        match crate::ledger_updater::return_expired_pending(
+                            &store_expiry,
+                            pending_ttl_secs,
+                            cutoff,
+                        ) {
+                            Ok(returned) => {
+                                for (destination, send_hash, info) in &returned {
+                                    tracing::info!(
+                                        %destination,
+                                        sender = %info.source,
+                                        %send_hash,
+                                        amount = info.amount,
+                                        "unreceived send returned to sender"
+                                    );
+                                }
+                            }
+                            Err(e) => {
+                                tracing::warn!("failed to return expired pending sends: {e}");
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        self.task_handles.push(expiry_handle);
+
+        // ── Verification timeout task — reselects verifiers, expires challenges ──
+        let store_vt = Arc::clone(&self.store);
+        let orch_vt = Arc::clone(&self.verification_orchestrator);
+        let vrf_vt = Arc::clone(&self.vrf_client);
+        let brn_engine_vt = Arc::clone(&self.brn_engine);
+        let ws_state_vt = Arc::clone(&self.ws_state);
+        let params_vt = self.config.params.clone();
+        let mut shutdown_rx_vt = self.shutdown.subscribe();
+
+        let verification_timeout_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(60));
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = shutdown_rx_vt.recv() => {
+                        tracing::info!("verification timeout task shutting down");
+                        break;
+                    }
+                    _ = interval.tick() => {
+                        let now = Timestamp::new(unix_now_secs());
+                        let events = {
+                            let mut orch = orch_vt.lock().await;
+                            // Expired challenges resolve in the target's favour;
+                            // their events stay queued for the block processor,
+                            // but the history behind the challenge cooldowns is
+                            // persisted right away.
+                            for event in orch.cleanup_expired_challenges(now) {
+                                if let burst_verification::VerificationEvent::ChallengeResolved {
+                                    ref outcome,
+                                    ..
+                                } = event
+                                {
+                                    persist_challenge_history(&store_vt, &orch, outcome);
+                                }
+                            }
+                            orch.check_timeouts(now, &params_vt)
+                        };
+                        for event in events {
+                            let (event_type, subject) = verification_event_kind(&event);
+                            ws_state_vt.publish_verification(
+                                event_type,
+                                subject.as_str(),
+                                subject.as_str(),
+                            );
+                            match event {
+                                burst_verification::VerificationEvent::VerifierPenalized {
+                                    ref verifier,
+                                    cooldown_until,
+                                    ..
+                                } => {
+                                    tracing::warn!(
+                                        %verifier,
+                                        cooldown_until,
+                                        "verifier missed the voting deadline — excluded from future selection"
+                                    );
+                                }
+                                burst_verification::VerificationEvent::VerifiersTimedOut {
+                                    ref wallet,
+                                    ref unresponsive,
+                                } => {
+                                    tracing::info!(
+                                        %wallet,
+                                        unresponsive = unresponsive.len(),
+                                        "verification round timed out, drawing replacements"
+                                    );
+                                    draw_verifiers_via_vrf(
+                                        &vrf_vt,
+                                        &store_vt,
+                                        &orch_vt,
+                                        wallet,
+                                        &params_vt,
+                                        true,
+                                    )
+                                    .await;
+                                }
+                                burst_verification::VerificationEvent::VerificationExpired {
+                                    ref wallet,
+                                    ref refunds,
+                                    ref state,
+                                } => {
+                                    tracing::warn!(
+                                        %wallet,
+                                        refunds = refunds.len(),
+                                        "verification expired — wallet back to Unverified"
+                                    );
+                                    persist_verification_record(
+                                        &store_vt,
+                                        state,
+                                        burst_store::VerificationRecordOutcome::Expired,
+                                    );
+                                    let mut brn_inner = brn_engine_vt.lock().await;
+                                    for (verifier, staked) in refunds {
+                                        match brn_inner.get_wallet_mut(verifier) {
+                                            Some(ws) => {
+                                                ws.total_staked =
+                                                    ws.total_staked.saturating_sub(*staked);
+                                            }
+                                            None => tracing::warn!(
+                                                %verifier,
+                                                "verifier wallet not tracked in BRN engine, cannot refund stake"
+                                            ),
+                                        }
+                                    }
+                                    drop(brn_inner);
+                                    transition_stored_account(
+                                        &store_vt,
+                                        wallet,
+                                        burst_types::WalletState::Unverified,
+                                        burst_types::TransitionReason::VerificationExpired,
+                                    );
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+            }
+        });
+        self.task_handles.push(verification_timeout_handle);
 
-        // ── Expired TRST cleanup task — returns expired pending tokens ─────
-        let store_expiry = Arc::clone(&self.store);
-        let trst_expiry_bg = self.config.params.trst_expiry_secs;
-        let mut shutdown_rx_expiry = self.shutdown.subscribe();
+        // ── Inactivity task — warns idle wallets, suspends their BRN accrual ──
+        let brn_engine_ia = Arc::clone(&self.brn_engine);
+        let ws_state_ia = Arc::clone(&self.ws_state);
+        let live_params_ia = Arc::clone(&self.live_params);
+        let dev_controls_ia = Arc::clone(&self.dev_controls);
+        let mut shutdown_rx_ia = self.shutdown.subscribe();
 
-        let expiry_handle = tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(60));
+        let inactivity_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(3600));
             loop {
                 tokio::select! {
                     biased;
-                    _ = shutdown_rx_expiry.recv() => {
-                        tracing::info!("expired TRST cleanup task shutting down");
+                    _ = shutdown_rx_ia.recv() => {
+                        tracing::info!("inactivity task shutting down");
                         break;
                     }
                     _ = interval.tick() => {
-                        let now_secs = unix_now_secs();
-                        let cutoff = Timestamp::new(now_secs);
-                        let trst_idx = store_expiry.trst_index_store();
-                        match trst_idx.get_expired_before(cutoff) {
-                            Ok(expired) if !expired.is_empty() => {
-                                tracing::info!(
-                                    count = expired.len(),
-                                    cutoff = now_secs,
-                                    expiry_secs = trst_expiry_bg,
-                                    "found expired TRST tokens for cleanup"
-                                );
-                                // Clean up expiry index entries. The TRST engine
-                                // uses lazy expiry checking via `is_transferable()`
-                                // at transfer time, so we don't update engine state
-                                // here. This cleanup only prevents index bloat.
-                                // Account-level expired_trst counters are updated
-                                // when transactions involving expired tokens are
-                                // rejected during block processing.
-                                for tx_hash in &expired {
-                                    if let Err(e) = trst_idx.delete_expiry_index(cutoff, tx_hash) {
-                                        tracing::warn!(token = %tx_hash, "failed to clean up expiry index: {e}");
-                                    }
+                        // Suspension takes effect at the deadline derived from
+                        // the last block, not at this tick, so nodes agree on
+                        // accrual however late they run the check.
+                        let Some((suspension_secs, grace_secs)) = live_params_ia
+                            .read()
+                            .ok()
+                            .map(|p| (p.inactivity_suspension_secs, p.inactivity_grace_secs))
+                        else {
+                            continue;
+                        };
+                        // Dated on the same clock as block economics.
+                        let now = dev_controls_ia.now();
+                        let events = brn_engine_ia.lock().await.check_inactivity(
+                            now,
+                            suspension_secs,
+                            grace_secs,
+                        );
+                        for event in &events {
+                            match event {
+                                burst_brn::InactivityEvent::GracePeriodStarted { wallet, suspends_at } => {
+                                    tracing::info!(%wallet, suspends_at = suspends_at.as_secs(), "wallet inactive — grace period started");
                                 }
+                                burst_brn::InactivityEvent::AccrualSuspended { wallet, at } => {
+                                    tracing::info!(%wallet, at = at.as_secs(), "BRN accrual suspended for inactivity");
+                                }
+                                burst_brn::InactivityEvent::AccrualResumed { .. } => {}
                             }
-                            Ok(_) => {} // no expired tokens
-                            Err(e) => {
-                                tracing::warn!("failed to query expired TRST indices: {e}");
-                            }
+                            publish_inactivity_event(&ws_state_ia, event);
                         }
                     }
                 }
             }
         });
-        self.task_handles.push(expiry_handle);
+        self.task_handles.push(inactivity_handle);
 
         // ── Pruning task — periodically removes expired/revoked TRST history ──
         let store_prune = Arc::clone(&self.store);
@@ -2573,6 +4775,7 @@ impl BurstNode {
         // ── Priority scheduler drain — creates elections for highest-priority blocks ──
         let priority_scheduler_drain = Arc::clone(&self.priority_scheduler);
         let active_elections_sched = Arc::clone(&self.active_elections);
+        let vote_cache_sched = Arc::clone(&self.vote_cache);
         let mut shutdown_rx_sched = self.shutdown.subscribe();
 
         let scheduler_handle = tokio::spawn(async move {
@@ -2587,7 +4790,8 @@ impl BurstNode {
                     _ = interval.tick() => {
                         let mut sched = priority_scheduler_drain.lock().await;
                         let ae = active_elections_sched.read().await;
-                        let capacity = MAX_ACTIVE_ELECTIONS.saturating_sub(ae.election_count());
+                        sched.release_finished(|root| ae.get_election(root).is_some());
+                        let capacity = ae.max_elections().saturating_sub(ae.election_count());
                         drop(ae);
 
                         let mut started = 0usize;
@@ -2597,6 +4801,10 @@ impl BurstNode {
                                     let now = Timestamp::new(unix_now_secs());
                                     let mut ae = active_elections_sched.write().await;
                                     if ae.start_election(hash, now).is_ok() {
+                                        vote_cache_sched
+                                            .write()
+                                            .await
+                                            .replay_into(&hash, &mut ae, now);
                                         started += 1;
                                     }
                                 }
@@ -2612,6 +4820,297 @@ impl BurstNode {
         });
         self.task_handles.push(scheduler_handle);
 
+        // ── Active election limit tuning ─────────────────────────────────────
+        let election_tuner_bg = Arc::clone(&self.election_tuner);
+        let active_elections_tune = Arc::clone(&self.active_elections);
+        let priority_scheduler_tune = Arc::clone(&self.priority_scheduler);
+        let metrics_tune = Arc::clone(&self.metrics);
+        let mut shutdown_rx_tune = self.shutdown.subscribe();
+        metrics_tune
+            .active_elections_limit
+            .set(self.election_tuner.lock().await.limit() as i64);
+
+        let tuner_handle = tokio::spawn(async move {
+            const TUNE_INTERVAL: Duration = Duration::from_secs(5);
+            let mut interval = tokio::time::interval(TUNE_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = shutdown_rx_tune.recv() => {
+                        tracing::debug!("election limit tuner shutting down");
+                        break;
+                    }
+                    scheduled = interval.tick() => {
+                        // How late the tick fired — a runtime starved of CPU
+                        // falls behind its timers.
+                        let lag_ms = tokio::time::Instant::now()
+                            .saturating_duration_since(scheduled)
+                            .as_millis() as u64;
+                        let backlog = metrics_tune.vote_backlog.get().max(0) as usize;
+                        let (previous, limit) = {
+                            let mut tuner = election_tuner_bg.lock().await;
+                            let previous = tuner.limit();
+                            (previous, tuner.adjust(backlog, lag_ms))
+                        };
+                        if limit != previous {
+                            active_elections_tune.write().await.set_max_elections(limit);
+                            priority_scheduler_tune.lock().await.set_max_elections(limit);
+                            tracing::info!(
+                                previous,
+                                limit,
+                                backlog,
+                                lag_ms,
+                                "active election limit adjusted"
+                            );
+                        }
+                        metrics_tune.active_elections_limit.set(limit as i64);
+                    }
+                }
+            }
+        });
+        self.task_handles.push(tuner_handle);
+
+        // ── Overload control — degrade gracefully instead of queueing ─────
+        let overload_ol = Arc::clone(&self.overload);
+        let block_queue_ol = Arc::clone(&self.block_queue);
+        let active_elections_ol = Arc::clone(&self.active_elections);
+        let prefilter_ol = Arc::clone(&self.block_prefilter);
+        let inbound_ol = Arc::clone(&self.inbound_queue);
+        let difficulty_adjuster_ol = Arc::clone(&self.difficulty_adjuster);
+        let block_processor_ol = Arc::clone(&self.block_processor);
+        let metrics_ol = Arc::clone(&self.metrics);
+        let mut shutdown_rx_ol = self.shutdown.subscribe();
+
+        let overload_handle = tokio::spawn(async move {
+            const OVERLOAD_INTERVAL: Duration = Duration::from_secs(1);
+            let mut interval = tokio::time::interval(OVERLOAD_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = shutdown_rx_ol.recv() => {
+                        tracing::debug!("overload controller shutting down");
+                        break;
+                    }
+                    _ = interval.tick() => {
+                        let (active_elections, election_limit) = {
+                            let elections = active_elections_ol.read().await;
+                            (elections.election_count(), elections.max_elections())
+                        };
+                        let sample = LoadSample {
+                            block_queue_len: block_queue_ol.len().await,
+                            block_queue_capacity: block_queue_ol.capacity(),
+                            active_elections,
+                            election_limit,
+                        };
+                        let state_change = overload_ol.evaluate(&sample);
+                        // Work thresholds: the network load multiplier
+                        // (which falls only after a hold) applies in the
+                        // block processor; the edge also demands the extra
+                        // asked while this node is overloaded.
+                        let network_multiplier =
+                            difficulty_adjuster_ol.lock().await.refresh(unix_now_secs());
+                        metrics_ol.work_network_multiplier.set(network_multiplier as i64);
+                        block_processor_ol
+                            .lock()
+                            .await
+                            .set_work_multiplier(network_multiplier);
+                        prefilter_ol
+                            .lock()
+                            .await
+                            .set_work_multiplier(overload_ol.work_multiplier() * network_multiplier as f64);
+                        let Some((overloaded, causes)) = state_change else {
+                            continue;
+                        };
+                        inbound_ol.set_shedding(overloaded).await;
+                        if overloaded {
+                            let causes: Vec<&str> = causes.iter().map(|c| c.as_str()).collect();
+                            tracing::warn!(
+                                ?causes,
+                                block_queue = sample.block_queue_len,
+                                active_elections,
+                                commit_latency_ms = overload_ol.commit_latency_ms(),
+                                "node overloaded: raising work threshold and shedding low-priority traffic"
+                            );
+                        } else {
+                            tracing::info!("node load back to normal");
+                        }
+                    }
+                }
+            }
+        });
+        self.task_handles.push(overload_handle);
+
+        // ── Storage guard — stop writing before the disk or map fills ─────
+        if self.config.storage.enabled {
+            let storage_guard_sg = Arc::clone(&self.storage_guard);
+            let store_sg = Arc::clone(&self.store);
+            let metrics_sg = Arc::clone(&self.metrics);
+            let data_dir_sg = self.config.data_dir.clone();
+            let interval_secs = self.config.storage.check_interval_secs.max(1);
+            let mut shutdown_rx_sg = self.shutdown.subscribe();
+
+            let storage_handle = tokio::spawn(async move {
+                let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+                interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                loop {
+                    tokio::select! {
+                        biased;
+                        _ = shutdown_rx_sg.recv() => {
+                            tracing::debug!("storage guard shutting down");
+                            break;
+                        }
+                        _ = interval.tick() => {
+                            let mut map = match store_sg.map_usage() {
+                                Ok(map) => map,
+                                Err(e) => {
+                                    tracing::warn!("failed to read LMDB map usage: {e}");
+                                    continue;
+                                }
+                            };
+                            // Grow the map between write batches before it
+                            // counts against the read-only threshold.
+                            if let Some(new_size) = storage_guard_sg.grow_target(&map) {
+                                let store = Arc::clone(&store_sg);
+                                let grown = tokio::task::spawn_blocking(move || {
+                                    store.grow_map(new_size, MAP_GROW_WAIT)
+                                })
+                                .await;
+                                match grown {
+                                    Ok(Ok(true)) => {
+                                        tracing::info!(
+                                            from_mb = map.map_size >> 20,
+                                            to_mb = new_size >> 20,
+                                            "grew LMDB map"
+                                        );
+                                        map.map_size = new_size as u64;
+                                    }
+                                    Ok(Ok(false)) => tracing::debug!(
+                                        "LMDB map growth deferred: transactions still open"
+                                    ),
+                                    Ok(Err(e)) => tracing::warn!("failed to grow LMDB map: {e}"),
+                                    Err(e) => tracing::warn!("LMDB map growth task failed: {e}"),
+                                }
+                            }
+                            let sample = StorageSample {
+                                map,
+                                disk_free_percent: disk_free_percent(&data_dir_sg),
+                            };
+                            metrics_sg.lmdb_map_size_bytes.set(map.map_size as i64);
+                            metrics_sg.lmdb_map_used_bytes.set(map.used as i64);
+                            let Some(level) = storage_guard_sg.evaluate(&sample) else {
+                                continue;
+                            };
+                            metrics_sg
+                                .storage_read_only
+                                .set((level == StorageLevel::ReadOnly) as i64);
+                            let map_used_percent = format!("{:.1}", map.used_percent());
+                            let disk_free_percent =
+                                sample.disk_free_percent.map(|free| format!("{free:.1}"));
+                            match level {
+                                StorageLevel::ReadOnly => tracing::error!(
+                                    map_used_percent,
+                                    ?disk_free_percent,
+                                    "storage nearly exhausted: node is read-only, no new blocks are written"
+                                ),
+                                StorageLevel::Low => tracing::warn!(
+                                    map_used_percent,
+                                    ?disk_free_percent,
+                                    "storage running low"
+                                ),
+                                StorageLevel::Normal => {
+                                    tracing::info!(map_used_percent, "storage back to normal")
+                                }
+                            }
+                        }
+                    }
+                }
+            });
+            self.task_handles.push(storage_handle);
+        }
+
+        // ── Backlog scan — re-schedules uncemented blocks whose elections lapsed ──
+        let store_scan = Arc::clone(&self.store);
+        let active_elections_scan = Arc::clone(&self.active_elections);
+        let recently_confirmed_scan = Arc::clone(&self.recently_confirmed);
+        let priority_scheduler_scan = Arc::clone(&self.priority_scheduler);
+        let mut shutdown_rx_scan = self.shutdown.subscribe();
+
+        let backlog_scan_handle = tokio::spawn(async move {
+            let mut scan = BacklogScan::new(
+                DEFAULT_ACCOUNTS_PER_PASS,
+                DEFAULT_MAX_SCHEDULED_PER_PASS,
+                ELECTION_LIFETIME_MS / 1000,
+            );
+            let mut interval = tokio::time::interval(BACKLOG_SCAN_INTERVAL);
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = shutdown_rx_scan.recv() => {
+                        tracing::debug!("backlog scan task shutting down");
+                        break;
+                    }
+                    _ = interval.tick() => {
+                        let ae = active_elections_scan.read().await;
+                        let rc = recently_confirmed_scan.read().await;
+                        let pass = scan.next_pass(
+                            &store_scan.account_store(),
+                            &store_scan.block_store(),
+                            unix_now_secs(),
+                            |hash| ae.get_election(hash).is_some() || rc.contains(hash),
+                        );
+                        drop(rc);
+                        drop(ae);
+                        match pass {
+                            Ok(candidates) if !candidates.is_empty() => {
+                                let count = candidates.len();
+                                let mut sched = priority_scheduler_scan.lock().await;
+                                for c in candidates {
+                                    sched.push(c.hash, c.account, c.balance);
+                                }
+                                tracing::debug!(count, "backlog scan re-scheduled blocks");
+                            }
+                            Ok(_) => {}
+                            Err(e) => tracing::warn!("backlog scan failed: {e}"),
+                        }
+                    }
+                }
+            }
+        });
+        self.task_handles.push(backlog_scan_handle);
+
+        // ── Rep weight check — corrects drift between the weight cache and accounts ──
+        let store_rw = Arc::clone(&self.store);
+        let rep_weights_rw = Arc::clone(&self.rep_weights);
+        let mut shutdown_rx_rw = self.shutdown.subscribe();
+
+        let rep_weight_check_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(REP_WEIGHT_CHECK_INTERVAL);
+            interval.tick().await; // skip the immediate first tick; weights load at startup
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = shutdown_rx_rw.recv() => {
+                        tracing::debug!("rep weight check task shutting down");
+                        break;
+                    }
+                    _ = interval.tick() => {
+                        // Hold the cache across the scan so no block lands in between.
+                        let mut rw = rep_weights_rw.write().await;
+                        if let Err(e) = burst_rpc::handlers::recalculate_rep_weights(
+                            &mut rw,
+                            &store_rw.account_store(),
+                            &store_rw.rep_weight_store(),
+                        ) {
+                            tracing::warn!("rep weight check failed: {e}");
+                        }
+                    }
+                }
+            }
+        });
+        self.task_handles.push(rep_weight_check_handle);
+
         // ── Online weight periodic sampling ──────────────────────────────────
         let online_weight_tracker_bg = Arc::clone(&self.online_weight_tracker);
         let online_weight_sampler_bg = Arc::clone(&self.online_weight_sampler);
@@ -2662,9 +5161,16 @@ impl BurstNode {
                             "online weight sample recorded"
                         );
 
-                        // Persist the online weight sample to LMDB
+                        // Persist the sample to LMDB for `online_weight_history`
+                        let sample = burst_store::WeightSample {
+                            timestamp: now_secs,
+                            online: total_online,
+                            trended: Some(tracker.trended_weight()),
+                            quorum_delta: Some(tracker.quorum_delta()),
+                        };
+                        drop(tracker);
                         let rw_store = store_ow.rep_weight_store();
-                        if let Err(e) = rw_store.put_online_weight_sample(now_secs, total_online) {
+                        if let Err(e) = rw_store.put_weight_sample(&sample) {
                             tracing::warn!(error = %e, "failed to persist online weight sample");
                         }
                     }
@@ -2738,39 +5244,52 @@ impl BurstNode {
             *f = new_frontier;
         }
 
-        // Restore the merger graph from LMDB if a previous snapshot exists.
+        // Back the merger graph with its LMDB tables, migrating any
+        // whole-graph snapshot left by an earlier version.
+        {
+            let mut trst = self.trst_engine.lock().await;
+            match crate::merger_graph::attach_merger_graph_store(
+                &self.store,
+                &mut trst.merger_graph,
+            ) {
+                Ok(revoked) => {
+                    tracing::info!(revoked_origins = revoked, "merger graph attached to LMDB");
+                }
+                Err(e) => {
+                    tracing::warn!("failed to attach merger graph store: {e}");
+                }
+            }
+        }
+
+        // Restore the confirmation history from the last snapshot.
         {
             let meta = self.store.meta_store();
-            match meta.get_meta(MERGER_GRAPH_META_KEY) {
-                Ok(bytes) => match burst_trst::MergerGraph::from_bytes(&bytes) {
-                    Ok(graph) => {
-                        let mut trst = self.trst_engine.lock().await;
-                        trst.merger_graph = graph;
-                        tracing::info!("restored merger graph from LMDB");
+            if let Ok(bytes) = meta.get_meta(CONFIRMATION_HISTORY_META_KEY) {
+                let capacity = self.config.confirmation_history_size;
+                match ConfirmationHistory::from_bytes(&bytes, capacity) {
+                    Ok(history) => {
+                        tracing::info!(entries = history.len(), "restored confirmation history");
+                        *self.confirmation_history.lock().await = history;
                     }
                     Err(e) => {
-                        tracing::warn!("failed to deserialize merger graph, starting fresh: {e}");
+                        tracing::warn!(
+                            "failed to deserialize confirmation history, starting fresh: {e}"
+                        );
                     }
-                },
-                Err(_) => {
-                    tracing::info!("no persisted merger graph found — starting fresh");
                 }
             }
         }
 
-        // Restore TRST engine per-wallet token portfolios from LMDB.
+        // Page TRST portfolios from LMDB, migrating any whole-engine
+        // snapshot left by an earlier version.
         {
-            let meta = self.store.meta_store();
-            match meta.get_meta(TrstEngine::meta_key()) {
-                Ok(bytes) => {
-                    let mut trst = self.trst_engine.lock().await;
-                    let restored =
-                        TrstEngine::load_wallets(&bytes, self.config.params.trst_expiry_secs);
-                    trst.wallets = restored.wallets;
-                    tracing::info!("TRST engine wallet portfolios restored from LMDB");
+            let mut trst = self.trst_engine.lock().await;
+            match crate::trst_portfolios::attach_portfolio_store(&self.store, &mut trst) {
+                Ok(wallets) => {
+                    tracing::info!(wallets, "TRST portfolios attached to LMDB");
                 }
-                Err(_) => {
-                    tracing::info!("no persisted TRST wallet portfolios — starting fresh");
+                Err(e) => {
+                    tracing::warn!("failed to attach TRST portfolio store: {e}");
                 }
             }
         }
@@ -2909,26 +5428,63 @@ impl BurstNode {
         if let Some(addr) = external_addr {
             let mut pm = self.peer_manager.write().await;
             pm.set_external_address(addr);
-            tracing::info!(
-                advertise = %addr,
-                "advertise address set (cloud VPS / no UPnP)"
-            );
+            if pm.self_peer_address().is_some() {
+                tracing::info!(
+                    advertise = %addr,
+                    "advertise address set (cloud VPS / no UPnP)"
+                );
+            } else {
+                tracing::warn!(
+                    advertise = %addr,
+                    "advertise address is not publicly routable; not advertising it"
+                );
+            }
         }
 
+        // ── Inbound processing workers ────────────────────────────────────
+        let inbound_ctx = InboundContext {
+            block_queue: Arc::clone(&self.block_queue),
+            active_elections: Arc::clone(&self.active_elections),
+            vote_cache: Arc::clone(&self.vote_cache),
+            rep_weights: Arc::clone(&self.rep_weights),
+            peer_manager: Arc::clone(&self.peer_manager),
+            online_weight_sampler: Arc::clone(&self.online_weight_sampler),
+            rep_crawler: Arc::clone(&self.rep_crawler),
+            connection_registry: Arc::clone(&self.connection_registry),
+            frontier: Arc::clone(&self.frontier),
+            store: Arc::clone(&self.store),
+            metrics: Arc::clone(&self.metrics),
+            our_params_hash: self.config.params.params_hash(),
+            overload: Arc::clone(&self.overload),
+            trst_engine: Arc::clone(&self.trst_engine),
+            online_weight_tracker: Arc::clone(&self.online_weight_tracker),
+            broadcaster: self.broadcaster.clone(),
+            gossip: self.config.gossip,
+            message_dedup: Arc::clone(&self.message_dedup),
+            mailbox: Arc::clone(&self.mailbox),
+            state_digests: Arc::clone(&self.state_digests),
+            difficulty_adjuster: Arc::clone(&self.difficulty_adjuster),
+        };
+        self.task_handles.extend(spawn_inbound_workers(
+            inbound_ctx,
+            Arc::clone(&self.inbound_queue),
+            INBOUND_WORKERS,
+            &self.shutdown,
+        ));
+
         // ── P2P TCP listener ──────────────────────────────────────────────
         let p2p_port = self.config.port;
         let peer_manager = Arc::clone(&self.peer_manager);
         let mut shutdown_rx_p2p = self.shutdown.subscribe();
         let metrics_p2p = Arc::clone(&self.metrics);
         let conn_registry_p2p = Arc::clone(&self.connection_registry);
-        let block_queue_p2p = Arc::clone(&self.block_queue);
-        let active_elections_p2p = Arc::clone(&self.active_elections);
-        let rep_weights_p2p = Arc::clone(&self.rep_weights);
         let syn_cookies_p2p = Arc::clone(&self.syn_cookies);
         let message_dedup_p2p = Arc::clone(&self.message_dedup);
-        let online_weight_sampler_p2p = Arc::clone(&self.online_weight_sampler);
-        let frontier_p2p = Arc::clone(&self.frontier);
-        let store_p2p = Arc::clone(&self.store);
+        let inbound_p2p = Arc::clone(&self.inbound_queue);
+        let prefilter_p2p = Arc::clone(&self.block_prefilter);
+        let rep_weights_p2p = Arc::clone(&self.rep_weights);
+        let vote_admission_p2p = Arc::clone(&self.vote_admission);
+        let read_buffers_p2p = Arc::clone(&self.read_buffers);
         let node_address_p2p = self.node_address.clone();
         let config_params_p2p = self.config.params.clone();
 
@@ -2963,6 +5519,16 @@ impl BurstNode {
                                 };
                                 let peer_id = format!("{}:{}", addr.ip(), addr.port());
 
+                                let refusal = peer_manager.read().await.check_connection(&peer_ip);
+                                if let Err(refusal) = refusal {
+                                    tracing::debug!(
+                                        peer = %peer_id,
+                                        reason = refusal.as_str(),
+                                        "refused inbound connection"
+                                    );
+                                    continue;
+                                }
+
                                 // One connection per IP: disconnect any existing connection
                                 // from this IP to avoid overcounting reconnects (same node,
                                 // different ephemeral ports).
@@ -3054,19 +5620,17 @@ impl BurstNode {
                                 spawn_peer_read_loop(
                                     peer_id.clone(),
                                     read_half,
-                                    Arc::clone(&block_queue_p2p),
                                     Arc::clone(&conn_registry_p2p),
                                     Arc::clone(&peer_manager),
                                     Arc::clone(&metrics_p2p),
-                                    Arc::clone(&active_elections_p2p),
-                                    Arc::clone(&rep_weights_p2p),
                                     Arc::clone(&message_dedup_p2p),
-                                    Arc::clone(&online_weight_sampler_p2p),
+                                    Arc::clone(&inbound_p2p),
+                                    Arc::clone(&prefilter_p2p),
+                                    Arc::clone(&rep_weights_p2p),
+                                    Arc::clone(&vote_admission_p2p),
                                     Some(Arc::clone(&syn_cookies_p2p)),
                                     peer_ip,
-                                    Arc::clone(&frontier_p2p),
-                                    Arc::clone(&store_p2p),
-                                    config_params_p2p.params_hash(),
+                                    Arc::clone(&read_buffers_p2p),
                                 );
 
                                 tracing::info!(peer = %peer_id, "inbound peer connected");
@@ -3130,29 +5694,60 @@ impl BurstNode {
             tracing::debug!("UPnP: disabled by configuration");
         }
 
-        // ── Peer cache loader — reconnect to previously known peers ───────
+        // ── Peer cache loader — reconnect to the best previously known peers ──
+        // The bootstrap task waits on `cache_done` so seeds are only a
+        // fallback when cached peers can't be reached.
+        let (cache_done_tx, cache_done_rx) = tokio::sync::oneshot::channel::<()>();
         {
             use burst_store::peer::PeerStore;
 
             let peer_store = self.store.peer_store();
-            match peer_store.iter_peers() {
+            match peer_store.iter_peer_records() {
                 Ok(cached) if !cached.is_empty() => {
                     tracing::info!(
                         count = cached.len(),
                         "peer cache: loaded cached peers from LMDB"
                     );
 
+                    let mut ranked: Vec<(String, PeerQuality)> = Vec::with_capacity(cached.len());
+                    {
+                        let mut pm = self.peer_manager.write().await;
+                        for (addr, record) in cached {
+                            let Some((ip, port)) = addr.rsplit_once(':') else {
+                                continue;
+                            };
+                            let Ok(port) = port.parse::<u16>() else {
+                                continue;
+                            };
+                            let quality = PeerQuality {
+                                uptime_secs: record.uptime_secs,
+                                handshake_attempts: record.handshake_attempts,
+                                handshake_successes: record.handshake_successes,
+                                useful_votes: record.useful_votes,
+                            };
+                            pm.restore_peer(
+                                PeerAddress {
+                                    ip: ip.to_string(),
+                                    port,
+                                },
+                                record.last_seen,
+                                quality,
+                            );
+                            ranked.push((addr, quality));
+                        }
+                    }
+                    ranked.sort_by_key(|(_, quality)| std::cmp::Reverse(quality.score()));
+
                     let cache_ctx = crate::peer_connector::PeerConnectorContext {
                         peer_manager: Arc::clone(&self.peer_manager),
                         connection_registry: Arc::clone(&self.connection_registry),
-                        block_queue: Arc::clone(&self.block_queue),
                         metrics: Arc::clone(&self.metrics),
-                        active_elections: Arc::clone(&self.active_elections),
-                        rep_weights: Arc::clone(&self.rep_weights),
                         message_dedup: Arc::clone(&self.message_dedup),
-                        online_weight_sampler: Arc::clone(&self.online_weight_sampler),
-                        frontier: Arc::clone(&self.frontier),
-                        store: Arc::clone(&self.store),
+                        inbound: Arc::clone(&self.inbound_queue),
+                        block_prefilter: Arc::clone(&self.block_prefilter),
+                        rep_weights: Arc::clone(&self.rep_weights),
+                        vote_admission: Arc::clone(&self.vote_admission),
+                        read_buffers: Arc::clone(&self.read_buffers),
                         node_private_key: burst_types::PrivateKey(self.node_private_key.0),
                         node_address: self.node_address.clone(),
                         params_hash: self.config.params.params_hash(),
@@ -3160,7 +5755,12 @@ impl BurstNode {
                     let mut shutdown_rx_cache = self.shutdown.subscribe();
 
                     let cache_handle = tokio::spawn(async move {
-                        for (addr, _ts) in cached {
+                        for (addr, quality) in ranked {
+                            if cache_ctx.peer_manager.read().await.connected_count()
+                                >= PEER_CACHE_RECONNECT_TARGET
+                            {
+                                break;
+                            }
                             if crate::peer_connector::is_peer_connected(
                                 &addr,
                                 &cache_ctx.peer_manager,
@@ -3175,7 +5775,11 @@ impl BurstNode {
                                 break;
                             }
 
-                            tracing::debug!(peer = %addr, "peer cache: connecting to cached peer");
+                            tracing::debug!(
+                                peer = %addr,
+                                score = quality.score(),
+                                "peer cache: connecting to cached peer"
+                            );
                             match crate::peer_connector::connect_to_peer(&addr, &cache_ctx).await {
                                 Ok(connected) => {
                                     tracing::info!(
@@ -3194,6 +5798,7 @@ impl BurstNode {
 
                             tokio::time::sleep(Duration::from_millis(500)).await;
                         }
+                        let _ = cache_done_tx.send(());
                     });
                     self.task_handles.push(cache_handle);
                 }
@@ -3216,14 +5821,13 @@ impl BurstNode {
             let bs_ctx = crate::peer_connector::PeerConnectorContext {
                 peer_manager: Arc::clone(&self.peer_manager),
                 connection_registry: Arc::clone(&self.connection_registry),
-                block_queue: Arc::clone(&self.block_queue),
                 metrics: Arc::clone(&self.metrics),
-                active_elections: Arc::clone(&self.active_elections),
-                rep_weights: Arc::clone(&self.rep_weights),
                 message_dedup: Arc::clone(&self.message_dedup),
-                online_weight_sampler: Arc::clone(&self.online_weight_sampler),
-                frontier: Arc::clone(&self.frontier),
-                store: Arc::clone(&self.store),
+                inbound: Arc::clone(&self.inbound_queue),
+                block_prefilter: Arc::clone(&self.block_prefilter),
+                rep_weights: Arc::clone(&self.rep_weights),
+                vote_admission: Arc::clone(&self.vote_admission),
+                read_buffers: Arc::clone(&self.read_buffers),
                 node_private_key: burst_types::PrivateKey(self.node_private_key.0),
                 node_address: self.node_address.clone(),
                 params_hash: self.config.params.params_hash(),
@@ -3234,8 +5838,15 @@ impl BurstNode {
             let mut shutdown_rx_bs = self.shutdown.subscribe();
 
             let bs_handle = tokio::spawn(async move {
+                // Let the peer cache loader go first; a dropped sender means
+                // it had nothing to do.
+                let _ = cache_done_rx.await;
                 loop {
-                    for addr_str in &bootstrap_peers {
+                    let seeds_needed = bs_ctx.peer_manager.read().await.connected_count()
+                        < SEED_FALLBACK_MIN_PEERS
+                        || frontier_bs.read().await.account_count() == 0;
+                    let seeds: &[String] = if seeds_needed { &bootstrap_peers } else { &[] };
+                    for addr_str in seeds {
                         if crate::peer_connector::is_peer_connected(addr_str, &bs_ctx.peer_manager)
                             .await
                         {
@@ -3410,56 +6021,171 @@ impl BurstNode {
                                 },
                             );
 
-                            let self_bytes = bincode::serialize(&self_msg).ok();
-                            let random_bytes = bincode::serialize(&random_msg).ok();
+                            let self_bytes = bincode::serialize(&self_msg).ok();
+                            let random_bytes = bincode::serialize(&random_msg).ok();
+
+                            // Send self-keepalive to ~25% of peers,
+                            // random-keepalive to the remaining ~75%.
+                            for (i, pid) in peer_ids.iter().enumerate() {
+                                let bytes = if i % 4 == (round as usize % 4) {
+                                    &self_bytes
+                                } else {
+                                    &random_bytes
+                                };
+                                if let Some(ref payload) = bytes {
+                                    let registry = conn_registry_ka.read().await;
+                                    if let Some(writer) = registry.get(pid) {
+                                        if let Err(e) = write_framed(&writer, payload).await {
+                                            tracing::debug!(
+                                                peer = %pid,
+                                                error = %e,
+                                                "keepalive send failed"
+                                            );
+                                        }
+                                    }
+                                }
+                            }
+
+                            tracing::trace!(
+                                connected = pm.connected_count(),
+                                round = round,
+                                "keepalive round"
+                            );
+                        }
+                    }
+                }
+            }
+        });
+        self.task_handles.push(ka_handle);
+
+        // ── Representative crawler ────────────────────────────────────────
+        // Periodically asks connected peers to vote on a recently confirmed
+        // block. Responses (handled in the peer read loop) reveal which peers
+        // are representatives and how quickly they answer.
+        let rep_crawler_rc = Arc::clone(&self.rep_crawler);
+        let recently_confirmed_rc = Arc::clone(&self.recently_confirmed);
+        let conn_registry_rc = Arc::clone(&self.connection_registry);
+        let mut shutdown_rx_rc = self.shutdown.subscribe();
+
+        let rc_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(5));
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = shutdown_rx_rc.recv() => {
+                        tracing::debug!("rep crawler task shutting down");
+                        break;
+                    }
+                    _ = interval.tick() => {
+                        let now = unix_now_ms();
+                        let mut crawler = rep_crawler_rc.lock().await;
+                        crawler.cleanup_expired(now);
+                        if !crawler.should_crawl(now) {
+                            continue;
+                        }
+                        let Some(hash) = recently_confirmed_rc.read().await.latest() else {
+                            continue;
+                        };
+                        let peer_ids: Vec<String> = {
+                            let registry = conn_registry_rc.read().await;
+                            registry.peer_ids().into_iter().cloned().collect()
+                        };
+                        if peer_ids.is_empty() {
+                            continue;
+                        }
+                        crawler.start_crawl(hash, &peer_ids, now);
+                        drop(crawler);
+
+                        let req = WireMessage::ConfirmReq(crate::wire_message::ConfirmReqMsg {
+                            block_hashes: vec![hash],
+                        });
+                        let Ok(bytes) = bincode::serialize(&req) else {
+                            continue;
+                        };
+                        let registry = conn_registry_rc.read().await;
+                        for pid in &peer_ids {
+                            if let Some(writer) = registry.get(pid) {
+                                if let Err(e) = write_framed(&writer, &bytes).await {
+                                    tracing::debug!(
+                                        peer = %pid,
+                                        error = %e,
+                                        "rep crawl confirm_req send failed"
+                                    );
+                                }
+                            }
+                        }
+                        tracing::trace!(peers = peer_ids.len(), "rep crawl round");
+                    }
+                }
+            }
+        });
+        self.task_handles.push(rc_handle);
+
+        // ── Peer probe — vet addresses shared in keepalives ───────────────
+        // A random sample of the unverified addresses is probed each round;
+        // those that answer with our network's handshake become candidates
+        // for the reachout loop.
+        {
+            let peer_manager_pp = Arc::clone(&self.peer_manager);
+            let params_hash_pp = self.config.params.params_hash();
+            let mut shutdown_rx_pp = self.shutdown.subscribe();
 
-                            // Send self-keepalive to ~25% of peers,
-                            // random-keepalive to the remaining ~75%.
-                            for (i, pid) in peer_ids.iter().enumerate() {
-                                let bytes = if i % 4 == (round as usize % 4) {
-                                    &self_bytes
-                                } else {
-                                    &random_bytes
+            let pp_handle = tokio::spawn(async move {
+                let mut interval = tokio::time::interval(PEER_PROBE_INTERVAL);
+                loop {
+                    tokio::select! {
+                        biased;
+                        _ = shutdown_rx_pp.recv() => {
+                            tracing::debug!("peer probe task shutting down");
+                            break;
+                        }
+                        _ = interval.tick() => {
+                            let candidates = peer_manager_pp
+                                .write()
+                                .await
+                                .take_probe_candidates(PEER_PROBE_SAMPLE);
+                            if candidates.is_empty() {
+                                continue;
+                            }
+                            let mut probes = tokio::task::JoinSet::new();
+                            for addr in candidates {
+                                probes.spawn(async move {
+                                    let ok =
+                                        crate::peer_connector::probe_peer(&addr, params_hash_pp)
+                                            .await;
+                                    (addr, ok)
+                                });
+                            }
+                            let mut verified = Vec::new();
+                            while let Some(result) = probes.join_next().await {
+                                let Ok((addr, ok)) = result else {
+                                    continue;
                                 };
-                                if let Some(ref payload) = bytes {
-                                    let registry = conn_registry_ka.read().await;
-                                    if let Some(writer) = registry.get(pid) {
-                                        if let Err(e) = write_framed(&writer, payload).await {
-                                            tracing::debug!(
-                                                peer = %pid,
-                                                error = %e,
-                                                "keepalive send failed"
-                                            );
-                                        }
-                                    }
+                                tracing::trace!(ip = %addr.ip, port = addr.port, ok, "peer probe");
+                                if ok {
+                                    verified.push(addr);
                                 }
                             }
-
-                            tracing::trace!(
-                                connected = pm.connected_count(),
-                                round = round,
-                                "keepalive round"
-                            );
+                            peer_manager_pp.write().await.add_probed_peers(verified);
                         }
                     }
                 }
-            }
-        });
-        self.task_handles.push(ka_handle);
+            });
+            self.task_handles.push(pp_handle);
+        }
 
         // ── Reachout loop — connect to peers discovered via keepalive ─────
         {
             let reachout_ctx = crate::peer_connector::PeerConnectorContext {
                 peer_manager: Arc::clone(&self.peer_manager),
                 connection_registry: Arc::clone(&self.connection_registry),
-                block_queue: Arc::clone(&self.block_queue),
                 metrics: Arc::clone(&self.metrics),
-                active_elections: Arc::clone(&self.active_elections),
-                rep_weights: Arc::clone(&self.rep_weights),
                 message_dedup: Arc::clone(&self.message_dedup),
-                online_weight_sampler: Arc::clone(&self.online_weight_sampler),
-                frontier: Arc::clone(&self.frontier),
-                store: Arc::clone(&self.store),
+                inbound: Arc::clone(&self.inbound_queue),
+                block_prefilter: Arc::clone(&self.block_prefilter),
+                rep_weights: Arc::clone(&self.rep_weights),
+                vote_admission: Arc::clone(&self.vote_admission),
+                read_buffers: Arc::clone(&self.read_buffers),
                 node_private_key: burst_types::PrivateKey(self.node_private_key.0),
                 node_address: self.node_address.clone(),
                 params_hash: self.config.params.params_hash(),
@@ -3548,22 +6274,34 @@ impl BurstNode {
                         break;
                     }
                     _ = interval.tick() => {
-                        let peer_ids: Vec<String> = {
+                        // Peers that reported telemetry version 2 are asked
+                        // for it; everyone else gets the version 1 request.
+                        let peer_ids: Vec<(String, bool)> = {
                             let pm = peer_manager_telem.read().await;
-                            pm.iter_connected().map(|(id, _)| id.clone()).collect()
+                            pm.iter_connected()
+                                .map(|(id, state)| {
+                                    let v2 = state
+                                        .telemetry
+                                        .as_ref()
+                                        .is_some_and(|t| t.protocol_version >= 2);
+                                    (id.clone(), v2)
+                                })
+                                .collect()
                         };
                         if peer_ids.is_empty() {
                             continue;
                         }
-                        let req = WireMessage::TelemetryReq;
-                        let bytes = match bincode::serialize(&req) {
-                            Ok(b) => b,
-                            Err(_) => continue,
+                        let (Ok(v1), Ok(v2)) = (
+                            bincode::serialize(&WireMessage::TelemetryReq),
+                            bincode::serialize(&WireMessage::TelemetryReqV2),
+                        ) else {
+                            continue;
                         };
                         let registry = conn_registry_telem.read().await;
-                        for pid in &peer_ids {
+                        for (pid, wants_v2) in &peer_ids {
+                            let bytes = if *wants_v2 { &v2 } else { &v1 };
                             if let Some(writer) = registry.get(pid) {
-                                if let Err(e) = crate::connection_registry::write_framed(&writer, &bytes).await {
+                                if let Err(e) = crate::connection_registry::write_framed(&writer, bytes).await {
                                     tracing::trace!(peer = %pid, "failed to send telemetry req: {e}");
                                 }
                             }
@@ -3575,14 +6313,14 @@ impl BurstNode {
         });
         self.task_handles.push(telem_handle);
 
-        // ── Peer cache writer — persist connected peers to LMDB ──────────
+        // ── Peer cache writer — persist known peers and their quality ────
         {
             let store_pc = Arc::clone(&self.store);
             let peer_manager_pc = Arc::clone(&self.peer_manager);
             let mut shutdown_rx_pc = self.shutdown.subscribe();
 
             let pc_handle = tokio::spawn(async move {
-                use burst_store::peer::PeerStore;
+                use burst_store::peer::{PeerRecord, PeerStore};
 
                 let mut interval = tokio::time::interval(Duration::from_secs(300));
                 loop {
@@ -3593,15 +6331,22 @@ impl BurstNode {
                             break;
                         }
                         _ = interval.tick() => {
-                            let connected = {
+                            let known = {
                                 let pm = peer_manager_pc.read().await;
-                                pm.connected_peer_addresses()
+                                pm.peer_quality_records()
                             };
 
                             let peer_store = store_pc.peer_store();
                             let mut saved = 0usize;
-                            for (addr, ts) in &connected {
-                                if let Err(e) = peer_store.put_peer(addr, *ts) {
+                            for (addr, last_seen, quality) in &known {
+                                let record = PeerRecord {
+                                    last_seen: *last_seen,
+                                    uptime_secs: quality.uptime_secs,
+                                    handshake_attempts: quality.handshake_attempts,
+                                    handshake_successes: quality.handshake_successes,
+                                    useful_votes: quality.useful_votes,
+                                };
+                                if let Err(e) = peer_store.put_peer_record(addr, &record) {
                                     tracing::warn!(
                                         peer = %addr,
                                         error = %e,
@@ -3613,7 +6358,7 @@ impl BurstNode {
                             }
 
                             let now = unix_now_secs();
-                            let cutoff = now.saturating_sub(3600);
+                            let cutoff = now.saturating_sub(PEER_CACHE_RETENTION_SECS);
                             match peer_store.purge_older_than(cutoff) {
                                 Ok(purged) if purged > 0 => {
                                     tracing::debug!(
@@ -3630,7 +6375,7 @@ impl BurstNode {
                             if saved > 0 {
                                 tracing::trace!(
                                     saved = saved,
-                                    "peer cache: persisted connected peers"
+                                    "peer cache: persisted known peers"
                                 );
                             }
                         }
@@ -3640,8 +6385,8 @@ impl BurstNode {
             self.task_handles.push(pc_handle);
         }
 
-        // ── RPC server (optional) ─────────────────────────────────────────
-        if self.config.enable_rpc {
+        // ── RPC server and IPC socket (optional) ──────────────────────────
+        if self.config.enable_rpc || self.config.ipc.enabled {
             let rpc_port = self.config.rpc_port;
             let metrics_registry = if self.config.enable_metrics {
                 Some(self.metrics.registry.clone())
@@ -3656,43 +6401,162 @@ impl BurstNode {
                 block_store: Arc::new(self.store.block_store()),
                 pending_store: Arc::new(self.store.pending_store()),
                 frontier_store: Arc::new(self.store.frontier_store()),
+                state_tree_store: Arc::new(self.store.state_tree_store()),
+                rep_weight_store: Arc::new(self.store.rep_weight_store()),
                 verification_store: Arc::new(self.store.verification_store()),
                 governance_store: Arc::new(self.store.governance_store()),
+                alias_store: Arc::new(self.store.alias_store()),
                 governance_engine: Some(Arc::clone(&self.governance)),
+                governance_ballots: Some(Arc::clone(&self.governance_ballots)),
+                treasury: Some(Arc::clone(&self.treasury)),
                 brn_engine: self.brn_engine.clone(),
                 rep_weight_cache: self.rep_weights.clone(),
                 work_generator: Arc::new(WorkGenerator),
                 params: Arc::new(self.config.params.clone()),
+                network: self.config.network,
+                difficulty_adjuster: Some(Arc::clone(&self.difficulty_adjuster)),
                 block_processor: Arc::new(NodeBlockProcessor {
                     block_queue: Arc::clone(&self.block_queue),
+                    live_params: Arc::clone(&self.live_params),
+                    tx_status: Arc::clone(&self.tx_status),
+                    storage_guard: Arc::clone(&self.storage_guard),
                 }),
                 online_reps: Arc::new(std::sync::RwLock::new(Vec::new())),
+                rep_crawler: Some(Arc::clone(&self.rep_crawler)),
                 peer_manager: Arc::clone(&self.peer_manager),
                 enable_faucet: self.config.enable_faucet,
                 rate_limiter: Arc::new(burst_rpc::RateLimiter::new(100)),
                 ledger_cache: Some(
                     self.ledger_cache.clone() as Arc<dyn burst_rpc::LedgerCacheView + Send + Sync>
                 ),
+                receive_minimum: self.config.receive_minimum,
+                active_elections: Some(Arc::clone(&self.active_elections)),
+                quorum_view: Some(Arc::new(NodeQuorumView {
+                    online_weight_tracker: Arc::clone(&self.online_weight_tracker),
+                })),
+                confirmation_history: (self.config.confirmation_history_size > 0).then(|| {
+                    Arc::new(NodeConfirmationHistoryView {
+                        history: Arc::clone(&self.confirmation_history),
+                    }) as Arc<dyn ConfirmationHistoryView>
+                }),
+                block_proof_source: Some(Arc::new(NodeBlockProofSource {
+                    store: Arc::clone(&self.store),
+                })),
+                checkpoint_view: Some(Arc::new(NodeCheckpointView {
+                    store: Arc::clone(&self.store),
+                    rep_weights: Arc::clone(&self.rep_weights),
+                    online_weight_tracker: Arc::clone(&self.online_weight_tracker),
+                })),
+                representative_key_admin: self.config.representative_key_file.clone().map(
+                    |key_file| {
+                        Arc::new(NodeRepresentativeKeyAdmin {
+                            key_file,
+                            vote_generator: Arc::clone(&self.vote_generator),
+                            ws_state: Arc::clone(&self.ws_state),
+                        }) as Arc<dyn RepresentativeKeyAdmin>
+                    },
+                ),
+                peer_admin: Some(Arc::new(NodePeerAdmin {
+                    peer_manager: Arc::clone(&self.peer_manager),
+                    connection_registry: Arc::clone(&self.connection_registry),
+                    store: Arc::clone(&self.store),
+                })),
+                sync_admin: Some(Arc::new(NodeSyncAdmin {
+                    peer_manager: Arc::clone(&self.peer_manager),
+                    broadcaster: self.broadcaster.clone(),
+                    store: Arc::clone(&self.store),
+                })),
+                dev_admin: matches!(self.config.network, burst_types::NetworkId::Dev).then(|| {
+                    Arc::new(NodeDevAdmin {
+                        controls: Arc::clone(&self.dev_controls),
+                        governance: Arc::clone(&self.governance),
+                    }) as Arc<dyn DevAdmin>
+                }),
+                audit_log: Some(Arc::clone(&self.audit_log) as Arc<dyn AuditLog>),
+                fork_history: Some(Arc::clone(&self.fork_log) as Arc<dyn burst_rpc::ForkHistory>),
+                direct_messages: self.config.direct_messages.enabled.then(|| {
+                    Arc::new(NodeDirectMessages {
+                        mailbox: Arc::clone(&self.mailbox),
+                        peer_manager: Arc::clone(&self.peer_manager),
+                        broadcaster: self.broadcaster.clone(),
+                        gossip: self.config.gossip,
+                        message_dedup: Arc::clone(&self.message_dedup),
+                        metrics: Arc::clone(&self.metrics),
+                    }) as Arc<dyn DirectMessageRelay>
+                }),
+                tx_status: Some(Arc::clone(&self.tx_status) as Arc<dyn burst_rpc::TxStatusView>),
+                verification_orchestrator: Some(Arc::clone(&self.verification_orchestrator)),
+                trst_value_view: Some(Arc::new(NodeTrstValueView {
+                    trst_engine: Arc::clone(&self.trst_engine),
+                    live_params: Arc::clone(&self.live_params),
+                })),
+                container_view: Some(Arc::new(NodeContainerView {
+                    block_processor: Arc::clone(&self.block_processor),
+                    vote_cache: Arc::clone(&self.vote_cache),
+                    active_elections: Arc::clone(&self.active_elections),
+                    fork_cache: Arc::clone(&self.fork_cache),
+                    backlog: Arc::clone(&self.backlog),
+                    message_dedup: Arc::clone(&self.message_dedup),
+                    block_queue: Arc::clone(&self.block_queue),
+                    trst_engine: Arc::clone(&self.trst_engine),
+                })),
+                stats: Some(Arc::clone(&self.stats)),
+                watchdog: Some(Arc::clone(&self.watchdog)),
             });
 
-            let rpc_server = RpcServer::with_state(rpc_port, rpc_state);
-            let mut shutdown_rx_rpc = self.shutdown.subscribe();
-
-            let rpc_handle = tokio::spawn(async move {
-                tokio::select! {
-                    biased;
-                    _ = shutdown_rx_rpc.recv() => {
-                        tracing::info!("RPC server shutting down");
-                    }
-                    result = rpc_server.start() => {
-                        match result {
-                            Ok(()) => tracing::info!("RPC server exited"),
-                            Err(e) => tracing::error!("RPC server error: {e}"),
+            #[cfg(unix)]
+            if self.config.ipc.enabled {
+                let path = self
+                    .config
+                    .ipc
+                    .path
+                    .clone()
+                    .unwrap_or_else(|| self.config.data_dir.join("burst.ipc"));
+                let ipc_server =
+                    burst_rpc::IpcServer::new(path, self.config.ipc.mode, Arc::clone(&rpc_state));
+                let mut shutdown_rx_ipc = self.shutdown.subscribe();
+                let ipc_handle = tokio::spawn(async move {
+                    tokio::select! {
+                        biased;
+                        _ = shutdown_rx_ipc.recv() => {
+                            tracing::info!("IPC server shutting down");
+                        }
+                        result = ipc_server.start() => {
+                            if let Err(e) = result {
+                                tracing::error!("IPC server error: {e}");
+                            }
                         }
                     }
+                });
+                self.task_handles.push(ipc_handle);
+            }
+
+            let mut rpc_servers = Vec::new();
+            if self.config.enable_rpc {
+                rpc_servers.push(RpcServer::with_state(rpc_port, Arc::clone(&rpc_state)));
+                if self.config.admin_rpc.enabled {
+                    rpc_servers.push(RpcServer::admin(&self.config.admin_rpc, rpc_state));
                 }
-            });
-            self.task_handles.push(rpc_handle);
+            }
+            for rpc_server in rpc_servers {
+                let mut shutdown_rx_rpc = self.shutdown.subscribe();
+                let rpc_handle = tokio::spawn(async move {
+                    let scope = rpc_server.scope;
+                    tokio::select! {
+                        biased;
+                        _ = shutdown_rx_rpc.recv() => {
+                            tracing::info!(?scope, "RPC server shutting down");
+                        }
+                        result = rpc_server.start() => {
+                            match result {
+                                Ok(()) => tracing::info!(?scope, "RPC server exited"),
+                                Err(e) => tracing::error!(?scope, "RPC server error: {e}"),
+                            }
+                        }
+                    }
+                });
+                self.task_handles.push(rpc_handle);
+            }
         }
 
         // ── WebSocket server (optional) ───────────────────────────────────
@@ -3719,6 +6583,63 @@ impl BurstNode {
             self.task_handles.push(ws_handle);
         }
 
+        // ── Callback webhooks (optional) ──────────────────────────────────
+        if let Some(callback) = self.config.callback.clone() {
+            let url = callback.url.clone();
+            let handles = CallbackDispatcher::new(callback)?.spawn(&self.ws_state, &self.shutdown);
+            tracing::info!(%url, topics = handles.len(), "callback webhooks enabled");
+            self.task_handles.extend(handles);
+        }
+
+        // ── Operator alerts (optional) ────────────────────────────────────
+        if let Some(alerts) = self.config.alerts.clone() {
+            let notifier = Arc::new(AlertNotifier::new(&alerts)?);
+            let interval_secs = alerts.check_interval_secs;
+            let mut monitor = AlertMonitor::new(alerts);
+            let confirming_set_al = Arc::clone(&self.confirming_set);
+            let peer_manager_al = Arc::clone(&self.peer_manager);
+            let clock_sync_al = Arc::clone(&self.clock_sync);
+            let metrics_al = Arc::clone(&self.metrics);
+            let data_dir_al = self.config.data_dir.clone();
+            let storage_guard_al = Arc::clone(&self.storage_guard);
+            let mut shutdown_rx_al = self.shutdown.subscribe();
+
+            let alerts_handle = tokio::spawn(async move {
+                let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+                loop {
+                    tokio::select! {
+                        biased;
+                        _ = shutdown_rx_al.recv() => break,
+                        _ = interval.tick() => {
+                            let integrity_failures = counter_total(&metrics_al.invariant_violations)
+                                + metrics_al.state_root_checks.with_label_values(&["diverged"]).get()
+                                + metrics_al.state_digest_checks.with_label_values(&["diverged"]).get();
+                            let sample = HealthSample {
+                                now_secs: unix_now_secs(),
+                                cemented_count: confirming_set_al.lock().await.cemented_count(),
+                                peer_count: peer_manager_al.read().await.connected_count(),
+                                disk_free_percent: disk_free_percent(&data_dir_al),
+                                clock_offset_ms: clock_sync_al.lock().await.offset_ms,
+                                integrity_failures,
+                                storage_read_only: storage_guard_al.is_read_only(),
+                            };
+                            for alert in monitor.evaluate(&sample) {
+                                tracing::warn!(
+                                    alert = alert.alert.as_str(),
+                                    status = ?alert.status,
+                                    "{}",
+                                    alert.summary
+                                );
+                                notifier.notify(alert);
+                            }
+                        }
+                    }
+                }
+            });
+            tracing::info!(interval_secs, "operator alerts enabled");
+            self.task_handles.push(alerts_handle);
+        }
+
         tracing::info!("BURST node started — all subsystems running");
 
         // Wait for the shutdown signal
@@ -3777,30 +6698,32 @@ impl BurstNode {
             }
         }
 
-        // Persist the merger graph to LMDB before flushing.
+        // Persist the confirmation history.
         {
-            let trst = self.trst_engine.lock().await;
-            let bytes = trst.merger_graph.to_bytes();
+            let bytes = self.confirmation_history.lock().await.to_bytes();
             let meta = self.store.meta_store();
-            if let Err(e) = meta.put_meta(MERGER_GRAPH_META_KEY, &bytes) {
-                tracing::warn!("failed to persist merger graph: {e}");
-            } else {
-                tracing::info!(bytes = bytes.len(), "merger graph persisted to LMDB");
+            if let Err(e) = meta.put_meta(CONFIRMATION_HISTORY_META_KEY, &bytes) {
+                tracing::warn!("failed to persist confirmation history: {e}");
             }
         }
 
-        // Persist TRST engine per-wallet token portfolios to LMDB.
+        // Flush merger graph changes not yet written by a block batch.
         {
-            let trst = self.trst_engine.lock().await;
-            let bytes = trst.save_wallets();
-            let meta = self.store.meta_store();
-            if let Err(e) = meta.put_meta(TrstEngine::meta_key(), &bytes) {
-                tracing::warn!("failed to persist TRST wallet portfolios: {e}");
-            } else {
-                tracing::info!(
-                    bytes = bytes.len(),
-                    "TRST wallet portfolios persisted to LMDB"
-                );
+            let mut trst = self.trst_engine.lock().await;
+            if let Err(e) = crate::merger_graph::persist_merger_graph_deltas(
+                &self.store,
+                &mut trst.merger_graph,
+            ) {
+                tracing::warn!("failed to persist merger graph changes: {e}");
+            }
+        }
+
+        // Flush TRST portfolio changes not yet written by a block batch.
+        {
+            let mut trst = self.trst_engine.lock().await;
+            if let Err(e) = crate::trst_portfolios::persist_dirty_portfolios(&self.store, &mut trst)
+            {
+                tracing::warn!("failed to persist TRST portfolios: {e}");
             }
         }
 
@@ -3841,19 +6764,24 @@ impl BurstNode {
             }
         }
 
-        // Persist representative weights to LMDB.
+        // Persist representative weights to LMDB, replacing the stored set so
+        // representatives dropped from the cache stay dropped.
         {
             let rw = self.rep_weights.read().await;
-            let rw_store = self.store.rep_weight_store();
-            let mut persisted = 0u64;
-            for (rep, weight) in rw.all_weights() {
-                if let Err(e) = rw_store.put_rep_weight(rep, *weight) {
-                    tracing::warn!(rep = %rep, error = %e, "failed to persist rep weight");
-                } else {
-                    persisted += 1;
+            let weights: Vec<(WalletAddress, u128)> = rw
+                .all_weights()
+                .iter()
+                .map(|(rep, weight)| (rep.clone(), *weight))
+                .collect();
+            match self.store.rep_weight_store().replace_rep_weights(&weights) {
+                Ok(()) => {
+                    tracing::info!(
+                        reps = weights.len(),
+                        "representative weights persisted to LMDB"
+                    )
                 }
+                Err(e) => tracing::warn!(error = %e, "failed to persist rep weights"),
             }
-            tracing::info!(reps = persisted, "representative weights persisted to LMDB");
         }
 
         // Flush LMDB
@@ -3894,11 +6822,14 @@ impl BurstNode {
     /// blocks to LMDB, updates the frontier, and processes any unchecked
     /// dependents that become unblocked (both gap-previous and gap-source).
     pub fn process_block(&self, block: &StateBlock) -> Result<ProcessResult, NodeError> {
+        if self.storage_guard.is_read_only() {
+            return Err(NodeError::ReadOnly);
+        }
         // We can't hold the async locks from sync code, so use try_lock.
         let mut processor = self
             .block_processor
             .try_lock()
-            .map_err(|_| NodeError::Other("block processor is busy".into()))?;
+            .map_err(|_| NodeError::Busy("block processor is busy".into()))?;
 
         // We need a mutable reference to frontier. try_write for RwLock.
         // Since this is sync context and the lock is tokio, we use
@@ -3906,7 +6837,7 @@ impl BurstNode {
         let mut frontier = self
             .frontier
             .try_write()
-            .map_err(|_| NodeError::Other("frontier is locked".into()))?;
+            .map_err(|_| NodeError::Busy("frontier is locked".into()))?;
 
         // Pre-validate balance transition against previous block in the store.
         if !block.previous.is_zero() {
@@ -3923,6 +6854,19 @@ impl BurstNode {
             }
         }
 
+        let account_epoch = self
+            .store
+            .account_store()
+            .get_account(&block.account)
+            .map_or(0, |acct| acct.epoch);
+        if let Err(reason) = burst_ledger::validate_block_version(
+            block,
+            account_epoch,
+            self.config.params.ledger_epoch,
+        ) {
+            return Ok(ProcessResult::Rejected(reason));
+        }
+
         let result = processor.process(block, &mut frontier);
 
         if result == ProcessResult::Accepted {
@@ -3964,12 +6908,12 @@ impl BurstNode {
         let mut processor = self
             .block_processor
             .try_lock()
-            .map_err(|_| NodeError::Other("block processor is busy".into()))?;
+            .map_err(|_| NodeError::Busy("block processor is busy".into()))?;
 
         let mut frontier = self
             .frontier
             .try_write()
-            .map_err(|_| NodeError::Other("frontier is locked".into()))?;
+            .map_err(|_| NodeError::Busy("frontier is locked".into()))?;
 
         let result = processor.rollback(block, &mut frontier);
 
@@ -4009,8 +6953,26 @@ impl BurstNode {
         let now = Timestamp::new(now_secs);
 
         // Step 1: Validate the transaction
-        validate_transaction(&tx, now, 300)
-            .map_err(|e| NodeError::Other(format!("transaction validation failed: {e}")))?;
+        validate_transaction(&tx, now, TX_TIME_TOLERANCE_SECS)
+            .map_err(|e| NodeError::InvalidTransaction(e.to_string()))?;
+
+        // Step 1b: A transaction already on the sender's chain is a replay.
+        // The block processor rejects it too; answering here gives the
+        // client the height it was applied at.
+        let applied = applied_height(
+            &self.store.account_store(),
+            &self.store.block_store(),
+            tx.sender(),
+            tx.hash(),
+            now,
+        )
+        .map_err(|e| NodeError::Other(format!("unable to check for replay: {e}")))?;
+        if let Some(height) = applied {
+            return Err(NodeError::Replayed {
+                tx: tx.hash().to_string(),
+                height,
+            });
+        }
 
         self.metrics.transactions_received.inc();
 
@@ -4019,20 +6981,29 @@ impl BurstNode {
         let block_hash = block.hash;
 
         // Step 3: Submit to the block priority queue (ordered by PoW difficulty)
+        self.tx_status.submitted(block_hash, now_secs);
         if !self.block_queue.push(block.clone()).await {
-            return Err(NodeError::Other("block priority queue full".into()));
+            self.tx_status
+                .dropped(&block_hash, "block priority queue full", now_secs);
+            return Err(NodeError::QueueFull("block priority queue full".into()));
         }
 
-        // Step 4: Broadcast to peers
-        if let Ok(msg_bytes) = bincode::serialize(&block) {
+        // Step 4: Flood to peers
+        {
             let peers: Vec<burst_network::PeerState> = {
                 let pm = self.peer_manager.read().await;
                 pm.iter_connected().map(|(_, s)| s.clone()).collect()
             };
-            let result = self
-                .broadcaster
-                .broadcast_with_fanout(&msg_bytes, &peers, 4)
-                .await;
+            let gossip_config = &self.config.gossip;
+            let result = gossip::flood(
+                &self.broadcaster,
+                gossip_config,
+                &self.message_dedup,
+                WireMessage::Block(Box::new(block)),
+                gossip_config.origin(),
+                &peers,
+            )
+            .await;
             tracing::debug!(
                 sent = result.sent,
                 failed = result.failed,
@@ -4089,10 +7060,11 @@ impl BurstNode {
         let (block_type, new_brn, new_trst, link) = match tx {
             burst_transactions::Transaction::Burn(burn) => {
                 if burn.amount > brn_balance {
-                    return Err(NodeError::Other(format!(
-                        "insufficient BRN: need {}, have {}",
-                        burn.amount, brn_balance
-                    )));
+                    return Err(BurstError::InsufficientBrn {
+                        needed: burn.amount,
+                        available: brn_balance,
+                    }
+                    .into());
                 }
                 let new_brn = brn_balance - burn.amount;
                 let new_trst = trst_balance;
@@ -4113,22 +7085,29 @@ impl BurstNode {
             }
             burst_transactions::Transaction::Send(send) => {
                 if send.amount > trst_balance {
-                    return Err(NodeError::Other(format!(
-                        "insufficient TRST: need {}, have {}",
-                        send.amount, trst_balance
-                    )));
+                    return Err(BurstError::InsufficientTrst {
+                        needed: send.amount,
+                        available: trst_balance,
+                    }
+                    .into());
                 }
                 // Verify sender has enough transferable (non-expired, non-revoked) TRST
                 {
+                    let params = self.current_params();
                     let mut trst = self.trst_engine.lock().await;
-                    let trst_expiry = self.config.params.trst_expiry_secs;
-                    if let Some(transferable) = trst.transferable_balance(&sender, now, trst_expiry)
-                    {
+                    if let Some(transferable) = trst.transferable_value(
+                        &sender,
+                        now,
+                        params.trst_expiry_secs,
+                        params.trst_decay_mode,
+                        params.trst_decay_half_life_secs,
+                    ) {
                         if send.amount > transferable {
-                            return Err(NodeError::Other(format!(
-                                "insufficient transferable TRST: need {} but only {} is transferable",
-                                send.amount, transferable
-                            )));
+                            return Err(BurstError::InsufficientTransferableTrst {
+                                needed: send.amount,
+                                transferable,
+                            }
+                            .into());
                         }
                     }
                 }
@@ -4148,6 +7127,142 @@ impl BurstNode {
                     BlockHash::new(link_bytes),
                 )
             }
+            burst_transactions::Transaction::HtlcLock(lock) => {
+                if is_open {
+                    return Err(NodeError::InvalidTransaction(
+                        "HTLC lock cannot open an account".into(),
+                    ));
+                }
+                if lock.amount > trst_balance {
+                    return Err(BurstError::InsufficientTrst {
+                        needed: lock.amount,
+                        available: trst_balance,
+                    }
+                    .into());
+                }
+                let mut link_bytes = [0u8; 32];
+                let addr_bytes = lock.receiver.as_str().as_bytes();
+                let copy_len = addr_bytes.len().min(32);
+                link_bytes[..copy_len].copy_from_slice(&addr_bytes[..copy_len]);
+                (
+                    BlockType::HtlcLock,
+                    brn_balance,
+                    trst_balance - lock.amount,
+                    BlockHash::new(link_bytes),
+                )
+            }
+            burst_transactions::Transaction::HtlcClaim(claim) => (
+                BlockType::HtlcClaim,
+                brn_balance,
+                trst_balance.saturating_add(claim.amount),
+                BlockHash::new(*claim.lock_block_hash.as_bytes()),
+            ),
+            burst_transactions::Transaction::HtlcRefund(refund) => (
+                BlockType::HtlcRefund,
+                brn_balance,
+                trst_balance.saturating_add(refund.amount),
+                BlockHash::new(*refund.lock_block_hash.as_bytes()),
+            ),
+            burst_transactions::Transaction::RejectReceive(reject) => (
+                BlockType::RejectReceive,
+                brn_balance,
+                trst_balance,
+                BlockHash::new(*reject.send_block_hash.as_bytes()),
+            ),
+            burst_transactions::Transaction::GroupAnchor(anchor) => (
+                BlockType::GroupAnchor,
+                brn_balance,
+                trst_balance,
+                BlockHash::new(anchor.anchor),
+            ),
+            burst_transactions::Transaction::RecoverySetup(_) => (
+                BlockType::RecoverySetup,
+                brn_balance,
+                trst_balance,
+                BlockHash::ZERO,
+            ),
+            burst_transactions::Transaction::Recovery(recovery) => (
+                BlockType::Recovery,
+                brn_balance,
+                trst_balance,
+                BlockHash::new(*recovery.new_key.as_bytes()),
+            ),
+            burst_transactions::Transaction::VerifierOptIn(opt_in) => {
+                if opt_in.stake_amount > brn_balance {
+                    return Err(BurstError::InsufficientBrn {
+                        needed: opt_in.stake_amount,
+                        available: brn_balance,
+                    }
+                    .into());
+                }
+                (
+                    BlockType::VerifierOptIn,
+                    brn_balance - opt_in.stake_amount,
+                    trst_balance,
+                    BlockHash::ZERO,
+                )
+            }
+            burst_transactions::Transaction::VerifierOptOut(opt_out) => (
+                BlockType::VerifierOptOut,
+                brn_balance.saturating_add(opt_out.stake_amount),
+                trst_balance,
+                BlockHash::ZERO,
+            ),
+            burst_transactions::Transaction::Liveness(_) => (
+                BlockType::Liveness,
+                brn_balance,
+                trst_balance,
+                BlockHash::ZERO,
+            ),
+            burst_transactions::Transaction::DelegateProfile(profile) => (
+                BlockType::DelegateProfile,
+                brn_balance,
+                trst_balance,
+                BlockHash::new(profile.anchor),
+            ),
+            burst_transactions::Transaction::Alias(alias) => {
+                if alias.fee > brn_balance {
+                    return Err(BurstError::InsufficientBrn {
+                        needed: alias.fee,
+                        available: brn_balance,
+                    }
+                    .into());
+                }
+                (
+                    BlockType::Alias,
+                    brn_balance - alias.fee,
+                    trst_balance,
+                    BlockHash::ZERO,
+                )
+            }
+            burst_transactions::Transaction::Delegate(delegate) => (
+                BlockType::Delegate,
+                brn_balance,
+                trst_balance,
+                BlockHash::new(
+                    burst_crypto::decode_address(delegate.delegate.as_str()).unwrap_or([0u8; 32]),
+                ),
+            ),
+            burst_transactions::Transaction::RotateDelegationKey(rotate) => (
+                BlockType::RotateDelegationKey,
+                brn_balance,
+                trst_balance,
+                BlockHash::new(
+                    burst_crypto::decode_address(rotate.delegate.as_str()).unwrap_or([0u8; 32]),
+                ),
+            ),
+            burst_transactions::Transaction::GovernanceCommit(commit) => (
+                BlockType::GovernanceCommit,
+                brn_balance,
+                trst_balance,
+                BlockHash::new(*commit.proposal_hash.as_bytes()),
+            ),
+            burst_transactions::Transaction::GovernanceReveal(reveal) => (
+                BlockType::GovernanceReveal,
+                brn_balance,
+                trst_balance,
+                BlockHash::new(*reveal.proposal_hash.as_bytes()),
+            ),
             _ => {
                 // For other transaction types, create a generic block
                 let block_type = if is_open {
@@ -4164,7 +7279,6 @@ impl BurstNode {
                         burst_transactions::Transaction::GovernanceVote(_) => {
                             BlockType::GovernanceVote
                         }
-                        burst_transactions::Transaction::Delegate(_) => BlockType::Delegate,
                         burst_transactions::Transaction::RevokeDelegation(_) => {
                             BlockType::RevokeDelegation
                         }
@@ -4174,16 +7288,130 @@ impl BurstNode {
                         _ => BlockType::Send, // fallback; unreachable
                     }
                 };
-                (block_type, brn_balance, trst_balance, BlockHash::ZERO)
+                let mut link = [0u8; 32];
+                if let burst_transactions::Transaction::GovernanceProposal(proposal) = tx {
+                    if let burst_transactions::governance::ProposalContent::TreasuryGrant {
+                        amount,
+                        ..
+                    } = proposal.proposal
+                    {
+                        link = burst_transactions::governance::treasury_grant_link(amount);
+                    }
+                    proposal.ballot.mark_proposal_link(&mut link);
+                    if proposal.requires.is_some() {
+                        burst_transactions::governance::mark_proposal_dependency(&mut link);
+                    }
+                }
+                (block_type, brn_balance, trst_balance, BlockHash::new(link))
             }
         };
 
         let tx_hash = *tx.hash();
 
-        let origin = if block_type == BlockType::Burn {
-            tx_hash
+        let origin = match tx {
+            _ if block_type == BlockType::Burn => tx_hash,
+            burst_transactions::Transaction::GovernanceProposal(proposal) => {
+                match proposal.proposal {
+                    burst_transactions::governance::ProposalContent::TreasuryGrant {
+                        ref recipient,
+                        ..
+                    } => burst_crypto::decode_address(recipient.as_str())
+                        .map(TxHash::new)
+                        .unwrap_or(previous_origin),
+                    _ => proposal.requires.unwrap_or(previous_origin),
+                }
+            }
+            _ => previous_origin,
+        };
+
+        // Sealed ballots travel in the transaction field.
+        let transaction = match tx {
+            burst_transactions::Transaction::GovernanceCommit(commit) => {
+                TxHash::new(commit.commitment)
+            }
+            burst_transactions::Transaction::GovernanceReveal(reveal) => {
+                TxHash::new(reveal.sealed_ballot())
+            }
+            _ => tx_hash,
+        };
+
+        let memo = match tx {
+            burst_transactions::Transaction::Send(send) if block_type == BlockType::Send => {
+                send.memo.clone()
+            }
+            burst_transactions::Transaction::Alias(alias) => Some(alias.name.clone().into_bytes()),
+            _ => None,
+        };
+
+        let htlc = match tx {
+            burst_transactions::Transaction::HtlcLock(lock) => {
+                Some(burst_transactions::htlc::HtlcData::Lock {
+                    hash_lock: lock.hash_lock,
+                    refund_after: lock.refund_after,
+                })
+            }
+            burst_transactions::Transaction::HtlcClaim(claim) => {
+                Some(burst_transactions::htlc::HtlcData::Claim {
+                    preimage: claim.preimage,
+                })
+            }
+            _ => None,
+        };
+
+        let reject_reason = match tx {
+            burst_transactions::Transaction::RejectReceive(reject) => Some(reject.reason),
+            _ => None,
+        };
+
+        let delegation_key = match tx {
+            burst_transactions::Transaction::Delegate(delegate) => Some(delegate.key.clone()),
+            burst_transactions::Transaction::RotateDelegationKey(rotate) => {
+                Some(rotate.key.clone())
+            }
+            _ => None,
+        };
+
+        let recovery = match tx {
+            burst_transactions::Transaction::RecoverySetup(setup) => {
+                Some(burst_transactions::recovery::RecoveryData::Setup {
+                    custodians: setup.custodians.clone(),
+                    threshold: setup.threshold,
+                    delay_secs: setup.delay_secs,
+                })
+            }
+            burst_transactions::Transaction::Recovery(recovery) => {
+                Some(burst_transactions::recovery::RecoveryData::Rotate {
+                    new_key: recovery.new_key.clone(),
+                    requested_at: recovery.requested_at,
+                    approvals: recovery.approvals.clone(),
+                })
+            }
+            _ => None,
+        };
+
+        // Lay the block out for the sender's epoch. From version 2 it
+        // carries its chain position: one past its head's stored height,
+        // which is what the processor checks it against.
+        let account_epoch = self
+            .store
+            .account_store()
+            .get_account(&sender)
+            .map_or(0, |acct| acct.epoch);
+        let layout = burst_ledger::BlockLayout::for_account(
+            account_epoch,
+            self.config.params.ledger_epoch,
+            self.config.network,
+        );
+        let sequence = if is_open || layout.version < burst_ledger::BLOCK_VERSION_V2 {
+            1
         } else {
-            previous_origin
+            self.store
+                .block_store()
+                .height_of_block(&previous)?
+                .ok_or_else(|| {
+                    NodeError::Other(format!("height of head {previous} is not stored"))
+                })?
+                + 1
         };
 
         let mut block = StateBlock {
@@ -4196,14 +7424,20 @@ impl BurstNode {
             trst_balance: new_trst,
             link,
             origin,
-            transaction: tx_hash,
+            transaction,
             timestamp: now,
             params_hash: BlockHash::ZERO,
+            memo,
+            htlc,
+            reject_reason,
+            recovery,
+            delegation_key,
+            extensions: Vec::new(),
             work: 0,
             signature: tx.signature().clone(),
             hash: BlockHash::ZERO,
         };
-        block.hash = block.compute_hash();
+        block.apply_layout(layout, sequence);
 
         Ok(block)
     }
@@ -4226,6 +7460,10 @@ impl BurstNode {
         if let Ok(count) = self.store.block_store().block_count() {
             self.metrics.block_count.set(count as i64);
         }
+
+        if let Ok(count) = self.store.final_vote_store().final_vote_count() {
+            self.metrics.final_votes_stored.set(count as i64);
+        }
     }
 
     /// Get the current protocol parameters.
@@ -4233,6 +7471,15 @@ impl BurstNode {
         &self.config.params
     }
 
+    /// The protocol parameters in force now, after any governance
+    /// activations since startup.
+    pub fn current_params(&self) -> ProtocolParams {
+        self.live_params
+            .read()
+            .map(|params| params.clone())
+            .unwrap_or_else(|_| self.config.params.clone())
+    }
+
     /// Get a handle to the block priority queue for submitting blocks.
     pub fn block_queue(&self) -> Arc<BlockPriorityQueue> {
         Arc::clone(&self.block_queue)
@@ -4276,6 +7523,337 @@ fn parse_advertise_address(s: &str, default_port: u16) -> Option<std::net::Socke
     Some(std::net::SocketAddrV4::new(ip, port))
 }
 
+/// Fetch drand randomness and draw verifiers for `target` from the persisted
+/// verifier pool, recording the draw's inputs so anyone can recompute it.
+///
+/// With `reselect` set, only the seats vacated by timed-out verifiers are
+/// filled; the votes already cast in the round are kept.
+async fn draw_verifiers_via_vrf(
+    vrf: &Mutex<burst_vrf::DrandClient>,
+    store: &LmdbStore,
+    orch: &Mutex<burst_verification::VerificationOrchestrator>,
+    target: &WalletAddress,
+    params: &ProtocolParams,
+    reselect: bool,
+) {
+    let client = vrf.lock().await;
+    let beacon = match client.fetch_latest().await {
+        Ok(beacon) => beacon,
+        Err(e) => {
+            tracing::error!(
+                error = %e,
+                "failed to fetch VRF randomness for verification"
+            );
+            return;
+        }
+    };
+    let randomness = hex::decode(&beacon.randomness).unwrap_or_else(|_| vec![0u8; 32]);
+    let mut rand_bytes = [0u8; 32];
+    let copy_len = randomness.len().min(32);
+    rand_bytes[..copy_len].copy_from_slice(&randomness[..copy_len]);
+
+    // Draw from the persisted pool so every node selects from the same set.
+    let verifier_addrs =
+        match VerifierPool::load(params.verifier_stake_amount, &store.verification_store()) {
+            Ok(pool) => pool.pool(),
+            Err(e) => {
+                tracing::error!(
+                    error = %e,
+                    "failed to read verifier pool for VRF selection"
+                );
+                return;
+            }
+        };
+
+    let mut orch = orch.lock().await;
+    let drawn = if reselect {
+        orch.reselect_verifiers(target, &verifier_addrs, &rand_bytes, params)
+    } else {
+        orch.select_verifiers(target, &verifier_addrs, &rand_bytes, params)
+    };
+    let selected = match drawn {
+        Ok(selected) => selected,
+        Err(e) => {
+            tracing::error!(
+                error = %e,
+                target = %target,
+                reselect,
+                "failed to assign verifiers via orchestrator"
+            );
+            return;
+        }
+    };
+    tracing::info!(
+        target = %target,
+        selected_count = selected.len(),
+        drand_round = beacon.round,
+        reselect,
+        "verifiers selected via VRF"
+    );
+
+    let candidates = orch
+        .get_state(target)
+        .map(|s| s.selection_candidates.clone())
+        .unwrap_or_default();
+    let selection = burst_store::VerifierSelection {
+        target: target.clone(),
+        drand_round: beacon.round,
+        randomness: rand_bytes,
+        pool_hash: burst_verification::pool_snapshot_hash(&candidates),
+        candidates,
+        selected,
+        selected_at: Timestamp::new(unix_now_secs()),
+    };
+    if let Err(e) = store
+        .verification_store()
+        .put_verifier_selection(&selection)
+    {
+        tracing::warn!(
+            error = %e,
+            target = %target,
+            "failed to record verifier selection"
+        );
+    }
+}
+
+/// Persist the challenge history of both parties to a resolved challenge,
+/// so the block processor can enforce challenge cooldowns and escalating
+/// stakes.
+fn persist_challenge_history(
+    store: &LmdbStore,
+    orch: &burst_verification::VerificationOrchestrator,
+    outcome: &burst_verification::ChallengeOutcomeEvent,
+) {
+    let verification_store = store.verification_store();
+    for wallet in [&outcome.challenged_wallet, &outcome.challenger] {
+        if let Err(e) = crate::verification_processor::save_challenge_history(
+            &verification_store,
+            wallet,
+            &orch.challenge_history(wallet),
+        ) {
+            tracing::warn!(%wallet, error = %e, "failed to persist challenge history");
+        }
+    }
+}
+
+/// Persist the protocol treasury beside the governance engine.
+fn persist_treasury(store: &LmdbStore, treasury: &Treasury) {
+    match bincode::serialize(treasury) {
+        Ok(bytes) => {
+            if let Err(e) = store.brn_store().put_meta(b"governance_treasury", &bytes) {
+                tracing::warn!(error = %e, "failed to persist treasury");
+            }
+        }
+        Err(e) => tracing::warn!(error = %e, "failed to serialize treasury"),
+    }
+}
+
+/// Pay the treasury grant approved by `proposal` when its activation block
+/// is processed: debit the treasury and leave the TRST pending for the
+/// recipient, keyed by the activation block.
+async fn pay_treasury_grant(
+    store: &LmdbStore,
+    treasury: &Mutex<Treasury>,
+    block: &StateBlock,
+    proposal: TxHash,
+    recipient: &WalletAddress,
+    amount: u128,
+) {
+    let mut treasury = treasury.lock().await;
+    if let Err(e) = treasury.disburse(proposal, recipient, amount, block.timestamp) {
+        tracing::warn!(%proposal, %recipient, amount, "treasury grant not paid: {e}");
+        return;
+    }
+    let pending = store
+        .write_batch()
+        .map_err(|e| e.to_string())
+        .and_then(|mut batch| {
+            crate::ledger_updater::create_grant_pending_entry(
+                &mut batch, block, proposal, recipient, amount,
+            )?;
+            batch.commit().map_err(|e| e.to_string())
+        });
+    match pending {
+        Ok(()) => tracing::info!(%proposal, %recipient, amount, "treasury grant paid"),
+        Err(e) => {
+            tracing::error!(%proposal, %recipient, "treasury grant debited but not left pending: {e}")
+        }
+    }
+    persist_treasury(store, &treasury);
+}
+
+/// Move an account through the wallet state machine and append the
+/// transition to its history. Returns `false`, leaving the account
+/// untouched, when it is already in `to` or the transition is not allowed.
+pub(crate) fn transition_account_state(
+    store: &LmdbStore,
+    acct: &mut burst_store::account::AccountInfo,
+    to: burst_types::WalletState,
+    reason: burst_types::TransitionReason,
+) -> bool {
+    if acct.state == to {
+        return false;
+    }
+    match burst_types::state_machine::validate_transition(acct.state, to, reason, Timestamp::now())
+    {
+        Ok(transition) => {
+            acct.state = to;
+            if let Err(e) = store
+                .account_store()
+                .put_state_transition(&acct.address, &transition)
+            {
+                tracing::warn!(wallet = %acct.address, error = %e, "failed to record state transition");
+            }
+            true
+        }
+        Err(e) => {
+            tracing::warn!(wallet = %acct.address, "{e}");
+            false
+        }
+    }
+}
+
+/// Sign, persist and flood a revocation notice for a fraud verdict whose
+/// deciding block was just cemented. Nothing is sent when the wallet
+/// already has a notice (a peer's arrived first) or the block has no
+/// quorum certificate.
+#[allow(clippy::too_many_arguments)]
+async fn issue_revocation_notice(
+    store: &LmdbStore,
+    broadcaster: &Broadcaster,
+    peer_manager: &RwLock<PeerManager>,
+    gossip_config: &GossipConfig,
+    message_dedup: &Mutex<burst_network::MessageDedup>,
+    metrics: &NodeMetrics,
+    node_key: &burst_types::PrivateKey,
+    wallet: WalletAddress,
+    verdict_hash: &BlockHash,
+) {
+    let verification_store = store.verification_store();
+    if !matches!(
+        load_revocation_notice(&verification_store, &wallet),
+        Ok(None)
+    ) {
+        return;
+    }
+    let certificate = match load_quorum_certificate(&store.quorum_certificate_store(), verdict_hash)
+    {
+        Ok(Some(certificate)) => certificate,
+        Ok(None) => {
+            tracing::warn!(%wallet, hash = %verdict_hash, "fraud verdict cemented without a quorum certificate");
+            return;
+        }
+        Err(e) => {
+            tracing::warn!(%wallet, "failed to load verdict certificate: {e}");
+            return;
+        }
+    };
+    let Some(verdict_block) = store
+        .block_store()
+        .get_block(verdict_hash)
+        .ok()
+        .and_then(|bytes| bincode::deserialize::<StateBlock>(&bytes).ok())
+    else {
+        return;
+    };
+    let notice = RevocationNotice::sign(
+        wallet,
+        verdict_block,
+        certificate,
+        node_key,
+        unix_now_secs(),
+    );
+    if let Err(e) = save_revocation_notice(&verification_store, &notice) {
+        tracing::warn!(wallet = %notice.wallet, "failed to persist revocation notice: {e}");
+    }
+    tracing::info!(wallet = %notice.wallet, hash = %verdict_hash, "issuing revocation notice");
+    metrics
+        .revocation_notices
+        .with_label_values(&["issued"])
+        .inc();
+    let peers: Vec<burst_network::PeerState> = {
+        let pm = peer_manager.read().await;
+        pm.iter_connected().map(|(_, s)| s.clone()).collect()
+    };
+    gossip::flood(
+        broadcaster,
+        gossip_config,
+        message_dedup,
+        WireMessage::RevocationNotice(Box::new(notice)),
+        gossip_config.origin(),
+        &peers,
+    )
+    .await;
+}
+
+/// Load a stored account, apply [`transition_account_state`] and save it.
+fn transition_stored_account(
+    store: &LmdbStore,
+    wallet: &WalletAddress,
+    to: burst_types::WalletState,
+    reason: burst_types::TransitionReason,
+) {
+    let Ok(mut acct) = store.account_store().get_account(wallet) else {
+        return;
+    };
+    if transition_account_state(store, &mut acct, to, reason) {
+        if let Err(e) = store.account_store().put_account(&acct) {
+            tracing::error!(%wallet, "failed to persist account state {to:?}: {e}");
+        }
+    }
+}
+
+/// Persist the anonymized outcome record of a finished verification or
+/// challenge, which feeds the `uhv_stats` RPC.
+fn persist_verification_record(
+    store: &LmdbStore,
+    state: &burst_verification::VerificationState,
+    outcome: burst_store::VerificationRecordOutcome,
+) {
+    let record =
+        crate::verification_processor::verification_record(state, outcome, Timestamp::now());
+    if let Err(e) = store
+        .verification_store()
+        .put_verification_record(&state.target, &record)
+    {
+        tracing::warn!(wallet = %state.target, error = %e, "failed to persist verification record");
+    }
+}
+
+/// Publish a BRN inactivity event on the `account_update` topic.
+fn publish_inactivity_event(ws: &WsState, event: &burst_brn::InactivityEvent) {
+    let (change_type, at) = match event {
+        burst_brn::InactivityEvent::GracePeriodStarted { suspends_at, .. } => {
+            ("inactivity_grace_period", suspends_at)
+        }
+        burst_brn::InactivityEvent::AccrualSuspended { at, .. } => ("brn_accrual_suspended", at),
+        burst_brn::InactivityEvent::AccrualResumed { at, .. } => ("brn_accrual_resumed", at),
+    };
+    ws.publish_inactivity(event.wallet().as_str(), change_type, at.as_secs());
+}
+
+/// Event type and subject wallet of a verification event, as published on
+/// the `verification` topic.
+fn verification_event_kind(
+    event: &burst_verification::VerificationEvent,
+) -> (&'static str, &WalletAddress) {
+    use burst_verification::{VerificationEvent, VerificationResult};
+    match event {
+        VerificationEvent::EndorsementComplete { wallet } => ("endorsement_complete", wallet),
+        VerificationEvent::VerifiersSelected { wallet, .. } => ("verifiers_selected", wallet),
+        VerificationEvent::VerificationComplete { wallet, result, .. } => match result {
+            VerificationResult::Verified => ("verified", wallet),
+            VerificationResult::Failed => ("verification_failed", wallet),
+        },
+        VerificationEvent::ChallengeResolved { wallet, .. } => ("challenge_resolved", wallet),
+        VerificationEvent::WalletUnverified { wallet } => ("unverified", wallet),
+        VerificationEvent::VerifierPenalized { verifier, .. } => ("verifier_penalized", verifier),
+        VerificationEvent::CollusionFlagged { wallet, .. } => ("collusion_flagged", wallet),
+        VerificationEvent::VerifiersTimedOut { wallet, .. } => ("verifiers_timed_out", wallet),
+        VerificationEvent::VerificationExpired { wallet, .. } => ("verification_expired", wallet),
+    }
+}
+
 /// Helper: current UNIX timestamp in seconds.
 fn unix_now_secs() -> u64 {
     std::time::SystemTime::now()
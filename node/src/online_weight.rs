@@ -73,6 +73,11 @@ impl OnlineWeightTracker {
         self.current_weight
     }
 
+    /// Minimum weight floor.
+    pub fn minimum_weight(&self) -> u128 {
+        self.minimum_weight
+    }
+
     /// Number of samples.
     pub fn sample_count(&self) -> usize {
         self.samples.len()
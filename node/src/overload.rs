@@ -0,0 +1,232 @@
+//! Graceful degradation under overload.
+//!
+//! Left alone, a node fed faster than it can process just queues more and
+//! more work and every confirmation gets slower. The [`OverloadController`]
+//! watches the block queue, the active elections container and LMDB commit
+//! latency. When any of them runs past its threshold the node enters
+//! overload mode: gossiped blocks must carry more proof-of-work to pass the
+//! edge checks, bootstrap and telemetry traffic is shed, and telemetry acks
+//! tell peers the node is overloaded. The mode is left once every measure
+//! falls well below its threshold, so the node does not flap at the edge.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Fraction of each threshold every measure must fall below before
+/// overload mode is left.
+const RECOVERY_RATIO: f64 = 0.75;
+
+/// Thresholds and responses of the overload controller.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverloadConfig {
+    /// Whether the node degrades under load at all.
+    pub enabled: bool,
+    /// Block queue fill, in percent of its capacity.
+    pub block_queue_percent: u8,
+    /// Active elections, in percent of the current election limit.
+    pub elections_percent: u8,
+    /// Average LMDB commit time of the block batch, in milliseconds.
+    pub commit_latency_ms: u64,
+    /// Factor applied to the work thresholds of gossiped blocks while
+    /// overloaded.
+    pub work_multiplier: f64,
+}
+
+impl Default for OverloadConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            block_queue_percent: 80,
+            elections_percent: 95,
+            commit_latency_ms: 250,
+            work_multiplier: 4.0,
+        }
+    }
+}
+
+/// A measure that pushed the node into overload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverloadCause {
+    BlockQueue,
+    ActiveElections,
+    CommitLatency,
+}
+
+impl OverloadCause {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OverloadCause::BlockQueue => "block_queue",
+            OverloadCause::ActiveElections => "active_elections",
+            OverloadCause::CommitLatency => "commit_latency",
+        }
+    }
+}
+
+/// Load readings taken for one evaluation.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LoadSample {
+    pub block_queue_len: usize,
+    pub block_queue_capacity: usize,
+    pub active_elections: usize,
+    pub election_limit: usize,
+}
+
+/// Tracks load and decides when the node is overloaded.
+///
+/// Shared between the block processor, which reports commit times, the
+/// inbound workers, which advertise the state, and the task that
+/// periodically evaluates it.
+pub struct OverloadController {
+    config: OverloadConfig,
+    overloaded: AtomicBool,
+    /// Moving average of block batch commit times, in microseconds.
+    commit_latency_us: AtomicU64,
+}
+
+impl OverloadController {
+    pub fn new(config: OverloadConfig) -> Self {
+        Self {
+            config,
+            overloaded: AtomicBool::new(false),
+            commit_latency_us: AtomicU64::new(0),
+        }
+    }
+
+    /// Fold one block batch commit time into the moving average.
+    pub fn record_commit(&self, elapsed: Duration) {
+        let sample = elapsed.as_micros().min(u64::MAX as u128) as u64;
+        let _ = self
+            .commit_latency_us
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |avg| {
+                Some(if avg == 0 {
+                    sample
+                } else {
+                    (avg.saturating_mul(7).saturating_add(sample)) / 8
+                })
+            });
+    }
+
+    /// Average block batch commit time, in milliseconds.
+    pub fn commit_latency_ms(&self) -> u64 {
+        self.commit_latency_us.load(Ordering::Relaxed) / 1_000
+    }
+
+    pub fn is_overloaded(&self) -> bool {
+        self.overloaded.load(Ordering::Relaxed)
+    }
+
+    /// Factor to apply to the edge work thresholds right now.
+    pub fn work_multiplier(&self) -> f64 {
+        if self.is_overloaded() {
+            self.config.work_multiplier.max(1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// Each measure as a fraction of its threshold.
+    fn pressures(&self, sample: &LoadSample) -> [(OverloadCause, f64); 3] {
+        let ratio = |value: f64, threshold: f64| {
+            if threshold <= 0.0 {
+                0.0
+            } else {
+                value / threshold
+            }
+        };
+        let percent = |len: usize, capacity: usize, limit: u8| {
+            ratio(len as f64 * 100.0, capacity as f64 * limit as f64)
+        };
+        [
+            (
+                OverloadCause::BlockQueue,
+                percent(
+                    sample.block_queue_len,
+                    sample.block_queue_capacity,
+                    self.config.block_queue_percent,
+                ),
+            ),
+            (
+                OverloadCause::ActiveElections,
+                percent(
+                    sample.active_elections,
+                    sample.election_limit,
+                    self.config.elections_percent,
+                ),
+            ),
+            (
+                OverloadCause::CommitLatency,
+                ratio(
+                    self.commit_latency_us.load(Ordering::Relaxed) as f64,
+                    self.config.commit_latency_ms as f64 * 1_000.0,
+                ),
+            ),
+        ]
+    }
+
+    /// Re-evaluate the mode from `sample`. Returns the new state on a
+    /// transition, with the measures over threshold when entering overload.
+    pub fn evaluate(&self, sample: &LoadSample) -> Option<(bool, Vec<OverloadCause>)> {
+        if !self.config.enabled {
+            return None;
+        }
+        let pressures = self.pressures(sample);
+        let causes: Vec<OverloadCause> = pressures
+            .iter()
+            .filter(|(_, pressure)| *pressure >= 1.0)
+            .map(|(cause, _)| *cause)
+            .collect();
+        let was = self.is_overloaded();
+        let now = if was {
+            pressures
+                .iter()
+                .any(|(_, pressure)| *pressure >= RECOVERY_RATIO)
+        } else {
+            !causes.is_empty()
+        };
+        if now == was {
+            return None;
+        }
+        self.overloaded.store(now, Ordering::Relaxed);
+        Some((now, causes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(block_queue_len: usize) -> LoadSample {
+        LoadSample {
+            block_queue_len,
+            block_queue_capacity: 100,
+            active_elections: 0,
+            election_limit: 100,
+        }
+    }
+
+    #[test]
+    fn enters_on_any_measure_and_leaves_with_hysteresis() {
+        let controller = OverloadController::new(OverloadConfig::default());
+        assert_eq!(controller.evaluate(&sample(79)), None);
+        assert_eq!(
+            controller.evaluate(&sample(80)),
+            Some((true, vec![OverloadCause::BlockQueue]))
+        );
+        assert_eq!(controller.work_multiplier(), 4.0);
+
+        // Below the threshold but not yet below the recovery point.
+        assert_eq!(controller.evaluate(&sample(70)), None);
+        assert_eq!(controller.evaluate(&sample(59)), Some((false, vec![])));
+        assert_eq!(controller.work_multiplier(), 1.0);
+
+        controller.record_commit(Duration::from_millis(400));
+        assert_eq!(controller.commit_latency_ms(), 400);
+        assert_eq!(
+            controller.evaluate(&sample(0)),
+            Some((true, vec![OverloadCause::CommitLatency]))
+        );
+    }
+}
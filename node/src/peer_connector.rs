@@ -10,16 +10,17 @@ use std::time::Duration;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::sync::{Mutex, RwLock};
 
-use burst_consensus::{ActiveElections, OnlineWeightSampler, RepWeightCache};
-use burst_ledger::DagFrontier;
+use burst_consensus::RepWeightCache;
 use burst_messages::PeerAddress;
 use burst_network::{MessageDedup, PeerManager};
-use burst_store_lmdb::LmdbStore;
+use burst_protocol::codec::BufferPool;
 use burst_types::BlockHash;
 
+use crate::block_prefilter::BlockPrefilter;
 use crate::connection_registry::{spawn_peer_read_loop, ConnectionRegistry};
+use crate::inbound_queue::InboundQueue;
 use crate::metrics::NodeMetrics;
-use crate::priority_queue::BlockPriorityQueue;
+use crate::vote_admission::VoteAdmissionControl;
 use crate::wire_message::{HandshakeMsg, WireMessage};
 
 /// Timeout for the initial TCP connection attempt.
@@ -34,14 +35,13 @@ const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
 pub struct PeerConnectorContext {
     pub peer_manager: Arc<RwLock<PeerManager>>,
     pub connection_registry: Arc<RwLock<ConnectionRegistry>>,
-    pub block_queue: Arc<BlockPriorityQueue>,
     pub metrics: Arc<NodeMetrics>,
-    pub active_elections: Arc<RwLock<ActiveElections>>,
-    pub rep_weights: Arc<RwLock<RepWeightCache>>,
     pub message_dedup: Arc<Mutex<MessageDedup>>,
-    pub online_weight_sampler: Arc<Mutex<OnlineWeightSampler>>,
-    pub frontier: Arc<RwLock<DagFrontier>>,
-    pub store: Arc<LmdbStore>,
+    pub inbound: Arc<InboundQueue>,
+    pub block_prefilter: Arc<Mutex<BlockPrefilter>>,
+    pub rep_weights: Arc<RwLock<RepWeightCache>>,
+    pub vote_admission: Arc<Mutex<VoteAdmissionControl>>,
+    pub read_buffers: Arc<BufferPool>,
     pub node_private_key: burst_types::PrivateKey,
     pub node_address: burst_types::WalletAddress,
     pub params_hash: BlockHash,
@@ -55,18 +55,39 @@ pub struct ConnectedPeer {
 
 /// Attempt an outbound TCP connection to `addr_str` ("ip:port"), perform the
 /// cookie handshake, register the peer in the connection registry and peer
-/// manager, and spawn a read loop.
+/// manager, and spawn a read loop. The outcome is recorded in the peer's
+/// quality figures.
 ///
 /// Returns `Ok(ConnectedPeer)` on success, `Err` on any failure.
 pub async fn connect_to_peer(
     addr_str: &str,
     ctx: &PeerConnectorContext,
 ) -> Result<ConnectedPeer, String> {
-    let stream = tokio::time::timeout(CONNECT_TIMEOUT, tokio::net::TcpStream::connect(addr_str))
+    let peer_addr = parse_peer_address(addr_str);
+    let peer_id = format!("{}:{}", peer_addr.ip, peer_addr.port);
+
+    if let Err(refusal) = ctx
+        .peer_manager
+        .read()
         .await
-        .map_err(|_| format!("connection timed out to {addr_str}"))?
-        .map_err(|e| format!("TCP connect to {addr_str} failed: {e}"))?;
+        .check_connection(&peer_addr.ip)
+    {
+        return Err(format!(
+            "not connecting to {addr_str}: {}",
+            refusal.as_str()
+        ));
+    }
+
+    let result = dial(addr_str, peer_addr, ctx).await;
+    ctx.peer_manager
+        .write()
+        .await
+        .record_handshake(&peer_id, result.is_ok());
+    result
+}
 
+/// Split `"ip:port"`, defaulting the port to 7075.
+fn parse_peer_address(addr_str: &str) -> PeerAddress {
     let parts: Vec<&str> = addr_str.rsplitn(2, ':').collect();
     let (port, ip) = if parts.len() == 2 {
         (
@@ -76,41 +97,28 @@ pub async fn connect_to_peer(
     } else {
         (7075, addr_str.to_string())
     };
-    let peer_addr = PeerAddress {
-        ip: ip.clone(),
-        port,
-    };
-    let peer_id = format!("{ip}:{port}");
+    PeerAddress { ip, port }
+}
+
+/// The connect, handshake and registration steps of [`connect_to_peer`].
+async fn dial(
+    addr_str: &str,
+    peer_addr: PeerAddress,
+    ctx: &PeerConnectorContext,
+) -> Result<ConnectedPeer, String> {
+    let ip = peer_addr.ip.clone();
+    let peer_id = format!("{}:{}", peer_addr.ip, peer_addr.port);
+
+    let stream = tokio::time::timeout(CONNECT_TIMEOUT, tokio::net::TcpStream::connect(addr_str))
+        .await
+        .map_err(|_| format!("connection timed out to {addr_str}"))?
+        .map_err(|e| format!("TCP connect to {addr_str} failed: {e}"))?;
 
     let (read_half, mut write_half) = stream.into_split();
 
     // Read the cookie challenge from the peer
     let mut reader = tokio::io::BufReader::new(read_half);
-    let cookie_opt = {
-        let mut len_buf = [0u8; 4];
-        match tokio::time::timeout(HANDSHAKE_TIMEOUT, reader.read_exact(&mut len_buf)).await {
-            Ok(Ok(_)) => {
-                let body_len = u32::from_be_bytes(len_buf) as usize;
-                if body_len > 0 && body_len < 65536 {
-                    let mut body = vec![0u8; body_len];
-                    if reader.read_exact(&mut body).await.is_ok() {
-                        if let Ok(WireMessage::Handshake(hs)) =
-                            bincode::deserialize::<WireMessage>(&body)
-                        {
-                            hs.cookie
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            }
-            _ => None,
-        }
-    };
+    let cookie_opt = read_handshake(&mut reader).await.and_then(|hs| hs.cookie);
 
     // Sign and send cookie response
     if let Some(cookie) = cookie_opt {
@@ -153,24 +161,60 @@ pub async fn connect_to_peer(
     spawn_peer_read_loop(
         peer_id.clone(),
         read_half,
-        Arc::clone(&ctx.block_queue),
         Arc::clone(&ctx.connection_registry),
         Arc::clone(&ctx.peer_manager),
         Arc::clone(&ctx.metrics),
-        Arc::clone(&ctx.active_elections),
-        Arc::clone(&ctx.rep_weights),
         Arc::clone(&ctx.message_dedup),
-        Arc::clone(&ctx.online_weight_sampler),
+        Arc::clone(&ctx.inbound),
+        Arc::clone(&ctx.block_prefilter),
+        Arc::clone(&ctx.rep_weights),
+        Arc::clone(&ctx.vote_admission),
         None,
         ip.clone(),
-        Arc::clone(&ctx.frontier),
-        Arc::clone(&ctx.store),
-        ctx.params_hash,
+        Arc::clone(&ctx.read_buffers),
     );
 
     Ok(ConnectedPeer { peer_id, peer_addr })
 }
 
+/// Read the handshake a peer sends first on a new connection, if it sends
+/// a well-formed one in time.
+async fn read_handshake<R: AsyncReadExt + Unpin>(reader: &mut R) -> Option<HandshakeMsg> {
+    let mut len_buf = [0u8; 4];
+    tokio::time::timeout(HANDSHAKE_TIMEOUT, reader.read_exact(&mut len_buf))
+        .await
+        .ok()?
+        .ok()?;
+    let body_len = u32::from_be_bytes(len_buf) as usize;
+    if body_len == 0 || body_len >= 65536 {
+        return None;
+    }
+    let mut body = vec![0u8; body_len];
+    reader.read_exact(&mut body).await.ok()?;
+    match bincode::deserialize::<WireMessage>(&body) {
+        Ok(WireMessage::Handshake(hs)) => Some(hs),
+        _ => None,
+    }
+}
+
+/// Check that `addr` runs a node on our network before it is treated as a
+/// connection candidate: connect, wait for its handshake, compare protocol
+/// parameters and hang up.
+pub async fn probe_peer(addr: &PeerAddress, params_hash: BlockHash) -> bool {
+    let target = match addr.ip.parse::<std::net::IpAddr>() {
+        Ok(ip) => std::net::SocketAddr::new(ip, addr.port),
+        Err(_) => return false,
+    };
+    let Ok(Ok(mut stream)) =
+        tokio::time::timeout(CONNECT_TIMEOUT, tokio::net::TcpStream::connect(target)).await
+    else {
+        return false;
+    };
+    read_handshake(&mut stream)
+        .await
+        .is_some_and(|hs| hs.params_hash == params_hash)
+}
+
 /// Check if the peer is already connected by parsing the address string.
 pub async fn is_peer_connected(addr_str: &str, pm: &RwLock<PeerManager>) -> bool {
     let parts: Vec<&str> = addr_str.rsplitn(2, ':').collect();
@@ -11,7 +11,8 @@ use tokio::sync::{Mutex, Notify};
 
 use burst_crypto::blake2b_256;
 use burst_ledger::StateBlock;
-use burst_types::BlockHash;
+use burst_types::memory::entry_bytes;
+use burst_types::{BlockHash, ContainerUsage, MemoryAccounting};
 
 /// A block wrapped with its PoW difficulty score for priority ordering.
 struct PrioritizedBlock {
@@ -45,6 +46,19 @@ impl PartialOrd for PrioritizedBlock {
     }
 }
 
+/// Queued blocks plus the insertion counter used for FIFO tiebreaking.
+struct QueuedBlocks(BinaryHeap<PrioritizedBlock>, u64);
+
+impl MemoryAccounting for QueuedBlocks {
+    fn element_count(&self) -> usize {
+        self.0.len()
+    }
+
+    fn approximate_bytes(&self) -> usize {
+        entry_bytes::<PrioritizedBlock>(self.0.len())
+    }
+}
+
 /// Compute the PoW difficulty score for a block.
 ///
 /// `Blake2b(block_hash || nonce_le_bytes)` interpreted as u64 LE.
@@ -66,7 +80,7 @@ pub fn work_difficulty(block_hash: &BlockHash, nonce: u64) -> u64 {
 /// will `await` until a block is available (like an async channel, but ordered
 /// by priority instead of FIFO).
 pub struct BlockPriorityQueue {
-    heap: Mutex<(BinaryHeap<PrioritizedBlock>, u64)>,
+    heap: Mutex<QueuedBlocks>,
     capacity: usize,
     notify: Notify,
 }
@@ -75,7 +89,7 @@ impl BlockPriorityQueue {
     /// Create a new priority queue with the given maximum capacity.
     pub fn new(capacity: usize) -> Self {
         Self {
-            heap: Mutex::new((BinaryHeap::with_capacity(capacity), 0)),
+            heap: Mutex::new(QueuedBlocks(BinaryHeap::with_capacity(capacity), 0)),
             capacity,
             notify: Notify::new(),
         }
@@ -88,7 +102,7 @@ impl BlockPriorityQueue {
         let difficulty = work_difficulty(&block.hash, block.work);
 
         let mut guard = self.heap.lock().await;
-        let (heap, seq) = &mut *guard;
+        let QueuedBlocks(heap, seq) = &mut *guard;
         if heap.len() >= self.capacity {
             return false;
         }
@@ -114,7 +128,7 @@ impl BlockPriorityQueue {
             Ok(g) => g,
             Err(_) => return false,
         };
-        let (heap, seq) = &mut *guard;
+        let QueuedBlocks(heap, seq) = &mut *guard;
         if heap.len() >= self.capacity {
             return false;
         }
@@ -151,6 +165,11 @@ impl BlockPriorityQueue {
         guard.0.pop().map(|entry| entry.block)
     }
 
+    /// Maximum number of blocks the queue holds.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
     /// Current number of blocks in the queue.
     pub async fn len(&self) -> usize {
         self.heap.lock().await.0.len()
@@ -160,6 +179,11 @@ impl BlockPriorityQueue {
     pub async fn is_empty(&self) -> bool {
         self.heap.lock().await.0.is_empty()
     }
+
+    /// Queued block count and approximate memory.
+    pub async fn memory_usage(&self) -> ContainerUsage {
+        self.heap.lock().await.memory_usage()
+    }
 }
 
 #[cfg(test)]
@@ -195,6 +219,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1_000_000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: nonce,
             signature: Signature([1u8; 64]),
             hash: BlockHash::ZERO,
@@ -217,6 +247,12 @@ mod tests {
             transaction: TxHash::new([difficulty as u8; 32]),
             timestamp: Timestamp::new(1_000_000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([1u8; 64]),
             hash: BlockHash::ZERO,
@@ -0,0 +1,145 @@
+//! Quorum certificates for cemented blocks.
+//!
+//! When a block is cemented, the final votes that confirmed it are collected
+//! from the [`FinalVoteArchive`] and stored with each representative's
+//! weight as a [`QuorumCertificate`]. The archive is bounded and trims old
+//! roots; the certificate is kept with the block, so the confirmation can
+//! still be audited (and served in block proofs) afterwards.
+
+use burst_consensus::RepWeightCache;
+use burst_ledger::{CertifiedVote, ProofVote, QuorumCertificate};
+use burst_store::{FinalVoteStore, QuorumCertificateStore, StoreError};
+use burst_types::BlockHash;
+
+use crate::final_votes::FinalVoteArchive;
+
+/// Build the certificate for `hash` from the archived final votes. Votes
+/// from representatives without weight are left out. Returns `None` when no
+/// weighted final vote is archived for the block.
+pub fn build_quorum_certificate<S: FinalVoteStore>(
+    archive: &FinalVoteArchive<S>,
+    weights: &RepWeightCache,
+    hash: &BlockHash,
+    now_secs: u64,
+) -> Result<Option<QuorumCertificate>, StoreError> {
+    let votes: Vec<CertifiedVote> = archive
+        .replay(std::slice::from_ref(hash))?
+        .into_iter()
+        .filter(|vote| vote.is_final)
+        .filter_map(|vote| {
+            let weight = weights.weight(&vote.voter);
+            if weight == 0 {
+                return None;
+            }
+            Some(CertifiedVote {
+                vote: ProofVote {
+                    voter: vote.voter,
+                    block_hashes: vote.block_hashes,
+                    timestamp: vote.timestamp,
+                    signature: vote.signature,
+                },
+                weight,
+            })
+        })
+        .collect();
+    if votes.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(QuorumCertificate {
+        block_hash: *hash,
+        votes,
+        cemented_at: now_secs,
+    }))
+}
+
+/// Build and persist the certificate for a freshly cemented block. An
+/// existing certificate is left alone. Returns whether one was written.
+pub fn record_quorum_certificate<S: FinalVoteStore, C: QuorumCertificateStore>(
+    archive: &FinalVoteArchive<S>,
+    certificates: &C,
+    weights: &RepWeightCache,
+    hash: &BlockHash,
+    now_secs: u64,
+) -> Result<bool, StoreError> {
+    if certificates.has_certificate(hash)? {
+        return Ok(false);
+    }
+    let Some(certificate) = build_quorum_certificate(archive, weights, hash, now_secs)? else {
+        return Ok(false);
+    };
+    let bytes =
+        bincode::serialize(&certificate).map_err(|e| StoreError::Serialization(e.to_string()))?;
+    certificates.put_certificate(hash, &bytes)?;
+    Ok(true)
+}
+
+/// Load the stored certificate for `hash`, if any.
+pub fn load_quorum_certificate<C: QuorumCertificateStore>(
+    certificates: &C,
+    hash: &BlockHash,
+) -> Result<Option<QuorumCertificate>, StoreError> {
+    match certificates.get_certificate(hash) {
+        Ok(bytes) => bincode::deserialize(&bytes)
+            .map(Some)
+            .map_err(|e| StoreError::Serialization(e.to_string())),
+        Err(StoreError::NotFound(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_store_lmdb::LmdbEnvironment;
+    use burst_types::{Signature, WalletAddress};
+
+    use crate::wire_message::WireVote;
+
+    fn final_vote(voter: &WalletAddress, hash: BlockHash) -> WireVote {
+        WireVote {
+            voter: voter.clone(),
+            block_hashes: vec![hash],
+            is_final: true,
+            timestamp: 1_000,
+            sequence: 1,
+            signature: Signature([7u8; 64]),
+        }
+    }
+
+    #[test]
+    fn certificate_outlives_the_archive_and_skips_unweighted_voters() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+        let archive = FinalVoteArchive::new(env.final_vote_store(), 1);
+        let certificates = env.quorum_certificate_store();
+        let hash = BlockHash::new([1u8; 32]);
+        let alice = WalletAddress::new("brst_test_rep_alice");
+        let nobody = WalletAddress::new("brst_test_rep_nobody");
+
+        let mut weights = RepWeightCache::new();
+        weights.add_weight(&alice, 700);
+
+        archive.record(&final_vote(&nobody, hash)).unwrap();
+        assert!(!record_quorum_certificate(&archive, &certificates, &weights, &hash, 5).unwrap());
+
+        archive.record(&final_vote(&alice, hash)).unwrap();
+        assert!(record_quorum_certificate(&archive, &certificates, &weights, &hash, 5).unwrap());
+        assert!(!record_quorum_certificate(&archive, &certificates, &weights, &hash, 6).unwrap());
+
+        // Push the block's votes out of the bounded archive.
+        let other = BlockHash::new([2u8; 32]);
+        archive.record(&final_vote(&alice, other)).unwrap();
+        assert!(archive.replay(&[hash]).unwrap().is_empty());
+
+        let certificate = load_quorum_certificate(&certificates, &hash)
+            .unwrap()
+            .unwrap();
+        assert_eq!(certificate.votes.len(), 1);
+        assert_eq!(certificate.votes[0].vote.voter, alice);
+        assert_eq!(certificate.total_weight(), 700);
+        assert_eq!(certificate.cemented_at, 5);
+        assert!(load_quorum_certificate(&certificates, &other)
+            .unwrap()
+            .is_none());
+    }
+}
@@ -50,6 +50,11 @@ impl RecentlyConfirmed {
         self.set.contains(hash)
     }
 
+    /// The most recently inserted hash, if any.
+    pub fn latest(&self) -> Option<BlockHash> {
+        self.order.back().copied()
+    }
+
     /// Number of entries in the cache.
     pub fn len(&self) -> usize {
         self.set.len()
@@ -0,0 +1,138 @@
+//! Persistent, encrypted representative key.
+//!
+//! The key that signs this node's votes lives in a keystore file (the wallet
+//! keystore format) under [`REPRESENTATIVE_KEY_LABEL`], so the node votes as
+//! the same representative across restarts and delegated weight accumulates.
+//! The passphrase comes from [`REPRESENTATIVE_KEY_PASSWORD_ENV`].
+
+use std::path::Path;
+
+use burst_types::{KeyPair, PrivateKey, Timestamp, WalletAddress};
+use burst_wallet_core::keystore::{self, KdfParams, KeyKind, KeystoreFile};
+
+use crate::error::NodeError;
+
+/// Keystore label of the representative key.
+pub const REPRESENTATIVE_KEY_LABEL: &str = "representative";
+
+/// Environment variable holding the representative key file passphrase.
+pub const REPRESENTATIVE_KEY_PASSWORD_ENV: &str = "BURST_REPRESENTATIVE_KEY_PASSWORD";
+
+/// Domain separator for rotation notices.
+const ROTATION_DOMAIN: &[u8] = b"burst-representative-rotation";
+
+/// Load the representative key from `path`, or generate one and write it
+/// there (encrypted under `password` with `params`) if the file is missing.
+pub fn load_or_create(
+    path: &Path,
+    password: &str,
+    params: &KdfParams,
+) -> Result<KeyPair, NodeError> {
+    if path.exists() {
+        let keystore = keystore::load_keystore(path)?;
+        let secret = keystore.decrypt_key(REPRESENTATIVE_KEY_LABEL, password)?;
+        return Ok(burst_crypto::keypair_from_private(PrivateKey(secret)));
+    }
+
+    let keys = burst_crypto::generate_keypair();
+    let mut keystore = KeystoreFile::new();
+    keystore.add_key(
+        REPRESENTATIVE_KEY_LABEL,
+        KeyKind::NodeIdentity,
+        &keys.private.0,
+        password,
+        params,
+    )?;
+    save(path, &keystore)?;
+    Ok(keys)
+}
+
+/// Replace the representative key in `path` with a freshly generated one.
+///
+/// `password` must unlock the current key; the new key is encrypted under
+/// it with the same Argon2id parameters, and other keys in the file are
+/// kept. Returns `(previous, new)`.
+pub fn rotate(path: &Path, password: &str) -> Result<(KeyPair, KeyPair), NodeError> {
+    let mut keystore = keystore::load_keystore(path)?;
+    let secret = keystore.decrypt_key(REPRESENTATIVE_KEY_LABEL, password)?;
+    let previous = burst_crypto::keypair_from_private(PrivateKey(secret));
+    let params = keystore
+        .entry(REPRESENTATIVE_KEY_LABEL)
+        .map(|entry| entry.crypto.kdf_params)
+        .unwrap_or_default();
+
+    let keys = burst_crypto::generate_keypair();
+    keystore.remove_key(REPRESENTATIVE_KEY_LABEL)?;
+    keystore.add_key(
+        REPRESENTATIVE_KEY_LABEL,
+        KeyKind::NodeIdentity,
+        &keys.private.0,
+        password,
+        &params,
+    )?;
+    save(path, &keystore)?;
+    Ok((previous, keys))
+}
+
+/// Digest both keys sign to announce a move from `previous` to
+/// `representative`.
+pub fn rotation_digest(
+    previous: &WalletAddress,
+    representative: &WalletAddress,
+    rotated_at: Timestamp,
+) -> [u8; 32] {
+    burst_crypto::blake2b_256_multi(&[
+        ROTATION_DOMAIN,
+        previous.as_str().as_bytes(),
+        representative.as_str().as_bytes(),
+        &rotated_at.as_secs().to_be_bytes(),
+    ])
+}
+
+/// Write the keystore next to `path` and rename it into place, so a crash
+/// mid-write never leaves a truncated key file.
+fn save(path: &Path, keystore: &KeystoreFile) -> Result<(), NodeError> {
+    let tmp = path.with_extension("tmp");
+    keystore::save_keystore(keystore, &tmp)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fast() -> KdfParams {
+        KdfParams {
+            memory: 64,
+            iterations: 1,
+            parallelism: 1,
+        }
+    }
+
+    #[test]
+    fn key_persists_across_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rep.json");
+        let created = load_or_create(&path, "pw", &fast()).unwrap();
+        let loaded = load_or_create(&path, "pw", &fast()).unwrap();
+        assert_eq!(created.public, loaded.public);
+        assert!(load_or_create(&path, "wrong", &fast()).is_err());
+    }
+
+    #[test]
+    fn rotate_replaces_key_under_same_password() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rep.json");
+        let original = load_or_create(&path, "pw", &fast()).unwrap();
+
+        assert!(rotate(&path, "wrong").is_err());
+        let (previous, current) = rotate(&path, "pw").unwrap();
+        assert_eq!(previous.public, original.public);
+        assert_ne!(current.public, original.public);
+        assert_eq!(
+            load_or_create(&path, "pw", &fast()).unwrap().public,
+            current.public
+        );
+    }
+}
@@ -0,0 +1,394 @@
+//! Revocation notices — gossiping fraud verdicts between nodes.
+//!
+//! A challenge is resolved by the verification vote block that completes
+//! its tally. Each node's orchestrator reaches the verdict on its own, so a
+//! node whose verification state lags would keep honouring the fraudulent
+//! wallet's TRST. Once the deciding vote block is cemented, the node that
+//! saw the verdict signs a [`RevocationNotice`] carrying that block and its
+//! [`QuorumCertificate`] and floods it.
+//!
+//! A receiving node trusts the certificate, not the issuer: the block must
+//! be an illegitimate verdict on the revoked wallet, every certified vote
+//! must be signed for it, and the voters must hold a quorum of the node's
+//! own representative weight. A wallet is revoked at most once; the applied
+//! notice is persisted so replays are dropped across restarts.
+
+use burst_consensus::RepWeightCache;
+use burst_ledger::{BlockType, QuorumCertificate, StateBlock};
+use burst_store::account::AccountStore;
+use burst_store::{StoreError, VerificationStore};
+use burst_store_lmdb::LmdbStore;
+use burst_trst::TrstEngine;
+use burst_types::{PrivateKey, PublicKey, Signature, TransitionReason, WalletAddress, WalletState};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::ledger_bridge::extract_receiver_from_link;
+use crate::merger_graph::persist_merger_graph_deltas;
+use crate::node::transition_account_state;
+use crate::trst_portfolios::persist_dirty_portfolios;
+
+/// Domain separator for the issuer signature.
+const NOTICE_DOMAIN: &[u8] = b"burst-revocation-notice";
+
+/// Verification vote byte for a "legitimate" verdict.
+const VOTE_LEGITIMATE: u8 = 0;
+
+/// A signed claim that `wallet` was revoked for fraud, with the quorum
+/// certificate of the vote block that decided it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RevocationNotice {
+    pub wallet: WalletAddress,
+    /// The verification vote block that completed the fraud verdict.
+    pub verdict_block: StateBlock,
+    /// Final votes that cemented `verdict_block`.
+    pub certificate: QuorumCertificate,
+    /// Node that issued the notice.
+    pub issuer: WalletAddress,
+    /// Unix seconds.
+    pub issued_at: u64,
+    pub signature: Signature,
+}
+
+/// Why a revocation notice was not accepted.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum RevocationNoticeError {
+    #[error("notice is not signed by its issuer")]
+    BadSignature,
+    #[error("verdict block hash does not match its contents")]
+    BlockHashMismatch,
+    #[error("verdict block is not an illegitimate verification vote on the wallet")]
+    NotAFraudVerdict,
+    #[error("certificate is for a different block")]
+    CertificateMismatch,
+    #[error("certificate vote from {0} is not a valid vote for the verdict block")]
+    InvalidVote(WalletAddress),
+    #[error("certificate carries {weight} weight, {required} required")]
+    InsufficientWeight { weight: u128, required: u128 },
+}
+
+impl RevocationNotice {
+    /// Build and sign a notice for `wallet` as the holder of `issuer_key`.
+    pub fn sign(
+        wallet: WalletAddress,
+        verdict_block: StateBlock,
+        certificate: QuorumCertificate,
+        issuer_key: &PrivateKey,
+        issued_at: u64,
+    ) -> Self {
+        let issuer = burst_crypto::derive_address(&burst_crypto::public_from_private(issuer_key));
+        let mut notice = Self {
+            wallet,
+            verdict_block,
+            certificate,
+            issuer,
+            issued_at,
+            signature: Signature([0u8; 64]),
+        };
+        notice.signature = burst_crypto::sign_message(&notice.signed_message(), issuer_key);
+        notice
+    }
+
+    /// The bytes the issuer signs: domain tag, revoked address, verdict
+    /// block hash and issue time (big-endian).
+    pub fn signed_message(&self) -> Vec<u8> {
+        let wallet = self.wallet.as_str().as_bytes();
+        let mut msg = Vec::with_capacity(NOTICE_DOMAIN.len() + wallet.len() + 40);
+        msg.extend_from_slice(NOTICE_DOMAIN);
+        msg.extend_from_slice(wallet);
+        msg.extend_from_slice(self.verdict_block.hash.as_bytes());
+        msg.extend_from_slice(&self.issued_at.to_be_bytes());
+        msg
+    }
+
+    /// Check the notice against the local representative weights. Returns
+    /// the weight that vouched for the verdict.
+    ///
+    /// Certified weights are ignored: each distinct voter counts with the
+    /// weight `weights` gives it, and the total must reach `quorum_delta`.
+    pub fn verify(
+        &self,
+        weights: &RepWeightCache,
+        quorum_delta: u128,
+    ) -> Result<u128, RevocationNoticeError> {
+        let issuer_key = burst_crypto::decode_address(self.issuer.as_str())
+            .ok_or(RevocationNoticeError::BadSignature)?;
+        if !burst_crypto::verify_signature(
+            &self.signed_message(),
+            &self.signature,
+            &PublicKey(issuer_key),
+        ) {
+            return Err(RevocationNoticeError::BadSignature);
+        }
+
+        let block = &self.verdict_block;
+        if block.compute_hash() != block.hash {
+            return Err(RevocationNoticeError::BlockHashMismatch);
+        }
+        let is_verdict = block.block_type == BlockType::VerificationVote
+            && block.transaction.as_bytes()[0] != VOTE_LEGITIMATE
+            && extract_receiver_from_link(&block.link).as_ref() == Some(&self.wallet);
+        if !is_verdict {
+            return Err(RevocationNoticeError::NotAFraudVerdict);
+        }
+        if self.certificate.block_hash != block.hash {
+            return Err(RevocationNoticeError::CertificateMismatch);
+        }
+
+        let mut counted: Vec<&WalletAddress> = Vec::with_capacity(self.certificate.votes.len());
+        let mut weight = 0u128;
+        for entry in &self.certificate.votes {
+            let vote = &entry.vote;
+            if !vote.block_hashes.contains(&block.hash) || !vote.verify_signature() {
+                return Err(RevocationNoticeError::InvalidVote(vote.voter.clone()));
+            }
+            if counted.contains(&&vote.voter) {
+                continue;
+            }
+            counted.push(&vote.voter);
+            weight = weight.saturating_add(weights.weight(&vote.voter));
+        }
+        if weight < quorum_delta || weight == 0 {
+            return Err(RevocationNoticeError::InsufficientWeight {
+                weight,
+                required: quorum_delta,
+            });
+        }
+        Ok(weight)
+    }
+}
+
+/// Revoke all TRST originating from `wallet` and mark its account Revoked.
+/// Returns the amount revoked; a wallet revoked before yields nothing.
+pub fn revoke_fraudulent_wallet(
+    store: &LmdbStore,
+    trst_engine: &mut TrstEngine,
+    wallet: &WalletAddress,
+) -> u128 {
+    let revocations = trst_engine.revoke_by_origin(wallet);
+    if let Err(e) = persist_merger_graph_deltas(store, &mut trst_engine.merger_graph) {
+        tracing::warn!(%wallet, "failed to persist revoked origins: {e}");
+    }
+    if let Err(e) = persist_dirty_portfolios(store, trst_engine) {
+        tracing::warn!(%wallet, "failed to persist revoked TRST portfolios: {e}");
+    }
+    let total_revoked: u128 = revocations.iter().map(|r| r.revoked_amount).sum();
+    if !revocations.is_empty() {
+        tracing::warn!(
+            %wallet,
+            revoked_count = revocations.len(),
+            total_revoked,
+            "TRST revoked for confirmed fraud"
+        );
+    }
+    if let Ok(mut acct) = store.account_store().get_account(wallet) {
+        transition_account_state(
+            store,
+            &mut acct,
+            WalletState::Revoked,
+            TransitionReason::FraudConfirmed,
+        );
+        acct.revoked_trst = acct.revoked_trst.saturating_add(total_revoked);
+        acct.trst_balance = acct.trst_balance.saturating_sub(total_revoked);
+        if let Err(e) = store.account_store().put_account(&acct) {
+            tracing::error!(%wallet, "failed to persist account Revoked state: {e}");
+        }
+    }
+    total_revoked
+}
+
+/// Load the notice applied for `wallet`, if any.
+pub fn load_revocation_notice(
+    store: &impl VerificationStore,
+    wallet: &WalletAddress,
+) -> Result<Option<RevocationNotice>, StoreError> {
+    store
+        .get_revocation_notice(wallet)?
+        .map(|bytes| {
+            bincode::deserialize(&bytes).map_err(|e| StoreError::Serialization(e.to_string()))
+        })
+        .transpose()
+}
+
+/// Persist an applied notice. Later notices for the same wallet are
+/// replays and are dropped before verification.
+pub fn save_revocation_notice(
+    store: &impl VerificationStore,
+    notice: &RevocationNotice,
+) -> Result<(), StoreError> {
+    let bytes = bincode::serialize(notice).map_err(|e| StoreError::Serialization(e.to_string()))?;
+    store.put_revocation_notice(&notice.wallet, &bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_ledger::{CertifiedVote, ProofVote, CURRENT_BLOCK_VERSION};
+    use burst_store_lmdb::LmdbEnvironment;
+    use burst_types::{BlockHash, KeyPair, Timestamp, TxHash};
+
+    fn verdict_block(target: &KeyPair, vote: u8) -> StateBlock {
+        let voter = burst_crypto::generate_keypair();
+        let mut block = StateBlock {
+            version: CURRENT_BLOCK_VERSION,
+            block_type: BlockType::VerificationVote,
+            account: burst_crypto::derive_address(&voter.public),
+            previous: BlockHash::new([1u8; 32]),
+            representative: burst_crypto::derive_address(&voter.public),
+            brn_balance: 0,
+            trst_balance: 0,
+            link: BlockHash::new(target.public.0),
+            origin: TxHash::ZERO,
+            transaction: TxHash::new([vote; 32]),
+            timestamp: Timestamp::new(100),
+            params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
+            work: 0,
+            signature: Signature([0u8; 64]),
+            hash: BlockHash::ZERO,
+        };
+        block.hash = block.compute_hash();
+        block
+    }
+
+    fn certify(block: &StateBlock, reps: &[&KeyPair]) -> QuorumCertificate {
+        let votes = reps
+            .iter()
+            .map(|rep| {
+                let mut vote = ProofVote {
+                    voter: burst_crypto::derive_address(&rep.public),
+                    block_hashes: vec![block.hash],
+                    timestamp: 200,
+                    signature: Signature([0u8; 64]),
+                };
+                vote.signature = burst_crypto::sign_message(&vote.signed_message(), &rep.private);
+                CertifiedVote {
+                    vote,
+                    weight: u128::MAX,
+                }
+            })
+            .collect();
+        QuorumCertificate {
+            block_hash: block.hash,
+            votes,
+            cemented_at: 300,
+        }
+    }
+
+    struct Fixture {
+        target: KeyPair,
+        issuer: KeyPair,
+        reps: [KeyPair; 2],
+        weights: RepWeightCache,
+    }
+
+    fn fixture() -> Fixture {
+        let reps = [
+            burst_crypto::generate_keypair(),
+            burst_crypto::generate_keypair(),
+        ];
+        let mut weights = RepWeightCache::new();
+        weights.add_weight(&burst_crypto::derive_address(&reps[0].public), 600);
+        weights.add_weight(&burst_crypto::derive_address(&reps[1].public), 400);
+        Fixture {
+            target: burst_crypto::generate_keypair(),
+            issuer: burst_crypto::generate_keypair(),
+            reps,
+            weights,
+        }
+    }
+
+    fn notice(f: &Fixture, vote: u8, reps: &[&KeyPair]) -> RevocationNotice {
+        let block = verdict_block(&f.target, vote);
+        let certificate = certify(&block, reps);
+        RevocationNotice::sign(
+            burst_crypto::derive_address(&f.target.public),
+            block,
+            certificate,
+            &f.issuer.private,
+            500,
+        )
+    }
+
+    #[test]
+    fn quorum_is_counted_with_local_weights() {
+        let f = fixture();
+        let both = notice(&f, 1, &[&f.reps[0], &f.reps[1]]);
+        assert_eq!(both.verify(&f.weights, 670), Ok(1_000));
+
+        // The certificate claims u128::MAX per vote; only local weight counts.
+        let one = notice(&f, 1, &[&f.reps[1]]);
+        assert_eq!(
+            one.verify(&f.weights, 670),
+            Err(RevocationNoticeError::InsufficientWeight {
+                weight: 400,
+                required: 670,
+            })
+        );
+
+        // Repeating a vote does not add weight.
+        let mut repeated = one.clone();
+        repeated
+            .certificate
+            .votes
+            .push(repeated.certificate.votes[0].clone());
+        assert!(matches!(
+            repeated.verify(&f.weights, 670),
+            Err(RevocationNoticeError::InsufficientWeight { weight: 400, .. })
+        ));
+    }
+
+    #[test]
+    fn tampered_notices_are_rejected() {
+        let f = fixture();
+        let valid = notice(&f, 1, &[&f.reps[0], &f.reps[1]]);
+
+        let mut retargeted = valid.clone();
+        retargeted.wallet = burst_crypto::derive_address(&f.issuer.public);
+        assert_eq!(
+            retargeted.verify(&f.weights, 670),
+            Err(RevocationNoticeError::BadSignature)
+        );
+
+        let legitimate = notice(&f, VOTE_LEGITIMATE, &[&f.reps[0], &f.reps[1]]);
+        assert_eq!(
+            legitimate.verify(&f.weights, 670),
+            Err(RevocationNoticeError::NotAFraudVerdict)
+        );
+
+        let mut forged = valid.clone();
+        forged.certificate.votes[0].vote.timestamp += 1;
+        assert!(matches!(
+            forged.verify(&f.weights, 670),
+            Err(RevocationNoticeError::InvalidVote(_))
+        ));
+
+        let mut swapped = valid;
+        swapped.verdict_block.timestamp = Timestamp::new(101);
+        assert_eq!(
+            swapped.verify(&f.weights, 670),
+            Err(RevocationNoticeError::BlockHashMismatch)
+        );
+    }
+
+    #[test]
+    fn applied_notice_roundtrips_through_the_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+        let store = env.verification_store();
+        let f = fixture();
+        let n = notice(&f, 1, &[&f.reps[0]]);
+
+        assert!(load_revocation_notice(&store, &n.wallet).unwrap().is_none());
+        save_revocation_notice(&store, &n).unwrap();
+        let loaded = load_revocation_notice(&store, &n.wallet).unwrap().unwrap();
+        assert_eq!(loaded.verdict_block.hash, n.verdict_block.hash);
+        assert_eq!(loaded.certificate, n.certificate);
+        assert_eq!(loaded.signature, n.signature);
+    }
+}
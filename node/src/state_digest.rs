@@ -0,0 +1,287 @@
+//! State digests — catching silent divergence between nodes.
+//!
+//! The node periodically digests its cemented state: every account's
+//! cemented height and frontier, and the representative weights. Accounts
+//! and representatives are spread over [`STATE_DIGEST_BUCKETS`] buckets by
+//! a hash of their address, and each bucket is hashed on its own with its
+//! entries in address order, so two nodes with the same cemented ledger get
+//! the same digest whatever order they learned it in.
+//!
+//! Digests travel in telemetry. A peer whose digest at the same cemented
+//! count differs from ours has diverged, and the first differing bucket
+//! narrows the search to one slice of the account space.
+
+use std::collections::{BTreeMap, HashMap};
+
+use burst_store::account::AccountStore;
+use burst_store::block::BlockStore;
+use burst_store::StoreError;
+use burst_types::{BlockHash, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+/// Buckets the account space is split into.
+pub const STATE_DIGEST_BUCKETS: usize = 16;
+
+/// Digests kept for comparison with peers that lag behind.
+pub const STATE_DIGEST_HISTORY: usize = 32;
+
+/// Domain tag hashed into every bucket and root.
+const STATE_DIGEST_DOMAIN: &[u8] = b"burst-state-digest";
+
+const ACCOUNT_ENTRY: u8 = b'a';
+const WEIGHT_ENTRY: u8 = b'w';
+
+/// Per-bucket hashes of the cemented state at one cemented count.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDigest {
+    /// Cemented blocks across all accounts.
+    pub cemented_count: u64,
+    /// One hash per bucket, see [`bucket_of`].
+    pub buckets: Vec<BlockHash>,
+}
+
+impl StateDigest {
+    /// A single hash over the cemented count and every bucket.
+    pub fn root(&self) -> BlockHash {
+        let mut parts: Vec<&[u8]> = Vec::with_capacity(self.buckets.len() + 2);
+        let count = self.cemented_count.to_be_bytes();
+        parts.push(STATE_DIGEST_DOMAIN);
+        parts.push(&count);
+        parts.extend(self.buckets.iter().map(|b| b.as_bytes().as_slice()));
+        BlockHash::new(burst_crypto::blake2b_256_multi(&parts))
+    }
+
+    /// The first bucket in which `other` differs, if both digests are for
+    /// the same cemented count. A digest with the wrong number of buckets
+    /// differs in bucket 0.
+    pub fn first_mismatch(&self, other: &StateDigest) -> Option<usize> {
+        if self.cemented_count != other.cemented_count {
+            return None;
+        }
+        if self.buckets.len() != other.buckets.len() {
+            return Some(0);
+        }
+        self.buckets
+            .iter()
+            .zip(&other.buckets)
+            .position(|(ours, theirs)| ours != theirs)
+    }
+}
+
+/// The bucket an account or representative falls in: the top four bits of
+/// the hash of its address.
+pub fn bucket_of(address: &WalletAddress) -> usize {
+    let hash = burst_crypto::blake2b_256(address.as_str().as_bytes());
+    (hash[0] as usize * STATE_DIGEST_BUCKETS) >> 8
+}
+
+/// The first and last address-hash byte covered by `bucket`.
+pub fn bucket_range(bucket: usize) -> (u8, u8) {
+    let width = 256 / STATE_DIGEST_BUCKETS;
+    let first = bucket * width;
+    (first as u8, (first + width - 1) as u8)
+}
+
+/// Digest the cemented ledger and the representative weights.
+///
+/// As for checkpoints, an account's frontier is the block at its
+/// confirmation height, and accounts with nothing cemented are left out.
+pub fn take_state_digest<A: AccountStore, B: BlockStore>(
+    accounts: &A,
+    blocks: &B,
+    weights: &HashMap<WalletAddress, u128>,
+) -> Result<StateDigest, StoreError> {
+    let mut entries: Vec<Vec<(&str, u8, Vec<u8>)>> = vec![Vec::new(); STATE_DIGEST_BUCKETS];
+    let accounts = accounts.iter_accounts()?;
+    let mut cemented_count = 0u64;
+    for info in &accounts {
+        if info.confirmation_height == 0 {
+            continue;
+        }
+        cemented_count = cemented_count.saturating_add(info.confirmation_height);
+        let frontier = if info.confirmation_height == info.block_count {
+            Some(info.head)
+        } else {
+            blocks.block_at_height(&info.address, info.confirmation_height)?
+        };
+        let mut value = info.confirmation_height.to_be_bytes().to_vec();
+        value.extend_from_slice(frontier.unwrap_or(BlockHash::ZERO).as_bytes());
+        entries[bucket_of(&info.address)].push((info.address.as_str(), ACCOUNT_ENTRY, value));
+    }
+    for (rep, weight) in weights {
+        entries[bucket_of(rep)].push((rep.as_str(), WEIGHT_ENTRY, weight.to_be_bytes().to_vec()));
+    }
+
+    let buckets = entries
+        .into_iter()
+        .map(|mut bucket| {
+            bucket.sort_unstable();
+            let mut hash = burst_crypto::blake2b_256(STATE_DIGEST_DOMAIN);
+            for (address, kind, value) in bucket {
+                hash =
+                    burst_crypto::blake2b_256_multi(&[&hash, address.as_bytes(), &[kind], &value]);
+            }
+            BlockHash::new(hash)
+        })
+        .collect();
+    Ok(StateDigest {
+        cemented_count,
+        buckets,
+    })
+}
+
+/// Outcome of comparing a peer's digest with ours.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DigestCheck {
+    /// Same cemented count, same state.
+    Matched,
+    /// Same cemented count, different state, first differing in `bucket`.
+    Diverged { bucket: usize },
+    /// We have no digest at the peer's cemented count.
+    Incomparable,
+}
+
+/// Our recent digests, by cemented count.
+pub struct StateDigestLog {
+    digests: BTreeMap<u64, StateDigest>,
+    capacity: usize,
+}
+
+impl StateDigestLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            digests: BTreeMap::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Keep `digest`, dropping the oldest once over capacity.
+    pub fn record(&mut self, digest: StateDigest) {
+        self.digests.insert(digest.cemented_count, digest);
+        while self.digests.len() > self.capacity {
+            self.digests.pop_first();
+        }
+    }
+
+    /// The digest at the highest cemented count.
+    pub fn latest(&self) -> Option<&StateDigest> {
+        self.digests.values().next_back()
+    }
+
+    /// Compare a peer's digest with ours at the same cemented count.
+    pub fn check(&self, theirs: &StateDigest) -> DigestCheck {
+        let Some(ours) = self.digests.get(&theirs.cemented_count) else {
+            return DigestCheck::Incomparable;
+        };
+        match ours.first_mismatch(theirs) {
+            Some(bucket) => DigestCheck::Diverged { bucket },
+            None => DigestCheck::Matched,
+        }
+    }
+}
+
+impl Default for StateDigestLog {
+    fn default() -> Self {
+        Self::new(STATE_DIGEST_HISTORY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_store::account::AccountInfo;
+    use burst_store_lmdb::LmdbEnvironment;
+    use burst_types::{Timestamp, WalletState};
+
+    fn account(name: &str, head: u8) -> AccountInfo {
+        let address = WalletAddress::new(name);
+        AccountInfo {
+            address: address.clone(),
+            state: WalletState::Verified,
+            verified_at: Some(Timestamp::new(1)),
+            head: BlockHash::new([head; 32]),
+            block_count: 1,
+            confirmation_height: 1,
+            representative: address,
+            total_brn_burned: 0,
+            total_brn_staked: 0,
+            trst_balance: 0,
+            expired_trst: 0,
+            revoked_trst: 0,
+            epoch: 0,
+        }
+    }
+
+    #[test]
+    fn digests_agree_on_equal_state_and_locate_divergence() {
+        let names = ["brst_alice", "brst_bob", "brst_carol", "brst_dave"];
+        let open = |heads: &[u8]| {
+            let dir = tempfile::tempdir().unwrap();
+            let env = LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+            for (name, head) in names.iter().zip(heads) {
+                env.account_store()
+                    .put_account(&account(name, *head))
+                    .unwrap();
+            }
+            (dir, env)
+        };
+        let weights = HashMap::from([(WalletAddress::new("brst_alice"), 500u128)]);
+
+        let (_a, ours_env) = open(&[1, 2, 3, 4]);
+        let ours = take_state_digest(&ours_env.account_store(), &ours_env.block_store(), &weights)
+            .unwrap();
+        assert_eq!(ours.cemented_count, 4);
+        assert_eq!(ours.buckets.len(), STATE_DIGEST_BUCKETS);
+
+        let (_b, same_env) = open(&[1, 2, 3, 4]);
+        let same = take_state_digest(&same_env.account_store(), &same_env.block_store(), &weights)
+            .unwrap();
+        assert_eq!(same, ours);
+        assert_eq!(same.root(), ours.root());
+
+        // Carol's cemented frontier differs: only her bucket mismatches.
+        let (_c, forked_env) = open(&[1, 2, 9, 4]);
+        let forked = take_state_digest(
+            &forked_env.account_store(),
+            &forked_env.block_store(),
+            &weights,
+        )
+        .unwrap();
+        let carol = bucket_of(&WalletAddress::new("brst_carol"));
+        assert_eq!(ours.first_mismatch(&forked), Some(carol));
+        assert_ne!(ours.root(), forked.root());
+
+        // A drifted representative weight shows up too.
+        let drifted = HashMap::from([(WalletAddress::new("brst_alice"), 501u128)]);
+        let drift = take_state_digest(&ours_env.account_store(), &ours_env.block_store(), &drifted)
+            .unwrap();
+        assert_eq!(
+            ours.first_mismatch(&drift),
+            Some(bucket_of(&WalletAddress::new("brst_alice")))
+        );
+
+        let mut log = StateDigestLog::new(2);
+        log.record(ours.clone());
+        assert_eq!(log.check(&same), DigestCheck::Matched);
+        assert_eq!(log.check(&forked), DigestCheck::Diverged { bucket: carol });
+        let ahead = StateDigest {
+            cemented_count: 5,
+            ..same
+        };
+        assert_eq!(log.check(&ahead), DigestCheck::Incomparable);
+        log.record(ahead.clone());
+        log.record(StateDigest {
+            cemented_count: 6,
+            ..ahead
+        });
+        assert_eq!(log.latest().unwrap().cemented_count, 6);
+        assert_eq!(
+            log.check(&ours),
+            DigestCheck::Incomparable,
+            "oldest evicted"
+        );
+
+        assert_eq!(bucket_range(0), (0x00, 0x0f));
+        assert_eq!(bucket_range(STATE_DIGEST_BUCKETS - 1), (0xf0, 0xff));
+    }
+}
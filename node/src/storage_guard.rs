@@ -0,0 +1,314 @@
+//! Disk space and LMDB map monitoring, and the emergency read-only mode.
+//!
+//! A write that finds the disk or the LMDB memory map full fails halfway
+//! through the block pipeline. The [`StorageGuard`] watches both and acts
+//! before that happens:
+//!
+//! - past `grow_at_percent` of the map, or below `low_disk_free_percent`
+//!   free on the disk, storage is low and the node warns;
+//! - past `read_only_map_percent` of the map, or below
+//!   `read_only_disk_free_percent` free, the node enters read-only mode.
+//!
+//! In read-only mode no new blocks are written: queued blocks wait, and
+//! blocks submitted over RPC are refused. The node keeps serving RPC and
+//! voting on the state it already has. It leaves the mode once both
+//! measures are back out of the low band, so it does not flap at the edge.
+//!
+//! With `auto_grow` the map doubles, up to `max_map_size_mb`, whenever its
+//! use crosses `grow_at_percent`: at startup to fit the data file (see
+//! [`map_size_at_open`]), and while running between write batches (see
+//! [`StorageGuard::grow_target`]). Only a map that can grow no further
+//! runs on into read-only mode.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use burst_store_lmdb::MapUsage;
+use serde::{Deserialize, Serialize};
+
+use crate::error::NodeError;
+
+const MIB: u64 = 1 << 20;
+
+/// Map size, growth and read-only thresholds of the storage guard.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    /// Whether storage is watched at all.
+    pub enabled: bool,
+    /// Seconds between checks.
+    pub check_interval_secs: u64,
+    /// Size of the LMDB memory map, in MiB.
+    pub map_size_mb: u64,
+    /// Grow the map at startup when the data file fills too much of it.
+    pub auto_grow: bool,
+    /// Largest map `auto_grow` will open, in MiB.
+    pub max_map_size_mb: u64,
+    /// Map use, in percent, at which the map is grown, or storage is low
+    /// once it can grow no further.
+    pub grow_at_percent: u8,
+    /// Map use, in percent, at which the node stops writing.
+    pub read_only_map_percent: u8,
+    /// Free share of the data disk, in percent, below which storage is low.
+    pub low_disk_free_percent: f64,
+    /// Free share of the data disk, in percent, below which the node stops
+    /// writing.
+    pub read_only_disk_free_percent: f64,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            check_interval_secs: 30,
+            map_size_mb: 1024,
+            auto_grow: false,
+            max_map_size_mb: 256 * 1024,
+            grow_at_percent: 80,
+            read_only_map_percent: 95,
+            low_disk_free_percent: 10.0,
+            read_only_disk_free_percent: 2.0,
+        }
+    }
+}
+
+impl StorageConfig {
+    /// Check that the thresholds are ordered and the map sizes sane.
+    pub fn validate(&self) -> Result<(), NodeError> {
+        let invalid = |msg: &str| Err(NodeError::Config(format!("storage: {msg}")));
+        if self.map_size_mb == 0 {
+            return invalid("map_size_mb must be at least 1");
+        }
+        if self.auto_grow && self.max_map_size_mb < self.map_size_mb {
+            return invalid("max_map_size_mb must not be below map_size_mb");
+        }
+        if self.read_only_map_percent > 100 {
+            return invalid("read_only_map_percent must be at most 100");
+        }
+        if self.grow_at_percent == 0 || self.grow_at_percent > self.read_only_map_percent {
+            return invalid("grow_at_percent must be between 1 and read_only_map_percent");
+        }
+        if self.read_only_disk_free_percent > self.low_disk_free_percent {
+            return invalid("read_only_disk_free_percent must not exceed low_disk_free_percent");
+        }
+        Ok(())
+    }
+}
+
+/// The map size to open the environment with, given its data file.
+///
+/// Without `auto_grow` this is the configured size. With it, the size
+/// doubles until the data file fills less than `grow_at_percent` of the
+/// map, up to `max_map_size_mb`.
+pub fn map_size_at_open(config: &StorageConfig, data_file_len: u64) -> usize {
+    let mut size = config.map_size_mb.max(1) * MIB;
+    if config.auto_grow {
+        let max = config.max_map_size_mb.max(config.map_size_mb) * MIB;
+        while data_file_len.saturating_mul(100) >= size * config.grow_at_percent as u64
+            && size < max
+        {
+            size = (size * 2).min(max);
+        }
+    }
+    size as usize
+}
+
+/// How close storage is to running out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum StorageLevel {
+    Normal = 0,
+    /// Past a warning threshold.
+    Low = 1,
+    /// Past a read-only threshold; no new blocks are written.
+    ReadOnly = 2,
+}
+
+impl StorageLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StorageLevel::Normal => "normal",
+            StorageLevel::Low => "low",
+            StorageLevel::ReadOnly => "read_only",
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            2 => StorageLevel::ReadOnly,
+            1 => StorageLevel::Low,
+            _ => StorageLevel::Normal,
+        }
+    }
+}
+
+/// Storage readings taken for one evaluation.
+#[derive(Clone, Copy, Debug)]
+pub struct StorageSample {
+    /// Size and high-water mark of the LMDB memory map.
+    pub map: MapUsage,
+    /// Free share of the data directory's disk; `None` if unknown.
+    pub disk_free_percent: Option<f64>,
+}
+
+/// Tracks storage and decides when the node must stop writing.
+///
+/// Shared between the block processor and the RPC bridge, which refuse
+/// writes while read-only, and the task that periodically evaluates it.
+pub struct StorageGuard {
+    config: StorageConfig,
+    level: AtomicU8,
+}
+
+impl StorageGuard {
+    pub fn new(config: StorageConfig) -> Self {
+        Self {
+            config,
+            level: AtomicU8::new(StorageLevel::Normal as u8),
+        }
+    }
+
+    pub fn config(&self) -> &StorageConfig {
+        &self.config
+    }
+
+    pub fn level(&self) -> StorageLevel {
+        StorageLevel::from_u8(self.level.load(Ordering::Relaxed))
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.level() == StorageLevel::ReadOnly
+    }
+
+    /// The level `sample` alone calls for.
+    fn level_for(&self, sample: &StorageSample) -> StorageLevel {
+        let map = sample.map.used_percent();
+        let free = sample.disk_free_percent.unwrap_or(100.0);
+        if map >= self.config.read_only_map_percent as f64
+            || free < self.config.read_only_disk_free_percent
+        {
+            StorageLevel::ReadOnly
+        } else if map >= self.config.grow_at_percent as f64
+            || free < self.config.low_disk_free_percent
+        {
+            StorageLevel::Low
+        } else {
+            StorageLevel::Normal
+        }
+    }
+
+    /// The size to grow the map to, if `auto_grow` is on, `map` is past
+    /// `grow_at_percent` and below `max_map_size_mb`: double, capped at
+    /// the maximum.
+    pub fn grow_target(&self, map: &MapUsage) -> Option<usize> {
+        let max = self.config.max_map_size_mb * MIB;
+        if !self.config.auto_grow
+            || map.used_percent() < self.config.grow_at_percent as f64
+            || map.map_size >= max
+        {
+            return None;
+        }
+        Some(map.map_size.saturating_mul(2).min(max) as usize)
+    }
+
+    /// Re-evaluate the level from `sample`. Returns the new level on a
+    /// transition. Read-only mode is only left once storage is no longer
+    /// low.
+    pub fn evaluate(&self, sample: &StorageSample) -> Option<StorageLevel> {
+        if !self.config.enabled {
+            return None;
+        }
+        let was = self.level();
+        let mut now = self.level_for(sample);
+        if was == StorageLevel::ReadOnly && now == StorageLevel::Low {
+            now = StorageLevel::ReadOnly;
+        }
+        if now == was {
+            return None;
+        }
+        self.level.store(now as u8, Ordering::Relaxed);
+        Some(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(map_used_percent: u64, disk_free_percent: f64) -> StorageSample {
+        StorageSample {
+            map: MapUsage {
+                map_size: 100 * MIB,
+                used: map_used_percent * MIB,
+            },
+            disk_free_percent: Some(disk_free_percent),
+        }
+    }
+
+    #[test]
+    fn enters_read_only_on_either_measure_and_leaves_with_hysteresis() {
+        let guard = StorageGuard::new(StorageConfig::default());
+        assert_eq!(guard.evaluate(&sample(50, 50.0)), None);
+        assert_eq!(guard.evaluate(&sample(80, 50.0)), Some(StorageLevel::Low));
+        assert_eq!(
+            guard.evaluate(&sample(95, 50.0)),
+            Some(StorageLevel::ReadOnly)
+        );
+        assert!(guard.is_read_only());
+
+        // Back in the low band: still read-only.
+        assert_eq!(guard.evaluate(&sample(85, 50.0)), None);
+        assert_eq!(
+            guard.evaluate(&sample(50, 50.0)),
+            Some(StorageLevel::Normal)
+        );
+
+        // The disk filling up does it too; an unknown disk does not.
+        assert_eq!(
+            guard.evaluate(&sample(10, 1.5)),
+            Some(StorageLevel::ReadOnly)
+        );
+        assert_eq!(guard.evaluate(&sample(10, 5.0)), None);
+        let unknown = StorageSample {
+            disk_free_percent: None,
+            ..sample(10, 0.0)
+        };
+        assert_eq!(guard.evaluate(&unknown), Some(StorageLevel::Normal));
+    }
+
+    #[test]
+    fn map_grows_only_when_configured() {
+        let mut config = StorageConfig {
+            map_size_mb: 64,
+            max_map_size_mb: 512,
+            ..StorageConfig::default()
+        };
+        assert_eq!(map_size_at_open(&config, 60 * MIB), (64 * MIB) as usize);
+
+        config.auto_grow = true;
+        assert_eq!(map_size_at_open(&config, 10 * MIB), (64 * MIB) as usize);
+        // 60 MiB fills 94% of 64 MiB and 47% of 128 MiB.
+        assert_eq!(map_size_at_open(&config, 60 * MIB), (128 * MIB) as usize);
+        assert_eq!(map_size_at_open(&config, 4096 * MIB), (512 * MIB) as usize);
+        assert!(config.validate().is_ok());
+
+        // While running: double past the watermark, stop at the maximum.
+        let guard = StorageGuard::new(config.clone());
+        let map = |size_mb: u64, used_mb: u64| MapUsage {
+            map_size: size_mb * MIB,
+            used: used_mb * MIB,
+        };
+        assert_eq!(guard.grow_target(&map(128, 90)), None);
+        assert_eq!(
+            guard.grow_target(&map(128, 110)),
+            Some((256 * MIB) as usize)
+        );
+        assert_eq!(
+            guard.grow_target(&map(384, 320)),
+            Some((512 * MIB) as usize)
+        );
+        assert_eq!(guard.grow_target(&map(512, 500)), None);
+
+        config.max_map_size_mb = 32;
+        assert!(matches!(config.validate(), Err(NodeError::Config(_))));
+    }
+}
@@ -0,0 +1,175 @@
+//! Disk-backed TRST wallet portfolios.
+//!
+//! The TRST engine keeps an LRU cache of hot portfolios and pages the rest
+//! in from `LmdbTrstIndexStore`. Portfolios it changes are written through
+//! in the same write batch as the block that changed them; changes made
+//! outside block processing are written in a batch of their own.
+
+use burst_store::meta::MetaStore;
+use burst_store::trst_index::TrstIndexStore;
+use burst_store::StoreError;
+use burst_store_lmdb::{LmdbStore, LmdbTrstIndexStore, WriteBatch};
+use burst_trst::{PortfolioSource, TrstEngine, WalletPortfolio};
+use burst_types::WalletAddress;
+
+/// Written-back portfolios kept in memory.
+pub const TRST_PORTFOLIO_CACHE_WALLETS: usize = 50_000;
+
+/// Stored portfolios read per page when rebuilding the origin indexes.
+const INDEX_PAGE_SIZE: usize = 1_000;
+
+/// Reads stored portfolios for the TRST engine's cache.
+pub struct LmdbPortfolioSource(pub LmdbTrstIndexStore);
+
+impl PortfolioSource for LmdbPortfolioSource {
+    fn load_portfolio(&self, wallet: &WalletAddress) -> Option<WalletPortfolio> {
+        match self.0.get_portfolio(wallet) {
+            Ok(bytes) => bincode::deserialize(&bytes)
+                .inspect_err(|e| tracing::warn!(%wallet, "corrupt TRST portfolio: {e}"))
+                .ok(),
+            Err(StoreError::NotFound(_)) => None,
+            Err(e) => {
+                tracing::warn!(%wallet, "failed to read TRST portfolio: {e}");
+                None
+            }
+        }
+    }
+}
+
+/// Add portfolio updates to a write batch.
+pub fn write_portfolios(
+    batch: &mut WriteBatch<'_>,
+    updates: &[(WalletAddress, WalletPortfolio)],
+) -> Result<(), StoreError> {
+    for (wallet, portfolio) in updates {
+        let bytes =
+            bincode::serialize(portfolio).map_err(|e| StoreError::Serialization(e.to_string()))?;
+        batch.put_portfolio(wallet, &bytes)?;
+    }
+    Ok(())
+}
+
+/// Write the engine's changed portfolios in a batch of their own, for
+/// changes made outside block processing. Returns how many were written.
+pub fn persist_dirty_portfolios(
+    store: &LmdbStore,
+    trst_engine: &mut TrstEngine,
+) -> Result<usize, StoreError> {
+    let updates = trst_engine.take_dirty_portfolios();
+    if updates.is_empty() {
+        return Ok(0);
+    }
+    let mut batch = store.write_batch()?;
+    write_portfolios(&mut batch, &updates)?;
+    batch.commit()?;
+    Ok(updates.len())
+}
+
+/// Back the engine's portfolios with the store.
+///
+/// A whole-engine snapshot left by an earlier version is written out per
+/// wallet first and then removed. Every stored portfolio is read once to
+/// rebuild the origin indexes revocation relies on; only the indexes are
+/// kept. Returns the number of stored portfolios.
+pub fn attach_portfolio_store(
+    store: &LmdbStore,
+    trst_engine: &mut TrstEngine,
+) -> Result<usize, StoreError> {
+    let meta = store.meta_store();
+    if let Ok(bytes) = meta.get_meta(TrstEngine::meta_key()) {
+        let legacy = TrstEngine::load_wallets(&bytes, trst_engine.expiry_secs);
+        let updates: Vec<(WalletAddress, WalletPortfolio)> = legacy
+            .cached_portfolios()
+            .map(|(wallet, portfolio)| (wallet.clone(), portfolio.clone()))
+            .collect();
+        let mut batch = store.write_batch()?;
+        write_portfolios(&mut batch, &updates)?;
+        batch.commit()?;
+        meta.delete_meta(TrstEngine::meta_key())?;
+        tracing::info!(
+            wallets = updates.len(),
+            "migrated TRST portfolio snapshot to per-wallet storage"
+        );
+    }
+
+    let index_store = store.trst_index_store();
+    let mut stored = 0;
+    let mut cursor: Option<WalletAddress> = None;
+    loop {
+        let page = index_store.iter_portfolios_paged(cursor.as_ref(), INDEX_PAGE_SIZE)?;
+        for (wallet, bytes) in &page {
+            match bincode::deserialize::<WalletPortfolio>(bytes) {
+                Ok(portfolio) => trst_engine.index_portfolio(&portfolio),
+                Err(e) => tracing::warn!(%wallet, "skipping corrupt TRST portfolio: {e}"),
+            }
+        }
+        stored += page.len();
+        match page.into_iter().last() {
+            Some((wallet, _)) => cursor = Some(wallet),
+            None => break,
+        }
+    }
+
+    trst_engine.attach_portfolio_source(
+        Box::new(LmdbPortfolioSource(index_store)),
+        TRST_PORTFOLIO_CACHE_WALLETS,
+    );
+    Ok(stored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_trst::TrstToken;
+    use burst_types::{Timestamp, TrstState, TxHash};
+
+    fn wallet(n: u8) -> WalletAddress {
+        WalletAddress::new(format!("brst_{:0>60}", n))
+    }
+
+    fn token(n: u8, holder: WalletAddress) -> TrstToken {
+        TrstToken {
+            id: TxHash::new([n; 32]),
+            amount: 100,
+            origin: TxHash::new([n; 32]),
+            link: TxHash::new([n; 32]),
+            holder,
+            origin_timestamp: Timestamp::new(100),
+            effective_origin_timestamp: Timestamp::new(100),
+            state: TrstState::Active,
+            origin_wallet: wallet(9),
+            origin_proportions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn legacy_snapshot_is_migrated_and_revocable_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LmdbStore::open(dir.path(), 46, 1 << 22).unwrap();
+
+        let mut legacy = TrstEngine::with_expiry(10_000);
+        legacy.track_token(token(1, wallet(1)));
+        store
+            .meta_store()
+            .put_meta(TrstEngine::meta_key(), &legacy.save_wallets())
+            .unwrap();
+
+        let mut engine = TrstEngine::with_expiry(10_000);
+        assert_eq!(attach_portfolio_store(&store, &mut engine).unwrap(), 1);
+        assert!(store.meta_store().get_meta(TrstEngine::meta_key()).is_err());
+        assert_eq!(engine.cached_portfolio_count(), 0);
+        assert_eq!(engine.transferable_balance_snapshot(&wallet(1)), Some(100));
+
+        engine.track_token(token(2, wallet(2)));
+        assert_eq!(persist_dirty_portfolios(&store, &mut engine).unwrap(), 1);
+
+        // A fresh engine finds both holders through the rebuilt indexes.
+        let mut restarted = TrstEngine::with_expiry(10_000);
+        assert_eq!(attach_portfolio_store(&store, &mut restarted).unwrap(), 2);
+        restarted.revoke_by_origin(&wallet(9));
+        assert_eq!(persist_dirty_portfolios(&store, &mut restarted).unwrap(), 2);
+        for n in 1..=2 {
+            assert_eq!(restarted.transferable_balance_snapshot(&wallet(n)), Some(0));
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! Progress of blocks submitted to this node, for the `tx_status` RPC.
+//!
+//! A block is tracked from the moment it is queued through `process` or
+//! `process_transaction`; blocks arriving from peers are not, since the
+//! ledger already answers for them. The block processor, confirmation and
+//! cementation tasks then move each tracked block along
+//! Queued → Accepted → Confirmed → Cemented, or end it as Rejected or
+//! Dropped with a reason. The table is bounded; the oldest submissions are
+//! forgotten first.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use burst_rpc::{ForkEntry, TxState, TxStatus, TxStatusView};
+use burst_types::BlockHash;
+
+/// Default number of submitted blocks tracked.
+pub const DEFAULT_TX_STATUS_CAPACITY: usize = 16_384;
+
+pub struct TxStatusTracker {
+    capacity: usize,
+    inner: Mutex<Tracked>,
+}
+
+#[derive(Default)]
+struct Tracked {
+    statuses: HashMap<BlockHash, TxStatus>,
+    /// Submission order, oldest first, for eviction.
+    order: VecDeque<BlockHash>,
+}
+
+impl TxStatusTracker {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::new(Tracked::default()),
+        }
+    }
+
+    /// Start tracking `hash` as queued, evicting the oldest entry when
+    /// full. Resubmitting a rejected or dropped block queues it again.
+    pub fn submitted(&self, hash: BlockHash, now: u64) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut inner = self.lock();
+        if inner.statuses.contains_key(&hash) {
+            drop(inner);
+            self.advance(&hash, TxState::Queued, None, now);
+            return;
+        }
+        while inner.order.len() >= self.capacity {
+            if let Some(oldest) = inner.order.pop_front() {
+                inner.statuses.remove(&oldest);
+            }
+        }
+        inner.order.push_back(hash);
+        inner.statuses.insert(
+            hash,
+            TxStatus {
+                state: TxState::Queued,
+                reason: None,
+                updated_at: now,
+            },
+        );
+    }
+
+    /// Still queued, waiting on something (a missing dependency, a fork
+    /// election).
+    pub fn waiting(&self, hash: &BlockHash, reason: &str, now: u64) {
+        self.advance(hash, TxState::Queued, Some(reason), now);
+    }
+
+    pub fn accepted(&self, hash: &BlockHash, now: u64) {
+        self.advance(hash, TxState::Accepted, None, now);
+    }
+
+    pub fn confirmed(&self, hash: &BlockHash, now: u64) {
+        self.advance(hash, TxState::Confirmed, None, now);
+    }
+
+    pub fn cemented(&self, hash: &BlockHash, now: u64) {
+        self.advance(hash, TxState::Cemented, None, now);
+    }
+
+    pub fn rejected(&self, hash: &BlockHash, reason: &str, now: u64) {
+        self.advance(hash, TxState::Rejected, Some(reason), now);
+    }
+
+    pub fn dropped(&self, hash: &BlockHash, reason: &str, now: u64) {
+        self.advance(hash, TxState::Dropped, Some(reason), now);
+    }
+
+    /// Drop whichever blocks of a settled fork were not confirmed.
+    pub fn fork_resolved(&self, fork: &ForkEntry, now: u64) {
+        let Some(winner) = fork.winner.as_deref() else {
+            return;
+        };
+        for candidate in [&fork.existing, &fork.fork] {
+            if candidate == winner {
+                continue;
+            }
+            if let Some(loser) = parse_hash(candidate) {
+                self.dropped(&loser, "lost fork election", now);
+            }
+        }
+    }
+
+    pub fn status(&self, hash: &BlockHash) -> Option<TxStatus> {
+        self.lock().statuses.get(hash).cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Move a tracked block to `state`. Untracked blocks are ignored, as
+    /// are moves backwards — events from different tasks can arrive out
+    /// of order — and anything after cementing.
+    fn advance(&self, hash: &BlockHash, state: TxState, reason: Option<&str>, now: u64) {
+        let mut inner = self.lock();
+        let Some(status) = inner.statuses.get_mut(hash) else {
+            return;
+        };
+        if !may_advance(status.state, state) {
+            return;
+        }
+        status.state = state;
+        status.reason = reason.map(str::to_string);
+        status.updated_at = now;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Tracked> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn parse_hash(hex_str: &str) -> Option<BlockHash> {
+    let bytes: [u8; 32] = hex::decode(hex_str).ok()?.try_into().ok()?;
+    Some(BlockHash::new(bytes))
+}
+
+/// Position along the successful path; failures restart from the bottom.
+fn progress(state: TxState) -> u8 {
+    match state {
+        TxState::Queued | TxState::Rejected | TxState::Dropped => 0,
+        TxState::Accepted => 1,
+        TxState::Confirmed => 2,
+        TxState::Cemented => 3,
+    }
+}
+
+fn may_advance(from: TxState, to: TxState) -> bool {
+    if from == TxState::Cemented {
+        return false;
+    }
+    matches!(to, TxState::Rejected | TxState::Dropped) || progress(to) >= progress(from)
+}
+
+impl TxStatusView for TxStatusTracker {
+    fn tx_status(&self, hash: &BlockHash) -> Option<TxStatus> {
+        self.status(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(byte: u8) -> BlockHash {
+        BlockHash::new([byte; 32])
+    }
+
+    #[test]
+    fn tracked_block_progresses_to_cemented() {
+        let tracker = TxStatusTracker::new(8);
+        tracker.submitted(hash(1), 1);
+        tracker.accepted(&hash(1), 2);
+        // A late "waiting" from the processor must not move it back.
+        tracker.waiting(&hash(1), "gap", 3);
+        assert_eq!(tracker.status(&hash(1)).unwrap().state, TxState::Accepted);
+        tracker.confirmed(&hash(1), 4);
+        tracker.cemented(&hash(1), 5);
+        tracker.dropped(&hash(1), "lost fork election", 6);
+        let status = tracker.status(&hash(1)).unwrap();
+        assert_eq!(status.state, TxState::Cemented);
+        assert_eq!(status.updated_at, 5);
+    }
+
+    #[test]
+    fn failures_keep_their_reason_and_resubmission_requeues() {
+        let tracker = TxStatusTracker::new(8);
+        tracker.submitted(hash(1), 1);
+        tracker.rejected(&hash(1), "insufficient balance", 2);
+        let status = tracker.status(&hash(1)).unwrap();
+        assert_eq!(status.state, TxState::Rejected);
+        assert_eq!(status.reason.as_deref(), Some("insufficient balance"));
+
+        tracker.submitted(hash(1), 3);
+        let status = tracker.status(&hash(1)).unwrap();
+        assert_eq!(status.state, TxState::Queued);
+        assert_eq!(status.reason, None);
+        assert_eq!(tracker.len(), 1);
+    }
+
+    #[test]
+    fn untracked_blocks_are_ignored_and_oldest_is_evicted() {
+        let tracker = TxStatusTracker::new(2);
+        tracker.accepted(&hash(9), 1);
+        assert!(tracker.is_empty());
+
+        tracker.submitted(hash(1), 1);
+        tracker.submitted(hash(2), 2);
+        tracker.submitted(hash(3), 3);
+        assert_eq!(tracker.len(), 2);
+        assert!(tracker.status(&hash(1)).is_none());
+        assert!(tracker.status(&hash(3)).is_some());
+    }
+
+    #[test]
+    fn fork_resolution_drops_the_loser() {
+        use burst_rpc::ForkOutcome;
+
+        let tracker = TxStatusTracker::new(8);
+        tracker.submitted(hash(1), 1);
+        tracker.submitted(hash(2), 1);
+        tracker.accepted(&hash(1), 2);
+        let fork = ForkEntry {
+            seq: 0,
+            account: "brst_a".into(),
+            root: BlockHash::ZERO.to_string(),
+            existing: hash(1).to_string(),
+            fork: hash(2).to_string(),
+            detected_at: 2,
+            outcome: ForkOutcome::ForkWon,
+            winner: Some(hash(2).to_string()),
+            resolved_at: Some(3),
+        };
+        tracker.fork_resolved(&fork, 3);
+        let loser = tracker.status(&hash(1)).unwrap();
+        assert_eq!(loser.state, TxState::Dropped);
+        assert_eq!(loser.reason.as_deref(), Some("lost fork election"));
+        assert_eq!(tracker.status(&hash(2)).unwrap().state, TxState::Queued);
+    }
+}
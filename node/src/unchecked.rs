@@ -6,7 +6,8 @@
 //! re-submitted to the block processor.
 
 use burst_ledger::StateBlock;
-use burst_types::BlockHash;
+use burst_types::memory::entry_bytes;
+use burst_types::{BlockHash, MemoryAccounting};
 use std::collections::HashMap;
 
 /// Reason a block is in the unchecked map.
@@ -160,6 +161,18 @@ impl UncheckedMap {
     }
 }
 
+impl MemoryAccounting for UncheckedMap {
+    fn element_count(&self) -> usize {
+        self.count
+    }
+
+    fn approximate_bytes(&self) -> usize {
+        let keys = self.entries.len() + self.source_dependents.len();
+        entry_bytes::<(BlockHash, Vec<UncheckedEntry>)>(keys)
+            + entry_bytes::<UncheckedEntry>(self.count)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,6 +205,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(1000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([1u8; 64]),
             hash: BlockHash::ZERO,
@@ -307,6 +326,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(2000),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0,
             signature: Signature([1u8; 64]),
             hash: BlockHash::ZERO,
@@ -7,7 +7,12 @@
 //! 4. Collect verification votes
 //! 5. Determine outcome
 
-use burst_types::WalletAddress;
+use burst_brn::Stake;
+use burst_ledger::{BlockType, StateBlock};
+use burst_store::verification::{VerificationRecord, VerificationRecordOutcome, VerificationStore};
+use burst_store::StoreError;
+use burst_types::{ProtocolParams, Timestamp, WalletAddress};
+use burst_verification::{ChallengeHistory, VerificationState, Vote};
 use std::collections::HashSet;
 
 // ---------------------------------------------------------------------------
@@ -101,9 +106,11 @@ impl VerificationProcessor {
 
 /// Tracks which accounts have opted in as verifiers.
 ///
-/// Verifiers must hold a minimum BRN balance (which they stake implicitly).
-/// The pool provides the set of eligible addresses used by
-/// [`burst_vrf::select_verifiers`] when a new verification round begins.
+/// Verifiers join with a VerifierOptIn block that locks at least the
+/// minimum BRN stake, and leave with a VerifierOptOut block that returns it.
+/// Membership is persisted in the verification store; [`VerifierPool::load`]
+/// rebuilds the pool from there, so every node hands the same set of
+/// eligible addresses to [`burst_vrf::select_verifiers`].
 pub struct VerifierPool {
     /// Set of addresses currently opted in.
     opted_in: HashSet<WalletAddress>,
@@ -120,6 +127,20 @@ impl VerifierPool {
         }
     }
 
+    /// Rebuild the pool from the membership persisted by opt-in and opt-out
+    /// blocks.
+    pub fn load(min_brn_stake: u128, store: &impl VerificationStore) -> Result<Self, StoreError> {
+        let opted_in = store
+            .iter_verifiers()?
+            .into_iter()
+            .map(|(address, _)| address)
+            .collect();
+        Ok(Self {
+            opted_in,
+            min_brn_stake,
+        })
+    }
+
     /// Opt in as a verifier. Fails if the BRN balance is below the minimum.
     pub fn opt_in(&mut self, address: WalletAddress, brn_balance: u128) -> Result<(), String> {
         if brn_balance < self.min_brn_stake {
@@ -162,6 +183,125 @@ impl VerifierPool {
     }
 }
 
+/// Check a VerifierOptIn or VerifierOptOut block against the account's
+/// current pool membership. An opt-in must lock at least `min_stake` and
+/// cannot repeat; an opt-out must return exactly the stake locked at opt-in.
+pub fn validate_pool_change(
+    block: &StateBlock,
+    prev_brn_balance: u128,
+    current: Option<&Stake>,
+    min_stake: u128,
+) -> Result<(), String> {
+    match (&block.block_type, current) {
+        (BlockType::VerifierOptIn, Some(_)) => {
+            Err("account is already in the verifier pool".into())
+        }
+        (BlockType::VerifierOptIn, None) => {
+            let stake = prev_brn_balance.saturating_sub(block.brn_balance);
+            if stake < min_stake {
+                return Err(format!(
+                    "verifier stake too small: locks {stake}, need {min_stake}"
+                ));
+            }
+            Ok(())
+        }
+        (BlockType::VerifierOptOut, None) => Err("account is not in the verifier pool".into()),
+        (BlockType::VerifierOptOut, Some(stake)) => {
+            let returned = block.brn_balance.saturating_sub(prev_brn_balance);
+            if returned != stake.amount {
+                return Err(format!(
+                    "verifier opt-out returns {returned} BRN, but {} is staked",
+                    stake.amount
+                ));
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// A wallet's persisted challenge history, empty if it never took part in
+/// a challenge.
+pub fn load_challenge_history(
+    store: &impl VerificationStore,
+    wallet: &WalletAddress,
+) -> Result<ChallengeHistory, StoreError> {
+    Ok(store
+        .get_challenge_history(wallet)?
+        .and_then(|bytes| bincode::deserialize(&bytes).ok())
+        .unwrap_or_default())
+}
+
+/// Persist a wallet's challenge history.
+pub fn save_challenge_history(
+    store: &impl VerificationStore,
+    wallet: &WalletAddress,
+    history: &ChallengeHistory,
+) -> Result<(), StoreError> {
+    let bytes =
+        bincode::serialize(history).map_err(|e| StoreError::Serialization(e.to_string()))?;
+    store.put_challenge_history(wallet, &bytes)
+}
+
+/// Summarize a finished verification or challenge as an anonymized record
+/// for the UHV statistics. A vote agrees when it sides with the outcome;
+/// undecided outcomes have no agreeing votes.
+pub fn verification_record(
+    state: &VerificationState,
+    outcome: VerificationRecordOutcome,
+    completed_at: Timestamp,
+) -> VerificationRecord {
+    let votes_agreeing = match outcome.found_legitimate() {
+        Some(legitimate) => state
+            .votes
+            .iter()
+            .filter(|v| (v.vote == Vote::Legitimate) == legitimate)
+            .count(),
+        None => 0,
+    };
+    VerificationRecord {
+        outcome,
+        endorsement_count: state.endorsements.len() as u32,
+        started_at: state.started_at,
+        completed_at,
+        votes_cast: state.votes.len() as u32,
+        votes_agreeing: votes_agreeing as u32,
+        neither_votes: state
+            .votes
+            .iter()
+            .filter(|v| v.vote == Vote::Neither)
+            .count() as u32,
+    }
+}
+
+/// Check a Challenge block against the persisted challenge history of its
+/// target and challenger: the target must be past its post-survival cooldown
+/// and the locked stake must cover the challenger's escalated minimum.
+pub fn validate_challenge(
+    block: &StateBlock,
+    prev_brn_balance: u128,
+    store: &impl VerificationStore,
+    now: Timestamp,
+    params: &ProtocolParams,
+) -> Result<(), String> {
+    let Some(target) = crate::ledger_bridge::extract_receiver_from_link(&block.link) else {
+        return Err("challenge block has no target".into());
+    };
+    let lookup_err = |e: StoreError| format!("unable to look up challenge history: {e}");
+    let target_history = load_challenge_history(store, &target).map_err(lookup_err)?;
+    let challenger_history = load_challenge_history(store, &block.account).map_err(lookup_err)?;
+    let stake = prev_brn_balance.saturating_sub(block.brn_balance);
+    burst_verification::check_challenge_allowed(
+        &target,
+        &target_history,
+        &challenger_history,
+        stake,
+        now,
+        params,
+    )
+    .map_err(|e| e.to_string())
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -299,6 +439,112 @@ mod tests {
         assert_eq!(pool.min_stake(), 500);
     }
 
+    #[test]
+    fn test_pool_change_needs_stake_and_membership() {
+        let mut block = StateBlock {
+            version: burst_ledger::CURRENT_BLOCK_VERSION,
+            block_type: BlockType::VerifierOptIn,
+            account: addr("alice"),
+            previous: burst_types::BlockHash::new([1; 32]),
+            representative: addr("rep"),
+            brn_balance: 400,
+            trst_balance: 0,
+            link: burst_types::BlockHash::ZERO,
+            origin: burst_types::TxHash::ZERO,
+            transaction: burst_types::TxHash::ZERO,
+            timestamp: burst_types::Timestamp::new(1),
+            params_hash: burst_types::BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
+            work: 0,
+            signature: burst_types::Signature([0; 64]),
+            hash: burst_types::BlockHash::ZERO,
+        };
+        let stake = Stake {
+            id: 1,
+            staker: addr("alice"),
+            amount: 600,
+            kind: burst_brn::StakeKind::VerifierPool,
+            created_at: burst_types::Timestamp::new(1),
+            resolved: false,
+        };
+
+        assert!(validate_pool_change(&block, 1_000, None, 500).is_ok());
+        assert!(validate_pool_change(&block, 800, None, 500).is_err());
+        assert!(validate_pool_change(&block, 1_000, Some(&stake), 500).is_err());
+
+        block.block_type = BlockType::VerifierOptOut;
+        block.brn_balance = 1_000;
+        assert!(validate_pool_change(&block, 400, Some(&stake), 500).is_ok());
+        assert!(validate_pool_change(&block, 500, Some(&stake), 500).is_err());
+        assert!(validate_pool_change(&block, 400, None, 500).is_err());
+    }
+
+    #[test]
+    fn test_challenge_respects_persisted_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = burst_store_lmdb::LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+        let store = env.verification_store();
+        let params = ProtocolParams {
+            challenge_stake_amount: 100,
+            challenge_loss_allowance: 1,
+            challenge_target_cooldown_secs: 1_000,
+            ..ProtocolParams::default()
+        };
+        let target = burst_crypto::derive_address(&burst_types::PublicKey([7; 32]));
+        let block = StateBlock {
+            version: burst_ledger::CURRENT_BLOCK_VERSION,
+            block_type: BlockType::Challenge,
+            account: addr("alice"),
+            previous: burst_types::BlockHash::new([1; 32]),
+            representative: addr("rep"),
+            brn_balance: 900,
+            trst_balance: 0,
+            link: burst_types::BlockHash::new([7; 32]),
+            origin: burst_types::TxHash::ZERO,
+            transaction: burst_types::TxHash::ZERO,
+            timestamp: Timestamp::new(1),
+            params_hash: burst_types::BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
+            work: 0,
+            signature: burst_types::Signature([0; 64]),
+            hash: burst_types::BlockHash::ZERO,
+        };
+        let now = Timestamp::new(5_000);
+
+        assert!(validate_challenge(&block, 1_000, &store, now, &params).is_ok());
+
+        let survived = ChallengeHistory {
+            last_survived_at: Some(Timestamp::new(4_500)),
+            ..ChallengeHistory::default()
+        };
+        save_challenge_history(&store, &target, &survived).unwrap();
+        assert_eq!(load_challenge_history(&store, &target).unwrap(), survived);
+        let err = validate_challenge(&block, 1_000, &store, now, &params).unwrap_err();
+        assert!(err.contains("cannot be challenged again"), "{err}");
+
+        let later = Timestamp::new(5_500);
+        assert!(validate_challenge(&block, 1_000, &store, later, &params).is_ok());
+
+        // Two losses with one free: the stake doubles to 200.
+        let loser = ChallengeHistory {
+            challenges_lost: 2,
+            ..ChallengeHistory::default()
+        };
+        save_challenge_history(&store, &addr("alice"), &loser).unwrap();
+        assert!(validate_challenge(&block, 1_000, &store, later, &params).is_err());
+        assert!(validate_challenge(&block, 1_100, &store, later, &params).is_ok());
+    }
+
     // -- Integration: VerifierPool + select_verifiers --
 
     #[test]
@@ -317,4 +563,59 @@ mod tests {
             assert!(pool.is_verifier(s));
         }
     }
+
+    #[test]
+    fn test_verification_record_counts_agreeing_votes() {
+        use burst_verification::state::{VerificationPhase, VerifierVote};
+
+        let vote = |name: &str, vote: Vote| VerifierVote {
+            verifier: addr(name),
+            vote,
+            stake_amount: 0,
+            timestamp: Timestamp::new(50),
+        };
+        let state = VerificationState {
+            target: addr("target"),
+            phase: VerificationPhase::Verified,
+            endorsements: Vec::new(),
+            selected_verifiers: Vec::new(),
+            votes: vec![
+                vote("v1", Vote::Legitimate),
+                vote("v2", Vote::Legitimate),
+                vote("v3", Vote::Illegitimate),
+                vote("v4", Vote::Neither),
+            ],
+            revote_count: 0,
+            excluded_verifiers: HashSet::new(),
+            selection_candidates: Vec::new(),
+            round_deadline: None,
+            timed_out_rounds: 0,
+            started_at: Timestamp::new(10),
+        };
+
+        let verified = verification_record(
+            &state,
+            VerificationRecordOutcome::Verified,
+            Timestamp::new(90),
+        );
+        assert_eq!(verified.started_at, Timestamp::new(10));
+        assert_eq!(verified.completed_at, Timestamp::new(90));
+        assert_eq!(verified.votes_cast, 4);
+        assert_eq!(verified.votes_agreeing, 2);
+        assert_eq!(verified.neither_votes, 1);
+
+        let fraud = verification_record(
+            &state,
+            VerificationRecordOutcome::FraudConfirmed,
+            Timestamp::new(90),
+        );
+        assert_eq!(fraud.votes_agreeing, 2);
+
+        let expired = verification_record(
+            &state,
+            VerificationRecordOutcome::Expired,
+            Timestamp::new(90),
+        );
+        assert_eq!(expired.votes_agreeing, 0);
+    }
 }
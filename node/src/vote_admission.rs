@@ -0,0 +1,217 @@
+//! Weight-proportional admission of inbound votes.
+//!
+//! Every vote that reaches the vote lane costs a signature check, so votes
+//! are rate limited in the read loop before they are queued. Each voter has
+//! a token bucket whose refill rate is a floor plus its share of a
+//! network-wide vote budget, in proportion to its cached weight: large
+//! representatives can vote as fast as elections need, while a key with no
+//! weight cannot flood the node. Voters with no weight share one small
+//! bucket — their votes never count towards quorum, so they only get
+//! whatever is left of that budget.
+
+use std::collections::HashMap;
+
+use burst_types::WalletAddress;
+
+/// Network-wide votes per second, shared among voters by weight.
+pub const DEFAULT_VOTE_BUDGET_PER_SEC: u64 = 2_000;
+
+/// Votes per second every voter with weight is allowed regardless of share.
+pub const MIN_WEIGHTED_VOTES_PER_SEC: u64 = 10;
+
+/// Votes per second shared by all voters without weight.
+pub const ZERO_WEIGHT_VOTES_PER_SEC: u64 = 20;
+
+/// Voter buckets kept before idle ones are evicted.
+const MAX_TRACKED_VOTERS: usize = 16_384;
+
+/// Outcome of offering a vote for admission.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoteAdmission {
+    /// The vote may be queued.
+    Admitted,
+    /// The voter has used up its weight-proportional rate.
+    RateLimited,
+    /// The voter has no weight and the shared budget for such voters is
+    /// exhausted.
+    ZeroWeight,
+}
+
+impl VoteAdmission {
+    /// Short name, used as the metrics label.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VoteAdmission::Admitted => "admitted",
+            VoteAdmission::RateLimited => "rate_limited",
+            VoteAdmission::ZeroWeight => "zero_weight",
+        }
+    }
+}
+
+/// Token bucket counted in thousandths of a vote, so a rate of `r` votes per
+/// second refills `r` milli-votes per millisecond. Holds up to two seconds
+/// of votes to absorb bursts.
+struct TokenBucket {
+    votes_per_sec: u64,
+    milli_tokens: u64,
+    last_refill_ms: u64,
+}
+
+impl TokenBucket {
+    fn new(votes_per_sec: u64, now_ms: u64) -> Self {
+        Self {
+            votes_per_sec,
+            milli_tokens: votes_per_sec * 2_000,
+            last_refill_ms: now_ms,
+        }
+    }
+
+    fn refill(&mut self, now_ms: u64) {
+        let elapsed = now_ms.saturating_sub(self.last_refill_ms);
+        self.milli_tokens = self
+            .milli_tokens
+            .saturating_add(elapsed.saturating_mul(self.votes_per_sec))
+            .min(self.votes_per_sec * 2_000);
+        self.last_refill_ms = now_ms;
+    }
+
+    fn try_take(&mut self, now_ms: u64) -> bool {
+        self.refill(now_ms);
+        if self.milli_tokens < 1_000 {
+            return false;
+        }
+        self.milli_tokens -= 1_000;
+        true
+    }
+
+    fn is_full(&mut self, now_ms: u64) -> bool {
+        self.refill(now_ms);
+        self.milli_tokens >= self.votes_per_sec * 2_000
+    }
+}
+
+/// Per-voter token buckets sized by representative weight.
+pub struct VoteAdmissionControl {
+    budget_per_sec: u64,
+    voters: HashMap<WalletAddress, TokenBucket>,
+    zero_weight: TokenBucket,
+}
+
+impl VoteAdmissionControl {
+    pub fn new(budget_per_sec: u64) -> Self {
+        Self {
+            budget_per_sec,
+            voters: HashMap::new(),
+            zero_weight: TokenBucket::new(ZERO_WEIGHT_VOTES_PER_SEC, 0),
+        }
+    }
+
+    /// Votes per second allowed for a voter with `weight` out of
+    /// `total_weight`.
+    pub fn rate_for(&self, weight: u128, total_weight: u128) -> u64 {
+        if weight == 0 {
+            return 0;
+        }
+        let share = (self.budget_per_sec as u128 * weight.min(total_weight))
+            .checked_div(total_weight)
+            .unwrap_or(0);
+        MIN_WEIGHTED_VOTES_PER_SEC + share as u64
+    }
+
+    /// Decide whether a vote from `voter` may be queued, consuming a token
+    /// when it is.
+    pub fn admit(
+        &mut self,
+        voter: &WalletAddress,
+        weight: u128,
+        total_weight: u128,
+        now_ms: u64,
+    ) -> VoteAdmission {
+        if weight == 0 {
+            if self.zero_weight.try_take(now_ms) {
+                return VoteAdmission::Admitted;
+            }
+            return VoteAdmission::ZeroWeight;
+        }
+
+        let rate = self.rate_for(weight, total_weight);
+        if !self.voters.contains_key(voter) && self.voters.len() >= MAX_TRACKED_VOTERS {
+            self.voters.retain(|_, bucket| !bucket.is_full(now_ms));
+        }
+        let bucket = self
+            .voters
+            .entry(voter.clone())
+            .or_insert_with(|| TokenBucket::new(rate, now_ms));
+        // Weights move as delegations change; resize the bucket in place.
+        if bucket.votes_per_sec != rate {
+            bucket.refill(now_ms);
+            bucket.votes_per_sec = rate;
+            bucket.milli_tokens = bucket.milli_tokens.min(rate * 2_000);
+        }
+        if bucket.try_take(now_ms) {
+            VoteAdmission::Admitted
+        } else {
+            VoteAdmission::RateLimited
+        }
+    }
+
+    /// Number of voters with a bucket.
+    pub fn tracked_voters(&self) -> usize {
+        self.voters.len()
+    }
+}
+
+impl Default for VoteAdmissionControl {
+    fn default() -> Self {
+        Self::new(DEFAULT_VOTE_BUDGET_PER_SEC)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn admitted(control: &mut VoteAdmissionControl, voter: &WalletAddress, weight: u128) -> u64 {
+        (0..100_000)
+            .take_while(|_| control.admit(voter, weight, 1_000, 0) == VoteAdmission::Admitted)
+            .count() as u64
+    }
+
+    #[test]
+    fn burst_allowance_scales_with_weight() {
+        let mut control = VoteAdmissionControl::new(1_000);
+        let whale = WalletAddress::new("brst_whale");
+        let minnow = WalletAddress::new("brst_minnow");
+
+        // 50% of a 1000 votes/s budget plus the floor, two seconds' worth.
+        assert_eq!(admitted(&mut control, &whale, 500), 2 * 510);
+        assert_eq!(admitted(&mut control, &minnow, 1), 2 * 11);
+        assert_eq!(
+            control.admit(&whale, 500, 1_000, 0),
+            VoteAdmission::RateLimited
+        );
+
+        // Half a second refills half a second of rate.
+        let refilled = (0..1_000)
+            .take_while(|_| control.admit(&whale, 500, 1_000, 500) == VoteAdmission::Admitted)
+            .count();
+        assert_eq!(refilled, 255);
+        assert_eq!(control.tracked_voters(), 2);
+    }
+
+    #[test]
+    fn zero_weight_voters_share_one_small_budget() {
+        let mut control = VoteAdmissionControl::default();
+        let mut outcomes = (0..1_000u32).map(|i| {
+            let voter = WalletAddress::new(format!("brst_nobody{i}"));
+            control.admit(&voter, 0, 1_000, 0)
+        });
+        let admitted = outcomes
+            .by_ref()
+            .take_while(|o| *o == VoteAdmission::Admitted)
+            .count() as u64;
+        assert_eq!(admitted, 2 * ZERO_WEIGHT_VOTES_PER_SEC);
+        assert!(outcomes.all(|o| o == VoteAdmission::ZeroWeight));
+        assert_eq!(control.tracked_voters(), 0);
+    }
+}
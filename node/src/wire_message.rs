@@ -3,12 +3,22 @@
 //! Every message sent between BURST nodes is wrapped in [`WireMessage`].
 //! The peer read loop deserializes incoming frames as `WireMessage` first;
 //! if that fails it falls back to bare `StateBlock` for backward compat.
-
-use burst_ledger::StateBlock;
+//!
+//! The codec is bincode, which encodes fields by position and has no notion
+//! of a missing field, so a released message layout never changes. New
+//! fields go into a new variant appended to the enum: older peers fail to
+//! decode it and drop the frame, and newer peers keep understanding the old
+//! variant.
+
+use burst_crypto::DirectMessage;
+use burst_ledger::{CheckpointVote, StateBlock};
+use burst_protocol::codec::decode_borrowed;
 use burst_types::{BlockHash, Signature, WalletAddress};
 use serde::{Deserialize, Serialize};
 
 use crate::bootstrap::BootstrapMessage;
+use crate::revocation_notice::RevocationNotice;
+use crate::state_digest::StateDigest;
 
 /// Top-level P2P wire message.
 /// Every message sent between nodes is wrapped in this enum.
@@ -40,6 +50,49 @@ pub enum WireMessage {
     TelemetryReq,
     /// Telemetry acknowledgment with node stats
     TelemetryAck(TelemetryAckMessage),
+    /// Fraud revocation backed by a quorum certificate
+    RevocationNotice(Box<RevocationNotice>),
+    /// A representative's signature over a ledger checkpoint
+    CheckpointVote(CheckpointVote),
+    /// A relayed message with its remaining relay budget
+    Gossip(GossipEnvelope),
+    /// An encrypted message from one wallet to another, held for the
+    /// recipient until it expires
+    DirectMessage(Box<DirectMessage>),
+    /// Telemetry request answered with a `TelemetryAckV2`; only sent to
+    /// peers whose telemetry reports [`TELEMETRY_VERSION`] 2 or later
+    TelemetryReqV2,
+    /// Telemetry acknowledgment with load and ledger-state figures
+    TelemetryAckV2(TelemetryAckV2Message),
+}
+
+/// Telemetry layout this node speaks, reported as `protocol_version` in its
+/// telemetry acks. Peers reporting 2 or later understand
+/// [`WireMessage::TelemetryReqV2`].
+pub const TELEMETRY_VERSION: u8 = 2;
+
+/// bincode tags of the [`WireMessage`] variants whose payload starts with a
+/// [`WireVote`].
+const VOTE_TAG: u32 = 1;
+const CONFIRM_ACK_TAG: u32 = 3;
+
+/// The leading field of a [`WireVote`], borrowed from the frame.
+#[derive(Deserialize)]
+struct VoterPrefix<'a> {
+    voter: &'a str,
+}
+
+/// The voter of a `Vote` or `ConfirmAck` frame, read in place without
+/// decoding the rest of the message. `None` for other messages or a
+/// malformed prefix.
+pub fn peek_voter(frame: &[u8]) -> Option<&str> {
+    let (tag, rest) = frame.split_first_chunk::<4>()?;
+    if !matches!(u32::from_le_bytes(*tag), VOTE_TAG | CONFIRM_ACK_TAG) {
+        return None;
+    }
+    decode_borrowed::<VoterPrefix<'_>>(rest)
+        .ok()
+        .map(|prefix| prefix.voter)
 }
 
 /// A vote broadcast on the network.
@@ -53,6 +106,17 @@ pub struct WireVote {
     pub signature: Signature,
 }
 
+/// A flooded message and how far it may still spread. See
+/// [`crate::gossip`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GossipEnvelope {
+    /// Relays left; each relay decrements it and none happens at 1.
+    pub ttl: u8,
+    /// Relays so far.
+    pub hops: u8,
+    pub message: Box<WireMessage>,
+}
+
 /// Request votes for specific block hashes (confirm_req).
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ConfirmReqMsg {
@@ -79,7 +143,6 @@ pub struct HandshakeMsg {
     pub cookie_signature: Option<Signature>,
     /// Deterministic hash of the node's current ProtocolParams.
     /// Peers compare this to detect protocol version divergence.
-    #[serde(default)]
     pub params_hash: BlockHash,
 }
 
@@ -117,7 +180,8 @@ pub struct GovernanceVoteMessage {
     pub vote: u8,
 }
 
-/// Telemetry acknowledgment carrying node statistics.
+/// Telemetry acknowledgment carrying node statistics, in the layout of
+/// telemetry version 1.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TelemetryAckMessage {
     pub block_count: u64,
@@ -134,10 +198,24 @@ pub struct TelemetryAckMessage {
     pub patch_version: u8,
     pub timestamp: u64,
     /// Deterministic hash of the node's current ProtocolParams.
-    #[serde(default)]
     pub params_hash: BlockHash,
 }
 
+/// Telemetry version 2 acknowledgment: the version 1 statistics plus the
+/// node's load and ledger state.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TelemetryAckV2Message {
+    pub base: TelemetryAckMessage,
+    /// Whether the node is shedding load and demanding extra work.
+    pub overloaded: bool,
+    /// Root of the node's account-state tree.
+    pub account_state_root: BlockHash,
+    /// The node's latest digest of its cemented state.
+    pub state_digest: Option<StateDigest>,
+    /// Network load multiplier the node applies to the work threshold.
+    pub network_multiplier: u64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,7 +223,7 @@ mod tests {
     use burst_types::{Timestamp, TxHash};
 
     fn addr(s: &str) -> WalletAddress {
-        WalletAddress::new(&format!("brst_{s}"))
+        WalletAddress::new(format!("brst_{s}"))
     }
 
     fn sample_block() -> StateBlock {
@@ -162,6 +240,12 @@ mod tests {
             transaction: TxHash::ZERO,
             timestamp: Timestamp::new(42),
             params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
             work: 0xDEAD,
             signature: Signature([0xFF; 64]),
             hash: BlockHash::ZERO,
@@ -201,12 +285,26 @@ mod tests {
             WireMessage::Vote(v) => {
                 assert_eq!(v.voter, addr("voter1"));
                 assert_eq!(v.block_hashes.len(), 1);
-                assert_eq!(v.is_final, false);
+                assert!(!v.is_final);
             }
             other => panic!("expected Vote, got {:?}", other),
         }
     }
 
+    #[test]
+    fn peek_voter_reads_votes_in_place() {
+        let vote = bincode::serialize(&WireMessage::Vote(sample_vote())).unwrap();
+        assert_eq!(peek_voter(&vote), Some("brst_voter1"));
+        let ack = bincode::serialize(&WireMessage::ConfirmAck(ConfirmAckMsg {
+            vote: sample_vote(),
+        }))
+        .unwrap();
+        assert_eq!(peek_voter(&ack), Some("brst_voter1"));
+        let block = bincode::serialize(&WireMessage::Block(Box::new(sample_block()))).unwrap();
+        assert_eq!(peek_voter(&block), None);
+        assert_eq!(peek_voter(&vote[..6]), None);
+    }
+
     #[test]
     fn confirm_req_roundtrip() {
         let msg = WireMessage::ConfirmReq(ConfirmReqMsg {
@@ -362,16 +460,15 @@ mod tests {
         assert!(matches!(decoded, WireMessage::TelemetryReq));
     }
 
-    #[test]
-    fn telemetry_ack_roundtrip() {
-        let msg = WireMessage::TelemetryAck(TelemetryAckMessage {
+    fn sample_telemetry() -> TelemetryAckMessage {
+        TelemetryAckMessage {
             block_count: 1_000_000,
             cemented_count: 999_000,
             unchecked_count: 500,
             account_count: 50_000,
             bandwidth_cap: 10_000_000,
             peer_count: 200,
-            protocol_version: 1,
+            protocol_version: TELEMETRY_VERSION,
             uptime: 86400,
             genesis_hash: BlockHash::new([0xFF; 32]),
             major_version: 0,
@@ -379,17 +476,87 @@ mod tests {
             patch_version: 0,
             timestamp: 1700000000,
             params_hash: BlockHash::ZERO,
+        }
+    }
+
+    #[test]
+    fn telemetry_ack_roundtrip() {
+        let msg = WireMessage::TelemetryAckV2(TelemetryAckV2Message {
+            base: sample_telemetry(),
+            overloaded: false,
+            account_state_root: BlockHash::new([0xAB; 32]),
+            state_digest: Some(StateDigest {
+                cemented_count: 999_000,
+                buckets: vec![BlockHash::new([0xCD; 32]); 16],
+            }),
+            network_multiplier: 4,
         });
         let bytes = bincode::serialize(&msg).unwrap();
         let decoded: WireMessage = bincode::deserialize(&bytes).unwrap();
         match decoded {
+            WireMessage::TelemetryAckV2(t) => {
+                assert_eq!(t.base.block_count, 1_000_000);
+                assert_eq!(t.base.peer_count, 200);
+                assert_eq!(t.base.protocol_version, TELEMETRY_VERSION);
+                assert_eq!(t.state_digest.unwrap().cemented_count, 999_000);
+                assert_eq!(t.network_multiplier, 4);
+            }
+            other => panic!("expected TelemetryAckV2, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn version_1_telemetry_keeps_its_layout() {
+        // A telemetry ack as sent by a version 1 peer: variant 12 followed
+        // by its fields in order, nothing appended.
+        let legacy = bincode::serialize(&(
+            12u32,
+            (1_000_000u64, 999_000u64, 500u64, 50_000u64, 10_000_000u64),
+            (200u32, 1u8, 86400u64, BlockHash::new([0xFF; 32])),
+            (0u8, 1u8, 0u8, 1700000000u64, BlockHash::ZERO),
+        ))
+        .unwrap();
+        match bincode::deserialize::<WireMessage>(&legacy).unwrap() {
             WireMessage::TelemetryAck(t) => {
                 assert_eq!(t.block_count, 1_000_000);
-                assert_eq!(t.peer_count, 200);
                 assert_eq!(t.protocol_version, 1);
+                assert_eq!(t.timestamp, 1700000000);
             }
             other => panic!("expected TelemetryAck, got {:?}", other),
         }
+
+        let mut current = sample_telemetry();
+        current.protocol_version = 1;
+        assert_eq!(
+            bincode::serialize(&WireMessage::TelemetryAck(current)).unwrap(),
+            legacy
+        );
+    }
+
+    #[test]
+    fn revocation_notice_roundtrip() {
+        let block = sample_block();
+        let msg = WireMessage::RevocationNotice(Box::new(RevocationNotice {
+            wallet: addr("fraud"),
+            certificate: burst_ledger::QuorumCertificate {
+                block_hash: block.hash,
+                votes: Vec::new(),
+                cemented_at: 50,
+            },
+            verdict_block: block,
+            issuer: addr("issuer"),
+            issued_at: 60,
+            signature: Signature([0x22; 64]),
+        }));
+        let bytes = bincode::serialize(&msg).unwrap();
+        let decoded: WireMessage = bincode::deserialize(&bytes).unwrap();
+        match decoded {
+            WireMessage::RevocationNotice(n) => {
+                assert_eq!(n.wallet, addr("fraud"));
+                assert_eq!(n.certificate.block_hash, n.verdict_block.hash);
+            }
+            other => panic!("expected RevocationNotice, got {:?}", other),
+        }
     }
 
     #[test]
@@ -20,7 +20,7 @@ use burst_types::{
 // ---------------------------------------------------------------------------
 
 fn arb_wallet_address() -> impl Strategy<Value = WalletAddress> {
-    "[a-z0-9]{10,30}".prop_map(|s| WalletAddress::new(&format!("brst_{s}")))
+    "[a-z0-9]{10,30}".prop_map(|s| WalletAddress::new(format!("brst_{s}")))
 }
 
 fn arb_block_hash() -> impl Strategy<Value = BlockHash> {
@@ -115,6 +115,12 @@ fn arb_state_block() -> impl Strategy<Value = StateBlock> {
                     transaction: TxHash::ZERO,
                     timestamp: ts,
                     params_hash: BlockHash::ZERO,
+                    memo: None,
+                    htlc: None,
+                    reject_reason: None,
+                    recovery: None,
+                    delegation_key: None,
+                    extensions: Vec::new(),
                     work,
                     signature: sig,
                     hash: BlockHash::ZERO,
@@ -238,6 +244,10 @@ fn arb_pending_info() -> impl Strategy<Value = PendingInfo> {
             amount,
             timestamp: ts,
             provenance: prov,
+            memo: None,
+            destination_tag: None,
+            htlc: None,
+            returned: false,
         })
 }
 
@@ -272,6 +282,7 @@ fn arb_brn_wallet_state() -> impl Strategy<Value = BrnWalletState> {
                 total_staked: staked,
                 accrual_active: active,
                 accrual_stopped_at: stopped,
+                ..BrnWalletState::new(verified_at)
             },
         )
 }
@@ -326,6 +337,7 @@ proptest! {
     #![proptest_config(ProptestConfig::with_cases(1000))]
 
     #[test]
+    #[allow(clippy::absurd_extreme_comparisons)]
     fn fuzz_brn_balance_never_negative(
         rate in 0u128..1_000_000,
         verified_secs in 0u64..1_000_000,
@@ -407,12 +419,12 @@ proptest! {
 fn stress_lmdb_1000_accounts() {
     use burst_store::account::AccountStore;
     let dir = tempfile::tempdir().unwrap();
-    let env = burst_store_lmdb::LmdbEnvironment::open(dir.path(), 30, 256 * 1024 * 1024).unwrap();
+    let env = burst_store_lmdb::LmdbEnvironment::open(dir.path(), 46, 256 * 1024 * 1024).unwrap();
     let store = env.account_store();
 
     let accounts: Vec<AccountInfo> = (0u16..1000)
         .map(|i| {
-            let addr = WalletAddress::new(&format!("brst_{i:05}_{:032x}", i as u128));
+            let addr = WalletAddress::new(format!("brst_{i:05}_{:032x}", i as u128));
             AccountInfo {
                 address: addr.clone(),
                 state: if i % 3 == 0 {
@@ -460,7 +472,7 @@ fn stress_lmdb_1000_accounts() {
 fn stress_lmdb_1000_pending_entries() {
     use burst_store::pending::PendingStore;
     let dir = tempfile::tempdir().unwrap();
-    let env = burst_store_lmdb::LmdbEnvironment::open(dir.path(), 30, 256 * 1024 * 1024).unwrap();
+    let env = burst_store_lmdb::LmdbEnvironment::open(dir.path(), 46, 256 * 1024 * 1024).unwrap();
     let store = env.pending_store();
 
     let dest = WalletAddress::new("brst_destination0000000000000000");
@@ -470,10 +482,14 @@ fn stress_lmdb_1000_pending_entries() {
         hash_bytes[..4].copy_from_slice(&i.to_be_bytes());
         let source_hash = TxHash::new(hash_bytes);
         let info = PendingInfo {
-            source: WalletAddress::new(&format!("brst_sender_{i:028}")),
+            source: WalletAddress::new(format!("brst_sender_{i:028}")),
             amount: i as u128 * 10,
             timestamp: Timestamp::new(i as u64 * 100),
             provenance: Vec::new(),
+            memo: None,
+            destination_tag: None,
+            htlc: None,
+            returned: false,
         };
         store.put_pending(&dest, &source_hash, &info).unwrap();
     }
@@ -488,11 +504,11 @@ fn stress_lmdb_1000_pending_entries() {
 fn stress_lmdb_account_pagination() {
     use burst_store::account::AccountStore;
     let dir = tempfile::tempdir().unwrap();
-    let env = burst_store_lmdb::LmdbEnvironment::open(dir.path(), 30, 256 * 1024 * 1024).unwrap();
+    let env = burst_store_lmdb::LmdbEnvironment::open(dir.path(), 46, 256 * 1024 * 1024).unwrap();
     let store = env.account_store();
 
     for i in 0u16..100 {
-        let addr = WalletAddress::new(&format!("brst_{i:05}_{:032x}", i as u128));
+        let addr = WalletAddress::new(format!("brst_{i:05}_{:032x}", i as u128));
         let info = AccountInfo {
             address: addr,
             state: WalletState::Unverified,
@@ -540,7 +556,7 @@ fn stress_lmdb_account_pagination() {
 fn write_batch_partial_failure_does_not_corrupt() {
     use burst_store::block::BlockStore;
     let dir = tempfile::tempdir().unwrap();
-    let env = burst_store_lmdb::LmdbEnvironment::open(dir.path(), 30, 64 * 1024 * 1024).unwrap();
+    let env = burst_store_lmdb::LmdbEnvironment::open(dir.path(), 46, 64 * 1024 * 1024).unwrap();
 
     let hash1 = BlockHash::new([1u8; 32]);
     let hash2 = BlockHash::new([2u8; 32]);
@@ -22,7 +22,7 @@ use burst_types::{BlockHash, Signature, Timestamp, TrstState, TxHash, WalletAddr
 
 fn temp_env() -> (tempfile::TempDir, LmdbEnvironment) {
     let dir = tempfile::tempdir().expect("temp dir");
-    let env = LmdbEnvironment::open(dir.path(), 30, 64 * 1024 * 1024).expect("open env");
+    let env = LmdbEnvironment::open(dir.path(), 46, 64 * 1024 * 1024).expect("open env");
     (dir, env)
 }
 
@@ -35,6 +35,7 @@ fn pubkey_bytes(addr: &WalletAddress) -> [u8; 32] {
     burst_crypto::decode_address(addr.as_str()).expect("valid address")
 }
 
+#[allow(clippy::too_many_arguments)]
 fn make_block(
     block_type: BlockType,
     account: &WalletAddress,
@@ -61,6 +62,12 @@ fn make_block(
         transaction: TxHash::ZERO,
         timestamp: Timestamp::new(ts),
         params_hash: BlockHash::ZERO,
+        memo: None,
+        htlc: None,
+        reject_reason: None,
+        recovery: None,
+        delegation_key: None,
+        extensions: Vec::new(),
         work: 0,
         signature: Signature(dummy_sig),
         hash: BlockHash::ZERO,
@@ -1573,6 +1580,10 @@ fn create_received_token_single_provenance() {
             effective_origin_timestamp: Timestamp::new(1000),
             origin_proportions: Vec::new(),
         }],
+        memo: None,
+        destination_tag: None,
+        htlc: None,
+        returned: false,
     };
 
     let token =
@@ -1626,6 +1637,10 @@ fn create_received_token_multi_provenance_uses_earliest_timestamp() {
                 origin_proportions: Vec::new(),
             },
         ],
+        memo: None,
+        destination_tag: None,
+        htlc: None,
+        returned: false,
     };
 
     let token =
@@ -1664,6 +1679,10 @@ fn create_received_token_no_provenance_uses_pending_timestamp() {
         amount: 100,
         timestamp: Timestamp::new(8000),
         provenance: Vec::new(),
+        memo: None,
+        destination_tag: None,
+        htlc: None,
+        returned: false,
     };
 
     let token =
@@ -2008,9 +2027,11 @@ fn reject_receive_returns_no_balance_change() {
         burst_node::EconomicResult::RejectReceive {
             rejecter: r,
             send_block_hash,
+            reason,
         } => {
             assert_eq!(r, rejecter);
             assert_eq!(send_block_hash, BlockHash::new([0xDD; 32]));
+            assert_eq!(reason, None);
         }
         other => panic!("expected RejectReceive, got {:?}", other),
     }
@@ -2036,3 +2057,164 @@ fn brn_accrual_piecewise_rate_history() {
     let accrued = history.total_accrued(verified_at, now);
     assert_eq!(accrued, 1_000_000);
 }
+
+// ---------------------------------------------------------------------------
+// 22. RejectReceive returns the pending transfer to the sender
+// ---------------------------------------------------------------------------
+
+#[test]
+fn reject_receive_returns_pending_to_sender() {
+    let (_dir, env) = temp_env();
+    let sender = make_address(230);
+    let receiver = make_address(231);
+    let rep = make_address(232);
+
+    let send_block = make_block(
+        BlockType::Send,
+        &sender,
+        BlockHash::new([1u8; 32]),
+        &rep,
+        0,
+        700,
+        BlockHash::new(pubkey_bytes(&receiver)),
+        TxHash::ZERO,
+        2000,
+    );
+    let mut batch = env.write_batch().unwrap();
+    burst_node::create_pending_entry(&mut batch, &send_block, 300, &receiver, Vec::new()).unwrap();
+    batch.commit().unwrap();
+
+    let pending_store = env.pending_store();
+    let send_hash = TxHash::new(*send_block.hash.as_bytes());
+    let rejected = pending_store.get_pending(&receiver, &send_hash).unwrap();
+
+    let mut reject_block = make_block(
+        BlockType::RejectReceive,
+        &receiver,
+        BlockHash::new([2u8; 32]),
+        &rep,
+        0,
+        0,
+        send_block.hash,
+        TxHash::ZERO,
+        3000,
+    );
+    reject_block.reject_reason =
+        Some(burst_transactions::reject_receive::RejectReason::UnknownSender);
+    reject_block.hash = reject_block.compute_hash();
+
+    let mut batch = env.write_batch().unwrap();
+    burst_node::delete_pending_entry(&mut batch, &reject_block).unwrap();
+    burst_node::create_returned_pending_entry(&mut batch, &reject_block, &rejected).unwrap();
+    batch.commit().unwrap();
+
+    assert!(pending_store.get_pending(&receiver, &send_hash).is_err());
+    let returned = pending_store
+        .get_pending(&sender, &TxHash::new(*reject_block.hash.as_bytes()))
+        .expect("returned pending keyed by the reject block");
+    assert_eq!(returned.amount, 300);
+    assert_eq!(returned.source, receiver);
+}
+
+// ---------------------------------------------------------------------------
+// 23. Unreceived sends go back to the sender after the pending TTL
+// ---------------------------------------------------------------------------
+
+#[test]
+fn pending_ttl_returns_unreceived_send_to_sender() {
+    let (_dir, env) = temp_env();
+    let sender = make_address(233);
+    let receiver = make_address(234);
+    let rep = make_address(235);
+    let ttl = 1_000;
+
+    let send_block = make_block(
+        BlockType::Send,
+        &sender,
+        BlockHash::new([1u8; 32]),
+        &rep,
+        0,
+        700,
+        BlockHash::new(pubkey_bytes(&receiver)),
+        TxHash::ZERO,
+        2000,
+    );
+    let mut batch = env.write_batch().unwrap();
+    burst_node::create_pending_entry(&mut batch, &send_block, 300, &receiver, Vec::new()).unwrap();
+    batch.commit().unwrap();
+    let send_hash = TxHash::new(*send_block.hash.as_bytes());
+
+    // Not yet expired.
+    let returned = burst_node::return_expired_pending(&env, ttl, Timestamp::new(2999)).unwrap();
+    assert!(returned.is_empty());
+
+    let returned = burst_node::return_expired_pending(&env, ttl, Timestamp::new(5000)).unwrap();
+    assert_eq!(returned.len(), 1);
+
+    let pending_store = env.pending_store();
+    assert!(pending_store.get_pending(&receiver, &send_hash).is_err());
+    let back = pending_store
+        .get_pending(&sender, &send_hash)
+        .expect("returned pending keyed by the send block");
+    assert_eq!(back.amount, 300);
+    assert_eq!(back.source, receiver);
+    assert!(back.returned);
+    assert_eq!(back.timestamp, Timestamp::new(3000));
+
+    // A returned entry never bounces back.
+    let again = burst_node::return_expired_pending(&env, ttl, Timestamp::new(99_999)).unwrap();
+    assert!(again.is_empty());
+}
+
+#[test]
+fn rep_weight_check_corrections_survive_restart() {
+    use burst_store::account::{AccountInfo, AccountStore};
+    use burst_store::rep_weights::RepWeightStore;
+
+    let dir = tempfile::tempdir().expect("temp dir");
+    let open = || LmdbEnvironment::open(dir.path(), 46, 64 * 1024 * 1024).expect("open env");
+    let (holder, stale_rep, live_rep) = (make_address(240), make_address(241), make_address(242));
+
+    let env = open();
+    env.account_store()
+        .put_account(&AccountInfo {
+            address: holder.clone(),
+            state: burst_types::WalletState::Verified,
+            verified_at: None,
+            head: BlockHash::ZERO,
+            block_count: 1,
+            confirmation_height: 1,
+            representative: live_rep.clone(),
+            total_brn_burned: 0,
+            total_brn_staked: 0,
+            trst_balance: 800,
+            expired_trst: 0,
+            revoked_trst: 0,
+            epoch: 0,
+        })
+        .unwrap();
+    // Weight persisted by an earlier run for a representative nobody
+    // delegates to any more.
+    env.rep_weight_store()
+        .put_rep_weight(&stale_rep, 800)
+        .unwrap();
+    let mut cache = RepWeightCache::new();
+    cache.add_weight(&stale_rep, 800);
+
+    // One tick of the periodic check.
+    let drift = burst_rpc::handlers::recalculate_rep_weights(
+        &mut cache,
+        &env.account_store(),
+        &env.rep_weight_store(),
+    )
+    .unwrap();
+    assert_eq!(drift.len(), 2);
+    drop(env);
+
+    // The next start-up loads the corrected set, without the stale rep.
+    let env = open();
+    assert_eq!(
+        env.rep_weight_store().iter_rep_weights().unwrap(),
+        vec![(live_rep, 800)]
+    );
+}
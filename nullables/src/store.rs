@@ -3,8 +3,9 @@
 use burst_store::account::{AccountInfo, AccountStore};
 use burst_store::block::BlockStore;
 use burst_store::delegation::{DelegationRecord, DelegationStore};
+use burst_store::recovery::{RecoveryConfig, RecoveryStore};
 use burst_store::StoreError;
-use burst_types::{BlockHash, WalletAddress};
+use burst_types::{BlockHash, PublicKey, StateTransition, WalletAddress};
 use std::collections::HashMap;
 use std::sync::Mutex;
 
@@ -14,6 +15,7 @@ pub struct NullStore {
     accounts: Mutex<HashMap<String, AccountInfo>>,
     blocks: Mutex<HashMap<[u8; 32], Vec<u8>>>,
     account_blocks: Mutex<HashMap<String, Vec<BlockHash>>>,
+    state_transitions: Mutex<HashMap<String, Vec<StateTransition>>>,
 }
 
 impl NullStore {
@@ -22,6 +24,7 @@ impl NullStore {
             accounts: Mutex::new(HashMap::new()),
             blocks: Mutex::new(HashMap::new()),
             account_blocks: Mutex::new(HashMap::new()),
+            state_transitions: Mutex::new(HashMap::new()),
         }
     }
 
@@ -82,6 +85,33 @@ impl AccountStore for NullStore {
             .cloned()
             .collect())
     }
+
+    fn put_state_transition(
+        &self,
+        address: &WalletAddress,
+        transition: &StateTransition,
+    ) -> Result<(), StoreError> {
+        self.state_transitions
+            .lock()
+            .unwrap()
+            .entry(address.to_string())
+            .or_default()
+            .push(transition.clone());
+        Ok(())
+    }
+
+    fn get_state_transitions(
+        &self,
+        address: &WalletAddress,
+    ) -> Result<Vec<StateTransition>, StoreError> {
+        Ok(self
+            .state_transitions
+            .lock()
+            .unwrap()
+            .get(address.as_str())
+            .cloned()
+            .unwrap_or_default())
+    }
 }
 
 impl BlockStore for NullStore {
@@ -162,14 +192,12 @@ impl Default for NullDelegationStore {
 impl DelegationStore for NullDelegationStore {
     fn put_delegation(&self, record: &DelegationRecord) -> Result<(), StoreError> {
         let key = record.delegator.to_string();
-        self.pubkey_index
-            .lock()
-            .unwrap()
-            .insert(record.delegation_public_key, key.clone());
-        self.by_delegator
-            .lock()
-            .unwrap()
-            .insert(key, record.clone());
+        let mut index = self.pubkey_index.lock().unwrap();
+        let mut records = self.by_delegator.lock().unwrap();
+        if let Some(previous) = records.insert(key.clone(), record.clone()) {
+            index.remove(&previous.delegation_public_key);
+        }
+        index.insert(record.delegation_public_key, key);
         Ok(())
     }
 
@@ -210,6 +238,53 @@ impl DelegationStore for NullDelegationStore {
     }
 }
 
+/// An in-memory recovery store for testing.
+#[derive(Default)]
+pub struct NullRecoveryStore {
+    configs: Mutex<HashMap<String, RecoveryConfig>>,
+    signing_keys: Mutex<HashMap<String, PublicKey>>,
+}
+
+impl NullRecoveryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RecoveryStore for NullRecoveryStore {
+    fn put_recovery_config(&self, config: &RecoveryConfig) -> Result<(), StoreError> {
+        self.configs
+            .lock()
+            .unwrap()
+            .insert(config.account.to_string(), config.clone());
+        Ok(())
+    }
+
+    fn get_recovery_config(
+        &self,
+        account: &WalletAddress,
+    ) -> Result<Option<RecoveryConfig>, StoreError> {
+        Ok(self.configs.lock().unwrap().get(account.as_str()).cloned())
+    }
+
+    fn put_signing_key(&self, account: &WalletAddress, key: &PublicKey) -> Result<(), StoreError> {
+        self.signing_keys
+            .lock()
+            .unwrap()
+            .insert(account.to_string(), key.clone());
+        Ok(())
+    }
+
+    fn get_signing_key(&self, account: &WalletAddress) -> Result<Option<PublicKey>, StoreError> {
+        Ok(self
+            .signing_keys
+            .lock()
+            .unwrap()
+            .get(account.as_str())
+            .cloned())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -270,4 +345,37 @@ mod tests {
         store.delete_block(&hash).unwrap();
         assert!(store.get_block(&hash).is_err());
     }
+
+    #[test]
+    fn test_rotate_delegation_key_supersedes_old_key() {
+        let store = NullDelegationStore::new();
+        let delegator = test_address();
+        store
+            .put_delegation(&DelegationRecord {
+                delegator: delegator.clone(),
+                delegate: WalletAddress::new("brst_delegate".to_string()),
+                delegation_public_key: [1u8; 32],
+                generation: 1,
+                created_at: Timestamp::new(100),
+                revoked: false,
+            })
+            .unwrap();
+
+        let rotated = store
+            .rotate_delegation_key(&delegator, [2u8; 32], Timestamp::new(200))
+            .unwrap();
+        assert_eq!(rotated.generation, 2);
+        assert!(store
+            .get_delegation_by_pubkey(&[1u8; 32])
+            .unwrap()
+            .is_none());
+        let current = store.get_delegation_by_pubkey(&[2u8; 32]).unwrap().unwrap();
+        assert_eq!(current.generation, 2);
+        assert_eq!(current.created_at, Timestamp::new(200));
+
+        store.revoke_delegation(&delegator).unwrap();
+        assert!(store
+            .rotate_delegation_key(&delegator, [3u8; 32], Timestamp::new(300))
+            .is_err());
+    }
 }
@@ -2,12 +2,32 @@
 //!
 //! Uses bincode for efficient binary serialization with 4-byte big-endian
 //! length-prefix framing.
+//!
+//! Read loops use [`FrameReader`]: it reads into a buffer taken from a
+//! shared [`BufferPool`] and hands out each frame body as [`Bytes`] that
+//! share that buffer, so steady-state reading allocates nothing per
+//! message. Hot message types can be inspected in place with
+//! [`decode_borrowed`] before (or instead of) a full owned decode.
+
+use std::sync::{Arc, Mutex};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use tokio::io::{AsyncRead, AsyncReadExt};
 
 use crate::ProtocolError;
 
 /// Maximum message size in bytes.
 pub const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024; // 16 MiB
 
+/// Size of the big-endian length prefix.
+pub const FRAME_HEADER_LEN: usize = 4;
+
+/// Starting capacity of pooled read buffers; most messages fit.
+pub const DEFAULT_READ_BUFFER_CAPACITY: usize = 16 * 1024;
+
+/// Read buffers kept for reuse once their connections close.
+pub const DEFAULT_POOLED_BUFFERS: usize = 256;
+
 /// Encode a message for transmission (4-byte big-endian length prefix + bincode body).
 pub fn encode(message: &impl serde::Serialize) -> Result<Vec<u8>, ProtocolError> {
     let body = bincode::serialize(message).map_err(|e| ProtocolError::Malformed(e.to_string()))?;
@@ -57,6 +77,153 @@ pub fn decode_framed<T: serde::de::DeserializeOwned>(
     Ok((message, 4 + len))
 }
 
+/// Deserialize a message that borrows strings and byte slices from `data`
+/// instead of copying them. Trailing bytes are ignored, so a prefix of a
+/// larger message can be read this way.
+pub fn decode_borrowed<'a, T: serde::Deserialize<'a>>(data: &'a [u8]) -> Result<T, ProtocolError> {
+    bincode::deserialize(data).map_err(|e| ProtocolError::Malformed(e.to_string()))
+}
+
+/// Append a framed message (length prefix + bincode body) to `buf`.
+pub fn encode_into(
+    message: &impl serde::Serialize,
+    buf: &mut BytesMut,
+) -> Result<(), ProtocolError> {
+    let len = bincode::serialized_size(message)
+        .map_err(|e| ProtocolError::Malformed(e.to_string()))? as usize;
+    if len > MAX_MESSAGE_SIZE {
+        return Err(ProtocolError::MessageTooLarge {
+            size: len,
+            max: MAX_MESSAGE_SIZE,
+        });
+    }
+    buf.reserve(FRAME_HEADER_LEN + len);
+    buf.put_u32(len as u32);
+    bincode::serialize_into(buf.writer(), message)
+        .map_err(|e| ProtocolError::Malformed(e.to_string()))
+}
+
+/// Split one complete frame off the front of `buf`, returning its body.
+///
+/// Returns `Ok(None)` until the whole frame has arrived. The body shares
+/// `buf`'s allocation; once every body handed out is dropped, `buf` can
+/// reuse the space.
+pub fn split_frame(buf: &mut BytesMut) -> Result<Option<Bytes>, ProtocolError> {
+    if buf.len() < FRAME_HEADER_LEN {
+        return Ok(None);
+    }
+    let len = u32::from_be_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
+    if len > MAX_MESSAGE_SIZE {
+        return Err(ProtocolError::MessageTooLarge {
+            size: len,
+            max: MAX_MESSAGE_SIZE,
+        });
+    }
+    if buf.len() < FRAME_HEADER_LEN + len {
+        buf.reserve(FRAME_HEADER_LEN + len - buf.len());
+        return Ok(None);
+    }
+    buf.advance(FRAME_HEADER_LEN);
+    Ok(Some(buf.split_to(len).freeze()))
+}
+
+/// Read buffers shared between connections.
+pub struct BufferPool {
+    buffers: Mutex<Vec<BytesMut>>,
+    buffer_capacity: usize,
+    max_pooled: usize,
+}
+
+impl BufferPool {
+    pub fn new(buffer_capacity: usize, max_pooled: usize) -> Self {
+        Self {
+            buffers: Mutex::new(Vec::new()),
+            buffer_capacity,
+            max_pooled,
+        }
+    }
+
+    /// An empty buffer, reused if one is available.
+    pub fn take(&self) -> BytesMut {
+        self.buffers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .pop()
+            .unwrap_or_else(|| BytesMut::with_capacity(self.buffer_capacity))
+    }
+
+    /// Return a buffer for reuse. Buffers grown well past the usual size
+    /// (by one huge message) are freed rather than kept.
+    pub fn give(&self, mut buf: BytesMut) {
+        buf.clear();
+        if buf.capacity() > self.buffer_capacity * 4 {
+            return;
+        }
+        let mut buffers = self.buffers.lock().unwrap_or_else(|e| e.into_inner());
+        if buffers.len() < self.max_pooled {
+            buffers.push(buf);
+        }
+    }
+
+    /// Buffers currently waiting for reuse.
+    pub fn pooled(&self) -> usize {
+        self.buffers.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+}
+
+impl Default for BufferPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_READ_BUFFER_CAPACITY, DEFAULT_POOLED_BUFFERS)
+    }
+}
+
+/// Reads length-prefixed frames from a stream into a pooled buffer.
+/// The buffer goes back to the pool when the reader is dropped.
+pub struct FrameReader<R> {
+    reader: R,
+    buf: BytesMut,
+    pool: Arc<BufferPool>,
+}
+
+impl<R: AsyncRead + Unpin> FrameReader<R> {
+    pub fn new(reader: R, pool: Arc<BufferPool>) -> Self {
+        Self {
+            reader,
+            buf: pool.take(),
+            pool,
+        }
+    }
+
+    /// The next frame body. Cancel-safe: bytes read before a cancellation
+    /// stay buffered for the next call.
+    pub async fn next_frame(&mut self) -> std::io::Result<Bytes> {
+        loop {
+            match split_frame(&mut self.buf) {
+                Ok(Some(frame)) => return Ok(frame),
+                Ok(None) => {}
+                Err(e) => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        e.to_string(),
+                    ))
+                }
+            }
+            if self.buf.capacity() == self.buf.len() {
+                self.buf.reserve(DEFAULT_READ_BUFFER_CAPACITY);
+            }
+            if self.reader.read_buf(&mut self.buf).await? == 0 {
+                return Err(std::io::ErrorKind::UnexpectedEof.into());
+            }
+        }
+    }
+}
+
+impl<R> Drop for FrameReader<R> {
+    fn drop(&mut self) {
+        self.pool.give(std::mem::take(&mut self.buf));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,6 +332,53 @@ mod tests {
         assert_eq!(decoded, msg);
     }
 
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct TestMessageRef<'a> {
+        value: u32,
+        text: &'a str,
+    }
+
+    #[tokio::test]
+    async fn test_frame_reader_splits_frames_and_returns_buffer() {
+        let pool = Arc::new(BufferPool::new(64, 4));
+        let mut wire = BytesMut::new();
+        for value in 0..3 {
+            let msg = TestMessage {
+                value,
+                text: "pooled".repeat(value as usize * 10),
+            };
+            encode_into(&msg, &mut wire).unwrap();
+        }
+        let wire = wire.freeze();
+        {
+            let mut reader = FrameReader::new(&wire[..], Arc::clone(&pool));
+            for value in 0..3 {
+                let frame = reader.next_frame().await.unwrap();
+                let msg: TestMessageRef<'_> = decode_borrowed(&frame).unwrap();
+                assert_eq!(msg.value, value);
+                assert_eq!(msg.text.len(), value as usize * 60);
+            }
+            let eof = reader.next_frame().await.unwrap_err();
+            assert_eq!(eof.kind(), std::io::ErrorKind::UnexpectedEof);
+        }
+        assert_eq!(pool.pooled(), 1);
+    }
+
+    #[test]
+    fn test_split_frame_waits_for_whole_frame() {
+        let msg = TestMessage {
+            value: 7,
+            text: "partial".to_string(),
+        };
+        let encoded = encode(&msg).unwrap();
+        let mut buf = BytesMut::from(&encoded[..encoded.len() - 1]);
+        assert!(split_frame(&mut buf).unwrap().is_none());
+        buf.extend_from_slice(&encoded[encoded.len() - 1..]);
+        let frame = split_frame(&mut buf).unwrap().unwrap();
+        assert_eq!(decode::<TestMessage>(&frame).unwrap(), msg);
+        assert!(buf.is_empty());
+    }
+
     #[test]
     fn test_bincode_is_more_compact_than_json() {
         let msg = TestMessage {
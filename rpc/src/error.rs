@@ -1,5 +1,27 @@
 //! RPC error types.
+//!
+//! A failed request answers with an error object in `error`:
+//!
+//! ```json
+//! { "error": { "code": 2001, "message": "insufficient TRST: have 4, need 10",
+//!              "data": { "name": "insufficient_trst", "category": "balance",
+//!                        "required": "10", "available": "4" } } }
+//! ```
+//!
+//! `code` is a stable [`ErrorCode`]; `data` always names the code and its
+//! category, and adds what the client needs to act on it where there is
+//! something — the required and available balance, the missing account.
+//! Each handler documents the codes it may return besides
+//! `invalid_request`, which any handler returns for malformed parameters,
+//! and `storage`, for a store that cannot be read.
+//!
+//! The envelope is the node's own action-based one, not JSON-RPC 2.0:
+//! requests name an `action` rather than a `method`, responses carry no
+//! `jsonrpc` or `id` member, and codes are [`ErrorCode`]s rather than the
+//! JSON-RPC `-326xx` range.
 
+use burst_types::{ErrorCode, HasErrorCode};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -33,15 +55,164 @@ pub enum RpcError {
 
     #[error("rate limited")]
     RateLimited,
+
+    /// An error that carries its code, and optionally details for `data`.
+    #[error("{message}")]
+    Coded {
+        code: ErrorCode,
+        message: String,
+        data: Option<serde_json::Value>,
+    },
+}
+
+impl RpcError {
+    pub fn coded(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self::Coded {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    /// Attach details for the error object's `data`. Only coded errors
+    /// carry extra details; others are returned unchanged.
+    pub fn with_data(self, details: serde_json::Value) -> Self {
+        match self {
+            Self::Coded { code, message, .. } => Self::Coded {
+                code,
+                message,
+                data: Some(details),
+            },
+            other => other,
+        }
+    }
+
+    /// Not enough BRN for an operation.
+    pub fn insufficient_brn(required: u128, available: u128) -> Self {
+        Self::coded(
+            ErrorCode::InsufficientBrn,
+            format!("insufficient BRN: have {available}, need {required}"),
+        )
+        .with_data(balance_data(required, available))
+    }
+
+    /// Not enough TRST for an operation.
+    pub fn insufficient_trst(required: u128, available: u128) -> Self {
+        Self::coded(
+            ErrorCode::InsufficientTrst,
+            format!("insufficient TRST: have {available}, need {required}"),
+        )
+        .with_data(balance_data(required, available))
+    }
+
+    /// The feature behind an action is not enabled on this node.
+    pub fn unsupported(message: impl Into<String>) -> Self {
+        Self::coded(ErrorCode::Unsupported, message)
+    }
+
+    /// Details specific to this error, before the code's name and category
+    /// are added.
+    fn details(&self) -> Option<serde_json::Value> {
+        match self {
+            Self::AccountNotFound(account) => Some(serde_json::json!({ "account": account })),
+            Self::BlockNotFound(hash) => Some(serde_json::json!({ "hash": hash })),
+            Self::ProposalNotFound(proposal) => Some(serde_json::json!({ "proposal": proposal })),
+            Self::Coded { data, .. } => data.clone(),
+            _ => None,
+        }
+    }
+
+    /// The error object for this error.
+    pub fn to_object(&self) -> RpcErrorObject {
+        RpcErrorObject::new(self.error_code(), self.to_string(), self.details())
+    }
+}
+
+impl HasErrorCode for RpcError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::AccountNotFound(_) => ErrorCode::AccountNotFound,
+            Self::BlockNotFound(_) => ErrorCode::BlockNotFound,
+            Self::ProposalNotFound(_) => ErrorCode::ProposalNotFound,
+            Self::InvalidRequest(_) => ErrorCode::InvalidRequest,
+            Self::NodeNotConnected => ErrorCode::NodeUnavailable,
+            Self::Node(_) | Self::Server(_) => ErrorCode::Internal,
+            Self::Store(_) => ErrorCode::Storage,
+            Self::WorkError(_) => ErrorCode::WorkGeneration,
+            Self::RateLimited => ErrorCode::RateLimited,
+            Self::Coded { code, .. } => *code,
+        }
+    }
 }
 
 impl From<burst_store::StoreError> for RpcError {
     fn from(e: burst_store::StoreError) -> Self {
-        match e {
-            burst_store::StoreError::NotFound(ref key) => {
-                RpcError::Store(format!("not found: {key}"))
-            }
-            other => RpcError::Store(other.to_string()),
+        let message = match e {
+            burst_store::StoreError::NotFound(ref key) => format!("store error: not found: {key}"),
+            ref other => format!("store error: {other}"),
+        };
+        RpcError::coded(e.error_code(), message)
+    }
+}
+
+/// An error object: a stable code, a message and structured `data`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RpcErrorObject {
+    pub code: ErrorCode,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+impl RpcErrorObject {
+    /// An error object whose `data` names `code` and its category, plus
+    /// the fields of `details` when it is an object.
+    pub fn new(
+        code: ErrorCode,
+        message: impl Into<String>,
+        details: Option<serde_json::Value>,
+    ) -> Self {
+        let mut data = serde_json::json!({
+            "name": code.name(),
+            "category": code.category().as_str(),
+        });
+        if let (Some(serde_json::Value::Object(details)), Some(fields)) =
+            (details, data.as_object_mut())
+        {
+            fields.extend(details);
+        }
+        Self {
+            code,
+            message: message.into(),
+            data: Some(data),
         }
     }
 }
+
+fn balance_data(required: u128, available: u128) -> serde_json::Value {
+    serde_json::json!({
+        "required": required.to_string(),
+        "available": available.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_object_names_the_code_and_adds_details() {
+        let object = RpcError::insufficient_trst(10, 4).to_object();
+        let json = serde_json::to_value(&object).unwrap();
+        assert_eq!(json["code"], 2001);
+        assert_eq!(json["message"], "insufficient TRST: have 4, need 10");
+        assert_eq!(json["data"]["name"], "insufficient_trst");
+        assert_eq!(json["data"]["category"], "balance");
+        assert_eq!(json["data"]["required"], "10");
+        assert_eq!(json["data"]["available"], "4");
+
+        let object = RpcError::AccountNotFound("brst_a".into()).to_object();
+        assert_eq!(object.code, ErrorCode::AccountNotFound);
+        assert_eq!(object.data.unwrap()["account"], "brst_a");
+    }
+}
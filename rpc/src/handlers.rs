@@ -10,11 +10,13 @@ use crate::server::RpcState;
 
 use crate::server::ProcessResult;
 use burst_brn::BrnWalletState;
+use burst_crypto::DirectMessage;
 use burst_governance::Proposal;
 use burst_ledger::StateBlock;
 use burst_store::account::AccountInfo;
 use burst_store::StoreError;
-use burst_types::{BlockHash, Signature, Timestamp, TxHash, WalletAddress};
+use burst_transactions::governance::{mark_proposal_dependency, treasury_grant_link, BallotMode};
+use burst_types::{BlockHash, ErrorCode, Signature, Timestamp, TxHash, WalletAddress};
 
 use serde::{Deserialize, Serialize};
 use tracing::debug;
@@ -56,10 +58,54 @@ fn validate_hash(hash: &str) -> Result<(), RpcError> {
     Ok(())
 }
 
+/// The node's logical time: wall-clock time, moved forward by any dev-network
+/// clock warp so balances read the same as the node computes them.
+fn node_now(state: &RpcState) -> Timestamp {
+    state
+        .dev_admin
+        .as_ref()
+        .map_or_else(Timestamp::now, |dev| dev.now())
+}
+
+/// Hash of the protocol parameters in force, which new blocks must carry.
+fn current_params_hash(state: &RpcState) -> BlockHash {
+    state
+        .block_processor
+        .params_hash()
+        .unwrap_or_else(|| state.params.params_hash())
+}
+
+/// How blocks built for `account` are laid out: the newest version its
+/// epoch allows under the ledger epoch in force, bound to this node's
+/// network from version 3.
+fn block_layout(state: &RpcState, account: &AccountInfo) -> burst_ledger::BlockLayout {
+    burst_ledger::BlockLayout::for_account(
+        account.epoch,
+        current_params(state).ledger_epoch,
+        state.network,
+    )
+}
+
+/// The protocol parameters in force, for reporting them.
+fn current_params(state: &RpcState) -> std::borrow::Cow<'_, burst_types::ProtocolParams> {
+    state.block_processor.params().map_or(
+        std::borrow::Cow::Borrowed(&*state.params),
+        std::borrow::Cow::Owned,
+    )
+}
+
 fn to_value<T: Serialize>(v: &T) -> serde_json::Value {
     serde_json::to_value(v).expect("serialization should not fail")
 }
 
+/// Current UNIX time in milliseconds.
+fn unix_now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
 /// Parse a 64-char hex string into a 32-byte BlockHash.
 fn parse_block_hash(hex_str: &str) -> Result<BlockHash, RpcError> {
     let bytes =
@@ -88,6 +134,16 @@ fn parse_tx_hash(hex_str: &str) -> Result<TxHash, RpcError> {
     Ok(TxHash::new(arr))
 }
 
+/// Parse a 128-char hex string into a 64-byte Signature.
+fn parse_signature(hex_str: &str) -> Result<Signature, RpcError> {
+    let bytes = hex::decode(hex_str)
+        .map_err(|e| RpcError::InvalidRequest(format!("invalid signature hex: {e}")))?;
+    let arr: [u8; 64] = bytes
+        .try_into()
+        .map_err(|_| RpcError::InvalidRequest("signature must decode to 64 bytes".into()))?;
+    Ok(Signature(arr))
+}
+
 /// Parse an optional difficulty string (hex u64), defaulting to protocol params.
 fn parse_difficulty(raw: &Option<String>, default: u64) -> Result<u64, RpcError> {
     match raw {
@@ -97,6 +153,11 @@ fn parse_difficulty(raw: &Option<String>, default: u64) -> Result<u64, RpcError>
     }
 }
 
+/// Hex-encode an optional send memo for JSON responses.
+fn memo_hex(memo: &Option<Vec<u8>>) -> Option<String> {
+    memo.as_ref().map(hex::encode)
+}
+
 /// Build a `BrnWalletState` from an `AccountInfo`.
 /// Rate is now stored globally in `RateHistory`, not per-wallet.
 fn brn_state_from_account(account: &AccountInfo, _brn_rate: u128) -> BrnWalletState {
@@ -106,6 +167,18 @@ fn brn_state_from_account(account: &AccountInfo, _brn_rate: u128) -> BrnWalletSt
     state
 }
 
+/// TRST value of `account` under the network's decay mode, falling back to
+/// the face-value balance when the node can't value its tokens.
+async fn trst_value(state: &RpcState, account: &AccountInfo, now: Timestamp) -> u128 {
+    match &state.trst_value_view {
+        Some(view) => view
+            .trst_value(&account.address, now)
+            .await
+            .unwrap_or(account.trst_balance),
+        None => account.trst_balance,
+    }
+}
+
 /// Map a `StoreError::NotFound` to an `RpcError::AccountNotFound`.
 fn account_not_found(e: StoreError, address: &str) -> RpcError {
     match e {
@@ -168,6 +241,9 @@ pub struct AccountInfoResponse {
     pub head: String,
     pub brn_balance: String,
     pub trst_balance: String,
+    /// Transferable TRST valued under `trst_decay_mode`.
+    pub trst_value: String,
+    pub trst_decay_mode: String,
     pub trst_expired: String,
     pub trst_revoked: String,
     pub total_brn_burned: String,
@@ -177,8 +253,16 @@ pub struct AccountInfoResponse {
     pub block_count: u64,
     pub confirmation_height: u64,
     pub representative: String,
+    /// Network this node runs on; version 3 blocks for the account must be
+    /// bound to it.
+    pub network: String,
+    /// Newest block version the account may publish.
+    pub block_version: u8,
 }
 
+/// Account balances and chain state.
+///
+/// Errors: `account_not_found`.
 pub async fn handle_account_info(
     params: serde_json::Value,
     state: &RpcState,
@@ -193,7 +277,7 @@ pub async fn handle_account_info(
         .get_account(&address)
         .map_err(|e| account_not_found(e, &req.account))?;
 
-    let now = Timestamp::now();
+    let now = node_now(state);
     let brn_state = brn_state_from_account(&account, state.params.brn_rate);
     let brn_balance = {
         let brn = state.brn_engine.lock().await;
@@ -204,12 +288,15 @@ pub async fn handle_account_info(
 
     let trst_expired = account.expired_trst.to_string();
     let trst_revoked = account.revoked_trst.to_string();
+    let trst_value = trst_value(state, &account, now).await;
 
     Ok(to_value(&AccountInfoResponse {
         address: req.account,
         head: format!("{}", account.head),
         brn_balance: brn_balance.to_string(),
         trst_balance: account.trst_balance.to_string(),
+        trst_value: trst_value.to_string(),
+        trst_decay_mode: state.params.trst_decay_mode.as_str().to_string(),
         trst_expired,
         trst_revoked,
         total_brn_burned: account.total_brn_burned.to_string(),
@@ -219,6 +306,105 @@ pub async fn handle_account_info(
         block_count: account.block_count,
         confirmation_height: account.confirmation_height,
         representative: account.representative.to_string(),
+        network: state.network.as_str().to_string(),
+        block_version: block_layout(state, &account).version,
+    }))
+}
+
+// ── account_limits ──────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct AccountLimitsRequest {
+    pub account: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccountLimitsResponse {
+    pub account: String,
+    /// `probation`, `provisional` or `established`.
+    pub tier: &'static str,
+    /// When the wallet reaches its next tier, if it is still climbing.
+    pub next_tier_at: Option<u64>,
+    /// Whether its challenge record is clean (lost challenges within the
+    /// allowance). Wallets with a blemished record climb at half speed.
+    pub clean_history: bool,
+    pub may_endorse: bool,
+    /// Daily send cap of its tier ("0" = no cap).
+    pub daily_send_cap: String,
+    /// TRST sent within the last day.
+    pub sent_today: String,
+    /// What it may still send today; absent when uncapped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining_today: Option<String>,
+    /// Per-transaction cap while the wallet counts as new ("0" = no cap).
+    pub new_wallet_spending_limit: String,
+    /// Network-wide per-transaction TRST cap, while the network is
+    /// bootstrapping.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bootstrap_spending_limit: Option<String>,
+    /// Network-wide per-burn BRN cap, while the network is bootstrapping.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bootstrap_burn_limit: Option<String>,
+}
+
+/// Errors: `account_not_found`.
+pub async fn handle_account_limits(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let req: AccountLimitsRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    validate_account(&req.account)?;
+
+    let address = WalletAddress::new(req.account.clone());
+    let account = state
+        .account_store
+        .get_account(&address)
+        .map_err(|e| account_not_found(e, &req.account))?;
+    let history: burst_verification::ChallengeHistory = state
+        .verification_store
+        .get_challenge_history(&address)?
+        .and_then(|bytes| bincode::deserialize(&bytes).ok())
+        .unwrap_or_default();
+
+    let now = node_now(state);
+    let params = &state.params;
+    let (tier, next_tier_at) =
+        burst_verification::probation_tier(account.verified_at, &history, now, params);
+    let since = Timestamp::new(
+        now.as_secs()
+            .saturating_sub(burst_verification::SEND_CAP_WINDOW_SECS),
+    );
+    let sent_today = burst_ledger::trst_sent_since(state.block_store.as_ref(), &account, since)?;
+    let daily_send_cap = tier.daily_send_cap(params);
+
+    let is_new = account.verified_at.is_none_or(|verified_at| {
+        now.as_secs().saturating_sub(verified_at.as_secs())
+            < params.new_wallet_rate_limit_duration_secs
+    });
+    let bootstrapping =
+        state.account_store.verified_account_count()? < u64::from(params.bootstrap_exit_threshold);
+
+    Ok(to_value(&AccountLimitsResponse {
+        account: req.account,
+        tier: tier.as_str(),
+        next_tier_at: next_tier_at.map(|t| t.as_secs()),
+        clean_history: burst_verification::clean_history(&history, params),
+        may_endorse: tier.may_endorse(),
+        daily_send_cap: daily_send_cap.to_string(),
+        sent_today: sent_today.to_string(),
+        remaining_today: (daily_send_cap > 0)
+            .then(|| daily_send_cap.saturating_sub(sent_today).to_string()),
+        new_wallet_spending_limit: if is_new {
+            params.new_wallet_spending_limit
+        } else {
+            0
+        }
+        .to_string(),
+        bootstrap_spending_limit: (bootstrapping && params.bootstrap_spending_limit > 0)
+            .then(|| params.bootstrap_spending_limit.to_string()),
+        bootstrap_burn_limit: (bootstrapping && params.bootstrap_burn_limit > 0)
+            .then(|| params.bootstrap_burn_limit.to_string()),
     }))
 }
 
@@ -238,6 +424,12 @@ pub struct HistoryEntry {
     pub account: String,
     pub amount: String,
     pub timestamp: u64,
+    /// Hex-encoded send memo/reference, if the block carries one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    /// Destination tag of a tagged send, if the block carries one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination_tag: Option<u64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -248,6 +440,7 @@ pub struct AccountHistoryResponse {
     pub cursor: Option<String>,
 }
 
+/// Errors: `account_not_found`.
 pub async fn handle_account_history(
     params: serde_json::Value,
     state: &RpcState,
@@ -281,6 +474,8 @@ pub async fn handle_account_history(
                         account: block.account.to_string(),
                         amount: block.trst_balance.to_string(),
                         timestamp: block.timestamp.as_secs(),
+                        memo: memo_hex(&block.memo),
+                        destination_tag: block.destination_tag(),
                     });
                 }
             }
@@ -291,6 +486,8 @@ pub async fn handle_account_history(
                     account: req.account.clone(),
                     amount: "0".to_string(),
                     timestamp: 0,
+                    memo: None,
+                    destination_tag: None,
                 });
             }
         }
@@ -320,8 +517,12 @@ pub struct AccountBalanceRequest {
 pub struct AccountBalanceResponse {
     pub brn_balance: String,
     pub trst_balance: String,
+    /// Transferable TRST valued under `trst_decay_mode`.
+    pub trst_value: String,
+    pub trst_decay_mode: String,
 }
 
+/// Errors: `account_not_found`.
 pub async fn handle_account_balance(
     params: serde_json::Value,
     state: &RpcState,
@@ -336,16 +537,20 @@ pub async fn handle_account_balance(
         .get_account(&address)
         .map_err(|e| account_not_found(e, &req.account))?;
 
-    let now = Timestamp::now();
+    let now = node_now(state);
     let brn_state = brn_state_from_account(&account, state.params.brn_rate);
     let brn_balance = {
         let brn = state.brn_engine.lock().await;
         brn.compute_balance(&brn_state, now)
     };
 
+    let trst_value = trst_value(state, &account, now).await;
+
     Ok(to_value(&AccountBalanceResponse {
         brn_balance: brn_balance.to_string(),
         trst_balance: account.trst_balance.to_string(),
+        trst_value: trst_value.to_string(),
+        trst_decay_mode: state.params.trst_decay_mode.as_str().to_string(),
     }))
 }
 
@@ -356,6 +561,8 @@ pub struct AccountPendingRequest {
     pub account: String,
     /// Minimum amount threshold — only return pending entries with amount >= this value.
     pub threshold: Option<String>,
+    /// Only return pending entries carrying this destination tag.
+    pub destination_tag: Option<u64>,
     #[serde(flatten)]
     pub pagination: PaginationParams,
 }
@@ -366,6 +573,17 @@ pub struct PendingEntry {
     pub source: String,
     pub amount: String,
     pub timestamp: u64,
+    /// Hex-encoded memo/reference from the originating send, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    /// Destination tag from the originating send, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination_tag: Option<u64>,
+    /// The transfer came back to this account, rejected or unreceived.
+    pub returned: bool,
+    /// When the entry goes back to its sender if not received, if ever.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -376,6 +594,7 @@ pub struct AccountPendingResponse {
     pub cursor: Option<String>,
 }
 
+/// Errors: none of its own.
 pub async fn handle_account_pending(
     params: serde_json::Value,
     state: &RpcState,
@@ -403,20 +622,21 @@ pub async fn handle_account_pending(
     // Sort by amount descending (highest priority first)
     all_pending.sort_by_key(|(_, p)| std::cmp::Reverse(p.amount));
 
-    // Apply threshold filter
-    let filtered: Vec<_> = if threshold > 0 {
-        all_pending
-            .into_iter()
-            .filter(|(_, p)| p.amount >= threshold)
-            .collect()
-    } else {
-        all_pending
-    };
+    // Apply threshold and destination tag filters
+    let filtered: Vec<_> = all_pending
+        .into_iter()
+        .filter(|(_, p)| p.amount >= threshold)
+        .filter(|(_, p)| {
+            req.destination_tag
+                .is_none_or(|tag| p.destination_tag == Some(tag))
+        })
+        .collect();
 
     let start = (offset as usize).min(filtered.len());
     let end = (start + count as usize).min(filtered.len());
     let page = &filtered[start..end];
 
+    let pending_ttl_secs = current_params(state).pending_ttl_secs;
     let pending: Vec<PendingEntry> = page
         .iter()
         .map(|(source_hash, p)| PendingEntry {
@@ -424,6 +644,10 @@ pub async fn handle_account_pending(
             source: p.source.to_string(),
             amount: p.amount.to_string(),
             timestamp: p.timestamp.as_secs(),
+            memo: memo_hex(&p.memo),
+            destination_tag: p.destination_tag,
+            returned: p.returned,
+            expires_at: p.expires_at(pending_ttl_secs).map(|t| t.as_secs()),
         })
         .collect();
 
@@ -440,6 +664,209 @@ pub async fn handle_account_pending(
     }))
 }
 
+// ── pending_sweep ───────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct PendingSweepRequest {
+    pub account: String,
+    /// Maximum number of receive blocks to build. Default: 100, max: 1000.
+    pub count: Option<u64>,
+    /// Override the node's `receive_minimum` for this request (raw TRST).
+    pub receive_minimum: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SweepEntry {
+    pub hash: String,
+    pub previous: String,
+    /// Hash of the send being pocketed.
+    pub source: String,
+    pub amount: String,
+    /// The unsigned block; the wallet signs `hash`, adds work, and submits it
+    /// via `process`.
+    pub block: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PendingSweepResponse {
+    pub account: String,
+    pub blocks: Vec<SweepEntry>,
+    /// Pending entries skipped because they are below the receive minimum.
+    pub below_minimum: u64,
+    /// Eligible pending entries left over after `count` blocks were built.
+    pub remaining: u64,
+}
+
+/// Chain unsigned receive blocks for `pending` (already ordered) on top of
+/// `account`'s current head, each at its chain position and laid out as
+/// `layout` describes.
+fn build_sweep_blocks(
+    account: &AccountInfo,
+    brn_balance: u128,
+    origin: TxHash,
+    pending: &[(TxHash, burst_store::PendingInfo)],
+    now: Timestamp,
+    params_hash: BlockHash,
+    layout: burst_ledger::BlockLayout,
+) -> Vec<StateBlock> {
+    let mut previous = account.head;
+    let mut sequence = account.block_count;
+    let mut trst_balance = account.trst_balance;
+    let mut blocks = Vec::with_capacity(pending.len());
+
+    for (send_hash, p) in pending {
+        sequence += 1;
+        let block_type = if previous == BlockHash::ZERO {
+            burst_ledger::BlockType::Open
+        } else {
+            burst_ledger::BlockType::Receive
+        };
+        trst_balance = trst_balance.saturating_add(p.amount);
+        let transaction = TxHash::new(burst_crypto::blake2b_256(
+            &[
+                account.address.as_str().as_bytes(),
+                send_hash.as_bytes(),
+                &now.as_secs().to_be_bytes(),
+                b"receive",
+            ]
+            .concat(),
+        ));
+
+        let mut block = StateBlock {
+            version: burst_ledger::CURRENT_BLOCK_VERSION,
+            block_type,
+            account: account.address.clone(),
+            previous,
+            representative: account.representative.clone(),
+            brn_balance,
+            trst_balance,
+            link: BlockHash::new(*send_hash.as_bytes()),
+            origin,
+            transaction,
+            timestamp: now,
+            params_hash,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
+            work: 0,
+            signature: Signature([0u8; 64]),
+            hash: BlockHash::ZERO,
+        };
+        block.apply_layout(layout, sequence);
+        previous = block.hash;
+        blocks.push(block);
+    }
+
+    blocks
+}
+
+/// Errors: `block_not_found` when the account's head block is missing.
+pub async fn handle_pending_sweep(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let req: PendingSweepRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    validate_account(&req.account)?;
+
+    let count = req.count.unwrap_or(100).min(1000) as usize;
+    let minimum: u128 = match req.receive_minimum.as_deref() {
+        Some(s) => s
+            .parse()
+            .map_err(|e| RpcError::InvalidRequest(format!("invalid receive_minimum: {e}")))?,
+        None => state.receive_minimum,
+    };
+
+    let address = WalletAddress::new(req.account.clone());
+    let account = state
+        .account_store
+        .get_account(&address)
+        .unwrap_or_else(|_| AccountInfo {
+            address: address.clone(),
+            state: burst_types::WalletState::Unverified,
+            verified_at: None,
+            head: BlockHash::ZERO,
+            representative: address.clone(),
+            block_count: 0,
+            confirmation_height: 0,
+            total_brn_burned: 0,
+            trst_balance: 0,
+            total_brn_staked: 0,
+            expired_trst: 0,
+            revoked_trst: 0,
+            epoch: 0,
+        });
+
+    let origin = if account.head == BlockHash::ZERO {
+        TxHash::ZERO
+    } else {
+        let bytes = state
+            .block_store
+            .get_block(&account.head)
+            .map_err(|_| RpcError::BlockNotFound(format!("{}", account.head)))?;
+        deserialize_block(&bytes)?.origin
+    };
+
+    let now = node_now(state);
+    let brn_state = brn_state_from_account(&account, state.params.brn_rate);
+    let brn_balance = {
+        let brn = state.brn_engine.lock().await;
+        brn.compute_balance(&brn_state, now)
+    };
+
+    let all_pending = state
+        .pending_store
+        .get_pending_for_account_with_hashes(&address)
+        .map_err(|e| RpcError::Store(format!("failed to query pending: {e}")))?;
+
+    // HTLC locks need a preimage or refund, not a plain receive.
+    let (mut eligible, below): (Vec<_>, Vec<_>) = all_pending
+        .into_iter()
+        .filter(|(_, p)| p.htlc.is_none())
+        .partition(|(_, p)| p.amount >= minimum);
+
+    // Oldest first, so the chain order matches arrival order.
+    eligible.sort_by(|(ha, a), (hb, b)| {
+        a.timestamp
+            .cmp(&b.timestamp)
+            .then_with(|| ha.as_bytes().cmp(hb.as_bytes()))
+    });
+    let remaining = eligible.len().saturating_sub(count);
+    eligible.truncate(count);
+
+    let blocks = build_sweep_blocks(
+        &account,
+        brn_balance,
+        origin,
+        &eligible,
+        now,
+        current_params_hash(state),
+        block_layout(state, &account),
+    );
+
+    let entries = blocks
+        .iter()
+        .zip(&eligible)
+        .map(|(block, (send_hash, p))| SweepEntry {
+            hash: format!("{}", block.hash),
+            previous: format!("{}", block.previous),
+            source: format!("{}", send_hash),
+            amount: p.amount.to_string(),
+            block: to_value(block),
+        })
+        .collect();
+
+    Ok(to_value(&PendingSweepResponse {
+        account: req.account,
+        blocks: entries,
+        below_minimum: below.len() as u64,
+        remaining: remaining as u64,
+    }))
+}
+
 // ── account_representative ──────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
@@ -453,6 +880,7 @@ pub struct AccountRepresentativeResponse {
     pub representative: String,
 }
 
+/// Errors: `account_not_found`.
 pub async fn handle_account_representative(
     params: serde_json::Value,
     state: &RpcState,
@@ -473,6 +901,62 @@ pub async fn handle_account_representative(
     }))
 }
 
+// ── account_state_history ───────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct AccountStateHistoryRequest {
+    pub account: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StateTransitionEntry {
+    pub from: String,
+    pub to: String,
+    pub reason: String,
+    pub at: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccountStateHistoryResponse {
+    pub account: String,
+    pub state: String,
+    pub transitions: Vec<StateTransitionEntry>,
+}
+
+/// An account's current wallet state and every recorded transition that
+/// led to it, oldest first.
+///
+/// Errors: `account_not_found`.
+pub async fn handle_account_state_history(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let req: AccountStateHistoryRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    validate_account(&req.account)?;
+
+    let address = WalletAddress::new(req.account.clone());
+    let account = state
+        .account_store
+        .get_account(&address)
+        .map_err(|e| account_not_found(e, &req.account))?;
+    let transitions = state.account_store.get_state_transitions(&address)?;
+
+    Ok(to_value(&AccountStateHistoryResponse {
+        account: req.account,
+        state: format!("{:?}", account.state).to_lowercase(),
+        transitions: transitions
+            .into_iter()
+            .map(|t| StateTransitionEntry {
+                from: format!("{:?}", t.from).to_lowercase(),
+                to: format!("{:?}", t.to).to_lowercase(),
+                reason: t.reason.as_str().to_string(),
+                at: t.at.as_secs(),
+            })
+            .collect(),
+    }))
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 // Block / transaction handlers
 // ═══════════════════════════════════════════════════════════════════════
@@ -492,6 +976,10 @@ pub struct ProcessResponse {
     pub detail: Option<String>,
 }
 
+/// Validate and submit a signed block.
+///
+/// Errors: whatever the node rejects the block with — `invalid_block`,
+/// `invalid_signature`, `invalid_work`, `insufficient_brn`, `fork` and so on.
 pub async fn handle_process(
     params: serde_json::Value,
     state: &RpcState,
@@ -522,10 +1010,7 @@ pub async fn handle_process(
     let block_bytes = bincode::serialize(&block)
         .map_err(|e| RpcError::Server(format!("failed to serialize block: {e}")))?;
 
-    let result = state
-        .block_processor
-        .process_block(&block_bytes)
-        .map_err(RpcError::Server)?;
+    let result = state.block_processor.process_block(&block_bytes)?;
 
     let accepted = matches!(result, ProcessResult::Accepted | ProcessResult::Queued);
     let detail = match &result {
@@ -568,8 +1053,15 @@ pub struct BlockInfoResponse {
     pub transaction: String,
     pub timestamp: u64,
     pub confirmed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination_tag: Option<u64>,
 }
 
+/// A stored block and its confirmation state.
+///
+/// Errors: `block_not_found`.
 pub async fn handle_block_info(
     params: serde_json::Value,
     state: &RpcState,
@@ -603,6 +1095,8 @@ pub async fn handle_block_info(
         transaction: format!("{}", block.transaction),
         timestamp: block.timestamp.as_secs(),
         confirmed,
+        memo: memo_hex(&block.memo),
+        destination_tag: block.destination_tag(),
     }))
 }
 
@@ -629,6 +1123,8 @@ pub struct BlocksInfoEntry {
 
 const MAX_BATCH_SIZE: usize = 1000;
 
+/// Errors: none of its own; every hash is looked up, missing ones are listed
+/// in the response.
 pub async fn handle_blocks_info(
     params: serde_json::Value,
     state: &RpcState,
@@ -692,6 +1188,8 @@ pub async fn handle_blocks_info(
                             transaction: format!("{}", block.transaction),
                             timestamp: block.timestamp.as_secs(),
                             confirmed,
+                            memo: memo_hex(&block.memo),
+                            destination_tag: block.destination_tag(),
                         }),
                         error: None,
                     });
@@ -719,6 +1217,7 @@ pub async fn handle_blocks_info(
 
 // ── pending (alias for account_pending) ─────────────────────────────────
 
+/// Errors: as `account_pending`.
 pub async fn handle_pending(
     params: serde_json::Value,
     state: &RpcState,
@@ -744,6 +1243,7 @@ pub struct WorkGenerateResponse {
     pub hash: String,
 }
 
+/// Errors: `work_generation` when no nonce is found.
 pub async fn handle_work_generate(
     params: serde_json::Value,
     state: &RpcState,
@@ -753,7 +1253,14 @@ pub async fn handle_work_generate(
     validate_hash(&req.hash)?;
 
     let block_hash = parse_block_hash(&req.hash)?;
-    let difficulty = parse_difficulty(&req.difficulty, state.params.min_work_difficulty)?;
+    // Without an explicit difficulty, generate for the threshold peers
+    // currently accept: the base scaled by the network load multiplier.
+    let base_difficulty = state.params.min_work_difficulty;
+    let network_multiplier = network_multiplier(state).await;
+    let default_difficulty = burst_work::WorkThresholds::with_base(base_difficulty)
+        .scaled(network_multiplier as f64)
+        .threshold_for(burst_work::WorkBlockKind::Base);
+    let difficulty = parse_difficulty(&req.difficulty, default_difficulty)?;
     let generator = state.work_generator.clone();
 
     // PoW generation is CPU-intensive; run it on a blocking thread.
@@ -762,21 +1269,82 @@ pub async fn handle_work_generate(
         .map_err(|e| RpcError::Server(format!("work generation task failed: {e}")))?
         .map_err(|e| RpcError::WorkError(e.to_string()))?;
 
-    let base_difficulty = state.params.min_work_difficulty;
-    let multiplier = if base_difficulty > 0 {
-        difficulty as f64 / base_difficulty as f64
-    } else {
-        1.0
-    };
-
     Ok(to_value(&WorkGenerateResponse {
         work: format!("{:016x}", result.0),
         difficulty: format!("{:016x}", difficulty),
-        multiplier: format!("{:.6}", multiplier),
+        multiplier: format!("{:.6}", difficulty_multiplier(difficulty, base_difficulty)),
         hash: req.hash,
     }))
 }
 
+/// How many times harder than `base` a `difficulty` threshold is, measured
+/// on the gap to `u64::MAX` as [`burst_work::WorkThresholds`] scales it.
+fn difficulty_multiplier(difficulty: u64, base: u64) -> f64 {
+    if base == 0 || difficulty <= base {
+        return 1.0;
+    }
+    (u64::MAX - base) as f64 / (u64::MAX - difficulty).max(1) as f64
+}
+
+/// The network load multiplier in force (1 without an adjuster).
+async fn network_multiplier(state: &RpcState) -> u64 {
+    match &state.difficulty_adjuster {
+        Some(adjuster) => adjuster.lock().await.current_multiplier(),
+        None => 1,
+    }
+}
+
+// ── active_difficulty ──────────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct WorkKindThreshold {
+    pub kind: String,
+    pub multiplier: String,
+    pub threshold: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActiveDifficultyResponse {
+    /// Base threshold (`min_work_difficulty`).
+    pub network_minimum: String,
+    /// Base threshold scaled by the current network load multiplier.
+    pub network_current: String,
+    /// Network load multiplier (1.0 when throughput is under target).
+    pub multiplier: String,
+    /// Governed per-block-kind multipliers and the thresholds they give.
+    pub thresholds: Vec<WorkKindThreshold>,
+}
+
+/// Errors: none.
+pub async fn handle_active_difficulty(
+    _params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let thresholds = burst_work::WorkThresholds::from_params(&state.params);
+    let network_multiplier = network_multiplier(state).await;
+    let mut loaded = thresholds.clone();
+    loaded.base_multiplier *= network_multiplier as f64;
+
+    let kinds = burst_work::WorkBlockKind::ALL
+        .iter()
+        .map(|kind| WorkKindThreshold {
+            kind: kind.as_str().to_string(),
+            multiplier: format!("{:.6}", thresholds.multiplier(*kind)),
+            threshold: format!("{:016x}", thresholds.threshold_for(*kind)),
+        })
+        .collect();
+
+    Ok(to_value(&ActiveDifficultyResponse {
+        network_minimum: format!("{:016x}", thresholds.base),
+        network_current: format!(
+            "{:016x}",
+            loaded.threshold_for(burst_work::WorkBlockKind::Base)
+        ),
+        multiplier: format!("{:.6}", network_multiplier as f64),
+        thresholds: kinds,
+    }))
+}
+
 // ── params_hash ────────────────────────────────────────────────────────
 
 #[derive(Debug, Serialize)]
@@ -784,11 +1352,12 @@ pub struct ParamsHashResponse {
     pub params_hash: String,
 }
 
+/// Errors: none.
 pub async fn handle_params_hash(
     _params: serde_json::Value,
     state: &RpcState,
 ) -> Result<serde_json::Value, RpcError> {
-    let ph = state.params.params_hash();
+    let ph = current_params_hash(state);
     Ok(to_value(&ParamsHashResponse {
         params_hash: format!("{}", ph),
     }))
@@ -814,6 +1383,7 @@ pub struct ProposalSummary {
     pub description: String,
     pub votes_yea: u32,
     pub votes_nay: u32,
+    pub ballot: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -823,6 +1393,7 @@ pub struct GovernanceProposalsResponse {
     pub cursor: Option<String>,
 }
 
+/// Errors: none of its own.
 pub async fn handle_governance_proposals(
     params: serde_json::Value,
     state: &RpcState,
@@ -835,6 +1406,7 @@ pub async fn handle_governance_proposals(
 
     if let Some(ref engine) = state.governance_engine {
         let gov = engine.lock().await;
+        let ballots = lock_ballots(state).await;
         let all: Vec<_> = gov.all_proposals().collect();
         let start = (offset as usize).min(all.len());
         let end = (start + count as usize).min(all.len());
@@ -852,6 +1424,7 @@ pub async fn handle_governance_proposals(
                     description,
                     votes_yea,
                     votes_nay,
+                    ballot: ballot_label(ballots.as_deref(), &proposal.hash),
                 }
             })
             .collect();
@@ -874,6 +1447,7 @@ pub async fn handle_governance_proposals(
     let end = (start + count as usize).min(active_hashes.len());
     let page_hashes = &active_hashes[start..end];
 
+    let ballots = lock_ballots(state).await;
     let mut proposals = Vec::with_capacity(page_hashes.len());
     for ph in page_hashes {
         if let Ok(data) = state.governance_store.get_proposal(ph) {
@@ -888,6 +1462,7 @@ pub async fn handle_governance_proposals(
                     description,
                     votes_yea,
                     votes_nay,
+                    ballot: ballot_label(ballots.as_deref(), ph),
                 });
             }
         }
@@ -916,6 +1491,9 @@ fn proposal_description(proposal: &Proposal) -> String {
         } => {
             format!("EMERGENCY {:?}: {}", param, description)
         }
+        burst_governance::ProposalContent::TreasuryGrant { recipient, amount } => {
+            format!("Treasury grant of {} to {}", amount, recipient)
+        }
     }
 }
 
@@ -952,6 +1530,7 @@ pub struct GovernanceVoteResponse {
     pub accepted: bool,
 }
 
+/// Errors: `proposal_not_found`.
 pub async fn handle_governance_vote(
     params: serde_json::Value,
     state: &RpcState,
@@ -1044,8 +1623,20 @@ pub struct GovernanceProposalInfoResponse {
     pub votes_abstain: u32,
     pub created_at: u64,
     pub phase_deadline: u64,
+    pub ballot: String,
+    /// Sealed ballots committed and revealed in the current (or last) vote.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sealed_commitments: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sealed_revealed: Option<u32>,
+    /// Proposal that must activate before this one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requires: Option<String>,
+    /// Undecided proposals that change the same thing.
+    pub conflicts_with: Vec<String>,
 }
 
+/// Errors: `proposal_not_found`.
 pub async fn handle_governance_proposal_info(
     params: serde_json::Value,
     state: &RpcState,
@@ -1075,6 +1666,9 @@ pub async fn handle_governance_proposal_info(
         burst_governance::ProposalContent::Emergency {
             param, new_value, ..
         } => (format!("{:?}", param), new_value.to_string()),
+        burst_governance::ProposalContent::TreasuryGrant { amount, .. } => {
+            ("treasury_grant".to_string(), amount.to_string())
+        }
     };
 
     let (votes_yea, votes_nay, votes_abstain) = match proposal.phase {
@@ -1096,6 +1690,24 @@ pub async fn handle_governance_proposal_info(
     };
 
     let phase_deadline = compute_phase_deadline(&proposal, &state.params);
+    let (requires, conflicts_with) = match state.governance_engine {
+        Some(ref engine) => {
+            let gov = engine.lock().await;
+            (
+                gov.dependency_of(&proposal_hash),
+                gov.conflicting_proposals(&proposal_hash),
+            )
+        }
+        None => (None, Vec::new()),
+    };
+    let (ballot, sealed_counts) = match lock_ballots(state).await {
+        Some(ballots) => {
+            let mode = ballots.mode(&proposal_hash);
+            let counts = (mode == BallotMode::Sealed).then(|| ballots.sealed_counts(&proposal));
+            (mode, counts)
+        }
+        None => (BallotMode::Open, None),
+    };
 
     Ok(to_value(&GovernanceProposalInfoResponse {
         hash: req.hash,
@@ -1109,6 +1721,11 @@ pub async fn handle_governance_proposal_info(
         votes_abstain,
         created_at: proposal.created_at.as_secs(),
         phase_deadline,
+        ballot: format_ballot_mode(ballot).to_string(),
+        sealed_commitments: sealed_counts.map(|(committed, _)| committed),
+        sealed_revealed: sealed_counts.map(|(_, revealed)| revealed),
+        requires: requires.map(|hash| hash.to_string()),
+        conflicts_with: conflicts_with.iter().map(|hash| hash.to_string()).collect(),
     }))
 }
 
@@ -1134,27 +1751,416 @@ fn compute_phase_deadline(proposal: &Proposal, params: &burst_types::ProtocolPar
     }
 }
 
-// ═══════════════════════════════════════════════════════════════════════
-// Telemetry
-// ═══════════════════════════════════════════════════════════════════════
+// ── governance_params ───────────────────────────────────────────────────
 
-#[derive(Debug, Deserialize)]
-pub struct TelemetryRequest {}
+#[derive(Debug, Serialize)]
+pub struct GovernanceParamsResponse {
+    pub params: Vec<GovernanceParamEntry>,
+}
 
 #[derive(Debug, Serialize)]
-pub struct TelemetryResponse {
-    pub block_count: u64,
-    pub account_count: u64,
-    pub peer_count: u32,
-    pub protocol_version: u16,
-    pub uptime_secs: u64,
+pub struct GovernanceParamEntry {
+    pub name: &'static str,
+    #[serde(rename = "type")]
+    pub ty: burst_governance::ParamType,
+    pub unit: &'static str,
+    pub min: String,
+    pub max: String,
+    pub threshold: burst_governance::ParamThreshold,
+    pub supermajority_bps: u32,
+    pub current: String,
+    /// Values proposed by proposals that have not yet activated.
+    pub pending: Vec<PendingParamChange>,
 }
 
-pub async fn handle_telemetry(
-    _params: serde_json::Value,
-    state: &RpcState,
-) -> Result<serde_json::Value, RpcError> {
-    let now = std::time::SystemTime::now()
+#[derive(Debug, Serialize)]
+pub struct PendingParamChange {
+    pub proposal: String,
+    pub phase: String,
+    pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activation_at: Option<u64>,
+}
+
+/// Errors: none.
+pub async fn handle_governance_params(
+    _params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    use burst_governance::{GovernableParam, GovernancePhase, ProposalContent};
+
+    let mut pending: Vec<(GovernableParam, PendingParamChange)> = Vec::new();
+    if let Some(ref engine) = state.governance_engine {
+        let gov = engine.lock().await;
+        for proposal in gov.all_proposals() {
+            if matches!(
+                proposal.phase,
+                GovernancePhase::Rejected | GovernancePhase::Activated | GovernancePhase::Withdrawn
+            ) {
+                continue;
+            }
+            if let ProposalContent::ParameterChange { param, new_value }
+            | ProposalContent::Emergency {
+                param, new_value, ..
+            } = &proposal.content
+            {
+                pending.push((
+                    param.clone(),
+                    PendingParamChange {
+                        proposal: proposal.hash.to_string(),
+                        phase: format!("{:?}", proposal.phase),
+                        value: new_value.to_string(),
+                        activation_at: proposal.activation_at.map(|t| t.as_secs()),
+                    },
+                ));
+            }
+        }
+    }
+
+    let current = current_params(state);
+    let params = GovernableParam::ALL
+        .into_iter()
+        .map(|param| {
+            let spec = param.spec();
+            let (changes, rest) = std::mem::take(&mut pending)
+                .into_iter()
+                .partition(|(p, _)| *p == param);
+            pending = rest;
+            GovernanceParamEntry {
+                name: param.name(),
+                ty: spec.ty,
+                unit: spec.ty.unit(),
+                min: spec.min.to_string(),
+                max: spec.max.to_string(),
+                threshold: spec.threshold,
+                supermajority_bps: spec.threshold.supermajority_bps(&current),
+                current: param.current_value(&current).to_string(),
+                pending: changes.into_iter().map(|(_, change)| change).collect(),
+            }
+        })
+        .collect();
+
+    Ok(to_value(&GovernanceParamsResponse { params }))
+}
+
+// ── delegate_profile_publish ────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct DelegateProfilePublishRequest {
+    pub delegate: String,
+    pub name: String,
+    #[serde(default)]
+    pub statement: String,
+    /// `global`, `category:<name>` or `proposal:<hash>`.
+    pub scopes: Vec<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    pub published_at: u64,
+    /// Hex signature over the profile anchor by the delegate's key.
+    pub signature: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DelegateProfilePublishResponse {
+    pub delegate: String,
+    pub anchor: String,
+    pub anchor_block: String,
+}
+
+/// Errors: `invalid_request` also for a bad profile signature, or a profile
+/// no DelegateProfile block on the delegate's chain anchors.
+pub async fn handle_delegate_profile_publish(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let req: DelegateProfilePublishRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    validate_account(&req.delegate)?;
+
+    let profile = burst_governance::DelegateProfile {
+        delegate: WalletAddress::new(req.delegate.clone()),
+        name: req.name,
+        statement: req.statement,
+        scopes: req
+            .scopes
+            .iter()
+            .map(|scope| parse_delegation_scope(scope))
+            .collect::<Result<_, _>>()?,
+        url: req.url,
+        published_at: Timestamp::new(req.published_at),
+    };
+    let signed = burst_governance::SignedDelegateProfile {
+        profile,
+        signature: parse_signature(&req.signature)?,
+    };
+    signed
+        .verify()
+        .map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+
+    let anchor = signed.profile.anchor();
+    let activity = delegate_activity(state, &signed.profile.delegate)?;
+    let anchor_block = match activity.anchor {
+        Some(block) if signed.profile.matches_anchor(&block.link) => block.hash,
+        Some(block) => {
+            return Err(RpcError::InvalidRequest(format!(
+                "profile anchor {} does not match the delegate's latest anchor {} in block {}",
+                hex::encode(anchor),
+                block.link,
+                block.hash
+            )))
+        }
+        None => {
+            return Err(RpcError::InvalidRequest(format!(
+                "no DelegateProfile block anchors {} on the delegate's chain",
+                hex::encode(anchor)
+            )))
+        }
+    };
+
+    let bytes = bincode::serialize(&signed)
+        .map_err(|e| RpcError::Server(format!("failed to serialize profile: {e}")))?;
+    state
+        .governance_store
+        .put_delegate_profile(&signed.profile.delegate, &bytes)
+        .map_err(|e| RpcError::Store(format!("failed to store profile: {e}")))?;
+
+    Ok(to_value(&DelegateProfilePublishResponse {
+        delegate: req.delegate,
+        anchor: hex::encode(anchor),
+        anchor_block: anchor_block.to_string(),
+    }))
+}
+
+// ── delegates ───────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct DelegatesRequest {
+    #[serde(flatten)]
+    pub pagination: PaginationParams,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DelegatesResponse {
+    pub delegates: Vec<DelegateEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DelegateEntry {
+    pub delegate: String,
+    pub name: String,
+    pub statement: String,
+    pub scopes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    pub published_at: u64,
+    pub anchor_block: String,
+    /// Confirmed governance votes, oldest first.
+    pub votes: Vec<DelegateVote>,
+    /// Share of the proposals put to a vote that the delegate voted on.
+    /// Absent when the node does not run the governance engine.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub participation_bps: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DelegateVote {
+    pub proposal: String,
+    pub vote: &'static str,
+    pub block: String,
+    pub timestamp: u64,
+}
+
+/// Errors: none of its own.
+pub async fn handle_delegates(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let req: DelegatesRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let count = req.pagination.effective_count() as usize;
+    let offset = req.pagination.decode_offset() as usize;
+
+    let stored = state
+        .governance_store
+        .list_delegate_profiles()
+        .map_err(|e| RpcError::Store(e.to_string()))?;
+    let start = offset.min(stored.len());
+    let end = (start + count).min(stored.len());
+
+    // Proposals that opened for voting, for the participation rate.
+    let put_to_vote: Option<std::collections::HashSet<TxHash>> = match state.governance_engine {
+        Some(ref engine) => Some(
+            engine
+                .lock()
+                .await
+                .all_proposals()
+                .filter(|p| p.exploration_started_at.is_some() || p.promotion_started_at.is_some())
+                .map(|p| p.hash)
+                .collect(),
+        ),
+        None => None,
+    };
+
+    let mut delegates = Vec::with_capacity(end - start);
+    for bytes in &stored[start..end] {
+        let signed: burst_governance::SignedDelegateProfile = bincode::deserialize(bytes)
+            .map_err(|e| RpcError::Node(format!("failed to deserialize profile: {e}")))?;
+        let profile = signed.profile;
+        let activity = delegate_activity(state, &profile.delegate)?;
+        // A newer anchor retires the stored profile until its replacement
+        // is published.
+        let Some(anchor) = activity
+            .anchor
+            .filter(|block| profile.matches_anchor(&block.link))
+        else {
+            continue;
+        };
+        let participation_bps = put_to_vote.as_ref().map(|opened| {
+            let voted: std::collections::HashSet<_> = activity
+                .votes
+                .iter()
+                .map(|(proposal, _)| *proposal)
+                .collect();
+            let eligible = opened.union(&voted).count() as u64;
+            (voted.len() as u64 * 10_000)
+                .checked_div(eligible)
+                .unwrap_or(0) as u32
+        });
+        delegates.push(DelegateEntry {
+            delegate: profile.delegate.to_string(),
+            name: profile.name,
+            statement: profile.statement,
+            scopes: profile.scopes.iter().map(format_delegation_scope).collect(),
+            url: profile.url,
+            published_at: profile.published_at.as_secs(),
+            anchor_block: anchor.hash.to_string(),
+            votes: activity.votes.into_iter().map(|(_, vote)| vote).collect(),
+            participation_bps,
+        });
+    }
+
+    let cursor = (end < stored.len()).then(|| pagination::encode_cursor(end as u64));
+    Ok(to_value(&DelegatesResponse { delegates, cursor }))
+}
+
+/// What a delegate's chain says about them as a delegate.
+struct DelegateActivity {
+    /// Latest `DelegateProfile` block, confirmed or not.
+    anchor: Option<StateBlock>,
+    /// Confirmed governance votes with the proposal voted on.
+    votes: Vec<(TxHash, DelegateVote)>,
+}
+
+fn delegate_activity(
+    state: &RpcState,
+    delegate: &WalletAddress,
+) -> Result<DelegateActivity, RpcError> {
+    let confirmation_height = match state.account_store.get_account(delegate) {
+        Ok(info) => info.confirmation_height,
+        Err(StoreError::NotFound(_)) => 0,
+        Err(e) => return Err(RpcError::Store(e.to_string())),
+    };
+    let chain = state
+        .block_store
+        .get_account_blocks(delegate)
+        .map_err(|e| RpcError::Store(e.to_string()))?
+        .iter()
+        .map(|hash| {
+            let bytes = state
+                .block_store
+                .get_block(hash)
+                .map_err(|e| RpcError::Store(e.to_string()))?;
+            deserialize_block(&bytes)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(scan_delegate_chain(chain, confirmation_height))
+}
+
+/// Pick the latest profile anchor and the confirmed votes out of an
+/// account chain in height order.
+fn scan_delegate_chain(chain: Vec<StateBlock>, confirmation_height: u64) -> DelegateActivity {
+    let mut activity = DelegateActivity {
+        anchor: None,
+        votes: Vec::new(),
+    };
+    for (height, block) in (1u64..).zip(chain) {
+        match block.block_type {
+            burst_ledger::BlockType::DelegateProfile => activity.anchor = Some(block),
+            burst_ledger::BlockType::GovernanceVote if height <= confirmation_height => {
+                // Same encoding the ledger applies: the link names the
+                // proposal and the first transaction byte the vote.
+                let vote = match block.transaction.as_bytes()[0] {
+                    0 => "yea",
+                    1 => "nay",
+                    2 => "abstain",
+                    _ => continue,
+                };
+                let proposal = block.link.into_tx_hash();
+                activity.votes.push((
+                    proposal,
+                    DelegateVote {
+                        proposal: proposal.to_string(),
+                        vote,
+                        block: block.hash.to_string(),
+                        timestamp: block.timestamp.as_secs(),
+                    },
+                ));
+            }
+            _ => {}
+        }
+    }
+    activity
+}
+
+fn parse_delegation_scope(scope: &str) -> Result<burst_governance::DelegationScope, RpcError> {
+    use burst_governance::DelegationScope;
+    match scope.split_once(':') {
+        None if scope == "global" => Ok(DelegationScope::Global),
+        Some(("category", name)) if !name.is_empty() => {
+            Ok(DelegationScope::Category(name.to_string()))
+        }
+        Some(("proposal", hash)) => Ok(DelegationScope::Proposal(parse_tx_hash(hash)?)),
+        _ => Err(RpcError::InvalidRequest(format!(
+            "invalid scope {scope:?}: expected global, category:<name> or proposal:<hash>"
+        ))),
+    }
+}
+
+fn format_delegation_scope(scope: &burst_governance::DelegationScope) -> String {
+    use burst_governance::DelegationScope;
+    match scope {
+        DelegationScope::Global => "global".to_string(),
+        DelegationScope::Category(name) => format!("category:{name}"),
+        DelegationScope::Proposal(hash) => format!("proposal:{hash}"),
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Telemetry
+// ═══════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Deserialize)]
+pub struct TelemetryRequest {}
+
+#[derive(Debug, Serialize)]
+pub struct TelemetryResponse {
+    pub block_count: u64,
+    pub account_count: u64,
+    pub peer_count: u32,
+    pub protocol_version: u16,
+    pub uptime_secs: u64,
+    /// Network load multiplier applied to the work threshold.
+    pub network_multiplier: String,
+}
+
+/// Errors: none.
+pub async fn handle_telemetry(
+    _params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
         .as_secs();
@@ -1175,9 +2181,11 @@ pub async fn handle_telemetry(
         peer_count,
         protocol_version: 1,
         uptime_secs: now.saturating_sub(state.started_at),
+        network_multiplier: format!("{:.6}", network_multiplier(state).await as f64),
     }))
 }
 
+/// Errors: none.
 pub async fn handle_peers(
     _params: serde_json::Value,
     state: &RpcState,
@@ -1205,6 +2213,7 @@ pub struct VerificationStatusResponse {
     pub challenge_active: bool,
 }
 
+/// Errors: none of its own.
 pub async fn handle_verification_status(
     params: serde_json::Value,
     state: &RpcState,
@@ -1249,6 +2258,385 @@ pub async fn handle_verification_status(
     }))
 }
 
+// ── verification_selection_proof ────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct VerificationSelectionProofRequest {
+    pub wallet: String,
+}
+
+/// One recorded verifier draw, with the result of recomputing it.
+#[derive(Debug, Serialize)]
+pub struct SelectionProofEntry {
+    pub drand_round: u64,
+    pub randomness: String,
+    pub pool_hash: String,
+    pub candidates: Vec<String>,
+    pub selected: Vec<String>,
+    pub selected_at: u64,
+    /// `pool_hash` commits to `candidates`.
+    pub pool_hash_valid: bool,
+    /// Drawing from `candidates` with `randomness` yields `selected`.
+    pub selection_valid: bool,
+}
+
+impl SelectionProofEntry {
+    pub fn from_selection(selection: &burst_store::VerifierSelection) -> Self {
+        let redrawn = burst_verification::draw_verifiers(
+            &selection.randomness,
+            &selection.candidates,
+            selection.selected.len(),
+        );
+        Self {
+            drand_round: selection.drand_round,
+            randomness: hex::encode(selection.randomness),
+            pool_hash: hex::encode(selection.pool_hash),
+            candidates: selection
+                .candidates
+                .iter()
+                .map(|c| c.as_str().to_string())
+                .collect(),
+            selected: selection
+                .selected
+                .iter()
+                .map(|s| s.as_str().to_string())
+                .collect(),
+            selected_at: selection.selected_at.as_secs(),
+            pool_hash_valid: burst_verification::pool_snapshot_hash(&selection.candidates)
+                == selection.pool_hash,
+            selection_valid: redrawn == selection.selected,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct VerificationSelectionProofResponse {
+    pub wallet: String,
+    pub selections: Vec<SelectionProofEntry>,
+}
+
+/// Every recorded verifier draw for a wallet, oldest first, each checked by
+/// recomputing it from its recorded inputs.
+///
+/// Errors: none of its own.
+pub async fn handle_verification_selection_proof(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let req: VerificationSelectionProofRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    validate_account(&req.wallet)?;
+
+    let selections = state
+        .verification_store
+        .get_verifier_selections(&WalletAddress::new(req.wallet.clone()))?;
+
+    Ok(to_value(&VerificationSelectionProofResponse {
+        wallet: req.wallet,
+        selections: selections
+            .iter()
+            .map(SelectionProofEntry::from_selection)
+            .collect(),
+    }))
+}
+
+// ── endorsement_risk / collusion_flags ──────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct EndorsementRiskRequest {
+    pub wallet: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EndorsementRiskResponse {
+    pub wallet: String,
+    pub score_bps: u32,
+    pub flagged: bool,
+    pub flags: Vec<burst_verification::CollusionFlag>,
+    pub endorsers: Vec<String>,
+    pub endorsed: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CollusionFlagsResponse {
+    pub threshold_bps: u32,
+    pub wallets: Vec<burst_verification::RiskAssessment>,
+}
+
+fn verification_orchestrator(
+    state: &RpcState,
+) -> Result<&tokio::sync::Mutex<burst_verification::VerificationOrchestrator>, RpcError> {
+    state
+        .verification_orchestrator
+        .as_deref()
+        .ok_or_else(|| RpcError::unsupported("endorsement graph is not available on this node"))
+}
+
+/// Collusion risk score for a wallet, with the patterns behind it and its
+/// direct neighbours in the endorsement graph.
+///
+/// Errors: `unsupported` without an endorsement graph.
+pub async fn handle_endorsement_risk(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let orchestrator = verification_orchestrator(state)?;
+    let req: EndorsementRiskRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    validate_account(&req.wallet)?;
+
+    let wallet = WalletAddress::new(req.wallet.clone());
+    let orch = orchestrator.lock().await;
+    let graph = &orch.endorsement_graph;
+    let assessment = graph.assess(&wallet);
+
+    Ok(to_value(&EndorsementRiskResponse {
+        wallet: req.wallet,
+        score_bps: assessment.score_bps,
+        flagged: assessment.score_bps >= graph.config().flag_threshold_bps,
+        flags: assessment.flags,
+        endorsers: graph
+            .endorsers_of(&wallet)
+            .iter()
+            .map(|e| e.as_str().to_string())
+            .collect(),
+        endorsed: graph
+            .endorsed_by(&wallet)
+            .iter()
+            .map(|e| e.as_str().to_string())
+            .collect(),
+    }))
+}
+
+/// Every wallet the endorsement graph currently flags, highest risk first —
+/// the starting list for challengers.
+///
+/// Errors: `unsupported` without an endorsement graph.
+pub async fn handle_collusion_flags(
+    _params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let orch = verification_orchestrator(state)?.lock().await;
+    Ok(to_value(&CollusionFlagsResponse {
+        threshold_bps: orch.endorsement_graph.config().flag_threshold_bps,
+        wallets: orch.endorsement_graph.flagged(),
+    }))
+}
+
+// ── uhv_stats ───────────────────────────────────────────────────────────
+
+/// Default width of a cohort or reporting period: 30 days.
+const UHV_STATS_DEFAULT_PERIOD_SECS: u64 = 30 * 24 * 3600;
+/// Cohorts and periods with fewer records than this report no counts, so
+/// a single wallet's outcome can't be read off a sparse bucket.
+const UHV_STATS_MIN_BUCKET: u64 = 5;
+/// Endorsement counts at or above this share the last histogram bucket.
+const UHV_STATS_MAX_DEGREE: u32 = 10;
+
+#[derive(Debug, Deserialize)]
+pub struct UhvStatsRequest {
+    /// Width of the cohorts and periods, in seconds.
+    #[serde(default)]
+    pub period_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DegreeBucket {
+    /// Endorsement count, `"10+"` for the open-ended last bucket.
+    pub endorsements: String,
+    pub wallets: u64,
+}
+
+/// Verification outcomes of the wallets whose verification started in one
+/// period. Counts are zero when `suppressed`.
+#[derive(Debug, Default, Serialize)]
+pub struct CohortStats {
+    pub cohort_start: u64,
+    pub suppressed: bool,
+    pub verified: u64,
+    pub failed: u64,
+    pub expired: u64,
+    /// Verified share of decided verifications.
+    pub pass_rate_bps: u32,
+}
+
+/// Challenges that concluded in one period. Counts are zero when
+/// `suppressed`.
+#[derive(Debug, Default, Serialize)]
+pub struct ChallengePeriodStats {
+    pub period_start: u64,
+    pub suppressed: bool,
+    pub fraud_confirmed: u64,
+    pub rejected: u64,
+    pub expired: u64,
+}
+
+/// How often verifier votes matched the decided outcome.
+#[derive(Debug, Default, Serialize)]
+pub struct VerifierAgreementStats {
+    pub votes_cast: u64,
+    pub votes_agreeing: u64,
+    pub neither_votes: u64,
+    pub agreement_bps: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UhvStatsResponse {
+    pub verifier_pool_size: u64,
+    pub period_secs: u64,
+    pub verifications: u64,
+    pub challenges: u64,
+    /// Endorsements collected per verified-or-failed-or-expired wallet.
+    pub endorsement_degree: Vec<DegreeBucket>,
+    pub cohorts: Vec<CohortStats>,
+    pub challenge_outcomes: Vec<ChallengePeriodStats>,
+    pub verifier_agreement: VerifierAgreementStats,
+}
+
+fn ratio_bps(part: u64, whole: u64) -> u32 {
+    part.saturating_mul(10_000).checked_div(whole).unwrap_or(0) as u32
+}
+
+impl UhvStatsResponse {
+    /// Aggregate outcome records into anonymized statistics.
+    pub fn from_records(
+        records: &[burst_store::VerificationRecord],
+        verifier_pool_size: u64,
+        period_secs: u64,
+    ) -> Self {
+        use burst_store::VerificationRecordOutcome as Outcome;
+        use std::collections::BTreeMap;
+
+        let bucket = |ts: Timestamp| ts.as_secs() / period_secs * period_secs;
+        let mut degrees = vec![0u64; UHV_STATS_MAX_DEGREE as usize + 1];
+        let mut cohorts: BTreeMap<u64, CohortStats> = BTreeMap::new();
+        let mut periods: BTreeMap<u64, ChallengePeriodStats> = BTreeMap::new();
+        let mut agreement = VerifierAgreementStats::default();
+        let (mut verifications, mut challenges) = (0, 0);
+
+        for record in records {
+            if record.outcome.found_legitimate().is_some() {
+                agreement.votes_cast += u64::from(record.votes_cast);
+                agreement.votes_agreeing += u64::from(record.votes_agreeing);
+                agreement.neither_votes += u64::from(record.neither_votes);
+            }
+
+            if record.outcome.is_challenge() {
+                challenges += 1;
+                let start = bucket(record.completed_at);
+                let period = periods
+                    .entry(start)
+                    .or_insert_with(|| ChallengePeriodStats {
+                        period_start: start,
+                        ..Default::default()
+                    });
+                match record.outcome {
+                    Outcome::FraudConfirmed => period.fraud_confirmed += 1,
+                    Outcome::ChallengeRejected => period.rejected += 1,
+                    _ => period.expired += 1,
+                }
+                continue;
+            }
+
+            verifications += 1;
+            degrees[record.endorsement_count.min(UHV_STATS_MAX_DEGREE) as usize] += 1;
+            let start = bucket(record.started_at);
+            let cohort = cohorts.entry(start).or_insert_with(|| CohortStats {
+                cohort_start: start,
+                ..Default::default()
+            });
+            match record.outcome {
+                Outcome::Verified => cohort.verified += 1,
+                Outcome::Failed => cohort.failed += 1,
+                _ => cohort.expired += 1,
+            }
+        }
+
+        let cohorts = cohorts
+            .into_values()
+            .map(|c| {
+                if c.verified + c.failed + c.expired < UHV_STATS_MIN_BUCKET {
+                    CohortStats {
+                        cohort_start: c.cohort_start,
+                        suppressed: true,
+                        ..Default::default()
+                    }
+                } else {
+                    CohortStats {
+                        pass_rate_bps: ratio_bps(c.verified, c.verified + c.failed),
+                        ..c
+                    }
+                }
+            })
+            .collect();
+        let challenge_outcomes = periods
+            .into_values()
+            .map(|p| {
+                if p.fraud_confirmed + p.rejected + p.expired < UHV_STATS_MIN_BUCKET {
+                    ChallengePeriodStats {
+                        period_start: p.period_start,
+                        suppressed: true,
+                        ..Default::default()
+                    }
+                } else {
+                    p
+                }
+            })
+            .collect();
+        agreement.agreement_bps = ratio_bps(agreement.votes_agreeing, agreement.votes_cast);
+
+        Self {
+            verifier_pool_size,
+            period_secs,
+            verifications,
+            challenges,
+            endorsement_degree: degrees
+                .into_iter()
+                .enumerate()
+                .map(|(degree, wallets)| DegreeBucket {
+                    endorsements: if degree == UHV_STATS_MAX_DEGREE as usize {
+                        format!("{degree}+")
+                    } else {
+                        degree.to_string()
+                    },
+                    wallets,
+                })
+                .collect(),
+            cohorts,
+            challenge_outcomes,
+            verifier_agreement: agreement,
+        }
+    }
+}
+
+/// Anonymized aggregate statistics on unique-humanity verification —
+/// endorsement degrees, pass rates by cohort, challenge outcomes over time
+/// and verifier agreement — for evaluating UHV health without raw data.
+///
+/// Errors: none of its own.
+pub async fn handle_uhv_stats(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let req: UhvStatsRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let period_secs = req.period_secs.unwrap_or(UHV_STATS_DEFAULT_PERIOD_SECS);
+    if period_secs == 0 {
+        return Err(RpcError::InvalidRequest(
+            "period_secs must be positive".into(),
+        ));
+    }
+
+    let records = state.verification_store.iter_verification_records()?;
+    let pool_size = state.verification_store.iter_verifiers()?.len() as u64;
+
+    Ok(to_value(&UhvStatsResponse::from_records(
+        &records,
+        pool_size,
+        period_secs,
+    )))
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 // Representatives
 // ═══════════════════════════════════════════════════════════════════════
@@ -1275,6 +2663,7 @@ pub struct RepresentativesResponse {
     pub cursor: Option<String>,
 }
 
+/// Errors: none of its own.
 pub async fn handle_representatives(
     params: serde_json::Value,
     state: &RpcState,
@@ -1322,13 +2711,47 @@ pub async fn handle_representatives(
 
 // ── representatives_online ──────────────────────────────────────────────
 
+/// A representative counts as online if it voted within this window.
+const REP_ONLINE_WINDOW_MS: u64 = 5 * 60 * 1000;
+
 #[derive(Debug, Deserialize)]
 pub struct RepresentativesOnlineRequest {}
 
-#[derive(Debug, Serialize)]
-pub struct RepresentativeOnlineEntry {
-    pub account: String,
+/// Responsiveness figures from the node's representative crawler. Fields are
+/// omitted until the crawler has observed the representative.
+#[derive(Debug, Default, Serialize)]
+pub struct RepresentativeStats {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_vote_age_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_p50_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_p90_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_p99_ms: Option<u64>,
+    /// Share of recent crawl queries answered, `0.0..=1.0`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uptime_score: Option<f64>,
+}
+
+impl RepresentativeStats {
+    fn from_performance(perf: &burst_consensus::RepPerformance, now_ms: u64) -> Self {
+        Self {
+            last_vote_age_secs: perf.last_vote.map(|at| now_ms.saturating_sub(at) / 1000),
+            latency_p50_ms: perf.latency_percentile(50),
+            latency_p90_ms: perf.latency_percentile(90),
+            latency_p99_ms: perf.latency_percentile(99),
+            uptime_score: perf.uptime_score(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RepresentativeOnlineEntry {
+    pub account: String,
     pub weight: String,
+    #[serde(flatten)]
+    pub stats: RepresentativeStats,
 }
 
 #[derive(Debug, Serialize)]
@@ -1336,483 +2759,2302 @@ pub struct RepresentativesOnlineResponse {
     pub representatives: Vec<RepresentativeOnlineEntry>,
 }
 
+/// List representatives that voted recently, heaviest first. Without a
+/// crawler, falls back to the node-supplied online list.
+///
+/// Errors: `internal` if the online set cannot be read.
 pub async fn handle_representatives_online(
     _params: serde_json::Value,
     state: &RpcState,
 ) -> Result<serde_json::Value, RpcError> {
-    let reps = state
-        .online_reps
-        .read()
-        .map_err(|_| RpcError::Server("failed to read online representatives".into()))?;
+    let Some(crawler) = &state.rep_crawler else {
+        let reps = state
+            .online_reps
+            .read()
+            .map_err(|_| RpcError::Server("failed to read online representatives".into()))?;
 
-    let representatives: Vec<RepresentativeOnlineEntry> = reps
-        .iter()
-        .map(|(addr, weight)| RepresentativeOnlineEntry {
-            account: addr.to_string(),
-            weight: weight.to_string(),
-        })
-        .collect();
+        let representatives: Vec<RepresentativeOnlineEntry> = reps
+            .iter()
+            .map(|(addr, weight)| RepresentativeOnlineEntry {
+                account: addr.to_string(),
+                weight: weight.to_string(),
+                stats: RepresentativeStats::default(),
+            })
+            .collect();
+        return Ok(to_value(&RepresentativesOnlineResponse { representatives }));
+    };
 
-    Ok(to_value(&RepresentativesOnlineResponse { representatives }))
-}
+    let now_ms = unix_now_ms();
+    let online: Vec<(WalletAddress, RepresentativeStats)> = {
+        let crawler = crawler.lock().await;
+        crawler
+            .performances()
+            .filter(|(_, perf)| {
+                perf.last_vote
+                    .is_some_and(|at| now_ms.saturating_sub(at) <= REP_ONLINE_WINDOW_MS)
+            })
+            .map(|(rep, perf)| {
+                (
+                    rep.clone(),
+                    RepresentativeStats::from_performance(perf, now_ms),
+                )
+            })
+            .collect()
+    };
 
-// ═══════════════════════════════════════════════════════════════════════
-// Convenience transaction endpoints
-// ═══════════════════════════════════════════════════════════════════════
+    let mut reps: Vec<(u128, RepresentativeOnlineEntry)> = {
+        let cache = state.rep_weight_cache.read().await;
+        online
+            .into_iter()
+            .map(|(rep, stats)| {
+                let weight = cache.weight(&rep);
+                (
+                    weight,
+                    RepresentativeOnlineEntry {
+                        account: rep.to_string(),
+                        weight: weight.to_string(),
+                        stats,
+                    },
+                )
+            })
+            .collect()
+    };
+    reps.sort_by_key(|(weight, _)| std::cmp::Reverse(*weight));
 
-// ── send (TRST transfer) ────────────────────────────────────────────────
+    Ok(to_value(&RepresentativesOnlineResponse {
+        representatives: reps.into_iter().map(|(_, entry)| entry).collect(),
+    }))
+}
+
+// ── representative_info ─────────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
-pub struct SendRequest {
-    pub wallet: String,
-    pub source: String,
-    pub destination: String,
-    pub amount: String,
+pub struct RepresentativeInfoRequest {
+    pub account: String,
 }
 
 #[derive(Debug, Serialize)]
-pub struct SendResponse {
-    pub block_hash: String,
-    pub accepted: bool,
+pub struct RepresentativeInfoResponse {
+    pub account: String,
+    /// Total weight delegated to the representative.
+    pub weight: String,
+    /// Whether the representative voted within the online window.
+    pub online: bool,
+    #[serde(flatten)]
+    pub stats: RepresentativeStats,
 }
 
-pub async fn handle_send(
+/// Delegated weight and voting performance of a single representative.
+///
+/// Errors: `internal` if the online set cannot be read.
+pub async fn handle_representative_info(
     params: serde_json::Value,
-    _state: &RpcState,
+    state: &RpcState,
 ) -> Result<serde_json::Value, RpcError> {
-    let req: SendRequest =
+    let req: RepresentativeInfoRequest =
         serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
-    validate_account(&req.source)?;
-    validate_account(&req.destination)?;
-    let _amount = validate_positive_amount(&req.amount)?;
+    validate_account(&req.account)?;
 
-    Err(RpcError::InvalidRequest(
-        "send requires a signed block via 'process' — use wallet_core to build and sign, then submit via 'process'".into(),
-    ))
+    let address = WalletAddress::new(req.account.clone());
+    let weight = state.rep_weight_cache.read().await.weight(&address);
+
+    let now_ms = unix_now_ms();
+    let stats = match &state.rep_crawler {
+        Some(crawler) => crawler
+            .lock()
+            .await
+            .performance(&address)
+            .map(|perf| RepresentativeStats::from_performance(perf, now_ms))
+            .unwrap_or_default(),
+        None => RepresentativeStats::default(),
+    };
+    let online = match &state.rep_crawler {
+        Some(_) => stats
+            .last_vote_age_secs
+            .is_some_and(|age| age * 1000 <= REP_ONLINE_WINDOW_MS),
+        None => state
+            .online_reps
+            .read()
+            .map_err(|_| RpcError::Server("failed to read online representatives".into()))?
+            .iter()
+            .any(|(rep, _)| *rep == address),
+    };
+
+    Ok(to_value(&RepresentativeInfoResponse {
+        account: req.account,
+        weight: weight.to_string(),
+        online,
+        stats,
+    }))
 }
 
-// ── burn (BRN → TRST) ──────────────────────────────────────────────────
+// ── confirmation_quorum ─────────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
-pub struct BurnRequest {
-    pub source: String,
-    pub destination: String,
-    pub amount: String,
+pub struct ConfirmationQuorumRequest {}
+
+#[derive(Debug, Serialize)]
+pub struct ConfirmationQuorumResponse {
+    pub online_weight: String,
+    pub trended_weight: String,
+    pub online_weight_minimum: String,
+    pub quorum_delta: String,
+    /// Weight new elections compute their confirmation threshold from.
+    pub effective_weight: String,
+    pub online_weight_samples: usize,
+    pub active_elections: usize,
 }
 
-pub async fn handle_burn(
-    params: serde_json::Value,
-    _state: &RpcState,
+/// Online weight, trend and the resulting quorum delta.
+///
+/// Errors: `unsupported` without an online weight view.
+pub async fn handle_confirmation_quorum(
+    _params: serde_json::Value,
+    state: &RpcState,
 ) -> Result<serde_json::Value, RpcError> {
-    let req: BurnRequest =
-        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
-    validate_account(&req.source)?;
-    validate_account(&req.destination)?;
-    let _amount = validate_positive_amount(&req.amount)?;
+    let Some(view) = state.quorum_view.as_ref() else {
+        return Err(RpcError::unsupported(
+            "quorum tracking is not available on this node",
+        ));
+    };
+    let quorum = view.quorum().await;
 
-    Err(RpcError::InvalidRequest(
-        "burn requires a signed block via 'process' — use wallet_core to build and sign, then submit via 'process'".into(),
-    ))
+    let (effective_weight, active_elections) = match &state.active_elections {
+        Some(ae) => {
+            let ae = ae.read().await;
+            (ae.effective_weight(), ae.election_count())
+        }
+        None => (0, 0),
+    };
+
+    Ok(to_value(&ConfirmationQuorumResponse {
+        online_weight: quorum.online_weight.to_string(),
+        trended_weight: quorum.trended_weight.to_string(),
+        online_weight_minimum: quorum.minimum_weight.to_string(),
+        quorum_delta: quorum.quorum_delta.to_string(),
+        effective_weight: effective_weight.to_string(),
+        online_weight_samples: quorum.sample_count,
+        active_elections,
+    }))
 }
 
-// ── receive ─────────────────────────────────────────────────────────────
+// ── confirmation_history ────────────────────────────────────────────────
 
-#[derive(Debug, Deserialize)]
-pub struct ReceiveRequest {
+/// Confirmations returned when the request doesn't say.
+const DEFAULT_CONFIRMATION_HISTORY_COUNT: usize = 100;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ConfirmationHistoryRequest {
+    #[serde(default)]
+    pub count: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConfirmationStatsResponse {
+    pub count: usize,
+    pub average_ms: u64,
+    pub p95_ms: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConfirmationHistoryEntry {
+    pub hash: String,
     pub account: String,
-    pub block: String,
+    pub amount: String,
+    pub tally: String,
+    pub duration_ms: u64,
+    pub time: u64,
 }
 
-pub async fn handle_receive(
+#[derive(Debug, Serialize)]
+pub struct ConfirmationHistoryResponse {
+    pub confirmation_stats: ConfirmationStatsResponse,
+    pub confirmations: Vec<ConfirmationHistoryEntry>,
+}
+
+impl ConfirmationHistoryResponse {
+    fn from_snapshot(snapshot: crate::server::ConfirmationHistorySnapshot) -> Self {
+        Self {
+            confirmation_stats: ConfirmationStatsResponse {
+                count: snapshot.count,
+                average_ms: snapshot.average_ms,
+                p95_ms: snapshot.p95_ms,
+            },
+            confirmations: snapshot
+                .confirmations
+                .into_iter()
+                .map(|entry| ConfirmationHistoryEntry {
+                    hash: entry.hash.to_string(),
+                    account: entry.account.to_string(),
+                    amount: entry.amount.to_string(),
+                    tally: entry.tally.to_string(),
+                    duration_ms: entry.duration_ms,
+                    time: entry.confirmed_at,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Recent confirmations, newest first, with average and p95 durations.
+///
+/// Errors: none of its own.
+pub async fn handle_confirmation_history(
     params: serde_json::Value,
-    _state: &RpcState,
+    state: &RpcState,
 ) -> Result<serde_json::Value, RpcError> {
-    let req: ReceiveRequest =
+    let req: ConfirmationHistoryRequest =
         serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
-    validate_account(&req.account)?;
-
-    Err(RpcError::InvalidRequest(
-        "receive requires a signed block via 'process' — use wallet_core to build and sign, then submit via 'process'".into(),
-    ))
+    let Some(view) = state.confirmation_history.as_ref() else {
+        return Err(RpcError::InvalidRequest(
+            "confirmation history is not recorded on this node".into(),
+        ));
+    };
+    let snapshot = view
+        .confirmation_history(req.count.unwrap_or(DEFAULT_CONFIRMATION_HISTORY_COUNT))
+        .await;
+    Ok(to_value(&ConfirmationHistoryResponse::from_snapshot(
+        snapshot,
+    )))
 }
 
-// ── wallet_create ───────────────────────────────────────────────────────
+// ── node_containers ─────────────────────────────────────────────────────
 
-#[derive(Debug, Deserialize)]
-pub struct WalletCreateRequest {
-    #[serde(default)]
-    pub seed: Option<String>,
+#[derive(Debug, Serialize)]
+pub struct NodeContainerEntry {
+    pub name: String,
+    pub count: usize,
+    pub bytes: usize,
 }
 
 #[derive(Debug, Serialize)]
-pub struct WalletCreateResponse {
-    pub address: String,
-    pub public_key: String,
+pub struct NodeContainersResponse {
+    pub containers: Vec<NodeContainerEntry>,
+    pub total_bytes: usize,
 }
 
-// Private key is NOT returned over RPC for security.
-// Wallet creation with key custody should be done client-side.
-pub async fn handle_wallet_create(
-    params: serde_json::Value,
-    _state: &RpcState,
-) -> Result<serde_json::Value, RpcError> {
-    let _req: WalletCreateRequest =
-        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+impl NodeContainersResponse {
+    fn from_usage(usage: Vec<(&'static str, burst_types::ContainerUsage)>) -> Self {
+        let containers: Vec<NodeContainerEntry> = usage
+            .into_iter()
+            .map(|(name, usage)| NodeContainerEntry {
+                name: name.to_string(),
+                count: usage.count,
+                bytes: usage.bytes,
+            })
+            .collect();
+        let total_bytes = containers.iter().map(|c| c.bytes).sum();
+        Self {
+            containers,
+            total_bytes,
+        }
+    }
+}
 
-    let kp = burst_crypto::generate_keypair();
-    let address = burst_crypto::derive_address(&kp.public);
-    let public_key = hex::encode(kp.public.as_bytes());
+/// Element counts and approximate memory of the node's caches and queues.
+///
+/// Errors: `unsupported` without a container view.
+pub async fn handle_node_containers(state: &RpcState) -> Result<serde_json::Value, RpcError> {
+    let Some(view) = state.container_view.as_ref() else {
+        return Err(RpcError::unsupported(
+            "container statistics are not available on this node",
+        ));
+    };
+    Ok(to_value(&NodeContainersResponse::from_usage(
+        view.containers().await,
+    )))
+}
 
-    Ok(to_value(&WalletCreateResponse {
-        address: address.to_string(),
-        public_key,
-    }))
+// ── stats ───────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsKind {
+    Counters,
+    Samples,
+    Clear,
 }
 
-// ── wallet_info ─────────────────────────────────────────────────────────
+#[derive(Debug, Deserialize)]
+pub struct StatsRequest {
+    #[serde(rename = "type")]
+    pub kind: StatsKind,
+}
 
-pub async fn handle_wallet_info(
+/// Read or reset the node's diagnostic counters and samples.
+///
+/// Errors: `invalid_request` when statistics are not collected.
+pub async fn handle_stats(
     params: serde_json::Value,
     state: &RpcState,
 ) -> Result<serde_json::Value, RpcError> {
-    // Alias for account_info
-    handle_account_info(params, state).await
+    let req: StatsRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let Some(stats) = state.stats.as_ref() else {
+        return Err(RpcError::InvalidRequest(
+            "statistics are not collected on this node".into(),
+        ));
+    };
+    Ok(match req.kind {
+        StatsKind::Counters => to_value(&stats.counters()),
+        StatsKind::Samples => serde_json::json!({ "samples": stats.samples() }),
+        StatsKind::Clear => {
+            stats.clear();
+            serde_json::json!({ "cleared": true })
+        }
+    })
 }
 
-// ── node_info ───────────────────────────────────────────────────────────
+// ── node_watchdog ───────────────────────────────────────────────────────
 
-pub async fn handle_node_info(
-    params: serde_json::Value,
-    state: &RpcState,
-) -> Result<serde_json::Value, RpcError> {
-    // Alias for telemetry
-    handle_telemetry(params, state).await
+#[derive(Debug, Serialize)]
+pub struct NodeWatchdogResponse {
+    pub thresholds: burst_utils::WatchdogConfig,
+    /// Newest first.
+    pub offenders: Vec<burst_utils::SlowOperation>,
 }
 
-// ═══════════════════════════════════════════════════════════════════════
-// Testnet faucet
-// ═══════════════════════════════════════════════════════════════════════
+/// Operations that recently ran over their watchdog thresholds.
+///
+/// Errors: `invalid_request` when the watchdog is not running.
+pub fn handle_node_watchdog(state: &RpcState) -> Result<serde_json::Value, RpcError> {
+    let Some(watchdog) = state.watchdog.as_ref() else {
+        return Err(RpcError::InvalidRequest(
+            "the watchdog is not running on this node".into(),
+        ));
+    };
+    Ok(to_value(&NodeWatchdogResponse {
+        thresholds: watchdog.config().clone(),
+        offenders: watchdog.recent(),
+    }))
+}
+
+// ── block_proof ─────────────────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
-pub struct FaucetRequest {
-    pub account: String,
+pub struct BlockProofRequest {
+    pub hash: String,
 }
 
 #[derive(Debug, Serialize)]
-pub struct FaucetResponse {
+pub struct BlockProofResponse {
+    pub hash: String,
     pub account: String,
-    pub status: String,
-    pub message: String,
+    /// Position of the block in its account chain (the open block is 1).
+    pub height: u64,
+    /// Number of final votes carried by the proof.
+    pub votes: usize,
+    pub proof: burst_ledger::BlockProof,
 }
 
-/// Testnet-only faucet: auto-verifies a wallet and credits test TRST.
-pub async fn handle_faucet(
+/// Proof that a block is in its account chain and finalized, for light
+/// clients to check against a representative set they trust.
+///
+/// Errors: `invalid_request` when the node serves no block proofs;
+/// `internal` if no proof can be built for the block.
+pub fn handle_block_proof(
     params: serde_json::Value,
     state: &RpcState,
 ) -> Result<serde_json::Value, RpcError> {
-    if !state.enable_faucet {
+    let req: BlockProofRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let hash = parse_block_hash(&req.hash)?;
+    let Some(source) = state.block_proof_source.as_ref() else {
         return Err(RpcError::InvalidRequest(
-            "faucet is disabled on this node".into(),
+            "block proofs are not served by this node".into(),
         ));
+    };
+    let proof = source.block_proof(&hash).map_err(RpcError::Node)?;
+    let account = proof
+        .block()
+        .map(|block| block.account.to_string())
+        .unwrap_or_default();
+    Ok(to_value(&BlockProofResponse {
+        hash: req.hash,
+        account,
+        height: proof.height(),
+        votes: proof.votes.len(),
+        proof,
+    }))
+}
+
+// ── tx_status ───────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct TxStatusRequest {
+    pub hash: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TxStatusResponse {
+    pub hash: String,
+    /// One of the [`TxState`](crate::server::TxState) names, or `unknown`
+    /// when neither the tracker nor the ledger has the block.
+    pub status: String,
+    /// Whether `status` is final.
+    pub terminal: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Unix seconds of the last transition, when tracked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<u64>,
+}
+
+/// Combine the tracked status of a block with what the ledger holds. The
+/// ledger wins when it is further along: the tracker only follows blocks
+/// submitted here, and forgets old ones.
+fn resolve_tx_status(
+    tracked: Option<crate::server::TxStatus>,
+    in_ledger: bool,
+    cemented: bool,
+) -> Option<crate::server::TxStatus> {
+    use crate::server::{TxState, TxStatus};
+
+    let ledger_state = match (in_ledger, cemented) {
+        (_, true) => Some(TxState::Cemented),
+        (true, false) => Some(TxState::Accepted),
+        (false, false) => None,
+    };
+    match (tracked, ledger_state) {
+        (Some(status), None) => Some(status),
+        (Some(status), Some(TxState::Accepted))
+            if matches!(status.state, TxState::Accepted | TxState::Confirmed) =>
+        {
+            Some(status)
+        }
+        (Some(status), Some(TxState::Cemented)) if status.state == TxState::Cemented => {
+            Some(status)
+        }
+        (_, Some(state)) => Some(TxStatus {
+            state,
+            reason: None,
+            updated_at: 0,
+        }),
+        (None, None) => None,
     }
+}
 
-    let req: FaucetRequest =
+/// Progress of a submitted block: queued, accepted, confirmed, cemented,
+/// or why it was rejected or dropped.
+///
+/// Errors: none of its own.
+pub fn handle_tx_status(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let req: TxStatusRequest =
         serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
-    validate_account(&req.account)?;
+    validate_hash(&req.hash)?;
+    let hash = parse_block_hash(&req.hash)?;
+
+    let tracked = state
+        .tx_status
+        .as_ref()
+        .and_then(|tracker| tracker.tx_status(&hash));
+    let stored = match state.block_store.get_block(&hash) {
+        Ok(bytes) => Some(deserialize_block(&bytes)?),
+        Err(StoreError::NotFound(_)) => None,
+        Err(e) => return Err(RpcError::Store(e.to_string())),
+    };
+    let cemented = stored
+        .as_ref()
+        .is_some_and(|block| is_block_confirmed(&hash, &block.account, state));
+
+    let response = match resolve_tx_status(tracked, stored.is_some(), cemented) {
+        Some(status) => TxStatusResponse {
+            hash: req.hash,
+            status: status.state.as_str().to_string(),
+            terminal: status.state.is_terminal(),
+            reason: status.reason,
+            updated_at: (status.updated_at > 0).then_some(status.updated_at),
+        },
+        None => TxStatusResponse {
+            hash: req.hash,
+            status: "unknown".to_string(),
+            terminal: false,
+            reason: None,
+            updated_at: None,
+        },
+    };
+    Ok(to_value(&response))
+}
 
-    let address = WalletAddress::new(req.account.clone());
-    let now = Timestamp::now();
+// ── checkpoints ─────────────────────────────────────────────────────────
 
-    let mut account_info = state
-        .account_store
-        .get_account(&address)
-        .unwrap_or_else(|_| AccountInfo {
-            address: address.clone(),
-            state: burst_types::WalletState::Unverified,
-            verified_at: None,
-            head: BlockHash::ZERO,
-            representative: address.clone(),
-            block_count: 0,
-            confirmation_height: 0,
-            total_brn_burned: 0,
-            trst_balance: 0,
-            total_brn_staked: 0,
-            expired_trst: 0,
-            revoked_trst: 0,
-            epoch: 0,
-        });
+/// Checkpoints returned when the request doesn't say.
+const DEFAULT_CHECKPOINT_COUNT: usize = 10;
 
-    account_info.state = burst_types::WalletState::Verified;
-    account_info.verified_at = Some(now);
-    account_info.trst_balance = account_info
-        .trst_balance
-        .saturating_add(1_000_000_000_000_000_000);
+#[derive(Debug, Default, Deserialize)]
+pub struct CheckpointsRequest {
+    #[serde(default)]
+    pub count: Option<usize>,
+}
 
-    state
-        .account_store
-        .put_account(&account_info)
-        .map_err(|e| RpcError::Store(format!("failed to update faucet account: {e}")))?;
+#[derive(Debug, Serialize)]
+pub struct CheckpointResponseEntry {
+    pub cemented_count: u64,
+    pub digest: String,
+    /// Signed weight under the node's current representative weights.
+    pub weight: String,
+    pub quorum: bool,
+    pub representatives: usize,
+    pub checkpoint: burst_ledger::Checkpoint,
+}
 
-    {
-        let faucet_amount: u128 = 1_000_000_000_000_000_000;
-        let mut cache = state.rep_weight_cache.write().await;
-        cache.add_weight(&account_info.representative, faucet_amount);
-    }
+#[derive(Debug, Serialize)]
+pub struct CheckpointsResponse {
+    pub checkpoints: Vec<CheckpointResponseEntry>,
+}
 
-    Ok(to_value(&FaucetResponse {
-        account: req.account,
-        status: "ok".to_string(),
-        message: "Account verified and 1 TRST credited (testnet faucet)".to_string(),
+/// Recent representative-signed checkpoints, highest cemented count first.
+/// Light clients take the newest one with quorum as their trust anchor.
+///
+/// Errors: `invalid_request` when the node serves no checkpoints;
+/// `internal` if they cannot be read.
+pub async fn handle_checkpoints(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let req: CheckpointsRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let Some(view) = state.checkpoint_view.as_ref() else {
+        return Err(RpcError::InvalidRequest(
+            "checkpoints are not served by this node".into(),
+        ));
+    };
+    let entries = view
+        .checkpoints(req.count.unwrap_or(DEFAULT_CHECKPOINT_COUNT))
+        .await
+        .map_err(RpcError::Node)?;
+    Ok(to_value(&CheckpointsResponse {
+        checkpoints: entries
+            .into_iter()
+            .map(|entry| CheckpointResponseEntry {
+                cemented_count: entry.checkpoint.digest.cemented_count,
+                digest: entry.checkpoint.digest.hash().to_string(),
+                weight: entry.weight.to_string(),
+                quorum: entry.quorum,
+                representatives: entry.checkpoint.votes.len(),
+                checkpoint: entry.checkpoint,
+            })
+            .collect(),
     }))
 }
 
-// ═══════════════════════════════════════════════════════════════════════
-// Testnet convenience RPCs (faucet-only)
-//
-// These endpoints construct, sign, and submit blocks server-side so that
-// testnet validation can be performed via simple curl commands without a
-// local wallet. They are gated behind `enable_faucet`.
-// ═══════════════════════════════════════════════════════════════════════
+// ── account_state_root / account_state_proof ───────────────────────────
 
-fn require_faucet(state: &RpcState) -> Result<(), RpcError> {
-    if !state.enable_faucet {
-        return Err(RpcError::InvalidRequest(
-            "this endpoint is only available on testnet nodes with faucet enabled".into(),
-        ));
-    }
-    Ok(())
+#[derive(Debug, Serialize)]
+pub struct AccountStateRootResponse {
+    pub account_state_root: String,
+    pub block_count: u64,
+    pub account_count: u64,
 }
 
-fn parse_private_key(hex_str: &str) -> Result<burst_types::PrivateKey, RpcError> {
-    let bytes = hex::decode(hex_str)
-        .map_err(|e| RpcError::InvalidRequest(format!("invalid private key hex: {e}")))?;
-    if bytes.len() != 32 {
-        return Err(RpcError::InvalidRequest(
-            "private key must be 32 bytes (64 hex chars)".into(),
-        ));
-    }
-    let mut arr = [0u8; 32];
-    arr.copy_from_slice(&bytes);
-    Ok(burst_types::PrivateKey(arr))
+/// Root of the node's account-state tree. Two nodes with the same block
+/// count and different roots hold different ledgers.
+///
+/// Errors: none of its own.
+pub fn handle_account_state_root(state: &RpcState) -> Result<serde_json::Value, RpcError> {
+    let root = state
+        .state_tree_store
+        .state_root()
+        .map_err(|e| RpcError::Store(e.to_string()))?;
+    let block_count = state
+        .block_store
+        .block_count()
+        .map_err(|e| RpcError::Store(e.to_string()))?;
+    let account_count = state
+        .account_store
+        .account_count()
+        .map_err(|e| RpcError::Store(e.to_string()))?;
+    Ok(to_value(&AccountStateRootResponse {
+        account_state_root: root.to_string(),
+        block_count,
+        account_count,
+    }))
 }
 
-/// Build a StateBlock, compute its hash, sign it, and generate PoW.
-#[allow(clippy::too_many_arguments)]
-fn build_and_sign_block(
-    block_type: burst_ledger::BlockType,
-    account: &WalletAddress,
-    previous: BlockHash,
-    representative: &WalletAddress,
-    brn_balance: u128,
-    trst_balance: u128,
-    link: BlockHash,
-    origin: TxHash,
-    transaction: TxHash,
-    private_key: &burst_types::PrivateKey,
-    work_generator: &burst_work::WorkGenerator,
-    min_work_difficulty: u64,
-    params_hash: BlockHash,
-) -> Result<burst_ledger::StateBlock, RpcError> {
-    use burst_ledger::CURRENT_BLOCK_VERSION;
+#[derive(Debug, Deserialize)]
+pub struct AccountStateProofRequest {
+    pub account: String,
+}
 
-    let now = Timestamp::now();
+#[derive(Debug, Serialize)]
+pub struct AccountStateProofResponse {
+    pub account_state_root: String,
+    /// The account record the proof is for.
+    pub account: AccountInfo,
+    pub proof: burst_store::AccountStateProof,
+}
 
-    let mut block = burst_ledger::StateBlock {
-        version: CURRENT_BLOCK_VERSION,
-        block_type,
-        account: account.clone(),
-        previous,
-        representative: representative.clone(),
-        brn_balance,
-        trst_balance,
-        link,
-        origin,
-        transaction,
-        timestamp: now,
-        params_hash,
-        work: 0,
-        signature: Signature([0u8; 64]),
-        hash: BlockHash::ZERO,
-    };
+/// An account's state with a proof against the current account-state root.
+///
+/// Errors: `account_not_found`.
+pub fn handle_account_state_proof(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let req: AccountStateProofRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    validate_account(&req.account)?;
 
-    block.hash = block.compute_hash();
-    block.signature = burst_crypto::sign_message(block.hash.as_bytes(), private_key);
+    let address = WalletAddress::new(req.account.clone());
+    let account = state
+        .account_store
+        .get_account(&address)
+        .map_err(|e| account_not_found(e, &req.account))?;
+    let proof = state
+        .state_tree_store
+        .account_proof(&address)
+        .map_err(|e| RpcError::Store(e.to_string()))?
+        .ok_or_else(|| RpcError::AccountNotFound(req.account.clone()))?;
+    Ok(to_value(&AccountStateProofResponse {
+        account_state_root: proof.root().to_string(),
+        account,
+        proof,
+    }))
+}
 
-    let work_kind = match block.block_type {
-        burst_ledger::BlockType::Open | burst_ledger::BlockType::Receive => {
-            burst_work::WorkBlockKind::ReceiveOrOpen
-        }
-        _ => burst_work::WorkBlockKind::Base,
-    };
-    let threshold =
-        burst_work::WorkThresholds::with_base(min_work_difficulty).threshold_for(work_kind);
-    let nonce = work_generator
-        .generate(&block.hash, threshold)
-        .map_err(|e| RpcError::Server(format!("work generation failed: {e}")))?;
-    block.work = nonce.0;
+// ── election_info ───────────────────────────────────────────────────────
 
-    Ok(block)
+#[derive(Debug, Deserialize)]
+pub struct ElectionInfoRequest {
+    pub root: String,
 }
 
-/// Submit a block through the block processor and return whether it was accepted.
-fn submit_block(block: &burst_ledger::StateBlock, state: &RpcState) -> Result<bool, RpcError> {
-    let block_bytes = bincode::serialize(block)
-        .map_err(|e| RpcError::Server(format!("block serialization failed: {e}")))?;
-    let result = state
-        .block_processor
-        .process_block(&block_bytes)
-        .map_err(RpcError::Server)?;
-    let accepted = matches!(
-        result,
-        crate::server::ProcessResult::Accepted | crate::server::ProcessResult::Queued
-    );
-    if !accepted {
-        let detail = match &result {
-            crate::server::ProcessResult::Rejected(r) => r.clone(),
-            crate::server::ProcessResult::Duplicate => "duplicate block".to_string(),
-            crate::server::ProcessResult::Fork => "fork detected".to_string(),
-            crate::server::ProcessResult::Gap => "gap — previous block unknown".to_string(),
-            _ => "unknown".to_string(),
-        };
-        return Err(RpcError::Server(format!("block not accepted: {detail}")));
+#[derive(Debug, Serialize)]
+pub struct ElectionCandidate {
+    pub block: String,
+    pub tally: String,
+    pub voters: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ElectionVoter {
+    pub account: String,
+    pub block: String,
+    pub weight: String,
+    pub is_final: bool,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ElectionInfoResponse {
+    pub root: String,
+    pub state: String,
+    pub created_at: u64,
+    pub state_changed_at: u64,
+    pub confirmation_threshold: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub leader: Option<String>,
+    /// Fork candidates, highest tally first.
+    pub candidates: Vec<ElectionCandidate>,
+    pub voters: Vec<ElectionVoter>,
+}
+
+impl ElectionInfoResponse {
+    fn from_election(election: &burst_consensus::Election) -> Self {
+        let mut candidates: Vec<(u128, ElectionCandidate)> = election
+            .tally
+            .iter()
+            .map(|(block, &tally)| {
+                let voters = election
+                    .last_votes
+                    .values()
+                    .filter(|v| v.block_hash == *block)
+                    .count();
+                (
+                    tally,
+                    ElectionCandidate {
+                        block: block.to_string(),
+                        tally: tally.to_string(),
+                        voters,
+                    },
+                )
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.block.cmp(&b.1.block)));
+
+        let mut voters: Vec<(u128, ElectionVoter)> = election
+            .last_votes
+            .values()
+            .map(|v| {
+                (
+                    v.weight,
+                    ElectionVoter {
+                        account: v.voter.to_string(),
+                        block: v.block_hash.to_string(),
+                        weight: v.weight.to_string(),
+                        is_final: v.is_final,
+                        timestamp: v.timestamp.as_secs(),
+                    },
+                )
+            })
+            .collect();
+        voters.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.account.cmp(&b.1.account)));
+
+        Self {
+            root: election.id.to_string(),
+            state: format!("{:?}", election.state).to_lowercase(),
+            created_at: election.created_at.as_secs(),
+            state_changed_at: election.state_changed_at.as_secs(),
+            confirmation_threshold: election.confirmation_threshold().to_string(),
+            leader: candidates.first().map(|(_, c)| c.block.clone()),
+            candidates: candidates.into_iter().map(|(_, c)| c).collect(),
+            voters: voters.into_iter().map(|(_, v)| v).collect(),
+        }
     }
-    Ok(true)
 }
 
-// ── wallet_create_full (testnet-only, returns private key) ──────────
+/// Tally per fork candidate and every voter seen for an active election.
+///
+/// Errors: `unsupported` without an election view; `invalid_request` when no
+/// election is active for the root.
+pub async fn handle_election_info(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let req: ElectionInfoRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let root = parse_block_hash(&req.root)?;
+
+    let Some(ae) = state.active_elections.as_ref() else {
+        return Err(RpcError::unsupported(
+            "elections are not available on this node",
+        ));
+    };
+    let ae = ae.read().await;
+    let election = ae.get_election(&root).ok_or_else(|| {
+        RpcError::InvalidRequest(format!("no active election for root {}", req.root))
+    })?;
+
+    Ok(to_value(&ElectionInfoResponse::from_election(election)))
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Convenience transaction endpoints
+// ═══════════════════════════════════════════════════════════════════════
+
+// ── send (TRST transfer) ────────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
-pub struct WalletCreateFullRequest {
-    #[serde(default)]
-    pub seed: Option<String>,
+pub struct SendRequest {
+    pub wallet: String,
+    pub source: String,
+    pub destination: String,
+    pub amount: String,
 }
 
 #[derive(Debug, Serialize)]
-pub struct WalletCreateFullResponse {
-    pub address: String,
-    pub public_key: String,
-    pub private_key: String,
+pub struct SendResponse {
+    pub block_hash: String,
+    pub accepted: bool,
 }
 
-pub async fn handle_wallet_create_full(
+/// Errors: always `invalid_request`: sends are submitted as signed blocks via
+/// `process`.
+pub async fn handle_send(
     params: serde_json::Value,
-    state: &RpcState,
+    _state: &RpcState,
 ) -> Result<serde_json::Value, RpcError> {
-    require_faucet(state)?;
-
-    let _req: WalletCreateFullRequest =
+    let req: SendRequest =
         serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    validate_account(&req.source)?;
+    validate_account(&req.destination)?;
+    let _amount = validate_positive_amount(&req.amount)?;
 
-    let kp = burst_crypto::generate_keypair();
-    let address = burst_crypto::derive_address(&kp.public);
-
-    Ok(to_value(&WalletCreateFullResponse {
-        address: address.to_string(),
-        public_key: hex::encode(kp.public.0),
-        private_key: hex::encode(kp.private.0),
-    }))
+    Err(RpcError::InvalidRequest(
+        "send requires a signed block via 'process' — use wallet_core to build and sign, then submit via 'process'".into(),
+    ))
 }
 
-// ── burn_simple (BRN → TRST) ────────────────────────────────────────
+// ── burn (BRN → TRST) ──────────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
-pub struct BurnSimpleRequest {
-    pub private_key: String,
+pub struct BurnRequest {
+    pub source: String,
+    pub destination: String,
     pub amount: String,
 }
 
+/// Errors: always `invalid_request`: burns are submitted as signed blocks via
+/// `process`.
+pub async fn handle_burn(
+    params: serde_json::Value,
+    _state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let req: BurnRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    validate_account(&req.source)?;
+    validate_account(&req.destination)?;
+    let _amount = validate_positive_amount(&req.amount)?;
+
+    Err(RpcError::InvalidRequest(
+        "burn requires a signed block via 'process' — use wallet_core to build and sign, then submit via 'process'".into(),
+    ))
+}
+
+// ── receive ─────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct ReceiveRequest {
+    pub account: String,
+    pub block: String,
+}
+
+/// Errors: always `invalid_request`: receives are submitted as signed blocks
+/// via `process`.
+pub async fn handle_receive(
+    params: serde_json::Value,
+    _state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let req: ReceiveRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    validate_account(&req.account)?;
+
+    Err(RpcError::InvalidRequest(
+        "receive requires a signed block via 'process' — use wallet_core to build and sign, then submit via 'process'".into(),
+    ))
+}
+
+// ── wallet_create ───────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct WalletCreateRequest {
+    #[serde(default)]
+    pub seed: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WalletCreateResponse {
+    pub address: String,
+    pub public_key: String,
+}
+
+// Private key is NOT returned over RPC for security.
+// Wallet creation with key custody should be done client-side.
+/// Errors: none of its own.
+pub async fn handle_wallet_create(
+    params: serde_json::Value,
+    _state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let _req: WalletCreateRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+
+    let kp = burst_crypto::generate_keypair();
+    let address = burst_crypto::derive_address(&kp.public);
+    let public_key = hex::encode(kp.public.as_bytes());
+
+    Ok(to_value(&WalletCreateResponse {
+        address: address.to_string(),
+        public_key,
+    }))
+}
+
+// ── wallet_info ─────────────────────────────────────────────────────────
+
+/// Errors: none.
+pub async fn handle_wallet_info(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    // Alias for account_info
+    handle_account_info(params, state).await
+}
+
+// ── node_info ───────────────────────────────────────────────────────────
+
+/// Errors: none.
+pub async fn handle_node_info(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    // Alias for telemetry
+    handle_telemetry(params, state).await
+}
+
+// ── online_weight_history ───────────────────────────────────────────────
+
+/// Span charted by `online_weight_history` when `from` is omitted: one week.
+const DEFAULT_WEIGHT_HISTORY_SPAN_SECS: u64 = 7 * 24 * 3600;
+
+/// Bucket width when `step` is omitted: one hour.
+const DEFAULT_WEIGHT_HISTORY_STEP_SECS: u64 = 3600;
+
+/// Most buckets one `online_weight_history` call returns.
+const MAX_WEIGHT_HISTORY_BUCKETS: u64 = 2_000;
+
+#[derive(Debug, Deserialize)]
+pub struct OnlineWeightHistoryRequest {
+    /// Unix seconds. Defaults to one week before `to`.
+    #[serde(default)]
+    pub from: Option<u64>,
+    /// Unix seconds. Defaults to now.
+    #[serde(default)]
+    pub to: Option<u64>,
+    /// Bucket width in seconds.
+    #[serde(default)]
+    pub step: Option<u64>,
+}
+
+/// Online weight samples summarised over one bucket.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WeightBucket {
+    /// Unix seconds at which the bucket starts.
+    pub start: u64,
+    pub samples: u64,
+    pub online_min: u128,
+    pub online_max: u128,
+    pub online_avg: u128,
+    /// Trended weight and quorum delta at the bucket's last sample that
+    /// recorded them.
+    pub trended: Option<u128>,
+    pub quorum_delta: Option<u128>,
+}
+
+/// Group `samples` (oldest first) into `step`-second buckets starting at
+/// `from`. Buckets without samples are left out.
+pub fn weight_buckets(
+    samples: &[burst_store::WeightSample],
+    from: u64,
+    step: u64,
+) -> Vec<WeightBucket> {
+    let mut buckets: Vec<WeightBucket> = Vec::new();
+    let mut total = 0u128;
+    for sample in samples {
+        let start = from + (sample.timestamp.saturating_sub(from) / step) * step;
+        match buckets.last_mut() {
+            Some(bucket) if bucket.start == start => {
+                bucket.samples += 1;
+                bucket.online_min = bucket.online_min.min(sample.online);
+                bucket.online_max = bucket.online_max.max(sample.online);
+                total = total.saturating_add(sample.online);
+            }
+            _ => {
+                total = sample.online;
+                buckets.push(WeightBucket {
+                    start,
+                    samples: 1,
+                    online_min: sample.online,
+                    online_max: sample.online,
+                    online_avg: 0,
+                    trended: None,
+                    quorum_delta: None,
+                });
+            }
+        }
+        let bucket = buckets.last_mut().expect("bucket just pushed");
+        bucket.online_avg = total / u128::from(bucket.samples);
+        bucket.trended = sample.trended.or(bucket.trended);
+        bucket.quorum_delta = sample.quorum_delta.or(bucket.quorum_delta);
+    }
+    buckets
+}
+
+/// Online weight, trended weight and quorum delta over time, for charting
+/// quorum stability and diagnosing past confirmation stalls.
+///
+/// Errors: none of its own.
+pub async fn handle_online_weight_history(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let req: OnlineWeightHistoryRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let to = req.to.unwrap_or_else(|| node_now(state).as_secs());
+    let from = req
+        .from
+        .unwrap_or_else(|| to.saturating_sub(DEFAULT_WEIGHT_HISTORY_SPAN_SECS));
+    let step = req.step.unwrap_or(DEFAULT_WEIGHT_HISTORY_STEP_SECS);
+    if from > to || step == 0 {
+        return Err(RpcError::InvalidRequest(
+            "`from` must not be after `to` and `step` must be positive".into(),
+        ));
+    }
+    if (to - from) / step >= MAX_WEIGHT_HISTORY_BUCKETS {
+        return Err(RpcError::InvalidRequest(format!(
+            "at most {MAX_WEIGHT_HISTORY_BUCKETS} buckets per call; widen `step` or narrow the range"
+        )));
+    }
+    let samples = state
+        .rep_weight_store
+        .weight_samples(from, to)
+        .map_err(|e| RpcError::Store(e.to_string()))?;
+    let amount = |value: Option<u128>| value.map(|v| v.to_string());
+    let buckets: Vec<serde_json::Value> = weight_buckets(&samples, from, step)
+        .into_iter()
+        .map(|b| {
+            serde_json::json!({
+                "start": b.start,
+                "samples": b.samples,
+                "online_min": b.online_min.to_string(),
+                "online_avg": b.online_avg.to_string(),
+                "online_max": b.online_max.to_string(),
+                "trended": amount(b.trended),
+                "quorum_delta": amount(b.quorum_delta),
+            })
+        })
+        .collect();
+    Ok(serde_json::json!({
+        "from": from,
+        "to": to,
+        "step": step,
+        "buckets": buckets,
+    }))
+}
+
+// ── rep_weights_recalculate ─────────────────────────────────────────────
+
+/// Rebuild `cache` from `accounts`, persist the corrected weights to
+/// `weights` in one transaction (dropping representatives that no longer
+/// hold any) and log every representative that had drifted.
+///
+/// Shared by `rep_weights_recalculate` and the node's periodic check. The
+/// caller holds the cache lock so no block lands during the scan.
+pub fn recalculate_rep_weights(
+    cache: &mut burst_consensus::RepWeightCache,
+    accounts: &dyn burst_store::account::AccountStore,
+    weights: &dyn burst_store::RepWeightStore,
+) -> Result<Vec<burst_consensus::WeightDrift>, StoreError> {
+    let drift = cache.reconcile(
+        accounts
+            .iter_accounts()?
+            .into_iter()
+            .map(|a| (a.address, a.representative, a.trst_balance)),
+    );
+    let reconciled: Vec<(WalletAddress, u128)> = cache
+        .all_weights()
+        .iter()
+        .map(|(rep, weight)| (rep.clone(), *weight))
+        .collect();
+    weights.replace_rep_weights(&reconciled)?;
+    for d in &drift {
+        tracing::warn!(
+            representative = %d.representative,
+            cached = d.cached,
+            expected = d.expected,
+            "representative weight drift corrected"
+        );
+    }
+    Ok(drift)
+}
+
+/// Recompute representative weights from the account store, correct and
+/// persist the weight cache and report every representative that had
+/// drifted.
+///
+/// Errors: none of its own.
+pub async fn handle_rep_weights_recalculate(
+    _params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let mut cache = state.rep_weight_cache.write().await;
+    let drift = recalculate_rep_weights(
+        &mut cache,
+        state.account_store.as_ref(),
+        state.rep_weight_store.as_ref(),
+    )
+    .map_err(|e| RpcError::Store(e.to_string()))?;
+    let representatives = cache.rep_count();
+    drop(cache);
+    let drift: Vec<serde_json::Value> = drift
+        .iter()
+        .map(|d| {
+            serde_json::json!({
+                "representative": d.representative.to_string(),
+                "cached": d.cached.to_string(),
+                "expected": d.expected.to_string(),
+            })
+        })
+        .collect();
+    Ok(serde_json::json!({
+        "representatives": representatives,
+        "drift": drift,
+    }))
+}
+
+// ── representative_key_rotate ───────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct RepresentativeKeyRotateRequest {
+    /// Passphrase of the representative key file.
+    pub password: String,
+}
+
+/// Rotate the node's representative key. The passphrase of the key file
+/// authorizes the call; the response is the signed rotation notice.
+///
+/// Errors: `invalid_request` without a key file; `internal` for a wrong
+/// passphrase or a failed rotation.
+pub async fn handle_representative_key_rotate(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let Some(admin) = state.representative_key_admin.as_ref() else {
+        return Err(RpcError::InvalidRequest(
+            "no representative key file configured on this node".into(),
+        ));
+    };
+    let req: RepresentativeKeyRotateRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let rotation = admin
+        .rotate_representative_key(&req.password)
+        .await
+        .map_err(RpcError::Node)?;
+    serde_json::to_value(rotation).map_err(|e| RpcError::Server(e.to_string()))
+}
+
+// ── peer_ban / peer_unban ───────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct PeerBanRequest {
+    /// An IP address or CIDR range (`"203.0.113.0/24"`).
+    pub address: String,
+    /// Ban length in seconds; omitted for an indefinite ban.
+    #[serde(default)]
+    pub duration: Option<u64>,
+}
+
+/// Ban a peer address or range, dropping any covered connections. The ban
+/// survives restarts.
+///
+/// Errors: `unsupported` without a network.
+pub async fn handle_peer_ban(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let Some(admin) = state.peer_admin.as_ref() else {
+        return Err(RpcError::unsupported(
+            "peer administration is not available on this node",
+        ));
+    };
+    let req: PeerBanRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let ban = admin
+        .ban_peer(&req.address, req.duration)
+        .await
+        .map_err(RpcError::InvalidRequest)?;
+    serde_json::to_value(ban).map_err(|e| RpcError::Server(e.to_string()))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PeerUnbanRequest {
+    /// The address or range exactly as it was banned.
+    pub address: String,
+}
+
+/// Lift a ban set with `peer_ban`.
+///
+/// Errors: `unsupported` without a network.
+pub async fn handle_peer_unban(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let Some(admin) = state.peer_admin.as_ref() else {
+        return Err(RpcError::unsupported(
+            "peer administration is not available on this node",
+        ));
+    };
+    let req: PeerUnbanRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let removed = admin
+        .unban_peer(&req.address)
+        .await
+        .map_err(RpcError::InvalidRequest)?;
+    Ok(serde_json::json!({
+        "address": req.address,
+        "unbanned": removed,
+    }))
+}
+
+// ── republish / bootstrap_account ───────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct RepublishRequest {
+    pub hash: String,
+}
+
+/// Flood a stored block to every connected peer again, for blocks that
+/// propagated poorly the first time.
+///
+/// Errors: `block_not_found`; `unsupported` without a network.
+pub async fn handle_republish(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let Some(admin) = state.sync_admin.as_ref() else {
+        return Err(RpcError::unsupported(
+            "sync administration is not available on this node",
+        ));
+    };
+    let req: RepublishRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    validate_hash(&req.hash)?;
+    let hash = parse_block_hash(&req.hash)?;
+    match state.block_store.get_block(&hash) {
+        Ok(_) => {}
+        Err(StoreError::NotFound(_)) => return Err(RpcError::BlockNotFound(req.hash)),
+        Err(e) => return Err(RpcError::Store(e.to_string())),
+    }
+    let peers = admin.republish(&hash).await.map_err(RpcError::Node)?;
+    Ok(serde_json::json!({
+        "hash": req.hash,
+        "peers": peers,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BootstrapAccountRequest {
+    pub account: String,
+}
+
+/// Pull one account's chain from peers, for a chain stuck behind a gap or
+/// lagging the network. Blocks arrive asynchronously through the block
+/// queue; the response only says how many peers were asked.
+///
+/// Errors: `unsupported` without sync administration; `internal` if the pull
+/// cannot start.
+pub async fn handle_bootstrap_account(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let Some(admin) = state.sync_admin.as_ref() else {
+        return Err(RpcError::unsupported(
+            "sync administration is not available on this node",
+        ));
+    };
+    let req: BootstrapAccountRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    validate_account(&req.account)?;
+    let account = WalletAddress::new(req.account.clone());
+    let head = match state.account_store.get_account(&account) {
+        Ok(info) => Some(info.head.to_string()),
+        Err(StoreError::NotFound(_)) => None,
+        Err(e) => return Err(RpcError::Store(e.to_string())),
+    };
+    let peers = admin
+        .bootstrap_account(&account)
+        .await
+        .map_err(RpcError::Node)?;
+    Ok(serde_json::json!({
+        "account": req.account,
+        "head": head,
+        "peers": peers,
+    }))
+}
+
+// ── audit_log ───────────────────────────────────────────────────────────
+
+/// Entries returned by `audit_log` when `count` is omitted.
+const DEFAULT_AUDIT_LOG_COUNT: usize = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct AuditLogRequest {
+    /// Maximum number of entries, newest first.
+    #[serde(default)]
+    pub count: Option<usize>,
+    /// Only entries numbered below this, to page back through the log.
+    #[serde(default)]
+    pub before: Option<u64>,
+}
+
+/// Recorded privileged actions, newest first.
+///
+/// Errors: `unsupported` without an audit log; `internal` if it cannot be
+/// read.
+pub async fn handle_audit_log(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let Some(log) = state.audit_log.as_ref() else {
+        return Err(RpcError::unsupported(
+            "the audit log is not available on this node",
+        ));
+    };
+    let req: AuditLogRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let count = req
+        .count
+        .unwrap_or(DEFAULT_AUDIT_LOG_COUNT)
+        .clamp(1, pagination::MAX_PAGE_SIZE as usize);
+    let entries = log
+        .entries(req.before, count)
+        .await
+        .map_err(RpcError::Node)?;
+    Ok(serde_json::json!({ "entries": entries }))
+}
+
+// ── forks_recent ────────────────────────────────────────────────────────
+
+/// Forks returned by `forks_recent` when `count` is omitted.
+const DEFAULT_FORKS_COUNT: usize = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct ForksRecentRequest {
+    /// Maximum number of forks, newest first.
+    #[serde(default)]
+    pub count: Option<usize>,
+    /// Only forks numbered below this, to page back through the table.
+    #[serde(default)]
+    pub before: Option<u64>,
+    /// Only forks signed by this account.
+    #[serde(default)]
+    pub account: Option<String>,
+}
+
+/// Recently detected forks and how they were resolved, newest first.
+///
+/// Errors: `unsupported` without fork tracking.
+pub async fn handle_forks_recent(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let Some(history) = state.fork_history.as_ref() else {
+        return Err(RpcError::unsupported(
+            "fork history is not available on this node",
+        ));
+    };
+    let req: ForksRecentRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let count = req
+        .count
+        .unwrap_or(DEFAULT_FORKS_COUNT)
+        .clamp(1, pagination::MAX_PAGE_SIZE as usize);
+    let forks = history
+        .forks(req.before, count, req.account.as_deref())
+        .await
+        .map_err(RpcError::Node)?;
+    Ok(serde_json::json!({ "forks": forks }))
+}
+
+// ── dm_send / dm_fetch ──────────────────────────────────────────────────
+
+/// A direct message as it travels over RPC, binary fields in hex.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DirectMessageJson {
+    /// Blake2b ID of the message; ignored on submission.
+    #[serde(default, skip_deserializing)]
+    pub id: String,
+    pub sender: String,
+    pub recipient: String,
+    pub ephemeral_public: String,
+    pub ciphertext: String,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub signature: String,
+}
+
+impl DirectMessageJson {
+    fn from_message(message: &DirectMessage) -> Self {
+        Self {
+            id: hex::encode(message.id()),
+            sender: message.sender.to_string(),
+            recipient: message.recipient.to_string(),
+            ephemeral_public: hex::encode(message.ephemeral_public),
+            ciphertext: hex::encode(&message.ciphertext),
+            created_at: message.created_at,
+            expires_at: message.expires_at,
+            signature: hex::encode(message.signature.0),
+        }
+    }
+
+    fn into_message(self) -> Result<DirectMessage, RpcError> {
+        validate_account(&self.sender)?;
+        validate_account(&self.recipient)?;
+        let ephemeral_public: [u8; 32] = hex::decode(&self.ephemeral_public)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| {
+                RpcError::InvalidRequest("ephemeral_public must be 32 bytes of hex".into())
+            })?;
+        let ciphertext = hex::decode(&self.ciphertext)
+            .map_err(|e| RpcError::InvalidRequest(format!("invalid ciphertext hex: {e}")))?;
+        Ok(DirectMessage {
+            sender: WalletAddress::new(self.sender),
+            recipient: WalletAddress::new(self.recipient),
+            ephemeral_public,
+            ciphertext,
+            created_at: self.created_at,
+            expires_at: self.expires_at,
+            signature: parse_signature(&self.signature)?,
+        })
+    }
+}
+
+fn direct_messages(state: &RpcState) -> Result<&dyn crate::server::DirectMessageRelay, RpcError> {
+    state
+        .direct_messages
+        .as_deref()
+        .ok_or_else(|| RpcError::unsupported("direct messages are not enabled on this node"))
+}
+
+/// Hand a sealed, signed direct message to the node, which holds it for
+/// the recipient until it expires and relays it to peers. Resubmitting a
+/// message already held is not an error.
+///
+/// Errors: `invalid_signature`; `unsupported` when direct messages are off.
+pub async fn handle_dm_send(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let relay = direct_messages(state)?;
+    let req: DirectMessageJson =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let message = req.into_message()?;
+    let id = hex::encode(message.id());
+    let relayed = relay.submit(message).await?;
+    Ok(serde_json::json!({ "id": id, "relayed": relayed }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DmFetchRequest {
+    pub account: String,
+}
+
+/// Unexpired direct messages held for `account`, oldest first. They are
+/// still encrypted; only the account's key opens them.
+///
+/// Errors: `unsupported` when direct messages are off.
+pub async fn handle_dm_fetch(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let relay = direct_messages(state)?;
+    let req: DmFetchRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    validate_account(&req.account)?;
+    let messages = relay.fetch(&WalletAddress::new(req.account)).await?;
+    let messages: Vec<DirectMessageJson> = messages
+        .iter()
+        .map(DirectMessageJson::from_message)
+        .collect();
+    Ok(serde_json::json!({ "messages": messages }))
+}
+
+// ── alias_resolve / alias_reverse ───────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+pub struct AliasResponse {
+    pub alias: String,
+    pub account: String,
+    pub registered_at: u64,
+    pub expires_at: u64,
+    /// The registration has lapsed; wallets should not send to it.
+    pub expired: bool,
+}
+
+impl AliasResponse {
+    fn from_record(record: burst_store::AliasRecord) -> Self {
+        let expired = record.is_expired(Timestamp::now());
+        Self {
+            alias: record.name,
+            account: record.owner.to_string(),
+            registered_at: record.registered_at.as_secs(),
+            expires_at: record.expires_at.as_secs(),
+            expired,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AliasResolveRequest {
+    pub alias: String,
+}
+
+/// The account an alias is registered to. Lapsed registrations are still
+/// returned, marked `expired`, until someone else takes the name.
+///
+/// Errors: `not_found`.
+pub async fn handle_alias_resolve(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let req: AliasResolveRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    burst_transactions::alias::validate_alias_name(&req.alias)
+        .map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let record = state.alias_store.get_alias(&req.alias)?.ok_or_else(|| {
+        RpcError::coded(
+            ErrorCode::NotFound,
+            format!("alias not found: {}", req.alias),
+        )
+        .with_data(serde_json::json!({ "alias": req.alias }))
+    })?;
+    Ok(to_value(&AliasResponse::from_record(record)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AliasReverseRequest {
+    pub account: String,
+}
+
+/// The alias `account` holds.
+///
+/// Errors: `not_found` when the account holds no alias.
+pub async fn handle_alias_reverse(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let req: AliasReverseRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    validate_account(&req.account)?;
+    let record = state
+        .alias_store
+        .get_account_alias(&WalletAddress::new(req.account.clone()))?
+        .ok_or_else(|| {
+            RpcError::coded(
+                ErrorCode::NotFound,
+                format!("no alias for account {}", req.account),
+            )
+            .with_data(serde_json::json!({ "account": req.account }))
+        })?;
+    Ok(to_value(&AliasResponse::from_record(record)))
+}
+
+// ── dev_clock_advance / dev_instant_confirmation / dev_governance_advance ──
+
+fn dev_admin(state: &RpcState) -> Result<&dyn crate::server::DevAdmin, RpcError> {
+    state
+        .dev_admin
+        .as_deref()
+        .ok_or_else(|| RpcError::unsupported("dev controls are not available on this node"))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DevClockAdvanceRequest {
+    /// Seconds to move the logical clock forward.
+    pub seconds: u64,
+}
+
+/// Move the node's logical clock forward (dev network only).
+///
+/// Errors: `unsupported` off the dev network.
+pub async fn handle_dev_clock_advance(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let admin = dev_admin(state)?;
+    let req: DevClockAdvanceRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let clock = admin
+        .advance_clock(req.seconds)
+        .await
+        .map_err(RpcError::InvalidRequest)?;
+    serde_json::to_value(clock).map_err(|e| RpcError::Server(e.to_string()))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DevInstantConfirmationRequest {
+    pub enabled: bool,
+}
+
+/// Turn confirming accepted blocks without elections on or off (dev network
+/// only).
+///
+/// Errors: `unsupported` off the dev network.
+pub async fn handle_dev_instant_confirmation(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let admin = dev_admin(state)?;
+    let req: DevInstantConfirmationRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let enabled = admin
+        .set_instant_confirmation(req.enabled)
+        .await
+        .map_err(RpcError::InvalidRequest)?;
+    Ok(serde_json::json!({ "instant_confirmation": enabled }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DevGovernanceAdvanceRequest {
+    /// Hash of the proposal, hex-encoded.
+    pub proposal: String,
+}
+
+/// Move a governance proposal into its next phase (dev network only).
+///
+/// Errors: `unsupported` off the dev network; `invalid_request` for a proposal
+/// that cannot advance.
+pub async fn handle_dev_governance_advance(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let admin = dev_admin(state)?;
+    let req: DevGovernanceAdvanceRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let proposal = parse_tx_hash(&req.proposal)?;
+    let phase = admin
+        .force_governance_phase(proposal)
+        .await
+        .map_err(RpcError::InvalidRequest)?;
+    Ok(serde_json::json!({
+        "proposal": req.proposal,
+        "phase": phase,
+    }))
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Testnet faucet
+// ═══════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Deserialize)]
+pub struct FaucetRequest {
+    pub account: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FaucetResponse {
+    pub account: String,
+    pub status: String,
+    pub message: String,
+}
+
+/// Testnet-only faucet: auto-verifies a wallet and credits test TRST.
+///
+/// Errors: `invalid_request` off the test networks.
+pub async fn handle_faucet(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    if !state.enable_faucet {
+        return Err(RpcError::InvalidRequest(
+            "faucet is disabled on this node".into(),
+        ));
+    }
+
+    let req: FaucetRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    validate_account(&req.account)?;
+
+    let address = WalletAddress::new(req.account.clone());
+    let now = node_now(state);
+
+    let mut account_info = state
+        .account_store
+        .get_account(&address)
+        .unwrap_or_else(|_| AccountInfo {
+            address: address.clone(),
+            state: burst_types::WalletState::Unverified,
+            verified_at: None,
+            head: BlockHash::ZERO,
+            representative: address.clone(),
+            block_count: 0,
+            confirmation_height: 0,
+            total_brn_burned: 0,
+            trst_balance: 0,
+            total_brn_staked: 0,
+            expired_trst: 0,
+            revoked_trst: 0,
+            epoch: 0,
+        });
+
+    if account_info.state != burst_types::WalletState::Verified {
+        let transition = burst_types::state_machine::validate_transition(
+            account_info.state,
+            burst_types::WalletState::Verified,
+            burst_types::TransitionReason::TestnetFaucet,
+            now,
+        )
+        .map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+        state
+            .account_store
+            .put_state_transition(&address, &transition)?;
+        account_info.state = burst_types::WalletState::Verified;
+        account_info.verified_at = Some(now);
+    }
+    account_info.trst_balance = account_info
+        .trst_balance
+        .saturating_add(1_000_000_000_000_000_000);
+
+    state
+        .account_store
+        .put_account(&account_info)
+        .map_err(|e| RpcError::Store(format!("failed to update faucet account: {e}")))?;
+
+    {
+        let faucet_amount: u128 = 1_000_000_000_000_000_000;
+        let mut cache = state.rep_weight_cache.write().await;
+        cache.add_weight(&account_info.representative, faucet_amount);
+    }
+
+    Ok(to_value(&FaucetResponse {
+        account: req.account,
+        status: "ok".to_string(),
+        message: "Account verified and 1 TRST credited (testnet faucet)".to_string(),
+    }))
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Testnet convenience RPCs (faucet-only)
+//
+// These endpoints construct, sign, and submit blocks server-side so that
+// testnet validation can be performed via simple curl commands without a
+// local wallet. They are gated behind `enable_faucet`.
+// ═══════════════════════════════════════════════════════════════════════
+
+fn require_faucet(state: &RpcState) -> Result<(), RpcError> {
+    if !state.enable_faucet {
+        return Err(RpcError::InvalidRequest(
+            "this endpoint is only available on testnet nodes with faucet enabled".into(),
+        ));
+    }
+    Ok(())
+}
+
+fn parse_private_key(hex_str: &str) -> Result<burst_types::PrivateKey, RpcError> {
+    let bytes = hex::decode(hex_str)
+        .map_err(|e| RpcError::InvalidRequest(format!("invalid private key hex: {e}")))?;
+    if bytes.len() != 32 {
+        return Err(RpcError::InvalidRequest(
+            "private key must be 32 bytes (64 hex chars)".into(),
+        ));
+    }
+    let mut arr = [0u8; 32];
+    arr.copy_from_slice(&bytes);
+    Ok(burst_types::PrivateKey(arr))
+}
+
+/// Build a StateBlock at chain position `sequence`, laid out as `layout`
+/// describes, sign it, and generate PoW.
+#[allow(clippy::too_many_arguments)]
+fn build_and_sign_block(
+    block_type: burst_ledger::BlockType,
+    account: &WalletAddress,
+    previous: BlockHash,
+    sequence: u64,
+    representative: &WalletAddress,
+    brn_balance: u128,
+    trst_balance: u128,
+    link: BlockHash,
+    origin: TxHash,
+    transaction: TxHash,
+    private_key: &burst_types::PrivateKey,
+    work_generator: &burst_work::WorkGenerator,
+    work_thresholds: &burst_work::WorkThresholds,
+    params_hash: BlockHash,
+    layout: burst_ledger::BlockLayout,
+) -> Result<burst_ledger::StateBlock, RpcError> {
+    let now = Timestamp::now();
+
+    let mut block = burst_ledger::StateBlock {
+        version: burst_ledger::CURRENT_BLOCK_VERSION,
+        block_type,
+        account: account.clone(),
+        previous,
+        representative: representative.clone(),
+        brn_balance,
+        trst_balance,
+        link,
+        origin,
+        transaction,
+        timestamp: now,
+        params_hash,
+        memo: None,
+        htlc: None,
+        reject_reason: None,
+        recovery: None,
+        delegation_key: None,
+        extensions: Vec::new(),
+        work: 0,
+        signature: Signature([0u8; 64]),
+        hash: BlockHash::ZERO,
+    };
+
+    block.apply_layout(layout, sequence);
+    block.signature = burst_crypto::sign_message(block.hash.as_bytes(), private_key);
+
+    let threshold = work_thresholds.threshold_for(block.block_type.work_kind());
+    let nonce = work_generator
+        .generate(&block.work_root(), threshold)
+        .map_err(|e| RpcError::Server(format!("work generation failed: {e}")))?;
+    block.work = nonce.0;
+
+    Ok(block)
+}
+
+/// Submit a block through the block processor and return whether it was accepted.
+fn submit_block(block: &burst_ledger::StateBlock, state: &RpcState) -> Result<bool, RpcError> {
+    let block_bytes = bincode::serialize(block)
+        .map_err(|e| RpcError::Server(format!("block serialization failed: {e}")))?;
+    let result = state.block_processor.process_block(&block_bytes)?;
+    let accepted = matches!(
+        result,
+        crate::server::ProcessResult::Accepted | crate::server::ProcessResult::Queued
+    );
+    if !accepted {
+        let detail = match &result {
+            crate::server::ProcessResult::Rejected(r) => r.clone(),
+            crate::server::ProcessResult::Duplicate => "duplicate block".to_string(),
+            crate::server::ProcessResult::Fork => "fork detected".to_string(),
+            crate::server::ProcessResult::Gap => "gap — previous block unknown".to_string(),
+            _ => "unknown".to_string(),
+        };
+        return Err(RpcError::Server(format!("block not accepted: {detail}")));
+    }
+    Ok(true)
+}
+
+// ── wallet_create_full (testnet-only, returns private key) ──────────
+
+#[derive(Debug, Deserialize)]
+pub struct WalletCreateFullRequest {
+    #[serde(default)]
+    pub seed: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WalletCreateFullResponse {
+    pub address: String,
+    pub public_key: String,
+    pub private_key: String,
+}
+
+/// Errors: `invalid_request` without the faucet.
+pub async fn handle_wallet_create_full(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    require_faucet(state)?;
+
+    let _req: WalletCreateFullRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+
+    let kp = burst_crypto::generate_keypair();
+    let address = burst_crypto::derive_address(&kp.public);
+
+    Ok(to_value(&WalletCreateFullResponse {
+        address: address.to_string(),
+        public_key: hex::encode(kp.public.0),
+        private_key: hex::encode(kp.private.0),
+    }))
+}
+
+// ── burn_simple (BRN → TRST) ────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct BurnSimpleRequest {
+    pub private_key: String,
+    pub amount: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BurnSimpleResponse {
+    pub block_hash: String,
+    pub account: String,
+    pub brn_before: String,
+    pub brn_after: String,
+    pub trst_before: String,
+    pub trst_after: String,
+    pub origin: String,
+}
+
+/// Errors: `account_not_found`, `wallet_not_verified`, `insufficient_brn`.
+pub async fn handle_burn_simple(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    require_faucet(state)?;
+
+    let req: BurnSimpleRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let amount = validate_positive_amount(&req.amount)?;
+    let private_key = parse_private_key(&req.private_key)?;
+    let public_key = burst_crypto::public_from_private(&private_key);
+    let address = burst_crypto::derive_address(&public_key);
+
+    let account = state
+        .account_store
+        .get_account(&address)
+        .map_err(|e| account_not_found(e, address.as_str()))?;
+
+    if account.state != burst_types::WalletState::Verified {
+        return Err(RpcError::coded(
+            ErrorCode::WalletNotVerified,
+            "account must be verified to burn BRN (use faucet first)",
+        ));
+    }
+
+    let now = node_now(state);
+    let brn_state = brn_state_from_account(&account, state.params.brn_rate);
+    let brn_balance = {
+        let brn = state.brn_engine.lock().await;
+        brn.compute_balance(&brn_state, now)
+    };
+
+    if brn_balance < amount {
+        return Err(RpcError::insufficient_brn(amount, brn_balance));
+    }
+
+    let brn_after = brn_balance - amount;
+    let trst_before = account.trst_balance;
+    let trst_after = trst_before + amount;
+
+    let tx_hash = TxHash::new(burst_crypto::blake2b_256(
+        &[
+            address.as_str().as_bytes(),
+            &now.as_secs().to_be_bytes(),
+            b"burn",
+        ]
+        .concat(),
+    ));
+
+    let is_first_block = account.head == BlockHash::ZERO;
+    let block_type = if is_first_block {
+        burst_ledger::BlockType::Open
+    } else {
+        burst_ledger::BlockType::Burn
+    };
+
+    let pk_bytes = private_key.0;
+    let block = tokio::task::spawn_blocking({
+        let address = address.clone();
+        let representative = account.representative.clone();
+        let previous = account.head;
+        let sequence = account.block_count + 1;
+        let work_gen = state.work_generator.clone();
+        let thresholds = burst_work::WorkThresholds::from_params(&state.params);
+        let ph = current_params_hash(state);
+        let layout = block_layout(state, &account);
+        move || {
+            let pk = burst_types::PrivateKey(pk_bytes);
+            build_and_sign_block(
+                block_type,
+                &address,
+                previous,
+                sequence,
+                &representative,
+                brn_after,
+                trst_after,
+                BlockHash::ZERO,
+                tx_hash,
+                tx_hash,
+                &pk,
+                &work_gen,
+                &thresholds,
+                ph,
+                layout,
+            )
+        }
+    })
+    .await
+    .map_err(|e| RpcError::Server(format!("block build task failed: {e}")))??;
+
+    submit_block(&block, state)?;
+
+    let mut updated = account.clone();
+    updated.head = block.hash;
+    updated.block_count += 1;
+    updated.total_brn_burned += amount;
+    updated.trst_balance = trst_after;
+
+    state
+        .account_store
+        .put_account(&updated)
+        .map_err(|e| RpcError::Store(format!("failed to update account: {e}")))?;
+
+    let block_bytes = bincode::serialize(&block)
+        .map_err(|e| RpcError::Server(format!("block serialization failed: {e}")))?;
+    let _ = state
+        .block_store
+        .put_block_with_account(&block.hash, &block_bytes, &address);
+
+    {
+        let mut cache = state.rep_weight_cache.write().await;
+        cache.add_weight(&account.representative, amount);
+    }
+
+    Ok(to_value(&BurnSimpleResponse {
+        block_hash: format!("{}", block.hash),
+        account: address.to_string(),
+        brn_before: brn_balance.to_string(),
+        brn_after: brn_after.to_string(),
+        trst_before: trst_before.to_string(),
+        trst_after: trst_after.to_string(),
+        origin: format!("{}", tx_hash),
+    }))
+}
+
+// ── send_simple (TRST transfer) ─────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct SendSimpleRequest {
+    pub private_key: String,
+    pub destination: String,
+    pub amount: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SendSimpleResponse {
+    pub block_hash: String,
+    pub source: String,
+    pub destination: String,
+    pub amount: String,
+    pub trst_before: String,
+    pub trst_after: String,
+}
+
+/// Errors: `account_not_found`, `wallet_not_verified`, `insufficient_trst`.
+pub async fn handle_send_simple(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    require_faucet(state)?;
+
+    let req: SendSimpleRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    validate_account(&req.destination)?;
+    let amount = validate_positive_amount(&req.amount)?;
+    let private_key = parse_private_key(&req.private_key)?;
+    let public_key = burst_crypto::public_from_private(&private_key);
+    let address = burst_crypto::derive_address(&public_key);
+
+    let account = state
+        .account_store
+        .get_account(&address)
+        .map_err(|e| account_not_found(e, address.as_str()))?;
+
+    if account.state != burst_types::WalletState::Verified {
+        return Err(RpcError::coded(
+            ErrorCode::WalletNotVerified,
+            "account must be verified to send TRST",
+        ));
+    }
+
+    if account.trst_balance < amount {
+        return Err(RpcError::insufficient_trst(amount, account.trst_balance));
+    }
+
+    let trst_before = account.trst_balance;
+    let trst_after = trst_before - amount;
+    let destination = WalletAddress::new(req.destination.clone());
+
+    let now = node_now(state);
+    let brn_state = brn_state_from_account(&account, state.params.brn_rate);
+    let brn_balance = {
+        let brn = state.brn_engine.lock().await;
+        brn.compute_balance(&brn_state, now)
+    };
+
+    let dest_hash = BlockHash::new(burst_crypto::blake2b_256(destination.as_str().as_bytes()));
+    let tx_hash = TxHash::new(burst_crypto::blake2b_256(
+        &[
+            address.as_str().as_bytes(),
+            &now.as_secs().to_be_bytes(),
+            b"send",
+        ]
+        .concat(),
+    ));
+
+    let is_first_block = account.head == BlockHash::ZERO;
+    let block_type = if is_first_block {
+        burst_ledger::BlockType::Open
+    } else {
+        burst_ledger::BlockType::Send
+    };
+
+    let pk_bytes = private_key.0;
+    let block = tokio::task::spawn_blocking({
+        let address = address.clone();
+        let representative = account.representative.clone();
+        let previous = account.head;
+        let sequence = account.block_count + 1;
+        let work_gen = state.work_generator.clone();
+        let thresholds = burst_work::WorkThresholds::from_params(&state.params);
+        let ph = current_params_hash(state);
+        let layout = block_layout(state, &account);
+        move || {
+            let pk = burst_types::PrivateKey(pk_bytes);
+            build_and_sign_block(
+                block_type,
+                &address,
+                previous,
+                sequence,
+                &representative,
+                brn_balance,
+                trst_after,
+                dest_hash,
+                TxHash::ZERO,
+                tx_hash,
+                &pk,
+                &work_gen,
+                &thresholds,
+                ph,
+                layout,
+            )
+        }
+    })
+    .await
+    .map_err(|e| RpcError::Server(format!("block build task failed: {e}")))??;
+
+    submit_block(&block, state)?;
+
+    let mut updated = account.clone();
+    updated.head = block.hash;
+    updated.block_count += 1;
+    updated.trst_balance = trst_after;
+
+    state
+        .account_store
+        .put_account(&updated)
+        .map_err(|e| RpcError::Store(format!("failed to update account: {e}")))?;
+
+    let block_bytes = bincode::serialize(&block)
+        .map_err(|e| RpcError::Server(format!("block serialization failed: {e}")))?;
+    let _ = state
+        .block_store
+        .put_block_with_account(&block.hash, &block_bytes, &address);
+
+    state
+        .pending_store
+        .put_pending(
+            &destination,
+            &tx_hash,
+            &burst_store::pending::PendingInfo {
+                source: address.clone(),
+                amount,
+                timestamp: now,
+                provenance: vec![],
+                memo: None,
+                destination_tag: None,
+                htlc: None,
+                returned: false,
+            },
+        )
+        .map_err(|e| RpcError::Store(format!("failed to create pending: {e}")))?;
+
+    {
+        let mut cache = state.rep_weight_cache.write().await;
+        cache.remove_weight(&account.representative, amount);
+    }
+
+    Ok(to_value(&SendSimpleResponse {
+        block_hash: format!("{}", block.hash),
+        source: address.to_string(),
+        destination: req.destination,
+        amount: req.amount,
+        trst_before: trst_before.to_string(),
+        trst_after: trst_after.to_string(),
+    }))
+}
+
+// ── receive_simple (pocket a pending TRST transfer) ──────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct ReceiveSimpleRequest {
+    pub private_key: String,
+    pub send_block_hash: String,
+}
+
 #[derive(Debug, Serialize)]
-pub struct BurnSimpleResponse {
+pub struct ReceiveSimpleResponse {
     pub block_hash: String,
     pub account: String,
-    pub brn_before: String,
-    pub brn_after: String,
+    pub amount: String,
     pub trst_before: String,
     pub trst_after: String,
-    pub origin: String,
 }
 
-pub async fn handle_burn_simple(
+/// Errors: `invalid_request` without the faucet or the pending transfer;
+/// `internal` if the node turns the receive block away.
+pub async fn handle_receive_simple(
     params: serde_json::Value,
     state: &RpcState,
 ) -> Result<serde_json::Value, RpcError> {
     require_faucet(state)?;
 
-    let req: BurnSimpleRequest =
+    let req: ReceiveSimpleRequest =
         serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
-    let amount = validate_positive_amount(&req.amount)?;
     let private_key = parse_private_key(&req.private_key)?;
     let public_key = burst_crypto::public_from_private(&private_key);
     let address = burst_crypto::derive_address(&public_key);
 
+    let send_tx_hash = parse_tx_hash(&req.send_block_hash)?;
+
+    let pending = state
+        .pending_store
+        .get_pending(&address, &send_tx_hash)
+        .map_err(|e| {
+            RpcError::InvalidRequest(format!(
+                "no pending receive found for hash {}: {e}",
+                req.send_block_hash
+            ))
+        })?;
+
     let account = state
         .account_store
         .get_account(&address)
-        .map_err(|e| account_not_found(e, address.as_str()))?;
+        .unwrap_or_else(|_| AccountInfo {
+            address: address.clone(),
+            state: burst_types::WalletState::Unverified,
+            verified_at: None,
+            head: BlockHash::ZERO,
+            representative: address.clone(),
+            block_count: 0,
+            confirmation_height: 0,
+            total_brn_burned: 0,
+            trst_balance: 0,
+            total_brn_staked: 0,
+            expired_trst: 0,
+            revoked_trst: 0,
+            epoch: 0,
+        });
 
-    if account.state != burst_types::WalletState::Verified {
-        return Err(RpcError::InvalidRequest(
-            "account must be verified to burn BRN (use faucet first)".into(),
-        ));
-    }
+    let trst_before = account.trst_balance;
+    let trst_after = trst_before + pending.amount;
 
-    let now = Timestamp::now();
+    let now = node_now(state);
     let brn_state = brn_state_from_account(&account, state.params.brn_rate);
     let brn_balance = {
         let brn = state.brn_engine.lock().await;
         brn.compute_balance(&brn_state, now)
     };
 
-    if brn_balance < amount {
-        return Err(RpcError::InvalidRequest(format!(
-            "insufficient BRN: have {brn_balance}, need {amount}"
-        )));
-    }
-
-    let brn_after = brn_balance - amount;
-    let trst_before = account.trst_balance;
-    let trst_after = trst_before + amount;
+    let is_open = account.head == BlockHash::ZERO;
+    let block_type = if is_open {
+        burst_ledger::BlockType::Open
+    } else {
+        burst_ledger::BlockType::Receive
+    };
 
+    let link = BlockHash::new(*send_tx_hash.as_bytes());
     let tx_hash = TxHash::new(burst_crypto::blake2b_256(
         &[
             address.as_str().as_bytes(),
             &now.as_secs().to_be_bytes(),
-            b"burn",
+            b"receive",
         ]
         .concat(),
     ));
 
-    let is_first_block = account.head == BlockHash::ZERO;
-    let block_type = if is_first_block {
-        burst_ledger::BlockType::Open
-    } else {
-        burst_ledger::BlockType::Burn
-    };
-
     let pk_bytes = private_key.0;
     let block = tokio::task::spawn_blocking({
         let address = address.clone();
         let representative = account.representative.clone();
         let previous = account.head;
+        let sequence = account.block_count + 1;
         let work_gen = state.work_generator.clone();
-        let min_diff = state.params.min_work_difficulty;
-        let ph = state.params.params_hash();
+        let thresholds = burst_work::WorkThresholds::from_params(&state.params);
+        let ph = current_params_hash(state);
+        let layout = block_layout(state, &account);
         move || {
             let pk = burst_types::PrivateKey(pk_bytes);
             build_and_sign_block(
                 block_type,
                 &address,
                 previous,
+                sequence,
                 &representative,
-                brn_after,
+                brn_balance,
                 trst_after,
-                BlockHash::ZERO,
-                tx_hash,
+                link,
+                TxHash::ZERO,
                 tx_hash,
                 &pk,
                 &work_gen,
-                min_diff,
+                &thresholds,
                 ph,
+                layout,
             )
         }
     })
@@ -1824,7 +5066,6 @@ pub async fn handle_burn_simple(
     let mut updated = account.clone();
     updated.head = block.hash;
     updated.block_count += 1;
-    updated.total_brn_burned += amount;
     updated.trst_balance = trst_after;
 
     state
@@ -1838,51 +5079,52 @@ pub async fn handle_burn_simple(
         .block_store
         .put_block_with_account(&block.hash, &block_bytes, &address);
 
+    state
+        .pending_store
+        .delete_pending(&address, &send_tx_hash)
+        .map_err(|e| RpcError::Store(format!("failed to delete pending: {e}")))?;
+
     {
         let mut cache = state.rep_weight_cache.write().await;
-        cache.add_weight(&account.representative, amount);
+        cache.add_weight(&account.representative, pending.amount);
     }
 
-    Ok(to_value(&BurnSimpleResponse {
+    Ok(to_value(&ReceiveSimpleResponse {
         block_hash: format!("{}", block.hash),
         account: address.to_string(),
-        brn_before: brn_balance.to_string(),
-        brn_after: brn_after.to_string(),
+        amount: pending.amount.to_string(),
         trst_before: trst_before.to_string(),
         trst_after: trst_after.to_string(),
-        origin: format!("{}", tx_hash),
     }))
 }
 
-// ── send_simple (TRST transfer) ─────────────────────────────────────
+// ── change_rep_simple (change consensus representative) ──────────────
 
 #[derive(Debug, Deserialize)]
-pub struct SendSimpleRequest {
+pub struct ChangeRepSimpleRequest {
     pub private_key: String,
-    pub destination: String,
-    pub amount: String,
+    pub new_representative: String,
 }
 
 #[derive(Debug, Serialize)]
-pub struct SendSimpleResponse {
+pub struct ChangeRepSimpleResponse {
     pub block_hash: String,
-    pub source: String,
-    pub destination: String,
-    pub amount: String,
-    pub trst_before: String,
-    pub trst_after: String,
+    pub account: String,
+    pub old_representative: String,
+    pub new_representative: String,
 }
 
-pub async fn handle_send_simple(
+/// Errors: `invalid_request` without the faucet; `account_not_found`;
+/// `internal` if the node turns the block away.
+pub async fn handle_change_rep_simple(
     params: serde_json::Value,
     state: &RpcState,
 ) -> Result<serde_json::Value, RpcError> {
     require_faucet(state)?;
 
-    let req: SendSimpleRequest =
+    let req: ChangeRepSimpleRequest =
         serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
-    validate_account(&req.destination)?;
-    let amount = validate_positive_amount(&req.amount)?;
+    validate_account(&req.new_representative)?;
     let private_key = parse_private_key(&req.private_key)?;
     let public_key = burst_crypto::public_from_private(&private_key);
     let address = burst_crypto::derive_address(&public_key);
@@ -1892,71 +5134,66 @@ pub async fn handle_send_simple(
         .get_account(&address)
         .map_err(|e| account_not_found(e, address.as_str()))?;
 
-    if account.state != burst_types::WalletState::Verified {
+    if account.head == BlockHash::ZERO {
         return Err(RpcError::InvalidRequest(
-            "account must be verified to send TRST".into(),
+            "account has no blocks yet — send or receive first".into(),
         ));
     }
 
-    if account.trst_balance < amount {
-        return Err(RpcError::InvalidRequest(format!(
-            "insufficient TRST: have {}, need {amount}",
-            account.trst_balance
-        )));
-    }
+    let old_representative = account.representative.clone();
+    let new_representative = WalletAddress::new(req.new_representative.clone());
 
-    let trst_before = account.trst_balance;
-    let trst_after = trst_before - amount;
-    let destination = WalletAddress::new(req.destination.clone());
+    if old_representative == new_representative {
+        return Err(RpcError::InvalidRequest(
+            "new representative is the same as current".into(),
+        ));
+    }
 
-    let now = Timestamp::now();
+    let now = node_now(state);
     let brn_state = brn_state_from_account(&account, state.params.brn_rate);
     let brn_balance = {
         let brn = state.brn_engine.lock().await;
         brn.compute_balance(&brn_state, now)
     };
 
-    let dest_hash = BlockHash::new(burst_crypto::blake2b_256(destination.as_str().as_bytes()));
     let tx_hash = TxHash::new(burst_crypto::blake2b_256(
         &[
             address.as_str().as_bytes(),
             &now.as_secs().to_be_bytes(),
-            b"send",
+            b"change_rep",
         ]
         .concat(),
     ));
 
-    let is_first_block = account.head == BlockHash::ZERO;
-    let block_type = if is_first_block {
-        burst_ledger::BlockType::Open
-    } else {
-        burst_ledger::BlockType::Send
-    };
-
     let pk_bytes = private_key.0;
     let block = tokio::task::spawn_blocking({
         let address = address.clone();
-        let representative = account.representative.clone();
+        let new_rep = new_representative.clone();
         let previous = account.head;
+        let sequence = account.block_count + 1;
+        let trst_balance = account.trst_balance;
         let work_gen = state.work_generator.clone();
-        let min_diff = state.params.min_work_difficulty;
-        let ph = state.params.params_hash();
+        let thresholds = burst_work::WorkThresholds::from_params(&state.params);
+        let ph = current_params_hash(state);
+        let layout = block_layout(state, &account);
         move || {
             let pk = burst_types::PrivateKey(pk_bytes);
             build_and_sign_block(
-                block_type,
+                burst_ledger::BlockType::ChangeRepresentative,
                 &address,
                 previous,
-                &representative,
+                sequence,
+                &new_rep,
                 brn_balance,
-                trst_after,
-                dest_hash,
+                trst_balance,
+                BlockHash::ZERO,
                 TxHash::ZERO,
                 tx_hash,
                 &pk,
                 &work_gen,
-                min_diff,
+                &thresholds,
                 ph,
+                layout,
             )
         }
     })
@@ -1968,7 +5205,7 @@ pub async fn handle_send_simple(
     let mut updated = account.clone();
     updated.head = block.hash;
     updated.block_count += 1;
-    updated.trst_balance = trst_after;
+    updated.representative = new_representative.clone();
 
     state
         .account_store
@@ -1981,118 +5218,116 @@ pub async fn handle_send_simple(
         .block_store
         .put_block_with_account(&block.hash, &block_bytes, &address);
 
-    state
-        .pending_store
-        .put_pending(
-            &destination,
-            &tx_hash,
-            &burst_store::pending::PendingInfo {
-                source: address.clone(),
-                amount,
-                timestamp: now,
-                provenance: vec![],
-            },
-        )
-        .map_err(|e| RpcError::Store(format!("failed to create pending: {e}")))?;
-
     {
         let mut cache = state.rep_weight_cache.write().await;
-        cache.remove_weight(&account.representative, amount);
+        cache.change_rep(
+            &old_representative,
+            &new_representative,
+            account.trst_balance,
+        );
     }
 
-    Ok(to_value(&SendSimpleResponse {
+    Ok(to_value(&ChangeRepSimpleResponse {
         block_hash: format!("{}", block.hash),
-        source: address.to_string(),
-        destination: req.destination,
-        amount: req.amount,
-        trst_before: trst_before.to_string(),
-        trst_after: trst_after.to_string(),
+        account: address.to_string(),
+        old_representative: old_representative.to_string(),
+        new_representative: req.new_representative,
     }))
 }
 
-// ── receive_simple (pocket a pending TRST transfer) ──────────────────
+// ── endorse_simple / challenge_simple (spend BRN on another wallet) ──
 
 #[derive(Debug, Deserialize)]
-pub struct ReceiveSimpleRequest {
+pub struct BrnTargetSimpleRequest {
     pub private_key: String,
-    pub send_block_hash: String,
+    /// The wallet being endorsed or challenged.
+    pub target: String,
+    /// BRN burned by an endorsement or staked by a challenge.
+    pub amount: String,
 }
 
 #[derive(Debug, Serialize)]
-pub struct ReceiveSimpleResponse {
+pub struct BrnTargetSimpleResponse {
     pub block_hash: String,
     pub account: String,
+    pub target: String,
     pub amount: String,
-    pub trst_before: String,
-    pub trst_after: String,
+    pub brn_before: String,
+    pub brn_after: String,
 }
 
-pub async fn handle_receive_simple(
+/// Endorse a wallet by burning BRN.
+///
+/// Errors: `account_not_found`, `wallet_not_verified`, `insufficient_brn`.
+pub async fn handle_endorse_simple(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    brn_target_simple(params, state, burst_ledger::BlockType::Endorse).await
+}
+
+/// Challenge a wallet's verification by staking BRN.
+///
+/// Errors: `account_not_found`, `wallet_not_verified`, `insufficient_brn`.
+pub async fn handle_challenge_simple(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    brn_target_simple(params, state, burst_ledger::BlockType::Challenge).await
+}
+
+async fn brn_target_simple(
     params: serde_json::Value,
     state: &RpcState,
+    block_type: burst_ledger::BlockType,
 ) -> Result<serde_json::Value, RpcError> {
     require_faucet(state)?;
 
-    let req: ReceiveSimpleRequest =
+    let req: BrnTargetSimpleRequest =
         serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    validate_account(&req.target)?;
+    let amount = validate_positive_amount(&req.amount)?;
     let private_key = parse_private_key(&req.private_key)?;
     let public_key = burst_crypto::public_from_private(&private_key);
     let address = burst_crypto::derive_address(&public_key);
 
-    let send_tx_hash = parse_tx_hash(&req.send_block_hash)?;
-
-    let pending = state
-        .pending_store
-        .get_pending(&address, &send_tx_hash)
-        .map_err(|e| {
-            RpcError::InvalidRequest(format!(
-                "no pending receive found for hash {}: {e}",
-                req.send_block_hash
-            ))
-        })?;
+    let target = burst_crypto::decode_address(&req.target)
+        .map(BlockHash::new)
+        .ok_or_else(|| RpcError::InvalidRequest(format!("invalid target: {}", req.target)))?;
 
     let account = state
         .account_store
         .get_account(&address)
-        .unwrap_or_else(|_| AccountInfo {
-            address: address.clone(),
-            state: burst_types::WalletState::Unverified,
-            verified_at: None,
-            head: BlockHash::ZERO,
-            representative: address.clone(),
-            block_count: 0,
-            confirmation_height: 0,
-            total_brn_burned: 0,
-            trst_balance: 0,
-            total_brn_staked: 0,
-            expired_trst: 0,
-            revoked_trst: 0,
-            epoch: 0,
-        });
+        .map_err(|e| account_not_found(e, address.as_str()))?;
 
-    let trst_before = account.trst_balance;
-    let trst_after = trst_before + pending.amount;
+    if account.state != burst_types::WalletState::Verified {
+        return Err(RpcError::coded(
+            ErrorCode::WalletNotVerified,
+            "account must be verified to spend BRN (use faucet first)",
+        ));
+    }
+    if account.head == BlockHash::ZERO {
+        return Err(RpcError::InvalidRequest(
+            "account has no blocks yet — burn BRN first".into(),
+        ));
+    }
 
-    let now = Timestamp::now();
+    let now = node_now(state);
     let brn_state = brn_state_from_account(&account, state.params.brn_rate);
     let brn_balance = {
         let brn = state.brn_engine.lock().await;
         brn.compute_balance(&brn_state, now)
     };
+    if brn_balance < amount {
+        return Err(RpcError::insufficient_brn(amount, brn_balance));
+    }
+    let brn_after = brn_balance - amount;
 
-    let is_open = account.head == BlockHash::ZERO;
-    let block_type = if is_open {
-        burst_ledger::BlockType::Open
-    } else {
-        burst_ledger::BlockType::Receive
-    };
-
-    let link = BlockHash::new(*send_tx_hash.as_bytes());
     let tx_hash = TxHash::new(burst_crypto::blake2b_256(
         &[
             address.as_str().as_bytes(),
             &now.as_secs().to_be_bytes(),
-            b"receive",
+            format!("{block_type:?}").as_bytes(),
         ]
         .concat(),
     ));
@@ -2101,26 +5336,32 @@ pub async fn handle_receive_simple(
     let block = tokio::task::spawn_blocking({
         let address = address.clone();
         let representative = account.representative.clone();
+        let block_type = block_type.clone();
         let previous = account.head;
+        let sequence = account.block_count + 1;
+        let trst_balance = account.trst_balance;
         let work_gen = state.work_generator.clone();
-        let min_diff = state.params.min_work_difficulty;
-        let ph = state.params.params_hash();
+        let thresholds = burst_work::WorkThresholds::from_params(&state.params);
+        let ph = current_params_hash(state);
+        let layout = block_layout(state, &account);
         move || {
             let pk = burst_types::PrivateKey(pk_bytes);
             build_and_sign_block(
                 block_type,
                 &address,
                 previous,
+                sequence,
                 &representative,
-                brn_balance,
-                trst_after,
-                link,
+                brn_after,
+                trst_balance,
+                target,
                 TxHash::ZERO,
                 tx_hash,
                 &pk,
                 &work_gen,
-                min_diff,
+                &thresholds,
                 ph,
+                layout,
             )
         }
     })
@@ -2132,7 +5373,11 @@ pub async fn handle_receive_simple(
     let mut updated = account.clone();
     updated.head = block.hash;
     updated.block_count += 1;
-    updated.trst_balance = trst_after;
+    if block_type == burst_ledger::BlockType::Challenge {
+        updated.total_brn_staked += amount;
+    } else {
+        updated.total_brn_burned += amount;
+    }
 
     state
         .account_store
@@ -2145,51 +5390,130 @@ pub async fn handle_receive_simple(
         .block_store
         .put_block_with_account(&block.hash, &block_bytes, &address);
 
-    state
-        .pending_store
-        .delete_pending(&address, &send_tx_hash)
-        .map_err(|e| RpcError::Store(format!("failed to delete pending: {e}")))?;
-
-    {
-        let mut cache = state.rep_weight_cache.write().await;
-        cache.add_weight(&account.representative, pending.amount);
-    }
-
-    Ok(to_value(&ReceiveSimpleResponse {
+    Ok(to_value(&BrnTargetSimpleResponse {
         block_hash: format!("{}", block.hash),
         account: address.to_string(),
-        amount: pending.amount.to_string(),
-        trst_before: trst_before.to_string(),
-        trst_after: trst_after.to_string(),
+        target: req.target,
+        amount: req.amount,
+        brn_before: brn_balance.to_string(),
+        brn_after: brn_after.to_string(),
     }))
 }
 
-// ── change_rep_simple (change consensus representative) ──────────────
+// ── governance_propose_simple (create a parameter change proposal) ────
 
 #[derive(Debug, Deserialize)]
-pub struct ChangeRepSimpleRequest {
+pub struct GovernanceProposeSimpleRequest {
     pub private_key: String,
-    pub new_representative: String,
+    pub param: String,
+    pub new_value: String,
+    /// `"open"` (default) or `"sealed"` for commit-reveal ballots.
+    #[serde(default)]
+    pub ballot: Option<String>,
+    /// Hash of a proposal that must activate first.
+    #[serde(default)]
+    pub requires: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
-pub struct ChangeRepSimpleResponse {
+pub struct GovernanceProposeSimpleResponse {
     pub block_hash: String,
+    pub proposal_hash: String,
     pub account: String,
-    pub old_representative: String,
-    pub new_representative: String,
+    pub param: String,
+    pub new_value: String,
+    pub ballot: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requires: Option<String>,
 }
 
-pub async fn handle_change_rep_simple(
+/// Errors: `invalid_request` without the faucet; `account_not_found`;
+/// `proposal_not_found` for an unknown `requires`; `internal` if the node
+/// turns the proposal block away.
+pub async fn handle_governance_propose_simple(
     params: serde_json::Value,
     state: &RpcState,
 ) -> Result<serde_json::Value, RpcError> {
     require_faucet(state)?;
 
-    let req: ChangeRepSimpleRequest =
+    let req: GovernanceProposeSimpleRequest =
         serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
-    validate_account(&req.new_representative)?;
     let private_key = parse_private_key(&req.private_key)?;
+
+    let param = parse_governable_param(&req.param)?;
+    let new_value: u128 = req
+        .new_value
+        .parse()
+        .map_err(|e| RpcError::InvalidRequest(format!("invalid new_value: {e}")))?;
+    param
+        .validate(new_value)
+        .map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let ballot = parse_ballot_mode(req.ballot.as_deref().unwrap_or("open"))?;
+    let requires = req.requires.as_deref().map(parse_tx_hash).transpose()?;
+    if let (Some(dependency), Some(ref engine)) = (requires, &state.governance_engine) {
+        if engine.lock().await.get_proposal(&dependency).is_none() {
+            return Err(RpcError::ProposalNotFound(dependency.to_string()));
+        }
+    }
+
+    let content = burst_governance::ProposalContent::ParameterChange {
+        param: param.clone(),
+        new_value,
+    };
+    let content_bytes = bincode::serialize(&content)
+        .map_err(|e| RpcError::Server(format!("failed to serialize proposal content: {e}")))?;
+    if content_bytes.len() > 32 {
+        return Err(RpcError::InvalidRequest(
+            "proposal content too large for link field (max 32 bytes)".into(),
+        ));
+    }
+    let mut link_bytes = [0u8; 32];
+    link_bytes[..content_bytes.len()].copy_from_slice(&content_bytes);
+    if requires.is_some() {
+        mark_proposal_dependency(&mut link_bytes);
+    }
+
+    let submitted = submit_proposal_block(
+        state,
+        private_key,
+        content,
+        link_bytes,
+        requires.unwrap_or(TxHash::ZERO),
+        ballot,
+        requires,
+    )
+    .await?;
+
+    Ok(to_value(&GovernanceProposeSimpleResponse {
+        block_hash: submitted.block_hash.to_string(),
+        proposal_hash: submitted.proposal_hash.to_string(),
+        account: submitted.account.to_string(),
+        param: req.param,
+        new_value: req.new_value,
+        ballot: format_ballot_mode(ballot).to_string(),
+        requires: req.requires,
+    }))
+}
+
+/// A GovernanceProposal block built and submitted by a `_simple` handler.
+struct SubmittedProposal {
+    block_hash: BlockHash,
+    proposal_hash: TxHash,
+    account: WalletAddress,
+}
+
+/// Build, sign and submit a GovernanceProposal block from the key's
+/// account, then register the proposal with the governance engine.
+/// `link` carries the encoded content; the ballot flag is marked here.
+async fn submit_proposal_block(
+    state: &RpcState,
+    private_key: burst_types::PrivateKey,
+    content: burst_governance::ProposalContent,
+    mut link: [u8; 32],
+    origin: TxHash,
+    ballot: BallotMode,
+    requires: Option<TxHash>,
+) -> Result<SubmittedProposal, RpcError> {
     let public_key = burst_crypto::public_from_private(&private_key);
     let address = burst_crypto::derive_address(&public_key);
 
@@ -2200,20 +5524,14 @@ pub async fn handle_change_rep_simple(
 
     if account.head == BlockHash::ZERO {
         return Err(RpcError::InvalidRequest(
-            "account has no blocks yet — send or receive first".into(),
+            "account has no blocks yet — burn BRN first".into(),
         ));
     }
 
-    let old_representative = account.representative.clone();
-    let new_representative = WalletAddress::new(req.new_representative.clone());
-
-    if old_representative == new_representative {
-        return Err(RpcError::InvalidRequest(
-            "new representative is the same as current".into(),
-        ));
-    }
+    ballot.mark_proposal_link(&mut link);
+    let link = BlockHash::new(link);
 
-    let now = Timestamp::now();
+    let now = node_now(state);
     let brn_state = brn_state_from_account(&account, state.params.brn_rate);
     let brn_balance = {
         let brn = state.brn_engine.lock().await;
@@ -2224,7 +5542,7 @@ pub async fn handle_change_rep_simple(
         &[
             address.as_str().as_bytes(),
             &now.as_secs().to_be_bytes(),
-            b"change_rep",
+            b"governance_propose",
         ]
         .concat(),
     ));
@@ -2232,28 +5550,32 @@ pub async fn handle_change_rep_simple(
     let pk_bytes = private_key.0;
     let block = tokio::task::spawn_blocking({
         let address = address.clone();
-        let new_rep = new_representative.clone();
+        let representative = account.representative.clone();
         let previous = account.head;
+        let sequence = account.block_count + 1;
         let trst_balance = account.trst_balance;
         let work_gen = state.work_generator.clone();
-        let min_diff = state.params.min_work_difficulty;
-        let ph = state.params.params_hash();
+        let thresholds = burst_work::WorkThresholds::from_params(&state.params);
+        let ph = current_params_hash(state);
+        let layout = block_layout(state, &account);
         move || {
             let pk = burst_types::PrivateKey(pk_bytes);
             build_and_sign_block(
-                burst_ledger::BlockType::ChangeRepresentative,
+                burst_ledger::BlockType::GovernanceProposal,
                 &address,
                 previous,
-                &new_rep,
+                sequence,
+                &representative,
                 brn_balance,
                 trst_balance,
-                BlockHash::ZERO,
-                TxHash::ZERO,
+                link,
+                origin,
                 tx_hash,
                 &pk,
                 &work_gen,
-                min_diff,
+                &thresholds,
                 ph,
+                layout,
             )
         }
     })
@@ -2265,7 +5587,6 @@ pub async fn handle_change_rep_simple(
     let mut updated = account.clone();
     updated.head = block.hash;
     updated.block_count += 1;
-    updated.representative = new_representative.clone();
 
     state
         .account_store
@@ -2278,48 +5599,247 @@ pub async fn handle_change_rep_simple(
         .block_store
         .put_block_with_account(&block.hash, &block_bytes, &address);
 
-    {
-        let mut cache = state.rep_weight_cache.write().await;
-        cache.change_rep(
-            &old_representative,
-            &new_representative,
-            account.trst_balance,
-        );
+    if let Some(ref engine) = state.governance_engine {
+        let proposal = burst_governance::Proposal {
+            hash: tx_hash,
+            proposer: address.clone(),
+            content,
+            phase: burst_governance::GovernancePhase::Proposal,
+            created_at: now,
+            endorsement_count: 0,
+            exploration_votes_yea: 0,
+            exploration_votes_nay: 0,
+            exploration_votes_abstain: 0,
+            promotion_votes_yea: 0,
+            promotion_votes_nay: 0,
+            promotion_votes_abstain: 0,
+            exploration_started_at: None,
+            cooldown_started_at: None,
+            promotion_started_at: None,
+            activation_at: None,
+            total_eligible_voters: 1,
+            round: 0,
+        };
+        let mut gov = engine.lock().await;
+        let required = match requires {
+            Some(dependency) => gov.require(tx_hash, dependency),
+            None => Ok(()),
+        };
+        match required
+            .and_then(|()| gov.submit_proposal(proposal, brn_balance, true, &state.params))
+        {
+            Ok(hash) => {
+                if let Some(ref ballots) = state.governance_ballots {
+                    ballots.lock().await.set_mode(hash, ballot);
+                }
+            }
+            Err(e) => {
+                tracing::warn!("governance proposal accepted as block but engine rejected: {e}");
+            }
+        }
     }
 
-    Ok(to_value(&ChangeRepSimpleResponse {
-        block_hash: format!("{}", block.hash),
-        account: address.to_string(),
-        old_representative: old_representative.to_string(),
-        new_representative: req.new_representative,
-    }))
+    Ok(SubmittedProposal {
+        block_hash: block.hash,
+        proposal_hash: tx_hash,
+        account: address,
+    })
 }
 
-// ── governance_propose_simple (create a parameter change proposal) ────
+// ── governance_grant_simple (propose a treasury grant) ───────────────
 
 #[derive(Debug, Deserialize)]
-pub struct GovernanceProposeSimpleRequest {
+pub struct GovernanceGrantSimpleRequest {
     pub private_key: String,
-    pub param: String,
-    pub new_value: String,
+    pub recipient: String,
+    pub amount: String,
+    /// `"open"` (default) or `"sealed"` for commit-reveal ballots.
+    #[serde(default)]
+    pub ballot: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
-pub struct GovernanceProposeSimpleResponse {
+pub struct GovernanceGrantSimpleResponse {
     pub block_hash: String,
     pub proposal_hash: String,
     pub account: String,
-    pub param: String,
-    pub new_value: String,
+    pub recipient: String,
+    pub amount: String,
+    pub ballot: String,
 }
 
-pub async fn handle_governance_propose_simple(
+/// Errors: `invalid_request` without the faucet; `account_not_found`;
+/// `internal` if the node turns the proposal block away.
+pub async fn handle_governance_grant_simple(
     params: serde_json::Value,
     state: &RpcState,
 ) -> Result<serde_json::Value, RpcError> {
     require_faucet(state)?;
 
-    let req: GovernanceProposeSimpleRequest =
+    let req: GovernanceGrantSimpleRequest =
+        serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
+    let private_key = parse_private_key(&req.private_key)?;
+    let recipient_key = burst_crypto::decode_address(&req.recipient)
+        .ok_or_else(|| RpcError::InvalidRequest(format!("invalid recipient: {}", req.recipient)))?;
+    let amount: u128 = req
+        .amount
+        .parse()
+        .map_err(|e| RpcError::InvalidRequest(format!("invalid amount: {e}")))?;
+    if amount == 0 {
+        return Err(RpcError::InvalidRequest(
+            "grant amount must be non-zero".into(),
+        ));
+    }
+    let ballot = parse_ballot_mode(req.ballot.as_deref().unwrap_or("open"))?;
+
+    let content = burst_governance::ProposalContent::TreasuryGrant {
+        recipient: burst_crypto::derive_address(&burst_types::PublicKey(recipient_key)),
+        amount,
+    };
+    let submitted = submit_proposal_block(
+        state,
+        private_key,
+        content,
+        treasury_grant_link(amount),
+        TxHash::new(recipient_key),
+        ballot,
+        None,
+    )
+    .await?;
+
+    Ok(to_value(&GovernanceGrantSimpleResponse {
+        block_hash: submitted.block_hash.to_string(),
+        proposal_hash: submitted.proposal_hash.to_string(),
+        account: submitted.account.to_string(),
+        recipient: req.recipient,
+        amount: req.amount,
+        ballot: format_ballot_mode(ballot).to_string(),
+    }))
+}
+
+// ── treasury ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+pub struct TreasuryRequest {
+    /// Most recent inflows and grants to list (default 50).
+    #[serde(default)]
+    pub count: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TreasuryInflowEntry {
+    pub source: String,
+    pub reference: String,
+    pub from: String,
+    pub amount: String,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TreasuryGrantEntry {
+    pub proposal: String,
+    pub recipient: String,
+    pub amount: String,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TreasuryResponse {
+    pub address: String,
+    pub balance: String,
+    /// Total credited per source.
+    pub inflows_by_source: std::collections::BTreeMap<String, String>,
+    pub total_granted: String,
+    pub inflow_count: usize,
+    pub grant_count: usize,
+    /// Most recent first.
+    pub inflows: Vec<TreasuryInflowEntry>,
+    /// Most recent first.
+    pub grants: Vec<TreasuryGrantEntry>,
+}
+
+/// Errors: none of its own.
+pub async fn handle_treasury(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    let req: TreasuryRequest =
+        serde_json::from_value(params).unwrap_or(TreasuryRequest { count: None });
+    let count = req.count.unwrap_or(50);
+    let treasury = match state.treasury {
+        Some(ref treasury) => treasury.lock().await.clone(),
+        None => burst_governance::Treasury::new(),
+    };
+
+    Ok(to_value(&TreasuryResponse {
+        address: burst_governance::Treasury::address().to_string(),
+        balance: treasury.balance().to_string(),
+        inflows_by_source: burst_governance::TreasurySource::ALL
+            .into_iter()
+            .map(|source| {
+                (
+                    source.as_str().to_string(),
+                    treasury.total_from(source).to_string(),
+                )
+            })
+            .collect(),
+        total_granted: treasury.total_granted().to_string(),
+        inflow_count: treasury.inflows().len(),
+        grant_count: treasury.grants().len(),
+        inflows: treasury
+            .inflows()
+            .iter()
+            .rev()
+            .take(count)
+            .map(|inflow| TreasuryInflowEntry {
+                source: inflow.source.as_str().to_string(),
+                reference: inflow.reference.to_string(),
+                from: inflow.from.to_string(),
+                amount: inflow.amount.to_string(),
+                timestamp: inflow.at.as_secs(),
+            })
+            .collect(),
+        grants: treasury
+            .grants()
+            .iter()
+            .rev()
+            .take(count)
+            .map(|grant| TreasuryGrantEntry {
+                proposal: grant.proposal.to_string(),
+                recipient: grant.recipient.to_string(),
+                amount: grant.amount.to_string(),
+                timestamp: grant.at.as_secs(),
+            })
+            .collect(),
+    }))
+}
+
+// ── governance_vote_simple (vote on a governance proposal) ───────────
+
+#[derive(Debug, Deserialize)]
+pub struct GovernanceVoteSimpleRequest {
+    pub private_key: String,
+    pub proposal_hash: String,
+    pub vote: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GovernanceVoteSimpleResponse {
+    pub block_hash: String,
+    pub account: String,
+    pub proposal_hash: String,
+    pub vote: String,
+}
+
+/// Errors: `invalid_request` without the faucet; `account_not_found`;
+/// `internal` if the node turns the vote block away.
+pub async fn handle_governance_vote_simple(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    require_faucet(state)?;
+
+    let req: GovernanceVoteSimpleRequest =
         serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
     let private_key = parse_private_key(&req.private_key)?;
     let public_key = burst_crypto::public_from_private(&private_key);
@@ -2335,69 +5855,74 @@ pub async fn handle_governance_propose_simple(
             "account has no blocks yet — burn BRN first".into(),
         ));
     }
-
-    let param = parse_governable_param(&req.param)?;
-    let new_value: u128 = req
-        .new_value
-        .parse()
-        .map_err(|e| RpcError::InvalidRequest(format!("invalid new_value: {e}")))?;
-
-    let content = burst_governance::ProposalContent::ParameterChange {
-        param: param.clone(),
-        new_value,
-    };
-    let content_bytes = bincode::serialize(&content)
-        .map_err(|e| RpcError::Server(format!("failed to serialize proposal content: {e}")))?;
-    if content_bytes.len() > 32 {
+
+    let vote_byte: u8 = match req.vote.to_lowercase().as_str() {
+        "yea" | "yes" | "y" => 0,
+        "nay" | "no" | "n" => 1,
+        "abstain" | "a" => 2,
+        _ => {
+            return Err(RpcError::InvalidRequest(
+                "vote must be 'yea', 'nay', or 'abstain'".into(),
+            ))
+        }
+    };
+
+    let proposal_hash_bytes = hex::decode(&req.proposal_hash)
+        .map_err(|e| RpcError::InvalidRequest(format!("invalid proposal_hash hex: {e}")))?;
+    if proposal_hash_bytes.len() != 32 {
+        return Err(RpcError::InvalidRequest(
+            "proposal_hash must be 32 bytes (64 hex chars)".into(),
+        ));
+    }
+    let mut ph = [0u8; 32];
+    ph.copy_from_slice(&proposal_hash_bytes);
+    let link = BlockHash::new(ph);
+    if ballot_mode(state, &TxHash::new(ph)).await == BallotMode::Sealed {
         return Err(RpcError::InvalidRequest(
-            "proposal content too large for link field (max 32 bytes)".into(),
+            "proposal uses sealed ballots — use governance_commit_simple".into(),
         ));
     }
-    let mut link_bytes = [0u8; 32];
-    link_bytes[..content_bytes.len()].copy_from_slice(&content_bytes);
-    let link = BlockHash::new(link_bytes);
 
-    let now = Timestamp::now();
+    let mut tx_bytes = [0u8; 32];
+    tx_bytes[0] = vote_byte;
+    let transaction = TxHash::new(tx_bytes);
+
+    let now = node_now(state);
     let brn_state = brn_state_from_account(&account, state.params.brn_rate);
     let brn_balance = {
         let brn = state.brn_engine.lock().await;
         brn.compute_balance(&brn_state, now)
     };
 
-    let tx_hash = TxHash::new(burst_crypto::blake2b_256(
-        &[
-            address.as_str().as_bytes(),
-            &now.as_secs().to_be_bytes(),
-            b"governance_propose",
-        ]
-        .concat(),
-    ));
-
     let pk_bytes = private_key.0;
     let block = tokio::task::spawn_blocking({
         let address = address.clone();
         let representative = account.representative.clone();
         let previous = account.head;
+        let sequence = account.block_count + 1;
         let trst_balance = account.trst_balance;
         let work_gen = state.work_generator.clone();
-        let min_diff = state.params.min_work_difficulty;
-        let ph = state.params.params_hash();
+        let thresholds = burst_work::WorkThresholds::from_params(&state.params);
+        let ph = current_params_hash(state);
+        let layout = block_layout(state, &account);
         move || {
             let pk = burst_types::PrivateKey(pk_bytes);
             build_and_sign_block(
-                burst_ledger::BlockType::GovernanceProposal,
+                burst_ledger::BlockType::GovernanceVote,
                 &address,
                 previous,
+                sequence,
                 &representative,
                 brn_balance,
                 trst_balance,
                 link,
                 TxHash::ZERO,
-                tx_hash,
+                transaction,
                 &pk,
                 &work_gen,
-                min_diff,
+                &thresholds,
                 ph,
+                layout,
             )
         }
     })
@@ -2422,111 +5947,141 @@ pub async fn handle_governance_propose_simple(
         .put_block_with_account(&block.hash, &block_bytes, &address);
 
     if let Some(ref engine) = state.governance_engine {
-        let proposal = burst_governance::Proposal {
-            hash: tx_hash,
-            proposer: address.clone(),
-            content,
-            phase: burst_governance::GovernancePhase::Proposal,
-            created_at: now,
-            endorsement_count: 0,
-            exploration_votes_yea: 0,
-            exploration_votes_nay: 0,
-            exploration_votes_abstain: 0,
-            promotion_votes_yea: 0,
-            promotion_votes_nay: 0,
-            promotion_votes_abstain: 0,
-            exploration_started_at: None,
-            cooldown_started_at: None,
-            promotion_started_at: None,
-            activation_at: None,
-            total_eligible_voters: 1,
-            round: 0,
+        let gov_vote = match vote_byte {
+            0 => burst_transactions::governance::GovernanceVote::Yea,
+            1 => burst_transactions::governance::GovernanceVote::Nay,
+            _ => burst_transactions::governance::GovernanceVote::Abstain,
         };
+        let proposal_tx_hash = TxHash::new(ph);
         let mut gov = engine.lock().await;
-        match gov.submit_proposal(proposal, brn_balance, true, &state.params) {
-            Ok(_) => {}
-            Err(e) => {
-                tracing::warn!("governance proposal accepted as block but engine rejected: {e}");
-            }
+        let open = burst_governance::BallotBox::new();
+        let ballots = lock_ballots(state).await;
+        if let Err(e) = gov.cast_vote(
+            ballots.as_deref().unwrap_or(&open),
+            &proposal_tx_hash,
+            &address,
+            gov_vote,
+            now,
+            &state.params,
+        ) {
+            tracing::warn!("governance vote rejected by engine: {e}");
         }
     }
 
-    Ok(to_value(&GovernanceProposeSimpleResponse {
+    let vote_name = match vote_byte {
+        0 => "yea",
+        1 => "nay",
+        _ => "abstain",
+    };
+
+    Ok(to_value(&GovernanceVoteSimpleResponse {
         block_hash: format!("{}", block.hash),
-        proposal_hash: format!("{}", tx_hash),
         account: address.to_string(),
-        param: req.param,
-        new_value: req.new_value,
+        proposal_hash: req.proposal_hash,
+        vote: vote_name.to_string(),
     }))
 }
 
-// ── governance_vote_simple (vote on a governance proposal) ───────────
+// ── governance_commit_simple / governance_reveal_simple (sealed ballots) ──
 
 #[derive(Debug, Deserialize)]
-pub struct GovernanceVoteSimpleRequest {
+pub struct GovernanceSealedBallotRequest {
     pub private_key: String,
     pub proposal_hash: String,
     pub vote: String,
 }
 
 #[derive(Debug, Serialize)]
-pub struct GovernanceVoteSimpleResponse {
+pub struct GovernanceSealedBallotResponse {
     pub block_hash: String,
     pub account: String,
     pub proposal_hash: String,
     pub vote: String,
+    pub commitment: String,
 }
 
-pub async fn handle_governance_vote_simple(
+/// Commit to a sealed ballot. The salt is derived from the private key and
+/// the proposal, so revealing needs only the same key and vote.
+///
+/// Errors: `invalid_request` without the faucet, or for a proposal without
+/// sealed ballots; `account_not_found`; `internal` if the node turns the
+/// ballot block away.
+pub async fn handle_governance_commit_simple(
+    params: serde_json::Value,
+    state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    sealed_ballot_simple(params, state, burst_ledger::BlockType::GovernanceCommit).await
+}
+
+/// Reveal a sealed ballot committed with `governance_commit_simple`.
+///
+/// Errors: `invalid_request` without the faucet, or for a proposal without
+/// sealed ballots; `account_not_found`; `internal` if the node turns the
+/// ballot block away.
+pub async fn handle_governance_reveal_simple(
     params: serde_json::Value,
     state: &RpcState,
+) -> Result<serde_json::Value, RpcError> {
+    sealed_ballot_simple(params, state, burst_ledger::BlockType::GovernanceReveal).await
+}
+
+async fn sealed_ballot_simple(
+    params: serde_json::Value,
+    state: &RpcState,
+    block_type: burst_ledger::BlockType,
 ) -> Result<serde_json::Value, RpcError> {
     require_faucet(state)?;
 
-    let req: GovernanceVoteSimpleRequest =
+    let req: GovernanceSealedBallotRequest =
         serde_json::from_value(params).map_err(|e| RpcError::InvalidRequest(e.to_string()))?;
     let private_key = parse_private_key(&req.private_key)?;
     let public_key = burst_crypto::public_from_private(&private_key);
     let address = burst_crypto::derive_address(&public_key);
+    validate_hash(&req.proposal_hash)?;
+    let proposal_hash = parse_tx_hash(&req.proposal_hash)?;
+    let vote = match req.vote.to_lowercase().as_str() {
+        "yea" | "yes" | "y" => burst_transactions::governance::GovernanceVote::Yea,
+        "nay" | "no" | "n" => burst_transactions::governance::GovernanceVote::Nay,
+        "abstain" | "a" => burst_transactions::governance::GovernanceVote::Abstain,
+        _ => {
+            return Err(RpcError::InvalidRequest(
+                "vote must be 'yea', 'nay', or 'abstain'".into(),
+            ))
+        }
+    };
+    if ballot_mode(state, &proposal_hash).await != BallotMode::Sealed {
+        return Err(RpcError::InvalidRequest(
+            "proposal does not use sealed ballots — use governance_vote_simple".into(),
+        ));
+    }
 
     let account = state
         .account_store
         .get_account(&address)
         .map_err(|e| account_not_found(e, address.as_str()))?;
-
     if account.head == BlockHash::ZERO {
         return Err(RpcError::InvalidRequest(
             "account has no blocks yet — burn BRN first".into(),
         ));
     }
 
-    let vote_byte: u8 = match req.vote.to_lowercase().as_str() {
-        "yea" | "yes" | "y" => 0,
-        "nay" | "no" | "n" => 1,
-        "abstain" | "a" => 2,
-        _ => {
-            return Err(RpcError::InvalidRequest(
-                "vote must be 'yea', 'nay', or 'abstain'".into(),
-            ))
-        }
+    let salt = burst_crypto::blake2b_256_multi(&[
+        b"burst-sealed-ballot-salt-v1",
+        &private_key.0,
+        proposal_hash.as_bytes(),
+    ]);
+    let mut sealed = [0u8; 32];
+    sealed[0] = vote.as_byte();
+    sealed[1..].copy_from_slice(&salt[..31]);
+    let commitment = burst_governance::ballot_commitment(&proposal_hash, &address, &sealed);
+    let committing = block_type == burst_ledger::BlockType::GovernanceCommit;
+    let transaction = if committing {
+        TxHash::new(commitment)
+    } else {
+        TxHash::new(sealed)
     };
 
-    let proposal_hash_bytes = hex::decode(&req.proposal_hash)
-        .map_err(|e| RpcError::InvalidRequest(format!("invalid proposal_hash hex: {e}")))?;
-    if proposal_hash_bytes.len() != 32 {
-        return Err(RpcError::InvalidRequest(
-            "proposal_hash must be 32 bytes (64 hex chars)".into(),
-        ));
-    }
-    let mut ph = [0u8; 32];
-    ph.copy_from_slice(&proposal_hash_bytes);
-    let link = BlockHash::new(ph);
-
-    let mut tx_bytes = [0u8; 32];
-    tx_bytes[0] = vote_byte;
-    let transaction = TxHash::new(tx_bytes);
-
-    let now = Timestamp::now();
+    let now = node_now(state);
     let brn_state = brn_state_from_account(&account, state.params.brn_rate);
     let brn_balance = {
         let brn = state.brn_engine.lock().await;
@@ -2538,16 +6093,20 @@ pub async fn handle_governance_vote_simple(
         let address = address.clone();
         let representative = account.representative.clone();
         let previous = account.head;
+        let sequence = account.block_count + 1;
         let trst_balance = account.trst_balance;
+        let link = BlockHash::new(*proposal_hash.as_bytes());
         let work_gen = state.work_generator.clone();
-        let min_diff = state.params.min_work_difficulty;
-        let ph = state.params.params_hash();
+        let thresholds = burst_work::WorkThresholds::from_params(&state.params);
+        let ph = current_params_hash(state);
+        let layout = block_layout(state, &account);
         move || {
             let pk = burst_types::PrivateKey(pk_bytes);
             build_and_sign_block(
-                burst_ledger::BlockType::GovernanceVote,
+                block_type,
                 &address,
                 previous,
+                sequence,
                 &representative,
                 brn_balance,
                 trst_balance,
@@ -2556,8 +6115,9 @@ pub async fn handle_governance_vote_simple(
                 transaction,
                 &pk,
                 &work_gen,
-                min_diff,
+                &thresholds,
                 ph,
+                layout,
             )
         }
     })
@@ -2581,95 +6141,543 @@ pub async fn handle_governance_vote_simple(
         .block_store
         .put_block_with_account(&block.hash, &block_bytes, &address);
 
-    if let Some(ref engine) = state.governance_engine {
-        let gov_vote = match vote_byte {
-            0 => burst_transactions::governance::GovernanceVote::Yea,
-            1 => burst_transactions::governance::GovernanceVote::Nay,
-            _ => burst_transactions::governance::GovernanceVote::Abstain,
-        };
-        let proposal_tx_hash = TxHash::new(ph);
+    if let (Some(ref engine), Some(ref ballots)) =
+        (&state.governance_engine, &state.governance_ballots)
+    {
         let mut gov = engine.lock().await;
-        match gov.cast_exploration_vote(&proposal_tx_hash, &address, gov_vote, now, &state.params) {
-            Ok(()) => {}
-            Err(burst_governance::GovernanceError::WrongPhase) => {
-                match gov.cast_promotion_vote(
-                    &proposal_tx_hash,
-                    &address,
-                    gov_vote,
-                    now,
-                    &state.params,
-                ) {
-                    Ok(()) => {}
-                    Err(e) => {
-                        tracing::warn!("governance vote rejected by engine: {e}");
-                    }
-                }
-            }
-            Err(e) => {
-                tracing::warn!("governance vote rejected by engine: {e}");
-            }
+        let mut ballots = ballots.lock().await;
+        let result = if committing {
+            gov.commit_ballot(
+                &mut ballots,
+                &proposal_hash,
+                &address,
+                commitment,
+                now,
+                &state.params,
+            )
+        } else {
+            gov.reveal_ballot(
+                &mut ballots,
+                &proposal_hash,
+                &address,
+                &sealed,
+                now,
+                &state.params,
+            )
+            .map(|_| ())
+        };
+        if let Err(e) = result {
+            tracing::warn!("sealed ballot rejected by engine: {e}");
         }
     }
 
-    let vote_name = match vote_byte {
-        0 => "yea",
-        1 => "nay",
-        _ => "abstain",
-    };
-
-    Ok(to_value(&GovernanceVoteSimpleResponse {
+    Ok(to_value(&GovernanceSealedBallotResponse {
         block_hash: format!("{}", block.hash),
         account: address.to_string(),
         proposal_hash: req.proposal_hash,
-        vote: vote_name.to_string(),
+        vote: format!("{vote:?}").to_lowercase(),
+        commitment: hex::encode(commitment),
     }))
 }
 
+/// The node's ballot box, if it keeps one. Take the governance engine lock
+/// first when holding both.
+async fn lock_ballots(
+    state: &RpcState,
+) -> Option<tokio::sync::MutexGuard<'_, burst_governance::BallotBox>> {
+    match state.governance_ballots {
+        Some(ref ballots) => Some(ballots.lock().await),
+        None => None,
+    }
+}
+
+/// How `proposal` is voted on, per the node's ballot box.
+async fn ballot_mode(state: &RpcState, proposal: &TxHash) -> BallotMode {
+    lock_ballots(state)
+        .await
+        .map_or(BallotMode::Open, |ballots| ballots.mode(proposal))
+}
+
+fn ballot_label(ballots: Option<&burst_governance::BallotBox>, proposal: &TxHash) -> String {
+    let mode = ballots.map_or(BallotMode::Open, |ballots| ballots.mode(proposal));
+    format_ballot_mode(mode).to_string()
+}
+
+fn parse_ballot_mode(mode: &str) -> Result<BallotMode, RpcError> {
+    match mode {
+        "open" => Ok(BallotMode::Open),
+        "sealed" => Ok(BallotMode::Sealed),
+        other => Err(RpcError::InvalidRequest(format!(
+            "ballot must be 'open' or 'sealed', got '{other}'"
+        ))),
+    }
+}
+
+fn format_ballot_mode(mode: BallotMode) -> &'static str {
+    match mode {
+        BallotMode::Open => "open",
+        BallotMode::Sealed => "sealed",
+    }
+}
+
 fn parse_governable_param(name: &str) -> Result<burst_governance::GovernableParam, RpcError> {
-    use burst_governance::GovernableParam;
-    match name {
-        "brn_rate" => Ok(GovernableParam::BrnRate),
-        "trst_expiry_secs" => Ok(GovernableParam::TrstExpirySecs),
-        "endorsement_threshold" => Ok(GovernableParam::EndorsementThreshold),
-        "endorsement_burn_amount" => Ok(GovernableParam::EndorsementBurnAmount),
-        "num_verifiers" => Ok(GovernableParam::NumVerifiers),
-        "verification_threshold_bps" => Ok(GovernableParam::VerificationThresholdBps),
-        "verifier_stake_amount" => Ok(GovernableParam::VerifierStakeAmount),
-        "max_revotes" => Ok(GovernableParam::MaxRevotes),
-        "challenge_stake_amount" => Ok(GovernableParam::ChallengeStakeAmount),
-        "governance_proposal_duration_secs" => Ok(GovernableParam::GovernanceProposalDurationSecs),
-        "governance_exploration_duration_secs" => {
-            Ok(GovernableParam::GovernanceExplorationDurationSecs)
+    burst_governance::GovernableParam::from_name(name)
+        .ok_or_else(|| RpcError::InvalidRequest(format!("unknown governable parameter: '{name}'")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(amount: u128, ts: u64) -> burst_store::PendingInfo {
+        burst_store::PendingInfo {
+            source: WalletAddress::new("brst_sender"),
+            amount,
+            timestamp: Timestamp::new(ts),
+            provenance: Vec::new(),
+            memo: None,
+            destination_tag: None,
+            htlc: None,
+            returned: false,
+        }
+    }
+
+    fn account(head: BlockHash, trst_balance: u128) -> AccountInfo {
+        let address = WalletAddress::new("brst_receiver");
+        AccountInfo {
+            address: address.clone(),
+            state: burst_types::WalletState::Verified,
+            verified_at: None,
+            head,
+            representative: address,
+            block_count: 1,
+            confirmation_height: 0,
+            total_brn_burned: 0,
+            trst_balance,
+            total_brn_staked: 0,
+            expired_trst: 0,
+            revoked_trst: 0,
+            epoch: 0,
+        }
+    }
+
+    #[test]
+    fn sweep_blocks_chain_previous_and_balances() {
+        let head = BlockHash::new([7u8; 32]);
+        let entries = vec![
+            (TxHash::new([1u8; 32]), pending(10, 100)),
+            (TxHash::new([2u8; 32]), pending(25, 200)),
+        ];
+        let blocks = build_sweep_blocks(
+            &account(head, 5),
+            0,
+            TxHash::ZERO,
+            &entries,
+            Timestamp::new(300),
+            BlockHash::ZERO,
+            burst_ledger::BlockLayout::for_account(3, 0, burst_types::NetworkId::Dev),
+        );
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].previous, head);
+        assert_eq!(blocks[0].block_type, burst_ledger::BlockType::Receive);
+        assert_eq!(blocks[0].trst_balance, 15);
+        assert_eq!(blocks[0].link, BlockHash::new([1u8; 32]));
+        assert_eq!(blocks[1].previous, blocks[0].hash);
+        assert_eq!(blocks[1].trst_balance, 40);
+        assert_eq!(blocks[0].sequence(), Some(2));
+        assert_eq!(blocks[1].sequence(), Some(3));
+        assert_eq!(blocks[1].network(), Some(burst_types::NetworkId::Dev));
+        for block in &blocks {
+            assert_eq!(block.hash, block.compute_hash());
         }
-        "governance_cooldown_duration_secs" => Ok(GovernableParam::GovernanceCooldownDurationSecs),
-        "governance_promotion_duration_secs" => {
-            Ok(GovernableParam::GovernancePromotionDurationSecs)
+    }
+
+    #[test]
+    fn sweep_opens_unopened_account() {
+        let entries = vec![
+            (TxHash::new([1u8; 32]), pending(10, 100)),
+            (TxHash::new([2u8; 32]), pending(20, 100)),
+        ];
+        let blocks = build_sweep_blocks(
+            &account(BlockHash::ZERO, 0),
+            0,
+            TxHash::ZERO,
+            &entries,
+            Timestamp::new(300),
+            BlockHash::ZERO,
+            burst_ledger::BlockLayout::for_account(0, 0, burst_types::NetworkId::Dev),
+        );
+
+        assert_eq!(blocks[0].block_type, burst_ledger::BlockType::Open);
+        assert_eq!(blocks[1].block_type, burst_ledger::BlockType::Receive);
+        assert_eq!(blocks[1].previous, blocks[0].hash);
+    }
+
+    #[test]
+    fn delegate_chain_scan_keeps_latest_anchor_and_confirmed_votes() {
+        use burst_ledger::BlockType;
+
+        let entries: Vec<_> = (1..=5u8)
+            .map(|i| (TxHash::new([i; 32]), pending(1, 100)))
+            .collect();
+        let mut chain = build_sweep_blocks(
+            &account(BlockHash::new([7u8; 32]), 0),
+            0,
+            TxHash::ZERO,
+            &entries,
+            Timestamp::new(300),
+            BlockHash::ZERO,
+            burst_ledger::BlockLayout::for_account(0, 0, burst_types::NetworkId::Dev),
+        );
+        let vote = |block: &mut StateBlock, proposal: u8, byte: u8| {
+            block.block_type = BlockType::GovernanceVote;
+            block.link = BlockHash::new([proposal; 32]);
+            let mut tx = [0u8; 32];
+            tx[0] = byte;
+            block.transaction = TxHash::new(tx);
+        };
+        chain[0].block_type = BlockType::DelegateProfile;
+        chain[0].link = BlockHash::new([0xaa; 32]);
+        vote(&mut chain[1], 9, 1);
+        chain[2].block_type = BlockType::DelegateProfile;
+        chain[2].link = BlockHash::new([0xbb; 32]);
+        vote(&mut chain[3], 8, 0);
+        vote(&mut chain[4], 7, 2);
+
+        let activity = scan_delegate_chain(chain, 4);
+        assert_eq!(activity.anchor.unwrap().link, BlockHash::new([0xbb; 32]));
+        // The vote at height 5 is not confirmed yet.
+        let votes: Vec<_> = activity
+            .votes
+            .iter()
+            .map(|(proposal, vote)| (*proposal, vote.vote))
+            .collect();
+        assert_eq!(
+            votes,
+            vec![(TxHash::new([9; 32]), "nay"), (TxHash::new([8; 32]), "yea")]
+        );
+
+        for scope in [
+            "global",
+            "category:economic",
+            &format!("proposal:{}", TxHash::new([3; 32])),
+        ] {
+            assert_eq!(
+                format_delegation_scope(&parse_delegation_scope(scope).unwrap()),
+                scope
+            );
         }
-        "governance_supermajority_bps" => Ok(GovernableParam::GovernanceSupermajorityBps),
-        "governance_quorum_bps" => Ok(GovernableParam::GovernanceQuorumBps),
-        "governance_proposal_endorsements" => Ok(GovernableParam::GovernanceProposalEndorsements),
-        "governance_ema_participation_bps" => Ok(GovernableParam::GovernanceEmaParticipationBps),
-        "consti_supermajority_bps" => Ok(GovernableParam::ConstiSupermajorityBps),
-        "consti_quorum_bps" => Ok(GovernableParam::ConstiQuorumBps),
-        "verification_timeout_secs" => Ok(GovernableParam::VerificationTimeoutSecs),
-        "challenge_duration_secs" => Ok(GovernableParam::ChallengeDurationSecs),
-        "endorser_reward_bps" => Ok(GovernableParam::EndorserRewardBps),
-        "new_wallet_spending_limit" => Ok(GovernableParam::NewWalletSpendingLimit),
-        "new_wallet_limit_duration_secs" => Ok(GovernableParam::NewWalletLimitDurationSecs),
-        "bootstrap_exit_threshold" => Ok(GovernableParam::BootstrapExitThreshold),
-        "new_wallet_tx_limit_per_day" => Ok(GovernableParam::NewWalletTxLimitPerDay),
-        "new_wallet_rate_limit_duration_secs" => {
-            Ok(GovernableParam::NewWalletRateLimitDurationSecs)
+        assert!(parse_delegation_scope("category:").is_err());
+    }
+
+    #[test]
+    fn representative_stats_from_crawler() {
+        let rep = WalletAddress::new("brst_rep");
+        let mut crawler = burst_consensus::RepCrawler::new(5_000, 30_000);
+        crawler.start_crawl(BlockHash::new([1u8; 32]), &["peer".to_string()], 1_000);
+        crawler.process_response("peer", &rep, 10, 1_250);
+
+        let stats =
+            RepresentativeStats::from_performance(crawler.performance(&rep).unwrap(), 61_250);
+        assert_eq!(stats.last_vote_age_secs, Some(60));
+        assert_eq!(stats.latency_p50_ms, Some(250));
+        assert_eq!(stats.latency_p99_ms, Some(250));
+        assert_eq!(stats.uptime_score, Some(1.0));
+
+        let json = serde_json::to_value(RepresentativeStats::default()).unwrap();
+        assert_eq!(json, serde_json::json!({}));
+    }
+
+    #[test]
+    fn election_info_orders_candidates_by_tally() {
+        let root = BlockHash::new([1u8; 32]);
+        let fork_a = BlockHash::new([2u8; 32]);
+        let fork_b = BlockHash::new([3u8; 32]);
+        let mut election = burst_consensus::Election::new(root, 1_000, Timestamp::new(100));
+        election.vote(
+            &WalletAddress::new("brst_rep1"),
+            fork_a,
+            300,
+            false,
+            Timestamp::new(101),
+        );
+        election.vote(
+            &WalletAddress::new("brst_rep2"),
+            fork_b,
+            200,
+            false,
+            Timestamp::new(101),
+        );
+        election.vote(
+            &WalletAddress::new("brst_rep3"),
+            fork_b,
+            250,
+            true,
+            Timestamp::new(102),
+        );
+
+        let info = ElectionInfoResponse::from_election(&election);
+        assert_eq!(info.state, "active");
+        assert_eq!(info.confirmation_threshold, "670");
+        assert_eq!(info.leader, Some(fork_b.to_string()));
+        assert_eq!(info.candidates[0].tally, "450");
+        assert_eq!(info.candidates[0].voters, 2);
+        assert_eq!(info.candidates[1].block, fork_a.to_string());
+        assert_eq!(info.voters.len(), 3);
+        assert_eq!(info.voters[0].account, "brst_rep1");
+        assert!(info.voters[1].is_final);
+    }
+
+    #[test]
+    fn node_containers_keeps_order_and_sums_bytes() {
+        let usage = |count, bytes| burst_types::ContainerUsage { count, bytes };
+        let response = to_value(&NodeContainersResponse::from_usage(vec![
+            ("unchecked", usage(3, 600)),
+            ("vote_cache", usage(0, 0)),
+            ("trst_portfolios", usage(2, 1_000)),
+        ]));
+        assert_eq!(response["total_bytes"], 1_600);
+        assert_eq!(response["containers"][0]["name"], "unchecked");
+        assert_eq!(response["containers"][2]["count"], 2);
+    }
+
+    #[test]
+    fn stats_counters_serialize_with_nano_style_keys() {
+        let req: StatsRequest =
+            serde_json::from_value(serde_json::json!({"type": "samples"})).unwrap();
+        assert_eq!(req.kind, StatsKind::Samples);
+
+        let stats = burst_utils::Stats::default();
+        stats.inc(
+            burst_utils::StatType::BlockProcessor,
+            burst_utils::DetailType::Accepted,
+        );
+        let counters = to_value(&stats.counters());
+        assert_eq!(counters["entries"][0]["type"], "block_processor");
+        assert_eq!(counters["entries"][0]["detail"], "all");
+        assert_eq!(counters["entries"][1]["detail"], "accepted");
+        assert_eq!(counters["entries"][1]["dir"], "in");
+    }
+
+    #[test]
+    fn confirmation_history_formats_amounts_as_strings() {
+        let snapshot = crate::server::ConfirmationHistorySnapshot {
+            confirmations: vec![crate::server::ConfirmationEntry {
+                hash: BlockHash::new([4u8; 32]),
+                account: WalletAddress::new("brst_sender"),
+                amount: u128::MAX,
+                tally: 900,
+                duration_ms: 350,
+                confirmed_at: 1_700,
+            }],
+            count: 12,
+            average_ms: 420,
+            p95_ms: 910,
+        };
+        let response = to_value(&ConfirmationHistoryResponse::from_snapshot(snapshot));
+        assert_eq!(response["confirmation_stats"]["p95_ms"], 910);
+        assert_eq!(response["confirmation_stats"]["count"], 12);
+        let entry = &response["confirmations"][0];
+        assert_eq!(entry["amount"], u128::MAX.to_string());
+        assert_eq!(entry["tally"], "900");
+        assert_eq!(entry["hash"], BlockHash::new([4u8; 32]).to_string());
+        assert_eq!(entry["time"], 1_700);
+    }
+
+    #[test]
+    fn selection_proof_recomputes_the_draw() {
+        let candidates: Vec<WalletAddress> = (0..8)
+            .map(|i| WalletAddress::new(format!("brst_verifier{i}")))
+            .collect();
+        let randomness = [9u8; 32];
+        let mut selection = burst_store::VerifierSelection {
+            target: WalletAddress::new("brst_target"),
+            drand_round: 42,
+            randomness,
+            pool_hash: burst_verification::pool_snapshot_hash(&candidates),
+            selected: burst_verification::draw_verifiers(&randomness, &candidates, 3),
+            candidates,
+            selected_at: Timestamp::new(1_000),
+        };
+
+        let entry = SelectionProofEntry::from_selection(&selection);
+        assert!(entry.pool_hash_valid && entry.selection_valid);
+        assert_eq!(entry.selected.len(), 3);
+
+        selection.selected[0] = WalletAddress::new("brst_insider");
+        selection
+            .candidates
+            .push(WalletAddress::new("brst_insider"));
+        let entry = SelectionProofEntry::from_selection(&selection);
+        assert!(!entry.pool_hash_valid);
+        assert!(!entry.selection_valid);
+    }
+
+    #[test]
+    fn uhv_stats_aggregates_and_suppresses_sparse_buckets() {
+        use burst_store::{VerificationRecord, VerificationRecordOutcome as Outcome};
+
+        let record = |outcome, endorsements, started_at, completed_at| VerificationRecord {
+            outcome,
+            endorsement_count: endorsements,
+            started_at: Timestamp::new(started_at),
+            completed_at: Timestamp::new(completed_at),
+            votes_cast: 5,
+            votes_agreeing: 4,
+            neither_votes: 1,
+        };
+        let mut records = Vec::new();
+        // A full first cohort: 4 verified, 1 failed.
+        for _ in 0..4 {
+            records.push(record(Outcome::Verified, 3, 10, 20));
         }
-        "governance_proposal_cost" => Ok(GovernableParam::GovernanceProposalCost),
-        "governance_max_rounds" => Ok(GovernableParam::GovernanceMaxRounds),
-        "governance_proposal_window_secs" => Ok(GovernableParam::GovernanceProposalWindowSecs),
-        "governance_propagation_buffer_secs" => {
-            Ok(GovernableParam::GovernancePropagationBufferSecs)
+        records.push(record(Outcome::Failed, 12, 10, 20));
+        // A sparse second cohort and a single expired request.
+        records.push(record(Outcome::Verified, 3, 150, 160));
+        records.push(record(Outcome::Expired, 0, 150, 400));
+        records.push(record(Outcome::FraudConfirmed, 3, 10, 250));
+
+        let stats = UhvStatsResponse::from_records(&records, 7, 100);
+        assert_eq!(stats.verifier_pool_size, 7);
+        assert_eq!(stats.verifications, 7);
+        assert_eq!(stats.challenges, 1);
+
+        assert_eq!(stats.endorsement_degree.len(), 11);
+        assert_eq!(stats.endorsement_degree[0].wallets, 1);
+        assert_eq!(stats.endorsement_degree[3].wallets, 5);
+        assert_eq!(stats.endorsement_degree[10].endorsements, "10+");
+        assert_eq!(stats.endorsement_degree[10].wallets, 1);
+
+        assert_eq!(stats.cohorts.len(), 2);
+        assert!(!stats.cohorts[0].suppressed);
+        assert_eq!(stats.cohorts[0].verified, 4);
+        assert_eq!(stats.cohorts[0].pass_rate_bps, 8_000);
+        assert_eq!(stats.cohorts[1].cohort_start, 100);
+        assert!(stats.cohorts[1].suppressed);
+        assert_eq!(stats.cohorts[1].verified, 0);
+
+        assert_eq!(stats.challenge_outcomes.len(), 1);
+        assert_eq!(stats.challenge_outcomes[0].period_start, 200);
+        assert!(stats.challenge_outcomes[0].suppressed);
+
+        // Seven decided records, five votes each; the expired one is skipped.
+        assert_eq!(stats.verifier_agreement.votes_cast, 35);
+        assert_eq!(stats.verifier_agreement.agreement_bps, 8_000);
+    }
+
+    #[test]
+    fn weight_buckets_summarise_samples_per_step() {
+        let sample = |timestamp, online, trended| burst_store::WeightSample {
+            timestamp,
+            online,
+            trended,
+            quorum_delta: trended.map(|t: u128| t * 2 / 3),
+        };
+        let samples = [
+            sample(1_000, 90, None),
+            sample(1_020, 120, Some(100)),
+            sample(1_050, 60, None),
+            sample(1_250, 300, Some(240)),
+        ];
+        let buckets = weight_buckets(&samples, 990, 100);
+        assert_eq!(
+            buckets,
+            vec![
+                WeightBucket {
+                    start: 990,
+                    samples: 3,
+                    online_min: 60,
+                    online_max: 120,
+                    online_avg: 90,
+                    trended: Some(100),
+                    quorum_delta: Some(66),
+                },
+                WeightBucket {
+                    start: 1_190,
+                    samples: 1,
+                    online_min: 300,
+                    online_max: 300,
+                    online_avg: 300,
+                    trended: Some(240),
+                    quorum_delta: Some(160),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn tx_status_prefers_the_ledger_when_it_is_further_along() {
+        use crate::server::{TxState, TxStatus};
+
+        let tracked = |state, reason: Option<&str>| TxStatus {
+            state,
+            reason: reason.map(str::to_string),
+            updated_at: 10,
+        };
+        assert_eq!(resolve_tx_status(None, false, false), None);
+        // Only the ledger knows the block.
+        assert_eq!(
+            resolve_tx_status(None, true, true).map(|s| s.state),
+            Some(TxState::Cemented)
+        );
+        // The tracker is ahead of the uncemented ledger.
+        let confirmed = tracked(TxState::Confirmed, None);
+        assert_eq!(
+            resolve_tx_status(Some(confirmed.clone()), true, false),
+            Some(confirmed)
+        );
+        // A rejected block that is not in the ledger keeps its reason.
+        let rejected = tracked(TxState::Rejected, Some("insufficient balance"));
+        assert_eq!(
+            resolve_tx_status(Some(rejected.clone()), false, false),
+            Some(rejected)
+        );
+        // Cementing overtakes whatever the tracker last saw.
+        let queued = tracked(TxState::Queued, None);
+        assert_eq!(
+            resolve_tx_status(Some(queued), true, true).map(|s| s.state),
+            Some(TxState::Cemented)
+        );
+    }
+
+    #[test]
+    fn rep_weight_recalculation_persists_corrections_and_removals() {
+        use burst_store::account::AccountStore;
+        use burst_store::RepWeightStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let env = burst_store_lmdb::LmdbEnvironment::open(dir.path(), 46, 1 << 24).unwrap();
+        let rep = |name: &str| WalletAddress::new(format!("brst_rep_{name}"));
+        let holder = |name: &str, representative: WalletAddress, trst_balance: u128| AccountInfo {
+            address: WalletAddress::new(format!("brst_acct_{name}")),
+            representative,
+            ..account(BlockHash::ZERO, trst_balance)
+        };
+        let accounts = env.account_store();
+        accounts
+            .put_account(&holder("a", rep("alice"), 500))
+            .unwrap();
+        accounts.put_account(&holder("b", rep("bob"), 200)).unwrap();
+
+        // The cache and the persisted set both still hold a stale carol.
+        let weights = env.rep_weight_store();
+        let mut cache = burst_consensus::RepWeightCache::new();
+        for (name, weight) in [("alice", 500), ("bob", 100), ("carol", 50)] {
+            cache.add_weight(&rep(name), weight);
+            weights.put_rep_weight(&rep(name), weight).unwrap();
         }
-        "min_work_difficulty" => Ok(GovernableParam::MinWorkDifficulty),
-        _ => Err(RpcError::InvalidRequest(format!(
-            "unknown governable parameter: '{name}'"
-        ))),
+
+        let drift = recalculate_rep_weights(&mut cache, &accounts, &weights).unwrap();
+        let drifted: Vec<_> = drift
+            .iter()
+            .map(|d| (d.representative.clone(), d.cached, d.expected))
+            .collect();
+        assert_eq!(drifted, vec![(rep("bob"), 100, 200), (rep("carol"), 50, 0)]);
+
+        // What the next start-up loads matches the corrected cache.
+        let mut persisted = weights.iter_rep_weights().unwrap();
+        persisted.sort_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+        assert_eq!(persisted, vec![(rep("alice"), 500), (rep("bob"), 200)]);
     }
 }
@@ -0,0 +1,151 @@
+//! Unix domain socket transport for the RPC server.
+//!
+//! Co-located wallets and scripts can reach the node without a TCP port.
+//! Each request is one line of JSON — the same body the HTTP listener
+//! accepts, single or batch — answered by one line of JSON. Access is
+//! controlled by the socket file's permissions.
+
+use crate::error::RpcError;
+use crate::server::{dispatch_body, error_body, RpcScope, RpcState};
+use burst_types::ErrorCode;
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tracing::{debug, info, warn};
+
+/// Longest request line accepted, in bytes.
+pub const MAX_IPC_REQUEST_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Caller recorded for requests arriving over the socket.
+const IPC_CALLER: &str = "ipc";
+
+/// RPC server listening on a Unix domain socket. Serves the same actions
+/// as the public HTTP listener.
+pub struct IpcServer {
+    pub path: PathBuf,
+    pub mode: u32,
+    pub state: Arc<RpcState>,
+}
+
+impl IpcServer {
+    pub fn new(path: impl Into<PathBuf>, mode: u32, state: Arc<RpcState>) -> Self {
+        Self {
+            path: path.into(),
+            mode,
+            state,
+        }
+    }
+
+    /// Bind the socket and serve connections. Blocks until the server is
+    /// shut down.
+    pub async fn start(&self) -> Result<(), RpcError> {
+        let listener = bind(&self.path, self.mode)?;
+        info!(path = %self.path.display(), "IPC server listening");
+        loop {
+            let (stream, _) = listener
+                .accept()
+                .await
+                .map_err(|e| RpcError::Server(e.to_string()))?;
+            let state = Arc::clone(&self.state);
+            tokio::spawn(async move {
+                if let Err(e) = serve_connection(stream, &state).await {
+                    debug!("IPC connection closed: {e}");
+                }
+            });
+        }
+    }
+}
+
+/// Bind `path`, replacing a socket left behind by an earlier run, and
+/// restrict it to `mode`.
+fn bind(path: &Path, mode: u32) -> Result<UnixListener, RpcError> {
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if std::os::unix::fs::FileTypeExt::is_socket(&meta.file_type()) => {
+            std::fs::remove_file(path).map_err(|e| RpcError::Server(e.to_string()))?;
+        }
+        Ok(_) => {
+            return Err(RpcError::Server(format!(
+                "{} exists and is not a socket",
+                path.display()
+            )));
+        }
+        Err(_) => {}
+    }
+    let listener = UnixListener::bind(path).map_err(|e| RpcError::Server(e.to_string()))?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+        .map_err(|e| RpcError::Server(e.to_string()))?;
+    Ok(listener)
+}
+
+/// Answer newline-delimited requests on one connection until the client
+/// disconnects.
+async fn serve_connection(stream: UnixStream, state: &RpcState) -> std::io::Result<()> {
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        let read = (&mut reader)
+            .take(MAX_IPC_REQUEST_BYTES + 1)
+            .read_until(b'\n', &mut line)
+            .await?;
+        if read == 0 {
+            return Ok(());
+        }
+        if line.last() != Some(&b'\n') && read as u64 > MAX_IPC_REQUEST_BYTES {
+            warn!("IPC request exceeds {MAX_IPC_REQUEST_BYTES} bytes");
+            write_line(
+                &mut write,
+                &error_body(ErrorCode::InvalidRequest, "request too large"),
+            )
+            .await?;
+            return Ok(());
+        }
+        let response = match serde_json::from_slice::<serde_json::Value>(&line) {
+            Ok(body) => dispatch_body(body, state, RpcScope::Public, IPC_CALLER).await,
+            Err(e) => error_body(ErrorCode::InvalidRequest, &format!("invalid request: {e}")),
+        };
+        write_line(&mut write, &response).await?;
+    }
+}
+
+async fn write_line(
+    write: &mut tokio::net::unix::OwnedWriteHalf,
+    value: &serde_json::Value,
+) -> std::io::Result<()> {
+    let mut bytes = serde_json::to_vec(value)?;
+    bytes.push(b'\n');
+    write.write_all(&bytes).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn bind_restricts_socket_permissions_and_replaces_stale_socket() {
+        let dir = std::env::temp_dir().join(format!("burst-ipc-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("node.ipc");
+
+        let first = bind(&path, 0o600).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        drop(first);
+
+        // The socket file outlives the listener; binding again replaces it.
+        let _second = bind(&path, 0o660).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o660);
+
+        let regular = dir.join("not-a-socket");
+        std::fs::write(&regular, b"keep me").unwrap();
+        assert!(bind(&regular, 0o600).is_err());
+        assert_eq!(std::fs::read(&regular).unwrap(), b"keep me");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
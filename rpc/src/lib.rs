@@ -1,20 +1,40 @@
 //! JSON-RPC server for the BURST node.
 //!
 //! Provides endpoints for:
-//! - Account info, balances (BRN + TRST), history, and pending
+//! - Account info, balances (BRN + TRST), history, wallet state history, and
+//!   pending
 //! - Transaction submission (burn, send, split, merge)
-//! - Block queries (single and batch)
-//! - Work generation
-//! - Verification status
+//! - Block queries (single and batch) and light-client block proofs
+//! - Work generation and active difficulty
+//! - Verification status, verifier selection proofs, endorsement risk scores
+//!   and anonymized UHV statistics
 //! - Governance proposals, voting, and proposal details
 //! - Representative listing
-//! - Node telemetry
+//! - Node telemetry, confirmation history, container memory usage,
+//!   diagnostic counters and slow-operation reports
+//! - Representative key rotation
+//! - Peer bans
+//!
+//! Administrative actions ([`ADMIN_ACTIONS`]) are served by a separate
+//! loopback-only listener; the public listener refuses them. On Unix the
+//! public actions are also served over a local socket ([`ipc`]).
 
 pub mod error;
 pub mod handlers;
+#[cfg(unix)]
+pub mod ipc;
 pub mod pagination;
 pub mod server;
 
+#[cfg(unix)]
+pub use ipc::IpcServer;
 pub use server::{
-    BlockProcessorCallback, LedgerCacheView, ProcessResult, RateLimiter, RpcServer, RpcState,
+    AdminFuture, AdminRpcConfig, AuditEntry, AuditLog, BlockProcessorCallback, BlockProofSource,
+    CheckpointEntry, CheckpointView, CheckpointsFuture, ConfirmationEntry,
+    ConfirmationHistoryFuture, ConfirmationHistorySnapshot, ConfirmationHistoryView, ContainerView,
+    ContainersFuture, DevAdmin, DevClock, DirectMessageFuture, DirectMessageRelay, ForkEntry,
+    ForkHistory, ForkOutcome, IpcConfig, LedgerCacheView, PeerAdmin, PeerBan, ProcessResult,
+    QuorumFuture, QuorumSnapshot, QuorumView, RateLimiter, RepresentativeKeyAdmin,
+    RepresentativeRotation, RpcScope, RpcServer, RpcState, SyncAdmin, TrstValueFuture,
+    TrstValueView, TxState, TxStatus, TxStatusView, ADMIN_ACTIONS,
 };
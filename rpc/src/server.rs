@@ -1,23 +1,29 @@
 //! Axum-based JSON-RPC server with action-based dispatch.
 
-use crate::error::RpcError;
+use crate::error::{RpcError, RpcErrorObject};
 use crate::handlers;
 
 use axum::{
     extract::{ConnectInfo, State},
-    http::{header, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     response::IntoResponse,
     routing::{get, post},
     Json, Router,
 };
 use burst_brn::BrnEngine;
-use burst_consensus::RepWeightCache;
+use burst_consensus::{ActiveElections, RepCrawler, RepWeightCache};
+use burst_crypto::DirectMessage;
+use burst_ledger::{BlockProof, Checkpoint};
 use burst_store::account::AccountStore;
+use burst_store::alias::AliasStore;
 use burst_store::block::BlockStore;
 use burst_store::governance::GovernanceStore;
 use burst_store::verification::VerificationStore;
-use burst_store::{FrontierStore, PendingStore};
-use burst_types::{ProtocolParams, WalletAddress};
+use burst_store::{FrontierStore, PendingStore, RepWeightStore, StateTreeStore};
+use burst_types::{
+    BlockHash, ContainerUsage, ErrorCode, NetworkId, ProtocolParams, Timestamp, TxHash,
+    WalletAddress,
+};
 
 /// Trait for O(1) ledger counter lookups. Implemented by the node's
 /// `LedgerCache` and injected into `RpcState` to break the circular
@@ -31,9 +37,13 @@ use burst_work::WorkGenerator;
 use prometheus::{Encoder, Registry, TextEncoder};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::net::SocketAddr;
+use std::future::Future;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
+use subtle::ConstantTimeEq;
 use tracing::{info, warn};
 
 /// Simple token-bucket rate limiter keyed by client IP.
@@ -100,13 +110,497 @@ pub enum ProcessResult {
 /// The node provides a concrete implementation that calls its `BlockProcessor`.
 /// This indirection breaks the `rpc → node → rpc` circular dependency.
 pub trait BlockProcessorCallback: Send + Sync {
-    fn process_block(&self, block_bytes: &[u8]) -> Result<ProcessResult, String>;
+    fn process_block(&self, block_bytes: &[u8]) -> Result<ProcessResult, RpcError>;
+
+    /// Hash of the protocol parameters currently in force, once governance
+    /// has changed them since start-up. `None` means `RpcState::params`.
+    fn params_hash(&self) -> Option<BlockHash> {
+        None
+    }
+
+    /// The protocol parameters currently in force, once governance has
+    /// changed them since start-up. `None` means `RpcState::params`.
+    fn params(&self) -> Option<ProtocolParams> {
+        None
+    }
+}
+
+/// Signed notice that a node moved its votes to a new representative key.
+///
+/// Both keys sign the rotation digest, so delegators can check that the
+/// old representative vouches for the new one before re-delegating.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RepresentativeRotation {
+    pub previous: WalletAddress,
+    pub representative: WalletAddress,
+    pub rotated_at: u64,
+    /// Hex signature by the previous key.
+    pub previous_signature: String,
+    /// Hex signature by the new key.
+    pub signature: String,
+}
+
+/// Future returned by node administration hooks.
+pub type AdminFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, String>> + Send + 'a>>;
+
+/// Hook for rotating the node's representative key at runtime.
+///
+/// Like [`BlockProcessorCallback`], the node provides the implementation.
+pub trait RepresentativeKeyAdmin: Send + Sync {
+    /// Replace the representative key with a fresh one, re-encrypting the
+    /// key file under `password` (which must unlock the current key), and
+    /// start voting with it immediately.
+    fn rotate_representative_key<'a>(
+        &'a self,
+        password: &'a str,
+    ) -> AdminFuture<'a, RepresentativeRotation>;
+}
+
+/// An operator ban on a peer address or range.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PeerBan {
+    /// The banned range in CIDR form (`/32` or `/128` for one address).
+    pub target: String,
+    /// Unix second the ban expires at; `None` for an indefinite ban.
+    pub until: Option<u64>,
+    /// Connected peers dropped because the ban covers them.
+    pub disconnected: usize,
+}
+
+/// Hook for banning peer addresses at runtime. The node records the ban
+/// in its peer manager and peer store and drops covered connections.
+pub trait PeerAdmin: Send + Sync {
+    /// Ban `target` (an address or CIDR range) for `duration_secs`, or
+    /// indefinitely when `None`.
+    fn ban_peer<'a>(
+        &'a self,
+        target: &'a str,
+        duration_secs: Option<u64>,
+    ) -> AdminFuture<'a, PeerBan>;
+
+    /// Lift the ban on exactly `target`. Returns `false` if there was none.
+    fn unban_peer<'a>(&'a self, target: &'a str) -> AdminFuture<'a, bool>;
+}
+
+/// Hook for pushing ledger data between this node and its peers on an
+/// operator's request.
+pub trait SyncAdmin: Send + Sync {
+    /// Flood the stored block `hash` to every connected peer again.
+    /// Returns the number of peers it was sent to.
+    fn republish<'a>(&'a self, hash: &'a BlockHash) -> AdminFuture<'a, usize>;
+
+    /// Ask peers for the whole chain of `account`, so blocks missing
+    /// locally (including those behind a gap) arrive through the block
+    /// queue. Returns the number of peers asked.
+    fn bootstrap_account<'a>(&'a self, account: &'a WalletAddress) -> AdminFuture<'a, usize>;
+}
+
+/// Actions recorded in the audit log when an [`AuditLog`] is configured.
+pub const AUDITED_ACTIONS: &[&str] = &[
+    "faucet",
+    "representative_key_rotate",
+    "peer_ban",
+    "peer_unban",
+    "rep_weights_recalculate",
+    "republish",
+    "bootstrap_account",
+    "dev_clock_advance",
+    "dev_instant_confirmation",
+    "dev_governance_advance",
+];
+
+/// Actions served only by the admin listener. The public listener refuses
+/// them, and the admin listener serves nothing else.
+pub const ADMIN_ACTIONS: &[&str] = &[
+    "representative_key_rotate",
+    "peer_ban",
+    "peer_unban",
+    "rep_weights_recalculate",
+    "republish",
+    "bootstrap_account",
+    "audit_log",
+    "dev_clock_advance",
+    "dev_instant_confirmation",
+    "dev_governance_advance",
+];
+
+/// Request parameters never written to the audit log.
+const AUDIT_REDACTED_PARAMS: &[&str] = &["password", "private_key", "seed"];
+
+/// One privileged action in the audit log.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, assigned when the entry is recorded.
+    #[serde(default)]
+    pub seq: u64,
+    /// Unix seconds.
+    pub timestamp: u64,
+    /// The RPC client's address. Admin callers share one token, so this is
+    /// the only identity the server has.
+    pub caller: String,
+    pub action: String,
+    /// Request parameters, without secrets.
+    pub params: serde_json::Value,
+    /// The error returned, if the action failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Append-only record of privileged actions.
+pub trait AuditLog: Send + Sync {
+    /// Append `entry` and return its sequence number.
+    fn record(&self, entry: AuditEntry) -> AdminFuture<'_, u64>;
+
+    /// Up to `limit` entries numbered below `before` (the newest when
+    /// `None`), newest first.
+    fn entries(&self, before: Option<u64>, limit: usize) -> AdminFuture<'_, Vec<AuditEntry>>;
+}
+
+/// How a recorded fork was settled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForkOutcome {
+    /// The election for the contested slot has not confirmed yet.
+    Pending,
+    /// The block already in the ledger was confirmed.
+    ExistingWon,
+    /// The competing block was confirmed and replaced the existing one.
+    ForkWon,
+    /// A third block for the same slot was confirmed.
+    Superseded,
+}
+
+impl ForkOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ForkOutcome::Pending => "pending",
+            ForkOutcome::ExistingWon => "existing_won",
+            ForkOutcome::ForkWon => "fork_won",
+            ForkOutcome::Superseded => "superseded",
+        }
+    }
+}
+
+/// One fork: two blocks from the same account competing for one slot.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ForkEntry {
+    /// Position in the fork table, assigned when the fork is recorded.
+    #[serde(default)]
+    pub seq: u64,
+    /// The account that signed both blocks.
+    pub account: String,
+    /// The block both compete to follow (zero for competing open blocks).
+    pub root: String,
+    /// The block already in the ledger when the fork was seen.
+    pub existing: String,
+    /// The competing block.
+    pub fork: String,
+    /// Unix seconds.
+    pub detected_at: u64,
+    pub outcome: ForkOutcome,
+    /// The block confirmed for the slot, once resolved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub winner: Option<String>,
+    /// Unix seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<u64>,
+}
+
+/// Persistent record of detected forks.
+pub trait ForkHistory: Send + Sync {
+    /// Up to `limit` forks numbered below `before` (the newest when
+    /// `None`), newest first, only those by `account` if given.
+    fn forks<'a>(
+        &'a self,
+        before: Option<u64>,
+        limit: usize,
+        account: Option<&'a str>,
+    ) -> AdminFuture<'a, Vec<ForkEntry>>;
+}
+
+/// Future returned by [`DirectMessageRelay`].
+pub type DirectMessageFuture<'a, T> =
+    Pin<Box<dyn Future<Output = Result<T, RpcError>> + Send + 'a>>;
+
+/// Hook for the node's direct message mailbox, which holds encrypted
+/// messages for wallets in memory and relays them to peers.
+pub trait DirectMessageRelay: Send + Sync {
+    /// Hold `message` for its recipient and relay it. Returns the number
+    /// of peers it was sent to, zero for a message already held.
+    fn submit(&self, message: DirectMessage) -> DirectMessageFuture<'_, usize>;
+
+    /// Unexpired messages held for `recipient`, oldest first.
+    fn fetch<'a>(
+        &'a self,
+        recipient: &'a WalletAddress,
+    ) -> DirectMessageFuture<'a, Vec<DirectMessage>>;
+}
+
+/// The node's logical clock after a dev-network clock change.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DevClock {
+    /// Seconds the logical clock runs ahead of wall-clock time.
+    pub offset_secs: u64,
+    /// Current logical time in Unix seconds.
+    pub now: u64,
+}
+
+/// Dev-network controls. The node only provides them on dev networks.
+pub trait DevAdmin: Send + Sync {
+    /// Current logical time.
+    fn now(&self) -> Timestamp;
+
+    /// Move the node's logical clock `secs` forward.
+    fn advance_clock(&self, secs: u64) -> AdminFuture<'_, DevClock>;
+
+    /// Turn confirming accepted blocks without elections on or off.
+    fn set_instant_confirmation(&self, enabled: bool) -> AdminFuture<'_, bool>;
+
+    /// Move a governance proposal into its next phase. Returns the new
+    /// phase.
+    fn force_governance_phase(&self, proposal: TxHash) -> AdminFuture<'_, String>;
+}
+
+/// The node's online weight and quorum figures at one instant.
+#[derive(Clone, Debug, Default)]
+pub struct QuorumSnapshot {
+    /// Live online weight from the most recent sample.
+    pub online_weight: u128,
+    /// Median of recent online weight samples.
+    pub trended_weight: u128,
+    /// Floor below which online weight is never assumed to drop.
+    pub minimum_weight: u128,
+    /// Weight a block needs to be confirmed.
+    pub quorum_delta: u128,
+    /// Number of online weight samples held.
+    pub sample_count: usize,
+}
+
+/// Future returned by [`QuorumView::quorum`].
+pub type QuorumFuture<'a> = Pin<Box<dyn Future<Output = QuorumSnapshot> + Send + 'a>>;
+
+/// Read access to the node's online weight tracker, which lives in
+/// `burst-node` and so cannot be shared with the RPC crate directly.
+pub trait QuorumView: Send + Sync {
+    fn quorum(&self) -> QuorumFuture<'_>;
+}
+
+/// Future returned by [`TrstValueView::trst_value`].
+pub type TrstValueFuture<'a> = Pin<Box<dyn Future<Output = Option<u128>> + Send + 'a>>;
+
+/// Read access to the node's TRST engine, which tracks the per-token
+/// provenance needed to value a balance under the network's decay mode.
+pub trait TrstValueView: Send + Sync {
+    /// Decay-adjusted transferable TRST held by `account` at `now`, or
+    /// `None` if the engine doesn't track the account.
+    fn trst_value<'a>(&'a self, account: &'a WalletAddress, now: Timestamp) -> TrstValueFuture<'a>;
+}
+
+/// One recorded confirmation.
+#[derive(Clone, Debug)]
+pub struct ConfirmationEntry {
+    pub hash: BlockHash,
+    pub account: WalletAddress,
+    pub amount: u128,
+    pub tally: u128,
+    pub duration_ms: u64,
+    /// Unix seconds.
+    pub confirmed_at: u64,
+}
+
+/// Recent confirmations and duration statistics over the whole history.
+#[derive(Clone, Debug, Default)]
+pub struct ConfirmationHistorySnapshot {
+    /// Newest first.
+    pub confirmations: Vec<ConfirmationEntry>,
+    /// Confirmations the statistics cover.
+    pub count: usize,
+    pub average_ms: u64,
+    pub p95_ms: u64,
+}
+
+/// Future returned by [`ConfirmationHistoryView::confirmation_history`].
+pub type ConfirmationHistoryFuture<'a> =
+    Pin<Box<dyn Future<Output = ConfirmationHistorySnapshot> + Send + 'a>>;
+
+/// Read access to the node's confirmation history.
+pub trait ConfirmationHistoryView: Send + Sync {
+    /// Up to `count` recent confirmations plus the history statistics.
+    fn confirmation_history(&self, count: usize) -> ConfirmationHistoryFuture<'_>;
+}
+
+/// Future returned by [`ContainerView::containers`].
+pub type ContainersFuture<'a> =
+    Pin<Box<dyn Future<Output = Vec<(&'static str, ContainerUsage)>> + Send + 'a>>;
+
+/// Sizes of the node's in-memory containers, each reported through its
+/// [`MemoryAccounting`](burst_types::MemoryAccounting) implementation.
+pub trait ContainerView: Send + Sync {
+    /// `(name, usage)` per container, in a stable order.
+    fn containers(&self) -> ContainersFuture<'_>;
+}
+
+/// A stored checkpoint with the weight that signed it under the node's
+/// current representative weights.
+#[derive(Clone, Debug)]
+pub struct CheckpointEntry {
+    pub checkpoint: Checkpoint,
+    pub weight: u128,
+    /// Whether `weight` reaches the node's current quorum.
+    pub quorum: bool,
+}
+
+/// Future returned by [`CheckpointView::checkpoints`].
+pub type CheckpointsFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<Vec<CheckpointEntry>, String>> + Send + 'a>>;
+
+/// Read access to the node's checkpoint store, weighed against the online
+/// weight tracker that lives in `burst-node`.
+pub trait CheckpointView: Send + Sync {
+    /// Up to `count` checkpoints, highest cemented count first.
+    fn checkpoints(&self, count: usize) -> CheckpointsFuture<'_>;
+}
+
+/// Builds light-client proofs from the node's ledger and final vote
+/// archive.
+pub trait BlockProofSource: Send + Sync {
+    /// Proof for `hash`, or why none can be built (unknown block, no final
+    /// votes stored, ...).
+    fn block_proof(&self, hash: &BlockHash) -> Result<BlockProof, String>;
+}
+
+/// Where a submitted block is on its way into the ledger.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxState {
+    /// Waiting in the block queue, or for a missing dependency.
+    Queued,
+    /// Applied to the ledger, not yet confirmed.
+    Accepted,
+    /// Confirmed by an election.
+    Confirmed,
+    /// Confirmation height has passed the block. Final.
+    Cemented,
+    /// Failed validation. Final unless resubmitted.
+    Rejected,
+    /// Discarded without being confirmed — queue overflow or a lost fork.
+    Dropped,
+}
+
+impl TxState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TxState::Queued => "queued",
+            TxState::Accepted => "accepted",
+            TxState::Confirmed => "confirmed",
+            TxState::Cemented => "cemented",
+            TxState::Rejected => "rejected",
+            TxState::Dropped => "dropped",
+        }
+    }
+
+    /// Whether the block will not move on from this state by itself.
+    pub fn is_terminal(self) -> bool {
+        matches!(
+            self,
+            TxState::Cemented | TxState::Rejected | TxState::Dropped
+        )
+    }
+}
+
+/// Last recorded state of a submitted block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxStatus {
+    pub state: TxState,
+    /// Why the block is rejected, dropped or still queued, when known.
+    pub reason: Option<String>,
+    /// Unix seconds.
+    pub updated_at: u64,
+}
+
+/// Status of blocks submitted to this node, for `tx_status`.
+pub trait TxStatusView: Send + Sync {
+    /// `None` when the node has not tracked `hash`, or has forgotten it.
+    fn tx_status(&self, hash: &BlockHash) -> Option<TxStatus>;
+}
+
+/// Which set of actions a listener serves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RpcScope {
+    /// Read and submit actions, on all interfaces.
+    Public,
+    /// The actions in [`ADMIN_ACTIONS`], on loopback only.
+    Admin,
+}
+
+impl RpcScope {
+    /// Whether a listener with this scope serves `action`.
+    pub fn serves(self, action: &str) -> bool {
+        ADMIN_ACTIONS.contains(&action) == (self == RpcScope::Admin)
+    }
+}
+
+/// Settings for the admin listener.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdminRpcConfig {
+    /// Whether to start the admin listener.
+    pub enabled: bool,
+    /// Loopback port the admin listener binds to.
+    pub port: u16,
+    /// Bearer token required in the `Authorization` header. When unset,
+    /// any local process may call admin actions.
+    pub auth_token: Option<String>,
+}
+
+impl Default for AdminRpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            port: 7079,
+            auth_token: None,
+        }
+    }
+}
+
+/// Settings for the IPC socket (Unix only).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IpcConfig {
+    /// Whether to serve RPC on a Unix socket.
+    pub enabled: bool,
+    /// Socket path. `None` places `burst.ipc` in the data directory.
+    pub path: Option<PathBuf>,
+    /// Permission bits applied to the socket file. Only users who can
+    /// write to the socket can connect.
+    pub mode: u32,
+}
+
+impl Default for IpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            mode: 0o600,
+        }
+    }
 }
 
 /// Top-level RPC server handle.
 pub struct RpcServer {
     pub port: u16,
     pub state: Arc<RpcState>,
+    pub scope: RpcScope,
+    /// Bearer token callers must present. `None` accepts every caller.
+    pub auth_token: Option<String>,
+}
+
+/// Per-listener state handed to the axum handlers.
+#[derive(Clone)]
+struct Listener {
+    state: Arc<RpcState>,
+    scope: RpcScope,
+    auth_token: Option<Arc<str>>,
 }
 
 /// Shared state accessible by all handlers.
@@ -128,12 +622,24 @@ pub struct RpcState {
     pub pending_store: Arc<dyn PendingStore + Send + Sync>,
     /// Frontier storage (account chain heads).
     pub frontier_store: Arc<dyn FrontierStore + Send + Sync>,
+    /// Account-state tree for `account_state_root` and
+    /// `account_state_proof`.
+    pub state_tree_store: Arc<dyn StateTreeStore + Send + Sync>,
+    /// Representative weights and the online weight samples behind
+    /// `online_weight_history`.
+    pub rep_weight_store: Arc<dyn RepWeightStore + Send + Sync>,
     /// Verification data storage.
     pub verification_store: Arc<dyn VerificationStore + Send + Sync>,
     /// Governance data storage.
     pub governance_store: Arc<dyn GovernanceStore + Send + Sync>,
+    /// Alias registry for `alias_resolve` and `alias_reverse`.
+    pub alias_store: Arc<dyn AliasStore + Send + Sync>,
     /// Governance engine (shared with the node) for live proposal state.
     pub governance_engine: Option<Arc<tokio::sync::Mutex<burst_governance::GovernanceEngine>>>,
+    /// Sealed-ballot modes and commitments kept beside the governance engine.
+    pub governance_ballots: Option<Arc<tokio::sync::Mutex<burst_governance::BallotBox>>>,
+    /// Protocol treasury (shared with the node).
+    pub treasury: Option<Arc<tokio::sync::Mutex<burst_governance::Treasury>>>,
     /// BRN computation engine (shared with the node).
     pub brn_engine: Arc<tokio::sync::Mutex<BrnEngine>>,
     /// Cached representative weights (shared with the node).
@@ -142,11 +648,20 @@ pub struct RpcState {
     pub work_generator: Arc<WorkGenerator>,
     /// Protocol parameters.
     pub params: Arc<ProtocolParams>,
+    /// The network this node runs on. Version 3 blocks built by the
+    /// node's RPC actions are bound to it.
+    pub network: NetworkId,
+    /// Load-based difficulty tracker (shared with the node) for
+    /// `active_difficulty`. `None` reports a multiplier of 1.
+    pub difficulty_adjuster: Option<Arc<tokio::sync::Mutex<burst_work::DifficultyAdjuster>>>,
     /// Block processor callback — the node injects a concrete implementation.
     pub block_processor: Arc<dyn BlockProcessorCallback>,
     /// Online representatives, updated by the peer manager.
     /// Each entry is (address, voting_weight).
     pub online_reps: Arc<std::sync::RwLock<Vec<(WalletAddress, u128)>>>,
+    /// Representative crawler (shared with the node) for vote latency,
+    /// uptime and last-vote figures. `None` falls back to `online_reps`.
+    pub rep_crawler: Option<Arc<tokio::sync::Mutex<RepCrawler>>>,
     /// Peer manager for connected peer count.
     pub peer_manager: Arc<tokio::sync::RwLock<burst_network::PeerManager>>,
     /// Whether the testnet faucet endpoint is enabled. Default: `false`.
@@ -157,6 +672,56 @@ pub struct RpcState {
     /// Cached ledger counters (block/account/pending counts) — O(1) lookups.
     /// Optional to avoid breaking test callers that don't provide one.
     pub ledger_cache: Option<Arc<dyn LedgerCacheView + Send + Sync>>,
+    /// Default minimum amount (raw TRST) a pending entry must carry to be
+    /// included by `pending_sweep`.
+    pub receive_minimum: u128,
+    /// Active elections (shared with the node) for `election_info` and
+    /// `confirmation_quorum`.
+    pub active_elections: Option<Arc<tokio::sync::RwLock<ActiveElections>>>,
+    /// Online weight and quorum figures. `None` in nodes without a tracker.
+    pub quorum_view: Option<Arc<dyn QuorumView>>,
+    /// Recent confirmations for `confirmation_history`. `None` when the
+    /// node does not record them.
+    pub confirmation_history: Option<Arc<dyn ConfirmationHistoryView>>,
+    /// Proof builder for `block_proof`. `None` disables the endpoint.
+    pub block_proof_source: Option<Arc<dyn BlockProofSource>>,
+    /// Progress of submitted blocks for `tx_status`. Without it the
+    /// endpoint answers from the ledger alone.
+    pub tx_status: Option<Arc<dyn TxStatusView>>,
+    /// Representative-signed checkpoints for `checkpoints`. `None`
+    /// disables the endpoint.
+    pub checkpoint_view: Option<Arc<dyn CheckpointView>>,
+    /// Representative key rotation hook. `None` when the node has no
+    /// persistent representative key file configured.
+    pub representative_key_admin: Option<Arc<dyn RepresentativeKeyAdmin>>,
+    /// Peer ban hook for `peer_ban` / `peer_unban`. `None` disables them.
+    pub peer_admin: Option<Arc<dyn PeerAdmin>>,
+    /// Hook for `republish` / `bootstrap_account`. `None` disables them.
+    pub sync_admin: Option<Arc<dyn SyncAdmin>>,
+    /// Dev-network controls for the `dev_*` actions. `None` everywhere but
+    /// on dev networks.
+    pub dev_admin: Option<Arc<dyn DevAdmin>>,
+    /// Audit log of the actions in [`AUDITED_ACTIONS`], also served by
+    /// `audit_log`. `None` records nothing.
+    pub audit_log: Option<Arc<dyn AuditLog>>,
+    /// Detected forks for `forks_recent`. `None` disables the endpoint.
+    pub fork_history: Option<Arc<dyn ForkHistory>>,
+    /// Mailbox behind `dm_send` and `dm_fetch`. `None` disables them.
+    pub direct_messages: Option<Arc<dyn DirectMessageRelay>>,
+    /// Verification orchestrator (shared with the node) for the endorsement
+    /// graph behind `endorsement_risk` and `collusion_flags`.
+    pub verification_orchestrator:
+        Option<Arc<tokio::sync::Mutex<burst_verification::VerificationOrchestrator>>>,
+    /// Decay-adjusted TRST valuation for balance reporting. `None` reports
+    /// face value only.
+    pub trst_value_view: Option<Arc<dyn TrstValueView>>,
+    /// Container sizes for `node_containers`. `None` disables the endpoint.
+    pub container_view: Option<Arc<dyn ContainerView>>,
+    /// Diagnostic counters for `stats`. `None` disables the endpoint.
+    pub stats: Option<Arc<burst_utils::Stats>>,
+    /// Recent slow operations for `node_watchdog`. `None` disables the
+    /// endpoint.
+    pub watchdog: Option<Arc<burst_utils::Watchdog>>,
 }
 
 // ── JSON-RPC envelope types ─────────────────────────────────────────────
@@ -169,13 +734,14 @@ struct RpcRequest {
     params: serde_json::Value,
 }
 
-/// Outgoing JSON-RPC response envelope.
+/// Outgoing JSON-RPC response envelope. Failures carry an
+/// [`RpcErrorObject`] in `error`.
 #[derive(Debug, Serialize)]
 struct RpcResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     result: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    error: Option<String>,
+    error: Option<RpcErrorObject>,
 }
 
 impl RpcResponse {
@@ -186,37 +752,68 @@ impl RpcResponse {
         }
     }
 
-    fn err(msg: impl Into<String>) -> Self {
+    fn err(error: RpcErrorObject) -> Self {
         Self {
             result: None,
-            error: Some(msg.into()),
+            error: Some(error),
         }
     }
 }
 
+/// Body of a request refused before dispatch.
+pub(crate) fn error_body(code: ErrorCode, msg: &str) -> serde_json::Value {
+    serde_json::json!({ "error": RpcErrorObject::new(code, msg, None) })
+}
+
 // ── Server impl ─────────────────────────────────────────────────────────
 
 impl RpcServer {
-    /// Create a server with custom shared state.
+    /// Create a public server with custom shared state.
     pub fn new(port: u16, state: Arc<RpcState>) -> Self {
-        Self { port, state }
+        Self {
+            port,
+            state,
+            scope: RpcScope::Public,
+            auth_token: None,
+        }
     }
 
-    /// Alias for `new` — create a server with custom shared state.
+    /// Alias for `new` — create a public server with custom shared state.
     pub fn with_state(port: u16, state: Arc<RpcState>) -> Self {
         Self::new(port, state)
     }
 
+    /// Create the loopback-only admin server described by `config`.
+    pub fn admin(config: &AdminRpcConfig, state: Arc<RpcState>) -> Self {
+        Self {
+            port: config.port,
+            state,
+            scope: RpcScope::Admin,
+            auth_token: config.auth_token.clone(),
+        }
+    }
+
     /// Start listening. Blocks until the server is shut down.
     pub async fn start(&self) -> Result<(), RpcError> {
-        let app = Router::new()
-            .route("/health", get(|| async { "ok" }))
-            .route("/metrics", get(metrics_handler))
-            .route("/", post(handle_rpc))
-            .with_state(Arc::clone(&self.state));
-
-        let addr = format!("0.0.0.0:{}", self.port);
-        info!("RPC server listening on {}", addr);
+        let listener = Listener {
+            state: Arc::clone(&self.state),
+            scope: self.scope,
+            auth_token: self.auth_token.as_deref().map(Arc::from),
+        };
+        let (app, addr) = match self.scope {
+            RpcScope::Public => (
+                Router::new()
+                    .route("/health", get(|| async { "ok" }))
+                    .route("/metrics", get(metrics_handler)),
+                SocketAddr::from((Ipv4Addr::UNSPECIFIED, self.port)),
+            ),
+            RpcScope::Admin => (
+                Router::new(),
+                SocketAddr::from((Ipv4Addr::LOCALHOST, self.port)),
+            ),
+        };
+        let app = app.route("/", post(handle_rpc)).with_state(listener);
+        info!(scope = ?self.scope, "RPC server listening on {}", addr);
 
         let listener = tokio::net::TcpListener::bind(&addr)
             .await
@@ -235,8 +832,8 @@ impl RpcServer {
 
 // ── Prometheus metrics endpoint ──────────────────────────────────────────
 
-async fn metrics_handler(State(state): State<Arc<RpcState>>) -> impl IntoResponse {
-    match &state.metrics_registry {
+async fn metrics_handler(State(listener): State<Listener>) -> impl IntoResponse {
+    match &listener.state.metrics_registry {
         Some(registry) => {
             let encoder = TextEncoder::new();
             let metric_families = registry.gather();
@@ -263,55 +860,136 @@ async fn metrics_handler(State(state): State<Arc<RpcState>>) -> impl IntoRespons
 
 async fn handle_rpc(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
-    State(state): State<Arc<RpcState>>,
+    State(listener): State<Listener>,
+    headers: HeaderMap,
     Json(body): Json<serde_json::Value>,
 ) -> impl IntoResponse {
+    if listener.scope == RpcScope::Admin && !addr.ip().is_loopback() {
+        warn!(ip = %addr.ip(), "admin RPC request from non-loopback address");
+        return (
+            StatusCode::FORBIDDEN,
+            Json(error_body(
+                ErrorCode::Forbidden,
+                "admin RPC is loopback only",
+            )),
+        );
+    }
+    if !authorized(&headers, listener.auth_token.as_deref()) {
+        warn!(ip = %addr.ip(), "RPC request with missing or wrong auth token");
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(error_body(ErrorCode::Unauthorized, "unauthorized")),
+        );
+    }
+    let (state, scope) = (listener.state, listener.scope);
     let client_ip = addr.ip().to_string();
     if !state.rate_limiter.check(&client_ip) {
         warn!(ip = %client_ip, "RPC rate limit exceeded");
         return (
             StatusCode::TOO_MANY_REQUESTS,
-            Json(serde_json::json!({ "error": "rate limited" })),
+            Json(error_body(ErrorCode::RateLimited, "rate limited")),
         );
     }
 
-    let response = if body.is_array() {
-        let items = match body.as_array() {
-            Some(a) => a,
-            None => {
-                return (
-                    StatusCode::BAD_REQUEST,
-                    Json(serde_json::json!({ "error": "invalid batch request" })),
-                );
+    let response = dispatch_body(body, &state, scope, &client_ip).await;
+    (StatusCode::OK, Json(response))
+}
+
+/// Dispatch a single request or a batch (a JSON array of requests) and
+/// return the matching response or array of responses. Shared by the HTTP
+/// listeners and the IPC socket.
+pub(crate) async fn dispatch_body(
+    body: serde_json::Value,
+    state: &RpcState,
+    scope: RpcScope,
+    caller: &str,
+) -> serde_json::Value {
+    match body {
+        serde_json::Value::Array(items) => {
+            let mut responses = Vec::with_capacity(items.len());
+            for item in items {
+                responses.push(dispatch_single(item, state, scope, caller).await);
             }
-        };
-        let mut responses = Vec::with_capacity(items.len());
-        for item in items {
-            let resp = dispatch_single(item.clone(), &state).await;
-            responses.push(resp);
+            serde_json::to_value(responses).unwrap_or_else(|_| serde_json::json!([]))
         }
-        serde_json::to_value(responses).unwrap_or_else(|_| serde_json::json!([]))
-    } else {
-        let resp = dispatch_single(body, &state).await;
-        serde_json::to_value(resp)
-            .unwrap_or_else(|_| serde_json::json!({"error": "serialization failed"}))
-    };
+        body => {
+            let resp = dispatch_single(body, state, scope, caller).await;
+            serde_json::to_value(resp)
+                .unwrap_or_else(|_| serde_json::json!({"error": "serialization failed"}))
+        }
+    }
+}
 
-    (StatusCode::OK, Json(response))
+/// Whether `headers` carry `Authorization: Bearer <token>`, compared in
+/// constant time. Always `true` when no token is configured.
+fn authorized(headers: &HeaderMap, token: Option<&str>) -> bool {
+    let Some(token) = token else {
+        return true;
+    };
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|given| bool::from(given.as_bytes().ct_eq(token.as_bytes())))
 }
 
 /// Parse a single JSON-RPC request and route it to the correct handler.
-async fn dispatch_single(body: serde_json::Value, state: &RpcState) -> RpcResponse {
+async fn dispatch_single(
+    body: serde_json::Value,
+    state: &RpcState,
+    scope: RpcScope,
+    caller: &str,
+) -> RpcResponse {
     let req: RpcRequest = match serde_json::from_value(body) {
         Ok(r) => r,
-        Err(e) => return RpcResponse::err(format!("invalid request: {e}")),
+        Err(e) => {
+            return RpcResponse::err(RpcErrorObject::new(
+                ErrorCode::InvalidRequest,
+                format!("invalid request: {e}"),
+                None,
+            ))
+        }
     };
+    if !scope.serves(&req.action) {
+        let message = match scope {
+            RpcScope::Public => format!("action {} is only served by the admin RPC", req.action),
+            RpcScope::Admin => format!("action {} is only served by the public RPC", req.action),
+        };
+        return RpcResponse::err(RpcErrorObject::new(ErrorCode::Forbidden, message, None));
+    }
 
+    let audit = state
+        .audit_log
+        .as_ref()
+        .filter(|_| AUDITED_ACTIONS.contains(&req.action.as_str()))
+        .map(|log| (log, redact(&req.params)));
     let result = dispatch_action(&req.action, req.params, state).await;
+    if let Some((log, params)) = audit {
+        let entry = AuditEntry {
+            seq: 0,
+            timestamp: Timestamp::now().as_secs(),
+            caller: caller.to_string(),
+            action: req.action.clone(),
+            params,
+            error: result.as_ref().err().map(|e| e.to_string()),
+        };
+        if let Err(e) = log.record(entry).await {
+            warn!(action = %req.action, "failed to record audit entry: {e}");
+        }
+    }
     match result {
         Ok(val) => RpcResponse::ok(val),
-        Err(e) => RpcResponse::err(e.to_string()),
+        Err(e) => RpcResponse::err(e.to_object()),
+    }
+}
+
+/// `params` without the fields in [`AUDIT_REDACTED_PARAMS`].
+fn redact(params: &serde_json::Value) -> serde_json::Value {
+    let mut params = params.clone();
+    if let Some(fields) = params.as_object_mut() {
+        fields.retain(|field, _| !AUDIT_REDACTED_PARAMS.contains(&field.as_str()));
     }
+    params
 }
 
 /// Route an action string to the corresponding handler.
@@ -322,25 +1000,56 @@ async fn dispatch_action(
 ) -> Result<serde_json::Value, RpcError> {
     match action {
         "account_info" => handlers::handle_account_info(params, state).await,
+        "account_limits" => handlers::handle_account_limits(params, state).await,
         "account_history" => handlers::handle_account_history(params, state).await,
         "account_balance" => handlers::handle_account_balance(params, state).await,
         "account_pending" => handlers::handle_account_pending(params, state).await,
+        "pending_sweep" => handlers::handle_pending_sweep(params, state).await,
         "account_representative" => handlers::handle_account_representative(params, state).await,
+        "account_state_history" => handlers::handle_account_state_history(params, state).await,
         "process" => handlers::handle_process(params, state).await,
         "block_info" => handlers::handle_block_info(params, state).await,
         "blocks_info" => handlers::handle_blocks_info(params, state).await,
         "pending" => handlers::handle_pending(params, state).await,
         "work_generate" => handlers::handle_work_generate(params, state).await,
+        "active_difficulty" => handlers::handle_active_difficulty(params, state).await,
+        "params_hash" => handlers::handle_params_hash(params, state).await,
         "governance_proposals" => handlers::handle_governance_proposals(params, state).await,
         "governance_vote" => handlers::handle_governance_vote(params, state).await,
         "governance_proposal_info" => {
             handlers::handle_governance_proposal_info(params, state).await
         }
+        "governance_params" => handlers::handle_governance_params(params, state).await,
+        "treasury" => handlers::handle_treasury(params, state).await,
+        "delegate_profile_publish" => {
+            handlers::handle_delegate_profile_publish(params, state).await
+        }
+        "delegates" => handlers::handle_delegates(params, state).await,
+        "alias_resolve" => handlers::handle_alias_resolve(params, state).await,
+        "alias_reverse" => handlers::handle_alias_reverse(params, state).await,
         "telemetry" => handlers::handle_telemetry(params, state).await,
         "peers" => handlers::handle_peers(params, state).await,
         "verification_status" => handlers::handle_verification_status(params, state).await,
+        "verification_selection_proof" => {
+            handlers::handle_verification_selection_proof(params, state).await
+        }
+        "endorsement_risk" => handlers::handle_endorsement_risk(params, state).await,
+        "collusion_flags" => handlers::handle_collusion_flags(params, state).await,
+        "uhv_stats" => handlers::handle_uhv_stats(params, state).await,
         "representatives" => handlers::handle_representatives(params, state).await,
         "representatives_online" => handlers::handle_representatives_online(params, state).await,
+        "representative_info" => handlers::handle_representative_info(params, state).await,
+        "confirmation_quorum" => handlers::handle_confirmation_quorum(params, state).await,
+        "confirmation_history" => handlers::handle_confirmation_history(params, state).await,
+        "node_containers" => handlers::handle_node_containers(state).await,
+        "stats" => handlers::handle_stats(params, state).await,
+        "node_watchdog" => handlers::handle_node_watchdog(state),
+        "block_proof" => handlers::handle_block_proof(params, state),
+        "tx_status" => handlers::handle_tx_status(params, state),
+        "checkpoints" => handlers::handle_checkpoints(params, state).await,
+        "account_state_root" => handlers::handle_account_state_root(state),
+        "account_state_proof" => handlers::handle_account_state_proof(params, state),
+        "election_info" => handlers::handle_election_info(params, state).await,
         "send" => handlers::handle_send(params, state).await,
         "burn" => handlers::handle_burn(params, state).await,
         "receive" => handlers::handle_receive(params, state).await,
@@ -353,10 +1062,37 @@ async fn dispatch_action(
         "send_simple" => handlers::handle_send_simple(params, state).await,
         "receive_simple" => handlers::handle_receive_simple(params, state).await,
         "change_rep_simple" => handlers::handle_change_rep_simple(params, state).await,
+        "endorse_simple" => handlers::handle_endorse_simple(params, state).await,
+        "challenge_simple" => handlers::handle_challenge_simple(params, state).await,
         "governance_propose_simple" => {
             handlers::handle_governance_propose_simple(params, state).await
         }
+        "governance_grant_simple" => handlers::handle_governance_grant_simple(params, state).await,
         "governance_vote_simple" => handlers::handle_governance_vote_simple(params, state).await,
+        "governance_commit_simple" => {
+            handlers::handle_governance_commit_simple(params, state).await
+        }
+        "governance_reveal_simple" => {
+            handlers::handle_governance_reveal_simple(params, state).await
+        }
+        "representative_key_rotate" => {
+            handlers::handle_representative_key_rotate(params, state).await
+        }
+        "peer_ban" => handlers::handle_peer_ban(params, state).await,
+        "online_weight_history" => handlers::handle_online_weight_history(params, state).await,
+        "rep_weights_recalculate" => handlers::handle_rep_weights_recalculate(params, state).await,
+        "peer_unban" => handlers::handle_peer_unban(params, state).await,
+        "republish" => handlers::handle_republish(params, state).await,
+        "bootstrap_account" => handlers::handle_bootstrap_account(params, state).await,
+        "audit_log" => handlers::handle_audit_log(params, state).await,
+        "forks_recent" => handlers::handle_forks_recent(params, state).await,
+        "dm_send" => handlers::handle_dm_send(params, state).await,
+        "dm_fetch" => handlers::handle_dm_fetch(params, state).await,
+        "dev_clock_advance" => handlers::handle_dev_clock_advance(params, state).await,
+        "dev_instant_confirmation" => {
+            handlers::handle_dev_instant_confirmation(params, state).await
+        }
+        "dev_governance_advance" => handlers::handle_dev_governance_advance(params, state).await,
         other => {
             warn!("unknown RPC action: {other}");
             Err(RpcError::InvalidRequest(format!("unknown action: {other}")))
@@ -368,6 +1104,20 @@ async fn dispatch_action(
 mod tests {
     use super::*;
 
+    #[test]
+    fn error_responses_carry_the_stable_code() {
+        let store_err = RpcError::from(burst_store::StoreError::NotFound("acct".into()));
+        let response = RpcResponse::err(store_err.to_object());
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["error"]["message"], "store error: not found: acct");
+        assert_eq!(json["error"]["code"], 3000);
+        assert_eq!(json["error"]["data"]["category"], "not_found");
+        assert!(json.get("result").is_none());
+
+        let ok = serde_json::to_value(RpcResponse::ok(serde_json::json!(1))).unwrap();
+        assert!(ok.get("error").is_none());
+    }
+
     #[test]
     fn rate_limiter_allows_first_request() {
         let limiter = RateLimiter::new(10);
@@ -420,6 +1170,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn scopes_split_admin_actions_from_public_ones() {
+        assert!(RpcScope::Public.serves("account_info"));
+        assert!(RpcScope::Public.serves("process"));
+        assert!(!RpcScope::Public.serves("peer_ban"));
+        assert!(!RpcScope::Public.serves("republish"));
+        assert!(!RpcScope::Public.serves("bootstrap_account"));
+        assert!(RpcScope::Admin.serves("peer_ban"));
+        assert!(!RpcScope::Admin.serves("account_info"));
+        for action in AUDITED_ACTIONS.iter().filter(|a| **a != "faucet") {
+            assert!(RpcScope::Admin.serves(action), "{action} should be admin");
+        }
+    }
+
+    #[test]
+    fn auth_token_must_match_bearer_header() {
+        let mut headers = HeaderMap::new();
+        assert!(authorized(&headers, None));
+        assert!(!authorized(&headers, Some("s3cret")));
+        headers.insert(header::AUTHORIZATION, "Bearer wrong".parse().unwrap());
+        assert!(!authorized(&headers, Some("s3cret")));
+        headers.insert(header::AUTHORIZATION, "Bearer s3cre".parse().unwrap());
+        assert!(!authorized(&headers, Some("s3cret")));
+        headers.insert(header::AUTHORIZATION, "Bearer s3cret".parse().unwrap());
+        assert!(authorized(&headers, Some("s3cret")));
+    }
+
     #[test]
     fn rate_limiter_single_request_exactly_at_limit() {
         let limiter = RateLimiter::new(1);
@@ -1,7 +1,7 @@
 //! Account storage trait.
 
 use crate::StoreError;
-use burst_types::{BlockHash, Timestamp, WalletAddress, WalletState};
+use burst_types::{BlockHash, StateTransition, Timestamp, WalletAddress, WalletState};
 use serde::{Deserialize, Serialize};
 
 /// Per-account information stored in the ledger.
@@ -42,6 +42,19 @@ pub trait AccountStore {
     fn iter_accounts(&self) -> Result<Vec<AccountInfo>, StoreError>;
     fn iter_verified_accounts(&self) -> Result<Vec<AccountInfo>, StoreError>;
 
+    /// Append a validated wallet state transition to an account's history.
+    fn put_state_transition(
+        &self,
+        address: &WalletAddress,
+        transition: &StateTransition,
+    ) -> Result<(), StoreError>;
+
+    /// An account's wallet state transitions, oldest first.
+    fn get_state_transitions(
+        &self,
+        address: &WalletAddress,
+    ) -> Result<Vec<StateTransition>, StoreError>;
+
     /// Count verified accounts without allocating the full result set.
     fn verified_account_count(&self) -> Result<u64, StoreError> {
         self.iter_verified_accounts().map(|v| v.len() as u64)
@@ -0,0 +1,44 @@
+//! Alias registry storage trait.
+//!
+//! Aliases are human-readable names registered on-chain by Alias blocks.
+//! The registry is indexed both ways: name to record for resolution, and
+//! account to its current name for reverse lookup.
+
+use crate::StoreError;
+use burst_types::{Timestamp, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+/// A registered alias.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AliasRecord {
+    pub name: String,
+    pub owner: WalletAddress,
+    /// When the current owner first registered the name.
+    pub registered_at: Timestamp,
+    /// When the name lapses unless renewed.
+    pub expires_at: Timestamp,
+}
+
+impl AliasRecord {
+    /// Whether the registration has lapsed at `now`.
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        now.as_secs() >= self.expires_at.as_secs()
+    }
+}
+
+/// Name and owner indexes of the alias registry.
+///
+/// Records are kept after they expire so the previous owner's grace period
+/// can be enforced; callers check [`AliasRecord::is_expired`].
+pub trait AliasStore {
+    /// Store `record`, releasing the owner's previous name and removing the
+    /// name from its previous owner's reverse entry.
+    fn put_alias(&self, record: &AliasRecord) -> Result<(), StoreError>;
+
+    /// The record for `name`, expired or not.
+    fn get_alias(&self, name: &str) -> Result<Option<AliasRecord>, StoreError>;
+
+    /// The record for the name `account` currently holds.
+    fn get_account_alias(&self, account: &WalletAddress)
+        -> Result<Option<AliasRecord>, StoreError>;
+}
@@ -0,0 +1,25 @@
+//! Audit log storage trait.
+//!
+//! The audit log records privileged operator actions for later review.
+//! Entries are only ever appended; nothing in the node deletes or rewrites
+//! them.
+
+use crate::StoreError;
+
+/// Append-only storage of audit entries, numbered from 1 in the order they
+/// were written.
+///
+/// Entries are stored as opaque serialized bytes so the store does not
+/// depend on the entry format.
+pub trait AuditStore {
+    /// Append `entry` and return its sequence number.
+    fn append_audit_entry(&self, entry: &[u8]) -> Result<u64, StoreError>;
+
+    /// Up to `limit` entries numbered below `before` (or the newest entries
+    /// when `before` is `None`), newest first.
+    fn audit_entries(
+        &self,
+        before: Option<u64>,
+        limit: usize,
+    ) -> Result<Vec<(u64, Vec<u8>)>, StoreError>;
+}
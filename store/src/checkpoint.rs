@@ -0,0 +1,35 @@
+//! Checkpoint storage trait.
+//!
+//! A checkpoint is a digest of the cemented ledger state signed by
+//! representatives. Several digests may be seen for the same cemented
+//! count while nodes disagree, so checkpoints are keyed by both.
+
+use crate::StoreError;
+use burst_types::BlockHash;
+
+/// Persistent storage of checkpoints, keyed by cemented count and digest.
+///
+/// Checkpoints are stored as opaque serialized bytes so the store does not
+/// depend on the checkpoint format.
+pub trait CheckpointStore {
+    /// Store a checkpoint, replacing any earlier one with the same key.
+    fn put_checkpoint(
+        &self,
+        cemented_count: u64,
+        digest: &BlockHash,
+        checkpoint: &[u8],
+    ) -> Result<(), StoreError>;
+
+    /// The checkpoint stored under `cemented_count` and `digest`.
+    fn get_checkpoint(
+        &self,
+        cemented_count: u64,
+        digest: &BlockHash,
+    ) -> Result<Vec<u8>, StoreError>;
+
+    /// Up to `limit` checkpoints, highest cemented count first.
+    fn recent_checkpoints(&self, limit: usize) -> Result<Vec<Vec<u8>>, StoreError>;
+
+    /// Number of stored checkpoints.
+    fn checkpoint_count(&self) -> Result<u64, StoreError>;
+}
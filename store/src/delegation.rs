@@ -7,11 +7,16 @@ pub struct DelegationRecord {
     pub delegator: WalletAddress,
     pub delegate: WalletAddress,
     pub delegation_public_key: [u8; 32],
+    /// Key generation: 1 for the first delegation key, incremented on every
+    /// rotation.
+    pub generation: u32,
     pub created_at: Timestamp,
     pub revoked: bool,
 }
 
 pub trait DelegationStore {
+    /// Store a delegation record. Replaces the delegator's previous record,
+    /// and its previous key stops resolving via `get_delegation_by_pubkey`.
     fn put_delegation(&self, record: &DelegationRecord) -> Result<(), StoreError>;
     fn get_delegation_by_delegator(
         &self,
@@ -22,4 +27,22 @@ pub trait DelegationStore {
         pubkey: &[u8; 32],
     ) -> Result<Option<DelegationRecord>, StoreError>;
     fn revoke_delegation(&self, delegator: &WalletAddress) -> Result<(), StoreError>;
+
+    /// Replace the active delegation's key with the next generation.
+    fn rotate_delegation_key(
+        &self,
+        delegator: &WalletAddress,
+        new_key: [u8; 32],
+        rotated_at: Timestamp,
+    ) -> Result<DelegationRecord, StoreError> {
+        let mut record = self
+            .get_delegation_by_delegator(delegator)?
+            .filter(|r| !r.revoked)
+            .ok_or_else(|| StoreError::NotFound(delegator.to_string()))?;
+        record.delegation_public_key = new_key;
+        record.generation += 1;
+        record.created_at = rotated_at;
+        self.put_delegation(&record)?;
+        Ok(record)
+    }
 }
@@ -1,3 +1,4 @@
+use burst_types::{ErrorCode, HasErrorCode};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -17,3 +18,15 @@ pub enum StoreError {
     #[error("database is corrupted: {0}")]
     Corruption(String),
 }
+
+impl HasErrorCode for StoreError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::NotFound(_) => ErrorCode::NotFound,
+            Self::Duplicate(_) => ErrorCode::Duplicate,
+            Self::Backend(_) => ErrorCode::Storage,
+            Self::Serialization(_) => ErrorCode::Serialization,
+            Self::Corruption(_) => ErrorCode::StorageCorrupted,
+        }
+    }
+}
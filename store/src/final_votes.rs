@@ -0,0 +1,34 @@
+//! Final vote storage trait.
+//!
+//! Final votes are what a representative sends once it considers a block
+//! confirmed. Keeping them across restarts lets a node answer `confirm_req`
+//! for already-finalized roots with the original signed votes instead of
+//! provoking a fresh election.
+
+use crate::StoreError;
+use burst_types::{BlockHash, WalletAddress};
+
+/// Bounded persistent storage of final votes, keyed by root and voter.
+///
+/// Votes are stored as opaque serialized bytes so the store does not depend
+/// on the wire format. A vote covering several roots is stored once per root.
+pub trait FinalVoteStore {
+    /// Store `vote` from `voter` for `root`, replacing any earlier vote from
+    /// the same voter for that root.
+    fn put_final_vote(
+        &self,
+        root: &BlockHash,
+        voter: &WalletAddress,
+        vote: &[u8],
+    ) -> Result<(), StoreError>;
+
+    /// All stored votes for `root`.
+    fn get_final_votes(&self, root: &BlockHash) -> Result<Vec<Vec<u8>>, StoreError>;
+
+    /// Number of stored `(root, voter)` entries.
+    fn final_vote_count(&self) -> Result<u64, StoreError>;
+
+    /// Delete the oldest entries until at most `max_entries` remain.
+    /// Returns the number of entries removed.
+    fn trim_final_votes(&self, max_entries: u64) -> Result<u64, StoreError>;
+}
@@ -0,0 +1,23 @@
+//! Fork record storage trait.
+//!
+//! Every fork the node detects — two blocks competing for the same slot in
+//! an account chain — is recorded with how it was resolved, so wallet
+//! providers can judge how often a counterparty attempts a double spend.
+
+use crate::StoreError;
+
+/// Fork records, numbered from 1 in the order they were detected.
+///
+/// Records are stored as opaque serialized bytes so the store does not
+/// depend on the record format.
+pub trait ForkStore {
+    /// Append `record` and return its sequence number.
+    fn append_fork(&self, record: &[u8]) -> Result<u64, StoreError>;
+
+    /// Replace the record numbered `seq`, once its fork is resolved.
+    fn update_fork(&self, seq: u64, record: &[u8]) -> Result<(), StoreError>;
+
+    /// Up to `limit` records numbered below `before` (or the newest records
+    /// when `before` is `None`), newest first.
+    fn forks(&self, before: Option<u64>, limit: usize) -> Result<Vec<(u64, Vec<u8>)>, StoreError>;
+}
@@ -39,4 +39,15 @@ pub trait GovernanceStore {
 
     /// Get the current constitution text.
     fn get_constitution(&self) -> Result<Vec<u8>, StoreError>;
+
+    /// Store a delegate's published profile, replacing any earlier one.
+    fn put_delegate_profile(&self, delegate: &WalletAddress, data: &[u8])
+        -> Result<(), StoreError>;
+
+    /// Get a delegate's published profile.
+    fn get_delegate_profile(&self, delegate: &WalletAddress)
+        -> Result<Option<Vec<u8>>, StoreError>;
+
+    /// List every published delegate profile, ordered by delegate address.
+    fn list_delegate_profiles(&self) -> Result<Vec<Vec<u8>>, StoreError>;
 }
@@ -4,30 +4,49 @@
 //! these traits. The rest of the codebase depends only on the traits.
 
 pub mod account;
+pub mod alias;
+pub mod audit;
 pub mod block;
 pub mod brn;
+pub mod checkpoint;
 pub mod delegation;
 pub mod error;
+pub mod final_votes;
+pub mod fork;
 pub mod frontier;
 pub mod governance;
 pub mod merger_graph;
 pub mod meta;
 pub mod peer;
 pub mod pending;
+pub mod quorum_certificate;
+pub mod recovery;
 pub mod rep_weights;
+pub mod state_tree;
 pub mod transaction;
 pub mod trst_index;
 pub mod verification;
 
+pub use alias::{AliasRecord, AliasStore};
+pub use audit::AuditStore;
 pub use brn::BrnStore;
+pub use checkpoint::CheckpointStore;
 pub use delegation::{DelegationRecord, DelegationStore};
 pub use error::StoreError;
+pub use final_votes::FinalVoteStore;
+pub use fork::ForkStore;
 pub use frontier::FrontierStore;
 pub use meta::MetaStore;
-pub use peer::PeerStore;
+pub use peer::{PeerRecord, PeerStore};
 pub use pending::{PendingInfo, PendingStore};
-pub use rep_weights::RepWeightStore;
+pub use quorum_certificate::QuorumCertificateStore;
+pub use recovery::{RecoveryConfig, RecoveryStore};
+pub use rep_weights::{RepWeightStore, WeightSample};
+pub use state_tree::{AccountStateProof, StateTreeStore};
 pub use trst_index::TrstIndexStore;
+pub use verification::{
+    VerificationRecord, VerificationRecordOutcome, VerificationStore, VerifierSelection,
+};
 
 /// Opaque transaction handle for atomic multi-store operations.
 /// Implementations can downcast to their specific transaction type.
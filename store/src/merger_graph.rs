@@ -22,4 +22,13 @@ pub trait MergerGraphStore {
 
     /// Retrieve merge node data.
     fn get_merge_node(&self, merge_tx: &TxHash) -> Result<Vec<u8>, StoreError>;
+
+    /// Mark an origin as revoked.
+    fn put_revoked_origin(&self, origin: &TxHash) -> Result<(), StoreError>;
+
+    /// Clear an origin's revocation mark.
+    fn delete_revoked_origin(&self, origin: &TxHash) -> Result<(), StoreError>;
+
+    /// Every revoked origin.
+    fn iter_revoked_origins(&self) -> Result<Vec<TxHash>, StoreError>;
 }
@@ -5,15 +5,67 @@
 
 use crate::StoreError;
 
+/// A cached peer's last-seen time and connection quality figures.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PeerRecord {
+    /// Unix seconds.
+    pub last_seen: u64,
+    pub uptime_secs: u64,
+    pub handshake_attempts: u32,
+    pub handshake_successes: u32,
+    pub useful_votes: u64,
+}
+
+impl PeerRecord {
+    /// Encoded size. Entries written before quality was tracked hold only
+    /// the 8-byte timestamp and decode with zeroed figures.
+    pub const ENCODED_LEN: usize = 32;
+
+    /// Little-endian encoding, timestamp first.
+    pub fn to_bytes(&self) -> [u8; Self::ENCODED_LEN] {
+        let mut out = [0u8; Self::ENCODED_LEN];
+        out[0..8].copy_from_slice(&self.last_seen.to_le_bytes());
+        out[8..16].copy_from_slice(&self.uptime_secs.to_le_bytes());
+        out[16..20].copy_from_slice(&self.handshake_attempts.to_le_bytes());
+        out[20..24].copy_from_slice(&self.handshake_successes.to_le_bytes());
+        out[24..32].copy_from_slice(&self.useful_votes.to_le_bytes());
+        out
+    }
+
+    /// Decode a stored value, or `None` if it has neither known length.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let u64_at = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap());
+        let u32_at = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+        match bytes.len() {
+            8 => Some(Self {
+                last_seen: u64_at(0),
+                ..Self::default()
+            }),
+            Self::ENCODED_LEN => Some(Self {
+                last_seen: u64_at(0),
+                uptime_secs: u64_at(8),
+                handshake_attempts: u32_at(16),
+                handshake_successes: u32_at(20),
+                useful_votes: u64_at(24),
+            }),
+            _ => None,
+        }
+    }
+}
+
 /// Trait for persisting peer addresses across restarts.
 ///
-/// Keys are peer address strings (`"ip:port"`), values are the last-seen
-/// Unix timestamp (seconds). Implementations must be safe for concurrent use
-/// from a single writer (the peer cache task).
+/// Keys are peer address strings (`"ip:port"`), values are a
+/// [`PeerRecord`] starting with the last-seen Unix timestamp (seconds).
+/// Implementations must be safe for concurrent use from a single writer
+/// (the peer cache task).
 pub trait PeerStore {
     /// Insert or update a peer's last-seen timestamp.
     fn put_peer(&self, addr: &str, timestamp: u64) -> Result<(), StoreError>;
 
+    /// Insert or update a peer with its quality figures.
+    fn put_peer_record(&self, addr: &str, record: &PeerRecord) -> Result<(), StoreError>;
+
     /// Get a peer's last-seen timestamp.
     fn get_peer(&self, addr: &str) -> Result<Option<u64>, StoreError>;
 
@@ -23,6 +75,19 @@ pub trait PeerStore {
     /// Iterate over all cached peers, returning `(address, timestamp)` pairs.
     fn iter_peers(&self) -> Result<Vec<(String, u64)>, StoreError>;
 
+    /// All cached peers with their quality figures.
+    fn iter_peer_records(&self) -> Result<Vec<(String, PeerRecord)>, StoreError>;
+
     /// Remove all peers whose timestamp is older than `cutoff_secs`.
     fn purge_older_than(&self, cutoff_secs: u64) -> Result<usize, StoreError>;
+
+    /// Record an operator ban on an address or CIDR range, expiring at
+    /// `until_secs` (`u64::MAX` for never).
+    fn put_ban(&self, target: &str, until_secs: u64) -> Result<(), StoreError>;
+
+    /// Remove a ban.
+    fn delete_ban(&self, target: &str) -> Result<(), StoreError>;
+
+    /// All recorded bans as `(target, until_secs)` pairs.
+    fn iter_bans(&self) -> Result<Vec<(String, u64)>, StoreError>;
 }
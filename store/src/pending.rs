@@ -14,6 +14,37 @@ pub struct PendingInfo {
     /// Empty if the sender wasn't tracked in the TRST engine.
     #[serde(default)]
     pub provenance: Vec<PendingProvenance>,
+    /// Memo/reference carried by the originating send block, if any.
+    pub memo: Option<Vec<u8>>,
+    /// Destination tag carried by the originating send block, if any.
+    pub destination_tag: Option<u64>,
+    /// Hash-lock terms when the entry was created by an HTLC lock block.
+    /// Such entries can only be claimed with the preimage or refunded.
+    pub htlc: Option<PendingHtlc>,
+    /// Set once the entry has gone back to its original sender (rejected,
+    /// or left unreceived past the pending TTL). Returned entries never
+    /// expire again.
+    pub returned: bool,
+}
+
+impl PendingInfo {
+    /// When this entry goes back to its sender under `ttl_secs`, or `None`
+    /// if it never does. HTLC entries have their own refund path.
+    pub fn expires_at(&self, ttl_secs: u64) -> Option<Timestamp> {
+        if ttl_secs == 0 || self.returned || self.htlc.is_some() {
+            return None;
+        }
+        Some(Timestamp::new(
+            self.timestamp.as_secs().saturating_add(ttl_secs),
+        ))
+    }
+}
+
+/// Hash-lock terms of a pending HTLC transfer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingHtlc {
+    pub hash_lock: [u8; 32],
+    pub refund_after: Timestamp,
 }
 
 /// Origin provenance for a consumed token portion, stored in pending entries.
@@ -71,4 +102,7 @@ pub trait PendingStore {
 
     /// Total number of pending receives across all accounts.
     fn pending_count(&self) -> Result<u64, StoreError>;
+
+    /// Every pending receive as `(destination, source_hash, info)`.
+    fn iter_pending(&self) -> Result<Vec<(WalletAddress, TxHash, PendingInfo)>, StoreError>;
 }
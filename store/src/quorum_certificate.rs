@@ -0,0 +1,28 @@
+//! Quorum certificate storage trait.
+//!
+//! A quorum certificate is the set of signed final votes, with the weight
+//! each representative held, that confirmed a block. Unlike the bounded
+//! final vote archive, certificates are kept for as long as the block is,
+//! so a confirmation can be audited long after the representatives that
+//! voted for it have rotated out.
+
+use crate::StoreError;
+use burst_types::BlockHash;
+
+/// Persistent storage of quorum certificates, keyed by block hash.
+///
+/// Certificates are stored as opaque serialized bytes so the store does not
+/// depend on the certificate format.
+pub trait QuorumCertificateStore {
+    /// Store the certificate for `hash`, replacing any earlier one.
+    fn put_certificate(&self, hash: &BlockHash, certificate: &[u8]) -> Result<(), StoreError>;
+
+    /// The certificate for `hash`.
+    fn get_certificate(&self, hash: &BlockHash) -> Result<Vec<u8>, StoreError>;
+
+    /// Whether a certificate is stored for `hash`.
+    fn has_certificate(&self, hash: &BlockHash) -> Result<bool, StoreError>;
+
+    /// Number of stored certificates.
+    fn certificate_count(&self) -> Result<u64, StoreError>;
+}
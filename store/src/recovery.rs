@@ -0,0 +1,28 @@
+use crate::StoreError;
+use burst_types::{PublicKey, Timestamp, WalletAddress};
+
+/// An account's designated recovery custodians.
+#[derive(Clone, Debug)]
+pub struct RecoveryConfig {
+    pub account: WalletAddress,
+    pub custodians: Vec<WalletAddress>,
+    /// Approvals required to rotate the account key.
+    pub threshold: u8,
+    /// Seconds that must pass between a recovery request and its rotation.
+    pub delay_secs: u64,
+    pub configured_at: Timestamp,
+}
+
+/// Custodian sets and rotated signing keys for social recovery.
+///
+/// An account's signing key is the key encoded in its address until a
+/// recovery rotates it; after that, blocks must be signed by the stored key.
+pub trait RecoveryStore {
+    fn put_recovery_config(&self, config: &RecoveryConfig) -> Result<(), StoreError>;
+    fn get_recovery_config(
+        &self,
+        account: &WalletAddress,
+    ) -> Result<Option<RecoveryConfig>, StoreError>;
+    fn put_signing_key(&self, account: &WalletAddress, key: &PublicKey) -> Result<(), StoreError>;
+    fn get_signing_key(&self, account: &WalletAddress) -> Result<Option<PublicKey>, StoreError>;
+}
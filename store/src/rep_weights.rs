@@ -3,6 +3,21 @@
 use crate::StoreError;
 use burst_types::WalletAddress;
 
+/// One persisted online weight sample.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WeightSample {
+    /// Unix seconds.
+    pub timestamp: u64,
+    /// Weight of the representatives seen online.
+    pub online: u128,
+    /// Trended (median) online weight. `None` for samples written before it
+    /// was recorded.
+    pub trended: Option<u128>,
+    /// Weight a block needed to be confirmed. `None` for samples written
+    /// before it was recorded.
+    pub quorum_delta: Option<u128>,
+}
+
 /// Persistent storage for representative weights and online weight samples.
 pub trait RepWeightStore {
     /// Store a representative's weight.
@@ -17,9 +32,20 @@ pub trait RepWeightStore {
     /// Get all representative weights.
     fn iter_rep_weights(&self) -> Result<Vec<(WalletAddress, u128)>, StoreError>;
 
+    /// Replace all representative weights with `weights` in one
+    /// transaction; representatives not listed are deleted.
+    fn replace_rep_weights(&self, weights: &[(WalletAddress, u128)]) -> Result<(), StoreError>;
+
     /// Store an online weight sample at the given timestamp.
     fn put_online_weight_sample(&self, timestamp: u64, weight: u128) -> Result<(), StoreError>;
 
+    /// Store a full weight sample: online and trended weight and the quorum
+    /// delta at `sample.timestamp`.
+    fn put_weight_sample(&self, sample: &WeightSample) -> Result<(), StoreError>;
+
+    /// Samples taken from `from` up to and including `to`, oldest first.
+    fn weight_samples(&self, from: u64, to: u64) -> Result<Vec<WeightSample>, StoreError>;
+
     /// Get the most recent online weight samples, up to `limit` entries.
     /// Returned in descending timestamp order (newest first).
     fn get_online_weight_samples(&self, limit: usize) -> Result<Vec<(u64, u128)>, StoreError>;
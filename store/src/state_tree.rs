@@ -0,0 +1,143 @@
+//! Account-state tree: a Merkle commitment to every account.
+//!
+//! Each account is a leaf of a binary tree keyed by the 256-bit hash of its
+//! address, read most significant bit first. The tree is compact: a subtree
+//! that holds a single account is represented by that account's leaf, so a
+//! leaf sits only as deep as needed to tell it apart from its neighbours
+//! (about log2 of the account count) and the shape depends only on the set
+//! of accounts, not on the order they were written in.
+//!
+//! The root lets two nodes compare their ledgers with one hash, and an
+//! [`AccountStateProof`] shows a single account's state against a root.
+//! A leaf commits to the account's consensus state; the confirmation height
+//! is left out because every node cements at its own pace.
+
+use crate::account::AccountInfo;
+use crate::StoreError;
+use burst_types::{BlockHash, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+/// Domain tag of leaf hashes.
+const LEAF_TAG: &[u8] = &[0];
+/// Domain tag of branch hashes.
+const BRANCH_TAG: &[u8] = &[1];
+
+/// Position of an account in the tree.
+pub fn account_key(address: &WalletAddress) -> [u8; 32] {
+    burst_crypto::blake2b_256(address.as_str().as_bytes())
+}
+
+/// Bit `depth` of `key`, most significant bit first.
+pub fn key_bit(key: &[u8; 32], depth: usize) -> u8 {
+    (key[depth / 8] >> (7 - depth % 8)) & 1
+}
+
+/// Hash of the consensus state of an account.
+pub fn account_state_hash(info: &AccountInfo) -> BlockHash {
+    let verified_at = info.verified_at.map_or(0, |t| t.as_secs());
+    BlockHash::new(burst_crypto::blake2b_256_multi(&[
+        info.address.as_str().as_bytes(),
+        &[info.state as u8],
+        &verified_at.to_be_bytes(),
+        info.head.as_bytes(),
+        &info.block_count.to_be_bytes(),
+        info.representative.as_str().as_bytes(),
+        &info.total_brn_burned.to_be_bytes(),
+        &info.total_brn_staked.to_be_bytes(),
+        &info.trst_balance.to_be_bytes(),
+        &info.expired_trst.to_be_bytes(),
+        &info.revoked_trst.to_be_bytes(),
+        &[info.epoch],
+    ]))
+}
+
+/// Hash of the leaf for the account at `key` with state `state_hash`.
+pub fn leaf_hash(key: &[u8; 32], state_hash: &BlockHash) -> BlockHash {
+    BlockHash::new(burst_crypto::blake2b_256_multi(&[
+        LEAF_TAG,
+        key,
+        state_hash.as_bytes(),
+    ]))
+}
+
+/// Hash of a branch over two subtrees. An empty subtree hashes to
+/// [`BlockHash::ZERO`].
+pub fn branch_hash(left: &BlockHash, right: &BlockHash) -> BlockHash {
+    BlockHash::new(burst_crypto::blake2b_256_multi(&[
+        BRANCH_TAG,
+        left.as_bytes(),
+        right.as_bytes(),
+    ]))
+}
+
+/// Root of a tree holding `leaves`, given as `(key, state hash)` pairs with
+/// distinct keys in any order: the root a store holding the same leaves
+/// reports, computed in memory.
+pub fn tree_root(leaves: &mut [([u8; 32], BlockHash)]) -> BlockHash {
+    leaves.sort_unstable_by_key(|(key, _)| *key);
+    subtree_root(leaves, 0)
+}
+
+/// Root of the subtree at `depth` holding `leaves`, sorted by key.
+fn subtree_root(leaves: &[([u8; 32], BlockHash)], depth: usize) -> BlockHash {
+    match leaves {
+        [] => BlockHash::ZERO,
+        [(key, state_hash)] => leaf_hash(key, state_hash),
+        _ => {
+            let split = leaves.partition_point(|(key, _)| key_bit(key, depth) == 0);
+            branch_hash(
+                &subtree_root(&leaves[..split], depth + 1),
+                &subtree_root(&leaves[split..], depth + 1),
+            )
+        }
+    }
+}
+
+/// Proof that an account has a given state under a state root.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountStateProof {
+    pub address: WalletAddress,
+    /// [`account_state_hash`] of the account.
+    pub state_hash: BlockHash,
+    /// Sibling of each node on the path from the root to the leaf; entry
+    /// `i` is the sibling at depth `i + 1`.
+    pub siblings: Vec<BlockHash>,
+}
+
+impl AccountStateProof {
+    /// The state root this proof leads to.
+    pub fn root(&self) -> BlockHash {
+        let key = account_key(&self.address);
+        self.siblings.iter().enumerate().rev().fold(
+            leaf_hash(&key, &self.state_hash),
+            |node, (depth, sibling)| {
+                if key_bit(&key, depth) == 0 {
+                    branch_hash(&node, sibling)
+                } else {
+                    branch_hash(sibling, &node)
+                }
+            },
+        )
+    }
+
+    /// Whether `info` is the account's state under `root`.
+    pub fn proves(&self, info: &AccountInfo, root: &BlockHash) -> bool {
+        info.address == self.address
+            && account_state_hash(info) == self.state_hash
+            && self.root() == *root
+    }
+}
+
+/// Access to the account-state tree. The tree is maintained by the backend
+/// whenever an account is written.
+pub trait StateTreeStore {
+    /// Root of the tree; [`BlockHash::ZERO`] when there are no accounts.
+    fn state_root(&self) -> Result<BlockHash, StoreError>;
+
+    /// Proof of an account's current state, or `None` if the account is
+    /// not in the tree.
+    fn account_proof(
+        &self,
+        address: &WalletAddress,
+    ) -> Result<Option<AccountStateProof>, StoreError>;
+}
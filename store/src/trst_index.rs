@@ -1,7 +1,7 @@
 //! TRST index storage traits for origin and expiry lookups.
 
 use crate::StoreError;
-use burst_types::{Timestamp, TxHash};
+use burst_types::{Timestamp, TxHash, WalletAddress};
 
 /// Trait for TRST secondary indexes.
 ///
@@ -10,6 +10,9 @@ use burst_types::{Timestamp, TxHash};
 ///   descended from it. Enables O(1) revocation of an entire sybil lineage.
 /// - **Expiry index**: maps `(expiry_timestamp, tx_hash)` pairs. Enables efficient
 ///   range scans to find all TRST that have expired before a given cutoff time.
+///
+/// It also holds each wallet's serialized token portfolio, which the TRST
+/// engine pages in when the wallet is not in its cache.
 pub trait TrstIndexStore {
     /// Record that `tx_hash` is derived from the given origin burn.
     fn put_origin_index(&self, origin_hash: &TxHash, tx_hash: &TxHash) -> Result<(), StoreError>;
@@ -36,4 +39,18 @@ pub trait TrstIndexStore {
     /// for matching entries since the expiry timestamp may not be known
     /// to the caller.
     fn delete_token(&self, tx_hash: &TxHash) -> Result<(), StoreError>;
+
+    /// Store a wallet's serialized token portfolio.
+    fn put_portfolio(&self, wallet: &WalletAddress, data: &[u8]) -> Result<(), StoreError>;
+
+    /// Get a wallet's serialized token portfolio.
+    fn get_portfolio(&self, wallet: &WalletAddress) -> Result<Vec<u8>, StoreError>;
+
+    /// Iterate stored portfolios in address order, returning up to `limit`
+    /// entries after `cursor` (or from the beginning if None).
+    fn iter_portfolios_paged(
+        &self,
+        cursor: Option<&WalletAddress>,
+        limit: usize,
+    ) -> Result<Vec<(WalletAddress, Vec<u8>)>, StoreError>;
 }
@@ -1,9 +1,89 @@
 //! Verification data storage trait.
 
 use crate::StoreError;
-use burst_types::WalletAddress;
+use burst_types::{Timestamp, WalletAddress};
+use serde::{Deserialize, Serialize};
 
-/// Trait for storing verification state (endorsements, votes, challenges).
+/// Inputs and outcome of one verifier draw, kept so anyone can recompute
+/// the draw and check that it was fair.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifierSelection {
+    /// The wallet whose verification the verifiers were drawn for.
+    pub target: WalletAddress,
+    /// drand round the randomness was taken from.
+    pub drand_round: u64,
+    /// The beacon randomness the draw was seeded with.
+    pub randomness: [u8; 32],
+    /// Commitment to `candidates`, in draw order.
+    pub pool_hash: [u8; 32],
+    /// Eligible verifiers the draw was made from.
+    pub candidates: Vec<WalletAddress>,
+    /// Verifiers the draw picked.
+    pub selected: Vec<WalletAddress>,
+    /// When the draw was made.
+    pub selected_at: Timestamp,
+}
+
+/// How a finished verification or challenge ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VerificationRecordOutcome {
+    /// Verifiers voted the wallet legitimate.
+    Verified,
+    /// Verifiers rejected the wallet.
+    Failed,
+    /// Abandoned after its voting rounds timed out.
+    Expired,
+    /// A challenge confirmed fraud.
+    FraudConfirmed,
+    /// A challenge was voted down.
+    ChallengeRejected,
+    /// A challenge ran out of time.
+    ChallengeExpired,
+}
+
+impl VerificationRecordOutcome {
+    /// Whether this is the outcome of a challenge rather than a first
+    /// verification.
+    pub fn is_challenge(self) -> bool {
+        matches!(
+            self,
+            Self::FraudConfirmed | Self::ChallengeRejected | Self::ChallengeExpired
+        )
+    }
+
+    /// Whether the wallet was found legitimate, or `None` when the process
+    /// ended without a decision.
+    pub fn found_legitimate(self) -> Option<bool> {
+        match self {
+            Self::Verified | Self::ChallengeRejected => Some(true),
+            Self::Failed | Self::FraudConfirmed => Some(false),
+            Self::Expired | Self::ChallengeExpired => None,
+        }
+    }
+}
+
+/// Anonymized summary of a finished verification or challenge, kept for
+/// aggregate UHV statistics. Holds no wallet, endorser or verifier addresses.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationRecord {
+    pub outcome: VerificationRecordOutcome,
+    /// Endorsements the wallet had collected.
+    pub endorsement_count: u32,
+    /// When the wallet's verification began — its cohort.
+    pub started_at: Timestamp,
+    /// When the verification or challenge concluded.
+    pub completed_at: Timestamp,
+    /// Verifier votes cast in the deciding round.
+    pub votes_cast: u32,
+    /// Votes that matched the outcome (0 when there was no decision).
+    pub votes_agreeing: u32,
+    /// Neither votes among `votes_cast`.
+    pub neither_votes: u32,
+}
+
+/// Trait for storing verification state (endorsements, votes, challenges and
+/// challenge history, verifier pool membership, verifier draws, outcome
+/// records).
 ///
 /// Endorsements and votes are keyed by `(target, actor)` composite key,
 /// enabling O(1) put/get and prefix range-scan for all actors per target.
@@ -35,4 +115,50 @@ pub trait VerificationStore {
 
     /// Get active challenge for a target wallet.
     fn get_challenge(&self, target: &WalletAddress) -> Result<Option<Vec<u8>>, StoreError>;
+
+    /// Record a verifier pool member with its locked stake.
+    fn put_verifier(&self, verifier: &WalletAddress, data: &[u8]) -> Result<(), StoreError>;
+
+    /// Get the stake record of a verifier pool member.
+    fn get_verifier(&self, verifier: &WalletAddress) -> Result<Option<Vec<u8>>, StoreError>;
+
+    /// Remove a verifier from the pool. Returns whether it was a member.
+    fn delete_verifier(&self, verifier: &WalletAddress) -> Result<bool, StoreError>;
+
+    /// All verifier pool members with their stake records, ordered by
+    /// address so every node sees the pool in the same order.
+    fn iter_verifiers(&self) -> Result<Vec<(WalletAddress, Vec<u8>)>, StoreError>;
+
+    /// Record a verifier draw. A wallet keeps one entry per drand round, so
+    /// re-votes and challenges add to its audit trail.
+    fn put_verifier_selection(&self, selection: &VerifierSelection) -> Result<(), StoreError>;
+
+    /// All verifier draws for a wallet, oldest round first.
+    fn get_verifier_selections(
+        &self,
+        target: &WalletAddress,
+    ) -> Result<Vec<VerifierSelection>, StoreError>;
+
+    /// Record a wallet's challenge history (challenges survived as a target,
+    /// won and lost as a challenger).
+    fn put_challenge_history(&self, wallet: &WalletAddress, data: &[u8]) -> Result<(), StoreError>;
+
+    /// Get a wallet's challenge history, if it was ever part of a challenge.
+    fn get_challenge_history(&self, wallet: &WalletAddress) -> Result<Option<Vec<u8>>, StoreError>;
+
+    /// Record how a wallet's verification or challenge ended.
+    fn put_verification_record(
+        &self,
+        target: &WalletAddress,
+        record: &VerificationRecord,
+    ) -> Result<(), StoreError>;
+
+    /// Every verification record, in completion order.
+    fn iter_verification_records(&self) -> Result<Vec<VerificationRecord>, StoreError>;
+
+    /// Record the revocation notice applied for a wallet.
+    fn put_revocation_notice(&self, wallet: &WalletAddress, data: &[u8]) -> Result<(), StoreError>;
+
+    /// The revocation notice applied for a wallet, if any.
+    fn get_revocation_notice(&self, wallet: &WalletAddress) -> Result<Option<Vec<u8>>, StoreError>;
 }
@@ -1,25 +1,31 @@
 //! LMDB implementation of AccountStore — binary serialized, byte-keyed.
 //!
 //! Maintains a `verified_count` counter in `meta_db` so that
-//! `verified_account_count()` is O(1) instead of a full table scan.
+//! `verified_account_count()` is O(1) instead of a full table scan. Wallet
+//! state history is kept per address in `state_transitions_db`, as one
+//! serialized list appended to on each transition. Every write also updates
+//! the account-state tree (see [`crate::state_tree`]).
 
 use std::sync::Arc;
 
 use heed::types::Bytes;
-use heed::{Database, Env};
+use heed::Database;
 
 use burst_store::account::{AccountInfo, AccountStore};
 use burst_store::StoreError;
-use burst_types::WalletAddress;
+use burst_types::{StateTransition, WalletAddress};
 
+use crate::gated_env::GatedEnv;
 use crate::LmdbError;
 
 const VERIFIED_COUNT_KEY: &[u8] = b"verified_count";
 
 pub struct LmdbAccountStore {
-    pub(crate) env: Arc<Env>,
+    pub(crate) env: Arc<GatedEnv>,
     pub(crate) accounts_db: Database<Bytes, Bytes>,
     pub(crate) meta_db: Database<Bytes, Bytes>,
+    pub(crate) state_transitions_db: Database<Bytes, Bytes>,
+    pub(crate) state_tree_db: Database<Bytes, Bytes>,
 }
 
 impl LmdbAccountStore {
@@ -74,6 +80,7 @@ impl AccountStore for LmdbAccountStore {
         self.accounts_db
             .put(&mut wtxn, info.address.as_str().as_bytes(), &bytes)
             .map_err(LmdbError::from)?;
+        crate::state_tree::update_account(&mut wtxn, &self.state_tree_db, info)?;
 
         let now_verified = is_verified(info);
         if was_verified != now_verified {
@@ -180,4 +187,83 @@ impl AccountStore for LmdbAccountStore {
 
         Ok(accounts)
     }
+
+    fn put_state_transition(
+        &self,
+        address: &WalletAddress,
+        transition: &StateTransition,
+    ) -> Result<(), StoreError> {
+        let key = address.as_str().as_bytes();
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        let mut history: Vec<StateTransition> = match self
+            .state_transitions_db
+            .get(&wtxn, key)
+            .map_err(LmdbError::from)?
+        {
+            Some(bytes) => bincode::deserialize(bytes).map_err(LmdbError::from)?,
+            None => Vec::new(),
+        };
+        history.push(transition.clone());
+        let bytes = bincode::serialize(&history).map_err(LmdbError::from)?;
+        self.state_transitions_db
+            .put(&mut wtxn, key, &bytes)
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn get_state_transitions(
+        &self,
+        address: &WalletAddress,
+    ) -> Result<Vec<StateTransition>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        match self
+            .state_transitions_db
+            .get(&rtxn, address.as_str().as_bytes())
+            .map_err(LmdbError::from)?
+        {
+            Some(bytes) => Ok(bincode::deserialize(bytes).map_err(LmdbError::from)?),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LmdbEnvironment;
+    use burst_types::{Timestamp, TransitionReason, WalletState};
+
+    #[test]
+    fn state_transitions_append_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 10 * 1024 * 1024).unwrap();
+        let store = env.account_store();
+        let wallet = WalletAddress::new("brst_wallet");
+        assert!(store.get_state_transitions(&wallet).unwrap().is_empty());
+
+        let first = StateTransition {
+            from: WalletState::Unverified,
+            to: WalletState::Endorsed,
+            reason: TransitionReason::EndorsementThresholdReached,
+            at: Timestamp::new(10),
+        };
+        let second = StateTransition {
+            from: WalletState::Endorsed,
+            to: WalletState::Voting,
+            reason: TransitionReason::VerifiersSelected,
+            at: Timestamp::new(10),
+        };
+        store.put_state_transition(&wallet, &first).unwrap();
+        store.put_state_transition(&wallet, &second).unwrap();
+
+        assert_eq!(
+            store.get_state_transitions(&wallet).unwrap(),
+            vec![first, second]
+        );
+        assert!(store
+            .get_state_transitions(&WalletAddress::new("brst_other"))
+            .unwrap()
+            .is_empty());
+    }
 }
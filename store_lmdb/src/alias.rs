@@ -0,0 +1,138 @@
+//! LMDB implementation of AliasStore.
+//!
+//! `aliases` maps each name to its bincode-encoded record and
+//! `alias_owners` maps each owning account to the name it holds.
+
+use std::sync::Arc;
+
+use heed::types::Bytes;
+use heed::{Database, RwTxn};
+
+use burst_store::alias::{AliasRecord, AliasStore};
+use burst_store::StoreError;
+use burst_types::WalletAddress;
+
+use crate::gated_env::GatedEnv;
+use crate::LmdbError;
+
+pub struct LmdbAliasStore {
+    pub(crate) env: Arc<GatedEnv>,
+    pub(crate) aliases_db: Database<Bytes, Bytes>,
+    pub(crate) alias_owners_db: Database<Bytes, Bytes>,
+}
+
+/// Write `record` to both indexes inside `txn`. Shared with the write batch.
+pub(crate) fn put_alias_in(
+    txn: &mut RwTxn,
+    aliases_db: Database<Bytes, Bytes>,
+    alias_owners_db: Database<Bytes, Bytes>,
+    record: &AliasRecord,
+) -> Result<(), LmdbError> {
+    let owner_key = record.owner.as_str().as_bytes();
+
+    // The owner holds one name at a time: release the previous one.
+    if let Some(previous) = alias_owners_db.get(txn, owner_key)? {
+        if previous != record.name.as_bytes() {
+            let previous = previous.to_vec();
+            aliases_db.delete(txn, &previous)?;
+        }
+    }
+    // A lapsed name taken over by someone else leaves its old owner.
+    if let Some(existing) = aliases_db.get(txn, record.name.as_bytes())? {
+        let existing: AliasRecord = bincode::deserialize(existing)?;
+        if existing.owner != record.owner {
+            alias_owners_db.delete(txn, existing.owner.as_str().as_bytes())?;
+        }
+    }
+
+    let bytes = bincode::serialize(record)?;
+    aliases_db.put(txn, record.name.as_bytes(), &bytes)?;
+    alias_owners_db.put(txn, owner_key, record.name.as_bytes())?;
+    Ok(())
+}
+
+impl AliasStore for LmdbAliasStore {
+    fn put_alias(&self, record: &AliasRecord) -> Result<(), StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        put_alias_in(&mut wtxn, self.aliases_db, self.alias_owners_db, record)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn get_alias(&self, name: &str) -> Result<Option<AliasRecord>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        match self
+            .aliases_db
+            .get(&rtxn, name.as_bytes())
+            .map_err(LmdbError::from)?
+        {
+            Some(bytes) => Ok(Some(bincode::deserialize(bytes).map_err(LmdbError::from)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn get_account_alias(
+        &self,
+        account: &WalletAddress,
+    ) -> Result<Option<AliasRecord>, StoreError> {
+        let name = {
+            let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+            match self
+                .alias_owners_db
+                .get(&rtxn, account.as_str().as_bytes())
+                .map_err(LmdbError::from)?
+            {
+                Some(name) => String::from_utf8_lossy(name).into_owned(),
+                None => return Ok(None),
+            }
+        };
+        self.get_alias(&name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LmdbEnvironment;
+    use burst_types::Timestamp;
+
+    fn record(name: &str, owner: &str, expires_at: u64) -> AliasRecord {
+        AliasRecord {
+            name: name.into(),
+            owner: WalletAddress::new(owner),
+            registered_at: Timestamp::new(0),
+            expires_at: Timestamp::new(expires_at),
+        }
+    }
+
+    #[test]
+    fn names_and_owners_stay_one_to_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+        let store = env.alias_store();
+        let alice = WalletAddress::new("brst_alice");
+        let bob = WalletAddress::new("brst_bob");
+
+        store
+            .put_alias(&record("alice", "brst_alice", 100))
+            .unwrap();
+        assert_eq!(store.get_alias("alice").unwrap().unwrap().owner, alice);
+        assert_eq!(
+            store.get_account_alias(&alice).unwrap().unwrap().name,
+            "alice"
+        );
+
+        // Registering a new name releases the old one.
+        store.put_alias(&record("ally", "brst_alice", 100)).unwrap();
+        assert!(store.get_alias("alice").unwrap().is_none());
+        assert_eq!(
+            store.get_account_alias(&alice).unwrap().unwrap().name,
+            "ally"
+        );
+
+        // Taking over a name removes it from its previous owner.
+        store.put_alias(&record("ally", "brst_bob", 200)).unwrap();
+        assert!(store.get_account_alias(&alice).unwrap().is_none());
+        assert_eq!(store.get_account_alias(&bob).unwrap().unwrap().name, "ally");
+    }
+}
@@ -0,0 +1,96 @@
+//! LMDB implementation of AuditStore.
+//!
+//! Keys are sequence numbers (8 bytes, big-endian), so the last key is the
+//! newest entry and the next number is read and written in one transaction.
+
+use std::sync::Arc;
+
+use heed::types::Bytes;
+use heed::Database;
+
+use burst_store::audit::AuditStore;
+use burst_store::StoreError;
+
+use crate::gated_env::GatedEnv;
+use crate::LmdbError;
+
+pub struct LmdbAuditStore {
+    pub(crate) env: Arc<GatedEnv>,
+    pub(crate) audit_log_db: Database<Bytes, Bytes>,
+}
+
+fn sequence(key: &[u8]) -> Result<u64, LmdbError> {
+    key.try_into()
+        .map(u64::from_be_bytes)
+        .map_err(|_| LmdbError::Serialization("corrupt audit log key".into()))
+}
+
+impl AuditStore for LmdbAuditStore {
+    fn append_audit_entry(&self, entry: &[u8]) -> Result<u64, StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        let last = match self.audit_log_db.last(&wtxn).map_err(LmdbError::from)? {
+            Some((key, _)) => sequence(key)?,
+            None => 0,
+        };
+        let seq = last + 1;
+        self.audit_log_db
+            .put(&mut wtxn, &seq.to_be_bytes(), entry)
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(seq)
+    }
+
+    fn audit_entries(
+        &self,
+        before: Option<u64>,
+        limit: usize,
+    ) -> Result<Vec<(u64, Vec<u8>)>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let end = before.unwrap_or(u64::MAX).to_be_bytes();
+        let range = (
+            std::ops::Bound::Unbounded,
+            std::ops::Bound::Excluded(&end[..]),
+        );
+        let iter = self
+            .audit_log_db
+            .rev_range(&rtxn, &range)
+            .map_err(LmdbError::from)?;
+        let mut entries = Vec::new();
+        for result in iter.take(limit) {
+            let (key, val) = result.map_err(LmdbError::from)?;
+            entries.push((sequence(key)?, val.to_vec()));
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LmdbEnvironment;
+
+    #[test]
+    fn entries_are_numbered_and_paged_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+        let store = env.audit_store();
+        assert!(store.audit_entries(None, 10).unwrap().is_empty());
+        for entry in [b"a", b"b", b"c"] {
+            store.append_audit_entry(entry).unwrap();
+        }
+        assert_eq!(
+            store.audit_entries(None, 2).unwrap(),
+            vec![(3, b"c".to_vec()), (2, b"b".to_vec())]
+        );
+        assert_eq!(
+            store.audit_entries(Some(2), 10).unwrap(),
+            vec![(1, b"a".to_vec())]
+        );
+
+        // Numbering carries on across reopening.
+        drop(store);
+        drop(env);
+        let env = LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+        assert_eq!(env.audit_store().append_audit_entry(b"d").unwrap(), 4);
+    }
+}
@@ -8,16 +8,17 @@ use std::ops::Bound;
 use std::sync::Arc;
 
 use heed::types::Bytes;
-use heed::{Database, Env};
+use heed::Database;
 
 use burst_store::block::BlockStore;
 use burst_store::StoreError;
 use burst_types::{BlockHash, WalletAddress};
 
+use crate::gated_env::GatedEnv;
 use crate::LmdbError;
 
 pub struct LmdbBlockStore {
-    pub(crate) env: Arc<Env>,
+    pub(crate) env: Arc<GatedEnv>,
     pub(crate) blocks_db: Database<Bytes, Bytes>,
     /// Forward height index: `(account_bytes ++ height_be_bytes)` → `block_hash_bytes`.
     pub(crate) height_db: Database<Bytes, Bytes>,
@@ -1,17 +1,19 @@
 use burst_store::{BrnStore, StoreError};
 use burst_types::WalletAddress;
-use heed::{types::Bytes, Database, Env};
+use heed::{types::Bytes, Database};
 use std::sync::Arc;
 
+use crate::gated_env::GatedEnv;
+
 pub struct LmdbBrnStore {
-    env: Arc<Env>,
+    env: Arc<GatedEnv>,
     wallets_db: Database<Bytes, Bytes>,
     meta_db: Database<Bytes, Bytes>,
 }
 
 impl LmdbBrnStore {
     pub fn new(
-        env: Arc<Env>,
+        env: Arc<GatedEnv>,
         wallets_db: Database<Bytes, Bytes>,
         meta_db: Database<Bytes, Bytes>,
     ) -> Self {
@@ -0,0 +1,113 @@
+//! LMDB implementation of CheckpointStore.
+//!
+//! Keys are the cemented count (8 bytes, big-endian) followed by the
+//! digest, so iterating backwards yields the newest checkpoints first.
+
+use std::sync::Arc;
+
+use heed::types::Bytes;
+use heed::Database;
+
+use burst_store::checkpoint::CheckpointStore;
+use burst_store::StoreError;
+use burst_types::BlockHash;
+
+use crate::gated_env::GatedEnv;
+use crate::LmdbError;
+
+pub struct LmdbCheckpointStore {
+    pub(crate) env: Arc<GatedEnv>,
+    pub(crate) checkpoints_db: Database<Bytes, Bytes>,
+}
+
+fn checkpoint_key(cemented_count: u64, digest: &BlockHash) -> [u8; 40] {
+    let mut key = [0u8; 40];
+    key[..8].copy_from_slice(&cemented_count.to_be_bytes());
+    key[8..].copy_from_slice(digest.as_bytes());
+    key
+}
+
+impl CheckpointStore for LmdbCheckpointStore {
+    fn put_checkpoint(
+        &self,
+        cemented_count: u64,
+        digest: &BlockHash,
+        checkpoint: &[u8],
+    ) -> Result<(), StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        self.checkpoints_db
+            .put(
+                &mut wtxn,
+                &checkpoint_key(cemented_count, digest),
+                checkpoint,
+            )
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn get_checkpoint(
+        &self,
+        cemented_count: u64,
+        digest: &BlockHash,
+    ) -> Result<Vec<u8>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let val = self
+            .checkpoints_db
+            .get(&rtxn, &checkpoint_key(cemented_count, digest))
+            .map_err(LmdbError::from)?
+            .ok_or_else(|| LmdbError::NotFound(format!("checkpoint {cemented_count}/{digest}")))?;
+        Ok(val.to_vec())
+    }
+
+    fn recent_checkpoints(&self, limit: usize) -> Result<Vec<Vec<u8>>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let mut checkpoints = Vec::new();
+        let iter = self
+            .checkpoints_db
+            .rev_iter(&rtxn)
+            .map_err(LmdbError::from)?;
+        for result in iter.take(limit) {
+            let (_key, val) = result.map_err(LmdbError::from)?;
+            checkpoints.push(val.to_vec());
+        }
+        Ok(checkpoints)
+    }
+
+    fn checkpoint_count(&self) -> Result<u64, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let count = self.checkpoints_db.len(&rtxn).map_err(LmdbError::from)?;
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LmdbEnvironment;
+
+    #[test]
+    fn recent_checkpoints_are_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+        let store = env.checkpoint_store();
+        store
+            .put_checkpoint(300, &BlockHash::new([1; 32]), b"c")
+            .unwrap();
+        store
+            .put_checkpoint(20, &BlockHash::new([2; 32]), b"a")
+            .unwrap();
+        store
+            .put_checkpoint(256, &BlockHash::new([3; 32]), b"b")
+            .unwrap();
+        assert_eq!(store.checkpoint_count().unwrap(), 3);
+        assert_eq!(
+            store.recent_checkpoints(2).unwrap(),
+            vec![b"c".to_vec(), b"b".to_vec()]
+        );
+        assert_eq!(
+            store.get_checkpoint(20, &BlockHash::new([2; 32])).unwrap(),
+            b"a".to_vec()
+        );
+    }
+}
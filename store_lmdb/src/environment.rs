@@ -2,29 +2,57 @@
 
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
 use heed::types::Bytes;
-use heed::{Database, Env, EnvOpenOptions};
+use heed::{Database, EnvOpenOptions};
 
 use crate::account::LmdbAccountStore;
+use crate::alias::LmdbAliasStore;
+use crate::audit::LmdbAuditStore;
 use crate::block::LmdbBlockStore;
 use crate::brn::LmdbBrnStore;
+use crate::checkpoint::LmdbCheckpointStore;
+use crate::final_votes::LmdbFinalVoteStore;
+use crate::fork::LmdbForkStore;
 use crate::frontier::LmdbFrontierStore;
+use crate::gated_env::GatedEnv;
 use crate::governance::LmdbGovernanceStore;
 use crate::merger_graph::LmdbMergerGraphStore;
 use crate::meta::LmdbMetaStore;
 use crate::peer::LmdbPeerStore;
 use crate::pending::LmdbPendingStore;
+use crate::quorum_certificate::LmdbQuorumCertificateStore;
 use crate::rep_weights::LmdbRepWeightStore;
+use crate::state_tree::LmdbStateTreeStore;
 use crate::transaction::LmdbTransactionStore;
 use crate::trst_index::LmdbTrstIndexStore;
 use crate::verification::LmdbVerificationStore;
 use crate::write_batch::WriteBatch;
 use crate::LmdbError;
 
+/// How much of the LMDB memory map is in use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MapUsage {
+    /// Size of the memory map, in bytes. Writes fail once it is full.
+    pub map_size: u64,
+    /// Bytes up to the highest page ever written.
+    pub used: u64,
+}
+
+impl MapUsage {
+    /// Share of the map in use, in percent.
+    pub fn used_percent(&self) -> f64 {
+        if self.map_size == 0 {
+            return 0.0;
+        }
+        self.used as f64 * 100.0 / self.map_size as f64
+    }
+}
+
 /// Wraps the LMDB environment and all database handles.
 pub struct LmdbEnvironment {
-    env: Arc<Env>,
+    env: Arc<GatedEnv>,
 
     // Account store
     pub(crate) accounts_db: Database<Bytes, Bytes>,
@@ -40,17 +68,25 @@ pub struct LmdbEnvironment {
     pub(crate) merger_origins_db: Database<Bytes, Bytes>,
     pub(crate) merger_downstream_db: Database<Bytes, Bytes>,
     pub(crate) merger_nodes_db: Database<Bytes, Bytes>,
+    pub(crate) merger_revoked_db: Database<Bytes, Bytes>,
 
     // Verification store
     pub(crate) endorsements_db: Database<Bytes, Bytes>,
     pub(crate) verification_votes_db: Database<Bytes, Bytes>,
     pub(crate) challenges_db: Database<Bytes, Bytes>,
+    pub(crate) verifier_pool_db: Database<Bytes, Bytes>,
+    pub(crate) verifier_selections_db: Database<Bytes, Bytes>,
+    pub(crate) challenge_history_db: Database<Bytes, Bytes>,
+    pub(crate) verification_records_db: Database<Bytes, Bytes>,
+    pub(crate) state_transitions_db: Database<Bytes, Bytes>,
+    pub(crate) revocation_notices_db: Database<Bytes, Bytes>,
 
     // Governance store
     pub(crate) proposals_db: Database<Bytes, Bytes>,
     pub(crate) votes_db: Database<Bytes, Bytes>,
     pub(crate) delegations_db: Database<Bytes, Bytes>,
     pub(crate) constitution_db: Database<Bytes, Bytes>,
+    pub(crate) delegate_profiles_db: Database<Bytes, Bytes>,
 
     // Frontier store
     pub(crate) frontiers_db: Database<Bytes, Bytes>,
@@ -68,6 +104,8 @@ pub struct LmdbEnvironment {
     /// Enables O(1) `delete_token` by providing the origin and expiry
     /// needed to construct exact keys for the forward indexes.
     pub(crate) trst_reverse_db: Database<Bytes, Bytes>,
+    /// Wallet address → serialized TRST token portfolio.
+    pub(crate) trst_portfolio_db: Database<Bytes, Bytes>,
 
     // BRN engine stores
     pub(crate) brn_wallets_db: Database<Bytes, Bytes>,
@@ -83,6 +121,30 @@ pub struct LmdbEnvironment {
 
     // Peer cache store
     pub(crate) peers_db: Database<Bytes, Bytes>,
+    pub(crate) peer_bans_db: Database<Bytes, Bytes>,
+
+    // Final vote stores
+    pub(crate) final_votes_db: Database<Bytes, Bytes>,
+    pub(crate) final_vote_order_db: Database<Bytes, Bytes>,
+
+    // Quorum certificate store
+    pub(crate) quorum_certificates_db: Database<Bytes, Bytes>,
+
+    // Checkpoint store
+    pub(crate) checkpoints_db: Database<Bytes, Bytes>,
+
+    // Account-state tree
+    pub(crate) state_tree_db: Database<Bytes, Bytes>,
+
+    // Audit log
+    pub(crate) audit_log_db: Database<Bytes, Bytes>,
+
+    // Fork records
+    pub(crate) forks_db: Database<Bytes, Bytes>,
+
+    // Alias registry
+    pub(crate) aliases_db: Database<Bytes, Bytes>,
+    pub(crate) alias_owners_db: Database<Bytes, Bytes>,
 }
 
 impl LmdbEnvironment {
@@ -107,19 +169,29 @@ impl LmdbEnvironment {
         let merger_origins_db = env.create_database(&mut wtxn, Some("merger_origins"))?;
         let merger_downstream_db = env.create_database(&mut wtxn, Some("merger_downstream"))?;
         let merger_nodes_db = env.create_database(&mut wtxn, Some("merger_nodes"))?;
+        let merger_revoked_db = env.create_database(&mut wtxn, Some("merger_revoked"))?;
         let endorsements_db = env.create_database(&mut wtxn, Some("endorsements"))?;
         let verification_votes_db = env.create_database(&mut wtxn, Some("verification_votes"))?;
         let challenges_db = env.create_database(&mut wtxn, Some("challenges"))?;
+        let verifier_pool_db = env.create_database(&mut wtxn, Some("verifier_pool"))?;
+        let verifier_selections_db = env.create_database(&mut wtxn, Some("verifier_selections"))?;
+        let challenge_history_db = env.create_database(&mut wtxn, Some("challenge_history"))?;
+        let verification_records_db =
+            env.create_database(&mut wtxn, Some("verification_records"))?;
+        let state_transitions_db = env.create_database(&mut wtxn, Some("state_transitions"))?;
+        let revocation_notices_db = env.create_database(&mut wtxn, Some("revocation_notices"))?;
         let proposals_db = env.create_database(&mut wtxn, Some("proposals"))?;
         let votes_db = env.create_database(&mut wtxn, Some("votes"))?;
         let delegations_db = env.create_database(&mut wtxn, Some("delegations"))?;
         let constitution_db = env.create_database(&mut wtxn, Some("constitution"))?;
+        let delegate_profiles_db = env.create_database(&mut wtxn, Some("delegate_profiles"))?;
         let frontiers_db = env.create_database(&mut wtxn, Some("frontiers"))?;
         let meta_db = env.create_database(&mut wtxn, Some("meta"))?;
         let pending_db = env.create_database(&mut wtxn, Some("pending"))?;
         let trst_origin_db = env.create_database(&mut wtxn, Some("trst_origins"))?;
         let trst_expiry_db = env.create_database(&mut wtxn, Some("trst_expiry"))?;
         let trst_reverse_db = env.create_database(&mut wtxn, Some("trst_reverse"))?;
+        let trst_portfolio_db = env.create_database(&mut wtxn, Some("trst_portfolios"))?;
         let brn_wallets_db = env.create_database(&mut wtxn, Some("brn_wallets"))?;
         let brn_meta_db = env.create_database(&mut wtxn, Some("brn_meta"))?;
         let height_db = env.create_database(&mut wtxn, Some("block_heights"))?;
@@ -127,11 +199,23 @@ impl LmdbEnvironment {
         let rep_weights_db = env.create_database(&mut wtxn, Some("rep_weights"))?;
         let online_weight_db = env.create_database(&mut wtxn, Some("online_weights"))?;
         let peers_db = env.create_database(&mut wtxn, Some("peers"))?;
+        let peer_bans_db = env.create_database(&mut wtxn, Some("peer_bans"))?;
+        let final_votes_db = env.create_database(&mut wtxn, Some("final_votes"))?;
+        let final_vote_order_db = env.create_database(&mut wtxn, Some("final_vote_order"))?;
+        let quorum_certificates_db = env.create_database(&mut wtxn, Some("quorum_certificates"))?;
+        let checkpoints_db = env.create_database(&mut wtxn, Some("checkpoints"))?;
+        let state_tree_db = env.create_database(&mut wtxn, Some("state_tree"))?;
+        let audit_log_db = env.create_database(&mut wtxn, Some("audit_log"))?;
+        let forks_db = env.create_database(&mut wtxn, Some("forks"))?;
+        let aliases_db = env.create_database(&mut wtxn, Some("aliases"))?;
+        let alias_owners_db = env.create_database(&mut wtxn, Some("alias_owners"))?;
+
+        crate::state_tree::build_if_missing(&mut wtxn, &state_tree_db, &accounts_db)?;
 
         wtxn.commit()?;
 
         Ok(Self {
-            env: Arc::new(env),
+            env: Arc::new(GatedEnv::new(env)),
             accounts_db,
             blocks_db,
             transactions_db,
@@ -139,19 +223,28 @@ impl LmdbEnvironment {
             merger_origins_db,
             merger_downstream_db,
             merger_nodes_db,
+            merger_revoked_db,
             endorsements_db,
             verification_votes_db,
             challenges_db,
+            verifier_pool_db,
+            verifier_selections_db,
+            challenge_history_db,
+            verification_records_db,
+            state_transitions_db,
+            revocation_notices_db,
             proposals_db,
             votes_db,
             delegations_db,
             constitution_db,
+            delegate_profiles_db,
             frontiers_db,
             meta_db,
             pending_db,
             trst_origin_db,
             trst_expiry_db,
             trst_reverse_db,
+            trst_portfolio_db,
             brn_wallets_db,
             brn_meta_db,
             height_db,
@@ -159,14 +252,55 @@ impl LmdbEnvironment {
             rep_weights_db,
             online_weight_db,
             peers_db,
+            peer_bans_db,
+            final_votes_db,
+            final_vote_order_db,
+            quorum_certificates_db,
+            checkpoints_db,
+            state_tree_db,
+            audit_log_db,
+            forks_db,
+            aliases_db,
+            alias_owners_db,
         })
     }
 
     /// Get a shared reference to the underlying heed environment.
-    pub fn env(&self) -> &Arc<Env> {
+    pub fn env(&self) -> &Arc<GatedEnv> {
         &self.env
     }
 
+    /// Size of the data file of the environment at `path`, or 0 if there
+    /// is none yet.
+    pub fn data_file_len(path: &Path) -> u64 {
+        std::fs::metadata(path.join("data.mdb")).map_or(0, |meta| meta.len())
+    }
+
+    /// Grow the memory map to `new_size` bytes (a multiple of the OS page
+    /// size) without closing the environment. The resize happens between
+    /// transactions: new ones wait for it, and it waits up to `wait` for
+    /// the open ones. Returns `false` if they did not finish in time.
+    pub fn grow_map(&self, new_size: usize, wait: Duration) -> Result<bool, LmdbError> {
+        if (new_size as u64) <= self.env.info().map_size as u64 {
+            return Ok(false);
+        }
+        Ok(self.env.resize(new_size, wait)?)
+    }
+
+    /// Current size and high-water mark of the memory map.
+    ///
+    /// Freed pages are reused before the map grows, so `used` only rises;
+    /// it is what runs into the map size.
+    pub fn map_usage(&self) -> Result<MapUsage, LmdbError> {
+        let info = self.env.info();
+        let rtxn = self.env.read_txn()?;
+        let page_size = self.meta_db.stat(&rtxn)?.page_size as u64;
+        Ok(MapUsage {
+            map_size: info.map_size as u64,
+            used: (info.last_page_number as u64 + 1) * page_size,
+        })
+    }
+
     /// Begin a write batch for grouping multiple store operations into a
     /// single LMDB write transaction, amortising the fsync cost.
     pub fn write_batch(&self) -> Result<WriteBatch<'_>, burst_store::StoreError> {
@@ -179,6 +313,8 @@ impl LmdbEnvironment {
             env: Arc::clone(&self.env),
             accounts_db: self.accounts_db,
             meta_db: self.meta_db,
+            state_transitions_db: self.state_transitions_db,
+            state_tree_db: self.state_tree_db,
         }
     }
 
@@ -208,6 +344,7 @@ impl LmdbEnvironment {
             merger_origins_db: self.merger_origins_db,
             merger_downstream_db: self.merger_downstream_db,
             merger_nodes_db: self.merger_nodes_db,
+            merger_revoked_db: self.merger_revoked_db,
         }
     }
 
@@ -218,6 +355,11 @@ impl LmdbEnvironment {
             endorsements_db: self.endorsements_db,
             verification_votes_db: self.verification_votes_db,
             challenges_db: self.challenges_db,
+            verifier_pool_db: self.verifier_pool_db,
+            verifier_selections_db: self.verifier_selections_db,
+            challenge_history_db: self.challenge_history_db,
+            verification_records_db: self.verification_records_db,
+            revocation_notices_db: self.revocation_notices_db,
         }
     }
 
@@ -229,6 +371,7 @@ impl LmdbEnvironment {
             votes_db: self.votes_db,
             delegations_db: self.delegations_db,
             constitution_db: self.constitution_db,
+            delegate_profiles_db: self.delegate_profiles_db,
         }
     }
 
@@ -275,6 +418,65 @@ impl LmdbEnvironment {
         LmdbPeerStore {
             env: Arc::clone(&self.env),
             peers_db: self.peers_db,
+            peer_bans_db: self.peer_bans_db,
+        }
+    }
+
+    /// Create a final vote store backed by this environment.
+    pub fn final_vote_store(&self) -> LmdbFinalVoteStore {
+        LmdbFinalVoteStore {
+            env: Arc::clone(&self.env),
+            final_votes_db: self.final_votes_db,
+            final_vote_order_db: self.final_vote_order_db,
+        }
+    }
+
+    /// Create a quorum certificate store backed by this environment.
+    pub fn quorum_certificate_store(&self) -> LmdbQuorumCertificateStore {
+        LmdbQuorumCertificateStore {
+            env: Arc::clone(&self.env),
+            quorum_certificates_db: self.quorum_certificates_db,
+        }
+    }
+
+    /// Create a checkpoint store backed by this environment.
+    pub fn checkpoint_store(&self) -> LmdbCheckpointStore {
+        LmdbCheckpointStore {
+            env: Arc::clone(&self.env),
+            checkpoints_db: self.checkpoints_db,
+        }
+    }
+
+    /// Create an account-state tree store backed by this environment.
+    pub fn state_tree_store(&self) -> LmdbStateTreeStore {
+        LmdbStateTreeStore {
+            env: Arc::clone(&self.env),
+            state_tree_db: self.state_tree_db,
+        }
+    }
+
+    /// Create an audit log store backed by this environment.
+    pub fn audit_store(&self) -> LmdbAuditStore {
+        LmdbAuditStore {
+            env: Arc::clone(&self.env),
+            audit_log_db: self.audit_log_db,
+        }
+    }
+
+    /// Create a fork record store backed by this environment.
+    pub fn fork_store(&self) -> LmdbForkStore {
+        LmdbForkStore {
+            env: Arc::clone(&self.env),
+            forks_db: self.forks_db,
+        }
+    }
+
+    /// Create an alias registry store backed by this environment.
+    pub fn alias_store(&self) -> LmdbAliasStore {
+        LmdbAliasStore {
+            env: Arc::clone(&self.env),
+            aliases_db: self.aliases_db,
+            alias_owners_db: self.alias_owners_db,
         }
     }
 
@@ -285,6 +487,7 @@ impl LmdbEnvironment {
             trst_origin_db: self.trst_origin_db,
             trst_expiry_db: self.trst_expiry_db,
             trst_reverse_db: self.trst_reverse_db,
+            trst_portfolio_db: self.trst_portfolio_db,
         }
     }
 
@@ -303,3 +506,35 @@ impl LmdbEnvironment {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_usage_grows_with_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+        let before = env.map_usage().unwrap();
+        assert_eq!(before.map_size, 1 << 22);
+        assert!(before.used > 0 && before.used < before.map_size);
+
+        let mut wtxn = env.env().write_txn().unwrap();
+        for i in 0u32..2_000 {
+            env.meta_db
+                .put(&mut wtxn, &i.to_be_bytes(), &[7u8; 256])
+                .unwrap();
+        }
+        wtxn.commit().unwrap();
+        let after = env.map_usage().unwrap();
+        assert!(after.used > before.used);
+        assert!(after.used_percent() > before.used_percent());
+        assert!(LmdbEnvironment::data_file_len(dir.path()) > 0);
+
+        assert!(env.grow_map(1 << 23, Duration::from_millis(10)).unwrap());
+        let grown = env.map_usage().unwrap();
+        assert_eq!(grown.map_size, 1 << 23);
+        assert_eq!(grown.used, after.used);
+        assert!(!env.grow_map(1 << 22, Duration::from_millis(10)).unwrap());
+    }
+}
@@ -10,6 +10,9 @@ pub enum LmdbError {
 
     #[error("serialization error: {0}")]
     Serialization(String),
+
+    #[error("schema error: {0}")]
+    Schema(String),
 }
 
 impl From<heed::Error> for LmdbError {
@@ -29,7 +32,7 @@ impl From<LmdbError> for burst_store::StoreError {
         match e {
             LmdbError::NotFound(msg) => burst_store::StoreError::NotFound(msg),
             LmdbError::Serialization(msg) => burst_store::StoreError::Serialization(msg),
-            LmdbError::Heed(msg) => burst_store::StoreError::Backend(msg),
+            LmdbError::Heed(msg) | LmdbError::Schema(msg) => burst_store::StoreError::Backend(msg),
         }
     }
 }
@@ -0,0 +1,209 @@
+//! LMDB implementation of FinalVoteStore.
+//!
+//! `final_votes_db` maps `root(32) ‖ voter` to `seq_be(8) ‖ vote`, and
+//! `final_vote_order_db` maps `seq_be(8)` back to `root(32) ‖ voter`, so the
+//! oldest entries can be trimmed in insertion order.
+
+use std::sync::Arc;
+
+use heed::types::Bytes;
+use heed::Database;
+
+use burst_store::final_votes::FinalVoteStore;
+use burst_store::StoreError;
+use burst_types::{BlockHash, WalletAddress};
+
+use crate::gated_env::GatedEnv;
+use crate::LmdbError;
+
+pub struct LmdbFinalVoteStore {
+    pub(crate) env: Arc<GatedEnv>,
+    pub(crate) final_votes_db: Database<Bytes, Bytes>,
+    pub(crate) final_vote_order_db: Database<Bytes, Bytes>,
+}
+
+fn vote_key(root: &BlockHash, voter: &WalletAddress) -> Vec<u8> {
+    let voter = voter.as_str().as_bytes();
+    let mut key = Vec::with_capacity(32 + voter.len());
+    key.extend_from_slice(root.as_bytes());
+    key.extend_from_slice(voter);
+    key
+}
+
+fn seq_of(value: &[u8]) -> Option<[u8; 8]> {
+    value.get(..8).and_then(|s| s.try_into().ok())
+}
+
+impl FinalVoteStore for LmdbFinalVoteStore {
+    fn put_final_vote(
+        &self,
+        root: &BlockHash,
+        voter: &WalletAddress,
+        vote: &[u8],
+    ) -> Result<(), StoreError> {
+        let key = vote_key(root, voter);
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+
+        let previous_seq = self
+            .final_votes_db
+            .get(&wtxn, &key)
+            .map_err(LmdbError::from)?
+            .and_then(seq_of);
+        if let Some(seq) = previous_seq {
+            self.final_vote_order_db
+                .delete(&mut wtxn, &seq)
+                .map_err(LmdbError::from)?;
+        }
+
+        let next_seq = match self
+            .final_vote_order_db
+            .last(&wtxn)
+            .map_err(LmdbError::from)?
+        {
+            Some((last, _)) => seq_of(last)
+                .map(u64::from_be_bytes)
+                .unwrap_or(0)
+                .saturating_add(1),
+            None => 0,
+        };
+        let seq = next_seq.to_be_bytes();
+
+        let mut value = Vec::with_capacity(8 + vote.len());
+        value.extend_from_slice(&seq);
+        value.extend_from_slice(vote);
+        self.final_votes_db
+            .put(&mut wtxn, &key, &value)
+            .map_err(LmdbError::from)?;
+        self.final_vote_order_db
+            .put(&mut wtxn, &seq, &key)
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn get_final_votes(&self, root: &BlockHash) -> Result<Vec<Vec<u8>>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let iter = self
+            .final_votes_db
+            .prefix_iter(&rtxn, root.as_bytes())
+            .map_err(LmdbError::from)?;
+        let mut votes = Vec::new();
+        for entry in iter {
+            let (_, val) = entry.map_err(LmdbError::from)?;
+            if val.len() > 8 {
+                votes.push(val[8..].to_vec());
+            }
+        }
+        Ok(votes)
+    }
+
+    fn final_vote_count(&self) -> Result<u64, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let count = self
+            .final_vote_order_db
+            .len(&rtxn)
+            .map_err(LmdbError::from)?;
+        Ok(count)
+    }
+
+    fn trim_final_votes(&self, max_entries: u64) -> Result<u64, StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        let count = self
+            .final_vote_order_db
+            .len(&wtxn)
+            .map_err(LmdbError::from)?;
+        let excess = count.saturating_sub(max_entries);
+        if excess == 0 {
+            return Ok(0);
+        }
+
+        let mut oldest = Vec::with_capacity(excess as usize);
+        for entry in self
+            .final_vote_order_db
+            .iter(&wtxn)
+            .map_err(LmdbError::from)?
+            .take(excess as usize)
+        {
+            let (seq, key) = entry.map_err(LmdbError::from)?;
+            oldest.push((seq.to_vec(), key.to_vec()));
+        }
+        for (seq, key) in &oldest {
+            self.final_vote_order_db
+                .delete(&mut wtxn, seq)
+                .map_err(LmdbError::from)?;
+            self.final_votes_db
+                .delete(&mut wtxn, key)
+                .map_err(LmdbError::from)?;
+        }
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(oldest.len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_test_env() -> crate::LmdbEnvironment {
+        let dir = tempfile::tempdir().unwrap();
+        crate::LmdbEnvironment::open(dir.path(), 46, 1 << 20).unwrap()
+    }
+
+    #[test]
+    fn votes_are_grouped_by_root_and_replaced_per_voter() {
+        let env = open_test_env();
+        let store = env.final_vote_store();
+        let root = BlockHash::new([1u8; 32]);
+        let other = BlockHash::new([2u8; 32]);
+        let alice = WalletAddress::new("brst_test_rep_alice");
+        let bob = WalletAddress::new("brst_test_rep_bob");
+
+        store.put_final_vote(&root, &alice, b"alice-1").unwrap();
+        store.put_final_vote(&root, &bob, b"bob-1").unwrap();
+        store
+            .put_final_vote(&other, &alice, b"alice-other")
+            .unwrap();
+        store.put_final_vote(&root, &alice, b"alice-2").unwrap();
+
+        let mut votes = store.get_final_votes(&root).unwrap();
+        votes.sort();
+        assert_eq!(votes, vec![b"alice-2".to_vec(), b"bob-1".to_vec()]);
+        assert_eq!(store.final_vote_count().unwrap(), 3);
+        assert!(store
+            .get_final_votes(&BlockHash::new([3u8; 32]))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn trim_removes_oldest_entries_first() {
+        let env = open_test_env();
+        let store = env.final_vote_store();
+        let voter = WalletAddress::new("brst_test_rep_alice");
+        for i in 0..5u8 {
+            store
+                .put_final_vote(&BlockHash::new([i; 32]), &voter, &[i])
+                .unwrap();
+        }
+        // Re-voting moves root 0 to the back of the queue.
+        store
+            .put_final_vote(&BlockHash::new([0u8; 32]), &voter, &[9])
+            .unwrap();
+
+        assert_eq!(store.trim_final_votes(3).unwrap(), 2);
+        assert_eq!(store.final_vote_count().unwrap(), 3);
+        assert!(store
+            .get_final_votes(&BlockHash::new([1u8; 32]))
+            .unwrap()
+            .is_empty());
+        assert!(store
+            .get_final_votes(&BlockHash::new([2u8; 32]))
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            store.get_final_votes(&BlockHash::new([0u8; 32])).unwrap(),
+            vec![vec![9]]
+        );
+        assert_eq!(store.trim_final_votes(3).unwrap(), 0);
+    }
+}
@@ -0,0 +1,106 @@
+//! LMDB implementation of ForkStore.
+//!
+//! Keys are sequence numbers (8 bytes, big-endian), so the last key is the
+//! newest record and the next number is read and written in one transaction.
+
+use std::sync::Arc;
+
+use heed::types::Bytes;
+use heed::Database;
+
+use burst_store::fork::ForkStore;
+use burst_store::StoreError;
+
+use crate::gated_env::GatedEnv;
+use crate::LmdbError;
+
+pub struct LmdbForkStore {
+    pub(crate) env: Arc<GatedEnv>,
+    pub(crate) forks_db: Database<Bytes, Bytes>,
+}
+
+fn sequence(key: &[u8]) -> Result<u64, LmdbError> {
+    key.try_into()
+        .map(u64::from_be_bytes)
+        .map_err(|_| LmdbError::Serialization("corrupt fork record key".into()))
+}
+
+impl ForkStore for LmdbForkStore {
+    fn append_fork(&self, record: &[u8]) -> Result<u64, StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        let last = match self.forks_db.last(&wtxn).map_err(LmdbError::from)? {
+            Some((key, _)) => sequence(key)?,
+            None => 0,
+        };
+        let seq = last + 1;
+        self.forks_db
+            .put(&mut wtxn, &seq.to_be_bytes(), record)
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(seq)
+    }
+
+    fn update_fork(&self, seq: u64, record: &[u8]) -> Result<(), StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        let key = seq.to_be_bytes();
+        if self
+            .forks_db
+            .get(&wtxn, &key)
+            .map_err(LmdbError::from)?
+            .is_none()
+        {
+            return Err(StoreError::NotFound(format!("fork record {seq}")));
+        }
+        self.forks_db
+            .put(&mut wtxn, &key, record)
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn forks(&self, before: Option<u64>, limit: usize) -> Result<Vec<(u64, Vec<u8>)>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let end = before.unwrap_or(u64::MAX).to_be_bytes();
+        let range = (
+            std::ops::Bound::Unbounded,
+            std::ops::Bound::Excluded(&end[..]),
+        );
+        let iter = self
+            .forks_db
+            .rev_range(&rtxn, &range)
+            .map_err(LmdbError::from)?;
+        let mut records = Vec::new();
+        for result in iter.take(limit) {
+            let (key, val) = result.map_err(LmdbError::from)?;
+            records.push((sequence(key)?, val.to_vec()));
+        }
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LmdbEnvironment;
+
+    #[test]
+    fn records_are_numbered_updated_and_paged_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+        let store = env.fork_store();
+        assert!(store.forks(None, 10).unwrap().is_empty());
+        for record in [b"a", b"b", b"c"] {
+            store.append_fork(record).unwrap();
+        }
+        store.update_fork(2, b"B").unwrap();
+        assert!(matches!(
+            store.update_fork(9, b"x"),
+            Err(StoreError::NotFound(_))
+        ));
+        assert_eq!(
+            store.forks(None, 2).unwrap(),
+            vec![(3, b"c".to_vec()), (2, b"B".to_vec())]
+        );
+        assert_eq!(store.forks(Some(2), 10).unwrap(), vec![(1, b"a".to_vec())]);
+    }
+}
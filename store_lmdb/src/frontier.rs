@@ -3,16 +3,17 @@
 use std::sync::Arc;
 
 use heed::types::Bytes;
-use heed::{Database, Env};
+use heed::Database;
 
 use burst_store::frontier::FrontierStore;
 use burst_store::StoreError;
 use burst_types::{BlockHash, WalletAddress};
 
+use crate::gated_env::GatedEnv;
 use crate::LmdbError;
 
 pub struct LmdbFrontierStore {
-    pub(crate) env: Arc<Env>,
+    pub(crate) env: Arc<GatedEnv>,
     pub(crate) frontiers_db: Database<Bytes, Bytes>,
 }
 
@@ -0,0 +1,227 @@
+//! An LMDB environment whose memory map can be grown while it is open.
+//!
+//! LMDB only allows the map size to change while no transaction is open in
+//! the process. [`GatedEnv`] counts the transactions it hands out, and
+//! [`GatedEnv::resize`] holds new ones back, waits for the open ones to
+//! finish and resizes in the gap — between write batches, without closing
+//! the environment.
+//!
+//! A thread that opens a transaction while already holding one would wait
+//! on a pending resize that in turn waits on it. The resize therefore only
+//! waits so long before giving up, and the caller tries again later.
+
+use std::ops::{Deref, DerefMut};
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::Duration;
+
+use heed::{Database, Env, EnvInfo, RoTxn, RwTxn};
+
+/// Open transactions and whether a resize is waiting for them.
+#[derive(Default)]
+struct Gate {
+    open: usize,
+    resizing: bool,
+}
+
+/// A heed environment that hands out counted transactions.
+pub struct GatedEnv {
+    env: Env,
+    gate: Mutex<Gate>,
+    /// Signalled when the last transaction closes and when a resize ends.
+    changed: Condvar,
+}
+
+/// Marks one open transaction until dropped.
+struct OpenTxn<'e>(&'e GatedEnv);
+
+impl Drop for OpenTxn<'_> {
+    fn drop(&mut self) {
+        let mut gate = self.0.lock();
+        gate.open -= 1;
+        if gate.open == 0 && gate.resizing {
+            self.0.changed.notify_all();
+        }
+    }
+}
+
+/// A read transaction; derefs to [`RoTxn`].
+pub struct ReadTxn<'e> {
+    txn: RoTxn<'e>,
+    _open: OpenTxn<'e>,
+}
+
+impl<'e> Deref for ReadTxn<'e> {
+    type Target = RoTxn<'e>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.txn
+    }
+}
+
+/// A write transaction; derefs to [`RwTxn`]. Dropped without
+/// [`WriteTxn::commit`], it is aborted.
+pub struct WriteTxn<'e> {
+    txn: RwTxn<'e>,
+    _open: OpenTxn<'e>,
+}
+
+impl<'e> WriteTxn<'e> {
+    pub fn commit(self) -> heed::Result<()> {
+        self.txn.commit()
+    }
+}
+
+impl<'e> Deref for WriteTxn<'e> {
+    type Target = RwTxn<'e>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.txn
+    }
+}
+
+impl DerefMut for WriteTxn<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.txn
+    }
+}
+
+impl GatedEnv {
+    pub fn new(env: Env) -> Self {
+        Self {
+            env,
+            gate: Mutex::new(Gate::default()),
+            changed: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Gate> {
+        self.gate.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Count a new transaction, waiting out any resize in progress.
+    fn enter(&self) -> OpenTxn<'_> {
+        let mut gate = self.lock();
+        while gate.resizing {
+            gate = self.changed.wait(gate).unwrap_or_else(|e| e.into_inner());
+        }
+        gate.open += 1;
+        OpenTxn(self)
+    }
+
+    pub fn read_txn(&self) -> heed::Result<ReadTxn<'_>> {
+        let open = self.enter();
+        Ok(ReadTxn {
+            txn: self.env.read_txn()?,
+            _open: open,
+        })
+    }
+
+    pub fn write_txn(&self) -> heed::Result<WriteTxn<'_>> {
+        let open = self.enter();
+        Ok(WriteTxn {
+            txn: self.env.write_txn()?,
+            _open: open,
+        })
+    }
+
+    pub fn info(&self) -> EnvInfo {
+        self.env.info()
+    }
+
+    pub fn open_database<KC: 'static, DC: 'static>(
+        &self,
+        rtxn: &RoTxn,
+        name: Option<&str>,
+    ) -> heed::Result<Option<Database<KC, DC>>> {
+        self.env.open_database(rtxn, name)
+    }
+
+    /// Set the map size to `new_size` bytes (a multiple of the OS page
+    /// size) once no transaction is open. Returns `false` if transactions
+    /// were still open after `wait`, or another resize is under way.
+    pub fn resize(&self, new_size: usize, wait: Duration) -> heed::Result<bool> {
+        let mut gate = self.lock();
+        if gate.resizing {
+            return Ok(false);
+        }
+        gate.resizing = true;
+        let (mut gate, _) = self
+            .changed
+            .wait_timeout_while(gate, wait, |gate| gate.open > 0)
+            .unwrap_or_else(|e| e.into_inner());
+        let result = if gate.open == 0 {
+            // SAFETY: every transaction on this environment is counted, the
+            // count is zero and new ones are held back until `resizing` is
+            // cleared below.
+            unsafe { self.env.resize(new_size) }.map(|()| true)
+        } else {
+            Ok(false)
+        };
+        gate.resizing = false;
+        drop(gate);
+        self.changed.notify_all();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heed::types::Bytes;
+    use heed::EnvOpenOptions;
+
+    fn open(dir: &std::path::Path) -> GatedEnv {
+        let env = unsafe {
+            EnvOpenOptions::new()
+                .max_dbs(1)
+                .map_size(1 << 20)
+                .open(dir)
+                .unwrap()
+        };
+        GatedEnv::new(env)
+    }
+
+    #[test]
+    fn resize_waits_for_open_transactions() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = open(dir.path());
+        let mut wtxn = env.write_txn().unwrap();
+        let db: Database<Bytes, Bytes> = env.env.create_database(&mut wtxn, None).unwrap();
+        wtxn.commit().unwrap();
+
+        // A reader is open: the resize gives up.
+        let rtxn = env.read_txn().unwrap();
+        assert!(!env.resize(1 << 22, Duration::from_millis(10)).unwrap());
+        assert_eq!(env.info().map_size, 1 << 20);
+        drop(rtxn);
+
+        // Fill past the old map, then grow it in the gap between writes.
+        assert!(env.resize(1 << 22, Duration::from_millis(10)).unwrap());
+        assert_eq!(env.info().map_size, 1 << 22);
+        let mut wtxn = env.write_txn().unwrap();
+        for i in 0u32..4_000 {
+            db.put(&mut wtxn, &i.to_be_bytes(), &[1u8; 256]).unwrap();
+        }
+        wtxn.commit().unwrap();
+        let rtxn = env.read_txn().unwrap();
+        assert_eq!(db.len(&rtxn).unwrap(), 4_000);
+    }
+
+    #[test]
+    fn resize_runs_once_a_busy_reader_finishes() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = std::sync::Arc::new(open(dir.path()));
+        let rtxn_env = std::sync::Arc::clone(&env);
+        let (opened_tx, opened_rx) = std::sync::mpsc::channel();
+        let reader = std::thread::spawn(move || {
+            let rtxn = rtxn_env.read_txn().unwrap();
+            opened_tx.send(()).unwrap();
+            std::thread::sleep(Duration::from_millis(50));
+            drop(rtxn);
+        });
+        opened_rx.recv().unwrap();
+        assert!(env.resize(1 << 21, Duration::from_secs(5)).unwrap());
+        reader.join().unwrap();
+        assert_eq!(env.info().map_size, 1 << 21);
+    }
+}
@@ -8,21 +8,23 @@ use std::ops::Bound;
 use std::sync::Arc;
 
 use heed::types::Bytes;
-use heed::{Database, Env};
+use heed::Database;
 
 use burst_store::governance::GovernanceStore;
 use burst_store::StoreError;
 use burst_types::{TxHash, WalletAddress};
 
 use crate::block::increment_prefix;
+use crate::gated_env::GatedEnv;
 use crate::LmdbError;
 
 pub struct LmdbGovernanceStore {
-    pub(crate) env: Arc<Env>,
+    pub(crate) env: Arc<GatedEnv>,
     pub(crate) proposals_db: Database<Bytes, Bytes>,
     pub(crate) votes_db: Database<Bytes, Bytes>,
     pub(crate) delegations_db: Database<Bytes, Bytes>,
     pub(crate) constitution_db: Database<Bytes, Bytes>,
+    pub(crate) delegate_profiles_db: Database<Bytes, Bytes>,
 }
 
 /// Build the composite key `proposal(32) ++ voter_bytes`.
@@ -152,4 +154,44 @@ impl GovernanceStore for LmdbGovernanceStore {
             .ok_or_else(|| LmdbError::NotFound("constitution".to_string()))?;
         Ok(val.to_vec())
     }
+
+    fn put_delegate_profile(
+        &self,
+        delegate: &WalletAddress,
+        data: &[u8],
+    ) -> Result<(), StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        self.delegate_profiles_db
+            .put(&mut wtxn, delegate.as_str().as_bytes(), data)
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn get_delegate_profile(
+        &self,
+        delegate: &WalletAddress,
+    ) -> Result<Option<Vec<u8>>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let val = self
+            .delegate_profiles_db
+            .get(&rtxn, delegate.as_str().as_bytes())
+            .map_err(LmdbError::from)?
+            .map(|b| b.to_vec());
+        Ok(val)
+    }
+
+    fn list_delegate_profiles(&self) -> Result<Vec<Vec<u8>>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let iter = self
+            .delegate_profiles_db
+            .iter(&rtxn)
+            .map_err(LmdbError::from)?;
+        let mut results = Vec::new();
+        for result in iter {
+            let (_key, val) = result.map_err(LmdbError::from)?;
+            results.push(val.to_vec());
+        }
+        Ok(results)
+    }
 }
@@ -4,11 +4,17 @@
 //! Each logical store maps to one or more LMDB databases within a single environment.
 
 pub mod account;
+pub mod alias;
+pub mod audit;
 pub mod block;
 pub mod brn;
+pub mod checkpoint;
 pub mod environment;
 pub mod error;
+pub mod final_votes;
+pub mod fork;
 pub mod frontier;
+pub mod gated_env;
 pub mod governance;
 pub mod integrity;
 pub mod merger_graph;
@@ -16,26 +22,37 @@ pub mod meta;
 pub mod migration;
 pub mod peer;
 pub mod pending;
+pub mod quorum_certificate;
 pub mod rep_weights;
+pub mod state_tree;
 pub mod transaction;
 pub mod trst_index;
 pub mod verification;
 pub mod write_batch;
 
 pub use account::LmdbAccountStore;
+pub use alias::LmdbAliasStore;
+pub use audit::LmdbAuditStore;
 pub use block::LmdbBlockStore;
 pub use brn::LmdbBrnStore;
-pub use environment::LmdbEnvironment;
+pub use checkpoint::LmdbCheckpointStore;
+pub use environment::{LmdbEnvironment, MapUsage};
 pub use error::LmdbError;
+pub use final_votes::LmdbFinalVoteStore;
+pub use fork::LmdbForkStore;
 pub use frontier::LmdbFrontierStore;
 pub use governance::LmdbGovernanceStore;
 pub use integrity::{check_data_dir, check_integrity, IntegrityReport};
 pub use merger_graph::LmdbMergerGraphStore;
 pub use meta::LmdbMetaStore;
-pub use migration::{Migrator, CURRENT_SCHEMA_VERSION};
+pub use migration::{
+    MigrationPlan, MigrationStep, Migrator, PlannedStep, Progress, CURRENT_SCHEMA_VERSION,
+};
 pub use peer::LmdbPeerStore;
 pub use pending::LmdbPendingStore;
+pub use quorum_certificate::LmdbQuorumCertificateStore;
 pub use rep_weights::LmdbRepWeightStore;
+pub use state_tree::LmdbStateTreeStore;
 pub use transaction::LmdbTransactionStore;
 pub use trst_index::LmdbTrstIndexStore;
 pub use verification::LmdbVerificationStore;
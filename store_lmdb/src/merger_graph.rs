@@ -2,26 +2,28 @@
 //!
 //! `merger_origins_db` and `merger_downstream_db` use composite keys
 //! `(parent(32), child(32))` → empty, enabling O(1) put and prefix
-//! range-scan for listing.
+//! range-scan for listing. `merger_revoked_db` keys revoked origins → empty.
 
 use std::ops::Bound;
 use std::sync::Arc;
 
 use heed::types::Bytes;
-use heed::{Database, Env};
+use heed::Database;
 
 use burst_store::merger_graph::MergerGraphStore;
 use burst_store::StoreError;
 use burst_types::TxHash;
 
 use crate::block::increment_prefix;
+use crate::gated_env::GatedEnv;
 use crate::LmdbError;
 
 pub struct LmdbMergerGraphStore {
-    pub(crate) env: Arc<Env>,
+    pub(crate) env: Arc<GatedEnv>,
     pub(crate) merger_origins_db: Database<Bytes, Bytes>,
     pub(crate) merger_downstream_db: Database<Bytes, Bytes>,
     pub(crate) merger_nodes_db: Database<Bytes, Bytes>,
+    pub(crate) merger_revoked_db: Database<Bytes, Bytes>,
 }
 
 /// Build a 64-byte composite key `parent(32) ++ child(32)`.
@@ -35,7 +37,7 @@ fn composite_key(parent: &[u8; 32], child: &[u8; 32]) -> [u8; 64] {
 /// Prefix range-scan: collect all 32-byte suffixes whose key starts with `prefix`.
 fn range_scan_children(
     db: &Database<Bytes, Bytes>,
-    env: &Env,
+    env: &GatedEnv,
     prefix: &[u8; 32],
 ) -> Result<Vec<TxHash>, LmdbError> {
     let rtxn = env.read_txn()?;
@@ -107,4 +109,38 @@ impl MergerGraphStore for LmdbMergerGraphStore {
             .ok_or_else(|| LmdbError::NotFound(format!("merge node {:?}", merge_tx)))?;
         Ok(val.to_vec())
     }
+
+    fn put_revoked_origin(&self, origin: &TxHash) -> Result<(), StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        self.merger_revoked_db
+            .put(&mut wtxn, origin.as_bytes().as_slice(), &[])
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn delete_revoked_origin(&self, origin: &TxHash) -> Result<(), StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        self.merger_revoked_db
+            .delete(&mut wtxn, origin.as_bytes().as_slice())
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn iter_revoked_origins(&self) -> Result<Vec<TxHash>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let iter = self
+            .merger_revoked_db
+            .iter(&rtxn)
+            .map_err(LmdbError::from)?;
+        let mut origins = Vec::new();
+        for entry in iter {
+            let (key, _) = entry.map_err(LmdbError::from)?;
+            if let Ok(arr) = <[u8; 32]>::try_from(key) {
+                origins.push(TxHash::new(arr));
+            }
+        }
+        Ok(origins)
+    }
 }
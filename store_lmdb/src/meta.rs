@@ -3,17 +3,18 @@
 use std::sync::Arc;
 
 use heed::types::Bytes;
-use heed::{Database, Env};
+use heed::Database;
 
 use burst_store::meta::MetaStore;
 use burst_store::StoreError;
 
+use crate::gated_env::GatedEnv;
 use crate::LmdbError;
 
-const SCHEMA_VERSION_KEY: &[u8] = b"schema_version";
+pub(crate) const SCHEMA_VERSION_KEY: &[u8] = b"schema_version";
 
 pub struct LmdbMetaStore {
-    pub(crate) env: Arc<Env>,
+    pub(crate) env: Arc<GatedEnv>,
     pub(crate) meta_db: Database<Bytes, Bytes>,
 }
 
@@ -57,6 +58,16 @@ impl MetaStore for LmdbMetaStore {
                 let arr: [u8; 4] = bytes.try_into().expect("checked length");
                 Ok(u32::from_le_bytes(arr))
             }
+            // Early nodes wrote the version as ASCII digits at genesis.
+            Some(bytes) if !bytes.is_empty() && bytes.iter().all(u8::is_ascii_digit) => {
+                std::str::from_utf8(bytes)
+                    .ok()
+                    .and_then(|digits| digits.parse().ok())
+                    .ok_or_else(|| {
+                        LmdbError::Serialization("schema_version is out of range".to_string())
+                    })
+                    .map_err(Into::into)
+            }
             Some(_) => Err(LmdbError::Serialization(
                 "schema_version has unexpected byte length".to_string(),
             ))?,
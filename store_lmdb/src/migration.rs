@@ -1,93 +1,476 @@
-//! Database schema migration engine.
+//! Database schema migrations.
 //!
-//! Tracks a monotonically increasing schema version in the meta store and
-//! runs sequential migration functions to bring an older database up to date.
+//! The schema version lives in the meta store. Each [`MigrationStep`]
+//! moves the database one version up, and back down again, and runs in a
+//! single write transaction together with the version bump, so an
+//! interrupted migration leaves the database at the last version it
+//! completed. Long steps report progress as they go.
+//!
+//! [`Migrator::plan`] is the dry run: for each step between the stored and
+//! the target version it reports how many records the step would touch,
+//! without writing anything.
+//!
+//! A database whose schema version is above what this binary knows was
+//! written by a newer node, and is refused rather than opened.
+//!
+//! Schema v3 changed how blocks and pending entries are encoded without a
+//! step to rewrite them, so a ledger from before v3 can only be migrated
+//! while it is empty; one holding records is refused and must be re-synced
+//! into an empty data directory.
+
+use std::time::Instant;
+
+use heed::{RoTxn, RwTxn};
 
 use burst_store::MetaStore;
 
+use crate::environment::LmdbEnvironment;
+use crate::meta::SCHEMA_VERSION_KEY;
 use crate::LmdbError;
 
 /// The schema version that the current code expects.
-pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+pub const CURRENT_SCHEMA_VERSION: u32 = 3;
 
-/// Runs database migrations to bring the schema up to date.
-pub struct Migrator;
+/// Records between progress log lines of a long step.
+const PROGRESS_EVERY: u64 = 10_000;
 
-impl Migrator {
-    /// Check the stored schema version and run any needed migrations.
-    ///
-    /// - Version 0 means a fresh database (no version stored yet).
-    /// - If the stored version matches `CURRENT_SCHEMA_VERSION`, this is a no-op.
-    /// - If the stored version is *higher* than what this code supports,
-    ///   the database was written by a newer node and we refuse to open it.
-    pub fn run(meta_store: &impl MetaStore) -> Result<(), LmdbError> {
-        let current = meta_store.get_schema_version().unwrap_or(0);
-
-        if current == CURRENT_SCHEMA_VERSION {
-            tracing::info!(version = current, "database schema is up to date");
-            return Ok(());
+/// Counts the records a step touches.
+pub type CountFn = fn(&LmdbEnvironment, &RoTxn) -> Result<u64, LmdbError>;
+
+/// Applies a step inside the given write transaction, reporting each
+/// record touched to the [`Progress`].
+pub type ApplyFn = fn(&LmdbEnvironment, &mut RwTxn, &mut Progress) -> Result<(), LmdbError>;
+
+/// One reversible step between adjacent schema versions.
+pub struct MigrationStep {
+    /// Version the step upgrades from; `up` leaves the database at
+    /// `from + 1` and `down` takes it back to `from`.
+    pub from: u32,
+    pub description: &'static str,
+    /// Records `up` or `down` would touch.
+    pub affected: CountFn,
+    pub up: ApplyFn,
+    pub down: ApplyFn,
+}
+
+/// Progress of the step being applied, logged every [`PROGRESS_EVERY`]
+/// records.
+pub struct Progress {
+    from: u32,
+    to: u32,
+    total: u64,
+    done: u64,
+}
+
+impl Progress {
+    /// Count `records` more as done.
+    pub fn advance(&mut self, records: u64) {
+        let before = self.done / PROGRESS_EVERY;
+        self.done += records;
+        if self.done / PROGRESS_EVERY > before {
+            tracing::info!(
+                from = self.from,
+                to = self.to,
+                done = self.done,
+                total = self.total,
+                "migration progress"
+            );
         }
+    }
+
+    pub fn done(&self) -> u64 {
+        self.done
+    }
+}
 
-        if current > CURRENT_SCHEMA_VERSION {
-            return Err(LmdbError::Heed(format!(
-                "database schema version {} is newer than supported version {}",
-                current, CURRENT_SCHEMA_VERSION
+/// One step a migration would run, and what it would touch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlannedStep {
+    pub from: u32,
+    pub to: u32,
+    pub description: &'static str,
+    pub affected: u64,
+}
+
+/// The steps between two schema versions, in the order they run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MigrationPlan {
+    pub from: u32,
+    pub to: u32,
+    pub steps: Vec<PlannedStep>,
+}
+
+impl MigrationPlan {
+    /// Records touched across all steps.
+    pub fn affected(&self) -> u64 {
+        self.steps.iter().map(|step| step.affected).sum()
+    }
+}
+
+/// Runs database migrations to bring the schema up (or down) to a version.
+pub struct Migrator {
+    steps: Vec<MigrationStep>,
+}
+
+impl Default for Migrator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Migrator {
+    /// A migrator with this binary's steps, up to [`CURRENT_SCHEMA_VERSION`].
+    pub fn new() -> Self {
+        Self::with_steps(builtin_steps())
+    }
+
+    /// A migrator with custom steps; step `i` must migrate from version `i`.
+    pub fn with_steps(steps: Vec<MigrationStep>) -> Self {
+        assert!(
+            steps.iter().enumerate().all(|(i, s)| s.from == i as u32),
+            "migration steps must be contiguous from version 0"
+        );
+        Self { steps }
+    }
+
+    /// The newest schema version these steps reach.
+    pub fn latest(&self) -> u32 {
+        self.steps.len() as u32
+    }
+
+    /// The schema version stored in `env`; 0 for a fresh database.
+    pub fn stored_version(env: &LmdbEnvironment) -> Result<u32, LmdbError> {
+        env.meta_store()
+            .get_schema_version()
+            .map_err(|e| LmdbError::Schema(e.to_string()))
+    }
+
+    /// Fail if `env` was written by a newer node.
+    pub fn check(&self, env: &LmdbEnvironment) -> Result<u32, LmdbError> {
+        let stored = Self::stored_version(env)?;
+        if stored > self.latest() {
+            return Err(LmdbError::Schema(format!(
+                "database schema version {stored} is newer than version {} supported by this node; \
+                 upgrade the node",
+                self.latest()
             )));
         }
+        Ok(stored)
+    }
 
-        for version in current..CURRENT_SCHEMA_VERSION {
-            tracing::info!(from = version, to = version + 1, "running migration");
-            run_migration(version, version + 1)?;
+    /// Dry run: the steps from the stored version to `target`, and how many
+    /// records each would touch. Nothing is written.
+    pub fn plan(&self, env: &LmdbEnvironment, target: u32) -> Result<MigrationPlan, LmdbError> {
+        let from = self.check(env)?;
+        if target > self.latest() {
+            return Err(LmdbError::Schema(format!(
+                "no migration to schema version {target}; the newest is {}",
+                self.latest()
+            )));
         }
+        let rtxn = env.env().read_txn()?;
+        let steps = self
+            .path(from, target)
+            .map(|(step, up)| {
+                let (step_from, step_to) = if up {
+                    (step.from, step.from + 1)
+                } else {
+                    (step.from + 1, step.from)
+                };
+                Ok(PlannedStep {
+                    from: step_from,
+                    to: step_to,
+                    description: step.description,
+                    affected: (step.affected)(env, &rtxn)?,
+                })
+            })
+            .collect::<Result<_, LmdbError>>()?;
+        Ok(MigrationPlan {
+            from,
+            to: target,
+            steps,
+        })
+    }
 
-        meta_store
-            .set_schema_version(CURRENT_SCHEMA_VERSION)
-            .map_err(|e| LmdbError::Heed(e.to_string()))?;
-
-        tracing::info!(version = CURRENT_SCHEMA_VERSION, "migration complete");
-        Ok(())
+    /// Bring the database to the newest schema version.
+    pub fn run(&self, env: &LmdbEnvironment) -> Result<MigrationPlan, LmdbError> {
+        self.migrate_to(env, self.latest())
     }
-}
 
-fn run_migration(from: u32, to: u32) -> Result<(), LmdbError> {
-    match (from, to) {
-        (0, 1) => {
-            // Initial schema — nothing to migrate from a blank slate.
-            Ok(())
+    /// Migrate the database up or down to `target`, one step per write
+    /// transaction. Returns the plan that was carried out.
+    pub fn migrate_to(
+        &self,
+        env: &LmdbEnvironment,
+        target: u32,
+    ) -> Result<MigrationPlan, LmdbError> {
+        let plan = self.plan(env, target)?;
+        if plan.steps.is_empty() {
+            tracing::info!(version = plan.from, "database schema is up to date");
+            return Ok(plan);
         }
-        (1, 2) => {
-            // Schema v2: composite binary keys for all indexes.
-            // account_blocks_db removed (height_db is canonical),
-            // trst_origin_db uses (origin, tx_hash) composite keys,
-            // trst_expiry_db uses binary (expiry_be, tx_hash) keys,
-            // pending_db uses binary (dest, source_hash) keys,
-            // votes_db uses composite (proposal, voter) only (no length-prefixed),
-            // verification stores use composite (target, actor) keys,
-            // account_txs_db uses composite (account, tx_hash) keys,
-            // meta_db tracks verified_count counter.
-            // No data migration needed — no production data exists yet.
-            Ok(())
+        for ((step, up), planned) in self.path(plan.from, target).zip(&plan.steps) {
+            tracing::info!(
+                from = planned.from,
+                to = planned.to,
+                records = planned.affected,
+                "running migration: {}",
+                step.description
+            );
+            let started = Instant::now();
+            let mut progress = Progress {
+                from: planned.from,
+                to: planned.to,
+                total: planned.affected,
+                done: 0,
+            };
+            let mut wtxn = env.env().write_txn()?;
+            if up {
+                (step.up)(env, &mut wtxn, &mut progress)?;
+            } else {
+                (step.down)(env, &mut wtxn, &mut progress)?;
+            }
+            env.meta_db
+                .put(&mut wtxn, SCHEMA_VERSION_KEY, &planned.to.to_le_bytes())?;
+            wtxn.commit()?;
+            tracing::info!(
+                from = planned.from,
+                to = planned.to,
+                records = progress.done(),
+                elapsed_ms = started.elapsed().as_millis() as u64,
+                "migration step complete"
+            );
         }
-        _ => Err(LmdbError::Heed(format!(
-            "unknown migration: {} -> {}",
-            from, to
-        ))),
+        tracing::info!(version = target, "migration complete");
+        Ok(plan)
+    }
+
+    /// The steps from `from` to `to`, each with whether it runs up.
+    fn path(&self, from: u32, to: u32) -> impl Iterator<Item = (&MigrationStep, bool)> {
+        let (up, down): (&[MigrationStep], &[MigrationStep]) = if to >= from {
+            (&self.steps[from as usize..to as usize], &[])
+        } else {
+            (&[], &self.steps[to as usize..from as usize])
+        };
+        up.iter()
+            .map(|step| (step, true))
+            .chain(down.iter().rev().map(|step| (step, false)))
     }
 }
 
+fn no_records(_: &LmdbEnvironment, _: &RoTxn) -> Result<u64, LmdbError> {
+    Ok(0)
+}
+
+fn no_change(_: &LmdbEnvironment, _: &mut RwTxn, _: &mut Progress) -> Result<(), LmdbError> {
+    Ok(())
+}
+
+/// Blocks, accounts and pending entries held by the ledger.
+fn ledger_records(env: &LmdbEnvironment, rtxn: &RoTxn) -> Result<u64, LmdbError> {
+    Ok(env.blocks_db.len(rtxn)? + env.accounts_db.len(rtxn)? + env.pending_db.len(rtxn)?)
+}
+
+/// Refuse to move a ledger holding records across a change of record
+/// encoding that has no rewrite step.
+fn require_empty_ledger(
+    env: &LmdbEnvironment,
+    wtxn: &mut RwTxn,
+    _: &mut Progress,
+) -> Result<(), LmdbError> {
+    let records = ledger_records(env, wtxn)?;
+    if records > 0 {
+        return Err(LmdbError::Schema(format!(
+            "the ledger holds {records} records in an encoding this node cannot convert; \
+             re-sync into an empty data directory"
+        )));
+    }
+    Ok(())
+}
+
+/// This binary's migrations, in order.
+fn builtin_steps() -> Vec<MigrationStep> {
+    vec![
+        MigrationStep {
+            from: 0,
+            description: "initial schema",
+            affected: no_records,
+            up: no_change,
+            down: no_change,
+        },
+        // Schema v2: composite binary keys for all indexes.
+        // account_blocks_db removed (height_db is canonical),
+        // trst_origin_db uses (origin, tx_hash) composite keys,
+        // trst_expiry_db uses binary (expiry_be, tx_hash) keys,
+        // pending_db uses binary (dest, source_hash) keys,
+        // votes_db uses composite (proposal, voter) only (no length-prefixed),
+        // verification stores use composite (target, actor) keys,
+        // account_txs_db uses composite (account, tx_hash) keys,
+        // meta_db tracks verified_count counter.
+        // No data migration needed — no production data exists yet.
+        MigrationStep {
+            from: 1,
+            description: "composite binary index keys",
+            affected: no_records,
+            up: no_change,
+            down: no_change,
+        },
+        // Schema v3: blocks carry memo, HTLC, reject-reason, recovery,
+        // delegation-key and extension fields; pending entries carry memo,
+        // destination tag, HTLC terms and the returned flag; the account
+        // state tree is kept alongside the accounts. Records are not
+        // rewritten either way, so only an empty ledger crosses this step.
+        MigrationStep {
+            from: 2,
+            description: "block and pending record layout v3 (empty ledgers only)",
+            affected: ledger_records,
+            up: require_empty_ledger,
+            down: require_empty_ledger,
+        },
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use burst_store::block::BlockStore;
+
+    const MARKER_PREFIX: &[u8] = b"marker:";
+
+    fn count_markers(env: &LmdbEnvironment, rtxn: &RoTxn) -> Result<u64, LmdbError> {
+        Ok(env.meta_db.prefix_iter(rtxn, MARKER_PREFIX)?.count() as u64)
+    }
+
+    /// Test step 3 -> 4: rewrite every marker value from "old" to "new".
+    fn rewrite_markers(
+        env: &LmdbEnvironment,
+        wtxn: &mut RwTxn,
+        progress: &mut Progress,
+        value: &[u8],
+    ) -> Result<(), LmdbError> {
+        let keys: Vec<Vec<u8>> = env
+            .meta_db
+            .prefix_iter(wtxn, MARKER_PREFIX)?
+            .map(|entry| entry.map(|(key, _)| key.to_vec()))
+            .collect::<Result<_, _>>()?;
+        for key in keys {
+            env.meta_db.put(wtxn, &key, value)?;
+            progress.advance(1);
+        }
+        Ok(())
+    }
+
+    fn steps() -> Vec<MigrationStep> {
+        let mut steps = builtin_steps();
+        steps.push(MigrationStep {
+            from: 3,
+            description: "rewrite markers",
+            affected: count_markers,
+            up: |env, wtxn, progress| rewrite_markers(env, wtxn, progress, b"new"),
+            down: |env, wtxn, progress| rewrite_markers(env, wtxn, progress, b"old"),
+        });
+        steps
+    }
+
+    fn marker(env: &LmdbEnvironment, i: u32) -> Vec<u8> {
+        env.meta_store()
+            .get_meta(&format!("marker:{i}"))
+            .expect("marker")
+    }
+
+    #[test]
+    fn builtin_steps_reach_current_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+        let migrator = Migrator::new();
+        assert_eq!(migrator.latest(), CURRENT_SCHEMA_VERSION);
+        assert_eq!(migrator.plan(&env, 2).unwrap().steps.len(), 2);
+
+        // Genesis used to store the version as ASCII.
+        env.meta_store().put_meta("schema_version", b"1").unwrap();
+        let plan = migrator.run(&env).unwrap();
+        assert_eq!((plan.from, plan.to, plan.steps.len()), (1, 3, 2));
+        assert_eq!(
+            Migrator::stored_version(&env).unwrap(),
+            CURRENT_SCHEMA_VERSION
+        );
+        assert!(migrator.run(&env).unwrap().steps.is_empty());
+    }
+
+    #[test]
+    fn dry_run_counts_and_steps_reverse() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+        let meta = env.meta_store();
+        for i in 0..3 {
+            meta.put_meta(&format!("marker:{i}"), b"old").unwrap();
+        }
+        Migrator::new().run(&env).unwrap();
+
+        let migrator = Migrator::with_steps(steps());
+        let plan = migrator.plan(&env, 4).unwrap();
+        assert_eq!(
+            plan.steps,
+            vec![PlannedStep {
+                from: 3,
+                to: 4,
+                description: "rewrite markers",
+                affected: 3,
+            }]
+        );
+        // The dry run wrote nothing.
+        assert_eq!(marker(&env, 0), b"old");
+        assert_eq!(Migrator::stored_version(&env).unwrap(), 3);
+
+        migrator.run(&env).unwrap();
+        assert_eq!(marker(&env, 2), b"new");
+        assert_eq!(Migrator::stored_version(&env).unwrap(), 4);
+
+        // Back down, through every step.
+        let plan = migrator.migrate_to(&env, 0).unwrap();
+        let path: Vec<(u32, u32)> = plan.steps.iter().map(|s| (s.from, s.to)).collect();
+        assert_eq!(path, vec![(4, 3), (3, 2), (2, 1), (1, 0)]);
+        assert_eq!(marker(&env, 1), b"old");
+        assert_eq!(Migrator::stored_version(&env).unwrap(), 0);
+    }
 
     #[test]
-    fn unknown_migration_is_error() {
-        let result = run_migration(99, 100);
-        assert!(result.is_err());
+    fn ledger_in_the_old_record_layout_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+        let migrator = Migrator::new();
+        migrator.migrate_to(&env, 2).unwrap();
+
+        // A block as a schema v2 node stored it.
+        let hash = burst_types::BlockHash::new([1u8; 32]);
+        let old_block = b"v2 block without the appended payloads";
+        env.block_store().put_block(&hash, old_block).unwrap();
+
+        let plan = migrator.plan(&env, CURRENT_SCHEMA_VERSION).unwrap();
+        assert_eq!(plan.affected(), 1);
+        assert!(matches!(migrator.run(&env), Err(LmdbError::Schema(_))));
+        // Nothing was written: the ledger stays at v2, its block untouched.
+        assert_eq!(Migrator::stored_version(&env).unwrap(), 2);
+        assert_eq!(env.block_store().get_block(&hash).unwrap(), old_block);
+
+        env.block_store().delete_block(&hash).unwrap();
+        migrator.run(&env).unwrap();
+        assert_eq!(
+            Migrator::stored_version(&env).unwrap(),
+            CURRENT_SCHEMA_VERSION
+        );
     }
 
     #[test]
-    fn initial_migration_succeeds() {
-        let result = run_migration(0, 1);
-        assert!(result.is_ok());
+    fn newer_schema_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+        Migrator::with_steps(steps()).run(&env).unwrap();
+
+        let older = Migrator::new();
+        assert!(matches!(older.check(&env), Err(LmdbError::Schema(_))));
+        assert!(older.run(&env).is_err());
+        assert!(older.plan(&env, 1).is_err());
+        assert!(Migrator::with_steps(steps()).plan(&env, 9).is_err());
     }
 }
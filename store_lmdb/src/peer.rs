@@ -3,23 +3,45 @@
 use std::sync::Arc;
 
 use heed::types::Bytes;
-use heed::{Database, Env};
+use heed::Database;
 
-use burst_store::peer::PeerStore;
+use burst_store::peer::{PeerRecord, PeerStore};
 use burst_store::StoreError;
 
+use crate::gated_env::GatedEnv;
 use crate::LmdbError;
 
 pub struct LmdbPeerStore {
-    pub(crate) env: Arc<Env>,
+    pub(crate) env: Arc<GatedEnv>,
     pub(crate) peers_db: Database<Bytes, Bytes>,
+    pub(crate) peer_bans_db: Database<Bytes, Bytes>,
 }
 
 impl PeerStore for LmdbPeerStore {
     fn put_peer(&self, addr: &str, timestamp: u64) -> Result<(), StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        // Keep the quality figures already recorded for the peer.
+        let existing = self
+            .peers_db
+            .get(&wtxn, addr.as_bytes())
+            .map_err(LmdbError::from)?
+            .and_then(PeerRecord::from_bytes)
+            .unwrap_or_default();
+        let record = PeerRecord {
+            last_seen: timestamp,
+            ..existing
+        };
+        self.peers_db
+            .put(&mut wtxn, addr.as_bytes(), &record.to_bytes())
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn put_peer_record(&self, addr: &str, record: &PeerRecord) -> Result<(), StoreError> {
         let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
         self.peers_db
-            .put(&mut wtxn, addr.as_bytes(), &timestamp.to_le_bytes())
+            .put(&mut wtxn, addr.as_bytes(), &record.to_bytes())
             .map_err(LmdbError::from)?;
         wtxn.commit().map_err(LmdbError::from)?;
         Ok(())
@@ -31,14 +53,7 @@ impl PeerStore for LmdbPeerStore {
             .peers_db
             .get(&rtxn, addr.as_bytes())
             .map_err(LmdbError::from)?;
-        match val {
-            Some(bytes) if bytes.len() == 8 => {
-                let arr: [u8; 8] = bytes.try_into().expect("checked length");
-                Ok(Some(u64::from_le_bytes(arr)))
-            }
-            Some(_) => Ok(None),
-            None => Ok(None),
-        }
+        Ok(val.and_then(PeerRecord::from_bytes).map(|r| r.last_seen))
     }
 
     fn delete_peer(&self, addr: &str) -> Result<(), StoreError> {
@@ -51,14 +66,23 @@ impl PeerStore for LmdbPeerStore {
     }
 
     fn iter_peers(&self) -> Result<Vec<(String, u64)>, StoreError> {
+        Ok(self
+            .iter_peer_records()?
+            .into_iter()
+            .map(|(addr, record)| (addr, record.last_seen))
+            .collect())
+    }
+
+    fn iter_peer_records(&self) -> Result<Vec<(String, PeerRecord)>, StoreError> {
         let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
         let iter = self.peers_db.iter(&rtxn).map_err(LmdbError::from)?;
         let mut result = Vec::new();
         for entry in iter {
             let (key, val) = entry.map_err(LmdbError::from)?;
-            if let (Ok(addr), true) = (std::str::from_utf8(key), val.len() == 8) {
-                let arr: [u8; 8] = val.try_into().expect("checked length");
-                result.push((addr.to_string(), u64::from_le_bytes(arr)));
+            if let (Ok(addr), Some(record)) =
+                (std::str::from_utf8(key), PeerRecord::from_bytes(val))
+            {
+                result.push((addr.to_string(), record));
             }
         }
         Ok(result)
@@ -70,10 +94,8 @@ impl PeerStore for LmdbPeerStore {
         let mut to_delete = Vec::new();
         for entry in iter {
             let (key, val) = entry.map_err(LmdbError::from)?;
-            if val.len() == 8 {
-                let arr: [u8; 8] = val.try_into().expect("checked length");
-                let ts = u64::from_le_bytes(arr);
-                if ts < cutoff_secs {
+            if let Some(record) = PeerRecord::from_bytes(val) {
+                if record.last_seen < cutoff_secs {
                     to_delete.push(key.to_vec());
                 }
             }
@@ -92,4 +114,107 @@ impl PeerStore for LmdbPeerStore {
         }
         Ok(count)
     }
+
+    fn put_ban(&self, target: &str, until_secs: u64) -> Result<(), StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        self.peer_bans_db
+            .put(&mut wtxn, target.as_bytes(), &until_secs.to_le_bytes())
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn delete_ban(&self, target: &str) -> Result<(), StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        self.peer_bans_db
+            .delete(&mut wtxn, target.as_bytes())
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn iter_bans(&self) -> Result<Vec<(String, u64)>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let iter = self.peer_bans_db.iter(&rtxn).map_err(LmdbError::from)?;
+        let mut result = Vec::new();
+        for entry in iter {
+            let (key, val) = entry.map_err(LmdbError::from)?;
+            if let (Ok(target), true) = (std::str::from_utf8(key), val.len() == 8) {
+                let arr: [u8; 8] = val.try_into().expect("checked length");
+                result.push((target.to_string(), u64::from_le_bytes(arr)));
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LmdbEnvironment;
+
+    #[test]
+    fn peer_records_keep_quality_and_read_legacy_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 10 * 1024 * 1024).unwrap();
+        let store = env.peer_store();
+
+        // An entry from before quality was tracked: just the timestamp.
+        {
+            let mut wtxn = env.env().write_txn().unwrap();
+            store
+                .peers_db
+                .put(&mut wtxn, b"1.1.1.1:7075", &500u64.to_le_bytes())
+                .unwrap();
+            wtxn.commit().unwrap();
+        }
+        let record = PeerRecord {
+            last_seen: 900,
+            uptime_secs: 7_200,
+            handshake_attempts: 3,
+            handshake_successes: 2,
+            useful_votes: 40,
+        };
+        store.put_peer_record("2.2.2.2:7075", &record).unwrap();
+        store.put_peer("2.2.2.2:7075", 1_000).unwrap();
+
+        let mut records = store.iter_peer_records().unwrap();
+        records.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            records,
+            vec![
+                (
+                    "1.1.1.1:7075".to_string(),
+                    PeerRecord {
+                        last_seen: 500,
+                        ..Default::default()
+                    }
+                ),
+                (
+                    "2.2.2.2:7075".to_string(),
+                    PeerRecord {
+                        last_seen: 1_000,
+                        ..record
+                    }
+                ),
+            ]
+        );
+        assert_eq!(store.purge_older_than(600).unwrap(), 1);
+        assert_eq!(store.get_peer("2.2.2.2:7075").unwrap(), Some(1_000));
+    }
+
+    #[test]
+    fn bans_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 10 * 1024 * 1024).unwrap();
+        let store = env.peer_store();
+        store.put_ban("10.0.0.0/24", 1_000).unwrap();
+        store.put_ban("192.168.1.1/32", u64::MAX).unwrap();
+        store.put_ban("10.0.0.0/24", 2_000).unwrap();
+        store.delete_ban("192.168.1.1/32").unwrap();
+        assert_eq!(
+            store.iter_bans().unwrap(),
+            vec![("10.0.0.0/24".to_string(), 2_000)]
+        );
+    }
 }
@@ -8,17 +8,18 @@ use std::ops::Bound;
 use std::sync::Arc;
 
 use heed::types::Bytes;
-use heed::{Database, Env};
+use heed::Database;
 
 use burst_store::pending::{PendingInfo, PendingStore};
 use burst_store::StoreError;
 use burst_types::{TxHash, WalletAddress};
 
 use crate::block::increment_prefix;
+use crate::gated_env::GatedEnv;
 use crate::LmdbError;
 
 pub struct LmdbPendingStore {
-    pub(crate) env: Arc<Env>,
+    pub(crate) env: Arc<GatedEnv>,
     pub(crate) pending_db: Database<Bytes, Bytes>,
 }
 
@@ -134,4 +135,22 @@ impl PendingStore for LmdbPendingStore {
         let count = self.pending_db.len(&rtxn).map_err(LmdbError::from)?;
         Ok(count)
     }
+
+    fn iter_pending(&self) -> Result<Vec<(WalletAddress, TxHash, PendingInfo)>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let iter = self.pending_db.iter(&rtxn).map_err(LmdbError::from)?;
+        let mut results = Vec::new();
+        for result in iter {
+            let (key, val) = result.map_err(LmdbError::from)?;
+            let Some(split) = key.len().checked_sub(32) else {
+                continue;
+            };
+            let destination = WalletAddress::new(String::from_utf8_lossy(&key[..split]));
+            let mut arr = [0u8; 32];
+            arr.copy_from_slice(&key[split..]);
+            let info: PendingInfo = bincode::deserialize(val).map_err(LmdbError::from)?;
+            results.push((destination, TxHash::new(arr), info));
+        }
+        Ok(results)
+    }
 }
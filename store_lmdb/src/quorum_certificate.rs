@@ -0,0 +1,58 @@
+//! LMDB implementation of QuorumCertificateStore.
+
+use std::sync::Arc;
+
+use heed::types::Bytes;
+use heed::Database;
+
+use burst_store::quorum_certificate::QuorumCertificateStore;
+use burst_store::StoreError;
+use burst_types::BlockHash;
+
+use crate::gated_env::GatedEnv;
+use crate::LmdbError;
+
+pub struct LmdbQuorumCertificateStore {
+    pub(crate) env: Arc<GatedEnv>,
+    pub(crate) quorum_certificates_db: Database<Bytes, Bytes>,
+}
+
+impl QuorumCertificateStore for LmdbQuorumCertificateStore {
+    fn put_certificate(&self, hash: &BlockHash, certificate: &[u8]) -> Result<(), StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        self.quorum_certificates_db
+            .put(&mut wtxn, hash.as_bytes(), certificate)
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn get_certificate(&self, hash: &BlockHash) -> Result<Vec<u8>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let val = self
+            .quorum_certificates_db
+            .get(&rtxn, hash.as_bytes())
+            .map_err(LmdbError::from)?
+            .ok_or_else(|| LmdbError::NotFound(format!("quorum certificate {hash}")))?;
+        Ok(val.to_vec())
+    }
+
+    fn has_certificate(&self, hash: &BlockHash) -> Result<bool, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let found = self
+            .quorum_certificates_db
+            .get(&rtxn, hash.as_bytes())
+            .map_err(LmdbError::from)?
+            .is_some();
+        Ok(found)
+    }
+
+    fn certificate_count(&self) -> Result<u64, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let count = self
+            .quorum_certificates_db
+            .len(&rtxn)
+            .map_err(LmdbError::from)?;
+        Ok(count)
+    }
+}
@@ -3,16 +3,23 @@
 use std::sync::Arc;
 
 use heed::types::Bytes;
-use heed::{Database, Env};
+use heed::Database;
 
-use burst_store::rep_weights::RepWeightStore;
+use burst_store::rep_weights::{RepWeightStore, WeightSample};
 use burst_store::StoreError;
 use burst_types::WalletAddress;
 
+use crate::gated_env::GatedEnv;
 use crate::LmdbError;
 
+/// Read the `index`th big-endian u128 of a sample value, if present.
+fn sample_field(val: &[u8], index: usize) -> Option<u128> {
+    let bytes = val.get(index * 16..(index + 1) * 16)?;
+    Some(u128::from_be_bytes(bytes.try_into().ok()?))
+}
+
 pub struct LmdbRepWeightStore {
-    pub(crate) env: Arc<Env>,
+    pub(crate) env: Arc<GatedEnv>,
     pub(crate) rep_weights_db: Database<Bytes, Bytes>,
     pub(crate) online_weight_db: Database<Bytes, Bytes>,
 }
@@ -79,6 +86,20 @@ impl RepWeightStore for LmdbRepWeightStore {
         Ok(results)
     }
 
+    fn replace_rep_weights(&self, weights: &[(WalletAddress, u128)]) -> Result<(), StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        self.rep_weights_db
+            .clear(&mut wtxn)
+            .map_err(LmdbError::from)?;
+        for (rep, weight) in weights {
+            self.rep_weights_db
+                .put(&mut wtxn, rep.as_str().as_bytes(), &weight.to_be_bytes())
+                .map_err(LmdbError::from)?;
+        }
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
     fn put_online_weight_sample(&self, timestamp: u64, weight: u128) -> Result<(), StoreError> {
         let key = timestamp.to_be_bytes();
         let val = weight.to_be_bytes();
@@ -102,14 +123,54 @@ impl RepWeightStore for LmdbRepWeightStore {
                 break;
             }
             let (key, val) = entry.map_err(LmdbError::from)?;
-            if key.len() != 8 || val.len() != 16 {
+            let (Ok(ts_buf), Some(weight)) = (<[u8; 8]>::try_from(key), sample_field(val, 0))
+            else {
                 continue;
-            }
-            let mut ts_buf = [0u8; 8];
-            ts_buf.copy_from_slice(key);
-            let mut wt_buf = [0u8; 16];
-            wt_buf.copy_from_slice(val);
-            results.push((u64::from_be_bytes(ts_buf), u128::from_be_bytes(wt_buf)));
+            };
+            results.push((u64::from_be_bytes(ts_buf), weight));
+        }
+        Ok(results)
+    }
+
+    fn put_weight_sample(&self, sample: &WeightSample) -> Result<(), StoreError> {
+        // Online, trended and quorum delta, so older readers still find the
+        // online weight in the first 16 bytes.
+        let mut val = Vec::with_capacity(48);
+        val.extend_from_slice(&sample.online.to_be_bytes());
+        val.extend_from_slice(&sample.trended.unwrap_or(0).to_be_bytes());
+        val.extend_from_slice(&sample.quorum_delta.unwrap_or(0).to_be_bytes());
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        self.online_weight_db
+            .put(&mut wtxn, &sample.timestamp.to_be_bytes(), &val)
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn weight_samples(&self, from: u64, to: u64) -> Result<Vec<WeightSample>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let (start, end) = (from.to_be_bytes(), to.to_be_bytes());
+        let range = (
+            std::ops::Bound::Included(&start[..]),
+            std::ops::Bound::Included(&end[..]),
+        );
+        let iter = self
+            .online_weight_db
+            .range(&rtxn, &range)
+            .map_err(LmdbError::from)?;
+        let mut results = Vec::new();
+        for entry in iter {
+            let (key, val) = entry.map_err(LmdbError::from)?;
+            let (Ok(ts_buf), Some(online)) = (<[u8; 8]>::try_from(key), sample_field(val, 0))
+            else {
+                continue;
+            };
+            results.push(WeightSample {
+                timestamp: u64::from_be_bytes(ts_buf),
+                online,
+                trended: sample_field(val, 1),
+                quorum_delta: sample_field(val, 2),
+            });
         }
         Ok(results)
     }
@@ -122,7 +183,7 @@ mod tests {
 
     fn open_test_env() -> crate::LmdbEnvironment {
         let dir = tempfile::tempdir().unwrap();
-        crate::LmdbEnvironment::open(dir.path(), 30, 1 << 20).unwrap()
+        crate::LmdbEnvironment::open(dir.path(), 46, 1 << 20).unwrap()
     }
 
     #[test]
@@ -216,6 +277,70 @@ mod tests {
         assert_eq!(samples[0].0, 380); // newest
     }
 
+    #[test]
+    fn weight_samples_in_range_include_trend_and_quorum() {
+        let env = open_test_env();
+        let store = env.rep_weight_store();
+
+        // A sample from before trended weight was recorded.
+        store.put_online_weight_sample(1000, 400).unwrap();
+        for ts in [1020, 1040, 1060] {
+            store
+                .put_weight_sample(&WeightSample {
+                    timestamp: ts,
+                    online: u128::from(ts),
+                    trended: Some(900),
+                    quorum_delta: Some(600),
+                })
+                .unwrap();
+        }
+
+        let samples = store.weight_samples(1000, 1040).unwrap();
+        assert_eq!(
+            samples,
+            vec![
+                WeightSample {
+                    timestamp: 1000,
+                    online: 400,
+                    trended: None,
+                    quorum_delta: None,
+                },
+                WeightSample {
+                    timestamp: 1020,
+                    online: 1020,
+                    trended: Some(900),
+                    quorum_delta: Some(600),
+                },
+                WeightSample {
+                    timestamp: 1040,
+                    online: 1040,
+                    trended: Some(900),
+                    quorum_delta: Some(600),
+                },
+            ]
+        );
+        assert_eq!(
+            store.get_online_weight_samples(1).unwrap(),
+            vec![(1060, 1060)]
+        );
+    }
+
+    #[test]
+    fn replace_rep_weights_deletes_unlisted_reps() {
+        let env = open_test_env();
+        let store = env.rep_weight_store();
+        let (alice, bob) = (
+            WalletAddress::new("brst_alice"),
+            WalletAddress::new("brst_bob"),
+        );
+        store.put_rep_weight(&alice, 100).unwrap();
+        store.put_rep_weight(&bob, 200).unwrap();
+
+        store.replace_rep_weights(&[(alice.clone(), 150)]).unwrap();
+        assert_eq!(store.iter_rep_weights().unwrap(), vec![(alice, 150)]);
+        assert_eq!(store.get_rep_weight(&bob).unwrap(), None);
+    }
+
     #[test]
     fn empty_store_returns_none_and_empty() {
         let env = open_test_env();
@@ -0,0 +1,323 @@
+//! LMDB implementation of the account-state tree.
+//!
+//! Nodes live in `state_tree_db`, keyed by their depth (2 bytes, big-endian)
+//! followed by the leading `depth` bits of their path, zero-padded to whole
+//! bytes. A branch stores its hash; a leaf stores the account key and state
+//! hash so proofs can be built from the tree alone. Writing an account
+//! rehashes only the branches on its path, inside the same write
+//! transaction as the account record, so the root always matches the
+//! committed accounts.
+
+use std::sync::Arc;
+
+use heed::types::Bytes;
+use heed::{Database, RoTxn, RwTxn};
+
+use burst_store::account::AccountInfo;
+use burst_store::state_tree::{
+    account_key, account_state_hash, branch_hash, key_bit, leaf_hash, AccountStateProof,
+    StateTreeStore,
+};
+use burst_store::StoreError;
+use burst_types::{BlockHash, WalletAddress};
+
+use crate::gated_env::GatedEnv;
+use crate::LmdbError;
+
+const BRANCH: u8 = 0;
+const LEAF: u8 = 1;
+
+enum Node {
+    Branch(BlockHash),
+    Leaf {
+        key: [u8; 32],
+        state_hash: BlockHash,
+    },
+}
+
+impl Node {
+    fn hash(&self) -> BlockHash {
+        match self {
+            Node::Branch(hash) => *hash,
+            Node::Leaf { key, state_hash } => leaf_hash(key, state_hash),
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Node::Branch(hash) => [&[BRANCH][..], hash.as_bytes()].concat(),
+            Node::Leaf { key, state_hash } => [&[LEAF][..], key, state_hash.as_bytes()].concat(),
+        }
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, LmdbError> {
+        let corrupt = || LmdbError::Serialization("corrupt state tree node".into());
+        match bytes.split_first() {
+            Some((&BRANCH, hash)) => Ok(Node::Branch(BlockHash::new(
+                hash.try_into().map_err(|_| corrupt())?,
+            ))),
+            Some((&LEAF, rest)) if rest.len() == 64 => Ok(Node::Leaf {
+                key: rest[..32].try_into().map_err(|_| corrupt())?,
+                state_hash: BlockHash::new(rest[32..].try_into().map_err(|_| corrupt())?),
+            }),
+            _ => Err(corrupt()),
+        }
+    }
+}
+
+/// Database key of the node at `depth` on the path of `key`.
+fn node_key(key: &[u8; 32], depth: usize) -> Vec<u8> {
+    let mut node_key = Vec::with_capacity(2 + depth.div_ceil(8));
+    node_key.extend_from_slice(&(depth as u16).to_be_bytes());
+    node_key.extend_from_slice(&key[..depth.div_ceil(8)]);
+    if !depth.is_multiple_of(8) {
+        if let Some(last) = node_key.last_mut() {
+            *last &= 0xff << (8 - depth % 8);
+        }
+    }
+    node_key
+}
+
+/// `key` with bit `depth` flipped: a path into the sibling subtree.
+fn sibling_path(key: &[u8; 32], depth: usize) -> [u8; 32] {
+    let mut path = *key;
+    path[depth / 8] ^= 0x80 >> (depth % 8);
+    path
+}
+
+fn get_node(
+    txn: &RoTxn<'_>,
+    db: &Database<Bytes, Bytes>,
+    key: &[u8; 32],
+    depth: usize,
+) -> Result<Option<Node>, LmdbError> {
+    db.get(txn, &node_key(key, depth))?
+        .map(Node::decode)
+        .transpose()
+}
+
+fn put_node(
+    txn: &mut RwTxn<'_>,
+    db: &Database<Bytes, Bytes>,
+    key: &[u8; 32],
+    depth: usize,
+    node: &Node,
+) -> Result<(), LmdbError> {
+    db.put(txn, &node_key(key, depth), &node.encode())?;
+    Ok(())
+}
+
+fn node_hash(
+    txn: &RoTxn<'_>,
+    db: &Database<Bytes, Bytes>,
+    key: &[u8; 32],
+    depth: usize,
+) -> Result<BlockHash, LmdbError> {
+    Ok(get_node(txn, db, key, depth)?.map_or(BlockHash::ZERO, |node| node.hash()))
+}
+
+/// Insert or update the leaf of `info` and rehash the branches above it.
+pub(crate) fn update_account(
+    txn: &mut RwTxn<'_>,
+    db: &Database<Bytes, Bytes>,
+    info: &AccountInfo,
+) -> Result<(), LmdbError> {
+    let key = account_key(&info.address);
+    let state_hash = account_state_hash(info);
+
+    let mut depth = 0;
+    loop {
+        match get_node(txn, db, &key, depth)? {
+            Some(Node::Branch(_)) => depth += 1,
+            Some(Node::Leaf {
+                key: other,
+                state_hash: old,
+            }) if other != key => {
+                // Push the other account down to where the two paths part;
+                // the branches in between are rehashed below.
+                let split = (depth..256)
+                    .find(|&d| key_bit(&key, d) != key_bit(&other, d))
+                    .ok_or_else(|| LmdbError::Heed("state tree key collision".into()))?;
+                put_node(
+                    txn,
+                    db,
+                    &other,
+                    split + 1,
+                    &Node::Leaf {
+                        key: other,
+                        state_hash: old,
+                    },
+                )?;
+                depth = split + 1;
+                break;
+            }
+            Some(Node::Leaf {
+                state_hash: old, ..
+            }) if old == state_hash => return Ok(()),
+            _ => break,
+        }
+    }
+    put_node(txn, db, &key, depth, &Node::Leaf { key, state_hash })?;
+
+    for d in (0..depth).rev() {
+        let own = node_hash(txn, db, &key, d + 1)?;
+        let sibling = node_hash(txn, db, &sibling_path(&key, d), d + 1)?;
+        let hash = if key_bit(&key, d) == 0 {
+            branch_hash(&own, &sibling)
+        } else {
+            branch_hash(&sibling, &own)
+        };
+        put_node(txn, db, &key, d, &Node::Branch(hash))?;
+    }
+    Ok(())
+}
+
+/// Build the tree from `accounts_db` if it is empty while accounts exist,
+/// as in a ledger written before the tree was introduced.
+pub(crate) fn build_if_missing(
+    txn: &mut RwTxn<'_>,
+    db: &Database<Bytes, Bytes>,
+    accounts_db: &Database<Bytes, Bytes>,
+) -> Result<(), LmdbError> {
+    if !db.is_empty(txn)? || accounts_db.is_empty(txn)? {
+        return Ok(());
+    }
+    let accounts = accounts_db
+        .iter(txn)?
+        .map(|entry| Ok(bincode::deserialize::<AccountInfo>(entry?.1)?))
+        .collect::<Result<Vec<_>, LmdbError>>()?;
+    tracing::info!(accounts = accounts.len(), "building account state tree");
+    for info in &accounts {
+        update_account(txn, db, info)?;
+    }
+    Ok(())
+}
+
+pub struct LmdbStateTreeStore {
+    pub(crate) env: Arc<GatedEnv>,
+    pub(crate) state_tree_db: Database<Bytes, Bytes>,
+}
+
+impl StateTreeStore for LmdbStateTreeStore {
+    fn state_root(&self) -> Result<BlockHash, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        Ok(node_hash(&rtxn, &self.state_tree_db, &[0; 32], 0)?)
+    }
+
+    fn account_proof(
+        &self,
+        address: &WalletAddress,
+    ) -> Result<Option<AccountStateProof>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let key = account_key(address);
+        let mut siblings = Vec::new();
+        let mut depth = 0;
+        loop {
+            match get_node(&rtxn, &self.state_tree_db, &key, depth)? {
+                Some(Node::Branch(_)) => {
+                    siblings.push(node_hash(
+                        &rtxn,
+                        &self.state_tree_db,
+                        &sibling_path(&key, depth),
+                        depth + 1,
+                    )?);
+                    depth += 1;
+                }
+                Some(Node::Leaf {
+                    key: leaf,
+                    state_hash,
+                }) if leaf == key => {
+                    return Ok(Some(AccountStateProof {
+                        address: address.clone(),
+                        state_hash,
+                        siblings,
+                    }))
+                }
+                _ => return Ok(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LmdbEnvironment;
+    use burst_store::account::AccountStore;
+    use burst_types::WalletState;
+
+    fn account(name: &str, trst_balance: u128) -> AccountInfo {
+        let address = WalletAddress::new(format!("brst_{name}"));
+        AccountInfo {
+            address: address.clone(),
+            state: WalletState::Verified,
+            verified_at: None,
+            head: BlockHash::new([1; 32]),
+            block_count: 1,
+            confirmation_height: 0,
+            representative: address,
+            total_brn_burned: 0,
+            total_brn_staked: 0,
+            trst_balance,
+            expired_trst: 0,
+            revoked_trst: 0,
+            epoch: 0,
+        }
+    }
+
+    #[test]
+    fn root_tracks_accounts_and_proves_each_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 1 << 22).unwrap();
+        let tree = env.state_tree_store();
+        assert_eq!(tree.state_root().unwrap(), BlockHash::ZERO);
+
+        let accounts: Vec<_> = (0..40).map(|i| account(&format!("a{i}"), i)).collect();
+        for info in &accounts {
+            env.account_store().put_account(info).unwrap();
+        }
+        let root = tree.state_root().unwrap();
+        for info in &accounts {
+            let proof = tree.account_proof(&info.address).unwrap().unwrap();
+            assert!(proof.proves(info, &root));
+            assert!(!proof.proves(&account(&info.address.as_str()[5..], 99), &root));
+        }
+        assert!(tree
+            .account_proof(&WalletAddress::new("brst_missing"))
+            .unwrap()
+            .is_none());
+
+        // Cementing does not change the consensus state.
+        let mut cemented = accounts[3].clone();
+        cemented.confirmation_height = 1;
+        env.account_store().put_account(&cemented).unwrap();
+        assert_eq!(tree.state_root().unwrap(), root);
+
+        // The same accounts written in another order give the same root,
+        // and so does building the tree from scratch.
+        let dir2 = tempfile::tempdir().unwrap();
+        let env2 = LmdbEnvironment::open(dir2.path(), 46, 1 << 22).unwrap();
+        for info in accounts.iter().rev() {
+            env2.account_store().put_account(info).unwrap();
+        }
+        assert_eq!(env2.state_tree_store().state_root().unwrap(), root);
+
+        let mut wtxn = env2.env().write_txn().unwrap();
+        env2.state_tree_db.clear(&mut wtxn).unwrap();
+        build_if_missing(&mut wtxn, &env2.state_tree_db, &env2.accounts_db).unwrap();
+        wtxn.commit().unwrap();
+        assert_eq!(env2.state_tree_store().state_root().unwrap(), root);
+
+        // The same leaves hashed in memory give the same root.
+        let mut leaves: Vec<_> = accounts
+            .iter()
+            .map(|info| (account_key(&info.address), account_state_hash(info)))
+            .collect();
+        assert_eq!(burst_store::state_tree::tree_root(&mut leaves), root);
+
+        let mut batch = env.write_batch().unwrap();
+        batch.put_account_info(&account("a7", 1_000), true).unwrap();
+        batch.commit().unwrap();
+        assert_ne!(tree.state_root().unwrap(), root);
+    }
+}
@@ -8,17 +8,18 @@ use std::ops::Bound;
 use std::sync::Arc;
 
 use heed::types::Bytes;
-use heed::{Database, Env};
+use heed::Database;
 
 use burst_store::transaction::TransactionStore;
 use burst_store::StoreError;
 use burst_types::{TxHash, WalletAddress};
 
 use crate::block::increment_prefix;
+use crate::gated_env::GatedEnv;
 use crate::LmdbError;
 
 pub struct LmdbTransactionStore {
-    pub(crate) env: Arc<Env>,
+    pub(crate) env: Arc<GatedEnv>,
     pub(crate) transactions_db: Database<Bytes, Bytes>,
     pub(crate) account_txs_db: Database<Bytes, Bytes>,
 }
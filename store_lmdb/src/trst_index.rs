@@ -7,26 +7,31 @@
 //!   Big-endian u64 sorts lexicographically by time, enabling efficient range scans.
 //! - `trst_reverse_db`: `tx_hash(32)` → `origin_hash(32) ++ expiry_be_u64(8)`.
 //!   Enables O(1) `delete_token`.
+//!
+//! Wallet portfolios live alongside them in `trst_portfolio_db`:
+//! `wallet_address` → bincode-serialized portfolio.
 
 use std::ops::Bound;
 use std::sync::Arc;
 
 use heed::types::Bytes;
-use heed::{Database, Env};
+use heed::Database;
 
 use burst_store::trst_index::TrstIndexStore;
 use burst_store::StoreError;
-use burst_types::{Timestamp, TxHash};
+use burst_types::{Timestamp, TxHash, WalletAddress};
 
 use crate::block::increment_prefix;
+use crate::gated_env::GatedEnv;
 use crate::LmdbError;
 
 pub struct LmdbTrstIndexStore {
-    pub(crate) env: Arc<Env>,
+    pub(crate) env: Arc<GatedEnv>,
     pub(crate) trst_origin_db: Database<Bytes, Bytes>,
     pub(crate) trst_expiry_db: Database<Bytes, Bytes>,
     /// Reverse index: tx_hash(32) → origin_hash(32) + expiry_be(8).
     pub(crate) trst_reverse_db: Database<Bytes, Bytes>,
+    pub(crate) trst_portfolio_db: Database<Bytes, Bytes>,
 }
 
 /// Build the 64-byte composite key `origin_hash ++ tx_hash` for `trst_origin_db`.
@@ -227,4 +232,51 @@ impl TrstIndexStore for LmdbTrstIndexStore {
         wtxn.commit().map_err(LmdbError::from)?;
         Ok(())
     }
+
+    fn put_portfolio(&self, wallet: &WalletAddress, data: &[u8]) -> Result<(), StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        self.trst_portfolio_db
+            .put(&mut wtxn, wallet.as_str().as_bytes(), data)
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn get_portfolio(&self, wallet: &WalletAddress) -> Result<Vec<u8>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let val = self
+            .trst_portfolio_db
+            .get(&rtxn, wallet.as_str().as_bytes())
+            .map_err(LmdbError::from)?
+            .ok_or_else(|| LmdbError::NotFound(format!("portfolio {wallet}")))?;
+        Ok(val.to_vec())
+    }
+
+    fn iter_portfolios_paged(
+        &self,
+        cursor: Option<&WalletAddress>,
+        limit: usize,
+    ) -> Result<Vec<(WalletAddress, Vec<u8>)>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let start = match cursor {
+            Some(wallet) => Bound::Excluded(wallet.as_str().as_bytes()),
+            None => Bound::Unbounded,
+        };
+        let bounds = (start, Bound::<&[u8]>::Unbounded);
+        let mut page = Vec::with_capacity(limit);
+        for result in self
+            .trst_portfolio_db
+            .range(&rtxn, &bounds)
+            .map_err(LmdbError::from)?
+        {
+            if page.len() >= limit {
+                break;
+            }
+            let (key, val) = result.map_err(LmdbError::from)?;
+            let address =
+                std::str::from_utf8(key).map_err(|e| LmdbError::Serialization(e.to_string()))?;
+            page.push((WalletAddress::new(address), val.to_vec()));
+        }
+        Ok(page)
+    }
 }
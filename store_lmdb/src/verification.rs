@@ -2,26 +2,38 @@
 //!
 //! Endorsements and votes use composite keys `target_bytes ++ actor_bytes`
 //! so each entry is its own LMDB key/value pair. Listing all entries for a
-//! target is a prefix range-scan.
+//! target is a prefix range-scan. Verifier pool members are keyed by
+//! address alone, so a full scan yields the pool in address order.
+//! Verifier draws are keyed `target_bytes ++ drand_round_be`, so a target's
+//! prefix scan yields its draws oldest round first. Challenge history is
+//! keyed by address alone; outcome records by `completed_at_be ++ target`,
+//! so a full scan yields them in completion order. Revocation notices are
+//! keyed by the revoked address.
 
 use std::ops::Bound;
 use std::sync::Arc;
 
 use heed::types::Bytes;
-use heed::{Database, Env};
+use heed::Database;
 
-use burst_store::verification::VerificationStore;
+use burst_store::verification::{VerificationRecord, VerificationStore, VerifierSelection};
 use burst_store::StoreError;
 use burst_types::WalletAddress;
 
 use crate::block::increment_prefix;
+use crate::gated_env::GatedEnv;
 use crate::LmdbError;
 
 pub struct LmdbVerificationStore {
-    pub(crate) env: Arc<Env>,
+    pub(crate) env: Arc<GatedEnv>,
     pub(crate) endorsements_db: Database<Bytes, Bytes>,
     pub(crate) verification_votes_db: Database<Bytes, Bytes>,
     pub(crate) challenges_db: Database<Bytes, Bytes>,
+    pub(crate) verifier_pool_db: Database<Bytes, Bytes>,
+    pub(crate) verifier_selections_db: Database<Bytes, Bytes>,
+    pub(crate) challenge_history_db: Database<Bytes, Bytes>,
+    pub(crate) verification_records_db: Database<Bytes, Bytes>,
+    pub(crate) revocation_notices_db: Database<Bytes, Bytes>,
 }
 
 /// Build composite key `target_bytes ++ actor_bytes`.
@@ -37,7 +49,7 @@ fn composite_key(target: &WalletAddress, actor: &WalletAddress) -> Vec<u8> {
 /// Prefix range-scan: collect all values whose key starts with `prefix`.
 fn range_scan_values(
     db: &Database<Bytes, Bytes>,
-    env: &Env,
+    env: &GatedEnv,
     prefix: &[u8],
 ) -> Result<Vec<Vec<u8>>, LmdbError> {
     let rtxn = env.read_txn()?;
@@ -116,4 +128,249 @@ impl VerificationStore for LmdbVerificationStore {
             .map(|b| b.to_vec());
         Ok(val)
     }
+
+    fn put_verifier(&self, verifier: &WalletAddress, data: &[u8]) -> Result<(), StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        self.verifier_pool_db
+            .put(&mut wtxn, verifier.as_str().as_bytes(), data)
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn get_verifier(&self, verifier: &WalletAddress) -> Result<Option<Vec<u8>>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let val = self
+            .verifier_pool_db
+            .get(&rtxn, verifier.as_str().as_bytes())
+            .map_err(LmdbError::from)?
+            .map(|b| b.to_vec());
+        Ok(val)
+    }
+
+    fn delete_verifier(&self, verifier: &WalletAddress) -> Result<bool, StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        let existed = self
+            .verifier_pool_db
+            .delete(&mut wtxn, verifier.as_str().as_bytes())
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(existed)
+    }
+
+    fn iter_verifiers(&self) -> Result<Vec<(WalletAddress, Vec<u8>)>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let iter = self.verifier_pool_db.iter(&rtxn).map_err(LmdbError::from)?;
+        let mut result = Vec::new();
+        for entry in iter {
+            let (key, val) = entry.map_err(LmdbError::from)?;
+            if let Ok(address) = std::str::from_utf8(key) {
+                result.push((WalletAddress::new(address), val.to_vec()));
+            }
+        }
+        Ok(result)
+    }
+
+    fn put_verifier_selection(&self, selection: &VerifierSelection) -> Result<(), StoreError> {
+        let mut key = selection.target.as_str().as_bytes().to_vec();
+        key.extend_from_slice(&selection.drand_round.to_be_bytes());
+        let bytes = bincode::serialize(selection).map_err(LmdbError::from)?;
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        self.verifier_selections_db
+            .put(&mut wtxn, &key, &bytes)
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn get_verifier_selections(
+        &self,
+        target: &WalletAddress,
+    ) -> Result<Vec<VerifierSelection>, StoreError> {
+        let values = range_scan_values(
+            &self.verifier_selections_db,
+            &self.env,
+            target.as_str().as_bytes(),
+        )?;
+        values
+            .iter()
+            .map(|bytes| {
+                bincode::deserialize::<VerifierSelection>(bytes)
+                    .map_err(|e| StoreError::from(LmdbError::from(e)))
+            })
+            .filter(|selection| {
+                // A longer address can share this one as a prefix.
+                selection.as_ref().map_or(true, |s| &s.target == target)
+            })
+            .collect()
+    }
+
+    fn put_challenge_history(&self, wallet: &WalletAddress, data: &[u8]) -> Result<(), StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        self.challenge_history_db
+            .put(&mut wtxn, wallet.as_str().as_bytes(), data)
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn get_challenge_history(&self, wallet: &WalletAddress) -> Result<Option<Vec<u8>>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let val = self
+            .challenge_history_db
+            .get(&rtxn, wallet.as_str().as_bytes())
+            .map_err(LmdbError::from)?
+            .map(|b| b.to_vec());
+        Ok(val)
+    }
+
+    fn put_verification_record(
+        &self,
+        target: &WalletAddress,
+        record: &VerificationRecord,
+    ) -> Result<(), StoreError> {
+        let mut key = record.completed_at.as_secs().to_be_bytes().to_vec();
+        key.extend_from_slice(target.as_str().as_bytes());
+        let bytes = bincode::serialize(record).map_err(LmdbError::from)?;
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        self.verification_records_db
+            .put(&mut wtxn, &key, &bytes)
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn iter_verification_records(&self) -> Result<Vec<VerificationRecord>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let iter = self
+            .verification_records_db
+            .iter(&rtxn)
+            .map_err(LmdbError::from)?;
+        let mut result = Vec::new();
+        for entry in iter {
+            let (_, val) = entry.map_err(LmdbError::from)?;
+            result.push(bincode::deserialize(val).map_err(LmdbError::from)?);
+        }
+        Ok(result)
+    }
+
+    fn put_revocation_notice(&self, wallet: &WalletAddress, data: &[u8]) -> Result<(), StoreError> {
+        let mut wtxn = self.env.write_txn().map_err(LmdbError::from)?;
+        self.revocation_notices_db
+            .put(&mut wtxn, wallet.as_str().as_bytes(), data)
+            .map_err(LmdbError::from)?;
+        wtxn.commit().map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    fn get_revocation_notice(&self, wallet: &WalletAddress) -> Result<Option<Vec<u8>>, StoreError> {
+        let rtxn = self.env.read_txn().map_err(LmdbError::from)?;
+        let val = self
+            .revocation_notices_db
+            .get(&rtxn, wallet.as_str().as_bytes())
+            .map_err(LmdbError::from)?
+            .map(|b| b.to_vec());
+        Ok(val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LmdbEnvironment;
+    use burst_store::VerificationRecordOutcome;
+
+    #[test]
+    fn verifier_pool_lists_members_in_address_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 10 * 1024 * 1024).unwrap();
+        let store = env.verification_store();
+        let (alice, bob, carol) = (
+            WalletAddress::new("brst_alice"),
+            WalletAddress::new("brst_bob"),
+            WalletAddress::new("brst_carol"),
+        );
+
+        store.put_verifier(&carol, b"c").unwrap();
+        store.put_verifier(&alice, b"a").unwrap();
+        store.put_verifier(&bob, b"b").unwrap();
+        assert_eq!(store.get_verifier(&bob).unwrap(), Some(b"b".to_vec()));
+
+        assert!(store.delete_verifier(&bob).unwrap());
+        assert!(!store.delete_verifier(&bob).unwrap());
+        let pool = store.iter_verifiers().unwrap();
+        assert_eq!(pool, vec![(alice, b"a".to_vec()), (carol, b"c".to_vec())]);
+    }
+
+    #[test]
+    fn verifier_selections_are_kept_per_round() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 10 * 1024 * 1024).unwrap();
+        let store = env.verification_store();
+        let target = WalletAddress::new("brst_target");
+        let selection = |round: u64| VerifierSelection {
+            target: target.clone(),
+            drand_round: round,
+            randomness: [round as u8; 32],
+            pool_hash: [0; 32],
+            candidates: vec![WalletAddress::new("brst_v1"), WalletAddress::new("brst_v2")],
+            selected: vec![WalletAddress::new("brst_v2")],
+            selected_at: burst_types::Timestamp::new(round),
+        };
+
+        store.put_verifier_selection(&selection(300)).unwrap();
+        store.put_verifier_selection(&selection(7)).unwrap();
+        let mut other = selection(1);
+        other.target = WalletAddress::new("brst_target2");
+        store.put_verifier_selection(&other).unwrap();
+
+        let rounds: Vec<u64> = store
+            .get_verifier_selections(&target)
+            .unwrap()
+            .iter()
+            .map(|s| s.drand_round)
+            .collect();
+        assert_eq!(rounds, vec![7, 300]);
+    }
+
+    #[test]
+    fn verification_records_iterate_in_completion_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = LmdbEnvironment::open(dir.path(), 46, 10 * 1024 * 1024).unwrap();
+        let store = env.verification_store();
+        let record = |outcome, completed_at: u64| VerificationRecord {
+            outcome,
+            endorsement_count: 3,
+            started_at: burst_types::Timestamp::new(1),
+            completed_at: burst_types::Timestamp::new(completed_at),
+            votes_cast: 7,
+            votes_agreeing: 6,
+            neither_votes: 1,
+        };
+        let wallet = WalletAddress::new("brst_wallet");
+
+        store
+            .put_verification_record(
+                &wallet,
+                &record(VerificationRecordOutcome::FraudConfirmed, 900),
+            )
+            .unwrap();
+        store
+            .put_verification_record(&wallet, &record(VerificationRecordOutcome::Verified, 300))
+            .unwrap();
+
+        let outcomes: Vec<_> = store
+            .iter_verification_records()
+            .unwrap()
+            .into_iter()
+            .map(|r| r.outcome)
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                VerificationRecordOutcome::Verified,
+                VerificationRecordOutcome::FraudConfirmed
+            ]
+        );
+    }
 }
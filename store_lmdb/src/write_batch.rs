@@ -14,19 +14,19 @@
 //! If the batch is dropped without calling [`WriteBatch::commit`], all
 //! operations are rolled back (the underlying LMDB transaction is aborted).
 
-use heed::RwTxn;
-
 use burst_store::account::AccountInfo;
+use burst_store::alias::AliasRecord;
 use burst_store::StoreError;
 use burst_types::{BlockHash, Timestamp, TxHash, WalletAddress};
 
 use crate::environment::LmdbEnvironment;
+use crate::gated_env::WriteTxn;
 use crate::LmdbError;
 
 /// A write batch that groups multiple store operations into a single LMDB
 /// write transaction, amortising the cost of the fsync.
 pub struct WriteBatch<'a> {
-    txn: RwTxn<'a>,
+    txn: WriteTxn<'a>,
     env: &'a LmdbEnvironment,
 }
 
@@ -119,18 +119,25 @@ impl<'a> WriteBatch<'a> {
     // ── Account operations ──────────────────────────────────────────────
 
     /// Put an account info into the batch (pre-serialised bytes).
+    ///
+    /// The account-state tree is updated when `data` decodes as an
+    /// [`AccountInfo`].
     pub fn put_account(&mut self, address: &WalletAddress, data: &[u8]) -> Result<(), StoreError> {
         self.env
             .accounts_db
             .put(&mut self.txn, address.as_str().as_bytes(), data)
             .map_err(LmdbError::from)?;
+        if let Ok(info) = bincode::deserialize::<AccountInfo>(data) {
+            crate::state_tree::update_account(&mut self.txn, &self.env.state_tree_db, &info)?;
+        }
         Ok(())
     }
 
     /// Put an [`AccountInfo`] into the batch, serialising it automatically.
     ///
     /// Maintains the `verified_count` counter in `meta_db` by detecting
-    /// state transitions between Verified and non-Verified.
+    /// state transitions between Verified and non-Verified, and updates the
+    /// account-state tree.
     ///
     /// `was_verified` indicates whether the account was previously in the
     /// `Verified` state, avoiding an extra LMDB read + `bincode::deserialize`
@@ -145,6 +152,7 @@ impl<'a> WriteBatch<'a> {
             .accounts_db
             .put(&mut self.txn, info.address.as_str().as_bytes(), &bytes)
             .map_err(LmdbError::from)?;
+        crate::state_tree::update_account(&mut self.txn, &self.env.state_tree_db, info)?;
 
         let now_verified = info.state == burst_types::WalletState::Verified;
         if was_verified != now_verified {
@@ -319,6 +327,113 @@ impl<'a> WriteBatch<'a> {
         Ok(())
     }
 
+    /// Put a wallet's serialized TRST portfolio into the batch.
+    pub fn put_portfolio(&mut self, wallet: &WalletAddress, data: &[u8]) -> Result<(), StoreError> {
+        self.env
+            .trst_portfolio_db
+            .put(&mut self.txn, wallet.as_str().as_bytes(), data)
+            .map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    // ── Merger graph operations ─────────────────────────────────────────
+
+    /// Record that `merge_tx` consumed `origin`.
+    pub fn put_merge_origin(
+        &mut self,
+        origin: &TxHash,
+        merge_tx: &TxHash,
+    ) -> Result<(), StoreError> {
+        let mut key = [0u8; 64];
+        key[..32].copy_from_slice(origin.as_bytes());
+        key[32..].copy_from_slice(merge_tx.as_bytes());
+        self.env
+            .merger_origins_db
+            .put(&mut self.txn, &key[..], &[])
+            .map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    /// Record that `child` consumed the merged token of `parent`.
+    pub fn put_merge_downstream(
+        &mut self,
+        parent: &TxHash,
+        child: &TxHash,
+    ) -> Result<(), StoreError> {
+        let mut key = [0u8; 64];
+        key[..32].copy_from_slice(parent.as_bytes());
+        key[32..].copy_from_slice(child.as_bytes());
+        self.env
+            .merger_downstream_db
+            .put(&mut self.txn, &key[..], &[])
+            .map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    /// Store a serialized merge node.
+    pub fn put_merge_node(&mut self, merge_tx: &TxHash, data: &[u8]) -> Result<(), StoreError> {
+        self.env
+            .merger_nodes_db
+            .put(&mut self.txn, merge_tx.as_bytes().as_slice(), data)
+            .map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    /// Mark an origin as revoked.
+    pub fn put_revoked_origin(&mut self, origin: &TxHash) -> Result<(), StoreError> {
+        self.env
+            .merger_revoked_db
+            .put(&mut self.txn, origin.as_bytes().as_slice(), &[])
+            .map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    /// Clear an origin's revocation mark.
+    pub fn delete_revoked_origin(&mut self, origin: &TxHash) -> Result<(), StoreError> {
+        self.env
+            .merger_revoked_db
+            .delete(&mut self.txn, origin.as_bytes().as_slice())
+            .map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    // ── Verifier pool operations ────────────────────────────────────────
+
+    /// Record a verifier pool member with its locked stake.
+    pub fn put_verifier(
+        &mut self,
+        verifier: &WalletAddress,
+        data: &[u8],
+    ) -> Result<(), StoreError> {
+        self.env
+            .verifier_pool_db
+            .put(&mut self.txn, verifier.as_str().as_bytes(), data)
+            .map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    /// Remove a verifier from the pool.
+    pub fn delete_verifier(&mut self, verifier: &WalletAddress) -> Result<(), StoreError> {
+        self.env
+            .verifier_pool_db
+            .delete(&mut self.txn, verifier.as_str().as_bytes())
+            .map_err(LmdbError::from)?;
+        Ok(())
+    }
+
+    // ── Alias registry operations ───────────────────────────────────────
+
+    /// Register or renew an alias, keeping both indexes one-to-one.
+    pub fn put_alias(&mut self, record: &AliasRecord) -> Result<(), StoreError> {
+        crate::alias::put_alias_in(
+            &mut self.txn,
+            self.env.aliases_db,
+            self.env.alias_owners_db,
+            record,
+        )?;
+        Ok(())
+    }
+
     // ── Commit / rollback ───────────────────────────────────────────────
 
     /// Commit all batched operations in a single write transaction.
@@ -341,7 +456,7 @@ mod tests {
     fn temp_env() -> (tempfile::TempDir, LmdbEnvironment) {
         let dir = tempfile::tempdir().expect("failed to create temp dir");
         let env =
-            LmdbEnvironment::open(dir.path(), 30, 10 * 1024 * 1024).expect("failed to open env");
+            LmdbEnvironment::open(dir.path(), 46, 10 * 1024 * 1024).expect("failed to open env");
         (dir, env)
     }
 
@@ -0,0 +1,78 @@
+//! Alias transaction: register a human-readable name for an account.
+
+use burst_types::{Signature, Timestamp, TxHash, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+use crate::error::TransactionError;
+use crate::send::MAX_MEMO_LEN;
+
+/// Shortest alias accepted.
+pub const MIN_ALIAS_LEN: usize = 3;
+/// Longest alias accepted. The name travels in the block's memo field.
+pub const MAX_ALIAS_LEN: usize = MAX_MEMO_LEN;
+
+/// Register or renew `name` for the signing account, burning `fee` BRN.
+///
+/// Names are first come, first served. The owner keeps a name for the
+/// governed alias term after each registration or renewal; once the term
+/// and its grace period have passed anyone may register it. Registering a
+/// new name releases the account's previous one.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AliasTx {
+    pub hash: TxHash,
+    pub account: WalletAddress,
+    pub name: String,
+    /// BRN burned, the governed alias fee.
+    pub fee: u128,
+    pub timestamp: Timestamp,
+    pub work: u64,
+    pub signature: Signature,
+}
+
+/// Check that `name` is a well-formed alias: 3 to 32 lowercase ASCII
+/// letters, digits and inner hyphens, and not address-like.
+pub fn validate_alias_name(name: &str) -> Result<(), TransactionError> {
+    let invalid = |reason: &str| TransactionError::InvalidAlias(format!("{name:?}: {reason}"));
+    if !(MIN_ALIAS_LEN..=MAX_ALIAS_LEN).contains(&name.len()) {
+        return Err(invalid(&format!(
+            "must be {MIN_ALIAS_LEN} to {MAX_ALIAS_LEN} characters"
+        )));
+    }
+    if !name
+        .bytes()
+        .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+    {
+        return Err(invalid("only a-z, 0-9 and '-' are allowed"));
+    }
+    if name.starts_with('-') || name.ends_with('-') {
+        return Err(invalid("cannot start or end with '-'"));
+    }
+    if name.starts_with("brst") {
+        return Err(invalid("cannot start with \"brst\""));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alias_names() {
+        for good in ["bob", "alice-42", "a1b", &"x".repeat(MAX_ALIAS_LEN)] {
+            assert!(validate_alias_name(good).is_ok(), "{good}");
+        }
+        for bad in [
+            "ab",
+            "Bob",
+            "bob smith",
+            "-bob",
+            "bob-",
+            "brst_1abc",
+            "bob.eth",
+            &"x".repeat(MAX_ALIAS_LEN + 1),
+        ] {
+            assert!(validate_alias_name(bad).is_err(), "{bad}");
+        }
+    }
+}
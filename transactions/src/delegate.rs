@@ -1,8 +1,28 @@
-//! Delegation transactions: delegate and revoke voting power.
+//! Delegation transactions: delegate, rotate the delegation key, and revoke.
 
-use burst_types::{Signature, Timestamp, TxHash, WalletAddress};
+use burst_types::{PublicKey, Signature, Timestamp, TxHash, WalletAddress};
 use serde::{Deserialize, Serialize};
 
+/// Length of an encrypted delegation private key: 32 bytes of key plus the
+/// 16-byte Poly1305 tag.
+pub const ENCRYPTED_DELEGATION_KEY_LEN: usize = 48;
+
+/// A delegation key pair's public half, with the private half encrypted to
+/// the delegate so it can be distributed on-chain.
+///
+/// The private key is encrypted with X25519 + ChaCha20-Poly1305
+/// (`burst_crypto::encrypt_delegation_key`) from a one-time sender key, so
+/// every key generation is encrypted under a fresh key and nonce.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DelegationKey {
+    pub public_key: PublicKey,
+    /// The delegation private key, encrypted to the delegate.
+    pub encrypted_private_key: Vec<u8>,
+    /// X25519 public key of the one-time sender key; the delegate needs it
+    /// to decrypt.
+    pub sender_x25519_public: [u8; 32],
+}
+
 /// A delegation transaction.
 ///
 /// The delegator generates a secondary key pair and encrypts the private key
@@ -14,15 +34,27 @@ pub struct DelegateTx {
     pub delegator: WalletAddress,
     /// The wallet receiving delegation authority.
     pub delegate: WalletAddress,
-    /// The delegation public key (secondary key pair).
-    pub delegation_public_key: Vec<u8>,
-    /// The delegation private key, encrypted with the delegate's public key.
-    pub encrypted_delegation_key: Vec<u8>,
-    /// The delegator's X25519 public key (needed by delegate to decrypt).
-    #[serde(default)]
-    pub delegator_x25519_public: Vec<u8>,
+    /// The delegation key handed to the delegate.
+    pub key: DelegationKey,
+    pub timestamp: Timestamp,
+    pub work: u64,
+    pub signature: Signature,
+}
+
+/// Replace the delegation key of an active delegation with a new generation.
+///
+/// The delegate keeps its authority; signatures by the previous delegation
+/// key stop being accepted.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RotateDelegationKeyTx {
+    pub hash: TxHash,
+    pub delegator: WalletAddress,
+    /// The current delegate, who receives the new key.
+    pub delegate: WalletAddress,
+    pub key: DelegationKey,
     pub timestamp: Timestamp,
     pub work: u64,
+    /// Signed by the primary private key.
     pub signature: Signature,
 }
 
@@ -0,0 +1,21 @@
+//! Delegate profile transaction: anchor a governance delegate's profile.
+
+use burst_types::{Signature, Timestamp, TxHash, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+/// Publish the hash of a delegate's signed profile on their own chain.
+///
+/// The profile itself (name, statement, scopes) is kept off-chain and
+/// submitted to nodes separately; `anchor` is its hash (see
+/// `burst_governance::DelegateProfile::anchor`). A node only accepts the
+/// profile whose hash matches the delegate's latest anchor, so publishing a
+/// new anchor retires the previous profile.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DelegateProfileTx {
+    pub hash: TxHash,
+    pub delegate: WalletAddress,
+    pub anchor: [u8; 32],
+    pub timestamp: Timestamp,
+    pub work: u64,
+    pub signature: Signature,
+}
@@ -17,6 +17,33 @@ pub enum TransactionError {
     #[error("amount must be positive")]
     ZeroAmount,
 
+    #[error("memo is {len} bytes, maximum is {max}")]
+    MemoTooLong { len: usize, max: usize },
+
+    #[error("HTLC preimage does not match the hash lock")]
+    HtlcPreimageMismatch,
+
+    #[error("HTLC expired at {refund_after}; only a refund is possible")]
+    HtlcExpired { refund_after: u64 },
+
+    #[error("HTLC cannot be refunded before {refund_after}")]
+    HtlcNotExpired { refund_after: u64 },
+
+    #[error("HTLC refund timeout {refund_after} must be after creation time {created_at}")]
+    HtlcInvalidTimeout { refund_after: u64, created_at: u64 },
+
+    #[error("invalid recovery setup: {0}")]
+    RecoverySetupInvalid(String),
+
+    #[error("recovery needs {need} custodian approvals, got {have}")]
+    RecoveryInsufficientApprovals { have: usize, need: usize },
+
+    #[error("recovery cannot complete before {ready_at}")]
+    RecoveryTooEarly { ready_at: u64 },
+
+    #[error("invalid alias {0}")]
+    InvalidAlias(String),
+
     #[error("{0}")]
     Brn(String),
 
@@ -1,6 +1,13 @@
 //! Governance transactions: proposals and votes.
+//!
+//! A proposal is voted on either openly, with `GovernanceVoteTx`, or with
+//! sealed ballots: voters commit to a hidden ballot with
+//! `GovernanceCommitTx` while voting is open and disclose it with
+//! `GovernanceRevealTx` once it closes, so nobody sees a running tally.
+//! A proposal may also name another proposal that has to activate first.
+//! A treasury grant proposal pays TRST from the protocol treasury.
 
-use burst_types::{Signature, Timestamp, TxHash, WalletAddress};
+use burst_types::{BlockHash, Signature, Timestamp, TxHash, WalletAddress};
 use serde::{Deserialize, Serialize};
 
 /// A governance proposal transaction.
@@ -13,10 +20,89 @@ pub struct GovernanceProposalTx {
     pub timestamp: Timestamp,
     /// What is being proposed.
     pub proposal: ProposalContent,
+    /// How the proposal is voted on.
+    #[serde(default)]
+    pub ballot: BallotMode,
+    /// Proposal that has to activate before this one can.
+    #[serde(default)]
+    pub requires: Option<TxHash>,
     pub work: u64,
     pub signature: Signature,
 }
 
+/// How votes on a proposal are cast.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BallotMode {
+    /// Votes are public as soon as they are cast.
+    #[default]
+    Open,
+    /// Commit-reveal: votes stay hidden until the voting window closes.
+    Sealed,
+}
+
+impl BallotMode {
+    /// Flag in the last byte of a GovernanceProposal block's link. The
+    /// bincode-encoded content that fits in the link leaves it unused.
+    const SEALED_LINK_FLAG: u8 = 0x01;
+
+    /// The ballot mode a proposal block's link selects.
+    pub fn from_proposal_link(link: &BlockHash) -> Self {
+        if link.as_bytes()[31] & Self::SEALED_LINK_FLAG != 0 {
+            BallotMode::Sealed
+        } else {
+            BallotMode::Open
+        }
+    }
+
+    /// Mark a proposal block's link with this ballot mode.
+    pub fn mark_proposal_link(self, link: &mut [u8; 32]) {
+        if self == BallotMode::Sealed {
+            link[31] |= Self::SEALED_LINK_FLAG;
+        }
+    }
+}
+
+/// Flag in the last byte of a GovernanceProposal block's link saying the
+/// block's `origin` names a proposal that has to activate first.
+const DEPENDENCY_LINK_FLAG: u8 = 0x02;
+
+/// The proposal a GovernanceProposal block requires to activate first.
+pub fn proposal_dependency(link: &BlockHash, origin: &TxHash) -> Option<TxHash> {
+    let flags = link.as_bytes()[31];
+    (flags & DEPENDENCY_LINK_FLAG != 0 && flags & TREASURY_GRANT_LINK_FLAG == 0).then_some(*origin)
+}
+
+/// Mark a proposal block's link as requiring the proposal in its `origin`.
+pub fn mark_proposal_dependency(link: &mut [u8; 32]) {
+    link[31] |= DEPENDENCY_LINK_FLAG;
+}
+
+/// Flag in the last byte of a GovernanceProposal block's link saying the
+/// proposal is a treasury grant. The link starts with the amount
+/// (little-endian) and the block's `origin` holds the recipient's public
+/// key, so a grant cannot also require another proposal.
+const TREASURY_GRANT_LINK_FLAG: u8 = 0x04;
+
+/// Link of a GovernanceProposal block granting `amount` from the treasury.
+/// Ballot flags may be marked on it as usual.
+pub fn treasury_grant_link(amount: u128) -> [u8; 32] {
+    let mut link = [0u8; 32];
+    link[..16].copy_from_slice(&amount.to_le_bytes());
+    link[31] = TREASURY_GRANT_LINK_FLAG;
+    link
+}
+
+/// The amount and recipient public key of a treasury grant block.
+pub fn treasury_grant(link: &BlockHash, origin: &TxHash) -> Option<(u128, [u8; 32])> {
+    let bytes = link.as_bytes();
+    if bytes[31] & TREASURY_GRANT_LINK_FLAG == 0 {
+        return None;
+    }
+    let mut amount = [0u8; 16];
+    amount.copy_from_slice(&bytes[..16]);
+    Some((u128::from_le_bytes(amount), *origin.as_bytes()))
+}
+
 /// The content of a governance proposal.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum ProposalContent {
@@ -28,6 +114,11 @@ pub enum ProposalContent {
     },
     /// Amend the on-chain constitution (Consti).
     ConstitutionalAmendment { title: String, text: String },
+    /// Pay TRST from the protocol treasury.
+    TreasuryGrant {
+        recipient: WalletAddress,
+        amount: u128,
+    },
 }
 
 /// A governance vote transaction.
@@ -45,6 +136,46 @@ pub struct GovernanceVoteTx {
     pub signature: Signature,
 }
 
+/// Commit to a sealed ballot on a proposal.
+///
+/// `commitment` is `burst_governance::ballot_commitment` of the proposal,
+/// the voter and the sealed ballot; the vote itself is not disclosed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GovernanceCommitTx {
+    pub hash: TxHash,
+    pub voter: WalletAddress,
+    pub proposal_hash: TxHash,
+    pub commitment: [u8; 32],
+    pub timestamp: Timestamp,
+    pub work: u64,
+    pub signature: Signature,
+}
+
+/// Reveal a previously committed sealed ballot.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GovernanceRevealTx {
+    pub hash: TxHash,
+    pub voter: WalletAddress,
+    pub proposal_hash: TxHash,
+    pub vote: GovernanceVote,
+    /// Random salt that keeps the commitment from being brute-forced.
+    pub salt: [u8; 31],
+    pub timestamp: Timestamp,
+    pub work: u64,
+    pub signature: Signature,
+}
+
+impl GovernanceRevealTx {
+    /// The sealed ballot: the vote byte followed by the salt. Carried in
+    /// the reveal block's transaction field.
+    pub fn sealed_ballot(&self) -> [u8; 32] {
+        let mut ballot = [0u8; 32];
+        ballot[0] = self.vote.as_byte();
+        ballot[1..].copy_from_slice(&self.salt);
+        ballot
+    }
+}
+
 /// A vote on a governance proposal.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GovernanceVote {
@@ -55,3 +186,24 @@ pub enum GovernanceVote {
     /// Abstain (counted for quorum but not for supermajority).
     Abstain,
 }
+
+impl GovernanceVote {
+    /// Byte encoding used in vote and sealed-ballot blocks.
+    pub fn as_byte(self) -> u8 {
+        match self {
+            GovernanceVote::Yea => 0,
+            GovernanceVote::Nay => 1,
+            GovernanceVote::Abstain => 2,
+        }
+    }
+
+    /// Decode a vote byte.
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(GovernanceVote::Yea),
+            1 => Some(GovernanceVote::Nay),
+            2 => Some(GovernanceVote::Abstain),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+//! Group anchor transaction: publish a group's identity commitment on-chain.
+
+use burst_types::{Signature, Timestamp, TxHash, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+/// Anchor a trust group's signing key and endpoint on the registrant's chain.
+///
+/// `anchor` is a 32-byte commitment to the group ID, public key, and endpoint
+/// hash (see `burst_groups::GroupIdentity::anchor`). Clients that learn a
+/// group's identity off-chain recompute the commitment and compare it with
+/// the block's link to confirm the group key hasn't been swapped.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupAnchorTx {
+    pub hash: TxHash,
+    pub registrant: WalletAddress,
+    pub anchor: [u8; 32],
+    pub timestamp: Timestamp,
+    pub work: u64,
+    pub signature: Signature,
+}
@@ -0,0 +1,161 @@
+//! Hash-time-locked TRST transfers (conditional / atomic swaps).
+//!
+//! An HTLC lock debits the sender like a send, but the resulting pending
+//! entry can only be pocketed by the receiver with the preimage of
+//! `hash_lock` before `refund_after`. Once `refund_after` has passed, only
+//! the sender can reclaim it with a refund. Pairing two locks that share the
+//! same hash lock (one on BURST, one on an external chain) gives a
+//! trust-minimized trade: revealing the preimage to claim one side lets the
+//! counterparty claim the other.
+
+use burst_types::{Signature, Timestamp, TxHash, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+use crate::error::TransactionError;
+
+/// Length in bytes of an HTLC preimage (and of the hash lock).
+pub const HTLC_PREIMAGE_LEN: usize = 32;
+
+/// Compute the hash lock for a preimage (Blake2b-256).
+pub fn hash_lock_for(preimage: &[u8; HTLC_PREIMAGE_LEN]) -> [u8; 32] {
+    burst_crypto::blake2b_256(preimage)
+}
+
+/// HTLC payload carried on lock and claim blocks.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HtlcData {
+    /// Terms of a lock: claimable with the preimage of `hash_lock` strictly
+    /// before `refund_after`, refundable to the sender from then on.
+    Lock {
+        hash_lock: [u8; 32],
+        refund_after: Timestamp,
+    },
+    /// The revealed preimage on a claim.
+    Claim { preimage: [u8; HTLC_PREIMAGE_LEN] },
+}
+
+/// Lock TRST for `receiver` behind a hash lock with a refund timeout.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HtlcLockTx {
+    pub hash: TxHash,
+    pub sender: WalletAddress,
+    pub receiver: WalletAddress,
+    pub amount: u128,
+    pub hash_lock: [u8; 32],
+    pub refund_after: Timestamp,
+    pub timestamp: Timestamp,
+    /// Hash of the immediately preceding transaction this TRST was derived from.
+    pub link: TxHash,
+    /// Hash of the original burn transaction that created this TRST.
+    pub origin: TxHash,
+    pub work: u64,
+    pub signature: Signature,
+}
+
+/// Claim a locked transfer by revealing the preimage.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HtlcClaimTx {
+    pub hash: TxHash,
+    pub claimer: WalletAddress,
+    /// Hash of the lock block being claimed.
+    pub lock_block_hash: TxHash,
+    pub preimage: [u8; HTLC_PREIMAGE_LEN],
+    pub amount: u128,
+    pub timestamp: Timestamp,
+    pub work: u64,
+    pub signature: Signature,
+}
+
+/// Reclaim a locked transfer after its timeout.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HtlcRefundTx {
+    pub hash: TxHash,
+    pub refunder: WalletAddress,
+    /// Hash of the lock block being refunded.
+    pub lock_block_hash: TxHash,
+    pub amount: u128,
+    pub timestamp: Timestamp,
+    pub work: u64,
+    pub signature: Signature,
+}
+
+/// Validate the terms of a new lock created at `created_at`.
+pub fn validate_lock_terms(
+    refund_after: Timestamp,
+    created_at: Timestamp,
+) -> Result<(), TransactionError> {
+    if refund_after <= created_at {
+        return Err(TransactionError::HtlcInvalidTimeout {
+            refund_after: refund_after.as_secs(),
+            created_at: created_at.as_secs(),
+        });
+    }
+    Ok(())
+}
+
+/// Validate a claim against the lock terms: the preimage must hash to
+/// `hash_lock` and the claim must happen strictly before `refund_after`.
+pub fn validate_claim(
+    hash_lock: &[u8; 32],
+    refund_after: Timestamp,
+    preimage: &[u8; HTLC_PREIMAGE_LEN],
+    at: Timestamp,
+) -> Result<(), TransactionError> {
+    if at >= refund_after {
+        return Err(TransactionError::HtlcExpired {
+            refund_after: refund_after.as_secs(),
+        });
+    }
+    if hash_lock_for(preimage) != *hash_lock {
+        return Err(TransactionError::HtlcPreimageMismatch);
+    }
+    Ok(())
+}
+
+/// Validate a refund: only possible once `refund_after` has been reached.
+pub fn validate_refund(refund_after: Timestamp, at: Timestamp) -> Result<(), TransactionError> {
+    if at < refund_after {
+        return Err(TransactionError::HtlcNotExpired {
+            refund_after: refund_after.as_secs(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn claim_requires_matching_preimage_before_timeout() {
+        let preimage = [9u8; 32];
+        let lock = hash_lock_for(&preimage);
+        let timeout = Timestamp::new(1_000);
+
+        assert!(validate_claim(&lock, timeout, &preimage, Timestamp::new(999)).is_ok());
+        assert!(matches!(
+            validate_claim(&lock, timeout, &[8u8; 32], Timestamp::new(999)),
+            Err(TransactionError::HtlcPreimageMismatch)
+        ));
+        assert!(matches!(
+            validate_claim(&lock, timeout, &preimage, Timestamp::new(1_000)),
+            Err(TransactionError::HtlcExpired { .. })
+        ));
+    }
+
+    #[test]
+    fn refund_only_after_timeout() {
+        let timeout = Timestamp::new(1_000);
+        assert!(matches!(
+            validate_refund(timeout, Timestamp::new(999)),
+            Err(TransactionError::HtlcNotExpired { .. })
+        ));
+        assert!(validate_refund(timeout, Timestamp::new(1_000)).is_ok());
+    }
+
+    #[test]
+    fn lock_timeout_must_be_in_the_future() {
+        assert!(validate_lock_terms(Timestamp::new(10), Timestamp::new(5)).is_ok());
+        assert!(validate_lock_terms(Timestamp::new(5), Timestamp::new(5)).is_err());
+    }
+}
@@ -9,24 +9,40 @@
 //! - **Challenge**: Challenger stakes BRN to contest a wallet's legitimacy
 //! - **GovernanceProposal**: Propose a parameter or constitutional change
 //! - **GovernanceVote**: Cast a vote on a proposal
+//! - **GovernanceCommit / GovernanceReveal**: Commit to a sealed ballot, then reveal it after voting closes
 //! - **Delegate**: Delegate voting power to a representative
+//! - **RotateDelegationKey**: Replace the delegation key handed to the current delegate
 //! - **RevokeDelegation**: Revoke a previously delegated vote
 //! - **ChangeRepresentative**: Change consensus representative (for ORV)
+//! - **HtlcLock / HtlcClaim / HtlcRefund**: Hash-time-locked TRST transfer for atomic swaps
+//! - **GroupAnchor**: Anchor a trust group's signing key and endpoint on-chain
+//! - **RecoverySetup / Recovery**: Designate custodians and rotate a lost key with their approval
+//! - **VerifierOptIn / VerifierOptOut**: Join or leave the verifier pool, locking a BRN stake
+//! - **Liveness**: Prove an idle wallet's key is still in use, resuming suspended BRN accrual
+//! - **DelegateProfile**: Anchor the hash of a governance delegate's off-chain profile
+//! - **Alias**: Register or renew a human-readable name for an account, burning BRN
 
+pub mod alias;
 pub mod burn;
 pub mod challenge;
 pub mod delegate;
+pub mod delegate_profile;
 pub mod endorse;
 pub mod error;
 pub mod governance;
+pub mod group_anchor;
+pub mod htlc;
+pub mod liveness;
 pub mod merge;
 pub mod receive;
+pub mod recovery;
 pub mod reject_receive;
 pub mod representative;
 pub mod send;
 pub mod split;
 pub mod validation;
 pub mod verification_vote;
+pub mod verifier_pool;
 
 use burst_types::{Signature, Timestamp, TxHash, WalletAddress};
 use serde::{Deserialize, Serialize};
@@ -42,12 +58,26 @@ pub enum Transaction {
     Challenge(challenge::ChallengeTx),
     GovernanceProposal(governance::GovernanceProposalTx),
     GovernanceVote(governance::GovernanceVoteTx),
+    GovernanceCommit(governance::GovernanceCommitTx),
+    GovernanceReveal(governance::GovernanceRevealTx),
     Delegate(delegate::DelegateTx),
     RevokeDelegation(delegate::RevokeDelegationTx),
+    RotateDelegationKey(delegate::RotateDelegationKeyTx),
     Receive(receive::ReceiveTx),
     ChangeRepresentative(representative::ChangeRepresentativeTx),
     RejectReceive(reject_receive::RejectReceiveTx),
     VerificationVote(verification_vote::VerificationVoteTx),
+    HtlcLock(htlc::HtlcLockTx),
+    HtlcClaim(htlc::HtlcClaimTx),
+    HtlcRefund(htlc::HtlcRefundTx),
+    GroupAnchor(group_anchor::GroupAnchorTx),
+    RecoverySetup(recovery::RecoverySetupTx),
+    Recovery(recovery::RecoveryTx),
+    VerifierOptIn(verifier_pool::VerifierOptInTx),
+    VerifierOptOut(verifier_pool::VerifierOptOutTx),
+    Liveness(liveness::LivenessTx),
+    DelegateProfile(delegate_profile::DelegateProfileTx),
+    Alias(alias::AliasTx),
 }
 
 impl Transaction {
@@ -62,12 +92,26 @@ impl Transaction {
             Self::Challenge(tx) => &tx.hash,
             Self::GovernanceProposal(tx) => &tx.hash,
             Self::GovernanceVote(tx) => &tx.hash,
+            Self::GovernanceCommit(tx) => &tx.hash,
+            Self::GovernanceReveal(tx) => &tx.hash,
             Self::Delegate(tx) => &tx.hash,
             Self::RevokeDelegation(tx) => &tx.hash,
+            Self::RotateDelegationKey(tx) => &tx.hash,
             Self::Receive(tx) => &tx.hash,
             Self::ChangeRepresentative(tx) => &tx.hash,
             Self::RejectReceive(tx) => &tx.hash,
             Self::VerificationVote(tx) => &tx.hash,
+            Self::HtlcLock(tx) => &tx.hash,
+            Self::HtlcClaim(tx) => &tx.hash,
+            Self::HtlcRefund(tx) => &tx.hash,
+            Self::GroupAnchor(tx) => &tx.hash,
+            Self::RecoverySetup(tx) => &tx.hash,
+            Self::Recovery(tx) => &tx.hash,
+            Self::VerifierOptIn(tx) => &tx.hash,
+            Self::VerifierOptOut(tx) => &tx.hash,
+            Self::Liveness(tx) => &tx.hash,
+            Self::DelegateProfile(tx) => &tx.hash,
+            Self::Alias(tx) => &tx.hash,
         }
     }
 
@@ -82,12 +126,26 @@ impl Transaction {
             Self::Challenge(tx) => &tx.challenger,
             Self::GovernanceProposal(tx) => &tx.proposer,
             Self::GovernanceVote(tx) => &tx.voter,
+            Self::GovernanceCommit(tx) => &tx.voter,
+            Self::GovernanceReveal(tx) => &tx.voter,
             Self::Delegate(tx) => &tx.delegator,
             Self::RevokeDelegation(tx) => &tx.delegator,
+            Self::RotateDelegationKey(tx) => &tx.delegator,
             Self::Receive(tx) => &tx.receiver,
             Self::ChangeRepresentative(tx) => &tx.account,
             Self::RejectReceive(tx) => &tx.rejecter,
             Self::VerificationVote(tx) => &tx.voter,
+            Self::HtlcLock(tx) => &tx.sender,
+            Self::HtlcClaim(tx) => &tx.claimer,
+            Self::HtlcRefund(tx) => &tx.refunder,
+            Self::GroupAnchor(tx) => &tx.registrant,
+            Self::RecoverySetup(tx) => &tx.account,
+            Self::Recovery(tx) => &tx.account,
+            Self::VerifierOptIn(tx) => &tx.verifier,
+            Self::VerifierOptOut(tx) => &tx.verifier,
+            Self::Liveness(tx) => &tx.account,
+            Self::DelegateProfile(tx) => &tx.delegate,
+            Self::Alias(tx) => &tx.account,
         }
     }
 
@@ -102,12 +160,26 @@ impl Transaction {
             Self::Challenge(tx) => tx.timestamp,
             Self::GovernanceProposal(tx) => tx.timestamp,
             Self::GovernanceVote(tx) => tx.timestamp,
+            Self::GovernanceCommit(tx) => tx.timestamp,
+            Self::GovernanceReveal(tx) => tx.timestamp,
             Self::Delegate(tx) => tx.timestamp,
             Self::RevokeDelegation(tx) => tx.timestamp,
+            Self::RotateDelegationKey(tx) => tx.timestamp,
             Self::Receive(tx) => tx.timestamp,
             Self::ChangeRepresentative(tx) => tx.timestamp,
             Self::RejectReceive(tx) => tx.timestamp,
             Self::VerificationVote(tx) => tx.timestamp,
+            Self::HtlcLock(tx) => tx.timestamp,
+            Self::HtlcClaim(tx) => tx.timestamp,
+            Self::HtlcRefund(tx) => tx.timestamp,
+            Self::GroupAnchor(tx) => tx.timestamp,
+            Self::RecoverySetup(tx) => tx.timestamp,
+            Self::Recovery(tx) => tx.timestamp,
+            Self::VerifierOptIn(tx) => tx.timestamp,
+            Self::VerifierOptOut(tx) => tx.timestamp,
+            Self::Liveness(tx) => tx.timestamp,
+            Self::DelegateProfile(tx) => tx.timestamp,
+            Self::Alias(tx) => tx.timestamp,
         }
     }
 
@@ -122,12 +194,26 @@ impl Transaction {
             Self::Challenge(tx) => tx.work,
             Self::GovernanceProposal(tx) => tx.work,
             Self::GovernanceVote(tx) => tx.work,
+            Self::GovernanceCommit(tx) => tx.work,
+            Self::GovernanceReveal(tx) => tx.work,
             Self::Delegate(tx) => tx.work,
             Self::RevokeDelegation(tx) => tx.work,
+            Self::RotateDelegationKey(tx) => tx.work,
             Self::Receive(tx) => tx.work,
             Self::ChangeRepresentative(tx) => tx.work,
             Self::RejectReceive(tx) => tx.work,
             Self::VerificationVote(tx) => tx.work,
+            Self::HtlcLock(tx) => tx.work,
+            Self::HtlcClaim(tx) => tx.work,
+            Self::HtlcRefund(tx) => tx.work,
+            Self::GroupAnchor(tx) => tx.work,
+            Self::RecoverySetup(tx) => tx.work,
+            Self::Recovery(tx) => tx.work,
+            Self::VerifierOptIn(tx) => tx.work,
+            Self::VerifierOptOut(tx) => tx.work,
+            Self::Liveness(tx) => tx.work,
+            Self::DelegateProfile(tx) => tx.work,
+            Self::Alias(tx) => tx.work,
         }
     }
 
@@ -142,12 +228,26 @@ impl Transaction {
             Self::Challenge(tx) => &tx.signature,
             Self::GovernanceProposal(tx) => &tx.signature,
             Self::GovernanceVote(tx) => &tx.signature,
+            Self::GovernanceCommit(tx) => &tx.signature,
+            Self::GovernanceReveal(tx) => &tx.signature,
             Self::Delegate(tx) => &tx.signature,
             Self::RevokeDelegation(tx) => &tx.signature,
+            Self::RotateDelegationKey(tx) => &tx.signature,
             Self::Receive(tx) => &tx.signature,
             Self::ChangeRepresentative(tx) => &tx.signature,
             Self::RejectReceive(tx) => &tx.signature,
             Self::VerificationVote(tx) => &tx.signature,
+            Self::HtlcLock(tx) => &tx.signature,
+            Self::HtlcClaim(tx) => &tx.signature,
+            Self::HtlcRefund(tx) => &tx.signature,
+            Self::GroupAnchor(tx) => &tx.signature,
+            Self::RecoverySetup(tx) => &tx.signature,
+            Self::Recovery(tx) => &tx.signature,
+            Self::VerifierOptIn(tx) => &tx.signature,
+            Self::VerifierOptOut(tx) => &tx.signature,
+            Self::Liveness(tx) => &tx.signature,
+            Self::DelegateProfile(tx) => &tx.signature,
+            Self::Alias(tx) => &tx.signature,
         }
     }
 }
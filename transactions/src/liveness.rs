@@ -0,0 +1,17 @@
+//! Liveness transaction: proof that a wallet's key is still in use.
+
+use burst_types::{Signature, Timestamp, TxHash, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+/// Keepalive proof for an otherwise idle wallet.
+///
+/// Moves no value. Like any block it resets the inactivity clock, and for a
+/// wallet whose BRN accrual was suspended for inactivity it resumes accrual.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LivenessTx {
+    pub hash: TxHash,
+    pub account: WalletAddress,
+    pub timestamp: Timestamp,
+    pub work: u64,
+    pub signature: Signature,
+}
@@ -0,0 +1,238 @@
+//! Social recovery: custodian designation and key rotation.
+//!
+//! An account designates `n` custodians and a threshold `m` with a
+//! `RecoverySetup` block. If the account key is lost, the owner generates a
+//! new key and asks the custodians to approve rotating to it. Each approval
+//! is a custodian signature over [`recovery_digest`], which binds the
+//! account, the new key, the account head at request time and the request
+//! time. With `m` approvals, a `Recovery` block signed by the new key becomes
+//! valid once the configured delay has passed since the request.
+//!
+//! The delay is the owner's veto window: any block the old key publishes
+//! moves the head, which invalidates approvals bound to the old head.
+
+use std::collections::HashSet;
+
+use burst_types::{BlockHash, PublicKey, Signature, Timestamp, TxHash, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+use crate::error::TransactionError;
+
+/// Maximum number of custodians an account may designate.
+pub const MAX_CUSTODIANS: usize = 16;
+
+/// Domain separator for custodian approval signatures.
+const RECOVERY_DOMAIN: &[u8] = b"burst-recovery";
+
+/// A custodian's signature approving a key rotation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustodianApproval {
+    pub custodian: WalletAddress,
+    pub signature: Signature,
+}
+
+/// Recovery payload carried on recovery blocks.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecoveryData {
+    /// Designate custodians: any `threshold` of them can approve a rotation,
+    /// which takes effect `delay_secs` after it was requested.
+    Setup {
+        custodians: Vec<WalletAddress>,
+        threshold: u8,
+        delay_secs: u64,
+    },
+    /// Rotate the account key to `new_key` with custodian approvals.
+    Rotate {
+        new_key: PublicKey,
+        requested_at: Timestamp,
+        approvals: Vec<CustodianApproval>,
+    },
+}
+
+/// Designate recovery custodians for an account.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecoverySetupTx {
+    pub hash: TxHash,
+    pub account: WalletAddress,
+    pub custodians: Vec<WalletAddress>,
+    pub threshold: u8,
+    pub delay_secs: u64,
+    pub timestamp: Timestamp,
+    pub work: u64,
+    pub signature: Signature,
+}
+
+/// Rotate an account's key after custodian approval. Signed by the new key.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecoveryTx {
+    pub hash: TxHash,
+    pub account: WalletAddress,
+    pub new_key: PublicKey,
+    /// The account head the custodians approved against.
+    pub previous: BlockHash,
+    pub requested_at: Timestamp,
+    pub approvals: Vec<CustodianApproval>,
+    pub timestamp: Timestamp,
+    pub work: u64,
+    pub signature: Signature,
+}
+
+/// Digest a custodian signs to approve rotating `account` to `new_key`.
+pub fn recovery_digest(
+    account: &WalletAddress,
+    new_key: &PublicKey,
+    previous: &BlockHash,
+    requested_at: Timestamp,
+) -> [u8; 32] {
+    burst_crypto::blake2b_256_multi(&[
+        RECOVERY_DOMAIN,
+        account.as_str().as_bytes(),
+        new_key.as_bytes(),
+        previous.as_bytes(),
+        &requested_at.as_secs().to_be_bytes(),
+    ])
+}
+
+/// Validate a custodian designation for `account`.
+pub fn validate_setup(
+    account: &WalletAddress,
+    custodians: &[WalletAddress],
+    threshold: u8,
+) -> Result<(), TransactionError> {
+    let invalid = |reason: String| Err(TransactionError::RecoverySetupInvalid(reason));
+    if custodians.is_empty() || custodians.len() > MAX_CUSTODIANS {
+        return invalid(format!(
+            "{} custodians, must be 1 to {MAX_CUSTODIANS}",
+            custodians.len()
+        ));
+    }
+    if threshold == 0 || threshold as usize > custodians.len() {
+        return invalid(format!(
+            "threshold {threshold} must be between 1 and {}",
+            custodians.len()
+        ));
+    }
+    let mut seen = HashSet::new();
+    for custodian in custodians {
+        if custodian == account {
+            return invalid("an account cannot be its own custodian".into());
+        }
+        if !seen.insert(custodian) {
+            return invalid(format!("duplicate custodian {custodian}"));
+        }
+    }
+    Ok(())
+}
+
+/// Count distinct designated custodians that validly signed the rotation and
+/// require at least `threshold` of them.
+pub fn verify_approvals(
+    custodians: &[WalletAddress],
+    threshold: u8,
+    digest: &[u8; 32],
+    approvals: &[CustodianApproval],
+) -> Result<(), TransactionError> {
+    let mut approved = HashSet::new();
+    for approval in approvals {
+        if !custodians.contains(&approval.custodian) || approved.contains(&approval.custodian) {
+            continue;
+        }
+        let Some(key) = burst_crypto::decode_address(approval.custodian.as_str()) else {
+            continue;
+        };
+        if burst_crypto::verify_signature(digest, &approval.signature, &PublicKey(key)) {
+            approved.insert(&approval.custodian);
+        }
+    }
+    if approved.len() < threshold as usize {
+        return Err(TransactionError::RecoveryInsufficientApprovals {
+            have: approved.len(),
+            need: threshold as usize,
+        });
+    }
+    Ok(())
+}
+
+/// The rotation may only land once `delay_secs` have passed since the request.
+pub fn validate_delay(
+    requested_at: Timestamp,
+    delay_secs: u64,
+    at: Timestamp,
+) -> Result<(), TransactionError> {
+    let ready_at = requested_at.as_secs().saturating_add(delay_secs);
+    if at.as_secs() < ready_at {
+        return Err(TransactionError::RecoveryTooEarly { ready_at });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custodian(seed: u8) -> (WalletAddress, burst_types::KeyPair) {
+        let keys = burst_crypto::keypair_from_seed(&[seed; 32]);
+        (burst_crypto::derive_address(&keys.public), keys)
+    }
+
+    #[test]
+    fn setup_rejects_bad_thresholds_and_duplicates() {
+        let (owner, _) = custodian(1);
+        let (a, _) = custodian(2);
+        let (b, _) = custodian(3);
+        assert!(validate_setup(&owner, &[a.clone(), b.clone()], 2).is_ok());
+        assert!(validate_setup(&owner, &[a.clone(), b.clone()], 3).is_err());
+        assert!(validate_setup(&owner, std::slice::from_ref(&a), 0).is_err());
+        assert!(validate_setup(&owner, &[a.clone(), a.clone()], 1).is_err());
+        assert!(validate_setup(&owner, &[a, owner.clone()], 1).is_err());
+        assert!(validate_setup(&owner, &[], 1).is_err());
+    }
+
+    #[test]
+    fn approvals_count_distinct_designated_custodians() {
+        let (owner, _) = custodian(1);
+        let (a, a_keys) = custodian(2);
+        let (b, b_keys) = custodian(3);
+        let (outsider, outsider_keys) = custodian(4);
+        let new_key = burst_crypto::keypair_from_seed(&[5u8; 32]).public;
+        let digest = recovery_digest(&owner, &new_key, &BlockHash::ZERO, Timestamp::new(100));
+        let approve = |who: &WalletAddress, keys: &burst_types::KeyPair| CustodianApproval {
+            custodian: who.clone(),
+            signature: burst_crypto::sign_message(&digest, &keys.private),
+        };
+        let custodians = [a.clone(), b.clone()];
+
+        // A repeated approval and an outsider's approval don't count.
+        let approvals = vec![
+            approve(&a, &a_keys),
+            approve(&a, &a_keys),
+            approve(&outsider, &outsider_keys),
+        ];
+        assert!(matches!(
+            verify_approvals(&custodians, 2, &digest, &approvals),
+            Err(TransactionError::RecoveryInsufficientApprovals { have: 1, need: 2 })
+        ));
+
+        // A custodian signing with the wrong key doesn't count either.
+        let forged = CustodianApproval {
+            custodian: b.clone(),
+            signature: burst_crypto::sign_message(&digest, &a_keys.private),
+        };
+        assert!(
+            verify_approvals(&custodians, 2, &digest, &[approve(&a, &a_keys), forged]).is_err()
+        );
+
+        let approvals = vec![approve(&a, &a_keys), approve(&b, &b_keys)];
+        assert!(verify_approvals(&custodians, 2, &digest, &approvals).is_ok());
+    }
+
+    #[test]
+    fn rotation_waits_for_the_delay() {
+        let requested = Timestamp::new(1_000);
+        assert!(matches!(
+            validate_delay(requested, 500, Timestamp::new(1_499)),
+            Err(TransactionError::RecoveryTooEarly { ready_at: 1_500 })
+        ));
+        assert!(validate_delay(requested, 500, Timestamp::new(1_500)).is_ok());
+    }
+}
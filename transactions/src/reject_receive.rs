@@ -1,17 +1,92 @@
 //! Reject-receive transaction: decline a pending TRST send.
 
+use std::fmt;
+
 use burst_types::{Signature, Timestamp, TxHash, WalletAddress};
 use serde::{Deserialize, Serialize};
 
+/// Why a receiver declined a pending transfer.
+///
+/// Carried on the reject block and relayed to the sender so wallets can show
+/// something more useful than "your payment bounced".
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectReason {
+    /// The transfer violates the receiver's wallet policy (amount, memo, …).
+    PolicyViolation,
+    /// The receiver does not accept transfers from this sender.
+    UnknownSender,
+    /// The TRST's provenance includes origins the receiver won't accept.
+    TaintedProvenance,
+}
+
+impl RejectReason {
+    /// Stable one-byte code used in the block hash preimage.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::PolicyViolation => 1,
+            Self::UnknownSender => 2,
+            Self::TaintedProvenance => 3,
+        }
+    }
+
+    /// Inverse of [`RejectReason::code`].
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(Self::PolicyViolation),
+            2 => Some(Self::UnknownSender),
+            3 => Some(Self::TaintedProvenance),
+            _ => None,
+        }
+    }
+
+    /// Snake-case name, as used on the wire.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PolicyViolation => "policy_violation",
+            Self::UnknownSender => "unknown_sender",
+            Self::TaintedProvenance => "tainted_provenance",
+        }
+    }
+}
+
+impl fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// A transaction that rejects a pending TRST receive, returning it to the sender.
 ///
 /// The `send_block_hash` references the original send block being declined.
+/// The rejected amount becomes a pending entry for the original sender, keyed
+/// by the reject block's hash.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RejectReceiveTx {
     pub hash: TxHash,
     pub rejecter: WalletAddress,
     pub send_block_hash: TxHash,
+    pub reason: RejectReason,
     pub timestamp: Timestamp,
     pub work: u64,
     pub signature: Signature,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reason_codes_roundtrip() {
+        for reason in [
+            RejectReason::PolicyViolation,
+            RejectReason::UnknownSender,
+            RejectReason::TaintedProvenance,
+        ] {
+            assert_eq!(RejectReason::from_code(reason.code()), Some(reason));
+            let json = serde_json::to_string(&reason).unwrap();
+            assert_eq!(json, format!("\"{reason}\""));
+        }
+        assert_eq!(RejectReason::from_code(0), None);
+    }
+}
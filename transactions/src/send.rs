@@ -3,6 +3,9 @@
 use burst_types::{Signature, Timestamp, TxHash, WalletAddress};
 use serde::{Deserialize, Serialize};
 
+/// Maximum length in bytes of the optional memo/reference on a send.
+pub const MAX_MEMO_LEN: usize = 32;
+
 /// A TRST send transaction.
 ///
 /// Carries `link` (previous tx) and `origin` (original burn tx) for provenance tracking.
@@ -17,6 +20,12 @@ pub struct SendTx {
     pub link: TxHash,
     /// Hash of the original burn transaction that created this TRST.
     pub origin: TxHash,
+    /// Optional payment memo/reference for merchant reconciliation
+    /// (at most `MAX_MEMO_LEN` bytes).
+    pub memo: Option<Vec<u8>>,
+    /// Optional destination tag identifying the depositor at a shared
+    /// receiving account (e.g. an exchange customer).
+    pub destination_tag: Option<u64>,
     pub work: u64,
     pub signature: Signature,
 }
@@ -68,6 +68,12 @@ pub fn validate_transaction(
             return Err(TransactionError::ZeroAmount);
         }
         Transaction::Endorse(_) => {}
+        Transaction::HtlcLock(lock_tx) => {
+            if lock_tx.amount == 0 {
+                return Err(TransactionError::ZeroAmount);
+            }
+            crate::htlc::validate_lock_terms(lock_tx.refund_after, lock_tx.timestamp)?;
+        }
         _ => {}
     }
 
@@ -117,6 +123,16 @@ pub fn validate_send(tx: &crate::send::SendTx, _now: Timestamp) -> Result<(), Tr
         ));
     }
 
+    // Memo must fit within the fixed reference size
+    if let Some(ref memo) = tx.memo {
+        if memo.len() > crate::send::MAX_MEMO_LEN {
+            return Err(TransactionError::MemoTooLong {
+                len: memo.len(),
+                max: crate::send::MAX_MEMO_LEN,
+            });
+        }
+    }
+
     Ok(())
 }
 
@@ -316,6 +332,8 @@ mod tests {
             timestamp: Timestamp::new(1000),
             link: dummy_tx_hash(),
             origin: dummy_tx_hash(),
+            memo: None,
+            destination_tag: None,
             work: 0,
             signature: dummy_signature(),
         };
@@ -336,6 +354,8 @@ mod tests {
             timestamp: Timestamp::new(1000),
             link: dummy_tx_hash(),
             origin: dummy_tx_hash(),
+            memo: None,
+            destination_tag: None,
             work: 0,
             signature: dummy_signature(),
         };
@@ -355,6 +375,8 @@ mod tests {
             timestamp: Timestamp::new(1000),
             link: dummy_tx_hash(),
             origin: TxHash::ZERO,
+            memo: None,
+            destination_tag: None,
             work: 0,
             signature: dummy_signature(),
         };
@@ -374,6 +396,8 @@ mod tests {
             timestamp: Timestamp::new(1000),
             link: TxHash::ZERO,
             origin: dummy_tx_hash(),
+            memo: None,
+            destination_tag: None,
             work: 0,
             signature: dummy_signature(),
         };
@@ -393,6 +417,8 @@ mod tests {
             timestamp: Timestamp::new(1000),
             link: dummy_tx_hash(),
             origin: dummy_tx_hash(),
+            memo: None,
+            destination_tag: None,
             work: 0,
             signature: dummy_signature(),
         };
@@ -401,6 +427,31 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_validate_send_memo_too_long() {
+        let mut tx = SendTx {
+            hash: dummy_tx_hash(),
+            sender: dummy_wallet_address(),
+            receiver: dummy_wallet_address_2(),
+            amount: 100,
+            timestamp: Timestamp::new(1000),
+            link: dummy_tx_hash(),
+            origin: dummy_tx_hash(),
+            memo: Some(vec![7u8; crate::send::MAX_MEMO_LEN]),
+            destination_tag: None,
+            work: 0,
+            signature: dummy_signature(),
+        };
+        assert!(validate_send(&tx, Timestamp::new(1000)).is_ok());
+
+        tx.memo = Some(vec![7u8; crate::send::MAX_MEMO_LEN + 1]);
+        let result = validate_send(&tx, Timestamp::new(1000));
+        assert!(matches!(
+            result.unwrap_err(),
+            TransactionError::MemoTooLong { len: 33, max: 32 }
+        ));
+    }
+
     #[test]
     fn test_validate_split_zero_parent_hash() {
         let tx = SplitTx {
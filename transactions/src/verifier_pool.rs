@@ -0,0 +1,32 @@
+//! Verifier pool transactions: join or leave the pool of eligible verifiers.
+
+use burst_types::{Signature, Timestamp, TxHash, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+/// Join the verifier pool.
+///
+/// Locks `stake_amount` BRN (the governed verifier stake) for as long as the
+/// verifier stays in the pool. Only pool members can be drawn by VRF
+/// selection to vote on a wallet's humanity.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VerifierOptInTx {
+    pub hash: TxHash,
+    pub verifier: WalletAddress,
+    /// BRN locked while in the pool.
+    pub stake_amount: u128,
+    pub timestamp: Timestamp,
+    pub work: u64,
+    pub signature: Signature,
+}
+
+/// Leave the verifier pool; the locked stake is returned.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VerifierOptOutTx {
+    pub hash: TxHash,
+    pub verifier: WalletAddress,
+    /// The stake locked at opt-in, credited back to the BRN balance.
+    pub stake_amount: u128,
+    pub timestamp: Timestamp,
+    pub work: u64,
+    pub signature: Signature,
+}
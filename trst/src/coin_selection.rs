@@ -0,0 +1,215 @@
+//! Coin control — choosing which tokens a send consumes.
+//!
+//! Sends are amount-based, but the tokens behind the amount differ in expiry
+//! and in how many origins they could be revoked through. A [`CoinSelection`]
+//! lets the sender decide, and [`select_coins`] reports the provenance that
+//! would leave the wallet so it can be shown before signing.
+
+use burst_types::{Timestamp, TxHash};
+use serde::{Deserialize, Serialize};
+
+use crate::engine::ConsumedProvenance;
+use crate::error::TrstError;
+use crate::token::TrstToken;
+
+/// How a send picks the tokens it consumes.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CoinSelection {
+    /// Oldest origin first — what the ledger does when nothing is specified.
+    #[default]
+    Fifo,
+    /// Exactly these tokens, in this order.
+    Pinned(Vec<TxHash>),
+    /// Tokens closest to expiry first, so value about to lapse is spent.
+    OldestExpiryFirst,
+    /// Tokens with the fewest distinct origin wallets first, so the receiver
+    /// gets the TRST least exposed to revocation.
+    CleanestProvenanceFirst,
+}
+
+/// Number of distinct origin wallets a token could be revoked through.
+fn origin_wallet_count(token: &TrstToken) -> usize {
+    if token.origin_proportions.is_empty() {
+        return 1;
+    }
+    let mut wallets: Vec<_> = token
+        .origin_proportions
+        .iter()
+        .map(|p| &p.origin_wallet)
+        .collect();
+    wallets.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    wallets.dedup();
+    wallets.len()
+}
+
+/// Transferable tokens in the order `selection` consumes them.
+fn consumption_order<'a>(
+    tokens: &'a [TrstToken],
+    now: Timestamp,
+    expiry_secs: u64,
+    selection: &CoinSelection,
+) -> Result<Vec<&'a TrstToken>, TrstError> {
+    let usable = |t: &&TrstToken| t.is_transferable(now, expiry_secs);
+    let mut ordered: Vec<&TrstToken> = match selection {
+        CoinSelection::Pinned(ids) => {
+            let mut pinned = Vec::with_capacity(ids.len());
+            for id in ids {
+                let token = tokens
+                    .iter()
+                    .find(|t| t.id == *id)
+                    .ok_or_else(|| TrstError::TokenNotFound(format!("{}", id)))?;
+                if !usable(&token) {
+                    return Err(TrstError::NotTransferable(format!("{:?}", token.state)));
+                }
+                if !pinned.iter().any(|t: &&TrstToken| t.id == *id) {
+                    pinned.push(token);
+                }
+            }
+            return Ok(pinned);
+        }
+        _ => tokens.iter().filter(usable).collect(),
+    };
+    // Stable sorts keep the portfolio's oldest-origin order as the tie-break.
+    match selection {
+        CoinSelection::Fifo | CoinSelection::Pinned(_) => {}
+        CoinSelection::OldestExpiryFirst => {
+            ordered.sort_by_key(|t| t.effective_origin_timestamp.as_secs());
+        }
+        CoinSelection::CleanestProvenanceFirst => {
+            ordered.sort_by_key(|t| origin_wallet_count(t));
+        }
+    }
+    Ok(ordered)
+}
+
+/// Choose the tokens that cover `amount` under `selection`, without
+/// consuming them. The last entry may cover only part of its token.
+pub fn select_coins(
+    tokens: &[TrstToken],
+    amount: u128,
+    now: Timestamp,
+    expiry_secs: u64,
+    selection: &CoinSelection,
+) -> Result<Vec<ConsumedProvenance>, TrstError> {
+    let mut remaining = amount;
+    let mut chosen = Vec::new();
+    let mut available = 0u128;
+    for token in consumption_order(tokens, now, expiry_secs, selection)? {
+        if remaining == 0 {
+            break;
+        }
+        let take = token.amount.min(remaining);
+        available = available.saturating_add(token.amount);
+        remaining -= take;
+        chosen.push(ConsumedProvenance::from_token(token, take));
+    }
+    if remaining > 0 {
+        return Err(TrstError::InsufficientBalance {
+            needed: amount,
+            available,
+        });
+    }
+    Ok(chosen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_types::{OriginProportion, TrstState, WalletAddress};
+
+    fn wallet(n: u8) -> WalletAddress {
+        WalletAddress::new(format!("brst_{:0>60}", n))
+    }
+
+    fn token(n: u8, amount: u128, origin_ts: u64, effective_ts: u64) -> TrstToken {
+        TrstToken {
+            id: TxHash::new([n; 32]),
+            amount,
+            origin: TxHash::new([n; 32]),
+            link: TxHash::new([n; 32]),
+            holder: wallet(1),
+            origin_timestamp: Timestamp::new(origin_ts),
+            effective_origin_timestamp: Timestamp::new(effective_ts),
+            state: TrstState::Active,
+            origin_wallet: wallet(n),
+            origin_proportions: Vec::new(),
+        }
+    }
+
+    fn ids(chosen: &[ConsumedProvenance]) -> Vec<u8> {
+        chosen.iter().map(|c| c.token_id.as_bytes()[0]).collect()
+    }
+
+    fn portfolio() -> Vec<TrstToken> {
+        let mut merged = token(3, 100, 300, 50);
+        merged.origin_proportions = vec![
+            OriginProportion {
+                origin: TxHash::new([7; 32]),
+                origin_wallet: wallet(7),
+                amount: 50,
+            },
+            OriginProportion {
+                origin: TxHash::new([8; 32]),
+                origin_wallet: wallet(8),
+                amount: 50,
+            },
+        ];
+        vec![token(1, 100, 100, 100), token(2, 100, 200, 200), merged]
+    }
+
+    #[test]
+    fn strategies_order_tokens() {
+        let tokens = portfolio();
+        let now = Timestamp::new(400);
+        let pick = |selection| select_coins(&tokens, 150, now, 10_000, &selection).unwrap();
+
+        assert_eq!(ids(&pick(CoinSelection::Fifo)), vec![1, 2]);
+        // The merged token inherits the earliest constituent's expiry.
+        assert_eq!(ids(&pick(CoinSelection::OldestExpiryFirst)), vec![3, 1]);
+        assert_eq!(
+            ids(&pick(CoinSelection::CleanestProvenanceFirst)),
+            vec![1, 2]
+        );
+        let partial = pick(CoinSelection::Fifo);
+        assert_eq!((partial[0].amount, partial[1].amount), (100, 50));
+    }
+
+    #[test]
+    fn pinned_tokens_are_used_as_given() {
+        let tokens = portfolio();
+        let now = Timestamp::new(400);
+        let pinned = CoinSelection::Pinned(vec![TxHash::new([2; 32]), TxHash::new([3; 32])]);
+        let chosen = select_coins(&tokens, 150, now, 10_000, &pinned).unwrap();
+        assert_eq!(ids(&chosen), vec![2, 3]);
+
+        // Pinned tokens must cover the amount on their own.
+        let err = select_coins(&tokens, 250, now, 10_000, &pinned).unwrap_err();
+        assert!(matches!(
+            err,
+            TrstError::InsufficientBalance {
+                needed: 250,
+                available: 200
+            }
+        ));
+        let unknown = CoinSelection::Pinned(vec![TxHash::new([9; 32])]);
+        assert!(matches!(
+            select_coins(&tokens, 10, now, 10_000, &unknown),
+            Err(TrstError::TokenNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn expired_tokens_are_never_selected() {
+        let tokens = portfolio();
+        // Token 3's effective origin (50) has expired; the others have not.
+        let now = Timestamp::new(1_060);
+        let chosen =
+            select_coins(&tokens, 200, now, 1_000, &CoinSelection::OldestExpiryFirst).unwrap();
+        assert_eq!(ids(&chosen), vec![1, 2]);
+        let pinned = CoinSelection::Pinned(vec![TxHash::new([3; 32])]);
+        assert!(matches!(
+            select_coins(&tokens, 10, now, 1_000, &pinned),
+            Err(TrstError::NotTransferable(_))
+        ));
+    }
+}
@@ -1,11 +1,15 @@
 //! Core TRST lifecycle engine.
 
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 
+use crate::coin_selection::{select_coins, CoinSelection};
 use crate::error::TrstError;
 use crate::merger_graph::MergerGraph;
+use crate::portfolio_cache::{PortfolioLru, PortfolioSource};
 use crate::token::{OriginProportion, TrstToken};
-use burst_types::{Timestamp, TrstState, TxHash, WalletAddress};
+use burst_types::memory::entry_bytes;
+use burst_types::{MemoryAccounting, Timestamp, TrstDecayMode, TrstState, TxHash, WalletAddress};
 
 /// Result of un-revoking a single token.
 #[derive(Clone, Debug)]
@@ -21,6 +25,8 @@ pub struct UnRevocationResult {
 /// Provenance info from a consumed token portion during debit.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct ConsumedProvenance {
+    /// The token this portion was taken from.
+    pub token_id: TxHash,
     pub amount: u128,
     pub origin: TxHash,
     pub origin_wallet: WalletAddress,
@@ -29,6 +35,21 @@ pub struct ConsumedProvenance {
     pub origin_proportions: Vec<OriginProportion>,
 }
 
+impl ConsumedProvenance {
+    /// Provenance of `amount` taken from `token`.
+    pub fn from_token(token: &TrstToken, amount: u128) -> Self {
+        Self {
+            token_id: token.id,
+            amount,
+            origin: token.origin,
+            origin_wallet: token.origin_wallet.clone(),
+            origin_timestamp: token.origin_timestamp,
+            effective_origin_timestamp: token.effective_origin_timestamp,
+            origin_proportions: token.origin_proportions.clone(),
+        }
+    }
+}
+
 /// Information about a pending token needed for expiry-based return.
 #[derive(Clone, Debug)]
 pub struct PendingTokenInfo {
@@ -51,6 +72,14 @@ pub struct PendingReturnResult {
     pub amount: u128,
 }
 
+/// A token that expired unspent, as recorded by the engine.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExpiredTrst {
+    pub wallet: WalletAddress,
+    pub token_id: TxHash,
+    pub amount: u128,
+}
+
 /// Per-wallet portfolio with O(1) balance lookups.
 ///
 /// Tokens are kept sorted by `origin_timestamp` (sorted invariant maintained
@@ -110,25 +139,28 @@ impl WalletPortfolio {
     }
 
     /// Flush expired tokens: if `now >= earliest_expiry`, mark expired tokens
-    /// and adjust the cached balance. Returns the total amount that expired.
-    fn flush_expired(&mut self, now: Timestamp, expiry_secs: u64) -> u128 {
+    /// and adjust the cached balance. Returns the id and amount of each
+    /// token that expired.
+    fn flush_expired(&mut self, now: Timestamp, expiry_secs: u64) -> Vec<(TxHash, u128)> {
         let needs_flush = match self.earliest_expiry {
             Some(exp) => now.as_secs() >= exp.as_secs(),
             None => false,
         };
         if !needs_flush {
-            return 0;
+            return Vec::new();
         }
+        let mut expired = Vec::new();
         let mut expired_amount = 0u128;
         for t in &mut self.tokens {
             if t.state == TrstState::Active && t.is_expired(now, expiry_secs) {
                 expired_amount = expired_amount.saturating_add(t.amount);
+                expired.push((t.id, t.amount));
                 t.state = TrstState::Expired;
             }
         }
         self.cached_transferable = self.cached_transferable.saturating_sub(expired_amount);
         self.recompute_earliest_expiry(expiry_secs);
-        expired_amount
+        expired
     }
 }
 
@@ -137,7 +169,16 @@ pub struct TrstEngine {
     /// The merger graph for proactive revocation.
     pub merger_graph: MergerGraph,
     /// Per-wallet portfolios with O(1) balance lookups and sorted tokens.
-    pub wallets: HashMap<WalletAddress, WalletPortfolio>,
+    /// Holds every portfolio unless a [`PortfolioSource`] is attached, in
+    /// which case it is a cache of recently used ones.
+    pub(crate) wallets: HashMap<WalletAddress, WalletPortfolio>,
+    /// Store that evicted portfolios are paged back in from.
+    portfolio_source: Option<Box<dyn PortfolioSource>>,
+    /// Cached portfolios kept once written back; only enforced with a source.
+    max_cached_wallets: usize,
+    lru: PortfolioLru,
+    /// Portfolios changed since the last `take_dirty_portfolios`.
+    dirty: HashSet<WalletAddress>,
     /// Maps each origin wallet to all burn tx hashes (origins) it produced.
     /// Populated incrementally on mint/track. Used by revocation to find
     /// all real origin hashes belonging to a sybil wallet.
@@ -148,6 +189,8 @@ pub struct TrstEngine {
     origin_wallet_holders: HashMap<WalletAddress, HashSet<WalletAddress>>,
     /// Global TRST expiry period in seconds (needed for earliest_expiry recomputation).
     pub expiry_secs: u64,
+    /// Tokens flushed as expired since the last `drain_expired`.
+    expired: Vec<ExpiredTrst>,
 }
 
 impl TrstEngine {
@@ -155,9 +198,14 @@ impl TrstEngine {
         Self {
             merger_graph: MergerGraph::new(),
             wallets: HashMap::new(),
+            portfolio_source: None,
+            max_cached_wallets: usize::MAX,
+            lru: PortfolioLru::default(),
+            dirty: HashSet::new(),
             wallet_origins: HashMap::new(),
             origin_wallet_holders: HashMap::new(),
             expiry_secs: u64::MAX,
+            expired: Vec::new(),
         }
     }
 
@@ -165,9 +213,30 @@ impl TrstEngine {
         Self {
             merger_graph: MergerGraph::new(),
             wallets: HashMap::new(),
+            portfolio_source: None,
+            max_cached_wallets: usize::MAX,
+            lru: PortfolioLru::default(),
+            dirty: HashSet::new(),
             wallet_origins: HashMap::new(),
             origin_wallet_holders: HashMap::new(),
             expiry_secs,
+            expired: Vec::new(),
+        }
+    }
+
+    /// Switch to a new expiry period, e.g. when governance changes it.
+    ///
+    /// Cached portfolios' next expiry is recomputed now, evicted ones as
+    /// they are paged back in. Tokens already flushed as expired stay
+    /// expired if the period grows.
+    pub fn set_expiry_secs(&mut self, expiry_secs: u64) {
+        if expiry_secs == self.expiry_secs {
+            return;
+        }
+        self.expiry_secs = expiry_secs;
+        for (wallet, portfolio) in &mut self.wallets {
+            portfolio.recompute_earliest_expiry(expiry_secs);
+            self.dirty.insert(wallet.clone());
         }
     }
 
@@ -176,7 +245,7 @@ impl TrstEngine {
     /// Also updates the `wallet_origins` index for simple tokens.
     pub fn track_token(&mut self, token: TrstToken) {
         self.index_origin(&token);
-        let portfolio = self.wallets.entry(token.holder.clone()).or_default();
+        let portfolio = self.portfolio_entry(&token.holder);
         if token.state == TrstState::Active {
             portfolio.cached_transferable += token.amount;
             let tok_expiry = token.earliest_expiry(u64::MAX);
@@ -197,7 +266,7 @@ impl TrstEngine {
     /// Also updates the `wallet_origins` index for simple tokens.
     pub fn track_token_with_expiry(&mut self, token: TrstToken, expiry_secs: u64) {
         self.index_origin(&token);
-        let portfolio = self.wallets.entry(token.holder.clone()).or_default();
+        let portfolio = self.portfolio_entry(&token.holder);
         if token.state == TrstState::Active {
             portfolio.cached_transferable += token.amount;
             let tok_expiry = token.earliest_expiry(expiry_secs);
@@ -214,6 +283,70 @@ impl TrstEngine {
         portfolio.insert_sorted(token);
     }
 
+    /// Bring `wallet`'s portfolio into the cache, paging it in from the
+    /// source if it was evicted. Returns false if the wallet has none.
+    fn page_in_portfolio(&mut self, wallet: &WalletAddress) -> bool {
+        let cached = self.wallets.contains_key(wallet)
+            || match self
+                .portfolio_source
+                .as_ref()
+                .and_then(|source| source.load_portfolio(wallet))
+            {
+                Some(mut portfolio) => {
+                    // Stored before any change to the expiry period.
+                    portfolio.recompute_earliest_expiry(self.expiry_secs);
+                    self.wallets.insert(wallet.clone(), portfolio);
+                    true
+                }
+                None => false,
+            };
+        if cached && self.portfolio_source.is_some() {
+            self.lru.touch(wallet);
+        }
+        cached
+    }
+
+    /// The cached portfolio for `wallet`, paged in if needed.
+    fn cached_portfolio_mut(&mut self, wallet: &WalletAddress) -> Option<&mut WalletPortfolio> {
+        if !self.page_in_portfolio(wallet) {
+            return None;
+        }
+        self.wallets.get_mut(wallet)
+    }
+
+    /// Like [`cached_portfolio_mut`](Self::cached_portfolio_mut), but marks
+    /// the portfolio for write-back.
+    fn portfolio_mut(&mut self, wallet: &WalletAddress) -> Option<&mut WalletPortfolio> {
+        if !self.page_in_portfolio(wallet) {
+            return None;
+        }
+        self.dirty.insert(wallet.clone());
+        self.wallets.get_mut(wallet)
+    }
+
+    /// Like [`portfolio_mut`](Self::portfolio_mut), creating an empty
+    /// portfolio for a wallet that has none.
+    fn portfolio_entry(&mut self, wallet: &WalletAddress) -> &mut WalletPortfolio {
+        if !self.page_in_portfolio(wallet) && self.portfolio_source.is_some() {
+            self.lru.touch(wallet);
+        }
+        self.dirty.insert(wallet.clone());
+        self.wallets.entry(wallet.clone()).or_default()
+    }
+
+    /// The portfolio for `wallet` from the cache or the source, without
+    /// caching it.
+    fn lookup_portfolio(&self, wallet: &WalletAddress) -> Option<Cow<'_, WalletPortfolio>> {
+        match self.wallets.get(wallet) {
+            Some(portfolio) => Some(Cow::Borrowed(portfolio)),
+            None => self
+                .portfolio_source
+                .as_ref()?
+                .load_portfolio(wallet)
+                .map(Cow::Owned),
+        }
+    }
+
     /// Record a token's origin(s) in the `wallet_origins` and
     /// `origin_wallet_holders` indexes.
     fn index_origin(&mut self, token: &TrstToken) {
@@ -249,7 +382,7 @@ impl TrstEngine {
     /// Remove a specific token from a wallet's tracked portfolio.
     pub fn untrack_token(&mut self, wallet: &WalletAddress, token_id: &TxHash) {
         let expiry = self.expiry_secs;
-        if let Some(portfolio) = self.wallets.get_mut(wallet) {
+        if let Some(portfolio) = self.portfolio_mut(wallet) {
             if let Some(pos) = portfolio.tokens.iter().position(|t| t.id == *token_id) {
                 let removed = portfolio.tokens.remove(pos);
                 if removed.state == TrstState::Active {
@@ -270,7 +403,7 @@ impl TrstEngine {
     /// would be O(n*k) due to linear scans + repeated expiry recomputation.
     pub fn bulk_untrack(&mut self, wallet: &WalletAddress, token_ids: &HashSet<TxHash>) {
         let expiry = self.expiry_secs;
-        if let Some(portfolio) = self.wallets.get_mut(wallet) {
+        if let Some(portfolio) = self.portfolio_mut(wallet) {
             let mut removed_amount = 0u128;
             portfolio.tokens.retain(|t| {
                 if token_ids.contains(&t.id) {
@@ -300,23 +433,51 @@ impl TrstEngine {
         now: Timestamp,
         expiry_secs: u64,
     ) -> Option<u128> {
-        if let Some(portfolio) = self.wallets.get_mut(wallet) {
-            portfolio.flush_expired(now, expiry_secs);
-            Some(portfolio.cached_transferable)
-        } else {
-            None
-        }
+        let portfolio = self.cached_portfolio_mut(wallet)?;
+        let expired = portfolio.flush_expired(now, expiry_secs);
+        let balance = portfolio.cached_transferable;
+        self.record_expired(wallet, expired);
+        Some(balance)
     }
 
     /// Read-only transferable balance (does not flush expiry).
     /// Use when you only need a snapshot and can't take `&mut self`.
     pub fn transferable_balance_snapshot(&self, wallet: &WalletAddress) -> Option<u128> {
-        self.wallets.get(wallet).map(|p| p.cached_transferable)
+        self.lookup_portfolio(wallet).map(|p| p.cached_transferable)
+    }
+
+    /// Transferable balance valued under the network's decay mode.
+    ///
+    /// Under `HardExpiry` this is [`transferable_balance`](Self::transferable_balance);
+    /// the decay modes sum each transferable token's decayed value instead,
+    /// which is what a send is checked against.
+    pub fn transferable_value(
+        &mut self,
+        wallet: &WalletAddress,
+        now: Timestamp,
+        expiry_secs: u64,
+        mode: TrstDecayMode,
+        half_life_secs: u64,
+    ) -> Option<u128> {
+        let portfolio = self.cached_portfolio_mut(wallet)?;
+        let expired = portfolio.flush_expired(now, expiry_secs);
+        let value = if mode == TrstDecayMode::HardExpiry {
+            portfolio.cached_transferable
+        } else {
+            portfolio
+                .tokens
+                .iter()
+                .filter(|t| t.is_transferable(now, expiry_secs))
+                .map(|t| t.decayed_value(now, expiry_secs, mode, half_life_secs))
+                .sum()
+        };
+        self.record_expired(wallet, expired);
+        Some(value)
     }
 
     /// Returns true if the wallet has tracked tokens in the engine.
     pub fn is_wallet_tracked(&self, wallet: &WalletAddress) -> bool {
-        self.wallets.contains_key(wallet)
+        self.lookup_portfolio(wallet).is_some()
     }
 
     /// Debit tokens from a wallet's tracked portfolio after a send.
@@ -329,7 +490,7 @@ impl TrstEngine {
             return;
         }
         let expiry_secs = self.expiry_secs;
-        if let Some(portfolio) = self.wallets.get_mut(wallet) {
+        if let Some(portfolio) = self.portfolio_mut(wallet) {
             portfolio.cached_transferable = portfolio.cached_transferable.saturating_sub(amount);
 
             let mut fully_consumed = 0;
@@ -370,7 +531,9 @@ impl TrstEngine {
     /// Same FIFO logic as `debit_wallet`, but returns a list of
     /// `ConsumedProvenance` entries describing what was consumed. Used to
     /// populate pending entries with origin info so receivers get properly
-    /// provenanced tokens.
+    /// provenanced tokens. See
+    /// [`debit_wallet_with_selection`](Self::debit_wallet_with_selection)
+    /// for sender-chosen tokens.
     pub fn debit_wallet_with_provenance(
         &mut self,
         wallet: &WalletAddress,
@@ -381,7 +544,7 @@ impl TrstEngine {
             return consumed;
         }
         let expiry_secs = self.expiry_secs;
-        if let Some(portfolio) = self.wallets.get_mut(wallet) {
+        if let Some(portfolio) = self.portfolio_mut(wallet) {
             portfolio.cached_transferable = portfolio.cached_transferable.saturating_sub(amount);
 
             let mut fully_consumed = 0;
@@ -391,14 +554,7 @@ impl TrstEngine {
                     break;
                 }
                 let take = t.amount.min(amount);
-                consumed.push(ConsumedProvenance {
-                    amount: take,
-                    origin: t.origin,
-                    origin_wallet: t.origin_wallet.clone(),
-                    origin_timestamp: t.origin_timestamp,
-                    effective_origin_timestamp: t.effective_origin_timestamp,
-                    origin_proportions: t.origin_proportions.clone(),
-                });
+                consumed.push(ConsumedProvenance::from_token(t, take));
                 if t.amount <= amount {
                     if t.state == TrstState::Active
                         && portfolio.earliest_expiry == Some(t.earliest_expiry(expiry_secs))
@@ -427,6 +583,47 @@ impl TrstEngine {
         consumed
     }
 
+    /// Preview which tokens a send of `amount` would consume under
+    /// `selection`, for display before signing.
+    pub fn select_tokens(
+        &self,
+        wallet: &WalletAddress,
+        amount: u128,
+        now: Timestamp,
+        selection: &CoinSelection,
+    ) -> Result<Vec<ConsumedProvenance>, TrstError> {
+        let portfolio = self.lookup_portfolio(wallet);
+        let tokens = portfolio
+            .as_ref()
+            .map(|p| p.tokens.as_slice())
+            .unwrap_or_default();
+        select_coins(tokens, amount, now, self.expiry_secs, selection)
+    }
+
+    /// Debit `amount` from the tokens `selection` picks and return their
+    /// provenance. Nothing is debited if the selection can't cover `amount`.
+    pub fn debit_wallet_with_selection(
+        &mut self,
+        wallet: &WalletAddress,
+        amount: u128,
+        now: Timestamp,
+        selection: &CoinSelection,
+    ) -> Result<Vec<ConsumedProvenance>, TrstError> {
+        let consumed = self.select_tokens(wallet, amount, now, selection)?;
+        let expiry_secs = self.expiry_secs;
+        if let Some(portfolio) = self.portfolio_mut(wallet) {
+            for c in &consumed {
+                if let Some(t) = portfolio.tokens.iter_mut().find(|t| t.id == c.token_id) {
+                    t.amount -= c.amount;
+                }
+            }
+            portfolio.tokens.retain(|t| t.amount > 0);
+            portfolio.cached_transferable = portfolio.cached_transferable.saturating_sub(amount);
+            portfolio.recompute_earliest_expiry(expiry_secs);
+        }
+        Ok(consumed)
+    }
+
     /// Mint fresh TRST from a burn transaction.
     ///
     /// Called when a consumer burns BRN for a provider. The provider receives
@@ -765,9 +962,10 @@ impl TrstEngine {
 
         for origin_tx in &origin_txs {
             self.merger_graph.mark_origin_revoked(*origin_tx);
+            self.merger_graph.page_in(origin_tx);
             let events = self.merger_graph.propagate_revocation(origin_tx);
             for event in &events {
-                if let Some(portfolio) = self.wallets.get_mut(&event.holder) {
+                if let Some(portfolio) = self.portfolio_mut(&event.holder) {
                     if let Some(t) = portfolio
                         .tokens
                         .iter_mut()
@@ -790,7 +988,7 @@ impl TrstEngine {
             .map(|s| s.iter().cloned().collect())
             .unwrap_or_default();
         for addr in holder_addrs {
-            if let Some(portfolio) = self.wallets.get_mut(&addr) {
+            if let Some(portfolio) = self.portfolio_mut(&addr) {
                 for t in &mut portfolio.tokens {
                     if t.state == TrstState::Active
                         && t.origin_proportions.is_empty()
@@ -833,9 +1031,10 @@ impl TrstEngine {
 
         // Restore merged tokens via the merger graph — O(k).
         for origin_tx in &origin_txs {
+            self.merger_graph.page_in(origin_tx);
             let events = self.merger_graph.propagate_unrevocation(origin_tx);
             for event in events {
-                if let Some(portfolio) = self.wallets.get_mut(&event.holder) {
+                if let Some(portfolio) = self.portfolio_mut(&event.holder) {
                     if let Some(t) = portfolio
                         .tokens
                         .iter_mut()
@@ -861,7 +1060,7 @@ impl TrstEngine {
             .map(|s| s.iter().cloned().collect())
             .unwrap_or_default();
         for addr in holder_addrs {
-            if let Some(portfolio) = self.wallets.get_mut(&addr) {
+            if let Some(portfolio) = self.portfolio_mut(&addr) {
                 for t in &mut portfolio.tokens {
                     if t.state == TrstState::Revoked
                         && t.origin_proportions.is_empty()
@@ -949,11 +1148,12 @@ impl TrstEngine {
     }
 }
 
-// Meta-store key used for persisting the TRST engine's token portfolios.
+// Meta-store key of the whole-engine portfolio snapshot, written by
+// versions that kept every portfolio in memory.
 const TRST_ENGINE_META_KEY: &str = "trst_engine_wallets";
 
 impl TrstEngine {
-    /// Serialize the per-wallet token portfolios to bytes for LMDB persistence.
+    /// Serialize the cached per-wallet token portfolios as one snapshot.
     pub fn save_wallets(&self) -> Vec<u8> {
         bincode::serialize(&self.wallets).unwrap_or_default()
     }
@@ -991,32 +1191,153 @@ impl TrstEngine {
         Self {
             merger_graph: MergerGraph::new(),
             wallets,
+            portfolio_source: None,
+            max_cached_wallets: usize::MAX,
+            lru: PortfolioLru::default(),
+            dirty: HashSet::new(),
             wallet_origins,
             origin_wallet_holders,
             expiry_secs,
+            expired: Vec::new(),
         }
     }
 
-    /// The meta-store key used for wallet portfolio persistence.
+    /// The meta-store key of the legacy whole-engine portfolio snapshot.
     pub fn meta_key() -> &'static str {
         TRST_ENGINE_META_KEY
     }
 
-    /// Flush expired tokens across all wallets. Call periodically (e.g. every 30s).
+    /// Flush expired tokens across all cached wallets. Call periodically
+    /// (e.g. every 30s); evicted wallets are flushed when next paged in.
     pub fn flush_all_expired(&mut self, now: Timestamp, expiry_secs: u64) {
-        for portfolio in self.wallets.values_mut() {
-            portfolio.flush_expired(now, expiry_secs);
+        let flushed: Vec<_> = self
+            .wallets
+            .iter_mut()
+            .map(|(wallet, portfolio)| (wallet.clone(), portfolio.flush_expired(now, expiry_secs)))
+            .collect();
+        for (wallet, expired) in flushed {
+            self.record_expired(&wallet, expired);
+        }
+    }
+
+    fn record_expired(&mut self, wallet: &WalletAddress, expired: Vec<(TxHash, u128)>) {
+        if expired.is_empty() {
+            return;
         }
+        self.dirty.insert(wallet.clone());
+        self.expired
+            .extend(expired.into_iter().map(|(token_id, amount)| ExpiredTrst {
+                wallet: wallet.clone(),
+                token_id,
+                amount,
+            }));
+    }
+
+    /// Take the tokens flushed as expired since the last call.
+    pub fn drain_expired(&mut self) -> Vec<ExpiredTrst> {
+        std::mem::take(&mut self.expired)
     }
 
-    /// Get a portfolio for a wallet (immutable).
-    pub fn get_portfolio(&self, wallet: &WalletAddress) -> Option<&WalletPortfolio> {
-        self.wallets.get(wallet)
+    /// Get a portfolio for a wallet, reading it from the source if it is
+    /// not cached.
+    pub fn get_portfolio(&self, wallet: &WalletAddress) -> Option<Cow<'_, WalletPortfolio>> {
+        self.lookup_portfolio(wallet)
     }
 
-    /// Get a portfolio for a wallet (mutable).
+    /// Get a portfolio for a wallet (mutable). It is written back with the
+    /// next [`take_dirty_portfolios`](Self::take_dirty_portfolios).
     pub fn get_portfolio_mut(&mut self, wallet: &WalletAddress) -> Option<&mut WalletPortfolio> {
-        self.wallets.get_mut(wallet)
+        self.portfolio_mut(wallet)
+    }
+
+    /// Page portfolios from `source` on demand, keeping at most
+    /// `max_cached_wallets` in memory once they have been written back.
+    pub fn attach_portfolio_source(
+        &mut self,
+        source: Box<dyn PortfolioSource>,
+        max_cached_wallets: usize,
+    ) {
+        for wallet in self.wallets.keys() {
+            self.lru.touch(wallet);
+        }
+        self.portfolio_source = Some(source);
+        self.max_cached_wallets = max_cached_wallets;
+    }
+
+    /// Add a stored portfolio's tokens to the origin indexes used by
+    /// revocation, without caching the portfolio itself.
+    pub fn index_portfolio(&mut self, portfolio: &WalletPortfolio) {
+        for token in &portfolio.tokens {
+            self.index_origin(token);
+        }
+    }
+
+    /// Portfolios changed since the last call, for the caller to write to
+    /// the store. Afterwards, least recently used portfolios beyond the
+    /// cache limit are evicted.
+    pub fn take_dirty_portfolios(&mut self) -> Vec<(WalletAddress, WalletPortfolio)> {
+        let updates = self
+            .dirty
+            .drain()
+            .filter_map(|wallet| {
+                let portfolio = self.wallets.get(&wallet)?.clone();
+                Some((wallet, portfolio))
+            })
+            .collect();
+        self.evict_cold_portfolios();
+        updates
+    }
+
+    /// Drop least recently used, already written portfolios until the cache
+    /// is within its limit.
+    fn evict_cold_portfolios(&mut self) {
+        if self.portfolio_source.is_none() || self.wallets.len() <= self.max_cached_wallets {
+            return;
+        }
+        let excess = self.wallets.len() - self.max_cached_wallets;
+        let cold: Vec<WalletAddress> = self
+            .lru
+            .oldest_first()
+            .filter(|wallet| !self.dirty.contains(*wallet))
+            .take(excess)
+            .cloned()
+            .collect();
+        for wallet in cold {
+            self.wallets.remove(&wallet);
+            self.lru.remove(&wallet);
+        }
+    }
+
+    /// Number of portfolios currently held in memory.
+    pub fn cached_portfolio_count(&self) -> usize {
+        self.wallets.len()
+    }
+
+    /// Portfolios currently held in memory.
+    pub fn cached_portfolios(&self) -> impl Iterator<Item = (&WalletAddress, &WalletPortfolio)> {
+        self.wallets.iter()
+    }
+}
+
+impl MemoryAccounting for TrstEngine {
+    /// Portfolios held in memory.
+    fn element_count(&self) -> usize {
+        self.wallets.len()
+    }
+
+    fn approximate_bytes(&self) -> usize {
+        self.wallets
+            .values()
+            .map(|portfolio| {
+                entry_bytes::<(WalletAddress, WalletPortfolio)>(1)
+                    + entry_bytes::<TrstToken>(portfolio.tokens.len())
+                    + portfolio
+                        .tokens
+                        .iter()
+                        .map(|t| entry_bytes::<OriginProportion>(t.origin_proportions.len()))
+                        .sum::<usize>()
+            })
+            .sum()
     }
 }
 
@@ -1450,7 +1771,7 @@ mod tests {
         let merge1_tx = test_hash(10);
         let mut merged1 = engine
             .merge(
-                &vec![token1, token2],
+                &[token1, token2],
                 holder1.clone(),
                 merge1_tx,
                 test_timestamp(1500),
@@ -1463,7 +1784,7 @@ mod tests {
         let merge2_tx = test_hash(11);
         let _merged2 = engine
             .merge(
-                &vec![merged1, token3],
+                &[merged1, token3],
                 holder2.clone(),
                 merge2_tx,
                 test_timestamp(1600),
@@ -2208,4 +2529,153 @@ mod tests {
 
         let _ = merged;
     }
+
+    #[test]
+    fn test_decay_modes_value_tokens_differently() {
+        let mut engine = TrstEngine::new();
+        let token = engine
+            .mint(
+                test_hash(1),
+                test_address(1),
+                1000,
+                test_address(10),
+                test_timestamp(0),
+            )
+            .unwrap();
+        let expiry = 1000;
+        let half_life = 200;
+        let at = |secs, mode| token.decayed_value(test_timestamp(secs), expiry, mode, half_life);
+
+        assert_eq!(at(500, TrstDecayMode::HardExpiry), 1000);
+        assert_eq!(at(500, TrstDecayMode::Linear), 500);
+        assert_eq!(at(0, TrstDecayMode::Exponential), 1000);
+        assert_eq!(at(200, TrstDecayMode::Exponential), 500);
+        assert_eq!(at(300, TrstDecayMode::Exponential), 375);
+        assert_eq!(at(400, TrstDecayMode::Exponential), 250);
+        // Expiry still ends every mode.
+        for mode in [
+            TrstDecayMode::HardExpiry,
+            TrstDecayMode::Linear,
+            TrstDecayMode::Exponential,
+        ] {
+            assert_eq!(at(1000, mode), 0);
+        }
+        // A zero half-life disables exponential decay.
+        assert_eq!(
+            token.decayed_value(test_timestamp(500), expiry, TrstDecayMode::Exponential, 0),
+            1000
+        );
+    }
+
+    #[test]
+    fn test_transferable_value_follows_decay_mode() {
+        let mut engine = TrstEngine::new();
+        let expiry = 1000;
+        let holder = test_address(1);
+        for (n, minted_at) in [(1, 0), (2, 500)] {
+            let token = engine
+                .mint(
+                    test_hash(n),
+                    holder.clone(),
+                    1000,
+                    test_address(10),
+                    test_timestamp(minted_at),
+                )
+                .unwrap();
+            engine.track_token_with_expiry(token, expiry);
+        }
+        let now = test_timestamp(750);
+
+        assert_eq!(
+            engine.transferable_value(&holder, now, expiry, TrstDecayMode::HardExpiry, 0),
+            Some(2000)
+        );
+        assert_eq!(
+            engine.transferable_value(&holder, now, expiry, TrstDecayMode::Linear, 0),
+            Some(250 + 750)
+        );
+        assert_eq!(
+            engine.transferable_value(&test_address(2), now, expiry, TrstDecayMode::Linear, 0),
+            None
+        );
+        // The first token has expired; only the second still counts.
+        assert_eq!(
+            engine.transferable_value(
+                &holder,
+                test_timestamp(1000),
+                expiry,
+                TrstDecayMode::Linear,
+                0
+            ),
+            Some(500)
+        );
+    }
+
+    #[test]
+    fn test_debit_wallet_with_selection_consumes_chosen_tokens() {
+        let mut engine = TrstEngine::with_expiry(10_000);
+        let holder = test_address(1);
+        for n in 1..=3 {
+            let token = engine
+                .mint(
+                    test_hash(n),
+                    holder.clone(),
+                    100,
+                    test_address(10),
+                    test_timestamp(n as u64),
+                )
+                .unwrap();
+            engine.track_token_with_expiry(token, 10_000);
+        }
+        let now = test_timestamp(50);
+        let pinned = CoinSelection::Pinned(vec![test_hash(3), test_hash(2)]);
+
+        let preview = engine.select_tokens(&holder, 150, now, &pinned).unwrap();
+        let consumed = engine
+            .debit_wallet_with_selection(&holder, 150, now, &pinned)
+            .unwrap();
+        let ids: Vec<_> = consumed.iter().map(|c| c.token_id).collect();
+        assert_eq!(ids, preview.iter().map(|c| c.token_id).collect::<Vec<_>>());
+        assert_eq!(ids, vec![test_hash(3), test_hash(2)]);
+
+        let portfolio = engine.get_portfolio(&holder).unwrap();
+        let left: Vec<_> = portfolio.tokens.iter().map(|t| (t.id, t.amount)).collect();
+        assert_eq!(left, vec![(test_hash(1), 100), (test_hash(2), 50)]);
+        assert_eq!(portfolio.cached_transferable, 150);
+
+        // A selection that can't cover the amount debits nothing.
+        assert!(engine
+            .debit_wallet_with_selection(&holder, 500, now, &CoinSelection::Fifo)
+            .is_err());
+        assert_eq!(engine.transferable_balance_snapshot(&holder), Some(150));
+    }
+
+    #[test]
+    fn shortened_expiry_applies_to_tracked_tokens() {
+        let mut engine = TrstEngine::with_expiry(10_000);
+        let holder = test_address(1);
+        let token = engine
+            .mint(
+                test_hash(1),
+                holder.clone(),
+                500,
+                test_address(2),
+                test_timestamp(1_000),
+            )
+            .unwrap();
+        engine.track_token_with_expiry(token, 10_000);
+
+        let now = test_timestamp(3_000);
+        assert_eq!(
+            engine.transferable_balance(&holder, now, engine.expiry_secs),
+            Some(500)
+        );
+
+        engine.set_expiry_secs(1_000);
+        assert_eq!(
+            engine.transferable_balance(&holder, now, engine.expiry_secs),
+            Some(0)
+        );
+        assert_eq!(engine.drain_expired().len(), 1);
+    }
 }
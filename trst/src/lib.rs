@@ -7,15 +7,21 @@
 //! This crate handles the full lifecycle: mint, transfer, split, merge, expiry, revocation.
 //! It also maintains the **merger graph** — the forward index enabling O(1) revocation.
 
+pub mod coin_selection;
 pub mod engine;
 pub mod error;
 pub mod merger_graph;
+pub mod portfolio_cache;
 pub mod token;
 
+pub use coin_selection::{select_coins, CoinSelection};
 pub use engine::{
-    ConsumedProvenance, PendingReturnResult, PendingTokenInfo, TrstEngine, UnRevocationResult,
-    WalletPortfolio,
+    ConsumedProvenance, ExpiredTrst, PendingReturnResult, PendingTokenInfo, TrstEngine,
+    UnRevocationResult, WalletPortfolio,
 };
 pub use error::TrstError;
-pub use merger_graph::{MergerGraph, UnRevocationEvent};
+pub use merger_graph::{
+    MergeNode, MergerGraph, MergerGraphDelta, MergerGraphSource, UnRevocationEvent,
+};
+pub use portfolio_cache::PortfolioSource;
 pub use token::TrstToken;
@@ -6,13 +6,21 @@
 //! Without the merger graph, every transaction requires O(n) backward traversal to check
 //! for revoked origins. With it, revocation is a one-time O(k) forward traversal at catch time,
 //! and every subsequent transaction is O(1).
+//!
+//! The graph grows with every merge, so the in-memory structure is a cache
+//! over a persisted copy. Every mutation is journaled as a
+//! [`MergerGraphDelta`] for the caller to write alongside the block that
+//! caused it, and with a [`MergerGraphSource`] attached the subgraph reachable
+//! from an origin is paged in before it is traversed, so the cache can be
+//! dropped whenever it grows too large.
 
 use burst_types::{TxHash, WalletAddress};
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
 
 /// A node in the merger graph representing a merge operation.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MergeNode {
     /// Hash of the merge transaction.
     pub merge_tx: TxHash,
@@ -25,12 +33,37 @@ pub struct MergeNode {
 }
 
 /// One source in a merge operation.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MergeSource {
     pub origin: TxHash,
     pub amount: u128,
 }
 
+/// A change to the graph that has not been written to disk yet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MergerGraphDelta {
+    /// `merge_tx` consumed `origin`.
+    OriginEdge { origin: TxHash, merge_tx: TxHash },
+    /// `child` consumed the merged token of `parent`.
+    DownstreamEdge { parent: TxHash, child: TxHash },
+    /// A merge was recorded.
+    Node(MergeNode),
+    /// An origin was revoked.
+    Revoked(TxHash),
+    /// An origin's revocation was lifted.
+    Unrevoked(TxHash),
+}
+
+/// Read access to the persisted graph, used to page subgraphs in on demand.
+pub trait MergerGraphSource: Send + Sync {
+    /// Merges that consumed `origin`.
+    fn merges_for_origin(&self, origin: &TxHash) -> Vec<TxHash>;
+    /// Merges that consumed the merged token of `merge_tx`.
+    fn downstream(&self, merge_tx: &TxHash) -> Vec<TxHash>;
+    /// The recorded merge `merge_tx`, if any.
+    fn merge_node(&self, merge_tx: &TxHash) -> Option<MergeNode>;
+}
+
 /// The merger graph — maps origins forward to all current live balances.
 ///
 /// ```text
@@ -51,6 +84,18 @@ pub struct MergerGraph {
     /// Used to determine whether a merged token can be un-revoked
     /// (all its constituent origins must be non-revoked).
     revoked_origins: HashSet<TxHash>,
+
+    /// Persisted copy of the graph, when the graph is disk-backed.
+    #[serde(skip)]
+    source: Option<Box<dyn MergerGraphSource>>,
+
+    /// Origins and merges whose edges have been paged in from `source`.
+    #[serde(skip)]
+    paged_in: HashSet<TxHash>,
+
+    /// Mutations not yet handed to the caller for persistence.
+    #[serde(skip)]
+    deltas: Vec<MergerGraphDelta>,
 }
 
 impl MergerGraph {
@@ -60,7 +105,127 @@ impl MergerGraph {
             merge_nodes: HashMap::new(),
             merge_to_downstream: HashMap::new(),
             revoked_origins: HashSet::new(),
+            source: None,
+            paged_in: HashSet::new(),
+            deltas: Vec::new(),
+        }
+    }
+
+    /// Back the graph with a persisted copy. Only the revoked origin set is
+    /// kept in memory in full; edges and nodes are paged in as needed.
+    pub fn attach_source(
+        &mut self,
+        source: Box<dyn MergerGraphSource>,
+        revoked_origins: impl IntoIterator<Item = TxHash>,
+    ) {
+        self.source = Some(source);
+        self.revoked_origins.extend(revoked_origins);
+    }
+
+    /// Hand over the mutations made since the last call, in order.
+    pub fn take_deltas(&mut self) -> Vec<MergerGraphDelta> {
+        std::mem::take(&mut self.deltas)
+    }
+
+    /// The whole in-memory graph as deltas, for writing out a graph that
+    /// was never disk-backed.
+    pub fn snapshot_deltas(&self) -> Vec<MergerGraphDelta> {
+        let mut deltas: Vec<MergerGraphDelta> = self
+            .merge_nodes
+            .values()
+            .cloned()
+            .map(MergerGraphDelta::Node)
+            .collect();
+        for (origin, merges) in &self.origin_to_merges {
+            deltas.extend(merges.iter().map(|merge_tx| MergerGraphDelta::OriginEdge {
+                origin: *origin,
+                merge_tx: *merge_tx,
+            }));
+        }
+        for (parent, children) in &self.merge_to_downstream {
+            deltas.extend(
+                children
+                    .iter()
+                    .map(|child| MergerGraphDelta::DownstreamEdge {
+                        parent: *parent,
+                        child: *child,
+                    }),
+            );
+        }
+        deltas.extend(
+            self.revoked_origins
+                .iter()
+                .copied()
+                .map(MergerGraphDelta::Revoked),
+        );
+        deltas
+    }
+
+    /// Number of merge nodes held in memory.
+    pub fn cached_node_count(&self) -> usize {
+        self.merge_nodes.len()
+    }
+
+    /// Drop the in-memory edges and nodes once more than `max_nodes` are
+    /// cached. Only a disk-backed graph with every delta taken is trimmed,
+    /// so nothing is lost; the revoked origin set is always kept.
+    pub fn trim_cache(&mut self, max_nodes: usize) -> bool {
+        if self.source.is_none() || !self.deltas.is_empty() || self.merge_nodes.len() <= max_nodes {
+            return false;
         }
+        self.origin_to_merges.clear();
+        self.merge_nodes.clear();
+        self.merge_to_downstream.clear();
+        self.paged_in.clear();
+        true
+    }
+
+    /// Page in everything reachable from `origin` from the persisted copy.
+    pub fn page_in(&mut self, origin: &TxHash) {
+        let Some(source) = self.source.take() else {
+            return;
+        };
+        if self.paged_in.insert(*origin) {
+            let stored = source.merges_for_origin(origin);
+            if !stored.is_empty() {
+                merge_edges(self.origin_to_merges.entry(*origin).or_default(), stored);
+            }
+        }
+        let mut queue: Vec<TxHash> = self
+            .origin_to_merges
+            .get(origin)
+            .cloned()
+            .unwrap_or_default();
+        while let Some(merge_tx) = queue.pop() {
+            if !self.paged_in.insert(merge_tx) {
+                continue;
+            }
+            if let Entry::Vacant(slot) = self.merge_nodes.entry(merge_tx) {
+                if let Some(node) = source.merge_node(&merge_tx) {
+                    slot.insert(node);
+                }
+            }
+            let stored = source.downstream(&merge_tx);
+            if !stored.is_empty() {
+                merge_edges(
+                    self.merge_to_downstream.entry(merge_tx).or_default(),
+                    stored,
+                );
+            }
+            if let Some(children) = self.merge_to_downstream.get(&merge_tx) {
+                queue.extend(children.iter().copied());
+            }
+        }
+        self.source = Some(source);
+    }
+
+    /// Whether `tx` is a recorded merge, in memory or on disk.
+    fn is_merge(&self, tx: &TxHash) -> bool {
+        self.merge_nodes.contains_key(tx)
+            || self
+                .source
+                .as_ref()
+                .is_some_and(|source| source.merge_node(tx).is_some())
     }
 
     /// Record a new merge operation in the graph.
@@ -76,14 +241,19 @@ impl MergerGraph {
                 .entry(source.origin)
                 .or_default()
                 .push(merge_tx);
+            self.deltas.push(MergerGraphDelta::OriginEdge {
+                origin: source.origin,
+                merge_tx,
+            });
 
             // Auto-downstream: if this source origin is itself a merge tx,
             // link the parent merge to this new merge. This happens when a
             // merged token (whose origin = its merge_tx) is consumed again.
-            if self.merge_nodes.contains_key(&source.origin) {
+            if self.is_merge(&source.origin) {
                 self.record_downstream(source.origin, merge_tx);
             }
         }
+        self.deltas.push(MergerGraphDelta::Node(node.clone()));
         self.merge_nodes.insert(merge_tx, node);
     }
 
@@ -93,6 +263,10 @@ impl MergerGraph {
             .entry(parent_merge)
             .or_default()
             .push(child_merge);
+        self.deltas.push(MergerGraphDelta::DownstreamEdge {
+            parent: parent_merge,
+            child: child_merge,
+        });
     }
 
     /// Propagate a revocation from a single origin forward through the graph.
@@ -181,12 +355,16 @@ impl MergerGraph {
 
     /// Mark an origin as revoked in the graph.
     pub fn mark_origin_revoked(&mut self, origin: TxHash) {
-        self.revoked_origins.insert(origin);
+        if self.revoked_origins.insert(origin) {
+            self.deltas.push(MergerGraphDelta::Revoked(origin));
+        }
     }
 
     /// Remove revocation mark for an origin (wallet re-verified).
     pub fn mark_origin_unrevoked(&mut self, origin: &TxHash) {
-        self.revoked_origins.remove(origin);
+        if self.revoked_origins.remove(origin) {
+            self.deltas.push(MergerGraphDelta::Unrevoked(*origin));
+        }
     }
 
     /// Check if a specific origin is currently revoked.
@@ -274,6 +452,15 @@ impl MergerGraph {
     }
 }
 
+/// Add the `stored` edges missing from `cached`.
+fn merge_edges(cached: &mut Vec<TxHash>, stored: Vec<TxHash>) {
+    for tx in stored {
+        if !cached.contains(&tx) {
+            cached.push(tx);
+        }
+    }
+}
+
 impl Default for MergerGraph {
     fn default() -> Self {
         Self::new()
@@ -612,4 +799,134 @@ mod tests {
         assert!(!restored.is_empty());
         assert_eq!(restored[0].merge_tx, merge2);
     }
+
+    /// A persisted graph held in another `MergerGraph`, as a source.
+    struct SnapshotSource(std::sync::Mutex<MergerGraph>);
+
+    impl MergerGraphSource for SnapshotSource {
+        fn merges_for_origin(&self, origin: &TxHash) -> Vec<TxHash> {
+            let graph = self.0.lock().unwrap();
+            graph
+                .origin_to_merges
+                .get(origin)
+                .cloned()
+                .unwrap_or_default()
+        }
+
+        fn downstream(&self, merge_tx: &TxHash) -> Vec<TxHash> {
+            let graph = self.0.lock().unwrap();
+            graph
+                .merge_to_downstream
+                .get(merge_tx)
+                .cloned()
+                .unwrap_or_default()
+        }
+
+        fn merge_node(&self, merge_tx: &TxHash) -> Option<MergeNode> {
+            self.0.lock().unwrap().merge_nodes.get(merge_tx).cloned()
+        }
+    }
+
+    fn two_level_graph() -> MergerGraph {
+        let mut graph = MergerGraph::new();
+        graph.record_merge(MergeNode {
+            merge_tx: tx(10),
+            source_origins: vec![
+                MergeSource {
+                    origin: tx(1),
+                    amount: 50,
+                },
+                MergeSource {
+                    origin: tx(2),
+                    amount: 50,
+                },
+            ],
+            total_amount: 100,
+            holder: wallet("alice"),
+        });
+        graph.record_merge(MergeNode {
+            merge_tx: tx(20),
+            source_origins: vec![
+                MergeSource {
+                    origin: tx(10),
+                    amount: 60,
+                },
+                MergeSource {
+                    origin: tx(3),
+                    amount: 40,
+                },
+            ],
+            total_amount: 100,
+            holder: wallet("bob"),
+        });
+        graph
+    }
+
+    #[test]
+    fn mutations_are_journaled_once() {
+        let mut graph = two_level_graph();
+        let deltas = graph.take_deltas();
+        assert_eq!(
+            deltas
+                .iter()
+                .filter(|d| matches!(d, MergerGraphDelta::Node(_)))
+                .count(),
+            2
+        );
+        assert!(deltas.contains(&MergerGraphDelta::DownstreamEdge {
+            parent: tx(10),
+            child: tx(20),
+        }));
+        assert!(graph.take_deltas().is_empty());
+
+        graph.mark_origin_revoked(tx(1));
+        graph.mark_origin_revoked(tx(1));
+        graph.mark_origin_unrevoked(&tx(1));
+        assert_eq!(
+            graph.take_deltas(),
+            vec![
+                MergerGraphDelta::Revoked(tx(1)),
+                MergerGraphDelta::Unrevoked(tx(1))
+            ]
+        );
+    }
+
+    #[test]
+    fn trimmed_graph_pages_subgraphs_back_in() {
+        let mut persisted = two_level_graph();
+        persisted.take_deltas();
+        let mut graph = MergerGraph::new();
+        graph.attach_source(
+            Box::new(SnapshotSource(std::sync::Mutex::new(persisted))),
+            [tx(9)],
+        );
+        assert!(graph.is_origin_revoked(&tx(9)));
+
+        // Merge 20 is only on disk, yet consuming it is linked downstream.
+        graph.record_merge(MergeNode {
+            merge_tx: tx(30),
+            source_origins: vec![MergeSource {
+                origin: tx(20),
+                amount: 100,
+            }],
+            total_amount: 100,
+            holder: wallet("carol"),
+        });
+        assert!(!graph.trim_cache(0), "untaken deltas block trimming");
+        assert!(graph
+            .take_deltas()
+            .contains(&MergerGraphDelta::DownstreamEdge {
+                parent: tx(20),
+                child: tx(30),
+            }));
+        assert!(graph.trim_cache(0));
+        assert_eq!(graph.cached_node_count(), 0);
+
+        // Revoking origin 1 pages in 1 → 10 → 20 from disk.
+        graph.page_in(&tx(1));
+        let events = graph.propagate_revocation(&tx(1));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].merge_tx, tx(20));
+        assert_eq!(events[0].revoked_amount, 60);
+    }
 }
@@ -0,0 +1,142 @@
+//! Bounded cache of wallet portfolios.
+//!
+//! With a [`PortfolioSource`] attached, the engine keeps only recently used
+//! portfolios in memory and pages the rest in from the store on demand.
+//! Portfolios the engine changes are marked dirty and must be written back
+//! (see [`TrstEngine::take_dirty_portfolios`](crate::TrstEngine::take_dirty_portfolios))
+//! before they can be evicted, so the store is never behind the cache.
+
+use std::collections::{BTreeMap, HashMap};
+
+use burst_types::WalletAddress;
+
+use crate::engine::WalletPortfolio;
+
+/// Persisted portfolios, read when a wallet is not in the cache.
+pub trait PortfolioSource: Send + Sync {
+    /// The stored portfolio for `wallet`, if it has one.
+    fn load_portfolio(&self, wallet: &WalletAddress) -> Option<WalletPortfolio>;
+}
+
+/// Least-recently-used ordering of cached wallets.
+#[derive(Default)]
+pub(crate) struct PortfolioLru {
+    tick: u64,
+    last_used: HashMap<WalletAddress, u64>,
+    by_age: BTreeMap<u64, WalletAddress>,
+}
+
+impl PortfolioLru {
+    /// Mark `wallet` as the most recently used.
+    pub(crate) fn touch(&mut self, wallet: &WalletAddress) {
+        self.tick += 1;
+        if let Some(old) = self.last_used.insert(wallet.clone(), self.tick) {
+            self.by_age.remove(&old);
+        }
+        self.by_age.insert(self.tick, wallet.clone());
+    }
+
+    pub(crate) fn remove(&mut self, wallet: &WalletAddress) {
+        if let Some(old) = self.last_used.remove(wallet) {
+            self.by_age.remove(&old);
+        }
+    }
+
+    /// Wallets from least to most recently used.
+    pub(crate) fn oldest_first(&self) -> impl Iterator<Item = &WalletAddress> {
+        self.by_age.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use burst_types::{Timestamp, TrstState, TxHash};
+
+    use crate::engine::TrstEngine;
+    use crate::token::TrstToken;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct MemorySource(Arc<Mutex<HashMap<WalletAddress, WalletPortfolio>>>);
+
+    impl MemorySource {
+        fn write_back(&self, engine: &mut TrstEngine) {
+            let mut stored = self.0.lock().unwrap();
+            for (wallet, portfolio) in engine.take_dirty_portfolios() {
+                stored.insert(wallet, portfolio);
+            }
+        }
+    }
+
+    impl PortfolioSource for MemorySource {
+        fn load_portfolio(&self, wallet: &WalletAddress) -> Option<WalletPortfolio> {
+            self.0.lock().unwrap().get(wallet).cloned()
+        }
+    }
+
+    fn wallet(n: u8) -> WalletAddress {
+        WalletAddress::new(format!("brst_{:0>60}", n))
+    }
+
+    fn token(n: u8, holder: WalletAddress, amount: u128) -> TrstToken {
+        TrstToken {
+            id: TxHash::new([n; 32]),
+            amount,
+            origin: TxHash::new([n; 32]),
+            link: TxHash::new([n; 32]),
+            holder,
+            origin_timestamp: Timestamp::new(100),
+            effective_origin_timestamp: Timestamp::new(100),
+            state: TrstState::Active,
+            origin_wallet: wallet(200),
+            origin_proportions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn cold_portfolios_are_evicted_and_paged_back_in() {
+        let source = MemorySource::default();
+        let mut engine = TrstEngine::with_expiry(10_000);
+        engine.attach_portfolio_source(Box::new(source.clone()), 2);
+
+        for n in 1..=3 {
+            engine.track_token(token(n, wallet(n), 100));
+        }
+        // Unwritten portfolios stay in memory past the limit.
+        assert_eq!(engine.cached_portfolio_count(), 3);
+        source.write_back(&mut engine);
+        assert_eq!(engine.cached_portfolio_count(), 2);
+        assert!(engine.get_portfolio(&wallet(1)).is_some());
+
+        // Wallet 1 was evicted; a debit pages it back in and marks it dirty.
+        engine.debit_wallet(&wallet(1), 40);
+        assert_eq!(engine.transferable_balance_snapshot(&wallet(1)), Some(60));
+        let dirty = engine.take_dirty_portfolios();
+        assert_eq!(dirty.len(), 1);
+        assert_eq!(dirty[0].0, wallet(1));
+        assert_eq!(dirty[0].1.cached_transferable, 60);
+    }
+
+    #[test]
+    fn revocation_reaches_evicted_holders() {
+        let source = MemorySource::default();
+        let mut engine = TrstEngine::with_expiry(10_000);
+        engine.attach_portfolio_source(Box::new(source.clone()), 1);
+
+        engine.track_token(token(1, wallet(1), 100));
+        engine.track_token(token(2, wallet(2), 50));
+        source.write_back(&mut engine);
+        assert_eq!(engine.cached_portfolio_count(), 1);
+
+        engine.revoke_by_origin(&wallet(200));
+        source.write_back(&mut engine);
+        for n in 1..=2 {
+            let stored = source.load_portfolio(&wallet(n)).unwrap();
+            assert_eq!(stored.cached_transferable, 0);
+            assert_eq!(stored.tokens[0].state, TrstState::Revoked);
+        }
+    }
+}
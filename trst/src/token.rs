@@ -1,6 +1,6 @@
 //! TRST token representation.
 
-use burst_types::{Timestamp, TrstState, TxHash, WalletAddress};
+use burst_types::{Timestamp, TrstDecayMode, TrstState, TxHash, WalletAddress};
 use serde::{Deserialize, Serialize};
 
 /// A TRST token — the fundamental unit of transferable currency.
@@ -106,6 +106,58 @@ impl TrstToken {
         self.amount.saturating_mul(bps) / 10_000
     }
 
+    /// Current value in basis points of face value under the network's
+    /// decay mode. Expired and revoked tokens are worth nothing in every mode.
+    ///
+    /// Exponential decay halves the value every `half_life_secs` and
+    /// interpolates linearly within each half-life, so every node computes
+    /// the same integer result. A zero half-life disables decay.
+    pub fn decayed_value_bps(
+        &self,
+        now: Timestamp,
+        expiry_secs: u64,
+        mode: TrstDecayMode,
+        half_life_secs: u64,
+    ) -> u64 {
+        if matches!(self.state, TrstState::Expired | TrstState::Revoked)
+            || self.is_expired(now, expiry_secs)
+        {
+            return 0;
+        }
+        match mode {
+            TrstDecayMode::HardExpiry => 10_000,
+            TrstDecayMode::Linear => self.current_value_bps(now, expiry_secs),
+            TrstDecayMode::Exponential => {
+                if half_life_secs == 0 {
+                    return 10_000;
+                }
+                let age_secs = now
+                    .as_secs()
+                    .saturating_sub(self.effective_origin_timestamp.as_secs());
+                let halvings = age_secs / half_life_secs;
+                if halvings >= 64 {
+                    return 0;
+                }
+                let start = 10_000u64 >> halvings;
+                let drop = (start / 2) as u128 * (age_secs % half_life_secs) as u128
+                    / half_life_secs as u128;
+                start - drop as u64
+            }
+        }
+    }
+
+    /// Face value scaled by [`decayed_value_bps`](Self::decayed_value_bps).
+    pub fn decayed_value(
+        &self,
+        now: Timestamp,
+        expiry_secs: u64,
+        mode: TrstDecayMode,
+        half_life_secs: u64,
+    ) -> u128 {
+        let bps = self.decayed_value_bps(now, expiry_secs, mode, half_life_secs) as u128;
+        self.amount.saturating_mul(bps) / 10_000
+    }
+
     /// For merged tokens with multiple origins, compute effective value
     /// considering each origin's individual expiry timeline.
     pub fn effective_value_proportional(&self, now: Timestamp, expiry_secs: u64) -> u128 {
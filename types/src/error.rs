@@ -1,5 +1,16 @@
-//! Top-level error type shared across crates.
+//! Top-level error type shared across crates, and the registry of stable
+//! error codes.
+//!
+//! Every error that crosses a crate boundary maps to an [`ErrorCode`]: a
+//! number that never changes meaning once released, grouped by thousands
+//! into an [`ErrorCategory`]. RPC responses carry the code next to the
+//! message, so clients branch on the code rather than the English text.
+//! New codes are appended within their category; retired ones are never
+//! reused.
 
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Common error type for the BURST protocol.
@@ -11,6 +22,11 @@ pub enum BurstError {
     #[error("insufficient TRST balance: need {needed}, have {available}")]
     InsufficientTrst { needed: u128, available: u128 },
 
+    #[error(
+        "insufficient transferable TRST: need {needed} but only {transferable} is transferable"
+    )]
+    InsufficientTransferableTrst { needed: u128, transferable: u128 },
+
     #[error("TRST token has expired")]
     TrstExpired,
 
@@ -56,3 +72,302 @@ pub enum BurstError {
     #[error("{0}")]
     Other(String),
 }
+
+/// Broad class of an error, the thousands digit of its code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// The request or block is malformed or fails validation.
+    Request,
+    /// Not enough BRN or TRST, or the wallet may not spend it.
+    Balance,
+    /// The named account, block or record does not exist.
+    NotFound,
+    /// The request conflicts with what the ledger already holds.
+    Conflict,
+    /// The node refused or could not take the request right now.
+    Access,
+    /// The database failed.
+    Storage,
+    /// A protocol subsystem (network, consensus, governance, ...) failed.
+    Subsystem,
+    /// A bug or an unclassified failure.
+    Internal,
+}
+
+impl ErrorCategory {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Request => "request",
+            Self::Balance => "balance",
+            Self::NotFound => "not_found",
+            Self::Conflict => "conflict",
+            Self::Access => "access",
+            Self::Storage => "storage",
+            Self::Subsystem => "subsystem",
+            Self::Internal => "internal",
+        }
+    }
+}
+
+/// Stable error codes. The numeric value of each variant is part of the
+/// RPC interface.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum ErrorCode {
+    // ── 1xxx request ─────────────────────────────────────────────────────
+    InvalidRequest = 1000,
+    InvalidBlock = 1001,
+    InvalidSignature = 1002,
+    InvalidWork = 1003,
+    InvalidAddress = 1004,
+    InvalidTransaction = 1005,
+    Serialization = 1006,
+    InvalidConfig = 1007,
+
+    // ── 2xxx balance ─────────────────────────────────────────────────────
+    InsufficientBrn = 2000,
+    InsufficientTrst = 2001,
+    TrstExpired = 2002,
+    TrstRevoked = 2003,
+    WalletNotVerified = 2004,
+    WalletRevoked = 2005,
+
+    // ── 3xxx not found ───────────────────────────────────────────────────
+    NotFound = 3000,
+    AccountNotFound = 3001,
+    BlockNotFound = 3002,
+    ProposalNotFound = 3003,
+    ProofUnavailable = 3004,
+
+    // ── 4xxx conflict ────────────────────────────────────────────────────
+    Duplicate = 4000,
+    DuplicateTransaction = 4001,
+    Fork = 4002,
+
+    // ── 5xxx access ──────────────────────────────────────────────────────
+    Unauthorized = 5000,
+    Forbidden = 5001,
+    RateLimited = 5002,
+    Busy = 5003,
+    QueueFull = 5004,
+    NodeUnavailable = 5005,
+    Unsupported = 5006,
+
+    // ── 6xxx storage ─────────────────────────────────────────────────────
+    Storage = 6000,
+    StorageCorrupted = 6001,
+    StorageReadOnly = 6002,
+
+    // ── 7xxx subsystem ───────────────────────────────────────────────────
+    Network = 7000,
+    Consensus = 7001,
+    Governance = 7002,
+    Verification = 7003,
+    Keystore = 7004,
+    WorkGeneration = 7005,
+
+    // ── 9xxx internal ────────────────────────────────────────────────────
+    Internal = 9000,
+}
+
+impl ErrorCode {
+    /// Every registered code, in numeric order.
+    pub const ALL: &'static [ErrorCode] = &[
+        Self::InvalidRequest,
+        Self::InvalidBlock,
+        Self::InvalidSignature,
+        Self::InvalidWork,
+        Self::InvalidAddress,
+        Self::InvalidTransaction,
+        Self::Serialization,
+        Self::InvalidConfig,
+        Self::InsufficientBrn,
+        Self::InsufficientTrst,
+        Self::TrstExpired,
+        Self::TrstRevoked,
+        Self::WalletNotVerified,
+        Self::WalletRevoked,
+        Self::NotFound,
+        Self::AccountNotFound,
+        Self::BlockNotFound,
+        Self::ProposalNotFound,
+        Self::ProofUnavailable,
+        Self::Duplicate,
+        Self::DuplicateTransaction,
+        Self::Fork,
+        Self::Unauthorized,
+        Self::Forbidden,
+        Self::RateLimited,
+        Self::Busy,
+        Self::QueueFull,
+        Self::NodeUnavailable,
+        Self::Unsupported,
+        Self::Storage,
+        Self::StorageCorrupted,
+        Self::StorageReadOnly,
+        Self::Network,
+        Self::Consensus,
+        Self::Governance,
+        Self::Verification,
+        Self::Keystore,
+        Self::WorkGeneration,
+        Self::Internal,
+    ];
+
+    pub fn code(self) -> u16 {
+        self as u16
+    }
+
+    /// The registered code numbered `code`.
+    pub fn from_code(code: u16) -> Option<Self> {
+        Self::ALL.iter().copied().find(|c| c.code() == code)
+    }
+
+    pub fn category(self) -> ErrorCategory {
+        match self.code() / 1000 {
+            1 => ErrorCategory::Request,
+            2 => ErrorCategory::Balance,
+            3 => ErrorCategory::NotFound,
+            4 => ErrorCategory::Conflict,
+            5 => ErrorCategory::Access,
+            6 => ErrorCategory::Storage,
+            7 => ErrorCategory::Subsystem,
+            _ => ErrorCategory::Internal,
+        }
+    }
+
+    /// Snake-case name of the code, as stable as the number.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::InvalidRequest => "invalid_request",
+            Self::InvalidBlock => "invalid_block",
+            Self::InvalidSignature => "invalid_signature",
+            Self::InvalidWork => "invalid_work",
+            Self::InvalidAddress => "invalid_address",
+            Self::InvalidTransaction => "invalid_transaction",
+            Self::Serialization => "serialization",
+            Self::InvalidConfig => "invalid_config",
+            Self::InsufficientBrn => "insufficient_brn",
+            Self::InsufficientTrst => "insufficient_trst",
+            Self::TrstExpired => "trst_expired",
+            Self::TrstRevoked => "trst_revoked",
+            Self::WalletNotVerified => "wallet_not_verified",
+            Self::WalletRevoked => "wallet_revoked",
+            Self::NotFound => "not_found",
+            Self::AccountNotFound => "account_not_found",
+            Self::BlockNotFound => "block_not_found",
+            Self::ProposalNotFound => "proposal_not_found",
+            Self::ProofUnavailable => "proof_unavailable",
+            Self::Duplicate => "duplicate",
+            Self::DuplicateTransaction => "duplicate_transaction",
+            Self::Fork => "fork",
+            Self::Unauthorized => "unauthorized",
+            Self::Forbidden => "forbidden",
+            Self::RateLimited => "rate_limited",
+            Self::Busy => "busy",
+            Self::QueueFull => "queue_full",
+            Self::NodeUnavailable => "node_unavailable",
+            Self::Unsupported => "unsupported",
+            Self::Storage => "storage",
+            Self::StorageCorrupted => "storage_corrupted",
+            Self::StorageReadOnly => "storage_read_only",
+            Self::Network => "network",
+            Self::Consensus => "consensus",
+            Self::Governance => "governance",
+            Self::Verification => "verification",
+            Self::Keystore => "keystore",
+            Self::WorkGeneration => "work_generation",
+            Self::Internal => "internal",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.code(), self.name())
+    }
+}
+
+impl Serialize for ErrorCode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for ErrorCode {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = u16::deserialize(deserializer)?;
+        Self::from_code(code)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown error code {code}")))
+    }
+}
+
+/// An error that maps to a registered [`ErrorCode`].
+pub trait HasErrorCode {
+    fn error_code(&self) -> ErrorCode;
+}
+
+impl HasErrorCode for BurstError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::InsufficientBrn { .. } => ErrorCode::InsufficientBrn,
+            Self::InsufficientTrst { .. } | Self::InsufficientTransferableTrst { .. } => {
+                ErrorCode::InsufficientTrst
+            }
+            Self::TrstExpired => ErrorCode::TrstExpired,
+            Self::TrstRevoked => ErrorCode::TrstRevoked,
+            Self::InvalidSignature => ErrorCode::InvalidSignature,
+            Self::InvalidAddress(_) => ErrorCode::InvalidAddress,
+            Self::WalletNotVerified => ErrorCode::WalletNotVerified,
+            Self::WalletRevoked => ErrorCode::WalletRevoked,
+            Self::DuplicateTransaction => ErrorCode::DuplicateTransaction,
+            Self::InvalidBlock { .. } => ErrorCode::InvalidBlock,
+            Self::InvalidWork => ErrorCode::InvalidWork,
+            Self::Governance(_) => ErrorCode::Governance,
+            Self::Verification(_) => ErrorCode::Verification,
+            Self::Storage(_) => ErrorCode::Storage,
+            Self::Network(_) => ErrorCode::Network,
+            Self::Serialization(_) => ErrorCode::Serialization,
+            Self::Other(_) => ErrorCode::Internal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_unique_ordered_and_round_trip() {
+        for pair in ErrorCode::ALL.windows(2) {
+            assert!(pair[0].code() < pair[1].code(), "{} out of order", pair[1]);
+        }
+        for &code in ErrorCode::ALL {
+            assert_eq!(ErrorCode::from_code(code.code()), Some(code));
+            let bytes = bincode::serialize(&code).unwrap();
+            assert_eq!(bytes, code.code().to_le_bytes());
+            assert_eq!(bincode::deserialize::<ErrorCode>(&bytes).unwrap(), code);
+        }
+        assert_eq!(ErrorCode::from_code(1999), None);
+    }
+
+    #[test]
+    fn category_follows_the_thousands_digit() {
+        assert_eq!(
+            ErrorCode::InsufficientTrst.category(),
+            ErrorCategory::Balance
+        );
+        assert_eq!(ErrorCode::BlockNotFound.category(), ErrorCategory::NotFound);
+        assert_eq!(ErrorCode::RateLimited.category(), ErrorCategory::Access);
+        assert_eq!(ErrorCode::Internal.category(), ErrorCategory::Internal);
+        assert_eq!(
+            BurstError::InsufficientBrn {
+                needed: 2,
+                available: 1
+            }
+            .error_code(),
+            ErrorCode::InsufficientBrn
+        );
+    }
+}
@@ -1,7 +1,8 @@
 //! Fundamental types for the BURST protocol.
 //!
 //! This crate defines the core types shared across every other crate in the workspace:
-//! addresses, hashes, amounts, timestamps, protocol parameters, and state enums.
+//! addresses, hashes, amounts, timestamps, protocol parameters, state enums and the
+//! wallet state machine.
 
 pub mod address;
 pub mod amount;
@@ -9,20 +10,24 @@ pub mod block;
 pub mod error;
 pub mod hash;
 pub mod keys;
+pub mod memory;
 pub mod network;
 pub mod params;
 pub mod state;
+pub mod state_machine;
 pub mod time;
 
 pub use address::WalletAddress;
 pub use amount::{BrnAmount, TrstAmount, BRN_UNIT, MBRN_UNIT, MTRST_UNIT, TRST_UNIT};
 pub use block::BlockHash;
-pub use error::BurstError;
+pub use error::{BurstError, ErrorCategory, ErrorCode, HasErrorCode};
 pub use hash::TxHash;
 pub use keys::{KeyPair, PrivateKey, PublicKey, Signature};
+pub use memory::{ContainerUsage, MemoryAccounting};
 pub use network::NetworkId;
-pub use params::ProtocolParams;
+pub use params::{GovernanceTallyMode, ProtocolParams, TrstDecayMode};
 pub use state::{TrstState, WalletState};
+pub use state_machine::{StateTransition, TransitionReason};
 pub use time::Timestamp;
 
 /// Tracks what fraction of a merged token came from a specific origin.
@@ -0,0 +1,38 @@
+//! Memory accounting for in-memory containers.
+//!
+//! Caches and queues throughout the node implement [`MemoryAccounting`] so
+//! operators can see, per subsystem, how many elements are held and roughly
+//! how much memory they take.
+
+use serde::{Deserialize, Serialize};
+
+/// Element count and approximate memory of one container.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContainerUsage {
+    pub count: usize,
+    /// Estimated from element sizes; allocator overhead and spare capacity
+    /// are not counted.
+    pub bytes: usize,
+}
+
+/// A container that can report its size.
+pub trait MemoryAccounting {
+    /// Number of elements held.
+    fn element_count(&self) -> usize;
+
+    /// Approximate bytes held by the elements.
+    fn approximate_bytes(&self) -> usize;
+
+    /// Both figures together.
+    fn memory_usage(&self) -> ContainerUsage {
+        ContainerUsage {
+            count: self.element_count(),
+            bytes: self.approximate_bytes(),
+        }
+    }
+}
+
+/// Approximate bytes of `count` entries of type `T` in a collection.
+pub fn entry_bytes<T>(count: usize) -> usize {
+    count.saturating_mul(std::mem::size_of::<T>())
+}
@@ -31,4 +31,33 @@ impl NetworkId {
             Self::Dev => "dev",
         }
     }
+
+    /// The network with the given [`as_str`](Self::as_str) name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "live" => Some(Self::Live),
+            "test" => Some(Self::Test),
+            "dev" => Some(Self::Dev),
+            _ => None,
+        }
+    }
+
+    /// One-byte code identifying the network inside signed data.
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::Live => 1,
+            Self::Test => 2,
+            Self::Dev => 3,
+        }
+    }
+
+    /// The network with the given [`code`](Self::code).
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(Self::Live),
+            2 => Some(Self::Test),
+            3 => Some(Self::Dev),
+            _ => None,
+        }
+    }
 }
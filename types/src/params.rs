@@ -8,6 +8,86 @@ use blake2::digest::consts::U32;
 use blake2::{Blake2b, Digest};
 use serde::{Deserialize, Serialize};
 
+/// How TRST value declines between mint and expiry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrstDecayMode {
+    /// Full face value until expiry, then nothing.
+    #[default]
+    HardExpiry,
+    /// Value falls linearly from face value at mint to zero at expiry.
+    Linear,
+    /// Value halves every `trst_decay_half_life_secs`; expiry still applies.
+    Exponential,
+}
+
+impl TrstDecayMode {
+    /// Numeric code used by governance proposals.
+    pub fn code(&self) -> u128 {
+        match self {
+            Self::HardExpiry => 0,
+            Self::Linear => 1,
+            Self::Exponential => 2,
+        }
+    }
+
+    /// Parse a governance code; unknown codes yield `None`.
+    pub fn from_code(code: u128) -> Option<Self> {
+        match code {
+            0 => Some(Self::HardExpiry),
+            1 => Some(Self::Linear),
+            2 => Some(Self::Exponential),
+            _ => None,
+        }
+    }
+
+    /// Name reported over RPC.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::HardExpiry => "hard_expiry",
+            Self::Linear => "linear",
+            Self::Exponential => "exponential",
+        }
+    }
+}
+
+/// How governance votes are weighed when they are counted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GovernanceTallyMode {
+    /// One wallet, one vote.
+    #[default]
+    Majority,
+    /// Votes gain weight the longer they stand before the window closes.
+    /// Experimental; needs the governance crate's `conviction-voting` feature.
+    Conviction,
+}
+
+impl GovernanceTallyMode {
+    /// Numeric code used by governance proposals.
+    pub fn code(&self) -> u128 {
+        match self {
+            Self::Majority => 0,
+            Self::Conviction => 1,
+        }
+    }
+
+    /// Parse a governance code; unknown codes yield `None`.
+    pub fn from_code(code: u128) -> Option<Self> {
+        match code {
+            0 => Some(Self::Majority),
+            1 => Some(Self::Conviction),
+            _ => None,
+        }
+    }
+
+    /// Name reported over RPC.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Majority => "majority",
+            Self::Conviction => "conviction",
+        }
+    }
+}
+
 /// All protocol parameters stored by every node.
 ///
 /// The general equation: BURST is defined by `brn_rate` and `trst_expiry_secs`.
@@ -23,6 +103,30 @@ pub struct ProtocolParams {
     /// Set to `u64::MAX` for "never expires" (normal money mode).
     pub trst_expiry_secs: u64,
 
+    /// How TRST loses value before it expires. `HardExpiry` keeps full face
+    /// value until `trst_expiry_secs`; the decay modes let testnets try
+    /// demurrage without a fork.
+    pub trst_decay_mode: TrstDecayMode,
+
+    /// Half-life of TRST value under `TrstDecayMode::Exponential`.
+    /// Default: 90 days.
+    pub trst_decay_half_life_secs: u64,
+
+    // ── Inactivity ───────────────────────────────────────────────────────
+    /// Seconds without any block or liveness proof after which a wallet stops
+    /// accruing BRN until it re-verifies or submits a Liveness block.
+    /// `0` disables the rule. Default: 180 days.
+    pub inactivity_suspension_secs: u64,
+
+    /// How long before the suspension the wallet enters its grace period and
+    /// is warned. Default: 30 days.
+    pub inactivity_grace_secs: u64,
+
+    // ── Pending transfers ────────────────────────────────────────────────
+    /// Seconds a send may stay unreceived before it is returned to the
+    /// sender. `0` keeps pending sends forever. Default: 90 days.
+    pub pending_ttl_secs: u64,
+
     // ── Verification ─────────────────────────────────────────────────────
     /// Number of endorsers required before verification begins.
     pub endorsement_threshold: u32,
@@ -54,6 +158,18 @@ pub struct ProtocolParams {
     /// Default: 7 days = 604800 seconds.
     pub neither_penalty_cooldown_secs: u64,
 
+    /// Period (seconds) after surviving a challenge during which a wallet
+    /// cannot be challenged again. Default: 30 days.
+    pub challenge_target_cooldown_secs: u64,
+
+    /// Number of lost challenges a challenger may accumulate before the
+    /// required stake starts to escalate.
+    pub challenge_loss_allowance: u32,
+
+    /// Stake increase (basis points, compounding) per lost challenge beyond
+    /// `challenge_loss_allowance`. Default: 10000 (stake doubles each time).
+    pub challenge_stake_escalation_bps: u32,
+
     // ── Governance (5-phase: Proposal → Exploration → Cooldown → Promotion → Activation) ──
     /// Duration of the Proposal phase in seconds.
     pub governance_proposal_duration_secs: u64,
@@ -96,6 +212,15 @@ pub struct ProtocolParams {
     /// Default: 3600 (1 hour).
     pub governance_propagation_buffer_secs: u64,
 
+    /// How votes are weighed when counted. `Majority` counts every vote
+    /// once; `Conviction` lets test networks try time-weighted voting
+    /// without a fork.
+    pub governance_tally_mode: GovernanceTallyMode,
+
+    /// Seconds a vote must stand before the window closes to reach full
+    /// conviction under `GovernanceTallyMode::Conviction`. Default: 7 days.
+    pub governance_conviction_period_secs: u64,
+
     // ── Consti ────────────────────────────────────────────────────────────
     /// Supermajority threshold for constitutional amendments (basis points).
     /// Separate from parameter governance — can be higher or lower.
@@ -109,6 +234,14 @@ pub struct ProtocolParams {
     /// Duration (seconds) of a verification session before it expires.
     pub verification_timeout_secs: u64,
 
+    /// Duration (seconds) selected verifiers have to vote before the
+    /// non-voters are penalized and replaced.
+    pub verification_round_timeout_secs: u64,
+
+    /// Number of voting rounds a verification may time out before it is
+    /// abandoned and the wallet falls back to Unverified.
+    pub max_verification_rounds: u32,
+
     /// Duration (seconds) of a challenge review period.
     pub challenge_duration_secs: u64,
 
@@ -125,15 +258,66 @@ pub struct ProtocolParams {
     /// Number of verified wallets required to exit bootstrap phase.
     pub bootstrap_exit_threshold: u32,
 
+    /// Per-transaction TRST cap applied to every wallet while the network is
+    /// in its bootstrap phase (raw units; 0 = no limit).
+    pub bootstrap_spending_limit: u128,
+
+    /// Per-burn BRN cap applied to every wallet while the network is in its
+    /// bootstrap phase (raw units; 0 = no limit).
+    pub bootstrap_burn_limit: u128,
+
+    /// Age (seconds since verification) before a wallet leaves probation and
+    /// may issue endorsements.
+    pub probation_period_secs: u64,
+
+    /// TRST a wallet on probation may send per day (0 = no cap).
+    pub probation_daily_send_cap: u128,
+
+    /// TRST a provisional wallet — past probation, but verified for less
+    /// than `new_wallet_rate_limit_duration_secs` — may send per day
+    /// (0 = no cap).
+    pub provisional_daily_send_cap: u128,
+
     // ── Anti-Spam ────────────────────────────────────────────────────────
     /// Minimum proof-of-work difficulty for transaction submission.
     pub min_work_difficulty: u64,
 
+    /// Work multiplier for ordinary blocks (sends, burns, delegation, ...),
+    /// in basis points of `min_work_difficulty`. Default: 10000 (1x).
+    pub work_base_multiplier_bps: u32,
+
+    /// Work multiplier for receive and open blocks. Default: 80000 (8x).
+    pub work_receive_multiplier_bps: u32,
+
+    /// Work multiplier for epoch and governance activation blocks.
+    /// Default: 640000 (64x).
+    pub work_epoch_multiplier_bps: u32,
+
+    /// Work multiplier for governance proposals and votes. Default: 40000 (4x).
+    pub work_governance_multiplier_bps: u32,
+
     /// Maximum transactions per day for new wallets (rate limiting).
     pub new_wallet_tx_limit_per_day: u32,
 
     /// Duration (seconds) that new-wallet rate limit applies.
     pub new_wallet_rate_limit_duration_secs: u64,
+
+    // ── Aliases ──────────────────────────────────────────────────────────
+    /// BRN burned to register or renew an alias.
+    pub alias_fee: u128,
+
+    /// Duration (seconds) an alias stays with its owner after each
+    /// registration or renewal (0 = alias registration disabled).
+    pub alias_term_secs: u64,
+
+    /// Duration (seconds) after an alias expires during which only its
+    /// previous owner may renew it.
+    pub alias_grace_secs: u64,
+
+    // ── Ledger format ────────────────────────────────────────────────────
+    /// Network-wide account epoch. Every account may publish blocks up to
+    /// this version without its own epoch block. Default: 0.
+    pub ledger_epoch: u8,
 }
 
 impl ProtocolParams {
@@ -158,6 +342,13 @@ impl ProtocolParams {
         Self {
             brn_rate: Self::BRN_RATE_1_PER_HOUR,
             trst_expiry_secs: 365 * 24 * 3600, // 1 year
+            trst_decay_mode: TrstDecayMode::HardExpiry,
+            trst_decay_half_life_secs: 90 * 24 * 3600, // 90 days
+
+            inactivity_suspension_secs: 180 * 24 * 3600, // ~6 months
+            inactivity_grace_secs: 30 * 24 * 3600,       // 30 days
+
+            pending_ttl_secs: 90 * 24 * 3600, // 90 days
 
             endorsement_threshold: 3,
             endorsement_burn_amount: 336 * BRN_UNIT,
@@ -168,6 +359,9 @@ impl ProtocolParams {
 
             challenge_stake_amount: 1000 * BRN_UNIT,
             neither_penalty_cooldown_secs: 7 * 24 * 3600, // 7 days
+            challenge_target_cooldown_secs: 30 * 24 * 3600, // 30 days
+            challenge_loss_allowance: 2,
+            challenge_stake_escalation_bps: 10_000, // 2x per excess loss
 
             governance_proposal_duration_secs: 7 * 24 * 3600, // 1 week
             governance_exploration_duration_secs: 14 * 24 * 3600, // 2 weeks
@@ -181,20 +375,39 @@ impl ProtocolParams {
             governance_max_rounds: 3,
             governance_proposal_window_secs: 7 * 24 * 3600, // 7 days
             governance_propagation_buffer_secs: 3600,       // 1 hour
+            governance_tally_mode: GovernanceTallyMode::Majority,
+            governance_conviction_period_secs: 7 * 24 * 3600, // 7 days
 
-            consti_supermajority_bps: 9000,           // 90%
-            consti_quorum_bps: 3000,                  // 30%
-            verification_timeout_secs: 7 * 24 * 3600, // 1 week
-            challenge_duration_secs: 7 * 24 * 3600,   // 1 week
-            endorser_reward_bps: 1000,                // 10%
+            consti_supermajority_bps: 9000,             // 90%
+            consti_quorum_bps: 3000,                    // 30%
+            verification_timeout_secs: 7 * 24 * 3600,   // 1 week
+            verification_round_timeout_secs: 24 * 3600, // 1 day
+            max_verification_rounds: 3,
+            challenge_duration_secs: 7 * 24 * 3600, // 1 week
+            endorser_reward_bps: 1000,              // 10%
 
             new_wallet_spending_limit: 0,
             new_wallet_limit_duration_secs: 0,
             bootstrap_exit_threshold: 50,
+            bootstrap_spending_limit: 0,
+            bootstrap_burn_limit: 0,
+            probation_period_secs: 7 * 24 * 3600, // 1 week
+            probation_daily_send_cap: 0,
+            provisional_daily_send_cap: 0,
 
             min_work_difficulty: 0xffff_f000_0000_0000,
+            work_base_multiplier_bps: 10_000,
+            work_receive_multiplier_bps: 80_000,
+            work_epoch_multiplier_bps: 640_000,
+            work_governance_multiplier_bps: 40_000,
             new_wallet_tx_limit_per_day: 10,
             new_wallet_rate_limit_duration_secs: 30 * 24 * 3600, // 30 days
+
+            alias_fee: 100 * BRN_UNIT,
+            alias_term_secs: 365 * 24 * 3600, // 1 year
+            alias_grace_secs: 30 * 24 * 3600, // 30 days
+
+            ledger_epoch: 0,
         }
     }
 }
@@ -213,6 +426,7 @@ impl ProtocolParams {
         p.governance_supermajority_bps = 5100; // 51%
         p.governance_propagation_buffer_secs = 5;
         p.governance_proposal_window_secs = 30;
+        p.governance_conviction_period_secs = 60;
         p.min_work_difficulty = 0xffff_f000_0000_0000;
         p
     }
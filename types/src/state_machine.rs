@@ -0,0 +1,205 @@
+//! Wallet state machine — the single place `WalletState` transitions are
+//! validated.
+//!
+//! Every allowed transition is listed in [`ALLOWED_TRANSITIONS`] together
+//! with the reason that justifies it; anything else is rejected. Accepted
+//! transitions produce a [`StateTransition`] record that is kept in the
+//! account's history.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{Timestamp, WalletState};
+
+/// Why a wallet changed state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionReason {
+    /// Enough endorsements were collected to start verification.
+    EndorsementThresholdReached,
+    /// Verifiers were drawn and voting began.
+    VerifiersSelected,
+    /// Verifiers voted the wallet legitimate.
+    VerificationPassed,
+    /// Verifiers rejected the wallet.
+    VerificationFailed,
+    /// Verification was abandoned after its rounds timed out.
+    VerificationExpired,
+    /// A challenge was opened against the wallet.
+    ChallengeInitiated,
+    /// The challenge was voted down.
+    ChallengeRejected,
+    /// The challenge ran out of time.
+    ChallengeExpired,
+    /// Fraud was confirmed; originated TRST is revoked.
+    FraudConfirmed,
+    /// No activity for longer than the inactivity limit.
+    Inactivity,
+    /// The holder deactivated the wallet.
+    VoluntaryDeactivation,
+    /// A deactivated wallet proved it is alive again.
+    Reactivated,
+    /// Testnet faucet grant (never available on mainnet).
+    TestnetFaucet,
+}
+
+impl TransitionReason {
+    /// Stable reason code, as persisted and reported over RPC.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::EndorsementThresholdReached => "endorsement_threshold_reached",
+            Self::VerifiersSelected => "verifiers_selected",
+            Self::VerificationPassed => "verification_passed",
+            Self::VerificationFailed => "verification_failed",
+            Self::VerificationExpired => "verification_expired",
+            Self::ChallengeInitiated => "challenge_initiated",
+            Self::ChallengeRejected => "challenge_rejected",
+            Self::ChallengeExpired => "challenge_expired",
+            Self::FraudConfirmed => "fraud_confirmed",
+            Self::Inactivity => "inactivity",
+            Self::VoluntaryDeactivation => "voluntary_deactivation",
+            Self::Reactivated => "reactivated",
+            Self::TestnetFaucet => "testnet_faucet",
+        }
+    }
+}
+
+/// Every allowed `(from, to, reason)` triple.
+pub const ALLOWED_TRANSITIONS: &[(WalletState, WalletState, TransitionReason)] = {
+    use TransitionReason as R;
+    use WalletState as S;
+    &[
+        (S::Unverified, S::Endorsed, R::EndorsementThresholdReached),
+        (S::Endorsed, S::Voting, R::VerifiersSelected),
+        (S::Voting, S::Verified, R::VerificationPassed),
+        (S::Voting, S::Unverified, R::VerificationFailed),
+        (S::Endorsed, S::Unverified, R::VerificationExpired),
+        (S::Voting, S::Unverified, R::VerificationExpired),
+        (S::Verified, S::Challenged, R::ChallengeInitiated),
+        (S::Challenged, S::Verified, R::ChallengeRejected),
+        (S::Challenged, S::Verified, R::ChallengeExpired),
+        (S::Challenged, S::Revoked, R::FraudConfirmed),
+        // Challenges opened before the account recorded `Challenged`.
+        (S::Verified, S::Revoked, R::FraudConfirmed),
+        (S::Verified, S::Deactivated, R::Inactivity),
+        (S::Verified, S::Deactivated, R::VoluntaryDeactivation),
+        (S::Deactivated, S::Verified, R::Reactivated),
+        (S::Unverified, S::Verified, R::TestnetFaucet),
+        (S::Endorsed, S::Verified, R::TestnetFaucet),
+        (S::Voting, S::Verified, R::TestnetFaucet),
+    ]
+};
+
+/// A transition the state machine does not allow.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[error("wallet cannot move from {from:?} to {to:?} ({})", reason.as_str())]
+pub struct InvalidTransition {
+    pub from: WalletState,
+    pub to: WalletState,
+    pub reason: TransitionReason,
+}
+
+/// One entry in an account's state history.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateTransition {
+    pub from: WalletState,
+    pub to: WalletState,
+    pub reason: TransitionReason,
+    pub at: Timestamp,
+}
+
+/// Whether `from -> to` is allowed for `reason`.
+pub fn is_allowed(from: WalletState, to: WalletState, reason: TransitionReason) -> bool {
+    ALLOWED_TRANSITIONS.contains(&(from, to, reason))
+}
+
+/// Validate `from -> to` and return the record to persist.
+pub fn validate_transition(
+    from: WalletState,
+    to: WalletState,
+    reason: TransitionReason,
+    at: Timestamp,
+) -> Result<StateTransition, InvalidTransition> {
+    if is_allowed(from, to, reason) {
+        Ok(StateTransition {
+            from,
+            to,
+            reason,
+            at,
+        })
+    } else {
+        Err(InvalidTransition { from, to, reason })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verification_path_is_allowed() {
+        let path = [
+            (
+                WalletState::Unverified,
+                WalletState::Endorsed,
+                TransitionReason::EndorsementThresholdReached,
+            ),
+            (
+                WalletState::Endorsed,
+                WalletState::Voting,
+                TransitionReason::VerifiersSelected,
+            ),
+            (
+                WalletState::Voting,
+                WalletState::Verified,
+                TransitionReason::VerificationPassed,
+            ),
+            (
+                WalletState::Verified,
+                WalletState::Challenged,
+                TransitionReason::ChallengeInitiated,
+            ),
+            (
+                WalletState::Challenged,
+                WalletState::Revoked,
+                TransitionReason::FraudConfirmed,
+            ),
+        ];
+        for (from, to, reason) in path {
+            let t = validate_transition(from, to, reason, Timestamp::new(5)).unwrap();
+            assert_eq!((t.from, t.to, t.reason), (from, to, reason));
+        }
+    }
+
+    #[test]
+    fn reason_must_match_the_transition() {
+        let err = validate_transition(
+            WalletState::Unverified,
+            WalletState::Verified,
+            TransitionReason::VerificationPassed,
+            Timestamp::new(5),
+        )
+        .unwrap_err();
+        assert_eq!(err.reason, TransitionReason::VerificationPassed);
+        assert!(!is_allowed(
+            WalletState::Voting,
+            WalletState::Verified,
+            TransitionReason::ChallengeRejected
+        ));
+    }
+
+    #[test]
+    fn revoked_is_terminal() {
+        assert!(ALLOWED_TRANSITIONS
+            .iter()
+            .all(|(from, _, _)| *from != WalletState::Revoked));
+    }
+
+    #[test]
+    fn reason_codes_are_unique() {
+        let mut codes: Vec<_> = ALLOWED_TRANSITIONS.iter().map(|t| t.2.as_str()).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), 13);
+    }
+}
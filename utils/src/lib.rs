@@ -3,6 +3,9 @@
 pub mod logging;
 pub mod stats;
 pub mod time;
+pub mod watchdog;
 
 pub use logging::init_tracing;
+pub use stats::{DetailType, Direction, SampleType, StatType, Stats};
 pub use time::format_duration;
+pub use watchdog::{SlowOperation, Watchdog, WatchdogConfig, WatchedOp};
@@ -1,45 +1,290 @@
 //! Statistics collection and reporting utilities.
+//!
+//! [`Stats`] is a registry of diagnostic counters keyed by
+//! [`StatType`] / [`DetailType`] / [`Direction`], plus rolling windows of
+//! sampled values. One registry is shared across the node's subsystems and
+//! read back through the `stats` RPC.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, VecDeque};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::Instant;
 
-/// A thread-safe counter collection for protocol statistics.
-pub struct StatsCounter {
-    counters: HashMap<&'static str, AtomicU64>,
+use serde::{Deserialize, Serialize};
+
+/// Values kept per sample type before the oldest is dropped.
+pub const DEFAULT_SAMPLE_CAPACITY: usize = 1_024;
+
+/// The subsystem a counter belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatType {
+    BlockProcessor,
+    Election,
+    Vote,
+    VoteCache,
+    Message,
+}
+
+/// What a counter counts within its subsystem.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetailType {
+    All,
+    // Block processing outcomes.
+    Accepted,
+    Gap,
+    GapSource,
+    Fork,
+    Rejected,
+    Duplicate,
+    Queued,
+    // Election lifecycle.
+    Started,
+    Confirmed,
+    Expired,
+    Overflow,
+    // Votes and messages.
+    Processed,
+    Invalid,
+    Unique,
+    // Caches.
+    Hit,
+    Miss,
+    Evicted,
+}
+
+/// Whether a counted item was received or sent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    In,
+    Out,
+}
+
+/// A series of sampled values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SampleType {
+    /// Time a block spent in the processing pipeline, in milliseconds.
+    BlockProcessTime,
+    /// Time from election start to confirmation, in milliseconds.
+    ElectionDuration,
+}
+
+type CounterKey = (StatType, DetailType, Direction);
+
+/// One counter as reported by [`Stats::counters`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CounterEntry {
+    #[serde(rename = "type")]
+    pub stat_type: StatType,
+    pub detail: DetailType,
+    pub dir: Direction,
+    pub value: u64,
+    /// Average per second since the counters were last cleared.
+    pub rate: f64,
 }
 
-impl StatsCounter {
-    pub fn new(names: &[&'static str]) -> Self {
-        let mut counters = HashMap::new();
-        for &name in names {
-            counters.insert(name, AtomicU64::new(0));
+/// Summary of one sample window as reported by [`Stats::samples`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SampleEntry {
+    pub sample: SampleType,
+    pub count: usize,
+    pub min: u64,
+    pub max: u64,
+    pub mean: u64,
+    /// Values in the window, oldest first.
+    pub values: Vec<u64>,
+}
+
+/// Counters with the time they have been accumulating for.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CountersSnapshot {
+    /// Seconds since the counters were created or last cleared.
+    pub elapsed_secs: u64,
+    pub entries: Vec<CounterEntry>,
+}
+
+/// Registry of diagnostic counters and samples.
+///
+/// Counters are created on first use, so only what actually happened is
+/// reported. All methods take `&self`; share the registry behind an `Arc`.
+#[derive(Debug)]
+pub struct Stats {
+    counters: RwLock<BTreeMap<CounterKey, AtomicU64>>,
+    samples: Mutex<BTreeMap<SampleType, VecDeque<u64>>>,
+    sample_capacity: usize,
+    cleared_at: Mutex<Instant>,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self::new(DEFAULT_SAMPLE_CAPACITY)
+    }
+}
+
+impl Stats {
+    /// Create an empty registry keeping up to `sample_capacity` values per
+    /// sample type.
+    pub fn new(sample_capacity: usize) -> Self {
+        Self {
+            counters: RwLock::new(BTreeMap::new()),
+            samples: Mutex::new(BTreeMap::new()),
+            sample_capacity: sample_capacity.max(1),
+            cleared_at: Mutex::new(Instant::now()),
         }
-        Self { counters }
     }
 
-    pub fn increment(&self, name: &str) {
-        if let Some(counter) = self.counters.get(name) {
-            counter.fetch_add(1, Ordering::Relaxed);
+    /// Count one inbound occurrence.
+    pub fn inc(&self, stat_type: StatType, detail: DetailType) {
+        self.add_dir(stat_type, detail, Direction::In, 1);
+    }
+
+    /// Count one occurrence in the given direction.
+    pub fn inc_dir(&self, stat_type: StatType, detail: DetailType, dir: Direction) {
+        self.add_dir(stat_type, detail, dir, 1);
+    }
+
+    /// Add `value` to a counter. The subsystem's [`DetailType::All`]
+    /// counter is bumped along with every detail.
+    pub fn add_dir(&self, stat_type: StatType, detail: DetailType, dir: Direction, value: u64) {
+        self.bump((stat_type, detail, dir), value);
+        if detail != DetailType::All {
+            self.bump((stat_type, DetailType::All, dir), value);
         }
     }
 
-    pub fn add(&self, name: &str, value: u64) {
-        if let Some(counter) = self.counters.get(name) {
+    fn bump(&self, key: CounterKey, value: u64) {
+        let counters = self.counters.read().unwrap_or_else(|e| e.into_inner());
+        if let Some(counter) = counters.get(&key) {
             counter.fetch_add(value, Ordering::Relaxed);
+            return;
         }
+        drop(counters);
+        self.counters
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(key)
+            .or_default()
+            .fetch_add(value, Ordering::Relaxed);
     }
 
-    pub fn get(&self, name: &str) -> u64 {
+    /// Current value of a counter.
+    pub fn count(&self, stat_type: StatType, detail: DetailType, dir: Direction) -> u64 {
         self.counters
-            .get(name)
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&(stat_type, detail, dir))
             .map(|c| c.load(Ordering::Relaxed))
             .unwrap_or(0)
     }
 
-    pub fn snapshot(&self) -> HashMap<&'static str, u64> {
-        self.counters
+    /// Record a sampled value, dropping the oldest once the window is full.
+    pub fn sample(&self, sample: SampleType, value: u64) {
+        let mut samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        let window = samples.entry(sample).or_default();
+        if window.len() >= self.sample_capacity {
+            window.pop_front();
+        }
+        window.push_back(value);
+    }
+
+    /// All counters in key order, with their average rates.
+    pub fn counters(&self) -> CountersSnapshot {
+        let elapsed = self
+            .cleared_at
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .elapsed();
+        let secs = elapsed.as_secs_f64().max(1.0);
+        let entries = self
+            .counters
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(&(stat_type, detail, dir), counter)| {
+                let value = counter.load(Ordering::Relaxed);
+                CounterEntry {
+                    stat_type,
+                    detail,
+                    dir,
+                    value,
+                    rate: value as f64 / secs,
+                }
+            })
+            .collect();
+        CountersSnapshot {
+            elapsed_secs: elapsed.as_secs(),
+            entries,
+        }
+    }
+
+    /// Every sample window with its summary.
+    pub fn samples(&self) -> Vec<SampleEntry> {
+        self.samples
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
             .iter()
-            .map(|(&k, v)| (k, v.load(Ordering::Relaxed)))
+            .filter(|(_, window)| !window.is_empty())
+            .map(|(&sample, window)| {
+                let values: Vec<u64> = window.iter().copied().collect();
+                let sum: u128 = values.iter().map(|&v| v as u128).sum();
+                SampleEntry {
+                    sample,
+                    count: values.len(),
+                    min: values.iter().copied().min().unwrap_or(0),
+                    max: values.iter().copied().max().unwrap_or(0),
+                    mean: (sum / values.len() as u128) as u64,
+                    values,
+                }
+            })
             .collect()
     }
+
+    /// Reset every counter and sample window and restart the rate clock.
+    pub fn clear(&self) {
+        self.counters
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+        self.samples
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+        *self.cleared_at.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counters_roll_up_and_samples_roll_over() {
+        let stats = Stats::new(2);
+        stats.inc(StatType::BlockProcessor, DetailType::Accepted);
+        stats.inc(StatType::BlockProcessor, DetailType::Fork);
+        stats.inc_dir(StatType::Message, DetailType::Unique, Direction::Out);
+        assert_eq!(
+            stats.count(StatType::BlockProcessor, DetailType::All, Direction::In),
+            2
+        );
+        assert_eq!(
+            stats.count(StatType::Message, DetailType::Unique, Direction::In),
+            0
+        );
+        assert_eq!(stats.counters().entries.len(), 5);
+
+        for value in [10, 20, 40] {
+            stats.sample(SampleType::BlockProcessTime, value);
+        }
+        let samples = stats.samples();
+        assert_eq!(samples[0].values, vec![20, 40]);
+        assert_eq!(samples[0].mean, 30);
+
+        stats.clear();
+        assert!(stats.counters().entries.is_empty());
+        assert!(stats.samples().is_empty());
+    }
 }
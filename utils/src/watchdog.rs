@@ -0,0 +1,192 @@
+//! Slow-operation watchdog.
+//!
+//! Hot paths time themselves against per-operation thresholds. An operation
+//! that runs over is logged as a structured `tracing` warning naming the
+//! subsystem, and kept in a short list of recent offenders that the
+//! `node_watchdog` RPC reports.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// Offenders kept for `node_watchdog` by default.
+pub const DEFAULT_WATCHDOG_HISTORY: usize = 64;
+
+/// The kind of operation being timed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchedOp {
+    /// One pass of the block processor loop, from dequeue to done.
+    BlockProcessorIteration,
+    /// Committing an LMDB write batch.
+    LmdbCommit,
+    /// Writing one framed message to a peer socket.
+    PeerWrite,
+    /// Holding a shared lock.
+    LockHold,
+}
+
+/// Per-operation limits, in milliseconds, above which a warning is logged.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchdogConfig {
+    pub block_processor_iteration_ms: u64,
+    pub lmdb_commit_ms: u64,
+    pub peer_write_ms: u64,
+    pub lock_hold_ms: u64,
+    /// Offenders kept for `node_watchdog`.
+    pub history: usize,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            block_processor_iteration_ms: 250,
+            lmdb_commit_ms: 100,
+            peer_write_ms: 500,
+            lock_hold_ms: 100,
+            history: DEFAULT_WATCHDOG_HISTORY,
+        }
+    }
+}
+
+impl WatchdogConfig {
+    fn threshold(&self, op: WatchedOp) -> Duration {
+        Duration::from_millis(match op {
+            WatchedOp::BlockProcessorIteration => self.block_processor_iteration_ms,
+            WatchedOp::LmdbCommit => self.lmdb_commit_ms,
+            WatchedOp::PeerWrite => self.peer_write_ms,
+            WatchedOp::LockHold => self.lock_hold_ms,
+        })
+    }
+}
+
+/// An operation that exceeded its threshold.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlowOperation {
+    pub op: WatchedOp,
+    /// What was being done, e.g. `"block_batch"` or `"trst_engine"`.
+    pub subsystem: String,
+    pub elapsed_ms: u64,
+    pub threshold_ms: u64,
+    /// Unix time in seconds when the operation finished.
+    pub at: u64,
+}
+
+/// Times operations against their thresholds and remembers recent offenders.
+#[derive(Debug)]
+pub struct Watchdog {
+    config: WatchdogConfig,
+    recent: Mutex<VecDeque<SlowOperation>>,
+}
+
+impl Default for Watchdog {
+    fn default() -> Self {
+        Self::new(WatchdogConfig::default())
+    }
+}
+
+impl Watchdog {
+    pub fn new(config: WatchdogConfig) -> Self {
+        Self {
+            recent: Mutex::new(VecDeque::with_capacity(config.history)),
+            config,
+        }
+    }
+
+    /// Start timing an operation; it is checked when the timer is dropped.
+    pub fn start(&self, op: WatchedOp, subsystem: &'static str) -> WatchdogTimer<'_> {
+        WatchdogTimer {
+            watchdog: self,
+            op,
+            subsystem,
+            started: Instant::now(),
+        }
+    }
+
+    /// Check a measured duration. Returns `true` if it was over threshold.
+    pub fn observe(&self, op: WatchedOp, subsystem: &str, elapsed: Duration) -> bool {
+        let threshold = self.config.threshold(op);
+        if elapsed <= threshold {
+            return false;
+        }
+        let elapsed_ms = elapsed.as_millis() as u64;
+        let threshold_ms = threshold.as_millis() as u64;
+        tracing::warn!(?op, subsystem, elapsed_ms, threshold_ms, "slow operation");
+        if self.config.history == 0 {
+            return true;
+        }
+        let at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let mut recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+        if recent.len() >= self.config.history {
+            recent.pop_front();
+        }
+        recent.push_back(SlowOperation {
+            op,
+            subsystem: subsystem.to_string(),
+            elapsed_ms,
+            threshold_ms,
+            at,
+        });
+        true
+    }
+
+    /// Recent offenders, newest first.
+    pub fn recent(&self) -> Vec<SlowOperation> {
+        self.recent
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .rev()
+            .cloned()
+            .collect()
+    }
+
+    /// The configured thresholds.
+    pub fn config(&self) -> &WatchdogConfig {
+        &self.config
+    }
+}
+
+/// Times one operation from creation until drop.
+pub struct WatchdogTimer<'a> {
+    watchdog: &'a Watchdog,
+    op: WatchedOp,
+    subsystem: &'static str,
+    started: Instant,
+}
+
+impl Drop for WatchdogTimer<'_> {
+    fn drop(&mut self) {
+        self.watchdog
+            .observe(self.op, self.subsystem, self.started.elapsed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_slow_operations_are_kept_newest_first() {
+        let watchdog = Watchdog::new(WatchdogConfig {
+            lmdb_commit_ms: 10,
+            history: 2,
+            ..WatchdogConfig::default()
+        });
+        assert!(!watchdog.observe(WatchedOp::LmdbCommit, "a", Duration::from_millis(10)));
+        for subsystem in ["b", "c", "d"] {
+            assert!(watchdog.observe(WatchedOp::LmdbCommit, subsystem, Duration::from_millis(11)));
+        }
+        let recent = watchdog.recent();
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].subsystem, "d");
+        assert_eq!(recent[1].subsystem, "c");
+        assert_eq!(recent[0].threshold_ms, 10);
+    }
+}
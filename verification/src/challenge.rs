@@ -1,6 +1,7 @@
 //! Challenge engine — any verified wallet can contest another's legitimacy.
 
-use burst_types::{Timestamp, WalletAddress};
+use crate::error::VerificationError;
+use burst_types::{ProtocolParams, Timestamp, WalletAddress};
 use serde::{Deserialize, Serialize};
 
 /// Maximum time (in seconds) a challenge can remain open before auto-expiring.
@@ -17,6 +18,67 @@ pub struct Challenge {
     pub initiated_at: Timestamp,
 }
 
+/// A wallet's record in past challenges, used by the anti-harassment rules.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChallengeHistory {
+    /// When the wallet last survived a challenge against it.
+    pub last_survived_at: Option<Timestamp>,
+    /// Challenges this wallet initiated that confirmed fraud.
+    pub challenges_won: u32,
+    /// Challenges this wallet initiated that were rejected.
+    pub challenges_lost: u32,
+}
+
+/// Stake a challenger must lock given how many challenges they have lost.
+///
+/// The first `challenge_loss_allowance` losses cost nothing extra; every loss
+/// beyond that raises the stake by `challenge_stake_escalation_bps`,
+/// compounding, so serial harassers price themselves out.
+pub fn required_challenge_stake(challenges_lost: u32, params: &ProtocolParams) -> u128 {
+    let excess = challenges_lost.saturating_sub(params.challenge_loss_allowance);
+    let factor = 10_000 + params.challenge_stake_escalation_bps as u128;
+    let mut stake = params.challenge_stake_amount;
+    for _ in 0..excess {
+        if stake == u128::MAX || factor == 10_000 {
+            break;
+        }
+        stake = stake.checked_mul(factor).map_or(u128::MAX, |s| s / 10_000);
+    }
+    stake
+}
+
+/// Check the anti-harassment rules for a new challenge: the target must not
+/// be inside its post-survival cooldown and the stake must cover the
+/// challenger's escalated minimum.
+pub fn check_challenge_allowed(
+    target: &WalletAddress,
+    target_history: &ChallengeHistory,
+    challenger_history: &ChallengeHistory,
+    stake: u128,
+    now: Timestamp,
+    params: &ProtocolParams,
+) -> Result<(), VerificationError> {
+    if let Some(survived_at) = target_history.last_survived_at {
+        if !survived_at.has_expired(params.challenge_target_cooldown_secs, now) {
+            return Err(VerificationError::ChallengeCooldown {
+                target: target.to_string(),
+                until: survived_at
+                    .as_secs()
+                    .saturating_add(params.challenge_target_cooldown_secs),
+            });
+        }
+    }
+
+    let needed = required_challenge_stake(challenger_history.challenges_lost, params);
+    if stake < needed {
+        return Err(VerificationError::InsufficientStake {
+            needed,
+            provided: stake,
+        });
+    }
+    Ok(())
+}
+
 pub struct ChallengeEngine;
 
 impl ChallengeEngine {
@@ -52,7 +114,7 @@ mod tests {
     use super::*;
 
     fn addr(s: &str) -> WalletAddress {
-        WalletAddress::new(&format!("brst_{s}"))
+        WalletAddress::new(format!("brst_{s}"))
     }
 
     #[test]
@@ -93,4 +155,54 @@ mod tests {
         let way_past = Timestamp::new(CHALLENGE_TIMEOUT_SECS * 10);
         assert!(engine.is_timed_out(&c, way_past));
     }
+
+    #[test]
+    fn stake_escalates_after_loss_allowance() {
+        let params = ProtocolParams {
+            challenge_stake_amount: 100,
+            challenge_loss_allowance: 2,
+            challenge_stake_escalation_bps: 5000,
+            ..ProtocolParams::default()
+        };
+        assert_eq!(required_challenge_stake(0, &params), 100);
+        assert_eq!(required_challenge_stake(2, &params), 100);
+        assert_eq!(required_challenge_stake(3, &params), 150);
+        assert_eq!(required_challenge_stake(4, &params), 225);
+        assert_eq!(required_challenge_stake(u32::MAX, &params), u128::MAX);
+    }
+
+    #[test]
+    fn surviving_a_challenge_starts_a_cooldown() {
+        let params = ProtocolParams {
+            challenge_target_cooldown_secs: 1000,
+            ..ProtocolParams::default()
+        };
+        let survived = ChallengeHistory {
+            last_survived_at: Some(Timestamp::new(5000)),
+            ..ChallengeHistory::default()
+        };
+        let stake = params.challenge_stake_amount;
+        let none = ChallengeHistory::default();
+
+        assert!(matches!(
+            check_challenge_allowed(
+                &addr("t"),
+                &survived,
+                &none,
+                stake,
+                Timestamp::new(5999),
+                &params
+            ),
+            Err(VerificationError::ChallengeCooldown { until: 6000, .. })
+        ));
+        assert!(check_challenge_allowed(
+            &addr("t"),
+            &survived,
+            &none,
+            stake,
+            Timestamp::new(6000),
+            &params
+        )
+        .is_ok());
+    }
 }
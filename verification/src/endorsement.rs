@@ -74,7 +74,7 @@ mod tests {
     use std::collections::HashSet;
 
     fn addr(s: &str) -> WalletAddress {
-        WalletAddress::new(&format!("brst_{s}"))
+        WalletAddress::new(format!("brst_{s}"))
     }
 
     fn new_state(target: &str) -> VerificationState {
@@ -86,6 +86,9 @@ mod tests {
             votes: Vec::new(),
             revote_count: 0,
             excluded_verifiers: HashSet::new(),
+            selection_candidates: Vec::new(),
+            round_deadline: None,
+            timed_out_rounds: 0,
             started_at: Timestamp::new(0),
         }
     }
@@ -0,0 +1,370 @@
+//! Endorsement graph — who endorsed whom — and collusion heuristics.
+//!
+//! Endorsements are the only way into verification, so the cheapest Sybil
+//! attacks are a ring of wallets vouching for each other or a single wallet
+//! vouching for fakes at a rate no real social circle produces. The graph
+//! detects both patterns and condenses them into a per-wallet risk score
+//! that challengers (and the challenge system) can act on.
+
+use burst_types::{Timestamp, WalletAddress};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Score contributed by the first endorsement ring a wallet sits on.
+const RING_SCORE_BPS: u32 = 5000;
+/// Score contributed by each further ring.
+const EXTRA_RING_SCORE_BPS: u32 = 1000;
+/// Score contributed by endorsing at an implausible rate.
+const BURST_SCORE_BPS: u32 = 4000;
+/// Score contributed by each endorser that endorses at an implausible rate.
+const BURST_ENDORSER_SCORE_BPS: u32 = 2000;
+/// Upper bound on the rings reported per wallet, to keep the search bounded
+/// on dense graphs.
+const MAX_REPORTED_RINGS: usize = 8;
+
+/// Thresholds for the collusion heuristics.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CollusionConfig {
+    /// Longest endorsement cycle still treated as a ring.
+    pub max_ring_len: usize,
+    /// Window (seconds) over which an endorser's rate is measured.
+    pub burst_window_secs: u64,
+    /// Endorsements within one window above which the rate is implausible.
+    pub burst_threshold: u32,
+    /// Risk score (basis points) at or above which a wallet is flagged.
+    pub flag_threshold_bps: u32,
+}
+
+impl Default for CollusionConfig {
+    fn default() -> Self {
+        Self {
+            max_ring_len: 4,
+            burst_window_secs: 24 * 3600,
+            burst_threshold: 10,
+            flag_threshold_bps: 5000,
+        }
+    }
+}
+
+/// A suspicious pattern found around a wallet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CollusionFlag {
+    /// The wallet sits on an endorsement cycle. Members are listed in
+    /// endorsement order, starting with the wallet itself.
+    EndorsementRing { members: Vec<WalletAddress> },
+    /// The wallet endorsed `count` wallets within `window_secs`.
+    EndorsementBurst { count: u32, window_secs: u64 },
+    /// The wallet was endorsed by a wallet that endorses at an implausible rate.
+    EndorsedByBurstEndorser { endorser: WalletAddress },
+}
+
+/// Risk score and supporting evidence for one wallet.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RiskAssessment {
+    pub wallet: WalletAddress,
+    /// 0 (no suspicion) to 10 000 (maximum).
+    pub score_bps: u32,
+    pub flags: Vec<CollusionFlag>,
+}
+
+/// Directed graph of endorsements (endorser → endorsed wallet).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EndorsementGraph {
+    /// Endorser -> wallets it endorsed, with when.
+    endorsed: HashMap<WalletAddress, Vec<(WalletAddress, Timestamp)>>,
+    /// Endorsed wallet -> its endorsers.
+    endorsers: HashMap<WalletAddress, Vec<WalletAddress>>,
+    /// Wallets already reported by [`Self::record`], so each is flagged once.
+    flagged: HashSet<WalletAddress>,
+    config: CollusionConfig,
+}
+
+impl EndorsementGraph {
+    pub fn new(config: CollusionConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    pub fn config(&self) -> &CollusionConfig {
+        &self.config
+    }
+
+    /// Record that `endorser` endorsed `target` at `at`.
+    ///
+    /// Returns the assessments of the endorser and target if this edge just
+    /// pushed either of them over the flag threshold. Repeated endorsements of
+    /// the same wallet are ignored.
+    pub fn record(
+        &mut self,
+        endorser: &WalletAddress,
+        target: &WalletAddress,
+        at: Timestamp,
+    ) -> Vec<RiskAssessment> {
+        let edges = self.endorsed.entry(endorser.clone()).or_default();
+        if edges.iter().any(|(t, _)| t == target) {
+            return Vec::new();
+        }
+        edges.push((target.clone(), at));
+        self.endorsers
+            .entry(target.clone())
+            .or_default()
+            .push(endorser.clone());
+
+        let mut newly_flagged = Vec::new();
+        for wallet in [endorser, target] {
+            if self.flagged.contains(wallet) {
+                continue;
+            }
+            let assessment = self.assess(wallet);
+            if assessment.score_bps >= self.config.flag_threshold_bps {
+                self.flagged.insert(wallet.clone());
+                newly_flagged.push(assessment);
+            }
+        }
+        newly_flagged
+    }
+
+    /// Wallets that endorsed `target`.
+    pub fn endorsers_of(&self, target: &WalletAddress) -> &[WalletAddress] {
+        self.endorsers.get(target).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Wallets `endorser` has endorsed.
+    pub fn endorsed_by(&self, endorser: &WalletAddress) -> Vec<WalletAddress> {
+        self.endorsed
+            .get(endorser)
+            .map(|edges| edges.iter().map(|(t, _)| t.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Total number of endorsement edges.
+    pub fn edge_count(&self) -> usize {
+        self.endorsed.values().map(Vec::len).sum()
+    }
+
+    /// Endorsement cycles of at most `max_ring_len` wallets passing through
+    /// `wallet`, each starting with `wallet`.
+    pub fn rings(&self, wallet: &WalletAddress) -> Vec<Vec<WalletAddress>> {
+        let mut rings = Vec::new();
+        let mut path = vec![wallet.clone()];
+        self.extend_ring(wallet, &mut path, &mut rings);
+        rings
+    }
+
+    fn extend_ring(
+        &self,
+        start: &WalletAddress,
+        path: &mut Vec<WalletAddress>,
+        rings: &mut Vec<Vec<WalletAddress>>,
+    ) {
+        let Some(edges) = path.last().and_then(|last| self.endorsed.get(last)) else {
+            return;
+        };
+        for (next, _) in edges {
+            if rings.len() >= MAX_REPORTED_RINGS {
+                return;
+            }
+            if next == start {
+                if path.len() >= 2 {
+                    rings.push(path.clone());
+                }
+            } else if path.len() < self.config.max_ring_len && !path.contains(next) {
+                path.push(next.clone());
+                self.extend_ring(start, path, rings);
+                path.pop();
+            }
+        }
+    }
+
+    /// Largest number of endorsements `endorser` made within any window of
+    /// `burst_window_secs`.
+    pub fn peak_endorsement_rate(&self, endorser: &WalletAddress) -> u32 {
+        let Some(edges) = self.endorsed.get(endorser) else {
+            return 0;
+        };
+        let mut times: Vec<u64> = edges.iter().map(|(_, at)| at.as_secs()).collect();
+        times.sort_unstable();
+
+        let mut peak = 0;
+        let mut window_start = 0;
+        for (i, &t) in times.iter().enumerate() {
+            while t.saturating_sub(times[window_start]) >= self.config.burst_window_secs {
+                window_start += 1;
+            }
+            peak = peak.max(i - window_start + 1);
+        }
+        peak as u32
+    }
+
+    fn is_bursting(&self, endorser: &WalletAddress) -> bool {
+        self.peak_endorsement_rate(endorser) > self.config.burst_threshold
+    }
+
+    /// Score `wallet` against every heuristic.
+    pub fn assess(&self, wallet: &WalletAddress) -> RiskAssessment {
+        let mut flags = Vec::new();
+        let mut score: u32 = 0;
+
+        let rings = self.rings(wallet);
+        if !rings.is_empty() {
+            score += RING_SCORE_BPS + EXTRA_RING_SCORE_BPS * (rings.len() as u32 - 1);
+        }
+        flags.extend(
+            rings
+                .into_iter()
+                .map(|members| CollusionFlag::EndorsementRing { members }),
+        );
+
+        let peak = self.peak_endorsement_rate(wallet);
+        if peak > self.config.burst_threshold {
+            score += BURST_SCORE_BPS;
+            flags.push(CollusionFlag::EndorsementBurst {
+                count: peak,
+                window_secs: self.config.burst_window_secs,
+            });
+        }
+
+        for endorser in self.endorsers_of(wallet) {
+            if self.is_bursting(endorser) {
+                score += BURST_ENDORSER_SCORE_BPS;
+                flags.push(CollusionFlag::EndorsedByBurstEndorser {
+                    endorser: endorser.clone(),
+                });
+            }
+        }
+
+        RiskAssessment {
+            wallet: wallet.clone(),
+            score_bps: score.min(10_000),
+            flags,
+        }
+    }
+
+    /// Every wallet at or above the flag threshold, highest score first.
+    pub fn flagged(&self) -> Vec<RiskAssessment> {
+        let wallets: HashSet<&WalletAddress> =
+            self.endorsed.keys().chain(self.endorsers.keys()).collect();
+        let mut flagged: Vec<RiskAssessment> = wallets
+            .into_iter()
+            .map(|w| self.assess(w))
+            .filter(|a| a.score_bps >= self.config.flag_threshold_bps)
+            .collect();
+        flagged.sort_by(|a, b| {
+            b.score_bps
+                .cmp(&a.score_bps)
+                .then_with(|| a.wallet.as_str().cmp(b.wallet.as_str()))
+        });
+        flagged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(s: &str) -> WalletAddress {
+        WalletAddress::new(format!("brst_{s}"))
+    }
+
+    #[test]
+    fn mutual_endorsement_is_a_ring() {
+        let mut graph = EndorsementGraph::default();
+        let (a, b) = (addr("a"), addr("b"));
+
+        assert!(graph.record(&a, &b, Timestamp::new(100)).is_empty());
+        let flagged = graph.record(&b, &a, Timestamp::new(200));
+
+        assert_eq!(flagged.len(), 2);
+        assert_eq!(graph.rings(&a), vec![vec![a.clone(), b.clone()]]);
+        assert_eq!(graph.assess(&b).score_bps, RING_SCORE_BPS);
+
+        // Already flagged wallets are not reported again.
+        let c = addr("c");
+        assert!(graph.record(&a, &c, Timestamp::new(300)).is_empty());
+    }
+
+    #[test]
+    fn rings_longer_than_the_limit_are_ignored() {
+        let mut graph = EndorsementGraph::new(CollusionConfig {
+            max_ring_len: 3,
+            ..CollusionConfig::default()
+        });
+        let wallets: Vec<WalletAddress> = ["a", "b", "c", "d"].iter().map(|s| addr(s)).collect();
+        for i in 0..wallets.len() {
+            graph.record(
+                &wallets[i],
+                &wallets[(i + 1) % wallets.len()],
+                Timestamp::new(i as u64),
+            );
+        }
+
+        assert!(graph.rings(&wallets[0]).is_empty());
+        assert_eq!(graph.assess(&wallets[0]).score_bps, 0);
+        assert_eq!(graph.edge_count(), 4);
+    }
+
+    #[test]
+    fn endorsement_burst_flags_endorser_and_endorsed() {
+        let mut graph = EndorsementGraph::new(CollusionConfig {
+            burst_threshold: 3,
+            ..CollusionConfig::default()
+        });
+        let spammer = addr("spammer");
+        for i in 0..4u64 {
+            graph.record(
+                &spammer,
+                &addr(&format!("fake{i}")),
+                Timestamp::new(1000 + i),
+            );
+        }
+
+        assert_eq!(graph.peak_endorsement_rate(&spammer), 4);
+        let assessment = graph.assess(&spammer);
+        assert_eq!(assessment.score_bps, BURST_SCORE_BPS);
+        assert!(matches!(
+            assessment.flags[0],
+            CollusionFlag::EndorsementBurst { count: 4, .. }
+        ));
+        assert_eq!(
+            graph.assess(&addr("fake0")).flags,
+            vec![CollusionFlag::EndorsedByBurstEndorser {
+                endorser: spammer.clone()
+            }]
+        );
+    }
+
+    #[test]
+    fn endorsements_spread_over_time_are_not_a_burst() {
+        let mut graph = EndorsementGraph::new(CollusionConfig {
+            burst_threshold: 2,
+            burst_window_secs: 100,
+            ..CollusionConfig::default()
+        });
+        let endorser = addr("patient");
+        for i in 0..5u64 {
+            graph.record(&endorser, &addr(&format!("w{i}")), Timestamp::new(i * 100));
+        }
+
+        assert_eq!(graph.peak_endorsement_rate(&endorser), 1);
+        assert!(graph.flagged().is_empty());
+    }
+
+    #[test]
+    fn flagged_lists_highest_risk_first() {
+        let mut graph = EndorsementGraph::default();
+        let (a, b, c) = (addr("a"), addr("b"), addr("c"));
+        graph.record(&a, &b, Timestamp::new(1));
+        graph.record(&b, &a, Timestamp::new(2));
+        graph.record(&b, &c, Timestamp::new(3));
+        graph.record(&c, &b, Timestamp::new(4));
+
+        let flagged = graph.flagged();
+        assert_eq!(flagged.len(), 3);
+        // b sits on both rings.
+        assert_eq!(flagged[0].wallet, b);
+        assert_eq!(flagged[0].score_bps, RING_SCORE_BPS + EXTRA_RING_SCORE_BPS);
+    }
+}
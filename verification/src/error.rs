@@ -35,6 +35,9 @@ pub enum VerificationError {
     #[error("insufficient stake: needed {needed}, provided {provided}")]
     InsufficientStake { needed: u128, provided: u128 },
 
+    #[error("wallet {target} survived a challenge and cannot be challenged again until {until}")]
+    ChallengeCooldown { target: String, until: u64 },
+
     #[error("bootstrap phase has ended — normal verification rules apply")]
     BootstrapPhaseEnded,
 
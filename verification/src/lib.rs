@@ -5,23 +5,29 @@
 //! 2. **Verification**: Randomly selected verifiers vote (Legitimate / Illegitimate / Neither).
 //!
 //! Plus the post-verification **challenge** mechanism: any verified wallet can
-//! challenge another at any time by staking BRN.
+//! challenge another at any time by staking BRN. The **endorsement graph**
+//! scores wallets for collusion patterns so challengers know where to look.
 //!
 //! The verification *method* is modular — the protocol specifies *that* verification
 //! must happen, not *how*. Different methods can be plugged in.
 
 pub mod challenge;
 pub mod endorsement;
+pub mod endorsement_graph;
 pub mod error;
 pub mod method;
 pub mod orchestrator;
 pub mod outcomes;
+pub mod probation;
 pub mod state;
 pub mod verifier_selection;
 pub mod voting;
 
-pub use challenge::ChallengeEngine;
+pub use challenge::{
+    check_challenge_allowed, required_challenge_stake, ChallengeEngine, ChallengeHistory,
+};
 pub use endorsement::EndorsementEngine;
+pub use endorsement_graph::{CollusionConfig, CollusionFlag, EndorsementGraph, RiskAssessment};
 pub use error::VerificationError;
 pub use method::VerificationMethod;
 pub use orchestrator::{OrchestratorSnapshot, VerificationEvent, VerificationOrchestrator};
@@ -30,6 +36,7 @@ pub use outcomes::{
     ChallengeResult, EndorserOutcome, VerificationOutcomeEvent, VerificationResult,
     VerifierOutcome,
 };
+pub use probation::{clean_history, probation_tier, ProbationTier, SEND_CAP_WINDOW_SECS};
 pub use state::VerificationState;
-pub use verifier_selection::VerifierSelector;
+pub use verifier_selection::{draw_verifiers, pool_snapshot_hash, VerifierSelector};
 pub use voting::{NeitherPenaltyAction, NeitherVoteTracker, VerificationVoting, Vote};
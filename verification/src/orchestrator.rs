@@ -1,14 +1,18 @@
 //! Verification orchestrator — connects endorsement, selection, voting, and outcomes
 //! into a single end-to-end verification workflow.
 
-use crate::challenge::{Challenge, ChallengeEngine, CHALLENGE_TIMEOUT_SECS};
+use crate::challenge::{
+    check_challenge_allowed, Challenge, ChallengeEngine, ChallengeHistory, CHALLENGE_TIMEOUT_SECS,
+};
 use crate::endorsement::EndorsementEngine;
+use crate::endorsement_graph::{CollusionFlag, EndorsementGraph};
 use crate::error::VerificationError;
 use crate::outcomes::{
     compute_challenge_outcome, compute_verification_outcomes, ChallengeOutcomeEvent,
     ChallengeResult, VerificationOutcomeEvent, VerificationResult,
 };
 use crate::state::{VerificationPhase, VerificationState};
+use crate::verifier_selection::draw_verifiers;
 use crate::voting::{NeitherVoteTracker, VerificationVoting, Vote, VotingOutcome};
 use burst_types::{ProtocolParams, Timestamp, WalletAddress};
 use serde::{Deserialize, Serialize};
@@ -43,6 +47,27 @@ pub enum VerificationEvent {
         reason: String,
         cooldown_until: u64,
     },
+    /// Selected verifiers missed the round deadline and were dropped; the
+    /// node should draw replacements via `reselect_verifiers`.
+    VerifiersTimedOut {
+        wallet: WalletAddress,
+        unresponsive: Vec<WalletAddress>,
+    },
+    /// The endorsement graph flagged a wallet as a likely collusion
+    /// participant — a candidate for a challenge.
+    CollusionFlagged {
+        wallet: WalletAddress,
+        score_bps: u32,
+        flags: Vec<CollusionFlag>,
+    },
+    /// Verification abandoned after too many timed-out rounds — the wallet is
+    /// back to Unverified and the stakes of verifiers who did vote are refunded.
+    VerificationExpired {
+        wallet: WalletAddress,
+        refunds: Vec<(WalletAddress, u128)>,
+        /// The abandoned state, as it stood when the request expired.
+        state: Box<VerificationState>,
+    },
 }
 
 /// The orchestrator ties together all verification subsystems.
@@ -51,10 +76,13 @@ pub struct VerificationOrchestrator {
     pub voting: VerificationVoting,
     pub challenges: ChallengeEngine,
     pub neither_tracker: NeitherVoteTracker,
+    pub endorsement_graph: EndorsementGraph,
     states: HashMap<WalletAddress, VerificationState>,
     active_challenges: HashMap<WalletAddress, Challenge>,
     /// Verifiers under penalty cooldown: address -> cooldown_until timestamp (secs).
     penalized_verifiers: HashMap<WalletAddress, u64>,
+    /// Past challenge outcomes per wallet, for the anti-harassment rules.
+    challenge_history: HashMap<WalletAddress, ChallengeHistory>,
     /// Pending events for the node to process.
     pending_events: Vec<VerificationEvent>,
 }
@@ -66,9 +94,11 @@ impl Default for VerificationOrchestrator {
             voting: VerificationVoting,
             challenges: ChallengeEngine,
             neither_tracker: NeitherVoteTracker::new(5000),
+            endorsement_graph: EndorsementGraph::default(),
             states: HashMap::new(),
             active_challenges: HashMap::new(),
             penalized_verifiers: HashMap::new(),
+            challenge_history: HashMap::new(),
             pending_events: Vec::new(),
         }
     }
@@ -99,20 +129,30 @@ impl VerificationOrchestrator {
                 votes: Vec::new(),
                 revote_count: 0,
                 excluded_verifiers: std::collections::HashSet::new(),
+                selection_candidates: Vec::new(),
+                round_deadline: None,
+                timed_out_rounds: 0,
                 started_at: Timestamp::now(),
             });
 
-        self.endorsement.submit_endorsement(
-            state,
-            endorser.clone(),
-            brn_burned,
-            Timestamp::now(),
-        )?;
+        let now = Timestamp::now();
+        self.endorsement
+            .submit_endorsement(state, endorser.clone(), brn_burned, now)?;
 
-        if self
+        let reached_threshold = self
             .endorsement
-            .check_threshold(state, params.endorsement_threshold)
-        {
+            .check_threshold(state, params.endorsement_threshold);
+
+        for assessment in self.endorsement_graph.record(endorser, wallet, now) {
+            self.pending_events
+                .push(VerificationEvent::CollusionFlagged {
+                    wallet: assessment.wallet,
+                    score_bps: assessment.score_bps,
+                    flags: assessment.flags,
+                });
+        }
+
+        if reached_threshold {
             self.pending_events
                 .push(VerificationEvent::EndorsementComplete {
                     wallet: wallet.clone(),
@@ -149,32 +189,21 @@ impl VerificationOrchestrator {
             }
         }
 
-        let penalized = &self.penalized_verifiers;
-        let filtered: Vec<&WalletAddress> = eligible_verifiers
-            .iter()
-            .filter(|w| !state.excluded_verifiers.contains(w))
-            .filter(|w| penalized.get(w).is_none_or(|&until| until <= now_secs))
-            .collect();
-
-        let count = params.num_verifiers as usize;
-        let mut scored: Vec<(usize, [u8; 32])> = filtered
-            .iter()
-            .enumerate()
-            .map(|(i, addr)| {
-                let hash = burst_crypto::blake2b_256_multi(&[randomness, addr.as_str().as_bytes()]);
-                (i, hash)
-            })
-            .collect();
-
-        scored.sort_by_key(|a| a.1);
-        scored.truncate(count);
-
-        let selected: Vec<WalletAddress> =
-            scored.iter().map(|(i, _)| filtered[*i].clone()).collect();
+        let candidates = selection_candidates(
+            state,
+            eligible_verifiers,
+            &self.penalized_verifiers,
+            now_secs,
+        );
+        let selected = draw_verifiers(randomness, &candidates, params.num_verifiers as usize);
 
+        state.selection_candidates = candidates;
         state.selected_verifiers = selected.clone();
         state.votes.clear();
         state.phase = VerificationPhase::Voting;
+        state.round_deadline = Some(Timestamp::new(
+            now_secs.saturating_add(params.verification_round_timeout_secs),
+        ));
 
         self.pending_events
             .push(VerificationEvent::VerifiersSelected {
@@ -185,6 +214,156 @@ impl VerificationOrchestrator {
         Ok(selected)
     }
 
+    /// Fill the seats vacated by verifiers that timed out, keeping the votes
+    /// already cast this round.
+    ///
+    /// Replacements are drawn from the eligible pool minus previously excluded,
+    /// currently seated and penalized verifiers. Returns only the newly drawn
+    /// verifiers and starts a fresh round deadline.
+    pub fn reselect_verifiers(
+        &mut self,
+        wallet: &WalletAddress,
+        eligible_verifiers: &[WalletAddress],
+        randomness: &[u8; 32],
+        params: &ProtocolParams,
+    ) -> Result<Vec<WalletAddress>, VerificationError> {
+        let now_secs = Timestamp::now().as_secs();
+        self.cleanup_expired_penalties(now_secs);
+
+        let state = self.states.get_mut(wallet).ok_or_else(|| {
+            VerificationError::Other(format!("no verification state for {wallet}"))
+        })?;
+
+        if state.phase != VerificationPhase::Voting {
+            return Err(VerificationError::Other(format!(
+                "wallet {wallet} is in phase {:?}, cannot reselect verifiers",
+                state.phase
+            )));
+        }
+
+        let candidates: Vec<WalletAddress> = selection_candidates(
+            state,
+            eligible_verifiers,
+            &self.penalized_verifiers,
+            now_secs,
+        )
+        .into_iter()
+        .filter(|w| !state.selected_verifiers.contains(w))
+        .collect();
+        let open_seats =
+            (params.num_verifiers as usize).saturating_sub(state.selected_verifiers.len());
+        let replacements = draw_verifiers(randomness, &candidates, open_seats);
+
+        state.selection_candidates = candidates;
+        state
+            .selected_verifiers
+            .extend(replacements.iter().cloned());
+        state.round_deadline = Some(Timestamp::new(
+            now_secs.saturating_add(params.verification_round_timeout_secs),
+        ));
+
+        self.pending_events
+            .push(VerificationEvent::VerifiersSelected {
+                wallet: wallet.clone(),
+                verifiers: replacements.clone(),
+            });
+
+        Ok(replacements)
+    }
+
+    /// Enforce voting deadlines on in-flight verifications.
+    ///
+    /// Selected verifiers that have not voted by the round deadline are
+    /// penalized (excluded from selection for `neither_penalty_cooldown_secs`)
+    /// and dropped from the round, emitting `VerifiersTimedOut` so the node
+    /// can draw replacements. Once `max_verification_rounds` rounds have timed
+    /// out, or the whole session exceeds `verification_timeout_secs`, the
+    /// request is abandoned with `VerificationExpired`. Challenge re-votes are
+    /// left to [`Self::cleanup_expired_challenges`]. Should be called
+    /// periodically from the node's tick loop.
+    pub fn check_timeouts(
+        &mut self,
+        now: Timestamp,
+        params: &ProtocolParams,
+    ) -> Vec<VerificationEvent> {
+        let now_secs = now.as_secs();
+        let mut events = Vec::new();
+        let mut expired = Vec::new();
+
+        for (wallet, state) in self.states.iter_mut() {
+            if state.phase != VerificationPhase::Voting
+                || self.active_challenges.contains_key(wallet)
+            {
+                continue;
+            }
+
+            let session_over = state
+                .started_at
+                .has_expired(params.verification_timeout_secs, now);
+            let round_over = state
+                .round_deadline
+                .is_some_and(|deadline| now_secs >= deadline.as_secs());
+            if !round_over {
+                if session_over {
+                    expired.push(wallet.clone());
+                }
+                continue;
+            }
+
+            let (responsive, unresponsive): (Vec<WalletAddress>, Vec<WalletAddress>) = state
+                .selected_verifiers
+                .iter()
+                .cloned()
+                .partition(|v| state.votes.iter().any(|vote| &vote.verifier == v));
+
+            let cooldown_until = now_secs.saturating_add(params.neither_penalty_cooldown_secs);
+            for verifier in &unresponsive {
+                self.penalized_verifiers
+                    .insert(verifier.clone(), cooldown_until);
+                events.push(VerificationEvent::VerifierPenalized {
+                    verifier: verifier.clone(),
+                    reason: "vote_timeout".into(),
+                    cooldown_until,
+                });
+            }
+
+            state
+                .excluded_verifiers
+                .extend(unresponsive.iter().cloned());
+            state.selected_verifiers = responsive;
+            state.round_deadline = None;
+            state.timed_out_rounds += 1;
+
+            if session_over || state.timed_out_rounds >= params.max_verification_rounds {
+                expired.push(wallet.clone());
+            } else {
+                events.push(VerificationEvent::VerifiersTimedOut {
+                    wallet: wallet.clone(),
+                    unresponsive,
+                });
+            }
+        }
+
+        for wallet in expired {
+            let Some(state) = self.states.remove(&wallet) else {
+                continue;
+            };
+            let refunds = state
+                .votes
+                .iter()
+                .filter(|v| v.stake_amount > 0)
+                .map(|v| (v.verifier.clone(), v.stake_amount))
+                .collect();
+            events.push(VerificationEvent::VerificationExpired {
+                wallet,
+                refunds,
+                state: Box::new(state),
+            });
+        }
+
+        events
+    }
+
     /// Process a verification vote.
     ///
     /// For regular verification: auto-tallies when all verifiers have voted and
@@ -297,12 +476,15 @@ impl VerificationOrchestrator {
             ));
         }
 
-        if stake < params.challenge_stake_amount {
-            return Err(VerificationError::InsufficientStake {
-                needed: params.challenge_stake_amount,
-                provided: stake,
-            });
-        }
+        let now = Timestamp::now();
+        check_challenge_allowed(
+            target,
+            &self.challenge_history(target),
+            &self.challenge_history(challenger),
+            stake,
+            now,
+            params,
+        )?;
 
         let state = self.states.get_mut(target).ok_or_else(|| {
             VerificationError::Other(format!("no verification state for {target}"))
@@ -314,9 +496,9 @@ impl VerificationOrchestrator {
             )));
         }
 
-        let challenge =
-            self.challenges
-                .initiate(challenger.clone(), target.clone(), stake, Timestamp::now());
+        let challenge = self
+            .challenges
+            .initiate(challenger.clone(), target.clone(), stake, now);
 
         self.active_challenges.insert(target.clone(), challenge);
         state.phase = VerificationPhase::Challenged;
@@ -385,8 +567,17 @@ impl VerificationOrchestrator {
                 .push(VerificationEvent::WalletUnverified {
                     wallet: target.clone(),
                 });
+            self.challenge_history
+                .entry(challenge.challenger.clone())
+                .or_default()
+                .challenges_won += 1;
         } else {
             state.phase = VerificationPhase::Verified;
+            self.record_survived(target, Timestamp::now());
+            self.challenge_history
+                .entry(challenge.challenger.clone())
+                .or_default()
+                .challenges_lost += 1;
         }
 
         let event = VerificationEvent::ChallengeResolved {
@@ -428,6 +619,9 @@ impl VerificationOrchestrator {
                 votes: Vec::new(),
                 revote_count: 0,
                 excluded_verifiers: std::collections::HashSet::new(),
+                selection_candidates: Vec::new(),
+                round_deadline: None,
+                timed_out_rounds: 0,
                 started_at: Timestamp::now(),
             });
 
@@ -470,6 +664,9 @@ impl VerificationOrchestrator {
                 votes: Vec::new(),
                 revote_count: 0,
                 excluded_verifiers: std::collections::HashSet::new(),
+                selection_candidates: Vec::new(),
+                round_deadline: None,
+                timed_out_rounds: 0,
                 started_at: Timestamp::now(),
             });
 
@@ -523,6 +720,9 @@ impl VerificationOrchestrator {
             if let Some(state) = self.states.get_mut(&target) {
                 state.phase = VerificationPhase::Verified;
             }
+            // Verifiers not voting isn't the challenger's fault, so an expiry
+            // protects the target without counting as a loss.
+            self.record_survived(&target, now);
 
             let outcome_event = compute_challenge_outcome(
                 &target,
@@ -542,6 +742,31 @@ impl VerificationOrchestrator {
         events
     }
 
+    /// Whether `target` is under challenge and every selected verifier has
+    /// voted, i.e. the challenge is ready for [`Self::resolve_challenge`].
+    pub fn challenge_votes_complete(&self, target: &WalletAddress) -> bool {
+        self.active_challenges.contains_key(target)
+            && self.states.get(target).is_some_and(|state| {
+                !state.selected_verifiers.is_empty()
+                    && state.votes.len() >= state.selected_verifiers.len()
+            })
+    }
+
+    /// A wallet's past challenge outcomes (empty if it never took part in one).
+    pub fn challenge_history(&self, wallet: &WalletAddress) -> ChallengeHistory {
+        self.challenge_history
+            .get(wallet)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn record_survived(&mut self, target: &WalletAddress, now: Timestamp) {
+        self.challenge_history
+            .entry(target.clone())
+            .or_default()
+            .last_survived_at = Some(now);
+    }
+
     /// Get the verification state of a wallet.
     pub fn get_state(&self, wallet: &WalletAddress) -> Option<&VerificationState> {
         self.states.get(wallet)
@@ -563,6 +788,8 @@ impl VerificationOrchestrator {
             states: self.states.clone(),
             active_challenges: self.active_challenges.clone(),
             penalized_verifiers: self.penalized_verifiers.clone(),
+            endorsement_graph: self.endorsement_graph.clone(),
+            challenge_history: self.challenge_history.clone(),
         }
     }
 
@@ -573,9 +800,11 @@ impl VerificationOrchestrator {
             voting: VerificationVoting,
             challenges: ChallengeEngine,
             neither_tracker: NeitherVoteTracker::new(5000),
+            endorsement_graph: snapshot.endorsement_graph,
             states: snapshot.states,
             active_challenges: snapshot.active_challenges,
             penalized_verifiers: snapshot.penalized_verifiers,
+            challenge_history: snapshot.challenge_history,
             pending_events: Vec::new(),
         }
     }
@@ -587,6 +816,26 @@ pub struct OrchestratorSnapshot {
     pub states: HashMap<WalletAddress, VerificationState>,
     pub active_challenges: HashMap<WalletAddress, Challenge>,
     pub penalized_verifiers: HashMap<WalletAddress, u64>,
+    #[serde(default)]
+    pub endorsement_graph: EndorsementGraph,
+    #[serde(default)]
+    pub challenge_history: HashMap<WalletAddress, ChallengeHistory>,
+}
+
+/// Eligible verifiers a round may draw from: everyone not excluded by an
+/// earlier round and not under penalty cooldown.
+fn selection_candidates(
+    state: &VerificationState,
+    eligible_verifiers: &[WalletAddress],
+    penalized: &HashMap<WalletAddress, u64>,
+    now_secs: u64,
+) -> Vec<WalletAddress> {
+    eligible_verifiers
+        .iter()
+        .filter(|w| !state.excluded_verifiers.contains(w))
+        .filter(|w| penalized.get(w).is_none_or(|&until| until <= now_secs))
+        .cloned()
+        .collect()
 }
 
 fn build_verification_outcomes(
@@ -622,10 +871,7 @@ mod tests {
     use super::*;
 
     fn test_addr(s: &str) -> WalletAddress {
-        WalletAddress::new(format!(
-            "brst_{}",
-            s.repeat(60 / s.len() + 1)[..60].to_string()
-        ))
+        WalletAddress::new(format!("brst_{}", &s.repeat(60 / s.len() + 1)[..60]))
     }
 
     fn test_params() -> ProtocolParams {
@@ -914,6 +1160,52 @@ mod tests {
             .any(|e| matches!(e, VerificationEvent::WalletUnverified { .. })));
     }
 
+    #[test]
+    fn survived_target_cannot_be_rechallenged_during_cooldown() {
+        let mut orch = VerificationOrchestrator::new();
+        let params = ProtocolParams {
+            challenge_loss_allowance: 0,
+            ..test_params()
+        };
+        let wallet = test_addr("target");
+        verify_wallet(&mut orch, &wallet, &params);
+
+        let challenger = test_addr("challenger");
+        orch.initiate_challenge(&wallet, &challenger, true, 500, &params)
+            .unwrap();
+        let verifiers: Vec<WalletAddress> =
+            (10..=15).map(|i| test_addr(&format!("rv{i}"))).collect();
+        let selected = orch
+            .select_verifiers(&wallet, &verifiers, &[88u8; 32], &params)
+            .unwrap();
+        for v in &selected {
+            assert!(!orch.challenge_votes_complete(&wallet));
+            orch.process_vote(&wallet, v, Vote::Legitimate, &params)
+                .unwrap();
+        }
+        assert!(orch.challenge_votes_complete(&wallet));
+        orch.resolve_challenge(&wallet, &params).unwrap();
+
+        assert!(orch.challenge_history(&wallet).last_survived_at.is_some());
+        assert_eq!(orch.challenge_history(&challenger).challenges_lost, 1);
+
+        let other = test_addr("other");
+        assert!(matches!(
+            orch.initiate_challenge(&wallet, &other, true, 500, &params),
+            Err(VerificationError::ChallengeCooldown { .. })
+        ));
+
+        // The losing challenger now owes a doubled stake elsewhere.
+        let second = test_addr("second");
+        verify_wallet(&mut orch, &second, &params);
+        assert!(matches!(
+            orch.initiate_challenge(&second, &challenger, true, 500, &params),
+            Err(VerificationError::InsufficientStake { needed: 1000, .. })
+        ));
+        orch.initiate_challenge(&second, &challenger, true, 1000, &params)
+            .unwrap();
+    }
+
     #[test]
     fn challenge_non_verified_wallet_errors() {
         let mut orch = VerificationOrchestrator::new();
@@ -1319,4 +1611,149 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn mutual_endorsements_emit_collusion_flags() {
+        let mut orch = VerificationOrchestrator::new();
+        let params = test_params();
+        let (a, b) = (test_addr("ringa"), test_addr("ringb"));
+
+        orch.process_endorsement(&b, &a, 1000, &params).unwrap();
+        orch.process_endorsement(&a, &b, 1000, &params).unwrap();
+
+        let flagged: Vec<WalletAddress> = orch
+            .drain_events()
+            .into_iter()
+            .filter_map(|e| match e {
+                VerificationEvent::CollusionFlagged { wallet, .. } => Some(wallet),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(flagged.len(), 2);
+        assert!(flagged.contains(&a) && flagged.contains(&b));
+
+        // The graph survives a snapshot round-trip.
+        let restored = VerificationOrchestrator::restore(orch.snapshot());
+        assert_eq!(restored.endorsement_graph.endorsers_of(&a), [b]);
+    }
+
+    // ── Round timeouts ──────────────────────────────────────────────────
+
+    #[test]
+    fn unresponsive_verifiers_are_penalized_and_replaced() {
+        let mut orch = VerificationOrchestrator::new();
+        let params = test_params();
+        let wallet = test_addr("target");
+        endorse_wallet(&mut orch, &wallet, &params);
+
+        let pool: Vec<WalletAddress> = (1..=8).map(|i| test_addr(&format!("v{i}"))).collect();
+        let selected = orch
+            .select_verifiers(&wallet, &pool, &[42u8; 32], &params)
+            .unwrap();
+        orch.process_vote(&wallet, &selected[0], Vote::Legitimate, &params)
+            .unwrap();
+
+        // Nothing happens before the deadline.
+        assert!(orch.check_timeouts(Timestamp::now(), &params).is_empty());
+
+        let late =
+            Timestamp::new(Timestamp::now().as_secs() + params.verification_round_timeout_secs + 1);
+        let events = orch.check_timeouts(late, &params);
+        let penalized = events
+            .iter()
+            .filter(|e| {
+                matches!(
+                    e,
+                    VerificationEvent::VerifierPenalized { reason, .. } if reason == "vote_timeout"
+                )
+            })
+            .count();
+        assert_eq!(penalized, 2);
+        assert!(events.iter().any(|e| matches!(
+            e,
+            VerificationEvent::VerifiersTimedOut { wallet: w, unresponsive }
+                if *w == wallet && unresponsive.len() == 2
+        )));
+        assert!(orch.is_penalized(&selected[1], late.as_secs()));
+
+        let replacements = orch
+            .reselect_verifiers(&wallet, &pool, &[7u8; 32], &params)
+            .unwrap();
+        assert_eq!(replacements.len(), 2);
+        assert!(replacements.iter().all(|r| !selected.contains(r)));
+
+        // The vote cast before the timeout still counts.
+        let state = orch.get_state(&wallet).unwrap();
+        assert_eq!(state.votes.len(), 1);
+        assert_eq!(state.selected_verifiers.len(), 3);
+        assert_eq!(state.timed_out_rounds, 1);
+
+        for r in &replacements {
+            orch.process_vote(&wallet, r, Vote::Legitimate, &params)
+                .unwrap();
+        }
+        assert_eq!(
+            orch.get_state(&wallet).unwrap().phase,
+            VerificationPhase::Verified
+        );
+    }
+
+    #[test]
+    fn verification_expires_after_max_timed_out_rounds() {
+        let mut orch = VerificationOrchestrator::new();
+        let params = ProtocolParams {
+            max_verification_rounds: 2,
+            ..test_params()
+        };
+        let wallet = test_addr("target");
+        endorse_wallet(&mut orch, &wallet, &params);
+
+        let pool: Vec<WalletAddress> = (1..=9).map(|i| test_addr(&format!("v{i}"))).collect();
+        let selected = orch
+            .select_verifiers(&wallet, &pool, &[42u8; 32], &params)
+            .unwrap();
+        orch.process_vote(&wallet, &selected[0], Vote::Legitimate, &params)
+            .unwrap();
+
+        let late = |secs: u64| Timestamp::new(Timestamp::now().as_secs() + secs);
+        let round = params.verification_round_timeout_secs + 1;
+
+        orch.check_timeouts(late(round), &params);
+        orch.reselect_verifiers(&wallet, &pool, &[7u8; 32], &params)
+            .unwrap();
+
+        let events = orch.check_timeouts(late(2 * round), &params);
+        assert!(events.iter().any(|e| matches!(
+            e,
+            VerificationEvent::VerificationExpired { wallet: w, refunds, .. }
+                if *w == wallet
+                    && refunds == &vec![(selected[0].clone(), params.verifier_stake_amount)]
+        )));
+        assert!(orch.get_state(&wallet).is_none());
+
+        // The wallet can start over from scratch.
+        endorse_wallet(&mut orch, &wallet, &params);
+        assert_eq!(
+            orch.get_state(&wallet).unwrap().phase,
+            VerificationPhase::Endorsing
+        );
+    }
+
+    #[test]
+    fn challenge_revotes_are_not_timed_out() {
+        let mut orch = VerificationOrchestrator::new();
+        let params = test_params();
+        let wallet = test_addr("target");
+        verify_wallet(&mut orch, &wallet, &params);
+
+        orch.initiate_challenge(&wallet, &test_addr("challenger"), true, 500, &params)
+            .unwrap();
+        let pool: Vec<WalletAddress> = (6..=9).map(|i| test_addr(&format!("v{i}"))).collect();
+        orch.select_verifiers(&wallet, &pool, &[42u8; 32], &params)
+            .unwrap();
+
+        let late =
+            Timestamp::new(Timestamp::now().as_secs() + params.verification_round_timeout_secs + 1);
+        assert!(orch.check_timeouts(late, &params).is_empty());
+    }
 }
@@ -0,0 +1,137 @@
+//! Probation tiers — graduated trust for newly verified wallets.
+//!
+//! A wallet starts on **probation** when it is verified: it may not issue
+//! endorsements and its sends are held to `probation_daily_send_cap`. After
+//! `probation_period_secs` it becomes **provisional** and may endorse, with
+//! sends held to `provisional_daily_send_cap`. After
+//! `new_wallet_rate_limit_duration_secs` it is **established** and the tier
+//! limits no longer apply.
+//!
+//! Tiers relax with a clean history: a wallet that has lost more
+//! challenges than `challenge_loss_allowance` needs twice the age to reach
+//! each tier. Unverified wallets are always on probation.
+
+use burst_types::{ProtocolParams, Timestamp};
+use serde::{Deserialize, Serialize};
+
+use crate::challenge::ChallengeHistory;
+
+/// Window over which daily send caps are counted.
+pub const SEND_CAP_WINDOW_SECS: u64 = 24 * 3600;
+
+/// A wallet's trust tier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProbationTier {
+    Probation,
+    Provisional,
+    Established,
+}
+
+impl ProbationTier {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProbationTier::Probation => "probation",
+            ProbationTier::Provisional => "provisional",
+            ProbationTier::Established => "established",
+        }
+    }
+
+    /// Whether wallets in this tier may issue endorsements.
+    pub fn may_endorse(self) -> bool {
+        self != ProbationTier::Probation
+    }
+
+    /// TRST a wallet in this tier may send per day (0 = no cap).
+    pub fn daily_send_cap(self, params: &ProtocolParams) -> u128 {
+        match self {
+            ProbationTier::Probation => params.probation_daily_send_cap,
+            ProbationTier::Provisional => params.provisional_daily_send_cap,
+            ProbationTier::Established => 0,
+        }
+    }
+}
+
+/// Whether a wallet's challenge record counts as clean.
+pub fn clean_history(history: &ChallengeHistory, params: &ProtocolParams) -> bool {
+    history.challenges_lost <= params.challenge_loss_allowance
+}
+
+/// A wallet's standing at `now`: its tier, and when it reaches the next one
+/// (`None` once established or while unverified).
+pub fn probation_tier(
+    verified_at: Option<Timestamp>,
+    history: &ChallengeHistory,
+    now: Timestamp,
+    params: &ProtocolParams,
+) -> (ProbationTier, Option<Timestamp>) {
+    let Some(verified_at) = verified_at else {
+        return (ProbationTier::Probation, None);
+    };
+    let scale = if clean_history(history, params) { 1 } else { 2 };
+    let age = now.as_secs().saturating_sub(verified_at.as_secs());
+    let provisional_at = params.probation_period_secs.saturating_mul(scale);
+    let established_at = params
+        .new_wallet_rate_limit_duration_secs
+        .saturating_mul(scale);
+    let reached = |threshold: u64| {
+        Some(Timestamp::new(
+            verified_at.as_secs().saturating_add(threshold),
+        ))
+    };
+    if age < provisional_at {
+        (ProbationTier::Probation, reached(provisional_at))
+    } else if age < established_at {
+        (ProbationTier::Provisional, reached(established_at))
+    } else {
+        (ProbationTier::Established, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tiers_relax_with_age_and_clean_history() {
+        let params = ProtocolParams {
+            probation_period_secs: 100,
+            new_wallet_rate_limit_duration_secs: 1_000,
+            probation_daily_send_cap: 10,
+            provisional_daily_send_cap: 50,
+            challenge_loss_allowance: 1,
+            ..ProtocolParams::burst_defaults()
+        };
+        let clean = ChallengeHistory::default();
+        let verified = Some(Timestamp::new(1_000));
+        let tier = |history: &ChallengeHistory, now: u64| {
+            probation_tier(verified, history, Timestamp::new(now), &params)
+        };
+
+        assert_eq!(
+            tier(&clean, 1_050),
+            (ProbationTier::Probation, Some(Timestamp::new(1_100)))
+        );
+        assert_eq!(
+            tier(&clean, 1_100),
+            (ProbationTier::Provisional, Some(Timestamp::new(2_000)))
+        );
+        assert_eq!(tier(&clean, 2_000), (ProbationTier::Established, None));
+        assert_eq!(
+            probation_tier(None, &clean, Timestamp::new(9_999), &params).0,
+            ProbationTier::Probation
+        );
+
+        let harasser = ChallengeHistory {
+            challenges_lost: 2,
+            ..Default::default()
+        };
+        assert_eq!(tier(&harasser, 1_150).0, ProbationTier::Probation);
+        assert_eq!(tier(&harasser, 2_000).0, ProbationTier::Provisional);
+
+        assert!(!ProbationTier::Probation.may_endorse());
+        assert!(ProbationTier::Provisional.may_endorse());
+        assert_eq!(ProbationTier::Probation.daily_send_cap(&params), 10);
+        assert_eq!(ProbationTier::Established.daily_send_cap(&params), 0);
+    }
+}
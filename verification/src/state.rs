@@ -21,6 +21,17 @@ pub struct VerificationState {
     pub revote_count: u32,
     /// Verifiers excluded from future revote rounds (previous round participants).
     pub excluded_verifiers: HashSet<WalletAddress>,
+    /// Eligible verifiers the current round was drawn from, after
+    /// exclusions and penalties.
+    #[serde(default)]
+    pub selection_candidates: Vec<WalletAddress>,
+    /// Deadline for the selected verifiers to vote; `None` while the round
+    /// is waiting for verifiers to be (re)selected.
+    #[serde(default)]
+    pub round_deadline: Option<Timestamp>,
+    /// Number of voting rounds that ran past their deadline.
+    #[serde(default)]
+    pub timed_out_rounds: u32,
     /// When this verification process started.
     pub started_at: Timestamp,
 }
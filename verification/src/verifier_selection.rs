@@ -30,34 +30,51 @@ impl VerifierSelector {
             Err(_) => return Vec::new(),
         };
 
-        let mut scored: Vec<(usize, [u8; 32])> = eligible_verifiers
-            .iter()
-            .enumerate()
-            .map(|(i, addr)| {
-                let mut data = Vec::new();
-                data.extend_from_slice(&seed);
-                data.extend_from_slice(addr.as_str().as_bytes());
-                let hash = burst_crypto::blake2b_256(&data);
-                (i, hash)
-            })
-            .collect();
-
-        scored.sort_by_key(|a| a.1);
-        scored.truncate(count);
-        scored
-            .iter()
-            .map(|(i, _)| eligible_verifiers[*i].clone())
-            .collect()
+        draw_verifiers(&seed, eligible_verifiers, count)
     }
 }
 
+/// Pick `count` verifiers from `candidates`: each candidate scores
+/// `Hash(randomness || address)` and the lowest scores win. Anyone holding
+/// the randomness and the candidate list can repeat the draw.
+pub fn draw_verifiers(
+    randomness: &[u8],
+    candidates: &[WalletAddress],
+    count: usize,
+) -> Vec<WalletAddress> {
+    let mut scored: Vec<(usize, [u8; 32])> = candidates
+        .iter()
+        .enumerate()
+        .map(|(i, addr)| {
+            let hash = burst_crypto::blake2b_256_multi(&[randomness, addr.as_str().as_bytes()]);
+            (i, hash)
+        })
+        .collect();
+
+    scored.sort_by_key(|a| a.1);
+    scored.truncate(count);
+    scored.iter().map(|(i, _)| candidates[*i].clone()).collect()
+}
+
+/// Commitment to a candidate pool snapshot, in the order it was drawn from.
+/// Each address is length-prefixed so the encoding is unambiguous.
+pub fn pool_snapshot_hash(candidates: &[WalletAddress]) -> [u8; 32] {
+    let mut data = Vec::new();
+    for addr in candidates {
+        let bytes = addr.as_str().as_bytes();
+        data.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+        data.extend_from_slice(bytes);
+    }
+    burst_crypto::blake2b_256(&data)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use burst_vrf::{RandomOutput, VrfError, VrfProvider};
 
     fn addr(s: &str) -> WalletAddress {
-        WalletAddress::new(&format!("brst_{s}"))
+        WalletAddress::new(format!("brst_{s}"))
     }
 
     struct FixedVrf {
@@ -164,4 +181,18 @@ mod tests {
             "different seeds should generally produce different selections"
         );
     }
+
+    #[test]
+    fn pool_snapshot_hash_commits_to_order_and_boundaries() {
+        let pool = vec![addr("a"), addr("b")];
+        assert_eq!(pool_snapshot_hash(&pool), pool_snapshot_hash(&pool.clone()));
+        assert_ne!(
+            pool_snapshot_hash(&pool),
+            pool_snapshot_hash(&[addr("b"), addr("a")])
+        );
+        assert_ne!(
+            pool_snapshot_hash(&[addr("ab"), addr("c")]),
+            pool_snapshot_hash(&[addr("a"), addr("bc")])
+        );
+    }
 }
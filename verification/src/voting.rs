@@ -266,10 +266,7 @@ mod tests {
     use super::*;
 
     fn test_addr(s: &str) -> WalletAddress {
-        WalletAddress::new(format!(
-            "brst_{}",
-            s.repeat(60 / s.len() + 1)[..60].to_string()
-        ))
+        WalletAddress::new(format!("brst_{}", &s.repeat(60 / s.len() + 1)[..60]))
     }
 
     #[test]
@@ -0,0 +1,441 @@
+//! Batch sends — one signer, many recipients.
+//!
+//! Payroll-style payouts become a chain of send blocks from one account.
+//! [`BatchSend::plan`] builds the whole chain up front: coins are selected
+//! once for the batch total and the consumed provenance is split between
+//! the sends in order, and each block is chained on the one before it. If
+//! any send cannot be built (bad recipient, oversized memo, not enough
+//! TRST) the batch fails as a whole.
+//!
+//! Every block hash is known before anything is published, so work for
+//! the whole chain is requested at once, taking precomputed nonces from a
+//! [`WorkCache`] where it has them. Nothing is submitted until every block
+//! is signed and has work; blocks then go out in chain order.
+//!
+//! If submission stops part-way, the batch (which serializes) picks up
+//! again from the account's head on the node: sends already on chain are
+//! skipped. A head that is not part of the batch means the chain moved on
+//! without it, and the rest of the batch is refused rather than forked.
+
+use burst_ledger::StateBlock;
+use burst_transactions::Transaction;
+use burst_trst::{select_coins, CoinSelection, ConsumedProvenance, TrstToken};
+use burst_types::{BlockHash, PrivateKey, Timestamp, WalletAddress};
+use burst_work::WorkCache;
+use serde::{Deserialize, Serialize};
+
+use crate::error::WalletError;
+use crate::transaction_builder::{
+    build_state_block, build_tagged_send_tx, coin_selection_error, sign_state_block, AccountState,
+};
+
+/// Most sends one batch may hold.
+pub const MAX_BATCH_LEN: usize = 1000;
+
+/// One payout in a batch.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchRecipient {
+    pub receiver: WalletAddress,
+    /// Amount in raw TRST units.
+    pub amount: u128,
+    #[serde(default)]
+    pub memo: Option<Vec<u8>>,
+    #[serde(default)]
+    pub destination_tag: Option<u64>,
+}
+
+impl BatchRecipient {
+    pub fn new(receiver: WalletAddress, amount: u128) -> Self {
+        Self {
+            receiver,
+            amount,
+            memo: None,
+            destination_tag: None,
+        }
+    }
+}
+
+/// A planned send and the provenance it spends.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BatchEntry {
+    pub recipient: BatchRecipient,
+    pub block: StateBlock,
+    pub provenance: Vec<ConsumedProvenance>,
+}
+
+/// A chain of sends from one account, planned, signed and submitted
+/// together.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BatchSend {
+    sender: WalletAddress,
+    /// The account head the first send builds on.
+    base: BlockHash,
+    entries: Vec<BatchEntry>,
+    /// Entries known to be on chain, counted from the front.
+    submitted: usize,
+}
+
+impl BatchSend {
+    /// Plan unsigned sends to `recipients`, in order, on top of
+    /// `account_state`, spending `tokens` as `selection` dictates.
+    #[allow(clippy::too_many_arguments)]
+    pub fn plan(
+        sender: &WalletAddress,
+        account_state: &AccountState,
+        recipients: &[BatchRecipient],
+        tokens: &[TrstToken],
+        selection: &CoinSelection,
+        trst_expiry_secs: u64,
+        params_hash: BlockHash,
+        now: Timestamp,
+    ) -> Result<Self, WalletError> {
+        if recipients.is_empty() || recipients.len() > MAX_BATCH_LEN {
+            return Err(WalletError::TransactionBuild(format!(
+                "a batch holds 1 to {MAX_BATCH_LEN} sends, got {}",
+                recipients.len()
+            )));
+        }
+        if let Some(r) = recipients.iter().find(|r| r.amount == 0) {
+            return Err(WalletError::TransactionBuild(format!(
+                "send to {} has a zero amount",
+                r.receiver
+            )));
+        }
+        let total = recipients
+            .iter()
+            .try_fold(0u128, |sum, r| sum.checked_add(r.amount))
+            .ok_or_else(|| WalletError::TransactionBuild("batch total overflows".into()))?;
+        let mut portions = select_coins(tokens, total, now, trst_expiry_secs, selection)
+            .map_err(coin_selection_error)?
+            .into_iter();
+
+        let mut state = AccountState {
+            head: account_state.head,
+            block_count: account_state.block_count,
+            representative: account_state.representative.clone(),
+            brn_balance: account_state.brn_balance,
+            trst_balance: account_state.trst_balance,
+            network: account_state.network,
+            block_version: account_state.block_version,
+        };
+        let mut carry: Option<ConsumedProvenance> = None;
+        let mut entries = Vec::with_capacity(recipients.len());
+        for recipient in recipients {
+            let provenance = take_portions(&mut carry, &mut portions, recipient.amount);
+            let first = &provenance[0];
+            let tx = build_tagged_send_tx(
+                sender,
+                &recipient.receiver,
+                recipient.amount,
+                first.token_id,
+                first.origin,
+                recipient.memo.clone(),
+                recipient.destination_tag,
+                now,
+            )?;
+            let block =
+                build_state_block(&state, &Transaction::Send(tx), first.origin, params_hash)?;
+            state.head = block.hash;
+            state.block_count += 1;
+            state.trst_balance = block.trst_balance;
+            entries.push(BatchEntry {
+                recipient: recipient.clone(),
+                block,
+                provenance,
+            });
+        }
+
+        Ok(Self {
+            sender: sender.clone(),
+            base: account_state.head,
+            entries,
+            submitted: 0,
+        })
+    }
+
+    pub fn sender(&self) -> &WalletAddress {
+        &self.sender
+    }
+
+    pub fn entries(&self) -> &[BatchEntry] {
+        &self.entries
+    }
+
+    /// TRST sent by the whole batch.
+    pub fn total(&self) -> u128 {
+        self.entries.iter().map(|e| e.recipient.amount).sum()
+    }
+
+    /// Entries not yet known to be on chain.
+    pub fn remaining(&self) -> &[BatchEntry] {
+        &self.entries[self.submitted..]
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.submitted == self.entries.len()
+    }
+
+    /// Sign every block with the account's key.
+    pub fn sign(&mut self, private_key: &PrivateKey) {
+        for entry in &mut self.entries {
+            entry.block = sign_state_block(entry.block.clone(), private_key);
+        }
+    }
+
+    /// Whether every block carries a signature.
+    pub fn is_signed(&self) -> bool {
+        self.entries
+            .iter()
+            .all(|e| e.block.signature.0 != [0u8; 64])
+    }
+
+    /// Catch up with the account's `head` on the node: every send up to and
+    /// including the head counts as submitted. Fails if the head is neither
+    /// the batch's base nor one of its blocks.
+    pub fn resume_from(&mut self, head: BlockHash) -> Result<(), WalletError> {
+        if head == self.base {
+            self.submitted = 0;
+            return Ok(());
+        }
+        match self.entries.iter().position(|e| e.block.hash == head) {
+            Some(i) => {
+                self.submitted = i + 1;
+                Ok(())
+            }
+            None => Err(WalletError::TransactionBuild(format!(
+                "account head {head} is not part of this batch; the chain moved on without it"
+            ))),
+        }
+    }
+
+    /// Attach a nonce from `cache` to each remaining block that has none.
+    /// Returns the work roots still needing work.
+    pub fn apply_cached_work(&mut self, cache: &mut WorkCache) -> Vec<BlockHash> {
+        let submitted = self.submitted;
+        let mut missing = Vec::new();
+        for entry in &mut self.entries[submitted..] {
+            if entry.block.work != 0 {
+                continue;
+            }
+            let root = entry.block.work_root();
+            match cache.get(&root) {
+                Some(nonce) => {
+                    entry.block.work = nonce;
+                    cache.remove(&root);
+                }
+                None => missing.push(root),
+            }
+        }
+        missing
+    }
+}
+
+/// Take `amount` worth of provenance from the selected portions, splitting
+/// a portion between two sends where needed.
+fn take_portions(
+    carry: &mut Option<ConsumedProvenance>,
+    portions: &mut impl Iterator<Item = ConsumedProvenance>,
+    amount: u128,
+) -> Vec<ConsumedProvenance> {
+    let mut taken = Vec::new();
+    let mut remaining = amount;
+    while remaining > 0 {
+        let Some(mut portion) = carry.take().or_else(|| portions.next()) else {
+            break;
+        };
+        if portion.amount > remaining {
+            let mut rest = portion.clone();
+            rest.amount -= remaining;
+            *carry = Some(rest);
+            portion.amount = remaining;
+        }
+        remaining -= portion.amount;
+        taken.push(portion);
+    }
+    taken
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl BatchSend {
+    /// Request work for every remaining block the cache cannot supply, all
+    /// at once.
+    pub async fn generate_work(
+        &mut self,
+        client: &crate::wallet::NodeClient,
+        cache: &mut WorkCache,
+    ) -> Result<(), WalletError> {
+        let missing = self.apply_cached_work(cache);
+        let hashes: Vec<String> = missing.iter().map(|hash| format!("{hash}")).collect();
+        let results =
+            futures_util::future::join_all(hashes.iter().map(|hash| client.work_generate(hash)))
+                .await;
+        for (hash, result) in missing.iter().zip(results) {
+            let nonce = u64::from_str_radix(&result?.work, 16)
+                .map_err(|e| WalletError::Node(format!("invalid work value: {e}")))?;
+            cache.insert(hash, nonce);
+        }
+        self.apply_cached_work(cache);
+        Ok(())
+    }
+
+    /// Submit the remaining sends in chain order, resuming from the
+    /// account's current head. Returns the number of sends on chain.
+    ///
+    /// Nothing is published until every remaining block has work. On a
+    /// rejection the batch stops; call again to resume once the cause is
+    /// fixed.
+    pub async fn submit(
+        &mut self,
+        client: &crate::wallet::NodeClient,
+        cache: &mut WorkCache,
+    ) -> Result<usize, WalletError> {
+        if !self.is_signed() {
+            return Err(WalletError::Signing(
+                "batch must be signed before submission".into(),
+            ));
+        }
+        let info = client.account_info(self.sender.as_str()).await?;
+        self.resume_from(info.account_state()?.head)?;
+        self.generate_work(client, cache).await?;
+
+        let total = self.entries.len();
+        while self.submitted < total {
+            let entry = &self.entries[self.submitted];
+            let json = serde_json::to_string(&entry.block)
+                .map_err(|e| WalletError::Other(format!("failed to serialize block: {e}")))?;
+            let result = client.process(&json).await?;
+            if !result.accepted {
+                return Err(WalletError::Node(format!(
+                    "batch send {} of {total} rejected: {}",
+                    self.submitted + 1,
+                    result.detail.unwrap_or_default()
+                )));
+            }
+            self.submitted += 1;
+        }
+        Ok(self.submitted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_types::{NetworkId, TrstState, TxHash};
+
+    fn address(n: u8) -> WalletAddress {
+        burst_crypto::derive_address(&burst_crypto::keypair_from_seed(&[n; 32]).public)
+    }
+
+    fn holding(n: u8, amount: u128) -> TrstToken {
+        TrstToken {
+            id: TxHash::new([n; 32]),
+            amount,
+            origin: TxHash::new([n + 100; 32]),
+            link: TxHash::new([n; 32]),
+            holder: address(1),
+            origin_timestamp: Timestamp::new(10),
+            effective_origin_timestamp: Timestamp::new(10),
+            state: TrstState::Active,
+            origin_wallet: address(9),
+            origin_proportions: Vec::new(),
+        }
+    }
+
+    fn plan(recipients: &[BatchRecipient]) -> Result<BatchSend, WalletError> {
+        let state = AccountState {
+            head: BlockHash::new([7u8; 32]),
+            block_count: 4,
+            representative: address(1),
+            brn_balance: 0,
+            trst_balance: 500,
+            network: NetworkId::Dev,
+            block_version: burst_ledger::BLOCK_VERSION_V2,
+        };
+        BatchSend::plan(
+            &address(1),
+            &state,
+            recipients,
+            &[holding(1, 200), holding(2, 300)],
+            &CoinSelection::Fifo,
+            86_400,
+            BlockHash::ZERO,
+            Timestamp::new(1_000),
+        )
+    }
+
+    #[test]
+    fn plans_a_chain_and_splits_provenance() {
+        let mut batch = plan(&[
+            BatchRecipient::new(address(2), 150),
+            BatchRecipient::new(address(3), 100),
+            BatchRecipient::new(address(4), 200),
+        ])
+        .unwrap();
+        assert_eq!(batch.total(), 450);
+
+        let entries = batch.entries();
+        assert_eq!(entries[0].block.previous, BlockHash::new([7u8; 32]));
+        assert_eq!(entries[1].block.previous, entries[0].block.hash);
+        assert_eq!(entries[2].block.previous, entries[1].block.hash);
+        assert_eq!(entries[0].block.sequence(), Some(5));
+        assert_eq!(entries[2].block.sequence(), Some(7));
+        let balances: Vec<u128> = entries.iter().map(|e| e.block.trst_balance).collect();
+        assert_eq!(balances, vec![350, 250, 50]);
+
+        // The second send takes the rest of token 1 and the start of token 2.
+        let amounts = |i: usize| -> Vec<(TxHash, u128)> {
+            entries[i]
+                .provenance
+                .iter()
+                .map(|p| (p.token_id, p.amount))
+                .collect()
+        };
+        assert_eq!(amounts(0), vec![(TxHash::new([1u8; 32]), 150)]);
+        assert_eq!(
+            amounts(1),
+            vec![(TxHash::new([1u8; 32]), 50), (TxHash::new([2u8; 32]), 50)]
+        );
+        assert_eq!(amounts(2), vec![(TxHash::new([2u8; 32]), 200)]);
+
+        assert!(!batch.is_signed());
+        batch.sign(&burst_crypto::keypair_from_seed(&[1u8; 32]).private);
+        assert!(batch.is_signed());
+
+        // Work already in the cache is used; the rest is reported missing.
+        let mut cache = WorkCache::new(10);
+        let second = batch.entries()[1].block.work_root();
+        cache.insert(&second, 99);
+        let missing = batch.apply_cached_work(&mut cache);
+        assert_eq!(missing.len(), 2);
+        assert!(!missing.contains(&second));
+        assert_eq!(batch.entries()[1].block.work, 99);
+    }
+
+    #[test]
+    fn fails_as_a_whole_and_resumes_from_the_head() {
+        assert!(matches!(
+            plan(&[
+                BatchRecipient::new(address(2), 400),
+                BatchRecipient::new(address(3), 200),
+            ]),
+            Err(WalletError::InsufficientTrst { needed: 600, .. })
+        ));
+        assert!(plan(&[]).is_err());
+        assert!(plan(&[BatchRecipient::new(address(2), 0)]).is_err());
+
+        let mut batch = plan(&[
+            BatchRecipient::new(address(2), 100),
+            BatchRecipient::new(address(3), 100),
+        ])
+        .unwrap();
+        let first = batch.entries()[0].block.hash;
+        batch.resume_from(first).unwrap();
+        assert_eq!(batch.remaining().len(), 1);
+        let last = batch.entries()[1].block.hash;
+        batch.resume_from(last).unwrap();
+        assert!(batch.is_complete());
+        batch.resume_from(BlockHash::new([7u8; 32])).unwrap();
+        assert_eq!(batch.remaining().len(), 2);
+        assert!(batch.resume_from(BlockHash::new([8u8; 32])).is_err());
+    }
+}
@@ -0,0 +1,226 @@
+//! Address book — labeled addresses the user deals with.
+//!
+//! A contact is a label for an address, plus what the user knows about it:
+//! which groups have attested the person behind it, and a free-form trust
+//! note. The transaction builder resolves recipient labels through the
+//! address book ([`resolve_recipient`](crate::transaction_builder::resolve_recipient)),
+//! and contacts marked [`allow_receive`](Contact::allow_receive) form the
+//! receive gate's allowlist, so transfers from them are accepted without
+//! asking any group.
+//!
+//! The address book is saved encrypted in the keystore
+//! ([`KeystoreFile::set_contacts`](crate::keystore::KeystoreFile::set_contacts)).
+
+use burst_types::{Timestamp, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+use crate::error::WalletError;
+
+/// A group's attestation of a contact, as seen by the user.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupAttestation {
+    pub group_id: String,
+    /// When the user saw the group vouch for the contact.
+    pub attested_at: Timestamp,
+}
+
+/// One labeled address.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Contact {
+    pub label: String,
+    pub address: WalletAddress,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attestations: Vec<GroupAttestation>,
+    /// Why the user trusts (or doesn't trust) this contact.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Accept transfers from this contact without checking the trust policy.
+    #[serde(default)]
+    pub allow_receive: bool,
+}
+
+impl Contact {
+    pub fn new(label: impl Into<String>, address: WalletAddress) -> Self {
+        Self {
+            label: label.into(),
+            address,
+            attestations: Vec::new(),
+            note: None,
+            allow_receive: false,
+        }
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    pub fn with_attestation(mut self, group_id: impl Into<String>, at: Timestamp) -> Self {
+        self.attest(group_id, at);
+        self
+    }
+
+    /// Put the contact on the receive allowlist.
+    pub fn allowing_receive(mut self) -> Self {
+        self.allow_receive = true;
+        self
+    }
+
+    /// Record that `group_id` vouched for the contact at `at`, replacing an
+    /// earlier attestation by the same group.
+    pub fn attest(&mut self, group_id: impl Into<String>, at: Timestamp) {
+        let group_id = group_id.into();
+        self.attestations.retain(|a| a.group_id != group_id);
+        self.attestations.push(GroupAttestation {
+            group_id,
+            attested_at: at,
+        });
+    }
+
+    /// Whether `group_id` has attested the contact.
+    pub fn is_attested_by(&self, group_id: &str) -> bool {
+        self.attestations.iter().any(|a| a.group_id == group_id)
+    }
+}
+
+/// The wallet's address book. Labels are unique, ignoring case.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Contacts {
+    contacts: Vec<Contact>,
+}
+
+impl Contacts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.contacts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contacts.is_empty()
+    }
+
+    /// Contacts in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = &Contact> {
+        self.contacts.iter()
+    }
+
+    /// Add a contact. The label must be new and must not itself be an
+    /// address, and the address must be valid.
+    pub fn add(&mut self, contact: Contact) -> Result<(), WalletError> {
+        let label = contact.label.trim();
+        if label.is_empty() || is_address(label) {
+            return Err(WalletError::Other(format!(
+                "invalid contact label {:?}",
+                contact.label
+            )));
+        }
+        if !is_address(contact.address.as_str()) {
+            return Err(WalletError::InvalidAddress(contact.address.to_string()));
+        }
+        if self.get(label).is_some() {
+            return Err(WalletError::Other(format!(
+                "address book already has a contact labeled {label:?}"
+            )));
+        }
+        self.contacts.push(Contact {
+            label: label.to_string(),
+            ..contact
+        });
+        Ok(())
+    }
+
+    /// Remove the contact labeled `label`, returning it.
+    pub fn remove(&mut self, label: &str) -> Option<Contact> {
+        let position = self.position(label)?;
+        Some(self.contacts.remove(position))
+    }
+
+    pub fn get(&self, label: &str) -> Option<&Contact> {
+        self.position(label).map(|i| &self.contacts[i])
+    }
+
+    pub fn get_mut(&mut self, label: &str) -> Option<&mut Contact> {
+        self.position(label).map(|i| &mut self.contacts[i])
+    }
+
+    /// The contact for `address`, to show a label instead of the address.
+    pub fn by_address(&self, address: &WalletAddress) -> Option<&Contact> {
+        self.contacts.iter().find(|c| c.address == *address)
+    }
+
+    /// Addresses of the contacts marked [`Contact::allow_receive`].
+    pub fn allowlist(&self) -> Vec<WalletAddress> {
+        self.contacts
+            .iter()
+            .filter(|c| c.allow_receive)
+            .map(|c| c.address.clone())
+            .collect()
+    }
+
+    fn position(&self, label: &str) -> Option<usize> {
+        let label = label.trim();
+        self.contacts
+            .iter()
+            .position(|c| c.label.eq_ignore_ascii_case(label))
+    }
+}
+
+/// Whether `s` is a well-formed wallet address.
+pub(crate) fn is_address(s: &str) -> bool {
+    burst_crypto::decode_address(s).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address() -> WalletAddress {
+        burst_crypto::derive_address(&burst_crypto::generate_keypair().public)
+    }
+
+    #[test]
+    fn labels_are_unique_and_found_ignoring_case() {
+        let mut contacts = Contacts::new();
+        let bob = address();
+        contacts
+            .add(Contact::new(" Bob ", bob.clone()).with_note("met at the fair"))
+            .unwrap();
+        assert_eq!(contacts.get("bob").unwrap().address, bob);
+        assert_eq!(contacts.by_address(&bob).unwrap().label, "Bob");
+
+        assert!(contacts.add(Contact::new("BOB", address())).is_err());
+        assert!(contacts.add(Contact::new("", address())).is_err());
+        assert!(contacts.add(Contact::new(bob.as_str(), address())).is_err());
+        assert!(contacts
+            .add(Contact::new("carol", WalletAddress::new("brst_nope")))
+            .is_err());
+
+        assert_eq!(contacts.remove("BOB").unwrap().address, bob);
+        assert!(contacts.is_empty());
+    }
+
+    #[test]
+    fn allowlist_and_attestations() {
+        let mut contacts = Contacts::new();
+        let bob = address();
+        let carol = address();
+        contacts
+            .add(Contact::new("bob", bob.clone()).allowing_receive())
+            .unwrap();
+        contacts
+            .add(Contact::new("carol", carol).with_attestation("g1", Timestamp::new(10)))
+            .unwrap();
+        assert_eq!(contacts.allowlist(), vec![bob]);
+
+        let carol = contacts.get_mut("carol").unwrap();
+        carol.attest("g1", Timestamp::new(20));
+        assert_eq!(carol.attestations.len(), 1);
+        assert_eq!(carol.attestations[0].attested_at, Timestamp::new(20));
+        assert!(carol.is_attested_by("g1"));
+        assert!(!carol.is_attested_by("g2"));
+    }
+}
@@ -1,7 +1,11 @@
 //! Child wallet custodianship — allows a guardian to manage a ward's wallet
-//! until the ward transitions to independent control.
+//! until the ward transitions to independent control — and social recovery,
+//! where designated custodians approve rotating a lost account key.
 
-use burst_types::WalletAddress;
+use burst_transactions::recovery::{self, CustodianApproval, RecoveryTx};
+use burst_types::{BlockHash, PrivateKey, PublicKey, Timestamp, WalletAddress};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Custodianship allows a guardian wallet to manage operations on behalf of a
@@ -121,6 +125,21 @@ pub enum CustodianshipError {
     SelfCustodianship,
     AlreadyTerminated,
     NoCustodianship,
+    /// The approval came from a wallet that is not a designated custodian.
+    NotACustodian(WalletAddress),
+    /// The approval's signature does not cover this request.
+    InvalidApproval(WalletAddress),
+    /// Fewer approvals than the threshold have been collected.
+    InsufficientApprovals {
+        have: usize,
+        need: usize,
+    },
+    /// The recovery delay has not passed yet.
+    TooEarly {
+        ready_at: u64,
+    },
+    /// A sealed message could not be built or opened.
+    Exchange(String),
 }
 
 impl std::fmt::Display for CustodianshipError {
@@ -130,12 +149,262 @@ impl std::fmt::Display for CustodianshipError {
             Self::SelfCustodianship => write!(f, "cannot be own custodian"),
             Self::AlreadyTerminated => write!(f, "custodianship already terminated"),
             Self::NoCustodianship => write!(f, "no active custodianship found"),
+            Self::NotACustodian(who) => write!(f, "{who} is not a recovery custodian"),
+            Self::InvalidApproval(who) => write!(f, "invalid recovery approval from {who}"),
+            Self::InsufficientApprovals { have, need } => {
+                write!(f, "recovery needs {need} approvals, have {have}")
+            }
+            Self::TooEarly { ready_at } => write!(f, "recovery cannot complete before {ready_at}"),
+            Self::Exchange(reason) => write!(f, "recovery exchange failed: {reason}"),
         }
     }
 }
 
 impl std::error::Error for CustodianshipError {}
 
+// ---------------------------------------------------------------------------
+// Social recovery
+// ---------------------------------------------------------------------------
+//
+// 1. The owner designates custodians on-chain with a RecoverySetup block
+//    (`transaction_builder::build_recovery_setup_tx`).
+// 2. After losing the key, the owner generates a new key pair and opens a
+//    `RecoverySession`, which seals the `RecoveryRequest` to each custodian.
+// 3. Each custodian opens the request, confirms the owner's identity out of
+//    band, and returns an approval sealed to the new key.
+// 4. With `threshold` approvals and the delay elapsed, the session builds a
+//    Recovery transaction, which is signed with the new key.
+
+/// Context for requests sealed to custodians.
+const REQUEST_CONTEXT: &[u8] = b"burst-recovery-request";
+/// Context for approvals sealed to the requester's new key.
+const APPROVAL_CONTEXT: &[u8] = b"burst-recovery-approval";
+
+/// An encrypted message in the recovery exchange. Only the holder of the
+/// recipient key can read it, so it can be relayed over any channel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SealedMessage {
+    /// The sender's one-time X25519 public key.
+    pub ephemeral_public: [u8; 32],
+    pub ciphertext: Vec<u8>,
+}
+
+impl SealedMessage {
+    fn seal<T: Serialize>(
+        value: &T,
+        recipient: &PublicKey,
+        context: &[u8],
+    ) -> Result<Self, CustodianshipError> {
+        let plaintext =
+            serde_json::to_vec(value).map_err(|e| CustodianshipError::Exchange(e.to_string()))?;
+        let x25519_public = burst_crypto::ed25519_public_to_x25519(recipient.as_bytes())
+            .ok_or_else(|| CustodianshipError::Exchange("invalid recipient key".into()))?;
+        let (ephemeral_public, ciphertext) =
+            burst_crypto::seal(&plaintext, &x25519_public, context);
+        Ok(Self {
+            ephemeral_public,
+            ciphertext,
+        })
+    }
+
+    fn open<T: DeserializeOwned>(
+        &self,
+        recipient_private: &PrivateKey,
+        context: &[u8],
+    ) -> Result<T, CustodianshipError> {
+        let secret = burst_crypto::ed25519_private_to_x25519(&recipient_private.0);
+        let plaintext =
+            burst_crypto::open(&self.ephemeral_public, &self.ciphertext, &secret, context)
+                .map_err(|e| CustodianshipError::Exchange(e.into()))?;
+        serde_json::from_slice(&plaintext).map_err(|e| CustodianshipError::Exchange(e.to_string()))
+    }
+}
+
+/// A request to rotate `account` to `new_key`, made when the account head
+/// was `previous`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoveryRequest {
+    pub account: WalletAddress,
+    pub new_key: PublicKey,
+    pub previous: BlockHash,
+    pub requested_at: Timestamp,
+}
+
+impl RecoveryRequest {
+    /// The digest custodians sign.
+    pub fn digest(&self) -> [u8; 32] {
+        recovery::recovery_digest(
+            &self.account,
+            &self.new_key,
+            &self.previous,
+            self.requested_at,
+        )
+    }
+
+    /// Custodian side: decrypt a request sealed to `custodian_private`.
+    pub fn open_sealed(
+        sealed: &SealedMessage,
+        custodian_private: &PrivateKey,
+    ) -> Result<Self, CustodianshipError> {
+        sealed.open(custodian_private, REQUEST_CONTEXT)
+    }
+
+    /// Custodian side: sign the request.
+    pub fn approve(
+        &self,
+        custodian: &WalletAddress,
+        custodian_private: &PrivateKey,
+    ) -> CustodianApproval {
+        CustodianApproval {
+            custodian: custodian.clone(),
+            signature: burst_crypto::sign_message(&self.digest(), custodian_private),
+        }
+    }
+
+    /// Custodian side: sign the request and seal the approval to the new key.
+    pub fn seal_approval(
+        &self,
+        custodian: &WalletAddress,
+        custodian_private: &PrivateKey,
+    ) -> Result<SealedMessage, CustodianshipError> {
+        SealedMessage::seal(
+            &self.approve(custodian, custodian_private),
+            &self.new_key,
+            APPROVAL_CONTEXT,
+        )
+    }
+}
+
+/// Requester side of a recovery: seals the request to each custodian and
+/// collects their approvals until the threshold is met.
+#[derive(Debug, Clone)]
+pub struct RecoverySession {
+    request: RecoveryRequest,
+    custodians: Vec<WalletAddress>,
+    threshold: u8,
+    delay_secs: u64,
+    approvals: Vec<CustodianApproval>,
+}
+
+impl RecoverySession {
+    /// Start a session for `request` against the account's on-chain
+    /// custodian set.
+    pub fn new(
+        request: RecoveryRequest,
+        custodians: Vec<WalletAddress>,
+        threshold: u8,
+        delay_secs: u64,
+    ) -> Self {
+        Self {
+            request,
+            custodians,
+            threshold,
+            delay_secs,
+            approvals: Vec::new(),
+        }
+    }
+
+    pub fn request(&self) -> &RecoveryRequest {
+        &self.request
+    }
+
+    pub fn approvals(&self) -> &[CustodianApproval] {
+        &self.approvals
+    }
+
+    /// Whether enough custodians have approved.
+    pub fn is_complete(&self) -> bool {
+        self.approvals.len() >= self.threshold as usize
+    }
+
+    /// When the Recovery block becomes valid.
+    pub fn ready_at(&self) -> u64 {
+        self.request
+            .requested_at
+            .as_secs()
+            .saturating_add(self.delay_secs)
+    }
+
+    /// The request sealed to each custodian, ready to hand out.
+    pub fn sealed_requests(
+        &self,
+    ) -> Result<Vec<(WalletAddress, SealedMessage)>, CustodianshipError> {
+        self.custodians
+            .iter()
+            .map(|custodian| {
+                let key = burst_crypto::decode_address(custodian.as_str())
+                    .ok_or_else(|| CustodianshipError::NotACustodian(custodian.clone()))?;
+                let sealed = SealedMessage::seal(&self.request, &PublicKey(key), REQUEST_CONTEXT)?;
+                Ok((custodian.clone(), sealed))
+            })
+            .collect()
+    }
+
+    /// Decrypt an approval with the new key, check it, and keep it.
+    /// Returns the number of distinct approvals collected so far.
+    pub fn add_sealed_approval(
+        &mut self,
+        sealed: &SealedMessage,
+        new_private: &PrivateKey,
+    ) -> Result<usize, CustodianshipError> {
+        let approval: CustodianApproval = sealed.open(new_private, APPROVAL_CONTEXT)?;
+        self.add_approval(approval)
+    }
+
+    /// Check an approval and keep it. Repeat approvals are ignored.
+    pub fn add_approval(
+        &mut self,
+        approval: CustodianApproval,
+    ) -> Result<usize, CustodianshipError> {
+        if !self.custodians.contains(&approval.custodian) {
+            return Err(CustodianshipError::NotACustodian(approval.custodian));
+        }
+        let valid = burst_crypto::decode_address(approval.custodian.as_str()).is_some_and(|key| {
+            burst_crypto::verify_signature(
+                &self.request.digest(),
+                &approval.signature,
+                &PublicKey(key),
+            )
+        });
+        if !valid {
+            return Err(CustodianshipError::InvalidApproval(approval.custodian));
+        }
+        if !self
+            .approvals
+            .iter()
+            .any(|a| a.custodian == approval.custodian)
+        {
+            self.approvals.push(approval);
+        }
+        Ok(self.approvals.len())
+    }
+
+    /// Build the Recovery transaction. Sign the resulting block with the new
+    /// key.
+    pub fn build_recovery_tx(&self, now: Timestamp) -> Result<RecoveryTx, CustodianshipError> {
+        if !self.is_complete() {
+            return Err(CustodianshipError::InsufficientApprovals {
+                have: self.approvals.len(),
+                need: self.threshold as usize,
+            });
+        }
+        if now.as_secs() < self.ready_at() {
+            return Err(CustodianshipError::TooEarly {
+                ready_at: self.ready_at(),
+            });
+        }
+        crate::transaction_builder::build_recovery_tx(
+            &self.request.account,
+            self.request.new_key.clone(),
+            self.request.previous,
+            self.request.requested_at,
+            self.approvals.clone(),
+            now,
+        )
+        .map_err(|e| CustodianshipError::Exchange(e.to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -189,4 +458,88 @@ mod tests {
             CustodianshipError::AlreadyHasCustodian
         );
     }
+
+    fn keyed(seed: u8) -> (WalletAddress, burst_types::KeyPair) {
+        let keys = burst_crypto::keypair_from_seed(&[seed; 32]);
+        (burst_crypto::derive_address(&keys.public), keys)
+    }
+
+    #[test]
+    fn recovery_exchange_collects_threshold_approvals() {
+        let (owner, _) = keyed(1);
+        let custodians: Vec<_> = (2..5).map(keyed).collect();
+        let new_keys = burst_crypto::keypair_from_seed(&[9u8; 32]);
+        let request = RecoveryRequest {
+            account: owner.clone(),
+            new_key: new_keys.public.clone(),
+            previous: BlockHash::new([7u8; 32]),
+            requested_at: Timestamp::new(1_000),
+        };
+        let addresses = custodians.iter().map(|(a, _)| a.clone()).collect();
+        let mut session = RecoverySession::new(request.clone(), addresses, 2, 3_600);
+
+        let sealed = session.sealed_requests().unwrap();
+        assert_eq!(sealed.len(), 3);
+        // A custodian cannot read another custodian's copy.
+        assert!(RecoveryRequest::open_sealed(&sealed[0].1, &custodians[1].1.private).is_err());
+
+        for ((address, keys), (_, message)) in custodians.iter().zip(&sealed).take(2) {
+            let opened = RecoveryRequest::open_sealed(message, &keys.private).unwrap();
+            assert_eq!(opened, request);
+            let reply = opened.seal_approval(address, &keys.private).unwrap();
+            session
+                .add_sealed_approval(&reply, &new_keys.private)
+                .unwrap();
+            // Replaying the same approval doesn't count twice.
+            session
+                .add_sealed_approval(&reply, &new_keys.private)
+                .unwrap();
+        }
+        assert_eq!(session.approvals().len(), 2);
+        assert!(session.is_complete());
+
+        assert_eq!(
+            session
+                .build_recovery_tx(Timestamp::new(4_599))
+                .unwrap_err(),
+            CustodianshipError::TooEarly { ready_at: 4_600 }
+        );
+        let tx = session.build_recovery_tx(Timestamp::new(4_600)).unwrap();
+        assert_eq!(tx.previous, request.previous);
+        let digest = request.digest();
+        let designated: Vec<_> = custodians.iter().map(|(a, _)| a.clone()).collect();
+        assert!(recovery::verify_approvals(&designated, 2, &digest, &tx.approvals).is_ok());
+    }
+
+    #[test]
+    fn recovery_session_rejects_foreign_and_forged_approvals() {
+        let (owner, _) = keyed(1);
+        let (custodian, custodian_keys) = keyed(2);
+        let (outsider, outsider_keys) = keyed(3);
+        let new_keys = burst_crypto::keypair_from_seed(&[9u8; 32]);
+        let request = RecoveryRequest {
+            account: owner,
+            new_key: new_keys.public.clone(),
+            previous: BlockHash::ZERO,
+            requested_at: Timestamp::new(0),
+        };
+        let mut session = RecoverySession::new(request.clone(), vec![custodian.clone()], 1, 0);
+
+        assert_eq!(
+            session.add_approval(request.approve(&outsider, &outsider_keys.private)),
+            Err(CustodianshipError::NotACustodian(outsider))
+        );
+        assert_eq!(
+            session.add_approval(request.approve(&custodian, &outsider_keys.private)),
+            Err(CustodianshipError::InvalidApproval(custodian.clone()))
+        );
+        assert!(matches!(
+            session.build_recovery_tx(Timestamp::new(10)),
+            Err(CustodianshipError::InsufficientApprovals { have: 0, need: 1 })
+        ));
+        session
+            .add_approval(request.approve(&custodian, &custodian_keys.private))
+            .unwrap();
+        assert!(session.build_recovery_tx(Timestamp::new(10)).is_ok());
+    }
 }
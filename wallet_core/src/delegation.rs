@@ -1,31 +1,18 @@
-//! Delegation management — delegate and revoke voting power.
+//! Delegation management — delegate, rotate delegation keys, and revoke.
 
 use crate::error::WalletError;
+use burst_transactions::delegate::{DelegateTx, DelegationKey, RotateDelegationKeyTx};
 use burst_types::{PrivateKey, Signature, WalletAddress};
 
-/// Delegate voting power to a representative.
-///
-/// Generates a delegation key pair, encrypts the private key for the delegate
-/// using X25519 Diffie-Hellman, and builds the delegation transaction.
-/// The transaction must be signed externally by the delegator's primary key.
-///
-/// Key derivation uses the standard Ed25519-to-X25519 conversion:
-/// - Delegator: X25519 secret derived from their Ed25519 private key via `to_scalar_bytes()`
-/// - Delegate: X25519 public derived from their Ed25519 public key via `to_montgomery()`
+/// Generate a delegation key pair and encrypt its private half for `delegate`.
 ///
-/// The delegator's X25519 public key is included in the transaction so the
-/// delegate can reconstruct the DH shared secret and decrypt.
-pub fn create_delegation(
-    delegator: &WalletAddress,
-    delegate: &WalletAddress,
-    delegator_private: &PrivateKey,
-) -> Result<burst_transactions::delegate::DelegateTx, WalletError> {
+/// The private key is encrypted with X25519 Diffie-Hellman between a one-time
+/// sender key and the delegate's X25519 key (converted from their Ed25519
+/// public key via `to_montgomery()`). The one-time key's public half travels
+/// with the ciphertext, and since it is fresh for every key generation the
+/// derived encryption key and nonce never repeat across rotations.
+fn issue_delegation_key(delegate: &WalletAddress) -> Result<DelegationKey, WalletError> {
     let delegation_keys = burst_crypto::generate_keypair();
-    let delegation_public_key = delegation_keys.public.as_bytes().to_vec();
-
-    let delegator_x25519_secret = burst_crypto::ed25519_private_to_x25519(&delegator_private.0);
-    let delegator_x25519_pub =
-        x25519_dalek::PublicKey::from(&x25519_dalek::StaticSecret::from(delegator_x25519_secret));
 
     let delegate_ed25519_pub = burst_crypto::decode_address(delegate.as_str())
         .ok_or(WalletError::InvalidAddress(delegate.as_str().to_string()))?;
@@ -35,58 +22,106 @@ pub fn create_delegation(
         delegate.as_str()
     )))?;
 
-    let encrypted_delegation_key = burst_crypto::encrypt_delegation_key(
+    let sender_secret = x25519_dalek::StaticSecret::random_from_rng(rand::rngs::OsRng);
+    let sender_public = x25519_dalek::PublicKey::from(&sender_secret);
+    let encrypted_private_key = burst_crypto::encrypt_delegation_key(
         &delegation_keys.private.0,
         &delegate_x25519_pub_bytes,
-        &delegator_x25519_secret,
+        &sender_secret.to_bytes(),
     );
 
+    Ok(DelegationKey {
+        public_key: delegation_keys.public,
+        encrypted_private_key,
+        sender_x25519_public: *sender_public.as_bytes(),
+    })
+}
+
+/// Delegate voting power to a representative.
+///
+/// Generates a delegation key pair, encrypts the private key for the delegate
+/// and builds the delegation transaction. The transaction must be signed
+/// externally by the delegator's primary key.
+pub fn create_delegation(
+    delegator: &WalletAddress,
+    delegate: &WalletAddress,
+) -> Result<DelegateTx, WalletError> {
+    let key = issue_delegation_key(delegate)?;
+
     let tx_bytes = burst_crypto::blake2b_256_multi(&[
         b"delegate",
         delegator.as_str().as_bytes(),
         delegate.as_str().as_bytes(),
-        &delegation_public_key,
+        key.public_key.as_bytes(),
+    ]);
+    let hash = burst_types::TxHash::new(tx_bytes);
+
+    Ok(DelegateTx {
+        hash,
+        delegator: delegator.clone(),
+        delegate: delegate.clone(),
+        key,
+        timestamp: burst_types::Timestamp::now(),
+        work: 0,
+        signature: Signature([0u8; 64]),
+    })
+}
+
+/// Rotate the delegation key of an active delegation to `delegate`.
+///
+/// A fresh key pair is generated and encrypted for the same delegate; once
+/// the transaction is confirmed the previous key stops being accepted. Must
+/// be signed externally by the delegator's primary key.
+pub fn rotate_delegation_key(
+    delegator: &WalletAddress,
+    delegate: &WalletAddress,
+) -> Result<RotateDelegationKeyTx, WalletError> {
+    let key = issue_delegation_key(delegate)?;
+
+    let tx_bytes = burst_crypto::blake2b_256_multi(&[
+        b"rotate-delegation-key",
+        delegator.as_str().as_bytes(),
+        delegate.as_str().as_bytes(),
+        key.public_key.as_bytes(),
     ]);
     let hash = burst_types::TxHash::new(tx_bytes);
 
-    Ok(burst_transactions::delegate::DelegateTx {
+    Ok(RotateDelegationKeyTx {
         hash,
         delegator: delegator.clone(),
         delegate: delegate.clone(),
-        delegation_public_key,
-        encrypted_delegation_key,
-        delegator_x25519_public: delegator_x25519_pub.as_bytes().to_vec(),
+        key,
         timestamp: burst_types::Timestamp::now(),
         work: 0,
         signature: Signature([0u8; 64]),
     })
 }
 
-/// Decrypt the delegation private key from a received DelegateTx.
+/// Decrypt a delegation private key received on a Delegate or
+/// RotateDelegationKey transaction.
 ///
 /// The delegate uses their own Ed25519 private key to derive their X25519
-/// secret, then performs DH with the delegator's X25519 public key
-/// (included in the transaction) to recover the encryption key.
+/// secret, then performs DH with the sender's X25519 public key to recover
+/// the encryption key. The decrypted key must match the announced public key.
 pub fn receive_delegation(
-    tx: &burst_transactions::delegate::DelegateTx,
+    key: &DelegationKey,
     delegate_private: &PrivateKey,
 ) -> Result<[u8; 32], WalletError> {
     let delegate_x25519_secret = burst_crypto::ed25519_private_to_x25519(&delegate_private.0);
 
-    if tx.delegator_x25519_public.len() != 32 {
+    let private = burst_crypto::decrypt_delegation_key(
+        &key.encrypted_private_key,
+        &key.sender_x25519_public,
+        &delegate_x25519_secret,
+    )
+    .map_err(|e| WalletError::Key(format!("delegation decryption failed: {e}")))?;
+
+    if burst_crypto::public_from_private(&PrivateKey(private)) != key.public_key {
         return Err(WalletError::Key(
-            "delegation tx missing delegator X25519 public key".into(),
+            "decrypted delegation key does not match its public key".into(),
         ));
     }
-    let mut delegator_x25519_pub = [0u8; 32];
-    delegator_x25519_pub.copy_from_slice(&tx.delegator_x25519_public);
-
-    burst_crypto::decrypt_delegation_key(
-        &tx.encrypted_delegation_key,
-        &delegator_x25519_pub,
-        &delegate_x25519_secret,
-    )
-    .map_err(|e| WalletError::Key(format!("delegation decryption failed: {e}")))
+    Ok(private)
 }
 
 /// Revoke a delegation by generating a new delegation public key.
@@ -132,16 +167,17 @@ mod tests {
         let delegator_addr = derive_address(&delegator_kp.public);
         let delegate_addr = derive_address(&delegate_kp.public);
 
-        let tx = create_delegation(&delegator_addr, &delegate_addr, &delegator_kp.private)
+        let tx = create_delegation(&delegator_addr, &delegate_addr)
             .expect("create_delegation should succeed");
 
         assert_eq!(tx.delegator, delegator_addr);
         assert_eq!(tx.delegate, delegate_addr);
-        assert!(!tx.delegation_public_key.is_empty());
-        assert!(!tx.encrypted_delegation_key.is_empty());
-        assert_eq!(tx.delegator_x25519_public.len(), 32);
+        assert_eq!(
+            tx.key.encrypted_private_key.len(),
+            burst_transactions::delegate::ENCRYPTED_DELEGATION_KEY_LEN
+        );
 
-        let decrypted = receive_delegation(&tx, &delegate_kp.private)
+        let decrypted = receive_delegation(&tx.key, &delegate_kp.private)
             .expect("receive_delegation should succeed");
         assert_eq!(decrypted.len(), 32);
         assert_ne!(decrypted, [0u8; 32], "decrypted key should be non-zero");
@@ -155,10 +191,10 @@ mod tests {
         let delegator_addr = derive_address(&delegator_kp.public);
         let delegate_addr = derive_address(&delegate_kp.public);
 
-        let tx = create_delegation(&delegator_addr, &delegate_addr, &delegator_kp.private)
+        let tx = create_delegation(&delegator_addr, &delegate_addr)
             .expect("create_delegation should succeed");
 
-        let result = receive_delegation(&tx, &wrong_kp.private);
+        let result = receive_delegation(&tx.key, &wrong_kp.private);
         assert!(result.is_err(), "wrong private key should fail decryption");
     }
 
@@ -169,16 +205,14 @@ mod tests {
         let delegator_addr = derive_address(&delegator_kp.public);
         let delegate_addr = derive_address(&delegate_kp.public);
 
-        let tx1 =
-            create_delegation(&delegator_addr, &delegate_addr, &delegator_kp.private).unwrap();
-        let tx2 =
-            create_delegation(&delegator_addr, &delegate_addr, &delegator_kp.private).unwrap();
+        let tx1 = create_delegation(&delegator_addr, &delegate_addr).unwrap();
+        let tx2 = create_delegation(&delegator_addr, &delegate_addr).unwrap();
 
         // Delegation keys are randomly generated each time, so encrypted keys differ
-        assert_ne!(tx1.encrypted_delegation_key, tx2.encrypted_delegation_key);
+        assert_ne!(tx1.key.encrypted_private_key, tx2.key.encrypted_private_key);
         // But both should be decryptable by the delegate
-        let k1 = receive_delegation(&tx1, &delegate_kp.private).unwrap();
-        let k2 = receive_delegation(&tx2, &delegate_kp.private).unwrap();
+        let k1 = receive_delegation(&tx1.key, &delegate_kp.private).unwrap();
+        let k2 = receive_delegation(&tx2.key, &delegate_kp.private).unwrap();
         assert_ne!(k1, k2, "different delegation key pairs each time");
     }
 
@@ -201,7 +235,40 @@ mod tests {
         let delegator_addr = derive_address(&delegator_kp.public);
         let bad_addr = WalletAddress::new("brst_invalid_not_a_real_address");
 
-        let result = create_delegation(&delegator_addr, &bad_addr, &delegator_kp.private);
+        let result = create_delegation(&delegator_addr, &bad_addr);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn rotation_issues_fresh_key_for_same_delegate() {
+        let delegator_addr = derive_address(&make_kp(0x55).public);
+        let delegate_kp = make_kp(0x66);
+        let delegate_addr = derive_address(&delegate_kp.public);
+
+        let original = create_delegation(&delegator_addr, &delegate_addr).unwrap();
+        let rotated = rotate_delegation_key(&delegator_addr, &delegate_addr).unwrap();
+
+        assert_eq!(rotated.delegate, delegate_addr);
+        assert_ne!(rotated.key.public_key, original.key.public_key);
+        assert_ne!(
+            rotated.key.sender_x25519_public, original.key.sender_x25519_public,
+            "each generation is encrypted from a fresh sender key"
+        );
+        let private = receive_delegation(&rotated.key, &delegate_kp.private).unwrap();
+        assert_eq!(
+            burst_crypto::public_from_private(&PrivateKey(private)),
+            rotated.key.public_key
+        );
+    }
+
+    #[test]
+    fn receive_rejects_mismatched_public_key() {
+        let delegator_addr = derive_address(&make_kp(0x77).public);
+        let delegate_kp = make_kp(0x88);
+        let delegate_addr = derive_address(&delegate_kp.public);
+
+        let mut tx = create_delegation(&delegator_addr, &delegate_addr).unwrap();
+        tx.key.public_key = make_kp(0x99).public;
+        assert!(receive_delegation(&tx.key, &delegate_kp.private).is_err());
+    }
 }
@@ -0,0 +1,134 @@
+//! Deposit tags — many depositors, one receiving account.
+//!
+//! An exchange gives each customer a destination tag instead of an account
+//! chain of their own. Payers attach the tag to their send (it travels in a
+//! v2 block extension), and the exchange credits whichever customer the tag
+//! names.
+//!
+//! Tags are derived from the receiving account, so the exchange needs no
+//! tag table: the upper 48 bits are the customer number and the lower 16
+//! bits a checksum keyed by the receiving address. A mistyped tag, or one
+//! issued for a different account, fails the checksum; the receiver bounces
+//! such transfers (and untagged ones) with a RejectReceive rather than
+//! crediting the wrong customer.
+
+use burst_transactions::reject_receive::RejectReason;
+use burst_types::WalletAddress;
+
+use crate::error::WalletError;
+use crate::receive_gate::GateDecision;
+
+/// Largest customer number a tag can carry.
+pub const MAX_CUSTOMER_ID: u64 = (1 << 48) - 1;
+
+/// Issues and checks the deposit tags of one receiving account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DepositTags {
+    account: WalletAddress,
+}
+
+impl DepositTags {
+    /// Tags for deposits into `account`.
+    pub fn new(account: WalletAddress) -> Self {
+        Self { account }
+    }
+
+    /// The receiving account.
+    pub fn account(&self) -> &WalletAddress {
+        &self.account
+    }
+
+    /// The tag for `customer`. Fails above [`MAX_CUSTOMER_ID`].
+    pub fn tag_for(&self, customer: u64) -> Result<u64, WalletError> {
+        if customer > MAX_CUSTOMER_ID {
+            return Err(WalletError::InvalidDepositTag(format!(
+                "customer {customer} is above the maximum {MAX_CUSTOMER_ID}"
+            )));
+        }
+        Ok(customer << 16 | u64::from(self.checksum(customer)))
+    }
+
+    /// The customer a tag was issued for, or `None` if its checksum does
+    /// not match this account.
+    pub fn customer_for(&self, tag: u64) -> Option<u64> {
+        let customer = tag >> 16;
+        (tag as u16 == self.checksum(customer)).then_some(customer)
+    }
+
+    /// Whether to receive a pending transfer carrying `destination_tag`.
+    /// Untagged transfers and tags not issued for this account are
+    /// rejected as policy violations.
+    pub fn decide(&self, destination_tag: Option<u64>) -> GateDecision {
+        let reject = |detail: String| GateDecision::Reject {
+            reason: RejectReason::PolicyViolation,
+            detail,
+        };
+        match destination_tag {
+            None => reject("deposit carries no destination tag".into()),
+            Some(tag) if self.customer_for(tag).is_none() => reject(format!(
+                "destination tag {tag} was not issued by this account"
+            )),
+            Some(_) => GateDecision::Accept,
+        }
+    }
+
+    fn checksum(&self, customer: u64) -> u16 {
+        let digest = burst_crypto::blake2b_256_multi(&[
+            b"burst-deposit-tag",
+            self.account.as_str().as_bytes(),
+            &customer.to_be_bytes(),
+        ]);
+        u16::from_be_bytes([digest[0], digest[1]])
+    }
+}
+
+/// Parse a tag as written by users and payment URIs: a decimal integer,
+/// surrounding whitespace allowed.
+pub fn parse_tag(text: &str) -> Result<u64, WalletError> {
+    let text = text.trim();
+    if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(WalletError::InvalidDepositTag(format!(
+            "{text:?} is not a decimal number"
+        )));
+    }
+    text.parse()
+        .map_err(|e| WalletError::InvalidDepositTag(format!("{text:?}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_round_trip_and_are_bound_to_the_account() {
+        let exchange = DepositTags::new(WalletAddress::new("brst_exchange"));
+        let other = DepositTags::new(WalletAddress::new("brst_other"));
+
+        for customer in [0, 1, 42, MAX_CUSTOMER_ID] {
+            let tag = exchange.tag_for(customer).unwrap();
+            assert_eq!(exchange.customer_for(tag), Some(customer));
+            assert_eq!(exchange.decide(Some(tag)), GateDecision::Accept);
+            assert_eq!(parse_tag(&tag.to_string()).unwrap(), tag);
+        }
+        assert!(exchange.tag_for(MAX_CUSTOMER_ID + 1).is_err());
+
+        let tag = exchange.tag_for(42).unwrap();
+        assert_eq!(other.customer_for(tag), None);
+        assert_eq!(exchange.customer_for(tag ^ 1), None);
+        assert!(matches!(
+            exchange.decide(None),
+            GateDecision::Reject {
+                reason: RejectReason::PolicyViolation,
+                ..
+            }
+        ));
+        assert!(matches!(
+            other.decide(Some(tag)),
+            GateDecision::Reject { .. }
+        ));
+
+        for bad in ["", "-1", "1e3", "0x10", "18446744073709551616"] {
+            assert!(parse_tag(bad).is_err(), "{bad}");
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Encrypted direct messages between wallets.
+//!
+//! Verifiers and the candidate they are verifying coordinate the session
+//! (when to meet, over which channel) with short messages sealed to the
+//! recipient's key. Nodes hold them in memory for the recipient until they
+//! expire and relay them to their peers; nothing reaches the ledger.
+
+use burst_crypto::DirectMessage;
+use burst_types::{Signature, Timestamp, WalletAddress};
+use serde::Deserialize;
+
+use crate::error::WalletError;
+use crate::wallet::Wallet;
+
+/// How long a sent message is held for the recipient by default: one hour.
+pub const DEFAULT_MESSAGE_TTL_SECS: u64 = 60 * 60;
+
+/// A direct message opened by its recipient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceivedMessage {
+    /// Hex ID of the message, the same on every node.
+    pub id: String,
+    pub sender: WalletAddress,
+    pub body: Vec<u8>,
+    /// Unix seconds, as claimed by the sender.
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+/// Response from the `dm_send` RPC.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DmSendResult {
+    pub id: String,
+    /// Peers the node passed the message on to.
+    pub relayed: usize,
+}
+
+/// The `dm_send` parameters for `message`.
+pub(crate) fn to_json(message: &DirectMessage) -> serde_json::Value {
+    serde_json::json!({
+        "sender": message.sender.as_str(),
+        "recipient": message.recipient.as_str(),
+        "ephemeral_public": hex::encode(message.ephemeral_public),
+        "ciphertext": hex::encode(&message.ciphertext),
+        "created_at": message.created_at,
+        "expires_at": message.expires_at,
+        "signature": hex::encode(message.signature.0),
+    })
+}
+
+/// A message as listed by `dm_fetch`.
+pub(crate) fn from_json(value: &serde_json::Value) -> Result<DirectMessage, WalletError> {
+    let invalid = |field: &str| WalletError::Node(format!("invalid direct message {field}"));
+    let text = |field: &str| {
+        value
+            .get(field)
+            .and_then(|v| v.as_str())
+            .ok_or(invalid(field))
+    };
+    let number = |field: &str| {
+        value
+            .get(field)
+            .and_then(|v| v.as_u64())
+            .ok_or(invalid(field))
+    };
+    let bytes = |field: &str| text(field).and_then(|s| hex::decode(s).map_err(|_| invalid(field)));
+
+    Ok(DirectMessage {
+        sender: WalletAddress::new(text("sender")?),
+        recipient: WalletAddress::new(text("recipient")?),
+        ephemeral_public: bytes("ephemeral_public")?
+            .try_into()
+            .map_err(|_| invalid("ephemeral_public"))?,
+        ciphertext: bytes("ciphertext")?,
+        created_at: number("created_at")?,
+        expires_at: number("expires_at")?,
+        signature: Signature(
+            bytes("signature")?
+                .try_into()
+                .map_err(|_| invalid("signature"))?,
+        ),
+    })
+}
+
+impl Wallet {
+    /// Seal `body` for `recipient`, signed by this wallet and held by nodes
+    /// for `ttl_secs`.
+    pub fn seal_direct_message(
+        &self,
+        recipient: &WalletAddress,
+        body: &[u8],
+        ttl_secs: u64,
+    ) -> Result<DirectMessage, WalletError> {
+        let now = Timestamp::now().as_secs();
+        DirectMessage::seal(
+            &self.primary_keys,
+            recipient,
+            body,
+            now,
+            now.saturating_add(ttl_secs),
+        )
+        .map_err(|e| WalletError::InvalidAddress(e.to_string()))
+    }
+
+    /// Check and decrypt a message addressed to this wallet.
+    pub fn open_direct_message(
+        &self,
+        message: &DirectMessage,
+    ) -> Result<ReceivedMessage, WalletError> {
+        let body = message
+            .open(&self.primary_keys.private)
+            .map_err(|e| WalletError::Key(e.to_string()))?;
+        Ok(ReceivedMessage {
+            id: hex::encode(message.id()),
+            sender: message.sender.clone(),
+            body,
+            created_at: message.created_at,
+            expires_at: message.expires_at,
+        })
+    }
+
+    /// Send `body` to `recipient` through the connected node.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn send_direct_message(
+        &self,
+        recipient: &WalletAddress,
+        body: &[u8],
+        ttl_secs: u64,
+    ) -> Result<DmSendResult, WalletError> {
+        let client = self.node_client().ok_or(WalletError::NoNodeConnection)?;
+        let message = self.seal_direct_message(recipient, body, ttl_secs)?;
+        client.dm_send(&message).await
+    }
+
+    /// Messages the connected node holds for this wallet, opened, oldest
+    /// first. Messages that do not open (forged or corrupted) are left out.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn direct_messages(&self) -> Result<Vec<ReceivedMessage>, WalletError> {
+        let client = self.node_client().ok_or(WalletError::NoNodeConnection)?;
+        let messages = client.dm_fetch(self.address.as_str()).await?;
+        Ok(messages
+            .iter()
+            .filter_map(|message| self.open_direct_message(message).ok())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_survive_the_rpc_encoding() {
+        let verifier = Wallet::create().unwrap();
+        let candidate = Wallet::create().unwrap();
+
+        let sealed = verifier
+            .seal_direct_message(&candidate.address, b"call at 18:00?", 600)
+            .unwrap();
+        assert_eq!(sealed.expires_at - sealed.created_at, 600);
+        let fetched = from_json(&to_json(&sealed)).unwrap();
+        assert_eq!(fetched, sealed);
+
+        let opened = candidate.open_direct_message(&fetched).unwrap();
+        assert_eq!(opened.sender, verifier.address);
+        assert_eq!(opened.body, b"call at 18:00?");
+        assert!(verifier.open_direct_message(&fetched).is_err());
+    }
+}
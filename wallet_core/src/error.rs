@@ -1,3 +1,4 @@
+use burst_types::ErrorCode;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -26,9 +27,40 @@ pub enum WalletError {
     #[error("node RPC error: {0}")]
     Node(String),
 
+    /// The node refused the request with a registered error code. `data`
+    /// holds the error object's details, such as the balance required.
+    #[error("node error {}: {message}", code.code())]
+    Rpc {
+        code: ErrorCode,
+        message: String,
+        data: Option<serde_json::Value>,
+    },
+
+    #[error("invalid payment request: {0}")]
+    InvalidPaymentRequest(String),
+
+    #[error("invalid deposit tag: {0}")]
+    InvalidDepositTag(String),
+
+    #[error("invalid QR payload: {0}")]
+    InvalidQrPayload(String),
+
+    #[error("invalid block proof: {0}")]
+    InvalidProof(String),
+
     #[error("no node connection configured")]
     NoNodeConnection,
 
     #[error("{0}")]
     Other(String),
 }
+
+impl WalletError {
+    /// The node's error code, for errors the node reported with one.
+    pub fn node_error_code(&self) -> Option<ErrorCode> {
+        match self {
+            Self::Rpc { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,482 @@
+//! Invoices tracked against incoming pending entries.
+//!
+//! An [`Invoice`] wraps a [`PaymentRequest`] with payment state. The
+//! [`InvoiceTracker`] consumes `pending` events from the node's WebSocket
+//! (`account_update` topic) — or pending entries polled via RPC — and
+//! credits matching payments to open invoices, so point-of-sale flows can
+//! be built directly on top of the wallet crate.
+//!
+//! A payment matches an invoice when it is destined to the invoice address,
+//! carries the invoice's destination tag (or none, if the invoice has none)
+//! and, if the invoice carries a memo, the send block carries the same memo.
+
+use std::collections::{HashMap, HashSet};
+
+use burst_types::{Timestamp, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+use crate::error::WalletError;
+use crate::payment_uri::PaymentRequest;
+
+/// Payment state of an invoice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InvoiceStatus {
+    /// No matching payment received yet.
+    Open,
+    /// Some, but not all, of the requested amount has arrived.
+    PartiallyPaid,
+    /// The requested amount (or any amount, for open-amount invoices) arrived.
+    Paid,
+    /// The expiry passed before the invoice was fully paid.
+    Expired,
+    /// Cancelled by the merchant.
+    Cancelled,
+}
+
+impl InvoiceStatus {
+    /// Whether the invoice can still accept payments.
+    pub fn is_open(&self) -> bool {
+        matches!(self, Self::Open | Self::PartiallyPaid)
+    }
+}
+
+/// An incoming transfer observed on the node (a pending entry).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IncomingPayment {
+    /// Hash of the send block that created the pending entry.
+    pub block_hash: String,
+    /// Sender account.
+    pub source: String,
+    /// Receiving account.
+    pub destination: WalletAddress,
+    /// Amount in raw TRST units.
+    pub amount: u128,
+    /// Memo carried by the send block, if any.
+    pub memo: Option<Vec<u8>>,
+    /// Destination tag carried by the send block, if any.
+    pub destination_tag: Option<u64>,
+}
+
+impl IncomingPayment {
+    /// Extract a payment from a node WebSocket event.
+    ///
+    /// Returns `None` unless the event is an `account_update` with
+    /// `change_type == "pending"` and well-formed fields.
+    pub fn from_ws_event(event: &serde_json::Value) -> Option<Self> {
+        if event.get("topic")?.as_str()? != "account_update" {
+            return None;
+        }
+        let data = event.get("data")?;
+        if data.get("change_type")?.as_str()? != "pending" {
+            return None;
+        }
+        let destination = data.get("account")?.as_str()?;
+        if !destination.starts_with(WalletAddress::PREFIX) {
+            return None;
+        }
+        let memo = match data.get("memo").and_then(|m| m.as_str()) {
+            Some(hex_memo) => Some(hex::decode(hex_memo).ok()?),
+            None => None,
+        };
+        Some(Self {
+            block_hash: data.get("block_hash")?.as_str()?.to_string(),
+            source: data.get("source")?.as_str()?.to_string(),
+            destination: WalletAddress::new(destination),
+            amount: data.get("amount")?.as_str()?.parse().ok()?,
+            memo,
+            destination_tag: match data.get("destination_tag") {
+                Some(tag) => Some(tag.as_u64()?),
+                None => None,
+            },
+        })
+    }
+
+    /// Build a payment from an `account_pending` RPC entry, for polling
+    /// reconciliation when the WebSocket feed was unavailable.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_pending_block(
+        destination: &WalletAddress,
+        block: &crate::wallet::PendingBlock,
+    ) -> Option<Self> {
+        let memo = match block.memo {
+            Some(ref hex_memo) => Some(hex::decode(hex_memo).ok()?),
+            None => None,
+        };
+        Some(Self {
+            block_hash: block.hash.clone(),
+            source: block.source.clone(),
+            destination: destination.clone(),
+            amount: block.amount.parse().ok()?,
+            memo,
+            destination_tag: block.destination_tag,
+        })
+    }
+}
+
+/// An invoice: a payment request plus its payment state.
+#[derive(Clone, Debug)]
+pub struct Invoice {
+    /// Stable identifier derived from the request and creation time.
+    pub id: String,
+    pub request: PaymentRequest,
+    pub created_at: Timestamp,
+    pub status: InvoiceStatus,
+    /// Total amount credited so far.
+    pub received: u128,
+    /// Send block hashes credited to this invoice.
+    pub payments: Vec<String>,
+}
+
+impl Invoice {
+    /// Create a new open invoice for `request`.
+    pub fn new(request: PaymentRequest, created_at: Timestamp) -> Self {
+        let id = hex::encode(burst_crypto::blake2b_256_multi(&[
+            b"invoice",
+            request.to_uri().as_bytes(),
+            &created_at.as_secs().to_be_bytes(),
+        ]));
+        Self {
+            id,
+            request,
+            created_at,
+            status: InvoiceStatus::Open,
+            received: 0,
+            payments: Vec::new(),
+        }
+    }
+
+    /// Whether `payment` is addressed to this invoice.
+    pub fn matches(&self, payment: &IncomingPayment) -> bool {
+        if payment.destination != self.request.address
+            || payment.destination_tag != self.request.destination_tag
+        {
+            return false;
+        }
+        match self.request.memo {
+            Some(ref memo) => payment.memo.as_ref() == Some(memo),
+            None => true,
+        }
+    }
+
+    /// Credit a matching payment. Returns `true` if the invoice state changed.
+    fn credit(&mut self, payment: &IncomingPayment) -> bool {
+        if !self.status.is_open() || self.payments.contains(&payment.block_hash) {
+            return false;
+        }
+        self.payments.push(payment.block_hash.clone());
+        self.received = self.received.saturating_add(payment.amount);
+        self.status = match self.request.amount {
+            Some(requested) if self.received < requested => InvoiceStatus::PartiallyPaid,
+            _ => InvoiceStatus::Paid,
+        };
+        true
+    }
+}
+
+/// A state change emitted by the tracker.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvoiceUpdate {
+    pub invoice_id: String,
+    pub status: InvoiceStatus,
+    pub received: u128,
+    /// Send block that triggered the update (`None` for expiry).
+    pub block_hash: Option<String>,
+}
+
+/// Tracks a set of invoices against incoming payments.
+#[derive(Default)]
+pub struct InvoiceTracker {
+    invoices: HashMap<String, Invoice>,
+}
+
+impl InvoiceTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new invoice and return its id.
+    pub fn create(&mut self, request: PaymentRequest, now: Timestamp) -> String {
+        let invoice = Invoice::new(request, now);
+        let id = invoice.id.clone();
+        self.invoices.insert(id.clone(), invoice);
+        id
+    }
+
+    /// Look up an invoice by id.
+    pub fn get(&self, id: &str) -> Option<&Invoice> {
+        self.invoices.get(id)
+    }
+
+    /// All tracked invoices.
+    pub fn invoices(&self) -> impl Iterator<Item = &Invoice> {
+        self.invoices.values()
+    }
+
+    /// Cancel an open invoice.
+    pub fn cancel(&mut self, id: &str) -> Result<(), WalletError> {
+        let invoice = self
+            .invoices
+            .get_mut(id)
+            .ok_or_else(|| WalletError::Other(format!("unknown invoice: {id}")))?;
+        if !invoice.status.is_open() {
+            return Err(WalletError::Other(format!(
+                "invoice {id} is already {:?}",
+                invoice.status
+            )));
+        }
+        invoice.status = InvoiceStatus::Cancelled;
+        Ok(())
+    }
+
+    /// Accounts with at least one open invoice — the set to subscribe to.
+    pub fn watched_accounts(&self) -> Vec<String> {
+        let set: HashSet<&str> = self
+            .invoices
+            .values()
+            .filter(|i| i.status.is_open())
+            .map(|i| i.request.address.as_str())
+            .collect();
+        let mut accounts: Vec<String> = set.into_iter().map(String::from).collect();
+        accounts.sort();
+        accounts
+    }
+
+    /// The WebSocket subscribe message for all watched accounts.
+    pub fn subscribe_message(&self) -> serde_json::Value {
+        serde_json::json!({
+            "action": "subscribe",
+            "topic": "account_update",
+            "filter": { "accounts": self.watched_accounts() },
+        })
+    }
+
+    /// Apply an incoming payment. Expired invoices are closed first so a
+    /// late payment is never credited.
+    ///
+    /// A payment is credited to at most one invoice: memo matches take
+    /// precedence over memo-less invoices for the same address, and ties
+    /// go to the oldest invoice.
+    pub fn apply_payment(
+        &mut self,
+        payment: &IncomingPayment,
+        now: Timestamp,
+    ) -> Vec<InvoiceUpdate> {
+        let mut updates = self.expire(now);
+
+        let target = self
+            .invoices
+            .values()
+            .filter(|i| i.status.is_open() && i.matches(payment))
+            .min_by_key(|i| (i.request.memo.is_none(), i.created_at, i.id.clone()))
+            .map(|i| i.id.clone());
+
+        if let Some(id) = target {
+            if let Some(invoice) = self.invoices.get_mut(&id) {
+                if invoice.credit(payment) {
+                    updates.push(InvoiceUpdate {
+                        invoice_id: id,
+                        status: invoice.status,
+                        received: invoice.received,
+                        block_hash: Some(payment.block_hash.clone()),
+                    });
+                }
+            }
+        }
+        updates
+    }
+
+    /// Parse and apply a raw WebSocket message. Non-pending events are ignored.
+    pub fn handle_ws_message(&mut self, text: &str, now: Timestamp) -> Vec<InvoiceUpdate> {
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(text) else {
+            return Vec::new();
+        };
+        match IncomingPayment::from_ws_event(&value) {
+            Some(payment) => self.apply_payment(&payment, now),
+            None => Vec::new(),
+        }
+    }
+
+    /// Mark open invoices whose expiry has passed as expired.
+    pub fn expire(&mut self, now: Timestamp) -> Vec<InvoiceUpdate> {
+        let mut updates = Vec::new();
+        for invoice in self.invoices.values_mut() {
+            if invoice.status.is_open() && invoice.request.is_expired(now) {
+                invoice.status = InvoiceStatus::Expired;
+                updates.push(InvoiceUpdate {
+                    invoice_id: invoice.id.clone(),
+                    status: InvoiceStatus::Expired,
+                    received: invoice.received,
+                    block_hash: None,
+                });
+            }
+        }
+        updates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merchant() -> WalletAddress {
+        let kp = burst_crypto::keypair_from_seed(&[3u8; 32]);
+        burst_crypto::derive_address(&kp.public)
+    }
+
+    fn payment(hash: &str, amount: u128, memo: Option<&[u8]>) -> IncomingPayment {
+        IncomingPayment {
+            block_hash: hash.to_string(),
+            source: "brst_payer".to_string(),
+            destination: merchant(),
+            amount,
+            memo: memo.map(|m| m.to_vec()),
+            destination_tag: None,
+        }
+    }
+
+    #[test]
+    fn memo_invoice_paid_in_full() {
+        let mut tracker = InvoiceTracker::new();
+        let request = PaymentRequest::new(merchant())
+            .with_amount(100)
+            .unwrap()
+            .with_memo(b"order-1".to_vec())
+            .unwrap();
+        let id = tracker.create(request, Timestamp::new(10));
+
+        // Wrong memo is ignored
+        assert!(tracker
+            .apply_payment(&payment("h0", 100, Some(b"order-2")), Timestamp::new(11))
+            .is_empty());
+
+        let updates =
+            tracker.apply_payment(&payment("h1", 40, Some(b"order-1")), Timestamp::new(12));
+        assert_eq!(updates[0].status, InvoiceStatus::PartiallyPaid);
+
+        // Replayed event does not double-credit
+        assert!(tracker
+            .apply_payment(&payment("h1", 40, Some(b"order-1")), Timestamp::new(12))
+            .is_empty());
+
+        let updates =
+            tracker.apply_payment(&payment("h2", 60, Some(b"order-1")), Timestamp::new(13));
+        assert_eq!(updates[0].status, InvoiceStatus::Paid);
+        assert_eq!(tracker.get(&id).unwrap().received, 100);
+        assert_eq!(tracker.get(&id).unwrap().payments, vec!["h1", "h2"]);
+    }
+
+    #[test]
+    fn memo_match_preferred_over_open_invoice() {
+        let mut tracker = InvoiceTracker::new();
+        let open_id = tracker.create(PaymentRequest::new(merchant()), Timestamp::new(1));
+        let memo_id = tracker.create(
+            PaymentRequest::new(merchant())
+                .with_memo(b"x".to_vec())
+                .unwrap(),
+            Timestamp::new(2),
+        );
+
+        let updates = tracker.apply_payment(&payment("h1", 5, Some(b"x")), Timestamp::new(3));
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].invoice_id, memo_id);
+        assert_eq!(tracker.get(&open_id).unwrap().status, InvoiceStatus::Open);
+    }
+
+    #[test]
+    fn tagged_invoice_needs_the_same_tag() {
+        let mut tracker = InvoiceTracker::new();
+        let id = tracker.create(
+            PaymentRequest::new(merchant())
+                .with_amount(10)
+                .unwrap()
+                .with_destination_tag(42),
+            Timestamp::new(1),
+        );
+
+        let untagged = payment("h1", 10, None);
+        let mut wrong_tag = payment("h2", 10, None);
+        wrong_tag.destination_tag = Some(7);
+        assert!(tracker
+            .apply_payment(&untagged, Timestamp::new(2))
+            .is_empty());
+        assert!(tracker
+            .apply_payment(&wrong_tag, Timestamp::new(2))
+            .is_empty());
+        assert_eq!(tracker.get(&id).unwrap().status, InvoiceStatus::Open);
+
+        let mut tagged = payment("h3", 10, None);
+        tagged.destination_tag = Some(42);
+        assert_eq!(tracker.apply_payment(&tagged, Timestamp::new(3)).len(), 1);
+        assert_eq!(tracker.get(&id).unwrap().status, InvoiceStatus::Paid);
+    }
+
+    #[test]
+    fn expired_invoice_not_credited() {
+        let mut tracker = InvoiceTracker::new();
+        let id = tracker.create(
+            PaymentRequest::new(merchant()).with_expiry(Timestamp::new(50)),
+            Timestamp::new(1),
+        );
+        let updates = tracker.apply_payment(&payment("h1", 5, None), Timestamp::new(60));
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].status, InvoiceStatus::Expired);
+        assert_eq!(tracker.get(&id).unwrap().received, 0);
+        assert!(tracker.watched_accounts().is_empty());
+    }
+
+    #[test]
+    fn cancel_only_open_invoices() {
+        let mut tracker = InvoiceTracker::new();
+        let id = tracker.create(PaymentRequest::new(merchant()), Timestamp::new(1));
+        tracker.cancel(&id).unwrap();
+        assert!(tracker.cancel(&id).is_err());
+        assert!(tracker.cancel("missing").is_err());
+        assert!(tracker
+            .apply_payment(&payment("h1", 5, None), Timestamp::new(2))
+            .is_empty());
+    }
+
+    #[test]
+    fn handles_node_ws_pending_event() {
+        let mut tracker = InvoiceTracker::new();
+        let id = tracker.create(
+            PaymentRequest::new(merchant())
+                .with_amount(10)
+                .unwrap()
+                .with_memo(b"pos".to_vec())
+                .unwrap(),
+            Timestamp::new(1),
+        );
+        let subscribe = tracker.subscribe_message();
+        assert_eq!(subscribe["filter"]["accounts"][0], merchant().as_str());
+
+        let event = serde_json::json!({
+            "topic": "account_update",
+            "data": {
+                "account": merchant().as_str(),
+                "change_type": "pending",
+                "block_hash": "abcd",
+                "source": "brst_payer",
+                "amount": "10",
+                "memo": hex::encode(b"pos"),
+            },
+            "timestamp": 5,
+        });
+        let updates = tracker.handle_ws_message(&event.to_string(), Timestamp::new(5));
+        assert_eq!(updates.len(), 1);
+        assert_eq!(tracker.get(&id).unwrap().status, InvoiceStatus::Paid);
+
+        // Other account_update events are ignored
+        let balance_event = serde_json::json!({
+            "topic": "account_update",
+            "data": { "account": merchant().as_str(), "balance": "10", "change_type": "Send" },
+            "timestamp": 6,
+        });
+        assert!(tracker
+            .handle_ws_message(&balance_event.to_string(), Timestamp::new(6))
+            .is_empty());
+        assert!(tracker
+            .handle_ws_message("not json", Timestamp::new(6))
+            .is_empty());
+    }
+}
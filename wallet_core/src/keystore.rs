@@ -1,9 +1,18 @@
 //! Argon2id encrypted keystore for Ed25519 private keys.
 //!
-//! Encrypts a 32-byte Ed25519 secret key with a user-chosen password:
+//! Each key is encrypted with a user-chosen password:
 //! 1. Argon2id derives a 32-byte encryption key from the password + random salt
 //! 2. AES-256-GCM encrypts the secret key with a random nonce
 //! 3. The result is stored as a JSON file with all parameters for future decryption
+//!
+//! Version 2 files hold several labeled keys (primary, delegation, node
+//! identity), each with its own salt, nonce and Argon2id parameters, all
+//! under one passphrase. Version 1 files hold a single primary key in a
+//! top-level `crypto` section; they are still read, and [`KeystoreFile::upgrade`]
+//! converts them to version 2 without needing the passphrase.
+//!
+//! A version 2 file may also hold the wallet's address book ([`Contacts`]),
+//! encrypted under the same passphrase as the keys.
 
 use aes_gcm::aead::{Aead, KeyInit};
 use aes_gcm::{Aes256Gcm, Nonce};
@@ -12,9 +21,11 @@ use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
+use crate::contacts::Contacts;
 use crate::error::WalletError;
+use crate::scheduler::ScheduledPayment;
 
-/// Argon2id parameters: 64 MB memory, 3 iterations, 1 lane of parallelism.
+/// Default Argon2id parameters: 64 MB memory, 3 iterations, 1 lane of parallelism.
 const ARGON2_MEMORY_KIB: u32 = 65536; // 64 MB
 const ARGON2_ITERATIONS: u32 = 3;
 const ARGON2_PARALLELISM: u32 = 1;
@@ -25,10 +36,53 @@ const SALT_LEN: usize = 32;
 /// AES-GCM nonce length in bytes (96 bits).
 const NONCE_LEN: usize = 12;
 
+/// Single-key format with a top-level `crypto` section.
+pub const KEYSTORE_VERSION_1: u32 = 1;
+/// Multi-key format with labeled `entries`.
+pub const KEYSTORE_VERSION: u32 = 2;
+
+/// Label given to the primary key, and to the key of an upgraded v1 file.
+pub const PRIMARY_LABEL: &str = "primary";
+
+const CIPHER: &str = "aes-256-gcm";
+const KDF: &str = "argon2id";
+
 /// The top-level keystore file structure, serializable to/from JSON.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct KeystoreFile {
     pub version: u32,
+    /// The single key of a version 1 file. Absent in version 2 files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crypto: Option<KeystoreCrypto>,
+    /// Labeled keys of a version 2 file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entries: Vec<KeystoreEntry>,
+    /// Signed recurring-payment intents (see `scheduler`). Stored in the
+    /// clear; each entry carries its own signature.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schedules: Vec<ScheduledPayment>,
+    /// The encrypted address book, if one was saved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contacts: Option<KeystoreCrypto>,
+}
+
+/// What a stored key is used for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyKind {
+    /// The account's primary signing key.
+    Primary,
+    /// A delegation key received from a delegator.
+    Delegation,
+    /// A representative's node identity key.
+    NodeIdentity,
+}
+
+/// One labeled, encrypted key in a version 2 keystore.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KeystoreEntry {
+    pub label: String,
+    pub kind: KeyKind,
     pub crypto: KeystoreCrypto,
 }
 
@@ -47,18 +101,218 @@ pub struct KeystoreCrypto {
 }
 
 /// KDF parameters for Argon2id.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct KdfParams {
+    /// Memory cost in KiB.
     pub memory: u32,
     pub iterations: u32,
     pub parallelism: u32,
 }
 
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            memory: ARGON2_MEMORY_KIB,
+            iterations: ARGON2_ITERATIONS,
+            parallelism: ARGON2_PARALLELISM,
+        }
+    }
+}
+
+impl KeystoreFile {
+    /// An empty version 2 keystore.
+    pub fn new() -> Self {
+        Self {
+            version: KEYSTORE_VERSION,
+            crypto: None,
+            entries: Vec::new(),
+            schedules: Vec::new(),
+            contacts: None,
+        }
+    }
+
+    /// Encrypt `secret_key` under `password` and store it as `label`.
+    ///
+    /// Upgrades a version 1 file first. Labels must be unique.
+    pub fn add_key(
+        &mut self,
+        label: &str,
+        kind: KeyKind,
+        secret_key: &[u8; 32],
+        password: &str,
+        params: &KdfParams,
+    ) -> Result<(), WalletError> {
+        self.upgrade()?;
+        if self.entry(label).is_some() {
+            return Err(WalletError::Key(format!(
+                "keystore already has a key labeled {label:?}"
+            )));
+        }
+        self.entries.push(KeystoreEntry {
+            label: label.to_string(),
+            kind,
+            crypto: encrypt_bytes(secret_key, password, params)?,
+        });
+        Ok(())
+    }
+
+    /// Encrypt `contacts` under `password`, replacing any saved address book.
+    pub fn set_contacts(
+        &mut self,
+        contacts: &Contacts,
+        password: &str,
+        params: &KdfParams,
+    ) -> Result<(), WalletError> {
+        self.upgrade()?;
+        let json = serde_json::to_vec(contacts)
+            .map_err(|e| WalletError::Other(format!("JSON serialization failed: {}", e)))?;
+        self.contacts = Some(encrypt_bytes(&json, password, params)?);
+        Ok(())
+    }
+
+    /// Decrypt the saved address book. Empty if none was saved.
+    pub fn contacts(&self, password: &str) -> Result<Contacts, WalletError> {
+        self.check_version()?;
+        let Some(crypto) = &self.contacts else {
+            return Ok(Contacts::new());
+        };
+        let json = decrypt_bytes(crypto, password)?;
+        serde_json::from_slice(&json)
+            .map_err(|e| WalletError::Other(format!("invalid contacts JSON: {}", e)))
+    }
+
+    /// Remove the key labeled `label`. Returns whether it existed.
+    pub fn remove_key(&mut self, label: &str) -> Result<bool, WalletError> {
+        self.upgrade()?;
+        let before = self.entries.len();
+        self.entries.retain(|e| e.label != label);
+        Ok(self.entries.len() != before)
+    }
+
+    /// The entry labeled `label`. A version 1 file exposes its key as
+    /// [`PRIMARY_LABEL`].
+    pub fn entry(&self, label: &str) -> Option<KeystoreEntry> {
+        match self.version {
+            KEYSTORE_VERSION_1 if label == PRIMARY_LABEL => {
+                self.crypto.clone().map(|crypto| KeystoreEntry {
+                    label: PRIMARY_LABEL.to_string(),
+                    kind: KeyKind::Primary,
+                    crypto,
+                })
+            }
+            KEYSTORE_VERSION_1 => None,
+            _ => self.entries.iter().find(|e| e.label == label).cloned(),
+        }
+    }
+
+    /// Decrypt the key labeled `label`.
+    pub fn decrypt_key(&self, label: &str, password: &str) -> Result<[u8; 32], WalletError> {
+        self.check_version()?;
+        let entry = self
+            .entry(label)
+            .ok_or_else(|| WalletError::Key(format!("no key labeled {label:?} in keystore")))?;
+        decrypt_key(&entry.crypto, password)
+    }
+
+    /// Re-encrypt every key under `new_password`, with fresh salts and
+    /// nonces and the given Argon2id parameters. The keys themselves are
+    /// unchanged, and so is the address book, which is re-encrypted too.
+    /// Nothing is modified unless everything decrypts with `old_password`.
+    pub fn change_passphrase(
+        &mut self,
+        old_password: &str,
+        new_password: &str,
+        params: &KdfParams,
+    ) -> Result<(), WalletError> {
+        self.upgrade()?;
+        let mut rotated = Vec::with_capacity(self.entries.len());
+        for entry in &self.entries {
+            let secret = decrypt_key(&entry.crypto, old_password)?;
+            rotated.push(KeystoreEntry {
+                label: entry.label.clone(),
+                kind: entry.kind,
+                crypto: encrypt_bytes(&secret, new_password, params)?,
+            });
+        }
+        let contacts = match &self.contacts {
+            Some(crypto) => Some(encrypt_bytes(
+                &decrypt_bytes(crypto, old_password)?,
+                new_password,
+                params,
+            )?),
+            None => None,
+        };
+        self.entries = rotated;
+        self.contacts = contacts;
+        Ok(())
+    }
+
+    /// Convert a version 1 file to version 2 in place. The v1 key becomes the
+    /// [`PRIMARY_LABEL`] entry as-is, so no passphrase is needed.
+    pub fn upgrade(&mut self) -> Result<(), WalletError> {
+        self.check_version()?;
+        if self.version == KEYSTORE_VERSION_1 {
+            let crypto = self
+                .crypto
+                .take()
+                .ok_or_else(|| WalletError::Key("version 1 keystore has no key".into()))?;
+            self.entries = vec![KeystoreEntry {
+                label: PRIMARY_LABEL.to_string(),
+                kind: KeyKind::Primary,
+                crypto,
+            }];
+            self.version = KEYSTORE_VERSION;
+        }
+        Ok(())
+    }
+
+    fn check_version(&self) -> Result<(), WalletError> {
+        match self.version {
+            KEYSTORE_VERSION_1 | KEYSTORE_VERSION => Ok(()),
+            v => Err(WalletError::Key(format!(
+                "unsupported keystore version: {v}"
+            ))),
+        }
+    }
+}
+
+impl Default for KeystoreFile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Encrypt a 32-byte Ed25519 secret key with a password using Argon2id + AES-256-GCM.
+///
+/// Produces a version 2 keystore holding the key as its primary entry, with
+/// the default Argon2id parameters.
 pub fn encrypt_keystore(
     secret_key: &[u8; 32],
     password: &str,
 ) -> Result<KeystoreFile, WalletError> {
+    let mut keystore = KeystoreFile::new();
+    keystore.add_key(
+        PRIMARY_LABEL,
+        KeyKind::Primary,
+        secret_key,
+        password,
+        &KdfParams::default(),
+    )?;
+    Ok(keystore)
+}
+
+/// Decrypt the primary key of a version 1 or version 2 keystore.
+pub fn decrypt_keystore(keystore: &KeystoreFile, password: &str) -> Result<[u8; 32], WalletError> {
+    keystore.decrypt_key(PRIMARY_LABEL, password)
+}
+
+/// Encrypt one key, or the address book, under a fresh random salt and
+/// nonce.
+fn encrypt_bytes(
+    plaintext: &[u8],
+    password: &str,
+    params: &KdfParams,
+) -> Result<KeystoreCrypto, WalletError> {
     let mut rng = rand::thread_rng();
 
     // Generate random salt and nonce
@@ -69,7 +323,7 @@ pub fn encrypt_keystore(
     rng.fill_bytes(&mut nonce_bytes);
 
     // Derive encryption key via Argon2id
-    let derived_key = derive_key(password, &salt)?;
+    let derived_key = derive_key(password, &salt, params)?;
 
     // Encrypt with AES-256-GCM
     let cipher = Aes256Gcm::new_from_slice(&derived_key)
@@ -77,40 +331,48 @@ pub fn encrypt_keystore(
 
     let nonce = Nonce::from_slice(&nonce_bytes);
     let ciphertext = cipher
-        .encrypt(nonce, secret_key.as_ref())
+        .encrypt(nonce, plaintext)
         .map_err(|e| WalletError::Key(format!("encryption failed: {}", e)))?;
 
-    Ok(KeystoreFile {
-        version: 1,
-        crypto: KeystoreCrypto {
-            cipher: "aes-256-gcm".to_string(),
-            kdf: "argon2id".to_string(),
-            kdf_params: KdfParams {
-                memory: ARGON2_MEMORY_KIB,
-                iterations: ARGON2_ITERATIONS,
-                parallelism: ARGON2_PARALLELISM,
-            },
-            salt: hex_encode(&salt),
-            nonce: hex_encode(&nonce_bytes),
-            ciphertext: hex_encode(&ciphertext),
-        },
+    Ok(KeystoreCrypto {
+        cipher: CIPHER.to_string(),
+        kdf: KDF.to_string(),
+        kdf_params: params.clone(),
+        salt: hex_encode(&salt),
+        nonce: hex_encode(&nonce_bytes),
+        ciphertext: hex_encode(&ciphertext),
     })
 }
 
-/// Decrypt a keystore file with the given password, returning the 32-byte secret key.
-pub fn decrypt_keystore(keystore: &KeystoreFile, password: &str) -> Result<[u8; 32], WalletError> {
-    if keystore.version != 1 {
+/// Decrypt one key with the parameters stored alongside it.
+fn decrypt_key(crypto: &KeystoreCrypto, password: &str) -> Result<[u8; 32], WalletError> {
+    let plaintext = decrypt_bytes(crypto, password)?;
+    if plaintext.len() != 32 {
         return Err(WalletError::Key(format!(
-            "unsupported keystore version: {}",
-            keystore.version
+            "decrypted key has wrong length: expected 32, got {}",
+            plaintext.len()
         )));
     }
 
-    let salt = hex_decode(&keystore.crypto.salt)
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&plaintext);
+    Ok(key)
+}
+
+/// Decrypt whatever [`encrypt_bytes`] encrypted.
+fn decrypt_bytes(crypto: &KeystoreCrypto, password: &str) -> Result<Vec<u8>, WalletError> {
+    if crypto.cipher != CIPHER || crypto.kdf != KDF {
+        return Err(WalletError::Key(format!(
+            "unsupported keystore cipher/kdf: {}/{}",
+            crypto.cipher, crypto.kdf
+        )));
+    }
+
+    let salt = hex_decode(&crypto.salt)
         .map_err(|e| WalletError::Key(format!("invalid salt hex: {}", e)))?;
-    let nonce_bytes = hex_decode(&keystore.crypto.nonce)
+    let nonce_bytes = hex_decode(&crypto.nonce)
         .map_err(|e| WalletError::Key(format!("invalid nonce hex: {}", e)))?;
-    let ciphertext = hex_decode(&keystore.crypto.ciphertext)
+    let ciphertext = hex_decode(&crypto.ciphertext)
         .map_err(|e| WalletError::Key(format!("invalid ciphertext hex: {}", e)))?;
 
     if nonce_bytes.len() != NONCE_LEN {
@@ -122,27 +384,16 @@ pub fn decrypt_keystore(keystore: &KeystoreFile, password: &str) -> Result<[u8;
     }
 
     // Derive the same encryption key from password + salt
-    let derived_key = derive_key(password, &salt)?;
+    let derived_key = derive_key(password, &salt, &crypto.kdf_params)?;
 
     // Decrypt with AES-256-GCM
     let cipher = Aes256Gcm::new_from_slice(&derived_key)
         .map_err(|e| WalletError::Key(format!("AES key init failed: {}", e)))?;
 
     let nonce = Nonce::from_slice(&nonce_bytes);
-    let plaintext = cipher.decrypt(nonce, ciphertext.as_ref()).map_err(|_| {
+    cipher.decrypt(nonce, ciphertext.as_ref()).map_err(|_| {
         WalletError::Key("decryption failed: wrong password or corrupted data".to_string())
-    })?;
-
-    if plaintext.len() != 32 {
-        return Err(WalletError::Key(format!(
-            "decrypted key has wrong length: expected 32, got {}",
-            plaintext.len()
-        )));
-    }
-
-    let mut key = [0u8; 32];
-    key.copy_from_slice(&plaintext);
-    Ok(key)
+    })
 }
 
 /// Save a keystore to a JSON file.
@@ -164,11 +415,11 @@ pub fn load_keystore(path: &Path) -> Result<KeystoreFile, WalletError> {
 }
 
 /// Derive a 32-byte key from a password and salt using Argon2id.
-fn derive_key(password: &str, salt: &[u8]) -> Result<[u8; 32], WalletError> {
+fn derive_key(password: &str, salt: &[u8], params: &KdfParams) -> Result<[u8; 32], WalletError> {
     let params = Params::new(
-        ARGON2_MEMORY_KIB,
-        ARGON2_ITERATIONS,
-        ARGON2_PARALLELISM,
+        params.memory,
+        params.iterations,
+        params.parallelism,
         Some(ARGON2_OUTPUT_LEN),
     )
     .map_err(|e| WalletError::Key(format!("Argon2 params error: {}", e)))?;
@@ -225,26 +476,28 @@ mod tests {
     }
 
     #[test]
-    fn keystore_version_is_1() {
+    fn keystore_version_is_2() {
         let keystore = encrypt_keystore(&[0u8; 32], "pass").unwrap();
-        assert_eq!(keystore.version, 1);
+        assert_eq!(keystore.version, 2);
     }
 
     #[test]
     fn keystore_crypto_fields() {
         let keystore = encrypt_keystore(&[0u8; 32], "pass").unwrap();
-        assert_eq!(keystore.crypto.cipher, "aes-256-gcm");
-        assert_eq!(keystore.crypto.kdf, "argon2id");
-        assert_eq!(keystore.crypto.kdf_params.memory, 65536);
-        assert_eq!(keystore.crypto.kdf_params.iterations, 3);
-        assert_eq!(keystore.crypto.kdf_params.parallelism, 1);
+        let entry = keystore.entry(PRIMARY_LABEL).unwrap();
+        assert_eq!(entry.kind, KeyKind::Primary);
+        assert_eq!(entry.crypto.cipher, "aes-256-gcm");
+        assert_eq!(entry.crypto.kdf, "argon2id");
+        assert_eq!(entry.crypto.kdf_params.memory, 65536);
+        assert_eq!(entry.crypto.kdf_params.iterations, 3);
+        assert_eq!(entry.crypto.kdf_params.parallelism, 1);
     }
 
     #[test]
     fn keystore_serializes_to_json() {
         let keystore = encrypt_keystore(&[1u8; 32], "pass").unwrap();
         let json = serde_json::to_string_pretty(&keystore).unwrap();
-        assert!(json.contains("\"version\": 1"));
+        assert!(json.contains("\"version\": 2"));
         assert!(json.contains("\"cipher\": \"aes-256-gcm\""));
         assert!(json.contains("\"kdf\": \"argon2id\""));
     }
@@ -276,7 +529,10 @@ mod tests {
         let ks1 = encrypt_keystore(&secret_key, "password1").unwrap();
         let ks2 = encrypt_keystore(&secret_key, "password2").unwrap();
         // Different salts ensure different ciphertexts even with same key
-        assert_ne!(ks1.crypto.ciphertext, ks2.crypto.ciphertext);
+        assert_ne!(
+            ks1.entries[0].crypto.ciphertext,
+            ks2.entries[0].crypto.ciphertext
+        );
     }
 
     #[test]
@@ -292,4 +548,120 @@ mod tests {
         let result = decrypt_keystore(&keystore, "pass");
         assert!(result.is_err());
     }
+
+    /// Cheap Argon2id parameters so tests don't spend 64 MB per key.
+    fn fast() -> KdfParams {
+        KdfParams {
+            memory: 64,
+            iterations: 1,
+            parallelism: 1,
+        }
+    }
+
+    #[test]
+    fn reads_and_upgrades_v1_files() {
+        let secret = [5u8; 32];
+        let json = serde_json::json!({
+            "version": 1,
+            "crypto": encrypt_bytes(&secret, "pass", &KdfParams::default()).unwrap(),
+        })
+        .to_string();
+        let mut keystore: KeystoreFile = serde_json::from_str(&json).unwrap();
+        assert_eq!(decrypt_keystore(&keystore, "pass").unwrap(), secret);
+
+        keystore.upgrade().unwrap();
+        assert_eq!(keystore.version, KEYSTORE_VERSION);
+        assert!(keystore.crypto.is_none());
+        assert_eq!(decrypt_keystore(&keystore, "pass").unwrap(), secret);
+    }
+
+    #[test]
+    fn holds_multiple_labeled_keys() {
+        let mut keystore = KeystoreFile::new();
+        keystore
+            .add_key(PRIMARY_LABEL, KeyKind::Primary, &[1u8; 32], "pass", &fast())
+            .unwrap();
+        keystore
+            .add_key(
+                "delegation",
+                KeyKind::Delegation,
+                &[2u8; 32],
+                "pass",
+                &fast(),
+            )
+            .unwrap();
+        keystore
+            .add_key("node", KeyKind::NodeIdentity, &[3u8; 32], "pass", &fast())
+            .unwrap();
+        assert!(keystore
+            .add_key("node", KeyKind::NodeIdentity, &[4u8; 32], "pass", &fast())
+            .is_err());
+
+        let json = serde_json::to_string(&keystore).unwrap();
+        let loaded: KeystoreFile = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.decrypt_key("delegation", "pass").unwrap(), [2u8; 32]);
+        assert_eq!(loaded.decrypt_key("node", "pass").unwrap(), [3u8; 32]);
+        assert_eq!(loaded.entry("node").unwrap().kind, KeyKind::NodeIdentity);
+        assert!(loaded.decrypt_key("missing", "pass").is_err());
+
+        let mut keystore = loaded;
+        assert!(keystore.remove_key("delegation").unwrap());
+        assert!(keystore.entry("delegation").is_none());
+    }
+
+    #[test]
+    fn change_passphrase_keeps_keys() {
+        let mut keystore = KeystoreFile::new();
+        keystore
+            .add_key(PRIMARY_LABEL, KeyKind::Primary, &[1u8; 32], "old", &fast())
+            .unwrap();
+        keystore
+            .add_key("node", KeyKind::NodeIdentity, &[3u8; 32], "old", &fast())
+            .unwrap();
+        let before = keystore.entry("node").unwrap().crypto;
+
+        assert!(keystore.change_passphrase("wrong", "new", &fast()).is_err());
+        assert_eq!(keystore.decrypt_key("node", "old").unwrap(), [3u8; 32]);
+
+        let tuned = KdfParams {
+            memory: 128,
+            iterations: 2,
+            parallelism: 1,
+        };
+        keystore.change_passphrase("old", "new", &tuned).unwrap();
+        let after = keystore.entry("node").unwrap().crypto;
+        assert_ne!(before.salt, after.salt);
+        assert_eq!(after.kdf_params, tuned);
+        assert!(keystore.decrypt_key("node", "old").is_err());
+        assert_eq!(
+            keystore.decrypt_key(PRIMARY_LABEL, "new").unwrap(),
+            [1u8; 32]
+        );
+        assert_eq!(keystore.decrypt_key("node", "new").unwrap(), [3u8; 32]);
+    }
+
+    #[test]
+    fn contacts_are_encrypted_with_the_keys() {
+        let mut keystore = KeystoreFile::new();
+        keystore
+            .add_key(PRIMARY_LABEL, KeyKind::Primary, &[1u8; 32], "old", &fast())
+            .unwrap();
+        assert!(keystore.contacts("old").unwrap().is_empty());
+
+        let bob = burst_crypto::derive_address(&burst_crypto::generate_keypair().public);
+        let mut contacts = Contacts::new();
+        contacts
+            .add(crate::contacts::Contact::new("bob", bob.clone()))
+            .unwrap();
+        keystore.set_contacts(&contacts, "old", &fast()).unwrap();
+
+        let json = serde_json::to_string(&keystore).unwrap();
+        assert!(!json.contains(bob.as_str()));
+        let mut keystore: KeystoreFile = serde_json::from_str(&json).unwrap();
+        assert!(keystore.contacts("wrong").is_err());
+
+        keystore.change_passphrase("old", "new", &fast()).unwrap();
+        assert!(keystore.contacts("old").is_err());
+        assert_eq!(keystore.contacts("new").unwrap(), contacts);
+    }
 }
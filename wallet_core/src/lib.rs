@@ -5,27 +5,76 @@
 //! - BRN balance display (computed from time)
 //! - TRST portfolio (transferable, expired, revoked)
 //! - Transaction building and signing (burn, send, split, merge)
+//! - Batch sends: one signer, many recipients, resumable submission
+//! - Proof-of-work precomputed on each managed account's frontier
+//! - Address book with group attestations and a receive allowlist
 //! - Delegation management
+//! - Representative health monitoring and automatic re-delegation
+//! - Light-client verification of block proofs
+//! - Custodianship and social key recovery
+//! - Encrypted direct messages for coordinating verification
 //! - Voting interface
 //! - Group trust policy evaluation
+//! - Recurring / scheduled payments
+//! - `burst:` payment request URIs and invoice tracking
+//! - Deposit tags for exchanges receiving on one shared account
+//! - QR and animated-QR payloads for air-gapped signing (`qr` feature)
+//! - Node RPC with endpoint failover, and WebSocket balance push updates
+//! - Status tracking for submitted transactions
 
 pub mod auto_merge;
 pub mod balance;
+pub mod batch_send;
+pub mod contacts;
 pub mod custodianship;
 pub mod delegation;
+pub mod deposit_tag;
+pub mod direct_messages;
 pub mod error;
+pub mod invoice;
 pub mod keys;
 pub mod keystore;
+pub mod light;
+pub mod payment_uri;
 pub mod portfolio;
+#[cfg(feature = "qr")]
+pub mod qr;
+pub mod receive_gate;
+pub mod rep_monitor;
+pub mod scheduler;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod subscriber;
+pub mod tracked_tx;
 pub mod transaction_builder;
 pub mod trust_policy;
 pub mod wallet;
+pub mod work_precompute;
 
+pub use batch_send::{BatchRecipient, BatchSend};
+pub use contacts::{Contact, Contacts, GroupAttestation};
 pub use custodianship::{
-    Custodianship, CustodianshipError, CustodianshipRegistry, CustodianshipStatus,
+    Custodianship, CustodianshipError, CustodianshipRegistry, CustodianshipStatus, RecoveryRequest,
+    RecoverySession, SealedMessage,
 };
+pub use deposit_tag::DepositTags;
+pub use direct_messages::{DmSendResult, ReceivedMessage};
 pub use error::WalletError;
+pub use invoice::{IncomingPayment, Invoice, InvoiceStatus, InvoiceTracker, InvoiceUpdate};
 pub use keystore::{
-    decrypt_keystore, encrypt_keystore, load_keystore, save_keystore, KeystoreFile,
+    decrypt_keystore, encrypt_keystore, load_keystore, save_keystore, KdfParams, KeyKind,
+    KeystoreEntry, KeystoreFile,
 };
+pub use light::{verify_block_proof, RepresentativeSet, VerifiedBlock};
+pub use payment_uri::PaymentRequest;
+#[cfg(feature = "qr")]
+pub use qr::{QrImage, QrPayload, UrDecoder, UrFrame, UrKind};
+pub use receive_gate::{AuditEntry, GateDecision, ReceiveGate};
+pub use rep_monitor::{RepCheck, RepHealth, RepMonitor, RepMonitorConfig};
+pub use scheduler::{PaymentTemplate, Recurrence, ScheduleStatus, Scheduler};
+#[cfg(not(target_arch = "wasm32"))]
+pub use subscriber::{AccountUpdate, BalanceSubscriber, SubscriberEvent};
+pub use tracked_tx::{TrackedTransaction, TxState};
+#[cfg(not(target_arch = "wasm32"))]
+pub use wallet::RetryPolicy;
 pub use wallet::{NodeClient, Wallet};
+pub use work_precompute::WorkPrecompute;
@@ -0,0 +1,235 @@
+//! Light-client verification of block proofs.
+//!
+//! A wallet that does not keep the ledger can still confirm that a block it
+//! cares about (a payment it received, say) is settled: it asks any node for
+//! a [`BlockProof`] and checks it against a [`RepresentativeSet`] it trusts.
+//! The node serving the proof does not need to be trusted.
+//!
+//! Verification recomputes every block hash in the chain, checks that each
+//! block links to the one before it and that the chain starts at an open
+//! block, then sums the weight of the distinct trusted representatives whose
+//! valid final votes cover the block.
+
+use std::collections::{HashMap, HashSet};
+
+use burst_ledger::{BlockProof, StateBlock};
+use burst_types::{BlockHash, WalletAddress};
+
+use crate::error::WalletError;
+
+/// The representatives a light client trusts, with their weights.
+#[derive(Clone, Debug, Default)]
+pub struct RepresentativeSet {
+    weights: HashMap<WalletAddress, u128>,
+    quorum: u128,
+}
+
+impl RepresentativeSet {
+    /// A set whose proofs need at least `quorum` weight of final votes.
+    pub fn new(weights: HashMap<WalletAddress, u128>, quorum: u128) -> Self {
+        Self { weights, quorum }
+    }
+
+    /// A set whose proofs need `quorum_percent` of the set's total weight.
+    pub fn with_quorum_percent(weights: HashMap<WalletAddress, u128>, quorum_percent: u8) -> Self {
+        let total: u128 = weights.values().sum();
+        let quorum =
+            (total / 100) * quorum_percent as u128 + (total % 100) * quorum_percent as u128 / 100;
+        Self::new(weights, quorum)
+    }
+
+    pub fn weight(&self, representative: &WalletAddress) -> u128 {
+        self.weights.get(representative).copied().unwrap_or(0)
+    }
+
+    pub fn quorum(&self) -> u128 {
+        self.quorum
+    }
+}
+
+/// A block whose proof checked out.
+#[derive(Clone, Debug)]
+pub struct VerifiedBlock {
+    pub block: StateBlock,
+    /// Position in the account chain (the open block is 1).
+    pub height: u64,
+    /// Trusted weight behind the block's final votes.
+    pub weight: u128,
+}
+
+/// Verify that `proof` shows `hash` is in its account chain and finalized
+/// by at least the set's quorum.
+pub fn verify_block_proof(
+    proof: &BlockProof,
+    hash: &BlockHash,
+    representatives: &RepresentativeSet,
+) -> Result<VerifiedBlock, WalletError> {
+    let invalid = |reason: String| Err(WalletError::InvalidProof(reason));
+    let Some(block) = proof.block() else {
+        return invalid("empty chain".into());
+    };
+    if block.hash != *hash {
+        return invalid(format!("proof is for {}, not {hash}", block.hash));
+    }
+
+    let mut previous = BlockHash::ZERO;
+    for link in &proof.chain {
+        if link.compute_hash() != link.hash {
+            return invalid(format!("block {} does not match its hash", link.hash));
+        }
+        if link.previous != previous {
+            return invalid(format!("block {} does not extend {previous}", link.hash));
+        }
+        if link.account != block.account {
+            return invalid(format!("block {} belongs to another account", link.hash));
+        }
+        previous = link.hash;
+    }
+
+    let mut voters = HashSet::new();
+    let mut weight = 0u128;
+    for vote in &proof.votes {
+        let voter_weight = representatives.weight(&vote.voter);
+        if voter_weight == 0
+            || !vote.block_hashes.contains(hash)
+            || voters.contains(&vote.voter)
+            || !vote.verify_signature()
+        {
+            continue;
+        }
+        voters.insert(&vote.voter);
+        weight = weight.saturating_add(voter_weight);
+    }
+    if weight < representatives.quorum() {
+        return invalid(format!(
+            "final votes carry {weight} trusted weight, quorum is {}",
+            representatives.quorum()
+        ));
+    }
+
+    Ok(VerifiedBlock {
+        block: block.clone(),
+        height: proof.height(),
+        weight,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_ledger::{BlockType, ProofVote, CURRENT_BLOCK_VERSION};
+    use burst_types::{KeyPair, Signature, Timestamp, TxHash};
+
+    fn keys(seed: u8) -> (WalletAddress, KeyPair) {
+        let keys = burst_crypto::keypair_from_seed(&[seed; 32]);
+        (burst_crypto::derive_address(&keys.public), keys)
+    }
+
+    fn block(account: &WalletAddress, previous: BlockHash, balance: u128) -> StateBlock {
+        let mut block = StateBlock {
+            version: CURRENT_BLOCK_VERSION,
+            block_type: if previous.is_zero() {
+                BlockType::Open
+            } else {
+                BlockType::Send
+            },
+            account: account.clone(),
+            previous,
+            representative: account.clone(),
+            brn_balance: 0,
+            trst_balance: balance,
+            link: BlockHash::ZERO,
+            origin: TxHash::ZERO,
+            transaction: TxHash::ZERO,
+            timestamp: Timestamp::new(1_000),
+            params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
+            work: 0,
+            signature: Signature([1u8; 64]),
+            hash: BlockHash::ZERO,
+        };
+        block.hash = block.compute_hash();
+        block
+    }
+
+    fn vote(voter: &(WalletAddress, KeyPair), hash: BlockHash) -> ProofVote {
+        let mut vote = ProofVote {
+            voter: voter.0.clone(),
+            block_hashes: vec![hash],
+            timestamp: 2_000,
+            signature: Signature([0u8; 64]),
+        };
+        vote.signature = burst_crypto::sign_message(&vote.signed_message(), &voter.1.private);
+        vote
+    }
+
+    fn sample_proof() -> (BlockProof, BlockHash, [(WalletAddress, KeyPair); 3]) {
+        let (owner, _) = keys(1);
+        let open = block(&owner, BlockHash::ZERO, 100);
+        let send = block(&owner, open.hash, 60);
+        let target = send.hash;
+        let reps = [keys(2), keys(3), keys(4)];
+        let proof = BlockProof {
+            chain: vec![open, send],
+            votes: vec![vote(&reps[0], target), vote(&reps[1], target)],
+        };
+        (proof, target, reps)
+    }
+
+    fn trusted(reps: &[(WalletAddress, KeyPair)]) -> RepresentativeSet {
+        let weights = reps.iter().map(|(addr, _)| (addr.clone(), 100)).collect();
+        RepresentativeSet::with_quorum_percent(weights, 60)
+    }
+
+    #[test]
+    fn accepts_chain_with_quorum_of_final_votes() {
+        let (proof, target, reps) = sample_proof();
+        let verified = verify_block_proof(&proof, &target, &trusted(&reps)).unwrap();
+        assert_eq!(verified.height, 2);
+        assert_eq!(verified.weight, 200);
+        assert_eq!(verified.block.trst_balance, 60);
+    }
+
+    #[test]
+    fn rejects_broken_chains() {
+        let (mut proof, target, reps) = sample_proof();
+        proof.chain[0].trst_balance = 1_000_000;
+        assert!(matches!(
+            verify_block_proof(&proof, &target, &trusted(&reps)),
+            Err(WalletError::InvalidProof(_))
+        ));
+
+        // Dropping the open block leaves the chain without a starting point.
+        let (mut proof, target, reps) = sample_proof();
+        proof.chain.remove(0);
+        assert!(verify_block_proof(&proof, &target, &trusted(&reps)).is_err());
+    }
+
+    #[test]
+    fn requires_quorum_of_distinct_trusted_valid_votes() {
+        let (mut proof, target, reps) = sample_proof();
+        let outsider = keys(9);
+        let mut forged = vote(&reps[1], target);
+        forged.timestamp += 1;
+        proof.votes = vec![
+            vote(&reps[0], target),
+            vote(&reps[0], target),
+            vote(&outsider, target),
+            forged,
+        ];
+        assert!(verify_block_proof(&proof, &target, &trusted(&reps)).is_err());
+
+        proof.votes.push(vote(&reps[2], target));
+        assert_eq!(
+            verify_block_proof(&proof, &target, &trusted(&reps))
+                .unwrap()
+                .weight,
+            200
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! `burst:` payment request URIs.
+//!
+//! A payment request encodes everything a payer needs to build a send:
+//!
+//! ```text
+//! burst:brst_1abc...?amount=1000000&memo=696e762d3432&expiry=1767225600
+//! ```
+//!
+//! - `amount` — requested TRST amount in raw units (optional)
+//! - `memo` — hex-encoded memo/reference, at most `MAX_MEMO_LEN` bytes (optional)
+//! - `expiry` — Unix timestamp (seconds) after which the request is void (optional)
+//! - `tag` — destination tag the payer must attach, in decimal (optional; see
+//!   [`crate::deposit_tag`])
+//!
+//! Unknown query parameters are ignored so that newer wallets can add fields
+//! without breaking older parsers.
+
+use std::fmt;
+use std::str::FromStr;
+
+use burst_transactions::send::MAX_MEMO_LEN;
+use burst_types::{Timestamp, WalletAddress};
+
+use crate::error::WalletError;
+
+/// URI scheme prefix for BURST payment requests.
+pub const URI_SCHEME: &str = "burst:";
+
+/// A parsed `burst:` payment request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaymentRequest {
+    /// Destination wallet.
+    pub address: WalletAddress,
+    /// Requested amount in raw TRST units. `None` lets the payer choose.
+    pub amount: Option<u128>,
+    /// Memo/reference the payer should attach to the send block.
+    pub memo: Option<Vec<u8>>,
+    /// Time after which the request should no longer be paid.
+    pub expiry: Option<Timestamp>,
+    /// Destination tag the payer should attach to the send block.
+    pub destination_tag: Option<u64>,
+}
+
+impl PaymentRequest {
+    /// Create a request for the given address with no amount, memo, or expiry.
+    pub fn new(address: WalletAddress) -> Self {
+        Self {
+            address,
+            amount: None,
+            memo: None,
+            expiry: None,
+            destination_tag: None,
+        }
+    }
+
+    /// Set the requested amount. Fails on zero, which [`Self::parse`]
+    /// would reject; leave the amount unset to let the payer choose.
+    pub fn with_amount(mut self, amount: u128) -> Result<Self, WalletError> {
+        if amount == 0 {
+            return Err(invalid("amount must be greater than zero".into()));
+        }
+        self.amount = Some(amount);
+        Ok(self)
+    }
+
+    /// Set the memo/reference. Fails if it exceeds `MAX_MEMO_LEN` bytes.
+    pub fn with_memo(mut self, memo: impl Into<Vec<u8>>) -> Result<Self, WalletError> {
+        let memo = memo.into();
+        if memo.len() > MAX_MEMO_LEN {
+            return Err(WalletError::InvalidPaymentRequest(format!(
+                "memo is {} bytes, maximum is {}",
+                memo.len(),
+                MAX_MEMO_LEN
+            )));
+        }
+        self.memo = Some(memo);
+        Ok(self)
+    }
+
+    /// Set the expiry time.
+    pub fn with_expiry(mut self, expiry: Timestamp) -> Self {
+        self.expiry = Some(expiry);
+        self
+    }
+
+    /// Set the destination tag.
+    pub fn with_destination_tag(mut self, tag: u64) -> Self {
+        self.destination_tag = Some(tag);
+        self
+    }
+
+    /// Whether the request has expired at `now`.
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        self.expiry.is_some_and(|e| now >= e)
+    }
+
+    /// Serialize to a `burst:` URI string.
+    pub fn to_uri(&self) -> String {
+        self.to_string()
+    }
+
+    /// Parse a `burst:` URI string.
+    pub fn parse(uri: &str) -> Result<Self, WalletError> {
+        let rest = uri
+            .strip_prefix(URI_SCHEME)
+            .ok_or_else(|| invalid(format!("URI must start with '{URI_SCHEME}'")))?;
+        // Tolerate the `burst://address` form some QR generators emit.
+        let rest = rest.strip_prefix("//").unwrap_or(rest);
+
+        let (address_part, query) = match rest.split_once('?') {
+            Some((a, q)) => (a, Some(q)),
+            None => (rest, None),
+        };
+
+        if burst_crypto::decode_address(address_part).is_none() {
+            return Err(invalid(format!("invalid address: {address_part}")));
+        }
+        let mut request = PaymentRequest::new(WalletAddress::new(address_part));
+
+        for pair in query.unwrap_or("").split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| invalid(format!("malformed query parameter: {pair}")))?;
+            match key {
+                "amount" => {
+                    let amount: u128 = value
+                        .parse()
+                        .map_err(|e| invalid(format!("invalid amount: {e}")))?;
+                    request = request.with_amount(amount)?;
+                }
+                "memo" => {
+                    let memo =
+                        hex::decode(value).map_err(|e| invalid(format!("invalid memo: {e}")))?;
+                    request = request.with_memo(memo)?;
+                }
+                "expiry" => {
+                    let secs: u64 = value
+                        .parse()
+                        .map_err(|e| invalid(format!("invalid expiry: {e}")))?;
+                    request.expiry = Some(Timestamp::new(secs));
+                }
+                "tag" => {
+                    let tag = crate::deposit_tag::parse_tag(value)
+                        .map_err(|e| invalid(format!("invalid tag: {e}")))?;
+                    request.destination_tag = Some(tag);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(request)
+    }
+}
+
+impl fmt::Display for PaymentRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{URI_SCHEME}{}", self.address)?;
+        let mut params = Vec::new();
+        if let Some(amount) = self.amount {
+            params.push(format!("amount={amount}"));
+        }
+        if let Some(ref memo) = self.memo {
+            params.push(format!("memo={}", hex::encode(memo)));
+        }
+        if let Some(expiry) = self.expiry {
+            params.push(format!("expiry={}", expiry.as_secs()));
+        }
+        if let Some(tag) = self.destination_tag {
+            params.push(format!("tag={tag}"));
+        }
+        if !params.is_empty() {
+            write!(f, "?{}", params.join("&"))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PaymentRequest {
+    type Err = WalletError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+fn invalid(msg: String) -> WalletError {
+    WalletError::InvalidPaymentRequest(msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_address() -> WalletAddress {
+        let kp = burst_crypto::keypair_from_seed(&[7u8; 32]);
+        burst_crypto::derive_address(&kp.public)
+    }
+
+    #[test]
+    fn roundtrip_full_request() {
+        let request = PaymentRequest::new(test_address())
+            .with_amount(1_500)
+            .unwrap()
+            .with_memo(b"inv-42".to_vec())
+            .unwrap()
+            .with_expiry(Timestamp::new(1_700_000_000))
+            .with_destination_tag(12_345);
+
+        let uri = request.to_uri();
+        assert!(uri.starts_with("burst:brst_"));
+        assert!(uri.contains("amount=1500"));
+        assert!(uri.contains("tag=12345"));
+        assert!(uri.contains("memo=696e762d3432"));
+
+        let parsed: PaymentRequest = uri.parse().unwrap();
+        assert_eq!(parsed, request);
+    }
+
+    #[test]
+    fn address_only_request() {
+        let request = PaymentRequest::new(test_address());
+        let uri = request.to_uri();
+        assert!(!uri.contains('?'));
+        assert_eq!(PaymentRequest::parse(&uri).unwrap(), request);
+    }
+
+    #[test]
+    fn accepts_double_slash_form_and_ignores_unknown_params() {
+        let uri = format!("burst://{}?amount=5&label=coffee", test_address());
+        let parsed = PaymentRequest::parse(&uri).unwrap();
+        assert_eq!(parsed.amount, Some(5));
+        assert_eq!(parsed.memo, None);
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        let addr = test_address();
+        assert!(PaymentRequest::parse(&format!("nano:{addr}")).is_err());
+        assert!(PaymentRequest::parse("burst:brst_notanaddress").is_err());
+        assert!(PaymentRequest::parse(&format!("burst:{addr}?amount=abc")).is_err());
+        assert!(PaymentRequest::parse(&format!("burst:{addr}?amount=0")).is_err());
+        assert!(PaymentRequest::parse(&format!("burst:{addr}?memo=zz")).is_err());
+        assert!(PaymentRequest::parse(&format!("burst:{addr}?tag=-1")).is_err());
+        assert!(PaymentRequest::parse(&format!("burst:{addr}?amount")).is_err());
+
+        let long_memo = hex::encode([1u8; MAX_MEMO_LEN + 1]);
+        assert!(PaymentRequest::parse(&format!("burst:{addr}?memo={long_memo}")).is_err());
+    }
+
+    #[test]
+    fn zero_amount_is_rejected_when_built_as_when_parsed() {
+        let addr = test_address();
+        assert!(matches!(
+            PaymentRequest::new(addr.clone()).with_amount(0),
+            Err(WalletError::InvalidPaymentRequest(_))
+        ));
+
+        let request = PaymentRequest::new(addr).with_amount(1).unwrap();
+        assert_eq!(PaymentRequest::parse(&request.to_uri()).unwrap(), request);
+    }
+
+    #[test]
+    fn expiry_check() {
+        let request = PaymentRequest::new(test_address()).with_expiry(Timestamp::new(100));
+        assert!(!request.is_expired(Timestamp::new(99)));
+        assert!(request.is_expired(Timestamp::new(100)));
+        assert!(!PaymentRequest::new(test_address()).is_expired(Timestamp::new(u64::MAX)));
+    }
+}
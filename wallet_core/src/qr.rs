@@ -0,0 +1,409 @@
+//! QR payloads for air-gapped signing.
+//!
+//! An online wallet and an offline signing device exchange data by showing
+//! and scanning QR codes, with no cable between them. Three payloads are
+//! recognised when scanned ([`QrPayload::parse`]):
+//!
+//! - a bare address (`brst_...`);
+//! - a `burst:` payment request ([`PaymentRequest`]);
+//! - one frame of an animated QR, for data too large for a single code.
+//!
+//! Animated QRs follow the shape of Uniform Resources (UR): the message is
+//! cut into fragments and each frame carries
+//!
+//! ```text
+//! UR:BURST-UNSIGNED-BLOCK/2-5/9C1F03AB/<fragment as hex>
+//! ```
+//!
+//! — the payload kind, the frame's sequence number and the frame count, a
+//! checksum of the whole message, and the fragment. Frames are uppercase so
+//! they fit the denser QR alphanumeric mode. The sender shows the frames in
+//! a loop and the receiver feeds whatever it scans into a [`UrDecoder`]
+//! until every fragment has arrived, in any order.
+//!
+//! The usual flow is an unsigned block out to the signer
+//! ([`UrKind::UnsignedBlock`]) and the signed block back
+//! ([`UrKind::SignedBlock`]).
+
+use std::fmt;
+
+use burst_crypto::blake2b_256;
+use burst_ledger::StateBlock;
+use burst_types::{Signature, WalletAddress};
+
+use crate::error::WalletError;
+use crate::payment_uri::{PaymentRequest, URI_SCHEME};
+
+/// Prefix of every animated-QR frame.
+pub const UR_PREFIX: &str = "UR:";
+
+/// Message bytes per frame by default. Frames stay small enough to scan
+/// quickly from a phone screen.
+pub const DEFAULT_FRAGMENT_LEN: usize = 100;
+
+/// Most frames a message may be cut into.
+pub const MAX_FRAMES: u32 = 1000;
+
+fn invalid(reason: impl Into<String>) -> WalletError {
+    WalletError::InvalidQrPayload(reason.into())
+}
+
+/// What an animated QR carries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UrKind {
+    /// A block for the signing device to sign.
+    UnsignedBlock,
+    /// A signed block coming back from the signing device.
+    SignedBlock,
+}
+
+impl UrKind {
+    /// The type name in the frame header.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::UnsignedBlock => "BURST-UNSIGNED-BLOCK",
+            Self::SignedBlock => "BURST-SIGNED-BLOCK",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [Self::UnsignedBlock, Self::SignedBlock]
+            .into_iter()
+            .find(|kind| kind.as_str() == name)
+    }
+}
+
+/// One frame of an animated QR.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UrFrame {
+    pub kind: UrKind,
+    /// 1-based position of this fragment.
+    pub seq: u32,
+    pub total: u32,
+    /// Checksum of the whole message, the same in every frame.
+    pub checksum: u32,
+    pub fragment: Vec<u8>,
+}
+
+impl UrFrame {
+    /// Parse a scanned frame. Case is ignored, since some scanners
+    /// lowercase what they read.
+    pub fn parse(text: &str) -> Result<Self, WalletError> {
+        let text = text.trim().to_ascii_uppercase();
+        let rest = text
+            .strip_prefix(UR_PREFIX)
+            .ok_or_else(|| invalid(format!("frame must start with '{UR_PREFIX}'")))?;
+        let parts: Vec<&str> = rest.split('/').collect();
+        let [kind, position, checksum, fragment] = parts[..] else {
+            return Err(invalid("frame must have four '/'-separated parts"));
+        };
+        let kind =
+            UrKind::from_name(kind).ok_or_else(|| invalid(format!("unknown payload {kind}")))?;
+        let (seq, total) = position
+            .split_once('-')
+            .and_then(|(seq, total)| Some((seq.parse::<u32>().ok()?, total.parse::<u32>().ok()?)))
+            .ok_or_else(|| invalid(format!("bad frame position {position}")))?;
+        if total == 0 || total > MAX_FRAMES || seq == 0 || seq > total {
+            return Err(invalid(format!("bad frame position {seq}-{total}")));
+        }
+        let checksum = u32::from_str_radix(checksum, 16)
+            .ok()
+            .filter(|_| checksum.len() == 8)
+            .ok_or_else(|| invalid(format!("bad checksum {checksum}")))?;
+        let fragment = hex::decode(fragment).map_err(|e| invalid(format!("bad fragment: {e}")))?;
+        Ok(Self {
+            kind,
+            seq,
+            total,
+            checksum,
+            fragment,
+        })
+    }
+}
+
+impl fmt::Display for UrFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{UR_PREFIX}{}/{}-{}/{:08X}/{}",
+            self.kind.as_str(),
+            self.seq,
+            self.total,
+            self.checksum,
+            hex::encode_upper(&self.fragment)
+        )
+    }
+}
+
+fn checksum(message: &[u8]) -> u32 {
+    let digest = blake2b_256(message);
+    u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]])
+}
+
+/// Cut `message` into frames of at most `fragment_len` bytes each.
+pub fn encode_frames(
+    kind: UrKind,
+    message: &[u8],
+    fragment_len: usize,
+) -> Result<Vec<UrFrame>, WalletError> {
+    if message.is_empty() || fragment_len == 0 {
+        return Err(invalid("nothing to encode"));
+    }
+    let total = message.len().div_ceil(fragment_len);
+    let total = u32::try_from(total)
+        .ok()
+        .filter(|&total| total <= MAX_FRAMES)
+        .ok_or_else(|| invalid(format!("message needs more than {MAX_FRAMES} frames")))?;
+    let checksum = checksum(message);
+    Ok(message
+        .chunks(fragment_len)
+        .zip(1..)
+        .map(|(fragment, seq)| UrFrame {
+            kind,
+            seq,
+            total,
+            checksum,
+            fragment: fragment.to_vec(),
+        })
+        .collect())
+}
+
+/// Frames carrying `block` to or from a signing device. An unsigned block
+/// must not carry a signature yet, and a signed one must.
+pub fn block_frames(
+    kind: UrKind,
+    block: &StateBlock,
+    fragment_len: usize,
+) -> Result<Vec<UrFrame>, WalletError> {
+    let signed = block.signature != Signature([0u8; 64]);
+    if signed != (kind == UrKind::SignedBlock) {
+        return Err(invalid(format!(
+            "{} payload for a block that is {}signed",
+            kind.as_str(),
+            if signed { "" } else { "not " }
+        )));
+    }
+    let bytes = bincode::serialize(block).map_err(|e| invalid(e.to_string()))?;
+    encode_frames(kind, &bytes, fragment_len)
+}
+
+/// Collects scanned frames until a message is complete.
+#[derive(Debug, Default)]
+pub struct UrDecoder {
+    expected: Option<(UrKind, u32, u32)>,
+    fragments: Vec<Option<Vec<u8>>>,
+}
+
+impl UrDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take in one scanned frame and report whether the message is
+    /// complete. Repeated frames are ignored; a frame from a different
+    /// message is an error, and the caller may [`reset`](Self::reset).
+    pub fn receive(&mut self, text: &str) -> Result<bool, WalletError> {
+        let frame = UrFrame::parse(text)?;
+        let header = (frame.kind, frame.total, frame.checksum);
+        match self.expected {
+            None => {
+                self.expected = Some(header);
+                self.fragments = vec![None; frame.total as usize];
+            }
+            Some(expected) if expected != header => {
+                return Err(invalid("frame belongs to a different message"));
+            }
+            Some(_) => {}
+        }
+        self.fragments[frame.seq as usize - 1].get_or_insert(frame.fragment);
+        Ok(self.is_complete())
+    }
+
+    /// Fragments received and fragments expected.
+    pub fn progress(&self) -> (usize, usize) {
+        let received = self.fragments.iter().filter(|f| f.is_some()).count();
+        (received, self.fragments.len())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.expected.is_some() && self.fragments.iter().all(Option::is_some)
+    }
+
+    /// The kind of message being received, once a frame has arrived.
+    pub fn kind(&self) -> Option<UrKind> {
+        self.expected.map(|(kind, _, _)| kind)
+    }
+
+    /// Forget the frames received so far.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// The reassembled message, checked against its checksum.
+    pub fn message(&self) -> Result<Vec<u8>, WalletError> {
+        let (received, total) = self.progress();
+        let Some((_, _, expected)) = self.expected.filter(|_| self.is_complete()) else {
+            return Err(invalid(format!(
+                "message incomplete: {received} of {total} frames"
+            )));
+        };
+        let message: Vec<u8> = self.fragments.iter().flatten().flatten().copied().collect();
+        if checksum(&message) != expected {
+            return Err(invalid("message does not match its checksum"));
+        }
+        Ok(message)
+    }
+
+    /// The reassembled block. Its hash must match its contents; the
+    /// signature is left to whoever submits the block.
+    pub fn block(&self) -> Result<StateBlock, WalletError> {
+        let block: StateBlock =
+            bincode::deserialize(&self.message()?).map_err(|e| invalid(e.to_string()))?;
+        if block.hash != block.compute_hash() {
+            return Err(invalid("block hash does not match its contents"));
+        }
+        Ok(block)
+    }
+}
+
+/// A scanned QR code, classified.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QrPayload {
+    Address(WalletAddress),
+    Payment(PaymentRequest),
+    Frame(UrFrame),
+}
+
+impl QrPayload {
+    /// Classify and parse scanned text.
+    pub fn parse(text: &str) -> Result<Self, WalletError> {
+        let text = text.trim();
+        let lowercase = text.to_ascii_lowercase();
+        if lowercase.starts_with(URI_SCHEME) {
+            PaymentRequest::parse(text).map(Self::Payment)
+        } else if lowercase.starts_with(&UR_PREFIX.to_ascii_lowercase()) {
+            UrFrame::parse(text).map(Self::Frame)
+        } else if burst_crypto::decode_address(text).is_some() {
+            Ok(Self::Address(WalletAddress::new(text)))
+        } else {
+            Err(invalid("not an address, payment request or QR frame"))
+        }
+    }
+}
+
+/// A QR code ready to display.
+pub struct QrImage {
+    code: qrcode::QrCode,
+}
+
+impl QrImage {
+    /// Encode `text`, choosing the smallest QR version that holds it.
+    pub fn encode(text: &str) -> Result<Self, WalletError> {
+        qrcode::QrCode::new(text.as_bytes())
+            .map(|code| Self { code })
+            .map_err(|e| invalid(e.to_string()))
+    }
+
+    /// Modules per side, without the quiet zone.
+    pub fn width(&self) -> usize {
+        self.code.width()
+    }
+
+    /// Whether the module at column `x`, row `y` is dark.
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.code[(x, y)] == qrcode::Color::Dark
+    }
+
+    /// An SVG document at least `min_px` pixels square.
+    pub fn to_svg(&self, min_px: u32) -> String {
+        self.code
+            .render::<qrcode::render::svg::Color>()
+            .min_dimensions(min_px, min_px)
+            .build()
+    }
+
+    /// Block characters for a terminal, two module rows per line.
+    pub fn to_terminal(&self) -> String {
+        self.code
+            .render::<qrcode::render::unicode::Dense1x2>()
+            .build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction_builder::{build_burn_tx, build_state_block, AccountState};
+    use burst_types::{BlockHash, NetworkId, Timestamp, TxHash};
+
+    fn unsigned_block() -> StateBlock {
+        let keys = burst_crypto::generate_keypair();
+        let account = burst_crypto::derive_address(&keys.public);
+        let state = AccountState {
+            head: BlockHash::new([1u8; 32]),
+            block_count: 1,
+            representative: account.clone(),
+            brn_balance: 10_000,
+            trst_balance: 0,
+            network: NetworkId::Dev,
+            block_version: burst_ledger::CURRENT_BLOCK_VERSION,
+        };
+        let burn = build_burn_tx(&account, &account, 100, Timestamp::new(1000)).unwrap();
+        let tx = burst_transactions::Transaction::Burn(burn);
+        build_state_block(&state, &tx, TxHash::ZERO, BlockHash::ZERO).unwrap()
+    }
+
+    #[test]
+    fn blocks_survive_animated_frames_in_any_order() {
+        let block = unsigned_block();
+        let frames = block_frames(UrKind::UnsignedBlock, &block, 40).unwrap();
+        assert!(frames.len() > 2);
+        assert!(block_frames(UrKind::SignedBlock, &block, 40).is_err());
+
+        let mut decoder = UrDecoder::new();
+        let texts: Vec<String> = frames.iter().map(UrFrame::to_string).collect();
+        assert!(texts[0].starts_with("UR:BURST-UNSIGNED-BLOCK/1-"));
+        for text in texts[1..].iter().rev() {
+            assert!(!decoder.receive(text).unwrap());
+        }
+        assert!(decoder.message().is_err());
+        // A repeat changes nothing; the missing first frame completes it.
+        assert!(!decoder.receive(&texts[1]).unwrap());
+        assert!(decoder.receive(&texts[0].to_lowercase()).unwrap());
+        assert_eq!(decoder.kind(), Some(UrKind::UnsignedBlock));
+
+        let decoded = decoder.block().unwrap();
+        assert_eq!(decoded.hash, block.hash);
+
+        let other = encode_frames(UrKind::UnsignedBlock, b"other message", 40).unwrap();
+        assert!(decoder.receive(&other[0].to_string()).is_err());
+    }
+
+    #[test]
+    fn scanned_text_is_classified() {
+        let address = burst_crypto::derive_address(&burst_crypto::generate_keypair().public);
+        assert_eq!(
+            QrPayload::parse(address.as_str()).unwrap(),
+            QrPayload::Address(address.clone())
+        );
+
+        let request = PaymentRequest::new(address).with_amount(1500).unwrap();
+        assert_eq!(
+            QrPayload::parse(&request.to_uri()).unwrap(),
+            QrPayload::Payment(request.clone())
+        );
+
+        let frame = encode_frames(UrKind::SignedBlock, b"signed", 100).unwrap();
+        assert_eq!(
+            QrPayload::parse(&frame[0].to_string()).unwrap(),
+            QrPayload::Frame(frame[0].clone())
+        );
+        assert!(QrPayload::parse("UR:BURST-SIGNED-BLOCK/2-1/00000000/AB").is_err());
+        assert!(QrPayload::parse("hello").is_err());
+
+        let image = QrImage::encode(&request.to_uri()).unwrap();
+        assert!(image.width() >= 21);
+        assert!(image.is_dark(0, 0)); // finder pattern corner
+        assert!(image.to_svg(200).starts_with("<?xml"));
+    }
+}
@@ -0,0 +1,480 @@
+//! Receive gate — enforce a trust policy on incoming transfers.
+//!
+//! For each pending transfer the gate asks the groups named by the wallet's
+//! [`TrustPolicy`] about the sender, decides whether the sender clears the
+//! policy, and issues a Receive or a RejectReceive block accordingly. Every
+//! decision is appended to an audit log so the user can see why a payment
+//! was bounced.
+//!
+//! A group vouches for a sender when it reports them as a valid member with a
+//! score of at least [`ReceiveGate::min_score`]. Unreachable or unregistered
+//! groups never vouch. Custom rules other than `GroupMembership` need node or
+//! provenance data the gate doesn't have and are not evaluated here.
+//!
+//! Senders on the gate's allowlist — usually the address book contacts
+//! marked [`allow_receive`](crate::contacts::Contact::allow_receive) — are
+//! accepted without asking any group.
+
+use std::collections::HashSet;
+
+use burst_groups::{CachedGroupClient, GroupRegistry, MemberStatus};
+use burst_transactions::reject_receive::RejectReason;
+use burst_types::{Timestamp, WalletAddress};
+use serde::{Deserialize, Serialize};
+
+use crate::trust_policy::{TrustPolicy, TrustRule};
+
+/// What one group said about a sender.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GroupVerdict {
+    pub group_id: String,
+    pub valid: bool,
+    /// Score reported by the group, if it answered.
+    pub score: Option<f64>,
+    /// Why the group could not be asked, if it couldn't.
+    pub error: Option<String>,
+}
+
+impl GroupVerdict {
+    fn from_status(group_id: String, status: &MemberStatus) -> Self {
+        Self {
+            group_id,
+            valid: status.valid,
+            score: Some(status.score),
+            error: None,
+        }
+    }
+
+    fn failed(group_id: String, error: String) -> Self {
+        Self {
+            group_id,
+            valid: false,
+            score: None,
+            error: Some(error),
+        }
+    }
+
+    fn vouches(&self, min_score: f64) -> bool {
+        self.valid && self.score.is_some_and(|s| s >= min_score)
+    }
+}
+
+/// Outcome of evaluating a sender against the policy.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+pub enum GateDecision {
+    Accept,
+    Reject {
+        reason: RejectReason,
+        detail: String,
+    },
+}
+
+/// Evaluate a policy against the verdicts gathered for a sender.
+///
+/// Senders who are members of a required group but score below `min_score`
+/// are rejected with [`RejectReason::PolicyViolation`]; non-members with
+/// [`RejectReason::UnknownSender`].
+pub fn evaluate_membership(
+    policy: &TrustPolicy,
+    min_score: f64,
+    verdicts: &[GroupVerdict],
+) -> GateDecision {
+    let verdict = |id: &str| verdicts.iter().find(|v| v.group_id == id);
+    let failure = |groups: &[&String]| {
+        let low_score = groups
+            .iter()
+            .filter_map(|id| verdict(id))
+            .find(|v| v.valid && !v.vouches(min_score));
+        match low_score {
+            Some(v) => GateDecision::Reject {
+                reason: RejectReason::PolicyViolation,
+                detail: format!(
+                    "score {} from group {} is below the minimum {}",
+                    v.score.unwrap_or(0.0),
+                    v.group_id,
+                    min_score
+                ),
+            },
+            None => GateDecision::Reject {
+                reason: RejectReason::UnknownSender,
+                detail: format!(
+                    "sender is not a member of {}",
+                    groups
+                        .iter()
+                        .map(|g| g.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            },
+        }
+    };
+
+    match policy {
+        TrustPolicy::AcceptAll => GateDecision::Accept,
+        TrustPolicy::RequireGroup { trusted_groups } => {
+            if trusted_groups.is_empty()
+                || trusted_groups
+                    .iter()
+                    .any(|id| verdict(id).is_some_and(|v| v.vouches(min_score)))
+            {
+                GateDecision::Accept
+            } else {
+                failure(&trusted_groups.iter().collect::<Vec<_>>())
+            }
+        }
+        TrustPolicy::RequireAllGroups { trusted_groups } => {
+            let missing: Vec<&String> = trusted_groups
+                .iter()
+                .filter(|id| !verdict(id).is_some_and(|v| v.vouches(min_score)))
+                .collect();
+            if missing.is_empty() {
+                GateDecision::Accept
+            } else {
+                failure(&missing)
+            }
+        }
+        TrustPolicy::Custom { rules } => {
+            let missing: Vec<&String> = rules
+                .iter()
+                .filter_map(|rule| match rule {
+                    TrustRule::GroupMembership { group_id } => Some(group_id),
+                    _ => None,
+                })
+                .filter(|id| !verdict(id).is_some_and(|v| v.vouches(min_score)))
+                .collect();
+            if missing.is_empty() {
+                GateDecision::Accept
+            } else {
+                failure(&missing)
+            }
+        }
+    }
+}
+
+/// One audited receive decision.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: Timestamp,
+    /// Hash of the send being decided on.
+    pub send_block_hash: String,
+    pub sender: WalletAddress,
+    pub amount: u128,
+    pub decision: GateDecision,
+    pub verdicts: Vec<GroupVerdict>,
+    /// Hash of the Receive/RejectReceive block, once the node accepted it.
+    pub block_hash: Option<String>,
+    /// Why submission failed, if it did.
+    pub error: Option<String>,
+}
+
+/// Applies a trust policy to pending transfers and keeps an audit log.
+pub struct ReceiveGate {
+    policy: TrustPolicy,
+    min_score: f64,
+    registry: GroupRegistry,
+    client: CachedGroupClient,
+    allowlist: HashSet<WalletAddress>,
+    audit: Vec<AuditEntry>,
+}
+
+impl ReceiveGate {
+    /// Create a gate that resolves group IDs through `registry`.
+    pub fn new(policy: TrustPolicy, registry: GroupRegistry) -> Self {
+        Self {
+            policy,
+            min_score: 0.0,
+            registry,
+            client: CachedGroupClient::default(),
+            allowlist: HashSet::new(),
+            audit: Vec::new(),
+        }
+    }
+
+    /// Require vouching groups to report at least this score.
+    pub fn with_min_score(mut self, min_score: f64) -> Self {
+        self.min_score = min_score;
+        self
+    }
+
+    /// Use a specific group client (e.g. with different TTLs or fallback).
+    pub fn with_client(mut self, client: CachedGroupClient) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Accept transfers from these senders without checking the policy,
+    /// e.g. [`Contacts::allowlist`](crate::contacts::Contacts::allowlist).
+    pub fn with_allowlist(mut self, senders: impl IntoIterator<Item = WalletAddress>) -> Self {
+        self.allowlist = senders.into_iter().collect();
+        self
+    }
+
+    pub fn is_allowlisted(&self, sender: &WalletAddress) -> bool {
+        self.allowlist.contains(sender)
+    }
+
+    pub fn policy(&self) -> &TrustPolicy {
+        &self.policy
+    }
+
+    pub fn min_score(&self) -> f64 {
+        self.min_score
+    }
+
+    /// Every decision made so far, oldest first.
+    pub fn audit_log(&self) -> &[AuditEntry] {
+        &self.audit
+    }
+
+    /// Ask every group the policy references about `sender`.
+    pub async fn query_sender(&self, sender: &WalletAddress) -> Vec<GroupVerdict> {
+        let mut verdicts = Vec::new();
+        let mut endpoints = Vec::new();
+        for id in self.policy.referenced_groups() {
+            match self.registry.get(&id) {
+                Some(info) => endpoints.push((id, info.endpoint_url.clone())),
+                None => verdicts.push(GroupVerdict::failed(id, "group not registered".into())),
+            }
+        }
+        let pairs: Vec<(&str, &str)> = endpoints
+            .iter()
+            .map(|(id, url)| (id.as_str(), url.as_str()))
+            .collect();
+        let results = self
+            .client
+            .verify_member_multi(&pairs, sender.as_str())
+            .await;
+        // Results come back in input order; take IDs from `endpoints` since a
+        // failed task reports its group as "unknown".
+        for ((id, _), (_, result)) in endpoints.into_iter().zip(results) {
+            verdicts.push(match result {
+                Ok(status) => GroupVerdict::from_status(id, &status),
+                Err(e) => GroupVerdict::failed(id, e.to_string()),
+            });
+        }
+        verdicts
+    }
+
+    /// Query the groups about `sender` and evaluate the policy. Allowlisted
+    /// senders are accepted with no verdicts.
+    pub async fn decide(&self, sender: &WalletAddress) -> (GateDecision, Vec<GroupVerdict>) {
+        if self.is_allowlisted(sender) {
+            return (GateDecision::Accept, Vec::new());
+        }
+        let verdicts = self.query_sender(sender).await;
+        let decision = evaluate_membership(&self.policy, self.min_score, &verdicts);
+        (decision, verdicts)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ReceiveGate {
+    /// Decide on every pending transfer of `keys`' account and submit the
+    /// resulting Receive or RejectReceive blocks, chained in order.
+    ///
+    /// Returns the audit entries added by this call. A block the node
+    /// refuses is recorded with its error and does not advance the chain.
+    pub async fn process_pending(
+        &mut self,
+        client: &crate::wallet::NodeClient,
+        keys: &burst_types::KeyPair,
+        count: u64,
+        now: Timestamp,
+    ) -> Result<Vec<AuditEntry>, crate::error::WalletError> {
+        use crate::error::WalletError;
+        use crate::transaction_builder::{
+            build_and_sign_state_block, build_receive_tx, build_reject_receive_tx,
+        };
+        use burst_transactions::Transaction;
+        use burst_types::{BlockHash, TxHash};
+
+        let account = burst_crypto::derive_address(&keys.public);
+        let mut state = client
+            .account_info(account.as_str())
+            .await?
+            .account_state()?;
+        let pending = client.account_pending(account.as_str(), count).await?;
+
+        let first_new = self.audit.len();
+        for entry in pending.blocks {
+            let send_hash = hex::decode(&entry.hash)
+                .ok()
+                .and_then(|b| <[u8; 32]>::try_from(b).ok())
+                .map(TxHash::new)
+                .ok_or_else(|| {
+                    WalletError::Node(format!("invalid pending hash: {}", entry.hash))
+                })?;
+            let amount: u128 = entry
+                .amount
+                .parse()
+                .map_err(|e| WalletError::Node(format!("invalid pending amount: {e}")))?;
+            let sender = WalletAddress::new(entry.source.clone());
+
+            let (decision, verdicts) = self.decide(&sender).await;
+            let tx = match decision {
+                GateDecision::Accept => {
+                    Transaction::Receive(build_receive_tx(&account, send_hash, amount, now)?)
+                }
+                GateDecision::Reject { reason, .. } => Transaction::RejectReceive(
+                    build_reject_receive_tx(&account, send_hash, reason, now)?,
+                ),
+            };
+            let mut block = build_and_sign_state_block(
+                &state,
+                &tx,
+                &keys.private,
+                TxHash::ZERO,
+                BlockHash::ZERO,
+            )?;
+
+            let (block_hash, error) = match client.submit_block(&mut block).await {
+                Ok(result) if result.accepted => {
+                    state.head = block.hash;
+                    state.block_count += 1;
+                    state.trst_balance = block.trst_balance;
+                    (Some(format!("{}", block.hash)), None)
+                }
+                Ok(result) => (
+                    None,
+                    Some(result.detail.unwrap_or_else(|| "rejected".into())),
+                ),
+                Err(e) => (None, Some(e.to_string())),
+            };
+
+            self.audit.push(AuditEntry {
+                timestamp: now,
+                send_block_hash: entry.hash,
+                sender,
+                amount,
+                decision,
+                verdicts,
+                block_hash,
+                error,
+            });
+        }
+        Ok(self.audit[first_new..].to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(id: &str, score: f64) -> GroupVerdict {
+        GroupVerdict {
+            group_id: id.into(),
+            valid: true,
+            score: Some(score),
+            error: None,
+        }
+    }
+
+    fn groups(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn require_group_accepts_any_vouching_group() {
+        let policy = TrustPolicy::RequireGroup {
+            trusted_groups: groups(&["a", "b"]),
+        };
+        let verdicts = vec![
+            GroupVerdict::failed("a".into(), "unreachable".into()),
+            member("b", 0.9),
+        ];
+        assert_eq!(
+            evaluate_membership(&policy, 0.5, &verdicts),
+            GateDecision::Accept
+        );
+    }
+
+    #[test]
+    fn low_score_is_a_policy_violation() {
+        let policy = TrustPolicy::RequireGroup {
+            trusted_groups: groups(&["a"]),
+        };
+        let decision = evaluate_membership(&policy, 0.8, &[member("a", 0.3)]);
+        assert!(matches!(
+            decision,
+            GateDecision::Reject {
+                reason: RejectReason::PolicyViolation,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn non_member_is_unknown_sender() {
+        let policy = TrustPolicy::RequireAllGroups {
+            trusted_groups: groups(&["a", "b"]),
+        };
+        let mut outsider = member("b", 0.0);
+        outsider.valid = false;
+        let decision = evaluate_membership(&policy, 0.0, &[member("a", 1.0), outsider]);
+        match decision {
+            GateDecision::Reject { reason, detail } => {
+                assert_eq!(reason, RejectReason::UnknownSender);
+                assert!(detail.ends_with("member of b"), "{detail}");
+            }
+            other => panic!("expected rejection, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn custom_policy_checks_only_group_rules() {
+        let policy = TrustPolicy::Custom {
+            rules: vec![
+                TrustRule::GroupMembership {
+                    group_id: "a".into(),
+                },
+                TrustRule::MinVerificationAge { seconds: 60 },
+            ],
+        };
+        assert_eq!(
+            evaluate_membership(&policy, 0.0, &[member("a", 0.1)]),
+            GateDecision::Accept
+        );
+        assert_eq!(policy.referenced_groups(), groups(&["a"]));
+    }
+
+    #[tokio::test]
+    async fn unregistered_groups_never_vouch() {
+        let policy = TrustPolicy::RequireGroup {
+            trusted_groups: groups(&["missing"]),
+        };
+        let gate = ReceiveGate::new(policy, GroupRegistry::new());
+        let sender = WalletAddress::new("brst_sender");
+        let (decision, verdicts) = gate.decide(&sender).await;
+        assert_eq!(verdicts.len(), 1);
+        assert!(verdicts[0].error.is_some());
+        assert!(matches!(
+            decision,
+            GateDecision::Reject {
+                reason: RejectReason::UnknownSender,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn allowlisted_contacts_skip_the_policy() {
+        let sender = burst_crypto::derive_address(&burst_crypto::generate_keypair().public);
+        let mut contacts = crate::contacts::Contacts::new();
+        contacts
+            .add(crate::contacts::Contact::new("friend", sender.clone()).allowing_receive())
+            .unwrap();
+        let policy = TrustPolicy::RequireGroup {
+            trusted_groups: groups(&["missing"]),
+        };
+        let gate =
+            ReceiveGate::new(policy, GroupRegistry::new()).with_allowlist(contacts.allowlist());
+
+        assert_eq!(
+            gate.decide(&sender).await,
+            (GateDecision::Accept, Vec::new())
+        );
+        let (decision, _) = gate.decide(&WalletAddress::new("brst_other")).await;
+        assert!(matches!(decision, GateDecision::Reject { .. }));
+    }
+}
@@ -0,0 +1,395 @@
+//! Health-based automatic re-delegation.
+//!
+//! A [`RepMonitor`] watches the wallet's consensus representative using the
+//! node's `representative_info` telemetry. When the representative has been
+//! offline for longer than the configured threshold, the monitor picks the
+//! first healthy replacement from the user's candidate list (in the user's
+//! order of preference) and builds a signed `ChangeRepresentative` block.
+//!
+//! The monitor never submits anything itself; the caller attaches PoW and
+//! submits the block, exactly as with scheduled payments.
+
+use burst_ledger::StateBlock;
+use burst_types::{BlockHash, PrivateKey, Timestamp, TxHash, WalletAddress};
+
+use crate::error::WalletError;
+use crate::transaction_builder::{build_and_sign_state_block, build_change_rep_tx, AccountState};
+
+/// Default time a representative may be offline before re-delegating.
+pub const DEFAULT_OFFLINE_THRESHOLD_SECS: u64 = 60 * 60;
+
+/// Configuration for automatic re-delegation.
+#[derive(Clone, Debug)]
+pub struct RepMonitorConfig {
+    /// Whether automatic re-delegation is enabled.
+    pub enabled: bool,
+    /// How long the representative must be offline before switching.
+    pub offline_threshold_secs: u64,
+    /// Replacement representatives, most preferred first.
+    pub candidates: Vec<WalletAddress>,
+    /// Minimum uptime score (`0.0..=1.0`) a candidate needs, when the node
+    /// reports one.
+    pub min_uptime_score: f64,
+}
+
+impl Default for RepMonitorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            offline_threshold_secs: DEFAULT_OFFLINE_THRESHOLD_SECS,
+            candidates: Vec::new(),
+            min_uptime_score: 0.5,
+        }
+    }
+}
+
+/// A representative's health as reported by the node.
+#[derive(Clone, Debug)]
+pub struct RepHealth {
+    pub account: WalletAddress,
+    pub weight: u128,
+    pub online: bool,
+    pub last_vote_age_secs: Option<u64>,
+    pub uptime_score: Option<f64>,
+}
+
+/// Result of one monitor check.
+#[derive(Clone, Debug)]
+pub enum RepCheck {
+    /// Auto re-delegation is disabled.
+    Disabled,
+    /// The representative is online.
+    Healthy,
+    /// The representative is offline but still within the threshold.
+    Offline { offline_secs: u64 },
+    /// Over the threshold, but no candidate is healthy enough to switch to.
+    NoCandidate { offline_secs: u64 },
+    /// A signed `ChangeRepresentative` block ready for PoW and submission.
+    Redelegate {
+        new_representative: WalletAddress,
+        block: Box<StateBlock>,
+    },
+}
+
+/// Everything needed to build the re-delegation block.
+pub struct RedelegationContext<'a> {
+    pub account: WalletAddress,
+    pub account_state: AccountState,
+    pub params_hash: BlockHash,
+    pub private_key: &'a PrivateKey,
+}
+
+/// Watches the configured representative and re-delegates when it goes dark.
+pub struct RepMonitor {
+    config: RepMonitorConfig,
+    /// Representative being watched and when we first saw it offline.
+    offline_since: Option<(WalletAddress, Timestamp)>,
+}
+
+impl RepMonitor {
+    pub fn new(config: RepMonitorConfig) -> Self {
+        Self {
+            config,
+            offline_since: None,
+        }
+    }
+
+    pub fn config(&self) -> &RepMonitorConfig {
+        &self.config
+    }
+
+    /// How long `current` has been offline at `now`, or `None` if it is online.
+    ///
+    /// Uses the larger of the node's last-vote age and the time since this
+    /// monitor first saw the representative offline, so a freshly started
+    /// wallet does not wait a full threshold for a long-dead representative.
+    pub fn observe(&mut self, current: &RepHealth, now: Timestamp) -> Option<u64> {
+        if current.online {
+            self.offline_since = None;
+            return None;
+        }
+        let since = match &self.offline_since {
+            Some((rep, since)) if *rep == current.account => *since,
+            _ => {
+                self.offline_since = Some((current.account.clone(), now));
+                now
+            }
+        };
+        let observed = now.as_secs().saturating_sub(since.as_secs());
+        Some(observed.max(current.last_vote_age_secs.unwrap_or(0)))
+    }
+
+    /// The first candidate, in preference order, that is online and meets
+    /// the minimum uptime score. `candidates` carries the node's view of
+    /// each configured candidate; unknown candidates are skipped.
+    pub fn choose_replacement(
+        &self,
+        current: &WalletAddress,
+        candidates: &[RepHealth],
+    ) -> Option<WalletAddress> {
+        self.config
+            .candidates
+            .iter()
+            .filter(|addr| *addr != current)
+            .find(|addr| {
+                candidates.iter().any(|health| {
+                    health.account == **addr
+                        && health.online
+                        && health.weight > 0
+                        && health
+                            .uptime_score
+                            .is_none_or(|score| score >= self.config.min_uptime_score)
+                })
+            })
+            .cloned()
+    }
+
+    /// Check the representative and build a re-delegation block if needed.
+    pub fn check(
+        &mut self,
+        current: &RepHealth,
+        candidates: &[RepHealth],
+        now: Timestamp,
+        ctx: RedelegationContext<'_>,
+    ) -> Result<RepCheck, WalletError> {
+        if !self.config.enabled {
+            return Ok(RepCheck::Disabled);
+        }
+        let Some(offline_secs) = self.observe(current, now) else {
+            return Ok(RepCheck::Healthy);
+        };
+        if offline_secs < self.config.offline_threshold_secs {
+            return Ok(RepCheck::Offline { offline_secs });
+        }
+        let Some(new_representative) = self.choose_replacement(&current.account, candidates) else {
+            return Ok(RepCheck::NoCandidate { offline_secs });
+        };
+
+        let tx = build_change_rep_tx(&ctx.account, &new_representative, now)?;
+        let block = build_and_sign_state_block(
+            &ctx.account_state,
+            &burst_transactions::Transaction::ChangeRepresentative(tx),
+            ctx.private_key,
+            TxHash::ZERO,
+            ctx.params_hash,
+        )?;
+        self.offline_since = None;
+        Ok(RepCheck::Redelegate {
+            new_representative,
+            block: Box::new(block),
+        })
+    }
+
+    /// Query the node for the account's representative and the candidates,
+    /// then run [`check`](Self::check).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn poll(
+        &mut self,
+        client: &crate::wallet::NodeClient,
+        account: &WalletAddress,
+        private_key: &PrivateKey,
+        params_hash: BlockHash,
+        now: Timestamp,
+    ) -> Result<RepCheck, WalletError> {
+        if !self.config.enabled {
+            return Ok(RepCheck::Disabled);
+        }
+        let info = client.account_info(account.as_str()).await?;
+        let account_state = info.account_state()?;
+        let current = client
+            .representative_info(&info.representative)
+            .await?
+            .health();
+
+        let mut candidates = Vec::with_capacity(self.config.candidates.len());
+        for candidate in &self.config.candidates {
+            match client.representative_info(candidate.as_str()).await {
+                Ok(info) => candidates.push(info.health()),
+                // An unreachable candidate just isn't eligible this round.
+                Err(WalletError::Node(_) | WalletError::Rpc { .. }) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        self.check(
+            &current,
+            &candidates,
+            now,
+            RedelegationContext {
+                account: account.clone(),
+                account_state,
+                params_hash,
+                private_key,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_ledger::BlockType;
+    use burst_types::{KeyPair, NetworkId};
+
+    fn address(seed: u8) -> (WalletAddress, KeyPair) {
+        let keys = burst_crypto::keypair_from_seed(&[seed; 32]);
+        (burst_crypto::derive_address(&keys.public), keys)
+    }
+
+    fn health(account: &WalletAddress, online: bool, uptime: Option<f64>) -> RepHealth {
+        RepHealth {
+            account: account.clone(),
+            weight: 1_000,
+            online,
+            last_vote_age_secs: None,
+            uptime_score: uptime,
+        }
+    }
+
+    fn ctx<'a>(
+        owner: &WalletAddress,
+        rep: &WalletAddress,
+        key: &'a PrivateKey,
+    ) -> RedelegationContext<'a> {
+        RedelegationContext {
+            account: owner.clone(),
+            account_state: AccountState {
+                head: BlockHash::new([3u8; 32]),
+                block_count: 4,
+                representative: rep.clone(),
+                brn_balance: 0,
+                trst_balance: 50,
+                network: NetworkId::Dev,
+                block_version: burst_ledger::CURRENT_BLOCK_VERSION,
+            },
+            params_hash: BlockHash::ZERO,
+            private_key: key,
+        }
+    }
+
+    #[test]
+    fn redelegates_after_threshold_to_first_healthy_candidate() {
+        let (owner, owner_keys) = address(1);
+        let (rep, _) = address(2);
+        let (flaky, _) = address(3);
+        let (steady, _) = address(4);
+        let mut monitor = RepMonitor::new(RepMonitorConfig {
+            enabled: true,
+            offline_threshold_secs: 600,
+            candidates: vec![flaky.clone(), steady.clone()],
+            min_uptime_score: 0.5,
+        });
+        let candidates = [
+            health(&flaky, true, Some(0.2)),
+            health(&steady, true, Some(0.9)),
+        ];
+        let current = health(&rep, false, None);
+
+        let first = monitor
+            .check(
+                &current,
+                &candidates,
+                Timestamp::new(1_000),
+                ctx(&owner, &rep, &owner_keys.private),
+            )
+            .unwrap();
+        assert!(matches!(first, RepCheck::Offline { offline_secs: 0 }));
+
+        let second = monitor
+            .check(
+                &current,
+                &candidates,
+                Timestamp::new(1_600),
+                ctx(&owner, &rep, &owner_keys.private),
+            )
+            .unwrap();
+        let RepCheck::Redelegate {
+            new_representative,
+            block,
+        } = second
+        else {
+            panic!("expected re-delegation, got {second:?}");
+        };
+        assert_eq!(new_representative, steady);
+        assert_eq!(block.block_type, BlockType::ChangeRepresentative);
+        assert_eq!(block.representative, steady);
+        assert_eq!(block.previous, BlockHash::new([3u8; 32]));
+        assert!(burst_crypto::verify_signature(
+            block.hash.as_bytes(),
+            &block.signature,
+            &owner_keys.public
+        ));
+    }
+
+    #[test]
+    fn last_vote_age_counts_toward_threshold() {
+        let (owner, owner_keys) = address(1);
+        let (rep, _) = address(2);
+        let (backup, _) = address(3);
+        let mut monitor = RepMonitor::new(RepMonitorConfig {
+            enabled: true,
+            offline_threshold_secs: 600,
+            candidates: vec![backup.clone()],
+            ..Default::default()
+        });
+        let mut current = health(&rep, false, None);
+        current.last_vote_age_secs = Some(3_600);
+
+        let check = monitor
+            .check(
+                &current,
+                &[health(&backup, true, None)],
+                Timestamp::new(1_000),
+                ctx(&owner, &rep, &owner_keys.private),
+            )
+            .unwrap();
+        assert!(matches!(check, RepCheck::Redelegate { .. }));
+    }
+
+    #[test]
+    fn recovery_resets_and_no_candidate_is_reported() {
+        let (owner, owner_keys) = address(1);
+        let (rep, _) = address(2);
+        let (backup, _) = address(3);
+        let mut monitor = RepMonitor::new(RepMonitorConfig {
+            enabled: true,
+            offline_threshold_secs: 600,
+            candidates: vec![backup.clone(), rep.clone()],
+            ..Default::default()
+        });
+        let offline = health(&rep, false, None);
+        monitor.observe(&offline, Timestamp::new(1_000));
+        assert_eq!(
+            monitor.observe(&health(&rep, true, None), Timestamp::new(1_500)),
+            None
+        );
+        assert_eq!(monitor.observe(&offline, Timestamp::new(1_700)), Some(0));
+
+        // The only other candidate is offline; the current rep is never chosen.
+        let check = monitor
+            .check(
+                &offline,
+                &[health(&backup, false, None)],
+                Timestamp::new(2_400),
+                ctx(&owner, &rep, &owner_keys.private),
+            )
+            .unwrap();
+        assert!(matches!(check, RepCheck::NoCandidate { offline_secs: 700 }));
+    }
+
+    #[test]
+    fn disabled_does_nothing() {
+        let (owner, owner_keys) = address(1);
+        let (rep, _) = address(2);
+        let mut monitor = RepMonitor::new(RepMonitorConfig::default());
+        let check = monitor
+            .check(
+                &health(&rep, false, None),
+                &[],
+                Timestamp::new(1_000),
+                ctx(&owner, &rep, &owner_keys.private),
+            )
+            .unwrap();
+        assert!(matches!(check, RepCheck::Disabled));
+    }
+}
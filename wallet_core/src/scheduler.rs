@@ -0,0 +1,742 @@
+//! Recurring / scheduled TRST payments.
+//!
+//! A [`Scheduler`] holds signed payment intents ("send 10 TRST to X every
+//! Monday"). When an intent falls due, the scheduler re-checks the wallet's
+//! transferable TRST balance and the intent's own expiry, then builds and
+//! signs the send block. A schedule only moves to its next occurrence once
+//! the node has accepted the block ([`Scheduler::record_accepted`]), so a
+//! run the node refused is built again on the next tick. Intents are signed
+//! with the wallet's primary key when created, so a tampered keystore file
+//! cannot redirect payments.
+//!
+//! Schedules persist alongside the encrypted key in the keystore file
+//! (see [`Scheduler::save_to`] / [`Scheduler::load_from`]).
+
+use burst_ledger::StateBlock;
+use burst_types::{
+    BlockHash, KeyPair, PrivateKey, PublicKey, Signature, Timestamp, TxHash, WalletAddress,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::error::WalletError;
+use crate::keystore::KeystoreFile;
+use crate::transaction_builder::{
+    build_and_sign_state_block, build_send_tx_with_memo, AccountState,
+};
+
+/// Seconds in a day.
+pub const DAY_SECS: u64 = 86_400;
+/// Seconds in a week.
+pub const WEEK_SECS: u64 = 7 * DAY_SECS;
+
+/// How often a scheduled payment repeats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Recurrence {
+    /// Runs once at `first_due`.
+    Once,
+    /// Runs every `every_secs` seconds starting at `first_due`.
+    Interval { every_secs: u64 },
+}
+
+impl Recurrence {
+    pub fn daily() -> Self {
+        Self::Interval {
+            every_secs: DAY_SECS,
+        }
+    }
+
+    /// Weekly on the weekday of the schedule's first due time.
+    pub fn weekly() -> Self {
+        Self::Interval {
+            every_secs: WEEK_SECS,
+        }
+    }
+}
+
+/// Lifecycle of a scheduled payment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleStatus {
+    Active,
+    Paused,
+    Cancelled,
+    /// A one-shot payment ran, or the schedule passed its expiry.
+    Completed,
+}
+
+/// The user-authored part of a schedule, covered by the intent signature.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymentTemplate {
+    pub receiver: WalletAddress,
+    /// Amount per run in raw TRST units.
+    pub amount: u128,
+    #[serde(default)]
+    pub memo: Option<Vec<u8>>,
+    pub recurrence: Recurrence,
+    pub first_due: Timestamp,
+    /// No runs are generated at or after this time.
+    #[serde(default)]
+    pub expires_at: Option<Timestamp>,
+}
+
+impl PaymentTemplate {
+    /// Canonical bytes signed by the wallet when the intent is created.
+    fn signing_bytes(&self, owner: &WalletAddress) -> [u8; 32] {
+        let recurrence = match self.recurrence {
+            Recurrence::Once => 0u64,
+            Recurrence::Interval { every_secs } => every_secs,
+        };
+        let memo = self.memo.as_deref().unwrap_or(&[]);
+        burst_crypto::blake2b_256_multi(&[
+            b"burst-scheduled-payment",
+            owner.as_str().as_bytes(),
+            self.receiver.as_str().as_bytes(),
+            &self.amount.to_be_bytes(),
+            &[memo.len() as u8],
+            memo,
+            &recurrence.to_be_bytes(),
+            &self.first_due.as_secs().to_be_bytes(),
+            &self.expires_at.map_or(0, |t| t.as_secs()).to_be_bytes(),
+        ])
+    }
+}
+
+/// A signed payment intent plus its execution state.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScheduledPayment {
+    /// Hex identifier (the intent digest).
+    pub id: String,
+    pub template: PaymentTemplate,
+    /// Hex-encoded Ed25519 signature over the intent digest.
+    pub intent_signature: String,
+    pub status: ScheduleStatus,
+    pub next_due: Timestamp,
+    /// Number of blocks generated so far.
+    #[serde(default)]
+    pub runs: u64,
+    /// Reason the most recent due run was skipped, if any.
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+impl ScheduledPayment {
+    fn is_due(&self, now: Timestamp) -> bool {
+        self.status == ScheduleStatus::Active && now >= self.next_due
+    }
+
+    fn verify(&self, owner: &WalletAddress, public_key: &PublicKey) -> bool {
+        let digest = self.template.signing_bytes(owner);
+        let Ok(bytes) = hex::decode(&self.intent_signature) else {
+            return false;
+        };
+        let Ok(sig) = <[u8; 64]>::try_from(bytes.as_slice()) else {
+            return false;
+        };
+        self.id == hex::encode(digest)
+            && burst_crypto::verify_signature(&digest, &Signature(sig), public_key)
+    }
+
+    /// Move to the next occurrence after the node accepted a run.
+    fn advance(&mut self, now: Timestamp) {
+        self.runs += 1;
+        self.last_error = None;
+        match self.template.recurrence {
+            Recurrence::Once => self.status = ScheduleStatus::Completed,
+            Recurrence::Interval { every_secs } => {
+                // Skip occurrences missed while the wallet was offline rather
+                // than paying them all at once.
+                self.next_due = first_occurrence_from(
+                    self.next_due,
+                    every_secs,
+                    now.as_secs().saturating_add(1),
+                );
+                if self.template.expires_at.is_some_and(|e| self.next_due >= e) {
+                    self.status = ScheduleStatus::Completed;
+                }
+            }
+        }
+    }
+}
+
+/// First occurrence at or after `at` of a schedule due at `next` and every
+/// `every_secs` (non-zero) seconds after that.
+fn first_occurrence_from(next: Timestamp, every_secs: u64, at: u64) -> Timestamp {
+    let next = next.as_secs();
+    if next >= at {
+        return Timestamp::new(next);
+    }
+    let steps = (at - next).div_ceil(every_secs);
+    Timestamp::new(next.saturating_add(steps.saturating_mul(every_secs)))
+}
+
+/// Everything needed to build blocks for due payments.
+///
+/// `account_state` and `transferable_trst` are advanced after each block so
+/// several payments falling due together chain correctly.
+pub struct ExecutionContext<'a> {
+    pub account_state: AccountState,
+    /// Spendable (non-expired, non-revoked) TRST.
+    pub transferable_trst: u128,
+    /// Origin carried by the account's head block.
+    pub previous_origin: TxHash,
+    /// Hash of the protocol parameters in force.
+    pub params_hash: BlockHash,
+    pub private_key: &'a PrivateKey,
+}
+
+/// Result of processing one due schedule.
+#[derive(Clone, Debug)]
+pub enum RunOutcome {
+    /// A signed send block ready for PoW and submission. The schedule stays
+    /// due until [`Scheduler::record_accepted`] is called for it.
+    Built(Box<StateBlock>),
+    /// The run was skipped and will be retried on the next tick.
+    Skipped(String),
+    /// The schedule reached its expiry without running.
+    Expired,
+}
+
+#[derive(Clone, Debug)]
+pub struct ScheduledRun {
+    pub schedule_id: String,
+    pub outcome: RunOutcome,
+}
+
+/// Holds and executes the wallet's scheduled payments.
+pub struct Scheduler {
+    owner: WalletAddress,
+    payments: Vec<ScheduledPayment>,
+}
+
+impl Scheduler {
+    pub fn new(owner: WalletAddress) -> Self {
+        Self {
+            owner,
+            payments: Vec::new(),
+        }
+    }
+
+    /// Sign and register a new payment intent. Returns the schedule id.
+    pub fn schedule(
+        &mut self,
+        template: PaymentTemplate,
+        keys: &KeyPair,
+    ) -> Result<String, WalletError> {
+        if template.amount == 0 {
+            return Err(WalletError::TransactionBuild(
+                "scheduled amount must be greater than zero".into(),
+            ));
+        }
+        if burst_crypto::derive_address(&keys.public) != self.owner {
+            return Err(WalletError::Signing(
+                "key does not belong to scheduler owner".into(),
+            ));
+        }
+        if template.recurrence == (Recurrence::Interval { every_secs: 0 }) {
+            return Err(WalletError::TransactionBuild(
+                "schedule interval must be greater than zero".into(),
+            ));
+        }
+        if let Some(ref memo) = template.memo {
+            if memo.len() > burst_transactions::send::MAX_MEMO_LEN {
+                return Err(WalletError::TransactionBuild(format!(
+                    "memo is {} bytes, maximum is {}",
+                    memo.len(),
+                    burst_transactions::send::MAX_MEMO_LEN
+                )));
+            }
+        }
+        let digest = template.signing_bytes(&self.owner);
+        let id = hex::encode(digest);
+        if self.get(&id).is_some() {
+            return Err(WalletError::Other(format!("schedule {id} already exists")));
+        }
+        let signature = burst_crypto::sign_message(&digest, &keys.private);
+        self.payments.push(ScheduledPayment {
+            id: id.clone(),
+            next_due: template.first_due,
+            template,
+            intent_signature: hex::encode(signature.0),
+            status: ScheduleStatus::Active,
+            runs: 0,
+            last_error: None,
+        });
+        Ok(id)
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ScheduledPayment> {
+        self.payments.iter().find(|p| p.id == id)
+    }
+
+    pub fn list(&self) -> &[ScheduledPayment] {
+        &self.payments
+    }
+
+    fn get_mut(&mut self, id: &str) -> Result<&mut ScheduledPayment, WalletError> {
+        self.payments
+            .iter_mut()
+            .find(|p| p.id == id)
+            .ok_or_else(|| WalletError::Other(format!("unknown schedule: {id}")))
+    }
+
+    /// Pause an active schedule.
+    pub fn pause(&mut self, id: &str) -> Result<(), WalletError> {
+        let p = self.get_mut(id)?;
+        if p.status != ScheduleStatus::Active {
+            return Err(WalletError::Other(format!(
+                "schedule {id} is {:?}",
+                p.status
+            )));
+        }
+        p.status = ScheduleStatus::Paused;
+        Ok(())
+    }
+
+    /// Resume a paused schedule. Occurrences missed while paused are skipped.
+    pub fn resume(&mut self, id: &str, now: Timestamp) -> Result<(), WalletError> {
+        let p = self.get_mut(id)?;
+        if p.status != ScheduleStatus::Paused {
+            return Err(WalletError::Other(format!(
+                "schedule {id} is {:?}",
+                p.status
+            )));
+        }
+        p.status = ScheduleStatus::Active;
+        if let Recurrence::Interval { every_secs } = p.template.recurrence {
+            p.next_due = first_occurrence_from(p.next_due, every_secs, now.as_secs());
+        }
+        Ok(())
+    }
+
+    /// Cancel a schedule permanently.
+    pub fn cancel(&mut self, id: &str) -> Result<(), WalletError> {
+        let p = self.get_mut(id)?;
+        if matches!(
+            p.status,
+            ScheduleStatus::Cancelled | ScheduleStatus::Completed
+        ) {
+            return Err(WalletError::Other(format!(
+                "schedule {id} is {:?}",
+                p.status
+            )));
+        }
+        p.status = ScheduleStatus::Cancelled;
+        Ok(())
+    }
+
+    /// Earliest upcoming due time among active schedules.
+    pub fn next_wakeup(&self) -> Option<Timestamp> {
+        self.payments
+            .iter()
+            .filter(|p| p.status == ScheduleStatus::Active)
+            .map(|p| p.next_due)
+            .min()
+    }
+
+    /// Build signed send blocks for every schedule due at `now`.
+    ///
+    /// Schedules are processed in due order. A run is skipped (and retried
+    /// later) when the transferable balance is insufficient; a schedule past
+    /// its expiry is completed without running. Built runs leave their
+    /// schedule due until the node accepts the block.
+    pub fn prepare_due(
+        &mut self,
+        now: Timestamp,
+        ctx: &mut ExecutionContext<'_>,
+    ) -> Vec<ScheduledRun> {
+        let mut due: Vec<usize> = (0..self.payments.len())
+            .filter(|&i| self.payments[i].is_due(now))
+            .collect();
+        due.sort_by_key(|&i| self.payments[i].next_due);
+
+        let mut runs = Vec::new();
+        for i in due {
+            let owner = self.owner.clone();
+            let payment = &mut self.payments[i];
+            let schedule_id = payment.id.clone();
+
+            if payment.template.expires_at.is_some_and(|e| now >= e) {
+                payment.status = ScheduleStatus::Completed;
+                runs.push(ScheduledRun {
+                    schedule_id,
+                    outcome: RunOutcome::Expired,
+                });
+                continue;
+            }
+
+            let outcome = match build_payment(&owner, payment, now, ctx) {
+                Ok(block) => {
+                    ctx.transferable_trst -= payment.template.amount;
+                    ctx.account_state.head = block.hash;
+                    ctx.account_state.block_count += 1;
+                    ctx.account_state.trst_balance = block.trst_balance;
+                    RunOutcome::Built(Box::new(block))
+                }
+                Err(e) => {
+                    let reason = e.to_string();
+                    payment.last_error = Some(reason.clone());
+                    RunOutcome::Skipped(reason)
+                }
+            };
+            runs.push(ScheduledRun {
+                schedule_id,
+                outcome,
+            });
+        }
+        runs
+    }
+
+    /// Move schedule `id` to its next occurrence once the node accepted the
+    /// block built for it at `now`.
+    pub fn record_accepted(&mut self, id: &str, now: Timestamp) -> Result<(), WalletError> {
+        self.get_mut(id)?.advance(now);
+        Ok(())
+    }
+
+    /// Note why the node refused the block built for schedule `id`. The
+    /// schedule stays due and is built again on the next tick.
+    pub fn record_rejected(&mut self, id: &str, reason: String) -> Result<(), WalletError> {
+        self.get_mut(id)?.last_error = Some(reason);
+        Ok(())
+    }
+
+    /// Persist schedules into the keystore file (alongside the encrypted key).
+    pub fn save_to(&self, keystore: &mut KeystoreFile) {
+        keystore.schedules = self.payments.clone();
+    }
+
+    /// Load schedules from a keystore file, verifying each intent signature
+    /// against the owner's public key. Fails on any tampered intent.
+    pub fn load_from(keystore: &KeystoreFile, public_key: &PublicKey) -> Result<Self, WalletError> {
+        let owner = burst_crypto::derive_address(public_key);
+        for p in &keystore.schedules {
+            if !p.verify(&owner, public_key) {
+                return Err(WalletError::Signing(format!(
+                    "scheduled payment {} has an invalid intent signature",
+                    p.id
+                )));
+            }
+            if p.template.recurrence == (Recurrence::Interval { every_secs: 0 }) {
+                return Err(WalletError::Other(format!(
+                    "scheduled payment {} has a zero interval",
+                    p.id
+                )));
+            }
+        }
+        Ok(Self {
+            owner,
+            payments: keystore.schedules.clone(),
+        })
+    }
+}
+
+fn build_payment(
+    owner: &WalletAddress,
+    payment: &ScheduledPayment,
+    now: Timestamp,
+    ctx: &ExecutionContext<'_>,
+) -> Result<StateBlock, WalletError> {
+    let template = &payment.template;
+    if ctx.transferable_trst < template.amount {
+        return Err(WalletError::InsufficientTrst {
+            needed: template.amount,
+            available: ctx.transferable_trst,
+        });
+    }
+    let tx = build_send_tx_with_memo(
+        owner,
+        &template.receiver,
+        template.amount,
+        TxHash::new(*ctx.account_state.head.as_bytes()),
+        ctx.previous_origin,
+        template.memo.clone(),
+        now,
+    )?;
+    build_and_sign_state_block(
+        &ctx.account_state,
+        &burst_transactions::Transaction::Send(tx),
+        ctx.private_key,
+        ctx.previous_origin,
+        ctx.params_hash,
+    )
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Scheduler {
+    /// Build, attach PoW to, and submit every due payment via the node.
+    ///
+    /// Fetches fresh account state first so balance checks use the node's
+    /// view of transferable TRST, along with the head block's origin and the
+    /// params hash in force. Each schedule advances only once its block is
+    /// accepted; the first rejection stops the round, since later blocks
+    /// chain on the refused one.
+    pub async fn submit_due(
+        &mut self,
+        client: &crate::wallet::NodeClient,
+        keys: &KeyPair,
+        now: Timestamp,
+    ) -> Result<Vec<ScheduledRun>, WalletError> {
+        let info = client.account_info(self.owner.as_str()).await?;
+        let account_state = info.account_state()?;
+        let previous_origin = if account_state.head.is_zero() {
+            TxHash::ZERO
+        } else {
+            client.block_origin(&account_state.head).await?
+        };
+        let mut ctx = ExecutionContext {
+            transferable_trst: account_state.trst_balance,
+            account_state,
+            previous_origin,
+            params_hash: client.params_hash().await?,
+            private_key: &keys.private,
+        };
+
+        let mut runs = self.prepare_due(now, &mut ctx);
+        for run in &mut runs {
+            let RunOutcome::Built(ref mut block) = run.outcome else {
+                continue;
+            };
+            let result = client.submit_block(block).await?;
+            if !result.accepted {
+                let reason = result.detail.unwrap_or_default();
+                self.record_rejected(&run.schedule_id, reason.clone())?;
+                return Err(WalletError::Node(format!(
+                    "scheduled payment {} rejected: {reason}",
+                    run.schedule_id
+                )));
+            }
+            self.record_accepted(&run.schedule_id, now)?;
+        }
+        Ok(runs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_types::NetworkId;
+
+    fn keys() -> KeyPair {
+        burst_crypto::keypair_from_seed(&[5u8; 32])
+    }
+
+    fn receiver() -> WalletAddress {
+        burst_crypto::derive_address(&burst_crypto::keypair_from_seed(&[6u8; 32]).public)
+    }
+
+    fn template(amount: u128, recurrence: Recurrence, first_due: u64) -> PaymentTemplate {
+        PaymentTemplate {
+            receiver: receiver(),
+            amount,
+            memo: Some(b"rent".to_vec()),
+            recurrence,
+            first_due: Timestamp::new(first_due),
+            expires_at: None,
+        }
+    }
+
+    fn context(keys: &KeyPair, trst: u128) -> ExecutionContext<'_> {
+        ExecutionContext {
+            account_state: AccountState {
+                head: BlockHash::new([1u8; 32]),
+                block_count: 3,
+                representative: burst_crypto::derive_address(&keys.public),
+                brn_balance: 0,
+                trst_balance: trst,
+                network: NetworkId::Dev,
+                block_version: burst_ledger::BLOCK_VERSION_V2,
+            },
+            transferable_trst: trst,
+            previous_origin: TxHash::ZERO,
+            params_hash: BlockHash::ZERO,
+            private_key: &keys.private,
+        }
+    }
+
+    fn scheduler(keys: &KeyPair) -> Scheduler {
+        Scheduler::new(burst_crypto::derive_address(&keys.public))
+    }
+
+    #[test]
+    fn weekly_payment_runs_when_due_and_advances() {
+        let kp = keys();
+        let mut s = scheduler(&kp);
+        let id = s
+            .schedule(template(10, Recurrence::weekly(), 1_000), &kp)
+            .unwrap();
+
+        let mut ctx = context(&kp, 100);
+        assert!(s.prepare_due(Timestamp::new(999), &mut ctx).is_empty());
+
+        let runs = s.prepare_due(Timestamp::new(1_000), &mut ctx);
+        let RunOutcome::Built(ref block) = runs[0].outcome else {
+            panic!("expected a built block");
+        };
+        assert_eq!(block.trst_balance, 90);
+        assert_eq!(block.memo.as_deref(), Some(&b"rent"[..]));
+        assert!(burst_crypto::verify_signature(
+            block.hash.as_bytes(),
+            &block.signature,
+            &kp.public
+        ));
+        assert_eq!(ctx.account_state.head, block.hash);
+
+        // Still due until the node accepts the block.
+        assert_eq!(s.get(&id).unwrap().next_due, Timestamp::new(1_000));
+        s.record_accepted(&id, Timestamp::new(1_000)).unwrap();
+        let p = s.get(&id).unwrap();
+        assert_eq!(p.runs, 1);
+        assert_eq!(p.next_due, Timestamp::new(1_000 + WEEK_SECS));
+    }
+
+    #[test]
+    fn insufficient_balance_skips_and_retries() {
+        let kp = keys();
+        let mut s = scheduler(&kp);
+        let id = s.schedule(template(50, Recurrence::Once, 10), &kp).unwrap();
+
+        let runs = s.prepare_due(Timestamp::new(10), &mut context(&kp, 20));
+        assert!(matches!(runs[0].outcome, RunOutcome::Skipped(_)));
+        assert!(s.get(&id).unwrap().last_error.is_some());
+
+        let runs = s.prepare_due(Timestamp::new(20), &mut context(&kp, 50));
+        assert!(matches!(runs[0].outcome, RunOutcome::Built(_)));
+        s.record_accepted(&id, Timestamp::new(20)).unwrap();
+        assert_eq!(s.get(&id).unwrap().status, ScheduleStatus::Completed);
+    }
+
+    #[test]
+    fn rejected_run_stays_due_and_is_rebuilt() {
+        let kp = keys();
+        let mut s = scheduler(&kp);
+        let id = s
+            .schedule(template(10, Recurrence::daily(), 10), &kp)
+            .unwrap();
+
+        let runs = s.prepare_due(Timestamp::new(10), &mut context(&kp, 50));
+        assert!(matches!(runs[0].outcome, RunOutcome::Built(_)));
+        s.record_rejected(&id, "fork".into()).unwrap();
+
+        let p = s.get(&id).unwrap();
+        assert_eq!(p.runs, 0);
+        assert_eq!(p.next_due, Timestamp::new(10));
+        assert_eq!(p.last_error.as_deref(), Some("fork"));
+
+        let runs = s.prepare_due(Timestamp::new(11), &mut context(&kp, 50));
+        assert!(matches!(runs[0].outcome, RunOutcome::Built(_)));
+        s.record_accepted(&id, Timestamp::new(11)).unwrap();
+        let p = s.get(&id).unwrap();
+        assert_eq!(p.runs, 1);
+        assert_eq!(p.last_error, None);
+        assert_eq!(p.next_due, Timestamp::new(10 + DAY_SECS));
+    }
+
+    #[test]
+    fn expired_schedule_completes_without_running() {
+        let kp = keys();
+        let mut s = scheduler(&kp);
+        let mut t = template(5, Recurrence::daily(), 10);
+        t.expires_at = Some(Timestamp::new(100));
+        let id = s.schedule(t, &kp).unwrap();
+
+        let runs = s.prepare_due(Timestamp::new(100), &mut context(&kp, 50));
+        assert!(matches!(runs[0].outcome, RunOutcome::Expired));
+        assert_eq!(s.get(&id).unwrap().status, ScheduleStatus::Completed);
+    }
+
+    #[test]
+    fn pause_resume_cancel() {
+        let kp = keys();
+        let mut s = scheduler(&kp);
+        let id = s
+            .schedule(template(5, Recurrence::daily(), 10), &kp)
+            .unwrap();
+
+        s.pause(&id).unwrap();
+        assert!(s
+            .prepare_due(Timestamp::new(10), &mut context(&kp, 50))
+            .is_empty());
+        assert!(s.pause(&id).is_err());
+
+        s.resume(&id, Timestamp::new(10 + 2 * DAY_SECS + 1))
+            .unwrap();
+        assert_eq!(
+            s.get(&id).unwrap().next_due,
+            Timestamp::new(10 + 3 * DAY_SECS)
+        );
+
+        s.cancel(&id).unwrap();
+        assert!(s.cancel(&id).is_err());
+        assert!(s.resume(&id, Timestamp::new(0)).is_err());
+        assert_eq!(s.next_wakeup(), None);
+    }
+
+    #[test]
+    fn long_pause_catches_up_in_one_step() {
+        let kp = keys();
+        let mut s = scheduler(&kp);
+        let id = s
+            .schedule(template(1, Recurrence::Interval { every_secs: 1 }, 10), &kp)
+            .unwrap();
+        s.pause(&id).unwrap();
+        s.resume(&id, Timestamp::new(u64::MAX / 2)).unwrap();
+        assert_eq!(s.get(&id).unwrap().next_due, Timestamp::new(u64::MAX / 2));
+        s.record_accepted(&id, Timestamp::new(u64::MAX / 2))
+            .unwrap();
+        assert_eq!(
+            s.get(&id).unwrap().next_due,
+            Timestamp::new(u64::MAX / 2 + 1)
+        );
+    }
+
+    #[test]
+    fn multiple_due_payments_chain_and_share_balance() {
+        let kp = keys();
+        let mut s = scheduler(&kp);
+        s.schedule(template(30, Recurrence::Once, 1), &kp).unwrap();
+        s.schedule(template(30, Recurrence::Once, 2), &kp).unwrap();
+
+        let mut ctx = context(&kp, 40);
+        let runs = s.prepare_due(Timestamp::new(5), &mut ctx);
+        assert!(
+            matches!(&runs[0].outcome, RunOutcome::Built(block) if block.sequence() == Some(4))
+        );
+        assert!(matches!(runs[1].outcome, RunOutcome::Skipped(_)));
+        assert_eq!(ctx.transferable_trst, 10);
+        assert_eq!(ctx.account_state.block_count, 4);
+    }
+
+    #[test]
+    fn keystore_persistence_detects_tampering() {
+        let kp = keys();
+        let mut s = scheduler(&kp);
+        let id = s
+            .schedule(template(5, Recurrence::daily(), 10), &kp)
+            .unwrap();
+
+        let mut ks = crate::keystore::encrypt_keystore(&kp.private.0, "pw").unwrap();
+        s.save_to(&mut ks);
+        let json = serde_json::to_string(&ks).unwrap();
+        let mut loaded: KeystoreFile = serde_json::from_str(&json).unwrap();
+
+        let restored = Scheduler::load_from(&loaded, &kp.public).unwrap();
+        assert_eq!(restored.get(&id).unwrap().template.amount, 5);
+
+        loaded.schedules[0].template.amount = 5_000;
+        assert!(Scheduler::load_from(&loaded, &kp.public).is_err());
+    }
+
+    #[test]
+    fn rejects_foreign_keys_and_zero_amount() {
+        let kp = keys();
+        let mut s = scheduler(&kp);
+        assert!(s.schedule(template(0, Recurrence::Once, 1), &kp).is_err());
+        let every_zero = Recurrence::Interval { every_secs: 0 };
+        assert!(s.schedule(template(1, every_zero, 1), &kp).is_err());
+        let other = burst_crypto::keypair_from_seed(&[9u8; 32]);
+        assert!(s
+            .schedule(template(1, Recurrence::Once, 1), &other)
+            .is_err());
+    }
+}
@@ -0,0 +1,235 @@
+//! Push updates for watched accounts over the node's WebSocket feed.
+//!
+//! [`BalanceSubscriber`] subscribes to the `account_update` topic for a set
+//! of accounts and forwards each update on a channel. When the connection
+//! drops it reconnects, moving on to the next endpoint and backing off per
+//! its [`RetryPolicy`]. Updates sent while disconnected are lost, so every
+//! (re)connection is announced with [`SubscriberEvent::Connected`]; wallets
+//! should refresh balances over RPC when they see it.
+
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::error::WalletError;
+use crate::wallet::RetryPolicy;
+
+/// Updates buffered before the subscriber waits for the wallet to catch up.
+const CHANNEL_CAPACITY: usize = 256;
+
+/// A change to a watched account, as published by the node.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct AccountUpdate {
+    pub account: String,
+    /// `send`, `receive`, `pending`, `rejected`, ... — see the node's
+    /// WebSocket documentation.
+    pub change_type: String,
+    /// New balance, for updates that change it.
+    #[serde(default)]
+    pub balance: Option<String>,
+    /// The full event payload, including fields specific to `change_type`.
+    #[serde(skip)]
+    pub data: serde_json::Value,
+    /// Unix seconds.
+    #[serde(skip)]
+    pub timestamp: u64,
+}
+
+/// What a [`BalanceSubscriber`] delivers.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SubscriberEvent {
+    /// Subscribed on `endpoint`. Updates may have been missed before this.
+    Connected {
+        endpoint: String,
+    },
+    Update(AccountUpdate),
+}
+
+/// Follows `account_update` events for a set of accounts, failing over
+/// between WebSocket endpoints (e.g. `ws://127.0.0.1:7078/ws`).
+pub struct BalanceSubscriber {
+    endpoints: Vec<String>,
+    accounts: Vec<String>,
+    retry: RetryPolicy,
+}
+
+impl BalanceSubscriber {
+    pub fn new(
+        endpoints: Vec<String>,
+        accounts: Vec<String>,
+        retry: RetryPolicy,
+    ) -> Result<Self, WalletError> {
+        if endpoints.is_empty() {
+            return Err(WalletError::NoNodeConnection);
+        }
+        Ok(Self {
+            endpoints,
+            accounts,
+            retry,
+        })
+    }
+
+    /// Start following in a background task. The task stops when the
+    /// receiver is dropped, or after `max_attempts` connection attempts in
+    /// a row fail.
+    pub fn spawn(self) -> mpsc::Receiver<SubscriberEvent> {
+        let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+        tokio::spawn(async move { self.run(tx).await });
+        rx
+    }
+
+    async fn run(self, tx: mpsc::Sender<SubscriberEvent>) {
+        let mut index = 0;
+        let mut failures = 0u32;
+        loop {
+            match self.follow(&self.endpoints[index], &tx).await {
+                Ok(Followed::ReceiverDropped) => return,
+                Ok(Followed::Disconnected) => failures = 0,
+                Err(_) => {
+                    failures += 1;
+                    if failures >= self.retry.max_attempts.max(1) {
+                        return;
+                    }
+                }
+            }
+            index = (index + 1) % self.endpoints.len();
+            tokio::time::sleep(self.retry.backoff(failures.max(1))).await;
+        }
+    }
+
+    /// Subscribe on `endpoint` and forward updates until the connection or
+    /// the receiver goes away. Errors mean the subscription never started.
+    async fn follow(
+        &self,
+        endpoint: &str,
+        tx: &mpsc::Sender<SubscriberEvent>,
+    ) -> Result<Followed, WalletError> {
+        let (mut socket, _) = tokio_tungstenite::connect_async(endpoint)
+            .await
+            .map_err(|e| WalletError::Node(format!("failed to connect to {endpoint}: {e}")))?;
+        let subscribe = serde_json::json!({
+            "action": "subscribe",
+            "topic": "account_update",
+            "filter": { "accounts": self.accounts },
+        });
+        socket
+            .send(Message::text(subscribe.to_string()))
+            .await
+            .map_err(|e| WalletError::Node(format!("failed to subscribe: {e}")))?;
+        let connected = SubscriberEvent::Connected {
+            endpoint: endpoint.to_string(),
+        };
+        if tx.send(connected).await.is_err() {
+            return Ok(Followed::ReceiverDropped);
+        }
+
+        while let Some(message) = socket.next().await {
+            let text = match message {
+                Ok(Message::Text(text)) => text,
+                Ok(_) => continue,
+                Err(_) => break,
+            };
+            let Some(update) = parse_update(&text) else {
+                continue;
+            };
+            if tx.send(SubscriberEvent::Update(update)).await.is_err() {
+                return Ok(Followed::ReceiverDropped);
+            }
+        }
+        Ok(Followed::Disconnected)
+    }
+}
+
+/// How a subscription that started ended.
+enum Followed {
+    Disconnected,
+    ReceiverDropped,
+}
+
+/// The account update in a WebSocket message, if it carries one. Acks,
+/// pongs and other topics yield `None`.
+fn parse_update(text: &str) -> Option<AccountUpdate> {
+    let event: serde_json::Value = serde_json::from_str(text).ok()?;
+    if event.get("topic")?.as_str()? != "account_update" {
+        return None;
+    }
+    let data = event.get("data")?.clone();
+    let mut update: AccountUpdate = serde_json::from_value(data.clone()).ok()?;
+    update.data = data;
+    update.timestamp = event.get("timestamp").and_then(|t| t.as_u64()).unwrap_or(0);
+    Some(update)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_update_skips_other_messages() {
+        assert_eq!(parse_update(r#"{"type":"pong"}"#), None);
+        assert_eq!(
+            parse_update(r#"{"topic":"confirmation","data":{},"timestamp":1}"#),
+            None
+        );
+        let update = parse_update(
+            r#"{"topic":"account_update","data":{"account":"brst_a","balance":"5","change_type":"receive"},"timestamp":9}"#,
+        )
+        .unwrap();
+        assert_eq!(update.account, "brst_a");
+        assert_eq!(update.balance.as_deref(), Some("5"));
+        assert_eq!(update.timestamp, 9);
+        assert_eq!(update.data["change_type"], "receive");
+    }
+
+    #[tokio::test]
+    async fn subscriber_fails_over_to_a_live_endpoint() {
+        // A port nothing listens on.
+        let dead = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let dead_url = format!("ws://{}/ws", dead.local_addr().unwrap());
+        drop(dead);
+
+        let live = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let live_url = format!("ws://{}/ws", live.local_addr().unwrap());
+        let node = tokio::spawn(async move {
+            let (stream, _) = live.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let Some(Ok(Message::Text(subscribe))) = socket.next().await else {
+                panic!("expected a subscribe message");
+            };
+            let subscribe: serde_json::Value = serde_json::from_str(&subscribe).unwrap();
+            assert_eq!(subscribe["filter"]["accounts"][0], "brst_a");
+            let event = serde_json::json!({
+                "topic": "account_update",
+                "data": { "account": "brst_a", "balance": "12", "change_type": "receive" },
+                "timestamp": 1,
+            });
+            socket.send(Message::text(event.to_string())).await.unwrap();
+            // Hold the connection until the client has read the event.
+            let _ = socket.next().await;
+        });
+
+        let retry = RetryPolicy {
+            max_attempts: 3,
+            initial_backoff_ms: 10,
+            max_backoff_ms: 10,
+        };
+        let mut events = BalanceSubscriber::new(
+            vec![dead_url, live_url.clone()],
+            vec!["brst_a".into()],
+            retry,
+        )
+        .unwrap()
+        .spawn();
+        assert_eq!(
+            events.recv().await,
+            Some(SubscriberEvent::Connected { endpoint: live_url })
+        );
+        let Some(SubscriberEvent::Update(update)) = events.recv().await else {
+            panic!("expected an update");
+        };
+        assert_eq!(update.balance.as_deref(), Some("12"));
+        drop(events);
+        node.abort();
+    }
+}
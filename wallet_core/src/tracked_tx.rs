@@ -0,0 +1,181 @@
+//! Following a submitted block until it settles.
+//!
+//! `process` only says the node queued a block. A [`TrackedTransaction`]
+//! follows it from there through the node's `tx_status` RPC, which the node
+//! keeps current from its ledger events: Queued → Accepted → Confirmed →
+//! Cemented, or Rejected / Dropped with the node's reason.
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::error::WalletError;
+
+/// Where a submitted block is, as reported by the node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxState {
+    Queued,
+    Accepted,
+    Confirmed,
+    Cemented,
+    Rejected,
+    Dropped,
+}
+
+impl TxState {
+    /// Parse a `tx_status` status name. `unknown` and unrecognised names
+    /// yield `None`.
+    pub fn parse(status: &str) -> Option<Self> {
+        match status {
+            "queued" => Some(Self::Queued),
+            "accepted" => Some(Self::Accepted),
+            "confirmed" => Some(Self::Confirmed),
+            "cemented" => Some(Self::Cemented),
+            "rejected" => Some(Self::Rejected),
+            "dropped" => Some(Self::Dropped),
+            _ => None,
+        }
+    }
+
+    /// Whether the block will not move on from this state by itself.
+    pub fn is_terminal(self) -> bool {
+        matches!(self, Self::Cemented | Self::Rejected | Self::Dropped)
+    }
+
+    /// Whether the block failed to make it into the ledger.
+    pub fn is_failure(self) -> bool {
+        matches!(self, Self::Rejected | Self::Dropped)
+    }
+
+    /// Position along the successful path.
+    fn progress(self) -> u8 {
+        match self {
+            Self::Queued | Self::Rejected | Self::Dropped => 0,
+            Self::Accepted => 1,
+            Self::Confirmed => 2,
+            Self::Cemented => 3,
+        }
+    }
+}
+
+/// A submitted block and the last state the node reported for it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrackedTransaction {
+    /// Hex block hash, as returned by `process`.
+    pub hash: String,
+    pub state: TxState,
+    /// The node's reason for a rejection, drop, or continued wait.
+    pub reason: Option<String>,
+    /// Unix seconds of the node's last transition, when it reported one.
+    pub updated_at: Option<u64>,
+}
+
+impl TrackedTransaction {
+    /// Start following `hash`, just queued by `process`.
+    pub fn new(hash: impl Into<String>) -> Self {
+        Self {
+            hash: hash.into(),
+            state: TxState::Queued,
+            reason: None,
+            updated_at: None,
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        self.state.is_terminal()
+    }
+
+    /// Apply a status reported by the node. Reports that would move the
+    /// transaction backwards — e.g. from a node that has not caught up —
+    /// and anything after cementing are ignored. Returns whether the
+    /// state changed.
+    pub fn apply(&mut self, status: &str, reason: Option<String>, updated_at: Option<u64>) -> bool {
+        let Some(state) = TxState::parse(status) else {
+            return false;
+        };
+        if self.state == TxState::Cemented {
+            return false;
+        }
+        if !state.is_failure() && state.progress() < self.state.progress() {
+            return false;
+        }
+        let changed = state != self.state || reason != self.reason;
+        self.state = state;
+        self.reason = reason;
+        self.updated_at = updated_at.or(self.updated_at);
+        changed
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl TrackedTransaction {
+    /// Poll `tx_status` once. Returns whether the state changed.
+    pub async fn refresh(
+        &mut self,
+        client: &crate::wallet::NodeClient,
+    ) -> Result<bool, WalletError> {
+        let status = client.tx_status(&self.hash).await?;
+        Ok(self.apply(&status.status, status.reason, status.updated_at))
+    }
+
+    /// Poll every `interval` until the transaction reaches `target` or
+    /// fails, or `timeout` passes. Returns the state it stopped in; check
+    /// it before assuming success.
+    pub async fn wait_for(
+        &mut self,
+        client: &crate::wallet::NodeClient,
+        target: TxState,
+        interval: std::time::Duration,
+        timeout: std::time::Duration,
+    ) -> Result<TxState, WalletError> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            self.refresh(client).await?;
+            let reached = !self.state.is_failure() && self.state.progress() >= target.progress();
+            if reached || self.state.is_terminal() {
+                return Ok(self.state);
+            }
+            if tokio::time::Instant::now() + interval > deadline {
+                return Err(WalletError::Node(format!(
+                    "timed out waiting for {} to reach {target:?}, last {:?}",
+                    self.hash, self.state
+                )));
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_moves_forward_only() {
+        let mut tx = TrackedTransaction::new("ab");
+        assert!(tx.apply("accepted", None, Some(2)));
+        assert!(!tx.apply("queued", None, Some(3)));
+        assert!(!tx.apply("unknown", None, None));
+        assert_eq!(tx.state, TxState::Accepted);
+        assert_eq!(tx.updated_at, Some(2));
+
+        assert!(tx.apply("cemented", None, Some(4)));
+        assert!(tx.is_terminal());
+        assert!(!tx.apply("dropped", Some("lost fork election".into()), Some(5)));
+        assert_eq!(tx.state, TxState::Cemented);
+    }
+
+    #[test]
+    fn failures_carry_the_reason() {
+        let mut tx = TrackedTransaction::new("ab");
+        assert!(tx.apply(
+            "queued",
+            Some("waiting for the previous block".into()),
+            Some(1)
+        ));
+        assert!(tx.apply("rejected", Some("insufficient balance".into()), Some(2)));
+        assert_eq!(tx.state, TxState::Rejected);
+        assert_eq!(tx.reason.as_deref(), Some("insufficient balance"));
+        assert!(tx.is_terminal());
+    }
+}
@@ -1,8 +1,14 @@
 //! Transaction building helpers.
 
 use burst_ledger::state_block::{BlockType, StateBlock, CURRENT_BLOCK_VERSION};
-use burst_types::{BlockHash, Signature, Timestamp, TxHash, WalletAddress};
+use burst_ledger::{BlockExtension, BlockLayout};
+use burst_transactions::htlc::HtlcData;
+use burst_transactions::recovery::RecoveryData;
+use burst_transactions::reject_receive::RejectReason;
+use burst_trst::{select_coins, CoinSelection, ConsumedProvenance, TrstError, TrstToken};
+use burst_types::{BlockHash, NetworkId, PublicKey, Signature, Timestamp, TxHash, WalletAddress};
 
+use crate::contacts::{is_address, Contacts};
 use crate::error::WalletError;
 
 /// Encode a wallet address as a 32-byte public key for the `link` field.
@@ -17,6 +23,25 @@ fn address_to_link(address: &WalletAddress) -> Result<BlockHash, WalletError> {
         .ok_or_else(|| WalletError::InvalidAddress(address.as_str().to_string()))
 }
 
+/// Resolve a recipient given as an address or as a contact label.
+///
+/// An address is returned as-is; anything else is looked up in `contacts`.
+pub fn resolve_recipient(
+    contacts: &Contacts,
+    recipient: &str,
+) -> Result<WalletAddress, WalletError> {
+    let recipient = recipient.trim();
+    if is_address(recipient) {
+        return Ok(WalletAddress::new(recipient));
+    }
+    contacts
+        .get(recipient)
+        .map(|contact| contact.address.clone())
+        .ok_or_else(|| {
+            WalletError::InvalidAddress(format!("{recipient} (not an address or a contact)"))
+        })
+}
+
 // ---------------------------------------------------------------------------
 // Existing builders
 // ---------------------------------------------------------------------------
@@ -50,10 +75,71 @@ pub fn build_send_tx(
     origin: TxHash,
     now: Timestamp,
 ) -> Result<burst_transactions::send::SendTx, WalletError> {
-    let hash_data = format!(
-        "send:{}:{}:{}:{}:{}:{}",
-        sender, receiver, amount, link, origin, now
-    );
+    build_send_tx_with_memo(sender, receiver, amount, link, origin, None, now)
+}
+
+/// Build a send transaction carrying an optional memo/reference.
+///
+/// The memo is at most `MAX_MEMO_LEN` bytes and is hashed into both the
+/// transaction and the resulting state block, so merchants can reconcile
+/// incoming payments against invoices.
+pub fn build_send_tx_with_memo(
+    sender: &WalletAddress,
+    receiver: &WalletAddress,
+    amount: u128,
+    link: TxHash,
+    origin: TxHash,
+    memo: Option<Vec<u8>>,
+    now: Timestamp,
+) -> Result<burst_transactions::send::SendTx, WalletError> {
+    build_tagged_send_tx(sender, receiver, amount, link, origin, memo, None, now)
+}
+
+/// Build a send transaction carrying a destination tag and optional memo.
+///
+/// The tag travels in a v2 block extension, so the sending account must
+/// have reached block version 2 (see `burst_ledger::validate_block_version`).
+/// Exchanges hand each customer a tag for one shared deposit account; see
+/// [`crate::deposit_tag`].
+#[allow(clippy::too_many_arguments)]
+pub fn build_tagged_send_tx(
+    sender: &WalletAddress,
+    receiver: &WalletAddress,
+    amount: u128,
+    link: TxHash,
+    origin: TxHash,
+    memo: Option<Vec<u8>>,
+    destination_tag: Option<u64>,
+    now: Timestamp,
+) -> Result<burst_transactions::send::SendTx, WalletError> {
+    if let Some(ref m) = memo {
+        if m.len() > burst_transactions::send::MAX_MEMO_LEN {
+            return Err(WalletError::TransactionBuild(format!(
+                "memo is {} bytes, maximum is {}",
+                m.len(),
+                burst_transactions::send::MAX_MEMO_LEN
+            )));
+        }
+    }
+    let mut hash_data = match memo {
+        Some(ref m) => format!(
+            "send:{}:{}:{}:{}:{}:{}:{}",
+            sender,
+            receiver,
+            amount,
+            link,
+            origin,
+            now,
+            hex::encode(m)
+        ),
+        None => format!(
+            "send:{}:{}:{}:{}:{}:{}",
+            sender, receiver, amount, link, origin, now
+        ),
+    };
+    if let Some(tag) = destination_tag {
+        hash_data.push_str(&format!(":tag={tag}"));
+    }
     let hash = burst_crypto::hash_transaction(hash_data.as_bytes());
     Ok(burst_transactions::send::SendTx {
         hash,
@@ -63,11 +149,53 @@ pub fn build_send_tx(
         timestamp: now,
         link,
         origin,
+        memo,
+        destination_tag,
         work: 0,
         signature: Signature([0u8; 64]),
     })
 }
 
+/// A send built under coin control, with the provenance it spends.
+#[derive(Clone, Debug)]
+pub struct CoinControlledSend {
+    pub tx: burst_transactions::send::SendTx,
+    /// The tokens (and how much of each) the send consumes, for display
+    /// before signing.
+    pub provenance: Vec<ConsumedProvenance>,
+}
+
+/// Build a send whose TRST is drawn from `tokens` — the sender's holdings —
+/// as `selection` dictates. The transaction's `link` and `origin` name the
+/// first token consumed.
+pub fn build_send_tx_with_coin_control(
+    sender: &WalletAddress,
+    receiver: &WalletAddress,
+    amount: u128,
+    tokens: &[TrstToken],
+    selection: &CoinSelection,
+    trst_expiry_secs: u64,
+    now: Timestamp,
+) -> Result<CoinControlledSend, WalletError> {
+    let provenance = select_coins(tokens, amount, now, trst_expiry_secs, selection)
+        .map_err(coin_selection_error)?;
+    let first = provenance
+        .first()
+        .ok_or_else(|| WalletError::TransactionBuild("send amount must be non-zero".into()))?;
+    let tx = build_send_tx(sender, receiver, amount, first.token_id, first.origin, now)?;
+    Ok(CoinControlledSend { tx, provenance })
+}
+
+/// Map a coin selection failure to the wallet error reported to the user.
+pub(crate) fn coin_selection_error(e: TrstError) -> WalletError {
+    match e {
+        TrstError::InsufficientBalance { needed, available } => {
+            WalletError::InsufficientTrst { needed, available }
+        }
+        other => WalletError::TransactionBuild(other.to_string()),
+    }
+}
+
 /// Build an endorsement transaction.
 pub fn build_endorse_tx(
     endorser: &WalletAddress,
@@ -116,6 +244,64 @@ pub fn build_governance_vote_tx(
     })
 }
 
+/// Build a sealed-ballot commitment on a proposal.
+///
+/// `commitment` is `burst_governance::ballot_commitment` of the proposal,
+/// the voter and the sealed ballot the voter will later reveal.
+pub fn build_governance_commit_tx(
+    voter: &WalletAddress,
+    proposal_hash: TxHash,
+    commitment: [u8; 32],
+    now: Timestamp,
+) -> Result<burst_transactions::governance::GovernanceCommitTx, WalletError> {
+    let hash_data = format!(
+        "governance_commit:{}:{}:{}:{}",
+        voter,
+        proposal_hash,
+        hex::encode(commitment),
+        now
+    );
+    let hash = burst_crypto::hash_transaction(hash_data.as_bytes());
+    Ok(burst_transactions::governance::GovernanceCommitTx {
+        hash,
+        voter: voter.clone(),
+        proposal_hash,
+        commitment,
+        timestamp: now,
+        work: 0,
+        signature: Signature([0u8; 64]),
+    })
+}
+
+/// Build the reveal of a sealed ballot committed earlier with `salt`.
+pub fn build_governance_reveal_tx(
+    voter: &WalletAddress,
+    proposal_hash: TxHash,
+    vote: burst_transactions::governance::GovernanceVote,
+    salt: [u8; 31],
+    now: Timestamp,
+) -> Result<burst_transactions::governance::GovernanceRevealTx, WalletError> {
+    let hash_data = format!(
+        "governance_reveal:{}:{}:{}:{}:{}",
+        voter,
+        proposal_hash,
+        vote.as_byte(),
+        hex::encode(salt),
+        now
+    );
+    let hash = burst_crypto::hash_transaction(hash_data.as_bytes());
+    Ok(burst_transactions::governance::GovernanceRevealTx {
+        hash,
+        voter: voter.clone(),
+        proposal_hash,
+        vote,
+        salt,
+        timestamp: now,
+        work: 0,
+        signature: Signature([0u8; 64]),
+    })
+}
+
 // ---------------------------------------------------------------------------
 // New builders (Items 1.7, 1.8)
 // ---------------------------------------------------------------------------
@@ -267,15 +453,32 @@ pub fn build_challenge_tx(
 pub fn build_governance_proposal_tx(
     proposer: &WalletAddress,
     proposal: burst_transactions::governance::ProposalContent,
+    ballot: burst_transactions::governance::BallotMode,
+    requires: Option<TxHash>,
     now: Timestamp,
 ) -> Result<burst_transactions::governance::GovernanceProposalTx, WalletError> {
-    let hash_data = format!("governance_proposal:{}:{:?}:{}", proposer, proposal, now);
+    if let burst_transactions::governance::ProposalContent::TreasuryGrant { recipient, .. } =
+        &proposal
+    {
+        address_to_link(recipient)?;
+        if requires.is_some() {
+            return Err(WalletError::TransactionBuild(
+                "a treasury grant cannot require another proposal".into(),
+            ));
+        }
+    }
+    let hash_data = format!(
+        "governance_proposal:{}:{:?}:{:?}:{:?}:{}",
+        proposer, proposal, ballot, requires, now
+    );
     let hash = burst_crypto::hash_transaction(hash_data.as_bytes());
     Ok(burst_transactions::governance::GovernanceProposalTx {
         hash,
         proposer: proposer.clone(),
         timestamp: now,
         proposal,
+        ballot,
+        requires,
         work: 0,
         signature: Signature([0u8; 64]),
     })
@@ -317,18 +520,256 @@ pub fn build_verification_vote_tx(
 /// Build a reject-receive transaction to decline a pending TRST send.
 ///
 /// The `send_block_hash` is the hash of the send block being rejected.
-/// The TRST is logically returned to the sender.
+/// The TRST returns to the sender as a new pending entry, and the sender is
+/// told `reason`.
 pub fn build_reject_receive_tx(
     rejecter: &WalletAddress,
     send_block_hash: TxHash,
+    reason: RejectReason,
     now: Timestamp,
 ) -> Result<burst_transactions::reject_receive::RejectReceiveTx, WalletError> {
-    let hash_data = format!("reject_receive:{}:{}:{}", rejecter, send_block_hash, now);
+    let hash_data = format!(
+        "reject_receive:{}:{}:{}:{}",
+        rejecter,
+        send_block_hash,
+        reason.code(),
+        now
+    );
     let hash = burst_crypto::hash_transaction(hash_data.as_bytes());
     Ok(burst_transactions::reject_receive::RejectReceiveTx {
         hash,
         rejecter: rejecter.clone(),
         send_block_hash,
+        reason,
+        timestamp: now,
+        work: 0,
+        signature: Signature([0u8; 64]),
+    })
+}
+
+/// Build an HTLC lock: `amount` TRST claimable by `receiver` with the
+/// preimage of `hash_lock` until `refund_after`, refundable afterwards.
+#[allow(clippy::too_many_arguments)]
+pub fn build_htlc_lock_tx(
+    sender: &WalletAddress,
+    receiver: &WalletAddress,
+    amount: u128,
+    hash_lock: [u8; 32],
+    refund_after: Timestamp,
+    link: TxHash,
+    origin: TxHash,
+    now: Timestamp,
+) -> Result<burst_transactions::htlc::HtlcLockTx, WalletError> {
+    if amount == 0 {
+        return Err(WalletError::TransactionBuild(
+            "HTLC amount must be greater than zero".to_string(),
+        ));
+    }
+    burst_transactions::htlc::validate_lock_terms(refund_after, now)
+        .map_err(|e| WalletError::TransactionBuild(e.to_string()))?;
+    let hash_data = format!(
+        "htlc_lock:{}:{}:{}:{}:{}:{}",
+        sender,
+        receiver,
+        amount,
+        hex::encode(hash_lock),
+        refund_after,
+        now
+    );
+    let hash = burst_crypto::hash_transaction(hash_data.as_bytes());
+    Ok(burst_transactions::htlc::HtlcLockTx {
+        hash,
+        sender: sender.clone(),
+        receiver: receiver.clone(),
+        amount,
+        hash_lock,
+        refund_after,
+        timestamp: now,
+        link,
+        origin,
+        work: 0,
+        signature: Signature([0u8; 64]),
+    })
+}
+
+/// Build an HTLC claim revealing `preimage` for the lock block `lock_block_hash`.
+pub fn build_htlc_claim_tx(
+    claimer: &WalletAddress,
+    lock_block_hash: TxHash,
+    preimage: [u8; 32],
+    amount: u128,
+    now: Timestamp,
+) -> Result<burst_transactions::htlc::HtlcClaimTx, WalletError> {
+    let hash_data = format!(
+        "htlc_claim:{}:{}:{}:{}",
+        claimer, lock_block_hash, amount, now
+    );
+    let hash = burst_crypto::hash_transaction(hash_data.as_bytes());
+    Ok(burst_transactions::htlc::HtlcClaimTx {
+        hash,
+        claimer: claimer.clone(),
+        lock_block_hash,
+        preimage,
+        amount,
+        timestamp: now,
+        work: 0,
+        signature: Signature([0u8; 64]),
+    })
+}
+
+/// Build an HTLC refund for an expired lock block `lock_block_hash`.
+pub fn build_htlc_refund_tx(
+    refunder: &WalletAddress,
+    lock_block_hash: TxHash,
+    amount: u128,
+    now: Timestamp,
+) -> Result<burst_transactions::htlc::HtlcRefundTx, WalletError> {
+    let hash_data = format!(
+        "htlc_refund:{}:{}:{}:{}",
+        refunder, lock_block_hash, amount, now
+    );
+    let hash = burst_crypto::hash_transaction(hash_data.as_bytes());
+    Ok(burst_transactions::htlc::HtlcRefundTx {
+        hash,
+        refunder: refunder.clone(),
+        lock_block_hash,
+        amount,
+        timestamp: now,
+        work: 0,
+        signature: Signature([0u8; 64]),
+    })
+}
+
+/// Build a group anchor committing `identity`'s key and endpoint on-chain.
+pub fn build_group_anchor_tx(
+    registrant: &WalletAddress,
+    identity: &burst_groups::GroupIdentity,
+    now: Timestamp,
+) -> Result<burst_transactions::group_anchor::GroupAnchorTx, WalletError> {
+    let anchor = identity.anchor();
+    let hash_data = format!(
+        "group_anchor:{}:{}:{}",
+        registrant,
+        hex::encode(anchor),
+        now
+    );
+    let hash = burst_crypto::hash_transaction(hash_data.as_bytes());
+    Ok(burst_transactions::group_anchor::GroupAnchorTx {
+        hash,
+        registrant: registrant.clone(),
+        anchor,
+        timestamp: now,
+        work: 0,
+        signature: Signature([0u8; 64]),
+    })
+}
+
+/// Build a delegate profile transaction anchoring `anchor`, the hash of
+/// the delegate's signed off-chain profile.
+pub fn build_delegate_profile_tx(
+    delegate: &WalletAddress,
+    anchor: [u8; 32],
+    now: Timestamp,
+) -> Result<burst_transactions::delegate_profile::DelegateProfileTx, WalletError> {
+    let hash_data = format!(
+        "delegate_profile:{}:{}:{}",
+        delegate,
+        hex::encode(anchor),
+        now
+    );
+    let hash = burst_crypto::hash_transaction(hash_data.as_bytes());
+    Ok(burst_transactions::delegate_profile::DelegateProfileTx {
+        hash,
+        delegate: delegate.clone(),
+        anchor,
+        timestamp: now,
+        work: 0,
+        signature: Signature([0u8; 64]),
+    })
+}
+
+/// Build an alias transaction registering or renewing `name` for `account`,
+/// burning `fee` BRN (the governed alias fee).
+pub fn build_alias_tx(
+    account: &WalletAddress,
+    name: &str,
+    fee: u128,
+    now: Timestamp,
+) -> Result<burst_transactions::alias::AliasTx, WalletError> {
+    burst_transactions::alias::validate_alias_name(name)
+        .map_err(|e| WalletError::TransactionBuild(e.to_string()))?;
+    let hash_data = format!("alias:{}:{}:{}:{}", account, name, fee, now);
+    let hash = burst_crypto::hash_transaction(hash_data.as_bytes());
+    Ok(burst_transactions::alias::AliasTx {
+        hash,
+        account: account.clone(),
+        name: name.to_string(),
+        fee,
+        timestamp: now,
+        work: 0,
+        signature: Signature([0u8; 64]),
+    })
+}
+
+/// Build a recovery setup designating `custodians`, any `threshold` of whom
+/// can approve rotating the account key after `delay_secs`.
+pub fn build_recovery_setup_tx(
+    account: &WalletAddress,
+    custodians: Vec<WalletAddress>,
+    threshold: u8,
+    delay_secs: u64,
+    now: Timestamp,
+) -> Result<burst_transactions::recovery::RecoverySetupTx, WalletError> {
+    burst_transactions::recovery::validate_setup(account, &custodians, threshold)
+        .map_err(|e| WalletError::TransactionBuild(e.to_string()))?;
+    let listed: Vec<&str> = custodians.iter().map(|c| c.as_str()).collect();
+    let hash_data = format!(
+        "recovery_setup:{}:{}:{}:{}:{}",
+        account,
+        listed.join(","),
+        threshold,
+        delay_secs,
+        now
+    );
+    let hash = burst_crypto::hash_transaction(hash_data.as_bytes());
+    Ok(burst_transactions::recovery::RecoverySetupTx {
+        hash,
+        account: account.clone(),
+        custodians,
+        threshold,
+        delay_secs,
+        timestamp: now,
+        work: 0,
+        signature: Signature([0u8; 64]),
+    })
+}
+
+/// Build a recovery rotating `account` to `new_key` with collected custodian
+/// approvals for the request made against head `previous` at `requested_at`.
+pub fn build_recovery_tx(
+    account: &WalletAddress,
+    new_key: PublicKey,
+    previous: BlockHash,
+    requested_at: Timestamp,
+    approvals: Vec<burst_transactions::recovery::CustodianApproval>,
+    now: Timestamp,
+) -> Result<burst_transactions::recovery::RecoveryTx, WalletError> {
+    let hash_data = format!(
+        "recovery:{}:{}:{}:{}:{}",
+        account,
+        hex::encode(new_key.as_bytes()),
+        previous,
+        requested_at,
+        now
+    );
+    let hash = burst_crypto::hash_transaction(hash_data.as_bytes());
+    Ok(burst_transactions::recovery::RecoveryTx {
+        hash,
+        account: account.clone(),
+        new_key,
+        previous,
+        requested_at,
+        approvals,
         timestamp: now,
         work: 0,
         signature: Signature([0u8; 64]),
@@ -348,12 +789,18 @@ pub struct AccountState {
     pub brn_balance: u128,
     /// TRST balance after the most recent block.
     pub trst_balance: u128,
+    /// Network the account lives on. Version 3 blocks are bound to it.
+    pub network: NetworkId,
+    /// Newest block version the account may publish, given its epoch and
+    /// the ledger epoch (see `burst_ledger::BlockLayout::for_account`).
+    pub block_version: u8,
 }
 
 /// Convert a high-level `Transaction` into a `StateBlock` that can be submitted to the node.
 ///
 /// Takes the account's current state (head hash, balances) and a transaction,
-/// and produces a StateBlock with the computed block hash. The signature and work
+/// and produces a StateBlock laid out for the account's block version (see
+/// `StateBlock::apply_layout`), with the computed block hash. The signature and work
 /// fields are left zeroed — the caller signs and attaches PoW separately.
 pub fn build_state_block(
     account_state: &AccountState,
@@ -434,15 +881,41 @@ pub fn build_state_block(
             account_state.trst_balance,
             None,
         ),
-        burst_transactions::Transaction::GovernanceProposal(tx) => (
-            BlockType::GovernanceProposal,
-            BlockHash::new(*tx.hash.as_bytes()),
+        burst_transactions::Transaction::GovernanceProposal(tx) => {
+            let mut link = match tx.proposal {
+                burst_transactions::governance::ProposalContent::TreasuryGrant {
+                    amount, ..
+                } => burst_transactions::governance::treasury_grant_link(amount),
+                _ => *tx.hash.as_bytes(),
+            };
+            tx.ballot.mark_proposal_link(&mut link);
+            if tx.requires.is_some() {
+                burst_transactions::governance::mark_proposal_dependency(&mut link);
+            }
+            (
+                BlockType::GovernanceProposal,
+                BlockHash::new(link),
+                account_state.brn_balance,
+                account_state.trst_balance,
+                None,
+            )
+        }
+        burst_transactions::Transaction::GovernanceVote(tx) => (
+            BlockType::GovernanceVote,
+            BlockHash::new(*tx.proposal_hash.as_bytes()),
             account_state.brn_balance,
             account_state.trst_balance,
             None,
         ),
-        burst_transactions::Transaction::GovernanceVote(tx) => (
-            BlockType::GovernanceVote,
+        burst_transactions::Transaction::GovernanceCommit(tx) => (
+            BlockType::GovernanceCommit,
+            BlockHash::new(*tx.proposal_hash.as_bytes()),
+            account_state.brn_balance,
+            account_state.trst_balance,
+            None,
+        ),
+        burst_transactions::Transaction::GovernanceReveal(tx) => (
+            BlockType::GovernanceReveal,
             BlockHash::new(*tx.proposal_hash.as_bytes()),
             account_state.brn_balance,
             account_state.trst_balance,
@@ -462,6 +935,13 @@ pub fn build_state_block(
             account_state.trst_balance,
             None,
         ),
+        burst_transactions::Transaction::RotateDelegationKey(tx) => (
+            BlockType::RotateDelegationKey,
+            address_to_link(&tx.delegate)?,
+            account_state.brn_balance,
+            account_state.trst_balance,
+            None,
+        ),
         burst_transactions::Transaction::Receive(tx) => (
             BlockType::Receive,
             BlockHash::new(*tx.send_block_hash.as_bytes()),
@@ -496,34 +976,210 @@ pub fn build_state_block(
             account_state.trst_balance,
             None,
         ),
+        burst_transactions::Transaction::HtlcLock(tx) => (
+            BlockType::HtlcLock,
+            address_to_link(&tx.receiver)?,
+            account_state.brn_balance,
+            account_state.trst_balance.checked_sub(tx.amount).ok_or(
+                WalletError::InsufficientTrst {
+                    needed: tx.amount,
+                    available: account_state.trst_balance,
+                },
+            )?,
+            None,
+        ),
+        burst_transactions::Transaction::HtlcClaim(tx) => (
+            BlockType::HtlcClaim,
+            BlockHash::new(*tx.lock_block_hash.as_bytes()),
+            account_state.brn_balance,
+            account_state.trst_balance.saturating_add(tx.amount),
+            None,
+        ),
+        burst_transactions::Transaction::HtlcRefund(tx) => (
+            BlockType::HtlcRefund,
+            BlockHash::new(*tx.lock_block_hash.as_bytes()),
+            account_state.brn_balance,
+            account_state.trst_balance.saturating_add(tx.amount),
+            None,
+        ),
+        burst_transactions::Transaction::GroupAnchor(tx) => (
+            BlockType::GroupAnchor,
+            BlockHash::new(tx.anchor),
+            account_state.brn_balance,
+            account_state.trst_balance,
+            None,
+        ),
+        burst_transactions::Transaction::RecoverySetup(_) => (
+            BlockType::RecoverySetup,
+            BlockHash::ZERO,
+            account_state.brn_balance,
+            account_state.trst_balance,
+            None,
+        ),
+        burst_transactions::Transaction::Recovery(tx) => (
+            BlockType::Recovery,
+            BlockHash::new(*tx.new_key.as_bytes()),
+            account_state.brn_balance,
+            account_state.trst_balance,
+            None,
+        ),
+        burst_transactions::Transaction::VerifierOptIn(tx) => (
+            BlockType::VerifierOptIn,
+            BlockHash::ZERO,
+            account_state
+                .brn_balance
+                .checked_sub(tx.stake_amount)
+                .ok_or(WalletError::InsufficientBrn {
+                    needed: tx.stake_amount,
+                    available: account_state.brn_balance,
+                })?,
+            account_state.trst_balance,
+            None,
+        ),
+        burst_transactions::Transaction::VerifierOptOut(tx) => (
+            BlockType::VerifierOptOut,
+            BlockHash::ZERO,
+            account_state.brn_balance.saturating_add(tx.stake_amount),
+            account_state.trst_balance,
+            None,
+        ),
+        burst_transactions::Transaction::Liveness(_) => (
+            BlockType::Liveness,
+            BlockHash::ZERO,
+            account_state.brn_balance,
+            account_state.trst_balance,
+            None,
+        ),
+        burst_transactions::Transaction::DelegateProfile(tx) => (
+            BlockType::DelegateProfile,
+            BlockHash::new(tx.anchor),
+            account_state.brn_balance,
+            account_state.trst_balance,
+            None,
+        ),
+        burst_transactions::Transaction::Alias(tx) => (
+            BlockType::Alias,
+            BlockHash::ZERO,
+            account_state
+                .brn_balance
+                .checked_sub(tx.fee)
+                .ok_or(WalletError::InsufficientBrn {
+                    needed: tx.fee,
+                    available: account_state.brn_balance,
+                })?,
+            account_state.trst_balance,
+            None,
+        ),
     };
 
     let representative = representative.unwrap_or_else(|| account_state.representative.clone());
 
-    let origin = match block_type {
-        BlockType::Burn => *transaction.hash(),
+    let origin = match transaction {
+        _ if block_type == BlockType::Burn => *transaction.hash(),
+        burst_transactions::Transaction::GovernanceProposal(tx) => match tx.proposal {
+            burst_transactions::governance::ProposalContent::TreasuryGrant {
+                ref recipient,
+                ..
+            } => address_to_link(recipient)?.into_tx_hash(),
+            _ => tx.requires.unwrap_or(previous_origin),
+        },
         _ => previous_origin,
     };
 
+    let memo = match transaction {
+        burst_transactions::Transaction::Send(tx) => tx.memo.clone(),
+        // The alias name travels in the memo field.
+        burst_transactions::Transaction::Alias(tx) => Some(tx.name.clone().into_bytes()),
+        _ => None,
+    };
+
+    let htlc = match transaction {
+        burst_transactions::Transaction::HtlcLock(tx) => Some(HtlcData::Lock {
+            hash_lock: tx.hash_lock,
+            refund_after: tx.refund_after,
+        }),
+        burst_transactions::Transaction::HtlcClaim(tx) => Some(HtlcData::Claim {
+            preimage: tx.preimage,
+        }),
+        _ => None,
+    };
+
+    let reject_reason = match transaction {
+        burst_transactions::Transaction::RejectReceive(tx) => Some(tx.reason),
+        _ => None,
+    };
+
+    let recovery = match transaction {
+        burst_transactions::Transaction::RecoverySetup(tx) => Some(RecoveryData::Setup {
+            custodians: tx.custodians.clone(),
+            threshold: tx.threshold,
+            delay_secs: tx.delay_secs,
+        }),
+        burst_transactions::Transaction::Recovery(tx) => Some(RecoveryData::Rotate {
+            new_key: tx.new_key.clone(),
+            requested_at: tx.requested_at,
+            approvals: tx.approvals.clone(),
+        }),
+        _ => None,
+    };
+
+    // A destination tag rides in the v2 extension area.
+    let extensions = match transaction {
+        burst_transactions::Transaction::Send(tx) => tx
+            .destination_tag
+            .map(BlockExtension::destination_tag)
+            .into_iter()
+            .collect(),
+        _ => Vec::new(),
+    };
+    let delegation_key = match transaction {
+        burst_transactions::Transaction::Delegate(tx) => Some(tx.key.clone()),
+        burst_transactions::Transaction::RotateDelegationKey(tx) => Some(tx.key.clone()),
+        _ => None,
+    };
+
+    // A recovery must extend exactly the head the custodians approved.
+    let previous = match transaction {
+        burst_transactions::Transaction::Recovery(tx) => tx.previous,
+        _ => account_state.head,
+    };
+
+    // Sealed ballots travel in the transaction field.
+    let transaction_field = match transaction {
+        burst_transactions::Transaction::GovernanceCommit(tx) => TxHash::new(tx.commitment),
+        burst_transactions::Transaction::GovernanceReveal(tx) => TxHash::new(tx.sealed_ballot()),
+        _ => *transaction.hash(),
+    };
+
     let mut block = StateBlock {
         version: CURRENT_BLOCK_VERSION,
         block_type,
         account: transaction.sender().clone(),
-        previous: account_state.head,
+        previous,
         representative,
         brn_balance,
         trst_balance,
         link,
         origin,
-        transaction: *transaction.hash(),
+        transaction: transaction_field,
         timestamp: transaction.timestamp(),
         params_hash,
+        memo,
+        htlc,
+        reject_reason,
+        recovery,
+        delegation_key,
+        extensions,
         work: 0,
         signature: Signature([0u8; 64]),
         hash: BlockHash::ZERO,
     };
 
-    block.hash = block.compute_hash();
+    let layout = BlockLayout {
+        version: account_state.block_version,
+        network: account_state.network,
+    };
+    block.apply_layout(layout, account_state.block_count + 1);
 
     Ok(block)
 }
@@ -557,6 +1213,7 @@ pub fn build_and_sign_state_block(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use burst_ledger::BLOCK_VERSION_V3;
     use burst_transactions::split::SplitOutput;
 
     fn test_address(suffix: &str) -> WalletAddress {
@@ -574,9 +1231,29 @@ mod tests {
             representative: test_address("rep1"),
             brn_balance: 10_000,
             trst_balance: 5_000,
+            network: NetworkId::Dev,
+            block_version: BLOCK_VERSION_V3,
         }
     }
 
+    #[test]
+    fn recipients_resolve_by_address_or_label() {
+        let bob = test_address("bob");
+        let mut contacts = Contacts::new();
+        contacts
+            .add(crate::contacts::Contact::new("Bob", bob.clone()))
+            .unwrap();
+
+        assert_eq!(resolve_recipient(&contacts, "bob").unwrap(), bob);
+        assert_eq!(resolve_recipient(&contacts, bob.as_str()).unwrap(), bob);
+        let carol = test_address("carol");
+        assert_eq!(resolve_recipient(&contacts, carol.as_str()).unwrap(), carol);
+        assert!(matches!(
+            resolve_recipient(&contacts, "carol"),
+            Err(WalletError::InvalidAddress(_))
+        ));
+    }
+
     #[test]
     fn build_burn_tx_creates_valid_tx() {
         let sender = test_address("sender1");
@@ -598,6 +1275,24 @@ mod tests {
         assert!(!tx.hash.is_zero());
     }
 
+    #[test]
+    fn group_anchor_block_links_identity_commitment() {
+        let registrant = test_address("registrant");
+        let group_key = burst_crypto::keypair_from_seed(&[8u8; 32]).public;
+        let identity = burst_groups::GroupIdentity::new("coop", group_key, "https://coop.example");
+        let tx = build_group_anchor_tx(&registrant, &identity, Timestamp::new(1000)).unwrap();
+        let block = build_state_block(
+            &test_account_state(),
+            &burst_transactions::Transaction::GroupAnchor(tx),
+            TxHash::ZERO,
+            BlockHash::ZERO,
+        )
+        .unwrap();
+        assert_eq!(block.block_type, BlockType::GroupAnchor);
+        assert!(identity.matches_anchor(&block.link));
+        assert_eq!(block.trst_balance, 5_000);
+    }
+
     #[test]
     fn build_change_rep_tx_creates_valid_tx() {
         let account = test_address("account1");
@@ -695,10 +1390,34 @@ mod tests {
         assert_eq!(block.block_type, BlockType::Burn);
         assert_eq!(block.brn_balance, 9_900);
         assert_eq!(block.trst_balance, 5_000);
-        assert_eq!(block.version, CURRENT_BLOCK_VERSION);
+        assert_eq!(block.version, BLOCK_VERSION_V3);
+        assert_eq!(block.network(), Some(NetworkId::Dev));
+        assert_eq!(block.sequence(), Some(1));
         assert!(!block.hash.is_zero());
     }
 
+    #[test]
+    fn build_state_block_follows_the_account_block_version() {
+        let mut state = test_account_state();
+        state.block_version = CURRENT_BLOCK_VERSION;
+        let burn = build_burn_tx(
+            &test_address("sender1"),
+            &test_address("receiver1"),
+            100,
+            Timestamp::new(1000),
+        )
+        .unwrap();
+        let tx = burst_transactions::Transaction::Burn(burn);
+        let block = build_state_block(&state, &tx, TxHash::ZERO, BlockHash::ZERO).unwrap();
+
+        // Before the account reaches version 2 the block has no extension
+        // area, so it carries neither a sequence nor a network id.
+        assert_eq!(block.version, CURRENT_BLOCK_VERSION);
+        assert!(block.extensions.is_empty());
+        assert_eq!(block.network(), None);
+        assert_eq!(block.hash, block.compute_hash());
+    }
+
     #[test]
     fn build_state_block_for_send() {
         let state = test_account_state();
@@ -721,6 +1440,135 @@ mod tests {
         assert_eq!(block.origin, prev_origin);
     }
 
+    #[test]
+    fn build_state_block_for_send_carries_memo() {
+        let state = test_account_state();
+        let send = build_send_tx_with_memo(
+            &test_address("sender1"),
+            &test_address("receiver1"),
+            200,
+            TxHash::new([1u8; 32]),
+            TxHash::new([2u8; 32]),
+            Some(b"order-1001".to_vec()),
+            Timestamp::new(2000),
+        )
+        .unwrap();
+        let tx = burst_transactions::Transaction::Send(send);
+        let block = build_state_block(&state, &tx, TxHash::ZERO, BlockHash::ZERO).unwrap();
+
+        assert_eq!(block.memo.as_deref(), Some(&b"order-1001"[..]));
+        assert_eq!(block.hash, block.compute_hash());
+        assert!(block.validate_memo().is_ok());
+    }
+
+    #[test]
+    fn build_state_block_for_tagged_send_carries_the_tag() {
+        let state = test_account_state();
+        let send = build_tagged_send_tx(
+            &test_address("sender1"),
+            &test_address("receiver1"),
+            200,
+            TxHash::new([1u8; 32]),
+            TxHash::new([2u8; 32]),
+            None,
+            Some(7_000_001),
+            Timestamp::new(2000),
+        )
+        .unwrap();
+        let tx = burst_transactions::Transaction::Send(send);
+        let block = build_state_block(&state, &tx, TxHash::ZERO, BlockHash::ZERO).unwrap();
+
+        assert_eq!(block.destination_tag(), Some(7_000_001));
+        assert_eq!(block.sequence(), Some(1));
+        assert!(block.validate_extensions().is_ok());
+        assert_eq!(block.hash, block.compute_hash());
+    }
+
+    #[test]
+    fn build_state_block_for_alias_burns_the_fee() {
+        let state = test_account_state();
+        let alias =
+            build_alias_tx(&test_address("sender1"), "alice", 300, Timestamp::new(3000)).unwrap();
+        let tx = burst_transactions::Transaction::Alias(alias);
+        let block = build_state_block(&state, &tx, TxHash::ZERO, BlockHash::ZERO).unwrap();
+
+        assert_eq!(block.block_type, BlockType::Alias);
+        assert_eq!(block.brn_balance, 9_700);
+        assert_eq!(block.trst_balance, 5_000);
+        assert_eq!(block.memo.as_deref(), Some(&b"alice"[..]));
+        assert!(block.validate_memo().is_ok());
+
+        assert!(build_alias_tx(&test_address("sender1"), "A", 300, Timestamp::new(3000)).is_err());
+    }
+
+    fn holding(n: u8, amount: u128, origin_ts: u64) -> TrstToken {
+        TrstToken {
+            id: TxHash::new([n; 32]),
+            amount,
+            origin: TxHash::new([n + 100; 32]),
+            link: TxHash::new([n; 32]),
+            holder: test_address("sender1"),
+            origin_timestamp: Timestamp::new(origin_ts),
+            effective_origin_timestamp: Timestamp::new(origin_ts),
+            state: burst_types::TrstState::Active,
+            origin_wallet: test_address("origin"),
+            origin_proportions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn coin_controlled_send_reports_provenance() {
+        let tokens = vec![holding(1, 100, 10), holding(2, 300, 20)];
+        let pinned = CoinSelection::Pinned(vec![TxHash::new([2u8; 32])]);
+        let built = build_send_tx_with_coin_control(
+            &test_address("sender1"),
+            &test_address("receiver1"),
+            250,
+            &tokens,
+            &pinned,
+            86_400,
+            Timestamp::new(2000),
+        )
+        .unwrap();
+
+        assert_eq!(built.tx.amount, 250);
+        assert_eq!(built.tx.link, TxHash::new([2u8; 32]));
+        assert_eq!(built.tx.origin, TxHash::new([102u8; 32]));
+        assert_eq!(built.provenance.len(), 1);
+        assert_eq!(built.provenance[0].amount, 250);
+
+        let short = build_send_tx_with_coin_control(
+            &test_address("sender1"),
+            &test_address("receiver1"),
+            350,
+            &tokens,
+            &pinned,
+            86_400,
+            Timestamp::new(2000),
+        );
+        assert!(matches!(
+            short,
+            Err(WalletError::InsufficientTrst {
+                needed: 350,
+                available: 300
+            })
+        ));
+    }
+
+    #[test]
+    fn build_send_tx_rejects_oversized_memo() {
+        let result = build_send_tx_with_memo(
+            &test_address("sender1"),
+            &test_address("receiver1"),
+            200,
+            TxHash::new([1u8; 32]),
+            TxHash::new([2u8; 32]),
+            Some(vec![0u8; burst_transactions::send::MAX_MEMO_LEN + 1]),
+            Timestamp::new(2000),
+        );
+        assert!(result.is_err());
+    }
+
     #[test]
     fn build_state_block_insufficient_brn() {
         let state = AccountState {
@@ -768,6 +1616,8 @@ mod tests {
             representative: test_address("rep1"),
             brn_balance: 10_000,
             trst_balance: 5_000,
+            network: NetworkId::Dev,
+            block_version: BLOCK_VERSION_V3,
         };
         let burn = build_burn_tx(
             &address,
@@ -17,6 +17,32 @@ pub enum TrustPolicy {
     Custom { rules: Vec<TrustRule> },
 }
 
+impl TrustPolicy {
+    /// IDs of the groups this policy needs to ask about a sender, in order
+    /// and without duplicates.
+    pub fn referenced_groups(&self) -> Vec<String> {
+        let ids: Vec<&String> = match self {
+            TrustPolicy::AcceptAll => Vec::new(),
+            TrustPolicy::RequireGroup { trusted_groups }
+            | TrustPolicy::RequireAllGroups { trusted_groups } => trusted_groups.iter().collect(),
+            TrustPolicy::Custom { rules } => rules
+                .iter()
+                .filter_map(|rule| match rule {
+                    TrustRule::GroupMembership { group_id } => Some(group_id),
+                    _ => None,
+                })
+                .collect(),
+        };
+        let mut out: Vec<String> = Vec::with_capacity(ids.len());
+        for id in ids {
+            if !out.contains(id) {
+                out.push(id.clone());
+            }
+        }
+        out
+    }
+}
+
 /// A single rule in a custom trust policy.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum TrustRule {
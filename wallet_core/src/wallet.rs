@@ -7,25 +7,135 @@ use crate::error::WalletError;
 #[cfg(not(target_arch = "wasm32"))]
 use serde::Deserialize;
 #[cfg(not(target_arch = "wasm32"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::{Arc, Mutex};
+#[cfg(not(target_arch = "wasm32"))]
 use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
 
 // ── NodeClient ──────────────────────────────────────────────────────────
 
-/// HTTP client for communicating with a BURST node via JSON-RPC.
+/// URL scheme selecting the node's IPC socket, e.g.
+/// `ipc:///var/burst/burst.ipc`.
+#[cfg(unix)]
+pub const IPC_SCHEME: &str = "ipc://";
+
+/// How requests that fail to reach a node are retried.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Rounds over all endpoints per request (or connection attempts in a
+    /// row for a subscriber), including the first.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on each further retry.
+    pub initial_backoff_ms: u64,
+    /// Upper bound on the delay between retries.
+    pub max_backoff_ms: u64,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff_ms: 250,
+            max_backoff_ms: 5_000,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl RetryPolicy {
+    /// Delay before retry number `retry` (1-based).
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1u64
+            .checked_shl(retry.saturating_sub(1))
+            .unwrap_or(u64::MAX);
+        Duration::from_millis(
+            self.initial_backoff_ms
+                .saturating_mul(factor)
+                .min(self.max_backoff_ms),
+        )
+    }
+}
+
+/// Client for communicating with a BURST node via JSON-RPC.
 ///
-/// Wraps `reqwest::Client` with the node's base URL and provides typed
+/// Talks HTTP through `reqwest::Client`, or — for `ipc://` URLs on Unix —
+/// newline-delimited JSON over the node's local socket, and provides typed
 /// methods for each RPC action the wallet needs.
+///
+/// A client may be given several endpoints:
+///
+/// - Writes (block submission and the work for it) stay pinned to one node
+///   and move to the next only when it can't be reached.
+/// - Reads go to the healthy endpoint with the lowest measured latency.
+/// - Balance, account and confirmation queries need `read_quorum` nodes to
+///   agree, so a single lying node can't fake a payment.
+///
+/// After a round in which no endpoint answered, the client backs off per
+/// its [`RetryPolicy`]. Errors reported by a node are returned as they are.
 #[cfg(not(target_arch = "wasm32"))]
 #[derive(Clone)]
 pub struct NodeClient {
     http: reqwest::Client,
-    node_url: String,
+    /// Endpoints in failover order.
+    endpoints: Arc<[String]>,
+    /// Index of the endpoint writes go to.
+    active: Arc<AtomicUsize>,
+    /// Latency and health per endpoint, by index.
+    stats: Arc<Mutex<Vec<EndpointStats>>>,
+    retry: RetryPolicy,
+    /// Nodes that must agree on a quorum read.
+    read_quorum: usize,
 }
 
+/// What a [`NodeClient`] has observed of one endpoint.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, Debug, Default)]
+struct EndpointStats {
+    /// Moving average of response times. `None` until the endpoint answers.
+    latency_ms: Option<u64>,
+    /// Requests in a row that failed to reach the endpoint.
+    failures: u32,
+}
+
+/// Fields of each quorum read that nodes must agree on. BRN balances and
+/// valuations grow with time, so they are left out.
+#[cfg(not(target_arch = "wasm32"))]
+const ACCOUNT_BALANCE_QUORUM_FIELDS: &[&str] = &["trst_balance"];
+#[cfg(not(target_arch = "wasm32"))]
+const ACCOUNT_INFO_QUORUM_FIELDS: &[&str] = &[
+    "head",
+    "trst_balance",
+    "total_brn_burned",
+    "total_brn_staked",
+    "verification_state",
+    "confirmation_height",
+    "representative",
+    "network",
+    "block_version",
+];
+#[cfg(not(target_arch = "wasm32"))]
+const BLOCK_INFO_QUORUM_FIELDS: &[&str] = &["account", "previous", "link", "origin", "confirmed"];
+
 #[cfg(not(target_arch = "wasm32"))]
 impl NodeClient {
-    /// Create a new NodeClient targeting the given base URL (e.g. `http://127.0.0.1:7076`).
+    /// Create a new NodeClient targeting the given base URL (e.g.
+    /// `http://127.0.0.1:7076`) or IPC socket (`ipc:///path/to/burst.ipc`).
     pub fn new(node_url: impl Into<String>) -> Result<Self, WalletError> {
+        Self::with_endpoints(vec![node_url.into()], RetryPolicy::default())
+    }
+
+    /// Create a NodeClient failing over between `endpoints`, in order.
+    /// Writes start on the first.
+    pub fn with_endpoints(endpoints: Vec<String>, retry: RetryPolicy) -> Result<Self, WalletError> {
+        if endpoints.is_empty() {
+            return Err(WalletError::NoNodeConnection);
+        }
+        let stats = vec![EndpointStats::default(); endpoints.len()];
         let http = reqwest::Client::builder()
             .timeout(Duration::from_secs(30))
             .connect_timeout(Duration::from_secs(10))
@@ -33,30 +143,213 @@ impl NodeClient {
             .map_err(|e| WalletError::Node(format!("failed to create HTTP client: {e}")))?;
         Ok(Self {
             http,
-            node_url: node_url.into(),
+            endpoints: endpoints.into(),
+            active: Arc::new(AtomicUsize::new(0)),
+            stats: Arc::new(Mutex::new(stats)),
+            retry,
+            read_quorum: 1,
         })
     }
 
-    /// The configured node URL.
+    /// Require `quorum` endpoints to agree on balance, account and
+    /// confirmation queries. Fails unless `1 <= quorum <= endpoints`.
+    pub fn with_read_quorum(mut self, quorum: usize) -> Result<Self, WalletError> {
+        if quorum == 0 || quorum > self.endpoints.len() {
+            return Err(WalletError::Other(format!(
+                "read quorum must be between 1 and {}, got {quorum}",
+                self.endpoints.len()
+            )));
+        }
+        self.read_quorum = quorum;
+        Ok(self)
+    }
+
+    /// Create a NodeClient talking to the node's IPC socket at `path`.
+    #[cfg(unix)]
+    pub fn ipc(path: impl AsRef<std::path::Path>) -> Result<Self, WalletError> {
+        Self::new(format!("{IPC_SCHEME}{}", path.as_ref().display()))
+    }
+
+    /// The URL of the endpoint writes currently go to.
     pub fn node_url(&self) -> &str {
-        &self.node_url
+        &self.endpoints[self.active.load(Ordering::Relaxed)]
+    }
+
+    /// All configured endpoints, in failover order.
+    pub fn endpoints(&self) -> &[String] {
+        &self.endpoints
     }
 
-    /// Send a JSON-RPC request and return the `result` field.
+    /// Send a write to the pinned endpoint, failing over in endpoint order,
+    /// and return the `result` field.
     async fn rpc_call(
         &self,
         action: &str,
         params: serde_json::Value,
     ) -> Result<serde_json::Value, WalletError> {
-        let mut body = params;
-        body.as_object_mut()
-            .ok_or_else(|| WalletError::Node("params must be a JSON object".into()))?
-            .insert("action".to_string(), serde_json::json!(action));
+        let body = request_body(action, params)?;
+        let order = || {
+            let first = self.active.load(Ordering::Relaxed);
+            (0..self.endpoints.len())
+                .map(|offset| (first + offset) % self.endpoints.len())
+                .collect()
+        };
+        self.call_in_order(&body, order, true).await
+    }
+
+    /// Send a read to the fastest healthy endpoint and return the `result`
+    /// field.
+    async fn read_call(
+        &self,
+        action: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, WalletError> {
+        let body = request_body(action, params)?;
+        self.call_in_order(&body, || self.by_latency(), false).await
+    }
+
+    /// Send a read that `read_quorum` endpoints must agree on, comparing
+    /// only `fields` of their results. The fastest `read_quorum` endpoints
+    /// are asked together, then the others one at a time until enough
+    /// agree. Node errors count as answers, so agreed errors are returned.
+    async fn quorum_call(
+        &self,
+        action: &str,
+        params: serde_json::Value,
+        fields: &[&str],
+    ) -> Result<serde_json::Value, WalletError> {
+        if self.read_quorum <= 1 {
+            return self.read_call(action, params).await;
+        }
+        let body = request_body(action, params)?;
+        let order = self.by_latency();
+        let (first, rest) = order.split_at(self.read_quorum);
+        let mut answers = futures_util::future::join_all(
+            first.iter().map(|&index| self.send_timed(index, &body)),
+        )
+        .await;
+        let mut rest = rest.iter();
+        // Distinct answers so far: (agreement key, count, first full answer).
+        let mut tally: Vec<(serde_json::Value, usize, Option<serde_json::Value>)> = Vec::new();
+        let mut last_error = None;
+        loop {
+            for answer in answers.drain(..) {
+                let json = match answer {
+                    Ok(json) => json,
+                    Err(e) => {
+                        last_error = Some(e);
+                        continue;
+                    }
+                };
+                let key = match json.get("error") {
+                    Some(error) => serde_json::json!({ "error": error }),
+                    None => agreement_key(json.get("result").unwrap_or(&json), fields),
+                };
+                let entry = match tally.iter().position(|(k, _, _)| *k == key) {
+                    Some(i) => &mut tally[i],
+                    None => {
+                        tally.push((key, 0, Some(json)));
+                        tally.last_mut().expect("entry just pushed")
+                    }
+                };
+                entry.1 += 1;
+                if entry.1 >= self.read_quorum {
+                    return rpc_result(entry.2.take().expect("answer kept until agreed"));
+                }
+            }
+            match rest.next() {
+                Some(&index) => answers.push(self.send_timed(index, &body).await),
+                None => break,
+            }
+        }
+        let reason = match last_error {
+            Some(e) if tally.is_empty() => e.to_string(),
+            _ => format!("{} different answers", tally.len()),
+        };
+        Err(WalletError::Node(format!(
+            "no {} of {} nodes agree on {action}: {reason}",
+            self.read_quorum,
+            self.endpoints.len()
+        )))
+    }
+
+    /// Try endpoints in the order `order` gives until one answers, for up
+    /// to `max_attempts` rounds, and return the `result` field. With `pin`,
+    /// the endpoint that answered takes the writes from now on.
+    async fn call_in_order(
+        &self,
+        body: &serde_json::Value,
+        order: impl Fn() -> Vec<usize>,
+        pin: bool,
+    ) -> Result<serde_json::Value, WalletError> {
+        let mut last_error = WalletError::NoNodeConnection;
+        for attempt in 0..self.retry.max_attempts.max(1) {
+            if attempt > 0 {
+                tokio::time::sleep(self.retry.backoff(attempt)).await;
+            }
+            for index in order() {
+                match self.send_timed(index, body).await {
+                    Ok(json) => {
+                        if pin {
+                            self.active.store(index, Ordering::Relaxed);
+                        }
+                        return rpc_result(json);
+                    }
+                    Err(e) => last_error = e,
+                }
+            }
+        }
+        Err(last_error)
+    }
+
+    /// Endpoint indices, healthy ones first, then by latency. Endpoints not
+    /// yet measured come first among their kind so they get measured.
+    fn by_latency(&self) -> Vec<usize> {
+        let stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        let mut order: Vec<usize> = (0..stats.len()).collect();
+        order.sort_by_key(|&i| (stats[i].failures > 0, stats[i].latency_ms.unwrap_or(0)));
+        order
+    }
+
+    /// [`send`](Self::send) to endpoint `index`, recording its latency or
+    /// failure.
+    async fn send_timed(
+        &self,
+        index: usize,
+        body: &serde_json::Value,
+    ) -> Result<serde_json::Value, WalletError> {
+        let started = Instant::now();
+        let result = self.send(&self.endpoints[index], body).await;
+        let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = &mut stats[index];
+        match &result {
+            Ok(_) => {
+                let ms = started.elapsed().as_millis() as u64;
+                entry.latency_ms = Some(entry.latency_ms.map_or(ms, |avg| (avg * 3 + ms) / 4));
+                entry.failures = 0;
+            }
+            Err(_) => entry.failures = entry.failures.saturating_add(1),
+        }
+        result
+    }
+
+    /// Deliver `body` to one endpoint and return the raw response.
+    async fn send(
+        &self,
+        endpoint: &str,
+        body: &serde_json::Value,
+    ) -> Result<serde_json::Value, WalletError> {
+        #[cfg(unix)]
+        if let Some(path) = endpoint.strip_prefix(IPC_SCHEME) {
+            return tokio::time::timeout(Duration::from_secs(30), ipc_call(path, body))
+                .await
+                .map_err(|_| WalletError::Node("IPC request timed out".into()))?;
+        }
 
         let response = self
             .http
-            .post(&self.node_url)
-            .json(&body)
+            .post(endpoint)
+            .json(body)
             .send()
             .await
             .map_err(|e| WalletError::Node(format!("request failed: {e}")))?;
@@ -68,16 +361,10 @@ impl NodeClient {
             )));
         }
 
-        let json: serde_json::Value = response
+        response
             .json()
             .await
-            .map_err(|e| WalletError::Node(format!("invalid JSON response: {e}")))?;
-
-        if let Some(err) = json.get("error").and_then(|e| e.as_str()) {
-            return Err(WalletError::Node(format!("node error: {err}")));
-        }
-
-        json.get("result").cloned().unwrap_or(json).pipe_ok()
+            .map_err(|e| WalletError::Node(format!("invalid JSON response: {e}")))
     }
 
     /// Fetch account balance (BRN + TRST).
@@ -86,7 +373,11 @@ impl NodeClient {
         account: &str,
     ) -> Result<AccountBalanceResult, WalletError> {
         let result = self
-            .rpc_call("account_balance", serde_json::json!({ "account": account }))
+            .quorum_call(
+                "account_balance",
+                serde_json::json!({ "account": account }),
+                ACCOUNT_BALANCE_QUORUM_FIELDS,
+            )
             .await?;
 
         let resp: AccountBalanceResult = serde_json::from_value(result)
@@ -97,7 +388,11 @@ impl NodeClient {
     /// Fetch full account info (state, block count, representative, etc.).
     pub async fn account_info(&self, account: &str) -> Result<AccountInfoResult, WalletError> {
         let result = self
-            .rpc_call("account_info", serde_json::json!({ "account": account }))
+            .quorum_call(
+                "account_info",
+                serde_json::json!({ "account": account }),
+                ACCOUNT_INFO_QUORUM_FIELDS,
+            )
             .await?;
 
         let resp: AccountInfoResult = serde_json::from_value(result)
@@ -122,7 +417,7 @@ impl NodeClient {
         count: u64,
     ) -> Result<AccountPendingResult, WalletError> {
         let result = self
-            .rpc_call(
+            .read_call(
                 "account_pending",
                 serde_json::json!({ "account": account, "count": count }),
             )
@@ -132,6 +427,154 @@ impl NodeClient {
             .map_err(|e| WalletError::Node(format!("invalid pending response: {e}")))
     }
 
+    /// Fetch unsigned, pre-chained receive blocks for every pending entry of
+    /// an account. Pass `receive_minimum` to override the node's default.
+    pub async fn pending_sweep(
+        &self,
+        account: &str,
+        count: u64,
+        receive_minimum: Option<u128>,
+    ) -> Result<PendingSweepResult, WalletError> {
+        let mut params = serde_json::json!({ "account": account, "count": count });
+        if let Some(min) = receive_minimum {
+            params["receive_minimum"] = serde_json::Value::String(min.to_string());
+        }
+        let result = self.read_call("pending_sweep", params).await?;
+
+        serde_json::from_value(result)
+            .map_err(|e| WalletError::Node(format!("invalid pending_sweep response: {e}")))
+    }
+
+    /// Attach node-generated PoW to a signed block and submit it. A block
+    /// that already carries work (e.g. from a
+    /// [`WorkPrecompute`](crate::work_precompute::WorkPrecompute)) is
+    /// submitted as is.
+    pub async fn submit_block(
+        &self,
+        block: &mut burst_ledger::StateBlock,
+    ) -> Result<ProcessResult, WalletError> {
+        if block.work == 0 {
+            let work = self
+                .work_generate(&format!("{}", block.work_root()))
+                .await?;
+            block.work = u64::from_str_radix(&work.work, 16)
+                .map_err(|e| WalletError::Node(format!("invalid work value: {e}")))?;
+        }
+        let json = serde_json::to_string(block)
+            .map_err(|e| WalletError::Other(format!("failed to serialize block: {e}")))?;
+        self.process(&json).await
+    }
+
+    /// Fetch a representative's delegated weight and voting performance.
+    pub async fn representative_info(
+        &self,
+        account: &str,
+    ) -> Result<RepresentativeInfoResult, WalletError> {
+        let result = self
+            .read_call(
+                "representative_info",
+                serde_json::json!({ "account": account }),
+            )
+            .await?;
+
+        serde_json::from_value(result)
+            .map_err(|e| WalletError::Node(format!("invalid representative_info response: {e}")))
+    }
+
+    /// Whether the block `hash` is confirmed, as agreed by `read_quorum`
+    /// nodes.
+    pub async fn block_confirmed(&self, hash: &str) -> Result<bool, WalletError> {
+        let result = self
+            .quorum_call(
+                "block_info",
+                serde_json::json!({ "hash": hash }),
+                BLOCK_INFO_QUORUM_FIELDS,
+            )
+            .await?;
+
+        result
+            .get("confirmed")
+            .and_then(|c| c.as_bool())
+            .ok_or_else(|| WalletError::Node("invalid block_info response".into()))
+    }
+
+    /// The TRST origin carried by block `hash`, which the account's next
+    /// block carries forward.
+    pub async fn block_origin(
+        &self,
+        hash: &burst_types::BlockHash,
+    ) -> Result<burst_types::TxHash, WalletError> {
+        let result = self
+            .quorum_call(
+                "block_info",
+                serde_json::json!({ "hash": hash.to_string() }),
+                BLOCK_INFO_QUORUM_FIELDS,
+            )
+            .await?;
+
+        let origin = result
+            .get("origin")
+            .and_then(|o| o.as_str())
+            .ok_or_else(|| WalletError::Node("invalid block_info response".into()))?;
+        Ok(burst_types::TxHash::new(parse_hash("origin", origin)?))
+    }
+
+    /// Hash of the protocol parameters in force, which new blocks carry.
+    pub async fn params_hash(&self) -> Result<burst_types::BlockHash, WalletError> {
+        let result = self
+            .quorum_call("params_hash", serde_json::json!({}), &["params_hash"])
+            .await?;
+
+        let hash = result
+            .get("params_hash")
+            .and_then(|h| h.as_str())
+            .ok_or_else(|| WalletError::Node("invalid params_hash response".into()))?;
+        Ok(burst_types::BlockHash::new(parse_hash("params", hash)?))
+    }
+
+    /// Progress of a block submitted with [`process`](Self::process).
+    /// Pinned like writes: only the node the block was submitted to
+    /// tracks it before it reaches the ledger.
+    pub async fn tx_status(&self, hash: &str) -> Result<TxStatusResult, WalletError> {
+        let result = self
+            .rpc_call("tx_status", serde_json::json!({ "hash": hash }))
+            .await?;
+
+        serde_json::from_value(result)
+            .map_err(|e| WalletError::Node(format!("invalid tx_status response: {e}")))
+    }
+
+    /// Hand a sealed direct message to the node to hold and relay.
+    pub async fn dm_send(
+        &self,
+        message: &burst_crypto::DirectMessage,
+    ) -> Result<crate::direct_messages::DmSendResult, WalletError> {
+        let result = self
+            .rpc_call("dm_send", crate::direct_messages::to_json(message))
+            .await?;
+
+        serde_json::from_value(result)
+            .map_err(|e| WalletError::Node(format!("invalid dm_send response: {e}")))
+    }
+
+    /// Direct messages the node holds for an account, still sealed.
+    pub async fn dm_fetch(
+        &self,
+        account: &str,
+    ) -> Result<Vec<burst_crypto::DirectMessage>, WalletError> {
+        let result = self
+            .read_call("dm_fetch", serde_json::json!({ "account": account }))
+            .await?;
+
+        result
+            .get("messages")
+            .and_then(|m| m.as_array())
+            .ok_or_else(|| WalletError::Node("invalid dm_fetch response".into()))?
+            .iter()
+            .map(crate::direct_messages::from_json)
+            .collect()
+    }
+
     /// Request proof-of-work for a block hash.
     pub async fn work_generate(&self, hash: &str) -> Result<WorkGenerateResult, WalletError> {
         let result = self
@@ -143,6 +586,101 @@ impl NodeClient {
     }
 }
 
+/// A 32-byte hash the node sent as hex.
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_hash(what: &str, hex_str: &str) -> Result<[u8; 32], WalletError> {
+    hex::decode(hex_str)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| WalletError::Node(format!("invalid {what} hash: {hex_str}")))
+}
+
+/// `params` with the `action` field added.
+#[cfg(not(target_arch = "wasm32"))]
+fn request_body(action: &str, params: serde_json::Value) -> Result<serde_json::Value, WalletError> {
+    let mut body = params;
+    body.as_object_mut()
+        .ok_or_else(|| WalletError::Node("params must be a JSON object".into()))?
+        .insert("action".to_string(), serde_json::json!(action));
+    Ok(body)
+}
+
+/// The part of a quorum read's result that nodes must agree on.
+#[cfg(not(target_arch = "wasm32"))]
+fn agreement_key(result: &serde_json::Value, fields: &[&str]) -> serde_json::Value {
+    fields
+        .iter()
+        .map(|field| {
+            (
+                field.to_string(),
+                result.get(field).cloned().unwrap_or_default(),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+/// The `result` field of a JSON-RPC response, or the node's error.
+#[cfg(not(target_arch = "wasm32"))]
+fn rpc_result(json: serde_json::Value) -> Result<serde_json::Value, WalletError> {
+    if let Some(error) = json.get("error") {
+        return Err(node_error(error));
+    }
+
+    json.get("result").cloned().unwrap_or(json).pipe_ok()
+}
+
+/// The node's error, from a structured error object or the bare message
+/// older nodes send.
+#[cfg(not(target_arch = "wasm32"))]
+fn node_error(error: &serde_json::Value) -> WalletError {
+    let message = error
+        .get("message")
+        .and_then(|m| m.as_str())
+        .or(error.as_str())
+        .unwrap_or("unknown error");
+    let code = error
+        .get("code")
+        .and_then(|c| c.as_u64())
+        .and_then(|c| u16::try_from(c).ok())
+        .and_then(burst_types::ErrorCode::from_code);
+    match code {
+        Some(code) => WalletError::Rpc {
+            code,
+            message: message.to_string(),
+            data: error.get("data").cloned(),
+        },
+        None => WalletError::Node(format!("node error: {message}")),
+    }
+}
+
+/// Send one request line to the IPC socket at `path` and read the response
+/// line.
+#[cfg(unix)]
+async fn ipc_call(path: &str, body: &serde_json::Value) -> Result<serde_json::Value, WalletError> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let stream = tokio::net::UnixStream::connect(path)
+        .await
+        .map_err(|e| WalletError::Node(format!("failed to connect to {path}: {e}")))?;
+    let (read, mut write) = stream.into_split();
+    let mut request = serde_json::to_vec(body)
+        .map_err(|e| WalletError::Other(format!("failed to serialize request: {e}")))?;
+    request.push(b'\n');
+    write
+        .write_all(&request)
+        .await
+        .map_err(|e| WalletError::Node(format!("request failed: {e}")))?;
+
+    let mut line = String::new();
+    BufReader::new(read)
+        .read_line(&mut line)
+        .await
+        .map_err(|e| WalletError::Node(format!("request failed: {e}")))?;
+    serde_json::from_str(&line)
+        .map_err(|e| WalletError::Node(format!("invalid JSON response: {e}")))
+}
+
 /// Balance response from the node.
 #[cfg(not(target_arch = "wasm32"))]
 #[derive(Debug, Clone, Deserialize)]
@@ -156,6 +694,9 @@ pub struct AccountBalanceResult {
 #[derive(Debug, Clone, Deserialize)]
 pub struct AccountInfoResult {
     pub address: String,
+    /// Hex hash of the account's head block (empty for unopened accounts).
+    #[serde(default)]
+    pub head: String,
     pub brn_balance: String,
     pub trst_balance: String,
     #[serde(default)]
@@ -173,6 +714,36 @@ pub struct AccountInfoResult {
     #[serde(default)]
     pub confirmation_height: u64,
     pub representative: String,
+    /// Network the node runs on (`live`, `test` or `dev`).
+    #[serde(default)]
+    pub network: String,
+    /// Newest block version the account may publish.
+    #[serde(default)]
+    pub block_version: u8,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl AccountInfoResult {
+    /// Chain state for building the account's next block.
+    pub fn account_state(&self) -> Result<crate::transaction_builder::AccountState, WalletError> {
+        let parse = |s: &str| s.parse::<u128>().unwrap_or(0);
+        let head = if self.head.is_empty() {
+            burst_types::BlockHash::ZERO
+        } else {
+            burst_types::BlockHash::new(parse_hash("head", &self.head)?)
+        };
+        let network = burst_types::NetworkId::from_name(&self.network)
+            .ok_or_else(|| WalletError::Node(format!("unknown network: {:?}", self.network)))?;
+        Ok(crate::transaction_builder::AccountState {
+            head,
+            block_count: self.block_count,
+            representative: WalletAddress::new(self.representative.clone()),
+            brn_balance: parse(&self.brn_balance),
+            trst_balance: parse(&self.trst_balance),
+            network,
+            block_version: self.block_version.max(burst_ledger::CURRENT_BLOCK_VERSION),
+        })
+    }
 }
 
 /// Response from the `process` RPC (block submission).
@@ -185,11 +756,27 @@ pub struct ProcessResult {
     pub detail: Option<String>,
 }
 
+/// Response from the `tx_status` RPC.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Deserialize)]
+pub struct TxStatusResult {
+    pub hash: String,
+    /// `queued`, `accepted`, `confirmed`, `cemented`, `rejected`,
+    /// `dropped`, or `unknown`.
+    pub status: String,
+    #[serde(default)]
+    pub terminal: bool,
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<u64>,
+}
+
 /// Response from the `account_pending` RPC.
 #[cfg(not(target_arch = "wasm32"))]
 #[derive(Debug, Clone, Deserialize)]
 pub struct AccountPendingResult {
-    #[serde(default)]
+    #[serde(default, alias = "pending")]
     pub blocks: Vec<PendingBlock>,
 }
 
@@ -197,10 +784,77 @@ pub struct AccountPendingResult {
 #[cfg(not(target_arch = "wasm32"))]
 #[derive(Debug, Clone, Deserialize)]
 pub struct PendingBlock {
+    /// Hash of the originating send block.
+    #[serde(default)]
+    pub hash: String,
     pub source: String,
     pub amount: String,
     #[serde(default)]
     pub block_type: String,
+    /// Hex-encoded memo/reference from the originating send, if any.
+    #[serde(default)]
+    pub memo: Option<String>,
+    /// Destination tag from the originating send, if any; see
+    /// [`crate::deposit_tag`].
+    #[serde(default)]
+    pub destination_tag: Option<u64>,
+}
+
+/// Response from the `pending_sweep` RPC.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Deserialize)]
+pub struct PendingSweepResult {
+    pub blocks: Vec<SweepBlock>,
+    #[serde(default)]
+    pub below_minimum: u64,
+    #[serde(default)]
+    pub remaining: u64,
+}
+
+/// One unsigned receive block from `pending_sweep`, in chain order.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Deserialize)]
+pub struct SweepBlock {
+    pub hash: String,
+    pub previous: String,
+    /// Hash of the send being pocketed.
+    pub source: String,
+    pub amount: String,
+    /// Sign `block.hash`, attach work, and submit with `process`.
+    pub block: burst_ledger::StateBlock,
+}
+
+/// Response from the `representative_info` RPC.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Deserialize)]
+pub struct RepresentativeInfoResult {
+    pub account: String,
+    pub weight: String,
+    pub online: bool,
+    #[serde(default)]
+    pub last_vote_age_secs: Option<u64>,
+    #[serde(default)]
+    pub latency_p50_ms: Option<u64>,
+    #[serde(default)]
+    pub latency_p90_ms: Option<u64>,
+    #[serde(default)]
+    pub latency_p99_ms: Option<u64>,
+    #[serde(default)]
+    pub uptime_score: Option<f64>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl RepresentativeInfoResult {
+    /// The subset the representative monitor acts on.
+    pub fn health(&self) -> crate::rep_monitor::RepHealth {
+        crate::rep_monitor::RepHealth {
+            account: WalletAddress::new(self.account.clone()),
+            weight: self.weight.parse::<u128>().unwrap_or(0),
+            online: self.online,
+            last_vote_age_secs: self.last_vote_age_secs,
+            uptime_score: self.uptime_score,
+        }
+    }
 }
 
 /// Response from the `work_generate` RPC.
@@ -307,9 +961,19 @@ impl Wallet {
 
     /// Restore a wallet from a BIP39 mnemonic phrase (24 words).
     pub fn from_mnemonic(phrase: &str) -> Result<Self, WalletError> {
-        use burst_crypto::{derive_address, keypair_from_mnemonic};
+        Self::from_mnemonic_with_passphrase(phrase, "")
+    }
 
-        let primary_keys = keypair_from_mnemonic(phrase)
+    /// Restore a wallet from a BIP39 mnemonic phrase and the passphrase it
+    /// was hardened with. Any passphrase opens some wallet; only the right
+    /// one opens this one.
+    pub fn from_mnemonic_with_passphrase(
+        phrase: &str,
+        passphrase: &str,
+    ) -> Result<Self, WalletError> {
+        use burst_crypto::{derive_address, keypair_from_mnemonic_with_passphrase};
+
+        let primary_keys = keypair_from_mnemonic_with_passphrase(phrase, passphrase)
             .map_err(|e| WalletError::Key(format!("mnemonic error: {e}")))?;
         let address = derive_address(&primary_keys.public);
 
@@ -640,4 +1304,195 @@ mod tests {
             "http://127.0.0.1:7076"
         );
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn node_client_talks_to_ipc_socket() {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let path = std::env::temp_dir().join(format!("burst-wallet-{}.ipc", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        let node = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (read, mut write) = stream.into_split();
+            let mut line = String::new();
+            BufReader::new(read).read_line(&mut line).await.unwrap();
+            let request: serde_json::Value = serde_json::from_str(&line).unwrap();
+            assert_eq!(request["action"], "account_balance");
+            let response = serde_json::json!({
+                "result": { "brn_balance": "7", "trst_balance": request["account"] }
+            });
+            write
+                .write_all(format!("{response}\n").as_bytes())
+                .await
+                .unwrap();
+        });
+
+        let client = NodeClient::ipc(&path).unwrap();
+        assert!(client.node_url().starts_with(IPC_SCHEME));
+        let balance = client.account_balance("42").await.unwrap();
+        assert_eq!(balance.brn_balance, "7");
+        assert_eq!(balance.trst_balance, "42");
+        node.await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn retry_backoff_doubles_up_to_the_cap() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            initial_backoff_ms: 100,
+            max_backoff_ms: 1_000,
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(5), Duration::from_millis(1_000));
+        assert_eq!(policy.backoff(90), Duration::from_millis(1_000));
+    }
+
+    #[test]
+    fn node_errors_keep_their_code() {
+        let err = rpc_result(serde_json::json!({
+            "error": {
+                "code": 2001,
+                "message": "insufficient TRST: have 4, need 10",
+                "data": { "name": "insufficient_trst", "required": "10", "available": "4" },
+            },
+        }))
+        .unwrap_err();
+        assert_eq!(
+            err.node_error_code(),
+            Some(burst_types::ErrorCode::InsufficientTrst)
+        );
+        let WalletError::Rpc { data, .. } = err else {
+            panic!("expected a coded error");
+        };
+        assert_eq!(data.unwrap()["available"], "4");
+
+        let err = rpc_result(serde_json::json!({ "error": "block rejected" })).unwrap_err();
+        assert!(matches!(err, WalletError::Node(_)));
+        assert_eq!(err.node_error_code(), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn node_client_fails_over_and_sticks_to_the_answering_endpoint() {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let dir = std::env::temp_dir().join(format!("burst-failover-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let live = dir.join("live.ipc");
+        let listener = tokio::net::UnixListener::bind(&live).unwrap();
+        let node = tokio::spawn(async move {
+            for _ in 0..2 {
+                let (stream, _) = listener.accept().await.unwrap();
+                let (read, mut write) = stream.into_split();
+                let mut line = String::new();
+                BufReader::new(read).read_line(&mut line).await.unwrap();
+                let response = r#"{"error":"block rejected"}"#;
+                write
+                    .write_all(format!("{response}\n").as_bytes())
+                    .await
+                    .unwrap();
+            }
+        });
+
+        let dead = format!("{IPC_SCHEME}{}", dir.join("dead.ipc").display());
+        let live = format!("{IPC_SCHEME}{}", live.display());
+        let client = NodeClient::with_endpoints(
+            vec![dead, live.clone()],
+            RetryPolicy {
+                max_attempts: 1,
+                initial_backoff_ms: 0,
+                max_backoff_ms: 0,
+            },
+        )
+        .unwrap();
+
+        // The live node answers with an error: returned, not retried.
+        let err = client.process("{}").await.unwrap_err();
+        assert!(err.to_string().contains("block rejected"));
+        assert_eq!(client.node_url(), live);
+        assert!(client.process("{}").await.is_err());
+        node.await.unwrap();
+
+        // With no endpoint reachable, the transport error surfaces.
+        let err = client.process("{}").await.unwrap_err();
+        assert!(err.to_string().contains("failed to connect"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Serve `response` to every request on a fresh IPC socket in `dir`.
+    #[cfg(unix)]
+    fn mock_node(dir: &std::path::Path, name: &str, response: serde_json::Value) -> String {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let path = dir.join(name);
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let (read, mut write) = stream.into_split();
+                let mut line = String::new();
+                BufReader::new(read).read_line(&mut line).await.unwrap();
+                let reply = format!("{response}\n");
+                write.write_all(reply.as_bytes()).await.unwrap();
+            }
+        });
+        format!("{IPC_SCHEME}{}", path.display())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn quorum_reads_outvote_a_lying_node() {
+        let dir = std::env::temp_dir().join(format!("burst-quorum-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let balance = |trst: &str, brn: &str| serde_json::json!({ "result": { "trst_balance": trst, "brn_balance": brn } });
+        // The liar answers first; the honest nodes differ only in BRN, which
+        // accrues with time and is left out of the comparison.
+        let endpoints = vec![
+            mock_node(&dir, "liar.ipc", balance("99000", "5")),
+            mock_node(&dir, "honest-a.ipc", balance("10", "5")),
+            mock_node(&dir, "honest-b.ipc", balance("10", "6")),
+        ];
+        let client = NodeClient::with_endpoints(endpoints.clone(), RetryPolicy::default())
+            .unwrap()
+            .with_read_quorum(2)
+            .unwrap();
+        let result = client.account_balance("brst_a").await.unwrap();
+        assert_eq!(result.trst_balance, "10");
+        // Reads leave writes on the first endpoint.
+        assert_eq!(client.node_url(), endpoints[0]);
+
+        let unanimous = client.clone().with_read_quorum(3).unwrap();
+        let err = unanimous.account_balance("brst_a").await.unwrap_err();
+        assert!(err.to_string().contains("no 3 of 3 nodes agree"));
+        assert!(client.clone().with_read_quorum(4).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reads_prefer_healthy_low_latency_endpoints() {
+        let endpoints = ["http://a", "http://b", "http://c", "http://d"]
+            .map(String::from)
+            .to_vec();
+        let client = NodeClient::with_endpoints(endpoints, RetryPolicy::default()).unwrap();
+        {
+            let mut stats = client.stats.lock().unwrap();
+            stats[0] = EndpointStats {
+                latency_ms: Some(80),
+                failures: 0,
+            };
+            stats[1] = EndpointStats {
+                latency_ms: Some(5),
+                failures: 2,
+            };
+            stats[2] = EndpointStats {
+                latency_ms: Some(20),
+                failures: 0,
+            };
+        }
+        // The unmeasured endpoint is probed first; the failing one goes last.
+        assert_eq!(client.by_latency(), vec![3, 2, 0, 1]);
+    }
 }
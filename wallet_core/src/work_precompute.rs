@@ -0,0 +1,268 @@
+//! Work precomputation for managed accounts.
+//!
+//! From block version 2 an account's next block does its proof-of-work on
+//! the account's frontier (see [`burst_ledger::work_root`]), so the work
+//! can be computed before the user asks to send anything. A
+//! [`WorkPrecompute`] tracks the frontier of each managed account and keeps
+//! a nonce for it in a [`WorkCache`]; when the frontier moves the old
+//! nonce is dropped and the new root is computed.
+//!
+//! [`WorkPrecompute::run`] keeps the cache warm from a
+//! [`BalanceSubscriber`](crate::subscriber::BalanceSubscriber) feed: every
+//! update for a managed account re-reads its frontier and precomputes work
+//! for it straight away. Blocks built later pick the nonce up with
+//! [`WorkPrecompute::attach`].
+//!
+//! Version 1 blocks work on their own hash and can't be precomputed.
+
+use std::collections::HashMap;
+
+use burst_ledger::StateBlock;
+use burst_types::{BlockHash, WalletAddress};
+use burst_work::{WorkCache, WorkGenerator};
+
+use crate::error::WalletError;
+
+/// Managed accounts, their frontiers, and precomputed work for each.
+pub struct WorkPrecompute {
+    cache: WorkCache,
+    /// Managed account -> work root of its next block.
+    roots: HashMap<WalletAddress, BlockHash>,
+}
+
+impl WorkPrecompute {
+    /// A precomputer that caches work for up to `capacity` roots.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            cache: WorkCache::new(capacity),
+            roots: HashMap::new(),
+        }
+    }
+
+    /// Keep work warm for `account`, whose chain currently ends at
+    /// `frontier` (`BlockHash::ZERO` if unopened).
+    pub fn manage(&mut self, account: WalletAddress, frontier: BlockHash) {
+        self.set_frontier(&account, frontier);
+        self.roots
+            .entry(account.clone())
+            .or_insert_with(|| burst_ledger::work_root(&account, frontier));
+    }
+
+    /// Stop precomputing for `account`, dropping any cached work.
+    pub fn unmanage(&mut self, account: &WalletAddress) {
+        if let Some(root) = self.roots.remove(account) {
+            self.cache.remove(&root);
+        }
+    }
+
+    /// The accounts being kept warm.
+    pub fn accounts(&self) -> impl Iterator<Item = &WalletAddress> {
+        self.roots.keys()
+    }
+
+    /// Record that `account`'s chain now ends at `frontier`. Returns `true`
+    /// if that moved the work root, in which case the old nonce is dropped.
+    /// Unmanaged accounts are ignored.
+    pub fn set_frontier(&mut self, account: &WalletAddress, frontier: BlockHash) -> bool {
+        let Some(current) = self.roots.get_mut(account) else {
+            return false;
+        };
+        let root = burst_ledger::work_root(account, frontier);
+        if *current == root {
+            return false;
+        }
+        self.cache.remove(current);
+        *current = root;
+        true
+    }
+
+    /// Whether work for `account`'s next block is ready.
+    pub fn is_warm(&self, account: &WalletAddress) -> bool {
+        self.roots
+            .get(account)
+            .is_some_and(|root| self.cache.get(root).is_some())
+    }
+
+    /// Work roots of managed accounts that have no nonce yet.
+    pub fn missing(&self) -> Vec<BlockHash> {
+        let mut missing: Vec<BlockHash> = self
+            .roots
+            .values()
+            .filter(|root| self.cache.get(root).is_none())
+            .copied()
+            .collect();
+        missing.sort_by_key(|root| *root.as_bytes());
+        missing
+    }
+
+    /// Store a nonce for `root`, if it is still some managed account's
+    /// current root. Returns whether it was kept.
+    pub fn insert(&mut self, root: &BlockHash, nonce: u64) -> bool {
+        if !self.roots.values().any(|r| r == root) {
+            return false;
+        }
+        self.cache.insert(root, nonce);
+        true
+    }
+
+    /// Attach precomputed work to `block` if it has none and its root is
+    /// warm. The nonce is used up. Returns whether work was attached.
+    pub fn attach(&mut self, block: &mut StateBlock) -> bool {
+        if block.work != 0 || block.version < burst_ledger::BLOCK_VERSION_V2 {
+            return false;
+        }
+        let root = block.work_root();
+        let Some(nonce) = self.cache.get(&root) else {
+            return false;
+        };
+        self.cache.remove(&root);
+        block.work = nonce;
+        true
+    }
+
+    /// Compute every missing nonce locally at `difficulty`. Returns how
+    /// many were computed.
+    pub fn precompute_local(
+        &mut self,
+        generator: &WorkGenerator,
+        difficulty: u64,
+    ) -> Result<usize, WalletError> {
+        let missing = self.missing();
+        for root in &missing {
+            let nonce = generator
+                .generate(root, difficulty)
+                .map_err(|e| WalletError::Other(format!("work generation failed: {e}")))?;
+            self.cache.insert(root, nonce.0);
+        }
+        Ok(missing.len())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl WorkPrecompute {
+    /// Keep `shared` warm until `events` closes: on (re)connection every
+    /// managed account is refreshed, on an update only the account it
+    /// names. Missing work is then requested from the node, all at once,
+    /// without holding the lock while the node works.
+    pub async fn run(
+        shared: std::sync::Arc<tokio::sync::Mutex<Self>>,
+        client: crate::wallet::NodeClient,
+        mut events: tokio::sync::mpsc::Receiver<crate::subscriber::SubscriberEvent>,
+    ) {
+        use crate::subscriber::SubscriberEvent;
+
+        while let Some(event) = events.recv().await {
+            let accounts: Vec<WalletAddress> = {
+                let precompute = shared.lock().await;
+                match event {
+                    SubscriberEvent::Connected { .. } => precompute.accounts().cloned().collect(),
+                    SubscriberEvent::Update(update) => {
+                        let account = WalletAddress::new(update.account);
+                        if update.change_type == "pending"
+                            || !precompute.roots.contains_key(&account)
+                        {
+                            continue;
+                        }
+                        vec![account]
+                    }
+                }
+            };
+            for account in &accounts {
+                // An account the node can't answer for keeps its old root;
+                // the next update tries again.
+                if let Ok(state) = client
+                    .account_info(account.as_str())
+                    .await
+                    .and_then(|info| info.account_state())
+                {
+                    shared.lock().await.set_frontier(account, state.head);
+                }
+            }
+
+            let missing = shared.lock().await.missing();
+            let hashes: Vec<String> = missing.iter().map(|root| format!("{root}")).collect();
+            let results = futures_util::future::join_all(
+                hashes.iter().map(|hash| client.work_generate(hash)),
+            )
+            .await;
+            let mut precompute = shared.lock().await;
+            for (root, result) in missing.iter().zip(results) {
+                if let Some(nonce) = result
+                    .ok()
+                    .and_then(|work| u64::from_str_radix(&work.work, 16).ok())
+                {
+                    precompute.insert(root, nonce);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burst_ledger::{BlockType, BLOCK_VERSION_V2};
+    use burst_types::{Signature, Timestamp, TxHash};
+
+    fn next_block(account: &WalletAddress, previous: BlockHash) -> StateBlock {
+        let mut block = StateBlock {
+            version: BLOCK_VERSION_V2,
+            block_type: BlockType::Send,
+            account: account.clone(),
+            previous,
+            representative: account.clone(),
+            brn_balance: 0,
+            trst_balance: 0,
+            link: BlockHash::ZERO,
+            origin: TxHash::ZERO,
+            transaction: TxHash::ZERO,
+            timestamp: Timestamp::new(1_000),
+            params_hash: BlockHash::ZERO,
+            memo: None,
+            htlc: None,
+            reject_reason: None,
+            recovery: None,
+            delegation_key: None,
+            extensions: Vec::new(),
+            work: 0,
+            signature: Signature([0u8; 64]),
+            hash: BlockHash::ZERO,
+        };
+        block.hash = block.compute_hash();
+        block
+    }
+
+    #[test]
+    fn precomputed_work_follows_the_frontier() {
+        let alice = WalletAddress::new("brst_alice");
+        let frontier = BlockHash::new([1u8; 32]);
+        let mut precompute = WorkPrecompute::new(10);
+        precompute.manage(alice.clone(), frontier);
+        assert_eq!(precompute.missing(), vec![frontier]);
+
+        precompute.precompute_local(&WorkGenerator, 1000).unwrap();
+        assert!(precompute.is_warm(&alice));
+        assert!(precompute.missing().is_empty());
+
+        // The next block picks the nonce up, and it validates.
+        let mut block = next_block(&alice, frontier);
+        assert!(precompute.attach(&mut block));
+        assert!(block.verify_work(1000));
+        assert!(!precompute.is_warm(&alice));
+
+        // A moved frontier drops stale work and asks for the new root.
+        precompute.precompute_local(&WorkGenerator, 1000).unwrap();
+        let moved = BlockHash::new([2u8; 32]);
+        assert!(precompute.set_frontier(&alice, moved));
+        assert!(!precompute.is_warm(&alice));
+        assert_eq!(precompute.missing(), vec![moved]);
+        assert!(!precompute.insert(&frontier, 7), "stale roots are not kept");
+        assert!(!precompute.set_frontier(&WalletAddress::new("brst_bob"), moved));
+
+        // Version 1 blocks work on their own hash.
+        let mut v1 = next_block(&alice, moved);
+        v1.version = 1;
+        precompute.insert(&moved, 7);
+        assert!(!precompute.attach(&mut v1));
+    }
+}
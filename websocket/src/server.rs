@@ -61,14 +61,32 @@ impl WsState {
     }
 
     /// Publish a block confirmation event.
-    pub fn publish_confirmation(&self, account: &str, block_hash: &str, amount: &str) {
+    ///
+    /// `memo` is the hex-encoded send memo/reference and `destination_tag`
+    /// the send's destination tag; each is included in the event data only
+    /// when present.
+    pub fn publish_confirmation(
+        &self,
+        account: &str,
+        block_hash: &str,
+        amount: &str,
+        memo: Option<&str>,
+        destination_tag: Option<u64>,
+    ) {
+        let mut data = serde_json::json!({
+            "account": account,
+            "block_hash": block_hash,
+            "amount": amount,
+        });
+        if let Some(memo) = memo {
+            data["memo"] = serde_json::Value::String(memo.to_string());
+        }
+        if let Some(tag) = destination_tag {
+            data["destination_tag"] = tag.into();
+        }
         let event = serde_json::json!({
             "topic": "confirmation",
-            "data": {
-                "account": account,
-                "block_hash": block_hash,
-                "amount": amount,
-            },
+            "data": data,
             "timestamp": unix_timestamp_secs(),
         });
         let _ = self.confirmation_tx.send(event.to_string());
@@ -88,6 +106,133 @@ impl WsState {
         let _ = self.account_update_tx.send(event.to_string());
     }
 
+    /// Publish a new pending entry on the destination's `account_update` feed.
+    ///
+    /// Lets merchants track incoming payments (and match them by memo or
+    /// destination tag) before the receiver has pocketed them.
+    pub fn publish_pending(
+        &self,
+        destination: &str,
+        source: &str,
+        block_hash: &str,
+        amount: &str,
+        memo: Option<&str>,
+        destination_tag: Option<u64>,
+    ) {
+        let mut data = serde_json::json!({
+            "account": destination,
+            "change_type": "pending",
+            "source": source,
+            "block_hash": block_hash,
+            "amount": amount,
+        });
+        if let Some(memo) = memo {
+            data["memo"] = serde_json::Value::String(memo.to_string());
+        }
+        if let Some(tag) = destination_tag {
+            data["destination_tag"] = tag.into();
+        }
+        let event = serde_json::json!({
+            "topic": "account_update",
+            "data": data,
+            "timestamp": unix_timestamp_secs(),
+        });
+        let _ = self.account_update_tx.send(event.to_string());
+    }
+
+    /// Publish a rejected-transfer notice to the original sender.
+    ///
+    /// Goes out on the `account_update` topic addressed to `sender`, with
+    /// `change_type: "rejected"`. `return_block_hash` is the reject block;
+    /// the returned amount is pending for the sender under that hash.
+    pub fn publish_rejected(
+        &self,
+        sender: &str,
+        rejecter: &str,
+        send_block_hash: &str,
+        return_block_hash: &str,
+        amount: &str,
+        reason: Option<&str>,
+    ) {
+        let event = serde_json::json!({
+            "topic": "account_update",
+            "data": {
+                "account": sender,
+                "change_type": "rejected",
+                "rejecter": rejecter,
+                "send_block_hash": send_block_hash,
+                "block_hash": return_block_hash,
+                "amount": amount,
+                "reason": reason.unwrap_or("unspecified"),
+            },
+            "timestamp": unix_timestamp_secs(),
+        });
+        let _ = self.account_update_tx.send(event.to_string());
+    }
+
+    /// Announce that a representative moved to a new key.
+    ///
+    /// Goes out on the `account_update` topic addressed to the previous
+    /// representative, with `change_type: "representative_rotated"`, so
+    /// delegators following that account learn where to re-delegate.
+    pub fn publish_representative_rotation(
+        &self,
+        previous: &str,
+        representative: &str,
+        previous_signature: &str,
+    ) {
+        let event = serde_json::json!({
+            "topic": "account_update",
+            "data": {
+                "account": previous,
+                "change_type": "representative_rotated",
+                "representative": representative,
+                "previous_signature": previous_signature,
+            },
+            "timestamp": unix_timestamp_secs(),
+        });
+        let _ = self.account_update_tx.send(event.to_string());
+    }
+
+    /// Announce a fork signed by `account`.
+    ///
+    /// Goes out on the `account_update` topic with `change_type`
+    /// `fork_detected` when the competing block arrives and `fork_resolved`
+    /// once the slot confirms, so wallet providers following a counterparty
+    /// see double-spend attempts as they happen. `fork` is the fork record
+    /// as served by the `forks_recent` RPC.
+    pub fn publish_fork(&self, account: &str, change_type: &str, fork: serde_json::Value) {
+        let event = serde_json::json!({
+            "topic": "account_update",
+            "data": {
+                "account": account,
+                "change_type": change_type,
+                "fork": fork,
+            },
+            "timestamp": unix_timestamp_secs(),
+        });
+        let _ = self.account_update_tx.send(event.to_string());
+    }
+
+    /// Announce a change in an account's inactivity standing.
+    ///
+    /// Goes out on the `account_update` topic with `change_type` one of
+    /// `inactivity_grace_period`, `brn_accrual_suspended` or
+    /// `brn_accrual_resumed`. `at` is when the suspension takes effect or
+    /// took effect, or when accrual resumed.
+    pub fn publish_inactivity(&self, account: &str, change_type: &str, at: u64) {
+        let event = serde_json::json!({
+            "topic": "account_update",
+            "data": {
+                "account": account,
+                "change_type": change_type,
+                "at": at,
+            },
+            "timestamp": unix_timestamp_secs(),
+        });
+        let _ = self.account_update_tx.send(event.to_string());
+    }
+
     /// Publish a governance event.
     pub fn publish_governance(&self, event_type: &str, proposal_id: &str, account: &str) {
         let event = serde_json::json!({
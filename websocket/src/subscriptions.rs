@@ -49,6 +49,10 @@ impl std::fmt::Display for SubscriptionTopic {
 pub struct SubscriptionFilter {
     /// Only receive events for these accounts.
     pub accounts: Option<Vec<String>>,
+    /// Only receive events carrying one of these destination tags, e.g. an
+    /// exchange watching deposits for a subset of its customers.
+    #[serde(default)]
+    pub destination_tags: Option<Vec<u64>>,
 }
 
 /// An event sent to subscribed clients.
@@ -114,17 +118,24 @@ impl ClientSubscriptions {
     /// Returns `false` if the client is not subscribed to the topic.
     /// Returns `true` if subscribed with no filter (match all).
     /// When an account filter is set, the event's `data.account` field must match
-    /// one of the listed accounts.
+    /// one of the listed accounts; when a destination tag filter is set,
+    /// `data.destination_tag` must match one of the listed tags.
     pub fn matches_filter(&self, topic: &SubscriptionTopic, event: &SubscriptionEvent) -> bool {
         match self.subscriptions.get(topic) {
             None => false,
             Some(None) => true,
             Some(Some(filter)) => {
                 if let Some(accounts) = &filter.accounts {
-                    if let Some(account) = event.data.get("account").and_then(|v| v.as_str()) {
-                        return accounts.iter().any(|a| a == account);
+                    let account = event.data.get("account").and_then(|v| v.as_str());
+                    if !account.is_some_and(|account| accounts.iter().any(|a| a == account)) {
+                        return false;
+                    }
+                }
+                if let Some(tags) = &filter.destination_tags {
+                    let tag = event.data.get("destination_tag").and_then(|v| v.as_u64());
+                    if !tag.is_some_and(|tag| tags.contains(&tag)) {
+                        return false;
                     }
-                    return false;
                 }
                 true
             }
@@ -209,6 +220,7 @@ mod tests {
             SubscriptionTopic::Confirmation,
             Some(SubscriptionFilter {
                 accounts: Some(vec!["brst_alice".to_string(), "brst_carol".to_string()]),
+                destination_tags: None,
             }),
         );
 
@@ -229,6 +241,7 @@ mod tests {
             SubscriptionTopic::Governance,
             Some(SubscriptionFilter {
                 accounts: Some(vec!["brst_alice".to_string()]),
+                destination_tags: None,
             }),
         );
 
@@ -242,7 +255,10 @@ mod tests {
         let mut subs = ClientSubscriptions::new();
         subs.subscribe(
             SubscriptionTopic::AccountUpdate,
-            Some(SubscriptionFilter { accounts: None }),
+            Some(SubscriptionFilter {
+                accounts: None,
+                destination_tags: None,
+            }),
         );
 
         let event = make_event("account_update", "brst_anyone");
@@ -256,6 +272,7 @@ mod tests {
             SubscriptionTopic::Confirmation,
             Some(SubscriptionFilter {
                 accounts: Some(vec!["brst_alice".to_string()]),
+                destination_tags: None,
             }),
         );
 
@@ -267,11 +284,36 @@ mod tests {
             SubscriptionTopic::Confirmation,
             Some(SubscriptionFilter {
                 accounts: Some(vec!["brst_bob".to_string()]),
+                destination_tags: None,
             }),
         );
         assert!(subs.matches_filter(&SubscriptionTopic::Confirmation, &event_bob));
     }
 
+    #[test]
+    fn test_matches_filter_with_destination_tags() {
+        let mut subs = ClientSubscriptions::new();
+        subs.subscribe(
+            SubscriptionTopic::AccountUpdate,
+            Some(SubscriptionFilter {
+                accounts: Some(vec!["brst_exchange".to_string()]),
+                destination_tags: Some(vec![1001, 1002]),
+            }),
+        );
+        let event = |account: &str, tag: Option<u64>| {
+            let mut event = make_event("account_update", account);
+            if let Some(tag) = tag {
+                event.data["destination_tag"] = serde_json::json!(tag);
+            }
+            event
+        };
+        let topic = SubscriptionTopic::AccountUpdate;
+        assert!(subs.matches_filter(&topic, &event("brst_exchange", Some(1002))));
+        assert!(!subs.matches_filter(&topic, &event("brst_exchange", Some(7))));
+        assert!(!subs.matches_filter(&topic, &event("brst_exchange", None)));
+        assert!(!subs.matches_filter(&topic, &event("brst_other", Some(1001))));
+    }
+
     #[test]
     fn test_topics_returns_subscribed_topics() {
         let mut subs = ClientSubscriptions::new();
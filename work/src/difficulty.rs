@@ -3,6 +3,10 @@
 //! When the network is under high load (many transactions per window), difficulty
 //! increases to make spam more expensive. During low activity, difficulty decreases
 //! to minimize friction for legitimate users.
+//!
+//! The multiplier rises as soon as throughput exceeds the target but only
+//! falls once throughput has stayed lower for a hold period, so a burst
+//! that stops and starts does not make the threshold flap.
 
 use std::collections::VecDeque;
 
@@ -16,8 +20,20 @@ pub struct DifficultyAdjuster {
     base_difficulty: u64,
     target_tps: u64,
     max_multiplier: u64,
+    /// Multiplier currently in force.
+    applied: u64,
+    /// Seconds the observed multiplier must stay below `applied` before
+    /// `applied` is lowered.
+    hold_secs: u64,
+    /// When the observed multiplier first dropped below `applied`.
+    below_since: Option<u64>,
+    /// Latest time seen, from a recorded block or [`Self::refresh`].
+    now: u64,
 }
 
+/// Default hold before a raised multiplier is lowered again.
+pub const DEFAULT_HOLD_SECS: u64 = 60;
+
 impl DifficultyAdjuster {
     pub fn new(base_difficulty: u64, target_tps: u64, window_size: usize) -> Self {
         Self {
@@ -26,35 +42,78 @@ impl DifficultyAdjuster {
             base_difficulty,
             target_tps,
             max_multiplier: 16,
+            applied: 1,
+            hold_secs: DEFAULT_HOLD_SECS,
+            below_since: None,
+            now: 0,
         }
     }
 
+    /// Set how long throughput must stay low before the multiplier falls.
+    pub fn with_hold_secs(mut self, hold_secs: u64) -> Self {
+        self.hold_secs = hold_secs;
+        self
+    }
+
     /// Record a block timestamp for throughput tracking.
     pub fn record_block(&mut self, timestamp_secs: u64) {
         self.window.push_back(timestamp_secs);
         while self.window.len() > self.window_size {
             self.window.pop_front();
         }
+        self.refresh(timestamp_secs);
+    }
+
+    /// Re-evaluate the applied multiplier at `now_secs`. Call this
+    /// periodically so the multiplier can fall when blocks stop arriving.
+    /// Returns the multiplier in force afterwards.
+    pub fn refresh(&mut self, now_secs: u64) -> u64 {
+        self.now = self.now.max(now_secs);
+        let observed = self.raw_multiplier();
+        if observed >= self.applied {
+            self.applied = observed;
+            self.below_since = None;
+        } else {
+            let since = *self.below_since.get_or_insert(self.now);
+            if self.now.saturating_sub(since) >= self.hold_secs {
+                self.applied = observed;
+                self.below_since = None;
+            }
+        }
+        self.applied
     }
 
     /// Compute the current effective difficulty based on recent throughput.
     pub fn current_difficulty(&self) -> u64 {
+        self.base_difficulty
+            .saturating_mul(self.current_multiplier())
+    }
+
+    /// Network load multiplier in force, with hysteresis applied.
+    pub fn current_multiplier(&self) -> u64 {
+        self.applied
+    }
+
+    /// Multiplier the observed throughput calls for right now: 1 at or
+    /// below the target TPS, otherwise the ratio of observed to target TPS
+    /// (capped). Blocks are spread over the window up to the latest time
+    /// seen, so the ratio decays once traffic stops.
+    pub fn raw_multiplier(&self) -> u64 {
         if self.window.len() < 2 {
-            return self.base_difficulty;
+            return 1;
         }
 
         let first = self.window.front().unwrap();
-        let last = self.window.back().unwrap();
+        let last = self.window.back().unwrap().max(&self.now);
         let elapsed = last.saturating_sub(*first).max(1);
         let count = self.window.len() as u64;
         let tps = count / elapsed;
 
         if tps <= self.target_tps {
-            return self.base_difficulty;
+            return 1;
         }
 
-        let multiplier = (tps / self.target_tps.max(1)).min(self.max_multiplier);
-        self.base_difficulty.saturating_mul(multiplier)
+        (tps / self.target_tps.max(1)).min(self.max_multiplier)
     }
 
     /// Update the base difficulty (e.g., via governance).
@@ -80,6 +139,7 @@ mod tests {
             adj.record_block(i * 10);
         }
         assert_eq!(adj.current_difficulty(), 1000);
+        assert_eq!(adj.current_multiplier(), 1);
     }
 
     #[test]
@@ -90,6 +150,7 @@ mod tests {
             adj.record_block(i / 100);
         }
         assert!(adj.current_difficulty() > 1000);
+        assert_eq!(adj.current_difficulty(), 1000 * adj.current_multiplier());
     }
 
     #[test]
@@ -108,4 +169,29 @@ mod tests {
         adj.set_base_difficulty(2000);
         assert_eq!(adj.current_difficulty(), 2000);
     }
+
+    #[test]
+    fn multiplier_rises_at_once_and_falls_after_the_hold() {
+        let mut adj = DifficultyAdjuster::new(1000, 10, 100).with_hold_secs(30);
+        // 100 blocks within second 100: 10x target.
+        for _ in 0..100 {
+            adj.record_block(100);
+        }
+        assert_eq!(adj.current_multiplier(), 10);
+
+        // Traffic stops; the observed rate decays but the multiplier holds.
+        assert_eq!(adj.refresh(105), 10);
+        assert!(adj.raw_multiplier() < 10);
+        assert_eq!(adj.refresh(134), 10);
+        // A brief burst back to the old rate resets the hold.
+        for _ in 0..100 {
+            adj.record_block(134);
+        }
+        assert_eq!(adj.refresh(140), 10);
+        assert_eq!(adj.refresh(169), 10);
+        // Low for the whole hold: the multiplier drops to what is observed.
+        assert_eq!(adj.refresh(170), adj.raw_multiplier());
+        assert!(adj.current_multiplier() < 10);
+        assert_eq!(adj.refresh(10_000), 1);
+    }
 }
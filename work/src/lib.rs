@@ -11,11 +11,11 @@ pub mod precompute;
 pub mod thresholds;
 pub mod validator;
 
-pub use difficulty::DifficultyAdjuster;
+pub use difficulty::{DifficultyAdjuster, DEFAULT_HOLD_SECS};
 pub use error::WorkError;
 pub use generator::WorkGenerator;
 pub use precompute::{PriorityBlock, WorkCache, WorkPriorityQueue};
-pub use thresholds::{WorkBlockKind, WorkThresholds};
+pub use thresholds::{WorkBlockKind, WorkThresholds, MULTIPLIER_BPS_SCALE};
 pub use validator::validate_work;
 
 /// The result of PoW generation.
@@ -4,6 +4,14 @@
 //! - Receive/Open blocks need HIGHER difficulty (anti-spam for free operations)
 //! - Send blocks need BASE difficulty (sender already proved ownership)
 //! - Epoch blocks require very high difficulty (only genesis can create)
+//! - Governance proposals and votes are priced separately, so governance
+//!   spam can be made costlier than payments
+//!
+//! The multipliers are protocol parameters (in basis points of the base
+//! difficulty) and can be changed by governance; see
+//! [`WorkThresholds::from_params`].
+
+use burst_types::ProtocolParams;
 
 /// Simplified block kind for PoW threshold selection.
 ///
@@ -12,35 +20,67 @@
 /// this enum.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum WorkBlockKind {
-    /// Send, Burn, Split, Merge, governance, delegation, etc.
+    /// Send, Burn, Split, Merge, delegation, etc.
     Base,
     /// Receive or Open — higher difficulty to deter spam.
     ReceiveOrOpen,
     /// Epoch — very high difficulty, only genesis can produce.
     Epoch,
+    /// Governance proposals and votes.
+    Governance,
+}
+
+impl WorkBlockKind {
+    /// Every kind, in reporting order.
+    pub const ALL: [WorkBlockKind; 4] = [
+        WorkBlockKind::Base,
+        WorkBlockKind::ReceiveOrOpen,
+        WorkBlockKind::Epoch,
+        WorkBlockKind::Governance,
+    ];
+
+    /// Short name, as reported over RPC.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkBlockKind::Base => "base",
+            WorkBlockKind::ReceiveOrOpen => "receive",
+            WorkBlockKind::Epoch => "epoch",
+            WorkBlockKind::Governance => "governance",
+        }
+    }
 }
 
 const BASE_THRESHOLD: u64 = 0xFFFFFE00_00000000;
 const RECEIVE_MULTIPLIER: f64 = 8.0;
 const EPOCH_MULTIPLIER: f64 = 64.0;
+const GOVERNANCE_MULTIPLIER: f64 = 4.0;
+
+/// Basis points per 1.0x multiplier in `ProtocolParams`.
+pub const MULTIPLIER_BPS_SCALE: u32 = 10_000;
 
 /// Per-block-type PoW thresholds.
 ///
 /// Higher threshold values = harder work required.  The `multiply` helper
 /// scales difficulty by shrinking the "inverse gap" (`u64::MAX - threshold`)
-/// which raises the bar the work nonce must clear.
+/// which raises the bar the work nonce must clear. Multipliers at or below
+/// 1.0 leave the base threshold unchanged: no kind is cheaper than the base.
+#[derive(Clone, Debug)]
 pub struct WorkThresholds {
     pub base: u64,
+    pub base_multiplier: f64,
     pub receive_multiplier: f64,
     pub epoch_multiplier: f64,
+    pub governance_multiplier: f64,
 }
 
 impl WorkThresholds {
     pub fn new() -> Self {
         Self {
             base: BASE_THRESHOLD,
+            base_multiplier: 1.0,
             receive_multiplier: RECEIVE_MULTIPLIER,
             epoch_multiplier: EPOCH_MULTIPLIER,
+            governance_multiplier: GOVERNANCE_MULTIPLIER,
         }
     }
 
@@ -48,28 +88,61 @@ impl WorkThresholds {
     pub fn with_base(base: u64) -> Self {
         Self {
             base,
-            receive_multiplier: RECEIVE_MULTIPLIER,
-            epoch_multiplier: EPOCH_MULTIPLIER,
+            ..Self::new()
         }
     }
 
-    /// Get the required work difficulty for a specific block kind.
-    pub fn threshold_for(&self, kind: WorkBlockKind) -> u64 {
+    /// Thresholds from the governed protocol parameters: the base is
+    /// `min_work_difficulty` and each multiplier is given in basis points.
+    pub fn from_params(params: &ProtocolParams) -> Self {
+        let multiplier = |bps: u32| bps as f64 / MULTIPLIER_BPS_SCALE as f64;
+        Self {
+            base: params.min_work_difficulty,
+            base_multiplier: multiplier(params.work_base_multiplier_bps),
+            receive_multiplier: multiplier(params.work_receive_multiplier_bps),
+            epoch_multiplier: multiplier(params.work_epoch_multiplier_bps),
+            governance_multiplier: multiplier(params.work_governance_multiplier_bps),
+        }
+    }
+
+    /// The multiplier applied to the base for a block kind.
+    pub fn multiplier(&self, kind: WorkBlockKind) -> f64 {
         match kind {
-            WorkBlockKind::ReceiveOrOpen => self.multiply(self.base, self.receive_multiplier),
-            WorkBlockKind::Epoch => self.multiply(self.base, self.epoch_multiplier),
-            WorkBlockKind::Base => self.base,
+            WorkBlockKind::Base => self.base_multiplier,
+            WorkBlockKind::ReceiveOrOpen => self.receive_multiplier,
+            WorkBlockKind::Epoch => self.epoch_multiplier,
+            WorkBlockKind::Governance => self.governance_multiplier,
         }
     }
 
+    /// These thresholds with every kind made `factor` times harder. A factor
+    /// at or below 1.0 returns them unchanged.
+    pub fn scaled(&self, factor: f64) -> Self {
+        if factor <= 1.0 {
+            return self.clone();
+        }
+        Self {
+            base: self.base,
+            base_multiplier: self.base_multiplier.max(1.0) * factor,
+            receive_multiplier: self.receive_multiplier.max(1.0) * factor,
+            epoch_multiplier: self.epoch_multiplier.max(1.0) * factor,
+            governance_multiplier: self.governance_multiplier.max(1.0) * factor,
+        }
+    }
+
+    /// Get the required work difficulty for a specific block kind.
+    pub fn threshold_for(&self, kind: WorkBlockKind) -> u64 {
+        self.multiply(self.base, self.multiplier(kind))
+    }
+
     /// Scale difficulty: higher threshold = harder work.
     ///
     /// The "difficulty inverse" is `u64::MAX - threshold`. Dividing that by the
     /// multiplier shrinks the gap, raising the threshold.  When `base` is 0
     /// (PoW disabled), all derived thresholds are also 0.
     fn multiply(&self, base: u64, multiplier: f64) -> u64 {
-        if base == 0 {
-            return 0;
+        if base == 0 || multiplier <= 1.0 {
+            return base;
         }
         let difficulty_inv = u64::MAX - base;
         let scaled_inv = (difficulty_inv as f64 / multiplier) as u64;
@@ -126,6 +199,30 @@ mod tests {
         assert!(recv > 1000);
     }
 
+    #[test]
+    fn multipliers_follow_params() {
+        let params = ProtocolParams {
+            min_work_difficulty: 1 << 40,
+            work_governance_multiplier_bps: 4 * MULTIPLIER_BPS_SCALE,
+            work_base_multiplier_bps: MULTIPLIER_BPS_SCALE / 2,
+            ..Default::default()
+        };
+        let thresholds = WorkThresholds::from_params(&params);
+        assert_eq!(thresholds.multiplier(WorkBlockKind::Governance), 4.0);
+        assert!(
+            thresholds.threshold_for(WorkBlockKind::Governance)
+                > thresholds.threshold_for(WorkBlockKind::Base)
+        );
+        // A multiplier below 1.0 never lowers the base.
+        assert_eq!(thresholds.threshold_for(WorkBlockKind::Base), 1 << 40);
+        assert_eq!(
+            WorkThresholds::from_params(&ProtocolParams::default())
+                .threshold_for(WorkBlockKind::ReceiveOrOpen),
+            WorkThresholds::with_base(ProtocolParams::default().min_work_difficulty)
+                .threshold_for(WorkBlockKind::ReceiveOrOpen)
+        );
+    }
+
     #[test]
     fn zero_base_stays_zero_for_base() {
         let thresholds = WorkThresholds::with_base(0);